@@ -16,6 +16,7 @@ use winter_air::{
 
 mod chiplets;
 mod options;
+mod proof;
 mod range;
 mod utils;
 use utils::TransitionConstraintRange;
@@ -24,6 +25,7 @@ use utils::TransitionConstraintRange;
 // ================================================================================================
 
 pub use options::ProofOptions;
+pub use proof::{ExecutionProof, ProofCompression, ProofFormatError, ProofHeader, PROOF_VERSION};
 pub use vm_core::{utils::ToElements, Felt, FieldElement, StarkField};
 pub use winter_air::{FieldExtension, HashFunction};
 
@@ -224,7 +226,7 @@ impl Air for ProcessorAir {
 // PUBLIC INPUTS
 // ================================================================================================
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct PublicInputs {
     program_hash: Digest,
     stack_inputs: Vec<Felt>,
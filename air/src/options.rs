@@ -1,7 +1,43 @@
 use core::ops::Deref;
+use vm_core::{AUX_TRACE_WIDTH, TRACE_WIDTH};
 use winter_air::{FieldExtension, HashFunction, ProofOptions as WinterProofOptions};
 
-/// TODO: add docs
+// CONSTANTS
+// ================================================================================================
+
+/// Blowup factor of the constraint evaluation domain of [ProcessorAir](crate::ProcessorAir),
+/// which is also the number of columns of the constraint composition polynomial. Proof options
+/// must use a blowup factor of at least this value.
+const CE_BLOWUP_FACTOR: usize = 8;
+
+/// Number of bits in a base field element, and the size of a serialized base field element.
+const BASE_FIELD_BITS: u32 = 64;
+const BASE_ELEMENT_SIZE: usize = 8;
+
+/// Query security (in bits) from which the grinding factor contributes to the security of a
+/// proof; mirrors the conjectured security estimate of the STARK prover.
+const GRINDING_CONTRIBUTION_FLOOR: u32 = 80;
+
+/// Maximum number of queries supported by the STARK prover.
+const MAX_NUM_QUERIES: usize = 128;
+
+/// Parameters explored when selecting proof options for a security level; the grinding factor
+/// and the FRI remainder size are those of the preset options.
+const BLOWUP_FACTORS: [usize; 4] = [8, 16, 32, 64];
+const FRI_FOLDING_FACTORS: [usize; 3] = [4, 8, 16];
+const FIELD_EXTENSIONS: [FieldExtension; 2] = [FieldExtension::Quadratic, FieldExtension::Cubic];
+const HASH_FUNCTIONS: [HashFunction; 2] = [HashFunction::Blake3_192, HashFunction::Blake3_256];
+const GRINDING_FACTOR: u32 = 16;
+const FRI_MAX_REMAINDER_SIZE: usize = 256;
+
+// PROOF OPTIONS
+// ================================================================================================
+
+/// Parameters of STARK proofs generated for executions of Miden VM programs.
+///
+/// Besides the preset options, options can be selected for a given trace length such that proofs
+/// provide the requested security level while being as small as possible (see
+/// [ProofOptions::with_security_level()]).
 #[derive(Clone)]
 pub struct ProofOptions(WinterProofOptions);
 
@@ -50,9 +86,130 @@ impl ProofOptions {
         ))
     }
 
+    /// Returns the options which provide at least the specified conjectured security level (in
+    /// bits) for execution traces of the specified length, and result in the smallest proofs
+    /// among the explored options (see [ProofOptions::estimate_proof_size()]).
+    ///
+    /// The trace length is the padded length of an execution trace, which can be obtained by
+    /// executing the program (see `TraceLenSummary::padded_trace_len()` of the processor).
+    ///
+    /// Returns None if none of the explored options provides the security level (e.g., since no
+    /// supported hash function is collision resistant at the security level).
+    pub fn with_security_level(trace_len: usize, security_level: u32) -> Option<Self> {
+        let mut selected: Option<(usize, Self)> = None;
+        for hash_fn in HASH_FUNCTIONS {
+            for field_extension in FIELD_EXTENSIONS {
+                for blowup_factor in BLOWUP_FACTORS {
+                    // the number of queries is the smallest one providing the security level
+                    let num_queries = (1..=MAX_NUM_QUERIES).find(|&num_queries| {
+                        let security = get_conjectured_security(
+                            num_queries,
+                            blowup_factor,
+                            GRINDING_FACTOR,
+                            hash_fn,
+                            field_extension,
+                            trace_len,
+                        );
+                        security >= security_level
+                    });
+                    let num_queries = match num_queries {
+                        Some(num_queries) => num_queries,
+                        None => continue,
+                    };
+
+                    for fri_folding_factor in FRI_FOLDING_FACTORS {
+                        let options = Self::new(
+                            num_queries,
+                            blowup_factor,
+                            GRINDING_FACTOR,
+                            hash_fn,
+                            field_extension,
+                            fri_folding_factor,
+                            FRI_MAX_REMAINDER_SIZE,
+                        );
+                        let proof_size = options.estimate_proof_size(trace_len);
+                        if selected
+                            .as_ref()
+                            .map_or(true, |(size, _)| proof_size < *size)
+                        {
+                            selected = Some((proof_size, options));
+                        }
+                    }
+                }
+            }
+        }
+        selected.map(|(_, options)| options)
+    }
+
     pub fn into_inner(self) -> WinterProofOptions {
         self.0
     }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the conjectured security level (in bits) of proofs generated with these options
+    /// for execution traces of the specified (padded) length.
+    ///
+    /// This is the security level reported by [ExecutionProof::security_level()](
+    /// crate::ExecutionProof::security_level) for such proofs.
+    pub fn security_level(&self, trace_len: usize) -> u32 {
+        get_conjectured_security(
+            self.num_queries(),
+            self.blowup_factor(),
+            self.grinding_factor(),
+            self.hash_fn(),
+            self.field_extension(),
+            trace_len,
+        )
+    }
+
+    /// Returns an estimate of the size (in bytes) of proofs generated with these options for
+    /// execution traces of the specified (padded) length.
+    ///
+    /// The estimate accounts for the openings of the trace and constraint commitments at the
+    /// queried positions, the out-of-domain evaluation frame, and the FRI layers and remainder.
+    /// Since openings of multiple positions share Merkle tree nodes, the number of nodes in an
+    /// opening is approximated, and thus the size of actual proofs differs slightly.
+    pub fn estimate_proof_size(&self, trace_len: usize) -> usize {
+        let num_queries = self.num_queries();
+        let lde_domain_size = trace_len * self.blowup_factor();
+        let element_size = BASE_ELEMENT_SIZE * self.field_extension().degree() as usize;
+        let digest_size = match self.hash_fn() {
+            HashFunction::Blake3_192 => 24,
+            HashFunction::Blake3_256 | HashFunction::Sha3_256 => 32,
+        };
+
+        // nodes of the paths from the queried leaves to the root, excluding the nodes shared by
+        // the paths near the root
+        let opening_size = |num_leaves: usize| {
+            let depth = num_leaves.trailing_zeros() as usize;
+            let shared_depth = num_queries.next_power_of_two().trailing_zeros() as usize;
+            num_queries * depth.saturating_sub(shared_depth) * digest_size
+        };
+
+        // queried rows of the main trace (base field), of the auxiliary trace, and of the
+        // constraint composition columns (extension field), together with their openings
+        let mut size = num_queries * TRACE_WIDTH * BASE_ELEMENT_SIZE
+            + num_queries * (AUX_TRACE_WIDTH + CE_BLOWUP_FACTOR) * element_size
+            + 3 * (opening_size(lde_domain_size) + digest_size);
+
+        // current and next rows of the trace, and the composition columns, at the out-of-domain
+        // point
+        size += (2 * (TRACE_WIDTH + AUX_TRACE_WIDTH) + CE_BLOWUP_FACTOR) * element_size;
+
+        // FRI layers are folded until the domain fits into the remainder
+        let fri_options = self.to_fri_options();
+        let folding_factor = fri_options.folding_factor();
+        let mut domain_size = lde_domain_size;
+        while domain_size > fri_options.max_remainder_size() {
+            domain_size /= folding_factor;
+            size += num_queries * folding_factor * element_size
+                + opening_size(domain_size)
+                + digest_size;
+        }
+        size + domain_size * element_size
+    }
 }
 
 impl Default for ProofOptions {
@@ -68,3 +225,103 @@ impl Deref for ProofOptions {
         &self.0
     }
 }
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the conjectured security level (in bits) of proofs generated with the specified
+/// parameters for execution traces of the specified length.
+///
+/// The security level is bounded by the size of the extension field relative to the size of the
+/// low-degree extension domain, by the security of the queries (including proof-of-work), and by
+/// the collision resistance of the hash function.
+fn get_conjectured_security(
+    num_queries: usize,
+    blowup_factor: usize,
+    grinding_factor: u32,
+    hash_fn: HashFunction,
+    field_extension: FieldExtension,
+    trace_len: usize,
+) -> u32 {
+    let lde_domain_size = (trace_len * blowup_factor) as u64;
+    let field_security = (BASE_FIELD_BITS * field_extension.degree())
+        .saturating_sub(lde_domain_size.trailing_zeros());
+
+    let mut query_security = blowup_factor.trailing_zeros() * num_queries as u32;
+    if query_security >= GRINDING_CONTRIBUTION_FLOOR {
+        query_security += grinding_factor;
+    }
+
+    field_security
+        .min(query_security)
+        .saturating_sub(1)
+        .min(hash_fn.collision_resistance())
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::{ProofOptions, CE_BLOWUP_FACTOR};
+    use crate::{ProcessorAir, PublicInputs};
+    use vm_core::{
+        Felt, FieldElement, AUX_TRACE_RAND_ELEMENTS, AUX_TRACE_WIDTH, MIN_TRACE_LEN, TRACE_WIDTH,
+    };
+    use winter_air::{Air, TraceInfo, TraceLayout};
+
+    #[test]
+    fn ce_blowup_factor() {
+        let layout = TraceLayout::new(TRACE_WIDTH, [AUX_TRACE_WIDTH], [AUX_TRACE_RAND_ELEMENTS]);
+        let trace_info = TraceInfo::new_multi_segment(layout, MIN_TRACE_LEN, vec![]);
+        let pub_inputs = PublicInputs::new([Felt::ZERO; 4].into(), vec![], vec![]);
+        let options = ProofOptions::default().into_inner();
+        let air = ProcessorAir::new(trace_info, pub_inputs, options);
+        assert_eq!(CE_BLOWUP_FACTOR, air.ce_blowup_factor());
+    }
+
+    #[test]
+    fn security_level() {
+        let trace_len = 1 << 16;
+        assert_eq!(
+            96,
+            ProofOptions::with_96_bit_security().security_level(trace_len)
+        );
+        assert_eq!(
+            128,
+            ProofOptions::with_128_bit_security().security_level(trace_len)
+        );
+
+        // for long traces, the security level is bounded by the size of the extension field
+        let options = ProofOptions::with_96_bit_security();
+        assert_eq!(128 - 31 - 1, options.security_level(1 << 28));
+    }
+
+    #[test]
+    fn with_security_level() {
+        let trace_len = 1 << 16;
+        for (security_level, preset) in [
+            (96, ProofOptions::with_96_bit_security()),
+            (128, ProofOptions::with_128_bit_security()),
+        ] {
+            let options = ProofOptions::with_security_level(trace_len, security_level).unwrap();
+            assert!(options.security_level(trace_len) >= security_level);
+            assert!(options.blowup_factor() >= CE_BLOWUP_FACTOR);
+            assert!(
+                options.estimate_proof_size(trace_len) <= preset.estimate_proof_size(trace_len)
+            );
+        }
+
+        // higher security levels result in larger proofs
+        let size = |security_level| {
+            ProofOptions::with_security_level(trace_len, security_level)
+                .unwrap()
+                .estimate_proof_size(trace_len)
+        };
+        assert!(size(64) < size(96));
+        assert!(size(96) < size(128));
+
+        // no supported hash function provides more than 128 bits of security
+        assert!(ProofOptions::with_security_level(trace_len, 129).is_none());
+    }
+}
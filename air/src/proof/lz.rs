@@ -0,0 +1,133 @@
+use vm_core::utils::{collections::Vec, DeserializationError};
+
+// CONSTANTS
+// ================================================================================================
+
+/// Smallest number of bytes encoded as a back-reference.
+const MIN_MATCH: usize = 4;
+
+/// Largest number of bytes encoded by a single back-reference.
+const MAX_MATCH: usize = MIN_MATCH + 0x7f;
+
+/// Largest number of literal bytes encoded by a single literal run.
+const MAX_LITERALS: usize = 0x80;
+
+/// Largest distance of a back-reference.
+const MAX_OFFSET: usize = u16::MAX as usize;
+
+/// Number of bits of the hash of 4-byte sequences used to find back-references.
+const HASH_BITS: u32 = 12;
+
+// LZ77 COMPRESSION
+// ================================================================================================
+//
+// Compressed data is a sequence of tokens, each starting with a control byte:
+// - A control byte `0b0nnnnnnn` is followed by `n + 1` literal bytes.
+// - A control byte `0b1nnnnnnn` is followed by a 16-bit little-endian offset `d`, and copies
+//   `n + MIN_MATCH` bytes starting `d` bytes before the end of the output (the copied range may
+//   overlap the bytes being written).
+
+/// Compresses the specified bytes.
+pub fn compress(input: &[u8]) -> Vec<u8> {
+    let mut output = Vec::with_capacity(input.len());
+    let mut table = vec![usize::MAX; 1 << HASH_BITS];
+    let mut literal_start = 0;
+    let mut pos = 0;
+
+    while pos + MIN_MATCH <= input.len() {
+        let hash = hash(&input[pos..pos + MIN_MATCH]);
+        let candidate = table[hash];
+        table[hash] = pos;
+
+        let match_len = if candidate != usize::MAX && pos - candidate <= MAX_OFFSET {
+            input[candidate..]
+                .iter()
+                .zip(&input[pos..])
+                .take(MAX_MATCH)
+                .take_while(|(a, b)| a == b)
+                .count()
+        } else {
+            0
+        };
+
+        if match_len >= MIN_MATCH {
+            write_literals(&mut output, &input[literal_start..pos]);
+            output.push(0x80 | (match_len - MIN_MATCH) as u8);
+            output.extend_from_slice(&((pos - candidate) as u16).to_le_bytes());
+            pos += match_len;
+            literal_start = pos;
+        } else {
+            pos += 1;
+        }
+    }
+
+    write_literals(&mut output, &input[literal_start..]);
+    output
+}
+
+/// Decompresses the specified bytes, which must decompress into exactly `size` bytes.
+///
+/// # Errors
+/// Returns an error if the input is not a valid compressed sequence of `size` bytes.
+pub fn decompress(input: &[u8], size: usize) -> Result<Vec<u8>, DeserializationError> {
+    let mut output = Vec::<u8>::new();
+    let mut pos = 0;
+    while pos < input.len() {
+        let control = input[pos] as usize;
+        pos += 1;
+
+        if control & 0x80 == 0 {
+            let literals = input
+                .get(pos..pos + control + 1)
+                .ok_or(DeserializationError::UnexpectedEOF)?;
+            output.extend_from_slice(literals);
+            pos += control + 1;
+        } else {
+            let offset = input
+                .get(pos..pos + 2)
+                .map(|bytes| u16::from_le_bytes([bytes[0], bytes[1]]) as usize)
+                .ok_or(DeserializationError::UnexpectedEOF)?;
+            pos += 2;
+            if offset == 0 || offset > output.len() {
+                return Err(DeserializationError::InvalidValue(format!(
+                    "back-reference offset {} is out of bounds",
+                    offset
+                )));
+            }
+            let start = output.len() - offset;
+            for i in 0..(control & 0x7f) + MIN_MATCH {
+                output.push(output[start + i]);
+            }
+        }
+
+        if output.len() > size {
+            break;
+        }
+    }
+
+    if output.len() != size {
+        return Err(DeserializationError::InvalidValue(format!(
+            "expected {} decompressed bytes, but was {}",
+            size,
+            output.len()
+        )));
+    }
+    Ok(output)
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Writes the specified literal bytes into the output as a sequence of literal runs.
+fn write_literals(output: &mut Vec<u8>, literals: &[u8]) {
+    for run in literals.chunks(MAX_LITERALS) {
+        output.push((run.len() - 1) as u8);
+        output.extend_from_slice(run);
+    }
+}
+
+/// Returns a hash of a 4-byte sequence.
+fn hash(bytes: &[u8]) -> usize {
+    let value = u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+    (value.wrapping_mul(0x9e37_79b1) >> (32 - HASH_BITS)) as usize
+}
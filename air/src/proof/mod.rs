@@ -0,0 +1,431 @@
+use core::fmt;
+use vm_core::utils::{
+    collections::Vec, ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable,
+    SliceReader,
+};
+use winter_air::{
+    proof::{Context, StarkProof},
+    ProofOptions as WinterProofOptions,
+};
+
+mod lz;
+
+#[cfg(test)]
+mod tests;
+
+// CONSTANTS
+// ================================================================================================
+
+/// Version of the proof format written by [ExecutionProof::to_bytes()].
+///
+/// The version must be incremented whenever the layout of the envelope changes; proofs with a
+/// newer version are rejected by [ExecutionProof::from_bytes()].
+pub const PROOF_VERSION: u8 = 1;
+
+/// Bytes with which every versioned proof starts.
+///
+/// Proofs serialized before the envelope was introduced start with the width of the main trace
+/// segment followed by the width of the auxiliary segment, and thus never start with these bytes.
+const MAGIC: [u8; 4] = *b"MVMP";
+
+/// Maximum size of a section of a compressed proof after decompression; this bounds the memory a
+/// malformed compressed proof can make the decoder allocate.
+const MAX_SECTION_SIZE: usize = 1 << 28;
+
+// EXECUTION PROOF
+// ================================================================================================
+
+/// A STARK proof of a correct execution of a program by Miden VM.
+///
+/// Proofs are serialized into a versioned envelope which consists of:
+/// - Magic bytes `MVMP`, and the version of the proof format (see [PROOF_VERSION]).
+/// - A parameter block with the options the proof was generated with and the length of the
+///   execution trace; the block can be read without parsing the proof via
+///   [ExecutionProof::read_header()].
+/// - The compression applied to the proof (see [ProofCompression]), followed by the proof itself.
+///
+/// [ExecutionProof::from_bytes()] also accepts proofs serialized before the envelope was
+/// introduced (i.e., raw STARK proofs); these are reported as proofs of version 0.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExecutionProof {
+    proof: StarkProof,
+}
+
+impl ExecutionProof {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Returns a new execution proof wrapping the specified STARK proof.
+    pub const fn new(proof: StarkProof) -> Self {
+        Self { proof }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the underlying STARK proof.
+    pub fn stark_proof(&self) -> &StarkProof {
+        &self.proof
+    }
+
+    /// Returns the underlying STARK proof, consuming this execution proof.
+    pub fn into_stark_proof(self) -> StarkProof {
+        self.proof
+    }
+
+    /// Returns the options with which this proof was generated.
+    pub fn options(&self) -> &WinterProofOptions {
+        self.proof.options()
+    }
+
+    /// Returns the length of the execution trace proven by this proof.
+    pub fn trace_length(&self) -> usize {
+        self.proof.trace_length()
+    }
+
+    /// Returns the security level of this proof in bits; see [StarkProof::security_level()].
+    pub fn security_level(&self, conjectured: bool) -> u32 {
+        self.proof.security_level(conjectured)
+    }
+
+    // SERIALIZATION / DESERIALIZATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Serializes this proof into a versioned envelope without compression.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.to_bytes_with(ProofCompression::None)
+    }
+
+    /// Serializes this proof into a versioned envelope with the specified compression.
+    pub fn to_bytes_with(&self, compression: ProofCompression) -> Vec<u8> {
+        let mut target = Vec::new();
+        target.write_u8_slice(&MAGIC);
+        target.write_u8(PROOF_VERSION);
+        self.options().write_into(&mut target);
+        target.write_u8(self.trace_length().trailing_zeros() as u8);
+        target.write_u8(compression as u8);
+
+        match compression {
+            ProofCompression::None => target.extend_from_slice(&self.proof.to_bytes()),
+            ProofCompression::Queries => {
+                for (section, compress) in self.sections() {
+                    write_section(&mut target, &section, compress);
+                }
+            }
+        }
+        target
+    }
+
+    /// Returns a proof read from the specified bytes, which can be either a versioned envelope,
+    /// or a raw STARK proof serialized before the envelope was introduced.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - The envelope was written in a newer version of the proof format, or with an unknown
+    ///   compression.
+    /// - The parameter block of the envelope does not match the parameters of the proof.
+    /// - The proof is malformed.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ProofFormatError> {
+        if !bytes.starts_with(&MAGIC) {
+            return StarkProof::from_bytes(bytes)
+                .map(Self::new)
+                .map_err(ProofFormatError::DeserializationError);
+        }
+
+        let mut source = SliceReader::new(bytes);
+        let header = ProofHeader::read_envelope(&mut source)?;
+        let proof_bytes = match header.compression {
+            ProofCompression::None => source
+                .read_u8_vec(bytes.len() - ProofHeader::ENVELOPE_SIZE)
+                .map_err(ProofFormatError::DeserializationError)?,
+            ProofCompression::Queries => {
+                let mut proof_bytes = Vec::new();
+                for _ in 0..NUM_SECTIONS {
+                    read_section(&mut source, &mut proof_bytes)?;
+                }
+                if source.has_more_bytes() {
+                    return Err(ProofFormatError::DeserializationError(
+                        DeserializationError::UnconsumedBytes,
+                    ));
+                }
+                proof_bytes
+            }
+        };
+
+        let proof =
+            StarkProof::from_bytes(&proof_bytes).map_err(ProofFormatError::DeserializationError)?;
+        if proof.options() != &header.options || proof.trace_length() != header.trace_length {
+            return Err(ProofFormatError::ParameterMismatch);
+        }
+        Ok(Self::new(proof))
+    }
+
+    /// Returns the header of the proof serialized into the specified bytes without parsing the
+    /// proof itself.
+    ///
+    /// For proofs serialized before the envelope was introduced, the header is read from the
+    /// context of the proof, and the version of the header is 0.
+    ///
+    /// # Errors
+    /// Returns an error if the envelope was written in a newer version of the proof format, or
+    /// if the header is malformed.
+    pub fn read_header(bytes: &[u8]) -> Result<ProofHeader, ProofFormatError> {
+        let mut source = SliceReader::new(bytes);
+        if !bytes.starts_with(&MAGIC) {
+            let context =
+                Context::read_from(&mut source).map_err(ProofFormatError::DeserializationError)?;
+            return Ok(ProofHeader {
+                version: 0,
+                options: context.options().clone(),
+                trace_length: context.trace_length(),
+                compression: ProofCompression::None,
+            });
+        }
+        ProofHeader::read_envelope(&mut source)
+    }
+
+    // HELPER FUNCTIONS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the serialized sections of this proof together with flags indicating whether the
+    /// section is compressed under [ProofCompression::Queries]. Concatenated, the sections are
+    /// the same as the raw STARK proof.
+    fn sections(&self) -> [(Vec<u8>, bool); NUM_SECTIONS] {
+        let mut head = Vec::new();
+        self.proof.context.write_into(&mut head);
+        self.proof.commitments.write_into(&mut head);
+
+        let mut queries = Vec::new();
+        self.proof.trace_queries.write_into(&mut queries);
+        self.proof.constraint_queries.write_into(&mut queries);
+
+        let mut ood_frame = Vec::new();
+        self.proof.ood_frame.write_into(&mut ood_frame);
+
+        let mut fri_proof = Vec::new();
+        self.proof.fri_proof.write_into(&mut fri_proof);
+
+        let pow_nonce = self.proof.pow_nonce.to_le_bytes().to_vec();
+
+        [
+            (head, false),
+            (queries, true),
+            (ood_frame, false),
+            (fri_proof, true),
+            (pow_nonce, false),
+        ]
+    }
+}
+
+/// Number of sections a proof is split into under [ProofCompression::Queries].
+const NUM_SECTIONS: usize = 5;
+
+// PROOF HEADER
+// ================================================================================================
+
+/// The parameter block of a serialized [ExecutionProof].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProofHeader {
+    version: u8,
+    options: WinterProofOptions,
+    trace_length: usize,
+    compression: ProofCompression,
+}
+
+impl ProofHeader {
+    /// Size of the envelope of an uncompressed proof in bytes: magic bytes, version, options,
+    /// trace length, and compression.
+    const ENVELOPE_SIZE: usize = 4 + 1 + 7 + 1 + 1;
+
+    /// Returns the version of the proof format; 0 for proofs serialized before the envelope was
+    /// introduced.
+    pub fn version(&self) -> u8 {
+        self.version
+    }
+
+    /// Returns the options with which the proof was generated.
+    pub fn options(&self) -> &WinterProofOptions {
+        &self.options
+    }
+
+    /// Returns the length of the execution trace proven by the proof.
+    pub fn trace_length(&self) -> usize {
+        self.trace_length
+    }
+
+    /// Returns the compression applied to the proof.
+    pub fn compression(&self) -> ProofCompression {
+        self.compression
+    }
+
+    /// Reads the envelope of a proof (excluding the proof itself) from the source.
+    fn read_envelope(source: &mut SliceReader) -> Result<Self, ProofFormatError> {
+        let _magic = source.read_u8_array::<4>();
+        let version = source
+            .read_u8()
+            .map_err(ProofFormatError::DeserializationError)?;
+        if version > PROOF_VERSION {
+            return Err(ProofFormatError::UnsupportedVersion(version));
+        }
+
+        let options = WinterProofOptions::read_from(source)
+            .map_err(ProofFormatError::DeserializationError)?;
+        let log_trace_length = source
+            .read_u8()
+            .map_err(ProofFormatError::DeserializationError)?;
+        if log_trace_length >= usize::BITS as u8 {
+            return Err(ProofFormatError::DeserializationError(
+                DeserializationError::InvalidValue(format!(
+                    "trace length 2^{} is too large",
+                    log_trace_length
+                )),
+            ));
+        }
+        let compression = source
+            .read_u8()
+            .map_err(ProofFormatError::DeserializationError)?;
+        let compression = ProofCompression::try_from(compression)
+            .map_err(|_| ProofFormatError::UnsupportedCompression(compression))?;
+
+        Ok(Self {
+            version,
+            options,
+            trace_length: 1 << log_trace_length,
+            compression,
+        })
+    }
+}
+
+// PROOF COMPRESSION
+// ================================================================================================
+
+/// Compression applied to a serialized [ExecutionProof].
+///
+/// Most of a STARK proof consists of field elements and hashes, which are close to random; thus,
+/// compression reduces the size of typical proofs only negligibly, and pays off mostly for proofs
+/// with repetitive query values (e.g., of traces with many constant columns). Sections which do
+/// not shrink when compressed are stored as is, and thus a compressed proof is at most 9 bytes
+/// per section larger than an uncompressed one.
+#[repr(u8)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ProofCompression {
+    /// The proof is not compressed.
+    None = 0,
+    /// Trace and constraint queries, as well as FRI layers and the FRI remainder, are compressed
+    /// with an LZ77-style compression.
+    Queries = 1,
+}
+
+impl TryFrom<u8> for ProofCompression {
+    type Error = u8;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::None),
+            1 => Ok(Self::Queries),
+            _ => Err(value),
+        }
+    }
+}
+
+// PROOF FORMAT ERROR
+// ================================================================================================
+
+/// An error returned when a serialized [ExecutionProof] cannot be read.
+#[derive(Debug, PartialEq, Eq)]
+pub enum ProofFormatError {
+    /// The proof was serialized in a newer version of the proof format.
+    UnsupportedVersion(u8),
+    /// The proof was compressed with an unknown compression.
+    UnsupportedCompression(u8),
+    /// The parameter block of the envelope does not match the parameters of the proof.
+    ParameterMismatch,
+    /// The proof is malformed.
+    DeserializationError(DeserializationError),
+}
+
+impl fmt::Display for ProofFormatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnsupportedVersion(version) => write!(
+                f,
+                "proof format version {} is not supported; the latest supported version is {}",
+                version, PROOF_VERSION
+            ),
+            Self::UnsupportedCompression(compression) => {
+                write!(f, "proof compression {} is not supported", compression)
+            }
+            Self::ParameterMismatch => write!(
+                f,
+                "parameters in the proof header do not match the parameters of the proof"
+            ),
+            Self::DeserializationError(err) => write!(f, "malformed proof: {}", err),
+        }
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Writes the specified section of a proof into the target, compressing it if requested and if
+/// compression reduces its size.
+///
+/// A section is written as a flag indicating whether the section is compressed, the size of the
+/// section before compression, and the size of the section as written, followed by its bytes.
+fn write_section(target: &mut Vec<u8>, section: &[u8], compress: bool) {
+    let compressed = if compress {
+        Some(lz::compress(section)).filter(|compressed| compressed.len() < section.len())
+    } else {
+        None
+    };
+
+    target.write_u8(compressed.is_some() as u8);
+    target.write_u32(section.len() as u32);
+    let bytes = compressed.as_deref().unwrap_or(section);
+    target.write_u32(bytes.len() as u32);
+    target.write_u8_slice(bytes);
+}
+
+/// Reads a section of a proof from the source, and appends the decompressed section to the
+/// target.
+fn read_section(source: &mut SliceReader, target: &mut Vec<u8>) -> Result<(), ProofFormatError> {
+    let read = |source: &mut SliceReader| -> Result<_, DeserializationError> {
+        let is_compressed = source.read_u8()?;
+        let size = source.read_u32()? as usize;
+        let num_bytes = source.read_u32()? as usize;
+        let bytes = source.read_u8_vec(num_bytes)?;
+        Ok((is_compressed, size, bytes))
+    };
+    let (is_compressed, size, bytes) =
+        read(source).map_err(ProofFormatError::DeserializationError)?;
+
+    let section = match is_compressed {
+        0 => bytes,
+        1 if size <= MAX_SECTION_SIZE => {
+            lz::decompress(&bytes, size).map_err(ProofFormatError::DeserializationError)?
+        }
+        1 => {
+            return Err(ProofFormatError::DeserializationError(
+                DeserializationError::InvalidValue(format!(
+                    "section of {} bytes exceeds the maximum section size",
+                    size
+                )),
+            ))
+        }
+        flag => {
+            return Err(ProofFormatError::DeserializationError(
+                DeserializationError::InvalidValue(format!("{} is not a valid section flag", flag)),
+            ))
+        }
+    };
+    if section.len() != size {
+        return Err(ProofFormatError::DeserializationError(
+            DeserializationError::InvalidValue(format!(
+                "expected section of {} bytes, but was {}",
+                size,
+                section.len()
+            )),
+        ));
+    }
+    target.extend_from_slice(&section);
+    Ok(())
+}
@@ -0,0 +1,112 @@
+use super::{
+    lz, ByteWriter, DeserializationError, ExecutionProof, ProofCompression, ProofFormatError,
+    Serializable, Vec, WinterProofOptions, MAGIC, PROOF_VERSION,
+};
+use winter_air::{FieldExtension, HashFunction};
+
+// LZ77 COMPRESSION
+// ================================================================================================
+
+#[test]
+fn lz_round_trip() {
+    let inputs: [Vec<u8>; 5] = [
+        Vec::new(),
+        vec![7],
+        vec![0; 1000],
+        (0..2000_u32).map(|i| (i % 251) as u8).collect(),
+        (0..3000_u64)
+            .map(|i| (i.wrapping_mul(0x9e37_79b9_7f4a_7c15) >> 56) as u8)
+            .collect(),
+    ];
+    for input in inputs {
+        let compressed = lz::compress(&input);
+        assert_eq!(input, lz::decompress(&compressed, input.len()).unwrap());
+    }
+
+    // repetitive data shrinks
+    assert!(lz::compress(&[0; 1000]).len() < 50);
+}
+
+#[test]
+fn lz_invalid_input() {
+    let compressed = lz::compress(&[1, 2, 3, 4, 1, 2, 3, 4, 1, 2, 3, 4]);
+
+    // wrong size or truncated input
+    assert!(lz::decompress(&compressed, 11).is_err());
+    assert!(lz::decompress(&compressed, 13).is_err());
+    assert_eq!(
+        Err(DeserializationError::UnexpectedEOF),
+        lz::decompress(&compressed[..compressed.len() - 1], 12)
+    );
+
+    // back-reference before the start of the output
+    assert!(lz::decompress(&[0x80, 1, 0], 4).is_err());
+}
+
+// PROOF ENVELOPE
+// ================================================================================================
+
+#[test]
+fn unsupported_envelopes() {
+    // a newer version of the format is rejected before anything else is read
+    let mut bytes = MAGIC.to_vec();
+    bytes.write_u8(PROOF_VERSION + 1);
+    assert_eq!(
+        Err(ProofFormatError::UnsupportedVersion(PROOF_VERSION + 1)),
+        ExecutionProof::from_bytes(&bytes)
+    );
+    assert_eq!(
+        Err(ProofFormatError::UnsupportedVersion(PROOF_VERSION + 1)),
+        ExecutionProof::read_header(&bytes).map(|_| ())
+    );
+
+    // an unknown compression is rejected as well
+    let mut bytes = envelope(9);
+    bytes.extend_from_slice(&[1, 2, 3]);
+    assert_eq!(
+        Err(ProofFormatError::UnsupportedCompression(9)),
+        ExecutionProof::from_bytes(&bytes)
+    );
+
+    // a valid header is readable without the proof
+    let header = ExecutionProof::read_header(&envelope(1)).unwrap();
+    assert_eq!(PROOF_VERSION, header.version());
+    assert_eq!(&options(), header.options());
+    assert_eq!(1024, header.trace_length());
+    assert_eq!(ProofCompression::Queries, header.compression());
+
+    // but the proof must be present
+    assert!(matches!(
+        ExecutionProof::from_bytes(&envelope(0)),
+        Err(ProofFormatError::DeserializationError(_))
+    ));
+    assert!(matches!(
+        ExecutionProof::from_bytes(&envelope(1)),
+        Err(ProofFormatError::DeserializationError(_))
+    ));
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+fn options() -> WinterProofOptions {
+    WinterProofOptions::new(
+        27,
+        8,
+        16,
+        HashFunction::Blake3_192,
+        FieldExtension::Quadratic,
+        8,
+        256,
+    )
+}
+
+/// Returns the envelope of a proof of a trace with 1024 rows without the proof itself.
+fn envelope(compression: u8) -> Vec<u8> {
+    let mut bytes = MAGIC.to_vec();
+    bytes.write_u8(PROOF_VERSION);
+    options().write_into(&mut bytes);
+    bytes.write_u8(10);
+    bytes.write_u8(compression);
+    bytes
+}
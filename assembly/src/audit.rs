@@ -0,0 +1,293 @@
+use super::{BTreeMap, Digest, Optimization, String, ToString, Vec};
+use core::{cell::RefCell, fmt::Write};
+use vm_core::utils::hex::digest_to_hex;
+
+#[cfg(not(feature = "std"))]
+use alloc::rc::Rc;
+
+#[cfg(feature = "std")]
+use std::rc::Rc;
+
+// CONSTANTS
+// ================================================================================================
+
+/// The name under which the decisions made while compiling the program body are logged.
+pub const PROGRAM_BODY_NAME: &str = "#main";
+
+// AUDIT LOG
+// ================================================================================================
+
+/// A log of the non-trivial decisions which the assembler made while compiling a program.
+///
+/// The log is produced by [Assembler::compile_with_audit()](crate::Assembler::compile_with_audit)
+/// and lists every procedure parsed during the compilation (including procedures of imported
+/// modules, and the program body itself) in the order in which they were parsed. For every
+/// procedure, the log contains the MAST root of the procedure compiled without any optional
+/// transformations (i.e., without optimizations and with the default push encoding) and the
+/// MAST root of the procedure as it was actually compiled, together with the decisions which led
+/// from one to the other.
+///
+/// The log can be serialized into JSON via [AuditLog::to_json()].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AuditLog {
+    procedures: Vec<ProcedureAudit>,
+}
+
+impl AuditLog {
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the audited procedures in the order in which they were parsed.
+    pub fn procedures(&self) -> &[ProcedureAudit] {
+        &self.procedures
+    }
+
+    /// Returns the audit of the procedure with the specified name, if any. Procedures of imported
+    /// modules are named by their full path (e.g., `std::math::u64::checked_add`), and the program
+    /// body is named `#main`.
+    pub fn get_procedure(&self, name: &str) -> Option<&ProcedureAudit> {
+        self.procedures.iter().find(|proc| proc.name == name)
+    }
+
+    /// Returns all decisions in this log.
+    pub fn decisions(&self) -> impl Iterator<Item = &Decision> {
+        self.procedures
+            .iter()
+            .flat_map(|proc| proc.decisions.iter())
+    }
+
+    /// Returns this log serialized as a JSON object.
+    ///
+    /// The object contains a single `procedures` array; every procedure is an object with `name`,
+    /// `before`, `after`, and `decisions` fields, and every decision is an object with `kind`,
+    /// `detail`, `before`, and `after` fields. Digests are hex-encoded with a `0x` prefix.
+    pub fn to_json(&self) -> String {
+        let mut json = String::from("{\"procedures\":[");
+        for (i, proc) in self.procedures.iter().enumerate() {
+            if i > 0 {
+                json.push(',');
+            }
+            write!(
+                json,
+                "{{\"name\":{},\"before\":\"{}\",\"after\":\"{}\",\"decisions\":[",
+                quote(&proc.name),
+                digest_to_hex(proc.before),
+                digest_to_hex(proc.after)
+            )
+            .expect("failed to write JSON");
+            for (j, decision) in proc.decisions.iter().enumerate() {
+                if j > 0 {
+                    json.push(',');
+                }
+                write!(
+                    json,
+                    "{{\"kind\":{},\"detail\":{},\"before\":\"{}\",\"after\":\"{}\"}}",
+                    quote(&decision.kind.to_string()),
+                    quote(&decision.detail),
+                    digest_to_hex(decision.before),
+                    digest_to_hex(decision.after)
+                )
+                .expect("failed to write JSON");
+            }
+            json.push_str("]}");
+        }
+        json.push_str("]}");
+        json
+    }
+}
+
+/// The part of an [AuditLog] describing a single procedure.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProcedureAudit {
+    name: String,
+    before: Digest,
+    after: Digest,
+    decisions: Vec<Decision>,
+}
+
+impl ProcedureAudit {
+    /// Returns the name of the procedure.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the MAST root of the procedure compiled without any optional transformations.
+    pub fn before(&self) -> Digest {
+        self.before
+    }
+
+    /// Returns the MAST root of the procedure as it was compiled.
+    pub fn after(&self) -> Digest {
+        self.after
+    }
+
+    /// Returns the decisions which the assembler made while compiling the procedure.
+    pub fn decisions(&self) -> &[Decision] {
+        &self.decisions
+    }
+
+    /// Returns true if optional transformations changed the MAST root of the procedure.
+    pub fn is_transformed(&self) -> bool {
+        self.before != self.after
+    }
+}
+
+/// A single decision made by the assembler.
+///
+/// Every decision refers to a code block: `before` is the hash of the block before the decision
+/// was applied, and `after` is the hash of the resulting block (or the zero digest if the block
+/// was removed entirely). Decisions which do not change the block (e.g., inlining a procedure)
+/// have the same `before` and `after` hashes.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Decision {
+    kind: DecisionKind,
+    detail: String,
+    before: Digest,
+    after: Digest,
+}
+
+impl Decision {
+    /// Returns the kind of this decision.
+    pub fn kind(&self) -> DecisionKind {
+        self.kind
+    }
+
+    /// Returns a human-readable description of this decision.
+    pub fn detail(&self) -> &str {
+        &self.detail
+    }
+
+    /// Returns the hash of the code block before the decision was applied.
+    pub fn before(&self) -> Digest {
+        self.before
+    }
+
+    /// Returns the hash of the code block after the decision was applied.
+    pub fn after(&self) -> Digest {
+        self.after
+    }
+}
+
+/// The kind of a [Decision].
+///
+/// The assembler never reorders operations, and thus there is no decision kind for reordering.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum DecisionKind {
+    /// A procedure was inlined at an `exec` instruction.
+    Inline,
+    /// A span block was transformed by the specified optimization; the detail of the decision
+    /// states how many times the optimization was applied.
+    Optimize(Optimization),
+    /// A `repeat` block was unrolled in full.
+    Unroll,
+    /// A `repeat` block was split into a counter-controlled loop over a partially unrolled body.
+    Split,
+    /// A `while.bounded` loop was wrapped into code which limits the number of its iterations; the
+    /// detail of the decision states the maximum number of iterations and the number of
+    /// operations in the loop body.
+    Bound,
+    /// NOOP operations were inserted into a span block to align operations with immediate values
+    /// to operation groups and batches.
+    Padding,
+}
+
+impl core::fmt::Display for DecisionKind {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Inline => write!(f, "inline"),
+            Self::Optimize(optimization) => write!(f, "optimize.{}", optimization.name()),
+            Self::Unroll => write!(f, "unroll"),
+            Self::Split => write!(f, "split"),
+            Self::Bound => write!(f, "bound"),
+            Self::Padding => write!(f, "padding"),
+        }
+    }
+}
+
+// AUDIT RECORDER
+// ================================================================================================
+
+/// Collects decisions of the assembler into an [AuditLog] during compilation.
+///
+/// The recorder is shared by all assembly contexts of a compilation. Decisions are attributed to
+/// the procedure which is being parsed; since procedures are parsed one after another (imported
+/// modules are parsed before any procedure of the importing module), at most one procedure is
+/// parsed at any time.
+#[derive(Clone, Debug, Default)]
+pub struct AuditRecorder {
+    state: Rc<RefCell<RecorderState>>,
+}
+
+#[derive(Debug, Default)]
+struct RecorderState {
+    log: AuditLog,
+    decisions: Vec<Decision>,
+}
+
+impl AuditRecorder {
+    /// Records a decision for the procedure which is currently being parsed.
+    pub fn record(&self, kind: DecisionKind, detail: String, before: Digest, after: Digest) {
+        self.state.borrow_mut().decisions.push(Decision {
+            kind,
+            detail,
+            before,
+            after,
+        });
+    }
+
+    /// Records a parsed procedure together with all decisions recorded since the previous
+    /// procedure was parsed.
+    pub fn add_procedure(&self, name: String, root: Digest) {
+        let mut state = self.state.borrow_mut();
+        let decisions = core::mem::take(&mut state.decisions);
+        state.log.procedures.push(ProcedureAudit {
+            name,
+            before: root,
+            after: root,
+            decisions,
+        });
+    }
+
+    /// Returns the log collected by this recorder; the `before` root of every procedure is the
+    /// same as its `after` root.
+    pub fn into_log(self) -> AuditLog {
+        self.state.borrow().log.clone()
+    }
+}
+
+impl AuditLog {
+    /// Sets the `before` root of every procedure in this log to the root of the procedure with
+    /// the same name in the specified baseline log.
+    pub(crate) fn set_baseline(&mut self, baseline: &AuditLog) {
+        let baseline_roots = baseline
+            .procedures
+            .iter()
+            .map(|proc| (proc.name.as_str(), proc.after))
+            .collect::<BTreeMap<_, _>>();
+        for proc in self.procedures.iter_mut() {
+            if let Some(&root) = baseline_roots.get(proc.name.as_str()) {
+                proc.before = root;
+            }
+        }
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the specified string as a quoted and escaped JSON string.
+fn quote(value: &str) -> String {
+    let mut result = String::from("\"");
+    for c in value.chars() {
+        match c {
+            '"' => result.push_str("\\\""),
+            '\\' => result.push_str("\\\\"),
+            c if (c as u32) < 0x20 => {
+                write!(result, "\\u{:04x}", c as u32).expect("failed to write JSON");
+            }
+            c => result.push(c),
+        }
+    }
+    result.push('"');
+    result
+}
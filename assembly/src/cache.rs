@@ -0,0 +1,83 @@
+use super::{Arc, ModuleMap, ProcMap};
+
+#[cfg(feature = "std")]
+use std::sync::{PoisonError, RwLock};
+
+// MODULE CACHE
+// ================================================================================================
+
+/// Library modules parsed by an assembler, shared by the assembler and all of its clones.
+///
+/// Modules parsed while compiling a program are added to the cache, so that subsequent
+/// compilations importing the same modules do not need to parse them again. The cache is guarded
+/// by a read-write lock, and thus it can be filled by compilations running on multiple threads
+/// concurrently. Without the standard library no lock is available; in this case modules are
+/// cached only via [Assembler::cache_module()](crate::Assembler::cache_module).
+///
+/// Parsed modules depend on the settings of the assembler; thus, builder methods which change
+/// the settings replace the cache of the assembler with an empty one.
+#[derive(Default)]
+pub struct ModuleCache {
+    #[cfg(feature = "std")]
+    modules: RwLock<ModuleMap>,
+    #[cfg(not(feature = "std"))]
+    modules: ModuleMap,
+}
+
+impl ModuleCache {
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns true if the module with the specified path is in this cache.
+    pub fn contains(&self, module_path: &str) -> bool {
+        #[cfg(feature = "std")]
+        let modules = self.modules.read().unwrap_or_else(PoisonError::into_inner);
+        #[cfg(not(feature = "std"))]
+        let modules = &self.modules;
+        modules.contains_key(module_path)
+    }
+
+    /// Returns the procedures exported from the module with the specified path, if the module is
+    /// in this cache.
+    pub fn get(&self, module_path: &str) -> Option<ProcMap> {
+        #[cfg(feature = "std")]
+        let modules = self.modules.read().unwrap_or_else(PoisonError::into_inner);
+        #[cfg(not(feature = "std"))]
+        let modules = &self.modules;
+        modules.get(module_path).cloned()
+    }
+
+    // STATE MUTATORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Adds the specified modules parsed during a compilation to this cache; modules which are
+    /// already in the cache are kept. Without the standard library, this does nothing.
+    pub fn extend(&self, modules: &ModuleMap) {
+        #[cfg(feature = "std")]
+        {
+            let mut cached = self.modules.write().unwrap_or_else(PoisonError::into_inner);
+            for (path, module_procs) in modules.iter() {
+                cached
+                    .entry(path.clone())
+                    .or_insert_with(|| module_procs.clone());
+            }
+        }
+        #[cfg(not(feature = "std"))]
+        let _ = modules;
+    }
+
+    /// Adds the specified modules to the specified cache; modules which are already in the cache
+    /// are kept. Without the standard library, the cache is copied first if it is shared.
+    pub fn insert(cache: &mut Arc<Self>, modules: ModuleMap) {
+        #[cfg(feature = "std")]
+        cache.extend(&modules);
+        #[cfg(not(feature = "std"))]
+        {
+            let mut cached = cache.modules.clone();
+            for (path, module_procs) in modules {
+                cached.entry(path).or_insert(module_procs);
+            }
+            *cache = Arc::new(Self { modules: cached });
+        }
+    }
+}
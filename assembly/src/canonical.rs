@@ -0,0 +1,221 @@
+use super::{
+    parse_constant, parse_data_segment, AssemblyContext, AssemblyError, AssemblyLimits, BTreeMap,
+    SpanOptions, String, ToString, Token, TokenStream, Vec, DEFAULT_SPLIT_THRESHOLD,
+};
+
+// CONSTANTS
+// ================================================================================================
+
+/// Number of spaces by which each nested block is indented in the canonical form.
+const INDENT_WIDTH: usize = 4;
+
+/// Prefix of the labels assigned to private procedures in the canonical form.
+const PRIVATE_PROC_PREFIX: &str = "proc";
+
+// CANONICAL FORM
+// ================================================================================================
+
+/// Returns the canonical form of the provided source.
+///
+/// The canonical form is obtained as follows:
+/// - Comments (including doc comments) are removed, and every instruction is placed on its own
+///   line indented according to the nesting depth of its block.
+/// - Imports are kept in their original order.
+/// - Constants are sorted by name, and their values are replaced with the values of the original
+///   constant expressions.
+/// - Data segments are kept in their original order, and the values of their elements are
+///   replaced with the values of the original expressions.
+/// - Private procedures are renamed to `proc0`, `proc1` etc. in the order of their declaration;
+///   exported procedures keep their labels.
+///
+/// None of these transformations affect the MAST of the compiled program or module. Thus, two
+/// sources differing only in formatting, comments, constant declarations, and labels of private
+/// procedures have the same canonical form.
+///
+/// # Errors
+/// Returns an error if the source is empty or contains malformed import, constant, or procedure
+/// declarations.
+pub fn canonicalize(source: &str) -> Result<String, AssemblyError> {
+    let mut tokens = TokenStream::new(source)?;
+    let mut result = String::new();
+
+    // imports are kept in their original order since the order in which modules are imported
+    // determines which procedures are visible under a given module name
+    while let Some(token) = tokens.read() {
+        if token.parts()[0] != Token::USE {
+            break;
+        }
+        push_line(&mut result, 0, &token.to_string());
+        tokens.advance();
+    }
+
+    // constants are sorted by name; since their values are inlined, the order of declaration
+    // does not matter anymore
+    let mut context = AssemblyContext::new(
+        None,
+        DEFAULT_SPLIT_THRESHOLD,
+        None,
+        SpanOptions::default(),
+        AssemblyLimits::default(),
+    );
+    let mut constants = BTreeMap::new();
+    while let Some(token) = tokens.read() {
+        if token.parts()[0] != Token::CONST {
+            break;
+        }
+        let (name, value) = parse_constant(token, &context)?;
+        if !context.add_constant(name.clone(), value) {
+            return Err(AssemblyError::duplicate_const(token, &name));
+        }
+        constants.insert(name, value);
+        tokens.advance();
+    }
+    if !result.is_empty() && !constants.is_empty() {
+        result.push('\n');
+    }
+    for (name, value) in constants {
+        push_line(
+            &mut result,
+            0,
+            &format!("{}.{}={}", Token::CONST, name, value),
+        );
+    }
+
+    // data segments keep their order since the order of declaration determines their addresses;
+    // the values of their elements are inlined
+    let mut is_first_segment = true;
+    while let Some(token) = tokens.read() {
+        if token.parts()[0] != Token::DATA {
+            break;
+        }
+        let (name, values) = parse_data_segment(token, &context)?;
+        if is_first_segment && !result.is_empty() {
+            result.push('\n');
+        }
+        is_first_segment = false;
+        let values = values
+            .iter()
+            .map(|value| value.to_string())
+            .collect::<Vec<_>>();
+        push_line(
+            &mut result,
+            0,
+            &format!("{}.{}=[{}]", Token::DATA, name, values.join(",")),
+        );
+        tokens.advance();
+    }
+
+    // assign canonical labels to private procedures
+    let body_start = tokens.pos();
+    let labels = rename_private_procs(&mut tokens)?;
+    tokens.seek(body_start);
+
+    // write out procedures and the program body, one instruction per line; annotations are
+    // written on separate lines right before the procedure header
+    let mut depth = 0;
+    let mut is_annotated = false;
+    while let Some(token) = tokens.read() {
+        let parts = token.parts();
+        match parts[0] {
+            _ if depth == 0 && token.is_annotation() => {
+                if !is_annotated && !result.is_empty() {
+                    result.push('\n');
+                }
+                push_line(&mut result, depth, &token.to_string());
+                is_annotated = true;
+            }
+            Token::PROC
+            | Token::EXPORT
+            | Token::BEGIN
+            | Token::IF
+            | Token::WHILE
+            | Token::REPEAT => {
+                if depth == 0 && !result.is_empty() && !is_annotated {
+                    result.push('\n');
+                }
+                is_annotated = false;
+                let line = match parts[0] {
+                    Token::PROC | Token::EXPORT => rename_label(token, &labels),
+                    _ => token.to_string(),
+                };
+                push_line(&mut result, depth, &line);
+                depth += 1;
+            }
+            Token::ELSE => push_line(&mut result, depth.saturating_sub(1), &token.to_string()),
+            Token::END => {
+                depth = depth.saturating_sub(1);
+                push_line(&mut result, depth, &token.to_string());
+            }
+            Token::EXEC | Token::CALL => {
+                push_line(&mut result, depth, &rename_label(token, &labels))
+            }
+            _ => push_line(&mut result, depth, &token.to_string()),
+        }
+        tokens.advance();
+    }
+
+    Ok(result)
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Scans procedure declarations from the current position of the token stream and returns a map
+/// from labels of private procedures to their canonical labels.
+///
+/// Canonical labels which clash with labels of exported procedures are suffixed with underscores.
+fn rename_private_procs(
+    tokens: &mut TokenStream,
+) -> Result<BTreeMap<String, String>, AssemblyError> {
+    let mut private_labels = Vec::new();
+    let mut export_labels = Vec::new();
+    while let Some(token) = tokens.read() {
+        if matches!(token.parts()[0], Token::PROC | Token::EXPORT) {
+            let (label, _, _, is_export) = token.parse_proc()?;
+            if is_export {
+                export_labels.push(label);
+            } else {
+                private_labels.push(label);
+            }
+        }
+        tokens.advance();
+    }
+
+    let mut labels = BTreeMap::new();
+    for (idx, label) in private_labels.into_iter().enumerate() {
+        let mut canonical_label = format!("{}{}", PRIVATE_PROC_PREFIX, idx);
+        while export_labels.contains(&canonical_label) {
+            canonical_label.push('_');
+        }
+        labels.insert(label, canonical_label);
+    }
+    Ok(labels)
+}
+
+/// Returns the specified procedure declaration or invocation token with the procedure label
+/// replaced according to the provided map. Generic parameters, invocation arguments, and the
+/// number of locals are retained.
+fn rename_label(token: &Token, labels: &BTreeMap<String, String>) -> String {
+    let mut parts = token
+        .parts()
+        .iter()
+        .map(|part| part.to_string())
+        .collect::<Vec<_>>();
+    if let Some(part) = parts.get_mut(1) {
+        let (label, suffix) = match part.find('<') {
+            Some(idx) => part.split_at(idx),
+            None => (part.as_str(), ""),
+        };
+        if let Some(canonical_label) = labels.get(label) {
+            *part = format!("{}{}", canonical_label, suffix);
+        }
+    }
+    parts.join(".")
+}
+
+/// Appends the specified line to the result indented according to the specified depth.
+fn push_line(result: &mut String, depth: usize, line: &str) {
+    result.extend(core::iter::repeat(' ').take(depth * INDENT_WIDTH));
+    result.push_str(line);
+    result.push('\n');
+}
@@ -1,4 +1,9 @@
-use super::{BTreeMap, CodeBlock, ProcMap, Procedure, String, ToString, MODULE_PATH_DELIM};
+use super::{
+    Arc, AssemblyError, AssemblyLimits, AuditRecorder, BTreeMap, BudgetPolicy, CodeBlock, Digest,
+    ExtensionMap, GenericProcedure, InstructionExtension, LocalsPath, ProcMap, Procedure,
+    SpanOptions, String, ToString, Token, Vec, WarningRecorder, DATA_SEGMENT_OFFSET,
+    MODULE_PATH_DELIM,
+};
 
 // ASSEMBLY CONTEXT
 // ================================================================================================
@@ -10,20 +15,126 @@ use super::{BTreeMap, CodeBlock, ProcMap, Procedure, String, ToString, MODULE_PA
 /// parsed from the body of a program, while imported procedures are imported from external
 /// libraries.
 ///
-/// Local procedures are owned by the context, while imported procedures are stored by reference.
-pub struct AssemblyContext<'a> {
+/// Procedures are reference-counted so that imported procedures can be shared between the
+/// contexts of all modules and programs which import them.
+/// Generic procedures are always local, and are kept in the order of their declaration.
+///
+/// Programs compiled against a kernel can also invoke procedures exported from the kernel via
+/// system calls; such procedures are kept separately from other procedures.
+///
+/// The context also holds constants and data segments declared in the program or module being
+/// parsed, the maximum number of operations a single `repeat` block is allowed to unroll into, the
+/// maximum number of locals which can be allocated along a single chain of procedure invocations,
+/// the level of optimizations applied to span blocks, and the limits on the size of the parsed
+/// source (see [AssemblyLimits]). When the compilation is audited, the
+/// context also holds the recorder to which decisions of the assembler are reported; when
+/// warnings are collected, it holds the recorder to which warnings are reported. When
+/// procedure profiling is enabled, the roots of procedures invoked via `exec` are kept as separate
+/// blocks (see [Assembler::with_profiling()](crate::Assembler::with_profiling)). Custom
+/// instructions are expanded by the instruction extensions held by the context.
+pub struct AssemblyContext {
     local_procs: ProcMap,
-    imported_procs: BTreeMap<String, &'a Procedure>,
+    imported_procs: ProcMap,
+    generic_procs: Vec<GenericProcedure>,
+    kernel_procs: Arc<ProcMap>,
+    extensions: Arc<ExtensionMap>,
+    constants: BTreeMap<String, u64>,
+    data_segments: BTreeMap<String, u64>,
+    data_size: u64,
+    unroll_budget: Option<usize>,
+    split_threshold: usize,
+    locals_budget: Option<(u64, BudgetPolicy)>,
+    span_options: SpanOptions,
+    limits: AssemblyLimits,
+    audit: Option<AuditRecorder>,
+    warnings: Option<WarningRecorder>,
+    profiling: bool,
+    module_path: String,
 }
 
-impl<'a> AssemblyContext<'a> {
+impl AssemblyContext {
     // CONSTRUCTOR
     // --------------------------------------------------------------------------------------------
-    /// Returns a new empty [AssemblyContext].
-    pub fn new() -> Self {
+    /// Returns a new empty [AssemblyContext] with the specified unroll budget, `repeat` split
+    /// threshold, locals budget, span optimization settings, and limits.
+    pub fn new(
+        unroll_budget: Option<usize>,
+        split_threshold: usize,
+        locals_budget: Option<(u64, BudgetPolicy)>,
+        span_options: SpanOptions,
+        limits: AssemblyLimits,
+    ) -> Self {
         Self {
             local_procs: BTreeMap::new(),
             imported_procs: BTreeMap::new(),
+            generic_procs: Vec::new(),
+            kernel_procs: Arc::new(BTreeMap::new()),
+            extensions: Arc::new(BTreeMap::new()),
+            constants: BTreeMap::new(),
+            data_segments: BTreeMap::new(),
+            data_size: 0,
+            unroll_budget,
+            split_threshold,
+            locals_budget,
+            span_options,
+            limits,
+            audit: None,
+            warnings: None,
+            profiling: false,
+            module_path: String::new(),
+        }
+    }
+
+    /// Returns a new [AssemblyContext] for instantiating the specified generic procedure of this
+    /// context with the specified arguments.
+    ///
+    /// The returned context contains all constants of this context with generic parameters bound
+    /// to the argument values, all non-generic procedures of this context, and generic procedures
+    /// declared before the instantiated procedure. Thus, generic procedures cannot recurse.
+    ///
+    /// # Panics
+    /// Panics if the procedure does not belong to this context, or if the number of arguments
+    /// differs from the number of generic parameters of the procedure.
+    pub fn instantiate_generic_proc(
+        &self,
+        proc: &GenericProcedure,
+        args: &[u64],
+    ) -> AssemblyContext {
+        assert_eq!(proc.params().len(), args.len(), "wrong number of arguments");
+        let proc_idx = self
+            .generic_procs
+            .iter()
+            .position(|p| p.label() == proc.label())
+            .expect("no generic procedure");
+
+        let mut imported_procs = self.imported_procs.clone();
+        for (label, local_proc) in self.local_procs.iter() {
+            imported_procs.insert(label.clone(), local_proc.clone());
+        }
+
+        let mut constants = self.constants.clone();
+        for (name, &value) in proc.params().iter().zip(args) {
+            constants.insert(name.clone(), value);
+        }
+
+        AssemblyContext {
+            local_procs: BTreeMap::new(),
+            imported_procs,
+            generic_procs: self.generic_procs[..proc_idx].to_vec(),
+            kernel_procs: self.kernel_procs.clone(),
+            extensions: self.extensions.clone(),
+            constants,
+            data_segments: self.data_segments.clone(),
+            data_size: self.data_size,
+            unroll_budget: self.unroll_budget,
+            split_threshold: self.split_threshold,
+            locals_budget: self.locals_budget,
+            span_options: self.span_options,
+            limits: self.limits,
+            audit: self.audit.clone(),
+            warnings: self.warnings.clone(),
+            profiling: self.profiling,
+            module_path: self.module_path.clone(),
         }
     }
 
@@ -32,14 +143,51 @@ impl<'a> AssemblyContext<'a> {
 
     /// Returns true if a procedure with the specified label exists in this context.
     pub fn contains_proc(&self, label: &str) -> bool {
-        self.local_procs.contains_key(label) || self.imported_procs.contains_key(label)
+        self.local_procs.contains_key(label)
+            || self.imported_procs.contains_key(label)
+            || self.get_generic_proc(label).is_some()
+    }
+
+    /// Returns a generic procedure with the specified label from this context.
+    pub fn get_generic_proc(&self, label: &str) -> Option<&GenericProcedure> {
+        self.generic_procs.iter().find(|p| p.label() == label)
+    }
+
+    /// Returns a non-generic procedure with the specified label from this context.
+    pub fn get_proc(&self, label: &str) -> Option<&Procedure> {
+        self.imported_procs
+            .get(label)
+            .or_else(|| self.local_procs.get(label))
+            .map(|proc| proc.as_ref())
+    }
+
+    /// Returns the code of a procedure with the specified MAST root which can be invoked via
+    /// `call` from this context, i.e., the code of a local or an imported procedure, or of a
+    /// procedure called from one of them.
+    pub fn get_callee_code(&self, fn_hash: Digest) -> Option<&CodeBlock> {
+        self.local_procs
+            .values()
+            .chain(self.imported_procs.values())
+            .find_map(|proc| {
+                if proc.code_root().hash() == fn_hash {
+                    Some(proc.code_root())
+                } else {
+                    proc.callees().get(fn_hash)
+                }
+            })
+    }
+
+    /// Returns a procedure with the specified label exported from the kernel against which the
+    /// code is compiled.
+    pub fn get_kernel_proc(&self, label: &str) -> Option<&Procedure> {
+        self.kernel_procs.get(label).map(|proc| proc.as_ref())
     }
 
     /// Returns a code root of a procedure for the specified label from this context.
     pub fn get_proc_code(&self, label: &str) -> Option<&CodeBlock> {
         // `expect()`'s are OK here because we first check if a given map contains the key
         if self.imported_procs.contains_key(label) {
-            let proc = *self
+            let proc = self
                 .imported_procs
                 .get(label)
                 .expect("no procedure after contains");
@@ -55,6 +203,126 @@ impl<'a> AssemblyContext<'a> {
         }
     }
 
+    /// Returns the value of a constant with the specified name, or None if the constant has not
+    /// been declared in this context.
+    pub fn get_constant(&self, name: &str) -> Option<u64> {
+        self.constants.get(name).copied()
+    }
+
+    /// Returns the memory address of the first element of a data segment with the specified name,
+    /// or None if the segment has not been declared in this context.
+    pub fn get_data_segment(&self, name: &str) -> Option<u64> {
+        self.data_segments.get(name).copied()
+    }
+
+    /// Returns the maximum number of operations a single `repeat` block can unroll into, or None
+    /// if unrolling is unbounded.
+    pub fn unroll_budget(&self) -> Option<usize> {
+        self.unroll_budget
+    }
+
+    /// Returns the number of operations above which a `repeat` block is executed in a loop rather
+    /// than unrolled entirely.
+    pub fn split_threshold(&self) -> usize {
+        self.split_threshold
+    }
+
+    /// Checks whether the specified invocation chain starting at the specified token fits into
+    /// the locals budget. If it does not, the chain is reported as a warning when warnings are
+    /// allowed by the budget policy.
+    ///
+    /// # Errors
+    /// Returns an error if the chain exceeds the budget and the budget policy does not allow
+    /// warnings.
+    pub fn check_locals_budget(
+        &self,
+        token: &Token,
+        path: &LocalsPath,
+    ) -> Result<(), AssemblyError> {
+        match self.locals_budget {
+            Some((budget, policy)) if path.num_locals() > budget => {
+                let error = AssemblyError::locals_budget_exceeded(token, path, budget);
+                match (policy, &self.warnings) {
+                    (BudgetPolicy::Error, _) => return Err(error),
+                    (BudgetPolicy::Warn, Some(warnings)) => warnings.record(error),
+                    (BudgetPolicy::Warn, None) => (),
+                }
+            }
+            _ => (),
+        }
+        Ok(())
+    }
+
+    /// Returns the settings which control how span blocks are optimized.
+    pub fn span_options(&self) -> SpanOptions {
+        self.span_options
+    }
+
+    /// Makes sure that a procedure with the specified label and number of locals declared by the
+    /// specified token can be added to this context without exceeding the limits of the context.
+    pub fn check_proc_limits(
+        &self,
+        token: &Token,
+        label: &str,
+        num_locals: u32,
+    ) -> Result<(), AssemblyError> {
+        self.check_name_len(token, label)?;
+        if num_locals > self.limits.max_proc_locals() {
+            return Err(AssemblyError::too_many_proc_locals(
+                token,
+                label,
+                num_locals,
+                self.limits.max_proc_locals(),
+            ));
+        }
+        if self.local_procs.len() + self.generic_procs.len() >= self.limits.max_procedures() {
+            return Err(AssemblyError::too_many_procedures(
+                token,
+                self.limits.max_procedures(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Makes sure that the specified name declared by the specified token does not exceed the
+    /// maximum name length of this context.
+    pub fn check_name_len(&self, token: &Token, name: &str) -> Result<(), AssemblyError> {
+        if name.len() > self.limits.max_name_len() {
+            return Err(AssemblyError::name_too_long(
+                token,
+                name,
+                self.limits.max_name_len(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Returns the recorder to which decisions of the assembler are reported, or None if the
+    /// compilation is not audited.
+    pub fn audit_recorder(&self) -> Option<&AuditRecorder> {
+        self.audit.as_ref()
+    }
+
+    /// Returns true if the roots of procedures invoked via `exec` must not be merged with the
+    /// surrounding code.
+    pub fn is_profiling(&self) -> bool {
+        self.profiling
+    }
+
+    /// Returns the extension which expands custom instructions of the specified namespace, if
+    /// any.
+    pub fn extension(&self, namespace: &str) -> Option<&dyn InstructionExtension> {
+        self.extensions
+            .get(namespace)
+            .map(|extension| extension.as_ref())
+    }
+
+    /// Returns the path of the library module which is being compiled in this context, or an
+    /// empty string if the program (or a kernel) is being compiled.
+    pub fn module_path(&self) -> &str {
+        &self.module_path
+    }
+
     // STATE MUTATORS
     // --------------------------------------------------------------------------------------------
 
@@ -67,7 +335,7 @@ impl<'a> AssemblyContext<'a> {
     pub fn add_local_proc(&mut self, proc: Procedure) {
         let label = proc.label();
         assert!(!self.contains_proc(label), "duplicate procedure: {}", label);
-        self.local_procs.insert(label.to_string(), proc);
+        self.local_procs.insert(label.to_string(), Arc::new(proc));
     }
 
     /// Adds an imported procedure to this context.
@@ -76,7 +344,7 @@ impl<'a> AssemblyContext<'a> {
     ///
     /// # Panics
     /// Panics if a procedure with the specified label already exists in this context.
-    pub fn add_imported_proc(&mut self, prefix: &str, proc: &'a Procedure) {
+    pub fn add_imported_proc(&mut self, prefix: &str, proc: Arc<Procedure>) {
         let label = format!("{}{}{}", prefix, MODULE_PATH_DELIM, proc.label());
         assert!(
             !self.contains_proc(&label),
@@ -86,6 +354,77 @@ impl<'a> AssemblyContext<'a> {
         self.imported_procs.insert(label, proc);
     }
 
+    /// Adds a generic procedure to this context.
+    ///
+    /// # Panics
+    /// Panics if a procedure with the specified label already exists in this context.
+    pub fn add_generic_proc(&mut self, proc: GenericProcedure) {
+        assert!(
+            !self.contains_proc(proc.label()),
+            "duplicate procedure: {}",
+            proc.label()
+        );
+        self.generic_procs.push(proc);
+    }
+
+    /// Sets the procedures which can be invoked from this context via system calls.
+    pub fn set_kernel_procs(&mut self, kernel_procs: Arc<ProcMap>) {
+        self.kernel_procs = kernel_procs;
+    }
+
+    /// Sets the extensions which expand custom instructions parsed in this context.
+    pub fn set_extensions(&mut self, extensions: Arc<ExtensionMap>) {
+        self.extensions = extensions;
+    }
+
+    /// Sets the recorder to which decisions of the assembler are reported.
+    pub fn set_audit_recorder(&mut self, audit: Option<AuditRecorder>) {
+        self.audit = audit;
+    }
+
+    /// Sets the recorder to which warnings are reported.
+    pub fn set_warning_recorder(&mut self, warnings: Option<WarningRecorder>) {
+        self.warnings = warnings;
+    }
+
+    /// Specifies whether the roots of procedures invoked via `exec` are kept as separate blocks.
+    pub fn set_profiling(&mut self, profiling: bool) {
+        self.profiling = profiling;
+    }
+
+    /// Sets the path of the library module which is being compiled in this context.
+    pub fn set_module_path(&mut self, path: &str) {
+        self.module_path = path.to_string();
+    }
+
+    /// Adds a constant to this context.
+    ///
+    /// Returns false if a constant with the same name already exists in this context.
+    pub fn add_constant(&mut self, name: String, value: u64) -> bool {
+        if self.constants.contains_key(&name) {
+            return false;
+        }
+        self.constants.insert(name, value);
+        true
+    }
+
+    /// Adds a data segment with the specified number of elements to this context, and returns the
+    /// memory address of the first element of the segment.
+    ///
+    /// Data segments are placed in memory one after another starting at [DATA_SEGMENT_OFFSET],
+    /// with a single element per memory address.
+    ///
+    /// Returns None if a data segment with the same name already exists in this context.
+    pub fn add_data_segment(&mut self, name: String, num_elements: usize) -> Option<u64> {
+        if self.data_segments.contains_key(&name) {
+            return None;
+        }
+        let addr = DATA_SEGMENT_OFFSET + self.data_size;
+        self.data_segments.insert(name, addr);
+        self.data_size += num_elements as u64;
+        Some(addr)
+    }
+
     /// Extracts local procedures from this context.
     pub fn into_local_procs(self) -> ProcMap {
         self.local_procs
@@ -0,0 +1,71 @@
+use super::{BTreeMap, Digest, Program, String};
+
+// PROGRAM DIGESTS
+// ================================================================================================
+
+/// Digests which identify a compiled program and the code it can invoke.
+///
+/// The digests are produced by [Assembler::program_digests()](crate::Assembler::program_digests)
+/// and consist of:
+/// - the MAST root of the program, which is the program hash against which proofs of execution
+///   of the program are verified;
+/// - the MAST roots of the local procedures of the program (generic procedures are not listed as
+///   they do not have MAST roots until instantiated); procedures invoked via `call` are executed
+///   by their MAST roots, and thus these roots identify the entry points of the program's
+///   contexts;
+/// - the hash of the kernel against which the program was compiled (see
+///   [Kernel::hash()](vm_core::Kernel::hash)), together with the MAST roots of the procedures
+///   exported from the kernel (i.e., the procedures which can be invoked via `syscall`).
+///
+/// All digests depend only on the source of the program and on the settings of the assembler,
+/// and thus can be published (e.g., on-chain) as commitments to the program.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProgramDigests {
+    program: Digest,
+    procedures: BTreeMap<String, Digest>,
+    kernel: Digest,
+    kernel_procedures: BTreeMap<String, Digest>,
+}
+
+impl ProgramDigests {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Returns digests of the specified program with the specified local procedures and kernel
+    /// procedures.
+    pub(crate) fn new(
+        program: &Program,
+        procedures: BTreeMap<String, Digest>,
+        kernel_procedures: BTreeMap<String, Digest>,
+    ) -> Self {
+        Self {
+            program: program.hash(),
+            procedures,
+            kernel: program.kernel().hash(),
+            kernel_procedures,
+        }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the MAST root of the program.
+    pub fn program(&self) -> Digest {
+        self.program
+    }
+
+    /// Returns the MAST roots of the local procedures of the program, keyed by procedure label.
+    pub fn procedures(&self) -> &BTreeMap<String, Digest> {
+        &self.procedures
+    }
+
+    /// Returns the hash of the kernel against which the program was compiled.
+    pub fn kernel(&self) -> Digest {
+        self.kernel
+    }
+
+    /// Returns the MAST roots of the procedures exported from the kernel against which the
+    /// program was compiled, keyed by procedure label.
+    pub fn kernel_procedures(&self) -> &BTreeMap<String, Digest> {
+        &self.kernel_procedures
+    }
+}
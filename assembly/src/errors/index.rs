@@ -0,0 +1,1119 @@
+use core::fmt;
+use vm_core::errors::ErrorCode;
+
+// ERROR CODES
+// ================================================================================================
+
+/// Error codes assigned to each kind of assembly error.
+pub(super) mod codes {
+    use super::ErrorCode;
+
+    pub const EMPTY_SOURCE: ErrorCode = ErrorCode::new(1);
+    pub const UNEXPECTED_EOF: ErrorCode = ErrorCode::new(2);
+    pub const UNEXPECTED_TOKEN: ErrorCode = ErrorCode::new(3);
+    pub const EMPTY_BLOCK: ErrorCode = ErrorCode::new(4);
+    pub const INVALID_OP: ErrorCode = ErrorCode::new(5);
+    pub const MISSING_PARAM: ErrorCode = ErrorCode::new(6);
+    pub const EXTRA_PARAM: ErrorCode = ErrorCode::new(7);
+    pub const INVALID_PARAM: ErrorCode = ErrorCode::new(8);
+    pub const DANGLING_ELSE: ErrorCode = ErrorCode::new(9);
+    pub const UNMATCHED_IF: ErrorCode = ErrorCode::new(10);
+    pub const UNMATCHED_WHILE: ErrorCode = ErrorCode::new(11);
+    pub const UNMATCHED_REPEAT: ErrorCode = ErrorCode::new(12);
+    pub const UNMATCHED_ELSE: ErrorCode = ErrorCode::new(13);
+    pub const UNMATCHED_COMMENT: ErrorCode = ErrorCode::new(14);
+    pub const UNMATCHED_BEGIN: ErrorCode = ErrorCode::new(15);
+    pub const DANGLING_OPS_AFTER_PROGRAM: ErrorCode = ErrorCode::new(16);
+    pub const DUPLICATE_PROC_LABEL: ErrorCode = ErrorCode::new(17);
+    pub const INVALID_PROC_LABEL: ErrorCode = ErrorCode::new(18);
+    pub const INVALID_PROC_LOCALS: ErrorCode = ErrorCode::new(19);
+    pub const UNMATCHED_PROC: ErrorCode = ErrorCode::new(20);
+    pub const UNDEFINED_PROC: ErrorCode = ErrorCode::new(21);
+    pub const PROC_EXPORT_NOT_ALLOWED: ErrorCode = ErrorCode::new(22);
+    pub const MISSING_IMPORT_SOURCE: ErrorCode = ErrorCode::new(23);
+    pub const DANGLING_OPS_AFTER_MODULE: ErrorCode = ErrorCode::new(24);
+    pub const CIRCULAR_MODULE_DEPENDENCY: ErrorCode = ErrorCode::new(25);
+    pub const INVALID_MODULE_PATH: ErrorCode = ErrorCode::new(26);
+    pub const INVALID_CONST_DECLARATION: ErrorCode = ErrorCode::new(27);
+    pub const INVALID_CONST_NAME: ErrorCode = ErrorCode::new(28);
+    pub const DUPLICATE_CONST: ErrorCode = ErrorCode::new(29);
+    pub const INVALID_CONST_EXPR: ErrorCode = ErrorCode::new(30);
+    pub const UNDEFINED_CONST: ErrorCode = ErrorCode::new(31);
+    pub const UNROLL_BUDGET_EXCEEDED: ErrorCode = ErrorCode::new(32);
+    pub const INVALID_GENERIC_PARAMS: ErrorCode = ErrorCode::new(33);
+    pub const GENERIC_PROC_EXPORT: ErrorCode = ErrorCode::new(34);
+    pub const GENERIC_ARGS_MISMATCH: ErrorCode = ErrorCode::new(35);
+    pub const LOCALS_BUDGET_EXCEEDED: ErrorCode = ErrorCode::new(36);
+    pub const UNDEFINED_KERNEL_PROC: ErrorCode = ErrorCode::new(37);
+    pub const MISPLACED_ANNOTATION: ErrorCode = ErrorCode::new(38);
+    pub const INVALID_DATA_DECLARATION: ErrorCode = ErrorCode::new(39);
+    pub const DUPLICATE_DATA: ErrorCode = ErrorCode::new(40);
+    pub const UNDEFINED_DATA: ErrorCode = ErrorCode::new(41);
+    pub const TOO_MANY_PROCEDURES: ErrorCode = ErrorCode::new(42);
+    pub const TOO_MANY_PROC_LOCALS: ErrorCode = ErrorCode::new(43);
+    pub const NAME_TOO_LONG: ErrorCode = ErrorCode::new(44);
+    pub const BOUNDED_LOOP_LOCALS: ErrorCode = ErrorCode::new(45);
+    pub const DUPLICATE_LIBRARY: ErrorCode = ErrorCode::new(46);
+    pub const INVALID_CUSTOM_OP: ErrorCode = ErrorCode::new(47);
+    pub const INVALID_EXTENSION: ErrorCode = ErrorCode::new(48);
+}
+
+// ERROR EXPLANATION
+// ================================================================================================
+
+/// A long-form, example-driven explanation of a kind of assembly error.
+#[derive(Debug, Eq, PartialEq)]
+pub struct ErrorExplanation {
+    code: ErrorCode,
+    title: &'static str,
+    explanation: &'static str,
+}
+
+impl ErrorExplanation {
+    /// Returns the code of the error described by this explanation.
+    pub fn code(&self) -> ErrorCode {
+        self.code
+    }
+
+    /// Returns a one-line summary of the error.
+    pub fn title(&self) -> &'static str {
+        self.title
+    }
+
+    /// Returns the full text of the explanation.
+    pub fn explanation(&self) -> &'static str {
+        self.explanation
+    }
+}
+
+impl fmt::Display for ErrorExplanation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}: {}", self.code, self.title)?;
+        writeln!(f)?;
+        write!(f, "{}", self.explanation)
+    }
+}
+
+/// Returns the long-form explanation for the specified error code, or None if the code has not
+/// been assigned to any kind of assembly error.
+pub fn explain_error(code: ErrorCode) -> Option<&'static ErrorExplanation> {
+    ERROR_INDEX
+        .binary_search_by_key(&code, |entry| entry.code)
+        .ok()
+        .map(|idx| &ERROR_INDEX[idx])
+}
+
+// ERROR INDEX
+// ================================================================================================
+
+/// Long-form explanations of all assembly errors, sorted by error code.
+pub const ERROR_INDEX: [ErrorExplanation; 48] = [
+    ErrorExplanation {
+        code: codes::EMPTY_SOURCE,
+        title: "source code cannot be an empty string",
+        explanation: "\
+The assembler was given a source which contains no instructions. This happens when the source is
+an empty string, or when it consists solely of whitespace and comments.
+
+Erroneous code example:
+
+    # this program does nothing
+
+A program must contain at least a `begin ... end` block with one instruction in it:
+
+    begin
+        push.1
+    end",
+    },
+    ErrorExplanation {
+        code: codes::UNEXPECTED_EOF,
+        title: "unexpected end of source",
+        explanation: "\
+The source ended while the assembler still expected more tokens. Most often this happens when the
+program body is missing after all procedures have been declared.
+
+Erroneous code example:
+
+    proc.foo
+        push.1
+    end
+
+Every program must end with a `begin ... end` block:
+
+    proc.foo
+        push.1
+    end
+
+    begin
+        exec.foo
+    end",
+    },
+    ErrorExplanation {
+        code: codes::UNEXPECTED_TOKEN,
+        title: "unexpected token",
+        explanation: "\
+The assembler encountered a token which is not valid at the current position. The diagnostic
+reports both the token which was expected and the token which was found.
+
+Erroneous code example:
+
+    push.1
+    begin
+        add
+    end
+
+Instructions outside of procedures must be placed inside the program body:
+
+    begin
+        push.1
+        add
+    end",
+    },
+    ErrorExplanation {
+        code: codes::EMPTY_BLOCK,
+        title: "a code block must contain at least one instruction",
+        explanation: "\
+A control block (the program body, a procedure body, or the body of `if`, `else`, `while`, or
+`repeat`) contains no instructions.
+
+Erroneous code example:
+
+    begin
+        if.true
+        else
+            push.1
+        end
+    end
+
+Every block must contain at least one instruction; use `noop`-like instructions such as
+`push.0 drop` if a branch should do nothing, or omit the `else` clause entirely:
+
+    begin
+        if.true
+            push.1
+        end
+    end",
+    },
+    ErrorExplanation {
+        code: codes::INVALID_OP,
+        title: "invalid instruction",
+        explanation: "\
+The instruction is not recognized by the assembler, or is recognized but cannot be used in the
+current context (for example, accessing procedure locals outside of a procedure).
+
+Erroneous code example:
+
+    begin
+        push.1 push.2 plus
+    end
+
+Check the spelling of the instruction against the list of instructions in the Miden assembly
+documentation:
+
+    begin
+        push.1 push.2 add
+    end",
+    },
+    ErrorExplanation {
+        code: codes::MISSING_PARAM,
+        title: "missing required parameter",
+        explanation: "\
+The instruction requires an immediate parameter which was not provided. Parameters are appended
+to the instruction name separated by a dot.
+
+Erroneous code example:
+
+    begin
+        movup
+    end
+
+Provide the parameter required by the instruction:
+
+    begin
+        movup.2
+    end",
+    },
+    ErrorExplanation {
+        code: codes::EXTRA_PARAM,
+        title: "too many parameters provided",
+        explanation: "\
+The instruction was given more immediate parameters than it accepts.
+
+Erroneous code example:
+
+    begin
+        add.1.2
+    end
+
+Remove the extra parameters, or split the computation into several instructions:
+
+    begin
+        add.1 add.2
+    end",
+    },
+    ErrorExplanation {
+        code: codes::INVALID_PARAM,
+        title: "invalid parameter",
+        explanation: "\
+An immediate parameter of the instruction could not be parsed, or its value falls outside of the
+range accepted by the instruction. For example, field element values must be smaller than the
+field modulus, and stack positions must refer to one of the top 16 stack items.
+
+Erroneous code example:
+
+    begin
+        dup.16
+    end
+
+Use a value within the range accepted by the instruction:
+
+    begin
+        dup.15
+    end",
+    },
+    ErrorExplanation {
+        code: codes::DANGLING_ELSE,
+        title: "else without matching if",
+        explanation: "\
+An `else` token was found outside of an `if.true` block, or an `if.true` block contains more than
+one `else` clause.
+
+Erroneous code example:
+
+    begin
+        push.1
+        else
+            push.2
+        end
+    end
+
+An `else` clause must follow the body of an `if.true` block:
+
+    begin
+        push.1
+        if.true
+            push.3
+        else
+            push.2
+        end
+    end",
+    },
+    ErrorExplanation {
+        code: codes::UNMATCHED_IF,
+        title: "if without matching else/end",
+        explanation: "\
+An `if.true` block was opened but was never closed with `end`.
+
+Erroneous code example:
+
+    begin
+        if.true
+            push.1
+
+Close the block with an `end` token:
+
+    begin
+        if.true
+            push.1
+        end
+    end",
+    },
+    ErrorExplanation {
+        code: codes::UNMATCHED_WHILE,
+        title: "while without matching end",
+        explanation: "\
+A `while.true` block was opened but was never closed with `end`.
+
+Erroneous code example:
+
+    begin
+        while.true
+            push.0
+
+Close the block with an `end` token:
+
+    begin
+        while.true
+            push.0
+        end
+    end",
+    },
+    ErrorExplanation {
+        code: codes::UNMATCHED_REPEAT,
+        title: "repeat without matching end",
+        explanation: "\
+A `repeat.n` block was opened but was never closed with `end`.
+
+Erroneous code example:
+
+    begin
+        repeat.4
+            add
+
+Close the block with an `end` token:
+
+    begin
+        repeat.4
+            add
+        end
+    end",
+    },
+    ErrorExplanation {
+        code: codes::UNMATCHED_ELSE,
+        title: "else without matching end",
+        explanation: "\
+The `else` clause of an `if.true` block was opened but was never closed with `end`.
+
+Erroneous code example:
+
+    begin
+        if.true
+            push.1
+        else
+            push.2
+
+Close the `else` clause with an `end` token:
+
+    begin
+        if.true
+            push.1
+        else
+            push.2
+        end
+    end",
+    },
+    ErrorExplanation {
+        code: codes::UNMATCHED_COMMENT,
+        title: "comment delimiter without matching delimiter",
+        explanation: "\
+A comment was opened but was never terminated. Comments start with `#` and extend until the end
+of the line.
+
+Erroneous code example:
+
+    begin
+        push.1 # a comment which never ends
+
+Make sure every comment is terminated by a line break.",
+    },
+    ErrorExplanation {
+        code: codes::UNMATCHED_BEGIN,
+        title: "begin without matching end",
+        explanation: "\
+The program body was opened with `begin` but was never closed with `end`, or it was closed by an
+unexpected token.
+
+Erroneous code example:
+
+    begin
+        push.1
+
+Close the program body with an `end` token:
+
+    begin
+        push.1
+    end",
+    },
+    ErrorExplanation {
+        code: codes::DANGLING_OPS_AFTER_PROGRAM,
+        title: "dangling instructions after program end",
+        explanation: "\
+Instructions were found after the `end` token which closes the program body. The program body
+must be the last item in the source.
+
+Erroneous code example:
+
+    begin
+        push.1
+    end
+    push.2
+
+Move the instructions into the program body:
+
+    begin
+        push.1
+        push.2
+    end",
+    },
+    ErrorExplanation {
+        code: codes::DUPLICATE_PROC_LABEL,
+        title: "duplicate procedure label",
+        explanation: "\
+Two procedures with the same label were declared in the same module, or a local procedure has the
+same label as an imported procedure.
+
+Erroneous code example:
+
+    proc.foo
+        push.1
+    end
+
+    proc.foo
+        push.2
+    end
+
+Give each procedure a unique label:
+
+    proc.foo
+        push.1
+    end
+
+    proc.bar
+        push.2
+    end",
+    },
+    ErrorExplanation {
+        code: codes::INVALID_PROC_LABEL,
+        title: "invalid procedure label",
+        explanation: "\
+A procedure label does not comply with the naming rules. A label must start with an ASCII letter
+and may contain only ASCII letters, digits, and underscores. Labels of invoked procedures may
+additionally contain `::` to refer to imported procedures.
+
+Erroneous code example:
+
+    proc.1foo
+        push.1
+    end
+
+Start the label with a letter:
+
+    proc.foo1
+        push.1
+    end",
+    },
+    ErrorExplanation {
+        code: codes::INVALID_PROC_LOCALS,
+        title: "invalid procedure locals",
+        explanation: "\
+The number of locals declared for a procedure is not a valid unsigned 32-bit integer.
+
+Erroneous code example:
+
+    proc.foo.many
+        push.1
+    end
+
+Specify the number of locals as a decimal integer:
+
+    proc.foo.2
+        push.1
+    end",
+    },
+    ErrorExplanation {
+        code: codes::UNMATCHED_PROC,
+        title: "proc without matching end",
+        explanation: "\
+A procedure declaration was opened with `proc` or `export` but was never closed with `end`.
+
+Erroneous code example:
+
+    proc.foo
+        push.1
+
+    begin
+        exec.foo
+    end
+
+Close the procedure body with an `end` token:
+
+    proc.foo
+        push.1
+    end
+
+    begin
+        exec.foo
+    end",
+    },
+    ErrorExplanation {
+        code: codes::UNDEFINED_PROC,
+        title: "undefined procedure",
+        explanation: "\
+A procedure invoked via `exec` is not defined in the current module and was not imported. Local
+procedures must be declared before they are invoked, and imported procedures must be referred to
+using the last component of their module path.
+
+Erroneous code example:
+
+    use.std::math::u64
+
+    begin
+        exec.u64::plus
+    end
+
+Make sure the procedure exists in the referenced module and is exported from it:
+
+    use.std::math::u64
+
+    begin
+        exec.u64::checked_add
+    end",
+    },
+    ErrorExplanation {
+        code: codes::PROC_EXPORT_NOT_ALLOWED,
+        title: "exported procedures not allowed in this context",
+        explanation: "\
+A procedure was declared with `export` in a program. Only library modules can export procedures;
+procedures declared in a program are always local to that program.
+
+Erroneous code example:
+
+    export.foo
+        push.1
+    end
+
+    begin
+        exec.foo
+    end
+
+Declare the procedure with `proc` instead:
+
+    proc.foo
+        push.1
+    end
+
+    begin
+        exec.foo
+    end",
+    },
+    ErrorExplanation {
+        code: codes::MISSING_IMPORT_SOURCE,
+        title: "module source not found",
+        explanation: "\
+A module referenced by a `use` instruction could not be found in any of the libraries available
+to the assembler.
+
+Erroneous code example:
+
+    use.std::math::u1024
+
+    begin
+        push.1
+    end
+
+Check the module path against the list of modules in the library:
+
+    use.std::math::u256
+
+    begin
+        push.1
+    end",
+    },
+    ErrorExplanation {
+        code: codes::DANGLING_OPS_AFTER_MODULE,
+        title: "dangling instructions after module end",
+        explanation: "\
+A library module contains instructions outside of procedure declarations. Modules may contain only
+`use` instructions followed by procedure declarations.
+
+Erroneous code example:
+
+    export.foo
+        push.1
+    end
+
+    push.2
+
+Move the instructions into a procedure body.",
+    },
+    ErrorExplanation {
+        code: codes::CIRCULAR_MODULE_DEPENDENCY,
+        title: "circular module dependency",
+        explanation: "\
+A module imports itself either directly or through a chain of other modules. The diagnostic lists
+the full chain of module paths which forms the cycle.
+
+Erroneous code example:
+
+    # in module `std::a`
+    use.std::b
+
+    # in module `std::b`
+    use.std::a
+
+Break the cycle by moving the shared procedures into a separate module which both modules can
+import.",
+    },
+    ErrorExplanation {
+        code: codes::INVALID_MODULE_PATH,
+        title: "invalid module import path",
+        explanation: "\
+A module path in a `use` instruction does not comply with the naming rules. A path must start
+with an ASCII letter and may contain only ASCII letters, digits, underscores, and `::`
+separators.
+
+Erroneous code example:
+
+    use.std/math/u64
+
+Separate path components with `::`:
+
+    use.std::math::u64",
+    },
+    ErrorExplanation {
+        code: codes::INVALID_CONST_DECLARATION,
+        title: "malformed constant declaration",
+        explanation: "\
+A constant declaration does not have the form `const.NAME=EXPR`. The name and the expression must
+be separated by `=` without any whitespace, and the expression must not be empty.
+
+Erroneous code example:
+
+    const.SIZE
+    const.COUNT = 4
+
+Write the declaration as a single token:
+
+    const.SIZE=8
+    const.COUNT=4",
+    },
+    ErrorExplanation {
+        code: codes::INVALID_CONST_NAME,
+        title: "invalid constant name",
+        explanation: "\
+The name in a constant declaration does not comply with the naming rules. A constant name must
+start with an uppercase ASCII letter and may contain only uppercase ASCII letters, digits, and
+underscores.
+
+Erroneous code example:
+
+    const.word_size=4
+
+Use an uppercase name:
+
+    const.WORD_SIZE=4",
+    },
+    ErrorExplanation {
+        code: codes::DUPLICATE_CONST,
+        title: "duplicate constant name",
+        explanation: "\
+Two constants with the same name are declared in the same program or module. Constant names must
+be unique within the program or module which declares them.
+
+Erroneous code example:
+
+    const.SIZE=4
+    const.SIZE=8
+
+Rename one of the constants.",
+    },
+    ErrorExplanation {
+        code: codes::INVALID_CONST_EXPR,
+        title: "invalid constant expression",
+        explanation: "\
+A constant expression could not be evaluated. Constant expressions consist of integer literals,
+names of previously declared constants, the operators `+`, `-`, `*`, `/`, and parentheses, and
+must not contain whitespace. All intermediate values must fit into 64 bits, division by zero is
+not allowed, and the value of a constant must be a valid field element.
+
+Erroneous code example:
+
+    const.SIZE=(4*8
+    const.HALF=SIZE/0
+
+Fix the expression:
+
+    const.SIZE=(4*8)
+    const.HALF=SIZE/2",
+    },
+    ErrorExplanation {
+        code: codes::UNDEFINED_CONST,
+        title: "undefined constant",
+        explanation: "\
+A constant expression refers to a constant which has not been declared. Constants must be
+declared before they are used, and constants declared in a module are not visible to programs
+or modules which import it.
+
+Erroneous code example:
+
+    begin
+        repeat.SIZE
+            push.1
+        end
+    end
+
+Declare the constant at the top of the program, after the `use` instructions:
+
+    const.SIZE=4
+
+    begin
+        repeat.SIZE
+            push.1
+        end
+    end",
+    },
+    ErrorExplanation {
+        code: codes::UNROLL_BUDGET_EXCEEDED,
+        title: "repeat block exceeds the unroll budget",
+        explanation: "\
+The assembler was configured with an unroll budget via `Assembler::with_unroll_budget()`, and a
+`repeat` block unrolls into more operations than the budget allows. The size of a `repeat` block
+is the number of operations in its body multiplied by the number of unrolled iterations; nested
+`repeat` blocks and procedures invoked via `exec` are counted at their unrolled size. Large
+`repeat` blocks are usually executed in chunks (see `Assembler::with_split_threshold()`), but a
+block is always unrolled entirely if its body accesses procedure locals.
+
+Erroneous code example (with the budget set to 100):
+
+    proc.foo.1
+        repeat.100
+            push.local.0 drop
+        end
+    end
+
+Reduce the iteration count, or replace the loop with a `while.true` loop whose size does not
+depend on the number of iterations.",
+    },
+    ErrorExplanation {
+        code: codes::INVALID_GENERIC_PARAMS,
+        title: "malformed generic parameter list",
+        explanation: "\
+A generic procedure declaration or invocation has a malformed parameter list. Generic parameters
+are listed in angle brackets right after the procedure label and are separated by commas, without
+any whitespace. In a declaration, each parameter must be a valid constant name; in an invocation,
+each argument must be a constant expression.
+
+Erroneous code example:
+
+    proc.sum_n<N
+        repeat.N
+            add
+        end
+    end
+
+Close the parameter list:
+
+    proc.sum_n<N>
+        repeat.N
+            add
+        end
+    end",
+    },
+    ErrorExplanation {
+        code: codes::GENERIC_PROC_EXPORT,
+        title: "generic procedures cannot be exported",
+        explanation: "\
+A generic procedure was declared with `export`. Generic procedures are instantiated from their
+source at every invocation, and thus they can be invoked only from the program or module which
+declares them.
+
+Erroneous code example:
+
+    export.sum_n<N>
+        repeat.N
+            add
+        end
+    end
+
+Declare the generic procedure with `proc`, and export non-generic procedures which invoke it:
+
+    proc.sum_n<N>
+        repeat.N
+            add
+        end
+    end
+
+    export.sum_4
+        exec.sum_n<3>
+    end",
+    },
+    ErrorExplanation {
+        code: codes::GENERIC_ARGS_MISMATCH,
+        title: "wrong number of generic arguments",
+        explanation: "\
+A procedure was invoked with a number of generic arguments which differs from the number of
+generic parameters in its declaration. Non-generic procedures must be invoked without generic
+arguments.
+
+Erroneous code example:
+
+    proc.sum_n<N>
+        repeat.N
+            add
+        end
+    end
+
+    begin
+        exec.sum_n
+    end
+
+Provide a value for every generic parameter:
+
+    begin
+        exec.sum_n<2>
+    end",
+    },
+    ErrorExplanation {
+        code: codes::LOCALS_BUDGET_EXCEEDED,
+        title: "procedure locals exceed the locals budget",
+        explanation: "\
+The assembler was configured with a locals budget via `Assembler::with_locals_budget()`, and a
+chain of nested procedure invocations allocates more locals than the budget allows. Locals of a
+procedure invoked via `exec` are allocated on top of the locals of all procedures up the chain of
+invocations, and thus the budget applies to the sum of locals along the chain. The error message
+lists every procedure in the offending chain together with the number of its locals. When the
+budget is configured with `BudgetPolicy::Warn`, the same diagnostic is reported as a warning and
+the code compiles.
+
+Erroneous code example (with the budget set to 8):
+
+    proc.inner.6
+        storew.local.0
+    end
+
+    proc.outer.4
+        exec.inner
+    end
+
+    begin
+        exec.outer
+    end
+
+Reduce the number of locals declared by procedures in the chain, or restructure the code so that
+procedures with many locals are not invoked from each other:
+
+    proc.inner.4
+        storew.local.0
+    end",
+    },
+    ErrorExplanation {
+        code: codes::UNDEFINED_KERNEL_PROC,
+        title: "undefined kernel procedure",
+        explanation: "\
+A procedure invoked via `syscall` is not exported from the kernel against which the program is
+compiled. System calls can invoke only procedures exported from the kernel module supplied via
+`Assembler::with_kernel()`; procedures of the program itself and of imported modules must be
+invoked via `exec`.
+
+Erroneous code example (with a kernel which exports only `get_balance`):
+
+    begin
+        syscall.set_balance
+    end
+
+Invoke one of the procedures exported from the kernel:
+
+    begin
+        syscall.get_balance
+    end",
+    },
+    ErrorExplanation {
+        code: codes::MISPLACED_ANNOTATION,
+        title: "misplaced procedure annotation",
+        explanation: "\
+Access control annotations (`@only_kernel` and `@only_context(root)`) are allowed only right
+before declarations of exported procedures. The annotations restrict which code may invoke
+the procedures a module (e.g., a kernel) exports, and thus they cannot be applied to private
+procedures. No annotations (including `@preserve`) can be applied to generic procedures, and
+the `@preserve` annotation cannot be repeated.
+
+Erroneous code example:
+
+    @only_kernel
+    proc.update_balance
+        ...
+    end
+
+Annotate an exported procedure instead:
+
+    @only_kernel
+    export.update_balance
+        ...
+    end",
+    },
+    ErrorExplanation {
+        code: codes::INVALID_DATA_DECLARATION,
+        title: "malformed data segment declaration",
+        explanation: "\
+A data segment declaration does not have the form `data.NAME=[EXPR,EXPR,...]`. The declaration
+must be a single token: the name and the list of elements must be separated by `=`, and the
+elements must be separated by commas without any whitespace. Every element is a constant
+expression, and the list must not be empty.
+
+Erroneous code example:
+
+    data.TABLE = [1, 2, 3]
+
+Write the declaration as a single token:
+
+    data.TABLE=[1,2,3]",
+    },
+    ErrorExplanation {
+        code: codes::DUPLICATE_DATA,
+        title: "duplicate data segment name",
+        explanation: "\
+A program declares two data segments with the same name. Every data segment must have a unique
+name, since the name is used to refer to the segment via `push.addr_of.NAME`.
+
+Erroneous code example:
+
+    data.TABLE=[1,2,3]
+    data.TABLE=[4,5,6]
+
+Rename one of the segments:
+
+    data.TABLE_A=[1,2,3]
+    data.TABLE_B=[4,5,6]",
+    },
+    ErrorExplanation {
+        code: codes::UNDEFINED_DATA,
+        title: "undefined data segment",
+        explanation: "\
+A `push.addr_of.NAME` instruction refers to a data segment which has not been declared. Data
+segments are declared in programs right after constant declarations, and they are visible in
+the procedures and the body of the program declaring them.
+
+Erroneous code example:
+
+    begin
+        push.addr_of.TABLE push.mem
+    end
+
+Declare the segment before using it:
+
+    data.TABLE=[1,2,3]
+
+    begin
+        push.addr_of.TABLE push.mem
+    end",
+    },
+    ErrorExplanation {
+        code: codes::TOO_MANY_PROCEDURES,
+        title: "too many procedures in a module",
+        explanation: "\
+A program or a module declares more procedures than the assembler allows. The limit applies to
+all procedures declared in a single source, including private and generic procedures, and it is
+configured via `Assembler::with_limits()`; the default limit is 65536 procedures.
+
+Erroneous code example (with the limit set to 2):
+
+    proc.foo
+        add
+    end
+
+    proc.bar
+        mul
+    end
+
+    proc.baz
+        sub
+    end
+
+Split the procedures across several modules, or raise the limit via
+`AssemblyLimits::with_max_procedures()`.",
+    },
+    ErrorExplanation {
+        code: codes::TOO_MANY_PROC_LOCALS,
+        title: "too many procedure locals",
+        explanation: "\
+A procedure declares more locals than the assembler allows. The limit is configured via
+`Assembler::with_limits()`; by default, the number of locals is restricted only by the size of
+the local memory (2^32 words). Unlike the locals budget (see E036), the limit applies to each
+procedure separately.
+
+Erroneous code example (with the limit set to 8):
+
+    proc.foo.16
+        storew.local.0
+    end
+
+Reduce the number of locals declared by the procedure, or raise the limit via
+`AssemblyLimits::with_max_proc_locals()`.",
+    },
+    ErrorExplanation {
+        code: codes::NAME_TOO_LONG,
+        title: "name too long",
+        explanation: "\
+A procedure label, a constant name, a data segment name, or a module path is longer than the
+assembler allows. The limit is configured via `Assembler::with_limits()`; the default limit is
+1024 characters.
+
+Erroneous code example (with the limit set to 8):
+
+    proc.compute_balance
+        add
+    end
+
+Use a shorter name, or raise the limit via `AssemblyLimits::with_max_name_len()`.",
+    },
+    ErrorExplanation {
+        code: codes::BOUNDED_LOOP_LOCALS,
+        title: "bounded loop accesses procedure locals",
+        explanation: "\
+The body of a `while.bounded.N` loop accesses locals of the enclosing procedure. The iteration
+counter of a bounded loop is kept in memory right above the locals of the procedure, and thus
+the locals cannot be addressed from within the loop body.
+
+Erroneous code example:
+
+    proc.countdown.1
+        pop.local.0
+        push.1
+        while.bounded.10
+            push.local.0 sub.1 dup pop.local.0
+            neq.0
+        end
+    end
+
+Keep the values used by the loop on the stack instead:
+
+    proc.countdown
+        push.1
+        while.bounded.10
+            sub.1 dup
+            neq.0
+        end
+        drop
+    end",
+    },
+    ErrorExplanation {
+        code: codes::DUPLICATE_LIBRARY,
+        title: "duplicate library namespace",
+        explanation: "\
+A library was added to the assembler via `Assembler::with_library()`, but its root namespace is
+already taken by the standard library (`std`) or by another library added to the assembler.
+Modules are looked up by the root namespace of their paths, and thus every library must have a
+distinct namespace.
+
+Erroneous code example:
+
+    let assembler = Assembler::default()
+        .with_library(MaslLibrary::read_from_dir(\"lib\", \"std\", \"0.1.0\")?)?;
+
+Assemble the library under a different namespace, e.g., `MaslLibrary::read_from_dir(\"lib\",
+\"mylib\", \"0.1.0\")`, and import its modules as `use.mylib::...`.",
+    },
+    ErrorExplanation {
+        code: codes::INVALID_CUSTOM_OP,
+        title: "invalid custom instruction",
+        explanation: "\
+A custom instruction (i.e., an instruction of the form `namespace::name`) was rejected by the
+extension registered for its namespace, or the extension expanded it into instructions which
+are not valid. Expansions must compile into a single span block, and thus cannot contain control
+flow instructions, procedure invocations, or other custom instructions.
+
+Erroneous code example (with an extension which expects a single parameter):
+
+    begin
+        myapp::store_note.1.2
+    end
+
+Check the parameters expected by the extension; if the expansion is invalid, fix the extension.",
+    },
+    ErrorExplanation {
+        code: codes::INVALID_EXTENSION,
+        title: "invalid instruction extension",
+        explanation: "\
+An instruction extension was registered via `Assembler::with_extension()` under a namespace
+which is not a valid identifier (i.e., a letter followed by letters, digits, or underscores), or
+under a namespace for which another extension is already registered.
+
+Erroneous code example:
+
+    let assembler = Assembler::default()
+        .with_extension(\"myapp\", expand_notes)?
+        .with_extension(\"myapp\", expand_accounts)?;
+
+Register every extension under a distinct namespace, or combine the extensions into one.",
+    },
+];
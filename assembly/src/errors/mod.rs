@@ -0,0 +1,679 @@
+use super::{LocalsPath, String, ToString, Token, Vec};
+use core::{cell::RefCell, fmt};
+
+#[cfg(not(feature = "std"))]
+use alloc::rc::Rc;
+
+#[cfg(feature = "std")]
+use std::rc::Rc;
+
+mod index;
+use index::codes;
+pub use index::{explain_error, ErrorExplanation, ERROR_INDEX};
+pub use vm_core::errors::ErrorCode;
+
+// ASSEMBLY ERROR
+// ================================================================================================
+
+#[derive(Clone, Eq, PartialEq)]
+pub struct AssemblyError {
+    code: ErrorCode,
+    message: String,
+    step: usize,
+    op: String,
+}
+
+impl AssemblyError {
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+
+    pub fn empty_source() -> Self {
+        AssemblyError {
+            code: codes::EMPTY_SOURCE,
+            message: "source code cannot be an empty string".to_string(),
+            step: 0,
+            op: "".to_string(),
+        }
+    }
+
+    pub fn unexpected_eof(step: usize) -> Self {
+        AssemblyError {
+            code: codes::UNEXPECTED_EOF,
+            message: "unexpected EOF".to_string(),
+            step,
+            op: "".to_string(),
+        }
+    }
+
+    pub fn unexpected_token(token: &Token, expected: &str) -> Self {
+        AssemblyError {
+            code: codes::UNEXPECTED_TOKEN,
+            message: format!(
+                "unexpected token: expected '{}' but was '{}'",
+                expected, token
+            ),
+            step: token.pos(),
+            op: token.to_string(),
+        }
+    }
+
+    pub fn empty_block(token: &Token) -> Self {
+        AssemblyError {
+            code: codes::EMPTY_BLOCK,
+            message: "a code block must contain at least one instruction".to_string(),
+            step: token.pos(),
+            op: token.to_string(),
+        }
+    }
+
+    pub fn invalid_op(token: &Token) -> Self {
+        AssemblyError {
+            code: codes::INVALID_OP,
+            message: format!("instruction '{}' is invalid", token),
+            step: token.pos(),
+            op: token.to_string(),
+        }
+    }
+
+    pub fn invalid_op_with_reason(token: &Token, reason: &str) -> Self {
+        AssemblyError {
+            code: codes::INVALID_OP,
+            message: format!("instruction '{}' is invalid: {}", token, reason),
+            step: token.pos(),
+            op: token.to_string(),
+        }
+    }
+
+    pub fn missing_param(token: &Token) -> Self {
+        AssemblyError {
+            code: codes::MISSING_PARAM,
+            message: format!(
+                "malformed instruction '{}': missing required parameter",
+                token
+            ),
+            step: token.pos(),
+            op: token.to_string(),
+        }
+    }
+
+    pub fn extra_param(token: &Token) -> Self {
+        AssemblyError {
+            code: codes::EXTRA_PARAM,
+            message: format!(
+                "malformed instruction '{}': too many parameters provided",
+                token
+            ),
+            step: token.pos(),
+            op: token.to_string(),
+        }
+    }
+
+    pub fn invalid_param(token: &Token, part_idx: usize) -> Self {
+        AssemblyError {
+            code: codes::INVALID_PARAM,
+            message: format!(
+                "malformed instruction `{}`: parameter '{}' is invalid",
+                token,
+                token.parts()[part_idx]
+            ),
+            step: token.pos(),
+            op: token.to_string(),
+        }
+    }
+
+    pub fn invalid_param_with_reason(token: &Token, part_idx: usize, reason: &str) -> Self {
+        AssemblyError {
+            code: codes::INVALID_PARAM,
+            message: format!(
+                "malformed instruction '{}', parameter {} is invalid: {}",
+                token,
+                token.parts()[part_idx],
+                reason
+            ),
+            step: token.pos(),
+            op: token.to_string(),
+        }
+    }
+
+    pub fn dangling_else(token: &Token) -> Self {
+        AssemblyError {
+            code: codes::DANGLING_ELSE,
+            message: "else without matching if".to_string(),
+            step: token.pos(),
+            op: token.to_string(),
+        }
+    }
+
+    pub fn unmatched_if(token: &Token) -> Self {
+        AssemblyError {
+            code: codes::UNMATCHED_IF,
+            message: "if without matching else/end".to_string(),
+            step: token.pos(),
+            op: token.to_string(),
+        }
+    }
+
+    pub fn unmatched_while(token: &Token) -> Self {
+        AssemblyError {
+            code: codes::UNMATCHED_WHILE,
+            message: "while without matching end".to_string(),
+            step: token.pos(),
+            op: token.to_string(),
+        }
+    }
+
+    pub fn unmatched_repeat(token: &Token) -> Self {
+        AssemblyError {
+            code: codes::UNMATCHED_REPEAT,
+            message: "repeat without matching end".to_string(),
+            step: token.pos(),
+            op: token.to_string(),
+        }
+    }
+
+    pub fn unmatched_else(token: &Token) -> Self {
+        AssemblyError {
+            code: codes::UNMATCHED_ELSE,
+            message: "else without matching end".to_string(),
+            step: token.pos(),
+            op: token.to_string(),
+        }
+    }
+
+    pub fn unmatched_comment(step: usize) -> Self {
+        AssemblyError {
+            code: codes::UNMATCHED_COMMENT,
+            message: "# comment delimiter without matching #".to_string(),
+            step,
+            op: "".to_string(),
+        }
+    }
+
+    // PROGRAM
+    // --------------------------------------------------------------------------------------------
+
+    pub fn unmatched_begin(token: &Token) -> Self {
+        AssemblyError {
+            code: codes::UNMATCHED_BEGIN,
+            message: "begin without matching end".to_string(),
+            step: token.pos(),
+            op: token.to_string(),
+        }
+    }
+
+    pub fn dangling_ops_after_program(token: &Token) -> Self {
+        AssemblyError {
+            code: codes::DANGLING_OPS_AFTER_PROGRAM,
+            message: "dangling instructions after program end".to_string(),
+            step: token.pos(),
+            op: token.to_string(),
+        }
+    }
+
+    // PROCEDURES
+    // --------------------------------------------------------------------------------------------
+
+    pub fn duplicate_proc_label(token: &Token, label: &str) -> Self {
+        AssemblyError {
+            code: codes::DUPLICATE_PROC_LABEL,
+            message: format!("duplicate procedure label: {}", label),
+            step: token.pos(),
+            op: token.to_string(),
+        }
+    }
+
+    pub fn invalid_proc_label(token: &Token, label: &str) -> Self {
+        AssemblyError {
+            code: codes::INVALID_PROC_LABEL,
+            message: format!("invalid procedure label: {}", label),
+            step: token.pos(),
+            op: token.to_string(),
+        }
+    }
+
+    pub fn invalid_proc_locals(token: &Token, locals: &str) -> Self {
+        AssemblyError {
+            code: codes::INVALID_PROC_LOCALS,
+            message: format!("invalid procedure locals: {}", locals),
+            step: token.pos(),
+            op: token.to_string(),
+        }
+    }
+
+    pub fn unmatched_proc(token: &Token) -> Self {
+        AssemblyError {
+            code: codes::UNMATCHED_PROC,
+            message: "proc without matching end".to_string(),
+            step: token.pos(),
+            op: token.to_string(),
+        }
+    }
+
+    pub fn undefined_proc(token: &Token, label: &str) -> Self {
+        AssemblyError {
+            code: codes::UNDEFINED_PROC,
+            message: format!("undefined procedure: {}", label),
+            step: token.pos(),
+            op: token.to_string(),
+        }
+    }
+
+    pub fn undefined_kernel_proc(token: &Token, label: &str) -> Self {
+        AssemblyError {
+            code: codes::UNDEFINED_KERNEL_PROC,
+            message: format!("undefined kernel procedure: {}", label),
+            step: token.pos(),
+            op: token.to_string(),
+        }
+    }
+
+    pub fn misplaced_annotation(token: &Token, label: &str) -> Self {
+        AssemblyError {
+            code: codes::MISPLACED_ANNOTATION,
+            message: format!(
+                "annotation is not allowed on procedure {}; access control annotations are allowed only on exported procedures, and no annotations are allowed on generic procedures",
+                label
+            ),
+            step: token.pos(),
+            op: token.to_string(),
+        }
+    }
+
+    pub fn proc_export_not_allowed(token: &Token, label: &str) -> Self {
+        AssemblyError {
+            code: codes::PROC_EXPORT_NOT_ALLOWED,
+            message: format!("exported procedures not allowed in this context: {}", label),
+            step: token.pos(),
+            op: token.to_string(),
+        }
+    }
+
+    pub fn invalid_generic_params(token: &Token) -> Self {
+        AssemblyError {
+            code: codes::INVALID_GENERIC_PARAMS,
+            message: format!("malformed generic parameter list in '{}'", token),
+            step: token.pos(),
+            op: token.to_string(),
+        }
+    }
+
+    pub fn generic_proc_export(token: &Token, label: &str) -> Self {
+        AssemblyError {
+            code: codes::GENERIC_PROC_EXPORT,
+            message: format!("generic procedures cannot be exported: {}", label),
+            step: token.pos(),
+            op: token.to_string(),
+        }
+    }
+
+    pub fn generic_args_mismatch(
+        token: &Token,
+        label: &str,
+        num_params: usize,
+        num_args: usize,
+    ) -> Self {
+        AssemblyError {
+            code: codes::GENERIC_ARGS_MISMATCH,
+            message: format!(
+                "procedure {} expects {} generic arguments, but {} were provided",
+                label, num_params, num_args
+            ),
+            step: token.pos(),
+            op: token.to_string(),
+        }
+    }
+
+    // IMPORTS AND MODULES
+    // --------------------------------------------------------------------------------------------
+
+    pub fn missing_import_source(token: &Token, module_path: &str) -> Self {
+        AssemblyError {
+            code: codes::MISSING_IMPORT_SOURCE,
+            message: format!("module source not found: {}", module_path),
+            step: token.pos(),
+            op: token.to_string(),
+        }
+    }
+
+    pub fn invalid_custom_op(token: &Token, reason: &str) -> Self {
+        AssemblyError {
+            code: codes::INVALID_CUSTOM_OP,
+            message: format!("custom instruction '{}' is invalid: {}", token, reason),
+            step: token.pos(),
+            op: token.to_string(),
+        }
+    }
+
+    pub fn invalid_extension(namespace: &str, reason: &str) -> Self {
+        AssemblyError {
+            code: codes::INVALID_EXTENSION,
+            message: format!("invalid instruction extension {}: {}", namespace, reason),
+            step: 0,
+            op: "".to_string(),
+        }
+    }
+
+    pub fn duplicate_library(namespace: &str) -> Self {
+        AssemblyError {
+            code: codes::DUPLICATE_LIBRARY,
+            message: format!("duplicate library namespace: {}", namespace),
+            step: 0,
+            op: "".to_string(),
+        }
+    }
+
+    pub fn missing_module_source(module_path: &str) -> Self {
+        AssemblyError {
+            code: codes::MISSING_IMPORT_SOURCE,
+            message: format!("module source not found: {}", module_path),
+            step: 0,
+            op: "".to_string(),
+        }
+    }
+
+    pub fn dangling_ops_after_module(token: &Token, module_path: &str) -> Self {
+        AssemblyError {
+            code: codes::DANGLING_OPS_AFTER_MODULE,
+            message: format!("dangling instructions after module end at {}", module_path),
+            step: token.pos(),
+            op: token.to_string(),
+        }
+    }
+
+    pub fn circular_module_dependency(token: &Token, module_chain: &[String]) -> Self {
+        AssemblyError {
+            code: codes::CIRCULAR_MODULE_DEPENDENCY,
+            message: format!(
+                "circular module dependency in the following chain: {:?}",
+                module_chain
+            ),
+            step: token.pos(),
+            op: token.to_string(),
+        }
+    }
+
+    pub fn invalid_module_path(token: &Token, module_path: &str) -> Self {
+        AssemblyError {
+            code: codes::INVALID_MODULE_PATH,
+            message: format!("invalid module import path: {}", module_path),
+            step: token.pos(),
+            op: token.to_string(),
+        }
+    }
+
+    // CONSTANTS
+    // --------------------------------------------------------------------------------------------
+
+    pub fn invalid_const_declaration(token: &Token) -> Self {
+        AssemblyError {
+            code: codes::INVALID_CONST_DECLARATION,
+            message: format!(
+                "malformed constant declaration '{}': expected 'const.NAME=EXPR'",
+                token
+            ),
+            step: token.pos(),
+            op: token.to_string(),
+        }
+    }
+
+    pub fn invalid_const_name(token: &Token, name: &str) -> Self {
+        AssemblyError {
+            code: codes::INVALID_CONST_NAME,
+            message: format!("invalid constant name: {}", name),
+            step: token.pos(),
+            op: token.to_string(),
+        }
+    }
+
+    pub fn duplicate_const(token: &Token, name: &str) -> Self {
+        AssemblyError {
+            code: codes::DUPLICATE_CONST,
+            message: format!("duplicate constant name: {}", name),
+            step: token.pos(),
+            op: token.to_string(),
+        }
+    }
+
+    pub fn invalid_predefined_const(name: &str, reason: &str) -> Self {
+        AssemblyError {
+            code: codes::INVALID_CONST_NAME,
+            message: format!("invalid predefined constant {}: {}", name, reason),
+            step: 0,
+            op: "".to_string(),
+        }
+    }
+
+    pub fn invalid_const_expr(token: &Token, expr: &str, reason: &str) -> Self {
+        AssemblyError {
+            code: codes::INVALID_CONST_EXPR,
+            message: format!("invalid constant expression '{}': {}", expr, reason),
+            step: token.pos(),
+            op: token.to_string(),
+        }
+    }
+
+    pub fn undefined_const(token: &Token, name: &str) -> Self {
+        AssemblyError {
+            code: codes::UNDEFINED_CONST,
+            message: format!("undefined constant: {}", name),
+            step: token.pos(),
+            op: token.to_string(),
+        }
+    }
+
+    pub fn invalid_data_declaration(token: &Token) -> Self {
+        AssemblyError {
+            code: codes::INVALID_DATA_DECLARATION,
+            message: format!(
+                "malformed data segment declaration '{}': expected 'data.NAME=[EXPR,EXPR,...]'",
+                token
+            ),
+            step: token.pos(),
+            op: token.to_string(),
+        }
+    }
+
+    pub fn duplicate_data(token: &Token, name: &str) -> Self {
+        AssemblyError {
+            code: codes::DUPLICATE_DATA,
+            message: format!("duplicate data segment name: {}", name),
+            step: token.pos(),
+            op: token.to_string(),
+        }
+    }
+
+    pub fn undefined_data(token: &Token, name: &str) -> Self {
+        AssemblyError {
+            code: codes::UNDEFINED_DATA,
+            message: format!("undefined data segment: {}", name),
+            step: token.pos(),
+            op: token.to_string(),
+        }
+    }
+
+    pub fn unroll_budget_exceeded(token: &Token, num_ops: usize, budget: usize) -> Self {
+        AssemblyError {
+            code: codes::UNROLL_BUDGET_EXCEEDED,
+            message: format!(
+                "unrolling '{}' produces {} operations, which exceeds the budget of {}",
+                token, num_ops, budget
+            ),
+            step: token.pos(),
+            op: token.to_string(),
+        }
+    }
+
+    pub fn locals_budget_exceeded(token: &Token, path: &LocalsPath, budget: u64) -> Self {
+        AssemblyError {
+            code: codes::LOCALS_BUDGET_EXCEEDED,
+            message: format!(
+                "'{}' allocates {} locals along the invocation path {}, which exceeds the budget of {}",
+                token,
+                path.num_locals(),
+                path,
+                budget
+            ),
+            step: token.pos(),
+            op: token.to_string(),
+        }
+    }
+
+    pub fn too_many_procedures(token: &Token, limit: usize) -> Self {
+        AssemblyError {
+            code: codes::TOO_MANY_PROCEDURES,
+            message: format!(
+                "'{}' exceeds the limit of {} procedures per module",
+                token, limit
+            ),
+            step: token.pos(),
+            op: token.to_string(),
+        }
+    }
+
+    pub fn too_many_proc_locals(token: &Token, label: &str, num_locals: u32, limit: u32) -> Self {
+        AssemblyError {
+            code: codes::TOO_MANY_PROC_LOCALS,
+            message: format!(
+                "procedure '{}' declares {} locals, which exceeds the limit of {}",
+                label, num_locals, limit
+            ),
+            step: token.pos(),
+            op: token.to_string(),
+        }
+    }
+
+    pub fn name_too_long(token: &Token, name: &str, limit: usize) -> Self {
+        AssemblyError {
+            code: codes::NAME_TOO_LONG,
+            message: format!(
+                "name of {} characters exceeds the limit of {} characters",
+                name.len(),
+                limit
+            ),
+            step: token.pos(),
+            op: token.to_string(),
+        }
+    }
+
+    pub fn bounded_loop_locals(token: &Token) -> Self {
+        AssemblyError {
+            code: codes::BOUNDED_LOOP_LOCALS,
+            message: format!(
+                "body of '{}' accesses locals of the enclosing procedure",
+                token
+            ),
+            step: token.pos(),
+            op: token.to_string(),
+        }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the stable code identifying the kind of this error.
+    ///
+    /// A long-form explanation of the code can be retrieved via [explain_error()].
+    pub fn error_code(&self) -> ErrorCode {
+        self.code
+    }
+
+    /// Returns the long-form explanation for the kind of this error.
+    pub fn explanation(&self) -> &'static ErrorExplanation {
+        explain_error(self.code).expect("no explanation for a known error code")
+    }
+
+    pub fn message(&self) -> &String {
+        &self.message
+    }
+
+    pub fn operation(&self) -> &String {
+        &self.op
+    }
+
+    pub fn step(&self) -> usize {
+        self.step
+    }
+}
+
+// COMMON TRAIT IMPLEMENTATIONS
+// ================================================================================================
+
+impl fmt::Debug for AssemblyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "assembly error at {}: {}", self.step, self.message)
+    }
+}
+
+impl fmt::Display for AssemblyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "assembly error at {}: {}", self.step, self.message)
+    }
+}
+
+// ASSEMBLY WARNING
+// ================================================================================================
+
+/// A diagnostic reported for code which compiles, but which the assembler would reject if it was
+/// configured to treat such code as an error (see [BudgetPolicy](crate::BudgetPolicy)).
+///
+/// A warning carries the same code, message, and position as the corresponding error.
+#[derive(Clone, Eq, PartialEq)]
+pub struct AssemblyWarning(AssemblyError);
+
+impl AssemblyWarning {
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the stable code identifying the kind of this warning.
+    pub fn error_code(&self) -> ErrorCode {
+        self.0.error_code()
+    }
+
+    pub fn message(&self) -> &String {
+        self.0.message()
+    }
+
+    pub fn step(&self) -> usize {
+        self.0.step()
+    }
+
+    /// Converts this warning into the error which the assembler reports for the same code when
+    /// warnings are not allowed.
+    pub fn into_error(self) -> AssemblyError {
+        self.0
+    }
+}
+
+impl fmt::Debug for AssemblyWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "assembly warning at {}: {}", self.0.step, self.0.message)
+    }
+}
+
+impl fmt::Display for AssemblyWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "assembly warning at {}: {}", self.0.step, self.0.message)
+    }
+}
+
+// WARNING RECORDER
+// ================================================================================================
+
+/// Collects warnings reported during a compilation; the recorder is shared by all assembly
+/// contexts of the compilation.
+#[derive(Clone, Debug, Default)]
+pub struct WarningRecorder {
+    warnings: Rc<RefCell<Vec<AssemblyWarning>>>,
+}
+
+impl WarningRecorder {
+    /// Records the specified error as a warning.
+    pub fn record(&self, error: AssemblyError) {
+        self.warnings.borrow_mut().push(AssemblyWarning(error));
+    }
+
+    /// Returns the warnings collected by this recorder in the order in which they were reported.
+    pub fn into_warnings(self) -> Vec<AssemblyWarning> {
+        self.warnings.borrow().clone()
+    }
+}
@@ -0,0 +1,54 @@
+use super::{Arc, BTreeMap, Decorator, String};
+
+// TYPE ALIASES
+// ================================================================================================
+
+/// Instruction extensions keyed by their namespaces.
+pub(crate) type ExtensionMap = BTreeMap<String, Arc<dyn InstructionExtension>>;
+
+// INSTRUCTION EXTENSION
+// ================================================================================================
+
+/// A namespace of custom instructions which are expanded into Miden assembly at parse time.
+///
+/// An extension is registered with the assembler under a namespace (see
+/// [Assembler::with_extension()](crate::Assembler::with_extension)); an instruction of the form
+/// `namespace::name.param1.param2...` is then passed to [InstructionExtension::expand()] with the
+/// name and the parameters of the instruction (constant parameters are resolved beforehand), and
+/// is replaced by the returned [Expansion]. Namespaced instructions whose namespace is not
+/// registered are rejected as invalid.
+///
+/// Any function or closure with the signature of [InstructionExtension::expand()] is an
+/// extension as well.
+pub trait InstructionExtension: Send + Sync {
+    /// Returns the expansion of the custom instruction with the specified name and parameters.
+    ///
+    /// # Errors
+    /// Returns a description of the problem if the instruction is not valid; the description is
+    /// reported as a part of an assembly error.
+    fn expand(&self, name: &str, params: &[&str]) -> Result<Expansion, String>;
+}
+
+impl<F> InstructionExtension for F
+where
+    F: Fn(&str, &[&str]) -> Result<Expansion, String> + Send + Sync,
+{
+    fn expand(&self, name: &str, params: &[&str]) -> Result<Expansion, String> {
+        self(name, params)
+    }
+}
+
+// EXPANSION
+// ================================================================================================
+
+/// Replacement of a custom instruction.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Expansion {
+    /// A whitespace-separated sequence of instructions which are compiled in place of the custom
+    /// instruction. The instructions must compile into a single span block, and thus cannot
+    /// include control flow instructions, procedure invocations, or other custom instructions.
+    Instructions(String),
+    /// A decorator which is attached to the operation following the custom instruction,
+    /// regardless of whether the assembler is in debug mode.
+    Decorator(Decorator),
+}
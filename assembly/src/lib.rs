@@ -4,30 +4,74 @@
 #[macro_use]
 extern crate alloc;
 
+use vm_core::errors::LibraryError;
 use vm_core::{
+    chiplets::hasher::Digest,
     code_blocks::CodeBlock,
     utils::{
         collections::{BTreeMap, Vec},
         string::{String, ToString},
     },
-    Library, Program,
+    CodeBlockTable, Decorator, Felt, Kernel, Library, Operation, Program, StarkField,
 };
 use vm_stdlib::StdLibrary;
 
+#[cfg(not(feature = "std"))]
+use alloc::sync::Arc;
+
+#[cfg(feature = "std")]
+use std::sync::Arc;
+
 mod context;
 use context::AssemblyContext;
 
 mod procedures;
-use procedures::Procedure;
+use procedures::{collect_callees, GenericProcedure, ProcAnnotation, Procedure};
+pub use procedures::{BudgetPolicy, LocalsPath};
+pub use vm_core::{ModuleParser, ProcedureInfo, ProcedureSignature};
 
 mod parsers;
-use parsers::{combine_blocks, parse_code_blocks};
+use parsers::{
+    build_preserve_ops, combine_blocks, combine_mergeable_blocks, parse_code_blocks,
+    parse_constant, parse_data_segment,
+};
 
 mod tokens;
 use tokens::{Token, TokenStream};
 
 mod errors;
-pub use errors::AssemblyError;
+use errors::WarningRecorder;
+pub use errors::{
+    explain_error, AssemblyError, AssemblyWarning, ErrorCode, ErrorExplanation, ERROR_INDEX,
+};
+
+mod cache;
+use cache::ModuleCache;
+
+mod canonical;
+
+mod digests;
+pub use digests::ProgramDigests;
+
+mod extensions;
+use extensions::ExtensionMap;
+pub use extensions::{Expansion, InstructionExtension};
+
+mod audit;
+pub use audit::{AuditLog, Decision, DecisionKind, ProcedureAudit};
+use audit::{AuditRecorder, PROGRAM_BODY_NAME};
+
+mod optimizer;
+use optimizer::SpanOptions;
+pub use optimizer::{OptLevel, Optimization, PushEncoding};
+
+mod limits;
+pub use limits::{
+    AssemblyLimits, DEFAULT_MAX_NAME_LEN, DEFAULT_MAX_PROCEDURES, DEFAULT_MAX_PROC_LOCALS,
+};
+
+mod outline;
+pub use outline::{ConstantDecl, ImportDecl, ProcedureDecl, ProcedureRef, SourceOutline};
 
 #[cfg(test)]
 mod tests;
@@ -37,33 +81,407 @@ mod tests;
 
 const MODULE_PATH_DELIM: &str = "::";
 
+/// Memory address at which the first data segment of a program is placed.
+pub const DATA_SEGMENT_OFFSET: u64 = 1 << 32;
+
+/// Default number of operations above which a `repeat` block is executed in a loop rather than
+/// unrolled entirely.
+pub const DEFAULT_SPLIT_THRESHOLD: usize = 1 << 16;
+
 // TYPE ALIASES
 // ================================================================================================
 
-type ProcMap = BTreeMap<String, Procedure>;
+type ProcMap = BTreeMap<String, Arc<Procedure>>;
 type ModuleMap = BTreeMap<String, ProcMap>;
+type LibraryMap = BTreeMap<String, Arc<dyn Library + Send + Sync>>;
 
 // ASSEMBLER
 // ================================================================================================
 
-/// TODO: add comments
+/// Describes how the body of a program is delimited in its source.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum ProgramBody {
+    /// The body is wrapped in `begin` and `end`.
+    Block,
+    /// The body consists of all instructions following the declarations of the program.
+    Instructions,
+}
+
+/// Miden Assembler which can be used to convert Miden assembly source code into program MAST.
+///
+/// An assembler holds the standard library, a cache of parsed library modules, and compilation
+/// settings. Library modules parsed while compiling a program are added to the cache, and thus
+/// every module is parsed only once, even by the default assembler. Besides the cache, which is
+/// guarded by a lock, the state of an assembler is immutable. Thus, an assembler can be used from
+/// multiple threads concurrently, and cloning an assembler is cheap as clones share the standard
+/// library and the module cache. Builder methods which change how modules are parsed (e.g.,
+/// [Assembler::with_opt_level()]) start with an empty cache, which is filled again by subsequent
+/// compilations.
+///
+/// An assembler can also be instantiated with a kernel (see [Assembler::with_kernel()]), in which
+/// case programs compiled by the assembler can invoke procedures of the kernel via system calls.
+///
+/// Besides the standard library, programs can import modules of user libraries added to the
+/// assembler via [Assembler::with_library()], and use custom instructions expanded by extensions
+/// registered via [Assembler::with_extension()].
+#[derive(Clone)]
 pub struct Assembler {
-    stdlib: StdLibrary,
-    parsed_modules: ModuleMap,
+    stdlib: Arc<StdLibrary>,
+    libraries: Arc<LibraryMap>,
+    extensions: Arc<ExtensionMap>,
+    constants: Arc<BTreeMap<String, u64>>,
+    module_cache: Arc<ModuleCache>,
+    kernel: Arc<ProcMap>,
     in_debug_mode: bool,
+    unroll_budget: Option<usize>,
+    split_threshold: usize,
+    locals_budget: Option<(u64, BudgetPolicy)>,
+    span_options: SpanOptions,
+    limits: AssemblyLimits,
+    profiling: bool,
 }
 
 impl Assembler {
     // CONSTRUCTOR
     // --------------------------------------------------------------------------------------------
-    /// Returns a new instance of [Assembler] instantiated with empty module map.
+    /// Returns a new instance of [Assembler] instantiated with empty module cache.
     /// Debug related decorators are added to span blocks when debug mode is on.
     pub fn new(in_debug_mode: bool) -> Self {
         Self {
-            stdlib: StdLibrary::default(),
-            parsed_modules: BTreeMap::new(),
+            stdlib: Arc::new(StdLibrary::default()),
+            libraries: Arc::new(BTreeMap::new()),
+            extensions: Arc::new(BTreeMap::new()),
+            constants: Arc::new(BTreeMap::new()),
+            module_cache: Arc::new(ModuleCache::default()),
+            kernel: Arc::new(BTreeMap::new()),
             in_debug_mode,
+            unroll_budget: None,
+            split_threshold: DEFAULT_SPLIT_THRESHOLD,
+            locals_budget: None,
+            span_options: SpanOptions::default(),
+            limits: AssemblyLimits::default(),
+            profiling: false,
+        }
+    }
+
+    /// Sets the maximum number of operations a single `repeat` block is allowed to unroll into.
+    ///
+    /// The size of a `repeat` block is computed as the number of operations in its body
+    /// (including unrolled nested `repeat` blocks and inlined procedures) times the number of
+    /// unrolled iterations; iterations executed in a loop (see [Assembler::with_split_threshold()])
+    /// are not counted. Compiling a program containing a larger `repeat` block results in an
+    /// error.
+    ///
+    /// Since modules in the cache were parsed without the budget, this also clears the module
+    /// cache.
+    pub fn with_unroll_budget(mut self, budget: usize) -> Self {
+        self.unroll_budget = Some(budget);
+        self.module_cache = Arc::new(ModuleCache::default());
+        self
+    }
+
+    /// Sets the number of operations above which a `repeat` block is not unrolled entirely.
+    ///
+    /// A `repeat` block which would unroll into more operations than the threshold allows is
+    /// split: its body is unrolled into chunks of at most half the threshold, and the chunks are
+    /// executed in a counter-controlled `while` loop, with the remaining iterations unrolled in
+    /// front of the loop. The loop counter is kept in a memory slot right above the locals of the
+    /// current procedure; thus, a `repeat` block whose body accesses procedure locals is always
+    /// unrolled entirely. Splitting adds about 15 cycles per executed chunk.
+    ///
+    /// The default threshold is [DEFAULT_SPLIT_THRESHOLD]; `usize::MAX` disables splitting.
+    ///
+    /// Since modules in the cache were parsed with a different threshold, this also clears the
+    /// module cache.
+    pub fn with_split_threshold(mut self, threshold: usize) -> Self {
+        self.split_threshold = threshold;
+        self.module_cache = Arc::new(ModuleCache::default());
+        self
+    }
+
+    /// Sets the maximum number of locals which can be allocated along a single chain of
+    /// procedure invocations, and how chains exceeding the budget are handled.
+    ///
+    /// Locals of a procedure invoked via `exec` are allocated in memory on top of the locals of
+    /// all procedures up the invocation chain. With [BudgetPolicy::Error], compiling a program or
+    /// a module in which a chain of invocations allocates more locals than the budget allows
+    /// results in an error listing every procedure in the chain (see [LocalsPath]). With
+    /// [BudgetPolicy::Warn], such code compiles, and the same diagnostic is reported as a warning
+    /// by [Assembler::compile_with_warnings()].
+    ///
+    /// Since modules in the cache were parsed without the budget, this also clears the module
+    /// cache.
+    pub fn with_locals_budget(mut self, budget: u64, policy: BudgetPolicy) -> Self {
+        self.locals_budget = Some((budget, policy));
+        self.module_cache = Arc::new(ModuleCache::default());
+        self
+    }
+
+    /// Sets the limits on the size of programs and modules compiled by this assembler (see
+    /// [AssemblyLimits]). Compiling a program which exceeds any of the limits, or which imports
+    /// a module exceeding them, results in an error.
+    ///
+    /// Since modules in the cache were parsed with different limits, this also clears the module
+    /// cache.
+    pub fn with_limits(mut self, limits: AssemblyLimits) -> Self {
+        self.limits = limits;
+        self.module_cache = Arc::new(ModuleCache::default());
+        self
+    }
+
+    /// Sets the level of peephole optimizations applied to span blocks (see [OptLevel]).
+    ///
+    /// Optimizations remove redundant sequences of operations, and thus change the hash of the
+    /// compiled program. In debug mode, every instruction must keep its own operations so that
+    /// they can be attributed to the instruction; thus, optimizations are not applied in debug
+    /// mode.
+    ///
+    /// Since modules in the cache were parsed without optimizations, this also clears the module
+    /// cache.
+    pub fn with_opt_level(mut self, level: OptLevel) -> Self {
+        self.span_options.level = level;
+        self.module_cache = Arc::new(ModuleCache::default());
+        self
+    }
+
+    /// Sets how constants pushed onto the stack are encoded (see [PushEncoding]).
+    ///
+    /// Encodings other than the default one change the hash of the compiled program. As with
+    /// optimizations, the encoding is not applied in debug mode.
+    ///
+    /// Since modules in the cache were parsed with the default encoding, this also clears the
+    /// module cache.
+    pub fn with_push_encoding(mut self, encoding: PushEncoding) -> Self {
+        self.span_options.encoding = encoding;
+        self.module_cache = Arc::new(ModuleCache::default());
+        self
+    }
+
+    /// Disables the specified optimization (see [Optimization]), regardless of the optimization
+    /// level and the push encoding of this assembler.
+    ///
+    /// Since modules in the cache were parsed without optimizations, this also clears the module
+    /// cache.
+    pub fn without_optimization(mut self, optimization: Optimization) -> Self {
+        self.span_options.disable(optimization);
+        self.module_cache = Arc::new(ModuleCache::default());
+        self
+    }
+
+    /// Enables procedure profiling.
+    ///
+    /// The body of a procedure invoked via `exec` is inlined into the invoking code; if the body
+    /// is a single span block, it is normally merged with the neighboring span blocks. With
+    /// profiling enabled, the bodies of such procedures are kept as separate blocks; thus, the
+    /// processor can attribute cycles to the procedures by the MAST roots of their bodies (see
+    /// `ExecutionOptions::with_block_profiling()` in the processor). This changes the hash of the
+    /// compiled program, and adds a few cycles for every separate block.
+    ///
+    /// Since modules in the cache were parsed without profiling, this also clears the module
+    /// cache.
+    pub fn with_profiling(mut self) -> Self {
+        self.profiling = true;
+        self.module_cache = Arc::new(ModuleCache::default());
+        self
+    }
+
+    /// Compiles the provided kernel module source, and sets the compiled kernel as the kernel
+    /// against which programs are compiled.
+    ///
+    /// A kernel module is a library module (i.e., it consists of imports, constants, and
+    /// procedure declarations); procedures exported from the module can be invoked by programs
+    /// via `syscall.<procedure>` instructions. Compiled programs carry the kernel together with
+    /// the code of its procedures, and the processor executes kernel procedures in the kernel
+    /// context.
+    ///
+    /// # Errors
+    /// Returns an error if the kernel module fails to compile.
+    pub fn with_kernel(mut self, kernel_source: &str) -> Result<Self, AssemblyError> {
+        let mut modules = ModuleMap::new();
+        self.parse_module(kernel_source, "", &mut modules, &mut Vec::new(), None, None)?;
+        let kernel = modules.remove("").expect("kernel module not parsed");
+        self.kernel = Arc::new(kernel);
+        Ok(self)
+    }
+
+    /// Adds the specified library to the libraries whose modules can be imported by programs
+    /// compiled by this assembler.
+    ///
+    /// Modules are looked up by the root namespace of their paths; for example, `use.foo::math`
+    /// imports the `foo::math` module from the library with the `foo` root namespace.
+    ///
+    /// # Errors
+    /// Returns an error if the root namespace of the library is already taken by the standard
+    /// library or by another library of this assembler.
+    pub fn with_library<L>(mut self, library: L) -> Result<Self, AssemblyError>
+    where
+        L: Library + Send + Sync + 'static,
+    {
+        let namespace = library.root_ns();
+        if namespace == self.stdlib.root_ns() || self.libraries.contains_key(namespace) {
+            return Err(AssemblyError::duplicate_library(namespace));
+        }
+        Arc::make_mut(&mut self.libraries).insert(namespace.to_string(), Arc::new(library));
+        Ok(self)
+    }
+
+    /// Registers the specified extension to expand custom instructions of the specified namespace
+    /// (e.g., `myapp::store_note.1`) in programs and modules compiled by this assembler.
+    ///
+    /// Since modules in the cache were parsed without the extension, this also clears the module
+    /// cache.
+    ///
+    /// # Errors
+    /// Returns an error if the namespace is not a valid identifier, or if an extension is already
+    /// registered for the namespace.
+    pub fn with_extension<E>(mut self, namespace: &str, extension: E) -> Result<Self, AssemblyError>
+    where
+        E: InstructionExtension + 'static,
+    {
+        let mut chars = namespace.chars();
+        let is_valid = matches!(chars.next(), Some(c) if c.is_ascii_alphabetic())
+            && chars.all(|c| c.is_ascii_alphanumeric() || c == '_');
+        if !is_valid {
+            return Err(AssemblyError::invalid_extension(
+                namespace,
+                "invalid namespace",
+            ));
+        }
+        if self.extensions.contains_key(namespace) {
+            return Err(AssemblyError::invalid_extension(
+                namespace,
+                "namespace is already registered",
+            ));
+        }
+        Arc::make_mut(&mut self.extensions).insert(namespace.to_string(), Arc::new(extension));
+        self.module_cache = Arc::new(ModuleCache::default());
+        Ok(self)
+    }
+
+    /// Defines a constant with the specified name and value in every program compiled by this
+    /// assembler, as if the program declared it via `const.NAME=value`.
+    ///
+    /// Predefined constants allow the same source to be compiled with different parameters
+    /// (e.g., with optional features enabled or disabled). They are not visible in library
+    /// modules, and a program declaring a constant with the same name fails to compile.
+    ///
+    /// # Errors
+    /// Returns an error if the name is not a valid constant name, or if the value is not a valid
+    /// field element.
+    pub fn with_constant(mut self, name: &str, value: u64) -> Result<Self, AssemblyError> {
+        if !tokens::is_valid_const_name(name) {
+            return Err(AssemblyError::invalid_predefined_const(
+                name,
+                "invalid constant name",
+            ));
+        }
+        if value >= Felt::MODULUS {
+            return Err(AssemblyError::invalid_predefined_const(
+                name,
+                "value is not a valid field element",
+            ));
         }
+        Arc::make_mut(&mut self.constants).insert(name.to_string(), value);
+        Ok(self)
+    }
+
+    // MODULE CACHE
+    // --------------------------------------------------------------------------------------------
+
+    /// Parses the library module located at the specified path, and adds it together with all of
+    /// its dependencies to the module cache of this assembler. Subsequent compilations which
+    /// import the cached modules do not need to parse them.
+    ///
+    /// Modules imported by compiled programs are cached automatically; this method allows
+    /// parsing modules ahead of time, e.g., before compiling programs on multiple threads. The
+    /// cache is shared by all clones of this assembler. Without the standard library, modules are
+    /// cached only via this method; if this assembler has clones at the time of the call, the
+    /// cache is copied first.
+    ///
+    /// # Errors
+    /// Returns an error if the module could not be found, or if parsing the module or any of its
+    /// dependencies fails.
+    pub fn cache_module(&mut self, module_path: &str) -> Result<(), AssemblyError> {
+        if self.module_cache.contains(module_path) {
+            return Ok(());
+        }
+
+        let module_source = self
+            .get_module_source(module_path)
+            .ok_or_else(|| AssemblyError::missing_module_source(module_path))?;
+        let modules = self.parse_library_module(module_source, module_path)?;
+
+        ModuleCache::insert(&mut self.module_cache, modules);
+        Ok(())
+    }
+
+    // LIBRARY INTROSPECTION
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns MAST roots of the procedures exported from the library module located at the
+    /// specified path, keyed by procedure label.
+    ///
+    /// Modules which are not in the module cache of this assembler are parsed and added to it.
+    ///
+    /// # Errors
+    /// Returns an error if the module could not be found, or if parsing the module or any of its
+    /// dependencies fails.
+    pub fn procedure_roots(
+        &self,
+        module_path: &str,
+    ) -> Result<BTreeMap<String, Digest>, AssemblyError> {
+        self.with_module_procs(module_path, |module_procs| {
+            module_procs
+                .iter()
+                .map(|(label, proc)| (label.clone(), proc.code_root().hash()))
+                .collect()
+        })
+    }
+
+    /// Returns descriptions of the procedures exported from the library module located at the
+    /// specified path, sorted by procedure label.
+    ///
+    /// This allows tools such as documentation generators and compilers targeting Miden assembly
+    /// to describe the procedures of the standard library and of user libraries. Generic
+    /// procedures are not described as they have no MAST roots until they are instantiated.
+    /// Modules which are not in the module cache of this assembler are parsed and added to it.
+    ///
+    /// # Errors
+    /// Returns an error if the module could not be found, or if parsing the module or any of its
+    /// dependencies fails.
+    pub fn exported_procedures(
+        &self,
+        module_path: &str,
+    ) -> Result<Vec<ProcedureInfo>, AssemblyError> {
+        self.with_module_procs(module_path, |module_procs| {
+            module_procs
+                .values()
+                .map(|proc| describe_procedure(proc))
+                .collect()
+        })
+    }
+
+    /// Returns documentation of the procedures exported from the library module located at the
+    /// specified path, keyed by procedure label.
+    ///
+    /// Documentation of a procedure consists of the `#!` comment lines immediately preceding the
+    /// procedure declaration (or its annotations), with the `#!` prefix (and a single space
+    /// following it) removed. Procedures without documentation comments are not included in the
+    /// result.
+    ///
+    /// # Errors
+    /// Returns an error if the module could not be found, or if parsing the module or any of its
+    /// dependencies fails.
+    pub fn procedure_docs(
+        &self,
+        module_path: &str,
+    ) -> Result<BTreeMap<String, String>, AssemblyError> {
+        self.with_module_procs(module_path, |module_procs| {
+            module_procs
+                .iter()
+                .filter_map(|(label, proc)| Some((label.clone(), proc.docs()?.to_string())))
+                .collect()
+        })
     }
 
     // PROGRAM COMPILER
@@ -72,23 +490,223 @@ impl Assembler {
     /// Compiles the provided source code into a [Program]. The resulting program can be executed
     /// on Miden VM.
     pub fn compile(&self, source: &str) -> Result<Program, AssemblyError> {
+        self.compile_with_locals_path(source, ProgramBody::Block, None, None)
+            .map(|(program, ..)| program)
+    }
+
+    /// Compiles the procedure with the specified name from the provided source code, and returns
+    /// the code block of the procedure.
+    ///
+    /// This is meant for hot-swapping procedures while debugging: once the source code of a
+    /// procedure is edited, the procedure can be recompiled on its own and its code can be
+    /// replaced in a previously compiled program. The source code can contain anything which can
+    /// precede the body of a program (imports, constants, and procedures), and must declare the
+    /// procedure. Code invoked by the procedure via `call` instructions is not included in the
+    /// returned block.
+    ///
+    /// # Errors
+    /// Returns an error if the source fails to compile, or if it does not declare the procedure.
+    pub fn recompile_procedure(
+        &self,
+        source: &str,
+        name: &str,
+    ) -> Result<CodeBlock, AssemblyError> {
+        let program = self.compile(&format!("{}\nbegin exec.{} end", source, name))?;
+        Ok(program.root().clone())
+    }
+
+    /// Compiles the provided source code into a [Program], and returns the program together with
+    /// the warnings reported while compiling it, in the order in which they were reported.
+    ///
+    /// Warnings are reported for code which exceeds a budget configured with
+    /// [BudgetPolicy::Warn] (see [Assembler::with_locals_budget()]). They cover the program and
+    /// all modules imported by it; thus, modules in the module cache of this assembler are parsed
+    /// anew.
+    ///
+    /// # Errors
+    /// Returns an error if the source fails to compile.
+    pub fn compile_with_warnings(
+        &self,
+        source: &str,
+    ) -> Result<(Program, Vec<AssemblyWarning>), AssemblyError> {
+        let assembler = Self {
+            module_cache: Arc::new(ModuleCache::default()),
+            ..self.clone()
+        };
+        let recorder = WarningRecorder::default();
+        let (program, ..) = assembler.compile_with_locals_path(
+            source,
+            ProgramBody::Block,
+            None,
+            Some(&recorder),
+        )?;
+        Ok((program, recorder.into_warnings()))
+    }
+
+    /// Compiles the provided sequence of instructions into a [Program] as if the instructions
+    /// were wrapped in `begin` and `end`.
+    ///
+    /// Besides the instructions, the source can contain anything which can precede the body of a
+    /// program (imports, constants, data segments, and procedures). This is useful for embedders
+    /// which generate code snippets programmatically, as the snippets do not need to be wrapped
+    /// into a program body. The resulting program is identical to the program compiled from the
+    /// wrapped source.
+    ///
+    /// # Errors
+    /// Returns an error if the source fails to compile, or if the instructions contain an `end`
+    /// or an `else` which does not close a block opened by the instructions.
+    pub fn compile_procedure(&self, source: &str) -> Result<Program, AssemblyError> {
+        self.compile_with_locals_path(source, ProgramBody::Instructions, None, None)
+            .map(|(program, ..)| program)
+    }
+
+    /// Compiles the provided source code and returns the chain of procedure invocations from the
+    /// program body along which the largest number of locals is allocated.
+    ///
+    /// This can be used to check how much local memory a program uses before setting a locals
+    /// budget via [Assembler::with_locals_budget()]. An empty path is returned if the program
+    /// does not invoke procedures with locals.
+    pub fn max_locals_path(&self, source: &str) -> Result<LocalsPath, AssemblyError> {
+        self.compile_with_locals_path(source, ProgramBody::Block, None, None)
+            .map(|(_, locals_path, _)| locals_path)
+    }
+
+    /// Compiles the provided source code and returns the digests which identify the compiled
+    /// program: the MAST root of the program, the MAST roots of its local procedures, and the
+    /// hash of the kernel (see [ProgramDigests]).
+    ///
+    /// # Errors
+    /// Returns an error if the source fails to compile.
+    pub fn program_digests(&self, source: &str) -> Result<ProgramDigests, AssemblyError> {
+        let (program, _, procedures) =
+            self.compile_with_locals_path(source, ProgramBody::Block, None, None)?;
+        let kernel_procedures = self
+            .kernel
+            .iter()
+            .filter(|(_, proc)| proc.is_export())
+            .map(|(label, proc)| (label.clone(), proc.code_root().hash()))
+            .collect();
+        Ok(ProgramDigests::new(&program, procedures, kernel_procedures))
+    }
+
+    /// Compiles the provided source code into a [Program], and returns the program together with
+    /// a log of the non-trivial decisions the assembler made while compiling it (see [AuditLog]).
+    ///
+    /// The log covers the program body, its local procedures, and all procedures of the modules
+    /// imported by the program (directly or indirectly); thus, modules in the module cache of
+    /// this assembler are parsed anew. Kernel procedures are compiled when the kernel is set, and
+    /// are not covered by the log.
+    ///
+    /// To determine which procedures were changed by optional transformations, the source is
+    /// compiled twice: once without optimizations and with the default push encoding, and once
+    /// with the settings of this assembler. Thus, compiling with an audit log takes about twice
+    /// as long as a regular compilation.
+    ///
+    /// # Errors
+    /// Returns an error if the source fails to compile.
+    pub fn compile_with_audit(&self, source: &str) -> Result<(Program, AuditLog), AssemblyError> {
+        let baseline = Self {
+            module_cache: Arc::new(ModuleCache::default()),
+            span_options: SpanOptions::default(),
+            ..self.clone()
+        };
+        let baseline_recorder = AuditRecorder::default();
+        baseline.compile_with_locals_path(
+            source,
+            ProgramBody::Block,
+            Some(&baseline_recorder),
+            None,
+        )?;
+
+        let assembler = Self {
+            module_cache: Arc::new(ModuleCache::default()),
+            ..self.clone()
+        };
+        let recorder = AuditRecorder::default();
+        let (program, ..) = assembler.compile_with_locals_path(
+            source,
+            ProgramBody::Block,
+            Some(&recorder),
+            None,
+        )?;
+
+        let mut log = recorder.into_log();
+        log.set_baseline(&baseline_recorder.into_log());
+        Ok((program, log))
+    }
+
+    /// Compiles the provided source code into a [Program], and finds the chain of procedure
+    /// invocations from the program body which allocates the largest number of locals. The
+    /// MAST roots of the local procedures of the program are returned as well, keyed by
+    /// procedure label. If an audit recorder is provided, decisions of the assembler are
+    /// reported to it.
+    fn compile_with_locals_path(
+        &self,
+        source: &str,
+        body: ProgramBody,
+        audit: Option<&AuditRecorder>,
+        warnings: Option<&WarningRecorder>,
+    ) -> Result<(Program, LocalsPath, BTreeMap<String, Digest>), AssemblyError> {
         let mut tokens = TokenStream::new(source)?;
-        let mut context = AssemblyContext::new();
+        let mut context = AssemblyContext::new(
+            self.unroll_budget,
+            self.split_threshold,
+            self.locals_budget,
+            self.span_options,
+            self.limits,
+        );
+        context.set_kernel_procs(self.kernel.clone());
+        context.set_extensions(self.extensions.clone());
+        context.set_audit_recorder(audit.cloned());
+        context.set_warning_recorder(warnings.cloned());
+        context.set_profiling(self.profiling);
+        for (name, &value) in self.constants.iter() {
+            context.add_constant(name.clone(), value);
+        }
+
+        // modules which are not in the cache are parsed into this map; the map is discarded at
+        // the end of the compilation
+        let mut modules = ModuleMap::new();
 
         // parse imported modules (if any), and add exported procedures from these modules to the
         // current context; since we are in the root context here, we initialize dependency chain
         // with an empty vector.
-        self.parse_imports(&mut tokens, &mut context, &mut Vec::new())?;
+        self.parse_imports(
+            &mut tokens,
+            &mut context,
+            &mut modules,
+            &mut Vec::new(),
+            audit,
+            warnings,
+        )?;
+
+        // parse constant declarations (if any), and add the constants to the current context
+        parse_constants(&mut tokens, &mut context)?;
+
+        // parse data segment declarations (if any), and add the segments to the current context;
+        // the segments are written into memory by the code executed before the program body
+        let data_init = parse_data_segments(&mut tokens, &mut context)?;
 
         // parse locally defined procedures (if any), and add these procedures to the current
         // context
+        let mut local_roots = BTreeMap::new();
         while let Some(token) = tokens.read() {
             let proc = match token.parts()[0] {
+                Token::PROC | Token::EXPORT if token.is_generic_proc() => {
+                    let proc = GenericProcedure::parse(&mut tokens, &context, false)?;
+                    context.add_generic_proc(proc);
+                    continue;
+                }
                 Token::PROC | Token::EXPORT => {
                     Procedure::parse(&mut tokens, &context, false, self.in_debug_mode)?
                 }
+                _ if token.is_annotation() => {
+                    Procedure::parse(&mut tokens, &context, false, self.in_debug_mode)?
+                }
                 _ => break,
             };
+            audit_procedure(audit, "", &proc);
+            local_roots.insert(proc.label().to_string(), proc.code_root().hash());
             context.add_local_proc(proc);
         }
 
@@ -96,36 +714,161 @@ impl Assembler {
         let next_token = tokens
             .read()
             .ok_or_else(|| AssemblyError::unexpected_eof(tokens.pos()))?;
-        if next_token.parts()[0] != Token::BEGIN {
+        if body == ProgramBody::Block && next_token.parts()[0] != Token::BEGIN {
             return Err(AssemblyError::unexpected_token(next_token, Token::BEGIN));
         }
 
-        // parse program body and return the resulting program
-        let program_root = parse_program(&mut tokens, &context, self.in_debug_mode)?;
-        Ok(Program::new(program_root))
+        // parse program body; the body spans all tokens between `begin` and the final `end`, or
+        // all remaining tokens if the body is not wrapped
+        let begin_pos = tokens.pos();
+        let (mut program_root, body_range) = match body {
+            ProgramBody::Block => {
+                let root = parse_program(&mut tokens, &context, self.in_debug_mode)?;
+                (root, begin_pos + 1..tokens.pos() - 1)
+            }
+            ProgramBody::Instructions => {
+                let root = parse_instructions(&mut tokens, &context, self.in_debug_mode)?;
+                (root, begin_pos..tokens.pos())
+            }
+        };
+        if !data_init.is_empty() {
+            program_root = combine_blocks(vec![CodeBlock::new_span(data_init), program_root]);
+        }
+        if let Some(audit) = audit {
+            audit.add_procedure(PROGRAM_BODY_NAME.to_string(), program_root.hash());
+        }
+
+        // make sure the locals allocated by invoked procedures fit into the budget
+        let locals_path =
+            LocalsPath::find_max(&mut tokens, body_range.start, body_range.end, &context);
+        let token = tokens.read_at(begin_pos).expect("no begin token");
+        context.check_locals_budget(token, &locals_path)?;
+
+        // the program carries the kernel together with the code of the kernel procedures and of
+        // the procedures invoked via `call` from the program or from the kernel
+        let kernel_hashes = self
+            .kernel
+            .values()
+            .map(|proc| proc.code_root().hash())
+            .collect::<Vec<_>>();
+        let mut cb_table = collect_callees(&program_root, &context);
+        for proc in self.kernel.values() {
+            cb_table.insert(proc.code_root().clone());
+            proc.callees()
+                .blocks()
+                .for_each(|block| cb_table.insert(block.clone()));
+        }
+        let kernel = Kernel::new(&kernel_hashes);
+        let program = Program::with_kernel(program_root, kernel, cb_table);
+
+        // imported modules parsed during this compilation do not need to be parsed again
+        self.module_cache.extend(&modules);
+
+        Ok((program, locals_path, local_roots))
+    }
+
+    /// Checks whether the provided source code is a valid library module, i.e., whether it
+    /// consists of imports, constants, and procedure declarations which compile successfully.
+    ///
+    /// This is useful to validate modules which are not yet a part of any library.
+    pub fn check_module(&self, source: &str) -> Result<(), AssemblyError> {
+        let mut modules = ModuleMap::new();
+        self.parse_module(source, "", &mut modules, &mut Vec::new(), None, None)
+    }
+
+    // CANONICALIZATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the canonical form of the provided program or module source.
+    ///
+    /// In the canonical form, comments are removed, instructions are formatted one per line,
+    /// constants are sorted by name and have their values inlined, and private procedures are
+    /// renamed deterministically in the order of their declaration. Thus, sources which differ
+    /// only in these aspects have the same canonical form, which makes it easy to compare
+    /// independently submitted sources claiming to compile to the same program.
+    ///
+    /// The canonical form of a program compiles to a program with the same hash as the original;
+    /// the canonical form of a module exports procedures with the same labels and MAST roots as
+    /// the original.
+    ///
+    /// # Errors
+    /// Returns an error if the source fails to compile.
+    ///
+    /// # Panics
+    /// Panics if the canonical form does not compile to the same program or module as the
+    /// original source.
+    pub fn canonicalize(&self, source: &str) -> Result<String, AssemblyError> {
+        if SourceOutline::parse(source).is_program() {
+            let program_hash = self.compile(source)?.hash();
+            let canonical_source = canonical::canonicalize(source)?;
+            let canonical_hash = self.compile(&canonical_source)?.hash();
+            assert!(
+                program_hash == canonical_hash,
+                "canonical form changed the program hash"
+            );
+            Ok(canonical_source)
+        } else {
+            let module_roots = self.get_module_roots(source)?;
+            let canonical_source = canonical::canonicalize(source)?;
+            let canonical_roots = self.get_module_roots(&canonical_source)?;
+            assert!(
+                module_roots == canonical_roots,
+                "canonical form changed exported procedures"
+            );
+            Ok(canonical_source)
+        }
+    }
+
+    /// Parses the provided module source and returns MAST roots of its exported procedures keyed
+    /// by procedure label.
+    fn get_module_roots(&self, source: &str) -> Result<BTreeMap<String, Digest>, AssemblyError> {
+        let mut modules = ModuleMap::new();
+        self.parse_module(source, "", &mut modules, &mut Vec::new(), None, None)?;
+        let roots = modules
+            .remove("")
+            .expect("module not parsed")
+            .into_iter()
+            .map(|(label, proc)| (label, proc.code_root().hash()))
+            .collect();
+        Ok(roots)
     }
 
     // IMPORT PARSERS
     // --------------------------------------------------------------------------------------------
 
+    /// Returns the source code of the module located at the specified path, looked up in the
+    /// standard library or in the library with the root namespace of the path.
+    fn get_module_source(&self, module_path: &str) -> Option<&str> {
+        let namespace = module_path.split(MODULE_PATH_DELIM).next()?;
+        if namespace == self.stdlib.root_ns() {
+            self.stdlib.get_module_source(module_path).ok()
+        } else {
+            let library = self.libraries.get(namespace)?;
+            library.get_module_source(module_path).ok()
+        }
+    }
+
     /// Parses `use` instructions from the token stream.
     ///
     /// For each `use` instructions, retrieves exported procedures from the specified module and
     /// inserts them into the provided context.
     ///
-    /// If a module specified by `use` instruction hasn't been parsed yet, parses it, and adds
-    /// the parsed module to `self.parsed_modules`.
+    /// If a module specified by `use` instruction is neither in the module cache nor in the
+    /// provided module map, parses it, and adds the parsed module to the module map.
     ///
     /// # Errors
     /// Returns an error if:
     /// - The `use` instruction is malformed.
     /// - A module specified by the `use` instruction could not be found.
     /// - Parsing the specified module results in an error.
-    fn parse_imports<'a>(
-        &'a self,
+    fn parse_imports(
+        &self,
         tokens: &mut TokenStream,
-        context: &mut AssemblyContext<'a>,
+        context: &mut AssemblyContext,
+        modules: &mut ModuleMap,
         dep_chain: &mut Vec<String>,
+        audit: Option<&AuditRecorder>,
+        warnings: Option<&WarningRecorder>,
     ) -> Result<(), AssemblyError> {
         // read tokens from the token stream until all `use` tokens are consumed
         while let Some(token) = tokens.read() {
@@ -133,6 +876,7 @@ impl Assembler {
                 Token::USE => {
                     // parse the `use` instruction to extract module path from it
                     let module_path = &token.parse_use()?;
+                    context.check_name_len(token, module_path)?;
 
                     // check if a module with the same path is currently being parsed somewhere up
                     // the chain; if it is, then we have a circular dependency.
@@ -146,29 +890,43 @@ impl Assembler {
 
                     // if the module hasn't been parsed yet, retrieve its source from the library
                     // and attempt to parse it; if the parsing is successful, this will also add
-                    // the parsed module to `self.parsed_modules`
-                    if !self.parsed_modules.contains_key(module_path) {
+                    // the parsed module to `modules`
+                    let cached_procs = self.module_cache.get(module_path);
+                    if cached_procs.is_none() && !modules.contains_key(module_path) {
                         let module_source =
-                            self.stdlib.get_module_source(module_path).map_err(|_| {
+                            self.get_module_source(module_path).ok_or_else(|| {
                                 AssemblyError::missing_import_source(token, module_path)
                             })?;
-                        self.parse_module(module_source, module_path, dep_chain)?;
+                        self.parse_module(
+                            module_source,
+                            module_path,
+                            modules,
+                            dep_chain,
+                            audit,
+                            warnings,
+                        )?;
                     }
 
                     // get procedures from the module at the specified path; we are guaranteed to
                     // not fail here because the above code block ensures that either there is a
                     // parsed module for the specified path, or the function returns with an error
-                    let module_procs = self
-                        .parsed_modules
-                        .get(module_path)
+                    let module_procs = cached_procs
+                        .as_ref()
+                        .or_else(|| modules.get(module_path))
                         .expect("no module procs");
 
                     // add all procedures to the current context; procedure labels are set to be
-                    // `last_part_of_module_path::procedure_name`. For example, `u256::add`.
+                    // `last_part_of_module_path::procedure_name`. For example, `u256::add`. Labels
+                    // collide if two imported modules have the same last path part (or the same
+                    // module is imported twice).
                     for proc in module_procs.values() {
                         let path_parts = module_path.split(MODULE_PATH_DELIM).collect::<Vec<_>>();
-                        let num_parts = path_parts.len();
-                        context.add_imported_proc(path_parts[num_parts - 1], proc);
+                        let prefix = path_parts[path_parts.len() - 1];
+                        let label = format!("{}{}{}", prefix, MODULE_PATH_DELIM, proc.label());
+                        if context.contains_proc(&label) {
+                            return Err(AssemblyError::duplicate_proc_label(token, &label));
+                        }
+                        context.add_imported_proc(prefix, proc.clone());
                     }
 
                     // consume the `use` token and pop the current module of the dependency chain
@@ -182,32 +940,106 @@ impl Assembler {
         Ok(())
     }
 
+    /// Applies the specified function to the procedures exported from the library module located
+    /// at the specified path; the module is taken from the module cache of this assembler, or
+    /// parsed and added to the cache if it is not in the cache.
+    fn with_module_procs<T>(
+        &self,
+        module_path: &str,
+        f: impl FnOnce(&ProcMap) -> T,
+    ) -> Result<T, AssemblyError> {
+        if let Some(module_procs) = self.module_cache.get(module_path) {
+            return Ok(f(&module_procs));
+        }
+
+        let module_source = self
+            .get_module_source(module_path)
+            .ok_or_else(|| AssemblyError::missing_module_source(module_path))?;
+        let modules = self.parse_library_module(module_source, module_path)?;
+        self.module_cache.extend(&modules);
+        Ok(f(&modules[module_path]))
+    }
+
+    /// Parses the module with the specified source code at the specified path, and returns the
+    /// parsed module together with all of its dependencies which are not in the module cache.
+    fn parse_library_module(
+        &self,
+        module_source: &str,
+        module_path: &str,
+    ) -> Result<ModuleMap, AssemblyError> {
+        let mut modules = ModuleMap::new();
+        let mut dep_chain = vec![module_path.to_string()];
+        self.parse_module(
+            module_source,
+            module_path,
+            &mut modules,
+            &mut dep_chain,
+            None,
+            None,
+        )?;
+        Ok(modules)
+    }
+
     /// Parses a set of exported procedures from the specified source code and adds these
-    /// procedures to `self.parsed_modules` using the specified path as the key.
-    #[allow(clippy::cast_ref_to_mut)]
+    /// procedures to the provided module map using the specified path as the key. If an audit
+    /// recorder is provided, decisions of the assembler are reported to it.
     fn parse_module(
         &self,
         source: &str,
         path: &str,
+        modules: &mut ModuleMap,
         dep_chain: &mut Vec<String>,
+        audit: Option<&AuditRecorder>,
+        warnings: Option<&WarningRecorder>,
     ) -> Result<(), AssemblyError> {
         let mut tokens = TokenStream::new(source)?;
-        let mut context = AssemblyContext::new();
+        let mut context = AssemblyContext::new(
+            self.unroll_budget,
+            self.split_threshold,
+            self.locals_budget,
+            self.span_options,
+            self.limits,
+        );
+        context.set_extensions(self.extensions.clone());
+        context.set_audit_recorder(audit.cloned());
+        context.set_warning_recorder(warnings.cloned());
+        context.set_profiling(self.profiling);
+        context.set_module_path(path);
 
         // parse imported modules (if any), and add exported procedures from these modules to
         // the current context
-        self.parse_imports(&mut tokens, &mut context, dep_chain)?;
+        self.parse_imports(
+            &mut tokens,
+            &mut context,
+            modules,
+            dep_chain,
+            audit,
+            warnings,
+        )?;
+
+        // parse constant declarations (if any), and add the constants to the current context
+        parse_constants(&mut tokens, &mut context)?;
 
         // parse procedures defined in the module, and add these procedures to the current
         // context
         while let Some(token) = tokens.read() {
-            let proc = match token.parts()[0] {
+            match token.parts()[0] {
+                Token::PROC | Token::EXPORT if token.is_generic_proc() => {
+                    let proc = GenericProcedure::parse(&mut tokens, &context, true)?;
+                    context.add_generic_proc(proc);
+                }
                 Token::PROC | Token::EXPORT => {
-                    Procedure::parse(&mut tokens, &context, true, self.in_debug_mode)?
+                    let proc = Procedure::parse(&mut tokens, &context, true, self.in_debug_mode)?;
+                    audit_procedure(audit, path, &proc);
+                    context.add_local_proc(proc);
+                }
+                _ if token.is_annotation() => {
+                    let proc = Procedure::parse(&mut tokens, &context, true, self.in_debug_mode)?;
+                    audit_procedure(audit, path, &proc);
+                    context.add_local_proc(proc);
                 }
                 _ => break,
-            };
-            context.add_local_proc(proc);
+            }
         }
 
         // make sure there are no dangling instructions after all procedures have been read
@@ -220,13 +1052,8 @@ impl Assembler {
         let mut module_procs = context.into_local_procs();
         module_procs.retain(|_, p| p.is_export());
 
-        // insert exported procedures into `self.parsed_procedures`
-        // TODO: figure out how to do this using interior mutability
-        unsafe {
-            let path = path.to_string();
-            let mutable_self = &mut *(self as *const _ as *mut Assembler);
-            mutable_self.parsed_modules.insert(path, module_procs);
-        }
+        // insert exported procedures into the module map
+        modules.insert(path.to_string(), module_procs);
 
         Ok(())
     }
@@ -239,9 +1066,147 @@ impl Default for Assembler {
     }
 }
 
+impl ModuleParser for Assembler {
+    /// Returns descriptions of the procedures exported from the module with the specified path
+    /// and source code, sorted by procedure label. Modules imported by the module are resolved
+    /// against the libraries of this assembler.
+    ///
+    /// # Errors
+    /// Returns an error if parsing the module or any of its dependencies fails.
+    fn parse_exported_procedures(
+        &self,
+        module_path: &str,
+        source: &str,
+    ) -> Result<Vec<ProcedureInfo>, LibraryError> {
+        self.parse_library_module(source, module_path)
+            .map(|modules| {
+                modules[module_path]
+                    .values()
+                    .map(|proc| describe_procedure(proc))
+                    .collect()
+            })
+            .map_err(|err| {
+                LibraryError::ModuleParsingFailed(module_path.to_string(), err.to_string())
+            })
+    }
+}
+
+/// Returns a description of the specified exported procedure.
+fn describe_procedure(proc: &Procedure) -> ProcedureInfo {
+    ProcedureInfo::new(
+        proc.label().to_string(),
+        proc.docs().map(String::from),
+        proc.code_root().hash(),
+    )
+}
+
+// SOURCE LOCATIONS
+// ================================================================================================
+
+/// Returns the line and column (both 1-based) of the token at the specified position in the
+/// provided source code, or None if the source contains fewer tokens.
+///
+/// Token positions are reported by [AssemblyError::step()], and thus this function can be used to
+/// point to the location of an error in the source code. The column is measured in characters.
+pub fn locate_token(source: &str, pos: usize) -> Option<(usize, usize)> {
+    TokenStream::locate(source, pos)
+}
+
 // PARSERS
 // ================================================================================================
 
+/// Parses constant declarations from the token stream and adds the declared constants to the
+/// provided context. Constants are visible only within the program or module declaring them.
+///
+/// # Errors
+/// Returns an error if a constant declaration is malformed, its value cannot be evaluated, its
+/// name is longer than the limits of the context allow, or a constant with the same name has
+/// already been declared.
+fn parse_constants(
+    tokens: &mut TokenStream,
+    context: &mut AssemblyContext,
+) -> Result<(), AssemblyError> {
+    while let Some(token) = tokens.read() {
+        if token.parts()[0] != Token::CONST {
+            break;
+        }
+
+        let (name, value) = parse_constant(token, context)?;
+        context.check_name_len(token, &name)?;
+        if !context.add_constant(name.clone(), value) {
+            return Err(AssemblyError::duplicate_const(token, &name));
+        }
+        tokens.advance();
+    }
+
+    Ok(())
+}
+
+/// Parses data segment declarations of the form `data.NAME=[EXPR,EXPR,...]`, adds the segments
+/// to the provided context, and returns the operations which write the segments into memory.
+fn parse_data_segments(
+    tokens: &mut TokenStream,
+    context: &mut AssemblyContext,
+) -> Result<Vec<Operation>, AssemblyError> {
+    let mut ops = Vec::new();
+    while let Some(token) = tokens.read() {
+        if token.parts()[0] != Token::DATA {
+            break;
+        }
+
+        let (name, values) = parse_data_segment(token, context)?;
+        context.check_name_len(token, &name)?;
+        let addr = context
+            .add_data_segment(name.clone(), values.len())
+            .ok_or_else(|| AssemblyError::duplicate_data(token, &name))?;
+
+        // every element is written into the first element of the word at its address
+        for (i, value) in values.into_iter().enumerate() {
+            ops.push(Operation::Push(value));
+            ops.push(Operation::Push(Felt::new(addr + i as u64)));
+            ops.push(Operation::MStore);
+            ops.push(Operation::Drop);
+        }
+        tokens.advance();
+    }
+
+    Ok(ops)
+}
+
+/// Reports the specified procedure of the module at the specified path to the audit recorder, if
+/// one is provided; procedures of the program itself are reported under their labels, and
+/// procedures of modules under their full paths.
+fn audit_procedure(audit: Option<&AuditRecorder>, module_path: &str, proc: &Procedure) {
+    if let Some(audit) = audit {
+        let name = if module_path.is_empty() {
+            proc.label().to_string()
+        } else {
+            format!("{}{}{}", module_path, MODULE_PATH_DELIM, proc.label())
+        };
+        audit.add_procedure(name, proc.code_root().hash());
+    }
+}
+
+/// Parses a sequence of instructions which spans all remaining tokens of the stream.
+///
+/// # Errors
+/// Returns an error if the instructions fail to parse, or if they are followed by an `end` or an
+/// `else` which does not close a block opened by the instructions.
+fn parse_instructions(
+    tokens: &mut TokenStream,
+    context: &AssemblyContext,
+    in_debug_mode: bool,
+) -> Result<CodeBlock, AssemblyError> {
+    let root = parse_code_blocks(tokens, context, 0, in_debug_mode)?;
+
+    // parsing of blocks stops only at the end of the stream or at a dangling `end` or `else`
+    match tokens.read() {
+        None => Ok(root),
+        Some(token) if token.parts()[0] == Token::ELSE => Err(AssemblyError::dangling_else(token)),
+        Some(token) => Err(AssemblyError::dangling_ops_after_program(token)),
+    }
+}
+
 /// TODO: add comments
 fn parse_program(
     tokens: &mut TokenStream,
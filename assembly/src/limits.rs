@@ -0,0 +1,83 @@
+// ASSEMBLY LIMITS
+// ================================================================================================
+
+/// Default maximum number of procedures (including generic procedures) declared in a single
+/// program or module.
+pub const DEFAULT_MAX_PROCEDURES: usize = 1 << 16;
+
+/// Default maximum number of locals declared by a single procedure. This is the size of the local
+/// memory of a context, and thus, the default does not restrict the number of locals.
+pub const DEFAULT_MAX_PROC_LOCALS: u32 = u32::MAX;
+
+/// Default maximum length of procedure labels, constant names, data segment names, and module
+/// paths.
+pub const DEFAULT_MAX_NAME_LEN: usize = 1024;
+
+/// Limits on the size of programs and modules accepted by the assembler.
+///
+/// The limits guard against sources which would otherwise consume excessive resources during
+/// compilation; exceeding any of the limits results in an error identifying the offending
+/// declaration. The defaults are set well above the sizes of hand-written code so that modules
+/// generated by compilers targeting Miden assembly can be compiled without adjusting the limits;
+/// tools which compile untrusted sources may lower them.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct AssemblyLimits {
+    max_procedures: usize,
+    max_proc_locals: u32,
+    max_name_len: usize,
+}
+
+impl AssemblyLimits {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+
+    /// Sets the maximum number of procedures (including generic procedures) which can be declared
+    /// in a single program or module.
+    pub fn with_max_procedures(mut self, max_procedures: usize) -> Self {
+        self.max_procedures = max_procedures;
+        self
+    }
+
+    /// Sets the maximum number of locals which can be declared by a single procedure.
+    pub fn with_max_proc_locals(mut self, max_proc_locals: u32) -> Self {
+        self.max_proc_locals = max_proc_locals;
+        self
+    }
+
+    /// Sets the maximum length of procedure labels, constant names, data segment names, and
+    /// module paths.
+    pub fn with_max_name_len(mut self, max_name_len: usize) -> Self {
+        self.max_name_len = max_name_len;
+        self
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the maximum number of procedures which can be declared in a single program or
+    /// module.
+    pub fn max_procedures(&self) -> usize {
+        self.max_procedures
+    }
+
+    /// Returns the maximum number of locals which can be declared by a single procedure.
+    pub fn max_proc_locals(&self) -> u32 {
+        self.max_proc_locals
+    }
+
+    /// Returns the maximum length of procedure labels, constant names, data segment names, and
+    /// module paths.
+    pub fn max_name_len(&self) -> usize {
+        self.max_name_len
+    }
+}
+
+impl Default for AssemblyLimits {
+    fn default() -> Self {
+        Self {
+            max_procedures: DEFAULT_MAX_PROCEDURES,
+            max_proc_locals: DEFAULT_MAX_PROC_LOCALS,
+            max_name_len: DEFAULT_MAX_NAME_LEN,
+        }
+    }
+}
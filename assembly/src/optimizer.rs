@@ -0,0 +1,391 @@
+use vm_core::{
+    code_blocks::OP_GROUP_SIZE, utils::collections::Vec, DecoratorList, Felt, FieldElement,
+    Operation, StarkField,
+};
+
+// OPTIMIZATION LEVEL
+// ================================================================================================
+
+/// Specifies which peephole optimizations the assembler applies to span blocks.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum OptLevel {
+    /// Operations are emitted exactly as they are specified by the source.
+    #[default]
+    None,
+    /// Redundant sequences which do not affect the execution of a program are removed:
+    /// - pushing, or duplicating, a value which is then dropped (e.g., `push.0 drop`, `dup.0 drop`).
+    /// - `swap swap`, and `movup.n movdn.n` or `movdn.n movup.n` pairs.
+    /// - arithmetic on immediate values, which is folded into a single immediate value (e.g.,
+    ///   `push.2 push.3 add` is compiled as `push.5`, and `add.2 add.3` as `add.5`).
+    Basic,
+    /// In addition to the basic optimizations, `not not` pairs are removed. A program optimized
+    /// in this way no longer fails when a `not` instruction is applied to a non-binary value.
+    Aggressive,
+}
+
+// PUSH ENCODING
+// ================================================================================================
+
+/// Specifies how the assembler encodes constants which are pushed onto the stack.
+///
+/// A constant can be pushed onto the stack with a PUSH operation, or composed from operations
+/// without immediate values: PAD pushes 0, and INCR and NEG turn it into small positive and
+/// negative values. Every operation takes one VM cycle and one operation slot in a span block;
+/// the immediate value of a PUSH operation takes an entire operation group (i.e., 9 slots) in
+/// addition to that. Thus, PUSH operations are the fastest way to push constants, while composed
+/// sequences make span blocks smaller (and may save the cycles needed to start new operation
+/// batches).
+///
+/// The VM does not provide operations which push multiple values at once. Instead, unless the
+/// default encoding is used, a constant which is pushed right after the same constant is encoded
+/// as DUP0, which is never more expensive than encoding the constant again.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum PushEncoding {
+    /// 0 is encoded as PAD, 1 as PAD INCR, and all other values as PUSH.
+    #[default]
+    Default,
+    /// Constants are encoded with the fewest VM cycles; ties are broken by the number of
+    /// operation slots.
+    MinCycles,
+    /// Constants are encoded with the fewest operation slots; ties are broken by the number of
+    /// VM cycles.
+    MinSize,
+}
+
+// OPTIMIZATIONS
+// ================================================================================================
+
+/// A kind of transformation which the assembler can apply to span blocks.
+///
+/// Optimizations are applied according to the optimization level and the push encoding of the
+/// assembler; individual optimizations can be disabled via [Assembler::without_optimization()]
+/// (crate::Assembler::without_optimization).
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Optimization {
+    /// Values which are dropped right after they were pushed or duplicated are not put onto the
+    /// stack (e.g., `push.0 drop` and `dup.0 drop` are removed).
+    DeadValues,
+    /// Stack manipulations which cancel each other out are removed (i.e., `swap swap`, and
+    /// `movup.n movdn.n` or `movdn.n movup.n` pairs).
+    CancellingMoves,
+    /// `not not` pairs are removed; applied only at the aggressive optimization level.
+    DoubleNot,
+    /// Arithmetic on immediate values is folded into a single immediate value (e.g.,
+    /// `push.2 push.3 add` is compiled as `push.5`).
+    ConstantFolding,
+    /// Adding zero and multiplying by one is removed.
+    ArithmeticIdentities,
+    /// Constants are encoded as specified by the push encoding rather than by the default one.
+    ConstantEncoding,
+}
+
+impl Optimization {
+    /// Returns a short name of this optimization.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::DeadValues => "dead_values",
+            Self::CancellingMoves => "cancelling_moves",
+            Self::DoubleNot => "double_not",
+            Self::ConstantFolding => "constant_folding",
+            Self::ArithmeticIdentities => "arithmetic_identities",
+            Self::ConstantEncoding => "constant_encoding",
+        }
+    }
+}
+
+/// Settings which control how span blocks are optimized.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct SpanOptions {
+    pub level: OptLevel,
+    pub encoding: PushEncoding,
+    disabled: u8,
+}
+
+impl SpanOptions {
+    /// Disables the specified optimization.
+    pub fn disable(&mut self, optimization: Optimization) {
+        self.disabled |= 1 << optimization as u8;
+    }
+
+    /// Returns true if the specified optimization is applied at the level of these settings and
+    /// was not disabled.
+    fn is_enabled(&self, optimization: Optimization) -> bool {
+        let min_level = match optimization {
+            Optimization::DoubleNot => OptLevel::Aggressive,
+            Optimization::ConstantEncoding => OptLevel::None,
+            _ => OptLevel::Basic,
+        };
+        self.level >= min_level && self.disabled & (1 << optimization as u8) == 0
+    }
+
+    /// Returns the push encoding which is in effect with these settings.
+    fn encoding(&self) -> PushEncoding {
+        if self.is_enabled(Optimization::ConstantEncoding) {
+            self.encoding
+        } else {
+            PushEncoding::Default
+        }
+    }
+}
+
+// SPAN OPTIMIZER
+// ================================================================================================
+
+/// Applies peephole optimizations to the operations of a span block, and encodes constants pushed
+/// onto the stack as specified by the push encoding. Every applied optimization is appended to
+/// `applied`.
+///
+/// Decorators are attached to specific operations, and thus operations are never moved across
+/// decorators: sequences of operations between decorators are optimized independently, and
+/// decorator positions are adjusted to the optimized sequences.
+pub fn optimize_span(
+    ops: Vec<Operation>,
+    decorators: DecoratorList,
+    options: SpanOptions,
+    applied: &mut Vec<Optimization>,
+) -> (Vec<Operation>, DecoratorList) {
+    if options.level == OptLevel::None && options.encoding() == PushEncoding::Default {
+        return (ops, decorators);
+    }
+
+    let mut result = Vec::with_capacity(ops.len());
+    let mut result_decorators = DecoratorList::new();
+    let mut segment_start = 0;
+    for (pos, decorator) in decorators {
+        if pos > segment_start {
+            optimize_segment(&ops[segment_start..pos], &mut result, options, applied);
+            segment_start = pos;
+        }
+        result_decorators.push((result.len(), decorator));
+    }
+    optimize_segment(&ops[segment_start..], &mut result, options, applied);
+
+    (result, result_decorators)
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Optimizes the specified sequence of operations and appends the result to `result`.
+///
+/// While the sequence is optimized, all constants are represented by PUSH operations; they are
+/// encoded as specified by the push encoding when the result is written out.
+///
+/// Without optimizations, the sequence is left as is, except that constants encoded by the
+/// assembler (i.e., PAD for 0, and PAD INCR for 1) are re-encoded.
+fn optimize_segment(
+    ops: &[Operation],
+    result: &mut Vec<Operation>,
+    options: SpanOptions,
+    applied: &mut Vec<Optimization>,
+) {
+    let mut optimized = Vec::with_capacity(ops.len());
+    for &op in ops {
+        match (op, optimized.as_slice()) {
+            (Operation::Pad, _) => optimized.push(Operation::Push(Felt::ZERO)),
+            (Operation::Incr, [.., Operation::Push(value)])
+                if options.level == OptLevel::None && *value == Felt::ZERO =>
+            {
+                optimized.pop();
+                optimized.push(Operation::Push(Felt::ONE));
+            }
+            (op, _) if options.level == OptLevel::None => optimized.push(op),
+            (op, _) => append_op(&mut optimized, op, options, applied),
+        }
+    }
+
+    let encoding = options.encoding();
+    let mut prev = None;
+    for op in optimized {
+        match op {
+            Operation::Push(value) => {
+                let start = result.len();
+                if encoding != PushEncoding::Default && value != Felt::ZERO && prev == Some(op) {
+                    result.push(Operation::Dup0);
+                } else {
+                    encode_push(value, encoding, result);
+                }
+
+                // record the encoding if it differs from the default one
+                let mut default = Vec::new();
+                encode_push(value, PushEncoding::Default, &mut default);
+                if result[start..] != default[..] {
+                    applied.push(Optimization::ConstantEncoding);
+                }
+            }
+            op => result.push(op),
+        }
+        prev = Some(op);
+    }
+}
+/// Appends operations which push the specified value onto the stack to `result`.
+///
+/// Besides PUSH, the candidate encodings are PAD followed by up to 8 INCR operations (for values
+/// between 0 and 8), optionally followed by NEG (for values between -8 and -1); the cheapest one
+/// according to the push encoding is chosen.
+fn encode_push(value: Felt, encoding: PushEncoding, result: &mut Vec<Operation>) {
+    const MAX_INCRS: u64 = 8;
+
+    if value == Felt::ZERO {
+        result.push(Operation::Pad);
+        return;
+    }
+
+    let (num_incrs, negate) = if value.as_int() <= MAX_INCRS {
+        (value.as_int(), false)
+    } else if (-value).as_int() <= MAX_INCRS {
+        ((-value).as_int(), true)
+    } else {
+        (0, false)
+    };
+
+    let use_push = match encoding {
+        PushEncoding::Default => value != Felt::ONE,
+        _ if num_incrs == 0 => true,
+        PushEncoding::MinCycles => true,
+        PushEncoding::MinSize => {
+            // PAD, the INCRs and NEG take a slot each; PUSH takes a slot and an entire group
+            let composed_slots = 1 + num_incrs as usize + negate as usize;
+            composed_slots > OP_GROUP_SIZE
+        }
+    };
+
+    if use_push {
+        result.push(Operation::Push(value));
+    } else {
+        result.push(Operation::Pad);
+        for _ in 0..num_incrs {
+            result.push(Operation::Incr);
+        }
+        if negate {
+            result.push(Operation::Neg);
+        }
+    }
+}
+
+/// Appends the specified operation to the optimized sequence, and rewrites the tail of the
+/// sequence if the operation together with the tail forms a redundant sequence. Every applied
+/// optimization is appended to `applied`.
+///
+/// Since the tail of the sequence has already been optimized, removing a pair of operations may
+/// expose another pair which can be removed, e.g., `swap dup.0 drop swap` is removed entirely.
+fn append_op(
+    ops: &mut Vec<Operation>,
+    op: Operation,
+    options: SpanOptions,
+    applied: &mut Vec<Optimization>,
+) {
+    use Operation::*;
+    use Optimization::*;
+    let len = ops.len();
+    let enabled = |optimization| options.is_enabled(optimization);
+    let optimization = match (op, ops.as_slice()) {
+        // ----- values which are dropped right after they were put onto the stack ---------------
+        (Drop, [.., last]) if enabled(DeadValues) && (matches!(last, Push(_)) || is_dup(last)) => {
+            ops.pop();
+            DeadValues
+        }
+
+        // ----- stack manipulations which cancel each other out ----------------------------------
+        (Swap, [.., Swap]) if enabled(CancellingMoves) => {
+            ops.pop();
+            CancellingMoves
+        }
+        (op, [.., last]) if enabled(CancellingMoves) && is_inverse_move(last, &op) => {
+            ops.pop();
+            CancellingMoves
+        }
+        (Not, [.., Not]) if enabled(DoubleNot) => {
+            ops.pop();
+            DoubleNot
+        }
+
+        // ----- arithmetic on immediate values ---------------------------------------------------
+        (Incr, [.., Push(a)]) if enabled(ConstantFolding) => {
+            let value = *a + Felt::ONE;
+            ops[len - 1] = Push(value);
+            ConstantFolding
+        }
+        (Incr, [.., Push(a), Add]) if enabled(ConstantFolding) => {
+            let value = *a + Felt::ONE;
+            ops[len - 2] = Push(value);
+            ConstantFolding
+        }
+        (Neg, [.., Push(a)]) if enabled(ConstantFolding) => {
+            let value = -*a;
+            ops[len - 1] = Push(value);
+            ConstantFolding
+        }
+        (Add | Mul, [.., Push(a), Push(b)]) if enabled(ConstantFolding) => {
+            let value = apply(op, *a, *b);
+            ops.truncate(len - 2);
+            ops.push(Push(value));
+            ConstantFolding
+        }
+        (Add | Mul, [.., Push(a), prev, Push(b)]) if enabled(ConstantFolding) && *prev == op => {
+            let value = apply(op, *a, *b);
+            ops.truncate(len - 3);
+            ops.push(Push(value));
+            applied.push(ConstantFolding);
+            append_op(ops, op, options, applied);
+            return;
+        }
+
+        // ----- adding zero and multiplying by one -----------------------------------------------
+        (Add, [.., Push(a)]) if enabled(ArithmeticIdentities) && *a == Felt::ZERO => {
+            ops.pop();
+            ArithmeticIdentities
+        }
+        (Mul, [.., Push(a)]) if enabled(ArithmeticIdentities) && *a == Felt::ONE => {
+            ops.pop();
+            ArithmeticIdentities
+        }
+
+        _ => {
+            ops.push(op);
+            return;
+        }
+    };
+    applied.push(optimization);
+}
+
+/// Returns the result of applying the specified binary operation to the provided values.
+fn apply(op: Operation, a: Felt, b: Felt) -> Felt {
+    match op {
+        Operation::Add => a + b,
+        Operation::Mul => a * b,
+        _ => unreachable!("not a binary arithmetic operation: {}", op),
+    }
+}
+
+/// Returns true if the specified operation duplicates a stack item.
+fn is_dup(op: &Operation) -> bool {
+    use Operation::*;
+    matches!(
+        op,
+        Dup0 | Dup1 | Dup2 | Dup3 | Dup4 | Dup5 | Dup6 | Dup7 | Dup9 | Dup11 | Dup13 | Dup15
+    )
+}
+
+/// Returns true if executing the second operation right after the first one leaves the stack
+/// unchanged, i.e., if the operations are MOVUP and MOVDN (in any order) with the same index.
+fn is_inverse_move(first: &Operation, second: &Operation) -> bool {
+    use Operation::*;
+    matches!(
+        (first, second),
+        (MovUp2, MovDn2)
+            | (MovUp3, MovDn3)
+            | (MovUp4, MovDn4)
+            | (MovUp5, MovDn5)
+            | (MovUp6, MovDn6)
+            | (MovUp7, MovDn7)
+            | (MovUp8, MovDn8)
+            | (MovDn2, MovUp2)
+            | (MovDn3, MovUp3)
+            | (MovDn4, MovUp4)
+            | (MovDn5, MovUp5)
+            | (MovDn6, MovUp6)
+            | (MovDn7, MovUp7)
+            | (MovDn8, MovUp8)
+    )
+}
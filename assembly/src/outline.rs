@@ -0,0 +1,301 @@
+use super::{String, ToString, Token, TokenStream, Vec};
+
+// SOURCE OUTLINE
+// ================================================================================================
+
+/// Declarations and procedure references found in a Miden assembly source.
+///
+/// An outline is built by scanning the source token by token without compiling it, and thus it
+/// can be built for sources which contain errors: malformed declarations and invocations are
+/// skipped. This makes outlines suitable for editor tooling (e.g., listing document symbols or
+/// resolving definitions).
+///
+/// All positions in an outline are token positions which can be converted into source locations
+/// via [locate_token()](super::locate_token).
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SourceOutline {
+    imports: Vec<ImportDecl>,
+    constants: Vec<ConstantDecl>,
+    procedures: Vec<ProcedureDecl>,
+    invocations: Vec<ProcedureRef>,
+    is_program: bool,
+}
+
+impl SourceOutline {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Returns an outline of the provided source.
+    pub fn parse(source: &str) -> Self {
+        let mut outline = Self::default();
+        let mut tokens = match TokenStream::new(source) {
+            Ok(tokens) => tokens,
+            Err(_) => return outline,
+        };
+
+        // depth of nested blocks within the procedure currently being scanned; 0 when outside of
+        // a procedure
+        let mut depth = 0;
+        // position of the first annotation preceding the current procedure header; doc comments
+        // of annotated procedures are attached to the annotations
+        let mut annotation_pos = None;
+        while let Some(token) = tokens.read() {
+            match token.parts()[0] {
+                _ if depth == 0 && token.is_annotation() => {
+                    annotation_pos.get_or_insert(token.pos());
+                }
+                Token::USE => {
+                    if let Ok(path) = token.parse_use() {
+                        outline.imports.push(ImportDecl {
+                            path,
+                            pos: token.pos(),
+                        });
+                    }
+                }
+                Token::CONST => {
+                    if let Ok((name, _)) = token.parse_const() {
+                        outline.constants.push(ConstantDecl {
+                            name,
+                            pos: token.pos(),
+                        });
+                    }
+                }
+                Token::PROC | Token::EXPORT => {
+                    depth = 1;
+                    let docs_pos = annotation_pos.take().unwrap_or_else(|| token.pos());
+                    if let Ok((label, params, num_locals, is_export)) = token.parse_proc() {
+                        outline.procedures.push(ProcedureDecl {
+                            label,
+                            params,
+                            num_locals,
+                            is_export,
+                            docs: tokens.doc_comment(docs_pos).map(String::from),
+                            pos: token.pos(),
+                            end_pos: None,
+                        });
+                    }
+                }
+                Token::BEGIN => {
+                    depth = 0;
+                    outline.is_program = true;
+                }
+                Token::IF | Token::WHILE | Token::REPEAT if depth > 0 => depth += 1,
+                Token::END if depth > 0 => {
+                    depth -= 1;
+                    if depth == 0 {
+                        if let Some(proc) = outline.procedures.last_mut() {
+                            proc.end_pos = Some(token.pos());
+                        }
+                    }
+                }
+                Token::EXEC => {
+                    if let Ok((label, _)) = token.parse_exec() {
+                        outline.invocations.push(ProcedureRef {
+                            label,
+                            pos: token.pos(),
+                        });
+                    }
+                }
+                Token::CALL => {
+                    if let Ok(label) = token.parse_call() {
+                        outline.invocations.push(ProcedureRef {
+                            label,
+                            pos: token.pos(),
+                        });
+                    }
+                }
+                _ => (),
+            }
+            tokens.advance();
+        }
+
+        outline
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns modules imported via `use` instructions.
+    pub fn imports(&self) -> &[ImportDecl] {
+        &self.imports
+    }
+
+    /// Returns constants declared via `const` instructions.
+    pub fn constants(&self) -> &[ConstantDecl] {
+        &self.constants
+    }
+
+    /// Returns procedures declared in the source, in the order of declaration.
+    pub fn procedures(&self) -> &[ProcedureDecl] {
+        &self.procedures
+    }
+
+    /// Returns procedure invocations (i.e., `exec` and `call` instructions) in the source.
+    pub fn invocations(&self) -> &[ProcedureRef] {
+        &self.invocations
+    }
+
+    /// Returns `true` if the source contains a program body (i.e., a `begin` block), and `false`
+    /// if the source looks like a library module.
+    pub fn is_program(&self) -> bool {
+        self.is_program
+    }
+
+    /// Returns a procedure declared in the source with the specified label, if any.
+    pub fn get_procedure(&self, label: &str) -> Option<&ProcedureDecl> {
+        self.procedures.iter().find(|proc| proc.label == label)
+    }
+
+    /// Returns an import whose module path ends with the specified name (e.g., `u64` for
+    /// `std::math::u64`), if any. This is the name via which the imported procedures are
+    /// invoked.
+    pub fn get_import(&self, name: &str) -> Option<&ImportDecl> {
+        self.imports.iter().find(|import| import.name() == name)
+    }
+}
+
+// IMPORT DECLARATION
+// ================================================================================================
+
+/// A module import declared via a `use` instruction.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ImportDecl {
+    path: String,
+    pos: usize,
+}
+
+impl ImportDecl {
+    /// Returns the full path of the imported module (e.g., `std::math::u64`).
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Returns the last component of the module path (e.g., `u64` for `std::math::u64`).
+    pub fn name(&self) -> &str {
+        self.path.rsplit("::").next().unwrap_or(&self.path)
+    }
+
+    /// Returns the position of the `use` token.
+    pub fn pos(&self) -> usize {
+        self.pos
+    }
+}
+
+// CONSTANT DECLARATION
+// ================================================================================================
+
+/// A constant declared via a `const` instruction.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ConstantDecl {
+    name: String,
+    pos: usize,
+}
+
+impl ConstantDecl {
+    /// Returns the name of the constant.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the position of the `const` token.
+    pub fn pos(&self) -> usize {
+        self.pos
+    }
+}
+
+// PROCEDURE DECLARATION
+// ================================================================================================
+
+/// A procedure declared via a `proc` or an `export` instruction.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProcedureDecl {
+    label: String,
+    params: Vec<String>,
+    num_locals: u32,
+    is_export: bool,
+    docs: Option<String>,
+    pos: usize,
+    end_pos: Option<usize>,
+}
+
+impl ProcedureDecl {
+    /// Returns the label of the procedure.
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    /// Returns names of generic parameters of the procedure; empty for regular procedures.
+    pub fn params(&self) -> &[String] {
+        &self.params
+    }
+
+    /// Returns the number of locals declared by the procedure.
+    pub fn num_locals(&self) -> u32 {
+        self.num_locals
+    }
+
+    /// Returns `true` if the procedure is exported.
+    pub fn is_export(&self) -> bool {
+        self.is_export
+    }
+
+    /// Returns documentation of the procedure, if it was declared with doc comments.
+    pub fn docs(&self) -> Option<&str> {
+        self.docs.as_deref()
+    }
+
+    /// Returns the position of the procedure header token.
+    pub fn pos(&self) -> usize {
+        self.pos
+    }
+
+    /// Returns the position of the `end` token terminating the procedure, or None if the
+    /// procedure is not terminated.
+    pub fn end_pos(&self) -> Option<usize> {
+        self.end_pos
+    }
+
+    /// Returns the declaration of the procedure as it would be written in the source, e.g.
+    /// `export.foo.2` or `proc.bar<N>`.
+    pub fn signature(&self) -> String {
+        let mut signature = String::from(if self.is_export {
+            Token::EXPORT
+        } else {
+            Token::PROC
+        });
+        signature.push('.');
+        signature.push_str(&self.label);
+        if !self.params.is_empty() {
+            signature.push('<');
+            signature.push_str(&self.params.join(","));
+            signature.push('>');
+        }
+        if self.num_locals > 0 {
+            signature.push('.');
+            signature.push_str(&self.num_locals.to_string());
+        }
+        signature
+    }
+}
+
+// PROCEDURE REFERENCE
+// ================================================================================================
+
+/// A procedure invocation via an `exec` or a `call` instruction.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProcedureRef {
+    label: String,
+    pos: usize,
+}
+
+impl ProcedureRef {
+    /// Returns the label of the invoked procedure as written in the source; labels of imported
+    /// procedures are qualified with the module name (e.g., `u64::add`).
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    /// Returns the position of the `exec` or `call` token.
+    pub fn pos(&self) -> usize {
+        self.pos
+    }
+}
@@ -1,8 +1,14 @@
 use super::{
-    parse_op_token, AssemblyContext, AssemblyError, CodeBlock, Operation, String, Token,
-    TokenStream, Vec,
+    eval_const_expr, parse_custom_op, parse_op_token, AssemblyContext, AssemblyError, CodeBlock,
+    Digest, Felt, FieldElement, Operation, String, ToString, Token, TokenStream, Vec,
+    MODULE_PATH_DELIM,
+};
+use crate::{audit::DecisionKind, optimizer::optimize_span};
+use vm_core::{
+    code_blocks::{Rewriter, Span},
+    utils::group_vector_elements,
+    Decorator, DecoratorList, SourceLocation,
 };
-use vm_core::{utils::group_vector_elements, DecoratorList};
 
 // BLOCK PARSER
 // ================================================================================================
@@ -20,20 +26,32 @@ pub fn parse_code_blocks(
         return Err(AssemblyError::unexpected_eof(start_pos));
     }
 
-    // parse the sequence of blocks and add each block to the list
+    // parse the sequence of blocks and add each block to the list; blocks elided at compile
+    // time are not added to the list. when profiling, the roots of procedures invoked via `exec`
+    // are not merged with neighboring span blocks; since `repeat` blocks may consist of such
+    // roots, they are not merged either
     let mut blocks = Vec::new();
-    while let Some(parser) = BlockParser::next(tokens)? {
-        let block = parser.parse(tokens, context, num_proc_locals, in_debug_mode)?;
-        blocks.push(block);
+    let mut num_parsed = 0;
+    while let Some(parser) = BlockParser::next(tokens, context)? {
+        let mergeable = !context.is_profiling()
+            || !matches!(parser, BlockParser::Exec(..) | BlockParser::Repeat(_));
+        if let Some(block) = parser.parse(tokens, context, num_proc_locals, in_debug_mode)? {
+            blocks.push((block, mergeable));
+        }
+        num_parsed += 1;
     }
 
     // make sure at least one block has been read
-    if blocks.is_empty() {
+    if num_parsed == 0 {
         let start_op = tokens.read_at(start_pos).expect("no start token");
         Err(AssemblyError::empty_block(start_op))
+    } else if blocks.is_empty() {
+        // all blocks were elided; since a code block cannot be empty, return a Span with a
+        // single noop
+        Ok(CodeBlock::new_span(vec![Operation::Noop]))
     } else {
         // build a binary tree out of the parsed list of blocks
-        Ok(combine_blocks(blocks))
+        Ok(combine_mergeable_blocks(blocks))
     }
 }
 
@@ -45,20 +63,28 @@ pub fn parse_code_blocks(
 enum BlockParser {
     Span,
     IfElse,
+    IfConst(bool),
     While,
+    BoundedWhile(u32),
     Repeat(u32),
-    Exec(String),
+    Exec(String, Vec<u64>),
+    ExecRoot(Digest),
+    Call(String),
+    DynExec,
+    SysCall(String),
 }
 
 impl BlockParser {
-    // TODO: add comments
+    /// Parses the block starting at the current position of the token stream. Returns `None` if
+    /// the block was elided at compile time (i.e., a compile-time conditional with a false
+    /// condition and no `else` clause).
     pub fn parse(
         &self,
         tokens: &mut TokenStream,
         context: &AssemblyContext,
         num_proc_locals: u32,
         in_debug_mode: bool,
-    ) -> Result<CodeBlock, AssemblyError> {
+    ) -> Result<Option<CodeBlock>, AssemblyError> {
         match self {
             Self::Span => {
                 // --------------------------------------------------------------------------------
@@ -68,16 +94,96 @@ impl BlockParser {
                     if op.is_control_token() {
                         break;
                     }
-                    parse_op_token(
-                        op,
-                        &mut span_ops,
-                        num_proc_locals,
-                        &mut decorators,
-                        in_debug_mode,
-                    )?;
+                    let location = in_debug_mode.then(|| {
+                        let (line, column) = tokens.location(op.pos());
+                        SourceLocation::new(context.module_path().to_string(), line, column)
+                    });
+                    let resolved = resolve_const_params(op, context)?;
+                    let op = match &resolved {
+                        Some(resolved) => Token::new(resolved, op.pos()),
+                        None => op.clone(),
+                    };
+
+                    // namespaced instructions are expanded by the extension registered for the
+                    // namespace
+                    if op.parts()[0].contains(MODULE_PATH_DELIM) {
+                        parse_custom_op(
+                            &op,
+                            context,
+                            &mut span_ops,
+                            num_proc_locals,
+                            &mut decorators,
+                            in_debug_mode,
+                            location,
+                        )?;
+                    } else {
+                        parse_op_token(
+                            &op,
+                            &mut span_ops,
+                            num_proc_locals,
+                            &mut decorators,
+                            in_debug_mode,
+                            location,
+                        )?;
+                    }
                     tokens.advance();
                 }
-                Ok(CodeBlock::new_span_with_decorators(span_ops, decorators))
+                pad_trailing_decorators(&mut span_ops, &decorators);
+
+                // in debug mode, every span starts with the chain of procedures it belongs to; the
+                // chain is relative to the enclosing procedure, and is extended with the labels of
+                // procedures whenever they are inlined via `exec`
+                if in_debug_mode && !span_ops.is_empty() {
+                    decorators.insert(0, (0, Decorator::ProcPath(Vec::new())));
+                }
+
+                // debug decorators attribute operations to instructions, and thus the span is not
+                // optimized in debug mode
+                if !in_debug_mode {
+                    let mut applied = Vec::new();
+                    let unoptimized = context.audit_recorder().map(|_| span_hash(&span_ops));
+                    (span_ops, decorators) =
+                        optimize_span(span_ops, decorators, context.span_options(), &mut applied);
+                    let elided = span_ops.is_empty() && decorators.is_empty();
+                    pad_trailing_decorators(&mut span_ops, &decorators);
+
+                    // report every applied optimization once, together with the number of its
+                    // applications
+                    if let (Some(audit), Some(before)) = (context.audit_recorder(), unoptimized) {
+                        let after = span_hash(&span_ops);
+                        let mut counts = Vec::<(_, usize)>::new();
+                        for optimization in applied {
+                            match counts.iter_mut().find(|(o, _)| *o == optimization) {
+                                Some((_, count)) => *count += 1,
+                                None => counts.push((optimization, 1)),
+                            }
+                        }
+                        for (optimization, count) in counts {
+                            let detail = format!("applied {} time(s)", count);
+                            audit.record(
+                                DecisionKind::Optimize(optimization),
+                                detail,
+                                before,
+                                after,
+                            );
+                        }
+                    }
+
+                    if elided {
+                        return Ok(None);
+                    }
+                }
+
+                let num_span_ops = span_ops.len();
+                let span = CodeBlock::new_span_with_decorators(span_ops, decorators);
+                if let Some(audit) = context.audit_recorder() {
+                    let num_noops = count_ops(&span) - num_span_ops;
+                    if num_noops > 0 {
+                        let detail = format!("inserted {} noop(s)", num_noops);
+                        audit.record(DecisionKind::Padding, detail, span.hash(), span.hash());
+                    }
+                }
+                Ok(Some(span))
             }
             Self::IfElse => {
                 // --------------------------------------------------------------------------------
@@ -88,62 +194,33 @@ impl BlockParser {
                 // read the `if` clause
                 let t_branch = parse_code_blocks(tokens, context, num_proc_locals, in_debug_mode)?;
 
-                // build the `else` clause; if the else clause is specified, then read it;
-                // otherwise, set to a Span with a single noop
-                let f_branch = match tokens.read() {
-                    Some(token) => match token.parts()[0] {
-                        Token::ELSE => {
-                            // record start of the `else` block and consume the `else` token
-                            token.validate_else()?;
-                            let else_start = tokens.pos();
-                            tokens.advance();
-
-                            // parse the `false` branch
-                            let f_branch =
-                                parse_code_blocks(tokens, context, num_proc_locals, in_debug_mode)?;
-
-                            // consume the `end` token
-                            match tokens.read() {
-                                None => Err(AssemblyError::unmatched_else(
-                                    tokens.read_at(else_start).expect("no else token"),
-                                )),
-                                Some(token) => match token.parts()[0] {
-                                    Token::END => token.validate_end(),
-                                    Token::ELSE => Err(AssemblyError::dangling_else(token)),
-                                    _ => Err(AssemblyError::unmatched_else(
-                                        tokens.read_at(else_start).expect("no else token"),
-                                    )),
-                                },
-                            }?;
-                            tokens.advance();
-
-                            // return the `false` branch
-                            f_branch
-                        }
-                        Token::END => {
-                            // consume the `end` token
-                            token.validate_end()?;
-                            tokens.advance();
+                // build the `else` clause; if the else clause is not specified, set it to a Span
+                // with a single noop
+                let f_branch =
+                    parse_else_clause(tokens, context, if_start, num_proc_locals, in_debug_mode)?
+                        .unwrap_or_else(|| CodeBlock::new_span(vec![Operation::Noop]));
 
-                            // when no `else` clause was specified, a Span with a single noop
-                            CodeBlock::new_span(vec![Operation::Noop])
-                        }
-                        _ => {
-                            return Err(AssemblyError::unmatched_if(
-                                tokens.read_at(if_start).expect("no if token"),
-                            ))
-                        }
-                    },
-                    None => {
-                        return Err(AssemblyError::unmatched_if(
-                            tokens.read_at(if_start).expect("no if token"),
-                        ))
-                    }
-                };
+                Ok(Some(CodeBlock::new_split(t_branch, f_branch)))
+            }
+            Self::IfConst(condition) => {
+                // --------------------------------------------------------------------------------
+                // record start of the if-else block and consume the 'if' token
+                let if_start = tokens.pos();
+                tokens.advance();
 
-                Ok(CodeBlock::new_split(t_branch, f_branch))
+                // both clauses are parsed so that errors in the discarded clause are not hidden,
+                // but only the clause selected by the condition is emitted
+                let t_branch = parse_code_blocks(tokens, context, num_proc_locals, in_debug_mode)?;
+                let f_branch =
+                    parse_else_clause(tokens, context, if_start, num_proc_locals, in_debug_mode)?;
+
+                if *condition {
+                    Ok(Some(t_branch))
+                } else {
+                    Ok(f_branch)
+                }
             }
-            Self::While => {
+            Self::While | Self::BoundedWhile(_) => {
                 // --------------------------------------------------------------------------------
                 // record start of the while block and consume the 'while' token
                 let while_start = tokens.pos();
@@ -151,6 +228,7 @@ impl BlockParser {
 
                 // read the loop body
                 let loop_body = parse_code_blocks(tokens, context, num_proc_locals, in_debug_mode)?;
+                let body_end = tokens.pos();
 
                 // consume the `end` token
                 match tokens.read() {
@@ -167,7 +245,35 @@ impl BlockParser {
                 }?;
                 tokens.advance();
 
-                Ok(CodeBlock::new_loop(loop_body))
+                let max_iters = match self {
+                    Self::BoundedWhile(max_iters) => *max_iters,
+                    _ => return Ok(Some(CodeBlock::new_loop(loop_body))),
+                };
+
+                // the iteration counter of a bounded loop is kept in memory right above the locals
+                // of the current procedure, and thus the body cannot access the locals
+                if accesses_locals(tokens, while_start + 1, body_end) {
+                    return Err(AssemblyError::bounded_loop_locals(
+                        tokens.read_at(while_start).expect("no while token"),
+                    ));
+                }
+
+                // when profiling, the body is not merged with the counter management code (see
+                // parse_code_blocks())
+                let body_ops = count_ops(&loop_body);
+                let [init, body, exit] =
+                    build_bounded_loop(loop_body.clone(), max_iters, !context.is_profiling());
+                let block =
+                    combine_mergeable_blocks(vec![(init, true), (body, true), (exit, true)]);
+
+                if let Some(audit) = context.audit_recorder() {
+                    let detail = format!(
+                        "bounded to {} iterations of {} operations",
+                        max_iters, body_ops
+                    );
+                    audit.record(DecisionKind::Bound, detail, loop_body.hash(), block.hash());
+                }
+                Ok(Some(block))
             }
             Self::Repeat(iter_count) => {
                 // --------------------------------------------------------------------------------
@@ -177,6 +283,7 @@ impl BlockParser {
 
                 // read the loop body
                 let loop_body = parse_code_blocks(tokens, context, num_proc_locals, in_debug_mode)?;
+                let body_end = tokens.pos();
 
                 // consume the `end` token
                 match tokens.read() {
@@ -193,63 +300,256 @@ impl BlockParser {
                 }?;
                 tokens.advance();
 
-                // if the body of the loop consists of a single span, unroll the loop as a single
-                // span; otherwise unroll the loop as a sequence of join blocks
-                if let CodeBlock::Span(span) = loop_body {
-                    Ok(CodeBlock::Span(span.replicate(*iter_count as usize)))
+                // if the unrolled loop would be too large, unroll only a part of it and execute the
+                // part in a counter-controlled loop; the counter is kept in memory right above the
+                // locals of the current procedure, and thus the loop is not split if its body
+                // accesses the locals
+                let iter_count = *iter_count as usize;
+                let body_ops = count_ops(&loop_body);
+                let threshold = context.split_threshold();
+                let chunk_size = if iter_count > 1
+                    && body_ops.saturating_mul(iter_count) > threshold
+                    && !accesses_locals(tokens, repeat_start + 1, body_end)
+                {
+                    Some(((threshold / body_ops) / 2).max(1))
                 } else {
-                    // TODO: transform the loop to a while loop instead?
-                    let blocks = (0..*iter_count)
-                        .map(|_| loop_body.clone())
-                        .collect::<Vec<_>>();
-                    Ok(combine_blocks(blocks))
+                    None
+                };
+
+                // make sure the unrolled parts of the loop fit into the unroll budget, if one is set
+                if let Some(budget) = context.unroll_budget() {
+                    let num_unrolled = match chunk_size {
+                        Some(chunk_size) => chunk_size + iter_count % chunk_size,
+                        None => iter_count,
+                    };
+                    let num_ops = body_ops.saturating_mul(num_unrolled);
+                    if num_ops > budget {
+                        return Err(AssemblyError::unroll_budget_exceeded(
+                            tokens.read_at(repeat_start).expect("no repeat token"),
+                            num_ops,
+                            budget,
+                        ));
+                    }
                 }
+
+                // when profiling, copies of the body are not merged (see parse_code_blocks())
+                let mergeable = !context.is_profiling();
+                let (block, decision) = match chunk_size {
+                    None => {
+                        let detail = format!("unrolled {} iterations", iter_count);
+                        (
+                            unroll(&loop_body, iter_count, mergeable),
+                            (DecisionKind::Unroll, detail),
+                        )
+                    }
+                    Some(chunk_size) => {
+                        let mut blocks = Vec::new();
+                        if iter_count % chunk_size > 0 {
+                            blocks.push((
+                                unroll(&loop_body, iter_count % chunk_size, mergeable),
+                                mergeable,
+                            ));
+                        }
+                        let [init, body, exit] = build_counter_loop(
+                            unroll(&loop_body, chunk_size, mergeable),
+                            iter_count / chunk_size,
+                            mergeable,
+                        );
+                        blocks.extend([(init, true), (body, true), (exit, true)]);
+                        let detail = format!(
+                            "split {} iterations into {} chunks of {} iterations and {} unrolled \
+                            iterations",
+                            iter_count,
+                            iter_count / chunk_size,
+                            chunk_size,
+                            iter_count % chunk_size
+                        );
+                        (
+                            combine_mergeable_blocks(blocks),
+                            (DecisionKind::Split, detail),
+                        )
+                    }
+                };
+
+                // a single iteration is emitted as is, and thus is not reported
+                if let Some(audit) = context.audit_recorder() {
+                    if iter_count > 1 {
+                        let (kind, detail) = decision;
+                        audit.record(kind, detail, loop_body.hash(), block.hash());
+                    }
+                }
+                Ok(Some(block))
             }
-            Self::Exec(label) => {
+            Self::Exec(label, args) => {
                 // --------------------------------------------------------------------------------
+                // if this is a generic procedure, instantiate it with the provided arguments, and
+                // then restore the position of the token stream and consume the 'exec' token
+                if let Some(proc) = context.get_generic_proc(label) {
+                    let exec_start = tokens.pos();
+                    if proc.params().len() != args.len() {
+                        return Err(AssemblyError::generic_args_mismatch(
+                            tokens.read().expect("no exec token"),
+                            label,
+                            proc.params().len(),
+                            args.len(),
+                        ));
+                    }
+                    let proc_context = context.instantiate_generic_proc(proc, args);
+                    let mut proc_root = proc.instantiate(tokens, &proc_context, in_debug_mode)?;
+                    if in_debug_mode {
+                        proc_root = prefix_proc_paths(&proc_root, label);
+                    }
+                    tokens.seek(exec_start);
+                    tokens.advance();
+                    if let Some(audit) = context.audit_recorder() {
+                        let args = args.iter().map(|a| a.to_string()).collect::<Vec<_>>();
+                        let detail = format!("exec.{}<{}>", label, args.join(","));
+                        let hash = proc_root.hash();
+                        audit.record(DecisionKind::Inline, detail, hash, hash);
+                    }
+                    return Ok(Some(proc_root));
+                }
+
                 // retrieve the procedure block from the proc map and consume the 'exec' token
+                let token = tokens.read().expect("no exec token");
                 let proc_root = context
                     .get_proc_code(label)
-                    .ok_or_else(|| {
-                        AssemblyError::undefined_proc(tokens.read().expect("no exec token"), label)
-                    })?
+                    .ok_or_else(|| AssemblyError::undefined_proc(token, label))?
                     .clone();
+                if !args.is_empty() {
+                    return Err(AssemblyError::generic_args_mismatch(
+                        token,
+                        label,
+                        0,
+                        args.len(),
+                    ));
+                }
                 tokens.advance();
-                Ok(proc_root)
+                if let Some(audit) = context.audit_recorder() {
+                    let hash = proc_root.hash();
+                    audit.record(DecisionKind::Inline, format!("exec.{}", label), hash, hash);
+                }
+                if in_debug_mode {
+                    return Ok(Some(prefix_proc_paths(&proc_root, label)));
+                }
+                Ok(Some(proc_root))
+            }
+            Self::ExecRoot(root) => {
+                // --------------------------------------------------------------------------------
+                // the body of the procedure is not known at compile time, and thus the procedure
+                // is represented by a proxy block; the processor resolves the proxy block into
+                // the body of the procedure at runtime. consume the 'exec' token
+                tokens.advance();
+                Ok(Some(CodeBlock::new_proxy(*root)))
+            }
+            Self::Call(label) => {
+                // --------------------------------------------------------------------------------
+                // the called procedure is executed by the processor in a new memory context, and
+                // thus the procedure is not inlined; the program refers to the procedure by its
+                // MAST root and carries the code of the procedure separately. consume the 'call'
+                // token
+                let token = tokens.read().expect("no call token");
+                if context.get_generic_proc(label).is_some() {
+                    return Err(AssemblyError::invalid_op_with_reason(
+                        token,
+                        "generic procedures cannot be invoked via call",
+                    ));
+                }
+                let proc = context
+                    .get_proc(label)
+                    .ok_or_else(|| AssemblyError::undefined_proc(token, label))?;
+                let fn_hash = proc.code_root().hash();
+                tokens.advance();
+                Ok(Some(CodeBlock::new_call(fn_hash)))
+            }
+            Self::SysCall(label) => {
+                // --------------------------------------------------------------------------------
+                // the kernel procedure is executed by the processor in the kernel context, and
+                // thus the procedure is not inlined; the program refers to the procedure by its
+                // MAST root. consume the 'syscall' token
+                let token = tokens.read().expect("no syscall token");
+                let proc = context
+                    .get_kernel_proc(label)
+                    .ok_or_else(|| AssemblyError::undefined_kernel_proc(token, label))?;
+                let fn_hash = proc.code_root().hash();
+                tokens.advance();
+                Ok(Some(CodeBlock::new_syscall(fn_hash)))
+            }
+            Self::DynExec => {
+                // --------------------------------------------------------------------------------
+                // the root of the executed procedure is read from the stack at runtime, and thus
+                // the block does not depend on the procedure. consume the 'dynexec' token
+                tokens.advance();
+                Ok(Some(CodeBlock::new_dyn()))
             }
         }
     }
 
     // TODO: add comments
-    fn next(tokens: &mut TokenStream) -> Result<Option<Self>, AssemblyError> {
+    fn next(
+        tokens: &mut TokenStream,
+        context: &AssemblyContext,
+    ) -> Result<Option<Self>, AssemblyError> {
         let parser = match tokens.read() {
             None => None,
             Some(token) => match token.parts()[0] {
-                Token::IF => {
-                    token.validate_if()?;
-                    Some(Self::IfElse)
-                }
+                Token::IF => match token.parse_if()? {
+                    None => Some(Self::IfElse),
+                    Some(expr) => {
+                        let condition = eval_const_expr(expr, token, context)?;
+                        Some(Self::IfConst(condition != 0))
+                    }
+                },
                 Token::ELSE => {
                     token.validate_else()?;
                     None
                 }
-                Token::WHILE => {
-                    token.validate_while()?;
-                    Some(Self::While)
-                }
+                Token::WHILE => match token.parse_while()? {
+                    None => Some(Self::While),
+                    Some(expr) => {
+                        let max_iters = eval_const_expr(expr, token, context)?;
+                        if max_iters == 0 || max_iters > u32::MAX as u64 {
+                            return Err(AssemblyError::invalid_param(token, 2));
+                        }
+                        Some(Self::BoundedWhile(max_iters as u32))
+                    }
+                },
                 Token::REPEAT => {
-                    let iter_count = token.parse_repeat()?;
-                    Some(Self::Repeat(iter_count))
+                    let expr = token.parse_repeat()?;
+                    let iter_count = eval_const_expr(expr, token, context)?;
+                    if iter_count == 0 || iter_count > u32::MAX as u64 {
+                        return Err(AssemblyError::invalid_param(token, 1));
+                    }
+                    Some(Self::Repeat(iter_count as u32))
                 }
                 Token::EXEC => {
-                    let label = token.parse_exec()?;
-                    Some(Self::Exec(label))
+                    if let Some(root) = token.parse_exec_root()? {
+                        return Ok(Some(Self::ExecRoot(root)));
+                    }
+                    let (label, args) = token.parse_exec()?;
+                    let args = args
+                        .into_iter()
+                        .map(|arg| eval_const_expr(arg, token, context))
+                        .collect::<Result<Vec<_>, _>>()?;
+                    Some(Self::Exec(label, args))
+                }
+                Token::CALL => {
+                    let label = token.parse_call()?;
+                    Some(Self::Call(label))
+                }
+                Token::SYSCALL => {
+                    let label = token.parse_syscall()?;
+                    Some(Self::SysCall(label))
+                }
+                Token::DYNEXEC => {
+                    token.validate_dynexec()?;
+                    Some(Self::DynExec)
                 }
                 Token::END => {
                     token.validate_end()?;
                     None
                 }
-                Token::USE | Token::EXPORT | Token::PROC | Token::BEGIN => None,
+                Token::USE | Token::CONST | Token::EXPORT | Token::PROC | Token::BEGIN => None,
                 _ => Some(Self::Span),
             },
         };
@@ -261,15 +561,75 @@ impl BlockParser {
 // UTILITY FUNCTIONS
 // ================================================================================================
 
-pub fn combine_blocks(mut blocks: Vec<CodeBlock>) -> CodeBlock {
+/// Parses an optional `else` clause of an if-else block and consumes the `end` token terminating
+/// the block. Returns `None` if the block does not have an `else` clause.
+fn parse_else_clause(
+    tokens: &mut TokenStream,
+    context: &AssemblyContext,
+    if_start: usize,
+    num_proc_locals: u32,
+    in_debug_mode: bool,
+) -> Result<Option<CodeBlock>, AssemblyError> {
+    match tokens.read() {
+        Some(token) => match token.parts()[0] {
+            Token::ELSE => {
+                // record start of the `else` block and consume the `else` token
+                token.validate_else()?;
+                let else_start = tokens.pos();
+                tokens.advance();
+
+                // parse the `false` branch
+                let f_branch = parse_code_blocks(tokens, context, num_proc_locals, in_debug_mode)?;
+
+                // consume the `end` token
+                match tokens.read() {
+                    None => Err(AssemblyError::unmatched_else(
+                        tokens.read_at(else_start).expect("no else token"),
+                    )),
+                    Some(token) => match token.parts()[0] {
+                        Token::END => token.validate_end(),
+                        Token::ELSE => Err(AssemblyError::dangling_else(token)),
+                        _ => Err(AssemblyError::unmatched_else(
+                            tokens.read_at(else_start).expect("no else token"),
+                        )),
+                    },
+                }?;
+                tokens.advance();
+
+                // return the `false` branch
+                Ok(Some(f_branch))
+            }
+            Token::END => {
+                // consume the `end` token
+                token.validate_end()?;
+                tokens.advance();
+                Ok(None)
+            }
+            _ => Err(AssemblyError::unmatched_if(
+                tokens.read_at(if_start).expect("no if token"),
+            )),
+        },
+        None => Err(AssemblyError::unmatched_if(
+            tokens.read_at(if_start).expect("no if token"),
+        )),
+    }
+}
+
+pub fn combine_blocks(blocks: Vec<CodeBlock>) -> CodeBlock {
+    combine_mergeable_blocks(blocks.into_iter().map(|block| (block, true)).collect())
+}
+
+/// Builds a binary tree of Join blocks out of the provided blocks, merging consecutive Span
+/// blocks; Span blocks which are not flagged as mergeable are kept as separate blocks.
+pub fn combine_mergeable_blocks(mut blocks: Vec<(CodeBlock, bool)>) -> CodeBlock {
     // merge consecutive Span blocks.
     let mut merged_blocks: Vec<CodeBlock> = Vec::with_capacity(blocks.len());
     // Keep track of all the consecutive Span blocks and are merged together when
     // there is a discontinuity.
     let mut contiguous_spans: Vec<CodeBlock> = Vec::new();
 
-    blocks.drain(0..).for_each(|block| {
-        if block.is_span() {
+    blocks.drain(0..).for_each(|(block, mergeable)| {
+        if block.is_span() && mergeable {
             contiguous_spans.push(block);
         } else {
             if !contiguous_spans.is_empty() {
@@ -306,6 +666,249 @@ pub fn combine_blocks(mut blocks: Vec<CodeBlock>) -> CodeBlock {
     blocks.remove(0)
 }
 
+/// Replaces instruction parameters which are constant names or parenthesized constant expressions
+/// (e.g., `push.N` or `add.(N*4)`) with their values. Returns the resulting instruction, or None if
+/// the instruction does not have such parameters.
+///
+/// A `push.addr_of.NAME` instruction is replaced with an instruction pushing the memory address
+/// of the data segment with the specified name.
+fn resolve_const_params(
+    op: &Token,
+    context: &AssemblyContext,
+) -> Result<Option<String>, AssemblyError> {
+    if let ["push", "addr_of", name] = op.parts() {
+        let addr = context
+            .get_data_segment(name)
+            .ok_or_else(|| AssemblyError::undefined_data(op, name))?;
+        return Ok(Some(format!("push.{}", addr)));
+    }
+
+    let is_const_param = |param: &&str| {
+        param.starts_with('(') || param.starts_with(|c: char| c.is_ascii_uppercase())
+    };
+
+    // values of `expect_stack.[a,b,...]` are resolved one by one
+    if let ["expect_stack", values] = op.parts() {
+        let values = match values.strip_prefix('[').and_then(|v| v.strip_suffix(']')) {
+            Some(values) if values.split(',').any(|value| is_const_param(&value)) => values,
+            _ => return Ok(None),
+        };
+        let values = values
+            .split(',')
+            .map(|value| eval_const_expr(value, op, context).map(|value| value.to_string()))
+            .collect::<Result<Vec<_>, _>>()?;
+        return Ok(Some(format!("expect_stack.[{}]", values.join(","))));
+    }
+
+    if !op.parts()[1..].iter().any(is_const_param) {
+        return Ok(None);
+    }
+
+    let mut resolved = String::from(op.parts()[0]);
+    for param in op.parts()[1..].iter() {
+        resolved.push('.');
+        if is_const_param(param) {
+            let value = eval_const_expr(param, op, context)?;
+            resolved.push_str(&value.to_string());
+        } else {
+            resolved.push_str(param);
+        }
+    }
+    Ok(Some(resolved))
+}
+
+/// Returns a block which executes the specified block the specified number of times.
+///
+/// If the block consists of a single span and is mergeable, the result is a single span;
+/// otherwise, the result is a sequence of join blocks.
+fn unroll(block: &CodeBlock, num_copies: usize, mergeable: bool) -> CodeBlock {
+    match block {
+        CodeBlock::Span(span) if mergeable => CodeBlock::Span(span.replicate(num_copies)),
+        _ => {
+            let blocks = (0..num_copies).map(|_| (block.clone(), false)).collect();
+            combine_mergeable_blocks(blocks)
+        }
+    }
+}
+
+/// Returns blocks which execute the specified body the specified number of times in a `while`
+/// loop.
+///
+/// The loop counter is stored in a memory slot allocated right above the locals of the current
+/// procedure (i.e., the free memory pointer is incremented by one for the duration of the loop),
+/// and thus the body of the loop must not access these locals. The stack is left unchanged by
+/// the counter management code, which is merged with the body only if the body is mergeable.
+fn build_counter_loop(body: CodeBlock, num_iters: usize, mergeable: bool) -> [CodeBlock; 3] {
+    use Operation::*;
+
+    // allocate the slot, initialize the counter, and enter the loop
+    let init = CodeBlock::new_span(vec![
+        Pad,
+        Incr,
+        FmpUpdate,
+        Push(Felt::new(num_iters as u64)),
+        Pad,
+        FmpAdd,
+        MStore,
+        Drop,
+        Pad,
+        Incr,
+    ]);
+
+    // decrement the counter, and continue the loop while the counter is not zero
+    let next = CodeBlock::new_span(vec![
+        Pad,
+        FmpAdd,
+        MLoad,
+        Push(-Felt::ONE),
+        Add,
+        Pad,
+        FmpAdd,
+        MStore,
+        Eqz,
+        Not,
+    ]);
+    let body = CodeBlock::new_loop(combine_mergeable_blocks(vec![
+        (body, mergeable),
+        (next, true),
+    ]));
+
+    // release the slot
+    let exit = CodeBlock::new_span(vec![Push(-Felt::ONE), FmpUpdate]);
+
+    [init, body, exit]
+}
+
+/// Returns blocks which execute the specified body in a `while` loop, failing the execution if
+/// the body is about to be executed more than the specified number of times.
+///
+/// Like the counter of [build_counter_loop()], the number of remaining iterations is kept in a
+/// memory slot allocated right above the locals of the current procedure. The loop condition is
+/// left to the body, and thus the counter is checked and decremented before every iteration; this
+/// adds 13 cycles per iteration and 10 cycles per loop.
+fn build_bounded_loop(body: CodeBlock, max_iters: u32, mergeable: bool) -> [CodeBlock; 3] {
+    use Operation::*;
+
+    // allocate the slot and initialize the counter
+    let init = CodeBlock::new_span(vec![
+        Pad,
+        Incr,
+        FmpUpdate,
+        Push(Felt::new(max_iters as u64)),
+        Pad,
+        FmpAdd,
+        MStore,
+        Drop,
+    ]);
+
+    // make sure the counter is not zero, and decrement it
+    let check = CodeBlock::new_span(vec![
+        Pad,
+        FmpAdd,
+        MLoad,
+        Dup0,
+        Eqz,
+        Not,
+        Assert(0),
+        Push(-Felt::ONE),
+        Add,
+        Pad,
+        FmpAdd,
+        MStore,
+        Drop,
+    ]);
+    let body = CodeBlock::new_loop(combine_mergeable_blocks(vec![
+        (check, true),
+        (body, mergeable),
+    ]));
+
+    // release the slot
+    let exit = CodeBlock::new_span(vec![Push(-Felt::ONE), FmpUpdate]);
+
+    [init, body, exit]
+}
+
+/// Returns true if any of the tokens in the specified range accesses locals of the current
+/// procedure.
+fn accesses_locals(tokens: &mut TokenStream, start: usize, end: usize) -> bool {
+    (start..end).any(|pos| match tokens.read_at(pos) {
+        Some(token) => {
+            let parts = token.parts();
+            (parts.len() > 1 && parts[1] == "local")
+                || (parts.len() > 2 && parts[1] == "env" && parts[2] == "locaddr")
+        }
+        None => false,
+    })
+}
+
+/// Returns the number of operations in the specified code block, including operations in all of
+/// its nested blocks.
+fn count_ops(block: &CodeBlock) -> usize {
+    match block {
+        CodeBlock::Span(span) => span.op_batches().iter().map(|b| b.ops().len()).sum(),
+        CodeBlock::Join(join) => count_ops(join.first()) + count_ops(join.second()),
+        CodeBlock::Split(split) => count_ops(split.on_true()) + count_ops(split.on_false()),
+        CodeBlock::Loop(loop_block) => count_ops(loop_block.body()),
+        CodeBlock::Call(_) | CodeBlock::Dyn(_) | CodeBlock::Proxy(_) => 0,
+    }
+}
+
+/// Appends a NOOP to the operations if some decorators do not precede any operation (e.g., when
+/// the span ends with an advice injector, or consists only of decorators), since every decorator
+/// of a span must be attached to one of its operations.
+fn pad_trailing_decorators(ops: &mut Vec<Operation>, decorators: &DecoratorList) {
+    if decorators
+        .last()
+        .map_or(false, |&(op_idx, _)| op_idx >= ops.len())
+    {
+        ops.push(Operation::Noop);
+    }
+}
+
+/// Returns the hash of a span block consisting of the specified operations, or the zero digest if
+/// there are no operations.
+fn span_hash(ops: &[Operation]) -> Digest {
+    if ops.is_empty() {
+        Digest::default()
+    } else {
+        CodeBlock::new_span(ops.to_vec()).hash()
+    }
+}
+
+/// Returns a copy of the specified block in which the label of the specified procedure is
+/// prepended to the chains of procedures of all span blocks; this attributes the code of a
+/// procedure inlined via `exec` to the procedure. The hash of the block is not affected.
+fn prefix_proc_paths(block: &CodeBlock, label: &str) -> CodeBlock {
+    ProcPathPrefixer(label).rewrite_block(block)
+}
+
+/// Prepends a procedure label to the [Decorator::ProcPath] decorators of span blocks.
+struct ProcPathPrefixer<'a>(&'a str);
+
+impl Rewriter for ProcPathPrefixer<'_> {
+    fn rewrite_span(&mut self, span: &Span) -> CodeBlock {
+        let decorators = span
+            .decorators()
+            .iter()
+            .map(|(op_idx, decorator)| match decorator {
+                Decorator::ProcPath(procs) => {
+                    let mut path = vec![self.0.to_string()];
+                    path.extend_from_slice(procs);
+                    (*op_idx, Decorator::ProcPath(path))
+                }
+                _ => (*op_idx, decorator.clone()),
+            })
+            .collect();
+        let ops = span
+            .op_batches()
+            .iter()
+            .flat_map(|batch| batch.ops())
+            .copied()
+            .collect();
+        CodeBlock::new_span_with_decorators(ops, decorators)
+    }
+}
+
 /// Returns a CodeBlock [Span] from sequence of Span blocks provided as input.
 pub fn combine_spans(spans: &mut Vec<CodeBlock>) -> CodeBlock {
     if spans.len() == 1 {
@@ -0,0 +1,223 @@
+use super::{AssemblyContext, AssemblyError, String, Token, Vec};
+use core::{iter::Peekable, str::Chars};
+use vm_core::{Felt, StarkField};
+
+// CONSTANT DECLARATIONS
+// ================================================================================================
+
+/// Parses a constant declaration of the form `const.NAME=EXPR` and returns the name of the
+/// constant together with its value.
+///
+/// The expression is evaluated in the provided context, and thus it can refer to constants
+/// declared earlier in the same program or module.
+///
+/// # Errors
+/// Returns an error if the declaration is malformed, if the expression cannot be evaluated, or if
+/// the value of the expression is not a valid field element.
+pub fn parse_constant(
+    token: &Token,
+    context: &AssemblyContext,
+) -> Result<(String, u64), AssemblyError> {
+    let (name, expr) = token.parse_const()?;
+    let value = eval_const_expr(expr, token, context)?;
+    if value >= Felt::MODULUS {
+        return Err(AssemblyError::invalid_const_expr(
+            token,
+            expr,
+            "value is not a valid field element",
+        ));
+    }
+    Ok((name, value))
+}
+
+/// Parses a data segment declaration of the form `data.NAME=[EXPR,EXPR,...]` and returns the name
+/// of the segment together with the values of its elements.
+///
+/// The expressions are evaluated in the provided context, and thus they can refer to constants
+/// declared in the same program.
+///
+/// # Errors
+/// Returns an error if the declaration is malformed, if any of the expressions cannot be
+/// evaluated, or if the value of any of the expressions is not a valid field element.
+pub fn parse_data_segment(
+    token: &Token,
+    context: &AssemblyContext,
+) -> Result<(String, Vec<Felt>), AssemblyError> {
+    let (name, exprs) = token.parse_data()?;
+    let values = exprs
+        .iter()
+        .map(|expr| {
+            let value = eval_const_expr(expr, token, context)?;
+            if value >= Felt::MODULUS {
+                return Err(AssemblyError::invalid_const_expr(
+                    token,
+                    expr,
+                    "value is not a valid field element",
+                ));
+            }
+            Ok(Felt::new(value))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok((name, values))
+}
+
+// CONSTANT EXPRESSIONS
+// ================================================================================================
+
+/// Evaluates a constant expression.
+///
+/// A constant expression consists of decimal or hexadecimal integer literals, names of constants
+/// declared in the provided context, binary operators `+`, `-`, `*`, `/` (integer division), and
+/// parentheses. Multiplication and division take precedence over addition and subtraction, and
+/// operators of the same precedence are evaluated from left to right. All intermediate values
+/// must fit into 64 bits.
+///
+/// The top level of an expression (or of a parenthesized sub-expression) may also compare two
+/// values via one of `==`, `!=`, `<`, `<=`, `>`, `>=`; a comparison evaluates to 1 if it holds
+/// and to 0 otherwise.
+///
+/// # Errors
+/// Returns an error if the expression is malformed, refers to an undefined constant, divides by
+/// zero, or overflows.
+pub fn eval_const_expr(
+    expr: &str,
+    token: &Token,
+    context: &AssemblyContext,
+) -> Result<u64, AssemblyError> {
+    let mut evaluator = ExprEvaluator {
+        chars: expr.chars().peekable(),
+        expr,
+        token,
+        context,
+    };
+    let value = evaluator.parse_comparison()?;
+    match evaluator.chars.next() {
+        None => Ok(value),
+        Some(c) => Err(evaluator.error(&format!("unexpected character '{}'", c))),
+    }
+}
+
+/// Recursive descent evaluator for constant expressions.
+struct ExprEvaluator<'a, 'b, 'c> {
+    chars: Peekable<Chars<'a>>,
+    expr: &'a str,
+    token: &'b Token<'b>,
+    context: &'c AssemblyContext,
+}
+
+impl<'a, 'b, 'c> ExprEvaluator<'a, 'b, 'c> {
+    /// comparison := sum (('==' | '!=' | '<' | '<=' | '>' | '>=') sum)?
+    fn parse_comparison(&mut self) -> Result<u64, AssemblyError> {
+        let lhs = self.parse_sum()?;
+        let op = match self.chars.peek().copied() {
+            Some(c @ ('=' | '!' | '<' | '>')) => {
+                self.chars.next();
+                let or_equal = self.chars.next_if_eq(&'=').is_some();
+                match (c, or_equal) {
+                    ('=', true) => u64::eq,
+                    ('!', true) => u64::ne,
+                    ('<', false) => u64::lt,
+                    ('<', true) => u64::le,
+                    ('>', false) => u64::gt,
+                    ('>', true) => u64::ge,
+                    _ => return Err(self.error(&format!("unexpected character '{}'", c))),
+                }
+            }
+            _ => return Ok(lhs),
+        };
+        let rhs = self.parse_sum()?;
+        Ok(op(&lhs, &rhs) as u64)
+    }
+
+    /// sum := product (('+' | '-') product)*
+    fn parse_sum(&mut self) -> Result<u64, AssemblyError> {
+        let mut value = self.parse_product()?;
+        while let Some(&op) = self.chars.peek() {
+            let result = match op {
+                '+' => {
+                    self.chars.next();
+                    value.checked_add(self.parse_product()?)
+                }
+                '-' => {
+                    self.chars.next();
+                    value.checked_sub(self.parse_product()?)
+                }
+                _ => break,
+            };
+            value = result.ok_or_else(|| self.error("arithmetic overflow"))?;
+        }
+        Ok(value)
+    }
+
+    /// product := atom (('*' | '/') atom)*
+    fn parse_product(&mut self) -> Result<u64, AssemblyError> {
+        let mut value = self.parse_atom()?;
+        while let Some(&op) = self.chars.peek() {
+            value = match op {
+                '*' => {
+                    self.chars.next();
+                    let rhs = self.parse_atom()?;
+                    value
+                        .checked_mul(rhs)
+                        .ok_or_else(|| self.error("arithmetic overflow"))?
+                }
+                '/' => {
+                    self.chars.next();
+                    let rhs = self.parse_atom()?;
+                    value
+                        .checked_div(rhs)
+                        .ok_or_else(|| self.error("division by zero"))?
+                }
+                _ => break,
+            };
+        }
+        Ok(value)
+    }
+
+    /// atom := integer | name | '(' comparison ')'
+    fn parse_atom(&mut self) -> Result<u64, AssemblyError> {
+        match self.chars.peek().copied() {
+            Some('(') => {
+                self.chars.next();
+                let value = self.parse_comparison()?;
+                match self.chars.next() {
+                    Some(')') => Ok(value),
+                    _ => Err(self.error("unmatched parenthesis")),
+                }
+            }
+            Some(c) if c.is_ascii_digit() => {
+                let literal = self.take_word();
+                let value = match literal.strip_prefix("0x") {
+                    Some(hex) => u64::from_str_radix(hex, 16),
+                    None => literal.parse::<u64>(),
+                };
+                value.map_err(|_| self.error(&format!("invalid integer '{}'", literal)))
+            }
+            Some(c) if c.is_ascii_alphabetic() => {
+                let name = self.take_word();
+                self.context
+                    .get_constant(&name)
+                    .ok_or_else(|| AssemblyError::undefined_const(self.token, &name))
+            }
+            Some(c) => Err(self.error(&format!("unexpected character '{}'", c))),
+            None => Err(self.error("unexpected end of expression")),
+        }
+    }
+
+    /// Consumes and returns the longest sequence of alphanumeric characters and underscores.
+    fn take_word(&mut self) -> String {
+        let mut word = String::new();
+        while let Some(&c) = self.chars.peek() {
+            if !c.is_ascii_alphanumeric() && c != '_' {
+                break;
+            }
+            word.push(c);
+            self.chars.next();
+        }
+        word
+    }
+
+    fn error(&self, reason: &str) -> AssemblyError {
+        AssemblyError::invalid_const_expr(self.token, self.expr, reason)
+    }
+}
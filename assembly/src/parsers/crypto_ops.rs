@@ -32,6 +32,14 @@ const RPHASH_NUM_ELEMENTS: u64 = 8;
 pub(super) fn parse_rphash(span_ops: &mut Vec<Operation>, op: &Token) -> Result<(), AssemblyError> {
     validate_operation!(op, "rphash", 0);
 
+    append_rphash(span_ops);
+
+    Ok(())
+}
+
+/// Appends the operations which compute a 2-to-1 Rescue Prime hash of the top two words of the
+/// stack, as described for [parse_rphash()].
+fn append_rphash(span_ops: &mut Vec<Operation>) {
     // Add 4 elements to the stack to prepare the capacity portion for the Rescue Prime permutation
     // The capacity should start at stack[8], and the number of elements to be hashed should
     // be deepest in the stack at stack[11]
@@ -52,8 +60,6 @@ pub(super) fn parse_rphash(span_ops: &mut Vec<Operation>, op: &Token) -> Result<
 
     // Drop 4 elements (the capacity portion)
     span_ops.push_many(Operation::Drop, 4);
-
-    Ok(())
 }
 
 /// Appends an RPPERM operation to the span block, which performs a Rescue Prime permutation on the
@@ -219,6 +225,43 @@ pub(super) fn parse_mtree_cwm(
     Ok(())
 }
 
+/// Appends the operations required to merge two Merkle trees into a new tree whose left and right
+/// subtrees are the Merkle trees with roots L and R respectively. The stack is expected to be
+/// arranged as follows (from the top):
+/// - root of the right tree R, 4 elements
+/// - root of the left tree L, 4 elements
+///
+/// After the operations are executed, the stack will be arranged as follows:
+/// - root of the new tree, i.e., the hash of L and R, 4 elements
+///
+/// The new tree is created in the advice provider, which must contain both merged trees, and the
+/// merged trees are retained in the provider. The root of the new tree is computed by the VM in
+/// the same way as by the rphash instruction, and thus nodes of the new tree can be accessed via
+/// mtree_get, mtree_set and mtree_cwm instructions without trusting the advice provider.
+///
+/// This operation takes 16 VM cycles.
+///
+/// # Errors:
+/// Returns an AssemblyError if the operation is malformed.
+pub(super) fn parse_mtree_merge(
+    span_ops: &mut Vec<Operation>,
+    op: &Token,
+    decorators: &mut DecoratorList,
+) -> Result<(), AssemblyError> {
+    validate_operation!(op, "mtree_merge", 0);
+
+    // create the merged tree in the advice provider => [R, L, ...]
+    decorators.push((
+        span_ops.len(),
+        Decorator::Advice(AdviceInjector::MerkleMerge),
+    ));
+
+    // compute the root of the merged tree => [hash(L, R), ...]
+    append_rphash(span_ops);
+
+    Ok(())
+}
+
 /// Validates that two 4 word Merkle roots at the top of the stack are equal, then drops the
 /// duplicate. The stack is expected to be arranged as follows (from the top):
 /// - root of a Merkle tree, 4 elements
@@ -245,7 +288,7 @@ fn validate_and_drop_root(span_ops: &mut Vec<Operation>) {
     span_ops.push(Operation::Dup2);
     span_ops.push(Operation::Eq);
     span_ops.push(Operation::And);
-    span_ops.push(Operation::Assert);
+    span_ops.push(Operation::Assert(0));
 
     // drop one of the duplicate roots
     span_ops.push_many(Operation::Drop, 4);
@@ -425,6 +468,10 @@ mod tests {
             parse_mtree_cwm(&mut span_ops, &op_mismatch, &mut decorators).unwrap_err(),
             AssemblyError::unexpected_token(&op_mismatch, "mtree_cwm")
         );
+        assert_eq!(
+            parse_mtree_merge(&mut span_ops, &op_mismatch, &mut decorators).unwrap_err(),
+            AssemblyError::unexpected_token(&op_mismatch, "mtree_merge")
+        );
 
         let op_too_long1 = Token::new("mtree_get.12", op_pos);
         assert_eq!(
@@ -443,5 +490,11 @@ mod tests {
             parse_mtree_cwm(&mut span_ops, &op_too_long3, &mut decorators).unwrap_err(),
             AssemblyError::extra_param(&op_too_long3)
         );
+
+        let op_too_long4 = Token::new("mtree_merge.12", op_pos);
+        assert_eq!(
+            parse_mtree_merge(&mut span_ops, &op_too_long4, &mut decorators).unwrap_err(),
+            AssemblyError::extra_param(&op_too_long4)
+        );
     }
 }
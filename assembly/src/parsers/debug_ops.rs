@@ -0,0 +1,139 @@
+use super::{
+    super::validate_operation, parse_decimal_param, parse_hex_param, parse_u32_param,
+    AssemblyError, DebugOptions, Decorator, DecoratorList, Operation, Token, Vec, MIN_STACK_DEPTH,
+};
+
+// DEBUGGING
+// ================================================================================================
+
+/// Appends a debug decorator to the span block if the assembler is in debug mode; otherwise, the
+/// instruction is discarded. Debug instructions are never compiled into operations, and thus they
+/// do not affect the hash of the program. The following debug instructions are supported:
+/// - debug.stack: reports the entire stack.
+/// - debug.stack.n: reports the top `n` items of the stack.
+/// - debug.mem: reports all memory addresses accessed so far.
+/// - debug.mem.a and debug.mem.a.b: reports the memory address `a`, or the accessed addresses
+///   between `a` and `b` (inclusive).
+/// - debug.local: reports all locals of the current procedure.
+/// - debug.local.i and debug.local.i.j: reports the local at index `i`, or the locals with indexes
+///   between `i` and `j` (inclusive).
+///
+/// Parameters are validated regardless of the mode of the assembler.
+///
+/// # Errors
+/// Returns an AssemblyError if:
+/// - the instruction is malformed, or its parameters are out of bounds.
+/// - the start of an interval is greater than its end.
+/// - locals are reported outside of a procedure which declares locals.
+pub(super) fn parse_debug(
+    span_ops: &mut [Operation],
+    op: &Token,
+    num_proc_locals: u32,
+    decorators: &mut DecoratorList,
+    in_debug_mode: bool,
+) -> Result<(), AssemblyError> {
+    validate_operation!(op, "debug.stack|mem|local", 0..2);
+
+    let options = match op.parts()[1] {
+        "stack" => match op.num_parts() {
+            2 => DebugOptions::StackAll,
+            3 => DebugOptions::StackTop(parse_u32_param(op, 2, 1, u16::MAX as u32)? as u16),
+            _ => return Err(AssemblyError::extra_param(op)),
+        },
+        "mem" => match op.num_parts() {
+            2 => DebugOptions::MemAll,
+            _ => {
+                let (start, end) = parse_interval(op, u32::MAX)?;
+                DebugOptions::MemInterval(start as u64, end as u64)
+            }
+        },
+        "local" => {
+            if num_proc_locals == 0 {
+                return Err(AssemblyError::invalid_op_with_reason(
+                    op,
+                    "no procedure locals were declared",
+                ));
+            }
+            let (start, end) = match op.num_parts() {
+                2 => (0, num_proc_locals - 1),
+                _ => parse_interval(op, num_proc_locals - 1)?,
+            };
+            DebugOptions::LocalInterval(start, end)
+        }
+        _ => return Err(AssemblyError::invalid_op(op)),
+    };
+
+    if in_debug_mode {
+        decorators.push((span_ops.len(), Decorator::Debug(options)));
+    }
+
+    Ok(())
+}
+
+/// Appends a decorator which checks the top of the stack against the listed values if the
+/// assembler is in debug mode; otherwise, the instruction is discarded. The values of the
+/// `expect_stack.[a,b,...]` instruction are listed starting with the top of the stack, and at most
+/// 16 values can be listed. If the values at the top of the stack differ from the listed values,
+/// the execution fails with an error which lists both sets of values.
+///
+/// Like debug instructions, the instruction is never compiled into operations, and its
+/// parameters are validated regardless of the mode of the assembler.
+///
+/// # Errors
+/// Returns an AssemblyError if:
+/// - the list of values is not enclosed in square brackets, or is empty.
+/// - a value is not a valid field element, or more than 16 values are listed.
+pub(super) fn parse_expect_stack(
+    span_ops: &mut [Operation],
+    op: &Token,
+    decorators: &mut DecoratorList,
+    in_debug_mode: bool,
+) -> Result<(), AssemblyError> {
+    validate_operation!(op, "expect_stack", 1);
+
+    let values = op.parts()[1]
+        .strip_prefix('[')
+        .and_then(|values| values.strip_suffix(']'))
+        .ok_or_else(|| AssemblyError::invalid_param(op, 1))?
+        .split(',')
+        .map(|value| match value.strip_prefix("0x") {
+            Some(value) => parse_hex_param(op, 1, value),
+            None => parse_decimal_param(op, 1, value),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    if values.len() > MIN_STACK_DEPTH {
+        return Err(AssemblyError::invalid_param_with_reason(
+            op,
+            1,
+            "at most 16 values can be expected",
+        ));
+    }
+
+    if in_debug_mode {
+        decorators.push((span_ops.len(), Decorator::ExpectStack(values)));
+    }
+
+    Ok(())
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Parses the inclusive interval specified by the parameters of the instruction; if only one
+/// parameter is provided, the interval consists of a single value.
+fn parse_interval(op: &Token, upper_bound: u32) -> Result<(u32, u32), AssemblyError> {
+    let start = parse_u32_param(op, 2, 0, upper_bound)?;
+    if op.num_parts() == 3 {
+        return Ok((start, start));
+    }
+
+    let end = parse_u32_param(op, 3, 0, upper_bound)?;
+    if start > end {
+        return Err(AssemblyError::invalid_param_with_reason(
+            op,
+            3,
+            "the end of the interval must not be smaller than its start",
+        ));
+    }
+    Ok((start, end))
+}
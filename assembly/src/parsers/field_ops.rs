@@ -1,32 +1,61 @@
 use vm_core::utils::PushMany;
 
 use super::{
-    super::validate_operation, parse_element_param, AssemblyError, Felt, FieldElement, Operation,
-    Token, Vec,
+    super::validate_operation, parse_element_param, parse_error_code, parse_string_param,
+    AssemblyError, Decorator, DecoratorList, Felt, FieldElement, Operation, StarkField, Token, Vec,
 };
 
 // ASSERTIONS AND TESTS
 // ================================================================================================
 
-/// Appends ASSERT operation to the span block.
+/// Appends ASSERT operation to the span block. The error code of the assertion can be specified
+/// via an `err=CODE` parameter (e.g., `assert.err=4096`); otherwise, it is 0.
 pub(super) fn parse_assert(span_ops: &mut Vec<Operation>, op: &Token) -> Result<(), AssemblyError> {
-    if op.num_parts() > 1 {
-        return Err(AssemblyError::extra_param(op));
-    }
-    span_ops.push(Operation::Assert);
+    let err_code = parse_error_code(op)?;
+    span_ops.push(Operation::Assert(err_code));
+    Ok(())
+}
+
+/// Appends PAD ASSERT operation sequence to the span block, preceded by a decorator carrying the
+/// message specified via a quoted string parameter (e.g., `panic."invalid input"`). The operations
+/// always fail; the message is reported in the execution error, but is not a part of the MAST.
+pub(super) fn parse_panic(
+    span_ops: &mut Vec<Operation>,
+    op: &Token,
+    decorators: &mut DecoratorList,
+) -> Result<(), AssemblyError> {
+    let message = match op.num_parts() {
+        0 | 1 => return Err(AssemblyError::missing_param(op)),
+        2 => parse_string_param(op, 1)?,
+        _ => return Err(AssemblyError::extra_param(op)),
+    };
+    decorators.push((span_ops.len(), Decorator::Panic(message)));
+    span_ops.push(Operation::Pad);
+    span_ops.push(Operation::Assert(0));
+    Ok(())
+}
+
+/// Appends EQZ ASSERT operation sequence to the span block. The error code of the assertion can
+/// be specified in the same way as for `assert`.
+pub(super) fn parse_assertz(
+    span_ops: &mut Vec<Operation>,
+    op: &Token,
+) -> Result<(), AssemblyError> {
+    let err_code = parse_error_code(op)?;
+    span_ops.push(Operation::Eqz);
+    span_ops.push(Operation::Assert(err_code));
     Ok(())
 }
 
-/// Appends EQ ASSERT operation sequence to the span block.
+/// Appends EQ ASSERT operation sequence to the span block. The error code of the assertion can be
+/// specified in the same way as for `assert`.
 pub(super) fn parse_assert_eq(
     span_ops: &mut Vec<Operation>,
     op: &Token,
 ) -> Result<(), AssemblyError> {
-    if op.num_parts() > 1 {
-        return Err(AssemblyError::extra_param(op));
-    }
+    let err_code = parse_error_code(op)?;
     span_ops.push(Operation::Eq);
-    span_ops.push(Operation::Assert);
+    span_ops.push(Operation::Assert(err_code));
     Ok(())
 }
 
@@ -151,6 +180,45 @@ pub(super) fn parse_pow2(
     Ok(())
 }
 
+/// Translates exp assembly instructions to VM operations.
+///
+/// Appends a sequence of operations to raise the base `a` to the power `b`. The instruction comes
+/// in the following forms:
+/// - `exp`: the exponent is provided via the stack as [b, a, ...] and must be smaller than 2^64.
+/// - `exp.uN`: the exponent is provided via the stack as [b, a, ...] and must be smaller than
+///   2^N, where N is between 1 and 64. The bit-length N determines the number of iterations of the
+///   square-and-multiply expansion, and thus smaller exponents can be handled more cheaply.
+/// - `exp.b`: the exponent is provided via an immediate value, and the stack is [a, ...].
+///
+/// In all cases, the resulting stack is [a^b, ...]. If the exponent provided via the stack does
+/// not fit into the specified number of bits, execution fails.
+///
+/// VM cycles per mode:
+/// - exp.uN: 14 * N + 4 cycles for N <= 32, and 14 * N + 6 cycles for N > 32.
+/// - exp: 902 cycles.
+/// - exp.b: at most 4 * floor(log2(b)) + 3 cycles.
+pub(super) fn parse_exp(span_ops: &mut Vec<Operation>, op: &Token) -> Result<(), AssemblyError> {
+    match op.num_parts() {
+        1 => append_exp_op(span_ops, MAX_EXP_BITS),
+        2 => {
+            let param = op.parts()[1];
+            match param.strip_prefix('u') {
+                Some(num_bits) => {
+                    let num_bits = parse_exp_bits(op, num_bits)?;
+                    append_exp_op(span_ops, num_bits);
+                }
+                None => {
+                    let exponent = parse_element_param(op, 1)?;
+                    append_exp_imm_op(span_ops, exponent.as_int());
+                }
+            }
+        }
+        _ => return Err(AssemblyError::extra_param(op)),
+    }
+
+    Ok(())
+}
+
 // BOOLEAN OPERATIONS
 // ================================================================================================
 
@@ -438,7 +506,7 @@ pub(super) fn append_pow2_op(span_ops: &mut Vec<Operation>, checked_mode: bool)
         span_ops.push(Operation::U32div);
         span_ops.push(Operation::Swap);
         span_ops.push(Operation::Eqz);
-        span_ops.push(Operation::Assert);
+        span_ops.push(Operation::Assert(0));
     }
 
     span_ops.push(Operation::Push(Felt::new(2)));
@@ -497,6 +565,157 @@ fn pow_of_two_at_bit(bit_idx: u32) -> u64 {
     base.pow(exponent)
 }
 
+// EXPONENTIATION HELPER FUNCTIONS
+// ================================================================================================
+
+/// The maximum bit-length of an exponent provided via the stack.
+const MAX_EXP_BITS: u32 = 64;
+
+/// Parses the bit-length of an `exp.uN` instruction, which must be between 1 and 64.
+fn parse_exp_bits(op: &Token, num_bits: &str) -> Result<u32, AssemblyError> {
+    match num_bits.parse::<u32>() {
+        Ok(num_bits) if (1..=MAX_EXP_BITS).contains(&num_bits) => Ok(num_bits),
+        _ => Err(AssemblyError::invalid_param_with_reason(
+            op,
+            1,
+            format!("bit-length must be between 1 and {}", MAX_EXP_BITS).as_str(),
+        )),
+    }
+}
+
+/// Raises the base to the power of an exponent of at most `num_bits` bits using a fixed number of
+/// square-and-multiply iterations.
+///
+/// The expected starting state of the stack (from the top) is: [b, a, ...].
+///
+/// After these operations, the stack state will be: [a^b, ...].
+///
+/// The exponent is split into its low and high 32-bit limbs. The low limb is processed first; if
+/// the exponent has more than 32 bits, the high limb is processed with the remaining iterations,
+/// and otherwise the high limb must be 0. After the last iteration, the remaining part of the
+/// exponent must be 0, which ensures that the exponent fits into `num_bits` bits.
+///
+/// VM cycles: 14 * num_bits + 4 for num_bits <= 32, and 14 * num_bits + 6 otherwise.
+fn append_exp_op(span_ops: &mut Vec<Operation>, num_bits: u32) {
+    // => [b_hi, b_lo, a, ...]
+    span_ops.push(Operation::U32split);
+
+    if num_bits <= 32 {
+        // make sure the high limb is 0 and initialize the accumulator to 1
+        // => [b_lo, a, 1, ...]
+        span_ops.push(Operation::Eqz);
+        span_ops.push(Operation::Assert(0));
+        span_ops.push(Operation::Push(Felt::ONE));
+        span_ops.push(Operation::MovDn2);
+
+        append_exp_iterations(span_ops, num_bits, false);
+    } else {
+        // initialize the accumulator to 1 and move the high limb out of the way
+        // => [b_lo, a, 1, b_hi, ...]
+        span_ops.push(Operation::Push(Felt::ONE));
+        span_ops.push(Operation::Swap);
+        span_ops.push(Operation::MovDn3);
+        span_ops.push(Operation::MovDn2);
+
+        append_exp_iterations(span_ops, 32, true);
+
+        // the low limb is a u32 value, and thus nothing remains of it after 32 iterations
+        // => [b_hi, a^(2^32), acc, ...]
+        span_ops.push(Operation::Drop);
+        span_ops.push(Operation::MovUp2);
+
+        append_exp_iterations(span_ops, num_bits - 32, false);
+    }
+
+    // make sure nothing remains of the exponent and drop the base
+    // => [a^b, ...]
+    span_ops.push(Operation::Eqz);
+    span_ops.push(Operation::Assert(0));
+    span_ops.push(Operation::Drop);
+}
+
+/// Performs the specified number of square-and-multiply iterations, each of which consumes the
+/// least significant bit of the exponent.
+///
+/// The expected starting state of the stack (from the top) is: [e, base, acc, ...], where e is a
+/// u32 value.
+///
+/// After these operations, the stack state will be: [e >> n, base^(2^n), acc * base^(e mod 2^n)]
+/// where n is the number of iterations. If `square_last` is false, the base is not squared in the
+/// last iteration, as the result is not needed.
+///
+/// VM cycles: 14 cycles per iteration, minus 4 cycles if `square_last` is false.
+fn append_exp_iterations(span_ops: &mut Vec<Operation>, num_iterations: u32, square_last: bool) {
+    for i in 0..num_iterations {
+        // extract the least significant bit of the exponent
+        // => [bit, e >> 1, base, acc, ...]
+        span_ops.push(Operation::Push(Felt::new(2)));
+        span_ops.push(Operation::U32div);
+
+        // multiply the accumulator by the base if the bit is set
+        // => [acc', e >> 1, base, ...]
+        span_ops.push(Operation::MovUp3);
+        span_ops.push(Operation::Dup3);
+        span_ops.push(Operation::Dup1);
+        span_ops.push(Operation::Mul);
+        span_ops.push(Operation::MovUp2);
+        span_ops.push(Operation::CSwap);
+        span_ops.push(Operation::Drop);
+
+        // => [e >> 1, base, acc', ...]
+        span_ops.push(Operation::MovDn2);
+
+        // square the base
+        // => [e >> 1, base^2, acc', ...]
+        if square_last || i + 1 < num_iterations {
+            span_ops.push(Operation::Swap);
+            span_ops.push(Operation::Dup0);
+            span_ops.push(Operation::Mul);
+            span_ops.push(Operation::Swap);
+        }
+    }
+}
+
+/// Raises the element at the top of the stack to the power of the specified exponent.
+///
+/// The expected starting state of the stack (from the top) is: [a, ...].
+///
+/// After these operations, the stack state will be: [a^b, ...].
+///
+/// The bits of the exponent are known at compile time, and thus only the required
+/// multiplications are performed, starting with the most significant bit.
+///
+/// VM cycles: 2 cycles per squaring, 2 cycles per set bit (other than the most significant one),
+/// and 3 additional cycles; raising to the power of 0 or 1 takes 2 and 0 cycles respectively.
+fn append_exp_imm_op(span_ops: &mut Vec<Operation>, exponent: u64) {
+    match exponent {
+        0 => {
+            span_ops.push(Operation::Drop);
+            span_ops.push(Operation::Push(Felt::ONE));
+            return;
+        }
+        1 => return,
+        _ => (),
+    }
+
+    // => [acc, a, ...]
+    span_ops.push(Operation::Dup0);
+
+    let num_bits = u64::BITS - exponent.leading_zeros();
+    for bit_idx in (0..num_bits - 1).rev() {
+        span_ops.push(Operation::Dup0);
+        span_ops.push(Operation::Mul);
+        if (exponent >> bit_idx) & 1 == 1 {
+            span_ops.push(Operation::Dup1);
+            span_ops.push(Operation::Mul);
+        }
+    }
+
+    // => [a^b, ...]
+    span_ops.push(Operation::Swap);
+    span_ops.push(Operation::Drop);
+}
+
 // COMPARISON OPERATION HELPER FUNCTIONS
 // ================================================================================================
 
@@ -767,4 +986,42 @@ mod tests {
             expected
         );
     }
+
+    #[test]
+    fn exp() {
+        // parse_exp should return an error if called with an invalid bit-length or extra params
+        let mut span_ops: Vec<Operation> = Vec::new();
+        let op_pos = 0;
+
+        for op_str in ["exp.u0", "exp.u65", "exp.u", "exp.ux"] {
+            let op = Token::new(op_str, op_pos);
+            let expected = AssemblyError::invalid_param_with_reason(
+                &op,
+                1,
+                "bit-length must be between 1 and 64",
+            );
+            assert_eq!(parse_exp(&mut span_ops, &op).unwrap_err(), expected);
+        }
+
+        let op_too_long = Token::new("exp.u8.2", op_pos);
+        let expected = AssemblyError::extra_param(&op_too_long);
+        assert_eq!(
+            parse_exp(&mut span_ops, &op_too_long).unwrap_err(),
+            expected
+        );
+
+        // the number of cycles depends on the bit-length of the exponent
+        span_ops.clear();
+        parse_exp(&mut span_ops, &Token::new("exp.u8", op_pos)).unwrap();
+        assert_eq!(14 * 8 + 4, span_ops.len());
+
+        span_ops.clear();
+        parse_exp(&mut span_ops, &Token::new("exp", op_pos)).unwrap();
+        assert_eq!(14 * 64 + 6, span_ops.len());
+
+        // 5 = 0b101: one squaring per bit after the most significant one, one multiplication
+        span_ops.clear();
+        parse_exp(&mut span_ops, &Token::new("exp.5", op_pos)).unwrap();
+        assert_eq!(2 * 2 + 2 + 3, span_ops.len());
+    }
 }
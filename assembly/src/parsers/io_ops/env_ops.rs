@@ -1,6 +1,7 @@
 use super::{
     parse_u32_param, push_value, validate_operation, AssemblyError, Felt, Operation, Token, Vec,
 };
+use vm_core::utils::PushMany;
 
 // ENVIRONMENT INPUTS
 // ================================================================================================
@@ -12,6 +13,11 @@ use super::{
 /// stack.
 /// - `push.env.sdepth` pushes the current depth of the stack onto the top of the stack, which is
 /// handled directly by the `SDEPTH` operation.
+/// - `push.env.clk` pushes the current value of the clock cycle onto the top of the stack, which
+///   is handled directly by the `CLK` operation.
+/// - `push.env.caller` pushes the hash of the caller onto the top of the stack. The stack is
+///   padded with a word of ZEROs which is then overwritten by the `CALLER` operation. This
+///   instruction can be executed only in the kernel context.
 ///
 /// # Errors
 ///
@@ -23,7 +29,7 @@ pub fn parse_push_env(
     op: &Token,
     num_proc_locals: u32,
 ) -> Result<(), AssemblyError> {
-    validate_operation!(op, "push.env.locaddr|sdepth");
+    validate_operation!(op, "push.env.locaddr|sdepth|clk|caller");
 
     // update the span block
     match op.parts()[2] {
@@ -44,6 +50,15 @@ pub fn parse_push_env(
             validate_operation!(@only_params op, "push.env.sdepth", 0);
             span_ops.push(Operation::SDepth);
         }
+        "clk" => {
+            validate_operation!(@only_params op, "push.env.clk", 0);
+            span_ops.push(Operation::Clk);
+        }
+        "caller" => {
+            validate_operation!(@only_params op, "push.env.caller", 0);
+            span_ops.push_many(Operation::Pad, 4);
+            span_ops.push(Operation::Caller);
+        }
         _ => return Err(AssemblyError::invalid_op(op)),
     }
 
@@ -79,6 +94,34 @@ mod tests {
         assert_eq!(span_ops, expected);
     }
 
+    #[test]
+    fn push_env_clk_and_caller() {
+        let num_proc_locals = 0;
+        let mut span_ops = Vec::new();
+
+        let op = Token::new("push.env.clk", 0);
+        parse_push(&mut span_ops, &op, num_proc_locals).expect("Failed to parse push.env.clk");
+        assert_eq!(span_ops, vec![Operation::Clk]);
+
+        // the stack is padded with a word which is then overwritten with the hash of the caller
+        let op = Token::new("push.env.caller", 0);
+        parse_push(&mut span_ops, &op, num_proc_locals).expect("Failed to parse push.env.caller");
+        let mut expected = vec![Operation::Clk];
+        expected.extend([Operation::Pad; 4]);
+        expected.push(Operation::Caller);
+        assert_eq!(span_ops, expected);
+
+        // neither instruction accepts parameters
+        for asm_op in ["push.env.clk.0", "push.env.caller.0"] {
+            let op = Token::new(asm_op, 0);
+            let expected = AssemblyError::extra_param(&op);
+            assert_eq!(
+                parse_push(&mut span_ops, &op, num_proc_locals).unwrap_err(),
+                expected
+            );
+        }
+    }
+
     #[test]
     fn push_env_locaddr() {
         let asm_op = "push.env.locaddr";
@@ -108,7 +151,8 @@ mod tests {
 
         // --- invalid env var --------------------------------------------------------------------
         let op_val_invalid = Token::new("push.env.invalid", pos);
-        let expected = AssemblyError::unexpected_token(&op_val_invalid, "push.env.locaddr|sdepth");
+        let expected =
+            AssemblyError::unexpected_token(&op_val_invalid, "push.env.locaddr|sdepth|clk|caller");
         assert_eq!(
             parse_push(&mut span_ops, &op_val_invalid, num_proc_locals).unwrap_err(),
             expected
@@ -256,23 +256,59 @@ pub fn parse_storew(
 /// - adv.u64div: this operation interprets four elements at the top of the stack as two 64-bit
 ///   values (represented by 32-bit limbs), divides one value by another, and injects the quotient
 ///   and the remainder into the advice tape.
+/// - adv.gas: this operation injects the amount of gas consumed by the program so far into the
+///   advice tape. If the program is executed without gas metering, ZERO is injected.
+/// - adv.mapval: this operation interprets the word at the top of the stack as a key of the
+///   advice map, and injects the number of values of the entry with this key followed by the
+///   values (padded with ZEROs to the next multiple of 8) into the advice tape.
+/// - adv.ext2inv: this operation interprets two elements at the top of the stack as an element
+///   of the quadratic extension field, and injects its multiplicative inverse into the advice
+///   tape.
 pub fn parse_adv_inject(
     span_ops: &mut [Operation],
     op: &Token,
     decorators: &mut DecoratorList,
 ) -> Result<(), AssemblyError> {
-    validate_operation!(op, "adv.u64div");
+    validate_operation!(op, "adv.u64div|gas|mapval|ext2inv");
     match op.parts()[1] {
         "u64div" => decorators.push((
             span_ops.len(),
             Decorator::Advice(AdviceInjector::DivResultU64),
         )),
+        "gas" => decorators.push((span_ops.len(), Decorator::Advice(AdviceInjector::GasUsed))),
+        "mapval" => decorators.push((span_ops.len(), Decorator::Advice(AdviceInjector::MapValue))),
+        "ext2inv" => decorators.push((span_ops.len(), Decorator::Advice(AdviceInjector::Ext2Inv))),
         _ => return Err(AssemblyError::invalid_op(op)),
     };
 
     Ok(())
 }
 
+// EVENTS
+// ================================================================================================
+
+/// Appends an event decorator for the `emit.<event_id>` instruction to the span block. When the
+/// decorator is executed, the processor passes the event ID together with the current VM state to
+/// the advice provider, which may react to it (e.g., by injecting values into the advice tape).
+///
+/// The instruction is not compiled into any VM operations, and thus it does not affect the hash
+/// of the program. Event IDs can be any u32 values.
+///
+/// # Errors
+/// Returns an AssemblyError if the instruction is malformed or the event ID is not a valid u32
+/// value.
+pub fn parse_emit(
+    span_ops: &mut [Operation],
+    op: &Token,
+    decorators: &mut DecoratorList,
+) -> Result<(), AssemblyError> {
+    validate_operation!(op, "emit", 1);
+    let event_id = parse_u32_param(op, 1, 0, u32::MAX)?;
+    decorators.push((span_ops.len(), Decorator::Event(event_id)));
+
+    Ok(())
+}
+
 // TESTS
 // ================================================================================================
 
@@ -1,16 +1,24 @@
-use super::{AssemblyContext, AssemblyError, CodeBlock, Token, TokenStream};
-pub use blocks::{combine_blocks, parse_code_blocks};
+use super::{
+    AssemblyContext, AssemblyError, CodeBlock, Digest, Expansion, Token, TokenStream,
+    MODULE_PATH_DELIM,
+};
+pub use blocks::{combine_blocks, combine_mergeable_blocks, parse_code_blocks};
+use constants::eval_const_expr;
+pub use constants::{parse_constant, parse_data_segment};
 use u32_ops::U32OpMode;
 use vm_core::{
     utils::{
         collections::Vec,
         string::{String, ToString},
     },
-    AssemblyOp, Decorator, DecoratorList, Felt, FieldElement, Operation, StarkField,
+    AssemblyOp, DebugOptions, Decorator, DecoratorList, Felt, FieldElement, Operation,
+    SourceLocation, StarkField, MIN_STACK_DEPTH,
 };
 
 mod blocks;
+mod constants;
 mod crypto_ops;
+mod debug_ops;
 mod field_ops;
 mod io_ops;
 mod stack_ops;
@@ -20,28 +28,35 @@ mod u32_ops;
 // ================================================================================================
 
 /// Transforms an assembly instruction into a sequence of one or more VM instructions.
+///
+/// In debug mode, the instruction is described by an AsmOp decorator which also carries the
+/// location of the instruction in the source code, if provided.
 fn parse_op_token(
     op: &Token,
     span_ops: &mut Vec<Operation>,
     num_proc_locals: u32,
     decorators: &mut DecoratorList,
     in_debug_mode: bool,
+    location: Option<SourceLocation>,
 ) -> Result<(), AssemblyError> {
     let dec_len = decorators.len();
     // if assembler is in debug mode, populate decorators list with debug related
     // decorators like AsmOp.
     if in_debug_mode {
-        decorators.push((
-            span_ops.len(),
-            Decorator::AsmOp(AssemblyOp::new(op.to_string(), 1)),
-        ));
+        let mut asmop = AssemblyOp::new(op.to_string(), 1);
+        if let Some(location) = location {
+            asmop = asmop.with_location(location);
+        }
+        decorators.push((span_ops.len(), Decorator::AsmOp(asmop)));
     }
 
     // based on the instruction, invoke the correct parser for the operation
     match op.parts()[0] {
         // ----- field operations -----------------------------------------------------------------
         "assert" => field_ops::parse_assert(span_ops, op),
+        "assertz" => field_ops::parse_assertz(span_ops, op),
         "assert_eq" => field_ops::parse_assert_eq(span_ops, op),
+        "panic" => field_ops::parse_panic(span_ops, op, decorators),
 
         "add" => field_ops::parse_add(span_ops, op),
         "sub" => field_ops::parse_sub(span_ops, op),
@@ -52,6 +67,7 @@ fn parse_op_token(
 
         "checked_pow2" => field_ops::parse_pow2(span_ops, op, true),
         "unchecked_pow2" => field_ops::parse_pow2(span_ops, op, false),
+        "exp" => field_ops::parse_exp(span_ops, op),
 
         "not" => field_ops::parse_not(span_ops, op),
         "and" => field_ops::parse_and(span_ops, op),
@@ -137,6 +153,13 @@ fn parse_op_token(
         "u32checked_max" => u32_ops::parse_u32max(span_ops, op, U32OpMode::Checked),
         "u32unchecked_max" => u32_ops::parse_u32max(span_ops, op, U32OpMode::Unchecked),
 
+        "u32wrapping_add_w" => u32_ops::parse_u32wrapping_add_w(span_ops, op),
+        "u32and_w" => u32_ops::parse_u32and_w(span_ops, op),
+        "u32or_w" => u32_ops::parse_u32or_w(span_ops, op),
+        "u32xor_w" => u32_ops::parse_u32xor_w(span_ops, op),
+        "u32rotl_w" => u32_ops::parse_u32rotl_w(span_ops, op),
+        "u32rotr_w" => u32_ops::parse_u32rotr_w(span_ops, op),
+
         // ----- stack manipulation ---------------------------------------------------------------
         "drop" => stack_ops::parse_drop(span_ops, op),
         "dropw" => stack_ops::parse_dropw(span_ops, op),
@@ -164,6 +187,7 @@ fn parse_op_token(
         "loadw" => io_ops::parse_loadw(span_ops, op, num_proc_locals),
         "storew" => io_ops::parse_storew(span_ops, op, num_proc_locals),
         "adv" => io_ops::parse_adv_inject(span_ops, op, decorators),
+        "emit" => io_ops::parse_emit(span_ops, op, decorators),
 
         // ----- cryptographic operations ---------------------------------------------------------
         "rphash" => crypto_ops::parse_rphash(span_ops, op),
@@ -172,6 +196,11 @@ fn parse_op_token(
         "mtree_get" => crypto_ops::parse_mtree_get(span_ops, op, decorators),
         "mtree_set" => crypto_ops::parse_mtree_set(span_ops, op, decorators),
         "mtree_cwm" => crypto_ops::parse_mtree_cwm(span_ops, op, decorators),
+        "mtree_merge" => crypto_ops::parse_mtree_merge(span_ops, op, decorators),
+
+        // ----- debugging ----------------------------------------------------------------------
+        "debug" => debug_ops::parse_debug(span_ops, op, num_proc_locals, decorators, in_debug_mode),
+        "expect_stack" => debug_ops::parse_expect_stack(span_ops, op, decorators, in_debug_mode),
 
         // ----- catch all ------------------------------------------------------------------------
         _ => return Err(AssemblyError::invalid_op(op)),
@@ -188,6 +217,136 @@ fn parse_op_token(
     Ok(())
 }
 
+// CUSTOM OP PARSER
+// ================================================================================================
+
+/// Replaces a custom instruction (i.e., an instruction of the form `namespace::name`) with the
+/// expansion returned by the extension registered in the context for the namespace.
+///
+/// Operations into which the expansion is compiled are attributed to the custom instruction; in
+/// debug mode, the custom instruction is described by a single AsmOp decorator which also carries
+/// the location of the instruction in the source code, if provided.
+///
+/// # Errors
+/// Returns an error if no extension is registered for the namespace, if the extension rejects the
+/// instruction, or if the expansion contains an invalid or unsupported instruction.
+fn parse_custom_op(
+    op: &Token,
+    context: &AssemblyContext,
+    span_ops: &mut Vec<Operation>,
+    num_proc_locals: u32,
+    decorators: &mut DecoratorList,
+    in_debug_mode: bool,
+    location: Option<SourceLocation>,
+) -> Result<(), AssemblyError> {
+    let (namespace, name) = op.parts()[0]
+        .split_once(MODULE_PATH_DELIM)
+        .ok_or_else(|| AssemblyError::invalid_op(op))?;
+    let extension = context
+        .extension(namespace)
+        .ok_or_else(|| AssemblyError::invalid_op(op))?;
+
+    let dec_len = decorators.len();
+    if in_debug_mode {
+        let mut asmop = AssemblyOp::new(op.to_string(), 1);
+        if let Some(location) = location {
+            asmop = asmop.with_location(location);
+        }
+        decorators.push((span_ops.len(), Decorator::AsmOp(asmop)));
+    }
+
+    let expansion = extension
+        .expand(name, &op.parts()[1..])
+        .map_err(|reason| AssemblyError::invalid_custom_op(op, &reason))?;
+    match expansion {
+        Expansion::Instructions(source) => {
+            for instruction in source.split_whitespace() {
+                let token = Token::new(instruction, op.pos());
+                if token.is_control_token() || token.parts()[0].contains(MODULE_PATH_DELIM) {
+                    let reason = format!("expansion contains unsupported instruction '{}'", token);
+                    return Err(AssemblyError::invalid_custom_op(op, &reason));
+                }
+
+                // instructions of the expansion are not described by their own AsmOp decorators
+                let mut expanded = DecoratorList::new();
+                parse_op_token(
+                    &token,
+                    span_ops,
+                    num_proc_locals,
+                    &mut expanded,
+                    in_debug_mode,
+                    None,
+                )
+                .map_err(|err| {
+                    let reason = format!("invalid expansion: {}", err.message());
+                    AssemblyError::invalid_custom_op(op, &reason)
+                })?;
+                decorators.extend(
+                    expanded
+                        .into_iter()
+                        .filter(|(_, decorator)| !matches!(decorator, Decorator::AsmOp(_))),
+                );
+            }
+        }
+        Expansion::Decorator(decorator) => decorators.push((span_ops.len(), decorator)),
+    }
+
+    if in_debug_mode {
+        let op_start = decorators[dec_len].0;
+        if let Decorator::AsmOp(assembly_op) = &mut decorators[dec_len].1 {
+            assembly_op.set_num_cycles((span_ops.len() - op_start) as u8)
+        }
+    }
+
+    Ok(())
+}
+
+// PRESERVED STACK ELEMENTS
+// ================================================================================================
+
+/// Returns operations which save the specified number of the deepest of the top 16 stack
+/// elements into procedure locals, and operations which put the saved elements back into their
+/// original positions.
+///
+/// The element at position `16 - num_preserved + i` is saved into the local at index
+/// `first_local + i`; both sequences of operations leave the depth of the stack unchanged. When
+/// restored, the saved elements replace whatever elements are at their positions at that time.
+pub fn build_preserve_ops(
+    first_local: u32,
+    num_preserved: u32,
+) -> (Vec<Operation>, Vec<Operation>) {
+    let num_proc_locals = first_local + num_preserved;
+    let mut save_ops = Vec::new();
+    let mut restore_ops = Vec::new();
+    for i in 0..num_preserved {
+        let pos = MIN_STACK_DEPTH as u32 - num_preserved + i;
+        let local = first_local + i;
+        for instruction in [format!("dup.{pos}"), format!("pop.local.{local}")] {
+            parse_generated_op(&instruction, &mut save_ops, num_proc_locals);
+        }
+        for instruction in [
+            format!("movup.{pos}"),
+            "drop".to_string(),
+            format!("push.local.{local}"),
+            format!("movdn.{pos}"),
+        ] {
+            parse_generated_op(&instruction, &mut restore_ops, num_proc_locals);
+        }
+    }
+    (save_ops, restore_ops)
+}
+
+/// Translates an instruction generated by the assembler into VM operations.
+///
+/// # Panics
+/// Panics if the instruction is not valid.
+fn parse_generated_op(instruction: &str, span_ops: &mut Vec<Operation>, num_proc_locals: u32) {
+    let op = Token::new(instruction, 0);
+    let mut decorators = DecoratorList::new();
+    parse_op_token(&op, span_ops, num_proc_locals, &mut decorators, false, None)
+        .expect("invalid generated instruction");
+}
+
 // HELPER FUNCTIONS
 // ================================================================================================
 
@@ -279,6 +438,56 @@ fn parse_u32_param(
     Ok(result)
 }
 
+/// Parses the optional error code parameter of an assertion (e.g., `assert.err=4096`). If the
+/// parameter is not provided, the error code is 0.
+///
+/// # Errors
+/// Returns an AssemblyError if the instruction has more than one parameter, or if the parameter is
+/// not of the form `err=CODE` where CODE is a decimal or a hexadecimal 32-bit unsigned integer.
+fn parse_error_code(op: &Token) -> Result<u32, AssemblyError> {
+    match op.num_parts() {
+        1 => Ok(0),
+        2 => {
+            let param = op.parts()[1]
+                .strip_prefix("err=")
+                .ok_or_else(|| AssemblyError::invalid_param(op, 1))?;
+            let err_code = match param.strip_prefix("0x") {
+                Some(hex) => u32::from_str_radix(hex, 16),
+                None => param.parse::<u32>(),
+            };
+            err_code.map_err(|_| {
+                AssemblyError::invalid_param_with_reason(
+                    op,
+                    1,
+                    "error code must be a 32-bit unsigned integer",
+                )
+            })
+        }
+        _ => Err(AssemblyError::extra_param(op)),
+    }
+}
+
+/// Parses the parameter at the specified index as a string enclosed in double quotes, and returns
+/// the string without the quotes.
+///
+/// # Errors
+/// Returns an invalid param AssemblyError if the parameter is not enclosed in double quotes, or if
+/// the string itself contains double quotes.
+fn parse_string_param(op: &Token, param_idx: usize) -> Result<String, AssemblyError> {
+    let param_value = op.parts()[param_idx];
+    match param_value
+        .strip_prefix('"')
+        .and_then(|value| value.strip_suffix('"'))
+    {
+        Some(value) if !value.contains('"') => Ok(value.to_string()),
+        _ => Err(AssemblyError::invalid_param_with_reason(
+            op,
+            param_idx,
+            "parameter must be a string enclosed in double quotes",
+        )),
+    }
+}
+
 /// This is a helper function that appends a PUSH operation to the span block which puts the
 /// provided value parameter onto the stack.
 ///
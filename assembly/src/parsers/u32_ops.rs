@@ -598,7 +598,7 @@ pub fn parse_u32rotr(
                 span_ops.push(Operation::Dup1);
                 span_ops.push(Operation::U32sub);
                 span_ops.push(Operation::Not);
-                span_ops.push(Operation::Assert);
+                span_ops.push(Operation::Assert(0));
                 span_ops.push(Operation::Incr);
                 span_ops.push(Operation::Dup1);
                 // If 32-b = 32, replace it with 0.
@@ -843,6 +843,106 @@ pub fn parse_u32max(
     Ok(())
 }
 
+// WORD OPERATIONS
+// ================================================================================================
+
+/// Translates u32wrapping_add_w assembly instruction to VM operations.
+///
+/// Adds the elements of the word at the top of the stack to the corresponding elements of the
+/// word below it, discarding the overflow bits. Each pair of elements is added via `U32ADD DROP`,
+/// total of 15 VM cycles.
+pub fn parse_u32wrapping_add_w(
+    span_ops: &mut Vec<Operation>,
+    op: &Token,
+) -> Result<(), AssemblyError> {
+    match op.num_parts() {
+        0 => return Err(AssemblyError::missing_param(op)),
+        1 => append_word_binary_op(span_ops, &[Operation::U32add, Operation::Drop]),
+        _ => return Err(AssemblyError::extra_param(op)),
+    }
+
+    Ok(())
+}
+
+/// Translates u32and_w assembly instruction to VM operations.
+///
+/// Computes a bitwise AND of each pair of corresponding elements of the top two words via
+/// `U32AND`, total of 11 VM cycles. The inputs are asserted to be u32 values implicitly.
+pub fn parse_u32and_w(span_ops: &mut Vec<Operation>, op: &Token) -> Result<(), AssemblyError> {
+    match op.num_parts() {
+        0 => return Err(AssemblyError::missing_param(op)),
+        1 => append_word_binary_op(span_ops, &[Operation::U32and]),
+        _ => return Err(AssemblyError::extra_param(op)),
+    }
+
+    Ok(())
+}
+
+/// Translates u32or_w assembly instruction to VM operations.
+///
+/// Computes a bitwise OR of each pair of corresponding elements of the top two words via
+/// `U32OR`, total of 11 VM cycles. The inputs are asserted to be u32 values implicitly.
+pub fn parse_u32or_w(span_ops: &mut Vec<Operation>, op: &Token) -> Result<(), AssemblyError> {
+    match op.num_parts() {
+        0 => return Err(AssemblyError::missing_param(op)),
+        1 => append_word_binary_op(span_ops, &[Operation::U32or]),
+        _ => return Err(AssemblyError::extra_param(op)),
+    }
+
+    Ok(())
+}
+
+/// Translates u32xor_w assembly instruction to VM operations.
+///
+/// Computes a bitwise XOR of each pair of corresponding elements of the top two words via
+/// `U32XOR`, total of 11 VM cycles. The inputs are asserted to be u32 values implicitly.
+pub fn parse_u32xor_w(span_ops: &mut Vec<Operation>, op: &Token) -> Result<(), AssemblyError> {
+    match op.num_parts() {
+        0 => return Err(AssemblyError::missing_param(op)),
+        1 => append_word_binary_op(span_ops, &[Operation::U32xor]),
+        _ => return Err(AssemblyError::extra_param(op)),
+    }
+
+    Ok(())
+}
+
+/// Translates u32rotl_w.b assembly instruction to VM operations.
+///
+/// Rotates each element of the word at the top of the stack to the left by b bits in the same
+/// way as u32unchecked_rotl.b does, total of 16 VM cycles. The shift value b must be provided as
+/// an immediate parameter, and the elements are not asserted to be u32 values.
+pub fn parse_u32rotl_w(span_ops: &mut Vec<Operation>, op: &Token) -> Result<(), AssemblyError> {
+    match op.num_parts() {
+        0 | 1 => return Err(AssemblyError::missing_param(op)),
+        2 => {
+            let b = parse_u32_param(op, 1, 0, 31)?;
+            append_word_rotl(span_ops, b);
+        }
+        _ => return Err(AssemblyError::extra_param(op)),
+    }
+
+    Ok(())
+}
+
+/// Translates u32rotr_w.b assembly instruction to VM operations.
+///
+/// Rotates each element of the word at the top of the stack to the right by b bits, which is
+/// implemented as a rotation to the left by (32 - b) mod 32 bits, total of 16 VM cycles. The
+/// shift value b must be provided as an immediate parameter, and the elements are not asserted
+/// to be u32 values.
+pub fn parse_u32rotr_w(span_ops: &mut Vec<Operation>, op: &Token) -> Result<(), AssemblyError> {
+    match op.num_parts() {
+        0 | 1 => return Err(AssemblyError::missing_param(op)),
+        2 => {
+            let b = parse_u32_param(op, 1, 0, 31)?;
+            append_word_rotl(span_ops, (32 - b) % 32);
+        }
+        _ => return Err(AssemblyError::extra_param(op)),
+    }
+
+    Ok(())
+}
+
 // HELPER FUNCTIONS
 // ================================================================================================
 /// Asserts that the value on the top of the stack is a u32.
@@ -1020,7 +1120,7 @@ fn handle_arithmetic_operation(
 
     if assert_u32_res {
         span_ops.push(Operation::Eqz);
-        span_ops.push(Operation::Assert);
+        span_ops.push(Operation::Assert(0));
     } else if drop_high_bits {
         span_ops.push(Operation::Drop);
     }
@@ -1054,3 +1154,43 @@ fn handle_division(
 
     Ok(())
 }
+
+/// Applies the specified operations, which replace the top two elements b and a of the stack
+/// with a single element c, to each pair of corresponding elements of the top two words, i.e.,
+/// [B, A, ...] -> [C, ...], where c_i is computed from a_i and b_i.
+///
+/// Each element of A is moved next to the corresponding element of B, and the result is moved
+/// below the remaining elements, which takes 7 VM cycles in addition to the operations.
+fn append_word_binary_op(span_ops: &mut Vec<Operation>, ops: &[Operation]) {
+    // [b3, b2, b1, b0, a3, a2, a1, a0, ...]
+    span_ops.push(Operation::MovUp4);
+    span_ops.extend_from_slice(ops);
+    span_ops.push(Operation::MovDn6);
+
+    // [b2, b1, b0, a2, a1, a0, c3, ...]
+    span_ops.push(Operation::MovUp3);
+    span_ops.extend_from_slice(ops);
+    span_ops.push(Operation::MovDn5);
+
+    // [b1, b0, a1, a0, c3, c2, ...]
+    span_ops.push(Operation::MovUp2);
+    span_ops.extend_from_slice(ops);
+    span_ops.push(Operation::MovDn4);
+
+    // [b0, a0, c3, c2, c1, ...]
+    span_ops.extend_from_slice(ops);
+    span_ops.push(Operation::MovDn3);
+}
+
+/// Rotates each element of the word at the top of the stack to the left by b bits by
+/// multiplying it by 2^b and adding the overflow limb to the shifted limb. Each rotated element
+/// is moved below the remaining elements of the word, and thus the order of the elements is
+/// preserved after all four elements have been rotated.
+fn append_word_rotl(span_ops: &mut Vec<Operation>, b: u32) {
+    for _ in 0..4 {
+        span_ops.push(Operation::Push(Felt::new(2u64.pow(b))));
+        span_ops.push(Operation::U32mul);
+        span_ops.push(Operation::Add);
+        span_ops.push(Operation::MovDn3);
+    }
+}
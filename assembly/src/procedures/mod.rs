@@ -1,7 +1,8 @@
 use super::{
-    combine_blocks, parse_code_blocks, AssemblyContext, AssemblyError, CodeBlock, String, Token,
-    TokenStream, Vec,
+    build_preserve_ops, combine_mergeable_blocks, parse_code_blocks, AssemblyContext,
+    AssemblyError, CodeBlock, CodeBlockTable, String, ToString, Token, TokenStream, Vec,
 };
+use core::fmt;
 use vm_core::{Felt, Operation};
 
 // PROCEDURE
@@ -10,10 +11,13 @@ use vm_core::{Felt, Operation};
 /// Contains metadata and code of a procedure.
 pub struct Procedure {
     label: String,
+    docs: Option<String>,
     is_export: bool,
     #[allow(dead_code)]
     num_locals: u32,
+    locals_path: LocalsPath,
     code_root: CodeBlock,
+    callees: CodeBlockTable,
 }
 
 impl Procedure {
@@ -25,50 +29,127 @@ impl Procedure {
         &self.code_root
     }
 
+    /// Returns the code of all procedures invoked via `call` from this procedure, either directly
+    /// or through other called procedures.
+    pub fn callees(&self) -> &CodeBlockTable {
+        &self.callees
+    }
+
     /// Returns a label of this procedure.
     pub fn label(&self) -> &str {
         &self.label
     }
 
+    /// Returns documentation of this procedure, if it was declared with doc comments.
+    pub fn docs(&self) -> Option<&str> {
+        self.docs.as_deref()
+    }
+
     /// Returns `true` if this is an exported procedure.
     pub fn is_export(&self) -> bool {
         self.is_export
     }
 
+    /// Returns the chain of procedure invocations starting at this procedure along which the
+    /// largest number of locals is allocated.
+    pub fn locals_path(&self) -> &LocalsPath {
+        &self.locals_path
+    }
+
     // PARSER
     // --------------------------------------------------------------------------------------------
 
     /// Parses and returns a single procedure from the provided token stream.
     ///
+    /// The procedure header may be preceded by annotations (see [ProcAnnotation]): access control
+    /// annotations are compiled into guard code executed before the procedure body, and the
+    /// `@preserve(K)` annotation makes the procedure save the deepest K of the top 16 stack
+    /// elements into extra locals on entry, and restore them on exit.
+    ///
     /// # Errors
     /// Returns an error if:
-    /// - The token stream does not contain a procedure header token at the current position.
+    /// - The token stream does not contain a procedure header token at the current position, or
+    ///   right after the annotations.
     /// - Parsing of procedure header token fails (e.g., invalid procedure label).
+    /// - An annotation is malformed or repeated, an access control annotation is applied to a
+    ///   private procedure, or any annotation is applied to a generic procedure.
     /// - The procedure is an exported procedure and `allow_export` is false.
     /// - A procedure with the same label already exists in the provided context.
+    /// - The procedure exceeds the limits of the provided context.
     /// - Parsing of procedure body fails for any reason.
     /// - The procedure body does not terminate with the `END` token.
+    /// - The number of locals allocated along a chain of invocations starting at the procedure
+    ///   exceeds the locals budget of the context.
     pub fn parse(
         tokens: &mut TokenStream,
         context: &AssemblyContext,
         allow_export: bool,
         in_debug_mode: bool,
     ) -> Result<Self, AssemblyError> {
+        let docs_pos = tokens.pos();
+
+        // read annotations preceding the procedure header
+        let mut annotations = Vec::new();
+        while let Some(token) = tokens.read() {
+            if !token.is_annotation() {
+                break;
+            }
+            annotations.push(token.parse_annotation()?);
+            tokens.advance();
+        }
         let proc_start = tokens.pos();
 
         // read procedure name and consume the procedure header token
-        let header = tokens.read().expect("missing procedure header");
-        let (label, num_locals, is_export) = header.parse_proc()?;
+        let header = tokens
+            .read()
+            .ok_or_else(|| AssemblyError::unexpected_eof(proc_start))?;
+        if !matches!(header.parts()[0], Token::PROC | Token::EXPORT) {
+            return Err(AssemblyError::unexpected_token(header, Token::EXPORT));
+        }
+        let (label, params, num_locals, is_export) = header.parse_proc()?;
+        if (!annotations.is_empty() && !params.is_empty())
+            || (annotations.iter().any(|a| a.is_access_control()) && !is_export)
+        {
+            return Err(AssemblyError::misplaced_annotation(header, &label));
+        }
+        assert!(params.is_empty(), "not a regular procedure");
+        let num_preserved = ProcAnnotation::num_preserved(&annotations)
+            .ok_or_else(|| AssemblyError::misplaced_annotation(header, &label))?;
+        let docs = tokens.doc_comment(docs_pos).map(String::from);
         if !allow_export && is_export {
             return Err(AssemblyError::proc_export_not_allowed(header, &label));
         }
         if context.contains_proc(&label) {
             return Err(AssemblyError::duplicate_proc_label(header, &label));
         }
+        context.check_proc_limits(header, &label, num_locals + num_preserved)?;
         tokens.advance();
+        let body_start = tokens.pos();
+
+        // parse procedure body, and handle memory allocation/deallocation of locals if any are
+        // declared or required to preserve stack elements
+        let mut code_root =
+            parse_proc_blocks(tokens, context, num_locals, num_preserved, in_debug_mode)?;
+        let guard_ops: Vec<_> = annotations
+            .iter()
+            .flat_map(ProcAnnotation::guard_ops)
+            .collect();
+        if !guard_ops.is_empty() {
+            code_root = combine_mergeable_blocks(vec![
+                (CodeBlock::new_span(guard_ops), true),
+                (code_root, !context.is_profiling()),
+            ]);
+        }
+        let callees = collect_callees(&code_root, context);
 
-        // parse procedure body, and handle memory allocation/deallocation of locals if any are declared
-        let code_root = parse_proc_blocks(tokens, context, num_locals, in_debug_mode)?;
+        // find the invocation chain allocating the largest number of locals, and make sure it
+        // fits into the budget
+        let callee_path = LocalsPath::find_max(tokens, body_start, tokens.pos(), context);
+        let locals_path = LocalsPath::with_caller(&label, num_locals + num_preserved, callee_path);
+        context.check_locals_budget(
+            tokens.read_at(proc_start).expect("no proc token"),
+            &locals_path,
+        )?;
 
         // consume the 'end' token
         match tokens.read() {
@@ -87,44 +168,435 @@ impl Procedure {
         // build and return the procedure
         Ok(Self {
             label,
+            docs,
             is_export,
             num_locals,
+            locals_path,
             code_root,
+            callees,
         })
     }
 }
 
+// CALLEES
+// ================================================================================================
+
+/// Returns the code of all procedures invoked via `call` from the specified code block, either
+/// directly or through other called procedures.
+///
+/// Called procedures are not inlined into the MAST of the caller, and thus their code must be
+/// supplied to the processor separately.
+pub(crate) fn collect_callees(root: &CodeBlock, context: &AssemblyContext) -> CodeBlockTable {
+    let mut callees = CodeBlockTable::new();
+    visit_calls(root, context, &mut callees);
+    callees
+}
+
+fn visit_calls(block: &CodeBlock, context: &AssemblyContext, callees: &mut CodeBlockTable) {
+    match block {
+        CodeBlock::Join(block) => {
+            visit_calls(block.first(), context, callees);
+            visit_calls(block.second(), context, callees);
+        }
+        CodeBlock::Split(block) => {
+            visit_calls(block.on_true(), context, callees);
+            visit_calls(block.on_false(), context, callees);
+        }
+        CodeBlock::Loop(block) => visit_calls(block.body(), context, callees),
+        CodeBlock::Call(block) if !block.is_syscall() => {
+            let fn_hash = block.fn_hash();
+            if !callees.contains(fn_hash) {
+                let code = context
+                    .get_callee_code(fn_hash)
+                    .expect("code of a called procedure not found");
+                callees.insert(code.clone());
+                visit_calls(code, context, callees);
+            }
+        }
+        _ => (),
+    }
+}
+
+// PROCEDURE ANNOTATION
+// ================================================================================================
+
+/// An annotation of a procedure.
+///
+/// Access control annotations can be applied only to exported procedures. They are compiled into
+/// guard code which is executed before the body of the procedure; the guard code leaves the stack
+/// unchanged, and makes the execution fail if the procedure is invoked from a context which the
+/// annotation does not allow.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ProcAnnotation {
+    /// `@only_kernel`: the procedure can be executed only in the kernel context, i.e., only as a
+    /// part of a system call.
+    OnlyKernel,
+    /// `@only_context(root)`: the procedure can be executed only as a part of a system call made
+    /// from the root context, i.e., from the program itself rather than from a procedure which
+    /// created its own context.
+    OnlyRootContext,
+    /// `@preserve(K)`: the deepest K of the top 16 stack elements are saved into locals allocated
+    /// after the declared locals of the procedure when the procedure is entered, and are put back
+    /// into their positions when the procedure exits, replacing whatever elements the procedure
+    /// left there. K must be between 1 and 14.
+    Preserve(u32),
+}
+
+impl ProcAnnotation {
+    /// Maximum number of stack elements which can be preserved by a procedure; the top two
+    /// elements of the stack are always left to the procedure.
+    pub const MAX_PRESERVED: u32 = 14;
+
+    /// Returns true if this annotation restricts the contexts in which a procedure can be invoked.
+    pub fn is_access_control(&self) -> bool {
+        matches!(self, Self::OnlyKernel | Self::OnlyRootContext)
+    }
+
+    /// Returns the number of stack elements preserved according to the specified annotations, or
+    /// None if the `@preserve` annotation is repeated.
+    fn num_preserved(annotations: &[Self]) -> Option<u32> {
+        let mut preserved = annotations
+            .iter()
+            .filter_map(|annotation| match annotation {
+                Self::Preserve(num_preserved) => Some(*num_preserved),
+                _ => None,
+            });
+        match (preserved.next(), preserved.next()) {
+            (_, Some(_)) => None,
+            (num_preserved, None) => Some(num_preserved.unwrap_or(0)),
+        }
+    }
+
+    /// Returns the guard operations enforcing this annotation; the guard is empty for
+    /// annotations other than access control annotations.
+    ///
+    /// Both access control guards put the hash of the caller onto the stack; the CALLER operation
+    /// fails outside of the kernel context.
+    fn guard_ops(&self) -> Vec<Operation> {
+        if !self.is_access_control() {
+            return Vec::new();
+        }
+        let mut ops = vec![Operation::Pad; 4];
+        ops.push(Operation::Caller);
+
+        match self {
+            // 9 cycles
+            Self::OnlyKernel => ops.extend([Operation::Drop; 4]),
+            // make sure all elements of the caller hash are ZEROs; 15 cycles
+            Self::OnlyRootContext => {
+                ops.push(Operation::Eqz);
+                for _ in 0..3 {
+                    ops.push(Operation::Swap);
+                    ops.push(Operation::Eqz);
+                    ops.push(Operation::And);
+                }
+                ops.push(Operation::Assert(0));
+            }
+            Self::Preserve(_) => unreachable!("not an access control annotation"),
+        }
+        ops
+    }
+}
+
+// GENERIC PROCEDURE
+// ================================================================================================
+
+/// Contains metadata of a procedure parameterized by compile-time constants, e.g. `proc.foo<N>`.
+///
+/// The body of a generic procedure is not parsed when the procedure is declared. Instead, the
+/// position of the body in the token stream is recorded, and the body is parsed anew every time
+/// the procedure is invoked, with generic parameters bound to the values of invocation arguments.
+/// Thus, generic procedures can be invoked only from the source which declares them.
+#[derive(Clone)]
+pub struct GenericProcedure {
+    label: String,
+    params: Vec<String>,
+    num_locals: u32,
+    body_start: usize,
+    body_end: usize,
+}
+
+impl GenericProcedure {
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a label of this procedure.
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    /// Returns names of generic parameters of this procedure.
+    pub fn params(&self) -> &[String] {
+        &self.params
+    }
+
+    /// Returns the chain of procedure invocations starting at this procedure along which the
+    /// largest number of locals is allocated.
+    ///
+    /// Since invocations within the body do not depend on generic arguments, the chain is the
+    /// same for all instantiations of this procedure.
+    pub fn locals_path(&self, tokens: &mut TokenStream, context: &AssemblyContext) -> LocalsPath {
+        let callee_path = LocalsPath::find_max(tokens, self.body_start, self.body_end, context);
+        LocalsPath::with_caller(&self.label, self.num_locals, callee_path)
+    }
+
+    // PARSER
+    // --------------------------------------------------------------------------------------------
+
+    /// Parses the header of a generic procedure from the provided token stream and skips over
+    /// the procedure body.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - Parsing of procedure header token fails (e.g., malformed generic parameter list).
+    /// - The procedure is declared as exported.
+    /// - A procedure with the same label already exists in the provided context.
+    /// - The procedure exceeds the limits of the provided context.
+    /// - The procedure body does not terminate with the `END` token.
+    pub fn parse(
+        tokens: &mut TokenStream,
+        context: &AssemblyContext,
+        allow_export: bool,
+    ) -> Result<Self, AssemblyError> {
+        let proc_start = tokens.pos();
+
+        // read procedure header and consume the procedure header token
+        let header = tokens.read().expect("missing procedure header");
+        let (label, params, num_locals, is_export) = header.parse_proc()?;
+        if is_export {
+            return if allow_export {
+                Err(AssemblyError::generic_proc_export(header, &label))
+            } else {
+                Err(AssemblyError::proc_export_not_allowed(header, &label))
+            };
+        }
+        if context.contains_proc(&label) {
+            return Err(AssemblyError::duplicate_proc_label(header, &label));
+        }
+        context.check_proc_limits(header, &label, num_locals)?;
+        tokens.advance();
+        let body_start = tokens.pos();
+
+        // skip over the procedure body and the 'end' token; the body is validated only when the
+        // procedure is instantiated
+        let mut depth = 1;
+        let mut body_end = body_start;
+        while depth > 0 {
+            let token = match tokens.read() {
+                Some(token) => token,
+                None => break,
+            };
+            match token.parts()[0] {
+                Token::IF | Token::WHILE | Token::REPEAT => depth += 1,
+                Token::END => depth -= 1,
+                Token::USE | Token::CONST | Token::PROC | Token::EXPORT | Token::BEGIN => break,
+                _ => (),
+            }
+            body_end = tokens.pos();
+            tokens.advance();
+        }
+        if depth > 0 {
+            return Err(AssemblyError::unmatched_proc(
+                tokens.read_at(proc_start).expect("no proc token"),
+            ));
+        }
+
+        Ok(Self {
+            label,
+            params,
+            num_locals,
+            body_start,
+            body_end,
+        })
+    }
+
+    /// Parses the body of this procedure in the provided context and returns the root of the
+    /// resulting MAST. The context is expected to bind generic parameters of this procedure.
+    ///
+    /// After this function returns, the current position of the token stream is undefined, and
+    /// thus the caller is responsible for restoring it.
+    pub fn instantiate(
+        &self,
+        tokens: &mut TokenStream,
+        context: &AssemblyContext,
+        in_debug_mode: bool,
+    ) -> Result<CodeBlock, AssemblyError> {
+        tokens.seek(self.body_start);
+        let code_root = parse_proc_blocks(tokens, context, self.num_locals, 0, in_debug_mode)?;
+
+        // make sure the body is terminated by the 'end' token
+        match tokens.read() {
+            Some(token) if token.parts()[0] == Token::END => token.validate_end(),
+            _ => Err(AssemblyError::unmatched_proc(
+                tokens.read_at(self.body_start - 1).expect("no proc token"),
+            )),
+        }?;
+
+        Ok(code_root)
+    }
+}
+
+// BUDGET POLICY
+// ================================================================================================
+
+/// Determines how the assembler handles code which exceeds a budget it was configured with.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum BudgetPolicy {
+    /// Code exceeding the budget fails to compile.
+    Error,
+    /// Code exceeding the budget compiles, and a warning is reported instead.
+    Warn,
+}
+
+// LOCALS PATH
+// ================================================================================================
+
+/// A chain of procedure invocations via `exec` instructions, together with the number of locals
+/// allocated by each procedure in the chain.
+///
+/// Locals of a procedure are allocated in memory on top of the locals of the procedures which
+/// invoked it, and thus the amount of local memory used by a program is determined by the chain
+/// of nested invocations which allocates the largest number of locals.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct LocalsPath {
+    frames: Vec<(String, u32)>,
+}
+
+impl LocalsPath {
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a path which starts with a procedure with the specified label and number of locals
+    /// and continues with the specified path of callees.
+    pub(crate) fn with_caller(label: &str, num_locals: u32, callees: LocalsPath) -> Self {
+        let mut frames = Vec::with_capacity(callees.frames.len() + 1);
+        frames.push((label.to_string(), num_locals));
+        frames.extend(callees.frames);
+        Self { frames }
+    }
+
+    /// Returns the path allocating the largest number of locals among the paths starting at
+    /// procedures invoked in the specified range of token positions, or an empty path if no
+    /// procedure allocating locals is invoked in the range. The range is expected to have been
+    /// parsed successfully in the provided context.
+    ///
+    /// Invocations are collected from all blocks in the range, including `if.const` branches
+    /// which were elided at compile time.
+    pub(crate) fn find_max(
+        tokens: &mut TokenStream,
+        start: usize,
+        end: usize,
+        context: &AssemblyContext,
+    ) -> Self {
+        let mut result = Self::default();
+        for pos in start..end {
+            let label = match tokens.read_at(pos) {
+                Some(token) if token.parts()[0] == Token::EXEC => match token.parse_exec() {
+                    Ok((label, _)) => label,
+                    Err(_) => continue,
+                },
+                _ => continue,
+            };
+            let path = if let Some(proc) = context.get_generic_proc(&label) {
+                proc.locals_path(tokens, context)
+            } else if let Some(proc) = context.get_proc(&label) {
+                proc.locals_path().with_label(&label)
+            } else {
+                continue;
+            };
+            if path.num_locals() > result.num_locals() {
+                result = path;
+            }
+        }
+        result
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the procedures in this path, starting with the outermost one, as pairs of
+    /// procedure labels (as written in the invoking code) and numbers of locals.
+    pub fn frames(&self) -> &[(String, u32)] {
+        &self.frames
+    }
+
+    /// Returns the total number of locals allocated along this path.
+    pub fn num_locals(&self) -> u64 {
+        self.frames.iter().map(|(_, n)| *n as u64).sum()
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a copy of this path with the label of the first procedure replaced with the
+    /// specified label.
+    fn with_label(&self, label: &str) -> Self {
+        let mut path = self.clone();
+        if let Some(frame) = path.frames.first_mut() {
+            frame.0 = label.to_string();
+        }
+        path
+    }
+}
+
+impl fmt::Display for LocalsPath {
+    /// Formats this path as a list of procedures with their numbers of locals, e.g.,
+    /// `foo (2) -> u64::bar (4)`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (idx, (label, num_locals)) in self.frames.iter().enumerate() {
+            if idx > 0 {
+                write!(f, " -> ")?;
+            }
+            write!(f, "{} ({})", label, num_locals)?;
+        }
+        Ok(())
+    }
+}
+
 // HELPER FUNCTIONS
 // ================================================================================================
 
+/// Parses the body of a procedure with the specified number of locals, and wraps it into the code
+/// which allocates and deallocates the locals.
+///
+/// When `num_preserved` is not zero, the deepest `num_preserved` of the top 16 stack elements are
+/// saved into extra locals allocated after the declared ones before the body is executed, and
+/// are restored after the body is executed. The body itself can access only the declared locals.
 pub fn parse_proc_blocks(
     tokens: &mut TokenStream,
     context: &AssemblyContext,
     num_proc_locals: u32,
+    num_preserved: u32,
     in_debug_mode: bool,
 ) -> Result<CodeBlock, AssemblyError> {
     // parse the procedure body
     let body = parse_code_blocks(tokens, context, num_proc_locals, in_debug_mode)?;
 
-    if num_proc_locals == 0 {
+    let num_locals = num_proc_locals + num_preserved;
+    if num_locals == 0 {
         // if no allocation of locals is required, return the procedure body
         return Ok(body);
     }
 
     let mut blocks = Vec::new();
-    let locals_felt = Felt::new(num_proc_locals as u64);
+    let locals_felt = Felt::new(num_locals as u64);
+    let (save_ops, restore_ops) = build_preserve_ops(num_proc_locals, num_preserved);
 
-    // allocate procedure locals before the procedure body
-    let alloc_ops = vec![Operation::Push(locals_felt), Operation::FmpUpdate];
-    blocks.push(CodeBlock::new_span(alloc_ops));
+    // allocate procedure locals and save the preserved stack elements before the procedure body
+    let mut alloc_ops = vec![Operation::Push(locals_felt), Operation::FmpUpdate];
+    alloc_ops.extend(save_ops);
+    blocks.push((CodeBlock::new_span(alloc_ops), true));
 
-    // add the procedure body code block
-    blocks.push(body);
+    // add the procedure body code block; when profiling, the body may be the root of a procedure
+    // invoked via `exec`, and thus it is not merged with the alloc/dealloc blocks
+    blocks.push((body, !context.is_profiling()));
 
-    // deallocate procedure locals after the procedure body
-    let dealloc_ops = vec![Operation::Push(-locals_felt), Operation::FmpUpdate];
-    blocks.push(CodeBlock::new_span(dealloc_ops));
+    // restore the preserved stack elements and deallocate procedure locals after the procedure
+    // body
+    let mut dealloc_ops = restore_ops;
+    dealloc_ops.extend([Operation::Push(-locals_felt), Operation::FmpUpdate]);
+    blocks.push((CodeBlock::new_span(dealloc_ops), true));
 
     // combine the local memory alloc/dealloc blocks with the procedure body code block
-    Ok(combine_blocks(blocks))
+    Ok(combine_mergeable_blocks(blocks))
 }
@@ -39,6 +39,292 @@ fn span_and_simple_if() {
     assert_eq!(expected, format!("{}", program));
 }
 
+#[test]
+fn span_with_trailing_decorators() {
+    // decorators which do not precede any operation of a span are attached to a noop; this holds
+    // also for spans consisting only of decorators
+    let source = "begin push.1 if.true add adv.u64div else adv.u64div end end";
+    let expected = "\
+        begin \
+            join \
+                span pad incr end \
+                if.true span add noop end else span noop end end \
+            end \
+        end";
+    let program = super::Assembler::default().compile(source).unwrap();
+    assert_eq!(expected, format!("{}", program));
+    let program = super::Assembler::new(true).compile(source).unwrap();
+    assert_eq!(expected, format!("{}", program));
+}
+
+#[test]
+fn unwrapped_instructions() {
+    let assembler = super::Assembler::default();
+    let source = "\
+        use.std::math::u64
+        const.A=2
+        proc.foo push.A add end
+        push.1 exec.foo
+        if.true exec.u64::checked_add else push.3 end";
+    let program = assembler.compile_procedure(source).unwrap();
+    let wrapped = source.replace("push.1 exec.foo", "begin push.1 exec.foo") + " end";
+    assert_eq!(assembler.compile(&wrapped).unwrap().hash(), program.hash());
+
+    // the instructions must not close blocks which they did not open
+    let error = assembler.compile_procedure("push.1 end").unwrap_err();
+    assert_eq!("dangling instructions after program end", error.message());
+    assert_eq!(1, error.step());
+    let error = assembler
+        .compile_procedure("push.1 else push.2")
+        .unwrap_err();
+    assert_eq!("else without matching if", error.message());
+
+    // the instructions must not be empty
+    assert!(assembler.compile_procedure("proc.foo push.1 end").is_err());
+}
+
+#[test]
+fn assertions_with_error_codes() {
+    let assembler = super::Assembler::default();
+    let source = "begin assert.err=4096 assertz.err=0x10 assert_eq assert.err=0 end";
+    let program = assembler.compile(source).unwrap();
+    let expected = "begin span assert(4096) eqz assert(16) eq assert assert end end";
+    assert_eq!(expected, format!("{}", program));
+
+    // error codes do not affect the hash of the program
+    let other = assembler
+        .compile("begin assert assertz assert_eq assert end")
+        .unwrap();
+    assert_eq!(program.hash(), other.hash());
+
+    for source in [
+        "begin assert.4096 end",
+        "begin assert.err=abc end",
+        "begin assert.err=4294967296 end",
+        "begin assert_eq.err=1.2 end",
+    ] {
+        assert!(assembler.compile(source).is_err(), "{}", source);
+    }
+}
+
+#[test]
+fn panic_with_message() {
+    let assembler = super::Assembler::default();
+    let source = "begin push.1 panic.\"invalid input: x. #1\" end";
+    let program = assembler.compile(source).unwrap();
+    let expected = "begin span pad incr pad assert end end";
+    assert_eq!(expected, format!("{}", program));
+
+    // the message is carried by a decorator, and does not affect the hash of the program
+    match program.root() {
+        vm_core::code_blocks::CodeBlock::Span(span) => assert_eq!(
+            &[(2, vm_core::Decorator::Panic("invalid input: x. #1".into()))],
+            span.decorators().as_slice()
+        ),
+        _ => panic!("expected a span block"),
+    }
+    let other = assembler
+        .compile("begin push.1 panic.\"other\" end")
+        .unwrap();
+    assert_eq!(program.hash(), other.hash());
+
+    for source in [
+        "begin panic end",
+        "begin panic.message end",
+        "begin panic.\"a\".\"b\" end",
+        "begin panic.\"unterminated end",
+    ] {
+        assert!(assembler.compile(source).is_err(), "{}", source);
+    }
+}
+
+#[test]
+fn debug_instructions() {
+    let source = "begin push.1 debug.stack debug.stack.4 debug.mem debug.mem.8.16 push.2 end";
+
+    // in release mode, debug instructions are discarded
+    let program = super::Assembler::default().compile(source).unwrap();
+    let expected = "begin span pad incr push(2) end end";
+    assert_eq!(expected, format!("{}", program));
+    match program.root() {
+        vm_core::code_blocks::CodeBlock::Span(span) => assert!(span.decorators().is_empty()),
+        _ => panic!("expected a span block"),
+    }
+
+    // in debug mode, they are attached as decorators to the next operation
+    let program = super::Assembler::new(true).compile(source).unwrap();
+    assert_eq!(expected, format!("{}", program));
+    let debug_decorators = match program.root() {
+        vm_core::code_blocks::CodeBlock::Span(span) => span
+            .decorators()
+            .iter()
+            .filter(|(_, decorator)| matches!(decorator, vm_core::Decorator::Debug(_)))
+            .map(|(op_idx, decorator)| format!("{}: {}", op_idx, decorator))
+            .collect::<Vec<_>>(),
+        _ => panic!("expected a span block"),
+    };
+    let expected = [
+        "2: debug(stack)",
+        "2: debug(stack.4)",
+        "2: debug(mem)",
+        "2: debug(mem.8.16)",
+    ];
+    assert_eq!(expected.to_vec(), debug_decorators);
+
+    // locals can be reported only within procedures which declare them
+    let source = "proc.foo.3 debug.local debug.local.2 debug.local.0.1 end begin exec.foo end";
+    assert!(super::Assembler::new(true).compile(source).is_ok());
+
+    for source in [
+        "begin debug end",
+        "begin debug.advice end",
+        "begin debug.stack.0 end",
+        "begin debug.stack.1.2 end",
+        "begin debug.mem.16.8 end",
+        "begin debug.mem.1.2.3 end",
+        "begin debug.local end",
+        "proc.foo.2 debug.local.2 end begin exec.foo end",
+        "proc.foo debug.local end begin exec.foo end",
+    ] {
+        assert!(
+            super::Assembler::default().compile(source).is_err(),
+            "{}",
+            source
+        );
+    }
+}
+
+#[test]
+fn expect_stack_instructions() {
+    // in release mode, stack expectations are discarded
+    let source = "begin push.1 push.2 expect_stack.[2,1] push.0x10 expect_stack.[16] end";
+    let program = super::Assembler::default().compile(source).unwrap();
+    assert_eq!(
+        "begin span pad incr push(2) push(16) end end",
+        format!("{}", program)
+    );
+    match program.root() {
+        vm_core::code_blocks::CodeBlock::Span(span) => assert!(span.decorators().is_empty()),
+        _ => panic!("expected a span block"),
+    }
+
+    // in debug mode, they are attached as decorators to the next operation, and do not affect
+    // the hash of the program
+    let debug_program = super::Assembler::new(true).compile(source).unwrap();
+    assert_eq!(program.hash(), debug_program.hash());
+    let decorators = match debug_program.root() {
+        vm_core::code_blocks::CodeBlock::Span(span) => span
+            .decorators()
+            .iter()
+            .filter(|(_, decorator)| matches!(decorator, vm_core::Decorator::ExpectStack(_)))
+            .map(|(op_idx, decorator)| format!("{}: {}", op_idx, decorator))
+            .collect::<Vec<_>>(),
+        _ => panic!("expected a span block"),
+    };
+    assert_eq!(
+        vec!["3: expectStack(2, 1)", "4: expectStack(16)"],
+        decorators
+    );
+
+    // expected values can be specified via constants
+    let source = "const.A=2 begin push.1 push.2 expect_stack.[A,A-1] end";
+    let program = super::Assembler::new(true).compile(source).unwrap();
+    match program.root() {
+        vm_core::code_blocks::CodeBlock::Span(span) => assert!(span
+            .decorators()
+            .iter()
+            .any(|(_, decorator)| decorator.to_string() == "expectStack(2, 1)")),
+        _ => panic!("expected a span block"),
+    }
+
+    for source in [
+        "begin expect_stack end",
+        "begin expect_stack.1 end",
+        "begin expect_stack.[] end",
+        "begin expect_stack.[1,x] end",
+        "begin expect_stack.[1].[2] end",
+        "begin expect_stack.[0,1,2,3,4,5,6,7,8,9,10,11,12,13,14,15,16] end",
+    ] {
+        assert!(
+            super::Assembler::default().compile(source).is_err(),
+            "{}",
+            source
+        );
+    }
+}
+
+#[test]
+fn emit_instructions() {
+    // events are attached as decorators to the next operation regardless of the mode; an event
+    // at the end of a span is attached to a NOOP
+    let source = "begin push.1 emit.7 push.2 emit.4294967295 end";
+    let program = super::Assembler::default().compile(source).unwrap();
+    let expected = "begin span pad incr push(2) noop end end";
+    assert_eq!(expected, format!("{}", program));
+    let decorators = match program.root() {
+        vm_core::code_blocks::CodeBlock::Span(span) => span
+            .decorators()
+            .iter()
+            .map(|(op_idx, decorator)| format!("{}: {}", op_idx, decorator))
+            .collect::<Vec<_>>(),
+        _ => panic!("expected a span block"),
+    };
+    assert_eq!(vec!["2: event(7)", "3: event(4294967295)"], decorators);
+
+    // otherwise, events do not affect the hash of the program
+    let without_events = super::Assembler::default()
+        .compile("begin push.1 push.2 end")
+        .unwrap();
+    let source = "begin push.1 emit.7 push.2 end";
+    let program = super::Assembler::default().compile(source).unwrap();
+    assert_eq!(without_events.hash(), program.hash());
+
+    for source in [
+        "begin emit end",
+        "begin emit.1.2 end",
+        "begin emit.4294967296 end",
+        "begin emit.a end",
+    ] {
+        assert!(
+            super::Assembler::default().compile(source).is_err(),
+            "{}",
+            source
+        );
+    }
+}
+
+#[test]
+fn proc_paths() {
+    let source = "\
+        proc.bar push.1 end \
+        proc.foo exec.bar push.2 end \
+        begin push.3 exec.foo push.4 end";
+
+    // in debug mode, every span starts with the chain of procedures inlined via exec to which
+    // its operations belong
+    let program = super::Assembler::new(true).compile(source).unwrap();
+    let proc_paths = match program.root() {
+        vm_core::code_blocks::CodeBlock::Span(span) => span
+            .decorators()
+            .iter()
+            .filter(|(_, decorator)| matches!(decorator, vm_core::Decorator::ProcPath(_)))
+            .map(|(op_idx, decorator)| format!("{}: {}", op_idx, decorator))
+            .collect::<Vec<_>>(),
+        _ => panic!("expected a span block"),
+    };
+    let expected = [
+        "0: procPath()",
+        "1: procPath(foo, bar)",
+        "3: procPath(foo)",
+        "4: procPath()",
+    ];
+    assert_eq!(expected.to_vec(), proc_paths);
+
+    // the decorators do not affect the hash of the program
+    let release = super::Assembler::default().compile(source).unwrap();
+    assert_eq!(release.hash(), program.hash());
+}
+
 // NESTED CONTROL BLOCKS
 // ================================================================================================
 
@@ -80,6 +366,58 @@ fn nested_control_blocks() {
     assert_eq!(expected, format!("{}", program));
 }
 
+#[test]
+fn bounded_while_loop() {
+    let assembler = super::Assembler::default();
+
+    // the remaining number of iterations is kept in a memory slot allocated for the loop, and is
+    // checked and decremented before every iteration
+    let source = "const.N=3 begin push.1 while.bounded.N push.0 end end";
+    let program = assembler.compile(source).unwrap();
+    let expected = "\
+        begin \
+            join \
+                span pad incr end \
+                join \
+                    join \
+                        span pad incr fmpupdate push(3) pad fmpadd mstore drop end \
+                        while.true \
+                            span \
+                                pad fmpadd mload dup0 eqz not assert \
+                                push(18446744069414584320) add pad fmpadd mstore drop pad \
+                            end \
+                        end \
+                    end \
+                    span push(18446744069414584320) fmpupdate end \
+                end \
+            end \
+        end";
+    assert_eq!(expected, format!("{}", program));
+
+    // the body of a bounded loop cannot access locals of the enclosing procedure
+    let source = "proc.foo.1 while.bounded.2 push.local.0 end end begin exec.foo end";
+    let error = assembler.compile(source).unwrap_err();
+    assert_eq!("E045", error.error_code().to_string());
+
+    // but procedures invoked from the body can have their own locals
+    let source = "\
+        proc.bar.1 pop.local.0 end \
+        proc.foo.1 while.bounded.2 exec.bar push.0 end end \
+        begin push.1 exec.foo end";
+    assert!(assembler.compile(source).is_ok());
+
+    // the maximum number of iterations must be in the range [1, 2^32)
+    for source in [
+        "begin while.bounded.0 push.0 end end",
+        "begin while.bounded.4294967296 push.0 end end",
+        "begin while.bounded push.0 end end",
+        "begin while.bounded.1.2 push.0 end end",
+        "begin while.true.1 push.0 end end",
+    ] {
+        assert!(assembler.compile(source).is_err());
+    }
+}
+
 // PROGRAMS WITH PROCEDURES
 // ================================================================================================
 
@@ -137,64 +475,1091 @@ fn program_with_proc_locals() {
     assert_eq!(expected, format!("{}", program));
 }
 
-#[test]
-fn program_with_exported_procedure() {
-    let assembler = super::Assembler::default();
-    let source = "export.foo push.3 push.7 mul end begin push.2 push.3 add exec.foo end";
-    assert!(assembler.compile(source).is_err());
-}
-
-// IMPORTS
+#[test]
+fn program_with_exported_procedure() {
+    let assembler = super::Assembler::default();
+    let source = "export.foo push.3 push.7 mul end begin push.2 push.3 add exec.foo end";
+    assert!(assembler.compile(source).is_err());
+}
+
+#[test]
+fn program_with_generic_procedures() {
+    let assembler = super::Assembler::default();
+    let source = "\
+        const.K=2 \
+        proc.foo<N> repeat.N push.K add end end \
+        proc.bar<N,M> push.M exec.foo<N*M> end \
+        begin push.1 exec.foo<2> exec.bar<1,3> exec.foo<K> end";
+    let program = assembler.compile(source).unwrap();
+    let expected = "begin span \
+        pad incr push(2) add push(2) add \
+        push(3) push(2) add push(2) add push(2) add \
+        push(2) add push(2) add \
+        end end";
+    assert_eq!(expected, format!("{}", program));
+
+    // generic procedures can be used in modules as well, and can have locals
+    let source = "\
+        proc.foo<N>.1 pop.local.0 push.N end \
+        begin exec.foo<5> end";
+    let program = assembler.compile(source).unwrap();
+    let expected = "begin span \
+        push(1) fmpupdate \
+        pad pad pad pad fmpadd mstorew drop drop drop drop \
+        push(5) \
+        push(18446744069414584320) fmpupdate \
+        end end";
+    assert_eq!(expected, format!("{}", program));
+}
+
+#[test]
+fn program_with_generic_procedure_errors() {
+    let assembler = super::Assembler::default();
+
+    let source = "proc.foo<N repeat.N add end end begin exec.foo<2> end";
+    let error = assembler.compile(source).unwrap_err();
+    assert_eq!(
+        error.message(),
+        "malformed generic parameter list in 'proc.foo<N'"
+    );
+
+    let source = "proc.foo<N,N> add end begin exec.foo<2,2> end";
+    let error = assembler.compile(source).unwrap_err();
+    assert_eq!(error.message(), "duplicate constant name: N");
+
+    let source = "proc.foo<N> repeat.N add end end begin exec.foo end";
+    let error = assembler.compile(source).unwrap_err();
+    assert_eq!(
+        error.message(),
+        "procedure foo expects 1 generic arguments, but 0 were provided"
+    );
+
+    let source = "proc.foo add end begin exec.foo<2> end";
+    let error = assembler.compile(source).unwrap_err();
+    assert_eq!(
+        error.message(),
+        "procedure foo expects 0 generic arguments, but 1 were provided"
+    );
+
+    // errors in the body are reported when the procedure is instantiated
+    let source = "proc.foo<N> repeat.N add end end begin exec.foo<0> end";
+    let error = assembler.compile(source).unwrap_err();
+    assert_eq!(
+        error.message(),
+        "malformed instruction `repeat.N`: parameter 'N' is invalid"
+    );
+
+    // generic procedures cannot invoke themselves
+    let source = "proc.foo<N> exec.foo<N> end begin exec.foo<1> end";
+    let error = assembler.compile(source).unwrap_err();
+    assert_eq!(error.message(), "undefined procedure: foo");
+
+    let source = "proc.foo<N> repeat.N add end begin exec.foo<1> end";
+    let error = assembler.compile(source).unwrap_err();
+    assert_eq!(error.message(), "proc without matching end");
+}
+
+#[test]
+fn program_with_exec_by_root() {
+    let assembler = super::Assembler::default();
+    let callee = assembler.compile("begin push.3 push.7 mul end").unwrap();
+    let root = <[u8; 32]>::from(callee.hash())
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect::<String>();
+
+    // a procedure invoked by its root is compiled into a proxy block with the same hash
+    let source = format!("begin push.2 exec.0x{} add end", root);
+    let program = assembler.compile(&source).unwrap();
+    let expected = format!(
+        "begin join join span push(2) end proxy.{:?} end span add end end end",
+        callee.hash()
+    );
+    assert_eq!(expected, format!("{}", program));
+
+    // the root must consist of exactly 64 hex digits
+    let source = format!("begin exec.0x{} end", &root[..62]);
+    let error = assembler.compile(&source).unwrap_err();
+    assert!(error
+        .message()
+        .contains("expected a MAST root of 64 hex digits"));
+    let source = format!("begin exec.0x{}zz end", &root[..62]);
+    assert!(assembler.compile(&source).is_err());
+    let source = format!("begin exec.0x{}.1 end", root);
+    assert!(assembler.compile(&source).is_err());
+
+    // elements of the root must be valid field elements
+    let source = format!("begin exec.0x{} end", "f".repeat(64));
+    let error = assembler.compile(&source).unwrap_err();
+    assert!(error
+        .message()
+        .contains("MAST root elements must be valid field elements"));
+}
+
+#[test]
+fn program_with_syscall() {
+    let kernel = "export.foo add end proc.bar mul end";
+    let assembler = super::Assembler::default().with_kernel(kernel).unwrap();
+    let foo = super::Assembler::default()
+        .compile("begin add end")
+        .unwrap()
+        .hash();
+
+    // kernel procedures are not inlined; the program carries the code of the kernel
+    let program = assembler.compile("begin push.2 syscall.foo end").unwrap();
+    let expected = format!("begin join span push(2) end syscall.{:?} end end", foo);
+    assert_eq!(expected, format!("{}", program));
+    assert!(program.kernel().contains_proc(foo));
+    assert_eq!(1, program.kernel().proc_hashes().len());
+    assert!(program.cb_table().contains(foo));
+
+    // only procedures exported from the kernel can be invoked via syscall
+    let error = assembler.compile("begin syscall.bar end").unwrap_err();
+    assert_eq!("E037", error.error_code().to_string());
+    assert!(super::Assembler::default()
+        .compile("begin syscall.foo end")
+        .is_err());
+    assert!(assembler.compile("begin syscall end").is_err());
+}
+
+#[test]
+fn program_with_annotated_syscall() {
+    let kernel = "
+        @only_kernel
+        export.foo add end
+        @only_context(root) export.bar mul end";
+    let assembler = super::Assembler::default().with_kernel(kernel).unwrap();
+
+    // annotations are compiled into guard code executed before the procedure body
+    use vm_core::{code_blocks::CodeBlock, Operation::*};
+    let foo = CodeBlock::new_span(vec![
+        Pad, Pad, Pad, Pad, Caller, Drop, Drop, Drop, Drop, Add,
+    ]);
+    let foo = foo.hash();
+    let program = assembler
+        .compile("begin syscall.foo syscall.bar end")
+        .unwrap();
+    assert!(program.kernel().contains_proc(foo));
+    assert_eq!(2, program.kernel().proc_hashes().len());
+
+    // annotations are allowed only on exported procedures
+    let kernel = "@only_kernel proc.foo add end export.bar mul end";
+    match super::Assembler::default().with_kernel(kernel) {
+        Err(error) => assert_eq!("E038", error.error_code().to_string()),
+        Ok(_) => panic!("annotated private procedure was accepted"),
+    }
+    let error = assembler
+        .compile("@only_kernel proc.foo add end begin exec.foo end")
+        .unwrap_err();
+    assert_eq!("E038", error.error_code().to_string());
+
+    // malformed annotations
+    for kernel in [
+        "@only_user export.foo add end",
+        "@only_context(kernel) export.foo add end",
+        "@only_kernel",
+    ] {
+        assert!(super::Assembler::default().with_kernel(kernel).is_err());
+    }
+}
+
+#[test]
+fn program_with_preserved_stack_elements() {
+    let assembler = super::Assembler::default();
+
+    // the deepest element is saved into a local allocated after the declared local, and is put
+    // back into its position after the procedure body
+    let source = "@preserve(1) proc.foo.1 pop.local.0 end begin exec.foo end";
+    let program = assembler.compile(source).unwrap();
+    let expected = "\
+        begin \
+            span \
+                push(2) fmpupdate \
+                dup15 pad pad pad push(18446744069414584320) fmpadd mstorew drop drop drop drop \
+                pad pad pad pad fmpadd mstorew drop drop drop drop \
+                swapdw movup7 swapdw movup8 drop \
+                pad pad pad pad push(18446744069414584320) fmpadd mloadw drop drop drop \
+                movdn8 swapdw movdn7 swapdw \
+                push(18446744069414584319) fmpupdate \
+            end \
+        end";
+    assert_eq!(expected, format!("{}", program));
+
+    // the body can access only the declared locals
+    let source = "@preserve(2) proc.foo.1 push.local.1 end begin exec.foo end";
+    assert!(assembler.compile(source).is_err());
+
+    // the annotation can be applied to private procedures, but not to generic ones
+    let source = "@preserve(2) proc.foo<N> push.N end begin exec.foo<1> end";
+    let error = assembler.compile(source).unwrap_err();
+    assert_eq!("E038", error.error_code().to_string());
+    let source = "@preserve(2) @preserve(3) proc.foo add end begin exec.foo end";
+    let error = assembler.compile(source).unwrap_err();
+    assert_eq!("E038", error.error_code().to_string());
+
+    // the number of preserved elements must be between 1 and 14
+    for annotation in ["@preserve(0)", "@preserve(15)", "@preserve(x)", "@preserve"] {
+        let source = format!("{} proc.foo add end begin exec.foo end", annotation);
+        assert!(assembler.compile(&source).is_err());
+    }
+}
+
+#[test]
+fn program_with_dynexec() {
+    let assembler = super::Assembler::default();
+    let source = "begin push.2 dynexec add end";
+    let program = assembler.compile(source).unwrap();
+    let expected = "begin join join span push(2) end dyn end span add end end end";
+    assert_eq!(expected, format!("{}", program));
+
+    // dynexec does not take any parameters
+    assert!(assembler.compile("begin dynexec.1 end").is_err());
+}
+
+#[test]
+fn program_with_call() {
+    let assembler = super::Assembler::default();
+    let source = "\
+        proc.foo push.1 end
+        proc.bar call.foo add end
+        begin push.2 call.bar call.foo end";
+    let program = assembler.compile(source).unwrap();
+
+    // called procedures are not inlined; the program carries their code instead
+    use vm_core::{code_blocks::CodeBlock, Operation::*};
+    let foo = CodeBlock::new_span(vec![Pad, Incr]);
+    let bar = CodeBlock::new_join([
+        CodeBlock::new_call(foo.hash()),
+        CodeBlock::new_span(vec![Add]),
+    ]);
+    let expected = format!(
+        "begin join join span push(2) end call.{:?} end call.{:?} end end",
+        bar.hash(),
+        foo.hash()
+    );
+    assert_eq!(expected, format!("{}", program));
+    assert_eq!(2, program.cb_table().len());
+    assert!(program.cb_table().contains(foo.hash()));
+    assert!(program.cb_table().contains(bar.hash()));
+
+    // only regular procedures can be called
+    let source = "proc.foo<N> push.N end begin call.foo<1> end";
+    assert!(assembler.compile(source).is_err());
+    assert!(assembler.compile("begin call.foo end").is_err());
+    assert!(assembler.compile("begin call end").is_err());
+}
+
+#[test]
+fn program_with_doc_comments() {
+    let source = "\
+        #! Multiplies two elements.
+        #!
+        #!   Input: [b, a, ...]
+        proc.foo push.3 push.7 mul end
+        # not a doc comment
+        proc.bar push.5 exec.foo add end
+        begin push.2 push.4 add exec.foo exec.bar end";
+    let tokens = super::TokenStream::new(source).unwrap();
+    assert_eq!(
+        Some("Multiplies two elements.\n\n  Input: [b, a, ...]"),
+        tokens.doc_comment(0)
+    );
+    assert_eq!(None, tokens.doc_comment(5));
+
+    // doc comments do not affect compilation
+    let assembler = super::Assembler::default();
+    let program = assembler.compile(source).unwrap();
+    let expected = "begin \
+        span push(2) push(4) add push(3) push(7) mul \
+        push(5) push(3) push(7) mul add \
+        end end";
+    assert_eq!(expected, format!("{}", program));
+}
+
+#[test]
+fn max_locals_path() {
+    let assembler = super::Assembler::default();
+    let source = "\
+        proc.inner.6 push.1 drop end
+        proc.small.1 push.1 drop end
+        proc.outer.4 exec.small exec.inner end
+        proc.sum<N>.2 repeat.N add end exec.inner end
+        begin exec.small exec.outer exec.sum<2> end";
+
+    // outer (4) + inner (6) exceeds sum (2) + inner (6)
+    let path = assembler.max_locals_path(source).unwrap();
+    assert_eq!(10, path.num_locals());
+    assert_eq!(
+        &[("outer".to_string(), 4), ("inner".to_string(), 6)],
+        path.frames()
+    );
+    assert_eq!("outer (4) -> inner (6)", path.to_string());
+
+    // procedures without locals are listed in the path as well
+    let source = "proc.foo.3 push.1 drop end proc.bar exec.foo end begin exec.bar end";
+    let path = assembler.max_locals_path(source).unwrap();
+    assert_eq!("bar (0) -> foo (3)", path.to_string());
+
+    // imported procedures are listed with their module prefix
+    let source = "use.std::math::secp256k1 begin exec.secp256k1::point_mul end";
+    let path = assembler.max_locals_path(source).unwrap();
+    assert_eq!("secp256k1::point_mul", path.frames()[0].0);
+    assert!(path.num_locals() > 20);
+
+    let path = assembler.max_locals_path("begin push.1 end").unwrap();
+    assert!(path.frames().is_empty());
+}
+
+#[test]
+fn locals_budget() {
+    let source = "\
+        proc.inner.6 push.1 drop end
+        proc.outer.4 exec.inner end
+        begin exec.outer end";
+    use super::BudgetPolicy;
+
+    let assembler = super::Assembler::default().with_locals_budget(10, BudgetPolicy::Error);
+    assert!(assembler.compile(source).is_ok());
+
+    let assembler = super::Assembler::default().with_locals_budget(8, BudgetPolicy::Error);
+    let error = assembler.compile(source).unwrap_err();
+    assert_eq!("E036", error.error_code().to_string());
+    assert_eq!(
+        "'proc.outer.4' allocates 10 locals along the invocation path outer (4) -> inner (6), \
+        which exceeds the budget of 8",
+        error.message()
+    );
+
+    // the budget applies to chains starting at the program body as well
+    let source = "\
+        proc.inner.6 push.1 drop end
+        proc.sum<N>.4 repeat.N add end exec.inner end
+        begin exec.sum<2> end";
+    let error = assembler.compile(source).unwrap_err();
+    assert_eq!(
+        "'begin' allocates 10 locals along the invocation path sum (4) -> inner (6), \
+        which exceeds the budget of 8",
+        error.message()
+    );
+
+    // the budget applies to library modules
+    let error = assembler
+        .check_module("proc.inner.6 push.1 drop end export.outer.4 exec.inner end")
+        .unwrap_err();
+    assert_eq!("E036", error.error_code().to_string());
+
+    // with the warn policy, the same chains are reported as warnings instead
+    let assembler = super::Assembler::default().with_locals_budget(8, BudgetPolicy::Warn);
+    let (program, warnings) = assembler.compile_with_warnings(source).unwrap();
+    assert_eq!(assembler.compile(source).unwrap().hash(), program.hash());
+    assert_eq!(1, warnings.len());
+    assert_eq!("E036", warnings[0].error_code().to_string());
+    assert_eq!(
+        "'begin' allocates 10 locals along the invocation path sum (4) -> inner (6), \
+        which exceeds the budget of 8",
+        warnings[0].message()
+    );
+    let module = "proc.inner.6 push.1 drop end export.outer.4 exec.inner end";
+    assert!(assembler.check_module(module).is_ok());
+    assert_eq!(
+        "E036",
+        warnings[0].clone().into_error().error_code().to_string()
+    );
+
+    // warnings cover imported modules, and chains within the budget are not reported
+    let source = "use.std::math::secp256k1 begin exec.secp256k1::point_mul end";
+    let (_, warnings) = assembler.compile_with_warnings(source).unwrap();
+    assert!(warnings.iter().any(|w| w.message().contains("secp256k1::")));
+    let assembler = super::Assembler::default().with_locals_budget(1000, BudgetPolicy::Warn);
+    assert!(assembler
+        .compile_with_warnings(source)
+        .unwrap()
+        .1
+        .is_empty());
+}
+
+#[test]
+fn assembly_limits() {
+    let source = "\
+        const.LIMIT=1
+        proc.foo.4 push.1 drop end
+        proc.bar<N> repeat.N add end end
+        proc.baz.2 exec.foo end
+        begin exec.baz end";
+
+    // limits are generous by default, and thus machine-generated names do not need to be short
+    let assembler = super::Assembler::default();
+    assert!(assembler.compile(source).is_ok());
+    let long_name = format!("proc_{}", "x".repeat(1000));
+    let long_source = format!(
+        "proc.{} push.1 drop end begin exec.{} end",
+        long_name, long_name
+    );
+    assert!(assembler.compile(&long_source).is_ok());
+
+    // generic procedures count towards the procedure limit
+    let limits = super::AssemblyLimits::default().with_max_procedures(2);
+    let assembler = super::Assembler::default().with_limits(limits);
+    let error = assembler.compile(source).unwrap_err();
+    assert_eq!("E042", error.error_code().to_string());
+    assert_eq!(
+        "'proc.baz.2' exceeds the limit of 2 procedures per module",
+        error.message()
+    );
+
+    let limits = super::AssemblyLimits::default().with_max_proc_locals(3);
+    let assembler = super::Assembler::default().with_limits(limits);
+    let error = assembler.compile(source).unwrap_err();
+    assert_eq!("E043", error.error_code().to_string());
+    assert_eq!(
+        "procedure 'foo' declares 4 locals, which exceeds the limit of 3",
+        error.message()
+    );
+
+    // names of constants, procedures, data segments, and imported modules are all limited
+    let limits = super::AssemblyLimits::default().with_max_name_len(4);
+    let assembler = super::Assembler::default().with_limits(limits);
+    for source in [
+        "const.LIMIT=1 begin push.LIMIT end",
+        "proc.foobar push.1 drop end begin exec.foobar end",
+        "data.TABLE=[1] begin push.addr_of.TABLE end",
+        "use.std::math::u64 begin push.1 end",
+    ] {
+        let error = assembler.compile(source).unwrap_err();
+        assert_eq!("E044", error.error_code().to_string(), "{}", source);
+    }
+    assert!(assembler
+        .compile("proc.foo push.1 drop end begin exec.foo end")
+        .is_ok());
+
+    // the limits apply to library modules
+    let error = assembler
+        .check_module("export.foobar push.1 drop end")
+        .unwrap_err();
+    assert_eq!("E044", error.error_code().to_string());
+
+    // importing the same module twice results in an error rather than a panic
+    let error = super::Assembler::default()
+        .compile("use.std::math::u64 use.std::math::u64 begin push.1 end")
+        .unwrap_err();
+    assert_eq!("E017", error.error_code().to_string());
+}
+
+// IMPORTS
+// ================================================================================================
+
+#[test]
+fn program_with_one_import() {
+    let assembler = super::Assembler::default();
+    let source = "\
+        use.std::math::u256
+        begin \
+            push.4 push.3 \
+            exec.u256::iszero_unsafe \
+        end";
+    let program = assembler.compile(source).unwrap();
+    let expected = "\
+        begin \
+            span \
+                push(4) push(3) \
+                eqz \
+                swap eqz and \
+                swap eqz and \
+                swap eqz and \
+                swap eqz and \
+                swap eqz and \
+                swap eqz and \
+                swap eqz and \
+            end \
+        end";
+    assert_eq!(expected, format!("{}", program));
+}
+
+#[test]
+fn program_with_import_errors() {
+    // --- non-existent import ------------------------------------------------
+    let assembler = super::Assembler::default();
+    let source = "\
+        use.std::math::u512
+        begin \
+            push.4 push.3 \
+            exec.u256::iszero_unsafe \
+        end";
+    assert!(assembler.compile(source).is_err());
+
+    // --- non-existent procedure in import -----------------------------------
+    let assembler = super::Assembler::default();
+    let source = "\
+        use.std::math::u256
+        begin \
+            push.4 push.3 \
+            exec.u256::foo \
+        end";
+    assert!(assembler.compile(source).is_err());
+}
+
+#[test]
+fn program_with_cached_import() {
+    let source = "\
+        use.std::crypto::hashes::blake3
+        begin \
+            exec.blake3::hash \
+        end";
+    let expected = super::Assembler::default().compile(source).unwrap();
+
+    let mut assembler = super::Assembler::default();
+    assembler
+        .cache_module("std::crypto::hashes::blake3")
+        .unwrap();
+    let program = assembler.compile(source).unwrap();
+    assert_eq!(expected.hash(), program.hash());
+
+    let error = assembler.cache_module("std::math::u512").unwrap_err();
+    assert_eq!(error.message(), "module source not found: std::math::u512");
+}
+
+#[test]
+fn program_with_lazily_cached_import() {
+    let source = "use.std::math::u64 begin exec.u64::checked_add end";
+    let assembler = super::Assembler::default();
+    let expected = assembler.compile(source).unwrap();
+
+    // imported modules are cached by the compilation, and the cache is shared with clones
+    assert!(assembler.module_cache.contains("std::math::u64"));
+    let clone = assembler.clone();
+    let handle = std::thread::spawn(move || {
+        clone
+            .compile("use.std::math::u256 begin exec.u256::add_unsafe end")
+            .unwrap();
+    });
+    handle.join().unwrap();
+    assert!(assembler.module_cache.contains("std::math::u256"));
+    assert_eq!(expected.hash(), assembler.compile(source).unwrap().hash());
+
+    // builders changing how modules are parsed start with an empty cache, which is refilled
+    let assembler = assembler.with_opt_level(super::OptLevel::Basic);
+    assert!(!assembler.module_cache.contains("std::math::u64"));
+    assembler.compile(source).unwrap();
+    assert!(assembler.module_cache.contains("std::math::u64"));
+
+    // modules parsed for introspection are cached as well, and failed compilations add nothing
+    let assembler = super::Assembler::default();
+    assembler.procedure_roots("std::math::u64").unwrap();
+    assert!(assembler.module_cache.contains("std::math::u64"));
+    assert!(assembler
+        .compile("use.std::math::u256 begin exec.u256::foo end")
+        .is_err());
+    assert!(!assembler.module_cache.contains("std::math::u256"));
+}
+
+#[test]
+fn program_with_library_import() {
+    use vm_core::MaslLibrary;
+
+    let modules = [
+        (
+            "foo::math".to_string(),
+            "use.foo::util export.add_one exec.util::one add end".to_string(),
+        ),
+        ("foo::util".to_string(), "export.one push.1 end".to_string()),
+    ];
+    let library = MaslLibrary::new("foo", "0.1.0", modules).unwrap();
+    let assembler = super::Assembler::default()
+        .with_library(library.clone())
+        .unwrap();
+
+    let source = "use.foo::math begin push.2 exec.math::add_one end";
+    let program = assembler.compile(source).unwrap();
+    let expected = "begin span push(2) pad incr add end end";
+    assert_eq!(expected, format!("{}", program));
+
+    // modules of libraries can be cached and introspected like modules of the standard library
+    let mut cached = assembler.clone();
+    cached.cache_module("foo::math").unwrap();
+    assert_eq!(program.hash(), cached.compile(source).unwrap().hash());
+    let roots = assembler.procedure_roots("foo::util").unwrap();
+    assert_eq!(vec!["one"], roots.keys().collect::<Vec<_>>());
+
+    // modules of libraries which were not added to the assembler cannot be imported
+    let error = super::Assembler::default().compile(source).unwrap_err();
+    assert_eq!(error.message(), "module source not found: foo::math");
+    let error = assembler
+        .compile("use.foo::bar begin push.1 end")
+        .unwrap_err();
+    assert_eq!(error.message(), "module source not found: foo::bar");
+
+    // namespaces of libraries must be distinct
+    let error = assembler.with_library(library).err().unwrap();
+    assert_eq!(error.message(), "duplicate library namespace: foo");
+    let library = MaslLibrary::new("std", "0.1.0", []).unwrap();
+    let error = super::Assembler::default()
+        .with_library(library)
+        .err()
+        .unwrap();
+    assert_eq!(error.message(), "duplicate library namespace: std");
+}
+
+#[test]
+fn library_procedure_roots() {
+    let program = super::Assembler::default()
+        .compile("use.std::math::u64 begin exec.u64::checked_add end")
+        .unwrap();
+
+    // roots are the same regardless of whether the module is cached
+    let mut assembler = super::Assembler::default();
+    let roots = assembler.procedure_roots("std::math::u64").unwrap();
+    assert_eq!(program.hash(), roots["checked_add"]);
+    assembler.cache_module("std::math::u64").unwrap();
+    assert_eq!(roots, assembler.procedure_roots("std::math::u64").unwrap());
+
+    // all exported procedures of the module are described together with their roots
+    let procs = assembler.exported_procedures("std::math::u64").unwrap();
+    assert_eq!(procs.len(), roots.len());
+    assert!(procs.iter().all(|proc| roots[proc.name()] == proc.root()));
+
+    let error = assembler.procedure_roots("std::math::u512").unwrap_err();
+    assert_eq!(error.message(), "module source not found: std::math::u512");
+}
+
+#[test]
+fn library_procedure_docs() {
+    use vm_core::{errors::LibraryError, Library, MaslLibrary};
+
+    let module = "\
+        #! Module documentation is not attached to any procedure.
+
+        #! Adds two elements.
+        #!
+        #!   Input: [b, a, ...]
+        #!   Output: [c, ...]
+        export.add.2
+            add
+        end
+        # not a doc comment
+        export.sub
+            sub
+        end
+        #! Private procedures are not included.
+        proc.mul
+            mul
+        end
+        #! Negates an element.
+            export.neg neg end
+        #! [A, b, ...] -> [b, A, ...], where A is a word
+        @preserve(1)
+        export.swap movdn.4 end";
+    let library = MaslLibrary::new("foo", "0.1.0", [("foo::bar".into(), module.into())]).unwrap();
+    let assembler = super::Assembler::default()
+        .with_library(library.clone())
+        .unwrap();
+
+    // docs are attached to indented declarations, and to annotations of annotated procedures
+    let docs = assembler.procedure_docs("foo::bar").unwrap();
+    assert_eq!(3, docs.len());
+    assert_eq!(
+        "Adds two elements.\n\n  Input: [b, a, ...]\n  Output: [c, ...]",
+        docs["add"]
+    );
+    assert_eq!("Negates an element.", docs["neg"]);
+    assert!(docs["swap"].starts_with("[A, b, ...]"));
+
+    // signatures are extracted from input and output lines, or from stack transition lines
+    let procs = assembler.exported_procedures("foo::bar").unwrap();
+    let names = procs.iter().map(|proc| proc.name()).collect::<Vec<_>>();
+    assert_eq!(vec!["add", "neg", "sub", "swap"], names);
+    let signature = procs[0].signature().unwrap();
+    assert_eq!(&["b".to_string(), "a".to_string()], signature.inputs());
+    assert_eq!(&["c".to_string()], signature.outputs());
+    assert!(procs[1].signature().is_none());
+    assert!(procs[2].docs().is_none() && procs[2].signature().is_none());
+    let signature = procs[3].signature().unwrap();
+    assert_eq!(&["A".to_string(), "b".to_string()], signature.inputs());
+    assert_eq!(&["b".to_string(), "A".to_string()], signature.outputs());
+
+    // docs of the standard library are available as well
+    let docs = assembler.procedure_docs("std::math::u64").unwrap();
+    assert!(docs["checked_add"].starts_with("Performs addition of two unsigned 64 bit"));
+    let procs = assembler.exported_procedures("std::math::u64").unwrap();
+    let checked_add = procs
+        .iter()
+        .find(|proc| proc.name() == "checked_add")
+        .unwrap();
+    assert_eq!(4, checked_add.signature().unwrap().num_inputs());
+    assert_eq!(2, checked_add.signature().unwrap().num_outputs());
+
+    assert!(assembler.procedure_docs("foo::baz").is_err());
+
+    // libraries describe their procedures by parsing their modules with the assembler
+    let std = vm_stdlib::StdLibrary::default();
+    assert_eq!(
+        assembler.procedure_docs("std::math::u64").unwrap(),
+        std.procedure_docs("std::math::u64", &assembler).unwrap()
+    );
+    assert_eq!(
+        procs,
+        std.exported_procedures("std::math::u64", &assembler)
+            .unwrap()
+    );
+    assert_eq!(
+        assembler.exported_procedures("foo::bar").unwrap(),
+        library.exported_procedures("foo::bar", &assembler).unwrap()
+    );
+    assert!(matches!(
+        library.procedure_docs("foo::baz", &assembler),
+        Err(LibraryError::ModuleNotFound(_))
+    ));
+    let library =
+        MaslLibrary::new("foo", "0.1.0", [("foo::bar".into(), "export.".into())]).unwrap();
+    assert!(matches!(
+        library.exported_procedures("foo::bar", &assembler),
+        Err(LibraryError::ModuleParsingFailed(..))
+    ));
+}
+
+#[test]
+fn program_digests() {
+    let kernel = "export.foo add end proc.bar mul end";
+    let assembler = super::Assembler::default().with_kernel(kernel).unwrap();
+    let source = "\
+        proc.baz push.1 end
+        proc.qux<N> push.N end
+        begin syscall.foo call.baz exec.qux<2> end";
+    let program = assembler.compile(source).unwrap();
+    let digests = assembler.program_digests(source).unwrap();
+    let foo = super::Assembler::default()
+        .compile("begin add end")
+        .unwrap()
+        .hash();
+    let baz = super::Assembler::default()
+        .compile("begin push.1 end")
+        .unwrap()
+        .hash();
+
+    // generic procedures of the program and private procedures of the kernel are not listed
+    assert_eq!(program.hash(), digests.program());
+    assert_eq!(program.kernel().hash(), digests.kernel());
+    assert_eq!(1, digests.procedures().len());
+    assert_eq!(baz, digests.procedures()["baz"]);
+    assert_eq!(1, digests.kernel_procedures().len());
+    assert_eq!(foo, digests.kernel_procedures()["foo"]);
+
+    // programs compiled without a kernel commit to the empty kernel
+    let digests = super::Assembler::default()
+        .program_digests("begin push.1 end")
+        .unwrap();
+    assert!(digests.procedures().is_empty());
+    assert!(digests.kernel_procedures().is_empty());
+    assert_eq!(vm_core::Kernel::default().hash(), digests.kernel());
+    assert_ne!(program.kernel().hash(), digests.kernel());
+}
+
+#[test]
+fn assembler_is_shared_between_threads() {
+    fn assert_send_sync<T: Send + Sync + Clone>() {}
+    assert_send_sync::<super::Assembler>();
+
+    let source = "\
+        use.std::math::u64
+        begin \
+            exec.u64::checked_add \
+        end";
+    let mut assembler = super::Assembler::default();
+    assembler.cache_module("std::math::u64").unwrap();
+    let expected = assembler.compile(source).unwrap().hash();
+
+    let handles = (0..4)
+        .map(|_| {
+            let assembler = assembler.clone();
+            std::thread::spawn(move || assembler.compile(source).unwrap().hash())
+        })
+        .collect::<Vec<_>>();
+    for handle in handles {
+        assert_eq!(expected, handle.join().unwrap());
+    }
+}
+
+// CONSTANTS
+// ================================================================================================
+
+#[test]
+fn repeat_with_constants() {
+    let assembler = super::Assembler::default();
+    let expected = "begin span pad incr pad incr pad incr pad incr end end";
+
+    let source = "const.N=4 begin repeat.N push.1 end end";
+    let program = assembler.compile(source).unwrap();
+    assert_eq!(expected, format!("{}", program));
+
+    let source = "const.N=2 const.M=N*3-4 begin repeat.(M*(N+1)-2) push.1 end end";
+    let program = assembler.compile(source).unwrap();
+    assert_eq!(expected, format!("{}", program));
+
+    let source = "begin repeat.0x4 push.1 end end";
+    let program = assembler.compile(source).unwrap();
+    assert_eq!(expected, format!("{}", program));
+}
+
+#[test]
+fn repeat_within_unroll_budget() {
+    let assembler = super::Assembler::default().with_unroll_budget(24);
+    let source = "begin repeat.3 repeat.4 dup add end end end";
+    assert!(assembler.compile(source).is_ok());
+}
+
+#[test]
+fn repeat_split() {
+    // 5 iterations of 2 ops with the threshold of 8 ops: 2 chunks of 2 iterations executed in a
+    // loop, with the remaining iteration unrolled in front of the loop
+    let assembler = super::Assembler::default().with_split_threshold(8);
+    let program = assembler.compile("begin repeat.5 dup add end end").unwrap();
+    let expected = "\
+        begin \
+            join \
+                join \
+                    span dup0 add pad incr fmpupdate push(2) pad fmpadd mstore drop pad incr end \
+                    while.true \
+                        span \
+                            dup0 add dup0 add \
+                            pad fmpadd mload push(18446744069414584320) add pad fmpadd mstore eqz not \
+                        end \
+                    end \
+                end \
+                span push(18446744069414584320) fmpupdate end \
+            end \
+        end";
+    assert_eq!(expected, format!("{}", program));
+
+    // the unroll budget applies only to the unrolled parts of the loop
+    let assembler = assembler.with_unroll_budget(6);
+    assert!(assembler
+        .compile("begin repeat.100 dup add end end")
+        .is_ok());
+
+    // loops whose bodies access procedure locals are always unrolled entirely
+    let source = "proc.foo.1 repeat.5 push.local.0 add end end begin exec.foo end";
+    assert!(matches!(
+        assembler.compile(source),
+        Err(error) if error.error_code().to_string() == "E032"
+    ));
+}
+
+#[test]
+fn instruction_params_with_constants() {
+    let assembler = super::Assembler::default();
+    let source = "const.ADDR=100 const.N=3 begin push.(N*2).N push.mem.ADDR add.(ADDR+1) end";
+    let program = assembler.compile(source).unwrap();
+    let expected = "begin span push(6) push(3) push(100) mload push(101) add end end";
+    assert_eq!(expected, format!("{}", program));
+
+    let source = "begin push.N end";
+    let error = assembler.compile(source).unwrap_err();
+    assert_eq!(error.message(), "undefined constant: N");
+}
+
+#[test]
+fn predefined_constants() {
+    let assembler = super::Assembler::default()
+        .with_constant("ADDR", 100)
+        .unwrap()
+        .with_constant("CHECKED", 1)
+        .unwrap();
+    let source = "begin push.mem.ADDR if.const.CHECKED assert end end";
+    let program = assembler.compile(source).unwrap();
+    let expected = "begin span push(100) mload assert end end";
+    assert_eq!(expected, format!("{}", program));
+
+    // predefined constants cannot be redeclared by programs
+    let error = assembler
+        .compile("const.ADDR=10 begin push.ADDR end")
+        .unwrap_err();
+    assert_eq!(error.message(), "duplicate constant name: ADDR");
+
+    for (name, value) in [("addr", 1), ("1ADDR", 1), ("ADDR", u64::MAX)] {
+        assert!(super::Assembler::default()
+            .with_constant(name, value)
+            .is_err());
+    }
+}
+
+#[test]
+fn custom_instructions() {
+    let notes = |name: &str, params: &[&str]| match (name, params) {
+        ("store_note", [addr]) => Ok(super::Expansion::Instructions(format!(
+            "storew.mem.{} dropw",
+            addr
+        ))),
+        ("note_created", []) => Ok(super::Expansion::Decorator(vm_core::Decorator::Event(42))),
+        ("nested", []) => Ok(super::Expansion::Instructions("myapp::nested".to_string())),
+        ("branch", []) => Ok(super::Expansion::Instructions(
+            "if.true add end".to_string(),
+        )),
+        _ => Err(format!("unknown instruction {}", name)),
+    };
+    let assembler = super::Assembler::default()
+        .with_extension("myapp", notes)
+        .unwrap();
+
+    // custom instructions are replaced by their expansions; constant parameters are resolved
+    // before the expansion
+    let source = "const.ADDR=8 begin push.1 myapp::store_note.ADDR myapp::note_created push.2 end";
+    let program = assembler.compile(source).unwrap();
+    let expected = "begin span pad incr push(8) mstorew drop drop drop drop push(2) end end";
+    assert_eq!(expected, format!("{}", program));
+    let decorators = match program.root() {
+        vm_core::code_blocks::CodeBlock::Span(span) => span
+            .decorators()
+            .iter()
+            .map(|(op_idx, decorator)| format!("{}: {}", op_idx, decorator))
+            .collect::<Vec<_>>(),
+        _ => panic!("expected a span block"),
+    };
+    assert_eq!(vec!["8: event(42)"], decorators);
+
+    // in debug mode, operations of an expansion are attributed to the custom instruction
+    let program = super::Assembler::new(true)
+        .with_extension("myapp", notes)
+        .unwrap()
+        .compile(source)
+        .unwrap();
+    let asmops = match program.root() {
+        vm_core::code_blocks::CodeBlock::Span(span) => span
+            .decorators()
+            .iter()
+            .filter(|(_, decorator)| matches!(decorator, vm_core::Decorator::AsmOp(_)))
+            .map(|(op_idx, decorator)| format!("{}: {}", op_idx, decorator))
+            .collect::<Vec<_>>(),
+        _ => panic!("expected a span block"),
+    };
+    let expected = [
+        "0: asmOp(push.1, 2)",
+        "2: asmOp(myapp::store_note.8, 6)",
+        "8: asmOp(myapp::note_created, 0)",
+        "8: asmOp(push.2, 1)",
+    ];
+    assert_eq!(expected.to_vec(), asmops);
+
+    for (source, message) in [
+        (
+            "begin other::store_note.8 end",
+            "instruction 'other::store_note.8' is invalid",
+        ),
+        (
+            "begin myapp::store_note end",
+            "custom instruction 'myapp::store_note' is invalid: unknown instruction store_note",
+        ),
+        (
+            "begin myapp::nested end",
+            "custom instruction 'myapp::nested' is invalid: expansion contains unsupported \
+            instruction 'myapp::nested'",
+        ),
+        (
+            "begin myapp::branch end",
+            "custom instruction 'myapp::branch' is invalid: expansion contains unsupported \
+            instruction 'if.true'",
+        ),
+    ] {
+        assert_eq!(message, assembler.compile(source).unwrap_err().message());
+    }
+
+    // namespaces must be valid and distinct
+    assert!(super::Assembler::default()
+        .with_extension("my::app", notes)
+        .is_err());
+    assert!(assembler.with_extension("myapp", notes).is_err());
+}
+
+#[test]
+fn if_const() {
+    let assembler = super::Assembler::default();
+
+    // only the selected branch is emitted
+    let source = "const.MODE=2 begin push.1 if.const.(MODE==2) add else mul end end";
+    let program = assembler.compile(source).unwrap();
+    assert_eq!("begin span pad incr add end end", format!("{}", program));
+
+    let source = "const.MODE=2 begin push.1 if.const.(MODE<2) add else mul end end";
+    let program = assembler.compile(source).unwrap();
+    assert_eq!("begin span pad incr mul end end", format!("{}", program));
+
+    // any non-zero value selects the `if` branch
+    let source = "const.N=3 begin push.1 if.const.N add end push.2 end";
+    let program = assembler.compile(source).unwrap();
+    assert_eq!(
+        "begin span pad incr add push(2) end end",
+        format!("{}", program)
+    );
+
+    // a false condition without an `else` clause emits nothing
+    let source = "const.N=3 begin push.1 if.const.N-3 add end push.2 end";
+    let program = assembler.compile(source).unwrap();
+    assert_eq!(
+        "begin span pad incr push(2) end end",
+        format!("{}", program)
+    );
+
+    // nested within a runtime conditional
+    let source = "\
+        const.N=3 \
+        begin \
+            push.1 \
+            if.true \
+                if.const.N>=4 add else push.2 mul end \
+            else \
+                if.const.N!=3 add end \
+            end \
+        end";
+    let program = assembler.compile(source).unwrap();
+    let expected = "\
+        begin \
+            join \
+                span pad incr end \
+                if.true span push(2) mul end else span noop end end \
+            end \
+        end";
+    assert_eq!(expected, format!("{}", program));
+}
+
+// DATA SEGMENTS
 // ================================================================================================
 
 #[test]
-fn program_with_one_import() {
+fn program_with_data_segments() {
     let assembler = super::Assembler::default();
     let source = "\
-        use.std::math::u256
-        begin \
-            push.4 push.3 \
-            exec.u256::iszero_unsafe \
-        end";
+        const.N=2
+        data.A=[3,N*4]
+        data.B=[5]
+        begin push.addr_of.B push.addr_of.A end";
     let program = assembler.compile(source).unwrap();
     let expected = "\
         begin \
             span \
-                push(4) push(3) \
-                eqz \
-                swap eqz and \
-                swap eqz and \
-                swap eqz and \
-                swap eqz and \
-                swap eqz and \
-                swap eqz and \
-                swap eqz and \
+                push(3) push(4294967296) mstore drop \
+                push(8) push(4294967297) mstore drop \
+                push(5) push(4294967298) mstore drop \
+                push(4294967298) push(4294967296) \
             end \
         end";
     assert_eq!(expected, format!("{}", program));
+
+    // data segments are a part of the canonical form
+    let canonical = assembler.canonicalize(source).unwrap();
+    assert!(canonical.starts_with("const.N=2\n\ndata.A=[3,8]\ndata.B=[5]\n\nbegin"));
 }
 
 #[test]
-fn program_with_import_errors() {
-    // --- non-existent import ------------------------------------------------
-    let assembler = super::Assembler::default();
-    let source = "\
-        use.std::math::u512
-        begin \
-            push.4 push.3 \
-            exec.u256::iszero_unsafe \
-        end";
-    assert!(assembler.compile(source).is_err());
-
-    // --- non-existent procedure in import -----------------------------------
+fn invalid_data_segments() {
     let assembler = super::Assembler::default();
-    let source = "\
-        use.std::math::u256
-        begin \
-            push.4 push.3 \
-            exec.u256::foo \
-        end";
-    assert!(assembler.compile(source).is_err());
+    for (source, code) in [
+        ("data.A=[1,,2] begin push.addr_of.A end", "E039"),
+        ("data.A=1 begin push.addr_of.A end", "E039"),
+        ("data.a=[1] begin push.addr_of.a end", "E028"),
+        ("data.A=[1] data.A=[2] begin push.addr_of.A end", "E040"),
+        ("data.A=[1] begin push.addr_of.B end", "E041"),
+    ] {
+        let error = assembler.compile(source).unwrap_err();
+        assert_eq!(code, error.error_code().to_string(), "{}", source);
+    }
 }
 
 // COMMENTS
@@ -267,6 +1632,525 @@ fn comment_after_program() {
     assert_eq!(expected, format!("{}", program));
 }
 
+// OPTIMIZATIONS
+// ================================================================================================
+
+#[test]
+fn optimized_span() {
+    use super::OptLevel;
+    let assembler = super::Assembler::default().with_opt_level(OptLevel::Basic);
+
+    // values which are dropped right away and stack manipulations which cancel each other out
+    let source = "begin push.0 drop swap dup.0 drop swap movup.3 movdn.3 add end";
+    let program = assembler.compile(source).unwrap();
+    assert_eq!("begin span add end end", format!("{}", program));
+
+    // arithmetic on immediate values
+    let source = "begin add.4 add.5 mul.2 mul.3 push.2 push.3 add add.1 sub.2 mul push.0 add end";
+    let program = assembler.compile(source).unwrap();
+    let expected = "begin span push(9) add push(24) mul end end";
+    assert_eq!(expected, format!("{}", program));
+
+    // `not not` is removed only by aggressive optimizations
+    let source = "begin not not end";
+    let program = assembler.compile(source).unwrap();
+    assert_eq!("begin span not not end end", format!("{}", program));
+    let aggressive = super::Assembler::default().with_opt_level(OptLevel::Aggressive);
+    let program = aggressive.compile(source).unwrap();
+    assert_eq!("begin span noop end end", format!("{}", program));
+
+    // operations are not moved across decorators
+    let source = "begin push.1 adv.u64div drop end";
+    let program = assembler.compile(source).unwrap();
+    let expected = "begin span pad incr drop end end";
+    assert_eq!(expected, format!("{}", program));
+}
+
+#[test]
+fn optimized_blocks() {
+    use super::OptLevel;
+    let assembler = super::Assembler::default().with_opt_level(OptLevel::Basic);
+
+    // spans which are optimized away are elided; blocks consisting only of elided spans are
+    // replaced with a single noop
+    let source = "\
+        proc.foo push.7 drop end
+        begin
+            push.1 if.true swap swap exec.foo else dup.1 drop push.2 end
+            repeat.2 movdn.2 movup.2 end
+        end";
+    let program = assembler.compile(source).unwrap();
+    let expected = "\
+        begin \
+            join \
+                join \
+                    span pad incr end \
+                    if.true span noop end else span push(2) end end \
+                end \
+                span noop noop end \
+            end \
+        end";
+    assert_eq!(expected, format!("{}", program));
+
+    // programs are not optimized by default or in debug mode
+    let source = "begin push.0 drop end";
+    let program = super::Assembler::default().compile(source).unwrap();
+    assert_eq!("begin span pad drop end end", format!("{}", program));
+    let debug = super::Assembler::new(true).with_opt_level(OptLevel::Basic);
+    let program = debug.compile(source).unwrap();
+    assert_eq!("begin span pad drop end end", format!("{}", program));
+}
+
+#[test]
+fn push_encoding() {
+    use super::{OptLevel, PushEncoding};
+    let source = "begin push.0 push.1 push.3 push.3 push.3 push.9 push.18446744069414584319 \
+        push.18446744069414584312 push.1000 end";
+
+    // by default, only 0 and 1 are composed from other operations
+    let program = super::Assembler::default().compile(source).unwrap();
+    let expected = "begin span pad pad incr push(3) push(3) push(3) push(9) \
+        push(18446744069414584319) push(18446744069414584312) push(1000) end end";
+    assert_eq!(expected, format!("{}", program));
+
+    // the fewest cycles: everything other than 0 is pushed, and repeated constants are duplicated
+    let assembler = super::Assembler::default().with_push_encoding(PushEncoding::MinCycles);
+    let program = assembler.compile(source).unwrap();
+    let expected = "begin span pad push(1) push(3) dup0 dup0 push(9) \
+        push(18446744069414584319) push(18446744069414584312) push(1000) end end";
+    assert_eq!(expected, format!("{}", program));
+
+    // the fewest slots: small values are composed from PAD, INCR and NEG
+    let assembler = super::Assembler::default().with_push_encoding(PushEncoding::MinSize);
+    let program = assembler.compile(source).unwrap();
+    let expected = "begin span pad pad incr pad incr incr incr dup0 dup0 push(9) \
+        pad incr incr neg push(18446744069414584312) push(1000) end end";
+    assert_eq!(expected, format!("{}", program));
+
+    // the encoding applies to constants produced by optimizations as well
+    let source = "begin push.2 push.3 add push.2 push.3 add end";
+    let assembler = super::Assembler::default()
+        .with_opt_level(OptLevel::Basic)
+        .with_push_encoding(PushEncoding::MinSize);
+    let program = assembler.compile(source).unwrap();
+    let expected = "begin span pad incr incr incr incr incr dup0 end end";
+    assert_eq!(expected, format!("{}", program));
+}
+
+#[test]
+fn disabled_optimizations() {
+    use super::{OptLevel, Optimization};
+    let source = "begin push.2 push.3 add swap swap end";
+    let assembler = super::Assembler::default().with_opt_level(OptLevel::Basic);
+
+    let program = assembler.clone().compile(source).unwrap();
+    assert_eq!("begin span push(5) end end", format!("{}", program));
+
+    let program = assembler
+        .clone()
+        .without_optimization(Optimization::ConstantFolding)
+        .compile(source)
+        .unwrap();
+    assert_eq!(
+        "begin span push(2) push(3) add end end",
+        format!("{}", program)
+    );
+
+    let program = assembler
+        .without_optimization(Optimization::CancellingMoves)
+        .compile(source)
+        .unwrap();
+    assert_eq!(
+        "begin span push(5) swap swap end end",
+        format!("{}", program)
+    );
+}
+
+// PROFILING
+// ================================================================================================
+
+#[test]
+fn procedure_profiling() {
+    let source = "\
+        proc.foo push.7 add end
+        proc.bar exec.foo mul end
+        begin push.1 exec.bar push.2 exec.foo end";
+
+    // by default, span roots of procedures are merged with the surrounding code
+    let program = super::Assembler::default().compile(source).unwrap();
+    let expected = "begin span pad incr push(7) add mul push(2) push(7) add end end";
+    assert_eq!(expected, format!("{}", program));
+
+    // with profiling, roots of procedures invoked via exec are kept as separate blocks
+    let assembler = super::Assembler::default().with_profiling();
+    let program = assembler.compile(source).unwrap();
+    let expected = "\
+        begin \
+            join \
+                join \
+                    span pad incr end \
+                    join span push(7) add end span mul end end \
+                end \
+                join span push(2) end span push(7) add end end \
+            end \
+        end";
+    assert_eq!(expected, format!("{}", program));
+
+    // copies of procedures invoked in repeat blocks are kept as separate blocks as well
+    let source = "proc.foo push.7 add end begin push.1 repeat.2 exec.foo end end";
+    let program = assembler.compile(source).unwrap();
+    let expected = "\
+        begin \
+            join \
+                span pad incr end \
+                join span push(7) add end span push(7) add end end \
+            end \
+        end";
+    assert_eq!(expected, format!("{}", program));
+}
+
+// AUDIT LOG
+// ================================================================================================
+
+#[test]
+fn audit_log() {
+    use super::{DecisionKind, OptLevel, Optimization};
+    let source = "\
+        use.std::math::u64 \
+        proc.foo push.2 push.3 add end \
+        begin exec.foo repeat.3 push.1 drop end exec.u64::checked_add end";
+
+    // without optimizations, procedures keep their roots, and only inlining, unrolling, and
+    // padding decisions are logged
+    let assembler = super::Assembler::default();
+    let (program, log) = assembler.compile_with_audit(source).unwrap();
+    assert_eq!(assembler.compile(source).unwrap().hash(), program.hash());
+    assert!(log.procedures().iter().all(|proc| !proc.is_transformed()));
+    assert!(log.get_procedure("std::math::u64::checked_add").is_some());
+
+    let main = log.procedures().last().unwrap();
+    assert_eq!("#main", main.name());
+    assert_eq!(program.hash(), main.after());
+    let kinds = main
+        .decisions()
+        .iter()
+        .map(|d| d.kind())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        vec![
+            DecisionKind::Inline,
+            DecisionKind::Unroll,
+            DecisionKind::Inline
+        ],
+        kinds
+    );
+    assert_eq!("exec.foo", main.decisions()[0].detail());
+    assert_eq!("exec.u64::checked_add", main.decisions()[2].detail());
+
+    // with optimizations, transformed procedures have different roots before and after
+    let assembler = super::Assembler::default().with_opt_level(OptLevel::Basic);
+    let (program, log) = assembler.compile_with_audit(source).unwrap();
+    assert_eq!(assembler.compile(source).unwrap().hash(), program.hash());
+
+    let foo = log.get_procedure("foo").unwrap();
+    assert!(foo.is_transformed());
+    assert_eq!(1, foo.decisions().len());
+    let decision = &foo.decisions()[0];
+    assert_eq!(
+        DecisionKind::Optimize(Optimization::ConstantFolding),
+        decision.kind()
+    );
+    assert_eq!(foo.before(), decision.before());
+    assert_eq!(foo.after(), decision.after());
+
+    // the body of the repeat block is elided entirely
+    let main = log.get_procedure("#main").unwrap();
+    assert!(main.is_transformed());
+    assert!(main.decisions().iter().any(|d| d.kind()
+        == DecisionKind::Optimize(Optimization::DeadValues)
+        && d.after() == Default::default()));
+
+    // a disabled optimization is not logged
+    let assembler = assembler.without_optimization(Optimization::ConstantFolding);
+    let (_, log) = assembler.compile_with_audit(source).unwrap();
+    let foo = log.get_procedure("foo").unwrap();
+    assert!(!foo.is_transformed());
+    assert!(foo.decisions().is_empty());
+}
+
+#[test]
+fn audit_log_json() {
+    use super::OptLevel;
+    let source = "proc.foo push.2 push.3 add end begin push.1 push.2 exec.foo end";
+    let assembler = super::Assembler::default().with_opt_level(OptLevel::Basic);
+    let (_, log) = assembler.compile_with_audit(source).unwrap();
+
+    let json = log.to_json();
+    assert!(json.starts_with("{\"procedures\":[{\"name\":\"foo\",\"before\":\"0x"));
+    assert!(
+        json.contains("\"kind\":\"optimize.constant_folding\",\"detail\":\"applied 1 time(s)\"")
+    );
+    assert!(json.contains("{\"name\":\"#main\""));
+    assert!(json.contains("\"kind\":\"inline\",\"detail\":\"exec.foo\""));
+    assert!(json.ends_with("]}]}"));
+}
+
+// CANONICALIZATION
+// ================================================================================================
+
+#[test]
+fn canonical_form() {
+    let assembler = super::Assembler::default();
+    let source = "\
+        use.std::math::u64
+        const.B=3
+        const.A=B*2 # A depends on B
+        # doubles the top item
+        proc.double dup add end
+        proc.proc0.1 exec.double end
+        begin
+            push.B if.true exec.proc0 else exec.u64::checked_add end
+        end";
+    let expected = "\
+use.std::math::u64
+
+const.A=6
+const.B=3
+
+proc.proc0
+    dup
+    add
+end
+
+proc.proc1.1
+    exec.proc0
+end
+
+begin
+    push.B
+    if.true
+        exec.proc1
+    else
+        exec.u64::checked_add
+    end
+end
+";
+    let canonical = assembler.canonicalize(source).unwrap();
+    assert_eq!(expected, canonical);
+
+    // the canonical form is a fixed point
+    assert_eq!(canonical, assembler.canonicalize(&canonical).unwrap());
+
+    // sources differing in formatting and private labels have the same canonical form
+    let other = "use.std::math::u64 const.A=6 const.B=3 proc.twice dup add end \
+        proc.helper.1 exec.twice end \
+        begin push.B if.true exec.helper else exec.u64::checked_add end end";
+    assert_eq!(canonical, assembler.canonicalize(other).unwrap());
+}
+
+#[test]
+fn canonical_form_of_module() {
+    let assembler = super::Assembler::default();
+
+    // canonical labels of private procedures do not clash with labels of exported procedures
+    let source = "proc.foo add end export.proc0 exec.foo end";
+    let expected = "proc.proc0_\n    add\nend\n\nexport.proc0\n    exec.proc0_\nend\n";
+    assert_eq!(expected, assembler.canonicalize(source).unwrap());
+
+    // annotations are written right before the procedure header
+    let source = "proc.foo add end @only_kernel export.bar exec.foo end";
+    let expected = "proc.proc0\n    add\nend\n\n@only_kernel\nexport.bar\n    exec.proc0\nend\n";
+    assert_eq!(expected, assembler.canonicalize(source).unwrap());
+
+    // invalid sources are rejected
+    assert!(assembler.canonicalize("begin plus end").is_err());
+}
+
+// SOURCE OUTLINES
+// ================================================================================================
+
+#[test]
+fn source_outline() {
+    let source = "\
+        use.std::math::u64
+        const.N=4
+        #! Adds two u64 values.
+        @only_kernel
+        export.add.2
+            if.true exec.u64::wrapping_add else exec.bar end
+        end
+        proc.foo<K>
+            repeat.K dup end
+        end
+        proc.bad.x
+        begin exec.add end";
+    let outline = super::SourceOutline::parse(source);
+
+    assert_eq!(1, outline.imports().len());
+    assert_eq!("u64", outline.imports()[0].name());
+    assert!(outline.get_import("u64").is_some());
+    assert_eq!("N", outline.constants()[0].name());
+
+    // malformed declarations are skipped
+    assert_eq!(2, outline.procedures().len());
+    let add = outline.get_procedure("add").unwrap();
+    assert_eq!("export.add.2", add.signature());
+    assert_eq!(Some("Adds two u64 values."), add.docs());
+    assert_eq!(Some((5, 9)), super::locate_token(source, add.pos()));
+    assert_eq!(
+        Some((7, 9)),
+        super::locate_token(source, add.end_pos().unwrap())
+    );
+    let foo = outline.get_procedure("foo").unwrap();
+    assert_eq!("proc.foo<K>", foo.signature());
+    assert_eq!(Some(add.end_pos().unwrap() + 5), foo.end_pos());
+
+    let invocations = outline
+        .invocations()
+        .iter()
+        .map(|proc| proc.label())
+        .collect::<Vec<_>>();
+    assert_eq!(vec!["u64::wrapping_add", "bar", "add"], invocations);
+    assert!(outline.is_program());
+
+    assert_eq!(
+        super::SourceOutline::default(),
+        super::SourceOutline::parse("")
+    );
+}
+
+#[test]
+fn check_module() {
+    let assembler = super::Assembler::default();
+    let source = "use.std::math::u64 export.foo exec.u64::wrapping_add end";
+    assert!(assembler.check_module(source).is_ok());
+
+    let error = assembler.check_module("export.foo plus end").unwrap_err();
+    assert_eq!("E005", error.error_code().to_string());
+}
+
+// SOURCE MAPS
+// ================================================================================================
+
+#[test]
+fn source_map() {
+    use vm_core::{errors::SourceMapError, SourceMap, SOURCE_MAP_VERSION};
+
+    let source = "begin push.1 push.2 add if.true mul.3 else push.4 end end";
+    let program = super::Assembler::new(true).compile(source).unwrap();
+    let source_map = SourceMap::new(&program);
+    assert_eq!(3, source_map.spans().len());
+
+    // push.1 is compiled into PAD INCR
+    let span = &source_map.spans()[0];
+    let instructions = span
+        .instructions()
+        .iter()
+        .map(|instruction| (instruction.op_idx(), instruction.text()))
+        .collect::<Vec<_>>();
+    assert_eq!(vec![(0, "push.1"), (2, "push.2"), (3, "add")], instructions);
+    assert_eq!(
+        Some("push.1"),
+        source_map
+            .instruction_at(span.hash(), 1)
+            .map(|instruction| instruction.text())
+    );
+    assert!(source_map.instruction_at(span.hash(), 4).is_none());
+    assert!(source_map.instruction_at([0; 32], 0).is_none());
+
+    // source maps survive a round trip through the serialized form
+    let bytes = source_map.to_bytes();
+    assert_eq!(Ok(source_map), SourceMap::from_bytes(&bytes));
+
+    let mut other = bytes.clone();
+    other[0] = b'X';
+    assert_eq!(
+        Err(SourceMapError::NotASourceMap),
+        SourceMap::from_bytes(&other)
+    );
+    let mut other = bytes.clone();
+    other[4] = SOURCE_MAP_VERSION + 1;
+    assert_eq!(
+        Err(SourceMapError::UnsupportedVersion(SOURCE_MAP_VERSION + 1)),
+        SourceMap::from_bytes(&other)
+    );
+    let mut other = bytes;
+    other.push(0);
+    assert!(SourceMap::from_bytes(&other).is_err());
+
+    // programs compiled in release mode do not retain assembly instructions
+    let program = super::Assembler::default().compile(source).unwrap();
+    let source_map = SourceMap::new(&program);
+    assert_eq!(3, source_map.spans().len());
+    assert!(source_map
+        .spans()
+        .iter()
+        .all(|span| span.instructions().is_empty()));
+}
+
+#[test]
+fn source_map_locations() {
+    use vm_core::{SourceLocation, SourceMap};
+
+    let source = "\
+proc.foo
+    push.1 drop
+end
+begin
+    exec.foo
+    push.2 assert
+end";
+    let program = super::Assembler::new(true).compile(source).unwrap();
+    let source_map = SourceMap::new(&program);
+
+    // instructions of inlined procedures are attributed to the chain of procedures containing
+    // them; instructions of the program itself are in no procedure
+    let instructions = source_map.spans()[0]
+        .instructions()
+        .iter()
+        .map(|instruction| {
+            (
+                instruction.text(),
+                instruction.procs().to_vec(),
+                instruction.location().cloned(),
+            )
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        vec![
+            (
+                "push.1",
+                vec!["foo".to_string()],
+                Some(SourceLocation::new(String::new(), 2, 5))
+            ),
+            (
+                "drop",
+                vec!["foo".to_string()],
+                Some(SourceLocation::new(String::new(), 2, 12))
+            ),
+            (
+                "push.2",
+                vec![],
+                Some(SourceLocation::new(String::new(), 6, 5))
+            ),
+            (
+                "assert",
+                vec![],
+                Some(SourceLocation::new(String::new(), 6, 12))
+            ),
+        ],
+        instructions
+    );
+
+    // locations of instructions of library procedures carry the path of the module
+    let source = "use.std::math::u64 begin exec.u64::checked_add end";
+    let program = super::Assembler::new(true).compile(source).unwrap();
+    let source_map = SourceMap::new(&program);
+    let instruction = &source_map.spans()[0].instructions()[0];
+    assert_eq!(&["u64::checked_add".to_string()], instruction.procs());
+    assert_eq!("std::math::u64", instruction.location().unwrap().module());
+}
+
 // ERRORS
 // ================================================================================================
 
@@ -423,11 +2307,112 @@ fn invalid_repeat() {
     if let Err(error) = program {
         assert_eq!(
             error.message(),
-            "malformed instruction `repeat.23x3`: parameter '23x3' is invalid"
+            "invalid constant expression '23x3': invalid integer '23x3'"
+        );
+    }
+
+    // zero iter count
+    let source = "const.N=2 begin push.1 add repeat.(N-2) mul end end";
+    let program = assembler.compile(source);
+    assert!(program.is_err());
+    if let Err(error) = program {
+        assert_eq!(
+            error.message(),
+            "malformed instruction `repeat.(N-2)`: parameter '(N-2)' is invalid"
+        );
+    }
+
+    // undefined constant
+    let source = "begin push.1 add repeat.N mul end end";
+    let program = assembler.compile(source);
+    assert!(program.is_err());
+    if let Err(error) = program {
+        assert_eq!(error.message(), "undefined constant: N");
+    }
+
+    // unroll budget exceeded
+    let assembler = super::Assembler::default().with_unroll_budget(20);
+    let source = "begin repeat.3 repeat.4 dup add end end end";
+    let program = assembler.compile(source);
+    assert!(program.is_err());
+    if let Err(error) = program {
+        assert_eq!(
+            error.message(),
+            "unrolling 'repeat.3' produces 24 operations, which exceeds the budget of 20"
         );
     }
 }
 
+#[test]
+fn invalid_constants() {
+    let assembler = super::Assembler::default();
+
+    let source = "const.N begin push.N end";
+    let error = assembler.compile(source).unwrap_err();
+    assert_eq!(
+        error.message(),
+        "malformed constant declaration 'const.N': expected 'const.NAME=EXPR'"
+    );
+
+    let source = "const.n=1 begin push.1 end";
+    let error = assembler.compile(source).unwrap_err();
+    assert_eq!(error.message(), "invalid constant name: n");
+
+    let source = "const.N=1 const.N=2 begin push.1 end";
+    let error = assembler.compile(source).unwrap_err();
+    assert_eq!(error.message(), "duplicate constant name: N");
+
+    let source = "const.N=(1+2 begin push.1 end";
+    let error = assembler.compile(source).unwrap_err();
+    assert_eq!(
+        error.message(),
+        "invalid constant expression '(1+2': unmatched parenthesis"
+    );
+
+    let source = "const.N=1/0 begin push.1 end";
+    let error = assembler.compile(source).unwrap_err();
+    assert_eq!(
+        error.message(),
+        "invalid constant expression '1/0': division by zero"
+    );
+
+    let source = "const.N=M+1 begin push.1 end";
+    let error = assembler.compile(source).unwrap_err();
+    assert_eq!(error.message(), "undefined constant: M");
+}
+
+#[test]
+fn invalid_if_const() {
+    let assembler = super::Assembler::default();
+
+    let source = "begin push.1 if.const add end end";
+    let error = assembler.compile(source).unwrap_err();
+    assert_eq!(
+        error.message(),
+        "malformed instruction 'if.const': missing required parameter"
+    );
+
+    let source = "begin push.1 if.const.N add end end";
+    let error = assembler.compile(source).unwrap_err();
+    assert_eq!(error.message(), "undefined constant: N");
+
+    let source = "begin push.1 if.const.1=1 add end end";
+    let error = assembler.compile(source).unwrap_err();
+    assert_eq!(
+        error.message(),
+        "invalid constant expression '1=1': unexpected character '='"
+    );
+
+    // errors in the discarded branch are still reported
+    let source = "begin push.1 if.const.0 add else exec.foo end end";
+    let error = assembler.compile(source).unwrap_err();
+    assert_eq!(error.message(), "undefined procedure: foo");
+
+    let source = "begin push.1 if.const.1 add";
+    let error = assembler.compile(source).unwrap_err();
+    assert_eq!(error.message(), "if without matching else/end");
+}
+
 #[test]
 fn invalid_while() {
     let assembler = super::Assembler::default();
@@ -459,3 +2444,55 @@ fn invalid_while() {
         assert_eq!(error.message(), "while without matching end");
     }
 }
+
+// ERROR INDEX
+// ================================================================================================
+
+#[test]
+fn error_codes() {
+    let assembler = super::Assembler::default();
+
+    let error = assembler.compile("begin push.1 plus end").unwrap_err();
+    assert_eq!("E005", error.error_code().to_string());
+    assert_eq!("invalid instruction", error.explanation().title());
+
+    let error = assembler
+        .compile("begin push.1 add while.true mul")
+        .unwrap_err();
+    assert_eq!("E011", error.error_code().to_string());
+
+    let error = assembler.compile("begin dup.16 end").unwrap_err();
+    assert_eq!(error.error_code(), "E008".parse().unwrap());
+}
+
+#[test]
+fn error_locations() {
+    let assembler = super::Assembler::default();
+    let source = "# comment\nbegin\n  push.1 # another comment\n\n  push.2 plus\nend";
+    let error = assembler.compile(source).unwrap_err();
+    assert_eq!(Some((5, 10)), super::locate_token(source, error.step()));
+
+    assert_eq!(Some((2, 1)), super::locate_token(source, 0));
+    assert_eq!(None, super::locate_token(source, 5));
+}
+
+#[test]
+fn error_index() {
+    // the index must be sorted by error code with no duplicates so that lookups can be done via
+    // binary search
+    for pair in super::ERROR_INDEX.windows(2) {
+        assert!(pair[0].code() < pair[1].code());
+    }
+
+    for entry in super::ERROR_INDEX.iter() {
+        // codes from 500 onwards are reserved for execution errors
+        assert!(entry.code().as_u16() < 500);
+        assert_eq!(Some(entry), super::explain_error(entry.code()));
+        assert!(!entry.explanation().is_empty());
+    }
+
+    assert_eq!(Ok(super::ERROR_INDEX[4].code()), "e005".parse());
+    assert_eq!(Ok(super::ERROR_INDEX[4].code()), "5".parse());
+    assert!("E0xx".parse::<super::ErrorCode>().is_err());
+    assert!(super::explain_error("E999".parse().unwrap()).is_none());
+}
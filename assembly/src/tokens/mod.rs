@@ -1,5 +1,6 @@
-use super::{AssemblyError, String, ToString, Vec};
+use super::{AssemblyError, Digest, ProcAnnotation, String, ToString, Vec};
 use core::fmt;
+use vm_core::{Felt, StarkField};
 
 mod stream;
 pub use stream::TokenStream;
@@ -18,6 +19,8 @@ impl<'a> Token<'a> {
     // --------------------------------------------------------------------------------------------
 
     pub const USE: &'static str = "use";
+    pub const CONST: &'static str = "const";
+    pub const DATA: &'static str = "data";
     pub const PROC: &'static str = "proc";
     pub const EXPORT: &'static str = "export";
 
@@ -27,8 +30,18 @@ impl<'a> Token<'a> {
     pub const WHILE: &'static str = "while";
     pub const REPEAT: &'static str = "repeat";
     pub const EXEC: &'static str = "exec";
+    pub const CALL: &'static str = "call";
+    pub const DYNEXEC: &'static str = "dynexec";
+    pub const SYSCALL: &'static str = "syscall";
     pub const END: &'static str = "end";
 
+    // PROCEDURE ANNOTATIONS
+    // --------------------------------------------------------------------------------------------
+
+    pub const ONLY_KERNEL: &'static str = "@only_kernel";
+    pub const ONLY_CONTEXT: &'static str = "@only_context";
+    pub const PRESERVE: &'static str = "@preserve";
+
     // CONSTRUCTOR
     // --------------------------------------------------------------------------------------------
     /// Returns a new token created from the specified string and position.
@@ -38,7 +51,7 @@ impl<'a> Token<'a> {
     pub fn new(token: &'a str, pos: usize) -> Self {
         assert!(!token.is_empty(), "token cannot be an empty string");
         Self {
-            parts: token.split('.').collect(),
+            parts: split_parts(token).collect(),
             pos,
         }
     }
@@ -66,6 +79,7 @@ impl<'a> Token<'a> {
         matches!(
             self.parts()[0],
             Self::USE
+                | Self::CONST
                 | Self::PROC
                 | Self::EXPORT
                 | Self::BEGIN
@@ -74,10 +88,18 @@ impl<'a> Token<'a> {
                 | Self::WHILE
                 | Self::REPEAT
                 | Self::EXEC
+                | Self::CALL
+                | Self::DYNEXEC
+                | Self::SYSCALL
                 | Self::END
         )
     }
 
+    /// Returns true if this token is a procedure annotation (e.g., `@only_kernel`).
+    pub fn is_annotation(&self) -> bool {
+        self.parts[0].starts_with('@')
+    }
+
     // STATE MUTATOR
     // --------------------------------------------------------------------------------------------
     /// Updates the contents of this token from the specified string and position.
@@ -87,7 +109,7 @@ impl<'a> Token<'a> {
     pub fn update(&mut self, token: &'a str, pos: usize) {
         assert!(!token.is_empty(), "token cannot be an empty string");
         self.parts.clear();
-        token.split('.').for_each(|part| self.parts.push(part));
+        split_parts(token).for_each(|part| self.parts.push(part));
         self.pos = pos;
     }
 
@@ -103,6 +125,46 @@ impl<'a> Token<'a> {
         }
     }
 
+    pub fn parse_const(&self) -> Result<(String, &'a str), AssemblyError> {
+        assert_eq!(Self::CONST, self.parts[0], "not a const");
+        match self.num_parts() {
+            1 => Err(AssemblyError::missing_param(self)),
+            2 => match self.parts[1].split_once('=') {
+                Some((name, expr)) if !expr.is_empty() => {
+                    let name = validate_const_name(name, self)?;
+                    Ok((name, expr))
+                }
+                _ => Err(AssemblyError::invalid_const_declaration(self)),
+            },
+            _ => Err(AssemblyError::extra_param(self)),
+        }
+    }
+
+    /// Returns the name of the data segment declared by this token together with the
+    /// expressions of its elements.
+    pub fn parse_data(&self) -> Result<(String, Vec<&'a str>), AssemblyError> {
+        assert_eq!(Self::DATA, self.parts[0], "not a data");
+        match self.num_parts() {
+            1 => Err(AssemblyError::missing_param(self)),
+            2 => match self.parts[1].split_once('=') {
+                Some((name, values)) => {
+                    let name = validate_const_name(name, self)?;
+                    let values = values
+                        .strip_prefix('[')
+                        .and_then(|values| values.strip_suffix(']'))
+                        .ok_or_else(|| AssemblyError::invalid_data_declaration(self))?;
+                    let values = values.split(',').collect::<Vec<_>>();
+                    if values.iter().any(|value| value.is_empty()) {
+                        return Err(AssemblyError::invalid_data_declaration(self));
+                    }
+                    Ok((name, values))
+                }
+                None => Err(AssemblyError::invalid_data_declaration(self)),
+            },
+            _ => Err(AssemblyError::extra_param(self)),
+        }
+    }
+
     pub fn validate_begin(&self) -> Result<(), AssemblyError> {
         assert_eq!(Self::BEGIN, self.parts[0], "not a begin");
         if self.num_parts() > 1 {
@@ -112,38 +174,59 @@ impl<'a> Token<'a> {
         }
     }
 
-    pub fn parse_proc(&self) -> Result<(String, u32, bool), AssemblyError> {
+    /// Returns the label, generic parameter names, number of locals, and export flag of the
+    /// procedure declared by this token. The list of generic parameters is empty for non-generic
+    /// procedures.
+    pub fn parse_proc(&self) -> Result<(String, Vec<String>, u32, bool), AssemblyError> {
         assert!(
             self.parts[0] == Self::PROC || self.parts[0] == Self::EXPORT,
             "invalid procedure declaration"
         );
         let is_export = self.parts[0] == Self::EXPORT;
-        match self.num_parts() {
-            1 => Err(AssemblyError::missing_param(self)),
-            2 => {
-                let label = validate_proc_declaration_label(self.parts[1], self)?;
-                Ok((label, 0, is_export))
+        let num_locals = match self.num_parts() {
+            1 => return Err(AssemblyError::missing_param(self)),
+            2 => 0,
+            3 => validate_proc_locals(self.parts[2], self)?,
+            _ => return Err(AssemblyError::extra_param(self)),
+        };
+
+        let (label, params) = split_generic_label(self.parts[1], self)?;
+        let label = validate_proc_declaration_label(label, self)?;
+        let mut param_names = Vec::with_capacity(params.len());
+        for param in params {
+            let name = validate_const_name(param, self)?;
+            if param_names.contains(&name) {
+                return Err(AssemblyError::duplicate_const(self, &name));
             }
-            3 => {
-                let label = validate_proc_declaration_label(self.parts[1], self)?;
-                let num_locals = validate_proc_locals(self.parts[2], self)?;
-                Ok((label, num_locals, is_export))
-            }
-            _ => Err(AssemblyError::extra_param(self)),
+            param_names.push(name);
         }
+
+        Ok((label, param_names, num_locals, is_export))
     }
 
-    pub fn validate_if(&self) -> Result<(), AssemblyError> {
+    /// Returns true if this token declares a generic procedure (e.g., `proc.foo<N>`).
+    pub fn is_generic_proc(&self) -> bool {
+        (self.parts[0] == Self::PROC || self.parts[0] == Self::EXPORT)
+            && self.num_parts() > 1
+            && self.parts[1].contains('<')
+    }
+
+    /// Returns `None` if this token starts a regular `if.true` block, and the condition
+    /// expression if this token starts a compile-time `if.const.EXPR` block.
+    pub fn parse_if(&self) -> Result<Option<&'a str>, AssemblyError> {
         assert_eq!(Self::IF, self.parts[0], "not an if");
         match self.num_parts() {
             1 => Err(AssemblyError::missing_param(self)),
-            2 => {
-                if self.parts[1] != "true" {
-                    Err(AssemblyError::invalid_param(self, 1))
-                } else {
-                    Ok(())
-                }
-            }
+            2 => match self.parts[1] {
+                "true" => Ok(None),
+                "const" => Err(AssemblyError::missing_param(self)),
+                _ => Err(AssemblyError::invalid_param(self, 1)),
+            },
+            3 => match self.parts[1] {
+                "const" => Ok(Some(self.parts[2])),
+                "true" => Err(AssemblyError::extra_param(self)),
+                _ => Err(AssemblyError::invalid_param(self, 1)),
+            },
             _ => Err(AssemblyError::extra_param(self)),
         }
     }
@@ -157,34 +240,99 @@ impl<'a> Token<'a> {
         }
     }
 
-    pub fn validate_while(&self) -> Result<(), AssemblyError> {
+    /// Returns `None` if this token starts a regular `while.true` loop, and the expression of the
+    /// maximum number of iterations if this token starts a bounded `while.bounded.EXPR` loop.
+    pub fn parse_while(&self) -> Result<Option<&'a str>, AssemblyError> {
         assert_eq!(Self::WHILE, self.parts[0], "not a while");
         match self.num_parts() {
             1 => Err(AssemblyError::missing_param(self)),
-            2 => {
-                if self.parts[1] != "true" {
-                    Err(AssemblyError::invalid_param(self, 1))
-                } else {
-                    Ok(())
-                }
-            }
+            2 => match self.parts[1] {
+                "true" => Ok(None),
+                "bounded" => Err(AssemblyError::missing_param(self)),
+                _ => Err(AssemblyError::invalid_param(self, 1)),
+            },
+            3 => match self.parts[1] {
+                "bounded" => Ok(Some(self.parts[2])),
+                "true" => Err(AssemblyError::extra_param(self)),
+                _ => Err(AssemblyError::invalid_param(self, 1)),
+            },
             _ => Err(AssemblyError::extra_param(self)),
         }
     }
 
-    pub fn parse_repeat(&self) -> Result<u32, AssemblyError> {
+    /// Returns the iteration count expression of a `repeat` token. The expression can be an
+    /// integer literal, a constant name, or a constant expression in parentheses.
+    pub fn parse_repeat(&self) -> Result<&'a str, AssemblyError> {
         assert_eq!(Self::REPEAT, self.parts[0], "not a repeat");
         match self.num_parts() {
             1 => Err(AssemblyError::missing_param(self)),
-            2 => self.parts[1]
-                .parse::<u32>()
-                .map_err(|_| AssemblyError::invalid_param(self, 1)),
+            2 => Ok(self.parts[1]),
             _ => Err(AssemblyError::extra_param(self)),
         }
     }
 
-    pub fn parse_exec(&self) -> Result<String, AssemblyError> {
+    /// Returns the label of the invoked procedure together with the generic argument
+    /// expressions. The list of arguments is empty if no generic arguments were provided.
+    pub fn parse_exec(&self) -> Result<(String, Vec<&'a str>), AssemblyError> {
         assert_eq!(Self::EXEC, self.parts[0], "not an exec");
+        match self.num_parts() {
+            1 => Err(AssemblyError::missing_param(self)),
+            2 => {
+                let (label, args) = split_generic_label(self.parts[1], self)?;
+                let label = validate_proc_invocation_label(label, self)?;
+                Ok((label, args))
+            }
+            _ => Err(AssemblyError::extra_param(self)),
+        }
+    }
+
+    /// Returns the MAST root of the procedure invoked by this `exec` token if the procedure is
+    /// specified by its root (e.g., `exec.0x<64 hex digits>`), or `None` if the procedure is
+    /// specified by its label.
+    ///
+    /// The root is expected to be a hex encoding of the digest bytes, i.e., of 4 field elements,
+    /// each encoded as 8 bytes in little-endian order.
+    pub fn parse_exec_root(&self) -> Result<Option<Digest>, AssemblyError> {
+        assert_eq!(Self::EXEC, self.parts[0], "not an exec");
+        let hex = match self.parts.get(1).and_then(|part| part.strip_prefix("0x")) {
+            Some(hex) => hex,
+            None => return Ok(None),
+        };
+        if self.num_parts() > 2 {
+            return Err(AssemblyError::extra_param(self));
+        }
+        if hex.len() != 64 || !hex.is_ascii() {
+            return Err(AssemblyError::invalid_param_with_reason(
+                self,
+                1,
+                "expected a MAST root of 64 hex digits",
+            ));
+        }
+
+        let mut elements = [Felt::default(); 4];
+        for (element, chunk) in elements.iter_mut().zip(hex.as_bytes().chunks(16)) {
+            let mut bytes = [0u8; 8];
+            for (byte, digits) in bytes.iter_mut().zip(chunk.chunks(2)) {
+                let digits = core::str::from_utf8(digits).expect("not ascii");
+                *byte = u8::from_str_radix(digits, 16)
+                    .map_err(|_| AssemblyError::invalid_param(self, 1))?;
+            }
+            let value = u64::from_le_bytes(bytes);
+            if value >= Felt::MODULUS {
+                return Err(AssemblyError::invalid_param_with_reason(
+                    self,
+                    1,
+                    "MAST root elements must be valid field elements",
+                ));
+            }
+            *element = Felt::new(value);
+        }
+        Ok(Some(elements.into()))
+    }
+
+    /// Returns the label of the procedure invoked by this `call` token.
+    pub fn parse_call(&self) -> Result<String, AssemblyError> {
+        assert_eq!(Self::CALL, self.parts[0], "not a call");
         match self.num_parts() {
             1 => Err(AssemblyError::missing_param(self)),
             2 => validate_proc_invocation_label(self.parts[1], self),
@@ -192,6 +340,70 @@ impl<'a> Token<'a> {
         }
     }
 
+    pub fn validate_dynexec(&self) -> Result<(), AssemblyError> {
+        assert_eq!(Self::DYNEXEC, self.parts[0], "not a dynexec");
+        if self.num_parts() > 1 {
+            Err(AssemblyError::extra_param(self))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Returns the label of the kernel procedure invoked by this `syscall` token.
+    pub fn parse_syscall(&self) -> Result<String, AssemblyError> {
+        assert_eq!(Self::SYSCALL, self.parts[0], "not a syscall");
+        match self.num_parts() {
+            1 => Err(AssemblyError::missing_param(self)),
+            2 => validate_proc_declaration_label(self.parts[1], self),
+            _ => Err(AssemblyError::extra_param(self)),
+        }
+    }
+
+    /// Returns the procedure annotation specified by this token, i.e., `@only_kernel`,
+    /// `@only_context(root)`, or `@preserve(K)`.
+    pub fn parse_annotation(&self) -> Result<ProcAnnotation, AssemblyError> {
+        assert!(self.is_annotation(), "not an annotation");
+        if self.num_parts() > 1 {
+            return Err(AssemblyError::extra_param(self));
+        }
+        let annotation = self.parts[0];
+        if annotation == Self::ONLY_KERNEL {
+            return Ok(ProcAnnotation::OnlyKernel);
+        }
+        if let Some(arg) = annotation
+            .strip_prefix(Self::PRESERVE)
+            .and_then(|arg| arg.strip_prefix('('))
+            .and_then(|arg| arg.strip_suffix(')'))
+        {
+            return match arg.parse::<u32>() {
+                Ok(num) if (1..=ProcAnnotation::MAX_PRESERVED).contains(&num) => {
+                    Ok(ProcAnnotation::Preserve(num))
+                }
+                _ => Err(AssemblyError::invalid_param_with_reason(
+                    self,
+                    0,
+                    &format!(
+                        "the number of preserved elements must be between 1 and {}",
+                        ProcAnnotation::MAX_PRESERVED
+                    ),
+                )),
+            };
+        }
+        match annotation
+            .strip_prefix(Self::ONLY_CONTEXT)
+            .and_then(|arg| arg.strip_prefix('('))
+            .and_then(|arg| arg.strip_suffix(')'))
+        {
+            Some("root") => Ok(ProcAnnotation::OnlyRootContext),
+            Some(_) => Err(AssemblyError::invalid_param_with_reason(
+                self,
+                0,
+                "only the root context can be specified",
+            )),
+            None => Err(AssemblyError::invalid_op(self)),
+        }
+    }
+
     pub fn validate_end(&self) -> Result<(), AssemblyError> {
         assert_eq!(Self::END, self.parts[0], "not an end");
         if self.num_parts() > 1 {
@@ -211,6 +423,18 @@ impl<'a> fmt::Display for Token<'a> {
 // HELPER FUNCTIONS
 // ================================================================================================
 
+/// Splits the specified token into parts separated by dots; dots within a quoted string do not
+/// separate parts.
+fn split_parts(token: &str) -> impl Iterator<Item = &str> {
+    let mut in_quotes = false;
+    token.split(move |c: char| {
+        if c == '"' {
+            in_quotes = !in_quotes;
+        }
+        c == '.' && !in_quotes
+    })
+}
+
 /// Label of a declared procedure must comply with the following rules:
 /// - It must start with an ascii letter.
 /// - It can contain only ascii letters, numbers, or underscores.
@@ -228,6 +452,23 @@ fn validate_proc_declaration_label(label: &str, token: &Token) -> Result<String,
     Ok(label.to_string())
 }
 
+/// Splits a procedure label of the form `label<a,b,...>` into the label and the list of items
+/// between the angle brackets. The list is empty if the label has no angle brackets.
+fn split_generic_label<'a>(
+    label: &'a str,
+    token: &Token,
+) -> Result<(&'a str, Vec<&'a str>), AssemblyError> {
+    match label.split_once('<') {
+        None => Ok((label, Vec::new())),
+        Some((label, items)) => match items.strip_suffix('>') {
+            Some(items) if items.split(',').all(|item| !item.is_empty()) => {
+                Ok((label, items.split(',').collect()))
+            }
+            _ => Err(AssemblyError::invalid_generic_params(token)),
+        },
+    }
+}
+
 /// A label of an invoked procedure must comply with the following rules:
 /// - It must start with an ascii letter.
 /// - It can contain only ascii letters, numbers, underscores, or colons.
@@ -251,6 +492,30 @@ fn validate_proc_invocation_label(label: &str, token: &Token) -> Result<String,
     Ok(label.to_string())
 }
 
+/// Name of a declared constant must comply with the following rules:
+/// - It must start with an uppercase ascii letter.
+/// - It can contain only uppercase ascii letters, numbers, or underscores.
+fn validate_const_name(name: &str, token: &Token) -> Result<String, AssemblyError> {
+    if !is_valid_const_name(name) {
+        return Err(AssemblyError::invalid_const_name(token, name));
+    }
+
+    Ok(name.to_string())
+}
+
+/// Returns true if the specified name complies with the rules for constant names (see
+/// [validate_const_name()]).
+pub fn is_valid_const_name(name: &str) -> bool {
+    // a name must start with an uppercase letter
+    if name.is_empty() || !name.chars().next().unwrap().is_ascii_uppercase() {
+        return false;
+    }
+
+    // a name can contain only uppercase letters, numbers, or underscores
+    name.chars()
+        .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_')
+}
+
 fn validate_proc_locals(locals: &str, token: &Token) -> Result<u32, AssemblyError> {
     match locals.parse::<u64>() {
         Ok(num_locals) => {
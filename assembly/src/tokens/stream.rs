@@ -1,6 +1,15 @@
 use super::{AssemblyError, Token};
 use core::fmt;
-use vm_core::utils::collections::Vec;
+use vm_core::utils::{
+    collections::{BTreeMap, Vec},
+    string::String,
+};
+
+// CONSTANTS
+// ================================================================================================
+
+/// Prefix of documentation comment lines.
+const DOC_COMMENT_PREFIX: &str = "#!";
 
 // TOKEN STREAM
 // ================================================================================================
@@ -8,6 +17,8 @@ use vm_core::utils::collections::Vec;
 #[derive(Debug)]
 pub struct TokenStream<'a> {
     tokens: Vec<&'a str>,
+    locations: Vec<(u32, u32)>,
+    doc_comments: BTreeMap<usize, String>,
     current: Token<'a>,
     pos: usize,
     temp: Token<'a>,
@@ -22,14 +33,29 @@ impl<'a> TokenStream<'a> {
             return Err(AssemblyError::empty_source());
         }
 
-        let tokens = source
-            .lines()
-            // Tokenize and remove comments
-            .flat_map(|line| {
-                line.split_whitespace()
-                    .take_while(|&token| !token.starts_with('#'))
-            })
-            .collect::<Vec<_>>();
+        // tokenize and remove comments; doc comments immediately preceding a line with tokens
+        // are attached to the first token of that line
+        let mut tokens = Vec::new();
+        let mut locations = Vec::new();
+        let mut doc_comments = BTreeMap::new();
+        let mut doc_lines = Vec::new();
+        for (line_idx, line) in source.lines().enumerate() {
+            if let Some(doc_line) = line.trim_start().strip_prefix(DOC_COMMENT_PREFIX) {
+                let doc_line = doc_line.trim_end();
+                doc_lines.push(doc_line.strip_prefix(' ').unwrap_or(doc_line));
+                continue;
+            }
+
+            let first_token_pos = tokens.len();
+            for token in line_tokens(line) {
+                tokens.push(token);
+                locations.push((line_idx as u32 + 1, token_column(line, token) as u32));
+            }
+            if !doc_lines.is_empty() && tokens.len() > first_token_pos {
+                doc_comments.insert(first_token_pos, doc_lines.join("\n"));
+            }
+            doc_lines.clear();
+        }
 
         if tokens.is_empty() {
             return Err(AssemblyError::empty_source());
@@ -37,12 +63,32 @@ impl<'a> TokenStream<'a> {
         let current = Token::new(tokens[0], 0);
         Ok(Self {
             tokens,
+            locations,
+            doc_comments,
             current,
             pos: 0,
             temp: Token::default(),
         })
     }
 
+    /// Returns the line and column (both 1-based) at which the token at the specified position
+    /// is located in the provided source, or None if the source contains fewer tokens. The column
+    /// is measured in characters.
+    ///
+    /// Tokens are counted in the same way as when a stream is constructed from the source.
+    pub fn locate(source: &str, pos: usize) -> Option<(usize, usize)> {
+        let mut num_tokens = 0;
+        for (line_idx, line) in source.lines().enumerate() {
+            for token in line_tokens(line) {
+                if num_tokens == pos {
+                    return Some((line_idx + 1, token_column(line, token)));
+                }
+                num_tokens += 1;
+            }
+        }
+        None
+    }
+
     // PUBLIC ACCESSORS
     // --------------------------------------------------------------------------------------------
 
@@ -51,11 +97,27 @@ impl<'a> TokenStream<'a> {
         self.pos
     }
 
+    /// Returns the line and column (both 1-based) at which the token at the specified position
+    /// is located in the source of this stream. The column is measured in characters.
+    ///
+    /// # Panics
+    /// Panics if the specified position is greater than or equal to the number of tokens in the
+    /// stream.
+    pub fn location(&self, pos: usize) -> (u32, u32) {
+        self.locations[pos]
+    }
+
     /// Returns 'true' all tokens from this stream have been read.
     pub fn eof(&self) -> bool {
         self.pos == self.tokens.len()
     }
 
+    /// Returns the doc comment (i.e., a block of `#!` comment lines) immediately preceding the
+    /// token at the specified position, if any.
+    pub fn doc_comment(&self, pos: usize) -> Option<&str> {
+        self.doc_comments.get(&pos).map(|comment| comment.as_str())
+    }
+
     // TOKEN READERS
     // --------------------------------------------------------------------------------------------
 
@@ -84,6 +146,22 @@ impl<'a> TokenStream<'a> {
         }
     }
 
+    /// Moves the current token position to the specified position. This is used to parse a
+    /// previously skipped range of tokens.
+    ///
+    /// # Panics
+    /// Panics if the specified position is greater than the number of tokens in the stream.
+    pub fn seek(&mut self, pos: usize) {
+        assert!(
+            pos <= self.tokens.len(),
+            "cannot seek past the end of the stream"
+        );
+        self.pos = pos;
+        if !self.eof() {
+            self.current.update(self.tokens[pos], pos);
+        }
+    }
+
     /// Increments the current token position by one. If the stream is at EOF, this is noop.
     pub fn advance(&mut self) {
         if !self.eof() {
@@ -100,3 +178,28 @@ impl<'a> fmt::Display for TokenStream<'a> {
         write!(f, "{:?}", &self.tokens[self.pos..])
     }
 }
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the 1-based column, measured in characters, at which the specified token of the
+/// specified line starts; the token must be a subslice of the line.
+fn token_column(line: &str, token: &str) -> usize {
+    let offset = token.as_ptr() as usize - line.as_ptr() as usize;
+    line[..offset].chars().count() + 1
+}
+
+/// Returns an iterator over the tokens of the specified line, up to the first token starting a
+/// comment. Tokens are separated by whitespace; whitespace within a quoted string (e.g., the
+/// message of a `panic` instruction) does not separate tokens.
+fn line_tokens(line: &str) -> impl Iterator<Item = &str> {
+    let mut in_quotes = false;
+    line.split(move |c: char| {
+        if c == '"' {
+            in_quotes = !in_quotes;
+        }
+        c.is_whitespace() && !in_quotes
+    })
+    .filter(|token| !token.is_empty())
+    .take_while(|token| !token.starts_with('#'))
+}
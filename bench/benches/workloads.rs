@@ -0,0 +1,127 @@
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+use miden_bench::{standard_workloads, Workload};
+use processor::Process;
+use prover::{ProofOptions, PublicInputs};
+use std::time::Duration;
+use vm_core::Program;
+
+fn assembly(c: &mut Criterion) {
+    let mut group = c.benchmark_group("assembly");
+    for workload in standard_workloads() {
+        group.bench_function(workload.name(), |bench| {
+            bench.iter(|| workload.compile().expect("Failed to compile workload."));
+        });
+    }
+    group.finish();
+}
+
+fn execution(c: &mut Criterion) {
+    let mut group = c.benchmark_group("execution");
+    for workload in standard_workloads() {
+        let program = compile(&workload);
+        let inputs = workload.inputs();
+        group.bench_function(workload.name(), |bench| {
+            bench.iter_batched(
+                || Process::new(inputs.clone()),
+                |mut process| {
+                    process
+                        .execute(&program)
+                        .expect("Failed to execute workload.")
+                },
+                BatchSize::SmallInput,
+            );
+        });
+    }
+    group.finish();
+}
+
+fn trace_generation(c: &mut Criterion) {
+    let mut group = c.benchmark_group("trace_generation");
+    for workload in standard_workloads() {
+        let program = compile(&workload);
+        let inputs = workload.inputs();
+        group.bench_function(workload.name(), |bench| {
+            bench.iter(|| {
+                processor::execute(&program, &inputs).expect("Failed to execute workload.")
+            });
+        });
+    }
+    group.finish();
+}
+
+fn proving(c: &mut Criterion) {
+    let mut group = c.benchmark_group("proving");
+    group.sample_size(10);
+    group.measurement_time(Duration::from_secs(30));
+    let options = ProofOptions::with_96_bit_security();
+    for workload in standard_workloads()
+        .into_iter()
+        .filter(Workload::is_provable)
+    {
+        let program = compile(&workload);
+        let inputs = workload.inputs();
+        group.bench_function(workload.name(), |bench| {
+            bench.iter_batched(
+                || {
+                    let trace =
+                        processor::execute(&program, &inputs).expect("Failed to execute workload.");
+                    let public_inputs = PublicInputs::new(
+                        program.hash(),
+                        trace.init_stack_state()[..workload.stack_init().len()].to_vec(),
+                        trace.last_stack_state()[..workload.num_outputs()].to_vec(),
+                    );
+                    (trace, public_inputs)
+                },
+                |(trace, public_inputs)| {
+                    prover::prove_trace(trace, public_inputs, &options)
+                        .expect("Failed to prove workload.")
+                },
+                BatchSize::LargeInput,
+            );
+        });
+    }
+    group.finish();
+}
+
+fn verification(c: &mut Criterion) {
+    let mut group = c.benchmark_group("verification");
+    let options = ProofOptions::with_96_bit_security();
+    for workload in standard_workloads()
+        .into_iter()
+        .filter(Workload::is_provable)
+    {
+        let program = compile(&workload);
+        let (outputs, proof) = prover::prove(
+            &program,
+            &workload.inputs(),
+            workload.num_outputs(),
+            &options,
+        )
+        .expect("Failed to prove workload.");
+        group.bench_function(workload.name(), |bench| {
+            bench.iter_batched(
+                || proof.clone(),
+                |proof| {
+                    verifier::verify(program.hash(), workload.stack_init(), &outputs, proof)
+                        .expect("Failed to verify workload.")
+                },
+                BatchSize::SmallInput,
+            );
+        });
+    }
+    group.finish();
+}
+
+fn compile(workload: &Workload) -> Program {
+    workload.compile().expect("Failed to compile workload.")
+}
+
+criterion_group!(
+    workloads_group,
+    assembly,
+    execution,
+    trace_generation,
+    proving,
+    verification
+);
+criterion_main!(workloads_group);
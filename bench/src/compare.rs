@@ -0,0 +1,222 @@
+use core::fmt;
+use std::{collections::BTreeMap, fs, path::Path};
+
+// BENCHMARK RESULTS
+// ================================================================================================
+
+/// Mean times of benchmarks saved by criterion under a baseline name, keyed by benchmark id
+/// (e.g., `execution/fib`).
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct BenchResults {
+    means: BTreeMap<String, f64>,
+}
+
+impl BenchResults {
+    /// Reads the results saved under the specified baseline name from the specified criterion
+    /// output directory (usually `target/criterion`).
+    ///
+    /// Criterion saves the estimates of a benchmark with id `group/function` into
+    /// `group/function/<baseline>/estimates.json`; the mean time (in nanoseconds) is read from
+    /// every such file.
+    ///
+    /// # Errors
+    /// Returns an error if the directory cannot be read, or if an estimates file is malformed.
+    pub fn read(criterion_dir: &Path, baseline: &str) -> Result<Self, String> {
+        let mut results = Self::default();
+        results.read_dir(criterion_dir, "", baseline)?;
+        Ok(results)
+    }
+
+    /// Returns the mean time (in nanoseconds) of the benchmark with the specified id, if known.
+    pub fn get(&self, id: &str) -> Option<f64> {
+        self.means.get(id).copied()
+    }
+
+    /// Returns an iterator over the ids and the mean times (in nanoseconds) of all benchmarks,
+    /// sorted by id.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, f64)> {
+        self.means.iter().map(|(id, &mean)| (id.as_str(), mean))
+    }
+
+    /// Returns true if no results were found.
+    pub fn is_empty(&self) -> bool {
+        self.means.is_empty()
+    }
+
+    /// Reads the results from the specified directory of benchmarks with ids starting with the
+    /// specified prefix, and from all of its subdirectories.
+    fn read_dir(&mut self, dir: &Path, id: &str, baseline: &str) -> Result<(), String> {
+        let entries = fs::read_dir(dir)
+            .map_err(|err| format!("Failed to read directory `{}` - {}", dir.display(), err))?;
+        for entry in entries.filter_map(Result::ok) {
+            let path = entry.path();
+            if !path.is_dir() {
+                continue;
+            }
+            let name = entry.file_name().to_string_lossy().to_string();
+            let estimates = path.join("estimates.json");
+            if name == baseline && !id.is_empty() && estimates.is_file() {
+                self.means.insert(id.to_string(), read_mean(&estimates)?);
+            } else if name != "report" {
+                let id = match id {
+                    "" => name,
+                    id => format!("{}/{}", id, name),
+                };
+                self.read_dir(&path, &id, baseline)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Reads the point estimate of the mean time from the specified criterion estimates file.
+fn read_mean(path: &Path) -> Result<f64, String> {
+    let estimates = fs::read_to_string(path).map_err(|err| {
+        format!(
+            "Failed to open estimates file `{}` - {}",
+            path.display(),
+            err
+        )
+    })?;
+    let estimates: serde_json::Value = serde_json::from_str(&estimates)
+        .map_err(|err| format!("Malformed estimates file `{}` - {}", path.display(), err))?;
+    estimates["mean"]["point_estimate"]
+        .as_f64()
+        .ok_or_else(|| format!("Estimates file `{}` has no mean time", path.display()))
+}
+
+// COMPARISON
+// ================================================================================================
+
+/// Change of the mean time of a benchmark between two sets of results.
+///
+/// A benchmark present only in one of the sets has no time in the other set; such a benchmark is
+/// never a regression.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BenchDelta {
+    id: String,
+    base: Option<f64>,
+    new: Option<f64>,
+    regression: bool,
+}
+
+impl BenchDelta {
+    /// Returns the id of the benchmark.
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Returns the mean time (in nanoseconds) of the benchmark in the base results.
+    pub fn base(&self) -> Option<f64> {
+        self.base
+    }
+
+    /// Returns the mean time (in nanoseconds) of the benchmark in the new results.
+    pub fn new_time(&self) -> Option<f64> {
+        self.new
+    }
+
+    /// Returns the relative change of the mean time (in percent), if the benchmark is present in
+    /// both results.
+    pub fn percent(&self) -> Option<f64> {
+        match (self.base, self.new) {
+            (Some(base), Some(new)) if base > 0.0 => Some((new - base) * 100.0 / base),
+            _ => None,
+        }
+    }
+
+    /// Returns true if the benchmark slowed down by more than the threshold of the comparison.
+    pub fn is_regression(&self) -> bool {
+        self.regression
+    }
+}
+
+/// Comparison of the results of all benchmarks in two sets of results (e.g., of two revisions of
+/// Miden VM).
+#[derive(Clone, Debug, PartialEq)]
+pub struct Comparison {
+    deltas: Vec<BenchDelta>,
+    threshold: f64,
+}
+
+impl Comparison {
+    /// Compares the specified results; benchmarks which slowed down by more than `threshold`
+    /// percent are regressions.
+    pub fn new(base: &BenchResults, new: &BenchResults, threshold: f64) -> Self {
+        let mut ids = base
+            .means
+            .keys()
+            .chain(new.means.keys())
+            .collect::<Vec<_>>();
+        ids.sort();
+        ids.dedup();
+
+        let deltas = ids
+            .into_iter()
+            .map(|id| {
+                let mut delta = BenchDelta {
+                    id: id.clone(),
+                    base: base.get(id),
+                    new: new.get(id),
+                    regression: false,
+                };
+                delta.regression = delta.percent().map_or(false, |percent| percent > threshold);
+                delta
+            })
+            .collect();
+
+        Self { deltas, threshold }
+    }
+
+    /// Returns the changes of all benchmarks, sorted by benchmark id.
+    pub fn deltas(&self) -> &[BenchDelta] {
+        &self.deltas
+    }
+
+    /// Returns the number of benchmarks which slowed down by more than the threshold.
+    pub fn num_regressions(&self) -> usize {
+        self.deltas.iter().filter(|delta| delta.regression).count()
+    }
+}
+
+impl fmt::Display for Comparison {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{:<32} {:>12} {:>12} {:>10}",
+            "benchmark", "base", "new", "change"
+        )?;
+        for delta in self.deltas.iter() {
+            let change = match delta.percent() {
+                Some(percent) => format!("{:+.2}%", percent),
+                None => "-".to_string(),
+            };
+            writeln!(
+                f,
+                "{:<32} {:>12} {:>12} {:>10}{}",
+                delta.id,
+                format_time(delta.base),
+                format_time(delta.new),
+                change,
+                if delta.regression { "  regression" } else { "" }
+            )?;
+        }
+        write!(
+            f,
+            "{} regression(s) above {}%",
+            self.num_regressions(),
+            self.threshold
+        )
+    }
+}
+
+/// Formats the specified time in nanoseconds with a unit suited to its magnitude.
+fn format_time(time: Option<f64>) -> String {
+    match time {
+        None => "-".to_string(),
+        Some(ns) if ns < 1e3 => format!("{:.1} ns", ns),
+        Some(ns) if ns < 1e6 => format!("{:.1} us", ns / 1e3),
+        Some(ns) if ns < 1e9 => format!("{:.1} ms", ns / 1e6),
+        Some(ns) => format!("{:.2} s", ns / 1e9),
+    }
+}
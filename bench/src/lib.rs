@@ -0,0 +1,232 @@
+use assembly::{Assembler, AssemblyError};
+use core::fmt::Write;
+use vm_core::{AdviceSet, Felt, FieldElement, Program, ProgramInputs, StarkField, Word};
+
+mod compare;
+pub use compare::{BenchDelta, BenchResults, Comparison};
+
+#[cfg(test)]
+mod tests;
+
+// CONSTANTS
+// ================================================================================================
+
+/// Number of Fibonacci terms computed by the standard `fib` workload.
+pub const FIB_TERMS: usize = 1000;
+
+/// Number of chained hashes computed by the standard `blake3` workload.
+pub const BLAKE3_HASHES: usize = 8;
+
+/// Number of leaf updates made by the standard `smt_updates` workload.
+pub const SMT_UPDATES: usize = 32;
+
+/// Number of chained multiplications computed by the standard `u256_mul` workload.
+pub const U256_MULS: usize = 32;
+
+/// Depth of the sparse Merkle tree updated by the `smt_updates` workload.
+const SMT_DEPTH: u32 = 32;
+
+/// Number of leaves of the sparse Merkle tree updated by the `smt_updates` workload.
+const SMT_LEAVES: u64 = 64;
+
+// WORKLOAD
+// ================================================================================================
+
+/// A program with inputs, which exercises a typical use case of Miden VM.
+///
+/// Workloads are parametrized by their size (e.g., the number of computed hashes), and the
+/// standard workloads returned by [standard_workloads()] are benchmarked by the benches of this
+/// crate. Workloads use the standard library, and are compiled in release mode.
+#[derive(Clone, Debug)]
+pub struct Workload {
+    name: &'static str,
+    source: String,
+    stack_init: Vec<u64>,
+    advice_sets: Vec<AdviceSet>,
+    num_outputs: usize,
+    provable: bool,
+}
+
+impl Workload {
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a workload computing the specified number of terms of the Fibonacci sequence (in
+    /// the field of Miden VM).
+    pub fn fib(num_terms: usize) -> Self {
+        let source = format!(
+            "
+            begin
+                repeat.{}
+                    swap dup.1 add
+                end
+            end",
+            num_terms
+        );
+        Self {
+            name: "fib",
+            source,
+            stack_init: vec![0, 1],
+            advice_sets: Vec::new(),
+            num_outputs: 1,
+            provable: true,
+        }
+    }
+
+    /// Returns a workload computing a chain of the specified number of BLAKE3 2-to-1 hashes,
+    /// where each hash is computed over two copies of the previous digest.
+    pub fn blake3(num_hashes: usize) -> Self {
+        let source = format!(
+            "
+            use.std::crypto::hashes::blake3
+            begin
+                repeat.{}
+                    exec.blake3::hash
+                    dupw.1 dupw.1
+                end
+                dropw dropw
+            end",
+            num_hashes
+        );
+        Self {
+            name: "blake3",
+            source,
+            stack_init: (0..16).collect(),
+            advice_sets: Vec::new(),
+            num_outputs: 8,
+            provable: false,
+        }
+    }
+
+    /// Returns a workload making the specified number of updates of leaves of a sparse Merkle
+    /// tree supplied by the advice provider; the root of the updated tree is left on the stack.
+    pub fn smt_updates(num_updates: usize) -> Self {
+        let tree = smt_tree();
+        let mut source = String::from("begin\n");
+        for (key, value) in smt_updates(num_updates) {
+            writeln!(
+                source,
+                "    push.{}.{}.{}.{} push.{} push.{} mtree_set dropw",
+                value[0], value[1], value[2], value[3], key, SMT_DEPTH
+            )
+            .unwrap();
+        }
+        source.push_str("end");
+
+        Self {
+            name: "smt_updates",
+            source,
+            stack_init: tree.root().iter().map(|value| value.as_int()).collect(),
+            advice_sets: vec![tree],
+            num_outputs: 4,
+            provable: true,
+        }
+    }
+
+    /// Returns a workload computing a chain of the specified number of wrapping 256-bit
+    /// multiplications, where each multiplication squares the previous product.
+    pub fn u256_mul(num_muls: usize) -> Self {
+        let source = format!(
+            "
+            use.std::math::u256
+            begin
+                repeat.{}
+                    dupw.1 dupw.1
+                    exec.u256::mul_unsafe
+                end
+            end",
+            num_muls
+        );
+        Self {
+            name: "u256_mul",
+            source,
+            stack_init: (0..8).map(|i| 0xfedc_ba98 - i * 0x0101_0101).collect(),
+            advice_sets: Vec::new(),
+            num_outputs: 8,
+            provable: false,
+        }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the name of this workload, which is used as the name of its benchmarks.
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Returns the source code of the program of this workload.
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// Returns the initial stack of this workload, with the top of the stack last.
+    pub fn stack_init(&self) -> &[u64] {
+        &self.stack_init
+    }
+
+    /// Returns the inputs with which the program of this workload is executed.
+    pub fn inputs(&self) -> ProgramInputs {
+        ProgramInputs::new(&self.stack_init, &[], self.advice_sets.clone())
+            .expect("invalid workload inputs")
+    }
+
+    /// Returns the number of stack outputs of the program of this workload.
+    pub fn num_outputs(&self) -> usize {
+        self.num_outputs
+    }
+
+    /// Returns true if a proof of the workload can be generated.
+    ///
+    /// Proving programs which use `std::crypto::hashes::blake3` or `std::math::u256` with a full
+    /// stack currently fails while building the auxiliary columns of the stack overflow table, and
+    /// thus such workloads are benchmarked only up to trace generation.
+    pub fn is_provable(&self) -> bool {
+        self.provable
+    }
+
+    /// Compiles the program of this workload.
+    pub fn compile(&self) -> Result<Program, AssemblyError> {
+        Assembler::default().compile(&self.source)
+    }
+}
+
+/// Returns the standard workloads: the `fib`, `blake3`, `smt_updates`, and `u256_mul` workloads
+/// of the standard sizes.
+pub fn standard_workloads() -> Vec<Workload> {
+    vec![
+        Workload::fib(FIB_TERMS),
+        Workload::blake3(BLAKE3_HASHES),
+        Workload::smt_updates(SMT_UPDATES),
+        Workload::u256_mul(U256_MULS),
+    ]
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the sparse Merkle tree updated by the `smt_updates` workload; the keys of its leaves
+/// are spread over the entire key space.
+fn smt_tree() -> AdviceSet {
+    let keys = (0..SMT_LEAVES).map(smt_key).collect();
+    let values = (0..SMT_LEAVES).map(|i| smt_value(i, 0)).collect();
+    AdviceSet::new_sparse_merkle_tree(keys, values, SMT_DEPTH).expect("invalid tree")
+}
+
+/// Returns the keys and the new values of the leaves updated by the `smt_updates` workload.
+fn smt_updates(num_updates: usize) -> impl Iterator<Item = (u64, Word)> {
+    (0..num_updates as u64).map(|i| (smt_key(i % SMT_LEAVES), smt_value(i, 1 + i / SMT_LEAVES)))
+}
+
+fn smt_key(i: u64) -> u64 {
+    i.wrapping_mul(0x9e37_79b9) % (1 << SMT_DEPTH)
+}
+
+fn smt_value(i: u64, version: u64) -> Word {
+    [
+        Felt::new(i),
+        Felt::new(version),
+        Felt::ONE,
+        Felt::new(i * version),
+    ]
+}
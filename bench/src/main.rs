@@ -0,0 +1,167 @@
+use miden_bench::{BenchResults, Comparison};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::{self, Command},
+};
+use structopt::StructOpt;
+
+/// Baseline name under which results of the base revision are saved.
+const BASE_BASELINE: &str = "compare-base";
+
+/// Baseline name under which results of the new revision are saved.
+const NEW_BASELINE: &str = "compare-new";
+
+#[derive(StructOpt, Debug)]
+#[structopt(
+    name = "miden-bench",
+    about = "Compare benchmarks of Miden VM between two git revisions"
+)]
+pub struct CompareCmd {
+    /// Git revision against which the new revision is compared (e.g., main)
+    #[structopt(long = "base")]
+    base: String,
+    /// Git revision compared against the base revision; if omitted, the working tree is
+    /// benchmarked
+    #[structopt(long = "new")]
+    new: Option<String>,
+    /// Only run benchmarks with ids matching this regular expression (e.g., execution/)
+    #[structopt(long = "filter")]
+    filter: Option<String>,
+    /// Minimum slowdown (in percent) of a benchmark considered a regression
+    #[structopt(long = "threshold", default_value = "5.0")]
+    threshold: f64,
+}
+
+impl CompareCmd {
+    pub fn execute(&self) -> Result<(), String> {
+        let root = PathBuf::from(git(Path::new("."), &["rev-parse", "--show-toplevel"])?);
+        let work_dir = root.join("target").join("miden-bench");
+        // both revisions are built into the same target directory, and thus criterion saves their
+        // results next to each other
+        let target_dir = work_dir.join("target");
+
+        self.bench_revision(
+            &root,
+            &work_dir,
+            &target_dir,
+            Some(&self.base),
+            BASE_BASELINE,
+        )?;
+        self.bench_revision(
+            &root,
+            &work_dir,
+            &target_dir,
+            self.new.as_deref(),
+            NEW_BASELINE,
+        )?;
+
+        let criterion_dir = target_dir.join("criterion");
+        let base = BenchResults::read(&criterion_dir, BASE_BASELINE)?;
+        let new = BenchResults::read(&criterion_dir, NEW_BASELINE)?;
+        let comparison = Comparison::new(&base, &new, self.threshold);
+        println!("{}", comparison);
+
+        match comparison.num_regressions() {
+            0 => Ok(()),
+            n => Err(format!("Found {} benchmark regression(s)", n)),
+        }
+    }
+
+    /// Runs the benchmarks of the specified revision (or of the working tree if no revision is
+    /// specified), and saves their results under the specified baseline name.
+    fn bench_revision(
+        &self,
+        root: &Path,
+        work_dir: &Path,
+        target_dir: &Path,
+        revision: Option<&str>,
+        baseline: &str,
+    ) -> Result<(), String> {
+        let worktree = match revision {
+            Some(revision) => {
+                let commit = git(
+                    root,
+                    &["rev-parse", "--verify", &format!("{}^{{commit}}", revision)],
+                )?;
+                println!("Benchmarking revision {} ({})", revision, commit);
+                let worktree = work_dir.join("worktrees").join(baseline);
+                if worktree.exists() {
+                    remove_worktree(root, &worktree)?;
+                }
+                let path = worktree.to_string_lossy();
+                git(root, &["worktree", "add", "--detach", &path, &commit])?;
+                Some(worktree)
+            }
+            None => {
+                println!("Benchmarking the working tree");
+                None
+            }
+        };
+
+        let mut args = vec!["bench", "-p", "miden-bench", "--bench", "workloads", "--"];
+        args.extend(["--save-baseline", baseline]);
+        if let Some(filter) = &self.filter {
+            args.push(filter);
+        }
+        let status = Command::new("cargo")
+            .args(&args)
+            .current_dir(worktree.as_deref().unwrap_or(root))
+            .env("CARGO_TARGET_DIR", target_dir)
+            .status()
+            .map_err(|err| format!("Failed to run cargo - {}", err))?;
+
+        if let Some(worktree) = &worktree {
+            remove_worktree(root, worktree)?;
+        }
+        match status.success() {
+            true => Ok(()),
+            false => Err(format!(
+                "Benchmarks of {} failed",
+                revision.unwrap_or("the working tree")
+            )),
+        }
+    }
+}
+
+/// Runs git with the specified arguments in the specified directory, and returns its trimmed
+/// standard output.
+fn git(dir: &Path, args: &[&str]) -> Result<String, String> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .map_err(|err| format!("Failed to run git - {}", err))?;
+    if !output.status.success() {
+        return Err(format!(
+            "git {} failed - {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Removes the specified git worktree, including any files left in it.
+fn remove_worktree(root: &Path, worktree: &Path) -> Result<(), String> {
+    let path = worktree.to_string_lossy();
+    if git(root, &["worktree", "remove", "--force", &path]).is_err() {
+        // the directory may be left over from a run which was interrupted
+        fs::remove_dir_all(worktree).map_err(|err| {
+            format!(
+                "Failed to remove worktree `{}` - {}",
+                worktree.display(),
+                err
+            )
+        })?;
+        git(root, &["worktree", "prune"])?;
+    }
+    Ok(())
+}
+
+fn main() {
+    if let Err(message) = CompareCmd::from_args().execute() {
+        eprintln!("{}", message);
+        process::exit(1);
+    }
+}
@@ -0,0 +1,171 @@
+use super::{smt_tree, smt_updates, standard_workloads, BenchResults, Comparison, Workload};
+use prover::ProofOptions;
+use std::{fs, path::Path};
+use vm_core::{Felt, FieldElement, StarkField};
+
+// WORKLOADS
+// ================================================================================================
+
+#[test]
+fn standard_workloads_execute() {
+    for workload in standard_workloads() {
+        let program = workload.compile().unwrap();
+        assert!(
+            processor::execute(&program, &workload.inputs()).is_ok(),
+            "workload {} failed",
+            workload.name()
+        );
+    }
+}
+
+#[test]
+fn fib_outputs() {
+    let outputs = execute(&Workload::fib(50));
+    let (mut second, mut top) = (Felt::ZERO, Felt::ONE);
+    for _ in 0..50 {
+        (second, top) = (top, top + second);
+    }
+    assert_eq!(vec![top.as_int()], outputs);
+}
+
+#[test]
+fn smt_updates_outputs() {
+    let mut tree = smt_tree();
+    for (key, value) in smt_updates(70) {
+        tree.update_leaf(key, value).unwrap();
+    }
+    let root = tree
+        .root()
+        .iter()
+        .rev()
+        .map(|value| value.as_int())
+        .collect::<Vec<_>>();
+    assert_eq!(root, execute(&Workload::smt_updates(70)));
+}
+
+#[test]
+fn u256_mul_outputs() {
+    let workload = Workload::u256_mul(3);
+    let mut limbs = workload
+        .stack_init()
+        .iter()
+        .map(|&limb| limb as u32)
+        .collect::<Vec<_>>();
+    for _ in 0..3 {
+        limbs = square(&limbs);
+    }
+    let expected = limbs
+        .iter()
+        .rev()
+        .map(|&limb| limb as u64)
+        .collect::<Vec<_>>();
+    assert_eq!(expected, execute(&workload));
+}
+
+#[test]
+fn workloads_prove_and_verify() {
+    let options = ProofOptions::with_96_bit_security();
+    for workload in [Workload::fib(10), Workload::smt_updates(1)] {
+        assert!(workload.is_provable());
+        let program = workload.compile().unwrap();
+        let (outputs, proof) = prover::prove(
+            &program,
+            &workload.inputs(),
+            workload.num_outputs(),
+            &options,
+        )
+        .unwrap();
+        assert!(verifier::verify(program.hash(), workload.stack_init(), &outputs, proof).is_ok());
+    }
+}
+
+// COMPARISON
+// ================================================================================================
+
+#[test]
+fn compare_results() {
+    let dir = std::env::temp_dir().join(format!("miden-bench-{}", std::process::id()));
+    write_estimates(&dir, "execution/fib/base", 1000.0);
+    write_estimates(&dir, "execution/fib/new", 1100.0);
+    write_estimates(&dir, "execution/blake3/base", 2000.0);
+    write_estimates(&dir, "execution/blake3/new", 1900.0);
+    write_estimates(&dir, "proving/fib/base", 5000.0);
+    write_estimates(&dir, "assembly/fib/new", 10.0);
+    fs::create_dir_all(dir.join("execution/report")).unwrap();
+
+    let base = BenchResults::read(&dir, "base").unwrap();
+    let new = BenchResults::read(&dir, "new").unwrap();
+    fs::remove_dir_all(&dir).unwrap();
+    assert_eq!(
+        vec![
+            ("execution/blake3", 2000.0),
+            ("execution/fib", 1000.0),
+            ("proving/fib", 5000.0)
+        ],
+        base.iter().collect::<Vec<_>>()
+    );
+    assert_eq!(Some(1100.0), new.get("execution/fib"));
+
+    // fib slowed down by 10%, and blake3 sped up by 5%; benchmarks with results of only one
+    // revision are never regressions
+    let comparison = Comparison::new(&base, &new, 5.0);
+    let ids = comparison
+        .deltas()
+        .iter()
+        .map(|delta| delta.id())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        vec![
+            "assembly/fib",
+            "execution/blake3",
+            "execution/fib",
+            "proving/fib"
+        ],
+        ids
+    );
+    assert_eq!(Some(-5.0), comparison.deltas()[1].percent());
+    assert!(comparison.deltas()[2].is_regression());
+    assert_eq!(None, comparison.deltas()[3].new_time());
+    assert_eq!(1, comparison.num_regressions());
+    assert!(comparison.to_string().contains("1.1 us"));
+
+    assert_eq!(0, Comparison::new(&base, &new, 10.0).num_regressions());
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Executes the workload and returns its stack outputs, with the top of the stack first.
+fn execute(workload: &Workload) -> Vec<u64> {
+    let program = workload.compile().unwrap();
+    let trace = processor::execute(&program, &workload.inputs()).unwrap();
+    trace.last_stack_state()[..workload.num_outputs()]
+        .iter()
+        .map(|value| value.as_int())
+        .collect()
+}
+
+/// Squares the 256-bit integer with the specified 32-bit limbs (least significant first), and
+/// discards the overflow.
+fn square(limbs: &[u32]) -> Vec<u32> {
+    let mut result = vec![0u32; 8];
+    for i in 0..8 {
+        let mut carry = 0u64;
+        for j in 0..8 - i {
+            let value = result[i + j] as u64 + limbs[i] as u64 * limbs[j] as u64 + carry;
+            result[i + j] = value as u32;
+            carry = value >> 32;
+        }
+    }
+    result
+}
+
+fn write_estimates(dir: &Path, path: &str, mean: f64) {
+    let dir = dir.join(path);
+    fs::create_dir_all(&dir).unwrap();
+    let estimates = format!(
+        "{{\"mean\":{{\"point_estimate\":{},\"standard_error\":1.0}}}}",
+        mean
+    );
+    fs::write(dir.join("estimates.json"), estimates).unwrap();
+}
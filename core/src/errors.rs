@@ -1,11 +1,65 @@
-use super::Word;
-use crate::utils::{collections::Vec, string::String};
+use super::{chiplets::hasher::Digest, Word};
+use crate::utils::{collections::Vec, hex::digest_to_hex, string::String, DeserializationError};
+use core::{fmt, str::FromStr};
+
+// ERROR CODE
+// ================================================================================================
+
+/// Stable identifier of a kind of error reported by Miden VM components.
+///
+/// Codes are rendered as `E` followed by a zero-padded three digit number (e.g., `E005`). Codes
+/// `E001` - `E499` identify kinds of assembly errors, and codes `E500` - `E999` identify kinds
+/// of execution errors. Once a code has been assigned to a kind of error it is never reused for a
+/// different kind of error, and thus, tooling can rely on codes remaining stable across versions
+/// of Miden VM rather than on error messages.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct ErrorCode(u16);
+
+impl ErrorCode {
+    /// Returns an error code with the specified numeric value.
+    pub const fn new(code: u16) -> Self {
+        Self(code)
+    }
+
+    /// Returns the numeric value of this error code.
+    pub const fn as_u16(&self) -> u16 {
+        self.0
+    }
+}
+
+impl fmt::Display for ErrorCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "E{:03}", self.0)
+    }
+}
+
+impl FromStr for ErrorCode {
+    type Err = ();
+
+    /// Parses an error code from a string. Both the rendered form (e.g., `E005` or `e005`) and
+    /// the bare numeric form (e.g., `5`) are accepted.
+    fn from_str(code: &str) -> Result<Self, Self::Err> {
+        let digits = code
+            .strip_prefix('E')
+            .or_else(|| code.strip_prefix('e'))
+            .unwrap_or(code);
+        digits.parse::<u16>().map(ErrorCode).map_err(|_| ())
+    }
+}
+
+// INPUT ERRORS
+// ================================================================================================
 
 #[derive(Clone, Debug)]
 pub enum InputError {
     NotFieldElement(u64, &'static str),
     TooManyStackValues(usize, usize),
     DuplicateAdviceRoot([u8; 32]),
+    DuplicateAdviceMapKey([u8; 32]),
+    InvalidStackPosition(usize),
+    DuplicateStackPosition(usize),
+    AdviceTapeTooLong(usize, usize),
+    TooManyAdviceSetNodes(usize, usize),
 }
 
 #[derive(Clone, Debug)]
@@ -18,9 +72,129 @@ pub enum AdviceSetError {
     InvalidDepth(u32, u32),
     InvalidPath(Vec<Word>),
     NodeNotInSet(u64),
+    InvalidNumPeaks(u64, usize),
 }
 
-#[derive(Clone, Debug)]
+#[derive(Debug)]
 pub enum LibraryError {
     ModuleNotFound(String),
+    InvalidNamespace(String),
+    InvalidModulePath(String),
+    FileError(String),
+    NotALibrary,
+    UnsupportedVersion(u8),
+    DeserializationError(DeserializationError),
+    ModuleParsingFailed(String, String),
+}
+
+impl fmt::Display for LibraryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ModuleNotFound(path) => write!(f, "module `{}` not found", path),
+            Self::InvalidNamespace(namespace) => {
+                write!(f, "`{}` is not a valid library namespace", namespace)
+            }
+            Self::InvalidModulePath(path) => {
+                write!(f, "`{}` is not a valid module path for the library", path)
+            }
+            Self::FileError(err) => write!(f, "failed to read library module {}", err),
+            Self::NotALibrary => write!(f, "not a library"),
+            Self::UnsupportedVersion(version) => {
+                write!(f, "unsupported library version {}", version)
+            }
+            Self::DeserializationError(err) => write!(f, "malformed library: {}", err),
+            Self::ModuleParsingFailed(path, err) => {
+                write!(f, "failed to parse module `{}`: {}", path, err)
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ReturnDataError {
+    InvalidLength(u64),
+    LengthMismatch(u64, u64),
+    CommitmentMismatch,
+    UnexpectedEnd(usize),
+    InvalidValue(usize, &'static str),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum HexParseError {
+    MissingPrefix,
+    InvalidLength(usize, usize),
+    InvalidCharacter(char),
+    NotFieldElement(u64),
+}
+
+impl fmt::Display for HexParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingPrefix => write!(f, "hex string must start with 0x"),
+            Self::InvalidLength(expected, actual) => write!(
+                f,
+                "expected {} hex digits after 0x, but found {}",
+                expected, actual
+            ),
+            Self::InvalidCharacter(c) => {
+                write!(
+                    f,
+                    "invalid character '{}': expected a lowercase hex digit",
+                    c
+                )
+            }
+            Self::NotFieldElement(value) => {
+                write!(f, "value {:#x} is not a valid field element", value)
+            }
+        }
+    }
+}
+
+// PROGRAM BUILDER ERRORS
+// ================================================================================================
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ProgramBuilderError {
+    KernelMismatch,
+    SyscallTargetNotInKernel(Digest),
+}
+
+impl fmt::Display for ProgramBuilderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::KernelMismatch => {
+                write!(
+                    f,
+                    "programs compiled against different kernels cannot be composed"
+                )
+            }
+            Self::SyscallTargetNotInKernel(hash) => write!(
+                f,
+                "system call target {} is not a part of the kernel",
+                digest_to_hex(*hash)
+            ),
+        }
+    }
+}
+
+// SOURCE MAP ERRORS
+// ================================================================================================
+
+#[derive(Debug, PartialEq)]
+pub enum SourceMapError {
+    DeserializationError(DeserializationError),
+    NotASourceMap,
+    UnsupportedVersion(u8),
+}
+
+impl fmt::Display for SourceMapError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::DeserializationError(err) => write!(f, "malformed source map: {}", err),
+            Self::NotASourceMap => write!(f, "not a source map"),
+            Self::UnsupportedVersion(version) => {
+                write!(f, "unsupported source map version {}", version)
+            }
+        }
+    }
 }
@@ -0,0 +1,179 @@
+use super::{hasher, AdviceSet, AdviceSetError, Word};
+use crate::utils::{
+    collections::Vec, Box, ByteReader, ByteWriter, Deserializable, DeserializationError,
+    Serializable,
+};
+
+// MERGED TREE
+// ================================================================================================
+
+/// A Merkle tree whose root is the hash of the roots of two advice sets of the same depth.
+///
+/// The left and the right subtrees of the root are the two merged advice sets, and thus the tree
+/// is one level deeper than the merged sets. The nodes of the subtrees are served by the merged
+/// sets themselves, and thus any kind of advice sets (including other merged trees) can be merged.
+///
+/// This struct is intended to be used as one of the variants of the [AdviceSet] enum.
+#[derive(Clone, Debug)]
+pub struct MergedTree {
+    root: Word,
+    left: Box<AdviceSet>,
+    right: Box<AdviceSet>,
+}
+
+impl MergedTree {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Returns a Merkle tree with the specified advice sets as the left and the right subtrees of
+    /// its root.
+    ///
+    /// # Errors
+    /// Returns an error if the depths of the advice sets are not equal.
+    pub fn new(left: AdviceSet, right: AdviceSet) -> Result<Self, AdviceSetError> {
+        if left.depth() != right.depth() {
+            return Err(AdviceSetError::InvalidDepth(left.depth(), right.depth()));
+        }
+        Ok(Self {
+            root: merge(&left, &right),
+            left: Box::new(left),
+            right: Box::new(right),
+        })
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the root of this Merkle tree.
+    pub fn root(&self) -> Word {
+        self.root
+    }
+
+    /// Returns the depth of this Merkle tree.
+    pub fn depth(&self) -> u32 {
+        self.left.depth() + 1
+    }
+
+    /// Returns the number of nodes stored in this Merkle tree, including the root.
+    pub fn num_nodes(&self) -> usize {
+        self.left.num_nodes() + self.right.num_nodes() + 1
+    }
+
+    /// Returns a node at the specified depth and index.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * The specified depth is greater than the depth of the tree.
+    /// * The specified index not valid for the specified depth.
+    /// * The merged advice set does not contain a node at the specified depth and index.
+    pub fn get_node(&self, depth: u32, index: u64) -> Result<Word, AdviceSetError> {
+        let (subtree, index) = self.locate(depth, index)?;
+        if depth == 1 {
+            Ok(subtree.root())
+        } else {
+            subtree.get_node(depth - 1, index)
+        }
+    }
+
+    /// Returns a Merkle path to a node at the specified depth and index. The node itself is
+    /// not included in the path.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * The specified depth is greater than the depth of the tree.
+    /// * The specified index not valid for the specified depth.
+    /// * The merged advice set does not contain a path to the node at the specified depth and
+    ///   index.
+    pub fn get_path(&self, depth: u32, index: u64) -> Result<Vec<Word>, AdviceSetError> {
+        let (subtree, subtree_index) = self.locate(depth, index)?;
+        let mut path = if depth == 1 {
+            Vec::with_capacity(1)
+        } else {
+            subtree.get_path(depth - 1, subtree_index)?
+        };
+
+        // the last node of the path is the root of the other subtree
+        let sibling = if index >> (depth - 1) == 0 {
+            &self.right
+        } else {
+            &self.left
+        };
+        path.push(sibling.root());
+        Ok(path)
+    }
+
+    // STATE MUTATORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Replaces the leaf at the specified index with the provided value.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * The specified index is not a valid leaf index for this tree.
+    /// * The merged advice set does not contain a leaf at the specified index.
+    pub fn update_leaf(&mut self, index: u64, value: Word) -> Result<(), AdviceSetError> {
+        let depth = self.depth();
+        if index >= 2u64.pow(depth) {
+            return Err(AdviceSetError::InvalidIndex(depth, index));
+        }
+
+        let subtree_index = index & (2u64.pow(depth - 1) - 1);
+        if index >> (depth - 1) == 0 {
+            self.left.update_leaf(subtree_index, value)?;
+        } else {
+            self.right.update_leaf(subtree_index, value)?;
+        }
+        self.root = merge(&self.left, &self.right);
+        Ok(())
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the subtree which contains the node at the specified depth and index, together
+    /// with the index of the node within the subtree.
+    fn locate(&self, depth: u32, index: u64) -> Result<(&AdviceSet, u64), AdviceSetError> {
+        if depth == 0 {
+            return Err(AdviceSetError::DepthTooSmall);
+        } else if depth > self.depth() {
+            return Err(AdviceSetError::DepthTooBig(depth));
+        }
+        if index >= 2u64.pow(depth) {
+            return Err(AdviceSetError::InvalidIndex(depth, index));
+        }
+
+        let subtree_index = index & (2u64.pow(depth - 1) - 1);
+        if index >> (depth - 1) == 0 {
+            Ok((&self.left, subtree_index))
+        } else {
+            Ok((&self.right, subtree_index))
+        }
+    }
+}
+
+// SERIALIZATION
+// ================================================================================================
+
+impl Serializable for MergedTree {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.left.write_into(target);
+        self.right.write_into(target);
+    }
+}
+
+impl Deserializable for MergedTree {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let left = AdviceSet::read_from(source)?;
+        let right = AdviceSet::read_from(source)?;
+        Self::new(left, right).map_err(|_| {
+            DeserializationError::InvalidValue("merged advice sets differ in depth".into())
+        })
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the hash of the roots of the specified advice sets.
+fn merge(left: &AdviceSet, right: &AdviceSet) -> Word {
+    hasher::merge(&[left.root().into(), right.root().into()]).into()
+}
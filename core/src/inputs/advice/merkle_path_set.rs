@@ -1,5 +1,9 @@
 use super::{hasher, AdviceSetError, Felt, FieldElement, Word};
-use crate::utils::collections::{BTreeMap, Vec};
+use crate::utils::{
+    bytes::{read_seq, read_word, write_seq, write_word},
+    collections::{BTreeMap, Vec},
+    ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable,
+};
 
 // MERKLE PATH SET
 // ================================================================================================
@@ -89,6 +93,12 @@ impl MerklePathSet {
         self.total_depth
     }
 
+    /// Returns the number of nodes stored in this set, i.e., the total length of all paths
+    /// including their leaves.
+    pub fn num_nodes(&self) -> usize {
+        self.paths.values().map(|path| path.len()).sum()
+    }
+
     /// Returns a node at the specified index.
     ///
     /// # Errors
@@ -248,6 +258,35 @@ fn compute_path_root(path: &[Word], depth: u32, index: u64) -> Word {
     comp_hash
 }
 
+// SERIALIZATION
+// ================================================================================================
+
+impl Serializable for MerklePathSet {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        write_word(target, &self.root);
+        target.write_u32(self.total_depth);
+        write_seq(target, &self.paths, |target, (index, path)| {
+            target.write_u64(*index);
+            write_seq(target, path, |target, node| write_word(target, node));
+        });
+    }
+}
+
+impl Deserializable for MerklePathSet {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let root = read_word(source)?;
+        let total_depth = source.read_u32()?;
+        let paths = read_seq(source, |source| {
+            Ok((source.read_u64()?, read_seq(source, read_word)?))
+        })?;
+        Ok(Self {
+            root,
+            total_depth,
+            paths: paths.into_iter().collect(),
+        })
+    }
+}
+
 // TESTS
 // ================================================================================================
 
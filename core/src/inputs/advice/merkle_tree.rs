@@ -2,7 +2,11 @@ use super::{
     hasher::{self, Digest},
     AdviceSetError, Felt, FieldElement, Word,
 };
-use crate::utils::collections::Vec;
+use crate::utils::{
+    bytes::{read_seq, read_word, write_seq, write_word},
+    collections::Vec,
+    ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable,
+};
 use core::slice;
 use math::log2;
 use winter_utils::uninit_vector;
@@ -66,6 +70,11 @@ impl MerkleTree {
         log2(self.nodes.len() / 2)
     }
 
+    /// Returns the number of nodes in this Merkle tree, including the leaves and the root.
+    pub fn num_nodes(&self) -> usize {
+        self.nodes.len() - 1
+    }
+
     /// Returns a node at the specified depth and index.
     ///
     /// # Errors
@@ -140,6 +149,28 @@ impl MerkleTree {
     }
 }
 
+// SERIALIZATION
+// ================================================================================================
+
+impl Serializable for MerkleTree {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        write_seq(target, &self.nodes, |target, node| write_word(target, node));
+    }
+}
+
+impl Deserializable for MerkleTree {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let nodes = read_seq(source, read_word)?;
+        if nodes.len() < 4 || !nodes.len().is_power_of_two() {
+            return Err(DeserializationError::InvalidValue(format!(
+                "{} is not a valid number of Merkle tree nodes",
+                nodes.len()
+            )));
+        }
+        Ok(Self { nodes })
+    }
+}
+
 // TESTS
 // ================================================================================================
 
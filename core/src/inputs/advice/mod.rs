@@ -1,12 +1,18 @@
 use super::{hasher, AdviceSetError, Felt, FieldElement, Word};
-use crate::utils::collections::Vec;
+use crate::utils::{
+    collections::Vec, ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable,
+};
 
+mod merged_tree;
+use merged_tree::MergedTree;
 mod merkle_tree;
 use merkle_tree::MerkleTree;
 mod merkle_path_set;
 use merkle_path_set::MerklePathSet;
 mod sparse_merkle_tree;
 use sparse_merkle_tree::SparseMerkleTree;
+mod types;
+pub use types::{LeafIndex, MmrPeaks, SmtRoot};
 
 // ADVICE SET
 // ================================================================================================
@@ -17,6 +23,7 @@ pub enum AdviceSet {
     MerkleTree(MerkleTree),
     SparseMerkleTree(SparseMerkleTree),
     MerklePathSet(MerklePathSet),
+    MergedTree(MergedTree),
 }
 
 impl AdviceSet {
@@ -46,6 +53,16 @@ impl AdviceSet {
         )?))
     }
 
+    /// Returns a new [AdviceSet] instantiated as a Merkle tree whose root is the hash of the roots
+    /// of the specified advice sets, i.e., a tree in which the left and the right subtrees of the
+    /// root are the specified advice sets.
+    ///
+    /// # Errors
+    /// Returns an error if the depths of the advice sets are not equal.
+    pub fn new_merged_tree(left: AdviceSet, right: AdviceSet) -> Result<Self, AdviceSetError> {
+        Ok(Self::MergedTree(MergedTree::new(left, right)?))
+    }
+
     // PUBLIC ACCESSORS
     // --------------------------------------------------------------------------------------------
 
@@ -53,8 +70,17 @@ impl AdviceSet {
     pub fn root(&self) -> Word {
         match self {
             Self::MerkleTree(tree) => tree.root(),
-            Self::SparseMerkleTree(tree) => tree.root(),
+            Self::SparseMerkleTree(tree) => tree.root().into(),
             Self::MerklePathSet(set) => set.root(),
+            Self::MergedTree(tree) => tree.root(),
+        }
+    }
+
+    /// Returns the root of this advice set if it is a sparse Merkle tree, or None otherwise.
+    pub fn smt_root(&self) -> Option<SmtRoot> {
+        match self {
+            Self::SparseMerkleTree(tree) => Some(tree.root()),
+            _ => None,
         }
     }
 
@@ -64,6 +90,17 @@ impl AdviceSet {
             Self::MerkleTree(tree) => tree.depth(),
             Self::SparseMerkleTree(tree) => tree.depth(),
             Self::MerklePathSet(set) => set.depth(),
+            Self::MergedTree(tree) => tree.depth(),
+        }
+    }
+
+    /// Returns the number of nodes stored in this advice set.
+    pub fn num_nodes(&self) -> usize {
+        match self {
+            Self::MerkleTree(tree) => tree.num_nodes(),
+            Self::SparseMerkleTree(tree) => tree.num_nodes(),
+            Self::MerklePathSet(set) => set.num_nodes(),
+            Self::MergedTree(tree) => tree.num_nodes(),
         }
     }
 
@@ -79,6 +116,7 @@ impl AdviceSet {
             Self::MerkleTree(tree) => tree.get_node(depth, index),
             Self::SparseMerkleTree(tree) => tree.get_node(depth, index),
             Self::MerklePathSet(set) => set.get_node(depth, index),
+            Self::MergedTree(tree) => tree.get_node(depth, index),
         }
     }
 
@@ -95,7 +133,24 @@ impl AdviceSet {
             Self::MerkleTree(tree) => tree.get_path(depth, index),
             Self::SparseMerkleTree(tree) => tree.get_path(depth, index),
             Self::MerklePathSet(set) => set.get_path(depth, index),
+            Self::MergedTree(tree) => tree.get_path(depth, index),
+        }
+    }
+
+    /// Returns the leaf at the specified index.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - The depth of the index is not equal to the depth of this advice set.
+    /// - This advice set does not contain a leaf at the specified index.
+    pub fn get_leaf<const DEPTH: u32>(
+        &self,
+        index: LeafIndex<DEPTH>,
+    ) -> Result<Word, AdviceSetError> {
+        if index.depth() != self.depth() {
+            return Err(AdviceSetError::InvalidDepth(self.depth(), index.depth()));
         }
+        self.get_node(DEPTH, index.value())
     }
 
     // DATA MUTATORS
@@ -112,6 +167,131 @@ impl AdviceSet {
             Self::MerkleTree(tree) => tree.update_leaf(index, value),
             Self::SparseMerkleTree(tree) => tree.update_leaf(index, value),
             Self::MerklePathSet(set) => set.update_leaf(index, value),
+            Self::MergedTree(tree) => tree.update_leaf(index, value),
+        }
+    }
+
+    /// Replaces the leaf at the specified index with the provided value.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - The depth of the index is not equal to the depth of this advice set.
+    /// - This advice set does not contain a leaf at the specified index.
+    pub fn set_leaf<const DEPTH: u32>(
+        &mut self,
+        index: LeafIndex<DEPTH>,
+        value: Word,
+    ) -> Result<(), AdviceSetError> {
+        if index.depth() != self.depth() {
+            return Err(AdviceSetError::InvalidDepth(self.depth(), index.depth()));
+        }
+        self.update_leaf(index.value(), value)
+    }
+}
+
+// SERIALIZATION
+// ================================================================================================
+
+impl Serializable for AdviceSet {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        match self {
+            Self::MerkleTree(tree) => {
+                target.write_u8(0);
+                tree.write_into(target);
+            }
+            Self::SparseMerkleTree(tree) => {
+                target.write_u8(1);
+                tree.write_into(target);
+            }
+            Self::MerklePathSet(set) => {
+                target.write_u8(2);
+                set.write_into(target);
+            }
+            Self::MergedTree(tree) => {
+                target.write_u8(3);
+                tree.write_into(target);
+            }
+        }
+    }
+}
+
+impl Deserializable for AdviceSet {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        match source.read_u8()? {
+            0 => MerkleTree::read_from(source).map(Self::MerkleTree),
+            1 => SparseMerkleTree::read_from(source).map(Self::SparseMerkleTree),
+            2 => MerklePathSet::read_from(source).map(Self::MerklePathSet),
+            3 => MergedTree::read_from(source).map(Self::MergedTree),
+            tag => Err(DeserializationError::InvalidValue(format!(
+                "{} is not a valid advice set type",
+                tag
+            ))),
+        }
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::{AdviceSet, Deserializable, Felt, MerklePathSet, Serializable, Word};
+    use crate::utils::SliceReader;
+
+    #[test]
+    fn advice_set_serialization() {
+        let leaves: Vec<Word> = (1..=4).map(|i| [Felt::new(i); 4]).collect();
+        let tree = AdviceSet::new_merkle_tree(leaves.clone()).unwrap();
+        let smt = AdviceSet::new_sparse_merkle_tree(vec![3, 7], leaves[..2].to_vec(), 3).unwrap();
+        let mut path_set = MerklePathSet::new(3).unwrap();
+        path_set
+            .add_path(1, leaves[1], tree.get_path(2, 1).unwrap())
+            .unwrap();
+        let path_set = AdviceSet::MerklePathSet(path_set);
+        let merged = AdviceSet::new_merged_tree(smt.clone(), path_set.clone()).unwrap();
+
+        for (set, depth, index) in [(tree, 2, 1), (smt, 3, 7), (path_set, 3, 1), (merged, 4, 9)] {
+            let bytes = set.to_bytes();
+            let result = AdviceSet::read_from(&mut SliceReader::new(&bytes)).unwrap();
+            assert_eq!(set.root(), result.root());
+            assert_eq!(set.depth(), result.depth());
+            assert_eq!(set.num_nodes(), result.num_nodes());
+            let node = set.get_node(depth, index).unwrap();
+            assert_eq!(node, result.get_node(depth, index).unwrap());
+            let path = set.get_path(depth, index).unwrap();
+            assert_eq!(path, result.get_path(depth, index).unwrap());
+            assert_eq!(bytes, result.to_bytes());
         }
     }
+
+    #[test]
+    fn merged_tree() {
+        let leaves: Vec<Word> = (1..=8).map(|i| [Felt::new(i); 4]).collect();
+        let tree = AdviceSet::new_merkle_tree(leaves.clone()).unwrap();
+        let left = AdviceSet::new_merkle_tree(leaves[..4].to_vec()).unwrap();
+        let right = AdviceSet::new_merkle_tree(leaves[4..].to_vec()).unwrap();
+
+        // merging two halves of a tree produces the same tree
+        let mut merged = AdviceSet::new_merged_tree(left.clone(), right).unwrap();
+        assert_eq!(tree.root(), merged.root());
+        assert_eq!(tree.depth(), merged.depth());
+        for depth in 1..=3 {
+            for index in 0..2u64.pow(depth) {
+                let node = tree.get_node(depth, index).unwrap();
+                assert_eq!(node, merged.get_node(depth, index).unwrap());
+                let path = tree.get_path(depth, index).unwrap();
+                assert_eq!(path, merged.get_path(depth, index).unwrap());
+            }
+        }
+
+        // updates of leaves are applied to the merged sets
+        let mut tree = tree;
+        tree.update_leaf(6, [Felt::new(9); 4]).unwrap();
+        merged.update_leaf(6, [Felt::new(9); 4]).unwrap();
+        assert_eq!(tree.root(), merged.root());
+        assert_eq!(tree.get_path(3, 6).unwrap(), merged.get_path(3, 6).unwrap());
+
+        // advice sets of different depths cannot be merged
+        assert!(AdviceSet::new_merged_tree(left, merged).is_err());
+    }
 }
@@ -1,9 +1,12 @@
 use super::{
     hasher::{self, Digest},
-    AdviceSetError, Word,
+    AdviceSetError, SmtRoot, Word,
+};
+use crate::utils::{
+    bytes::{read_seq, read_word, write_seq, write_word},
+    collections::{BTreeMap, Vec},
+    ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable,
 };
-use crate::utils::collections::BTreeMap;
-use crate::utils::collections::Vec;
 
 // SPARSE MERKLE TREE
 // ================================================================================================
@@ -35,8 +38,8 @@ impl SparseMerkleTree {
     }
 
     /// Returns the root of this Merkle tree.
-    pub fn root(&self) -> Word {
-        self.root
+    pub fn root(&self) -> SmtRoot {
+        SmtRoot::new(self.root)
     }
 
     /// Returns the depth of this Merkle tree.
@@ -44,6 +47,12 @@ impl SparseMerkleTree {
         self.depth
     }
 
+    /// Returns the number of nodes stored in this tree, i.e., the number of non-empty leaves and
+    /// the number of children of non-empty branch nodes.
+    pub fn num_nodes(&self) -> usize {
+        self.store.leaves.len() + 2 * self.store.branches.len()
+    }
+
     /// Returns a node at the specified key
     ///
     /// # Errors
@@ -217,6 +226,62 @@ impl Store {
     }
 }
 
+// SERIALIZATION
+// ================================================================================================
+
+impl Serializable for SparseMerkleTree {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        // hashes of empty subtrees are not written as they are determined by the depth of the tree
+        write_word(target, &self.root);
+        target.write_u32(self.depth);
+        write_seq(target, &self.store.leaves, |target, (key, node)| {
+            target.write_u64(*key);
+            write_word(target, node);
+        });
+        write_seq(
+            target,
+            &self.store.branches,
+            |target, ((key, depth), node)| {
+                target.write_u64(*key);
+                target.write_u32(*depth);
+                write_word(target, &node.left.into());
+                write_word(target, &node.right.into());
+            },
+        );
+    }
+}
+
+impl Deserializable for SparseMerkleTree {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let root = read_word(source)?;
+        let depth = source.read_u32()?;
+        if depth > 63 {
+            return Err(DeserializationError::InvalidValue(format!(
+                "{} is not a valid sparse Merkle tree depth",
+                depth
+            )));
+        }
+        let (mut store, _) = Store::new(depth);
+        for (key, node) in read_seq(source, |source| {
+            Ok((source.read_u64()?, read_word(source)?))
+        })? {
+            store.insert_leaf_node(key, node);
+        }
+        let branches = read_seq(source, |source| {
+            Ok((
+                source.read_u64()?,
+                source.read_u32()?,
+                read_word(source)?,
+                read_word(source)?,
+            ))
+        })?;
+        for (key, depth, left, right) in branches {
+            store.insert_branch_node(key, depth, left.into(), right.into());
+        }
+        Ok(Self { root, depth, store })
+    }
+}
+
 // TESTS
 // ================================================================================================
 
@@ -256,7 +321,7 @@ mod tests {
     fn build_empty_tree() {
         let smt = SparseMerkleTree::new(vec![], vec![], 3).unwrap();
         let mt = MerkleTree::new(ZERO_VALUES8.to_vec()).unwrap();
-        assert_eq!(mt.root(), smt.root());
+        assert_eq!(mt.root(), smt.root().as_word());
     }
 
     #[test]
@@ -271,7 +336,7 @@ mod tests {
         smt.insert_leaf(key, new_node)
             .expect("Failed to insert leaf");
         let mt2 = MerkleTree::new(values.clone()).unwrap();
-        assert_eq!(mt2.root(), smt.root());
+        assert_eq!(mt2.root(), smt.root().as_word());
         assert_eq!(mt2.get_path(3, 6).unwrap(), smt.get_path(3, 6).unwrap());
 
         // insert second value at distinct leaf branch
@@ -281,7 +346,7 @@ mod tests {
         smt.insert_leaf(key, new_node)
             .expect("Failed to insert leaf");
         let mt3 = MerkleTree::new(values).unwrap();
-        assert_eq!(mt3.root(), smt.root());
+        assert_eq!(mt3.root(), smt.root().as_word());
         assert_eq!(mt3.get_path(3, 2).unwrap(), smt.get_path(3, 2).unwrap());
     }
 
@@ -290,7 +355,7 @@ mod tests {
         let tree = super::SparseMerkleTree::new(KEYS4.to_vec(), VALUES4.to_vec(), 2).unwrap();
 
         let (root, node2, node3) = compute_internal_nodes();
-        assert_eq!(root, tree.root());
+        assert_eq!(root, tree.root().as_word());
         assert_eq!(node2, tree.get_node(1, 0).unwrap());
         assert_eq!(node3, tree.get_node(1, 1).unwrap());
     }
@@ -0,0 +1,258 @@
+use super::{AdviceSetError, Felt, Word};
+use crate::{
+    chiplets::hasher::{self, Digest},
+    errors::HexParseError,
+    utils::{
+        collections::Vec,
+        hex::{word_from_hex, word_to_hex},
+    },
+    StarkField,
+};
+use core::{fmt, str::FromStr};
+
+// SPARSE MERKLE TREE ROOT
+// ================================================================================================
+
+/// Root of a sparse Merkle tree.
+///
+/// Roots of different kinds of trees are all words, and thus can be easily confused with each
+/// other (or with any other word) by host code. Wrapping the root of a sparse Merkle tree into a
+/// distinct type makes such mix-ups compile-time errors. The root can be converted into a word
+/// where an untyped root is expected (e.g., when initializing the stack of a program).
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct SmtRoot(Word);
+
+impl SmtRoot {
+    /// Returns a root wrapping the specified word.
+    pub const fn new(root: Word) -> Self {
+        Self(root)
+    }
+
+    /// Returns the word of this root.
+    pub const fn as_word(&self) -> Word {
+        self.0
+    }
+}
+
+impl From<SmtRoot> for Word {
+    fn from(root: SmtRoot) -> Self {
+        root.0
+    }
+}
+
+impl fmt::Display for SmtRoot {
+    /// Writes the canonical hex encoding of this root.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", word_to_hex(self.0))
+    }
+}
+
+impl FromStr for SmtRoot {
+    type Err = HexParseError;
+
+    /// Parses a root from its canonical hex encoding.
+    fn from_str(hex: &str) -> Result<Self, Self::Err> {
+        word_from_hex(hex).map(Self)
+    }
+}
+
+// LEAF INDEX
+// ================================================================================================
+
+/// Index of a leaf in a Merkle tree of depth `DEPTH`.
+///
+/// An index can be constructed only if it is valid for a tree of the specified depth (i.e., if it
+/// is smaller than 2^DEPTH), and thus, it does not need to be validated again when a leaf is
+/// accessed. Depth must be between 1 and 63.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct LeafIndex<const DEPTH: u32>(u64);
+
+impl<const DEPTH: u32> LeafIndex<DEPTH> {
+    /// Returns a leaf index with the specified value.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - DEPTH is zero or greater than 63.
+    /// - The value is not smaller than 2^DEPTH.
+    pub fn new(value: u64) -> Result<Self, AdviceSetError> {
+        if DEPTH == 0 {
+            return Err(AdviceSetError::DepthTooSmall);
+        }
+        if DEPTH > 63 {
+            return Err(AdviceSetError::DepthTooBig(DEPTH));
+        }
+        if value >> DEPTH != 0 {
+            return Err(AdviceSetError::InvalidIndex(DEPTH, value));
+        }
+        Ok(Self(value))
+    }
+
+    /// Returns the depth of the tree this index refers to.
+    pub const fn depth(&self) -> u32 {
+        DEPTH
+    }
+
+    /// Returns the value of this index.
+    pub const fn value(&self) -> u64 {
+        self.0
+    }
+}
+
+impl<const DEPTH: u32> TryFrom<Felt> for LeafIndex<DEPTH> {
+    type Error = AdviceSetError;
+
+    fn try_from(value: Felt) -> Result<Self, Self::Error> {
+        Self::new(value.as_int())
+    }
+}
+
+impl<const DEPTH: u32> From<LeafIndex<DEPTH>> for Felt {
+    fn from(index: LeafIndex<DEPTH>) -> Self {
+        Felt::new(index.0)
+    }
+}
+
+// MMR PEAKS
+// ================================================================================================
+
+/// Peaks of a Merkle mountain range (MMR).
+///
+/// An MMR with `n` leaves consists of one perfect Merkle tree for every bit set in the binary
+/// representation of `n`; the peaks are the roots of these trees, ordered from the largest tree
+/// to the smallest one. Thus, the number of peaks must be equal to the number of bits set in the
+/// number of leaves.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MmrPeaks {
+    num_leaves: u64,
+    peaks: Vec<Word>,
+}
+
+impl MmrPeaks {
+    /// Returns the peaks of an MMR with the specified number of leaves.
+    ///
+    /// # Errors
+    /// Returns an error if the number of peaks is not equal to the number of bits set in the
+    /// number of leaves.
+    pub fn new(num_leaves: u64, peaks: Vec<Word>) -> Result<Self, AdviceSetError> {
+        if peaks.len() != num_leaves.count_ones() as usize {
+            return Err(AdviceSetError::InvalidNumPeaks(num_leaves, peaks.len()));
+        }
+        Ok(Self { num_leaves, peaks })
+    }
+
+    /// Returns the number of leaves in the MMR.
+    pub fn num_leaves(&self) -> u64 {
+        self.num_leaves
+    }
+
+    /// Returns the peaks of the MMR, ordered from the largest tree to the smallest one.
+    pub fn peaks(&self) -> &[Word] {
+        &self.peaks
+    }
+
+    /// Returns the hash of the peaks, i.e., the sequential hash of all elements of the peaks in
+    /// order. This commits to the whole MMR.
+    pub fn hash_peaks(&self) -> Digest {
+        let elements = self.peaks.iter().flatten().copied().collect::<Vec<_>>();
+        hasher::hash_elements(&elements)
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        super::AdviceSet, hasher, AdviceSetError, Felt, LeafIndex, MmrPeaks, SmtRoot, Word,
+    };
+    use crate::FieldElement;
+
+    #[test]
+    fn smt_root() {
+        let smt = AdviceSet::new_sparse_merkle_tree(vec![1, 5], vec![leaf(1), leaf(5)], 3).unwrap();
+        let root = smt.smt_root().unwrap();
+        assert_eq!(smt.root(), root.as_word());
+        assert_eq!(smt.root(), Word::from(root));
+
+        // roots can be parsed from their hex encoding
+        assert_eq!(Ok(root), root.to_string().parse::<SmtRoot>());
+        assert!("0x1234".parse::<SmtRoot>().is_err());
+
+        // roots of other advice sets are not sparse Merkle tree roots
+        let tree = AdviceSet::new_merkle_tree((0..4).map(leaf).collect()).unwrap();
+        assert!(tree.smt_root().is_none());
+    }
+
+    #[test]
+    fn leaf_index() {
+        // leaf indexes are validated against the depth of the tree
+        let index = LeafIndex::<2>::new(3).unwrap();
+        assert_eq!((2, 3), (index.depth(), index.value()));
+        assert_eq!(Felt::new(3), Felt::from(index));
+        assert!(matches!(
+            LeafIndex::<2>::new(4),
+            Err(AdviceSetError::InvalidIndex(2, 4))
+        ));
+        assert!(matches!(
+            LeafIndex::<0>::new(0),
+            Err(AdviceSetError::DepthTooSmall)
+        ));
+        assert!(matches!(
+            LeafIndex::<64>::new(0),
+            Err(AdviceSetError::DepthTooBig(64))
+        ));
+        assert!(LeafIndex::<2>::try_from(Felt::new(4)).is_err());
+    }
+
+    #[test]
+    fn leaf_index_access() {
+        let mut tree = AdviceSet::new_merkle_tree((0..4).map(leaf).collect()).unwrap();
+        let index = LeafIndex::<2>::new(1).unwrap();
+        assert_eq!(leaf(1), tree.get_leaf(index).unwrap());
+        tree.set_leaf(index, leaf(7)).unwrap();
+        assert_eq!(leaf(7), tree.get_leaf(index).unwrap());
+
+        // indexes of trees with other depths are rejected
+        assert!(matches!(
+            tree.get_leaf(LeafIndex::<3>::new(1).unwrap()),
+            Err(AdviceSetError::InvalidDepth(2, 3))
+        ));
+        assert!(tree
+            .set_leaf(LeafIndex::<1>::new(1).unwrap(), leaf(7))
+            .is_err());
+
+        let smt = AdviceSet::new_sparse_merkle_tree(vec![1, 5], vec![leaf(1), leaf(5)], 3).unwrap();
+        assert_eq!(
+            leaf(5),
+            smt.get_leaf(LeafIndex::<3>::new(5).unwrap()).unwrap()
+        );
+    }
+
+    #[test]
+    fn mmr_peaks() {
+        // the number of peaks must match the number of leaves
+        let peaks = MmrPeaks::new(5, vec![leaf(1), leaf(2)]).unwrap();
+        assert_eq!(5, peaks.num_leaves());
+        assert_eq!(&[leaf(1), leaf(2)], peaks.peaks());
+        assert!(matches!(
+            MmrPeaks::new(7, vec![leaf(1), leaf(2)]),
+            Err(AdviceSetError::InvalidNumPeaks(7, 2))
+        ));
+        assert!(MmrPeaks::new(0, Vec::new()).is_ok());
+
+        // the hash of the peaks is the sequential hash of their elements
+        let elements = [leaf(1), leaf(2)].concat();
+        assert_eq!(hasher::hash_elements(&elements), peaks.hash_peaks());
+        assert_ne!(
+            peaks.hash_peaks(),
+            MmrPeaks::new(6, vec![leaf(1), leaf(3)])
+                .unwrap()
+                .hash_peaks()
+        );
+    }
+
+    fn leaf(value: u64) -> Word {
+        [Felt::new(value), Felt::ZERO, Felt::ZERO, Felt::ZERO]
+    }
+}
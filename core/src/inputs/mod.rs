@@ -1,14 +1,20 @@
 use super::{
-    chiplets::hasher,
+    chiplets::hasher::{self, Digest},
     errors::{AdviceSetError, InputError},
     utils::IntoBytes,
-    Felt, FieldElement, Word, MIN_STACK_DEPTH,
+    Felt, FieldElement, StarkField, Word, MIN_STACK_DEPTH,
 };
 use core::convert::TryInto;
 use winter_utils::collections::{BTreeMap, Vec};
 
 mod advice;
-pub use advice::AdviceSet;
+pub use advice::{AdviceSet, LeafIndex, MmrPeaks, SmtRoot};
+
+// TYPE ALIASES
+// ================================================================================================
+
+/// Advice map keyed by the byte representations of the keys.
+pub type AdviceMap = BTreeMap<[u8; 32], Vec<Felt>>;
 
 // PROGRAM INPUTS
 // ================================================================================================
@@ -22,9 +28,11 @@ pub use advice::AdviceSet;
 ///    can receive initial value is limited to 16.
 /// 2. The program may request nondeterministic advice inputs from the prover. These inputs are
 ///    secret inputs. This means that the prover does not need to share them with the verifier.
-///    There are two types of advice inputs: (1) a single advice tape which can contain any number
-///    of elements and (2) a list of advice sets, which are used to provide nondeterministic
-///    inputs for instructions which work with Merkle trees.
+///    There are three types of advice inputs: (1) a single advice tape which can contain any
+///    number of elements, (2) a list of advice sets, which are used to provide nondeterministic
+///    inputs for instructions which work with Merkle trees, and (3) an advice map, which maps
+///    keys (words) to lists of elements which the program can request to be injected into the
+///    advice tape (see [ProgramInputs::with_advice_map()]).
 ///
 /// TODO: add more detailed explanation.
 #[derive(Clone, Debug)]
@@ -32,6 +40,7 @@ pub struct ProgramInputs {
     stack_init: Vec<Felt>,
     advice_tape: Vec<Felt>,
     advice_sets: BTreeMap<[u8; 32], AdviceSet>,
+    advice_map: AdviceMap,
 }
 
 impl ProgramInputs {
@@ -92,9 +101,45 @@ impl ProgramInputs {
             stack_init: init_stack_elements,
             advice_tape: advice_tape_elements,
             advice_sets: advice_map,
+            advice_map: BTreeMap::new(),
         })
     }
 
+    /// Returns [ProgramInputs] instantiated with the specified initial stack values, advice tape
+    /// values, and advice sets, provided that the advice inputs are within the specified limits.
+    ///
+    /// This constructor should be used when the inputs come from an untrusted party, so that
+    /// oversized advice inputs are rejected before the processor allocates memory for them.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - The advice tape is longer than allowed by the limits.
+    /// - The advice sets contain more nodes in total than allowed by the limits.
+    /// - Any of the conditions described in [ProgramInputs::new()] is not satisfied.
+    pub fn with_limits(
+        stack_init: &[u64],
+        advice_tape: &[u64],
+        advice_sets: Vec<AdviceSet>,
+        limits: &InputLimits,
+    ) -> Result<Self, InputError> {
+        if advice_tape.len() > limits.max_advice_tape_len() {
+            return Err(InputError::AdviceTapeTooLong(
+                limits.max_advice_tape_len(),
+                advice_tape.len(),
+            ));
+        }
+
+        let num_nodes = advice_sets.iter().map(AdviceSet::num_nodes).sum::<usize>();
+        if num_nodes > limits.max_advice_set_nodes() {
+            return Err(InputError::TooManyAdviceSetNodes(
+                limits.max_advice_set_nodes(),
+                num_nodes,
+            ));
+        }
+
+        Self::new(stack_init, advice_tape, advice_sets)
+    }
+
     /// Returns [ProgramInputs] initialized with stack inputs only.
     ///
     /// The provided inputs are pushed onto the stack one after the other. Thus, the first
@@ -116,9 +161,90 @@ impl ProgramInputs {
             stack_init: Vec::new(),
             advice_tape: Vec::new(),
             advice_sets: BTreeMap::new(),
+            advice_map: BTreeMap::new(),
         }
     }
 
+    /// Returns these [ProgramInputs] extended with the specified advice sets.
+    ///
+    /// # Errors
+    /// Returns an error if an advice set with the same root as any of the specified advice sets
+    /// is already present in these inputs or is specified more than once.
+    pub fn with_advice_sets(
+        mut self,
+        advice_sets: impl IntoIterator<Item = AdviceSet>,
+    ) -> Result<Self, InputError> {
+        for advice_set in advice_sets {
+            let key = advice_set.root().into_bytes();
+            if self.advice_sets.insert(key, advice_set).is_some() {
+                return Err(InputError::DuplicateAdviceRoot(key));
+            };
+        }
+        Ok(self)
+    }
+
+    /// Returns these [ProgramInputs] extended with the specified advice map entries.
+    ///
+    /// Values of an entry are injected into the advice tape when the program requests the entry by
+    /// its key (via the `adv.mapval` instruction). Since advice is not trusted, programs should
+    /// verify the values against a commitment; the `std::advice` module of the standard library
+    /// provides procedures which load values into memory and verify them in the same pass.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - Any of the values is not a valid field element.
+    /// - An entry with the same key as any of the specified entries is already present in these
+    ///   inputs or is specified more than once.
+    pub fn with_advice_map(
+        mut self,
+        entries: impl IntoIterator<Item = (Word, Vec<u64>)>,
+    ) -> Result<Self, InputError> {
+        for (key, values) in entries {
+            let values = values
+                .into_iter()
+                .map(|value| {
+                    if value < Felt::MODULUS {
+                        Ok(Felt::new(value))
+                    } else {
+                        Err(InputError::NotFieldElement(value, "advice map value"))
+                    }
+                })
+                .collect::<Result<Vec<Felt>, _>>()?;
+
+            let key = key.into_bytes();
+            if self.advice_map.insert(key, values).is_some() {
+                return Err(InputError::DuplicateAdviceMapKey(key));
+            }
+        }
+        Ok(self)
+    }
+
+    /// Returns these [ProgramInputs] extended with the peaks of the specified Merkle mountain
+    /// ranges.
+    ///
+    /// The peaks of every MMR are added to the advice map as an entry whose key is the hash of the
+    /// peaks (see [MmrPeaks::hash_peaks()]) and whose values are the elements of the peaks. Since
+    /// the key is also the commitment to the values, a program which knows the hash of the peaks
+    /// can load them via `std::advice::load_verified_map_value` with the hash used both as the key
+    /// and as the commitment.
+    ///
+    /// # Errors
+    /// Returns an error if an entry with the same key as any of the specified MMRs is already
+    /// present in these inputs or is specified more than once.
+    pub fn with_mmr_peaks(
+        mut self,
+        mmrs: impl IntoIterator<Item = MmrPeaks>,
+    ) -> Result<Self, InputError> {
+        for mmr in mmrs {
+            let key = Word::from(mmr.hash_peaks()).into_bytes();
+            let values = mmr.peaks().iter().flatten().copied().collect();
+            if self.advice_map.insert(key, values).is_some() {
+                return Err(InputError::DuplicateAdviceMapKey(key));
+            }
+        }
+        Ok(self)
+    }
+
     // PUBLIC ACCESSORS
     // --------------------------------------------------------------------------------------------
 
@@ -132,17 +258,237 @@ impl ProgramInputs {
         &self.advice_tape
     }
 
+    /// Returns a reference to the advice map.
+    pub fn advice_map(&self) -> &AdviceMap {
+        &self.advice_map
+    }
+
+    // COMMITMENTS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a commitment to the initial stack values.
+    ///
+    /// The commitment is a sequential hash of the initial stack values ordered from the top of
+    /// the stack down, i.e., in the same order as the values appear in the public inputs of
+    /// execution proofs. Thus, a party which knows the stack inputs of a program can compute the
+    /// commitment via [ProgramInputs::from_stack_inputs()] without executing the program.
+    pub fn stack_commitment(&self) -> Digest {
+        hasher::hash_elements(&self.stack_init)
+    }
+
+    /// Returns a commitment to the advice inputs (i.e., the advice tape, the advice sets, and the
+    /// advice map).
+    ///
+    /// The commitment is computed as hash(hash(T, S), M), where:
+    /// - T is a sequential hash of the advice tape values.
+    /// - S is a sequential hash of the roots of the advice sets, in ascending order of their
+    ///   bytes.
+    /// - M is a sequential hash of the advice map entries in ascending order of the bytes of
+    ///   their keys, where every entry contributes the 4 elements of its key followed by the 4
+    ///   elements of a sequential hash of its values.
+    ///
+    /// Advice inputs are not a part of the public inputs of execution proofs; the commitment
+    /// allows a party which supplies advice inputs to a prover to refer to them (e.g., by passing
+    /// the commitment as a stack input against which the program verifies the advice).
+    pub fn advice_commitment(&self) -> Digest {
+        let tape = hasher::hash_elements(&self.advice_tape);
+
+        let roots = self
+            .advice_sets
+            .values()
+            .flat_map(AdviceSet::root)
+            .collect::<Vec<_>>();
+        let sets = hasher::hash_elements(&roots);
+
+        let mut entries = Vec::with_capacity(self.advice_map.len() * 8);
+        for (key, values) in self.advice_map.iter() {
+            entries.extend(key.chunks(8).map(|chunk| {
+                Felt::new(u64::from_le_bytes(
+                    chunk.try_into().expect("invalid key chunk"),
+                ))
+            }));
+            entries.extend(<[Felt; 4]>::from(hasher::hash_elements(values)));
+        }
+        let map = hasher::hash_elements(&entries);
+
+        hasher::merge(&[hasher::merge(&[tape, sets]), map])
+    }
+
     // DESTRUCTURING
     // --------------------------------------------------------------------------------------------
 
     /// Decomposes these [ProgramInputs] into their raw components.
-    pub fn into_parts(self) -> (Vec<Felt>, Vec<Felt>, BTreeMap<[u8; 32], AdviceSet>) {
+    pub fn into_parts(
+        self,
+    ) -> (
+        Vec<Felt>,
+        Vec<Felt>,
+        BTreeMap<[u8; 32], AdviceSet>,
+        AdviceMap,
+    ) {
         let Self {
             stack_init,
             advice_tape,
             advice_sets,
+            advice_map,
         } = self;
 
-        (stack_init, advice_tape, advice_sets)
+        (stack_init, advice_tape, advice_sets, advice_map)
+    }
+}
+
+// INPUT LIMITS
+// ================================================================================================
+
+/// Limits on the size of advice inputs accepted via [ProgramInputs::with_limits()].
+///
+/// Advice inputs are not bound by the proof, and thus a party requesting a proof can supply
+/// advice inputs of any size. The limits allow a proving service to reject oversized inputs up
+/// front instead of running out of memory while processing them.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct InputLimits {
+    max_advice_tape_len: usize,
+    max_advice_set_nodes: usize,
+}
+
+impl InputLimits {
+    /// Default maximum number of elements on the advice tape.
+    pub const DEFAULT_MAX_ADVICE_TAPE_LEN: usize = 1 << 20;
+
+    /// Default maximum number of nodes in all advice sets combined.
+    pub const DEFAULT_MAX_ADVICE_SET_NODES: usize = 1 << 20;
+
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+    /// Returns new [InputLimits] with the specified maximum advice tape length and maximum number
+    /// of nodes in all advice sets combined.
+    pub fn new(max_advice_tape_len: usize, max_advice_set_nodes: usize) -> Self {
+        Self {
+            max_advice_tape_len,
+            max_advice_set_nodes,
+        }
+    }
+
+    /// Returns [InputLimits] which accept advice inputs of any size.
+    pub fn none() -> Self {
+        Self::new(usize::MAX, usize::MAX)
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the maximum number of elements on the advice tape.
+    pub fn max_advice_tape_len(&self) -> usize {
+        self.max_advice_tape_len
+    }
+
+    /// Returns the maximum number of nodes in all advice sets combined.
+    pub fn max_advice_set_nodes(&self) -> usize {
+        self.max_advice_set_nodes
+    }
+}
+
+impl Default for InputLimits {
+    fn default() -> Self {
+        Self::new(
+            Self::DEFAULT_MAX_ADVICE_TAPE_LEN,
+            Self::DEFAULT_MAX_ADVICE_SET_NODES,
+        )
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        hasher, AdviceSet, Felt, FieldElement, InputError, InputLimits, IntoBytes, MmrPeaks,
+        ProgramInputs, Word,
+    };
+
+    #[test]
+    fn inputs_with_limits() {
+        let leaves = (0..4).map(|i| [Felt::new(i), Felt::ZERO, Felt::ZERO, Felt::ZERO]);
+        let set = AdviceSet::new_merkle_tree(leaves.collect()).unwrap();
+        assert_eq!(7, set.num_nodes());
+
+        let limits = InputLimits::new(3, 7);
+        assert!(ProgramInputs::with_limits(&[1], &[1, 2, 3], vec![set.clone()], &limits).is_ok());
+        assert!(matches!(
+            ProgramInputs::with_limits(&[1], &[1, 2, 3, 4], vec![], &limits),
+            Err(InputError::AdviceTapeTooLong(3, 4))
+        ));
+
+        let limits = InputLimits::new(3, 6);
+        assert!(matches!(
+            ProgramInputs::with_limits(&[], &[], vec![set.clone()], &limits),
+            Err(InputError::TooManyAdviceSetNodes(6, 7))
+        ));
+        assert!(ProgramInputs::with_limits(&[], &[], vec![set], &InputLimits::none()).is_ok());
+    }
+
+    #[test]
+    fn input_commitments() {
+        let leaf = |i| [Felt::new(i), Felt::ZERO, Felt::ZERO, Felt::ZERO];
+        let set = AdviceSet::new_merkle_tree((0..4).map(leaf).collect()).unwrap();
+        let inputs = ProgramInputs::new(&[1, 2, 3], &[4, 5], vec![set.clone()])
+            .unwrap()
+            .with_advice_map([(leaf(1), vec![6, 7])])
+            .unwrap();
+
+        // the stack commitment is a hash of the stack values from the top of the stack down
+        let expected = hasher::hash_elements(&[Felt::new(3), Felt::new(2), Felt::new(1)]);
+        assert_eq!(expected, inputs.stack_commitment());
+        let stack_only = ProgramInputs::from_stack_inputs(&[1, 2, 3]).unwrap();
+        assert_eq!(expected, stack_only.stack_commitment());
+
+        // the advice commitment binds every kind of advice inputs, but not the stack values
+        assert_eq!(
+            inputs.advice_commitment(),
+            ProgramInputs::new(&[], &[4, 5], vec![set.clone()])
+                .unwrap()
+                .with_advice_map([(leaf(1), vec![6, 7])])
+                .unwrap()
+                .advice_commitment()
+        );
+        let other_inputs = [
+            ProgramInputs::new(&[], &[4, 5], vec![set.clone()]).unwrap(),
+            ProgramInputs::new(&[], &[5, 4], vec![set.clone()])
+                .unwrap()
+                .with_advice_map([(leaf(1), vec![6, 7])])
+                .unwrap(),
+            ProgramInputs::new(&[], &[4, 5], vec![])
+                .unwrap()
+                .with_advice_map([(leaf(1), vec![6, 7])])
+                .unwrap(),
+            ProgramInputs::new(&[], &[4, 5], vec![set])
+                .unwrap()
+                .with_advice_map([(leaf(2), vec![6, 7])])
+                .unwrap(),
+        ];
+        for other in other_inputs {
+            assert_ne!(inputs.advice_commitment(), other.advice_commitment());
+        }
+    }
+
+    #[test]
+    fn inputs_with_mmr_peaks() {
+        let leaf = |i| [Felt::new(i), Felt::ZERO, Felt::ZERO, Felt::ZERO];
+        let peaks = MmrPeaks::new(3, vec![leaf(1), leaf(2)]).unwrap();
+        let inputs = ProgramInputs::none()
+            .with_mmr_peaks([peaks.clone()])
+            .unwrap();
+
+        // the peaks are keyed by their hash
+        let key = Word::from(peaks.hash_peaks()).into_bytes();
+        let values = inputs.advice_map().get(&key).unwrap();
+        assert_eq!(&[leaf(1), leaf(2)].concat(), values);
+        assert_eq!(peaks.hash_peaks(), hasher::hash_elements(values));
+
+        assert!(matches!(
+            inputs.with_mmr_peaks([peaks]),
+            Err(InputError::DuplicateAdviceMapKey(duplicate)) if duplicate == key
+        ));
     }
 }
@@ -13,20 +13,32 @@ pub mod range;
 
 pub use math::{fields::f64::BaseElement as Felt, ExtensionOf, FieldElement, StarkField};
 
+/// Element of the quadratic extension of the base field defined by the irreducible polynomial
+/// x^2 - x + 2.
+pub type QuadFelt = math::fields::QuadExtension<Felt>;
+
 mod program;
-pub use program::{blocks as code_blocks, Library, Program};
+pub use program::{
+    blocks as code_blocks, CodeBlockTable, DuplicateBlock, DuplicationReport, InstructionInfo,
+    Kernel, Library, MaslLibrary, MastHasher, ModuleParser, ProcedureInfo, ProcedureSignature,
+    Program, ProgramBuilder, SourceMap, SpanSourceMap, MASL_VERSION, SOURCE_MAP_VERSION,
+};
 
 mod operations;
 pub use operations::{
-    AdviceInjector, AssemblyOp, Decorator, DecoratorIterator, DecoratorList, Operation,
+    AdviceInjector, AssemblyOp, DebugOptions, Decorator, DecoratorIterator, DecoratorList,
+    Operation, SourceLocation,
 };
 
 mod inputs;
-pub use inputs::{AdviceSet, ProgramInputs};
+pub use inputs::{AdviceMap, AdviceSet, InputLimits, LeafIndex, MmrPeaks, ProgramInputs, SmtRoot};
 
 pub mod utils;
 use utils::range;
 
+#[cfg(feature = "testing")]
+pub mod testing;
+
 // TYPE ALIASES
 // ================================================================================================
 
@@ -57,15 +69,16 @@ pub const NUM_STACK_HELPER_COLS: usize = 3;
 // ------------------------------------------------------------------------------------------------
 
 //      system          decoder           stack      range checks       chiplets
-//    (2 columns)     (23 columns)    (19 columns)    (4 columns)     (18 columns)
+//    (3 columns)     (23 columns)    (19 columns)    (4 columns)     (18 columns)
 // ├───────────────┴───────────────┴───────────────┴───────────────┴─────────────────┤
 
 pub const SYS_TRACE_OFFSET: usize = 0;
-pub const SYS_TRACE_WIDTH: usize = 2;
+pub const SYS_TRACE_WIDTH: usize = 3;
 pub const SYS_TRACE_RANGE: Range<usize> = range(SYS_TRACE_OFFSET, SYS_TRACE_WIDTH);
 
 pub const CLK_COL_IDX: usize = SYS_TRACE_OFFSET;
 pub const FMP_COL_IDX: usize = SYS_TRACE_OFFSET + 1;
+pub const CTX_COL_IDX: usize = SYS_TRACE_OFFSET + 2;
 
 // decoder trace
 pub const DECODER_TRACE_OFFSET: usize = SYS_TRACE_OFFSET + SYS_TRACE_WIDTH;
@@ -10,6 +10,16 @@ pub enum AdviceInjector {
     /// - root of the tree, 4 elements
     MerkleNode,
 
+    /// Creates a new Merkle tree in the advice provider by merging the Merkle trees specified by
+    /// the roots on the stack; the left and the right subtrees of the root of the new tree are
+    /// the merged trees. The stack is expected to be arranged as follows (from the top):
+    /// - root of the right tree, 4 elements
+    /// - root of the left tree, 4 elements
+    ///
+    /// The root of the new tree is the hash of the roots of the merged trees, i.e., the result of
+    /// the `rphash` instruction applied to the top two words of the stack.
+    MerkleMerge,
+
     /// Injects the result of u64 division (both the quotient and the remainder) at the head of
     /// the advice tape. The stack is expected to be arranged as follows (from the top):
     /// - divisor split into two 32-bit elements
@@ -18,13 +28,38 @@ pub enum AdviceInjector {
     /// The result is injected into the advice tape as follows: first the remainder is injected,
     /// then the quotient is injected.
     DivResultU64,
+
+    /// Injects the amount of gas consumed by the program so far at the head of the advice tape.
+    /// If the program is executed without gas metering, ZERO is injected.
+    GasUsed,
+
+    /// Injects the values of the advice map entry with the key specified by the word at the top
+    /// of the stack into the advice tape. The values are preceded by their number and padded
+    /// with ZEROs to the next multiple of 8, so that they are read from the advice tape as
+    /// follows: first the number of values `n`, then the values in the order in which they were
+    /// specified, then the padding.
+    MapValue,
+
+    /// Injects the multiplicative inverse of the element of the quadratic extension field at the
+    /// top of the stack at the head of the advice tape. The stack is expected to be arranged as
+    /// follows (from the top):
+    /// - the coefficient a1 of the element a = a0 + a1 * x, 1 element
+    /// - the coefficient a0 of the element a = a0 + a1 * x, 1 element
+    ///
+    /// The inverse b = b0 + b1 * x is injected so that b0 is read from the advice tape first, and
+    /// then b1 is read.
+    Ext2Inv,
 }
 
 impl fmt::Display for AdviceInjector {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Self::MerkleNode => write!(f, "merkle_node"),
+            Self::MerkleMerge => write!(f, "merkle_merge"),
             Self::DivResultU64 => write!(f, "div_result_u64"),
+            Self::GasUsed => write!(f, "gas_used"),
+            Self::MapValue => write!(f, "map_value"),
+            Self::Ext2Inv => write!(f, "ext2_inv"),
         }
     }
 }
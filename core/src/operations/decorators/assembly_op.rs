@@ -1,4 +1,9 @@
-use crate::utils::string::String;
+use crate::utils::{
+    bytes::{read_option, read_string, write_option, write_string},
+    string::String,
+    ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable,
+};
+use core::fmt;
 
 // ASSEMBLY OP
 // ================================================================================================
@@ -8,13 +13,24 @@ use crate::utils::string::String;
 pub struct AssemblyOp {
     op: String,
     num_cycles: u8,
+    location: Option<SourceLocation>,
 }
 
 impl AssemblyOp {
     /// Returns [AssemblyOp] instantiated with the specified assembly instruction string and number
     /// of cycles it takes to execute the assembly instruction.
     pub fn new(op: String, num_cycles: u8) -> Self {
-        Self { op, num_cycles }
+        Self {
+            op,
+            num_cycles,
+            location: None,
+        }
+    }
+
+    /// Sets the location in the source code of the assembly instruction of this decorator.
+    pub fn with_location(mut self, location: SourceLocation) -> Self {
+        self.location = Some(location);
+        self
     }
 
     /// Returns the assembly instruction corresponding to this decorator.
@@ -27,6 +43,12 @@ impl AssemblyOp {
         self.num_cycles
     }
 
+    /// Returns the location in the source code of the assembly instruction of this decorator, if
+    /// known.
+    pub fn location(&self) -> Option<&SourceLocation> {
+        self.location.as_ref()
+    }
+
     // STATE MUTATORS
     // --------------------------------------------------------------------------------------------
 
@@ -35,3 +57,99 @@ impl AssemblyOp {
         self.num_cycles = num_cycles;
     }
 }
+
+// SERIALIZATION
+// ================================================================================================
+
+impl Serializable for AssemblyOp {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        write_string(target, &self.op);
+        target.write_u8(self.num_cycles);
+        write_option(target, self.location.as_ref(), |target, location| {
+            location.write_into(target)
+        });
+    }
+}
+
+impl Deserializable for AssemblyOp {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let op = read_string(source)?;
+        let num_cycles = source.read_u8()?;
+        let location = read_option(source, SourceLocation::read_from)?;
+        Ok(Self {
+            op,
+            num_cycles,
+            location,
+        })
+    }
+}
+
+// SOURCE LOCATION
+// ================================================================================================
+
+/// Location of an assembly instruction in the source code from which it was compiled.
+///
+/// The source code is identified by the path of the library module containing the instruction
+/// (e.g., `std::math::u64`); the path is empty for instructions of the program itself, and of the
+/// kernel it was compiled against. Lines and columns are 1-based, and columns are measured in
+/// characters.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SourceLocation {
+    module: String,
+    line: u32,
+    column: u32,
+}
+
+impl SourceLocation {
+    /// Returns a new [SourceLocation] instantiated with the specified module path, line, and
+    /// column.
+    pub fn new(module: String, line: u32, column: u32) -> Self {
+        Self {
+            module,
+            line,
+            column,
+        }
+    }
+
+    /// Returns the path of the library module containing the instruction, or an empty string if
+    /// the instruction is a part of the program.
+    pub fn module(&self) -> &str {
+        &self.module
+    }
+
+    /// Returns the line of the instruction.
+    pub fn line(&self) -> u32 {
+        self.line
+    }
+
+    /// Returns the column at which the instruction starts.
+    pub fn column(&self) -> u32 {
+        self.column
+    }
+}
+
+impl fmt::Display for SourceLocation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if !self.module.is_empty() {
+            write!(f, "{}:", self.module)?;
+        }
+        write!(f, "{}:{}", self.line, self.column)
+    }
+}
+
+impl Serializable for SourceLocation {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        write_string(target, &self.module);
+        target.write_u32(self.line);
+        target.write_u32(self.column);
+    }
+}
+
+impl Deserializable for SourceLocation {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let module = read_string(source)?;
+        let line = source.read_u32()?;
+        let column = source.read_u32()?;
+        Ok(Self::new(module, line, column))
+    }
+}
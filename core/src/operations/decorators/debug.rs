@@ -0,0 +1,37 @@
+use core::fmt;
+
+// DEBUG OPTIONS
+// ================================================================================================
+
+/// Describes the part of the VM state to be reported by a debug decorator.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum DebugOptions {
+    /// Reports the entire stack, including its overflow table.
+    StackAll,
+
+    /// Reports the specified number of items at the top of the stack.
+    StackTop(u16),
+
+    /// Reports all memory addresses of the current context which have been accessed so far.
+    MemAll,
+
+    /// Reports the memory addresses of the current context within the specified inclusive
+    /// interval which have been accessed so far.
+    MemInterval(u64, u64),
+
+    /// Reports the locals of the current procedure with indexes within the specified inclusive
+    /// interval.
+    LocalInterval(u32, u32),
+}
+
+impl fmt::Display for DebugOptions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::StackAll => write!(f, "stack"),
+            Self::StackTop(n) => write!(f, "stack.{}", n),
+            Self::MemAll => write!(f, "mem"),
+            Self::MemInterval(start, end) => write!(f, "mem.{}.{}", start, end),
+            Self::LocalInterval(start, end) => write!(f, "local.{}.{}", start, end),
+        }
+    }
+}
@@ -1,9 +1,17 @@
 mod advice;
 mod assembly_op;
-use crate::utils::collections::Vec;
+mod debug;
+use crate::{
+    utils::{
+        collections::Vec,
+        string::{String, ToString},
+    },
+    Felt,
+};
 pub use advice::AdviceInjector;
-pub use assembly_op::AssemblyOp;
+pub use assembly_op::{AssemblyOp, SourceLocation};
 use core::fmt;
+pub use debug::DebugOptions;
 
 // DECORATORS
 // ================================================================================================
@@ -17,6 +25,27 @@ pub enum Decorator {
     /// Adds information about the assembly instruction at a particular index
     /// (only applicable in debug mode)
     AsmOp(AssemblyOp),
+    /// Terminates execution with the specified message. The decorator always precedes operations
+    /// which make execution fail (e.g., a failing assertion); thus, the message is not needed to
+    /// prove that execution fails, and is not a part of the MAST.
+    Panic(String),
+    /// Reports a part of the VM state (e.g., items at the top of the stack) as specified by the
+    /// options. This decorator is emitted only by the assembler in debug mode, and is executed
+    /// only when the program is executed in debug mode; it has no effect on the VM state.
+    Debug(DebugOptions),
+    /// Names the chain of procedures inlined via `exec` to which the following operations of the
+    /// span belong, starting with the outermost procedure of the current call context. An empty
+    /// chain denotes the code of the program or of a called procedure itself. This decorator is
+    /// emitted only by the assembler in debug mode, and is used to report stack traces.
+    ProcPath(Vec<String>),
+    /// Notifies the host (i.e., the advice provider) that the event with the specified ID has
+    /// occurred. The host may react to the event, e.g., by writing values into the advice tape;
+    /// otherwise, the decorator has no effect on the VM state.
+    Event(u32),
+    /// Makes execution fail if the top of the stack does not consist of the specified values,
+    /// listed starting with the top of the stack. This decorator is emitted only by the assembler
+    /// in debug mode, and is intended for testing; it has no effect on the VM state.
+    ExpectStack(Vec<Felt>),
 }
 
 impl fmt::Display for Decorator {
@@ -31,6 +60,17 @@ impl fmt::Display for Decorator {
                     assembly_op.num_cycles()
                 )
             }
+            Self::Panic(message) => write!(f, "panic(\"{}\")", message),
+            Self::Debug(options) => write!(f, "debug({})", options),
+            Self::ProcPath(procs) => write!(f, "procPath({})", procs.join(", ")),
+            Self::Event(event_id) => write!(f, "event({})", event_id),
+            Self::ExpectStack(values) => {
+                let values = values
+                    .iter()
+                    .map(|value| value.to_string())
+                    .collect::<Vec<_>>();
+                write!(f, "expectStack({})", values.join(", "))
+            }
         }
     }
 }
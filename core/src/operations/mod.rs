@@ -1,7 +1,16 @@
-use super::Felt;
+use super::{
+    utils::{
+        bytes::{read_bool, write_bool},
+        ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable,
+    },
+    Felt,
+};
 use core::fmt;
 mod decorators;
-pub use decorators::{AdviceInjector, AssemblyOp, Decorator, DecoratorIterator, DecoratorList};
+pub use decorators::{
+    AdviceInjector, AssemblyOp, DebugOptions, Decorator, DecoratorIterator, DecoratorList,
+    SourceLocation,
+};
 
 // OPERATIONS
 // ================================================================================================
@@ -13,8 +22,12 @@ pub enum Operation {
     /// Advances cycle counter, but does not change the state of user stack.
     Noop,
 
-    /// Pops the stack; if the popped value is not 1, execution fails.
-    Assert,
+    /// Pops the stack; if the popped value is not 1, execution fails with the specified error
+    /// code.
+    ///
+    /// The error code is not a part of the encoding of the operation in the MAST, and thus it
+    /// does not affect the hash of the program.
+    Assert(u32),
 
     /// Pops an element off the stack, adds the current value of the `fmp` register to it, and
     /// pushes the result back onto the stack.
@@ -23,6 +36,16 @@ pub enum Operation {
     /// Pops an element off the stack and adds it to the current value of `fmp` register.
     FmpUpdate,
 
+    /// Overwrites the top four stack items with the hash of the caller, i.e., with the MAST root
+    /// of the procedure which created the context from which the currently executing system call
+    /// was made. The hash of the root context is all ZEROs. Can be executed only in the kernel
+    /// context.
+    Caller,
+
+    /// Pushes the current value of the clock cycle (i.e., the cycle at which this operation is
+    /// executed) onto the stack.
+    Clk,
+
     // ----- flow control operations --------------------------------------------------------------
     /// Marks the beginning of a join block.
     Join,
@@ -33,6 +56,17 @@ pub enum Operation {
     /// Marks the beginning of a loop block.
     Loop,
 
+    /// Marks the beginning of a dyn block, i.e., of a procedure invoked dynamically by its MAST
+    /// root.
+    Dyn,
+
+    /// Marks the beginning of a call block, i.e., of a call to a procedure executed in a new
+    /// memory context.
+    Call,
+
+    /// Marks the beginning of a syscall block, i.e., of a call to a kernel procedure.
+    SysCall,
+
     /// Marks the beginning of a span code block.
     Span,
 
@@ -365,7 +399,7 @@ impl Operation {
     /// - 11xxx--: operations where constraint degree can be up to 5. These include control flow
     ///   operations and some other operations requiring very high degree constraints.
     #[rustfmt::skip]
-    pub fn op_code(&self) -> u8 {
+    pub const fn op_code(&self) -> u8 {
         match self {
             Self::Noop      => 0b0000_0000,
             Self::Eqz       => 0b0000_0001,
@@ -401,7 +435,7 @@ impl Operation {
             Self::SwapDW    => 0b0001_1110,
             // <empty>      => 0b0001_1111
 
-            Self::Assert    => 0b0010_0000,
+            Self::Assert(_) => 0b0010_0000,
             Self::Eq        => 0b0010_0001,
             Self::Add       => 0b0010_0010,
             Self::Mul       => 0b0010_0011,
@@ -433,7 +467,7 @@ impl Operation {
             Self::Dup15     => 0b0011_1100,
             Self::Read      => 0b0011_1101,
             Self::SDepth    => 0b0011_1110,
-            // <empty>      => 0b0011_1111
+            Self::Caller    => 0b0011_1111,
 
             Self::U32add    => 0b0100_0000,
             Self::U32sub    => 0b0100_0010,
@@ -446,8 +480,8 @@ impl Operation {
 
             Self::RpPerm    => 0b0101_0000,
             Self::MpVerify  => 0b0101_0010,
-            // <empty>      => 0b0101_0100
-            // <empty>      => 0b0101_0110
+            Self::SysCall   => 0b0101_0100,
+            Self::Dyn       => 0b0101_0110,
             Self::Span      => 0b0101_1000,
             Self::Join      => 0b0101_1010,
             Self::Split     => 0b0101_1100,
@@ -455,8 +489,8 @@ impl Operation {
 
             Self::MrUpdate(_) => 0b0110_0000,
             Self::Push(_)   => 0b0110_0100,
-            // <empty>      => 0b0110_1000
-            // <empty>      => 0b0110_1100
+            Self::Call      => 0b0110_1000,
+            Self::Clk       => 0b0110_1100,
             Self::End       => 0b0111_0000,
             Self::Repeat    => 0b0111_0100,
             Self::Respan    => 0b0111_1000,
@@ -480,6 +514,9 @@ impl Operation {
                 | Self::Join
                 | Self::Split
                 | Self::Loop
+                | Self::Dyn
+                | Self::Call
+                | Self::SysCall
                 | Self::Repeat
                 | Self::Respan
                 | Self::Span
@@ -493,15 +530,21 @@ impl fmt::Display for Operation {
         match self {
             // ----- system operations ------------------------------------------------------------
             Self::Noop => write!(f, "noop"),
-            Self::Assert => write!(f, "assert"),
+            Self::Assert(0) => write!(f, "assert"),
+            Self::Assert(err_code) => write!(f, "assert({})", err_code),
 
             Self::FmpAdd => write!(f, "fmpadd"),
             Self::FmpUpdate => write!(f, "fmpupdate"),
+            Self::Caller => write!(f, "caller"),
+            Self::Clk => write!(f, "clk"),
 
             // ----- flow control operations ------------------------------------------------------
             Self::Join => write!(f, "join"),
             Self::Split => write!(f, "split"),
             Self::Loop => write!(f, "loop"),
+            Self::Dyn => write!(f, "dyn"),
+            Self::Call => write!(f, "call"),
+            Self::SysCall => write!(f, "syscall"),
             Self::Repeat => write!(f, "repeat"),
             Self::Span => write!(f, "span"),
             Self::Respan => write!(f, "respan"),
@@ -605,3 +648,118 @@ impl fmt::Display for Operation {
         }
     }
 }
+
+// SERIALIZATION
+// ================================================================================================
+
+impl Serializable for Operation {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_u8(self.op_code());
+        match self {
+            Self::Assert(err_code) => target.write_u32(*err_code),
+            Self::MrUpdate(copy) => write_bool(target, *copy),
+            Self::Push(imm) => imm.write_into(target),
+            _ => (),
+        }
+    }
+}
+
+impl Deserializable for Operation {
+    #[rustfmt::skip]
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let op_code = source.read_u8()?;
+        let op = match op_code {
+            0b0000_0000 => Self::Noop,
+            0b0000_0001 => Self::Eqz,
+            0b0000_0010 => Self::Neg,
+            0b0000_0011 => Self::Inv,
+            0b0000_0100 => Self::Incr,
+            0b0000_0101 => Self::Not,
+            0b0000_0110 => Self::FmpAdd,
+            0b0000_0111 => Self::MLoad,
+            0b0000_1000 => Self::Swap,
+            0b0000_1010 => Self::MovUp2,
+            0b0000_1011 => Self::MovDn2,
+            0b0000_1100 => Self::MovUp3,
+            0b0000_1101 => Self::MovDn3,
+            0b0000_1110 => Self::ReadW,
+            0b0001_0000 => Self::MovUp4,
+            0b0001_0001 => Self::MovDn4,
+            0b0001_0010 => Self::MovUp5,
+            0b0001_0011 => Self::MovDn5,
+            0b0001_0100 => Self::MovUp6,
+            0b0001_0101 => Self::MovDn6,
+            0b0001_0110 => Self::MovUp7,
+            0b0001_0111 => Self::MovDn7,
+            0b0001_1000 => Self::SwapW,
+            0b0001_1010 => Self::MovUp8,
+            0b0001_1011 => Self::MovDn8,
+            0b0001_1100 => Self::SwapW2,
+            0b0001_1101 => Self::SwapW3,
+            0b0001_1110 => Self::SwapDW,
+            0b0010_0000 => Self::Assert(source.read_u32()?),
+            0b0010_0001 => Self::Eq,
+            0b0010_0010 => Self::Add,
+            0b0010_0011 => Self::Mul,
+            0b0010_0100 => Self::And,
+            0b0010_0101 => Self::Or,
+            0b0010_0110 => Self::U32and,
+            0b0010_0111 => Self::U32or,
+            0b0010_1000 => Self::U32xor,
+            0b0010_1001 => Self::Drop,
+            0b0010_1010 => Self::CSwap,
+            0b0010_1011 => Self::CSwapW,
+            0b0010_1100 => Self::MLoadW,
+            0b0010_1101 => Self::MStore,
+            0b0010_1110 => Self::MStoreW,
+            0b0010_1111 => Self::FmpUpdate,
+            0b0011_0000 => Self::Pad,
+            0b0011_0001 => Self::Dup0,
+            0b0011_0010 => Self::Dup1,
+            0b0011_0011 => Self::Dup2,
+            0b0011_0100 => Self::Dup3,
+            0b0011_0101 => Self::Dup4,
+            0b0011_0110 => Self::Dup5,
+            0b0011_0111 => Self::Dup6,
+            0b0011_1000 => Self::Dup7,
+            0b0011_1001 => Self::Dup9,
+            0b0011_1010 => Self::Dup11,
+            0b0011_1011 => Self::Dup13,
+            0b0011_1100 => Self::Dup15,
+            0b0011_1101 => Self::Read,
+            0b0011_1110 => Self::SDepth,
+            0b0011_1111 => Self::Caller,
+            0b0100_0000 => Self::U32add,
+            0b0100_0010 => Self::U32sub,
+            0b0100_0100 => Self::U32mul,
+            0b0100_0110 => Self::U32div,
+            0b0100_1000 => Self::U32split,
+            0b0100_1010 => Self::U32assert2,
+            0b0100_1100 => Self::U32add3,
+            0b0100_1110 => Self::U32madd,
+            0b0101_0000 => Self::RpPerm,
+            0b0101_0010 => Self::MpVerify,
+            0b0101_0100 => Self::SysCall,
+            0b0101_0110 => Self::Dyn,
+            0b0101_1000 => Self::Span,
+            0b0101_1010 => Self::Join,
+            0b0101_1100 => Self::Split,
+            0b0101_1110 => Self::Loop,
+            0b0110_1000 => Self::Call,
+            0b0110_1100 => Self::Clk,
+            0b0111_0000 => Self::End,
+            0b0111_0100 => Self::Repeat,
+            0b0111_1000 => Self::Respan,
+            0b0111_1100 => Self::Halt,
+            0b0110_0000 => Self::MrUpdate(read_bool(source)?),
+            0b0110_0100 => Self::Push(Felt::read_from(source)?),
+            _ => {
+                return Err(DeserializationError::InvalidValue(format!(
+                    "{} is not a valid opcode",
+                    op_code
+                )))
+            }
+        };
+        Ok(op)
+    }
+}
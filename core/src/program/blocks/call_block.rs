@@ -1,29 +1,69 @@
-use super::{fmt, hasher, Digest};
+use super::{fmt, hasher::Hasher, mast_hasher::call_hash, Digest, Felt, FieldElement, Operation};
+use crate::Word;
 
 // CALL BLOCK
 // ================================================================================================
 /// A code block describing a function call.
 ///
-/// When the VM executes a Call block, it simply executes the code of the underlying function.
-/// Thus, to execute a function call, the VM must have access to the function's body, otherwise,
-/// the execution fails.
+/// When the VM executes a Call block, it executes the code of the underlying function in a new
+/// memory context, i.e., the function cannot access the memory of the caller. Thus, to execute a
+/// function call, the VM must have access to the function's body, otherwise, the execution fails.
+///
+/// A Call block can also describe a system call, i.e., a call to a procedure of the kernel
+/// against which the program was compiled. The kernel procedure is executed in the kernel
+/// context, which has memory separate from the memory of the program.
 ///
 /// Hash of a Call block is computed by hashing a concatenation of the function's body hash with
-/// zero.
-/// TODO: update hashing methodology to make it different from Loop block.
+/// a word encoding the opcode of the CALL operation. Hash of a system call block is computed in
+/// the same way, but using the opcode of the SYSCALL operation.
 #[derive(Clone, Debug)]
 pub struct Call {
     hash: Digest,
     fn_hash: Digest,
+    is_syscall: bool,
 }
 
 impl Call {
-    // CONSTRUCTOR
+    // CONSTANTS
+    // --------------------------------------------------------------------------------------------
+    /// The word which is hashed together with the function's body hash to compute the hash of a
+    /// call block.
+    pub const CALL_DOMAIN: Word = [
+        Felt::new(Operation::Call.op_code() as u64),
+        Felt::ZERO,
+        Felt::ZERO,
+        Felt::ZERO,
+    ];
+
+    /// The word which is hashed together with the function's body hash to compute the hash of a
+    /// system call block.
+    pub const SYSCALL_DOMAIN: Word = [
+        Felt::new(Operation::SysCall.op_code() as u64),
+        Felt::ZERO,
+        Felt::ZERO,
+        Felt::ZERO,
+    ];
+
+    // CONSTRUCTORS
     // --------------------------------------------------------------------------------------------
     /// Returns a new [Call] block instantiated with the specified function body hash.
     pub fn new(fn_hash: Digest) -> Self {
-        let hash = hasher::merge(&[fn_hash, Digest::default()]);
-        Self { hash, fn_hash }
+        let hash = call_hash::<Hasher>(fn_hash, false);
+        Self {
+            hash,
+            fn_hash,
+            is_syscall: false,
+        }
+    }
+
+    /// Returns a new [Call] block instantiated with the specified kernel procedure body hash.
+    pub fn new_syscall(fn_hash: Digest) -> Self {
+        let hash = call_hash::<Hasher>(fn_hash, true);
+        Self {
+            hash,
+            fn_hash,
+            is_syscall: true,
+        }
     }
 
     // PUBLIC ACCESSORS
@@ -38,10 +78,19 @@ impl Call {
     pub fn fn_hash(&self) -> Digest {
         self.fn_hash
     }
+
+    /// Returns true if this block describes a call to a kernel procedure.
+    pub fn is_syscall(&self) -> bool {
+        self.is_syscall
+    }
 }
 
 impl fmt::Display for Call {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "call.{:?}", self.fn_hash) // TODO
+        if self.is_syscall {
+            write!(f, "syscall.{:?}", self.fn_hash)
+        } else {
+            write!(f, "call.{:?}", self.fn_hash)
+        }
     }
 }
@@ -0,0 +1,59 @@
+use super::{fmt, hasher::Hasher, mast_hasher::dyn_hash, Digest, Felt, FieldElement, Operation};
+use crate::Word;
+
+// DYN BLOCK
+// ================================================================================================
+/// A code block describing a dynamic procedure invocation.
+///
+/// When the VM executes a Dyn block, it reads the MAST root of the procedure to be executed from
+/// the top word of the stack, and then executes the code block with this root. The stack is not
+/// modified by the Dyn block itself, and thus the executed procedure finds the root at the top of
+/// the stack. The code of the procedure is not a part of the program, and must be supplied to the
+/// VM at runtime; otherwise, the execution fails.
+///
+/// Hash of a Dyn block does not depend on the executed procedure: it is computed by hashing a
+/// concatenation of zero with a word encoding the opcode of the DYN operation.
+#[derive(Clone, Debug)]
+pub struct Dyn {
+    hash: Digest,
+}
+
+impl Dyn {
+    // CONSTANTS
+    // --------------------------------------------------------------------------------------------
+    /// The word which is hashed together with zero to compute the hash of a Dyn block.
+    pub const DOMAIN: Word = [
+        Felt::new(Operation::Dyn.op_code() as u64),
+        Felt::ZERO,
+        Felt::ZERO,
+        Felt::ZERO,
+    ];
+
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Returns a new [Dyn] block.
+    pub fn new() -> Self {
+        let hash = dyn_hash::<Hasher>();
+        Self { hash }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a hash of this code block.
+    pub fn hash(&self) -> Digest {
+        self.hash
+    }
+}
+
+impl Default for Dyn {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Display for Dyn {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "dyn")
+    }
+}
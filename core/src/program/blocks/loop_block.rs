@@ -1,4 +1,4 @@
-use super::{fmt, hasher, Box, CodeBlock, Digest};
+use super::{fmt, hasher::Hasher, mast_hasher::loop_hash, Box, CodeBlock, Digest};
 
 // LOOP BLOCK
 // ================================================================================================
@@ -23,7 +23,7 @@ impl Loop {
     // --------------------------------------------------------------------------------------------
     /// Returns a new [Loop] bock instantiated with the specified body.
     pub fn new(body: CodeBlock) -> Self {
-        let hash = hasher::merge(&[body.hash(), Digest::default()]);
+        let hash = loop_hash::<Hasher>(body.hash());
         Self {
             body: Box::new(body),
             hash,
@@ -0,0 +1,262 @@
+use super::{get_span_op_group_count, hasher, Call, CodeBlock, Digest, Dyn, Felt, OpBatch, Vec};
+use crate::{program::CodeBlockTable, utils::collections::BTreeMap};
+
+// MAST HASHER
+// ================================================================================================
+
+/// A hash function used to compute hashes (MAST roots) of code blocks.
+///
+/// Code blocks are hashed with the hash function of the VM (Rescue Prime) when they are created,
+/// and only programs hashed with this function can be executed and proven by the VM, since the
+/// decoder recomputes the hashes of executed blocks in the hasher chiplet. Other algebraic hash
+/// functions (e.g., Poseidon2) can be plugged in by implementing this trait, and MAST roots of
+/// existing code blocks can then be computed with them via [CodeBlock::hash_with()] and
+/// [Program::hash_with()](crate::Program::hash_with). The block structure (i.e., what is hashed
+/// for each type of block) is the same regardless of the hash function.
+pub trait MastHasher {
+    /// Returns a hash of two digests.
+    fn merge(values: &[Digest; 2]) -> Digest;
+
+    /// Returns a hash of the provided list of field elements.
+    fn hash_elements(elements: &[Felt]) -> Digest;
+}
+
+impl MastHasher for hasher::Hasher {
+    fn merge(values: &[Digest; 2]) -> Digest {
+        hasher::merge(values)
+    }
+
+    fn hash_elements(elements: &[Felt]) -> Digest {
+        hasher::hash_elements(elements)
+    }
+}
+
+// BLOCK HASHES
+// ================================================================================================
+
+/// Returns the hash of a span block consisting of the specified operation batches.
+///
+/// The hash is computed over the operation groups of all batches; for the last batch, only the
+/// number of groups returned by [get_span_op_group_count()] is hashed.
+pub(super) fn span_hash<H: MastHasher>(batches: &[OpBatch]) -> Digest {
+    let num_op_groups = get_span_op_group_count(batches);
+    let op_groups = batches
+        .iter()
+        .flat_map(|batch| batch.groups().iter().copied())
+        .take(num_op_groups)
+        .collect::<Vec<_>>();
+    H::hash_elements(&op_groups)
+}
+
+/// Returns the hash of a join block with children with the specified hashes.
+pub(super) fn join_hash<H: MastHasher>(first: Digest, second: Digest) -> Digest {
+    H::merge(&[first, second])
+}
+
+/// Returns the hash of a split block with branches with the specified hashes.
+pub(super) fn split_hash<H: MastHasher>(on_true: Digest, on_false: Digest) -> Digest {
+    H::merge(&[on_true, on_false])
+}
+
+/// Returns the hash of a loop block with a body with the specified hash.
+pub(super) fn loop_hash<H: MastHasher>(body: Digest) -> Digest {
+    H::merge(&[body, Digest::default()])
+}
+
+/// Returns the hash of a call (or a system call) block invoking the function with the specified
+/// hash.
+pub(super) fn call_hash<H: MastHasher>(fn_hash: Digest, is_syscall: bool) -> Digest {
+    let domain = if is_syscall {
+        Call::SYSCALL_DOMAIN
+    } else {
+        Call::CALL_DOMAIN
+    };
+    H::merge(&[fn_hash, domain.into()])
+}
+
+/// Returns the hash of a dyn block.
+pub(super) fn dyn_hash<H: MastHasher>() -> Digest {
+    H::merge(&[Digest::default(), Dyn::DOMAIN.into()])
+}
+
+// REHASHING
+// ================================================================================================
+
+impl CodeBlock {
+    /// Returns a hash of this code block computed with the specified hash function.
+    ///
+    /// Call blocks and proxy blocks reference code which is not a part of this block by hash, and
+    /// thus the referenced hashes are used as is. To rehash the code invoked by a program as well,
+    /// use [Program::hash_with()](crate::Program::hash_with).
+    pub fn hash_with<H: MastHasher>(&self) -> Digest {
+        Rehasher::<H>::new(None).hash(self)
+    }
+}
+
+/// Computes hashes of code blocks with the hash function `H`.
+///
+/// If a code block table is provided, the hashes of the blocks invoked via call and proxy blocks
+/// are recomputed from the code of the blocks in the table. Rehashed blocks from the table are
+/// memoized, since the same procedure is commonly invoked from many places.
+pub(crate) struct Rehasher<'a, H> {
+    cb_table: Option<&'a CodeBlockTable>,
+    rehashed: BTreeMap<[u8; 32], Digest>,
+    hasher: core::marker::PhantomData<H>,
+}
+
+impl<'a, H: MastHasher> Rehasher<'a, H> {
+    /// Returns a new rehasher which resolves invoked code blocks in the specified table.
+    pub fn new(cb_table: Option<&'a CodeBlockTable>) -> Self {
+        Self {
+            cb_table,
+            rehashed: BTreeMap::new(),
+            hasher: core::marker::PhantomData,
+        }
+    }
+
+    /// Returns the hash of the specified block computed with the hash function `H`.
+    pub fn hash(&mut self, block: &CodeBlock) -> Digest {
+        match block {
+            CodeBlock::Span(span) => span_hash::<H>(span.op_batches()),
+            CodeBlock::Join(join) => {
+                let first = self.hash(join.first());
+                let second = self.hash(join.second());
+                join_hash::<H>(first, second)
+            }
+            CodeBlock::Split(split) => {
+                let on_true = self.hash(split.on_true());
+                let on_false = self.hash(split.on_false());
+                split_hash::<H>(on_true, on_false)
+            }
+            CodeBlock::Loop(block) => {
+                let body = self.hash(block.body());
+                loop_hash::<H>(body)
+            }
+            CodeBlock::Call(call) => {
+                let fn_hash = self.hash_invoked(call.fn_hash());
+                call_hash::<H>(fn_hash, call.is_syscall())
+            }
+            CodeBlock::Dyn(_) => dyn_hash::<H>(),
+            CodeBlock::Proxy(proxy) => self.hash_invoked(proxy.hash()),
+        }
+    }
+
+    /// Returns the hash of the block with the specified hash from the code block table computed
+    /// with the hash function `H`, or the specified hash if the block is not in the table.
+    fn hash_invoked(&mut self, hash: Digest) -> Digest {
+        let key = <[u8; 32]>::from(hash);
+        if let Some(&rehashed) = self.rehashed.get(&key) {
+            return rehashed;
+        }
+        match self.cb_table.and_then(|cb_table| cb_table.get(hash)) {
+            Some(block) => {
+                let rehashed = self.hash(block);
+                self.rehashed.insert(key, rehashed);
+                rehashed
+            }
+            None => hash,
+        }
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::{hasher, CodeBlock, Digest, Felt, MastHasher, Vec};
+    use crate::{
+        program::{blocks::Call, CodeBlockTable, Kernel},
+        FieldElement, Operation, Program,
+    };
+
+    /// A hash function which differs from the hash function of the VM only by absorbing an extra
+    /// element.
+    struct Salted;
+
+    impl MastHasher for Salted {
+        fn merge(values: &[Digest; 2]) -> Digest {
+            let mut elements = Vec::new();
+            elements.extend_from_slice(values[0].as_elements());
+            elements.extend_from_slice(values[1].as_elements());
+            Self::hash_elements(&elements)
+        }
+
+        fn hash_elements(elements: &[Felt]) -> Digest {
+            let mut elements = elements.to_vec();
+            elements.push(Felt::ONE);
+            hasher::hash_elements(&elements)
+        }
+    }
+
+    fn span(ops: &[Operation]) -> CodeBlock {
+        CodeBlock::new_span(ops.to_vec())
+    }
+
+    #[test]
+    fn hash_with_vm_hasher() {
+        let body = CodeBlock::new_join([
+            CodeBlock::new_split(span(&[Operation::Add]), span(&[Operation::Mul])),
+            CodeBlock::new_loop(span(&[Operation::Drop; 20])),
+        ]);
+        let root = CodeBlock::new_join([
+            body,
+            CodeBlock::new_join([
+                CodeBlock::new_dyn(),
+                CodeBlock::new_syscall(Digest::default()),
+            ]),
+        ]);
+
+        // the hash function of the VM reproduces the hashes computed when the blocks were created
+        assert_eq!(root.hash(), root.hash_with::<hasher::Hasher>());
+        assert_eq!(
+            root.hash(),
+            Program::new(root).hash_with::<hasher::Hasher>()
+        );
+    }
+
+    #[test]
+    fn hash_with_other_hasher() {
+        let add = span(&[Operation::Add]);
+        let mul = span(&[Operation::Mul]);
+        let root = CodeBlock::new_loop(CodeBlock::new_join([add.clone(), mul.clone()]));
+
+        // the block structure is retained, but all hashes are computed with the other function
+        let span_hash = |block: &CodeBlock| match block {
+            CodeBlock::Span(span) => Salted::hash_elements(&span.op_batches()[0].groups()[..1]),
+            _ => unreachable!(),
+        };
+        let expected = Salted::merge(&[
+            Salted::merge(&[span_hash(&add), span_hash(&mul)]),
+            Digest::default(),
+        ]);
+        assert_eq!(expected, root.hash_with::<Salted>());
+        assert_ne!(root.hash(), root.hash_with::<Salted>());
+    }
+
+    #[test]
+    fn hash_with_invoked_code() {
+        let callee = span(&[Operation::Add, Operation::Mul]);
+        let root = CodeBlock::new_join([
+            CodeBlock::new_call(callee.hash()),
+            CodeBlock::new_proxy(callee.hash()),
+        ]);
+        let callee_hash = callee.hash_with::<Salted>();
+
+        // a code block references invoked code by its original hash
+        let expected = Salted::merge(&[
+            Salted::merge(&[callee.hash(), Call::CALL_DOMAIN.into()]),
+            callee.hash(),
+        ]);
+        assert_eq!(expected, root.hash_with::<Salted>());
+
+        // a program rehashes invoked code which is in its code block table
+        let cb_table = [callee].into_iter().collect::<CodeBlockTable>();
+        let program = Program::with_kernel(root, Kernel::default(), cb_table);
+        let expected = Salted::merge(&[
+            Salted::merge(&[callee_hash, Call::CALL_DOMAIN.into()]),
+            callee_hash,
+        ]);
+        assert_eq!(expected, program.hash_with::<Salted>());
+    }
+}
@@ -3,21 +3,31 @@ use crate::DecoratorList;
 use core::fmt;
 
 mod call_block;
+mod dyn_block;
 mod join_block;
 mod loop_block;
+mod mast_hasher;
 mod proxy_block;
 mod span_block;
 mod split_block;
+mod visitor;
 
 pub use call_block::Call;
+pub use dyn_block::Dyn;
 pub use join_block::Join;
 pub use loop_block::Loop;
+pub use mast_hasher::MastHasher;
+pub(crate) use mast_hasher::Rehasher;
 pub use proxy_block::Proxy;
 pub use span_block::{
     get_span_op_group_count, OpBatch, Span, BATCH_SIZE as OP_BATCH_SIZE,
     GROUP_SIZE as OP_GROUP_SIZE,
 };
 pub use split_block::Split;
+pub use visitor::{
+    fold_block, fold_join, fold_loop, fold_split, walk_block, walk_join, walk_loop, walk_span,
+    walk_split, Rewriter, Visitor,
+};
 
 // PROGRAM BLOCK
 // ================================================================================================
@@ -29,6 +39,7 @@ pub enum CodeBlock {
     Split(Split),
     Loop(Loop),
     Call(Call),
+    Dyn(Dyn),
     Proxy(Proxy),
 }
 
@@ -66,6 +77,17 @@ impl CodeBlock {
         Self::Call(Call::new(code_hash))
     }
 
+    /// Returns a new Call block which invokes the kernel procedure with the specified hash.
+    pub fn new_syscall(code_hash: Digest) -> Self {
+        Self::Call(Call::new_syscall(code_hash))
+    }
+
+    /// Returns a new Dyn block which executes the procedure with the MAST root located at the
+    /// top of the stack.
+    pub fn new_dyn() -> Self {
+        Self::Dyn(Dyn::new())
+    }
+
     /// TODO: add comments
     pub fn new_proxy(code_hash: Digest) -> Self {
         Self::Proxy(Proxy::new(code_hash))
@@ -87,6 +109,7 @@ impl CodeBlock {
             CodeBlock::Split(block) => block.hash(),
             CodeBlock::Loop(block) => block.hash(),
             CodeBlock::Call(block) => block.hash(),
+            CodeBlock::Dyn(block) => block.hash(),
             CodeBlock::Proxy(block) => block.hash(),
         }
     }
@@ -100,6 +123,7 @@ impl fmt::Display for CodeBlock {
             CodeBlock::Split(block) => write!(f, "{}", block),
             CodeBlock::Loop(block) => write!(f, "{}", block),
             CodeBlock::Call(block) => write!(f, "{}", block),
+            CodeBlock::Dyn(block) => write!(f, "{}", block),
             CodeBlock::Proxy(block) => write!(f, "{}", block),
         }
     }
@@ -4,8 +4,9 @@ use super::{fmt, Digest};
 // ================================================================================================
 /// A code block used to conceal a part of a program.
 ///
-/// Proxy blocks cannot be executed by the VM. They are used primarily to verify the integrity of
-/// a program's hash while keeping parts of the program secret.
+/// Proxy blocks are used to verify the integrity of a program's hash while keeping parts of the
+/// program secret, or when parts of the program are supplied only at runtime. To execute a proxy
+/// block, the VM must be supplied with the code block it conceals; otherwise, the execution fails.
 ///
 /// Hash of a proxy block is not computed but is rather defined at instantiation time.
 #[derive(Clone, Debug)]
@@ -1,6 +1,7 @@
-use super::{fmt, hasher, Digest, Felt, FieldElement, Operation, Vec};
+use super::{
+    fmt, hasher::Hasher, mast_hasher::span_hash, Digest, Felt, FieldElement, Operation, Vec,
+};
 use crate::{DecoratorIterator, DecoratorList};
-use winter_utils::flatten_slice_elements;
 
 // CONSTANTS
 // ================================================================================================
@@ -327,7 +328,6 @@ impl OpBatchAccumulator {
 fn batch_ops(ops: Vec<Operation>) -> (Vec<OpBatch>, Digest) {
     let mut batch_acc = OpBatchAccumulator::new();
     let mut batches = Vec::<OpBatch>::new();
-    let mut batch_groups = Vec::<[Felt; BATCH_SIZE]>::new();
 
     for op in ops {
         // if the operation cannot be accepted into the current accumulator, add the contents of
@@ -336,7 +336,6 @@ fn batch_ops(ops: Vec<Operation>) -> (Vec<OpBatch>, Digest) {
             let batch = batch_acc.into_batch();
             batch_acc = OpBatchAccumulator::new();
 
-            batch_groups.push(*batch.groups());
             batches.push(batch);
         }
 
@@ -347,14 +346,11 @@ fn batch_ops(ops: Vec<Operation>) -> (Vec<OpBatch>, Digest) {
     // make sure we finished processing the last batch
     if !batch_acc.is_empty() {
         let batch = batch_acc.into_batch();
-        batch_groups.push(*batch.groups());
         batches.push(batch);
     }
 
     // compute the hash of all operation groups
-    let num_op_groups = get_span_op_group_count(&batches);
-    let op_groups = &flatten_slice_elements(&batch_groups)[..num_op_groups];
-    let hash = hasher::hash_elements(op_groups);
+    let hash = span_hash::<Hasher>(&batches);
 
     (batches, hash)
 }
@@ -400,7 +396,8 @@ fn validate_decorators(operations: &[Operation], decorators: &DecoratorList) {
 
 #[cfg(test)]
 mod tests {
-    use super::{hasher, Felt, FieldElement, Operation, BATCH_SIZE};
+    use super::{Felt, FieldElement, Operation, BATCH_SIZE};
+    use crate::chiplets::hasher;
 
     #[test]
     fn batch_ops() {
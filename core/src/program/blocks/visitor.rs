@@ -0,0 +1,328 @@
+use super::{Call, CodeBlock, Dyn, Join, Loop, Operation, Proxy, Span, Split};
+use crate::{Decorator, DecoratorIterator};
+
+// VISITOR
+// ================================================================================================
+
+/// Traverses a tree of code blocks.
+///
+/// Every method has a default implementation which visits the children of the block via the
+/// corresponding `walk_*` function. Implementors override the methods for the nodes they are
+/// interested in, and call the `walk_*` function from the overridden method if the traversal
+/// should continue into the children of the node.
+///
+/// For example, the following visitor counts code blocks and builds a histogram of operations:
+/// ```ignore
+/// #[derive(Default)]
+/// struct Stats {
+///     num_blocks: usize,
+///     ops: BTreeMap<String, usize>,
+/// }
+///
+/// impl Visitor for Stats {
+///     fn visit_block(&mut self, block: &CodeBlock) {
+///         self.num_blocks += 1;
+///         walk_block(self, block);
+///     }
+///
+///     fn visit_op(&mut self, op: &Operation) {
+///         *self.ops.entry(op.to_string()).or_default() += 1;
+///     }
+/// }
+///
+/// let mut stats = Stats::default();
+/// stats.visit_block(program.root());
+/// ```
+pub trait Visitor {
+    /// Visits a code block of any type.
+    fn visit_block(&mut self, block: &CodeBlock) {
+        walk_block(self, block);
+    }
+
+    /// Visits a span block; the default implementation visits its operations and decorators.
+    fn visit_span(&mut self, span: &Span) {
+        walk_span(self, span);
+    }
+
+    /// Visits a join block; the default implementation visits both of its children.
+    fn visit_join(&mut self, join: &Join) {
+        walk_join(self, join);
+    }
+
+    /// Visits a split block; the default implementation visits both of its branches.
+    fn visit_split(&mut self, split: &Split) {
+        walk_split(self, split);
+    }
+
+    /// Visits a loop block; the default implementation visits its body.
+    fn visit_loop(&mut self, block: &Loop) {
+        walk_loop(self, block);
+    }
+
+    /// Visits a call block. Call blocks reference other programs by hash, and thus they do not
+    /// have children.
+    fn visit_call(&mut self, _call: &Call) {}
+
+    /// Visits a dyn block. Dyn blocks execute procedures which are not known until runtime, and
+    /// thus they do not have children.
+    fn visit_dyn(&mut self, _block: &Dyn) {}
+
+    /// Visits a proxy block. Proxy blocks stand in for blocks which were not included in the
+    /// program, and thus they do not have children.
+    fn visit_proxy(&mut self, _proxy: &Proxy) {}
+
+    /// Visits an operation of a span block. Operations include NOOPs which were inserted to pad
+    /// operation groups.
+    fn visit_op(&mut self, _op: &Operation) {}
+
+    /// Visits a decorator of a span block; decorators are visited right before the operation they
+    /// precede.
+    fn visit_decorator(&mut self, _decorator: &Decorator) {}
+}
+
+/// Dispatches the specified block to the visitor method for its type.
+pub fn walk_block<V: Visitor + ?Sized>(visitor: &mut V, block: &CodeBlock) {
+    match block {
+        CodeBlock::Span(span) => visitor.visit_span(span),
+        CodeBlock::Join(join) => visitor.visit_join(join),
+        CodeBlock::Split(split) => visitor.visit_split(split),
+        CodeBlock::Loop(block) => visitor.visit_loop(block),
+        CodeBlock::Call(call) => visitor.visit_call(call),
+        CodeBlock::Dyn(block) => visitor.visit_dyn(block),
+        CodeBlock::Proxy(proxy) => visitor.visit_proxy(proxy),
+    }
+}
+
+/// Visits operations of the specified span in the order of execution, together with the
+/// decorators preceding them.
+pub fn walk_span<V: Visitor + ?Sized>(visitor: &mut V, span: &Span) {
+    let mut decorators = DecoratorIterator::new(span.decorators());
+    let mut op_idx = 0;
+    for batch in span.op_batches() {
+        for op in batch.ops() {
+            while let Some(decorator) = decorators.next(op_idx) {
+                visitor.visit_decorator(decorator);
+            }
+            visitor.visit_op(op);
+            op_idx += 1;
+        }
+    }
+}
+
+/// Visits the first and then the second child of the specified join block.
+pub fn walk_join<V: Visitor + ?Sized>(visitor: &mut V, join: &Join) {
+    visitor.visit_block(join.first());
+    visitor.visit_block(join.second());
+}
+
+/// Visits the true and then the false branch of the specified split block.
+pub fn walk_split<V: Visitor + ?Sized>(visitor: &mut V, split: &Split) {
+    visitor.visit_block(split.on_true());
+    visitor.visit_block(split.on_false());
+}
+
+/// Visits the body of the specified loop block.
+pub fn walk_loop<V: Visitor + ?Sized>(visitor: &mut V, block: &Loop) {
+    visitor.visit_block(block.body());
+}
+
+// REWRITER
+// ================================================================================================
+
+/// Rewrites a tree of code blocks.
+///
+/// Since the hash of a code block commits to all of its children, code blocks cannot be modified
+/// in place. Instead, a rewriter builds a new tree: every method returns the block which replaces
+/// the visited one. The default implementations rebuild join, split, and loop blocks from their
+/// rewritten children via the corresponding `fold_*` functions, and keep span, call,
+/// and proxy blocks unchanged.
+///
+/// For example, the following rewriter appends a NOOP to every span block:
+/// ```ignore
+/// struct Instrumenter;
+///
+/// impl Rewriter for Instrumenter {
+///     fn rewrite_span(&mut self, span: &Span) -> CodeBlock {
+///         let mut ops = span
+///             .op_batches()
+///             .iter()
+///             .flat_map(|batch| batch.ops())
+///             .copied()
+///             .collect::<Vec<_>>();
+///         ops.push(Operation::Noop);
+///         CodeBlock::new_span_with_decorators(ops, span.decorators().clone())
+///     }
+/// }
+///
+/// let program = Program::new(Instrumenter.rewrite_block(program.root()));
+/// ```
+pub trait Rewriter {
+    /// Rewrites a code block of any type.
+    fn rewrite_block(&mut self, block: &CodeBlock) -> CodeBlock {
+        fold_block(self, block)
+    }
+
+    /// Rewrites a span block; the default implementation returns the span unchanged.
+    fn rewrite_span(&mut self, span: &Span) -> CodeBlock {
+        CodeBlock::Span(span.clone())
+    }
+
+    /// Rewrites a join block; the default implementation rewrites both of its children.
+    fn rewrite_join(&mut self, join: &Join) -> CodeBlock {
+        fold_join(self, join)
+    }
+
+    /// Rewrites a split block; the default implementation rewrites both of its branches.
+    fn rewrite_split(&mut self, split: &Split) -> CodeBlock {
+        fold_split(self, split)
+    }
+
+    /// Rewrites a loop block; the default implementation rewrites its body.
+    fn rewrite_loop(&mut self, block: &Loop) -> CodeBlock {
+        fold_loop(self, block)
+    }
+
+    /// Rewrites a call block; the default implementation returns the block unchanged.
+    fn rewrite_call(&mut self, call: &Call) -> CodeBlock {
+        CodeBlock::Call(call.clone())
+    }
+
+    /// Rewrites a dyn block; the default implementation returns the block unchanged.
+    fn rewrite_dyn(&mut self, block: &Dyn) -> CodeBlock {
+        CodeBlock::Dyn(block.clone())
+    }
+
+    /// Rewrites a proxy block; the default implementation returns the block unchanged.
+    fn rewrite_proxy(&mut self, proxy: &Proxy) -> CodeBlock {
+        CodeBlock::Proxy(proxy.clone())
+    }
+}
+
+/// Dispatches the specified block to the rewriter method for its type.
+pub fn fold_block<R: Rewriter + ?Sized>(rewriter: &mut R, block: &CodeBlock) -> CodeBlock {
+    match block {
+        CodeBlock::Span(span) => rewriter.rewrite_span(span),
+        CodeBlock::Join(join) => rewriter.rewrite_join(join),
+        CodeBlock::Split(split) => rewriter.rewrite_split(split),
+        CodeBlock::Loop(block) => rewriter.rewrite_loop(block),
+        CodeBlock::Call(call) => rewriter.rewrite_call(call),
+        CodeBlock::Dyn(block) => rewriter.rewrite_dyn(block),
+        CodeBlock::Proxy(proxy) => rewriter.rewrite_proxy(proxy),
+    }
+}
+
+/// Returns a join block built from the rewritten children of the specified join block.
+pub fn fold_join<R: Rewriter + ?Sized>(rewriter: &mut R, join: &Join) -> CodeBlock {
+    let first = rewriter.rewrite_block(join.first());
+    let second = rewriter.rewrite_block(join.second());
+    CodeBlock::new_join([first, second])
+}
+
+/// Returns a split block built from the rewritten branches of the specified split block.
+pub fn fold_split<R: Rewriter + ?Sized>(rewriter: &mut R, split: &Split) -> CodeBlock {
+    let on_true = rewriter.rewrite_block(split.on_true());
+    let on_false = rewriter.rewrite_block(split.on_false());
+    CodeBlock::new_split(on_true, on_false)
+}
+
+/// Returns a loop block built from the rewritten body of the specified loop block.
+pub fn fold_loop<R: Rewriter + ?Sized>(rewriter: &mut R, block: &Loop) -> CodeBlock {
+    CodeBlock::new_loop(rewriter.rewrite_block(block.body()))
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::{walk_block, CodeBlock, Decorator, Operation, Rewriter, Span, Visitor};
+    use crate::{
+        utils::collections::{BTreeMap, Vec},
+        AdviceInjector, Felt,
+    };
+
+    /// Counts code blocks and builds a histogram of operations and decorators.
+    #[derive(Default)]
+    struct Stats {
+        num_blocks: usize,
+        num_decorators: usize,
+        ops: BTreeMap<String, usize>,
+    }
+
+    impl Visitor for Stats {
+        fn visit_block(&mut self, block: &CodeBlock) {
+            self.num_blocks += 1;
+            walk_block(self, block);
+        }
+
+        fn visit_op(&mut self, op: &Operation) {
+            *self.ops.entry(op.to_string()).or_default() += 1;
+        }
+
+        fn visit_decorator(&mut self, _decorator: &Decorator) {
+            self.num_decorators += 1;
+        }
+    }
+
+    /// Replaces every MUL operation with an ADD operation.
+    struct MulToAdd;
+
+    impl Rewriter for MulToAdd {
+        fn rewrite_span(&mut self, span: &Span) -> CodeBlock {
+            let ops = span
+                .op_batches()
+                .iter()
+                .flat_map(|batch| batch.ops())
+                .map(|&op| match op {
+                    Operation::Mul => Operation::Add,
+                    op => op,
+                })
+                .collect::<Vec<_>>();
+            CodeBlock::new_span_with_decorators(ops, span.decorators().clone())
+        }
+    }
+
+    fn build_program() -> CodeBlock {
+        let push = Operation::Push(Felt::new(3));
+        let span1 = CodeBlock::new_span_with_decorators(
+            vec![push, Operation::Mul],
+            vec![(1, Decorator::Advice(AdviceInjector::MerkleNode))],
+        );
+        let span2 = CodeBlock::new_span(vec![Operation::Pad, Operation::Mul]);
+        let span3 = CodeBlock::new_span(vec![Operation::Add]);
+        let split = CodeBlock::new_split(span2, span3);
+        CodeBlock::new_join([span1, CodeBlock::new_loop(split)])
+    }
+
+    #[test]
+    fn visit_blocks() {
+        let program = build_program();
+        let mut stats = Stats::default();
+        stats.visit_block(&program);
+
+        assert_eq!(6, stats.num_blocks);
+        assert_eq!(1, stats.num_decorators);
+        assert_eq!(Some(&2), stats.ops.get("mul"));
+        assert_eq!(Some(&1), stats.ops.get("add"));
+        assert_eq!(Some(&1), stats.ops.get("pad"));
+    }
+
+    #[test]
+    fn rewrite_blocks() {
+        let program = build_program();
+        let rewritten = MulToAdd.rewrite_block(&program);
+        assert_ne!(program.hash(), rewritten.hash());
+
+        let mut stats = Stats::default();
+        stats.visit_block(&rewritten);
+        assert_eq!(6, stats.num_blocks);
+        assert_eq!(1, stats.num_decorators);
+        assert_eq!(None, stats.ops.get("mul"));
+        assert_eq!(Some(&3), stats.ops.get("add"));
+
+        // a rewriter which does not override any methods rebuilds the same tree
+        struct Identity;
+        impl Rewriter for Identity {}
+        assert_eq!(program.hash(), Identity.rewrite_block(&program).hash());
+    }
+}
@@ -0,0 +1,258 @@
+use super::{blocks::CodeBlock, CodeBlockTable, Digest, Kernel, Operation, Program};
+use crate::{
+    errors::ProgramBuilderError,
+    utils::{collections::Vec, group_vector_elements},
+};
+
+// PROGRAM BUILDER
+// ================================================================================================
+
+/// A builder of programs composed of already compiled code blocks.
+///
+/// A builder holds a sequence of code blocks which form the body of the program; the blocks are
+/// executed one after another. This allows higher layers to stitch together programs (e.g., user
+/// scripts and kernel code) without compiling them from source anew:
+///
+/// ```ignore
+/// let program = ProgramBuilder::new()
+///     .push_program(&script)
+///     .push_call(proc_root)
+///     .if_else(t_branch, f_branch)
+///     .build()?;
+/// ```
+///
+/// The blocks are joined into a binary tree in the same way as the assembler joins the blocks of
+/// a program body, except that consecutive span blocks are never merged. Thus, pushing the roots
+/// of two compiled programs results in a program which executes exactly the same operations as
+/// the two programs executed one after another.
+#[derive(Clone, Debug, Default)]
+pub struct ProgramBuilder {
+    blocks: Vec<CodeBlock>,
+    kernel: Option<Kernel>,
+    program_kernels: Vec<Kernel>,
+    syscalls: Vec<Digest>,
+    cb_table: CodeBlockTable,
+}
+
+impl ProgramBuilder {
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+    /// Returns a new builder of a program with an empty body.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the kernel against which the program is built.
+    ///
+    /// If the kernel is not set, the program is built against the kernel of the programs pushed
+    /// into the builder (see [ProgramBuilder::push_program()]), or against an empty kernel if no
+    /// program compiled against a kernel was pushed.
+    pub fn with_kernel(mut self, kernel: Kernel) -> Self {
+        self.kernel = Some(kernel);
+        self
+    }
+
+    /// Adds the specified code block to the table of code blocks carried by the program, so that
+    /// the block can be invoked via `call` or `syscall` by its hash. The code of kernel procedures
+    /// must be supplied in this way, unless it is a part of a pushed program.
+    pub fn with_callee(mut self, block: CodeBlock) -> Self {
+        self.cb_table.insert(block);
+        self
+    }
+
+    // BODY BUILDERS
+    // --------------------------------------------------------------------------------------------
+
+    /// Appends the specified code block to the body of the program.
+    pub fn push_block(mut self, block: CodeBlock) -> Self {
+        self.blocks.push(block);
+        self
+    }
+
+    /// Appends the root of the specified program to the body of the program. The code blocks
+    /// carried by the specified program (e.g., the code of kernel procedures) are carried over.
+    ///
+    /// Programs compiled against a kernel must be compiled against the same kernel as the program
+    /// being built; this is checked when the program is built.
+    pub fn push_program(mut self, program: &Program) -> Self {
+        self.blocks.push(program.root().clone());
+        program
+            .cb_table()
+            .blocks()
+            .for_each(|block| self.cb_table.insert(block.clone()));
+        if !program.kernel().is_empty() {
+            self.program_kernels.push(program.kernel().clone());
+        }
+        self
+    }
+
+    /// Appends a call to the procedure with the specified MAST root to the body of the program.
+    ///
+    /// The code of the procedure is expected to be supplied either via
+    /// [ProgramBuilder::with_callee()], or by the advice provider when the program is executed.
+    pub fn push_call(self, proc_hash: Digest) -> Self {
+        self.push_block(CodeBlock::new_call(proc_hash))
+    }
+
+    /// Appends a system call to the kernel procedure with the specified MAST root to the body of
+    /// the program. The procedure must be a part of the kernel of the program; this is checked
+    /// when the program is built.
+    pub fn push_syscall(mut self, proc_hash: Digest) -> Self {
+        self.syscalls.push(proc_hash);
+        self.push_block(CodeBlock::new_syscall(proc_hash))
+    }
+
+    /// Appends a conditional block to the body of the program; the block executes `t_branch` if
+    /// the top of the stack is ONE, and `f_branch` if it is ZERO.
+    pub fn if_else(self, t_branch: CodeBlock, f_branch: CodeBlock) -> Self {
+        self.push_block(CodeBlock::new_split(t_branch, f_branch))
+    }
+
+    /// Appends a loop to the body of the program; the loop executes `body` while the top of the
+    /// stack is ONE.
+    pub fn while_true(self, body: CodeBlock) -> Self {
+        self.push_block(CodeBlock::new_loop(body))
+    }
+
+    // PROGRAM BUILDER
+    // --------------------------------------------------------------------------------------------
+
+    /// Builds the program out of the blocks pushed into this builder. A program with an empty
+    /// body consists of a single span block with a NOOP, since a code block cannot be empty.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - Programs compiled against different kernels were pushed, or a pushed program was
+    ///   compiled against a kernel other than the one set via [ProgramBuilder::with_kernel()].
+    /// - A system call was pushed to a procedure which is not a part of the kernel.
+    pub fn build(self) -> Result<Program, ProgramBuilderError> {
+        let mut program_kernels = self.program_kernels.into_iter();
+        let kernel = match self.kernel {
+            Some(kernel) => kernel,
+            None => program_kernels.next().unwrap_or_default(),
+        };
+        if program_kernels.any(|program_kernel| program_kernel != kernel) {
+            return Err(ProgramBuilderError::KernelMismatch);
+        }
+        if let Some(&proc_hash) = self
+            .syscalls
+            .iter()
+            .find(|&&proc_hash| !kernel.contains_proc(proc_hash))
+        {
+            return Err(ProgramBuilderError::SyscallTargetNotInKernel(proc_hash));
+        }
+
+        let root = join_blocks(self.blocks);
+        Ok(Program::with_kernel(root, kernel, self.cb_table))
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Builds a binary tree of Join blocks out of the provided blocks, pairing adjacent blocks level
+/// by level; if a level has an odd number of blocks, the last block is carried to the next level.
+fn join_blocks(mut blocks: Vec<CodeBlock>) -> CodeBlock {
+    if blocks.is_empty() {
+        return CodeBlock::new_span(vec![Operation::Noop]);
+    }
+
+    while blocks.len() > 1 {
+        let last_block = if blocks.len() % 2 == 0 {
+            None
+        } else {
+            blocks.pop()
+        };
+
+        blocks = group_vector_elements::<CodeBlock, 2>(blocks)
+            .into_iter()
+            .map(CodeBlock::new_join)
+            .collect();
+
+        if let Some(block) = last_block {
+            blocks.push(block);
+        }
+    }
+
+    blocks.remove(0)
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::{CodeBlock, Kernel, Program, ProgramBuilder, ProgramBuilderError};
+    use crate::Operation;
+
+    fn span(ops: &[Operation]) -> CodeBlock {
+        CodeBlock::new_span(ops.to_vec())
+    }
+
+    #[test]
+    fn build_body() {
+        let a = span(&[Operation::Add]);
+        let b = span(&[Operation::Mul]);
+        let c = span(&[Operation::Neg]);
+        let program = ProgramBuilder::new()
+            .push_block(a.clone())
+            .push_call(b.hash())
+            .if_else(a.clone(), c.clone())
+            .with_callee(b.clone())
+            .build()
+            .unwrap();
+
+        let expected = CodeBlock::new_join([
+            CodeBlock::new_join([a.clone(), CodeBlock::new_call(b.hash())]),
+            CodeBlock::new_split(a, c),
+        ]);
+        assert_eq!(expected.hash(), program.hash());
+        assert!(program.cb_table().contains(b.hash()));
+        assert!(program.kernel().is_empty());
+
+        // a program with an empty body consists of a single NOOP
+        let program = ProgramBuilder::new().build().unwrap();
+        assert_eq!(span(&[Operation::Noop]).hash(), program.hash());
+    }
+
+    #[test]
+    fn build_with_kernel() {
+        let proc = span(&[Operation::Add]);
+        let kernel = Kernel::new(&[proc.hash()]);
+        let script = Program::with_kernel(
+            CodeBlock::new_syscall(proc.hash()),
+            kernel.clone(),
+            [proc.clone()].into_iter().collect(),
+        );
+
+        // the kernel and the code of kernel procedures are carried over from pushed programs
+        let program = ProgramBuilder::new()
+            .push_program(&script)
+            .push_syscall(proc.hash())
+            .build()
+            .unwrap();
+        assert_eq!(&kernel, program.kernel());
+        assert!(program.cb_table().contains(proc.hash()));
+
+        // system calls must target procedures of the kernel
+        let other = span(&[Operation::Mul]);
+        assert_eq!(
+            Err(ProgramBuilderError::SyscallTargetNotInKernel(other.hash())),
+            ProgramBuilder::new()
+                .push_program(&script)
+                .push_syscall(other.hash())
+                .build()
+                .map(|program| program.hash())
+        );
+
+        // pushed programs must be compiled against the kernel of the program
+        assert_eq!(
+            Err(ProgramBuilderError::KernelMismatch),
+            ProgramBuilder::new()
+                .with_kernel(Kernel::new(&[other.hash()]))
+                .push_program(&script)
+                .build()
+                .map(|program| program.hash())
+        );
+    }
+}
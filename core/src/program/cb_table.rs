@@ -0,0 +1,70 @@
+use super::{blocks::CodeBlock, Digest};
+use crate::utils::collections::BTreeMap;
+
+// CODE BLOCK TABLE
+// ================================================================================================
+
+/// A set of code blocks keyed by their hashes (MAST roots).
+///
+/// A code block table is used to supply code to the VM at runtime: when the VM encounters a
+/// proxy block (e.g., a procedure invoked by its MAST root via `exec.0x...`), the proxy block is
+/// resolved into the code block with the same hash. Similarly, a program carries the code of the
+/// procedures it invokes via `call` and `syscall` instructions in a code block table.
+#[derive(Clone, Debug, Default)]
+pub struct CodeBlockTable {
+    blocks: BTreeMap<[u8; 32], CodeBlock>,
+}
+
+impl CodeBlockTable {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Returns a new empty code block table.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the code block with the specified hash, or `None` if the block is not in this table.
+    pub fn get(&self, hash: Digest) -> Option<&CodeBlock> {
+        self.blocks.get(&<[u8; 32]>::from(hash))
+    }
+
+    /// Returns true if a code block with the specified hash is in this table.
+    pub fn contains(&self, hash: Digest) -> bool {
+        self.blocks.contains_key(&<[u8; 32]>::from(hash))
+    }
+
+    /// Returns the number of code blocks in this table.
+    pub fn len(&self) -> usize {
+        self.blocks.len()
+    }
+
+    /// Returns true if this table contains no code blocks.
+    pub fn is_empty(&self) -> bool {
+        self.blocks.is_empty()
+    }
+
+    /// Returns an iterator over the code blocks in this table.
+    pub fn blocks(&self) -> impl Iterator<Item = &CodeBlock> {
+        self.blocks.values()
+    }
+
+    // STATE MUTATORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Adds the specified code block to this table. The block is keyed by its hash; a block with
+    /// the same hash which is already in the table is replaced.
+    pub fn insert(&mut self, block: CodeBlock) {
+        self.blocks.insert(block.hash().into(), block);
+    }
+}
+
+impl FromIterator<CodeBlock> for CodeBlockTable {
+    fn from_iter<I: IntoIterator<Item = CodeBlock>>(blocks: I) -> Self {
+        let mut table = Self::new();
+        blocks.into_iter().for_each(|block| table.insert(block));
+        table
+    }
+}
@@ -0,0 +1,338 @@
+use super::{
+    blocks::{CodeBlock, Span},
+    Digest, Program,
+};
+use crate::utils::collections::{BTreeMap, Vec};
+use core::fmt;
+
+// DUPLICATION REPORT
+// ================================================================================================
+
+/// A report on code blocks which appear in several places of a program's MAST.
+///
+/// Two code blocks are considered to be duplicates if they have the same hash. Only maximal
+/// duplicates are reported: a duplicated block is omitted from the report if every one of its
+/// occurrences is a part of the same larger duplicated block (e.g., the body of a duplicated
+/// loop is not reported separately, unless the body also appears outside of the loop).
+///
+/// Duplicates are sorted by the number of operations which would be saved if all of their
+/// occurrences were replaced with references to a single shared procedure, largest first.
+///
+/// Note that the program itself is not modified: the VM currently executes procedures by inlining
+/// their bodies into the MAST, and thus replacing duplicated blocks with calls to shared
+/// procedures would produce a program which the processor cannot execute.
+#[derive(Clone, Debug)]
+pub struct DuplicationReport {
+    duplicates: Vec<DuplicateBlock>,
+    num_ops: usize,
+}
+
+impl DuplicationReport {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Analyzes the MAST of the specified program and returns a report on its duplicated blocks.
+    pub fn new(program: &Program) -> Self {
+        let mut analyzer = Analyzer::default();
+        let root_stats = analyzer.visit(program.root(), None);
+
+        let entries = &analyzer.entries;
+        let mut duplicates = entries
+            .values()
+            .filter(|entry| entry.num_occurrences > 1)
+            .filter(|entry| match entry.parent {
+                Parent::Single(parent) => entries[&parent].num_occurrences != entry.num_occurrences,
+                _ => true,
+            })
+            .map(|entry| DuplicateBlock {
+                block: entry.block.clone(),
+                num_occurrences: entry.num_occurrences,
+                num_ops: entry.stats.num_ops,
+                num_cycles: entry.stats.num_cycles,
+            })
+            .collect::<Vec<_>>();
+        duplicates.sort_by(|a, b| {
+            b.num_redundant_ops()
+                .cmp(&a.num_redundant_ops())
+                .then_with(|| b.num_occurrences.cmp(&a.num_occurrences))
+                .then_with(|| hash_key(a.hash()).cmp(&hash_key(b.hash())))
+        });
+
+        Self {
+            duplicates,
+            num_ops: root_stats.num_ops,
+        }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the duplicated blocks of the program, sorted by the number of redundant operations.
+    pub fn duplicates(&self) -> &[DuplicateBlock] {
+        &self.duplicates
+    }
+
+    /// Returns the total number of operations in the span blocks of the program.
+    pub fn num_ops(&self) -> usize {
+        self.num_ops
+    }
+
+    /// Returns the number of operations which would be saved if every duplicated block appeared
+    /// in the program only once.
+    pub fn num_redundant_ops(&self) -> usize {
+        self.duplicates
+            .iter()
+            .map(DuplicateBlock::num_redundant_ops)
+            .sum()
+    }
+}
+
+impl fmt::Display for DuplicationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{} duplicated blocks, {} of {} operations are redundant",
+            self.duplicates.len(),
+            self.num_redundant_ops(),
+            self.num_ops
+        )?;
+        for duplicate in self.duplicates.iter() {
+            writeln!(f, "  {}", duplicate)?;
+        }
+        Ok(())
+    }
+}
+
+// DUPLICATE BLOCK
+// ================================================================================================
+
+/// A code block which appears in several places of a program's MAST.
+#[derive(Clone, Debug)]
+pub struct DuplicateBlock {
+    block: CodeBlock,
+    num_occurrences: usize,
+    num_ops: usize,
+    num_cycles: usize,
+}
+
+impl DuplicateBlock {
+    /// Returns the duplicated code block.
+    pub fn block(&self) -> &CodeBlock {
+        &self.block
+    }
+
+    /// Returns the hash of the duplicated code block.
+    pub fn hash(&self) -> Digest {
+        self.block.hash()
+    }
+
+    /// Returns the number of places in the program where this block appears.
+    pub fn num_occurrences(&self) -> usize {
+        self.num_occurrences
+    }
+
+    /// Returns the number of operations in the span blocks of this block, including the NOOPs
+    /// padding operation batches.
+    pub fn num_ops(&self) -> usize {
+        self.num_ops
+    }
+
+    /// Returns an estimate of the number of cycles needed to execute this block once.
+    ///
+    /// The estimate counts the cycles of the control flow operations, assumes that the longer
+    /// branch of every split block is taken, and that the body of every loop is executed once.
+    pub fn num_cycles(&self) -> usize {
+        self.num_cycles
+    }
+
+    /// Returns the number of operations in all but one occurrence of this block.
+    pub fn num_redundant_ops(&self) -> usize {
+        (self.num_occurrences - 1) * self.num_ops
+    }
+}
+
+impl fmt::Display for DuplicateBlock {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let kind = match self.block {
+            CodeBlock::Span(_) => "span",
+            CodeBlock::Join(_) => "join",
+            CodeBlock::Split(_) => "split",
+            CodeBlock::Loop(_) => "loop",
+            CodeBlock::Call(ref call) if call.is_syscall() => "syscall",
+            CodeBlock::Call(_) => "call",
+            CodeBlock::Dyn(_) => "dyn",
+            CodeBlock::Proxy(_) => "proxy",
+        };
+        for byte in &hash_key(self.hash())[..8] {
+            write!(f, "{:02x}", byte)?;
+        }
+        write!(
+            f,
+            ": {} block, {} occurrences, {} operations, ~{} cycles",
+            kind, self.num_occurrences, self.num_ops, self.num_cycles
+        )
+    }
+}
+
+// ANALYZER
+// ================================================================================================
+
+/// Size and execution cost of a code block.
+#[derive(Copy, Clone, Debug, Default)]
+struct BlockStats {
+    num_ops: usize,
+    num_cycles: usize,
+}
+
+/// Parents of all occurrences of a code block.
+#[derive(Copy, Clone, Debug)]
+enum Parent {
+    /// All occurrences of the block have a parent with the same hash.
+    Single([u8; 32]),
+    /// Occurrences of the block have parents with different hashes, or the block is the root.
+    Many,
+}
+
+/// Occurrences of code blocks with the same hash.
+#[derive(Debug)]
+struct Entry {
+    block: CodeBlock,
+    num_occurrences: usize,
+    parent: Parent,
+    stats: BlockStats,
+}
+
+#[derive(Default)]
+struct Analyzer {
+    entries: BTreeMap<[u8; 32], Entry>,
+}
+
+impl Analyzer {
+    /// Records an occurrence of the specified block and all of its descendants, and returns the
+    /// stats of the block.
+    fn visit(&mut self, block: &CodeBlock, parent: Option<[u8; 32]>) -> BlockStats {
+        let key = hash_key(block.hash());
+        let stats = match block {
+            CodeBlock::Span(span) => span_stats(span),
+            CodeBlock::Join(join) => {
+                let first = self.visit(join.first(), Some(key));
+                let second = self.visit(join.second(), Some(key));
+                BlockStats {
+                    num_ops: first.num_ops + second.num_ops,
+                    num_cycles: 2 + first.num_cycles + second.num_cycles,
+                }
+            }
+            CodeBlock::Split(split) => {
+                let on_true = self.visit(split.on_true(), Some(key));
+                let on_false = self.visit(split.on_false(), Some(key));
+                BlockStats {
+                    num_ops: on_true.num_ops + on_false.num_ops,
+                    num_cycles: 2 + on_true.num_cycles.max(on_false.num_cycles),
+                }
+            }
+            CodeBlock::Loop(block) => {
+                let body = self.visit(block.body(), Some(key));
+                BlockStats {
+                    num_ops: body.num_ops,
+                    num_cycles: 2 + body.num_cycles,
+                }
+            }
+            // the bodies of called functions are not a part of the program's MAST
+            CodeBlock::Call(_) | CodeBlock::Proxy(_) => BlockStats::default(),
+            // the DYN and END operations; the body of the executed procedure is not known
+            CodeBlock::Dyn(_) => BlockStats {
+                num_ops: 0,
+                num_cycles: 2,
+            },
+        };
+
+        let entry = self.entries.entry(key).or_insert_with(|| Entry {
+            block: block.clone(),
+            num_occurrences: 0,
+            parent: parent.map_or(Parent::Many, Parent::Single),
+            stats,
+        });
+        entry.num_occurrences += 1;
+        if let Parent::Single(prev) = entry.parent {
+            if Some(prev) != parent {
+                entry.parent = Parent::Many;
+            }
+        }
+
+        stats
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the stats of a span block: the processor executes all operations of the span, including
+/// the padding NOOPs, together with a SPAN, an END, and a RESPAN for every batch but the first.
+fn span_stats(span: &Span) -> BlockStats {
+    let batches = span.op_batches();
+    let num_ops = batches.iter().map(|batch| batch.ops().len()).sum::<usize>();
+    BlockStats {
+        num_ops,
+        num_cycles: num_ops + batches.len() + 1,
+    }
+}
+
+fn hash_key(hash: Digest) -> [u8; 32] {
+    hash.into()
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::{CodeBlock, DuplicationReport, Program};
+    use crate::Operation;
+
+    fn span(ops: &[Operation]) -> CodeBlock {
+        CodeBlock::new_span(ops.to_vec())
+    }
+
+    #[test]
+    fn no_duplicates() {
+        let program = Program::new(CodeBlock::new_join([
+            span(&[Operation::Add]),
+            span(&[Operation::Mul]),
+        ]));
+        let report = DuplicationReport::new(&program);
+        assert!(report.duplicates().is_empty());
+        assert_eq!(0, report.num_redundant_ops());
+        assert_eq!(2, report.num_ops());
+    }
+
+    #[test]
+    fn maximal_duplicates() {
+        let body = span(&[Operation::Mul, Operation::Swap]);
+        let inner = span(&[Operation::Add]);
+        let split = CodeBlock::new_split(inner.clone(), span(&[Operation::Drop]));
+        let looped = CodeBlock::new_loop(body.clone());
+        let duplicate = CodeBlock::new_join([looped.clone(), split.clone()]);
+        let program = Program::new(CodeBlock::new_join([
+            CodeBlock::new_join([duplicate.clone(), duplicate.clone()]),
+            inner.clone(),
+        ]));
+
+        let report = DuplicationReport::new(&program);
+        let duplicates = report.duplicates();
+        assert_eq!(2, duplicates.len());
+
+        // the loop and the split block appear only as parts of the duplicated join block
+        assert_eq!(duplicate.hash(), duplicates[0].hash());
+        assert_eq!(2, duplicates[0].num_occurrences());
+        assert_eq!(4, duplicates[0].num_ops());
+        assert_eq!(2 + (2 + 4) + (2 + 3), duplicates[0].num_cycles());
+
+        // the ADD span appears inside the split blocks and also on its own
+        assert_eq!(inner.hash(), duplicates[1].hash());
+        assert_eq!(3, duplicates[1].num_occurrences());
+        assert_eq!(1, duplicates[1].num_ops());
+        assert_eq!(3, duplicates[1].num_cycles());
+
+        assert_eq!(4 + 2, report.num_redundant_ops());
+        assert_eq!(9, report.num_ops());
+    }
+}
@@ -0,0 +1,62 @@
+use super::{hasher, Digest, Felt, Vec};
+
+// KERNEL
+// ================================================================================================
+
+/// A set of procedures which can be invoked by a program via system calls.
+///
+/// A kernel is compiled separately from the programs which use it, and is identified by the MAST
+/// roots of its procedures. A program can invoke only the procedures of the kernel against which
+/// it was compiled, and the procedures are executed in the kernel context, which has memory
+/// separate from the memory of the program.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Kernel {
+    proc_hashes: Vec<Digest>,
+}
+
+impl Kernel {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Returns a new [Kernel] consisting of the procedures with the specified MAST roots.
+    ///
+    /// Duplicated roots are removed, and the roots are sorted so that kernels with the same set
+    /// of procedures are equal.
+    pub fn new(proc_hashes: &[Digest]) -> Self {
+        let mut proc_hashes = proc_hashes.to_vec();
+        proc_hashes.sort_unstable_by_key(|&hash| <[u8; 32]>::from(hash));
+        proc_hashes.dedup();
+        Self { proc_hashes }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns true if this kernel does not contain any procedures.
+    pub fn is_empty(&self) -> bool {
+        self.proc_hashes.is_empty()
+    }
+
+    /// Returns true if a procedure with the specified MAST root is a part of this kernel.
+    pub fn contains_proc(&self, proc_hash: Digest) -> bool {
+        self.proc_hashes.contains(&proc_hash)
+    }
+
+    /// Returns the MAST roots of the procedures of this kernel, in ascending order of their bytes.
+    pub fn proc_hashes(&self) -> &[Digest] {
+        &self.proc_hashes
+    }
+
+    /// Returns a commitment to this kernel computed as a sequential hash of the MAST roots of its
+    /// procedures, in the order returned by [Kernel::proc_hashes()].
+    ///
+    /// Since the roots are sorted, the commitment depends only on the set of procedures of the
+    /// kernel.
+    pub fn hash(&self) -> Digest {
+        let elements = self
+            .proc_hashes
+            .iter()
+            .flat_map(|&hash| <[Felt; 4]>::from(hash))
+            .collect::<Vec<_>>();
+        hasher::hash_elements(&elements)
+    }
+}
@@ -1,6 +1,22 @@
-use crate::errors::LibraryError;
+use crate::{
+    chiplets::hasher::Digest,
+    errors::LibraryError,
+    utils::{
+        collections::{BTreeMap, Vec},
+        string::String,
+    },
+};
 
-/// TODO: add docs
+// LIBRARY
+// ================================================================================================
+
+/// A collection of Miden assembly modules located under a common root namespace.
+///
+/// Modules of a library are identified by their paths (e.g., `std::math::u64` is the `u64`
+/// module of the library with the `std` root namespace), and are provided to the assembler as
+/// source code. Procedures exported from a module are described by parsing the module with a
+/// [ModuleParser], which is usually the assembler, so that documentation and signatures of the
+/// procedures are exactly what the assembler sees when it compiles the module.
 pub trait Library {
     /// Returns the root namespace of this library.
     fn root_ns(&self) -> &str;
@@ -13,4 +29,199 @@ pub trait Library {
     /// # Errors
     /// Returns an error if the modules for the specified path does not exist in this library.
     fn get_module_source(&self, module_path: &str) -> Result<&str, LibraryError>;
+
+    /// Returns descriptions of the procedures exported from the module located at the specified
+    /// path, sorted by procedure label. The module is parsed by the specified parser.
+    ///
+    /// # Errors
+    /// Returns an error if the modules for the specified path does not exist in this library, or
+    /// if the parser fails to parse the module.
+    fn exported_procedures(
+        &self,
+        module_path: &str,
+        parser: &dyn ModuleParser,
+    ) -> Result<Vec<ProcedureInfo>, LibraryError> {
+        let source = self.get_module_source(module_path)?;
+        parser.parse_exported_procedures(module_path, source)
+    }
+
+    /// Returns documentation of the procedures exported from the module located at the specified
+    /// path, keyed by procedure label. The module is parsed by the specified parser.
+    ///
+    /// Documentation of a procedure consists of the `#!` comment lines immediately preceding the
+    /// procedure declaration (or its annotations), with the `#!` prefix (and a single space
+    /// following it) removed. Procedures without documentation comments are not included in the
+    /// result.
+    ///
+    /// # Errors
+    /// Returns an error if the modules for the specified path does not exist in this library, or
+    /// if the parser fails to parse the module.
+    fn procedure_docs(
+        &self,
+        module_path: &str,
+        parser: &dyn ModuleParser,
+    ) -> Result<BTreeMap<String, String>, LibraryError> {
+        let docs = self
+            .exported_procedures(module_path, parser)?
+            .into_iter()
+            .filter_map(|proc| Some((proc.name, proc.docs?)))
+            .collect();
+        Ok(docs)
+    }
+}
+
+// MODULE PARSER
+// ================================================================================================
+
+/// A parser of library modules; the assembler implements this trait so that libraries can
+/// describe their procedures without depending on the assembler.
+pub trait ModuleParser {
+    /// Returns descriptions of the procedures exported from the module with the specified path
+    /// and source code, sorted by procedure label.
+    ///
+    /// # Errors
+    /// Returns an error if the module or any of the modules it imports fails to parse.
+    fn parse_exported_procedures(
+        &self,
+        module_path: &str,
+        source: &str,
+    ) -> Result<Vec<ProcedureInfo>, LibraryError>;
+}
+
+// PROCEDURE INFO
+// ================================================================================================
+
+/// Description of a procedure exported from a library module.
+///
+/// Procedures are described by a [ModuleParser], and thus the documentation of a procedure is
+/// exactly the doc comment attached to it during parsing (i.e., the `#!` comment lines
+/// immediately preceding the procedure declaration or its annotations).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProcedureInfo {
+    name: String,
+    docs: Option<String>,
+    signature: Option<ProcedureSignature>,
+    root: Digest,
+}
+
+impl ProcedureInfo {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Returns a description of the procedure with the specified name, documentation, and MAST
+    /// root. The signature of the procedure is extracted from the documentation.
+    pub fn new(name: String, docs: Option<String>, root: Digest) -> Self {
+        let signature = docs
+            .as_deref()
+            .and_then(|docs| parse_signature(&docs.lines().collect::<Vec<_>>()));
+        Self {
+            name,
+            docs,
+            signature,
+            root,
+        }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the name of this procedure.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the documentation of this procedure, if any (see [Library::procedure_docs()]).
+    pub fn docs(&self) -> Option<&str> {
+        self.docs.as_deref()
+    }
+
+    /// Returns the signature of this procedure, if it is described in the documentation of the
+    /// procedure.
+    pub fn signature(&self) -> Option<&ProcedureSignature> {
+        self.signature.as_ref()
+    }
+
+    /// Returns the MAST root of this procedure.
+    pub fn root(&self) -> Digest {
+        self.root
+    }
+}
+
+// PROCEDURE SIGNATURE
+// ================================================================================================
+
+/// Stack items consumed and produced by a procedure.
+///
+/// Signatures are extracted from the documentation of procedures, which describes the stack
+/// either via a transition line (e.g., `[b, a, ...] -> [c, ...]`), or via `Input: [b, a, ...]`
+/// and `Output: [c, ...]` lines. Items are named as in the documentation, with the `...` item
+/// denoting the rest of the stack omitted. By convention, items named with a single uppercase
+/// letter (e.g., `K`) are words, and all other items are single elements.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProcedureSignature {
+    inputs: Vec<String>,
+    outputs: Vec<String>,
+}
+
+impl ProcedureSignature {
+    /// Returns the names of the stack items consumed by the procedure, starting from the top of
+    /// the stack.
+    pub fn inputs(&self) -> &[String] {
+        &self.inputs
+    }
+
+    /// Returns the names of the stack items produced by the procedure, starting from the top of
+    /// the stack.
+    pub fn outputs(&self) -> &[String] {
+        &self.outputs
+    }
+
+    /// Returns the number of stack items consumed by the procedure.
+    pub fn num_inputs(&self) -> usize {
+        self.inputs.len()
+    }
+
+    /// Returns the number of stack items produced by the procedure.
+    pub fn num_outputs(&self) -> usize {
+        self.outputs.len()
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Extracts the signature of a procedure from the lines of its documentation; a stack transition
+/// line takes precedence over `Input:` and `Output:` lines.
+fn parse_signature(doc_lines: &[&str]) -> Option<ProcedureSignature> {
+    let transition = doc_lines.iter().find_map(|line| {
+        let (inputs, outputs) = line.split_once("->")?;
+        Some(ProcedureSignature {
+            inputs: parse_stack_items(inputs)?,
+            outputs: parse_stack_items(outputs)?,
+        })
+    });
+    transition.or_else(|| {
+        let find_items = |prefix: &str| {
+            doc_lines
+                .iter()
+                .find_map(|line| parse_stack_items(line.trim().strip_prefix(prefix)?))
+        };
+        Some(ProcedureSignature {
+            inputs: find_items("Input:")?,
+            outputs: find_items("Output:")?,
+        })
+    })
+}
+
+/// Parses the names of stack items from the first bracketed list in the specified text (e.g.,
+/// `[b, a, ...]`); the `...` item is omitted.
+fn parse_stack_items(text: &str) -> Option<Vec<String>> {
+    let (_, list) = text.split_once('[')?;
+    let (list, _) = list.split_once(']')?;
+    let items = list
+        .split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty() && *item != "...")
+        .map(String::from)
+        .collect();
+    Some(items)
 }
@@ -0,0 +1,330 @@
+use super::Library;
+use crate::{
+    errors::LibraryError,
+    utils::{
+        bytes::{read_seq, read_string, write_seq, write_string},
+        collections::{BTreeMap, Vec},
+        string::{String, ToString},
+        ByteReader, DeserializationError, SliceReader,
+    },
+};
+
+#[cfg(feature = "std")]
+use std::{fs, path::Path};
+
+// CONSTANTS
+// ================================================================================================
+
+/// Version of the library format written by [MaslLibrary::to_bytes()].
+///
+/// The version must be incremented whenever the layout of a serialized library changes;
+/// libraries with a different version are rejected.
+pub const MASL_VERSION: u8 = 1;
+
+/// Bytes with which every serialized library starts.
+const MAGIC: [u8; 4] = *b"MASL";
+
+/// Extension of files containing Miden assembly modules.
+#[cfg(feature = "std")]
+const MODULE_EXTENSION: &str = "masm";
+
+/// Delimiter of the parts of a module path.
+const MODULE_PATH_DELIM: &str = "::";
+
+// MASL LIBRARY
+// ================================================================================================
+
+/// A library of Miden assembly modules which can be distributed as a single `.masl` file.
+///
+/// All modules of a library are located under the root namespace of the library; for example,
+/// a module `foo::math::u512` belongs to a library with the `foo` root namespace. A library can
+/// be assembled from a directory of `.masm` files (see [MaslLibrary::read_from_dir()]), in which
+/// case the path of a module follows the path of its file relative to the directory.
+///
+/// # Serialization format
+/// A serialized library has the following layout, where sizes (including lengths of sequences
+/// and strings) are encoded as little-endian `u64` values:
+/// - 4 bytes of magic `MASL`, followed by a `u8` version (currently 1).
+/// - The root namespace and the version of the library, as UTF-8 strings prefixed with their
+///   lengths in bytes.
+/// - A sequence of modules sorted by path, each consisting of the path and the source code of the
+///   module, encoded as strings.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MaslLibrary {
+    namespace: String,
+    version: String,
+    modules: BTreeMap<String, String>,
+}
+
+impl MaslLibrary {
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+    /// Returns a library with the specified root namespace and version, consisting of the
+    /// specified modules keyed by their paths.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - The namespace is not a valid identifier.
+    /// - A module path is not located under the namespace, or contains an empty part.
+    pub fn new<I>(namespace: &str, version: &str, modules: I) -> Result<Self, LibraryError>
+    where
+        I: IntoIterator<Item = (String, String)>,
+    {
+        if !is_valid_ident(namespace) {
+            return Err(LibraryError::InvalidNamespace(namespace.to_string()));
+        }
+        let modules = modules.into_iter().collect::<BTreeMap<_, _>>();
+        for path in modules.keys() {
+            let mut parts = path.split(MODULE_PATH_DELIM);
+            if parts.next() != Some(namespace) || !parts.clone().all(is_valid_ident) {
+                return Err(LibraryError::InvalidModulePath(path.clone()));
+            }
+            if parts.next().is_none() {
+                return Err(LibraryError::InvalidModulePath(path.clone()));
+            }
+        }
+
+        Ok(Self {
+            namespace: namespace.to_string(),
+            version: version.to_string(),
+            modules,
+        })
+    }
+
+    /// Returns a library with the specified root namespace and version, consisting of all `.masm`
+    /// files located in the specified directory and its subdirectories.
+    ///
+    /// The path of a module consists of the namespace followed by the path of its file relative to
+    /// the directory, without the extension; for example, the file `math/u512.masm` of a library
+    /// with the `foo` namespace contains the `foo::math::u512` module.
+    ///
+    /// # Errors
+    /// Returns an error if the directory could not be read, or if the resulting library is not
+    /// valid (see [MaslLibrary::new()]).
+    #[cfg(feature = "std")]
+    pub fn read_from_dir<P: AsRef<Path>>(
+        path: P,
+        namespace: &str,
+        version: &str,
+    ) -> Result<Self, LibraryError> {
+        let mut modules = BTreeMap::new();
+        read_modules(path.as_ref(), namespace, &mut modules)?;
+        Self::new(namespace, version, modules)
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns an iterator over the paths of all modules of this library, in lexicographic order.
+    pub fn module_paths(&self) -> impl Iterator<Item = &str> {
+        self.modules.keys().map(String::as_str)
+    }
+
+    // SERIALIZATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Serializes this library into a vector of bytes.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut target = MAGIC.to_vec();
+        target.push(MASL_VERSION);
+        write_string(&mut target, &self.namespace);
+        write_string(&mut target, &self.version);
+        write_seq(
+            &mut target,
+            self.modules.iter(),
+            |target, (path, source)| {
+                write_string(target, path);
+                write_string(target, source);
+            },
+        );
+        target
+    }
+
+    /// Returns a library deserialized from the specified bytes.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - The bytes are not a library, or the library was written in an unsupported version of the
+    ///   library format.
+    /// - The library is malformed or not valid (see [MaslLibrary::new()]).
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, LibraryError> {
+        let mut source = SliceReader::new(bytes);
+        let magic = source
+            .read_u8_array::<4>()
+            .map_err(|_| LibraryError::NotALibrary)?;
+        if magic != MAGIC {
+            return Err(LibraryError::NotALibrary);
+        }
+        let version = source
+            .read_u8()
+            .map_err(LibraryError::DeserializationError)?;
+        if version != MASL_VERSION {
+            return Err(LibraryError::UnsupportedVersion(version));
+        }
+
+        let (namespace, version, modules) =
+            read_body(&mut source).map_err(LibraryError::DeserializationError)?;
+        if source.has_more_bytes() {
+            return Err(LibraryError::DeserializationError(
+                DeserializationError::UnconsumedBytes,
+            ));
+        }
+        Self::new(&namespace, &version, modules)
+    }
+}
+
+impl Library for MaslLibrary {
+    /// Returns the root namespace of this library.
+    fn root_ns(&self) -> &str {
+        &self.namespace
+    }
+
+    /// Returns the version of this library.
+    fn version(&self) -> &str {
+        &self.version
+    }
+
+    /// Returns the source code of the module located at the specified path.
+    ///
+    /// # Errors
+    /// Returns an error if the modules for the specified path does not exist in this library.
+    fn get_module_source(&self, module_path: &str) -> Result<&str, LibraryError> {
+        self.modules
+            .get(module_path)
+            .map(String::as_str)
+            .ok_or_else(|| LibraryError::ModuleNotFound(module_path.to_string()))
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns true if the specified string can be used as a part of a module path.
+fn is_valid_ident(ident: &str) -> bool {
+    let mut chars = ident.chars();
+    matches!(chars.next(), Some(c) if c.is_ascii_alphabetic())
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Reads the namespace, the version, and the modules of a library (i.e., everything following
+/// the version of the format) from the source.
+#[allow(clippy::type_complexity)]
+fn read_body<R: ByteReader>(
+    source: &mut R,
+) -> Result<(String, String, Vec<(String, String)>), DeserializationError> {
+    let namespace = read_string(source)?;
+    let version = read_string(source)?;
+    let modules = read_seq(source, |source| {
+        Ok((read_string(source)?, read_string(source)?))
+    })?;
+    if modules.windows(2).any(|pair| pair[0].0 >= pair[1].0) {
+        return Err(DeserializationError::InvalidValue(
+            "modules of a library are not sorted".into(),
+        ));
+    }
+    Ok((namespace, version, modules))
+}
+
+/// Reads all `.masm` files located in the specified directory and its subdirectories into the
+/// provided map, keyed by the module paths prefixed with the specified path.
+#[cfg(feature = "std")]
+fn read_modules(
+    dir: &Path,
+    prefix: &str,
+    modules: &mut BTreeMap<String, String>,
+) -> Result<(), LibraryError> {
+    let io_error = |path: &Path, err: std::io::Error| {
+        LibraryError::FileError(format!("{}: {}", path.display(), err))
+    };
+
+    let entries = fs::read_dir(dir).map_err(|err| io_error(dir, err))?;
+    for entry in entries {
+        let path = entry.map_err(|err| io_error(dir, err))?.path();
+        let name = match path.file_stem().and_then(|name| name.to_str()) {
+            Some(name) => name,
+            None => continue,
+        };
+        let module_path = format!("{}{}{}", prefix, MODULE_PATH_DELIM, name);
+        if path.is_dir() {
+            read_modules(&path, &module_path, modules)?;
+        } else if path.extension().and_then(|ext| ext.to_str()) == Some(MODULE_EXTENSION) {
+            let source = fs::read_to_string(&path).map_err(|err| io_error(&path, err))?;
+            modules.insert(module_path, source);
+        }
+    }
+    Ok(())
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::{LibraryError, MaslLibrary, MASL_VERSION};
+    use crate::Library;
+
+    #[test]
+    fn masl_library_serialization() {
+        let modules = [
+            ("foo::math".to_string(), "export.add add end".to_string()),
+            (
+                "foo::bar::baz".to_string(),
+                "export.one push.1 end".to_string(),
+            ),
+        ];
+        let library = MaslLibrary::new("foo", "0.1.0", modules).unwrap();
+        assert_eq!(
+            vec!["foo::bar::baz", "foo::math"],
+            library.module_paths().collect::<Vec<_>>()
+        );
+
+        let bytes = library.to_bytes();
+        assert_eq!(b"MASL", &bytes[..4]);
+        assert_eq!(MASL_VERSION, bytes[4]);
+        let decoded = MaslLibrary::from_bytes(&bytes).unwrap();
+        assert_eq!(library, decoded);
+        assert_eq!("foo", decoded.root_ns());
+        assert_eq!("0.1.0", decoded.version());
+        assert_eq!(
+            "export.add add end",
+            decoded.get_module_source("foo::math").unwrap()
+        );
+
+        assert!(matches!(
+            MaslLibrary::from_bytes(b"MVMS"),
+            Err(LibraryError::NotALibrary)
+        ));
+        let mut bytes = library.to_bytes();
+        bytes[4] += 1;
+        assert!(matches!(
+            MaslLibrary::from_bytes(&bytes),
+            Err(LibraryError::UnsupportedVersion(_))
+        ));
+        let bytes = library.to_bytes();
+        assert!(matches!(
+            MaslLibrary::from_bytes(&bytes[..bytes.len() - 1]),
+            Err(LibraryError::DeserializationError(_))
+        ));
+    }
+
+    #[test]
+    fn masl_library_validation() {
+        for path in ["bar::math", "foo", "foo::", "foo::::math", "foo::1math"] {
+            let modules = [(path.to_string(), String::new())];
+            assert!(
+                matches!(
+                    MaslLibrary::new("foo", "0.1.0", modules),
+                    Err(LibraryError::InvalidModulePath(_))
+                ),
+                "{}",
+                path
+            );
+        }
+        for namespace in ["", "foo::bar", "1foo"] {
+            assert!(matches!(
+                MaslLibrary::new(namespace, "0.1.0", []),
+                Err(LibraryError::InvalidNamespace(_))
+            ));
+        }
+    }
+}
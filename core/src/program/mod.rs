@@ -6,10 +6,29 @@ use super::{
 use core::fmt;
 
 pub mod blocks;
-use blocks::CodeBlock;
+pub use blocks::MastHasher;
+use blocks::{CodeBlock, Rehasher};
+
+mod builder;
+pub use builder::ProgramBuilder;
+
+mod cb_table;
+pub use cb_table::CodeBlockTable;
+
+mod kernel;
+pub use kernel::Kernel;
+
+mod dedup;
+pub use dedup::{DuplicateBlock, DuplicationReport};
 
 mod library;
-pub use library::Library;
+pub use library::{Library, ModuleParser, ProcedureInfo, ProcedureSignature};
+
+mod masl;
+pub use masl::{MaslLibrary, MASL_VERSION};
+
+mod source_map;
+pub use source_map::{InstructionInfo, SourceMap, SpanSourceMap, SOURCE_MAP_VERSION};
 
 // PROGRAM
 // ================================================================================================
@@ -18,17 +37,44 @@ pub use library::Library;
 /// A program is described by a Merkelized Abstract Syntax Tree (MAST), where each node is a
 /// [CodeBlock]. Internal nodes describe control flow semantics of the program, while leaf nodes
 /// contain linear sequences of instructions which contain no control flow.
+///
+/// A program may be compiled against a [Kernel]. In this case, the program carries the kernel
+/// together with the code of the kernel procedures, which the program can invoke via system
+/// calls.
 #[derive(Clone, Debug)]
 pub struct Program {
     root: CodeBlock,
+    kernel: Kernel,
+    cb_table: CodeBlockTable,
 }
 
 impl Program {
-    // CONSTRUCTOR
+    // CONSTRUCTORS
     // --------------------------------------------------------------------------------------------
     /// Constructs a new program from the specified code block.
     pub fn new(root: CodeBlock) -> Self {
-        Self { root }
+        Self::with_kernel(root, Kernel::default(), CodeBlockTable::default())
+    }
+
+    /// Constructs a new program from the specified code block, compiled against the specified
+    /// kernel. The code block table is expected to contain the code of the kernel procedures.
+    pub fn with_kernel(root: CodeBlock, kernel: Kernel, cb_table: CodeBlockTable) -> Self {
+        Self {
+            root,
+            kernel,
+            cb_table,
+        }
+    }
+
+    /// Constructs a program from its hash alone.
+    ///
+    /// The root of such a program is a proxy block, and thus the code of the program is requested
+    /// from the advice provider when the program is executed. Since the proxy block is replaced by
+    /// the code supplied by the provider, the execution trace (and thus a proof of execution) is
+    /// the same as for the program itself. This allows hosts to execute and prove programs
+    /// identified only by their hashes, e.g., when the code is stored in a database.
+    pub fn from_hash(hash: Digest) -> Self {
+        Self::new(CodeBlock::new_proxy(hash))
     }
 
     // PUBLIC ACCESSORS
@@ -43,6 +89,27 @@ impl Program {
     pub fn hash(&self) -> Digest {
         self.root.hash()
     }
+
+    /// Returns a hash of this program computed with the specified hash function.
+    ///
+    /// Unlike [CodeBlock::hash_with()], the hashes of the procedures invoked by this program
+    /// whose code is in the code block table of this program are recomputed as well. Procedures
+    /// whose code is not available (e.g., procedures invoked by their MAST roots) are referenced
+    /// by their original hashes.
+    pub fn hash_with<H: MastHasher>(&self) -> Digest {
+        Rehasher::<H>::new(Some(&self.cb_table)).hash(&self.root)
+    }
+
+    /// Returns the kernel against which this program was compiled.
+    pub fn kernel(&self) -> &Kernel {
+        &self.kernel
+    }
+
+    /// Returns the table of code blocks which can be invoked by this program but are not a part
+    /// of its MAST (e.g., the code of kernel procedures).
+    pub fn cb_table(&self) -> &CodeBlockTable {
+        &self.cb_table
+    }
 }
 
 impl fmt::Display for Program {
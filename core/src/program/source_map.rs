@@ -0,0 +1,344 @@
+use super::{
+    blocks::{Span, Visitor},
+    Digest, Program,
+};
+use crate::{
+    errors::SourceMapError,
+    utils::{
+        bytes::{
+            read_option, read_seq, read_string, read_usize, write_option, write_seq, write_string,
+            write_usize,
+        },
+        collections::Vec,
+        string::String,
+        ByteReader, ByteWriter, Deserializable, DeserializationError, IntoBytes, Serializable,
+        SliceReader,
+    },
+    Decorator, SourceLocation, Word,
+};
+
+// CONSTANTS
+// ================================================================================================
+
+/// Version of the source map format written by [SourceMap::to_bytes()].
+///
+/// The version must be incremented whenever the layout of a source map changes; source maps with
+/// a different version are rejected.
+pub const SOURCE_MAP_VERSION: u8 = 2;
+
+/// Bytes with which every source map starts.
+const MAGIC: [u8; 4] = *b"MVMS";
+
+// SOURCE MAP
+// ================================================================================================
+
+/// Maps operations of a program back to the assembly instructions from which they were compiled,
+/// to the locations of these instructions in the source code, and to the procedures containing
+/// them.
+///
+/// A source map is extracted from a program compiled in debug mode, and can be serialized into a
+/// standalone artifact. Thus, external tools (e.g., debuggers or block explorers) can relate the
+/// operations executed by the VM to assembly instructions without access to the program itself.
+/// The reader API of a source map exposes only plain values (hashes are exposed as their 32-byte
+/// encodings), and thus consumers do not need to depend on the types describing programs.
+///
+/// The source map describes every span block of the program in the order in which the blocks
+/// appear in the MAST of the program (depth-first, the first child of a block before the second
+/// one), followed by the span blocks of the kernel procedures carried by the program. Span blocks
+/// are identified by their hashes; since the same span block may appear several times in a
+/// program, several span blocks of a source map may have the same hash.
+///
+/// # Serialization format
+/// A serialized source map has the following layout, where integers are encoded in little-endian
+/// byte order, and sizes (including lengths of sequences and strings) are encoded as `u64` values:
+/// - 4 bytes of magic `MVMS`, followed by a `u8` version (currently 2).
+/// - 32 bytes of the program hash.
+/// - A sequence of span blocks, each consisting of:
+///   - 32 bytes of the span hash.
+///   - A sequence of instructions, each consisting of the index of the first operation of the
+///     instruction within the span (a size), the number of operations of the instruction (a `u8`),
+///     the text of the instruction (a UTF-8 string prefixed with its length in bytes), a sequence
+///     of labels of the procedures containing the instruction (strings), and an optional source
+///     location (a `u8` flag followed, if the flag is 1, by the module path as a string, and the
+///     line and the column as `u32` values).
+///
+/// The instructions of a span block are sorted by the index of their first operation.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SourceMap {
+    program_hash: [u8; 32],
+    spans: Vec<SpanSourceMap>,
+}
+
+impl SourceMap {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Returns the source map of the specified program.
+    ///
+    /// Programs compiled in release mode do not retain assembly instructions; for such programs,
+    /// the source map describes all span blocks, but does not contain any instructions.
+    pub fn new(program: &Program) -> Self {
+        let mut builder = Builder::default();
+        builder.visit_block(program.root());
+        for block in program.cb_table().blocks() {
+            builder.visit_block(block);
+        }
+
+        Self {
+            program_hash: hash_bytes(program.hash()),
+            spans: builder.spans,
+        }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the hash of the program described by this source map.
+    pub fn program_hash(&self) -> [u8; 32] {
+        self.program_hash
+    }
+
+    /// Returns all span blocks described by this source map.
+    pub fn spans(&self) -> &[SpanSourceMap] {
+        &self.spans
+    }
+
+    /// Returns an iterator over the span blocks with the specified hash.
+    pub fn find_spans(&self, hash: [u8; 32]) -> impl Iterator<Item = &SpanSourceMap> {
+        self.spans.iter().filter(move |span| span.hash == hash)
+    }
+
+    /// Returns the instruction which the operation at the specified index of the span block with
+    /// the specified hash was compiled from, or None if the instruction is not known.
+    ///
+    /// If several span blocks have the specified hash, the first one containing an instruction
+    /// for the operation is used.
+    pub fn instruction_at(&self, span_hash: [u8; 32], op_idx: usize) -> Option<&InstructionInfo> {
+        self.find_spans(span_hash)
+            .find_map(|span| span.instruction_at(op_idx))
+    }
+
+    // SERIALIZATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Serializes this source map into a vector of bytes.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut target = MAGIC.to_vec();
+        target.push(SOURCE_MAP_VERSION);
+        target.write_u8_slice(&self.program_hash);
+        write_seq(&mut target, self.spans.iter(), |target, span| {
+            target.write_u8_slice(&span.hash);
+            write_seq(target, span.instructions.iter(), |target, instruction| {
+                write_usize(target, instruction.op_idx);
+                target.write_u8(instruction.num_ops);
+                write_string(target, &instruction.text);
+                write_seq(target, instruction.procs.iter(), |target, label| {
+                    write_string(target, label)
+                });
+                write_option(target, instruction.location.as_ref(), |target, location| {
+                    location.write_into(target)
+                });
+            });
+        });
+        target
+    }
+
+    /// Returns a source map deserialized from the specified bytes.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - The bytes are not a source map, or the source map was written in an unsupported version
+    ///   of the source map format.
+    /// - The source map is malformed.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, SourceMapError> {
+        let mut source = SliceReader::new(bytes);
+        let magic = source
+            .read_u8_array::<4>()
+            .map_err(|_| SourceMapError::NotASourceMap)?;
+        if magic != MAGIC {
+            return Err(SourceMapError::NotASourceMap);
+        }
+        let version = source
+            .read_u8()
+            .map_err(SourceMapError::DeserializationError)?;
+        if version != SOURCE_MAP_VERSION {
+            return Err(SourceMapError::UnsupportedVersion(version));
+        }
+
+        let source_map =
+            Self::read_body(&mut source).map_err(SourceMapError::DeserializationError)?;
+        if source.has_more_bytes() {
+            return Err(SourceMapError::DeserializationError(
+                DeserializationError::UnconsumedBytes,
+            ));
+        }
+        Ok(source_map)
+    }
+
+    /// Reads the body of a source map (i.e., everything following the version) from the source.
+    fn read_body<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let program_hash = source.read_u8_array::<32>()?;
+        let spans = read_seq(source, |source| {
+            let hash = source.read_u8_array::<32>()?;
+            let instructions = read_seq(source, |source| {
+                let op_idx = read_usize(source)?;
+                let num_ops = source.read_u8()?;
+                let text = read_string(source)?;
+                let procs = read_seq(source, read_string)?;
+                let location = read_option(source, SourceLocation::read_from)?;
+                Ok(InstructionInfo {
+                    op_idx,
+                    num_ops,
+                    text,
+                    procs,
+                    location,
+                })
+            })?;
+            if instructions
+                .windows(2)
+                .any(|pair| pair[0].op_idx > pair[1].op_idx)
+            {
+                return Err(DeserializationError::InvalidValue(
+                    "instructions of a span block are not sorted".into(),
+                ));
+            }
+            Ok(SpanSourceMap { hash, instructions })
+        })?;
+
+        Ok(Self {
+            program_hash,
+            spans,
+        })
+    }
+}
+
+// SPAN SOURCE MAP
+// ================================================================================================
+
+/// Assembly instructions from which the operations of a single span block were compiled.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SpanSourceMap {
+    hash: [u8; 32],
+    instructions: Vec<InstructionInfo>,
+}
+
+impl SpanSourceMap {
+    /// Returns the hash of this span block.
+    pub fn hash(&self) -> [u8; 32] {
+        self.hash
+    }
+
+    /// Returns the instructions of this span block, sorted by the index of their first operation.
+    pub fn instructions(&self) -> &[InstructionInfo] {
+        &self.instructions
+    }
+
+    /// Returns the instruction which the operation at the specified index of this span block was
+    /// compiled from, or None if the operation is not a part of any instruction (e.g., a NOOP
+    /// inserted by the assembler).
+    pub fn instruction_at(&self, op_idx: usize) -> Option<&InstructionInfo> {
+        let pos = self
+            .instructions
+            .partition_point(|instruction| instruction.op_idx <= op_idx);
+        self.instructions[..pos]
+            .last()
+            .filter(|instruction| instruction.contains(op_idx))
+    }
+}
+
+// INSTRUCTION INFO
+// ================================================================================================
+
+/// An assembly instruction and the operations of a span block it was compiled into.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InstructionInfo {
+    op_idx: usize,
+    num_ops: u8,
+    text: String,
+    procs: Vec<String>,
+    location: Option<SourceLocation>,
+}
+
+impl InstructionInfo {
+    /// Returns the index of the first operation of this instruction within its span block.
+    pub fn op_idx(&self) -> usize {
+        self.op_idx
+    }
+
+    /// Returns the number of operations this instruction was compiled into. This is also the
+    /// number of VM cycles it takes to execute the instruction.
+    pub fn num_ops(&self) -> u8 {
+        self.num_ops
+    }
+
+    /// Returns the text of this instruction (e.g., `push.1`).
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Returns the labels of the procedures inlined via `exec` which contain this instruction,
+    /// starting with the outermost one. Procedures invoked via `call` or `syscall` are not listed
+    /// since their code is not inlined into the caller.
+    pub fn procs(&self) -> &[String] {
+        &self.procs
+    }
+
+    /// Returns the location of this instruction in the source code, if known.
+    pub fn location(&self) -> Option<&SourceLocation> {
+        self.location.as_ref()
+    }
+
+    /// Returns true if the operation at the specified index of the span block is a part of this
+    /// instruction.
+    pub fn contains(&self, op_idx: usize) -> bool {
+        op_idx >= self.op_idx && op_idx < self.op_idx + self.num_ops as usize
+    }
+}
+
+// SOURCE MAP BUILDER
+// ================================================================================================
+
+/// Collects the assembly instructions of all visited span blocks.
+#[derive(Default)]
+struct Builder {
+    spans: Vec<SpanSourceMap>,
+}
+
+impl Visitor for Builder {
+    fn visit_span(&mut self, span: &Span) {
+        // decorators are sorted by the index of the operation they precede; the chain of
+        // procedures applies to all instructions up to the next chain
+        let mut procs: &[String] = &[];
+        let instructions = span
+            .decorators()
+            .iter()
+            .filter_map(|(op_idx, decorator)| match decorator {
+                Decorator::ProcPath(path) => {
+                    procs = path;
+                    None
+                }
+                Decorator::AsmOp(asmop) => Some(InstructionInfo {
+                    op_idx: *op_idx,
+                    num_ops: asmop.num_cycles(),
+                    text: asmop.op().clone(),
+                    procs: procs.to_vec(),
+                    location: asmop.location().cloned(),
+                }),
+                _ => None,
+            })
+            .collect();
+
+        self.spans.push(SpanSourceMap {
+            hash: hash_bytes(span.hash()),
+            instructions,
+        });
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the 32-byte encoding of the specified hash; this is the same encoding as the one used
+/// for serializing digests.
+fn hash_bytes(hash: Digest) -> [u8; 32] {
+    Word::from(hash).into_bytes()
+}
@@ -0,0 +1,174 @@
+use super::{AdviceSet, Felt, ProgramInputs, StarkField, Word, MIN_STACK_DEPTH};
+use proptest::{
+    arbitrary::Arbitrary,
+    collection::{btree_map, vec},
+    prelude::*,
+};
+
+// CONSTANTS
+// ================================================================================================
+
+/// Maximum depth of Merkle trees generated by [advice_set].
+pub const MAX_TREE_DEPTH: u32 = 8;
+
+/// Maximum number of advice tape values generated by [program_inputs].
+pub const MAX_ADVICE_TAPE_LEN: usize = 64;
+
+/// Maximum number of advice sets generated by [program_inputs].
+pub const MAX_ADVICE_SETS: usize = 3;
+
+// FIELD ELEMENTS
+// ================================================================================================
+
+/// Returns a strategy which generates field elements uniformly from the entire field.
+pub fn felt() -> impl Strategy<Value = Felt> {
+    (0..Felt::MODULUS).prop_map(Felt::new)
+}
+
+/// Returns a strategy which generates field elements with values in the range [0, 2^32).
+pub fn u32_felt() -> impl Strategy<Value = Felt> {
+    any::<u32>().prop_map(|value| Felt::new(value as u64))
+}
+
+/// Returns a strategy which generates words consisting of arbitrary field elements.
+pub fn word() -> impl Strategy<Value = Word> {
+    [felt(), felt(), felt(), felt()]
+}
+
+/// Returns a strategy which generates canonical `u64` representations of field elements. This is
+/// the form in which values are passed to [ProgramInputs] constructors.
+pub fn field_value() -> impl Strategy<Value = u64> {
+    0..Felt::MODULUS
+}
+
+// MERKLE STRUCTURES
+// ================================================================================================
+
+/// Returns a strategy which generates advice sets backed by Merkle trees with depth in the
+/// specified range. The depth must be at least 1 since a Merkle tree must have at least two leaves.
+pub fn merkle_tree(depth: core::ops::RangeInclusive<u32>) -> impl Strategy<Value = AdviceSet> {
+    assert!(*depth.start() >= 1, "Merkle tree depth must be at least 1");
+    depth
+        .prop_flat_map(|depth| vec(word(), 1usize << depth))
+        .prop_map(|leaves| AdviceSet::new_merkle_tree(leaves).expect("invalid Merkle tree"))
+}
+
+/// Returns a strategy which generates advice sets backed by sparse Merkle trees with depth in the
+/// specified range, populated with up to `max_leaves` leaves at arbitrary positions.
+pub fn sparse_merkle_tree(
+    depth: core::ops::RangeInclusive<u32>,
+    max_leaves: usize,
+) -> impl Strategy<Value = AdviceSet> {
+    assert!(
+        *depth.end() <= 63,
+        "sparse Merkle tree depth must be at most 63"
+    );
+    depth
+        .prop_flat_map(move |depth| {
+            let leaves = btree_map(0..(1u64 << depth), word(), 0..=max_leaves);
+            (Just(depth), leaves)
+        })
+        .prop_map(|(depth, leaves)| {
+            let (keys, values) = leaves.into_iter().unzip();
+            AdviceSet::new_sparse_merkle_tree(keys, values, depth)
+                .expect("invalid sparse Merkle tree")
+        })
+}
+
+/// Returns a strategy which generates Merkle trees and sparse Merkle trees with depth of at most
+/// [MAX_TREE_DEPTH].
+pub fn advice_set() -> impl Strategy<Value = AdviceSet> {
+    prop_oneof![
+        merkle_tree(1..=MAX_TREE_DEPTH),
+        sparse_merkle_tree(1..=MAX_TREE_DEPTH, 16),
+    ]
+}
+
+impl Arbitrary for AdviceSet {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_args: Self::Parameters) -> Self::Strategy {
+        advice_set().boxed()
+    }
+}
+
+// PROGRAM INPUTS
+// ================================================================================================
+
+/// Returns a strategy which generates initial stack values; at most 16 values are generated.
+pub fn stack_inputs() -> impl Strategy<Value = Vec<u64>> {
+    vec(field_value(), 0..=MIN_STACK_DEPTH)
+}
+
+/// Returns a strategy which generates program inputs with arbitrary initial stack values, up to
+/// [MAX_ADVICE_TAPE_LEN] advice tape values, and up to [MAX_ADVICE_SETS] advice sets.
+pub fn program_inputs() -> impl Strategy<Value = ProgramInputs> {
+    (
+        stack_inputs(),
+        vec(field_value(), 0..=MAX_ADVICE_TAPE_LEN),
+        vec(advice_set(), 0..=MAX_ADVICE_SETS),
+    )
+        .prop_filter_map("duplicate advice set roots", |(stack, tape, sets)| {
+            ProgramInputs::new(&stack, &tape, sets).ok()
+        })
+}
+
+impl Arbitrary for ProgramInputs {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_args: Self::Parameters) -> Self::Strategy {
+        program_inputs().boxed()
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::{merkle_tree, program_inputs, sparse_merkle_tree, Felt, StarkField};
+    use crate::chiplets::hasher;
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn merkle_tree_paths_are_consistent(tree in merkle_tree(1..=4)) {
+            let depth = tree.depth();
+            prop_assert!((1..=4).contains(&depth));
+            for index in 0..(1u64 << depth) {
+                let path = tree.get_path(depth, index).unwrap();
+                prop_assert_eq!(path.len(), depth as usize);
+            }
+        }
+
+        #[test]
+        fn sparse_merkle_tree_paths_are_consistent(tree in sparse_merkle_tree(1..=6, 8)) {
+            let depth = tree.depth();
+            prop_assert!((1..=6).contains(&depth));
+
+            // the path to every non-empty leaf leads to the root of the tree
+            for index in 0..(1u64 << depth) {
+                if let Ok(path) = tree.get_path(depth, index) {
+                    prop_assert_eq!(path.len(), depth as usize);
+                    let leaf = tree.get_node(depth, index).unwrap();
+                    let root = path.iter().enumerate().fold(leaf, |node, (i, sibling)| {
+                        if (index >> i) & 1 == 1 {
+                            hasher::merge(&[(*sibling).into(), node.into()]).into()
+                        } else {
+                            hasher::merge(&[node.into(), (*sibling).into()]).into()
+                        }
+                    });
+                    prop_assert_eq!(tree.root(), root);
+                }
+            }
+        }
+
+        #[test]
+        fn program_inputs_are_valid(inputs in program_inputs()) {
+            prop_assert!(inputs.stack_init().len() <= 16);
+            prop_assert!(inputs.advice_tape().iter().all(|v| v.as_int() < Felt::MODULUS));
+        }
+    }
+}
@@ -0,0 +1,261 @@
+use super::{
+    collections::Vec, string::String, ByteReader, ByteWriter, Deserializable, DeserializationError,
+    Serializable,
+};
+use crate::{Felt, Word};
+
+// BYTE ENCODING HELPERS
+// ================================================================================================
+//
+// Serialization of VM data structures builds on the [Serializable] and [Deserializable] traits;
+// the helpers below cover values for which these traits are not implemented:
+// - `usize` values are encoded as `u64` values, and `bool` values as a single byte (0 or 1).
+// - Sequences are encoded as their length (a `u64` value) followed by the encodings of their
+//   items; thus, sequences can be decoded without knowing their length upfront.
+// - Optional values are encoded as a `bool` flag followed by the encoding of the value (if any).
+
+/// Writes the specified `usize` value into the target.
+pub fn write_usize<W: ByteWriter>(target: &mut W, value: usize) {
+    target.write_u64(value as u64);
+}
+
+/// Reads a `usize` value from the source.
+///
+/// # Errors
+/// Returns an error if the source does not contain enough bytes, or if the value does not fit
+/// into a `usize` value.
+pub fn read_usize<R: ByteReader>(source: &mut R) -> Result<usize, DeserializationError> {
+    let value = source.read_u64()?;
+    usize::try_from(value)
+        .map_err(|_| DeserializationError::InvalidValue(format!("{} is not a valid size", value)))
+}
+
+/// Writes the specified `bool` value into the target.
+pub fn write_bool<W: ByteWriter>(target: &mut W, value: bool) {
+    target.write_u8(value as u8);
+}
+
+/// Reads a `bool` value from the source.
+///
+/// # Errors
+/// Returns an error if the source does not contain enough bytes, or if the next byte is neither
+/// 0 nor 1.
+pub fn read_bool<R: ByteReader>(source: &mut R) -> Result<bool, DeserializationError> {
+    match source.read_u8()? {
+        0 => Ok(false),
+        1 => Ok(true),
+        value => Err(DeserializationError::InvalidValue(format!(
+            "{} is not a valid boolean value",
+            value
+        ))),
+    }
+}
+
+/// Writes the specified word into the target.
+pub fn write_word<W: ByteWriter>(target: &mut W, word: &Word) {
+    Felt::write_batch_into(word, target);
+}
+
+/// Reads a word from the source.
+///
+/// # Errors
+/// Returns an error if the source does not contain enough bytes, or if any of the elements is
+/// not a valid field element.
+pub fn read_word<R: ByteReader>(source: &mut R) -> Result<Word, DeserializationError> {
+    Ok([
+        Felt::read_from(source)?,
+        Felt::read_from(source)?,
+        Felt::read_from(source)?,
+        Felt::read_from(source)?,
+    ])
+}
+
+/// Writes the specified string into the target.
+pub fn write_string<W: ByteWriter>(target: &mut W, value: &str) {
+    write_usize(target, value.len());
+    target.write_u8_slice(value.as_bytes());
+}
+
+/// Reads a string from the source.
+///
+/// # Errors
+/// Returns an error if the source does not contain enough bytes, or if the bytes are not valid
+/// UTF-8.
+pub fn read_string<R: ByteReader>(source: &mut R) -> Result<String, DeserializationError> {
+    let len = read_usize(source)?;
+    let bytes = source.read_u8_vec(len)?;
+    String::from_utf8(bytes)
+        .map_err(|err| DeserializationError::InvalidValue(format!("invalid string: {}", err)))
+}
+
+/// Writes the specified sequence into the target; every item is written using the provided
+/// function.
+pub fn write_seq<W, T, I, F>(target: &mut W, items: I, mut write_item: F)
+where
+    W: ByteWriter,
+    I: IntoIterator<Item = T>,
+    I::IntoIter: ExactSizeIterator,
+    F: FnMut(&mut W, T),
+{
+    let items = items.into_iter();
+    write_usize(target, items.len());
+    for item in items {
+        write_item(target, item);
+    }
+}
+
+/// Reads a sequence from the source; every item is read using the provided function.
+///
+/// # Errors
+/// Returns an error if the length of the sequence cannot be read, or if reading any of the items
+/// fails.
+pub fn read_seq<R, T, F>(source: &mut R, mut read_item: F) -> Result<Vec<T>, DeserializationError>
+where
+    R: ByteReader,
+    F: FnMut(&mut R) -> Result<T, DeserializationError>,
+{
+    // the length is not used to pre-allocate the vector, as it may come from untrusted input
+    let len = read_usize(source)?;
+    let mut result = Vec::new();
+    for _ in 0..len {
+        result.push(read_item(source)?);
+    }
+    Ok(result)
+}
+
+/// Writes the specified sequence of serializable items into the target.
+pub fn write_vec<W: ByteWriter, T: Serializable>(target: &mut W, items: &[T]) {
+    write_seq(target, items, |target, item| item.write_into(target));
+}
+
+/// Reads a sequence of deserializable items from the source.
+///
+/// # Errors
+/// Returns an error if the length of the sequence cannot be read, or if reading any of the items
+/// fails.
+pub fn read_vec<R: ByteReader, T: Deserializable>(
+    source: &mut R,
+) -> Result<Vec<T>, DeserializationError> {
+    read_seq(source, T::read_from)
+}
+
+/// Writes the specified columns of field elements into the target.
+pub fn write_columns<W: ByteWriter, const N: usize>(target: &mut W, columns: &[Vec<Felt>; N]) {
+    for column in columns.iter() {
+        write_vec(target, column);
+    }
+}
+
+/// Reads N columns of field elements from the source.
+///
+/// # Errors
+/// Returns an error if reading any of the columns fails.
+pub fn read_columns<R: ByteReader, const N: usize>(
+    source: &mut R,
+) -> Result<[Vec<Felt>; N], DeserializationError> {
+    let columns = (0..N)
+        .map(|_| read_vec(source))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(columns
+        .try_into()
+        .expect("failed to convert vector to array"))
+}
+
+/// Writes the specified optional value into the target; the value (if any) is written using the
+/// provided function.
+pub fn write_option<W, T, F>(target: &mut W, value: Option<T>, write_value: F)
+where
+    W: ByteWriter,
+    F: FnOnce(&mut W, T),
+{
+    write_bool(target, value.is_some());
+    if let Some(value) = value {
+        write_value(target, value);
+    }
+}
+
+/// Reads an optional value from the source; the value (if any) is read using the provided
+/// function.
+///
+/// # Errors
+/// Returns an error if the presence flag cannot be read, or if reading the value fails.
+pub fn read_option<R, T, F>(
+    source: &mut R,
+    read_value: F,
+) -> Result<Option<T>, DeserializationError>
+where
+    R: ByteReader,
+    F: FnOnce(&mut R) -> Result<T, DeserializationError>,
+{
+    if read_bool(source)? {
+        read_value(source).map(Some)
+    } else {
+        Ok(None)
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        read_bool, read_columns, read_option, read_seq, read_string, read_usize, read_word,
+        write_bool, write_columns, write_option, write_seq, write_string, write_usize, write_word,
+        ByteReader, ByteWriter, DeserializationError, Felt, Vec,
+    };
+    use winter_utils::SliceReader;
+
+    #[test]
+    fn round_trip() {
+        let word = [1, 2, 3, 4].map(Felt::new);
+        let columns = [vec![Felt::new(5)], vec![], vec![Felt::new(6), Felt::new(7)]];
+        let pairs = vec![(1_u32, true), (2, false)];
+
+        let mut bytes = Vec::new();
+        write_usize(&mut bytes, 42);
+        write_bool(&mut bytes, true);
+        write_word(&mut bytes, &word);
+        write_string(&mut bytes, "mem_load");
+        write_columns(&mut bytes, &columns);
+        write_seq(&mut bytes, &pairs, |target, (a, b)| {
+            target.write_u32(*a);
+            write_bool(target, *b);
+        });
+        write_option(&mut bytes, Some(7_u32), |target, value| {
+            target.write_u32(value)
+        });
+        write_option(&mut bytes, None::<u32>, |target, value| {
+            target.write_u32(value)
+        });
+
+        let mut source = SliceReader::new(&bytes);
+        assert_eq!(42, read_usize(&mut source).unwrap());
+        assert!(read_bool(&mut source).unwrap());
+        assert_eq!(word, read_word(&mut source).unwrap());
+        assert_eq!("mem_load", read_string(&mut source).unwrap());
+        assert_eq!(columns, read_columns::<_, 3>(&mut source).unwrap());
+        let result = read_seq(&mut source, |source| {
+            Ok((source.read_u32()?, read_bool(source)?))
+        });
+        assert_eq!(pairs, result.unwrap());
+        let value = read_option(&mut source, |source| source.read_u32());
+        assert_eq!(Some(7), value.unwrap());
+        let value = read_option(&mut source, |source| source.read_u32());
+        assert_eq!(None, value.unwrap());
+        assert!(!source.has_more_bytes());
+    }
+
+    #[test]
+    fn invalid_values() {
+        let bytes = [2_u8];
+        let result = read_bool(&mut SliceReader::new(&bytes));
+        assert!(matches!(result, Err(DeserializationError::InvalidValue(_))));
+
+        // a sequence which is longer than the source
+        let mut bytes = Vec::new();
+        write_usize(&mut bytes, usize::MAX);
+        let result = read_seq(&mut SliceReader::new(&bytes), |source| source.read_u8());
+        assert!(matches!(result, Err(DeserializationError::UnexpectedEOF)));
+    }
+}
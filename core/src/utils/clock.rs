@@ -0,0 +1,85 @@
+use core::time::Duration;
+
+// CLOCK
+// ================================================================================================
+
+/// A source of time used to measure how long long-running tasks (e.g., proof generation) take.
+///
+/// Time is never used in a way which affects the results of program execution or proof
+/// generation. However, access to time differs between environments (e.g., it may be unavailable
+/// in WASI or SGX enclaves, and is undesirable in deterministic tests), and thus components which
+/// report timings accept a clock instead of reading the system time directly.
+pub trait Clock {
+    /// Returns the time elapsed since an arbitrary fixed point in the past. Values returned by
+    /// subsequent invocations must not decrease.
+    fn now(&self) -> Duration;
+
+    /// Returns the time elapsed since the specified time previously returned by [Clock::now()].
+    fn elapsed(&self, since: Duration) -> Duration {
+        self.now().saturating_sub(since)
+    }
+}
+
+/// A clock which always returns the same time, and thus reports all durations as zero.
+///
+/// This clock is usable in any environment, and makes timing reports deterministic.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct FixedClock(Duration);
+
+impl FixedClock {
+    /// Returns a clock which always returns the specified time.
+    pub fn new(time: Duration) -> Self {
+        Self(time)
+    }
+}
+
+impl Clock for FixedClock {
+    fn now(&self) -> Duration {
+        self.0
+    }
+}
+
+/// A clock which reads the monotonic time of the operating system; the time is measured from the
+/// moment the clock was instantiated.
+#[cfg(feature = "std")]
+#[derive(Copy, Clone, Debug)]
+pub struct SystemClock {
+    origin: std::time::Instant,
+}
+
+#[cfg(feature = "std")]
+impl Default for SystemClock {
+    fn default() -> Self {
+        Self {
+            origin: std::time::Instant::now(),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Clock for SystemClock {
+    fn now(&self) -> Duration {
+        self.origin.elapsed()
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::{Clock, Duration, FixedClock, SystemClock};
+
+    #[test]
+    fn clocks() {
+        let clock = FixedClock::new(Duration::from_secs(5));
+        let now = clock.now();
+        assert_eq!(Duration::from_secs(5), now);
+        assert_eq!(Duration::ZERO, clock.elapsed(now));
+        assert_eq!(Duration::ZERO, clock.elapsed(Duration::from_secs(6)));
+
+        let clock = SystemClock::default();
+        let now = clock.now();
+        assert!(clock.now() >= now);
+    }
+}
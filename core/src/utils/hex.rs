@@ -0,0 +1,159 @@
+use super::{string::String, IntoBytes};
+use crate::{chiplets::hasher::Digest, errors::HexParseError, Felt, StarkField, Word};
+
+// CANONICAL HEX ENCODING
+// ================================================================================================
+//
+// Field elements, words, and digests are encoded as lowercase hex strings with a `0x` prefix:
+// - A field element is encoded as its canonical integer value written with exactly 16 hex digits
+//   (most significant digit first). For example, ONE is encoded as `0x0000000000000001`.
+// - A word (or a digest) is encoded as its 32 bytes written with exactly 64 hex digits, where every
+//   element occupies 8 bytes in little-endian order, starting with the first element of the word.
+//   This is the same byte layout as the one used for serializing digests (e.g., program hashes).
+//
+// Parsing is strict: a string is accepted only if it is exactly the encoding of some value. Thus,
+// strings without the prefix, with uppercase digits, with a different number of digits, or
+// encoding integers which are not valid field elements are rejected.
+
+/// Returns the canonical hex encoding of the specified field element.
+pub fn felt_to_hex(value: Felt) -> String {
+    format!("0x{:016x}", value.as_int())
+}
+
+/// Parses a field element from its canonical hex encoding.
+///
+/// # Errors
+/// Returns an error if the string is not a canonical encoding of a field element.
+pub fn felt_from_hex(hex: &str) -> Result<Felt, HexParseError> {
+    let mut bytes = [0; 8];
+    decode(hex, &mut bytes)?;
+    to_felt(u64::from_be_bytes(bytes))
+}
+
+/// Returns the canonical hex encoding of the specified word.
+pub fn word_to_hex(word: Word) -> String {
+    let mut result = String::with_capacity(66);
+    result.push_str("0x");
+    for byte in word.into_bytes() {
+        result.push_str(&format!("{:02x}", byte));
+    }
+    result
+}
+
+/// Parses a word from its canonical hex encoding.
+///
+/// # Errors
+/// Returns an error if the string is not a canonical encoding of a word.
+pub fn word_from_hex(hex: &str) -> Result<Word, HexParseError> {
+    let mut bytes = [0; 32];
+    decode(hex, &mut bytes)?;
+
+    let mut result = [Felt::default(); 4];
+    for (element, chunk) in result.iter_mut().zip(bytes.chunks_exact(8)) {
+        let value = u64::from_le_bytes(chunk.try_into().expect("chunk has 8 bytes"));
+        *element = to_felt(value)?;
+    }
+    Ok(result)
+}
+
+/// Returns the canonical hex encoding of the specified digest.
+pub fn digest_to_hex(digest: Digest) -> String {
+    word_to_hex(digest.into())
+}
+
+/// Parses a digest from its canonical hex encoding.
+///
+/// # Errors
+/// Returns an error if the string is not a canonical encoding of a digest.
+pub fn digest_from_hex(hex: &str) -> Result<Digest, HexParseError> {
+    word_from_hex(hex).map(Digest::from)
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Decodes a `0x` prefixed string of lowercase hex digits into the specified buffer; the string
+/// must contain exactly two digits for every byte of the buffer.
+fn decode(hex: &str, bytes: &mut [u8]) -> Result<(), HexParseError> {
+    let digits = hex.strip_prefix("0x").ok_or(HexParseError::MissingPrefix)?;
+    if let Some(c) = digits.chars().find(|c| !matches!(c, '0'..='9' | 'a'..='f')) {
+        return Err(HexParseError::InvalidCharacter(c));
+    }
+    if digits.len() != bytes.len() * 2 {
+        return Err(HexParseError::InvalidLength(bytes.len() * 2, digits.len()));
+    }
+
+    for (byte, pair) in bytes.iter_mut().zip(digits.as_bytes().chunks_exact(2)) {
+        *byte = (decode_digit(pair[0]) << 4) | decode_digit(pair[1]);
+    }
+    Ok(())
+}
+
+/// Returns the value of a lowercase hex digit.
+fn decode_digit(digit: u8) -> u8 {
+    match digit {
+        b'0'..=b'9' => digit - b'0',
+        _ => digit - b'a' + 10,
+    }
+}
+
+fn to_felt(value: u64) -> Result<Felt, HexParseError> {
+    if value >= Felt::MODULUS {
+        return Err(HexParseError::NotFieldElement(value));
+    }
+    Ok(Felt::new(value))
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        digest_from_hex, digest_to_hex, felt_from_hex, felt_to_hex, word_from_hex, word_to_hex,
+        Digest, Felt, HexParseError, StarkField,
+    };
+
+    #[test]
+    fn felt_encoding() {
+        assert_eq!("0x0000000000000001", felt_to_hex(Felt::new(1)));
+        let max = Felt::new(Felt::MODULUS - 1);
+        assert_eq!("0xffffffff00000000", felt_to_hex(max));
+        assert_eq!(max, felt_from_hex("0xffffffff00000000").unwrap());
+
+        // parsing is strict
+        let cases = [
+            ("0000000000000001", HexParseError::MissingPrefix),
+            ("0x1", HexParseError::InvalidLength(16, 1)),
+            ("0x00000000000000001", HexParseError::InvalidLength(16, 17)),
+            ("0x000000000000000A", HexParseError::InvalidCharacter('A')),
+            ("0x00000000000000é", HexParseError::InvalidCharacter('é')),
+            (
+                "0xffffffff00000001",
+                HexParseError::NotFieldElement(Felt::MODULUS),
+            ),
+        ];
+        for (hex, error) in cases {
+            assert_eq!(Err(error), felt_from_hex(hex), "{}", hex);
+        }
+    }
+
+    #[test]
+    fn word_encoding() {
+        let word = [1, 2, 3, Felt::MODULUS - 1].map(Felt::new);
+        let hex = word_to_hex(word);
+        assert_eq!(66, hex.len());
+        assert!(hex.starts_with("0x01000000000000000200000000000000"));
+        assert_eq!(word, word_from_hex(&hex).unwrap());
+
+        // digests are encoded in the same way as words
+        let digest = Digest::from(word);
+        assert_eq!(hex, digest_to_hex(digest));
+        assert_eq!(digest, digest_from_hex(&hex).unwrap());
+
+        // every element must be a valid field element
+        let hex = format!("0x{}", "f".repeat(64));
+        let error = HexParseError::NotFieldElement(u64::MAX);
+        assert_eq!(Err(error), word_from_hex(&hex));
+    }
+}
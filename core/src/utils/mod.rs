@@ -2,6 +2,17 @@ use super::{Felt, StarkField};
 use core::{fmt::Debug, ops::Range};
 use winter_utils::collections::Vec;
 
+pub mod bytes;
+pub mod hex;
+
+mod clock;
+#[cfg(feature = "std")]
+pub use clock::SystemClock;
+pub use clock::{Clock, FixedClock};
+
+#[cfg(feature = "serde")]
+pub mod serde;
+
 // FEATURE BASED RE-EXPORT
 // ================================================================================================
 
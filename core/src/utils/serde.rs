@@ -0,0 +1,140 @@
+use super::hex::{
+    digest_from_hex, digest_to_hex, felt_from_hex, felt_to_hex, word_from_hex, word_to_hex,
+};
+use crate::errors::HexParseError;
+use ::serde::{de, Deserializer, Serializer};
+use core::{fmt, marker::PhantomData};
+
+// SERDE ENCODING
+// ================================================================================================
+//
+// The modules below serialize field elements, words, and digests into their canonical hex
+// encoding (see [super::hex]), and are meant to be used with the `#[serde(with = "...")]` field
+// attribute. For example:
+//
+//     #[derive(Serialize, Deserialize)]
+//     struct Commitment {
+//         #[serde(with = "vm_core::utils::serde::digest")]
+//         root: Digest,
+//         #[serde(with = "vm_core::utils::serde::felt")]
+//         num_leaves: Felt,
+//     }
+
+/// Serialization of a field element into its canonical hex encoding.
+pub mod felt {
+    use super::{felt_from_hex, felt_to_hex, Deserializer, HexVisitor, Serializer};
+    use crate::Felt;
+
+    pub fn serialize<S: Serializer>(value: &Felt, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&felt_to_hex(*value))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Felt, D::Error> {
+        deserializer.deserialize_str(HexVisitor::new("a field element", felt_from_hex))
+    }
+}
+
+/// Serialization of a word into its canonical hex encoding.
+pub mod word {
+    use super::{word_from_hex, word_to_hex, Deserializer, HexVisitor, Serializer};
+    use crate::Word;
+
+    pub fn serialize<S: Serializer>(value: &Word, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&word_to_hex(*value))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Word, D::Error> {
+        deserializer.deserialize_str(HexVisitor::new("a word", word_from_hex))
+    }
+}
+
+/// Serialization of a digest into its canonical hex encoding.
+pub mod digest {
+    use super::{digest_from_hex, digest_to_hex, Deserializer, HexVisitor, Serializer};
+    use crate::chiplets::hasher::Digest;
+
+    pub fn serialize<S: Serializer>(value: &Digest, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&digest_to_hex(*value))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Digest, D::Error> {
+        deserializer.deserialize_str(HexVisitor::new("a digest", digest_from_hex))
+    }
+}
+
+// HEX VISITOR
+// ================================================================================================
+
+/// Deserializes a value from a string holding its canonical hex encoding.
+struct HexVisitor<T> {
+    expecting: &'static str,
+    parse: fn(&str) -> Result<T, HexParseError>,
+    _value: PhantomData<T>,
+}
+
+impl<T> HexVisitor<T> {
+    fn new(expecting: &'static str, parse: fn(&str) -> Result<T, HexParseError>) -> Self {
+        Self {
+            expecting,
+            parse,
+            _value: PhantomData,
+        }
+    }
+}
+
+impl<'de, T> de::Visitor<'de> for HexVisitor<T> {
+    type Value = T;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} encoded as a 0x prefixed hex string", self.expecting)
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<T, E> {
+        (self.parse)(value).map_err(E::custom)
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use crate::{chiplets::hasher::Digest, Felt, Word};
+    use serde_derive::{Deserialize, Serialize};
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Values {
+        #[serde(with = "super::felt")]
+        felt: Felt,
+        #[serde(with = "super::word")]
+        word: Word,
+        #[serde(with = "super::digest")]
+        digest: Digest,
+    }
+
+    #[test]
+    fn serde_roundtrip() {
+        let values = Values {
+            felt: Felt::new(255),
+            word: [1, 0, 0, 256].map(Felt::new),
+            digest: Digest::from([Felt::new(0xabcd), Felt::new(0), Felt::new(0), Felt::new(0)]),
+        };
+        let json = serde_json::to_string(&values).unwrap();
+        let expected = format!(
+            r#"{{"felt":"0x00000000000000ff","word":"0x{}{}{}{}","digest":"0x{}{}"}}"#,
+            "0100000000000000",
+            "0000000000000000",
+            "0000000000000000",
+            "0001000000000000",
+            "cdab000000000000",
+            "0".repeat(48)
+        );
+        assert_eq!(expected, json);
+        assert_eq!(values, serde_json::from_str(&json).unwrap());
+
+        // invalid encodings are rejected with a descriptive error
+        let json = json.replace("0x00000000000000ff", "0xFF");
+        let error = serde_json::from_str::<Values>(&json).unwrap_err();
+        assert!(error.to_string().contains("invalid character 'F'"));
+    }
+}
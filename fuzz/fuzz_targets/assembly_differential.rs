@@ -0,0 +1,13 @@
+//! Feeds arbitrary sources into all front ends of the assembler and checks that they agree on
+//! them (see `miden_test::check_source()`).
+
+#![no_main]
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(source) = std::str::from_utf8(data) {
+        if let Err(divergence) = miden_test::check_source(source) {
+            panic!("{}", divergence);
+        }
+    }
+});
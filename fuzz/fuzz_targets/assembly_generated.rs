@@ -0,0 +1,12 @@
+//! Generates a program from the grammar of Miden assembly (see `miden_test::source_strategy()`)
+//! using the input as a seed, and checks that all front ends of the assembler agree on it.
+
+#![no_main]
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|seed: u64| {
+    let source = miden_test::generate_source(seed);
+    if let Err(divergence) = miden_test::check_source(&source) {
+        panic!("{} for source:\n{}", divergence, source);
+    }
+});
@@ -0,0 +1,362 @@
+use assembly::{locate_token, Assembler, ProcedureDecl, SourceOutline};
+use lsp_types::{
+    Diagnostic, DiagnosticSeverity, DocumentSymbol, Hover, HoverContents, MarkupContent,
+    MarkupKind, NumberOrString, Position, Range, SymbolKind,
+};
+use vm_core::Library;
+
+// CONSTANTS
+// ================================================================================================
+
+/// Name of the diagnostics source reported to the client.
+const DIAGNOSTICS_SOURCE: &str = "miden-assembly";
+
+/// Delimiter between the module name and the procedure label in imported procedure invocations.
+const MODULE_PATH_DELIM: &str = "::";
+
+// DIAGNOSTICS
+// ================================================================================================
+
+/// Compiles the provided source and returns the resulting errors as diagnostics.
+///
+/// Sources containing a `begin` block are compiled as programs; all other sources are checked as
+/// library modules.
+pub fn diagnostics(assembler: &Assembler, source: &str) -> Vec<Diagnostic> {
+    let result = if SourceOutline::parse(source).is_program() {
+        assembler.compile(source).map(|_| ())
+    } else {
+        assembler.check_module(source)
+    };
+
+    match result {
+        Ok(()) => Vec::new(),
+        Err(error) => {
+            // errors without a location (e.g., unexpected end of file) are reported at the end
+            // of the source
+            let range = token_range(source, error.step()).unwrap_or_else(|| {
+                let end = end_position(source);
+                Range::new(end, end)
+            });
+            vec![Diagnostic {
+                range,
+                severity: Some(DiagnosticSeverity::ERROR),
+                code: Some(NumberOrString::String(error.error_code().to_string())),
+                source: Some(DIAGNOSTICS_SOURCE.to_string()),
+                message: error.message().clone(),
+                ..Default::default()
+            }]
+        }
+    }
+}
+
+// DOCUMENT SYMBOLS
+// ================================================================================================
+
+/// Returns imports, constants, and procedures declared in the provided source.
+#[allow(deprecated)]
+pub fn document_symbols(source: &str) -> Vec<DocumentSymbol> {
+    let outline = SourceOutline::parse(source);
+    let mut symbols = Vec::new();
+
+    let mut add_symbol = |name: &str, detail: Option<String>, kind, pos, end_pos: Option<usize>| {
+        let selection_range = match token_range(source, pos) {
+            Some(range) => range,
+            None => return,
+        };
+        let range = end_pos
+            .and_then(|end_pos| token_range(source, end_pos))
+            .map(|end| Range::new(selection_range.start, end.end))
+            .unwrap_or(selection_range);
+        symbols.push(DocumentSymbol {
+            name: name.to_string(),
+            detail,
+            kind,
+            tags: None,
+            deprecated: None,
+            range,
+            selection_range,
+            children: None,
+        });
+    };
+
+    for import in outline.imports() {
+        add_symbol(import.path(), None, SymbolKind::MODULE, import.pos(), None);
+    }
+    for constant in outline.constants() {
+        add_symbol(
+            constant.name(),
+            None,
+            SymbolKind::CONSTANT,
+            constant.pos(),
+            None,
+        );
+    }
+    for proc in outline.procedures() {
+        let detail = Some(proc.signature());
+        add_symbol(
+            proc.label(),
+            detail,
+            SymbolKind::FUNCTION,
+            proc.pos(),
+            proc.end_pos(),
+        );
+    }
+
+    symbols
+}
+
+// PROCEDURE RESOLUTION
+// ================================================================================================
+
+/// A procedure declaration referenced from a source.
+#[derive(Debug)]
+pub struct ResolvedProcedure {
+    /// Declaration of the procedure.
+    pub decl: ProcedureDecl,
+    /// Path of the module declaring the procedure, or None if the procedure is declared in the
+    /// source from which it is referenced.
+    pub module_path: Option<String>,
+    /// Location of the procedure header in the declaring source.
+    pub range: Range,
+}
+
+/// Resolves the procedure invoked or declared by the token at the specified position.
+///
+/// Invocations of imported procedures (e.g., `exec.u64::add`) are resolved via `use` imports of
+/// the source against the provided library.
+pub fn resolve_procedure(
+    source: &str,
+    position: Position,
+    library: &dyn Library,
+) -> Option<ResolvedProcedure> {
+    let label = proc_label(token_at(source, position)?)?;
+    let outline = SourceOutline::parse(source);
+
+    match label.split_once(MODULE_PATH_DELIM) {
+        None => {
+            let decl = outline.get_procedure(label)?.clone();
+            let range = token_range(source, decl.pos())?;
+            Some(ResolvedProcedure {
+                decl,
+                module_path: None,
+                range,
+            })
+        }
+        Some((module_name, label)) => {
+            let module_path = outline.get_import(module_name)?.path();
+            let module_source = library.get_module_source(module_path).ok()?;
+            let decl = SourceOutline::parse(module_source)
+                .get_procedure(label)
+                .filter(|decl| decl.is_export())?
+                .clone();
+            let range = token_range(module_source, decl.pos())?;
+            Some(ResolvedProcedure {
+                decl,
+                module_path: Some(module_path.to_string()),
+                range,
+            })
+        }
+    }
+}
+
+/// Returns a description of the procedure invoked or declared by the token at the specified
+/// position. The description contains the procedure signature, the number of its locals, the
+/// module in which it is declared, and its documentation.
+pub fn hover(source: &str, position: Position, library: &dyn Library) -> Option<Hover> {
+    let proc = resolve_procedure(source, position, library)?;
+
+    let mut contents = format!("```masm\n{}\n```", proc.decl.signature());
+    if proc.decl.num_locals() > 0 {
+        contents.push_str(&format!("\n\nLocals: {}", proc.decl.num_locals()));
+    }
+    if let Some(module_path) = &proc.module_path {
+        contents.push_str(&format!("\n\nDeclared in `{}`", module_path));
+    }
+    if let Some(docs) = proc.decl.docs() {
+        contents.push_str("\n\n---\n\n");
+        contents.push_str(docs);
+    }
+
+    Some(Hover {
+        contents: HoverContents::Markup(MarkupContent {
+            kind: MarkupKind::Markdown,
+            value: contents,
+        }),
+        range: None,
+    })
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the range covering the token at the specified position in the provided source, or
+/// None if the source contains fewer tokens.
+///
+/// Columns are counted in characters; this matches the UTF-16 offsets expected by clients as
+/// long as the source does not contain characters outside of the basic multilingual plane.
+pub fn token_range(source: &str, pos: usize) -> Option<Range> {
+    let (line, column) = locate_token(source, pos)?;
+    let text = source.lines().nth(line - 1)?;
+    let len = text
+        .chars()
+        .skip(column - 1)
+        .take_while(|c| !c.is_whitespace())
+        .count();
+
+    let start = Position::new((line - 1) as u32, (column - 1) as u32);
+    let end = Position::new((line - 1) as u32, (column - 1 + len) as u32);
+    Some(Range::new(start, end))
+}
+
+/// Returns the whitespace-delimited token at the specified position in the provided source, or
+/// None if there is no token at the position or the position is inside a comment.
+fn token_at(source: &str, position: Position) -> Option<&str> {
+    let line = source.lines().nth(position.line as usize)?;
+    let (offset, c) = line.char_indices().nth(position.character as usize)?;
+    if c.is_whitespace() {
+        return None;
+    }
+
+    let start = line[..offset]
+        .rfind(char::is_whitespace)
+        .map(|idx| idx + 1)
+        .unwrap_or(0);
+    let end = line[offset..]
+        .find(char::is_whitespace)
+        .map(|idx| offset + idx)
+        .unwrap_or(line.len());
+    if line[..start].contains('#') || line[start..end].starts_with('#') {
+        return None;
+    }
+    Some(&line[start..end])
+}
+
+/// Returns the label of the procedure invoked or declared by the specified token (e.g., `foo` for
+/// `exec.foo`, `proc.foo.2`, or `export.foo<N>`), or None if the token is not an invocation or a
+/// declaration.
+fn proc_label(token: &str) -> Option<&str> {
+    let (instruction, rest) = token.split_once('.')?;
+    match instruction {
+        "exec" | "proc" | "export" => rest.split(['.', '<']).next(),
+        _ => None,
+    }
+}
+
+/// Returns the position right after the last character of the provided source.
+fn end_position(source: &str) -> Position {
+    let num_lines = source.lines().count();
+    let last_line = source.lines().last().unwrap_or("");
+    Position::new(
+        num_lines.saturating_sub(1) as u32,
+        last_line.chars().count() as u32,
+    )
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::{Assembler, NumberOrString, Position, Range, SymbolKind};
+    use stdlib::StdLibrary;
+
+    const SOURCE: &str = "\
+use.std::math::u64
+
+#! Doubles the top stack item.
+proc.double.1
+    dup add
+end
+
+begin
+    exec.double
+    exec.u64::checked_add
+end";
+
+    #[test]
+    fn diagnostics() {
+        let assembler = Assembler::default();
+        assert!(super::diagnostics(&assembler, SOURCE).is_empty());
+        assert!(super::diagnostics(&assembler, "export.foo add end").is_empty());
+
+        let diagnostics = super::diagnostics(&assembler, "begin\n  push.1 plus\nend");
+        assert_eq!(1, diagnostics.len());
+        assert_eq!(
+            Range::new(Position::new(1, 9), Position::new(1, 13)),
+            diagnostics[0].range
+        );
+        assert_eq!(
+            Some(NumberOrString::String("E005".into())),
+            diagnostics[0].code
+        );
+
+        let diagnostics = super::diagnostics(&assembler, "begin\n  push.1");
+        assert_eq!(
+            Range::new(Position::new(0, 0), Position::new(0, 5)),
+            diagnostics[0].range
+        );
+    }
+
+    #[test]
+    fn document_symbols() {
+        let symbols = super::document_symbols(SOURCE);
+        assert_eq!(2, symbols.len());
+        assert_eq!("std::math::u64", symbols[0].name);
+        assert_eq!(SymbolKind::MODULE, symbols[0].kind);
+
+        assert_eq!("double", symbols[1].name);
+        assert_eq!(Some("proc.double.1".into()), symbols[1].detail);
+        assert_eq!(
+            Range::new(Position::new(3, 0), Position::new(5, 3)),
+            symbols[1].range
+        );
+        assert_eq!(
+            Range::new(Position::new(3, 0), Position::new(3, 13)),
+            symbols[1].selection_range
+        );
+    }
+
+    #[test]
+    fn resolve_procedure() {
+        let library = StdLibrary::default();
+
+        // local procedure invocation and declaration
+        for position in [Position::new(8, 10), Position::new(3, 2)] {
+            let proc = super::resolve_procedure(SOURCE, position, &library).unwrap();
+            assert_eq!("double", proc.decl.label());
+            assert_eq!(None, proc.module_path);
+            assert_eq!(Position::new(3, 0), proc.range.start);
+        }
+
+        // imported procedure invocation
+        let proc = super::resolve_procedure(SOURCE, Position::new(9, 4), &library).unwrap();
+        assert_eq!("checked_add", proc.decl.label());
+        assert_eq!(Some("std::math::u64".into()), proc.module_path);
+
+        // not a procedure reference
+        assert!(super::resolve_procedure(SOURCE, Position::new(4, 5), &library).is_none());
+        assert!(super::resolve_procedure(SOURCE, Position::new(2, 4), &library).is_none());
+    }
+
+    #[test]
+    fn hover() {
+        let library = StdLibrary::default();
+        let hover = super::hover(SOURCE, Position::new(8, 10), &library).unwrap();
+        let contents = match hover.contents {
+            lsp_types::HoverContents::Markup(contents) => contents.value,
+            _ => panic!("expected markup contents"),
+        };
+        assert_eq!(
+            "```masm\nproc.double.1\n```\n\nLocals: 1\n\n---\n\nDoubles the top stack item.",
+            contents
+        );
+
+        let hover = super::hover(SOURCE, Position::new(9, 10), &library).unwrap();
+        let contents = match hover.contents {
+            lsp_types::HoverContents::Markup(contents) => contents.value,
+            _ => panic!("expected markup contents"),
+        };
+        assert!(contents.starts_with("```masm\nexport.checked_add\n```"));
+        assert!(contents.contains("Declared in `std::math::u64`"));
+    }
+}
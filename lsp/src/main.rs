@@ -0,0 +1,33 @@
+use lsp_server::Connection;
+use lsp_types::{
+    HoverProviderCapability, InitializeParams, OneOf, ServerCapabilities,
+    TextDocumentSyncCapability, TextDocumentSyncKind,
+};
+
+mod analysis;
+
+mod server;
+use server::{Server, ServerError};
+
+/// Language server entry point; the server communicates with the client via stdin and stdout.
+pub fn main() -> Result<(), ServerError> {
+    let (connection, io_threads) = Connection::stdio();
+
+    let capabilities = ServerCapabilities {
+        text_document_sync: Some(TextDocumentSyncCapability::Kind(TextDocumentSyncKind::FULL)),
+        hover_provider: Some(HoverProviderCapability::Simple(true)),
+        definition_provider: Some(OneOf::Left(true)),
+        document_symbol_provider: Some(OneOf::Left(true)),
+        ..Default::default()
+    };
+    let params = connection.initialize(serde_json::to_value(capabilities)?)?;
+    let params: InitializeParams = serde_json::from_value(params)?;
+
+    Server::new(&params).run(&connection)?;
+
+    // make sure the connection is dropped before joining the threads so that the writer thread
+    // can terminate
+    drop(connection);
+    io_threads.join()?;
+    Ok(())
+}
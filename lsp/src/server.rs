@@ -0,0 +1,372 @@
+use super::analysis;
+use assembly::Assembler;
+use lsp_server::{
+    Connection, ErrorCode, ExtractError, Message, Notification, Request, RequestId, Response,
+};
+use lsp_types::{
+    notification::{
+        DidChangeTextDocument, DidCloseTextDocument, DidOpenTextDocument,
+        Notification as NotificationTrait, PublishDiagnostics,
+    },
+    request::{DocumentSymbolRequest, GotoDefinition, HoverRequest, Request as RequestTrait},
+    Diagnostic, DocumentSymbolResponse, GotoDefinitionResponse, InitializeParams, Location,
+    Position, PublishDiagnosticsParams, Url,
+};
+use std::{collections::BTreeMap, error::Error, fs, path::PathBuf};
+use stdlib::StdLibrary;
+use vm_core::Library;
+
+// CONSTANTS
+// ================================================================================================
+
+/// Name of the initialization option specifying the directory containing standard library
+/// sources.
+const STDLIB_PATH_OPTION: &str = "stdlibPath";
+
+/// Location of standard library sources relative to the root of the Miden VM repository.
+const STDLIB_SOURCE_DIR: &str = "stdlib/asm";
+
+// TYPE ALIASES
+// ================================================================================================
+
+pub type ServerError = Box<dyn Error + Sync + Send>;
+
+// SERVER
+// ================================================================================================
+
+/// Miden assembly language server.
+///
+/// The server keeps the latest contents of all open documents, and recompiles a document every
+/// time it changes.
+pub struct Server {
+    assembler: Assembler,
+    stdlib: StdLibrary,
+    stdlib_dir: Option<PathBuf>,
+    documents: BTreeMap<Url, String>,
+}
+
+impl Server {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Returns a new server configured according to the provided initialization parameters.
+    ///
+    /// Standard library sources are looked up in the directory specified via the `stdlibPath`
+    /// initialization option. If the option is not provided, and the workspace is a Miden VM
+    /// repository, sources in the `stdlib/asm` directory of the workspace are used.
+    pub fn new(params: &InitializeParams) -> Self {
+        let configured_dir = params
+            .initialization_options
+            .as_ref()
+            .and_then(|options| options.get(STDLIB_PATH_OPTION))
+            .and_then(|path| path.as_str())
+            .map(PathBuf::from);
+        let workspace_dir = params
+            .root_uri
+            .as_ref()
+            .and_then(|uri| uri.to_file_path().ok())
+            .map(|root| root.join(STDLIB_SOURCE_DIR))
+            .filter(|dir| dir.is_dir());
+
+        Self {
+            assembler: Assembler::default(),
+            stdlib: StdLibrary::default(),
+            stdlib_dir: configured_dir.or(workspace_dir),
+            documents: BTreeMap::new(),
+        }
+    }
+
+    // MESSAGE LOOP
+    // --------------------------------------------------------------------------------------------
+
+    /// Handles messages received via the provided connection until the client requests a
+    /// shutdown.
+    ///
+    /// Requests with malformed parameters are answered with an error response; notifications
+    /// with malformed parameters are logged to stderr and otherwise ignored.
+    pub fn run(&mut self, connection: &Connection) -> Result<(), ServerError> {
+        for message in &connection.receiver {
+            match message {
+                Message::Request(request) => {
+                    if connection.handle_shutdown(&request)? {
+                        return Ok(());
+                    }
+                    let response = self.handle_request(request);
+                    connection.sender.send(Message::Response(response))?;
+                }
+                Message::Notification(notification) => {
+                    let (uri, diagnostics) = match self.handle_notification(notification) {
+                        Ok(Some(result)) => result,
+                        Ok(None) => continue,
+                        Err(err) => {
+                            eprintln!("skipping notification: {}", err);
+                            continue;
+                        }
+                    };
+                    let params = PublishDiagnosticsParams::new(uri, diagnostics, None);
+                    let notification =
+                        Notification::new(PublishDiagnostics::METHOD.to_string(), params);
+                    connection
+                        .sender
+                        .send(Message::Notification(notification))?;
+                }
+                Message::Response(_) => (),
+            }
+        }
+        Ok(())
+    }
+
+    // REQUEST HANDLERS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the response to the provided request; requests of unsupported types and requests
+    /// with malformed parameters are answered with errors.
+    fn handle_request(&self, request: Request) -> Response {
+        let id = request.id.clone();
+        let response = match request.method.as_str() {
+            HoverRequest::METHOD => cast_request::<HoverRequest>(request).map(|(id, params)| {
+                let position = params.text_document_position_params;
+                let hover = self
+                    .documents
+                    .get(&position.text_document.uri)
+                    .and_then(|source| analysis::hover(source, position.position, &self.stdlib));
+                Response::new_ok(id, hover)
+            }),
+            GotoDefinition::METHOD => {
+                cast_request::<GotoDefinition>(request).map(|(id, params)| {
+                    let position = params.text_document_position_params;
+                    let location = self
+                        .documents
+                        .get(&position.text_document.uri)
+                        .and_then(|source| {
+                            self.find_definition(
+                                &position.text_document.uri,
+                                source,
+                                position.position,
+                            )
+                        })
+                        .map(GotoDefinitionResponse::Scalar);
+                    Response::new_ok(id, location)
+                })
+            }
+            DocumentSymbolRequest::METHOD => {
+                cast_request::<DocumentSymbolRequest>(request).map(|(id, params)| {
+                    let symbols = self.documents.get(&params.text_document.uri).map(|source| {
+                        DocumentSymbolResponse::Nested(analysis::document_symbols(source))
+                    });
+                    Response::new_ok(id, symbols)
+                })
+            }
+            _ => Ok(Response::new_err(
+                request.id,
+                ErrorCode::MethodNotFound as i32,
+                format!("unsupported request: {}", request.method),
+            )),
+        };
+        response.unwrap_or_else(|err| Response::new_err(id, ErrorCode::InvalidParams as i32, err))
+    }
+
+    /// Returns the location of the procedure invoked or declared at the specified position.
+    ///
+    /// Procedures imported from the standard library can be located only if the directory with
+    /// standard library sources is known.
+    fn find_definition(&self, uri: &Url, source: &str, position: Position) -> Option<Location> {
+        let proc = analysis::resolve_procedure(source, position, &self.stdlib)?;
+        let module_path = match &proc.module_path {
+            None => return Some(Location::new(uri.clone(), proc.range)),
+            Some(module_path) => module_path,
+        };
+
+        // sources on disk may differ from the sources embedded into the library, and thus the
+        // procedure is located anew in the module file
+        let path = self.module_file(module_path)?;
+        let module_source = fs::read_to_string(&path).ok()?;
+        let range = assembly::SourceOutline::parse(&module_source)
+            .get_procedure(proc.decl.label())
+            .and_then(|decl| analysis::token_range(&module_source, decl.pos()))
+            .unwrap_or_default();
+        Some(Location::new(Url::from_file_path(path).ok()?, range))
+    }
+
+    /// Returns the path of the file containing the source of the specified standard library
+    /// module, if the file exists.
+    fn module_file(&self, module_path: &str) -> Option<PathBuf> {
+        let stdlib_dir = self.stdlib_dir.as_deref()?;
+        let relative_path = module_path
+            .strip_prefix(self.stdlib.root_ns())?
+            .strip_prefix("::")?
+            .split("::")
+            .collect::<PathBuf>();
+        Some(stdlib_dir.join(relative_path).with_extension("masm")).filter(|path| path.is_file())
+    }
+
+    // NOTIFICATION HANDLERS
+    // --------------------------------------------------------------------------------------------
+
+    /// Updates the set of open documents according to the provided notification. Returns the
+    /// diagnostics of the affected document which need to be published to the client, if any.
+    fn handle_notification(
+        &mut self,
+        notification: Notification,
+    ) -> Result<Option<(Url, Vec<Diagnostic>)>, ServerError> {
+        let uri = match notification.method.as_str() {
+            DidOpenTextDocument::METHOD => {
+                let params = cast_notification::<DidOpenTextDocument>(notification)?;
+                let document = params.text_document;
+                self.documents.insert(document.uri.clone(), document.text);
+                document.uri
+            }
+            DidChangeTextDocument::METHOD => {
+                // documents are synchronized in full, and thus the last change contains the
+                // entire text of the document
+                let params = cast_notification::<DidChangeTextDocument>(notification)?;
+                let uri = params.text_document.uri;
+                if let Some(change) = params.content_changes.into_iter().last() {
+                    self.documents.insert(uri.clone(), change.text);
+                }
+                uri
+            }
+            DidCloseTextDocument::METHOD => {
+                // clear diagnostics of the closed document
+                let params = cast_notification::<DidCloseTextDocument>(notification)?;
+                self.documents.remove(&params.text_document.uri);
+                return Ok(Some((params.text_document.uri, Vec::new())));
+            }
+            _ => return Ok(None),
+        };
+
+        let diagnostics = self
+            .documents
+            .get(&uri)
+            .map(|source| analysis::diagnostics(&self.assembler, source))
+            .unwrap_or_default();
+        Ok(Some((uri, diagnostics)))
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Extracts parameters of the request of the specified type.
+///
+/// # Errors
+/// Returns a description of the failure if the request is of a different type, or if its
+/// parameters are malformed.
+fn cast_request<R: RequestTrait>(request: Request) -> Result<(RequestId, R::Params), String> {
+    request.extract(R::METHOD).map_err(|err| match err {
+        ExtractError::MethodMismatch(request) => {
+            format!("unexpected request: {}", request.method)
+        }
+        ExtractError::JsonError { method, error } => {
+            format!("malformed parameters of request {}: {}", method, error)
+        }
+    })
+}
+
+/// Extracts parameters of the notification of the specified type.
+fn cast_notification<N: NotificationTrait>(
+    notification: Notification,
+) -> Result<N::Params, ServerError> {
+    Ok(notification
+        .extract(N::METHOD)
+        .map_err(|err| format!("{:?}", err))?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        Connection, DidOpenTextDocument, DocumentSymbolRequest, ErrorCode, HoverRequest,
+        InitializeParams, Message, Notification, NotificationTrait, PublishDiagnostics,
+        PublishDiagnosticsParams, Request, RequestId, RequestTrait, Response, Server, Url,
+    };
+    use lsp_types::{
+        DidOpenTextDocumentParams, DocumentSymbolParams, TextDocumentIdentifier, TextDocumentItem,
+    };
+    use serde_json::{json, Value};
+    use std::thread;
+
+    #[test]
+    fn requests_and_notifications() {
+        let (connection, client) = Connection::memory();
+        let server = thread::spawn(move || {
+            Server::new(&InitializeParams::default())
+                .run(&connection)
+                .map_err(|err| err.to_string())
+        });
+        let uri = Url::parse("file:///test.masm").unwrap();
+
+        // opening a document publishes its diagnostics
+        let params = DidOpenTextDocumentParams {
+            text_document: TextDocumentItem::new(
+                uri.clone(),
+                "masm".into(),
+                1,
+                "proc.foo add end begin\n  push.1 plus\nend".into(),
+            ),
+        };
+        send_notification(&client, DidOpenTextDocument::METHOD, json!(params));
+        let params: PublishDiagnosticsParams = match client.receiver.recv().unwrap() {
+            Message::Notification(notification) => {
+                assert_eq!(PublishDiagnostics::METHOD, notification.method);
+                serde_json::from_value(notification.params).unwrap()
+            }
+            message => panic!("unexpected message: {:?}", message),
+        };
+        assert_eq!(uri, params.uri);
+        assert_eq!(1, params.diagnostics.len());
+
+        // notifications with malformed parameters are skipped
+        send_notification(&client, DidOpenTextDocument::METHOD, json!({ "uri": 1 }));
+
+        // requests are answered with their results
+        let params = DocumentSymbolParams {
+            text_document: TextDocumentIdentifier::new(uri),
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        };
+        let response = send_request(&client, 1, DocumentSymbolRequest::METHOD, json!(params));
+        assert!(response.error.is_none());
+        let symbols = response.result.unwrap();
+        assert_eq!(Some("foo"), symbols[0]["name"].as_str());
+
+        // requests with malformed parameters and unsupported requests are answered with errors
+        let response = send_request(&client, 2, HoverRequest::METHOD, json!({ "position": 1 }));
+        assert_eq!(
+            ErrorCode::InvalidParams as i32,
+            response.error.unwrap().code
+        );
+        let response = send_request(&client, 3, "textDocument/rename", Value::Null);
+        assert_eq!(
+            ErrorCode::MethodNotFound as i32,
+            response.error.unwrap().code
+        );
+
+        // the server stops after the client requests a shutdown
+        let response = send_request(&client, 4, "shutdown", Value::Null);
+        assert!(response.error.is_none());
+        send_notification(&client, "exit", Value::Null);
+        assert_eq!(Ok(()), server.join().unwrap());
+    }
+
+    // HELPER FUNCTIONS
+    // --------------------------------------------------------------------------------------------
+
+    fn send_notification(client: &Connection, method: &str, params: Value) {
+        let notification = Notification::new(method.into(), params);
+        client
+            .sender
+            .send(Message::Notification(notification))
+            .unwrap();
+    }
+
+    fn send_request(client: &Connection, id: i32, method: &str, params: Value) -> Response {
+        let request = Request::new(id.into(), method.into(), params);
+        client.sender.send(Message::Request(request)).unwrap();
+        match client.receiver.recv().unwrap() {
+            Message::Response(response) => {
+                assert_eq!(RequestId::from(id), response.id);
+                response
+            }
+            message => panic!("unexpected message: {:?}", message),
+        }
+    }
+}
@@ -0,0 +1,48 @@
+use assembly::Assembler;
+use std::{fs, path::PathBuf};
+use structopt::StructOpt;
+
+#[derive(StructOpt, Debug)]
+#[structopt(
+    name = "Canonicalize",
+    about = "Print the canonical form of a miden program or module"
+)]
+pub struct CanonicalizeCmd {
+    /// Path to .masm assembly file
+    #[structopt(short = "a", long = "assembly", parse(from_os_str))]
+    assembly_file: PathBuf,
+    /// Path to the file into which the canonical form is written; if omitted, the canonical form
+    /// is printed to stdout
+    #[structopt(short = "o", long = "output-file", parse(from_os_str))]
+    output_file: Option<PathBuf>,
+}
+
+impl CanonicalizeCmd {
+    pub fn execute(&self) -> Result<(), String> {
+        let source = fs::read_to_string(&self.assembly_file).map_err(|err| {
+            format!(
+                "Failed to open program file `{}` - {}",
+                self.assembly_file.display(),
+                err
+            )
+        })?;
+
+        let canonical_source = Assembler::default()
+            .canonicalize(&source)
+            .map_err(|err| format!("Failed to compile program - {}", err))?;
+
+        match &self.output_file {
+            Some(path) => fs::write(path, canonical_source).map_err(|err| {
+                format!(
+                    "Failed to write canonical form to `{}` - {}",
+                    path.display(),
+                    err
+                )
+            }),
+            None => {
+                print!("{}", canonical_source);
+                Ok(())
+            }
+        }
+    }
+}
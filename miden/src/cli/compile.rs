@@ -1,27 +1,70 @@
-use super::data::ProgramFile;
-use crypto::Digest;
+use super::{
+    data::{AuditLogFile, ProgramFile, SourceMapFile},
+    BuildOptions, CommandReport, Diagnostic, OutputFormat,
+};
 use std::path::PathBuf;
 use structopt::StructOpt;
+use vm_core::{utils::hex::digest_to_hex, SourceMap};
 
 #[derive(StructOpt, Debug)]
 #[structopt(name = "Compile", about = "Compile a miden program")]
 pub struct CompileCmd {
-    /// Path to .masm assembly file
-    #[structopt(short = "a", long = "assembly", parse(from_os_str))]
-    assembly_file: PathBuf,
+    #[structopt(flatten)]
+    build: BuildOptions,
+    /// Format in which results are reported (text or json)
+    #[structopt(long = "output", default_value = "text")]
+    output_format: OutputFormat,
+    /// Path to a file into which a log of the decisions made by the assembler is written as JSON,
+    /// or `-` to write the log to stdout
+    #[structopt(long = "audit-log", parse(from_os_str))]
+    audit_log_file: Option<PathBuf>,
+    /// Path to a file into which the source map of the program is written, or `-` to write the
+    /// source map to stdout; requires the debug profile
+    #[structopt(long = "source-map", parse(from_os_str))]
+    source_map_file: Option<PathBuf>,
 }
 
 impl CompileCmd {
     pub fn execute(&self) -> Result<(), String> {
-        println!("============================================================");
-        println!("Compile program");
-        println!("============================================================");
+        let mut report = CommandReport::new("compile", self.output_format);
+        let result = self.compile(&mut report);
+        report.finish(result)
+    }
+
+    fn compile(&self, report: &mut CommandReport) -> Result<(), Diagnostic> {
+        report.print_header("Compile program");
+
+        let build = self.build.resolve(report)?;
 
-        // load and compile program file
-        let program = ProgramFile::read(&self.assembly_file)?;
+        // assembly instructions are retained only when compiling in debug mode
+        if self.source_map_file.is_some() && !build.profile().is_debug() {
+            return Err(
+                "Source maps can be written only with profiles which compile in debug mode"
+                    .to_string()
+                    .into(),
+            );
+        }
+
+        // load and compile program file; if an audit log was requested, write it to file
+        let program = match &self.audit_log_file {
+            Some(path) => {
+                let (program, log) = ProgramFile::read_with_audit(&build, report)?;
+                AuditLogFile::write(&log, path, report)?;
+                program
+            }
+            None => ProgramFile::read(&build, report)?,
+        };
 
         // report program hash to user
-        println!("program hash is {}", hex::encode(program.hash().as_bytes()));
+        report.println(format_args!(
+            "program hash is {}",
+            digest_to_hex(program.hash())
+        ));
+
+        // write the source map of the program to file
+        if let Some(path) = &self.source_map_file {
+            SourceMapFile::write(&SourceMap::new(&program), path, report)?;
+        }
 
         Ok(())
     }
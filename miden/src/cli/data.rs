@@ -1,10 +1,69 @@
-use assembly::Assembler;
-use prover::StarkProof;
+use super::{project::Build, CommandReport, Diagnostic, Profile};
+use assembly::{Assembler, AssemblyError, AssemblyWarning, AuditLog, ProgramDigests};
+use processor::TraceDump;
+use prover::{ExecutionProof, ProofCompression};
 use serde_derive::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
-use std::{fs, io::Write, time::Instant};
-use vm_core::{chiplets::hasher::Digest, Program, ProgramInputs};
-use winter_utils::{Deserializable, SliceReader};
+use std::{
+    fs,
+    io::{self, BufWriter, Read, Write},
+    time::Instant,
+};
+use vm_core::{
+    chiplets::hasher::Digest,
+    utils::{hex::digest_to_hex, IntoBytes},
+    InputLimits, Program, ProgramInputs, SourceMap, Word,
+};
+
+// STANDARD STREAMS
+// ================================================================================================
+
+/// Path which refers to stdin when reading a file, and to stdout when writing a file.
+pub const STDIO_PATH: &str = "-";
+
+/// Returns true if the specified path refers to stdin or stdout.
+pub fn is_stdio(path: &Path) -> bool {
+    path == Path::new(STDIO_PATH)
+}
+
+/// Returns true if the specified optional path refers to stdin or stdout.
+pub fn is_stdio_opt(path: &Option<PathBuf>) -> bool {
+    path.as_deref().map_or(false, is_stdio)
+}
+
+/// Reads the specified file to string, or reads stdin to string if the path refers to stdin.
+fn read_to_string(path: &Path, kind: &str) -> Result<String, String> {
+    if is_stdio(path) {
+        let mut source = String::new();
+        io::stdin()
+            .read_to_string(&mut source)
+            .map_err(|err| format!("Failed to read {} from stdin - {}", kind, err))?;
+        Ok(source)
+    } else {
+        fs::read_to_string(path).map_err(|err| {
+            format!(
+                "Failed to open {} file `{}` - {}",
+                kind,
+                path.display(),
+                err
+            )
+        })
+    }
+}
+
+/// Writes the specified artifact to stdout as a single frame.
+///
+/// A frame consists of a header line with the artifact kind and the length of the payload in
+/// bytes separated by a space (e.g., `proof 13682`), followed by the payload and a newline. This
+/// allows several artifacts, including binary ones, to be written to stdout one after another.
+fn write_frame(kind: &str, payload: &[u8]) -> Result<(), String> {
+    let mut stdout = io::stdout().lock();
+    writeln!(stdout, "{} {}", kind, payload.len())
+        .and_then(|_| stdout.write_all(payload))
+        .and_then(|_| writeln!(stdout))
+        .and_then(|_| stdout.flush())
+        .map_err(|err| format!("Failed to write {} to stdout - {}", kind, err))
+}
 
 // INPUT FILE
 // ================================================================================================
@@ -13,16 +72,26 @@ use winter_utils::{Deserializable, SliceReader};
 #[derive(Deserialize, Debug)]
 pub struct InputFile {
     pub stack_init: Vec<String>,
+    #[serde(default)]
+    pub advice_tape: Vec<String>,
 }
 
 /// Helper methods to interact with the input file
 impl InputFile {
-    pub fn read(inputs_path: &Option<PathBuf>, program_path: &Path) -> Result<Self, String> {
+    pub fn read(
+        inputs_path: &Option<PathBuf>,
+        program_path: &Path,
+        report: &mut CommandReport,
+    ) -> Result<Self, String> {
         // if file not specified explicitly and corresponding file with same name as program_path
-        // with '.inputs' extension does't exist, set stack_init to empty vector
-        if !inputs_path.is_some() && !program_path.with_extension("inputs").exists() {
+        // with '.inputs' extension does't exist (or the program was read from stdin), set
+        // stack_init to empty vector
+        if !inputs_path.is_some()
+            && (is_stdio(program_path) || !program_path.with_extension("inputs").exists())
+        {
             return Ok(Self {
                 stack_init: Vec::new(),
+                advice_tape: Vec::new(),
             });
         }
 
@@ -33,11 +102,19 @@ impl InputFile {
             None => program_path.with_extension("inputs"),
         };
 
-        println!("Reading input file `{}`", path.display());
+        if is_stdio(&path) && is_stdio(program_path) {
+            return Err("Program and inputs cannot both be read from stdin".to_string());
+        }
+
+        if is_stdio(&path) {
+            report.println(format_args!("Reading inputs from stdin"));
+        } else {
+            report.println(format_args!("Reading input file `{}`", path.display()));
+        }
+        report.add_artifact("inputs", &path);
 
         // read input file to string
-        let inputs_file = fs::read_to_string(&path)
-            .map_err(|err| format!("Failed to open input file `{}` - {}", path.display(), err))?;
+        let inputs_file = read_to_string(&path, "input")?;
 
         // deserialize input data
         let inputs: InputFile = serde_json::from_str(&inputs_file)
@@ -46,9 +123,15 @@ impl InputFile {
         Ok(inputs)
     }
 
-    // TODO add handling of advice provider inputs
-    pub fn get_program_inputs(&self) -> ProgramInputs {
-        ProgramInputs::from_stack_inputs(&self.stack_init()).unwrap()
+    // TODO add handling of advice sets
+    pub fn get_program_inputs(&self) -> Result<ProgramInputs, String> {
+        ProgramInputs::with_limits(
+            &self.stack_init(),
+            &self.advice_tape(),
+            vec![],
+            &InputLimits::default(),
+        )
+        .map_err(|err| format!("Failed to build program inputs - {:?}", err))
     }
 
     /// Parse stack_init vector of strings to a vector of u64
@@ -58,6 +141,14 @@ impl InputFile {
             .map(|v| v.parse::<u64>().unwrap())
             .collect::<Vec<u64>>()
     }
+
+    /// Parse advice_tape vector of strings to a vector of u64
+    pub fn advice_tape(&self) -> Vec<u64> {
+        self.advice_tape
+            .iter()
+            .map(|v| v.parse::<u64>().unwrap())
+            .collect::<Vec<u64>>()
+    }
 }
 
 // OUTPUT FILE
@@ -82,7 +173,11 @@ impl OutputFile {
     }
 
     /// Read the output file
-    pub fn read(outputs_path: &Option<PathBuf>, program_path: &Path) -> Result<Self, String> {
+    pub fn read(
+        outputs_path: &Option<PathBuf>,
+        program_path: &Path,
+        report: &mut CommandReport,
+    ) -> Result<Self, String> {
         // If outputs_path has been provided then use this as path.  Alternatively we will
         // replace the program_path extension with `.outputs` and use this as a default.
         let path = match outputs_path {
@@ -90,7 +185,8 @@ impl OutputFile {
             None => program_path.with_extension("outputs"),
         };
 
-        println!("Reading output file `{}`", path.display());
+        report.println(format_args!("Reading output file `{}`", path.display()));
+        report.add_artifact("outputs", &path);
 
         // read outputs file to string
         let outputs_file = fs::read_to_string(&path)
@@ -107,10 +203,22 @@ impl OutputFile {
     }
 
     /// Write the output file
-    pub fn write(outputs: Vec<u64>, path: &Option<PathBuf>) -> Result<(), String> {
-        if let Some(path) = path {
+    pub fn write(
+        outputs: Vec<u64>,
+        path: &Option<PathBuf>,
+        report: &mut CommandReport,
+    ) -> Result<(), String> {
+        report.set_outputs(&outputs);
+        if is_stdio_opt(path) {
+            report.println(format_args!("Writing outputs to stdout"));
+            let json = serde_json::to_vec(&Self::new(outputs))
+                .map_err(|err| format!("Failed to serialize output data - {}", err))?;
+            write_frame("outputs", &json)?;
+            Ok(())
+        } else if let Some(path) = path {
             // if path provided, create output file
-            println!("Creating output file `{}`", path.display());
+            report.println(format_args!("Creating output file `{}`", path.display()));
+            report.add_artifact("outputs", path);
 
             let file = fs::File::create(&path).map_err(|err| {
                 format!(
@@ -120,12 +228,12 @@ impl OutputFile {
                 )
             })?;
 
-            println!("Writing data to output file");
+            report.println(format_args!("Writing data to output file"));
 
             // write outputs to output file
             serde_json::to_writer_pretty(file, &Self::new(outputs))
         } else {
-            println!("Output: {:?}", outputs);
+            report.println(format_args!("Output: {:?}", outputs));
             Ok(())
         }
         .map_err(|err| format!("Failed to write output data - {}", err))
@@ -147,24 +255,218 @@ pub struct ProgramFile;
 
 /// Helper methods to interact with masm program file
 impl ProgramFile {
-    pub fn read(path: &PathBuf) -> Result<Program, String> {
-        println!("Reading program file `{}`", path.display());
+    /// Reads the program from the file of the specified build and compiles it using the
+    /// assembler of the build.
+    pub fn read(build: &Build, report: &mut CommandReport) -> Result<Program, Diagnostic> {
+        Self::read_with(
+            build.assembly_file(),
+            build.profile(),
+            build.assembler(),
+            report,
+            |assembler, source| {
+                let (program, warnings) = assembler.compile_with_warnings(source)?;
+                Ok((program, (), warnings))
+            },
+        )
+        .map(|(program, _)| program)
+    }
+
+    /// Reads the program from the specified file and compiles it using the assembler configured
+    /// by the specified profile, against the kernel read from the specified kernel file (if
+    /// any); returns the program together with its digests.
+    pub fn read_with_digests(
+        path: &PathBuf,
+        kernel_path: &Option<PathBuf>,
+        profile: &Profile,
+        report: &mut CommandReport,
+    ) -> Result<(Program, ProgramDigests), Diagnostic> {
+        let assembler = match kernel_path {
+            Some(kernel_path) => {
+                report.println(format_args!(
+                    "Reading kernel file `{}`",
+                    kernel_path.display()
+                ));
+                report.add_artifact("kernel", kernel_path);
+                let kernel_source = read_to_string(kernel_path, "kernel")?;
+                profile
+                    .assembler()
+                    .with_kernel(&kernel_source)
+                    .map_err(|err| {
+                        Diagnostic::from_assembly_error(
+                            &err,
+                            &kernel_source,
+                            Some(kernel_path),
+                            "Failed to compile kernel",
+                        )
+                    })?
+            }
+            None => profile.assembler(),
+        };
+
+        Self::read_with(path, profile, &assembler, report, |assembler, source| {
+            let (program, warnings) = assembler.compile_with_warnings(source)?;
+            let digests = assembler.program_digests(source)?;
+            Ok((program, digests, warnings))
+        })
+    }
+
+    /// Reads the program from the file of the specified build and compiles it using the
+    /// assembler of the build; returns the program together with the log of decisions the
+    /// assembler made while compiling it.
+    pub fn read_with_audit(
+        build: &Build,
+        report: &mut CommandReport,
+    ) -> Result<(Program, AuditLog), Diagnostic> {
+        Self::read_with(
+            build.assembly_file(),
+            build.profile(),
+            build.assembler(),
+            report,
+            |assembler, source| {
+                let (program, log) = assembler.compile_with_audit(source)?;
+                let (_, warnings) = assembler.compile_with_warnings(source)?;
+                Ok((program, log, warnings))
+            },
+        )
+    }
+
+    /// Reads the program from the specified file and compiles it with the specified assembler,
+    /// configured by the specified profile, using the provided function. Warnings returned by the
+    /// function are added to the report.
+    fn read_with<T, F>(
+        path: &Path,
+        profile: &Profile,
+        assembler: &Assembler,
+        report: &mut CommandReport,
+        compile: F,
+    ) -> Result<(Program, T), Diagnostic>
+    where
+        F: FnOnce(&Assembler, &str) -> Result<(Program, T, Vec<AssemblyWarning>), AssemblyError>,
+    {
+        if is_stdio(path) {
+            report.println(format_args!("Reading program from stdin"));
+        } else {
+            report.println(format_args!("Reading program file `{}`", path.display()));
+        }
+        report.add_artifact("program", path);
 
         // read program file to string
-        let program_file = fs::read_to_string(&path)
-            .map_err(|err| format!("Failed to open program file `{}` - {}", path.display(), err))?;
+        let program_file = read_to_string(path, "program")?;
+        let file = if is_stdio(path) { None } else { Some(path) };
 
-        print!("Compiling program... ");
+        report.print(format_args!("Compiling program ({} profile)... ", profile));
         let now = Instant::now();
 
         // compile program
-        let program = Assembler::default()
-            .compile(&program_file)
-            .map_err(|err| format!("Failed to compile program - {}", err))?;
+        let (program, result, warnings) = compile(assembler, &program_file).map_err(|err| {
+            Diagnostic::from_assembly_error(&err, &program_file, file, "Failed to compile program")
+        })?;
+
+        report.println(format_args!("done ({} ms)", now.elapsed().as_millis()));
+        for warning in warnings.iter() {
+            report.add_warning(Diagnostic::from_assembly_warning(
+                warning,
+                &program_file,
+                file,
+            ));
+        }
+        report.add_timing("compile", now.elapsed());
+        report.set_program_hash(digest_to_hex(program.hash()));
+
+        Ok((program, result))
+    }
+}
 
-        println!("done ({} ms)", now.elapsed().as_millis());
+// AUDIT LOG FILE
+// ================================================================================================
+
+pub struct AuditLogFile;
+
+/// Helper methods to interact with audit log file
+impl AuditLogFile {
+    /// Write the audit log of a compilation to file as JSON, or to stdout if the path refers to
+    /// stdout
+    pub fn write(log: &AuditLog, path: &Path, report: &mut CommandReport) -> Result<(), String> {
+        let num_decisions = log.decisions().count();
+        if is_stdio(path) {
+            report.println(format_args!("Writing audit log to stdout"));
+            return write_frame("audit-log", log.to_json().as_bytes());
+        }
 
-        Ok(program)
+        report.println(format_args!("Creating audit log file `{}`", path.display()));
+        report.add_artifact("audit-log", path);
+        fs::write(path, log.to_json())
+            .map_err(|err| format!("Failed to write audit log `{}` - {}", path.display(), err))?;
+        report.println(format_args!(
+            "Logged {} decisions in {} procedures",
+            num_decisions,
+            log.procedures().len()
+        ));
+        Ok(())
+    }
+}
+
+// SOURCE MAP FILE
+// ================================================================================================
+
+pub struct SourceMapFile;
+
+/// Helper methods to interact with source map file
+impl SourceMapFile {
+    /// Read the source map of a program from file and check that it describes the program with
+    /// the specified hash
+    pub fn read(
+        path: &Path,
+        program_hash: Digest,
+        report: &mut CommandReport,
+    ) -> Result<SourceMap, String> {
+        report.println(format_args!("Reading source map file `{}`", path.display()));
+
+        let bytes = fs::read(path)
+            .map_err(|err| format!("Failed to open source map `{}` - {}", path.display(), err))?;
+        let source_map = SourceMap::from_bytes(&bytes)
+            .map_err(|err| format!("Failed to decode source map - {}", err))?;
+
+        if source_map.program_hash() != Word::from(program_hash).into_bytes() {
+            return Err(format!(
+                "Source map `{}` does not describe the program; compile the program with \
+                `--profile debug --source-map` to regenerate it",
+                path.display()
+            ));
+        }
+        Ok(source_map)
+    }
+
+    /// Write the source map of a program to file, or to stdout if the path refers to stdout
+    pub fn write(
+        source_map: &SourceMap,
+        path: &Path,
+        report: &mut CommandReport,
+    ) -> Result<(), String> {
+        let bytes = source_map.to_bytes();
+        if is_stdio(path) {
+            report.println(format_args!("Writing source map to stdout"));
+            return write_frame("source-map", &bytes);
+        }
+
+        report.println(format_args!(
+            "Creating source map file `{}`",
+            path.display()
+        ));
+        report.add_artifact("source-map", path);
+        fs::write(path, bytes)
+            .map_err(|err| format!("Failed to write source map `{}` - {}", path.display(), err))?;
+        let num_instructions: usize = source_map
+            .spans()
+            .iter()
+            .map(|span| span.instructions().len())
+            .sum();
+        report.println(format_args!(
+            "Mapped {} instructions in {} span blocks",
+            num_instructions,
+            source_map.spans().len()
+        ));
+        Ok(())
     }
 }
 
@@ -175,8 +477,12 @@ pub struct ProofFile;
 
 /// Helper methods to interact with proof file
 impl ProofFile {
-    /// Read stark proof from file
-    pub fn read(proof_path: &Option<PathBuf>, program_path: &Path) -> Result<StarkProof, String> {
+    /// Read execution proof from file; both versioned and legacy proofs are accepted
+    pub fn read(
+        proof_path: &Option<PathBuf>,
+        program_path: &Path,
+        report: &mut CommandReport,
+    ) -> Result<ExecutionProof, String> {
         // If proof_path has been provided then use this as path.  Alternatively we will
         // replace the program_path extension with `.proof` and use this as a default.
         let path = match proof_path {
@@ -184,42 +490,60 @@ impl ProofFile {
             None => program_path.with_extension("proof"),
         };
 
-        println!("Reading proof file `{}`", path.display());
+        report.println(format_args!("Reading proof file `{}`", path.display()));
+        report.add_artifact("proof", &path);
 
         // read the file to bytes
         let file = fs::read(&path)
             .map_err(|err| format!("Failed to open proof file `{}` - {}", path.display(), err))?;
 
-        // deserialize bytes into a stark proof
-        StarkProof::from_bytes(&file)
+        // deserialize bytes into an execution proof
+        ExecutionProof::from_bytes(&file)
             .map_err(|err| format!("Failed to decode proof data - {}", err))
     }
 
-    /// Write stark proof to file
+    /// Write execution proof to file with the specified compression
     pub fn write(
-        proof: StarkProof,
+        proof: ExecutionProof,
+        compression: ProofCompression,
         proof_path: &Option<PathBuf>,
         program_path: &Path,
+        report: &mut CommandReport,
     ) -> Result<(), String> {
         // If proof_path has been provided then use this as path.  Alternatively we will
         // replace the program_path extension with `.proof` and use this as a default.
         let path = match proof_path {
             Some(path) => path.clone(),
+            None if is_stdio(program_path) => {
+                return Err(
+                    "Proof file must be specified when the program is read from stdin".to_string(),
+                )
+            }
             None => program_path.with_extension("proof"),
         };
 
-        println!("Creating proof file `{}`", path.display());
+        if is_stdio(&path) {
+            let proof_bytes = proof.to_bytes_with(compression);
+            report.println(format_args!(
+                "Writing proof to stdout - size {} KB",
+                proof_bytes.len() / 1024
+            ));
+            return write_frame("proof", &proof_bytes);
+        }
+
+        report.println(format_args!("Creating proof file `{}`", path.display()));
+        report.add_artifact("proof", &path);
 
         // create output fille
         let mut file = fs::File::create(&path)
             .map_err(|err| format!("Failed to create proof file `{}` - {}", path.display(), err))?;
 
-        let proof_bytes = proof.to_bytes();
+        let proof_bytes = proof.to_bytes_with(compression);
 
-        println!(
+        report.println(format_args!(
             "Writing data to proof file - size {} KB",
             proof_bytes.len() / 1024
-        );
+        ));
 
         // write proof bytes to file
         file.write_all(&proof_bytes).unwrap();
@@ -228,25 +552,67 @@ impl ProofFile {
     }
 }
 
-// PROGRAM HASH
+// TRACE DUMP FILE
 // ================================================================================================
 
-pub struct ProgramHash;
+pub struct TraceDumpFile;
+
+/// Helper methods to interact with trace dump file
+impl TraceDumpFile {
+    /// Write the selected columns and rows of an execution trace to file as comma-separated
+    /// values, or as a Parquet file if the file has the `.parquet` extension
+    pub fn write(dump: &TraceDump, path: &Path, report: &mut CommandReport) -> Result<(), String> {
+        let is_parquet = path.extension().map_or(false, |ext| ext == "parquet");
+        if is_parquet && !cfg!(feature = "parquet") {
+            return Err(
+                "Parquet trace dumps require Miden VM to be compiled with the `parquet` feature"
+                    .to_string(),
+            );
+        }
 
-/// Helper method to parse program hash from hex
-impl ProgramHash {
-    pub fn read(hash_hex_string: &String) -> Result<Digest, String> {
-        // decode hex to bytes
-        let program_hash_bytes = hex::decode(hash_hex_string)
-            .map_err(|err| format!("Failed to convert program hash to bytes {}", err))?;
+        report.println(format_args!(
+            "Creating trace dump file `{}`",
+            path.display()
+        ));
+        report.add_artifact("trace", path);
+
+        let file = fs::File::create(path).map_err(|err| {
+            format!(
+                "Failed to create trace dump file `{}` - {}",
+                path.display(),
+                err
+            )
+        })?;
+
+        report.println(format_args!(
+            "Writing {} columns to trace dump file",
+            dump.column_names().len()
+        ));
+
+        #[cfg(feature = "parquet")]
+        if is_parquet {
+            return dump
+                .write_parquet(BufWriter::new(file))
+                .map_err(|err| format!("Failed to write trace dump - {}", err));
+        }
 
-        // create slice reader from bytes
-        let mut program_hash_slice = SliceReader::new(&program_hash_bytes);
+        // the dump is streamed into the file since dumps of large traces may not fit into memory
+        let mut writer = BufWriter::new(file);
+        write!(writer, "{}", dump)
+            .and_then(|_| writer.flush())
+            .map_err(|err| format!("Failed to write trace dump - {}", err))
+    }
+}
 
-        // create hash digest from slice
-        let program_hash = Digest::read_from(&mut program_hash_slice)
-            .map_err(|err| format!("Failed to deserialize program hash from bytes - {}", err))?;
+// PROGRAM HASH
+// ================================================================================================
+
+pub struct ProgramHash;
 
-        Ok(program_hash)
+/// Helper method to parse program hash from its `0x` prefixed hex encoding
+impl ProgramHash {
+    pub fn read(hash_hex_string: &str) -> Result<Digest, String> {
+        verifier::parse_program_hash(hash_hex_string)
+            .map_err(|err| format!("Failed to parse program hash - {}", err))
     }
 }
@@ -0,0 +1,45 @@
+use assembly::{explain_error, ErrorCode, ERROR_INDEX};
+use processor::EXECUTION_ERROR_INDEX;
+use structopt::StructOpt;
+
+#[derive(StructOpt, Debug)]
+#[structopt(
+    name = "Explain Error",
+    about = "Print an explanation of an assembly or execution error code"
+)]
+pub struct ExplainErrorCmd {
+    /// Error code to explain (e.g., E005); if omitted, all known error codes are listed
+    code: Option<String>,
+}
+
+impl ExplainErrorCmd {
+    pub fn execute(&self) -> Result<(), String> {
+        match &self.code {
+            Some(code) => {
+                let error_code = code
+                    .parse::<ErrorCode>()
+                    .map_err(|_| format!("`{}` is not a valid error code", code))?;
+                if let Some(explanation) = explain_error(error_code) {
+                    println!("{}", explanation);
+                } else {
+                    // execution errors come with a one-line description only
+                    let (_, title) = EXECUTION_ERROR_INDEX
+                        .iter()
+                        .find(|(code, _)| *code == error_code)
+                        .ok_or_else(|| format!("error code {} is not known", error_code))?;
+                    println!("{}: {}", error_code, title);
+                }
+            }
+            None => {
+                for entry in ERROR_INDEX.iter() {
+                    println!("{}: {}", entry.code(), entry.title());
+                }
+                for (code, title) in EXECUTION_ERROR_INDEX.iter() {
+                    println!("{}: {}", code, title);
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
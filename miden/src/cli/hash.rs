@@ -0,0 +1,85 @@
+use super::{data::ProgramFile, CommandReport, Diagnostic, OutputFormat, Profile};
+use std::path::PathBuf;
+use structopt::StructOpt;
+use vm_core::{chiplets::hasher::Digest, utils::hex::digest_to_hex, StarkField};
+
+#[derive(StructOpt, Debug)]
+#[structopt(
+    name = "Hash",
+    about = "Print the MAST root, procedure roots, and kernel hash of a miden program"
+)]
+pub struct HashCmd {
+    /// Path to .masm assembly file, or `-` to read the program from stdin
+    #[structopt(short = "a", long = "assembly", parse(from_os_str))]
+    assembly_file: PathBuf,
+    /// Path to .masm kernel module against which the program is compiled
+    #[structopt(short = "k", long = "kernel", parse(from_os_str))]
+    kernel_file: Option<PathBuf>,
+    /// Build profile used to compile the program (debug, release, or audit)
+    #[structopt(long = "profile", env = "MIDEN_PROFILE", default_value = "release")]
+    profile: Profile,
+    /// Format in which results are reported (text or json)
+    #[structopt(long = "output", default_value = "text")]
+    output_format: OutputFormat,
+}
+
+impl HashCmd {
+    pub fn execute(&self) -> Result<(), String> {
+        let mut report = CommandReport::new("hash", self.output_format);
+        let result = self.hash(&mut report);
+        report.finish(result)
+    }
+
+    fn hash(&self, report: &mut CommandReport) -> Result<(), Diagnostic> {
+        report.print_header("Hash program");
+
+        // load and compile program file, and compute the digests of the compiled program
+        let (_, digests) = ProgramFile::read_with_digests(
+            &self.assembly_file,
+            &self.kernel_file,
+            &self.profile,
+            report,
+        )?;
+
+        // digests are listed in a fixed order, and procedures are sorted by label, so that the
+        // listing of a program is the same across runs
+        print_digest(report, "program hash", digests.program());
+        report.add_digest("program", None, digests.program());
+
+        print_digest(report, "kernel hash", digests.kernel());
+        report.add_digest("kernel", None, digests.kernel());
+
+        if !digests.procedures().is_empty() {
+            report.println(format_args!("procedures:"));
+        }
+        for (label, &root) in digests.procedures() {
+            print_digest(report, &format!("  {}", label), root);
+            report.add_digest("procedure", Some(label), root);
+        }
+
+        if !digests.kernel_procedures().is_empty() {
+            report.println(format_args!("kernel procedures:"));
+        }
+        for (label, &root) in digests.kernel_procedures() {
+            print_digest(report, &format!("  {}", label), root);
+            report.add_digest("kernel_procedure", Some(label), root);
+        }
+
+        Ok(())
+    }
+}
+
+/// Prints the specified digest in hex form followed by its field elements.
+fn print_digest(report: &CommandReport, title: &str, digest: Digest) {
+    let elements = digest
+        .as_elements()
+        .iter()
+        .map(|element| element.as_int().to_string())
+        .collect::<Vec<_>>();
+    report.println(format_args!(
+        "{}: {} [{}]",
+        title,
+        digest_to_hex(digest),
+        elements.join(", ")
+    ));
+}
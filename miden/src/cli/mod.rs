@@ -1,11 +1,23 @@
+mod canonicalize;
 mod compile;
 mod data;
+mod explain;
+mod hash;
+mod output;
+mod profile;
+mod project;
 mod prove;
 mod run;
 mod verify;
 
+pub use canonicalize::CanonicalizeCmd;
 pub use compile::CompileCmd;
 pub use data::InputFile;
+pub use explain::ExplainErrorCmd;
+pub use hash::HashCmd;
+pub use output::{CommandReport, Diagnostic, OutputFormat};
+pub use profile::{Profile, ProfileSettings};
+pub use project::BuildOptions;
 pub use prove::ProveCmd;
 pub use run::RunCmd;
 pub use verify::VerifyCmd;
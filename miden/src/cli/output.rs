@@ -0,0 +1,459 @@
+use assembly::{AssemblyError, AssemblyWarning};
+use processor::ExecutionError;
+use serde_derive::Serialize;
+use std::{
+    collections::BTreeMap,
+    fmt::{self, Write as _},
+    io::Write,
+    path::{Path, PathBuf},
+    str::FromStr,
+    time::Duration,
+};
+use vm_core::{
+    chiplets::hasher::Digest,
+    utils::{hex::digest_to_hex, IntoBytes},
+    SourceMap, StarkField, Word,
+};
+
+// OUTPUT FORMAT
+// ================================================================================================
+
+/// Format in which CLI subcommands report their results.
+///
+/// - `text` prints human-readable progress messages and results.
+/// - `json` prints nothing but a single [CommandReport] serialized as JSON once the subcommand
+///   completes (successfully or not).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OutputFormat {
+    Text,
+    Json,
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Self::Text),
+            "json" => Ok(Self::Json),
+            other => Err(format!(
+                "`{}` is not a valid output format; expected one of: text, json",
+                other
+            )),
+        }
+    }
+}
+
+impl fmt::Display for OutputFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Text => write!(f, "text"),
+            Self::Json => write!(f, "json"),
+        }
+    }
+}
+
+// COMMAND REPORT
+// ================================================================================================
+
+/// Results of a single CLI subcommand.
+///
+/// In text mode, a report only forwards progress messages to stdout. In JSON mode, progress
+/// messages are suppressed, and the report collected over the course of the subcommand is printed
+/// once the subcommand completes. The JSON report has the following fields:
+/// - `command`: name of the subcommand.
+/// - `success`: whether the subcommand completed successfully.
+/// - `program_hash`: hex-encoded hash of the compiled program, if a program was compiled.
+/// - `outputs`: stack outputs of the program, if the program was executed or verified.
+/// - `cycles`: number of cycles executed by the VM, if the program was executed.
+/// - `trace_length`: length of the padded execution trace, if the program was proved or verified.
+/// - `artifacts`: paths of files written or read by the subcommand, keyed by artifact kind.
+/// - `timings`: durations of the subcommand stages in milliseconds, keyed by stage name.
+/// - `diagnostics`: list of [Diagnostic]s: the warnings reported by the subcommand, followed by
+///   the error which caused the subcommand to fail (if any).
+/// - `analysis`: program analysis results; present only for the `analyze` subcommand.
+/// - `digests`: list of [DigestEntry]s; present only for the `hash` subcommand.
+#[derive(Debug, Serialize)]
+pub struct CommandReport {
+    command: &'static str,
+    success: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    program_hash: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    outputs: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    cycles: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    trace_length: Option<usize>,
+    artifacts: BTreeMap<&'static str, PathBuf>,
+    timings: BTreeMap<&'static str, u128>,
+    diagnostics: Vec<Diagnostic>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    analysis: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    digests: Vec<DigestEntry>,
+    #[serde(skip)]
+    format: OutputFormat,
+    #[serde(skip)]
+    to_stderr: bool,
+}
+
+impl CommandReport {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Returns a new empty report for the specified subcommand.
+    pub fn new(command: &'static str, format: OutputFormat) -> Self {
+        Self {
+            command,
+            success: false,
+            program_hash: None,
+            outputs: None,
+            cycles: None,
+            trace_length: None,
+            artifacts: BTreeMap::new(),
+            timings: BTreeMap::new(),
+            diagnostics: Vec::new(),
+            analysis: None,
+            digests: Vec::new(),
+            format,
+            to_stderr: false,
+        }
+    }
+
+    /// Returns a new empty report for the specified subcommand which is printed to stderr rather
+    /// than to stdout if `to_stderr` is true.
+    ///
+    /// This is used when the subcommand writes artifacts to stdout, so that the report does not
+    /// get mixed up with the artifacts.
+    pub fn with_stderr(command: &'static str, format: OutputFormat, to_stderr: bool) -> Self {
+        Self {
+            to_stderr,
+            ..Self::new(command, format)
+        }
+    }
+
+    // PROGRESS MESSAGES
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns true if results are reported as human-readable text.
+    pub fn is_text(&self) -> bool {
+        self.format == OutputFormat::Text
+    }
+
+    /// Prints a banner with the specified title in text mode.
+    pub fn print_header(&self, title: &str) {
+        self.println(format_args!(
+            "============================================================"
+        ));
+        self.println(format_args!("{}", title));
+        self.println(format_args!(
+            "============================================================"
+        ));
+    }
+
+    /// Prints the specified message without a trailing newline in text mode.
+    pub fn print(&self, args: fmt::Arguments) {
+        if self.is_text() {
+            self.write(args);
+        }
+    }
+
+    /// Prints the specified message followed by a newline in text mode.
+    pub fn println(&self, args: fmt::Arguments) {
+        if self.is_text() {
+            self.write(format_args!("{}\n", args));
+        }
+    }
+
+    // STATE MUTATORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Records the hash of the compiled program.
+    pub fn set_program_hash(&mut self, program_hash: String) {
+        self.program_hash = Some(program_hash);
+    }
+
+    /// Records stack outputs of the program.
+    pub fn set_outputs(&mut self, outputs: &[u64]) {
+        self.outputs = Some(outputs.iter().map(|v| v.to_string()).collect());
+    }
+
+    /// Records the number of cycles executed by the VM.
+    pub fn set_cycles(&mut self, cycles: usize) {
+        self.cycles = Some(cycles);
+    }
+
+    /// Records the length of the padded execution trace.
+    pub fn set_trace_length(&mut self, trace_length: usize) {
+        self.trace_length = Some(trace_length);
+    }
+
+    /// Records the path of an artifact of the specified kind.
+    pub fn add_artifact(&mut self, kind: &'static str, path: &Path) {
+        self.artifacts.insert(kind, path.to_path_buf());
+    }
+
+    /// Records the duration of the specified stage.
+    pub fn add_timing(&mut self, stage: &'static str, duration: Duration) {
+        self.timings.insert(stage, duration.as_millis());
+    }
+
+    /// Records a warning reported while running the subcommand; in text mode, the warning is
+    /// printed right away.
+    pub fn add_warning(&mut self, warning: Diagnostic) {
+        self.println(format_args!("{}", warning.message));
+        self.diagnostics.push(warning);
+    }
+
+    /// Records program analysis results.
+    pub fn set_analysis<T: serde::Serialize>(&mut self, analysis: &T) {
+        self.analysis = Some(serde_json::to_value(analysis).expect("failed to serialize analysis"));
+    }
+
+    /// Records a digest of the specified kind; the name identifies the digest among the digests
+    /// of the same kind (e.g., the label of a procedure).
+    pub fn add_digest(&mut self, kind: &'static str, name: Option<&str>, digest: Digest) {
+        self.digests.push(DigestEntry {
+            kind,
+            name: name.map(str::to_string),
+            hex: digest_to_hex(digest),
+            elements: digest
+                .as_elements()
+                .iter()
+                .map(|element| element.as_int().to_string())
+                .collect(),
+        });
+    }
+
+    // REPORTING
+    // --------------------------------------------------------------------------------------------
+
+    /// Completes this report with the result of the subcommand.
+    ///
+    /// In text mode, the result is returned with the diagnostic (if any) rendered as an error
+    /// message. In JSON mode, the report is printed to stdout; if the subcommand failed, the
+    /// process exits with a non-zero status code.
+    pub fn finish(mut self, result: Result<(), Diagnostic>) -> Result<(), String> {
+        self.success = result.is_ok();
+        if self.is_text() {
+            return result.map_err(|diagnostic| diagnostic.message);
+        }
+
+        if let Err(diagnostic) = result {
+            self.diagnostics.push(diagnostic);
+        }
+        let json = serde_json::to_string_pretty(&self)
+            .map_err(|err| format!("Failed to serialize command report - {}", err))?;
+        self.write(format_args!("{}\n", json));
+
+        if !self.success {
+            std::process::exit(1);
+        }
+        Ok(())
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Writes the specified message to stdout, or to stderr if artifacts are written to stdout.
+    fn write(&self, args: fmt::Arguments) {
+        if self.to_stderr {
+            let mut stderr = std::io::stderr();
+            stderr.write_fmt(args).ok();
+            stderr.flush().ok();
+        } else {
+            let mut stdout = std::io::stdout();
+            stdout.write_fmt(args).ok();
+            stdout.flush().ok();
+        }
+    }
+}
+
+// DIGEST ENTRY
+// ================================================================================================
+
+/// A digest reported by the `hash` subcommand, in both hex and field element form.
+///
+/// Field elements are serialized as strings, since they may not fit into the range of integers
+/// which JSON parsers represent exactly.
+#[derive(Debug, Serialize)]
+pub struct DigestEntry {
+    kind: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    hex: String,
+    elements: Vec<String>,
+}
+
+// DIAGNOSTIC
+// ================================================================================================
+
+/// Describes an error which caused a subcommand to fail, or a warning reported by a subcommand.
+///
+/// Errors originating from the assembler or the processor carry the error code (e.g., `E005`).
+/// Assembly errors also carry a [Span] pointing to the offending token, if the source location
+/// of the error could be determined.
+#[derive(Debug, Serialize)]
+pub struct Diagnostic {
+    severity: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    code: Option<String>,
+    message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    span: Option<Span>,
+}
+
+impl Diagnostic {
+    /// Returns a new error diagnostic with the specified message.
+    pub fn error(message: String) -> Self {
+        Self {
+            severity: "error",
+            code: None,
+            message,
+            span: None,
+        }
+    }
+
+    /// Returns a new error diagnostic for the specified assembly error. The source from which the
+    /// error originated is used to locate the offending token.
+    pub fn from_assembly_error(
+        error: &AssemblyError,
+        source: &str,
+        file: Option<&Path>,
+        context: &str,
+    ) -> Self {
+        Self {
+            severity: "error",
+            code: Some(error.error_code().to_string()),
+            message: format!("{} - {}", context, error),
+            span: Span::locate(source, error.step(), file),
+        }
+    }
+
+    /// Returns a new warning diagnostic for the specified assembly warning; the source is used
+    /// to locate the token the warning points to.
+    pub fn from_assembly_warning(
+        warning: &AssemblyWarning,
+        source: &str,
+        file: Option<&Path>,
+    ) -> Self {
+        Self {
+            severity: "warning",
+            code: Some(warning.error_code().to_string()),
+            message: warning.to_string(),
+            span: Span::locate(source, warning.step(), file),
+        }
+    }
+}
+
+impl Diagnostic {
+    /// Returns a new error diagnostic for the specified execution error.
+    pub fn from_execution_error(error: &ExecutionError, context: &str) -> Self {
+        Self {
+            severity: "error",
+            code: Some(error.error_code().to_string()),
+            message: with_error_details(format!("{} - {:?}", context, error), error),
+            span: None,
+        }
+    }
+
+    /// Returns a new error diagnostic for the specified error of a program executed in debug
+    /// mode. The source map of the program is used to look up the instruction which failed.
+    ///
+    /// If the instruction belongs to the program itself rather than to a library module, the
+    /// diagnostic carries a [Span] pointing to the instruction in the specified file.
+    pub fn from_execution_error_with_source_map(
+        error: &ExecutionError,
+        source_map: &SourceMap,
+        file: Option<&Path>,
+        context: &str,
+    ) -> Self {
+        let (inner, call_stack) = match error {
+            ExecutionError::WithCallStack(inner, call_stack) => (inner, call_stack),
+            _ => return Self::from_execution_error(error, context),
+        };
+
+        let mut message =
+            with_error_details(format!("{} - {:?} {}", context, inner, call_stack), inner);
+        let mut span = None;
+        let instruction = call_stack.span_op().and_then(|(hash, op_idx)| {
+            source_map.instruction_at(Word::from(hash).into_bytes(), op_idx)
+        });
+        if let Some(instruction) = instruction {
+            write!(message, "\nFailed instruction: {}", instruction.text()).unwrap();
+            if !instruction.procs().is_empty() {
+                write!(message, " in {}", instruction.procs().join(" > ")).unwrap();
+            }
+            match (instruction.location(), file) {
+                (Some(location), Some(file)) if location.module().is_empty() => {
+                    write!(message, " at {}:{}", file.display(), location).unwrap();
+                }
+                (Some(location), _) => write!(message, " at {}", location).unwrap(),
+                (None, _) => (),
+            }
+            span = instruction
+                .location()
+                .filter(|location| location.module().is_empty())
+                .map(|location| Span {
+                    file: file.map(|path| path.to_path_buf()),
+                    token: None,
+                    line: location.line() as usize,
+                    column: location.column() as usize,
+                });
+        }
+
+        Self {
+            severity: "error",
+            code: Some(error.error_code().to_string()),
+            message,
+            span,
+        }
+    }
+}
+
+/// Appends the details of the specified error to the message: the table of expected and actual
+/// stack values if the error was caused by a failed `expect_stack` instruction, or the
+/// description of the violated memory protection if the error was caused by a memory access.
+fn with_error_details(mut message: String, error: &ExecutionError) -> String {
+    match error {
+        ExecutionError::StackMismatch(mismatch) => write!(message, "\n{}", mismatch).unwrap(),
+        ExecutionError::MemoryAccessViolation(violation) => {
+            write!(message, "\n{}", violation).unwrap()
+        }
+        _ => (),
+    }
+    message
+}
+
+impl From<String> for Diagnostic {
+    fn from(message: String) -> Self {
+        Self::error(message)
+    }
+}
+
+/// Location of a token in a source file.
+///
+/// `token` is the position of the token in the source counting from 0, while `line` and `column`
+/// are 1-based. The position of the token is not known for locations read from a source map.
+#[derive(Debug, Serialize)]
+pub struct Span {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    file: Option<PathBuf>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    token: Option<usize>,
+    line: usize,
+    column: usize,
+}
+
+impl Span {
+    /// Returns the location of the token at the specified position in the source, or None if
+    /// the source contains fewer tokens.
+    fn locate(source: &str, token: usize, file: Option<&Path>) -> Option<Self> {
+        assembly::locate_token(source, token).map(|(line, column)| Self {
+            file: file.map(|path| path.to_path_buf()),
+            token: Some(token),
+            line,
+            column,
+        })
+    }
+}
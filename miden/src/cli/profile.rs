@@ -0,0 +1,321 @@
+use assembly::{Assembler, AssemblyLimits, BudgetPolicy, OptLevel};
+use serde_derive::Deserialize;
+use std::{fmt, str::FromStr};
+
+// CONSTANTS
+// ================================================================================================
+
+/// Maximum number of operations a single `repeat` block may unroll into under the audit profile.
+const AUDIT_UNROLL_BUDGET: usize = 1 << 16;
+
+/// Names of the built-in profiles.
+const BUILTIN_PROFILES: [&str; 3] = ["debug", "release", "audit"];
+
+// BUILD PROFILE
+// ================================================================================================
+
+/// Named set of assembler settings used to compile programs.
+///
+/// A profile is selected via the `--profile` option of the CLI subcommands which compile programs,
+/// or via the `MIDEN_PROFILE` environment variable if the option is not provided. There are three
+/// built-in profiles:
+///
+/// - `debug` compiles programs in debug mode, retaining source mappings between assembly
+///   instructions and VM operations.
+/// - `release` compiles programs without debug information, and applies basic optimizations.
+/// - `audit` compiles programs in debug mode, rejects `repeat` blocks which unroll into more
+///   than 2^16 operations, and treats warnings as errors.
+///
+/// Warnings are reported for code exceeding the locals budget of a profile; when warnings are
+/// treated as errors, such code fails to compile instead.
+///
+/// Projects can override the settings of the built-in profiles, or define new profiles, via
+/// `[profile.<name>]` tables of their manifests (see [ProfileSettings]).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Profile {
+    name: String,
+    debug: bool,
+    opt_level: OptLevel,
+    unroll_budget: Option<usize>,
+    locals_budget: Option<u64>,
+    warnings_as_errors: bool,
+    limits: AssemblyLimits,
+    features: Vec<String>,
+}
+
+impl Profile {
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the built-in `debug` profile.
+    pub fn debug() -> Self {
+        Self::new("debug", true, OptLevel::None)
+    }
+
+    /// Returns the built-in `release` profile.
+    pub fn release() -> Self {
+        Self::new("release", false, OptLevel::Basic)
+    }
+
+    /// Returns the built-in `audit` profile.
+    pub fn audit() -> Self {
+        let mut profile = Self::new("audit", true, OptLevel::None);
+        profile.unroll_budget = Some(AUDIT_UNROLL_BUDGET);
+        profile.warnings_as_errors = true;
+        profile
+    }
+
+    /// Returns a profile with the specified name defined by the specified settings.
+    ///
+    /// The settings are applied on top of the built-in profile they inherit from: the profile
+    /// named by `inherits`, or the built-in profile with the same name, or `release` if there is
+    /// no such profile.
+    ///
+    /// # Errors
+    /// Returns an error if the settings inherit from a profile which is not built-in, or if any
+    /// of the settings is invalid.
+    pub fn with_settings(name: &str, settings: &ProfileSettings) -> Result<Self, String> {
+        let mut profile = match &settings.inherits {
+            Some(base) => base.parse()?,
+            None => name.parse().unwrap_or_else(|_| Self::release()),
+        };
+        profile.name = name.to_string();
+
+        if let Some(debug) = settings.debug {
+            profile.debug = debug;
+        }
+        if let Some(level) = &settings.opt_level {
+            profile.opt_level = parse_opt_level(level)?;
+        }
+        if let Some(budget) = settings.unroll_budget {
+            profile.unroll_budget = Some(budget);
+        }
+        if let Some(budget) = settings.locals_budget {
+            profile.locals_budget = Some(budget);
+        }
+        if let Some(warnings_as_errors) = settings.warnings_as_errors {
+            profile.warnings_as_errors = warnings_as_errors;
+        }
+        if let Some(max_procedures) = settings.max_procedures {
+            profile.limits = profile.limits.with_max_procedures(max_procedures);
+        }
+        if let Some(max_proc_locals) = settings.max_proc_locals {
+            profile.limits = profile.limits.with_max_proc_locals(max_proc_locals);
+        }
+        if let Some(max_name_len) = settings.max_name_len {
+            profile.limits = profile.limits.with_max_name_len(max_name_len);
+        }
+        profile.features = settings.features.clone();
+
+        Ok(profile)
+    }
+
+    fn new(name: &str, debug: bool, opt_level: OptLevel) -> Self {
+        Self {
+            name: name.to_string(),
+            debug,
+            opt_level,
+            unroll_budget: None,
+            locals_budget: None,
+            warnings_as_errors: false,
+            limits: AssemblyLimits::default(),
+            features: Vec::new(),
+        }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns true if programs are compiled in debug mode under this profile.
+    pub fn is_debug(&self) -> bool {
+        self.debug
+    }
+
+    /// Returns the features of the project enabled by this profile.
+    pub fn features(&self) -> &[String] {
+        &self.features
+    }
+
+    /// Returns a new assembler configured according to this profile.
+    ///
+    /// Code exceeding the locals budget of the profile fails to compile if warnings are treated
+    /// as errors, and is reported via [Assembler::compile_with_warnings()] otherwise.
+    pub fn assembler(&self) -> Assembler {
+        let mut assembler = Assembler::new(self.debug)
+            .with_opt_level(self.opt_level)
+            .with_limits(self.limits);
+        if let Some(budget) = self.unroll_budget {
+            assembler = assembler.with_unroll_budget(budget);
+        }
+        if let Some(budget) = self.locals_budget {
+            let policy = if self.warnings_as_errors {
+                BudgetPolicy::Error
+            } else {
+                BudgetPolicy::Warn
+            };
+            assembler = assembler.with_locals_budget(budget, policy);
+        }
+        assembler
+    }
+}
+
+impl FromStr for Profile {
+    type Err = String;
+
+    /// Parses the name of a built-in profile.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "debug" => Ok(Self::debug()),
+            "release" => Ok(Self::release()),
+            "audit" => Ok(Self::audit()),
+            other => Err(format!(
+                "`{}` is not a built-in profile; expected one of: {}",
+                other,
+                BUILTIN_PROFILES.join(", ")
+            )),
+        }
+    }
+}
+
+impl fmt::Display for Profile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+// PROFILE SETTINGS
+// ================================================================================================
+
+/// Settings of a profile, as declared in a `[profile.<name>]` table of a project manifest.
+///
+/// Settings which are not declared are taken from the built-in profile the profile inherits from
+/// (see [Profile::with_settings()]); features enabled by the profile are enabled in addition to
+/// the default features of the project.
+#[derive(Deserialize, Debug, Default)]
+#[serde(deny_unknown_fields, rename_all = "kebab-case")]
+pub struct ProfileSettings {
+    inherits: Option<String>,
+    debug: Option<bool>,
+    opt_level: Option<String>,
+    unroll_budget: Option<usize>,
+    locals_budget: Option<u64>,
+    warnings_as_errors: Option<bool>,
+    max_procedures: Option<usize>,
+    max_proc_locals: Option<u32>,
+    max_name_len: Option<usize>,
+    #[serde(default)]
+    features: Vec<String>,
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Parses the optimization level declared in a manifest.
+fn parse_opt_level(level: &str) -> Result<OptLevel, String> {
+    match level {
+        "none" => Ok(OptLevel::None),
+        "basic" => Ok(OptLevel::Basic),
+        "aggressive" => Ok(OptLevel::Aggressive),
+        other => Err(format!(
+            "`{}` is not a valid optimization level; expected one of: none, basic, aggressive",
+            other
+        )),
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::{OptLevel, Profile, ProfileSettings, AUDIT_UNROLL_BUDGET};
+
+    #[test]
+    fn builtin_profiles() {
+        assert_eq!(Ok(Profile::debug()), "debug".parse());
+        assert_eq!(Ok(Profile::release()), "release".parse());
+        assert_eq!(Ok(Profile::audit()), "audit".parse());
+        let error = "ci".parse::<Profile>().unwrap_err();
+        assert_eq!(
+            "`ci` is not a built-in profile; expected one of: debug, release, audit",
+            error
+        );
+
+        assert!(Profile::debug().is_debug() && !Profile::release().is_debug());
+        assert_eq!(OptLevel::Basic, Profile::release().opt_level);
+        assert_eq!(Some(AUDIT_UNROLL_BUDGET), Profile::audit().unroll_budget);
+        assert!(Profile::audit().warnings_as_errors && !Profile::release().warnings_as_errors);
+        assert_eq!("audit", Profile::audit().to_string());
+    }
+
+    #[test]
+    fn profile_settings() {
+        // settings of a new profile are applied on top of the release profile by default
+        let settings: ProfileSettings = toml::from_str(
+            r#"
+            opt-level = "aggressive"
+            locals-budget = 64
+            max-procedures = 10
+            features = ["fast-hash"]
+            "#,
+        )
+        .unwrap();
+        let profile = Profile::with_settings("ci", &settings).unwrap();
+        assert_eq!("ci", profile.to_string());
+        assert!(!profile.is_debug());
+        assert_eq!(OptLevel::Aggressive, profile.opt_level);
+        assert_eq!(Some(64), profile.locals_budget);
+        assert_eq!(10, profile.limits.max_procedures());
+        assert_eq!(&["fast-hash".to_string()], profile.features());
+
+        // settings of a built-in profile override its defaults
+        let settings: ProfileSettings = toml::from_str("unroll-budget = 100").unwrap();
+        let profile = Profile::with_settings("audit", &settings).unwrap();
+        assert!(profile.is_debug());
+        assert_eq!(Some(100), profile.unroll_budget);
+
+        // profiles can inherit from built-in profiles only
+        let settings: ProfileSettings = toml::from_str("inherits = \"debug\"").unwrap();
+        assert!(Profile::with_settings("trace", &settings)
+            .unwrap()
+            .is_debug());
+        let settings: ProfileSettings = toml::from_str("inherits = \"ci\"").unwrap();
+        assert!(Profile::with_settings("nightly", &settings).is_err());
+
+        let settings: ProfileSettings = toml::from_str("opt-level = \"max\"").unwrap();
+        assert!(Profile::with_settings("ci", &settings).is_err());
+        assert!(toml::from_str::<ProfileSettings>("strip = true").is_err());
+    }
+
+    #[test]
+    fn profile_assembler() {
+        let source = "begin push.1 push.2 add push.0 drop end";
+        let release = Profile::release().assembler().compile(source).unwrap();
+        let debug = Profile::debug().assembler().compile(source).unwrap();
+        assert_eq!("begin span push(3) end end", format!("{}", release));
+        assert_ne!(release.hash(), debug.hash());
+
+        let settings: ProfileSettings = toml::from_str("max-name-len = 3").unwrap();
+        let assembler = Profile::with_settings("strict", &settings)
+            .unwrap()
+            .assembler();
+        assert!(assembler.compile(source).is_ok());
+        assert!(assembler
+            .compile("proc.long_name add end begin exec.long_name end")
+            .is_err());
+
+        // locals budget overruns are warnings unless warnings are treated as errors
+        let source = "proc.foo.6 push.1 drop end begin exec.foo end";
+        let settings: ProfileSettings = toml::from_str("locals-budget = 4").unwrap();
+        let assembler = Profile::with_settings("ci", &settings).unwrap().assembler();
+        let (_, warnings) = assembler.compile_with_warnings(source).unwrap();
+        assert_eq!("E036", warnings[0].error_code().to_string());
+
+        let settings: ProfileSettings =
+            toml::from_str("locals-budget = 4\nwarnings-as-errors = true").unwrap();
+        let profile = Profile::with_settings("ci", &settings).unwrap();
+        assert!(profile.warnings_as_errors);
+        let error = profile.assembler().compile(source).unwrap_err();
+        assert_eq!("E036", error.error_code().to_string());
+    }
+}
@@ -0,0 +1,337 @@
+use super::{CommandReport, Diagnostic, Profile, ProfileSettings};
+use assembly::Assembler;
+use serde_derive::Deserialize;
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
+use structopt::StructOpt;
+use vm_core::MaslLibrary;
+
+// CONSTANTS
+// ================================================================================================
+
+/// Name of the file in which the manifest of a project is stored.
+const MANIFEST_FILE_NAME: &str = "miden.toml";
+
+/// Prefix of the names of constants through which features are exposed to programs.
+const FEATURE_CONST_PREFIX: &str = "FEATURE_";
+
+// BUILD OPTIONS
+// ================================================================================================
+
+/// Options of the subcommands which compile a program, which are resolved against the manifest of
+/// the project the program belongs to (if any).
+///
+/// Options provided on the command line take precedence over the options declared in the
+/// manifest.
+#[derive(StructOpt, Debug)]
+pub struct BuildOptions {
+    /// Path to .masm assembly file, or `-` to read the program from stdin; defaults to the entry
+    /// program of the project
+    #[structopt(short = "a", long = "assembly", parse(from_os_str))]
+    assembly_file: Option<PathBuf>,
+    /// Build profile used to compile the program (debug, release, audit, or a profile declared
+    /// in the project manifest); defaults to the profile of the project, or to release
+    #[structopt(long = "profile", env = "MIDEN_PROFILE")]
+    profile: Option<String>,
+    /// Path to the project manifest; by default, `miden.toml` is looked up in the current
+    /// directory and its ancestors
+    #[structopt(long = "manifest-path", parse(from_os_str))]
+    manifest_path: Option<PathBuf>,
+    /// Comma-separated features of the project to enable in addition to the default ones
+    #[structopt(long = "features", use_delimiter = true)]
+    features: Vec<String>,
+}
+
+impl BuildOptions {
+    /// Resolves these options against the project manifest, and returns the program file
+    /// together with the assembler which should be used to compile it.
+    pub fn resolve(&self, report: &mut CommandReport) -> Result<Build, Diagnostic> {
+        let manifest_path = match &self.manifest_path {
+            Some(path) => Some(path.clone()),
+            None => find_manifest()?,
+        };
+        let project = match manifest_path {
+            Some(path) => Some(Project::read(&path, report)?),
+            None => None,
+        };
+
+        let assembly_file = match (&self.assembly_file, &project) {
+            (Some(path), _) => path.clone(),
+            (None, Some(project)) => project.entry(),
+            (None, None) => {
+                return Err(format!(
+                    "No program to compile; specify it via `--assembly`, or declare it in `{}`",
+                    MANIFEST_FILE_NAME
+                )
+                .into())
+            }
+        };
+
+        let profile_name = self
+            .profile
+            .as_deref()
+            .or_else(|| project.as_ref().and_then(|project| project.profile_name()))
+            .unwrap_or("release");
+
+        let (profile, assembler) = match &project {
+            Some(project) => {
+                let profile = project.profile(profile_name)?;
+                let assembler = project.assembler(&profile, &self.features, report)?;
+                (profile, assembler)
+            }
+            None if !self.features.is_empty() => {
+                return Err("Features can be enabled only for programs of a project"
+                    .to_string()
+                    .into())
+            }
+            None => {
+                let profile = profile_name.parse::<Profile>()?;
+                let assembler = profile.assembler();
+                (profile, assembler)
+            }
+        };
+
+        Ok(Build {
+            assembly_file,
+            profile,
+            assembler,
+        })
+    }
+}
+
+// BUILD
+// ================================================================================================
+
+/// A program file together with the settings with which it is compiled.
+pub struct Build {
+    assembly_file: PathBuf,
+    profile: Profile,
+    assembler: Assembler,
+}
+
+impl Build {
+    /// Returns the path of the program file.
+    pub fn assembly_file(&self) -> &Path {
+        &self.assembly_file
+    }
+
+    /// Returns the profile with which the program is compiled.
+    pub fn profile(&self) -> &Profile {
+        &self.profile
+    }
+
+    /// Returns the assembler with which the program is compiled.
+    pub fn assembler(&self) -> &Assembler {
+        &self.assembler
+    }
+}
+
+// PROJECT MANIFEST
+// ================================================================================================
+
+/// Manifest of a project, as declared in `miden.toml`.
+#[derive(Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+struct Manifest {
+    program: ProgramSection,
+    #[serde(default)]
+    build: BuildSection,
+    #[serde(default)]
+    profile: BTreeMap<String, ProfileSettings>,
+    #[serde(default)]
+    features: BTreeMap<String, bool>,
+    #[serde(default)]
+    libraries: BTreeMap<String, PathBuf>,
+    #[serde(default)]
+    dependencies: BTreeMap<String, PathBuf>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+struct ProgramSection {
+    entry: PathBuf,
+    #[serde(default = "default_version")]
+    version: String,
+}
+
+#[derive(Deserialize, Debug, Default)]
+#[serde(deny_unknown_fields, rename_all = "kebab-case")]
+struct BuildSection {
+    profile: Option<String>,
+    split_threshold: Option<usize>,
+}
+
+fn default_version() -> String {
+    "0.1.0".to_string()
+}
+
+/// A project manifest together with the directory of the manifest, relative to which the paths
+/// declared in the manifest are resolved.
+struct Project {
+    dir: PathBuf,
+    manifest: Manifest,
+    profiles: BTreeMap<String, Profile>,
+}
+
+impl Project {
+    /// Reads and validates the manifest at the specified path.
+    fn read(path: &Path, report: &mut CommandReport) -> Result<Self, String> {
+        report.println(format_args!(
+            "Reading project manifest `{}`",
+            path.display()
+        ));
+        report.add_artifact("manifest", path);
+
+        let source = fs::read_to_string(path)
+            .map_err(|err| format!("Failed to open manifest `{}` - {}", path.display(), err))?;
+        let manifest: Manifest = toml::from_str(&source)
+            .map_err(|err| format!("Failed to parse manifest `{}` - {}", path.display(), err))?;
+
+        let profiles = manifest
+            .profile
+            .iter()
+            .map(|(name, settings)| {
+                let profile = Profile::with_settings(name, settings)
+                    .map_err(|err| format!("Invalid profile `{}` - {}", name, err))?;
+                Ok((name.clone(), profile))
+            })
+            .collect::<Result<_, String>>()?;
+
+        Ok(Self {
+            dir: path.parent().unwrap_or_else(|| Path::new("")).to_path_buf(),
+            manifest,
+            profiles,
+        })
+    }
+
+    /// Returns the name of the default profile of the project, if it is declared.
+    fn profile_name(&self) -> Option<&str> {
+        self.manifest.build.profile.as_deref()
+    }
+
+    /// Returns the profile with the specified name: the profile declared in the manifest, or the
+    /// built-in profile if the manifest does not declare a profile with this name.
+    fn profile(&self, name: &str) -> Result<Profile, String> {
+        match self.profiles.get(name) {
+            Some(profile) => Ok(profile.clone()),
+            None => name.parse().map_err(|_| {
+                format!(
+                    "Profile `{}` is neither built-in nor declared by the project",
+                    name
+                )
+            }),
+        }
+    }
+
+    /// Returns the path of the entry program of the project.
+    fn entry(&self) -> PathBuf {
+        self.dir.join(&self.manifest.program.entry)
+    }
+
+    /// Returns an assembler configured by the specified profile and by the manifest: the
+    /// assembler options of the manifest are applied, its libraries and dependencies are made
+    /// available to programs, and its features are defined as `FEATURE_<NAME>` constants set to
+    /// 1 if the feature is enabled (either by default, by the profile, or via the specified
+    /// features), and to 0 otherwise.
+    fn assembler(
+        &self,
+        profile: &Profile,
+        features: &[String],
+        report: &CommandReport,
+    ) -> Result<Assembler, String> {
+        let manifest = &self.manifest;
+        if let Some(feature) = features
+            .iter()
+            .chain(profile.features())
+            .find(|feature| !manifest.features.contains_key(*feature))
+        {
+            return Err(format!(
+                "Feature `{}` is not declared by the project",
+                feature
+            ));
+        }
+
+        let mut assembler = profile.assembler();
+        if let Some(threshold) = manifest.build.split_threshold {
+            assembler = assembler.with_split_threshold(threshold);
+        }
+
+        // libraries are assembled from the modules in their directories
+        for (namespace, dir) in manifest.libraries.iter() {
+            let dir = self.dir.join(dir);
+            report.println(format_args!(
+                "Reading library `{}` from `{}`",
+                namespace,
+                dir.display()
+            ));
+            let library = MaslLibrary::read_from_dir(&dir, namespace, &manifest.program.version)
+                .map_err(|err| format!("Failed to read library `{}` - {}", namespace, err))?;
+            assembler = assembler
+                .with_library(library)
+                .map_err(|err| format!("Failed to add library `{}` - {}", namespace, err))?;
+        }
+
+        // dependencies are read from .masl files, and must be declared under their namespaces
+        for (namespace, path) in manifest.dependencies.iter() {
+            let path = self.dir.join(path);
+            report.println(format_args!(
+                "Reading dependency `{}` from `{}`",
+                namespace,
+                path.display()
+            ));
+            let bytes = fs::read(&path).map_err(|err| {
+                format!("Failed to open dependency `{}` - {}", path.display(), err)
+            })?;
+            let library = MaslLibrary::from_bytes(&bytes)
+                .map_err(|err| format!("Failed to decode dependency `{}` - {}", namespace, err))?;
+            if vm_core::Library::root_ns(&library) != namespace {
+                return Err(format!(
+                    "Dependency `{}` is a library with the `{}` namespace",
+                    namespace,
+                    vm_core::Library::root_ns(&library)
+                ));
+            }
+            assembler = assembler
+                .with_library(library)
+                .map_err(|err| format!("Failed to add dependency `{}` - {}", namespace, err))?;
+        }
+        for (feature, &default) in manifest.features.iter() {
+            let enabled =
+                default || features.contains(feature) || profile.features().contains(feature);
+            let name = feature_const_name(feature);
+            assembler = assembler
+                .with_constant(&name, enabled as u64)
+                .map_err(|err| format!("Invalid feature `{}` - {}", feature, err))?;
+        }
+
+        Ok(assembler)
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the path of the manifest located in the current directory or in the closest of its
+/// ancestors, if any.
+fn find_manifest() -> Result<Option<PathBuf>, String> {
+    let current_dir = std::env::current_dir()
+        .map_err(|err| format!("Failed to read current directory - {}", err))?;
+    let manifest = current_dir
+        .ancestors()
+        .map(|dir| dir.join(MANIFEST_FILE_NAME))
+        .find(|path| path.is_file());
+    Ok(manifest)
+}
+
+/// Returns the name of the constant through which the specified feature is exposed to programs;
+/// for example, the `fast-hash` feature is exposed as `FEATURE_FAST_HASH`.
+fn feature_const_name(feature: &str) -> String {
+    format!(
+        "{}{}",
+        FEATURE_CONST_PREFIX,
+        feature.to_ascii_uppercase().replace('-', "_")
+    )
+}
@@ -1,30 +1,61 @@
-use super::data::{InputFile, OutputFile, ProgramFile, ProofFile};
+use super::{
+    data::{is_stdio_opt, InputFile, OutputFile, ProgramFile, ProofFile},
+    BuildOptions, CommandReport, Diagnostic, OutputFormat,
+};
 use air::ProofOptions;
+use processor::{ExecutionError, ExecutionOptions};
+use prover::{ExecutionProof, ProgressObserver, ProofCompression, ProvingStage};
 use std::path::PathBuf;
-use std::time::Instant;
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    mpsc::{self, RecvTimeoutError, Sender},
+    Arc,
+};
+use std::thread;
+use std::time::{Duration, Instant};
 use structopt::StructOpt;
+use vm_core::{utils::SystemClock, Program, ProgramInputs};
+
+/// Interval at which the progress of the prover and the cancellation flag are polled.
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Width of the progress bar in characters.
+const PROGRESS_BAR_WIDTH: usize = 20;
 
 #[derive(StructOpt, Debug)]
 #[structopt(name = "Prove", about = "Prove a miden program")]
 pub struct ProveCmd {
-    /// Path to .masm assembly file
-    #[structopt(short = "a", long = "assembly", parse(from_os_str))]
-    assembly_file: PathBuf,
-    /// Path to input file
+    #[structopt(flatten)]
+    build: BuildOptions,
+    /// Path to input file, or `-` to read the inputs from stdin
     #[structopt(short = "i", long = "input", parse(from_os_str))]
     input_file: Option<PathBuf>,
     /// Number of ouptuts
     #[structopt(short = "n", long = "num-outputs", default_value = "16")]
     num_outputs: usize,
-    /// Path to output file
-    #[structopt(short = "o", long = "output", parse(from_os_str))]
+    /// Path to output file, or `-` to write the outputs to stdout
+    #[structopt(short = "o", long = "output-file", parse(from_os_str))]
     output_file: Option<PathBuf>,
-    /// Path to proof file
+    /// Path to proof file, or `-` to write the proof to stdout
     #[structopt(short = "p", long = "proof", parse(from_os_str))]
     proof_file: Option<PathBuf>,
     /// Security level for execution proofs generated by the VM
     #[structopt(short = "s", long = "security", default_value = "96bits")]
     security: String,
+    /// Target security level in bits; when specified, proof options providing this level for the
+    /// trace length of the program are selected such that the proof is as small as possible
+    /// (overrides `--security`)
+    #[structopt(long = "security-level")]
+    security_level: Option<u32>,
+    /// Format in which results are reported (text or json)
+    #[structopt(long = "output", default_value = "text")]
+    output_format: OutputFormat,
+    /// Maximum number of cycles the program can run for
+    #[structopt(long = "max-cycles")]
+    max_cycles: Option<usize>,
+    /// Compress trace queries and FRI layers of the proof
+    #[structopt(long = "compress")]
+    compress: bool,
 }
 
 impl ProveCmd {
@@ -37,36 +68,186 @@ impl ProveCmd {
     }
 
     pub fn execute(&self) -> Result<(), String> {
-        println!("============================================================");
-        println!("Prove program");
-        println!("============================================================");
+        // artifacts written to stdout are not mixed up with the report
+        let to_stderr = is_stdio_opt(&self.output_file) || is_stdio_opt(&self.proof_file);
+        let mut report = CommandReport::with_stderr("prove", self.output_format, to_stderr);
+        let result = self.prove(&mut report);
+        report.finish(result)
+    }
+
+    fn prove(&self, report: &mut CommandReport) -> Result<(), Diagnostic> {
+        report.print_header("Prove program");
 
         // load program from file and compile
-        let program = ProgramFile::read(&self.assembly_file)?;
+        let build = self.build.resolve(report)?;
+        let program = ProgramFile::read(&build, report)?;
 
         // load input data from file
-        let input_data = InputFile::read(&self.input_file, &self.assembly_file)?;
+        let input_data = InputFile::read(&self.input_file, build.assembly_file(), report)?;
 
-        println!("Proving program ...");
+        let mut exec_options = ExecutionOptions::default();
+        if let Some(max_cycles) = self.max_cycles {
+            exec_options = exec_options.with_max_cycles(max_cycles);
+        }
+        let inputs = input_data.get_program_inputs()?;
+        let num_outputs = self.num_outputs;
+        let options = match self.security_level {
+            Some(security_level) => select_proof_options(
+                &program,
+                &inputs,
+                security_level,
+                exec_options.clone(),
+                report,
+            )?,
+            None => self.get_proof_security(),
+        };
+
+        report.println(format_args!("Proving program ..."));
         let now = Instant::now();
 
-        // execute program and generate proof
-        let (outputs, proof) = prover::prove(
-            &program,
-            &input_data.get_program_inputs(),
-            self.num_outputs,
-            &self.get_proof_security(),
-        )
-        .map_err(|err| format!("Failed to prove program - {:?}", err))?;
+        // execute program and generate proof on a separate thread, so that proving can be
+        // abandoned on Ctrl-C without waiting for the prover to complete
+
+        let cancelled = Arc::new(AtomicBool::new(false));
+        let flag = cancelled.clone();
+        ctrlc::set_handler(move || flag.store(true, Ordering::SeqCst))
+            .map_err(|err| format!("Failed to set Ctrl-C handler - {}", err))?;
 
-        println!("Program proved in {} ms", now.elapsed().as_millis());
+        let (sender, receiver) = mpsc::channel();
+        let mut observer = ProverObserver {
+            sender: sender.clone(),
+            cancelled: cancelled.clone(),
+        };
+        thread::spawn(move || {
+            let result = prover::prove_with_progress(
+                &program,
+                &inputs,
+                num_outputs,
+                &options,
+                exec_options,
+                &SystemClock::default(),
+                &mut observer,
+            );
+            sender.send(ProverEvent::Done(Box::new(result))).ok();
+        });
+
+        let result = loop {
+            match receiver.recv_timeout(POLL_INTERVAL) {
+                Ok(ProverEvent::Stage(stage)) => print_progress(report, stage),
+                Ok(ProverEvent::Done(result)) => break *result,
+                Err(RecvTimeoutError::Timeout) if cancelled.load(Ordering::SeqCst) => {
+                    break Err(ExecutionError::ProvingCancelled)
+                }
+                Err(RecvTimeoutError::Timeout) => (),
+                Err(RecvTimeoutError::Disconnected) => {
+                    return Err(Diagnostic::error(
+                        "Prover thread terminated unexpectedly".into(),
+                    ))
+                }
+            }
+        };
+        let (outputs, proof) = result
+            .map_err(|err| Diagnostic::from_execution_error(&err, "Failed to prove program"))?;
+
+        report.println(format_args!(
+            "Program proved in {} ms",
+            now.elapsed().as_millis()
+        ));
+        report.add_timing("prove", now.elapsed());
+        report.set_trace_length(proof.trace_length());
 
         // write proof to file
-        ProofFile::write(proof, &self.proof_file, &self.assembly_file)?;
+        let compression = if self.compress {
+            ProofCompression::Queries
+        } else {
+            ProofCompression::None
+        };
+        ProofFile::write(
+            proof,
+            compression,
+            &self.proof_file,
+            build.assembly_file(),
+            report,
+        )?;
 
         // write outputs
-        OutputFile::write(outputs, &self.output_file)?;
+        OutputFile::write(outputs, &self.output_file, report)?;
 
         Ok(())
     }
 }
+
+// PROOF OPTIONS SELECTION
+// ================================================================================================
+
+/// Executes the program to determine the length of its trace, and returns the proof options
+/// providing the specified security level for the trace with the smallest proof.
+fn select_proof_options(
+    program: &Program,
+    inputs: &ProgramInputs,
+    security_level: u32,
+    exec_options: ExecutionOptions,
+    report: &CommandReport,
+) -> Result<ProofOptions, Diagnostic> {
+    report.println(format_args!(
+        "Selecting proof options for {}-bit security ...",
+        security_level
+    ));
+    let estimate = prover::select_proof_options(program, inputs, security_level, exec_options)
+        .map_err(|err| Diagnostic::from_execution_error(&err, "Failed to select proof options"))?;
+
+    let options = estimate.options();
+    report.println(format_args!(
+        "Selected {} queries, blowup factor {}, FRI folding factor {}, {:?} hash, and {:?} field \
+        extension for trace length {} (conjectured security: {} bits, estimated proof size: {} \
+        bytes)",
+        options.num_queries(),
+        options.blowup_factor(),
+        options.to_fri_options().folding_factor(),
+        options.hash_fn(),
+        options.field_extension(),
+        estimate.trace_len(),
+        estimate.security_level(),
+        estimate.proof_size()
+    ));
+    Ok(options.clone())
+}
+
+// PROGRESS REPORTING
+// ================================================================================================
+
+/// Events sent by the prover thread.
+enum ProverEvent {
+    Stage(ProvingStage),
+    Done(Box<Result<(Vec<u64>, ExecutionProof), ExecutionError>>),
+}
+
+/// Forwards the stages of proof generation to the main thread, and cancels proof generation once
+/// Ctrl-C is pressed.
+struct ProverObserver {
+    sender: Sender<ProverEvent>,
+    cancelled: Arc<AtomicBool>,
+}
+
+impl ProgressObserver for ProverObserver {
+    fn on_stage(&mut self, stage: ProvingStage) {
+        self.sender.send(ProverEvent::Stage(stage)).ok();
+    }
+
+    fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+}
+
+/// Prints a progress bar showing how many stages of proof generation have been entered.
+fn print_progress(report: &CommandReport, stage: ProvingStage) {
+    let done = (stage.index() + 1) * PROGRESS_BAR_WIDTH / ProvingStage::NUM_STAGES;
+    report.println(format_args!(
+        "[{}{}] {}/{} {}",
+        "#".repeat(done),
+        " ".repeat(PROGRESS_BAR_WIDTH - done),
+        stage.index() + 1,
+        ProvingStage::NUM_STAGES,
+        stage
+    ));
+}
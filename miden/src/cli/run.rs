@@ -1,5 +1,11 @@
-use super::data::{InputFile, OutputFile, ProgramFile};
+use super::{
+    data::{
+        is_stdio, is_stdio_opt, InputFile, OutputFile, ProgramFile, SourceMapFile, TraceDumpFile,
+    },
+    BuildOptions, CommandReport, Diagnostic, OutputFormat,
+};
 use air::StarkField;
+use processor::{ColumnSelection, ExecutionOptions};
 use std::path::PathBuf;
 use std::time::Instant;
 use structopt::StructOpt;
@@ -7,40 +13,97 @@ use structopt::StructOpt;
 #[derive(StructOpt, Debug)]
 #[structopt(name = "Run", about = "Run a miden program")]
 pub struct RunCmd {
-    /// Path to .masm assembly file
-    #[structopt(short = "a", long = "assembly", parse(from_os_str))]
-    assembly_file: PathBuf,
-    /// Path to input file
+    #[structopt(flatten)]
+    build: BuildOptions,
+    /// Path to input file, or `-` to read the inputs from stdin
     #[structopt(short = "i", long = "input", parse(from_os_str))]
     input_file: Option<PathBuf>,
     /// Number of ouptuts
     #[structopt(short = "n", long = "num-outputs", default_value = "16")]
     num_outputs: usize,
-    /// Path to output file
-    #[structopt(short = "o", long = "output", parse(from_os_str))]
+    /// Path to output file, or `-` to write the outputs to stdout
+    #[structopt(short = "o", long = "output-file", parse(from_os_str))]
     output_file: Option<PathBuf>,
+    /// Format in which results are reported (text or json)
+    #[structopt(long = "output", default_value = "text")]
+    output_format: OutputFormat,
+    /// Path to a file into which the execution trace is dumped as comma-separated values, or as
+    /// a Parquet file if the path has the `.parquet` extension
+    #[structopt(long = "trace-dump", parse(from_os_str))]
+    trace_dump_file: Option<PathBuf>,
+    /// Comma-separated trace columns to dump, e.g. `stack[0..4],memory`; all columns by default
+    #[structopt(
+        long = "trace-columns",
+        use_delimiter = true,
+        requires = "trace-dump-file"
+    )]
+    trace_columns: Vec<ColumnSelection>,
+    /// Dump only every n-th row of the execution trace; every row is dumped by default
+    #[structopt(long = "trace-step", requires = "trace-dump-file")]
+    trace_step: Option<usize>,
+    /// Maximum number of cycles the program can run for
+    #[structopt(long = "max-cycles")]
+    max_cycles: Option<usize>,
+    /// Path to the source map of the program; if provided, the program is executed in debug mode
+    /// and execution errors point to the failed instruction
+    #[structopt(long = "source-map", parse(from_os_str))]
+    source_map_file: Option<PathBuf>,
 }
 
 impl RunCmd {
     pub fn execute(&self) -> Result<(), String> {
-        println!("============================================================");
-        println!("Run program");
-        println!("============================================================");
+        // outputs written to stdout are not mixed up with the report
+        let to_stderr = is_stdio_opt(&self.output_file);
+        let mut report = CommandReport::with_stderr("run", self.output_format, to_stderr);
+        let result = self.run(&mut report);
+        report.finish(result)
+    }
+
+    fn run(&self, report: &mut CommandReport) -> Result<(), Diagnostic> {
+        report.print_header("Run program");
 
         // load program from file and compile
-        let program = ProgramFile::read(&self.assembly_file)?;
+        let build = self.build.resolve(report)?;
+        let program = ProgramFile::read(&build, report)?;
+
+        // load source map from file
+        let source_map = match &self.source_map_file {
+            Some(path) => Some(SourceMapFile::read(path, program.hash(), report)?),
+            None => None,
+        };
 
         // load input data from file
-        let input_data = InputFile::read(&self.input_file, &self.assembly_file)?;
+        let input_data = InputFile::read(&self.input_file, build.assembly_file(), report)?;
 
-        print!("Executing program... ");
+        report.print(format_args!("Executing program... "));
         let now = Instant::now();
 
         // generate execution trace
-        let trace = processor::execute(&program, &input_data.get_program_inputs())
-            .map_err(|err| format!("Failed to generate exection trace = {:?}", err))?;
+        let mut options = ExecutionOptions::default();
+        if let Some(max_cycles) = self.max_cycles {
+            options = options.with_max_cycles(max_cycles);
+        }
+        if source_map.is_some() {
+            options = options.with_debug_mode();
+        }
+        let trace =
+            processor::execute_with_options(&program, &input_data.get_program_inputs()?, options)
+                .map_err(|err| {
+                let context = "Failed to generate execution trace";
+                match &source_map {
+                    Some(source_map) => Diagnostic::from_execution_error_with_source_map(
+                        &err,
+                        source_map,
+                        (!is_stdio(build.assembly_file())).then(|| build.assembly_file()),
+                        context,
+                    ),
+                    None => Diagnostic::from_execution_error(&err, context),
+                }
+            })?;
 
-        println!("done ({} ms)", now.elapsed().as_millis());
+        report.println(format_args!("done ({} ms)", now.elapsed().as_millis()));
+        report.add_timing("execute", now.elapsed());
+        report.set_cycles(trace.num_cycles());
 
         // extract outputs from execution trace
         let outputs = trace.last_stack_state()[..self.num_outputs]
@@ -49,7 +112,20 @@ impl RunCmd {
             .collect::<Vec<_>>();
 
         // write outputs to file
-        OutputFile::write(outputs, &self.output_file)?;
+        OutputFile::write(outputs, &self.output_file, report)?;
+
+        // dump the selected columns of the execution trace
+        if let Some(path) = &self.trace_dump_file {
+            let step = self.trace_step.unwrap_or(1);
+            if step == 0 {
+                return Err("Trace step must be greater than zero".to_string().into());
+            }
+            let mut dump = trace.dump().with_step(step);
+            if !self.trace_columns.is_empty() {
+                dump = dump.with_columns(self.trace_columns.clone());
+            }
+            TraceDumpFile::write(&dump, path, report)?;
+        }
 
         Ok(())
     }
@@ -1,4 +1,7 @@
-use super::data::{InputFile, OutputFile, ProgramHash, ProofFile};
+use super::{
+    data::{InputFile, OutputFile, ProgramHash, ProofFile},
+    CommandReport, Diagnostic, OutputFormat,
+};
 use std::path::PathBuf;
 use std::time::Instant;
 use structopt::StructOpt;
@@ -10,7 +13,7 @@ pub struct VerifyCmd {
     #[structopt(short = "i", long = "input", parse(from_os_str))]
     input_file: Option<PathBuf>,
     /// Path to output file
-    #[structopt(short = "o", long = "output", parse(from_os_str))]
+    #[structopt(short = "o", long = "output-file", parse(from_os_str))]
     output_file: Option<PathBuf>,
     /// Path to proof file
     #[structopt(short = "p", long = "proof", parse(from_os_str))]
@@ -18,27 +21,36 @@ pub struct VerifyCmd {
     /// Program hash (hex)
     #[structopt(short = "h", long = "program-hash")]
     program_hash: String,
+    /// Format in which results are reported (text or json)
+    #[structopt(long = "output", default_value = "text")]
+    output_format: OutputFormat,
 }
 
 impl VerifyCmd {
     pub fn execute(&self) -> Result<(), String> {
-        println!("============================================================");
-        println!("Verify program");
-        println!("============================================================");
+        let mut report = CommandReport::new("verify", self.output_format);
+        let result = self.verify(&mut report);
+        report.finish(result)
+    }
+
+    fn verify(&self, report: &mut CommandReport) -> Result<(), Diagnostic> {
+        report.print_header("Verify program");
 
         // read program hash from input
         let program_hash = ProgramHash::read(&self.program_hash)?;
+        report.set_program_hash(self.program_hash.clone());
 
         // load input data from file
-        let input_data = InputFile::read(&self.input_file, &self.proof_file)?;
+        let input_data = InputFile::read(&self.input_file, &self.proof_file, report)?;
 
         // load outputs data from file
-        let outputs_data = OutputFile::read(&self.output_file, &self.proof_file)?;
+        let outputs_data = OutputFile::read(&self.output_file, &self.proof_file, report)?;
 
         // load proof from file
-        let proof = ProofFile::read(&Some(self.proof_file.clone()), &self.proof_file)?;
+        let proof = ProofFile::read(&Some(self.proof_file.clone()), &self.proof_file, report)?;
+        report.set_trace_length(proof.trace_length());
 
-        println!("verifying program...");
+        report.println(format_args!("verifying program..."));
         let now = Instant::now();
 
         // verify proof
@@ -50,7 +62,15 @@ impl VerifyCmd {
         )
         .map_err(|err| format!("Program failed verification! - {}", err))?;
 
-        println!("Verification complete in {} ms", now.elapsed().as_millis());
+        report.println(format_args!(
+            "Verification complete in {} ms",
+            now.elapsed().as_millis()
+        ));
+        report.add_timing("verify", now.elapsed());
+
+        // output file stores the outputs in reverse order of how the verifier expects them
+        let outputs = outputs_data.outputs().into_iter().rev().collect::<Vec<_>>();
+        report.set_outputs(&outputs);
 
         Ok(())
     }
@@ -1,4 +1,4 @@
-use miden::{Program, ProgramInputs, ProofOptions, StarkProof};
+use miden::{ExecutionProof, Program, ProgramInputs, ProofOptions};
 use std::time::Instant;
 use structopt::StructOpt;
 
@@ -96,7 +96,7 @@ impl ExampleOptions {
 
         // verify that executing a program with a given hash and given inputs
         // results in the expected output
-        let proof = StarkProof::from_bytes(&proof_bytes).unwrap();
+        let proof = ExecutionProof::from_bytes(&proof_bytes).unwrap();
         let now = Instant::now();
         match miden::verify(program.hash(), &pub_inputs, &outputs, proof) {
             Ok(_) => println!("Execution verified in {} ms", now.elapsed().as_millis()),
@@ -3,15 +3,43 @@
 // EXPORTS
 // ================================================================================================
 
-pub use air::{FieldExtension, HashFunction, ProofOptions};
-pub use assembly::{Assembler, AssemblyError};
+pub use air::{FieldExtension, HashFunction, ProofOptions, PublicInputs, PROOF_VERSION};
+pub use assembly::{
+    explain_error, Assembler, AssemblyError, ErrorCode, ErrorExplanation, Expansion,
+    InstructionExtension,
+};
 pub use processor::{
-    execute, execute_iter, AsmOpInfo, ExecutionError, ExecutionTrace, VmState, VmStateIterator,
+    execute, execute_iter, execute_with_advice, execute_with_options, hot_swap_iter,
+    replace_procedure, resume_iter, AdviceProvider, AdviceRecord, AsmOpInfo, BlockProfile,
+    BlockStats, CallStack, CallStackFrame, ChainedProvider, CheckpointError, ColumnGroup,
+    ColumnSelection, ContextScope, Coverage, CoverageReport, ExecutionError, ExecutionOptions,
+    ExecutionStatus, ExecutionTrace, FallbackProvider, GasSchedule, HintProvider, HotSwapError,
+    MemAdviceProvider, MemoryAccess, MemoryAccessType, MemoryPermission, MemoryPermissions,
+    MemoryViolation, OpClass, Process, RecordingProvider, ResolvedAdvice, ResolvingProvider,
+    TraceDump, VmState, VmStateIterator, BLS381_FP_REDUCE_EVENT, CHECKPOINT_VERSION,
+    EXECUTION_ERROR_INDEX, KERNEL_CONTEXT, ROOT_CONTEXT, U64_DIVMOD_EVENT, VEC_SORT_EVENT,
+};
+pub use prover::{
+    prove, prove_trace, prove_with_options, prove_with_progress, select_proof_options,
+    ExecutionProof, ProgressObserver, ProofCompression, ProofEstimate, ProofFormatError,
+    ProofHeader, ProvingStage, StarkProof, TraceLenSummary,
+};
+pub use stdlib::{RandomCoin, ReturnData, ReturnDataReader, RETURNDATA_ADDR};
+pub use verifier::{
+    parse_program_hash, stack_inputs_commitment, verify, verify_batch, verify_bytes,
+    BatchVerificationError, VerificationError,
 };
-pub use prover::{prove, StarkProof};
-pub use verifier::{verify, VerificationError};
 pub use vm_core::{
     chiplets::hasher::Digest,
-    errors::{AdviceSetError, InputError},
-    AdviceSet, Program, ProgramInputs,
+    errors::{AdviceSetError, HexParseError, InputError, ReturnDataError, SourceMapError},
+    AdviceSet, CodeBlockTable, DuplicateBlock, DuplicationReport, InputLimits, InstructionInfo,
+    Kernel, LeafIndex, MastHasher, MmrPeaks, Program, ProgramInputs, SmtRoot, SourceMap,
+    SpanSourceMap, SOURCE_MAP_VERSION,
 };
+
+#[cfg(feature = "std")]
+pub use vm_core::utils::SystemClock;
+pub use vm_core::utils::{Clock, FixedClock};
+
+#[cfg(feature = "testing")]
+pub use vm_core::testing;
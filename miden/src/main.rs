@@ -17,8 +17,13 @@ pub struct Cli {
 #[derive(StructOpt, Debug)]
 pub enum Actions {
     Analyze(tools::Analyze),
+    Canonicalize(cli::CanonicalizeCmd),
     Compile(cli::CompileCmd),
+    Coverage(tools::Coverage),
+    CycleDiff(tools::CycleDiff),
     Example(examples::ExampleOptions),
+    ExplainError(cli::ExplainErrorCmd),
+    Hash(cli::HashCmd),
     Prove(cli::ProveCmd),
     Run(cli::RunCmd),
     Verify(cli::VerifyCmd),
@@ -29,8 +34,13 @@ impl Cli {
     pub fn execute(&self) -> Result<(), String> {
         match &self.action {
             Actions::Analyze(analyze) => analyze.execute(),
+            Actions::Canonicalize(canonicalize) => canonicalize.execute(),
             Actions::Compile(compile) => compile.execute(),
+            Actions::Coverage(coverage) => coverage.execute(),
+            Actions::CycleDiff(cycle_diff) => cycle_diff.execute(),
             Actions::Example(example) => example.execute(),
+            Actions::ExplainError(explain) => explain.execute(),
+            Actions::Hash(hash) => hash.execute(),
             Actions::Prove(prove) => prove.execute(),
             Actions::Run(run) => run.execute(),
             Actions::Verify(verify) => verify.execute(),
@@ -49,8 +59,9 @@ pub fn main() {
     // read command-line args
     let cli = Cli::from_args();
 
-    // execute cli action
+    // execute cli action; failures are reported via a non-zero exit code
     if let Err(error) = cli.execute() {
         println!("{}", error);
+        std::process::exit(1);
     }
 }
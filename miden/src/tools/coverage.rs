@@ -0,0 +1,234 @@
+use super::{
+    super::cli::{CommandReport, Diagnostic, OutputFormat},
+    read_corpus, ProgramError,
+};
+use assembly::Assembler;
+use core::fmt;
+use processor::{CoverageReport, ExecutionOptions};
+use serde_derive::Serialize;
+use std::{fs, path::PathBuf, time::Instant};
+use structopt::StructOpt;
+use vm_core::{ProgramInputs, SourceMap};
+
+// CLI
+// ================================================================================================
+
+/// Defines cli interface
+#[derive(StructOpt, Debug)]
+#[structopt(about = "Report coverage of the source code of a corpus of programs")]
+pub struct Coverage {
+    /// Path to a directory with .masm programs; inputs of a program are read from the .inputs file
+    /// with the same name, if there is one
+    #[structopt(short = "c", long = "corpus", parse(from_os_str))]
+    corpus_dir: PathBuf,
+    /// Path to a file into which the coverage report is written in the LCOV format
+    #[structopt(long = "lcov", parse(from_os_str))]
+    lcov_file: Option<PathBuf>,
+    /// Number of the most executed instructions to report
+    #[structopt(long = "top", default_value = "10")]
+    num_instructions: usize,
+    /// Format in which results are reported (text or json)
+    #[structopt(long = "output", default_value = "text")]
+    output_format: OutputFormat,
+}
+
+/// Implements CLI execution logic
+impl Coverage {
+    pub fn execute(&self) -> Result<(), String> {
+        let mut report = CommandReport::new("coverage", self.output_format);
+        let result = self.cover(&mut report);
+        report.finish(result)
+    }
+
+    fn cover(&self, report: &mut CommandReport) -> Result<(), Diagnostic> {
+        report.print_header("Report coverage");
+        let corpus = read_corpus(&self.corpus_dir, "coverage", report)?;
+
+        let now = Instant::now();
+        let mut coverage = CoverageReport::default();
+        for (name, source, inputs) in corpus.iter() {
+            let file = self.corpus_dir.join(name).to_string_lossy().to_string();
+            cover_program(&mut coverage, source, inputs, &file).map_err(|err| {
+                Diagnostic::error(format!("Failed to cover program `{}` - {}", name, err))
+            })?;
+        }
+        report.add_timing("execute", now.elapsed());
+
+        if let Some(path) = &self.lcov_file {
+            fs::write(path, coverage.to_lcov()).map_err(|err| {
+                format!("Failed to write LCOV file `{}` - {}", path.display(), err)
+            })?;
+            report.println(format_args!("LCOV report written to `{}`", path.display()));
+            report.add_artifact("lcov", path);
+        }
+
+        let summary = CoverageSummary::new(&coverage, corpus.len(), self.num_instructions);
+        report.println(format_args!("\n{}", summary));
+        report.set_analysis(&summary);
+        Ok(())
+    }
+}
+
+/// Compiles the provided program in debug mode, executes it against the provided inputs, and adds
+/// the coverage of the execution to the specified report; instructions of the program itself are
+/// attributed to the specified file.
+pub fn cover_program(
+    report: &mut CoverageReport,
+    source: &str,
+    inputs: &ProgramInputs,
+    file: &str,
+) -> Result<(), ProgramError> {
+    let program = Assembler::new(true)
+        .compile(source)
+        .map_err(ProgramError::AssemblyError)?;
+    let options = ExecutionOptions::default().with_coverage();
+    let trace = processor::execute_with_options(&program, inputs, options)
+        .map_err(ProgramError::ExecutionError)?;
+    let coverage = trace.coverage().expect("coverage recording not enabled");
+    report.add(coverage, &SourceMap::new(&program), file);
+    Ok(())
+}
+
+// COVERAGE SUMMARY
+// ================================================================================================
+
+/// Summary of a coverage report: the numbers of covered lines in every source file, and the most
+/// executed instructions.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct CoverageSummary {
+    num_programs: usize,
+    num_lines: usize,
+    num_lines_hit: usize,
+    files: Vec<FileSummary>,
+    instructions: Vec<(String, u64)>,
+}
+
+/// Numbers of lines with instructions, and of executed lines, of a single source file.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct FileSummary {
+    file: String,
+    num_lines: usize,
+    num_lines_hit: usize,
+}
+
+impl CoverageSummary {
+    /// Returns a summary of the specified report of the specified number of programs, listing
+    /// up to `num_instructions` of the most executed instructions.
+    pub fn new(report: &CoverageReport, num_programs: usize, num_instructions: usize) -> Self {
+        let files = report
+            .files()
+            .map(|file| {
+                let lines = report.lines(file).collect::<Vec<_>>();
+                FileSummary {
+                    file: file.to_string(),
+                    num_lines: lines.len(),
+                    num_lines_hit: lines.iter().filter(|(_, count)| *count > 0).count(),
+                }
+            })
+            .collect();
+
+        let mut instructions = report
+            .instruction_histogram()
+            .iter()
+            .map(|(name, &count)| (name.clone(), count))
+            .collect::<Vec<_>>();
+        instructions.sort_by(|(a_name, a_count), (b_name, b_count)| {
+            b_count.cmp(a_count).then_with(|| a_name.cmp(b_name))
+        });
+        instructions.truncate(num_instructions);
+
+        Self {
+            num_programs,
+            num_lines: report.num_lines(),
+            num_lines_hit: report.num_lines_hit(),
+            files,
+            instructions,
+        }
+    }
+}
+
+impl fmt::Display for CoverageSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "Covered {} of {} lines ({}) in {} programs",
+            self.num_lines_hit,
+            self.num_lines,
+            percent(self.num_lines_hit, self.num_lines),
+            self.num_programs
+        )?;
+        for file in self.files.iter() {
+            writeln!(
+                f,
+                "  {}: {} of {} lines ({})",
+                file.file,
+                file.num_lines_hit,
+                file.num_lines,
+                percent(file.num_lines_hit, file.num_lines)
+            )?;
+        }
+        if !self.instructions.is_empty() {
+            writeln!(f, "Most executed instructions:")?;
+            for (name, count) in self.instructions.iter() {
+                writeln!(f, "  {:<24} {}", name, count)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Formats the ratio of the specified numbers as a percentage.
+fn percent(part: usize, total: usize) -> String {
+    match total {
+        0 => "-".to_string(),
+        total => format!("{:.1}%", part as f64 * 100.0 / total as f64),
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::{cover_program, CoverageReport, CoverageSummary};
+    use vm_core::ProgramInputs;
+
+    const SOURCE: &str = "\
+use.std::math::u64
+begin
+    push.1.2.3.4
+    exec.u64::wrapping_add
+    drop drop
+end";
+
+    #[test]
+    fn cover_library_procedures() {
+        let mut report = CoverageReport::default();
+        cover_program(&mut report, SOURCE, &ProgramInputs::none(), "a.masm").unwrap();
+        cover_program(&mut report, SOURCE, &ProgramInputs::none(), "a.masm").unwrap();
+
+        // instructions of the stdlib procedures are attributed to the files of their modules
+        assert_eq!(
+            vec!["a.masm", "std/math/u64.masm"],
+            report.files().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![(3, 2), (5, 2)],
+            report.lines("a.masm").collect::<Vec<_>>()
+        );
+        let procedures = report
+            .procedures("std/math/u64.masm")
+            .map(|(label, _)| label)
+            .collect::<Vec<_>>();
+        assert!(procedures.contains(&"u64::wrapping_add"));
+        assert!(report
+            .procedures("std/math/u64.masm")
+            .all(|(_, count)| count == 2));
+
+        let summary = CoverageSummary::new(&report, 2, 3);
+        assert_eq!(report.num_lines(), summary.num_lines);
+        assert_eq!(3, summary.instructions.len());
+        assert!(summary.instructions[0].1 >= summary.instructions[2].1);
+        assert!(summary.to_string().starts_with("Covered "));
+    }
+}
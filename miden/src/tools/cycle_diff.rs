@@ -0,0 +1,725 @@
+use super::{
+    super::cli::{CommandReport, Diagnostic, OutputFormat},
+    read_corpus, ProgramError,
+};
+use assembly::{Assembler, OptLevel, Optimization, PushEncoding};
+use core::fmt;
+use processor::ExecutionOptions;
+use serde_derive::{Deserialize, Serialize};
+use std::{collections::BTreeMap, fs, path::PathBuf, time::Instant};
+use structopt::StructOpt;
+use vm_core::ProgramInputs;
+
+// CLI
+// ================================================================================================
+
+/// Defines cli interface
+#[derive(StructOpt, Debug)]
+#[structopt(
+    about = "Compare cycle counts of a corpus of programs compiled with two sets of \
+    assembler options"
+)]
+pub struct CycleDiff {
+    /// Path to a directory with .masm programs; inputs of a program are read from the .inputs file
+    /// with the same name, if there is one
+    #[structopt(short = "c", long = "corpus", parse(from_os_str))]
+    corpus_dir: PathBuf,
+    /// Optimization level of the base assembler (none, basic, or aggressive)
+    #[structopt(long = "base-opt-level", default_value = "none", parse(try_from_str = parse_opt_level))]
+    base_opt_level: OptLevel,
+    /// Push encoding of the base assembler (default, min-cycles, or min-size)
+    #[structopt(long = "base-push-encoding", default_value = "default", parse(try_from_str = parse_push_encoding))]
+    base_push_encoding: PushEncoding,
+    /// Optimization disabled in the base assembler; can be specified multiple times
+    #[structopt(long = "base-disable", number_of_values = 1, parse(try_from_str = parse_optimization))]
+    base_disabled: Vec<Optimization>,
+    /// Optimization level of the new assembler (none, basic, or aggressive)
+    #[structopt(long = "new-opt-level", default_value = "none", parse(try_from_str = parse_opt_level))]
+    new_opt_level: OptLevel,
+    /// Push encoding of the new assembler (default, min-cycles, or min-size)
+    #[structopt(long = "new-push-encoding", default_value = "default", parse(try_from_str = parse_push_encoding))]
+    new_push_encoding: PushEncoding,
+    /// Optimization disabled in the new assembler; can be specified multiple times
+    #[structopt(long = "new-disable", number_of_values = 1, parse(try_from_str = parse_optimization))]
+    new_disabled: Vec<Optimization>,
+    /// Path to cycle counts saved by an earlier run (e.g., with a previous version of the
+    /// assembler); if specified, these are used instead of compiling the corpus with the base
+    /// options
+    #[structopt(long = "baseline", parse(from_os_str))]
+    baseline_file: Option<PathBuf>,
+    /// Path to a file into which cycle counts of the corpus compiled with the new options are
+    /// written, for use as a baseline by later runs
+    #[structopt(long = "save-baseline", parse(from_os_str))]
+    save_baseline_file: Option<PathBuf>,
+    /// Minimum relative change (in percent) of a significant cycle count delta
+    #[structopt(long = "threshold", default_value = "1.0")]
+    threshold_percent: f64,
+    /// Minimum absolute change (in cycles) of a significant cycle count delta
+    #[structopt(long = "min-cycles", default_value = "10")]
+    threshold_cycles: u64,
+    /// Format in which results are reported (text or json)
+    #[structopt(long = "output", default_value = "text")]
+    output_format: OutputFormat,
+}
+
+/// Implements CLI execution logic
+impl CycleDiff {
+    pub fn execute(&self) -> Result<(), String> {
+        let mut report = CommandReport::new("cycle-diff", self.output_format);
+        let result = self.compare(&mut report);
+        report.finish(result)
+    }
+
+    fn compare(&self, report: &mut CommandReport) -> Result<(), Diagnostic> {
+        report.print_header("Compare cycle counts");
+        let corpus = read_corpus(&self.corpus_dir, "cycle-diff", report)?;
+
+        // measure the corpus with the base options, unless a saved baseline is provided
+        let now = Instant::now();
+        let base = match &self.baseline_file {
+            Some(path) => {
+                report.println(format_args!("Reading baseline `{}`", path.display()));
+                report.add_artifact("baseline", path);
+                let baseline = fs::read_to_string(path).map_err(|err| {
+                    format!(
+                        "Failed to open baseline file `{}` - {}",
+                        path.display(),
+                        err
+                    )
+                })?;
+                serde_json::from_str(&baseline)
+                    .map_err(|err| format!("Failed to deserialize baseline - {}", err))?
+            }
+            None => {
+                let options = AssemblerOptions::new(
+                    self.base_opt_level,
+                    self.base_push_encoding,
+                    &self.base_disabled,
+                );
+                report.println(format_args!(
+                    "Measuring corpus with base options: {}",
+                    options
+                ));
+                measure_corpus(&corpus, &options)?
+            }
+        };
+        let options = AssemblerOptions::new(
+            self.new_opt_level,
+            self.new_push_encoding,
+            &self.new_disabled,
+        );
+        report.println(format_args!(
+            "Measuring corpus with new options: {}",
+            options
+        ));
+        let new = measure_corpus(&corpus, &options)?;
+        report.add_timing("measure", now.elapsed());
+
+        if let Some(path) = &self.save_baseline_file {
+            let baseline = serde_json::to_string_pretty(&new)
+                .map_err(|err| format!("Failed to serialize baseline - {}", err))?;
+            fs::write(path, baseline).map_err(|err| {
+                format!(
+                    "Failed to write baseline file `{}` - {}",
+                    path.display(),
+                    err
+                )
+            })?;
+            report.println(format_args!("Baseline written to `{}`", path.display()));
+            report.add_artifact("saved_baseline", path);
+        }
+
+        let threshold = Threshold::new(self.threshold_percent, self.threshold_cycles);
+        let diff = CycleDiffReport::new(&base, &new, threshold);
+        report.println(format_args!("\n{}", diff));
+        report.set_analysis(&diff);
+
+        match diff.num_regressions() {
+            0 => Ok(()),
+            n => Err(Diagnostic::error(format!(
+                "Found {} significant cycle count regression(s)",
+                n
+            ))),
+        }
+    }
+}
+
+// ASSEMBLER OPTIONS
+// ================================================================================================
+
+/// Settings of an assembler compared by [CycleDiff].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AssemblerOptions {
+    opt_level: OptLevel,
+    push_encoding: PushEncoding,
+    disabled: Vec<Optimization>,
+}
+
+impl AssemblerOptions {
+    /// Returns options with the specified optimization level, push encoding, and disabled
+    /// optimizations.
+    pub fn new(
+        opt_level: OptLevel,
+        push_encoding: PushEncoding,
+        disabled: &[Optimization],
+    ) -> Self {
+        Self {
+            opt_level,
+            push_encoding,
+            disabled: disabled.to_vec(),
+        }
+    }
+
+    /// Returns a new release assembler configured with these options.
+    pub fn assembler(&self) -> Assembler {
+        let assembler = Assembler::new(false)
+            .with_opt_level(self.opt_level)
+            .with_push_encoding(self.push_encoding);
+        self.disabled
+            .iter()
+            .fold(assembler, |assembler, &optimization| {
+                assembler.without_optimization(optimization)
+            })
+    }
+}
+
+impl fmt::Display for AssemblerOptions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let opt_level = match self.opt_level {
+            OptLevel::None => "none",
+            OptLevel::Basic => "basic",
+            OptLevel::Aggressive => "aggressive",
+        };
+        let push_encoding = match self.push_encoding {
+            PushEncoding::Default => "default",
+            PushEncoding::MinCycles => "min-cycles",
+            PushEncoding::MinSize => "min-size",
+        };
+        write!(
+            f,
+            "opt-level {}, push-encoding {}",
+            opt_level, push_encoding
+        )?;
+        if !self.disabled.is_empty() {
+            let disabled = self.disabled.iter().map(|o| o.name()).collect::<Vec<_>>();
+            write!(f, ", disabled {}", disabled.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+// CYCLE COUNTS
+// ================================================================================================
+
+/// Cycle counts of all programs of a corpus, keyed by program file name.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CorpusCycles {
+    programs: BTreeMap<String, ProgramCycles>,
+}
+
+/// Cycle counts of a single program.
+///
+/// The total number of cycles is measured on the program as it is normally compiled. Cycles of
+/// procedures are measured on the program compiled with procedure profiling, which keeps the
+/// bodies of procedures invoked via `exec` as separate blocks; thus, they include the few cycles
+/// needed to enter and leave these blocks. The cycles of a procedure include the cycles of all
+/// procedures it invokes, summed over all invocations of the procedure. Procedures with identical
+/// bodies cannot be told apart, and are all attributed the cycles of all of them.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProgramCycles {
+    cycles: u64,
+    procedures: BTreeMap<String, u64>,
+}
+
+impl ProgramCycles {
+    /// Returns the number of cycles executed by the program.
+    pub fn cycles(&self) -> u64 {
+        self.cycles
+    }
+
+    /// Returns the number of cycles executed by each procedure invoked by the program, keyed by
+    /// procedure name (e.g., `std::math::u64::checked_add`).
+    pub fn procedures(&self) -> &BTreeMap<String, u64> {
+        &self.procedures
+    }
+}
+
+/// Compiles the provided program with the specified options, executes it against the provided
+/// inputs, and returns the numbers of executed cycles.
+pub fn measure_program(
+    source: &str,
+    inputs: &ProgramInputs,
+    options: &AssemblerOptions,
+) -> Result<ProgramCycles, ProgramError> {
+    let assembler = options.assembler();
+    let program = assembler
+        .compile(source)
+        .map_err(ProgramError::AssemblyError)?;
+    let trace = processor::execute(&program, inputs).map_err(ProgramError::ExecutionError)?;
+
+    // attribute the cycles of the profiling build to procedures by the MAST roots of their bodies;
+    // names starting with `#` refer to the program body rather than to procedures
+    let (program, audit_log) = assembler
+        .with_profiling()
+        .compile_with_audit(source)
+        .map_err(ProgramError::AssemblyError)?;
+    let exec_options = ExecutionOptions::default().with_block_profiling();
+    let profiled = processor::execute_with_options(&program, inputs, exec_options)
+        .map_err(ProgramError::ExecutionError)?;
+    let profile = profiled
+        .block_profile()
+        .expect("block profiling not enabled");
+    let procedures = audit_log
+        .procedures()
+        .iter()
+        .filter(|proc| !proc.name().starts_with('#'))
+        .filter_map(|proc| {
+            let stats = profile.get(proc.after())?;
+            Some((proc.name().to_string(), stats.cycles()))
+        })
+        .collect();
+
+    Ok(ProgramCycles {
+        cycles: trace.num_cycles() as u64,
+        procedures,
+    })
+}
+
+/// Measures all programs of the provided corpus with the specified options.
+fn measure_corpus(
+    corpus: &[(String, String, ProgramInputs)],
+    options: &AssemblerOptions,
+) -> Result<CorpusCycles, Diagnostic> {
+    let mut programs = BTreeMap::new();
+    for (name, source, inputs) in corpus {
+        let cycles = measure_program(source, inputs, options).map_err(|err| {
+            Diagnostic::error(format!(
+                "Failed to measure program `{}` with options {} - {}",
+                name, options, err
+            ))
+        })?;
+        programs.insert(name.clone(), cycles);
+    }
+    Ok(CorpusCycles { programs })
+}
+
+// THRESHOLD
+// ================================================================================================
+
+/// Specifies which cycle count deltas are significant: a delta is significant if it changes the
+/// cycle count by at least the specified percentage and by at least the specified number of
+/// cycles.
+#[derive(Copy, Clone, Debug, PartialEq, Serialize)]
+pub struct Threshold {
+    percent: f64,
+    cycles: u64,
+}
+
+impl Threshold {
+    /// Returns a threshold with the specified minimum relative and absolute changes.
+    pub fn new(percent: f64, cycles: u64) -> Self {
+        Self { percent, cycles }
+    }
+
+    /// Returns true if the change from `base` to `new` cycles is significant.
+    pub fn is_significant(&self, base: u64, new: u64) -> bool {
+        let change = base.abs_diff(new);
+        change >= self.cycles && change as f64 * 100.0 >= self.percent * base as f64
+    }
+}
+
+// CYCLE DIFF REPORT
+// ================================================================================================
+
+/// Change of the number of cycles executed by a program or a procedure.
+///
+/// A program or a procedure measured only with one of the compared options has no count for the
+/// other options; the change of such a program or procedure is never significant.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct CycleDelta {
+    name: String,
+    base: Option<u64>,
+    new: Option<u64>,
+    delta: i64,
+    percent: Option<f64>,
+    significant: bool,
+}
+
+impl CycleDelta {
+    /// Returns the change between the specified cycle counts.
+    fn new(name: &str, base: Option<u64>, new: Option<u64>, threshold: Threshold) -> Self {
+        let (delta, percent, significant) = match (base, new) {
+            (Some(base), Some(new)) => {
+                let delta = new as i64 - base as i64;
+                let percent = (base != 0).then(|| delta as f64 * 100.0 / base as f64);
+                (delta, percent, threshold.is_significant(base, new))
+            }
+            _ => (0, None, false),
+        };
+        Self {
+            name: name.to_string(),
+            base,
+            new,
+            delta,
+            percent,
+            significant,
+        }
+    }
+
+    /// Returns the name of the program or procedure.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the number of cycles measured with the base options.
+    pub fn base(&self) -> Option<u64> {
+        self.base
+    }
+
+    /// Returns the number of cycles measured with the new options.
+    pub fn new_cycles(&self) -> Option<u64> {
+        self.new
+    }
+
+    /// Returns the change of the number of cycles; positive if the count increased.
+    pub fn delta(&self) -> i64 {
+        self.delta
+    }
+
+    /// Returns true if the change is significant.
+    pub fn is_significant(&self) -> bool {
+        self.significant
+    }
+
+    /// Returns true if the number of cycles increased significantly.
+    pub fn is_regression(&self) -> bool {
+        self.significant && self.delta > 0
+    }
+
+    /// Returns true if the number of cycles decreased significantly.
+    pub fn is_improvement(&self) -> bool {
+        self.significant && self.delta < 0
+    }
+}
+
+impl fmt::Display for CycleDelta {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // the width of the name column can be overridden to indent nested rows
+        let width = f.width().unwrap_or(44);
+        let count = |count: Option<u64>| count.map_or("-".to_string(), |c| c.to_string());
+        let delta = match (self.base(), self.new_cycles(), self.percent) {
+            (Some(_), Some(_), Some(percent)) => format!("{:+} ({:+.2}%)", self.delta(), percent),
+            (Some(_), Some(_), None) => format!("{:+}", self.delta()),
+            (None, _, _) => "added".to_string(),
+            (_, None, _) => "removed".to_string(),
+        };
+        let marker = if self.is_significant() { " *" } else { "" };
+        write!(
+            f,
+            "{:<width$} {:>10} {:>10}   {}{}",
+            self.name(),
+            count(self.base()),
+            count(self.new_cycles()),
+            delta,
+            marker,
+            width = width
+        )
+    }
+}
+
+/// Changes of the number of cycles executed by a program and by the procedures it invokes.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct ProgramDelta {
+    program: CycleDelta,
+    procedures: Vec<CycleDelta>,
+}
+
+impl ProgramDelta {
+    /// Returns the change of the number of cycles executed by the entire program.
+    pub fn program(&self) -> &CycleDelta {
+        &self.program
+    }
+
+    /// Returns the changes of the number of cycles executed by procedures invoked by the program.
+    pub fn procedures(&self) -> &[CycleDelta] {
+        &self.procedures
+    }
+
+    /// Returns an iterator over the changes of the program and of all of its procedures.
+    fn deltas(&self) -> impl Iterator<Item = &CycleDelta> {
+        core::iter::once(&self.program).chain(self.procedures.iter())
+    }
+}
+
+/// Result of comparing cycle counts of a corpus measured with two sets of assembler options.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct CycleDiffReport {
+    threshold: Threshold,
+    programs: Vec<ProgramDelta>,
+}
+
+impl CycleDiffReport {
+    /// Compares the specified cycle counts of a corpus; programs and procedures are compared by
+    /// name.
+    pub fn new(base: &CorpusCycles, new: &CorpusCycles, threshold: Threshold) -> Self {
+        let empty = ProgramCycles::default();
+        let mut names = base.programs.keys().collect::<Vec<_>>();
+        names.extend(new.programs.keys());
+        names.sort();
+        names.dedup();
+
+        let programs = names
+            .into_iter()
+            .map(|name| {
+                let base = base.programs.get(name);
+                let new = new.programs.get(name);
+                let program = CycleDelta::new(
+                    name,
+                    base.map(|p| p.cycles()),
+                    new.map(|p| p.cycles()),
+                    threshold,
+                );
+
+                // procedures are compared only if the program was measured with both options
+                let (base, new) = match (base, new) {
+                    (Some(base), Some(new)) => (base, new),
+                    _ => (&empty, &empty),
+                };
+                let mut proc_names = base.procedures().keys().collect::<Vec<_>>();
+                proc_names.extend(new.procedures().keys());
+                proc_names.sort();
+                proc_names.dedup();
+                let procedures = proc_names
+                    .into_iter()
+                    .map(|proc_name| {
+                        let base = base.procedures().get(proc_name).copied();
+                        let new = new.procedures().get(proc_name).copied();
+                        CycleDelta::new(proc_name, base, new, threshold)
+                    })
+                    .collect();
+                ProgramDelta {
+                    program,
+                    procedures,
+                }
+            })
+            .collect();
+
+        Self {
+            threshold,
+            programs,
+        }
+    }
+
+    /// Returns the changes of all compared programs, sorted by program name.
+    pub fn programs(&self) -> &[ProgramDelta] {
+        &self.programs
+    }
+
+    /// Returns the number of significant increases of the number of cycles executed by programs
+    /// and procedures.
+    pub fn num_regressions(&self) -> usize {
+        self.programs
+            .iter()
+            .flat_map(|program| program.deltas())
+            .filter(|delta| delta.is_regression())
+            .count()
+    }
+
+    /// Returns the number of significant decreases of the number of cycles executed by programs
+    /// and procedures.
+    pub fn num_improvements(&self) -> usize {
+        self.programs
+            .iter()
+            .flat_map(|program| program.deltas())
+            .filter(|delta| delta.is_improvement())
+            .count()
+    }
+}
+
+impl fmt::Display for CycleDiffReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{:<44} {:>10} {:>10}   Delta",
+            "Program / Procedure", "Base", "New"
+        )?;
+        for program in self.programs() {
+            writeln!(f, "{}", program.program())?;
+
+            // only the procedures whose cycle counts changed are listed
+            for procedure in program
+                .procedures()
+                .iter()
+                .filter(|p| p.base() != p.new_cycles())
+            {
+                writeln!(f, "  {:42}", procedure)?;
+            }
+        }
+        write!(
+            f,
+            "\n{} significant improvement(s), {} significant regression(s) \
+            (* marks changes of at least {}% and {} cycles)",
+            self.num_improvements(),
+            self.num_regressions(),
+            self.threshold.percent,
+            self.threshold.cycles
+        )
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Parses an optimization level from its name.
+fn parse_opt_level(s: &str) -> Result<OptLevel, String> {
+    match s {
+        "none" => Ok(OptLevel::None),
+        "basic" => Ok(OptLevel::Basic),
+        "aggressive" => Ok(OptLevel::Aggressive),
+        other => Err(format!(
+            "`{}` is not a valid optimization level; expected one of: none, basic, aggressive",
+            other
+        )),
+    }
+}
+
+/// Parses a push encoding from its name.
+fn parse_push_encoding(s: &str) -> Result<PushEncoding, String> {
+    match s {
+        "default" => Ok(PushEncoding::Default),
+        "min-cycles" => Ok(PushEncoding::MinCycles),
+        "min-size" => Ok(PushEncoding::MinSize),
+        other => Err(format!(
+            "`{}` is not a valid push encoding; expected one of: default, min-cycles, min-size",
+            other
+        )),
+    }
+}
+
+/// Parses an optimization from its name (see [Optimization::name()]).
+fn parse_optimization(s: &str) -> Result<Optimization, String> {
+    [
+        Optimization::DeadValues,
+        Optimization::CancellingMoves,
+        Optimization::DoubleNot,
+        Optimization::ConstantFolding,
+        Optimization::ArithmeticIdentities,
+        Optimization::ConstantEncoding,
+    ]
+    .into_iter()
+    .find(|optimization| optimization.name() == s)
+    .ok_or_else(|| format!("`{}` is not a valid optimization", s))
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        measure_program, AssemblerOptions, CorpusCycles, CycleDiffReport, OptLevel, ProgramCycles,
+        Threshold,
+    };
+    use std::collections::BTreeMap;
+    use vm_core::ProgramInputs;
+
+    const SOURCE: &str = "
+        use.std::math::u64
+        proc.foo
+            push.0 drop push.3 add
+        end
+        begin
+            push.1.2.3.4
+            exec.u64::wrapping_add
+            repeat.4 exec.foo end
+        end";
+
+    #[test]
+    fn measure_procedures() {
+        let inputs = ProgramInputs::none();
+        let base = measure_program(SOURCE, &inputs, &AssemblerOptions::default()).unwrap();
+        let options = AssemblerOptions::new(OptLevel::Basic, Default::default(), &[]);
+        let new = measure_program(SOURCE, &inputs, &options).unwrap();
+
+        // removing `push.0 drop` saves 2 cycles in every invocation of foo; the program as a
+        // whole may save a few more cycles due to less padding of its single span block
+        assert!(new.cycles() <= base.cycles() - 8);
+        let foo = |cycles: &ProgramCycles| cycles.procedures()["foo"];
+        assert_eq!(foo(&base) - 8, foo(&new));
+
+        // cycles of stdlib procedures are attributed to them by their full names; the cycles of
+        // wrapping_add include the cycles of overflowing_add which it invokes
+        let wrapping_add = base.procedures()["std::math::u64::wrapping_add"];
+        let overflowing_add = base.procedures()["std::math::u64::overflowing_add"];
+        assert!(wrapping_add > overflowing_add);
+        assert_eq!(3, base.procedures().len());
+
+        // the u64 procedures are not changed by the optimizer
+        assert_eq!(
+            base.procedures()["std::math::u64::wrapping_add"],
+            new.procedures()["std::math::u64::wrapping_add"]
+        );
+    }
+
+    #[test]
+    fn compare_cycles() {
+        let program = |cycles, procedures: &[(&str, u64)]| ProgramCycles {
+            cycles,
+            procedures: procedures
+                .iter()
+                .map(|(name, cycles)| (name.to_string(), *cycles))
+                .collect(),
+        };
+        let corpus = |programs: Vec<(&str, ProgramCycles)>| CorpusCycles {
+            programs: programs
+                .into_iter()
+                .map(|(name, cycles)| (name.to_string(), cycles))
+                .collect::<BTreeMap<_, _>>(),
+        };
+        let base = corpus(vec![
+            ("a.masm", program(1000, &[("foo", 500), ("bar", 100)])),
+            ("b.masm", program(100, &[])),
+            ("c.masm", program(100, &[])),
+        ]);
+        let new = corpus(vec![
+            ("a.masm", program(1005, &[("foo", 450), ("bar", 155)])),
+            ("b.masm", program(120, &[])),
+            ("d.masm", program(100, &[])),
+        ]);
+
+        // a.masm changed by less than 1%; foo changed by 50 cycles (10%), and bar by 55 cycles
+        let diff = CycleDiffReport::new(&base, &new, Threshold::new(1.0, 10));
+        let names = diff
+            .programs()
+            .iter()
+            .map(|p| p.program().name())
+            .collect::<Vec<_>>();
+        assert_eq!(vec!["a.masm", "b.masm", "c.masm", "d.masm"], names);
+        assert!(!diff.programs()[0].program().is_significant());
+        assert!(diff.programs()[0].procedures()[0].is_regression());
+        assert!(diff.programs()[0].procedures()[1].is_improvement());
+        assert!(diff.programs()[1].program().is_regression());
+        assert_eq!(2, diff.num_regressions());
+        assert_eq!(1, diff.num_improvements());
+
+        // programs measured with only one of the options are never significant
+        assert_eq!(None, diff.programs()[2].program().new_cycles());
+        assert_eq!(None, diff.programs()[3].program().base());
+        assert!(!diff.programs()[3].program().is_significant());
+
+        // with a higher absolute threshold, only the change of bar is significant
+        let diff = CycleDiffReport::new(&base, &new, Threshold::new(1.0, 51));
+        assert_eq!(1, diff.num_regressions());
+        assert_eq!(0, diff.num_improvements());
+    }
+
+    #[test]
+    fn baseline_round_trip() {
+        let cycles = measure_program(SOURCE, &ProgramInputs::none(), &Default::default()).unwrap();
+        let corpus = CorpusCycles {
+            programs: [("a.masm".to_string(), cycles)].into_iter().collect(),
+        };
+        let json = serde_json::to_string(&corpus).unwrap();
+        assert_eq!(corpus, serde_json::from_str(&json).unwrap());
+    }
+}
@@ -1,11 +1,22 @@
-use super::cli::InputFile;
+use super::cli::{CommandReport, Diagnostic, InputFile, OutputFormat};
 use assembly::{Assembler, AssemblyError};
 use core::fmt;
-use processor::{AsmOpInfo, ExecutionError};
-use std::path::PathBuf;
+use processor::{AsmOpInfo, ChipletsLengths, ExecutionError, TraceLenSummary};
+use serde_derive::Serialize;
+use std::time::Instant;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
 use structopt::StructOpt;
 use vm_core::{utils::collections::Vec, Operation, ProgramInputs};
 
+mod coverage;
+pub use coverage::Coverage;
+
+mod cycle_diff;
+pub use cycle_diff::CycleDiff;
+
 // CLI
 // ================================================================================================
 
@@ -19,18 +30,48 @@ pub struct Analyze {
     /// Path to .inputs file
     #[structopt(short = "i", long = "input", parse(from_os_str))]
     input_file: Option<PathBuf>,
+    /// Format in which results are reported (text or json)
+    #[structopt(long = "output", default_value = "text")]
+    output_format: OutputFormat,
 }
 
 /// Implements CLI execution logic
 impl Analyze {
     pub fn execute(&self) -> Result<(), String> {
-        let program =
-            std::fs::read_to_string(&self.assembly_file).expect("Could not read masm file");
+        let mut report = CommandReport::new("analyze", self.output_format);
+        let result = self.analyze(&mut report);
+        report.finish(result)
+    }
+
+    fn analyze(&self, report: &mut CommandReport) -> Result<(), Diagnostic> {
+        let program = std::fs::read_to_string(&self.assembly_file).map_err(|err| {
+            format!(
+                "Failed to open program file `{}` - {}",
+                self.assembly_file.display(),
+                err
+            )
+        })?;
+        report.add_artifact("program", &self.assembly_file);
+
         // load input data from file
-        let input_data = InputFile::read(&self.input_file, &self.assembly_file)?;
-        let program_info: ProgramInfo = analyze(program.as_str(), input_data.get_program_inputs())
-            .expect("Could not retrieve program info");
-        println!("{}", program_info);
+        let input_data = InputFile::read(&self.input_file, &self.assembly_file, report)?;
+
+        let now = Instant::now();
+        let program_info = analyze(program.as_str(), input_data.get_program_inputs()?).map_err(
+            |err| match err {
+                ProgramError::AssemblyError(err) => Diagnostic::from_assembly_error(
+                    &err,
+                    &program,
+                    Some(&self.assembly_file),
+                    "Failed to compile program",
+                ),
+                err => Diagnostic::error(format!("Failed to analyze program - {}", err)),
+            },
+        )?;
+        report.add_timing("analyze", now.elapsed());
+
+        report.println(format_args!("{}", program_info));
+        report.set_analysis(&program_info);
         Ok(())
     }
 }
@@ -41,14 +82,25 @@ impl Analyze {
 /// Contains info of a program. Used for program analysis. Contains the following fields:
 /// - total_vm_cycles: vm cycles it takes to execute the entire program
 /// - total_noops: total noops executed as part of a program
+/// - total_padding_noops: noops executed only to satisfy alignment rules of span blocks, i.e.,
+///   noops which are not a part of any assembly instruction
 /// - asm_op_stats: vector of [AsmOpStats] that contains assembly instructions and
 ///   the number of vm cycles it takes to execute the instruction and the number of times the
 ///   instruction is run as part of the given program.
-#[derive(Debug, Default, Eq, PartialEq)]
+/// - padding_stats: vector of [PaddingStats] that attributes padding noops to the assembly
+///   instructions after which they were executed, sorted by the number of noops (largest first).
+/// - trace_stats: [TraceStats] with the lengths of the execution trace and its components.
+/// - chiplet_stats: vector of [ChipletStats] that attributes rows of the chiplet traces to the
+///   assembly instructions which caused them, sorted by the number of rows (largest first).
+#[derive(Debug, Default, Eq, PartialEq, Serialize)]
 pub struct ProgramInfo {
     total_vm_cycles: usize,
     total_noops: usize,
+    total_padding_noops: usize,
     asm_op_stats: Vec<AsmOpStats>,
+    padding_stats: Vec<PaddingStats>,
+    trace_stats: TraceStats,
+    chiplet_stats: Vec<ChipletStats>,
 }
 
 impl ProgramInfo {
@@ -62,12 +114,34 @@ impl ProgramInfo {
         self.total_noops
     }
 
+    /// Returns total noops executed to satisfy alignment rules of span blocks
+    pub fn total_padding_noops(&self) -> usize {
+        self.total_padding_noops
+    }
+
     /// Returns [AsmOpStats] that contains assembly instructions and the number of vm cycles
     /// it takes to execute them and the number of times they are run as part of the given program.
     pub fn asm_op_stats(&self) -> &[AsmOpStats] {
         &self.asm_op_stats
     }
 
+    /// Returns [PaddingStats] which attribute padding noops to the assembly instructions after
+    /// which they were executed, starting with the instructions which caused the most padding.
+    pub fn padding_stats(&self) -> &[PaddingStats] {
+        &self.padding_stats
+    }
+
+    /// Returns [TraceStats] with the lengths of the execution trace and its components.
+    pub fn trace_stats(&self) -> &TraceStats {
+        &self.trace_stats
+    }
+
+    /// Returns [ChipletStats] which attribute rows of the chiplet traces to the assembly
+    /// instructions which caused them, starting with the instructions which caused the most rows.
+    pub fn chiplet_stats(&self) -> &[ChipletStats] {
+        &self.chiplet_stats
+    }
+
     // STATE MUTATORS
     // --------------------------------------------------------------------------------------------
 
@@ -76,6 +150,79 @@ impl ProgramInfo {
         self.total_noops += 1;
     }
 
+    /// Records a padding noop executed after the specified assembly instruction for the specified
+    /// reason.
+    pub fn record_padding(&mut self, instruction: &str, reason: PaddingReason) {
+        self.total_padding_noops += 1;
+        match self
+            .padding_stats
+            .iter_mut()
+            .find(|stats| stats.instruction == instruction && stats.reason == reason)
+        {
+            Some(stats) => stats.num_noops += 1,
+            None => self
+                .padding_stats
+                .push(PaddingStats::new(instruction.to_string(), reason, 1)),
+        }
+    }
+
+    /// Sorts padding stats so that the instructions which caused the most padding come first;
+    /// ties are broken by the instruction name.
+    fn sort_padding_stats(&mut self) {
+        self.padding_stats.sort_by(|a, b| {
+            b.num_noops
+                .cmp(&a.num_noops)
+                .then_with(|| a.instruction.cmp(&b.instruction))
+                .then_with(|| a.reason.cmp(&b.reason))
+        });
+    }
+
+    /// Records the chiplet rows added by the specified instruction; the rows are given as the
+    /// lengths of the chiplet traces before and after the instruction was executed.
+    pub fn record_chiplet_rows(
+        &mut self,
+        instruction: &str,
+        before: ChipletsLengths,
+        after: ChipletsLengths,
+    ) {
+        let stats = ChipletStats::new(
+            instruction.to_string(),
+            after.hasher_len() - before.hasher_len(),
+            after.bitwise_len() - before.bitwise_len(),
+            after.memory_len() - before.memory_len(),
+        );
+        if stats.total_rows() == 0 {
+            return;
+        }
+        match self
+            .chiplet_stats
+            .iter_mut()
+            .find(|s| s.instruction == instruction)
+        {
+            Some(entry) => {
+                entry.hasher_rows += stats.hasher_rows;
+                entry.bitwise_rows += stats.bitwise_rows;
+                entry.memory_rows += stats.memory_rows;
+            }
+            None => self.chiplet_stats.push(stats),
+        }
+    }
+
+    /// Sorts chiplet stats so that the instructions which caused the most chiplet rows come first;
+    /// ties are broken by the instruction name.
+    fn sort_chiplet_stats(&mut self) {
+        self.chiplet_stats.sort_by(|a, b| {
+            b.total_rows()
+                .cmp(&a.total_rows())
+                .then_with(|| a.instruction.cmp(&b.instruction))
+        });
+    }
+
+    /// Sets the trace stats from the provided summary of the trace lengths.
+    pub fn set_trace_stats(&mut self, summary: &TraceLenSummary) {
+        self.trace_stats = TraceStats::from(summary);
+    }
+
     /// Sets the total vm cycles to the provided value
     pub fn set_total_vm_cycles(&mut self, total_vm_cycles: usize) {
         self.total_vm_cycles = total_vm_cycles;
@@ -118,6 +265,13 @@ impl fmt::Display for ProgramInfo {
         let asm_op_stats = self.asm_op_stats();
         writeln!(f, "Total Number of VM Cycles: {}\n", total_vm_cycles)?;
         writeln!(f, "Total Number of NOOPs executed: {}\n", total_noops)?;
+        writeln!(
+            f,
+            "Total Number of NOOPs inserted for alignment: {} ({:.2}% of VM cycles)\n",
+            self.total_padding_noops,
+            percentage(self.total_padding_noops, total_vm_cycles)
+        )?;
+        writeln!(f, "{}", self.trace_stats())?;
         writeln!(
             f,
             "{0: <20} | {1: <20} | {2: <20} | {3: <20}",
@@ -133,33 +287,181 @@ impl fmt::Display for ProgramInfo {
                 op_info.total_vm_cycles() as f64 / op_info.frequency() as f64
             )?;
         }
+
+        if !self.padding_stats.is_empty() {
+            writeln!(
+                f,
+                "\n{0: <20} | {1: <20} | {2: <20} | {3: <20}",
+                "Padded After", "Reason", "NOOPs", "% of VM Cycles"
+            )?;
+            for stats in self.padding_stats.iter() {
+                writeln!(
+                    f,
+                    "{0: <20} | {1: <20} | {2: <20} | {3: <20.2}",
+                    stats.instruction(),
+                    stats.reason(),
+                    stats.num_noops(),
+                    percentage(stats.num_noops(), total_vm_cycles)
+                )?;
+            }
+        }
+
+        let chiplet_stats = self.chiplet_stats();
+        if !chiplet_stats.is_empty() {
+            writeln!(
+                f,
+                "\n{0: <20} | {1: <20} | {2: <20} | {3: <20}",
+                "Chiplet Rows By", "Hasher Rows", "Bitwise Rows", "Memory Rows"
+            )?;
+            for stats in chiplet_stats.iter().take(MAX_DISPLAYED_CHIPLET_STATS) {
+                writeln!(
+                    f,
+                    "{0: <20} | {1: <20} | {2: <20} | {3: <20}",
+                    stats.instruction(),
+                    stats.hasher_rows(),
+                    stats.bitwise_rows(),
+                    stats.memory_rows()
+                )?;
+            }
+            let num_omitted = chiplet_stats
+                .len()
+                .saturating_sub(MAX_DISPLAYED_CHIPLET_STATS);
+            if num_omitted > 0 {
+                writeln!(f, "... and {} more instructions", num_omitted)?;
+            }
+        }
         Ok(())
     }
 }
 
+/// Maximum number of instructions listed in the chiplet rows table of the text output; the JSON
+/// output lists all instructions.
+const MAX_DISPLAYED_CHIPLET_STATS: usize = 10;
+
+/// Returns the specified number of cycles as a percentage of the total number of cycles.
+fn percentage(num_cycles: usize, total_vm_cycles: usize) -> f64 {
+    if total_vm_cycles == 0 {
+        0.0
+    } else {
+        num_cycles as f64 * 100.0 / total_vm_cycles as f64
+    }
+}
+
 /// Returns program analysis of a given program.
 pub fn analyze(program: &str, inputs: ProgramInputs) -> Result<ProgramInfo, ProgramError> {
     let assembler = Assembler::new(true);
     let program = assembler
         .compile(program)
         .map_err(ProgramError::AssemblyError)?;
-    let vm_state_iterator = processor::execute_iter(&program, &inputs);
+    let mut vm_state_iterator = processor::execute_iter(&program, &inputs);
     let mut program_info = ProgramInfo::default();
 
-    for state in vm_state_iterator {
+    // the operation and the assembly instruction executed most recently; these are used to
+    // attribute padding noops
+    let mut prev_op = None;
+    let mut prev_asmop = None;
+
+    // the assembly instructions executed at each cycle; operations which are not a part of any
+    // instruction (e.g., control flow operations) are listed by their names
+    let mut cycle_instructions = Vec::new();
+
+    for state in vm_state_iterator.by_ref() {
         let vm_state = state.map_err(ProgramError::ExecutionError)?;
+        if let Some(op) = vm_state.op {
+            cycle_instructions.push(match &vm_state.asmop {
+                Some(asmop_info) => asmop_info.op_generalized(),
+                None => op.to_string(),
+            });
+        }
         if matches!(vm_state.op, Some(Operation::Noop)) {
             program_info.incr_noop_count();
+
+            // noops which are not a part of any assembly instruction are inserted by the
+            // processor to satisfy alignment rules of span blocks
+            if vm_state.asmop.is_none() {
+                let reason = match prev_op {
+                    Some(op) if Operation::imm_value(&op).is_some() => {
+                        PaddingReason::ImmediateValue
+                    }
+                    _ => PaddingReason::GroupAlignment,
+                };
+                let instruction = prev_asmop.as_deref().unwrap_or("-");
+                program_info.record_padding(instruction, reason);
+            }
         }
         if let Some(asmop_info) = vm_state.asmop {
+            prev_asmop = Some(asmop_info.op().clone());
             program_info.record_asmop(asmop_info);
         }
+        prev_op = vm_state.op;
         program_info.set_total_vm_cycles(vm_state.clk);
     }
+    program_info.sort_padding_stats();
+
+    // attribute chiplet rows added at each cycle to the instruction executed at that cycle
+    for (i, instruction) in cycle_instructions.iter().enumerate() {
+        program_info.record_chiplet_rows(
+            instruction,
+            vm_state_iterator.chiplets_lengths_at(i),
+            vm_state_iterator.chiplets_lengths_at(i + 1),
+        );
+    }
+    program_info.sort_chiplet_stats();
+    program_info.set_trace_stats(&vm_state_iterator.trace_len_summary());
 
     Ok(program_info)
 }
 
+// CORPUS
+// ================================================================================================
+
+/// Reads the sources and inputs of all .masm programs in the specified corpus directory, sorted
+/// by file name; inputs of a program are read from the .inputs file with the same name, if there
+/// is one.
+fn read_corpus(
+    corpus_dir: &Path,
+    command: &'static str,
+    report: &mut CommandReport,
+) -> Result<Vec<(String, String, ProgramInputs)>, Diagnostic> {
+    let entries = fs::read_dir(corpus_dir).map_err(|err| {
+        format!(
+            "Failed to open corpus directory `{}` - {}",
+            corpus_dir.display(),
+            err
+        )
+    })?;
+    let mut paths = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().map_or(false, |ext| ext == "masm"))
+        .collect::<Vec<_>>();
+    paths.sort();
+    report.add_artifact("corpus", corpus_dir);
+    report.println(format_args!(
+        "Reading {} programs from `{}`",
+        paths.len(),
+        corpus_dir.display()
+    ));
+
+    // inputs are read with a report of their own, so that reading the inputs of every program is
+    // not reported separately
+    let mut inputs_report = CommandReport::new(command, OutputFormat::Json);
+    let mut corpus = Vec::new();
+    for path in paths {
+        let name = path
+            .file_name()
+            .expect("no file name")
+            .to_string_lossy()
+            .to_string();
+        let source = fs::read_to_string(&path)
+            .map_err(|err| format!("Failed to open program file `{}` - {}", path.display(), err))?;
+        let inputs = InputFile::read(&None, &path, &mut inputs_report)?
+            .get_program_inputs()
+            .map_err(|err| format!("Invalid inputs of program `{}` - {}", name, err))?;
+        corpus.push((name, source, inputs));
+    }
+    Ok(corpus)
+}
+
 // PROGRAM ERROR
 // ================================================================================================
 
@@ -174,6 +476,9 @@ impl fmt::Display for ProgramError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             ProgramError::AssemblyError(e) => write!(f, "Assembly Error: {:?}", e),
+            ProgramError::ExecutionError(ExecutionError::WithCallStack(e, call_stack)) => {
+                write!(f, "Execution Error: {:?} {}", e, call_stack)
+            }
             ProgramError::ExecutionError(e) => write!(f, "Execution Error: {:?}", e),
         }
     }
@@ -182,7 +487,7 @@ impl fmt::Display for ProgramError {
 // ASMOP STATS
 // ================================================================================================
 
-#[derive(Debug, Eq, PartialEq)]
+#[derive(Debug, Eq, PartialEq, Serialize)]
 pub struct AsmOpStats {
     op: String,
     frequency: usize,
@@ -230,12 +535,248 @@ impl AsmOpStats {
     }
 }
 
+// PADDING STATS
+// ================================================================================================
+
+/// Reason for which a padding noop was executed.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PaddingReason {
+    /// An operation group ended with an operation carrying an immediate value; such an operation
+    /// cannot be the last operation in a group, and thus a noop is executed after it.
+    ImmediateValue,
+    /// The number of operation groups in a batch was not a power of two; one noop is executed
+    /// for every missing group.
+    GroupAlignment,
+}
+
+impl fmt::Display for PaddingReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ImmediateValue => f.pad("immediate value"),
+            Self::GroupAlignment => f.pad("group alignment"),
+        }
+    }
+}
+
+/// Number of padding noops executed after an assembly instruction for the same reason.
+#[derive(Debug, Eq, PartialEq, Serialize)]
+pub struct PaddingStats {
+    instruction: String,
+    reason: PaddingReason,
+    num_noops: usize,
+}
+
+impl PaddingStats {
+    /// Returns [PaddingStats] instantiated with the specified assembly instruction string, reason
+    /// for padding, and the number of padding noops executed after the instruction.
+    pub fn new(instruction: String, reason: PaddingReason, num_noops: usize) -> Self {
+        Self {
+            instruction,
+            reason,
+            num_noops,
+        }
+    }
+
+    /// Returns the assembly instruction after which padding noops were executed; `-` if the
+    /// noops were not preceded by any assembly instruction.
+    pub fn instruction(&self) -> &str {
+        &self.instruction
+    }
+
+    /// Returns the reason for which padding noops were executed.
+    pub fn reason(&self) -> PaddingReason {
+        self.reason
+    }
+
+    /// Returns the number of padding noops executed after the instruction.
+    pub fn num_noops(&self) -> usize {
+        self.num_noops
+    }
+}
+
+// TRACE STATS
+// ================================================================================================
+
+/// Lengths of the execution trace and its components. The length of the execution trace is the
+/// length of the longest component (main trace, range checker trace, or chiplets trace) padded
+/// to the next power of two.
+#[derive(Debug, Default, Eq, PartialEq, Serialize)]
+pub struct TraceStats {
+    trace_length: usize,
+    padding_rows: usize,
+    main_trace_len: usize,
+    range_trace_len: usize,
+    chiplets_trace_len: usize,
+    hasher_rows: usize,
+    bitwise_rows: usize,
+    memory_rows: usize,
+}
+
+impl TraceStats {
+    /// Returns the length of the execution trace.
+    pub fn trace_length(&self) -> usize {
+        self.trace_length
+    }
+
+    /// Returns the number of rows by which the execution trace exceeds its longest component.
+    pub fn padding_rows(&self) -> usize {
+        self.padding_rows
+    }
+
+    /// Returns the length of the main trace, i.e., the number of executed cycles.
+    pub fn main_trace_len(&self) -> usize {
+        self.main_trace_len
+    }
+
+    /// Returns the length of the range checker trace.
+    pub fn range_trace_len(&self) -> usize {
+        self.range_trace_len
+    }
+
+    /// Returns the length of the chiplets trace, including its mandatory padding row.
+    pub fn chiplets_trace_len(&self) -> usize {
+        self.chiplets_trace_len
+    }
+
+    /// Returns the number of rows of the hasher chiplet.
+    pub fn hasher_rows(&self) -> usize {
+        self.hasher_rows
+    }
+
+    /// Returns the number of rows of the bitwise chiplet.
+    pub fn bitwise_rows(&self) -> usize {
+        self.bitwise_rows
+    }
+
+    /// Returns the number of rows of the memory chiplet.
+    pub fn memory_rows(&self) -> usize {
+        self.memory_rows
+    }
+
+    /// Returns the name of the component which determined the length of the execution trace.
+    pub fn dominant_component(&self) -> &'static str {
+        if self.main_trace_len >= self.range_trace_len
+            && self.main_trace_len >= self.chiplets_trace_len
+        {
+            "main trace"
+        } else if self.range_trace_len >= self.chiplets_trace_len {
+            "range checker"
+        } else if self.hasher_rows >= self.bitwise_rows && self.hasher_rows >= self.memory_rows {
+            "chiplets (hasher)"
+        } else if self.bitwise_rows >= self.memory_rows {
+            "chiplets (bitwise)"
+        } else {
+            "chiplets (memory)"
+        }
+    }
+}
+
+impl From<&TraceLenSummary> for TraceStats {
+    fn from(summary: &TraceLenSummary) -> Self {
+        let chiplets = summary.chiplets_lengths();
+        Self {
+            trace_length: summary.padded_trace_len(),
+            padding_rows: summary.padding_len(),
+            main_trace_len: summary.main_trace_len(),
+            range_trace_len: summary.range_trace_len(),
+            chiplets_trace_len: summary.chiplets_trace_len(),
+            hasher_rows: chiplets.hasher_len(),
+            bitwise_rows: chiplets.bitwise_len(),
+            memory_rows: chiplets.memory_len(),
+        }
+    }
+}
+
+impl fmt::Display for TraceStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "Trace Length: {} ({} padding rows, {:.2}% of trace; determined by {})\n",
+            self.trace_length(),
+            self.padding_rows(),
+            percentage(self.padding_rows(), self.trace_length()),
+            self.dominant_component()
+        )?;
+        writeln!(f, "Main Trace Length: {}\n", self.main_trace_len())?;
+        writeln!(
+            f,
+            "Range Checker Trace Length: {}\n",
+            self.range_trace_len()
+        )?;
+        writeln!(
+            f,
+            "Chiplets Trace Length: {} (hasher: {}, bitwise: {}, memory: {})",
+            self.chiplets_trace_len(),
+            self.hasher_rows(),
+            self.bitwise_rows(),
+            self.memory_rows()
+        )
+    }
+}
+
+// CHIPLET STATS
+// ================================================================================================
+
+/// Number of chiplet rows added by all executions of an assembly instruction.
+#[derive(Debug, Eq, PartialEq, Serialize)]
+pub struct ChipletStats {
+    instruction: String,
+    hasher_rows: usize,
+    bitwise_rows: usize,
+    memory_rows: usize,
+}
+
+impl ChipletStats {
+    /// Returns [ChipletStats] instantiated with the specified assembly instruction string and the
+    /// number of rows the instruction added to the hasher, bitwise, and memory chiplets.
+    pub fn new(
+        instruction: String,
+        hasher_rows: usize,
+        bitwise_rows: usize,
+        memory_rows: usize,
+    ) -> Self {
+        Self {
+            instruction,
+            hasher_rows,
+            bitwise_rows,
+            memory_rows,
+        }
+    }
+
+    /// Returns the assembly instruction which added the rows, or the name of the operation for
+    /// operations which are not a part of any assembly instruction.
+    pub fn instruction(&self) -> &str {
+        &self.instruction
+    }
+
+    /// Returns the number of rows added to the hasher chiplet.
+    pub fn hasher_rows(&self) -> usize {
+        self.hasher_rows
+    }
+
+    /// Returns the number of rows added to the bitwise chiplet.
+    pub fn bitwise_rows(&self) -> usize {
+        self.bitwise_rows
+    }
+
+    /// Returns the number of rows added to the memory chiplet.
+    pub fn memory_rows(&self) -> usize {
+        self.memory_rows
+    }
+
+    /// Returns the number of rows added to all chiplets.
+    pub fn total_rows(&self) -> usize {
+        self.hasher_rows + self.bitwise_rows + self.memory_rows
+    }
+}
+
 // TESTS
 // ================================================================================================
 
 #[cfg(test)]
 mod tests {
-    use super::{AsmOpStats, ProgramInfo};
+    use super::{AsmOpStats, ChipletStats, PaddingReason, PaddingStats, ProgramInfo, TraceStats};
 
     #[test]
     fn analyze_test() {
@@ -247,23 +788,93 @@ mod tests {
         let expected_program_info = ProgramInfo {
             total_vm_cycles: 27,
             total_noops: 1,
+            total_padding_noops: 1,
             asm_op_stats: vec![
                 AsmOpStats::new("movdn.2".to_string(), 1, 1),
                 AsmOpStats::new("pop.local".to_string(), 1, 10),
                 AsmOpStats::new("popw.mem".to_string(), 1, 6),
                 AsmOpStats::new("push".to_string(), 2, 3),
             ],
+            padding_stats: vec![PaddingStats::new(
+                "pop.local.0".to_string(),
+                PaddingReason::GroupAlignment,
+                1,
+            )],
+            trace_stats: TraceStats {
+                trace_length: 1024,
+                padding_rows: 444,
+                main_trace_len: 27,
+                range_trace_len: 580,
+                chiplets_trace_len: 11,
+                hasher_rows: 8,
+                bitwise_rows: 0,
+                memory_rows: 2,
+            },
+            chiplet_stats: vec![
+                ChipletStats::new("span".to_string(), 8, 0, 0),
+                ChipletStats::new("pop.local".to_string(), 0, 0, 1),
+                ChipletStats::new("popw.mem".to_string(), 0, 0, 1),
+            ],
         };
         assert_eq!(program_info, expected_program_info);
     }
 
+    #[test]
+    fn analyze_test_padding() {
+        // the first group of the first batch ends with push.7 (push.1 is executed as PAD INCR),
+        // and thus a noop is executed after it; the batch contains 7 groups (1 operation group and
+        // 6 immediate values), and thus one more noop pads the batch to 8 groups
+        let source = "begin push.1 push.2 push.3 push.4 push.5 push.6 push.7 push.8 add end";
+        let program_inputs = super::ProgramInputs::none();
+        let program_info =
+            super::analyze(source, program_inputs).expect("analyze_test: Unexpected Error");
+        let expected_padding_stats = vec![
+            PaddingStats::new("push.7".to_string(), PaddingReason::ImmediateValue, 1),
+            PaddingStats::new("push.7".to_string(), PaddingReason::GroupAlignment, 1),
+        ];
+        assert_eq!(program_info.total_noops(), 2);
+        assert_eq!(program_info.total_padding_noops(), 2);
+        assert_eq!(program_info.padding_stats(), expected_padding_stats);
+    }
+
+    #[test]
+    fn analyze_test_chiplets() {
+        // the program is hashed in the hasher chiplet at the start of the span block; each bitwise
+        // operation adds 8 rows to the bitwise chiplet
+        let source =
+            "begin push.1 push.2 u32checked_and push.3 u32checked_xor push.5 u32checked_and end";
+        let program_inputs = super::ProgramInputs::none();
+        let program_info =
+            super::analyze(source, program_inputs).expect("analyze_test: Unexpected Error");
+        let expected_chiplet_stats = vec![
+            ChipletStats::new("u32checked_and".to_string(), 0, 16, 0),
+            ChipletStats::new("span".to_string(), 8, 0, 0),
+            ChipletStats::new("u32checked_xor".to_string(), 0, 8, 0),
+        ];
+        assert_eq!(program_info.chiplet_stats(), expected_chiplet_stats);
+
+        let trace_stats = program_info.trace_stats();
+        assert_eq!(trace_stats.hasher_rows(), 8);
+        assert_eq!(trace_stats.bitwise_rows(), 24);
+        assert_eq!(trace_stats.memory_rows(), 0);
+        assert_eq!(trace_stats.chiplets_trace_len(), 33);
+        assert_eq!(trace_stats.dominant_component(), "range checker");
+    }
+
     #[test]
     fn analyze_test_execution_error() {
         let source = "begin div end";
         let stack_input = vec![1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
         let program_inputs = super::ProgramInputs::new(&stack_input, &[], vec![]).unwrap();
         let program_info = super::analyze(source, program_inputs);
-        let expected_error = "Execution Error: DivideByZero(1)";
+        let expected_error = "Execution Error: DivideByZero(1) at clock cycle 1";
+        assert_eq!(program_info.err().unwrap().to_string(), expected_error);
+
+        // procedures being executed when the error occurred are listed below the error
+        let source = "proc.foo div end begin exec.foo end";
+        let program_inputs = super::ProgramInputs::new(&stack_input, &[], vec![]).unwrap();
+        let program_info = super::analyze(source, program_inputs);
+        let expected_error = "Execution Error: DivideByZero(1) at clock cycle 1\n  0: foo";
         assert_eq!(program_info.err().unwrap().to_string(), expected_error);
     }
 
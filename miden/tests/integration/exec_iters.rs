@@ -1,5 +1,6 @@
 use super::build_debug_test;
-use processor::{AsmOpInfo, VmState};
+use processor::{AsmOpInfo, ChipletsLengths, VmState};
+use prover::Trace;
 use vm_core::{utils::ToElements, Felt, FieldElement, Operation};
 
 // EXEC ITER TESTS
@@ -205,6 +206,34 @@ fn test_exec_iter() {
     }
 }
 
+#[test]
+fn test_exec_iter_trace_lengths() {
+    let source = "begin push.1 push.2 u32checked_and popw.mem.1 end";
+    let test = build_debug_test!(source, &[1, 2, 3, 4]);
+    let mut states = test.execute_iter();
+    for state in states.by_ref() {
+        state.unwrap();
+    }
+
+    // chiplet lengths grow with the operations which use the chiplets: the program is hashed at
+    // the start of the span, u32checked_and adds 8 bitwise rows, and popw.mem adds a memory row
+    assert_eq!(ChipletsLengths::default(), states.chiplets_lengths_at(0));
+    assert_eq!(ChipletsLengths::new(8, 0, 0), states.chiplets_lengths_at(1));
+    let trace = test.execute().unwrap();
+    assert_eq!(
+        ChipletsLengths::new(8, 8, 1),
+        states.chiplets_lengths_at(trace.num_cycles())
+    );
+
+    // the summary of the iterator matches the trace generated by the same program
+    assert_eq!(states.trace_len_summary(), *trace.trace_len_summary());
+    assert_eq!(trace.length(), trace.trace_len_summary().padded_trace_len());
+    assert_eq!(
+        trace.num_cycles(),
+        trace.trace_len_summary().main_trace_len()
+    );
+}
+
 // HELPER FUNCTIONS
 // =================================================================
 fn slice_to_word(values: &[i32]) -> [Felt; 4] {
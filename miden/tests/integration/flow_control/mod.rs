@@ -1,4 +1,10 @@
-use crate::build_test;
+use crate::{
+    build_test,
+    helpers::{Felt, ProgramInputs, TestError},
+};
+use assembly::Assembler;
+use processor::{ExecutionError, MemAdviceProvider};
+use vm_core::{code_blocks::CodeBlock, CodeBlockTable, Kernel, Program, StarkField};
 
 // SIMPLE FLOW CONTROL TESTS
 // ================================================================================================
@@ -47,6 +53,54 @@ fn conditional_loop() {
     test.expect_stack(&[10]);
 }
 
+#[test]
+fn bounded_loop() {
+    // computes sum of values from 0 to the value at the top of the stack in at most 10 iterations
+    let source = "
+        proc.sum_to.1
+            dup push.0 movdn.2 neq.0
+            while.bounded.10
+                dup movup.2 add swap push.1 sub dup neq.0
+            end
+            drop
+        end
+        begin
+            exec.sum_to
+        end";
+
+    let test = build_test!(source, &[10]);
+    test.expect_stack(&[55]);
+
+    let test = build_test!(source, &[0]);
+    test.expect_stack(&[0]);
+
+    // the 11th iteration makes the execution fail
+    let test = build_test!(source, &[11]);
+    test.expect_error(TestError::ExecutionError("FailedAssertion"));
+
+    // every execution of a nested loop is bounded separately; procedures invoked from the body
+    // allocate their locals above the loop counters
+    let source = "
+        proc.double.1
+            pop.local.0
+            push.local.0 push.local.0 add
+        end
+        begin
+            push.1 push.3 push.1
+            while.bounded.3
+                push.2 push.1
+                while.bounded.2
+                    movup.2 exec.double movdn.2 sub.1 dup neq.0
+                end
+                drop sub.1 dup neq.0
+            end
+            drop
+        end";
+
+    let test = build_test!(source);
+    test.expect_stack(&[64]);
+}
+
 #[test]
 fn counter_controlled_loop() {
     // --- entering the loop ----------------------------------------------------------------------
@@ -117,3 +171,317 @@ fn if_in_loop_in_if() {
     let test = build_test!(source, &[11]);
     test.expect_stack(&[121]);
 }
+
+#[test]
+fn exec_by_root() {
+    let assembler = Assembler::default();
+    let callee = assembler.compile("begin push.3 push.7 mul end").unwrap();
+    let root = <[u8; 32]>::from(callee.hash())
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect::<String>();
+    let program = assembler
+        .compile(&format!("begin push.2 exec.0x{} add end", root))
+        .unwrap();
+    let inputs = ProgramInputs::none();
+
+    // the program cannot be executed without the code of the procedure
+    assert!(matches!(
+        processor::execute(&program, &inputs),
+        Err(ExecutionError::CodeBlockNotFound(_))
+    ));
+
+    // the code of the procedure is supplied at runtime by the advice provider
+    let code_blocks = CodeBlockTable::from_iter([callee.root().clone()]);
+    let advice = MemAdviceProvider::new(inputs.clone()).with_code_blocks(code_blocks);
+    let trace = processor::execute_with_advice(&program, &inputs, advice).unwrap();
+    assert_eq!(program.hash(), trace.program_hash());
+    assert_eq!(Felt::new(23), trace.last_stack_state()[0]);
+}
+
+#[test]
+fn dynexec() {
+    let assembler = Assembler::default();
+    let callee = assembler
+        .compile("begin dropw push.3 push.7 mul end")
+        .unwrap();
+    let root = callee
+        .hash()
+        .as_elements()
+        .iter()
+        .map(|element| element.as_int().to_string())
+        .collect::<Vec<_>>()
+        .join(".");
+    let program = assembler
+        .compile(&format!("begin push.2 push.{} dynexec add end", root))
+        .unwrap();
+    let inputs = ProgramInputs::none();
+
+    // the root of the procedure is read from the stack, but its code must be supplied at runtime
+    assert!(matches!(
+        processor::execute(&program, &inputs),
+        Err(ExecutionError::CodeBlockNotFound(_))
+    ));
+
+    let code_blocks = CodeBlockTable::from_iter([callee.root().clone()]);
+    let advice = MemAdviceProvider::new(inputs.clone()).with_code_blocks(code_blocks);
+    let trace = processor::execute_with_advice(&program, &inputs, advice).unwrap();
+    assert_eq!(program.hash(), trace.program_hash());
+    assert_eq!(Felt::new(23), trace.last_stack_state()[0]);
+}
+
+#[test]
+fn syscall() {
+    let kernel = "
+        export.set
+            dup.0 pop.mem.0
+        end
+        export.get
+            drop push.mem.0
+        end
+        export.leak
+            push.1
+        end";
+    let assembler = Assembler::default().with_kernel(kernel).unwrap();
+    let inputs = ProgramInputs::none();
+
+    // kernel procedures access memory separate from the memory of the program
+    let source = "
+        begin
+            push.7 pop.mem.0
+            push.5 syscall.set drop
+            push.0 syscall.get
+            push.mem.0
+            add
+        end";
+    let program = assembler.compile(source).unwrap();
+    let trace = processor::execute(&program, &inputs).unwrap();
+    assert_eq!(Felt::new(12), trace.last_stack_state()[0]);
+
+    // kernel procedures must leave the stack at the same depth
+    let program = assembler.compile("begin syscall.leak end").unwrap();
+    assert!(matches!(
+        processor::execute(&program, &inputs),
+        Err(ExecutionError::InvalidStackDepthOnReturn(16, 17))
+    ));
+}
+
+#[test]
+fn annotated_syscall() {
+    let kernel = "
+        @only_kernel
+        export.double
+            dup.0 add
+        end
+        @only_context(root)
+        export.square
+            dup.0 mul
+        end";
+    let assembler = Assembler::default().with_kernel(kernel).unwrap();
+
+    // guards of annotated procedures pass when the procedures are invoked via syscall
+    let program = assembler
+        .compile("begin push.3 syscall.double syscall.square end")
+        .unwrap();
+    let trace = processor::execute(&program, &ProgramInputs::none()).unwrap();
+    assert_eq!(Felt::new(36), trace.last_stack_state()[0]);
+    let kernel_code = program.cb_table().clone();
+
+    // but fail when the code of the procedures is executed outside of the kernel context
+    for fn_hash in program.kernel().proc_hashes() {
+        let root = fn_hash
+            .as_elements()
+            .iter()
+            .map(|element| element.as_int().to_string())
+            .collect::<Vec<_>>()
+            .join(".");
+        let program = Assembler::default()
+            .compile(&format!("begin push.3 push.{} dynexec end", root))
+            .unwrap();
+        let inputs = ProgramInputs::none();
+        let advice = MemAdviceProvider::new(inputs.clone()).with_code_blocks(kernel_code.clone());
+        assert!(matches!(
+            processor::execute_with_advice(&program, &inputs, advice),
+            Err(ExecutionError::CallerOutsideOfKernel(_))
+        ));
+    }
+}
+
+#[test]
+fn preserved_stack_elements() {
+    // the procedure adds 100 to every element of the top 16, and uses a declared local
+    let body = "
+            dup.0 pop.local.0
+            repeat.16 movup.15 add.100 end
+            push.local.0 add
+        end
+        begin
+            exec.foo
+        end";
+    let inputs = [16, 15, 14, 13, 12, 11, 10, 9, 8, 7, 6, 5, 4, 3, 2, 1];
+
+    let source = format!("proc.foo.1 {body}");
+    let test = build_test!(&source, &inputs);
+    test.expect_stack(&[
+        102, 102, 103, 104, 105, 106, 107, 108, 109, 110, 111, 112, 113, 114, 115, 116,
+    ]);
+
+    // the deepest 4 elements are put back after the procedure exits
+    let source = format!("@preserve(4) proc.foo.1 {body}");
+    let test = build_test!(&source, &inputs);
+    test.expect_stack(&[
+        102, 102, 103, 104, 105, 106, 107, 108, 109, 110, 111, 112, 13, 14, 15, 16,
+    ]);
+}
+
+#[test]
+fn split_repeat() {
+    let source = "
+        proc.double.1
+            pop.local.0
+            push.local.0 push.local.0 add
+        end
+        begin
+            push.1
+            repeat.7
+                repeat.3 exec.double end
+                add.1
+            end
+        end";
+    let inputs = ProgramInputs::none();
+    let expected = processor::execute(&Assembler::default().compile(source).unwrap(), &inputs)
+        .unwrap()
+        .last_stack_state();
+    assert_eq!(Felt::new(0o11111111), expected[0]);
+
+    // nested loops executed in chunks, with procedures allocating locals above the loop counters
+    for threshold in [1, 20, 100] {
+        let assembler = Assembler::default().with_split_threshold(threshold);
+        let program = assembler.compile(source).unwrap();
+        let trace = processor::execute(&program, &inputs).unwrap();
+        assert_eq!(expected, trace.last_stack_state());
+    }
+}
+
+#[test]
+fn call() {
+    // called procedures are executed in a new memory context
+    let source = "
+        proc.foo
+            push.mem.0 add
+            push.7 pop.mem.0
+        end
+        begin
+            push.5 pop.mem.0
+            push.1 call.foo call.foo
+            push.mem.0
+        end";
+    let test = build_test!(source);
+    test.expect_stack(&[5, 1]);
+
+    // the depth of the stack must be the same before and after the call
+    let program = Assembler::default()
+        .compile("proc.foo push.1 end begin call.foo end")
+        .unwrap();
+    assert!(matches!(
+        processor::execute(&program, &ProgramInputs::none()),
+        Err(ExecutionError::InvalidStackDepthOnReturn(16, 17))
+    ));
+}
+
+#[test]
+fn syscall_from_called_procedure() {
+    let kernel = "
+        @only_kernel
+        export.double
+            dup.0 add
+        end
+        @only_context(root)
+        export.square
+            dup.0 mul
+        end";
+    let assembler = Assembler::default().with_kernel(kernel).unwrap();
+
+    // kernel procedures can be invoked from called procedures
+    let program = assembler
+        .compile("proc.foo syscall.double end begin push.3 call.foo end")
+        .unwrap();
+    let trace = processor::execute(&program, &ProgramInputs::none()).unwrap();
+    assert_eq!(Felt::new(6), trace.last_stack_state()[0]);
+
+    // unless the procedure is restricted to the root context
+    let program = assembler
+        .compile("proc.foo syscall.square end begin push.3 call.foo end")
+        .unwrap();
+    assert!(matches!(
+        processor::execute(&program, &ProgramInputs::none()),
+        Err(ExecutionError::FailedAssertion(..))
+    ));
+}
+
+#[test]
+fn caller_hash() {
+    let kernel = "
+        export.caller_hash
+            push.env.caller swapw dropw
+        end";
+    let assembler = Assembler::default().with_kernel(kernel).unwrap();
+
+    // the hash of the root context is all ZEROs
+    let program = assembler
+        .compile("begin push.1 syscall.caller_hash end")
+        .unwrap();
+    let trace = processor::execute(&program, &ProgramInputs::none()).unwrap();
+    assert_eq!([Felt::new(0); 4], trace.last_stack_state()[..4]);
+
+    // the hash of any other context is the hash of the procedure which created it
+    let program = assembler
+        .compile("proc.foo syscall.caller_hash end begin call.foo end")
+        .unwrap();
+    let fn_hash: [Felt; 4] = match program.root() {
+        CodeBlock::Call(block) => block.fn_hash().into(),
+        _ => panic!("expected a call block"),
+    };
+    let trace = processor::execute(&program, &ProgramInputs::none()).unwrap();
+    let mut expected = fn_hash;
+    expected.reverse();
+    assert_eq!(expected, trace.last_stack_state()[..4]);
+
+    // the hash of the caller is not defined outside of the kernel context
+    let program = assembler.compile("begin push.env.caller end").unwrap();
+    assert!(matches!(
+        processor::execute(&program, &ProgramInputs::none()),
+        Err(ExecutionError::CallerOutsideOfKernel(_))
+    ));
+}
+
+#[test]
+fn syscall_outside_of_kernel() {
+    let inputs = ProgramInputs::none();
+    let target = CodeBlock::new_span(vec![vm_core::Operation::Noop]);
+    let fn_hash = target.hash();
+
+    // the invoked procedure must be a part of the kernel
+    let program = Program::with_kernel(
+        CodeBlock::new_syscall(fn_hash),
+        Kernel::default(),
+        CodeBlockTable::from_iter([target.clone()]),
+    );
+    assert!(matches!(
+        processor::execute(&program, &inputs),
+        Err(ExecutionError::SyscallTargetNotInKernel(_))
+    ));
+
+    // kernel procedures cannot make system calls
+    let nested = CodeBlock::new_syscall(fn_hash);
+    let kernel = Kernel::new(&[fn_hash, nested.hash()]);
+    let program = Program::with_kernel(
+        CodeBlock::new_syscall(nested.hash()),
+        kernel,
+        CodeBlockTable::from_iter([target, nested]),
+    );
+    assert!(matches!(
+        processor::execute(&program, &inputs),
+        Err(ExecutionError::SyscallFromKernel(_))
+    ));
+}
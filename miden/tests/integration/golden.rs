@@ -0,0 +1,103 @@
+use crate::build_test;
+
+// GOLDEN TRACE TESTS
+// ================================================================================================
+// These tests detect changes to the execution trace of programs covering all parts of the VM. If
+// a change is intended (e.g., the trace layout was modified), run the tests with MIDEN_BLESS=1 set
+// in the environment to update the golden files, and commit the updated files together with the
+// change.
+
+#[test]
+fn golden_flow_control() {
+    let source = "
+        proc.double
+            dup add
+        end
+
+        begin
+            push.5
+            push.1
+            while.true
+                exec.double
+                dup push.100 lt
+            end
+            dup push.2 eq
+            if.true
+                push.3
+            else
+                push.4 mul
+            end
+            call.double
+        end";
+
+    build_test!(source, &[1, 2]).expect_golden_trace(golden_path("flow_control"));
+}
+
+#[test]
+fn golden_u32_ops() {
+    let source = "
+        begin
+            u32checked_and
+            dup.1 u32checked_xor
+            u32wrapping_mul
+            u32split
+            u32overflowing_add
+        end";
+
+    build_test!(source, &[1234567, 7654321, 55555]).expect_golden_trace(golden_path("u32_ops"));
+}
+
+#[test]
+fn golden_memory() {
+    let source = "
+        proc.store.2
+            storew.local.1
+            dropw
+            loadw.local.1
+        end
+
+        begin
+            push.7 pop.mem.3
+            push.1.2.3.4 storew.mem.5 dropw
+            loadw.mem.5
+            exec.store
+            push.mem.3
+        end";
+
+    build_test!(source).expect_golden_trace(golden_path("memory"));
+}
+
+#[test]
+fn golden_hashing() {
+    let source = "
+        begin
+            push.1.2.3.4
+            push.5.6.7.8
+            push.0.0.0.8
+            rpperm
+            dropw
+            rphash
+        end";
+
+    build_test!(source).expect_golden_trace(golden_path("hashing"));
+}
+
+#[test]
+fn golden_stdlib() {
+    let source = "
+        use.std::math::u64
+        begin
+            exec.u64::unchecked_div
+            exec.u64::wrapping_add
+        end";
+
+    build_test!(source, &[3, 9, 1234, 456, 17, 2]).expect_golden_trace(golden_path("stdlib"));
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the path to the golden file with the specified name relative to the miden package.
+fn golden_path(name: &str) -> String {
+    format!("tests/integration/goldens/{}.golden", name)
+}
@@ -1,9 +1,13 @@
 mod helpers;
+use helpers::ProofOptions;
+pub use miden_test::{build_debug_test, build_op_test, build_test};
 
 mod air;
 mod exec_iters;
 mod flow_control;
+mod golden;
 mod operations;
+mod optimizer;
 mod stdlib;
 
 // TESTS
@@ -20,105 +24,172 @@ fn multi_output_program() {
     test.prove_and_verify(vec![1, 2, 3], 2, false);
 }
 
-// MACROS TO BUILD TESTS
-// ================================================================================================
+#[test]
+fn prove_executed_trace() {
+    let test = build_test!("begin mul movup.2 drop end", &[1, 2, 3]);
+    let program = test.compile();
+    let trace = test.execute().unwrap();
+
+    let stack_inputs = trace.init_stack_state()[..3].to_vec();
+    let stack_outputs = trace.last_stack_state()[..2].to_vec();
+    let public_inputs = miden::PublicInputs::new(program.hash(), stack_inputs, stack_outputs);
+    let proof = miden::prove_trace(trace, public_inputs, &ProofOptions::default()).unwrap();
 
-/// Returns a Test struct in non debug mode from a string of one or more operations and any
-/// specified stack and advice inputs.
-///
-/// Parameters are expected in the following order:
-/// `source`, `stack_inputs` (optional), `advice_tape` (optional), `advice_sets` (optional)
-///
-/// * `source`: a string of one or more operations, e.g. "push.1 push.2".
-/// * `stack_inputs` (optional): the initial inputs which must be at the top of the stack before
-/// executing the `source`. Stack inputs can be provided independently without any advice inputs.
-/// * `advice_tape` (optional): the initial advice tape values. When provided, `stack_inputs` and
-/// `advice_sets` are also expected.
-/// * `advice_sets` (optional): the initial advice set values. When provided, `stack_inputs` and
-/// `advice_tape` are also expected.
-#[macro_export]
-macro_rules! build_op_test {
-    ($op_str:expr) => {{
-        let source = format!("begin {} end", $op_str);
-        $crate::build_test!(&source)
-    }};
-    ($op_str:expr, $($tail:tt)+) => {{
-        let source = format!("begin {} end", $op_str);
-        $crate::build_test!(&source, $($tail)+)
-    }};
+    assert!(miden::verify(program.hash(), &[1, 2, 3], &[6, 1], proof.clone()).is_ok());
+    assert!(miden::verify(program.hash(), &[1, 2, 3], &[6, 2], proof).is_err());
 }
 
-/// Returns a Test struct in non debug mode from the provided source string, and any specified
-/// stack and advice inputs.
-///
-/// Parameters are expected in the following order:
-/// `source`, `stack_inputs` (optional), `advice_tape` (optional), `advice_sets` (optional)
-///
-/// * `source`: a well-formed source string.
-/// * `stack_inputs` (optional): the initial inputs which must be at the top of the stack before
-/// executing the `source`. Stack inputs can be provided independently without any advice inputs.
-/// * `advice_tape` (optional): the initial advice tape values. When provided, `stack_inputs` and
-/// `advice_sets` are also expected.
-/// * `advice_sets` (optional): the initial advice set values. When provided, `stack_inputs` and
-/// `advice_tape` are also expected.
-#[macro_export]
-macro_rules! build_test {
-    ($($params:tt)+) => {{
-        $crate::build_test_by_mode!(false, $($params)+)
-    }}
+#[test]
+fn prove_program_by_hash() {
+    let program = miden::Assembler::default()
+        .compile("begin push.3 mul end")
+        .unwrap();
+    let hash_hex = vm_core::utils::hex::digest_to_hex(program.hash());
+
+    // the host knows the program only by its hash; the code is supplied via the advice provider
+    let program_hash = miden::parse_program_hash(&hash_hex).unwrap();
+    let inputs = miden::ProgramInputs::new(&[5], &[], vec![]).unwrap();
+    let code_blocks = miden::CodeBlockTable::from_iter([program.root().clone()]);
+    let provider = miden::MemAdviceProvider::new(inputs.clone()).with_code_blocks(code_blocks);
+    let trace =
+        miden::execute_with_advice(&miden::Program::from_hash(program_hash), &inputs, provider)
+            .unwrap();
+
+    let stack_inputs = trace.init_stack_state()[..1].to_vec();
+    let stack_outputs = trace.last_stack_state()[..1].to_vec();
+    let public_inputs = miden::PublicInputs::new(program_hash, stack_inputs, stack_outputs);
+    let proof = miden::prove_trace(trace, public_inputs, &ProofOptions::default()).unwrap();
+
+    assert_eq!(program.hash(), program_hash);
+    assert!(miden::verify(program_hash, &[5], &[15], proof).is_ok());
+
+    // the verifier accepts only the canonical encoding of the hash
+    assert!(matches!(
+        miden::parse_program_hash(hash_hex.trim_start_matches("0x")),
+        Err(miden::HexParseError::MissingPrefix)
+    ));
+}
+
+#[test]
+fn verify_proof_batch() {
+    let assembler = miden::Assembler::default();
+    let options = ProofOptions::default();
+    let add = assembler.compile("begin add end").unwrap();
+    let mul = assembler.compile("begin mul end").unwrap();
+
+    let inputs = miden::ProgramInputs::new(&[3, 5], &[], vec![]).unwrap();
+    let (add_outputs, add_proof) = miden::prove(&add, &inputs, 1, &options).unwrap();
+    let (mul_outputs, mul_proof) = miden::prove(&mul, &inputs, 1, &options).unwrap();
+    assert_eq!(vec![8], add_outputs);
+    assert_eq!(vec![15], mul_outputs);
+
+    let batch = vec![
+        (add.hash(), &[3, 5][..], &[8][..], add_proof.clone()),
+        (mul.hash(), &[3, 5][..], &[15][..], mul_proof.clone()),
+    ];
+    assert!(miden::verify_batch(batch).is_ok());
+    assert!(miden::verify_batch(Vec::new()).is_ok());
+
+    // a single bad proof is reported without affecting the other entries
+    let batch = vec![
+        (add.hash(), &[3, 5][..], &[8][..], add_proof.clone()),
+        (mul.hash(), &[3, 5][..], &[15][..], add_proof.clone()),
+        (mul.hash(), &[3, 5][..], &[15][..], mul_proof.clone()),
+    ];
+    let err = miden::verify_batch(batch).unwrap_err();
+    assert_eq!(vec![1], err.failed_indexes());
+    assert!(matches!(
+        err.failures()[0].1,
+        miden::VerificationError::VerifierError(_)
+    ));
+
+    // all failing entries are reported, including entries with malformed outputs
+    let batch = vec![
+        (add.hash(), &[3, 5][..], &[8][..], add_proof.clone()),
+        (mul.hash(), &[3, 5][..], &[8][..], mul_proof.clone()),
+        (add.hash(), &[3, 5][..], &[0; 17][..], add_proof),
+        (add.hash(), &[3, 5][..], &[15][..], mul_proof),
+    ];
+    let err = miden::verify_batch(batch).unwrap_err();
+    assert_eq!(vec![1, 2, 3], err.failed_indexes());
+    assert_eq!(
+        miden::VerificationError::TooManyOutputValues(16, 17),
+        err.failures()[1].1
+    );
 }
 
-/// Returns a Test struct in debug mode from the provided source string and any specified stack
-/// and advice inputs.
-///
-/// Parameters are expected in the following order:
-/// `source`, `stack_inputs` (optional), `advice_tape` (optional), `advice_sets` (optional)
-///
-/// * `source`: a well-formed source string.
-/// * `stack_inputs` (optional): the initial inputs which must be at the top of the stack before
-/// executing the `source`. Stack inputs can be provided independently without any advice inputs.
-/// * `advice_tape` (optional): the initial advice tape values. When provided, `stack_inputs` and
-/// `advice_sets` are also expected.
-/// * `advice_sets` (optional): the initial advice set values. When provided, `stack_inputs` and
-/// `advice_tape` are also expected.
-#[macro_export]
-macro_rules! build_debug_test {
-    ($($params:tt)+) => {{
-        $crate::build_test_by_mode!(true, $($params)+)
-    }}
+#[test]
+fn stack_inputs_commitment() {
+    // the verifier computes the same commitment from the stack inputs passed to verify()
+    let inputs = miden::ProgramInputs::new(&[1, 2, 3], &[4], vec![]).unwrap();
+    assert_eq!(
+        inputs.stack_commitment(),
+        miden::stack_inputs_commitment(&[1, 2, 3]).unwrap()
+    );
+    assert_ne!(
+        inputs.stack_commitment(),
+        miden::stack_inputs_commitment(&[3, 2, 1]).unwrap()
+    );
+
+    assert_eq!(
+        Err(miden::VerificationError::TooManyInputValues(16, 17)),
+        miden::stack_inputs_commitment(&[0; 17])
+    );
 }
 
-/// Returns a Test struct in the specified debug or non-debug mode using the provided source string
-/// and any specified stack and advice inputs.
-///
-/// Parameters start with a boolean flag, `in_debug_mode`, specifying whether the test is built in
-/// debug or non-debug mode. After that, they match the parameters of `build_test` and
-///`build_debug_test` macros.
-///
-/// This macro is an internal test builder, and is not intended to be called directly from tests.
-/// Instead, the build_test and build_debug_test wrappers should be used.
-#[macro_export]
-macro_rules! build_test_by_mode {
-    ($in_debug_mode:expr, $source:expr) => {{
-        $crate::helpers::Test::new($source, $in_debug_mode)
-    }};
-    ($in_debug_mode:expr, $source:expr, $stack_inputs:expr) => {{
-        let inputs = $crate::helpers::ProgramInputs::new($stack_inputs, &[], vec![]).unwrap();
-
-        $crate::helpers::Test {
-            source: String::from($source),
-            inputs,
-            in_debug_mode: $in_debug_mode,
-        }
-    }};
-    ($in_debug_mode:expr, $source:expr, $stack_inputs:expr, $advice_tape:expr, $advice_sets:expr) => {{
-        let inputs =
-            $crate::helpers::ProgramInputs::new($stack_inputs, $advice_tape, $advice_sets).unwrap();
-
-        $crate::helpers::Test {
-            source: String::from($source),
-            inputs,
-            in_debug_mode: $in_debug_mode,
-        }
-    }};
+#[test]
+fn proof_serialization() {
+    use miden::{ExecutionProof, ProofCompression, ProofFormatError, PROOF_VERSION};
+
+    let program = miden::Assembler::default()
+        .compile("begin push.3 push.5 add mul end")
+        .unwrap();
+    let inputs = miden::ProgramInputs::new(&[2], &[], vec![]).unwrap();
+    let options = ProofOptions::default();
+    let (outputs, proof) = miden::prove(&program, &inputs, 1, &options).unwrap();
+    assert_eq!(vec![16], outputs);
+
+    // proofs round-trip through both encodings; sections which do not shrink when compressed are
+    // stored as is, and thus compression increases the size by at most a few bytes per section
+    let bytes = proof.to_bytes();
+    let compressed = proof.to_bytes_with(ProofCompression::Queries);
+    assert!(compressed.len() <= bytes.len() + 64);
+    for bytes in [&bytes, &compressed] {
+        assert_eq!(proof, ExecutionProof::from_bytes(bytes).unwrap());
+        assert!(miden::verify_bytes::<{ 1 << 20 }>(program.hash(), &[2], &[16], bytes).is_ok());
+    }
+
+    let header = ExecutionProof::read_header(&compressed).unwrap();
+    assert_eq!(PROOF_VERSION, header.version());
+    assert_eq!(proof.options(), header.options());
+    assert_eq!(proof.trace_length(), header.trace_length());
+    assert_eq!(ProofCompression::Queries, header.compression());
+
+    // proofs serialized before the envelope was introduced can still be read
+    let legacy = proof.stark_proof().to_bytes();
+    assert_eq!(proof, ExecutionProof::from_bytes(&legacy).unwrap());
+    assert_eq!(0, ExecutionProof::read_header(&legacy).unwrap().version());
+
+    // proofs of unknown versions are rejected
+    let mut future = bytes.clone();
+    future[4] = PROOF_VERSION + 1;
+    assert_eq!(
+        Err(ProofFormatError::UnsupportedVersion(PROOF_VERSION + 1)),
+        ExecutionProof::from_bytes(&future)
+    );
+
+    // the parameter block must match the proof
+    let mut mismatched = bytes;
+    mismatched[12] += 1;
+    assert_eq!(
+        Err(ProofFormatError::ParameterMismatch),
+        ExecutionProof::from_bytes(&mismatched)
+    );
+
+    // corrupted compressed proofs are rejected
+    let truncated = &compressed[..compressed.len() - 1];
+    assert!(matches!(
+        ExecutionProof::from_bytes(truncated),
+        Err(ProofFormatError::DeserializationError(_))
+    ));
 }
@@ -4,8 +4,9 @@ use vm_core::{
     AdviceSet, Felt, FieldElement, StarkField,
 };
 
-use crate::build_op_test;
 use crate::helpers::crypto::{init_merkle_leaf, init_merkle_leaves};
+use crate::helpers::TestError;
+use crate::{build_op_test, build_test};
 
 // TESTS
 // ================================================================================================
@@ -189,6 +190,65 @@ fn mtree_update() {
     test.expect_stack(&final_stack);
 }
 
+#[test]
+fn mtree_merge() {
+    let leaves = init_merkle_leaves(&[1, 2, 3, 4, 5, 6, 7, 8]);
+    let left = AdviceSet::new_merkle_tree(leaves[..4].to_vec()).unwrap();
+    let right = AdviceSet::new_merkle_tree(leaves[4..].to_vec()).unwrap();
+    let tree = AdviceSet::new_merkle_tree(leaves.clone()).unwrap();
+
+    let mut stack_inputs = left.root().map(|value| value.as_int()).to_vec();
+    stack_inputs.extend(right.root().map(|value| value.as_int()));
+
+    // the root of the merged tree is the root of the tree with the leaves of both trees
+    let final_stack = tree.root().map(|value| value.as_int());
+    let final_stack = final_stack.iter().rev().copied().collect::<Vec<_>>();
+    let test = build_op_test!(
+        "mtree_merge",
+        &stack_inputs,
+        &[],
+        vec![left.clone(), right.clone()]
+    );
+    test.expect_stack(&final_stack);
+
+    // nodes of the merged tree can be read and updated
+    let index = 6;
+    let new_node = init_merkle_leaf(9);
+    let mut new_leaves = leaves.clone();
+    new_leaves[index] = new_node;
+    let new_tree = AdviceSet::new_merkle_tree(new_leaves).unwrap();
+
+    let source = format!(
+        "begin
+            mtree_merge
+            push.{index}
+            push.3
+            mtree_get
+            dropw
+            push.{}.{}.{}.{}
+            push.{index}
+            push.3
+            mtree_set
+        end",
+        new_node[0], new_node[1], new_node[2], new_node[3]
+    );
+    let mut final_stack = new_node
+        .iter()
+        .rev()
+        .map(|value| value.as_int())
+        .collect::<Vec<_>>();
+    final_stack.extend(new_tree.root().iter().rev().map(|value| value.as_int()));
+    let test = build_test!(&source, &stack_inputs, &[], vec![left.clone(), right]);
+    test.expect_stack(&final_stack);
+
+    // trees of different depths cannot be merged
+    let small = AdviceSet::new_merkle_tree(leaves[..2].to_vec()).unwrap();
+    let mut stack_inputs = left.root().map(|value| value.as_int()).to_vec();
+    stack_inputs.extend(small.root().map(|value| value.as_int()));
+    let test = build_op_test!("mtree_merge", &stack_inputs, &[], vec![left, small]);
+    test.expect_error(TestError::ExecutionError("AdviceSetLookupFailed"));
+}
+
 // HELPER FUNCTIONS
 // ================================================================================================
 
@@ -1,4 +1,7 @@
-use crate::build_test;
+use crate::{
+    build_test,
+    helpers::{Felt, ProgramInputs, Test, TestError},
+};
 use rand_utils::rand_value;
 
 // ADVICE INJECTION
@@ -117,3 +120,25 @@ fn advice_inject_u64div_conditional_execution() {
     let test = build_test!(source, &[8, 0, 4, 0, 1, 0]);
     test.expect_stack(&[0, 0, 0, 0, 0, 4, 0, 8]);
 }
+
+#[test]
+fn advice_inject_map_value() {
+    let source = "begin adv.mapval push.adv.9 end";
+    let key = [Felt::new(1), Felt::new(2), Felt::new(3), Felt::new(4)];
+
+    // the number of values is injected first, followed by the values padded to 8 elements
+    let inputs = ProgramInputs::new(&[1, 2, 3, 4], &[], vec![])
+        .unwrap()
+        .with_advice_map([(key, vec![7, 8, 9])])
+        .unwrap();
+    let test = Test {
+        source: source.to_string(),
+        inputs,
+        in_debug_mode: false,
+    };
+    test.expect_stack(&[0, 0, 0, 0, 0, 9, 8, 7, 3, 4, 3, 2, 1]);
+
+    // keys which are not in the advice map are rejected
+    let test = build_test!(source, &[1, 2, 3, 5]);
+    test.expect_error(TestError::ExecutionError("AdviceMapKeyNotFound"));
+}
@@ -0,0 +1,144 @@
+use crate::helpers::{Felt, ProgramInputs};
+use miden::{AdviceProvider, Assembler, ExecutionError, MemAdviceProvider, VmState};
+use vm_core::{StarkField, Word};
+
+// EVENTS
+// ================================================================================================
+
+#[test]
+fn emit_event() {
+    // the provider reacts to event 1 by injecting the double of the top stack item into the tape
+    let source = "
+        begin
+            push.5
+            emit.1
+            push.adv.1
+            push.10
+            pop.mem.3
+            emit.7
+        end";
+
+    for in_debug_mode in [false, true] {
+        let program = Assembler::new(in_debug_mode).compile(source).unwrap();
+        let inputs = ProgramInputs::none();
+        let mut provider = EventProvider::new(MemAdviceProvider::new(inputs.clone()));
+        let trace = miden::execute_with_advice(&program, &inputs, &mut provider).unwrap();
+
+        let stack = trace.last_stack_state();
+        assert_eq!(vec![10, 5], to_ints(&stack[..2]));
+
+        let events = provider
+            .events
+            .iter()
+            .map(|(event_id, state)| (*event_id, to_ints(&state.stack[..2])))
+            .collect::<Vec<_>>();
+        assert_eq!(vec![(1, vec![5, 0]), (7, vec![10, 5])], events);
+
+        // the state passed with an event includes the memory accessed so far
+        let (_, state) = &provider.events[1];
+        assert_eq!(
+            vec![(3, [Felt::new(10), Felt::new(0), Felt::new(0), Felt::new(0)])],
+            state.memory
+        );
+    }
+}
+
+#[test]
+fn emit_event_error() {
+    // errors returned by the provider abort the execution
+    let program = Assembler::default()
+        .compile("begin push.1 emit.2 push.2 end")
+        .unwrap();
+    let inputs = ProgramInputs::none();
+    let provider = EventProvider::new(MemAdviceProvider::new(inputs.clone()));
+    let result = miden::execute_with_advice(&program, &inputs, provider);
+    assert!(matches!(result, Err(ExecutionError::EmptyAdviceTape(_))));
+}
+
+// HELPER STRUCTS
+// ================================================================================================
+
+/// An advice provider which records all emitted events. In response to event 1, the provider
+/// injects the double of the item at the top of the stack into the advice tape; in response to
+/// event 2, it fails with an error.
+struct EventProvider {
+    provider: MemAdviceProvider,
+    events: Vec<(u32, VmState)>,
+}
+
+impl EventProvider {
+    fn new(provider: MemAdviceProvider) -> Self {
+        Self {
+            provider,
+            events: Vec::new(),
+        }
+    }
+}
+
+impl AdviceProvider for EventProvider {
+    fn read_tape(&mut self) -> Result<Felt, ExecutionError> {
+        self.provider.read_tape()
+    }
+
+    fn write_tape(&mut self, value: Felt) {
+        self.provider.write_tape(value)
+    }
+
+    fn has_advice_set(&self, root: Word) -> bool {
+        self.provider.has_advice_set(root)
+    }
+
+    fn get_tree_node(
+        &mut self,
+        root: Word,
+        depth: Felt,
+        index: Felt,
+    ) -> Result<Word, ExecutionError> {
+        self.provider.get_tree_node(root, depth, index)
+    }
+
+    fn get_merkle_path(
+        &mut self,
+        root: Word,
+        depth: Felt,
+        index: Felt,
+    ) -> Result<Vec<Word>, ExecutionError> {
+        self.provider.get_merkle_path(root, depth, index)
+    }
+
+    fn update_merkle_leaf(
+        &mut self,
+        root: Word,
+        index: Felt,
+        leaf_value: Word,
+        update_in_copy: bool,
+    ) -> Result<Vec<Word>, ExecutionError> {
+        self.provider
+            .update_merkle_leaf(root, index, leaf_value, update_in_copy)
+    }
+
+    fn merge_merkle_trees(&mut self, left: Word, right: Word) -> Result<Word, ExecutionError> {
+        self.provider.merge_merkle_trees(left, right)
+    }
+
+    fn on_event(&mut self, event_id: u32, state: &VmState) -> Result<(), ExecutionError> {
+        match event_id {
+            1 => self.write_tape(state.stack[0] + state.stack[0]),
+            2 => return Err(ExecutionError::EmptyAdviceTape(state.clk)),
+            _ => (),
+        }
+        self.events.push((event_id, state.clone()));
+        Ok(())
+    }
+
+    fn advance_clock(&mut self) {
+        self.provider.advance_clock()
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+fn to_ints(values: &[Felt]) -> Vec<u64> {
+    values.iter().map(|value| value.as_int()).collect()
+}
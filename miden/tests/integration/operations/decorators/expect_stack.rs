@@ -0,0 +1,35 @@
+use crate::{build_debug_test, build_test, helpers::TestError};
+
+// STACK EXPECTATIONS
+// ================================================================================================
+
+#[test]
+fn expect_stack() {
+    let source = "
+        begin
+            push.3 add
+            expect_stack.[5,1]
+            push.0x10 swap
+            expect_stack.[5,16,1]
+        end";
+
+    let test = build_debug_test!(source, &[1, 2]);
+    test.expect_stack(&[5, 16, 1]);
+}
+
+#[test]
+fn expect_stack_mismatch() {
+    let source = "
+        begin
+            push.3 add
+            expect_stack.[5,2]
+        end";
+
+    // in debug mode, a mismatch aborts the execution
+    let test = build_debug_test!(source, &[1, 2]);
+    test.expect_error(TestError::ExecutionError("StackMismatch"));
+
+    // in release mode, the expectation is ignored
+    let test = build_test!(source, &[1, 2]);
+    test.expect_stack(&[5, 1]);
+}
@@ -1,2 +1,4 @@
 mod advice;
 mod asmop;
+mod events;
+mod expect_stack;
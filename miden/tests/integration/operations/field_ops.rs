@@ -2,8 +2,8 @@ use proptest::prelude::*;
 use rand_utils::rand_value;
 use vm_core::{Felt, FieldElement, StarkField};
 
-use crate::build_op_test;
 use crate::helpers::{prop_randw, TestError, WORD_LEN};
+use crate::{build_op_test, build_test};
 
 // FIELD OPS ASSERTIONS - MANUAL TESTS
 // ================================================================================================
@@ -46,6 +46,60 @@ fn assert_eq_fail() {
     test.expect_error(TestError::ExecutionError("FailedAssertion"));
 }
 
+#[test]
+fn assertz() {
+    let asm_op = "assertz";
+
+    let test = build_op_test!(asm_op, &[0]);
+    test.expect_stack(&[]);
+
+    let test = build_op_test!(asm_op, &[1]);
+    test.expect_error(TestError::ExecutionError("FailedAssertion"));
+}
+
+#[test]
+fn assert_with_error_code() {
+    let test = build_op_test!("assert.err=4096", &[1]);
+    test.expect_stack(&[]);
+
+    // the error code of a failing assertion is reported in the execution error
+    let test = build_op_test!("assert.err=4096", &[2]);
+    test.expect_error(TestError::ExecutionError(", 4096)"));
+
+    let test = build_op_test!("assertz.err=0x10", &[1]);
+    test.expect_error(TestError::ExecutionError(", 16)"));
+
+    let test = build_op_test!("assert_eq.err=7", &[2, 1]);
+    test.expect_error(TestError::ExecutionError(", 7)"));
+
+    // without an error code, the error code is 0
+    let test = build_op_test!("assert_eq", &[2, 1]);
+    test.expect_error(TestError::ExecutionError(", 0)"));
+}
+
+#[test]
+fn panic_with_message() {
+    let source = "
+        proc.check_input
+            dup push.10 gte
+            if.true
+                panic.\"input must be smaller than 10\"
+            end
+        end
+        begin
+            exec.check_input
+        end";
+
+    let test = build_test!(source, &[3]);
+    test.expect_stack(&[3]);
+
+    // the message of the panic is reported in the execution error
+    let test = build_test!(source, &[12]);
+    test.expect_error(TestError::ExecutionError(
+        "\"input must be smaller than 10\")",
+    ));
+}
+
 // FIELD OPS ARITHMETIC - MANUAL TESTS
 // ================================================================================================
 
@@ -335,6 +389,57 @@ fn unchecked_pow2() {
     assert!(test.execute().is_ok());
 }
 
+#[test]
+fn exp() {
+    let asm_op = "exp";
+
+    // --- simple cases ---------------------------------------------------------------------------
+    build_op_test!(asm_op, &[5, 0]).expect_stack(&[1]);
+    build_op_test!(asm_op, &[5, 3]).expect_stack(&[125]);
+    build_op_test!(asm_op, &[0, 0]).expect_stack(&[1]);
+    build_op_test!(asm_op, &[2, 63]).expect_stack(&[1 << 63]);
+
+    // --- exponents larger than 32 bits ----------------------------------------------------------
+    let b = (1_u64 << 40) + 3;
+    let expected = Felt::new(3).exp(b).as_int();
+    build_op_test!(asm_op, &[3, b]).expect_stack(&[expected]);
+
+    // --- test that the rest of the stack isn't affected -----------------------------------------
+    let c = rand_value::<u64>();
+    build_op_test!(asm_op, &[c, 7, 2]).expect_stack(&[49, c]);
+}
+
+#[test]
+fn exp_bits() {
+    build_op_test!("exp.u8", &[3, 255]).expect_stack(&[Felt::new(3).exp(255).as_int()]);
+    build_op_test!("exp.u1", &[3, 1]).expect_stack(&[3]);
+    build_op_test!("exp.u32", &[2, 31]).expect_stack(&[1 << 31]);
+
+    let b = u64::MAX >> 20;
+    let expected = Felt::new(5).exp(b).as_int();
+    build_op_test!("exp.u44", &[5, b]).expect_stack(&[expected]);
+}
+
+#[test]
+fn exp_bits_fail() {
+    // --- exponents which do not fit into the specified number of bits ---------------------------
+    build_op_test!("exp.u8", &[3, 256]).expect_error(TestError::ExecutionError("FailedAssertion"));
+    build_op_test!("exp.u32", &[3, 1 << 32])
+        .expect_error(TestError::ExecutionError("FailedAssertion"));
+    build_op_test!("exp.u40", &[3, 1 << 40])
+        .expect_error(TestError::ExecutionError("FailedAssertion"));
+}
+
+#[test]
+fn exp_imm() {
+    build_op_test!("exp.0", &[7]).expect_stack(&[1]);
+    build_op_test!("exp.1", &[7]).expect_stack(&[7]);
+    build_op_test!("exp.10", &[2]).expect_stack(&[1024]);
+
+    let c = rand_value::<u64>();
+    build_op_test!("exp.3", &[c, 5]).expect_stack(&[125, c]);
+}
+
 // FIELD OPS BOOLEAN - MANUAL TESTS
 // ================================================================================================
 
@@ -651,6 +756,22 @@ proptest! {
 
         build_op_test!(asm_op, &[b as u64]).prop_expect_stack(&[expected as u64])?;
     }
+
+    #[test]
+    fn exp_proptest(a in any::<u64>(), b in 0..Felt::MODULUS) {
+        let asm_op = "exp";
+        let expected = Felt::new(a).exp(b).as_int();
+
+        build_op_test!(asm_op, &[a, b]).prop_expect_stack(&[expected])?;
+    }
+
+    #[test]
+    fn exp_imm_proptest(a in any::<u64>(), b in any::<u32>()) {
+        let asm_op = format!("exp.{}", b);
+        let expected = Felt::new(a).exp(b as u64).as_int();
+
+        build_op_test!(&asm_op, &[a]).prop_expect_stack(&[expected])?;
+    }
 }
 
 // FIELD OPS COMPARISON - RANDOMIZED TESTS
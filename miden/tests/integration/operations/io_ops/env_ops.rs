@@ -24,6 +24,20 @@ fn push_env_sdepth() {
     test.expect_stack(&[18, 1, 1, 7, 6, 5, 4, 3, 2, 1, 0, 7, 6, 5, 4, 3]);
 }
 
+#[test]
+fn push_env_clk() {
+    let test_op = "push.env.clk";
+
+    // --- the SPAN operation is executed at cycle 0 ----------------------------------------------
+    let test = build_op_test!(test_op, &[2, 4]);
+    test.expect_stack(&[1, 4, 2]);
+
+    // --- the value is the cycle at which the instruction is executed ----------------------------
+    let source = format!("begin {} push.3 drop {} end", test_op, test_op);
+    let test = build_test!(&source, &[]);
+    test.expect_stack(&[4, 1]);
+}
+
 #[test]
 fn push_env_locaddr() {
     // --- locaddr returns expected address -------------------------------------------------------
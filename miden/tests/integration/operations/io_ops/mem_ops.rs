@@ -199,3 +199,19 @@ fn read_after_write() {
     let test = build_op_test!("popw.mem.0 loadw.mem.0", &[1, 2, 3, 4, 5, 6, 7, 8]);
     test.expect_stack(&[8, 7, 6, 5]);
 }
+
+// DATA SEGMENTS
+// ================================================================================================
+
+#[test]
+fn read_data_segment() {
+    let source = "
+        data.TABLE=[3,5,7]
+        begin
+            push.addr_of.TABLE add.2 push.mem
+        end";
+
+    // --- the segment is initialized before the program is executed ------------------------------
+    let test = build_test!(source, &[1, 2]);
+    test.expect_stack(&[7, 2, 1]);
+}
@@ -5,6 +5,7 @@ mod arithmetic_ops;
 mod bitwise_ops;
 mod comparison_ops;
 mod conversion_ops;
+mod word_ops;
 
 // HELPER FUNCTIONS
 // ================================================================================================
@@ -0,0 +1,160 @@
+use super::{build_op_test, test_param_out_of_bounds, TestError, U32_BOUND, WORD_LEN};
+use proptest::prelude::*;
+use rand_utils::rand_array;
+
+// U32 OPERATIONS TESTS - MANUAL - WORD OPERATIONS
+// ================================================================================================
+
+#[test]
+fn u32wrapping_add_w() {
+    let asm_op = "u32wrapping_add_w";
+
+    // --- simple cases ---------------------------------------------------------------------------
+    // the sums of the lanes overflow independently
+    let test = build_op_test!(
+        asm_op,
+        &[1, u32::MAX as u64, 3, 4, 5, 6, 7, u32::MAX as u64]
+    );
+    test.expect_stack(&[3, 10, 5, 6]);
+
+    // --- random u32 values ----------------------------------------------------------------------
+    let a = rand_array::<u32, WORD_LEN>();
+    let b = rand_array::<u32, WORD_LEN>();
+    let c = apply_lanes(a, b, u32::wrapping_add);
+    let test = build_op_test!(asm_op, &to_stack_inputs(&[a, b]));
+    test.expect_stack(&to_stack(c));
+
+    // --- test that the rest of the stack isn't affected -----------------------------------------
+    let mut inputs = vec![42];
+    inputs.extend_from_slice(&to_stack_inputs(&[a, b]));
+    let mut expected = to_stack(c).to_vec();
+    expected.push(42);
+    let test = build_op_test!(asm_op, &inputs);
+    test.expect_stack(&expected);
+}
+
+#[test]
+fn u32bitwise_w() {
+    let a = rand_array::<u32, WORD_LEN>();
+    let b = rand_array::<u32, WORD_LEN>();
+
+    let test = build_op_test!("u32and_w", &to_stack_inputs(&[a, b]));
+    test.expect_stack(&to_stack(apply_lanes(a, b, |a, b| a & b)));
+
+    let test = build_op_test!("u32or_w", &to_stack_inputs(&[a, b]));
+    test.expect_stack(&to_stack(apply_lanes(a, b, |a, b| a | b)));
+
+    let test = build_op_test!("u32xor_w", &to_stack_inputs(&[a, b]));
+    test.expect_stack(&to_stack(apply_lanes(a, b, |a, b| a ^ b)));
+}
+
+#[test]
+fn u32bitwise_w_fail() {
+    // an out of bounds value in any lane of either word is rejected
+    for asm_op in ["u32and_w", "u32or_w", "u32xor_w"] {
+        for i in 0..2 * WORD_LEN {
+            let mut inputs = vec![0; 2 * WORD_LEN];
+            inputs[i] = U32_BOUND;
+            let test = build_op_test!(asm_op, &inputs);
+            test.expect_error(TestError::ExecutionError("NotU32Value"));
+        }
+    }
+}
+
+#[test]
+fn u32rotl_w() {
+    // --- simple cases ---------------------------------------------------------------------------
+    let test = build_op_test!("u32rotl_w.1", &[1, 2, 1 << 31, 3]);
+    test.expect_stack(&[6, 1, 4, 2]);
+
+    let test = build_op_test!("u32rotl_w.0", &[1, 2, 1 << 31, 3]);
+    test.expect_stack(&[3, 1 << 31, 2, 1]);
+
+    // --- random u32 values ----------------------------------------------------------------------
+    let a = rand_array::<u32, WORD_LEN>();
+    for b in [5, 31] {
+        let test = build_op_test!(format!("u32rotl_w.{b}"), &to_stack_inputs(&[a]));
+        test.expect_stack(&to_stack(a.map(|a| a.rotate_left(b))));
+    }
+
+    // --- invalid shift values -------------------------------------------------------------------
+    test_param_out_of_bounds("u32rotl_w", 32);
+    let test = build_op_test!("u32rotl_w");
+    test.expect_error(TestError::AssemblyError("missing"));
+}
+
+#[test]
+fn u32rotr_w() {
+    // --- simple cases ---------------------------------------------------------------------------
+    let test = build_op_test!("u32rotr_w.1", &[1, 2, 1 << 31, 3]);
+    test.expect_stack(&[(1 << 31) + 1, 1 << 30, 1, 1 << 31]);
+
+    let test = build_op_test!("u32rotr_w.0", &[1, 2, 1 << 31, 3]);
+    test.expect_stack(&[3, 1 << 31, 2, 1]);
+
+    // --- random u32 values ----------------------------------------------------------------------
+    let a = rand_array::<u32, WORD_LEN>();
+    for b in [7, 31] {
+        let test = build_op_test!(format!("u32rotr_w.{b}"), &to_stack_inputs(&[a]));
+        test.expect_stack(&to_stack(a.map(|a| a.rotate_right(b))));
+    }
+
+    // --- invalid shift values -------------------------------------------------------------------
+    test_param_out_of_bounds("u32rotr_w", 32);
+}
+
+// U32 OPERATIONS TESTS - RANDOMIZED - WORD OPERATIONS
+// ================================================================================================
+
+proptest! {
+    #[test]
+    fn u32wrapping_add_w_proptest(a in any::<[u32; WORD_LEN]>(), b in any::<[u32; WORD_LEN]>()) {
+        let test = build_op_test!("u32wrapping_add_w", &to_stack_inputs(&[a, b]));
+        test.prop_expect_stack(&to_stack(apply_lanes(a, b, u32::wrapping_add)))?;
+    }
+
+    #[test]
+    fn u32xor_w_proptest(a in any::<[u32; WORD_LEN]>(), b in any::<[u32; WORD_LEN]>()) {
+        let test = build_op_test!("u32xor_w", &to_stack_inputs(&[a, b]));
+        test.prop_expect_stack(&to_stack(apply_lanes(a, b, |a, b| a ^ b)))?;
+    }
+
+    #[test]
+    fn u32rotl_w_proptest(a in any::<[u32; WORD_LEN]>(), b in 0_u32..32) {
+        let test = build_op_test!(format!("u32rotl_w.{b}"), &to_stack_inputs(&[a]));
+        test.prop_expect_stack(&to_stack(a.map(|a| a.rotate_left(b))))?;
+    }
+
+    #[test]
+    fn u32rotr_w_proptest(a in any::<[u32; WORD_LEN]>(), b in 0_u32..32) {
+        let test = build_op_test!(format!("u32rotr_w.{b}"), &to_stack_inputs(&[a]));
+        test.prop_expect_stack(&to_stack(a.map(|a| a.rotate_right(b))))?;
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Applies the specified function to each pair of corresponding lanes of two words.
+fn apply_lanes(a: [u32; WORD_LEN], b: [u32; WORD_LEN], f: fn(u32, u32) -> u32) -> [u32; WORD_LEN] {
+    [f(a[0], b[0]), f(a[1], b[1]), f(a[2], b[2]), f(a[3], b[3])]
+}
+
+/// Returns the word as it is positioned on the stack, with the last element at the top.
+fn to_stack(word: [u32; WORD_LEN]) -> [u64; WORD_LEN] {
+    [
+        word[3] as u64,
+        word[2] as u64,
+        word[1] as u64,
+        word[0] as u64,
+    ]
+}
+
+/// Returns stack inputs which place the specified words onto the stack, with the last word at
+/// the top.
+fn to_stack_inputs(words: &[[u32; WORD_LEN]]) -> Vec<u64> {
+    words
+        .iter()
+        .flat_map(|word| word.iter().map(|&value| value as u64))
+        .collect()
+}
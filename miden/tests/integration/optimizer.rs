@@ -0,0 +1,83 @@
+use assembly::{Assembler, OptLevel, PushEncoding};
+use proptest::prelude::*;
+use vm_core::{ProgramInputs, StackTopState, MIN_STACK_DEPTH};
+
+// CONSTANTS
+// ================================================================================================
+
+/// Programs containing redundant sequences of operations of all kinds removed by the optimizer.
+const SOURCES: [&str; 6] = [
+    "begin push.0 drop swap swap dup.3 drop add end",
+    "begin movup.3 movdn.3 movdn.5 movup.5 swap dup.0 drop swap mul end",
+    "begin push.2 push.3 add add add.4 add.5 mul.2 mul.3 push.0 add push.1 mul end",
+    "begin dup.0 push.1 add.1 sub.2 mul swap push.7 neg add end",
+    "begin push.1 push.1 adv.u64div drop drop swap swap dup.1 eq not not end",
+    "begin repeat.3 swap swap dup.2 add movup.2 movdn.2 end end",
+];
+
+// TESTS
+// ================================================================================================
+
+#[test]
+fn optimized_programs_are_smaller() {
+    for source in SOURCES {
+        let program = Assembler::default().compile(source).unwrap();
+        let optimized = Assembler::default()
+            .with_opt_level(OptLevel::Basic)
+            .compile(source)
+            .unwrap();
+        assert_ne!(program.hash(), optimized.hash(), "{}", source);
+    }
+}
+
+proptest! {
+    #[test]
+    fn optimized_programs_are_equivalent(
+        inputs in prop::collection::vec(any::<u32>(), MIN_STACK_DEPTH)
+    ) {
+        let inputs = inputs.into_iter().map(|v| v as u64).collect::<Vec<_>>();
+        for source in SOURCES {
+            assert_equivalent(source, &inputs)?;
+        }
+    }
+
+    #[test]
+    fn optimized_stdlib_is_equivalent(
+        inputs in prop::collection::vec(any::<u32>(), 4)
+    ) {
+        let inputs = inputs.into_iter().map(|v| v as u64).collect::<Vec<_>>();
+        for proc in ["wrapping_add", "wrapping_mul", "checked_lt", "checked_eq", "checked_xor"] {
+            let source = format!("use.std::math::u64 begin exec.u64::{} end", proc);
+            assert_equivalent(&source, &inputs)?;
+        }
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Executes the program compiled from the specified source with and without optimizations (and
+/// with different push encodings), and asserts that the resulting stack states are the same.
+fn assert_equivalent(source: &str, stack_inputs: &[u64]) -> Result<(), TestCaseError> {
+    let inputs = ProgramInputs::from_stack_inputs(stack_inputs).unwrap();
+    let expected = run(&Assembler::default(), source, &inputs);
+    for (level, encoding) in [
+        (OptLevel::Basic, PushEncoding::Default),
+        (OptLevel::Aggressive, PushEncoding::Default),
+        (OptLevel::None, PushEncoding::MinSize),
+        (OptLevel::Basic, PushEncoding::MinCycles),
+    ] {
+        let assembler = Assembler::default()
+            .with_opt_level(level)
+            .with_push_encoding(encoding);
+        prop_assert_eq!(expected, run(&assembler, source, &inputs), "{}", source);
+    }
+    Ok(())
+}
+
+fn run(assembler: &Assembler, source: &str, inputs: &ProgramInputs) -> StackTopState {
+    let program = assembler.compile(source).unwrap();
+    processor::execute(&program, inputs)
+        .unwrap()
+        .last_stack_state()
+}
@@ -0,0 +1,121 @@
+use crate::helpers::{Felt, ProgramInputs, Test, TestError};
+use vm_core::{chiplets::hasher, MmrPeaks, StarkField, Word};
+
+// LOADING ADVICE MAP ENTRIES
+// ================================================================================================
+
+#[test]
+fn load_map_value() {
+    let source = "
+        use.std::advice
+        begin
+            exec.advice::load_map_value
+        end";
+
+    // the commitment to the values and the number of values are returned
+    for num_values in [0, 3, 8, 13] {
+        let values = (1..=num_values).collect::<Vec<u64>>();
+        let test = build_map_test(source, &[100], (key(), values.clone()));
+
+        let mut expected = commit(&values)
+            .iter()
+            .rev()
+            .map(|value| value.as_int())
+            .collect::<Vec<_>>();
+        expected.push(num_values);
+        test.expect_stack(&expected);
+    }
+}
+
+#[test]
+fn load_verified_map_value() {
+    let source = "
+        use.std::advice
+        begin
+            exec.advice::load_verified_map_value
+            pushw.mem.200
+            pushw.mem.201
+            pushw.mem.202
+        end";
+
+    let values = (1..=10).collect::<Vec<u64>>();
+    let mut stack_inputs = vec![200];
+    stack_inputs.extend(commit(&values).iter().map(|value| value.as_int()));
+    let test = build_map_test(source, &stack_inputs, (key(), values.clone()));
+    test.expect_stack(&[0, 0, 10, 9, 8, 7, 6, 5, 4, 3, 2, 1, 10]);
+
+    // values which do not match the commitment are rejected
+    let mut tampered = values;
+    tampered[9] = 11;
+    let test = build_map_test(source, &stack_inputs, (key(), tampered));
+    test.expect_error(TestError::ExecutionError("FailedAssertion"));
+
+    // so are keys which are not in the advice map
+    let other_key = [Felt::new(5), Felt::new(6), Felt::new(7), Felt::new(8)];
+    let test = build_map_test(source, &stack_inputs, (other_key, vec![1]));
+    test.expect_error(TestError::ExecutionError("AdviceMapKeyNotFound"));
+}
+
+#[test]
+fn load_mmr_peaks() {
+    let source = "
+        use.std::advice
+        begin
+            exec.advice::load_verified_map_value
+            pushw.mem.200
+            pushw.mem.201
+        end";
+
+    // the hash of the peaks is both the key of the advice map entry and the commitment to it
+    let peaks = MmrPeaks::new(6, vec![word(1), word(5)]).unwrap();
+    let hash = Word::from(peaks.hash_peaks()).map(|value| value.as_int());
+    let mut stack_inputs = vec![200];
+    stack_inputs.extend(hash);
+    stack_inputs.extend(hash);
+    let inputs = ProgramInputs::new(&stack_inputs, &[], vec![])
+        .unwrap()
+        .with_mmr_peaks([peaks])
+        .unwrap();
+    let test = Test {
+        source: source.to_string(),
+        inputs,
+        in_debug_mode: false,
+    };
+    test.expect_stack(&[0, 0, 0, 5, 0, 0, 0, 1, 8]);
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+fn key() -> Word {
+    [Felt::new(1), Felt::new(2), Felt::new(3), Felt::new(4)]
+}
+
+fn word(value: u64) -> Word {
+    [Felt::new(value), Felt::new(0), Felt::new(0), Felt::new(0)]
+}
+
+fn commit(values: &[u64]) -> Word {
+    let values = values
+        .iter()
+        .map(|&value| Felt::new(value))
+        .collect::<Vec<_>>();
+    hasher::hash_elements(&values).into()
+}
+
+/// Builds a test with the specified stack inputs followed by the key of the advice map entry with
+/// the key `key()`, and with the specified entry in the advice map.
+fn build_map_test(source: &str, stack_inputs: &[u64], entry: (Word, Vec<u64>)) -> Test {
+    let mut stack_inputs = stack_inputs.to_vec();
+    stack_inputs.extend(key().iter().map(|value| value.as_int()));
+    let inputs = ProgramInputs::new(&stack_inputs, &[], vec![])
+        .unwrap()
+        .with_advice_map([entry])
+        .unwrap();
+
+    Test {
+        source: source.to_string(),
+        inputs,
+        in_debug_mode: false,
+    }
+}
@@ -0,0 +1,197 @@
+use super::{build_test, TestError};
+use crate::helpers::execute_with_hints;
+use vm_core::StarkField;
+
+/// Creates a vector with capacity 3 using the memory allocator and leaves its address on top of
+/// the stack.
+const NEW_VEC: &str = "
+    push.3
+    dup
+    add.1
+    exec.mem::alloc
+    dup
+    movdn.2
+    exec.vec::init";
+
+/// Creates a vector with capacity 4 using the memory allocator and leaves its address on top of
+/// the stack.
+const NEW_VEC4: &str = "
+    push.4
+    dup
+    add.1
+    exec.mem::alloc
+    dup
+    movdn.2
+    exec.vec::init";
+
+#[test]
+fn init() {
+    let source = format!(
+        "use.std::collections::vec use.std::mem
+        begin
+            {NEW_VEC}
+            dup
+            exec.vec::len
+            swap
+            exec.vec::capacity
+        end"
+    );
+
+    build_test!(&source).expect_stack(&[3, 0]);
+}
+
+#[test]
+fn push_pop() {
+    let source = format!(
+        "use.std::collections::vec use.std::mem
+        begin
+            {NEW_VEC}
+            push.1.2.3.4
+            dup.4
+            exec.vec::push
+            push.5.6.7.8
+            dup.4
+            exec.vec::push
+
+            dup
+            exec.vec::len
+            swap
+            dup
+            exec.vec::pop
+            movup.4
+            exec.vec::len
+        end"
+    );
+
+    build_test!(&source).expect_stack(&[1, 8, 7, 6, 5, 2]);
+
+    // pushing into a full vector should fail
+    let source = format!(
+        "use.std::collections::vec use.std::mem
+        begin
+            {NEW_VEC}
+            repeat.4
+                push.1.2.3.4
+                dup.4
+                exec.vec::push
+            end
+        end"
+    );
+
+    build_test!(&source).expect_error(TestError::ExecutionError("FailedAssertion"));
+
+    // popping from an empty vector should fail
+    let source = format!(
+        "use.std::collections::vec use.std::mem
+        begin
+            {NEW_VEC}
+            exec.vec::pop
+        end"
+    );
+
+    build_test!(&source).expect_error(TestError::ExecutionError("FailedAssertion"));
+}
+
+#[test]
+fn get_set() {
+    let source = format!(
+        "use.std::collections::vec use.std::mem
+        begin
+            {NEW_VEC}
+            push.1.2.3.4
+            dup.4
+            exec.vec::push
+            push.5.6.7.8
+            dup.4
+            exec.vec::push
+
+            push.9.10.11.12
+            push.0
+            dup.5
+            exec.vec::set
+
+            push.1
+            dup.1
+            exec.vec::get
+            push.0
+            dup.5
+            exec.vec::get
+            movup.8
+            drop
+        end"
+    );
+
+    build_test!(&source).expect_stack(&[12, 11, 10, 9, 8, 7, 6, 5]);
+
+    // accessing an index beyond the length of the vector should fail, even if it is within the
+    // capacity of the vector
+    let source = format!(
+        "use.std::collections::vec use.std::mem
+        begin
+            {NEW_VEC}
+            push.1.2.3.4
+            dup.4
+            exec.vec::push
+
+            push.1
+            swap
+            exec.vec::get
+        end"
+    );
+
+    build_test!(&source).expect_error(TestError::ExecutionError("FailedAssertion"));
+}
+
+#[test]
+fn sort_hint() {
+    // keys are the first elements of the items, and the last elements identify the items; after
+    // sorting, the last elements of the items are read with the last element of the first item
+    // deepest in the stack
+    let read_ids = (0..4)
+        .map(|i| format!("push.{i} dup.1 exec.vec::get movdn.3 drop drop drop swap"))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let source = format!(
+        "use.std::collections::vec use.std::mem
+        begin
+            {NEW_VEC4}
+            push.5.0.0.1
+            dup.4
+            exec.vec::push
+            push.2.0.0.2
+            dup.4
+            exec.vec::push
+            push.5.0.0.3
+            dup.4
+            exec.vec::push
+            push.1.0.0.4
+            dup.4
+            exec.vec::push
+
+            dup
+            exec.vec::sort_hint
+            {read_ids}
+            drop
+        end"
+    );
+
+    // the permutation is supplied by the host; items with equal keys retain their order
+    let test = build_test!(&source);
+    let trace = execute_with_hints(&test).unwrap();
+    let stack = trace.last_stack_state().map(|value| value.as_int());
+    assert_eq!([3, 1, 2, 4], stack[..4]);
+
+    // without a host which answers the request, the advice tape is empty
+    test.expect_error(TestError::ExecutionError("EmptyAdviceTape"));
+
+    // a correct permutation is accepted regardless of where it comes from
+    let test = build_test!(&source, &[], &[3, 1, 0, 2], vec![]);
+    test.expect_stack(&[3, 1, 2, 4]);
+
+    // permutations which do not sort the vector, repeat an item, or refer to an item beyond the
+    // end of the vector are rejected
+    for tape in [[0, 1, 2, 3], [3, 1, 0, 0], [3, 1, 0, 4]] {
+        let test = build_test!(&source, &[], &tape, vec![]);
+        test.expect_error(TestError::ExecutionError("FailedAssertion"));
+    }
+}
@@ -0,0 +1,191 @@
+use super::{build_test, Felt};
+use crate::helpers::TestError;
+use rand_utils::rand_vector;
+use vm_core::{AdviceSet, StarkField, Word};
+
+/// Address of the Merkle path used by membership proofs.
+const PATH_ADDR: u64 = 1000;
+
+#[test]
+fn build_root() {
+    for num_leaves in [2, 4, 8, 32] {
+        let leaves = rand_leaves(num_leaves);
+        let tree = AdviceSet::new_merkle_tree(leaves.clone()).unwrap();
+
+        let source = format!(
+            "use.std::crypto::merkle
+            begin
+                {}
+                push.{num_leaves}
+                push.0
+                exec.merkle::build_root
+            end",
+            store_words(&leaves, 0)
+        );
+        build_test!(&source).expect_stack(&word_to_stack(tree.root()));
+    }
+
+    // the root of a tree with a single leaf is the leaf itself
+    let leaf = rand_leaves(1)[0];
+    let source = format!(
+        "use.std::crypto::merkle
+        begin
+            {}
+            push.1
+            push.0
+            exec.merkle::build_root
+        end",
+        store_words(&[leaf], 0)
+    );
+    build_test!(&source).expect_stack(&word_to_stack(leaf));
+}
+
+#[test]
+fn build_root_invalid_num_leaves() {
+    for num_leaves in [0, 3, 6] {
+        let source = format!(
+            "use.std::crypto::merkle
+            begin
+                push.{num_leaves}
+                push.0
+                exec.merkle::build_root
+            end"
+        );
+        build_test!(&source).expect_error(TestError::ExecutionError("FailedAssertion"));
+    }
+}
+
+#[test]
+fn compute_root() {
+    let leaves = rand_leaves(16);
+    let tree = AdviceSet::new_merkle_tree(leaves.clone()).unwrap();
+
+    for index in [0, 5, 15] {
+        let path = tree.get_path(tree.depth(), index).unwrap();
+        let source = format!(
+            "use.std::crypto::merkle
+            begin
+                {}
+                exec.merkle::compute_root
+            end",
+            store_words(&path, PATH_ADDR)
+        );
+        let stack = membership_inputs(leaves[index as usize], index, tree.depth(), None);
+        build_test!(&source, &stack).expect_stack(&word_to_stack(tree.root()));
+    }
+}
+
+#[test]
+fn verify_membership() {
+    let leaves = rand_leaves(8);
+    let tree = AdviceSet::new_merkle_tree(leaves.clone()).unwrap();
+    let index = 6;
+    let depth = tree.depth();
+    let path = tree.get_path(depth, index).unwrap();
+    let source = format!(
+        "use.std::crypto::merkle
+        begin
+            {}
+            exec.merkle::verify_membership
+            push.1
+        end",
+        store_words(&path, PATH_ADDR)
+    );
+
+    let root = Some(tree.root());
+    let stack = membership_inputs(leaves[index as usize], index, depth, root);
+    build_test!(&source, &stack).expect_stack(&[1]);
+
+    // a different leaf, a different index, or an index beyond the last leaf are rejected
+    let invalid_inputs = [
+        membership_inputs(leaves[0], index, depth, root),
+        membership_inputs(leaves[index as usize], index - 1, depth, root),
+        membership_inputs(leaves[index as usize], index + 8, depth, root),
+    ];
+    for stack in invalid_inputs {
+        build_test!(&source, &stack).expect_error(TestError::ExecutionError("FailedAssertion"));
+    }
+}
+
+#[test]
+fn build_root_and_verify_membership() {
+    // the path of the leaf [0, 0, 0, 25] at index 5 of the tree with leaves [0, 0, 0, i^2]
+    let leaves = (0..8u64)
+        .map(|i| [Felt::new(0), Felt::new(0), Felt::new(0), Felt::new(i * i)])
+        .collect::<Vec<_>>();
+    let tree = AdviceSet::new_merkle_tree(leaves).unwrap();
+    let path = tree.get_path(3, 5).unwrap();
+
+    // a program commits to data computed in the VM, and then verifies that a word is a part of it
+    let source = format!(
+        "use.std::crypto::merkle
+        begin
+            {}
+            push.0
+            push.1
+            while.true
+                push.0.0.0
+                dup.3
+                dup.4
+                mul
+                dup.4
+                popw.mem
+                add.1
+                dup
+                neq.8
+            end
+            drop
+            push.8
+            push.0
+            exec.merkle::build_root
+            push.0.0.0.25
+            push.5
+            push.3
+            push.{PATH_ADDR}
+            exec.merkle::verify_membership
+            push.1
+        end",
+        store_words(&path, PATH_ADDR)
+    );
+    build_test!(&source).expect_stack(&[1]);
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+fn rand_leaves(num_leaves: usize) -> Vec<Word> {
+    rand_vector::<u64>(num_leaves * 4)
+        .chunks(4)
+        .map(|chunk| [0, 1, 2, 3].map(|i| Felt::new(chunk[i])))
+        .collect()
+}
+
+/// Returns the code which stores the words into consecutive addresses starting at addr.
+fn store_words(words: &[Word], addr: u64) -> String {
+    words
+        .iter()
+        .enumerate()
+        .map(|(i, word)| {
+            let [a, b, c, d] = word.map(|value| value.as_int());
+            format!("push.{a}.{b}.{c}.{d} popw.mem.{}", addr + i as u64)
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Returns the stack inputs [p, d, i, V, R, ...], where the path is at PATH_ADDR; the root is
+/// omitted if not provided.
+fn membership_inputs(value: Word, index: u64, depth: u32, root: Option<Word>) -> Vec<u64> {
+    let mut stack = Vec::new();
+    if let Some(root) = root {
+        stack.extend(root.map(|value| value.as_int()));
+    }
+    stack.extend(value.map(|value| value.as_int()));
+    stack.extend([index, depth as u64, PATH_ADDR]);
+    stack
+}
+
+/// Returns the elements of the word in the order in which they appear on the stack.
+fn word_to_stack(word: Word) -> Vec<u64> {
+    word.iter().rev().map(|value| value.as_int()).collect()
+}
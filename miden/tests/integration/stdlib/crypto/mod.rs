@@ -3,4 +3,6 @@ use crate::helpers::{Felt, MIN_STACK_DEPTH};
 
 mod blake3;
 mod keccak256;
+mod merkle;
+mod random_coin;
 mod sha256;
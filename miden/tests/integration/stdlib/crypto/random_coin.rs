@@ -0,0 +1,115 @@
+use super::{build_test, Felt};
+use miden::RandomCoin;
+use vm_core::{StarkField, Word};
+
+/// Initializes a coin at address 10 with the seed [1, 2, 3, 4].
+const INIT_COIN: &str = "
+    push.1.2.3.4
+    push.10
+    exec.random_coin::init";
+
+#[test]
+fn draw() {
+    let source = format!(
+        "use.std::crypto::random_coin
+        begin
+            {INIT_COIN}
+            push.10
+            exec.random_coin::draw
+            push.10
+            exec.random_coin::draw
+            push.10
+            exec.random_coin::draw_word
+        end"
+    );
+
+    let mut coin = RandomCoin::new(to_word(&[1, 2, 3, 4]));
+    let r0 = coin.draw();
+    let r1 = coin.draw();
+    let mut expected = vec![r0, r1];
+    expected.extend_from_slice(&coin.draw_word());
+
+    let test = build_test!(&source);
+    test.expect_stack(&to_stack(&expected));
+}
+
+#[test]
+fn reseed() {
+    let source = format!(
+        "use.std::crypto::random_coin
+        begin
+            {INIT_COIN}
+            push.10
+            exec.random_coin::draw
+            push.5.6.7.8
+            push.10
+            exec.random_coin::reseed
+            push.10
+            exec.random_coin::draw
+        end"
+    );
+
+    let mut coin = RandomCoin::new(to_word(&[1, 2, 3, 4]));
+    let r0 = coin.draw();
+    coin.reseed(to_word(&[5, 6, 7, 8]));
+    let r1 = coin.draw();
+
+    // reseeding resets the counter, and thus the value drawn after reseeding depends only on the
+    // new seed
+    assert_eq!(r1, RandomCoin::new(coin.seed()).draw());
+
+    let test = build_test!(&source);
+    test.expect_stack(&to_stack(&[r0, r1]));
+}
+
+#[test]
+fn draw_index() {
+    let source = format!(
+        "use.std::crypto::random_coin
+        begin
+            {INIT_COIN}
+            repeat.8
+                push.7
+                push.10
+                exec.random_coin::draw_index
+            end
+        end"
+    );
+
+    let mut coin = RandomCoin::new(to_word(&[1, 2, 3, 4]));
+    let mut expected = (0..8)
+        .map(|_| coin.draw_index(7) as u64)
+        .collect::<Vec<_>>();
+    assert!(expected.iter().all(|&i| i < 7));
+    expected.reverse();
+
+    let test = build_test!(&source);
+    test.expect_stack(&expected);
+
+    // drawing an index from an empty range should fail
+    let source = format!(
+        "use.std::crypto::random_coin
+        begin
+            {INIT_COIN}
+            push.0
+            push.10
+            exec.random_coin::draw_index
+        end"
+    );
+
+    let test = build_test!(&source);
+    assert!(test.execute().is_err());
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+fn to_word(values: &[u64; 4]) -> Word {
+    values.map(Felt::new)
+}
+
+/// Converts the specified values into the stack expected after pushing them in order, i.e., with
+/// the last value on top of the stack.
+fn to_stack(values: &[Felt]) -> Vec<u64> {
+    values.iter().rev().map(|value| value.as_int()).collect()
+}
@@ -52,3 +52,129 @@ fn sha256_2_to_1_hash() {
     let test = build_test!(source, &i_words);
     test.expect_stack(&digest_words);
 }
+
+#[test]
+fn sha256_compress() {
+    let state = rand_utils::rand_array::<u32, 8>();
+    let block = rand_utils::rand_array::<u8, 64>();
+
+    let mut expected = state;
+    sha2::compress256(&mut expected, &[block.into()]);
+
+    // the state is pushed onto the stack above the message block, with the first word of each
+    // at the top
+    let source = format!(
+        "
+    use.std::crypto::hashes::sha256
+
+    begin
+        push.{}
+        exec.sha256::compress
+    end",
+        state
+            .iter()
+            .rev()
+            .map(|word| word.to_string())
+            .collect::<Vec<_>>()
+            .join(".")
+    );
+    let mut inputs = to_words(&block);
+    inputs.reverse();
+
+    let test = build_test!(&source, &inputs);
+    test.expect_stack(&expected.map(|word| word as u64));
+}
+
+#[test]
+fn sha256_hash_memory() {
+    // NIST test vectors, see https://csrc.nist.gov/projects/cryptographic-standards-and-guidelines/example-values
+    let vectors: [(&[u8], &str); 4] = [
+        (
+            b"",
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855",
+        ),
+        (
+            b"abc",
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad",
+        ),
+        (
+            b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq",
+            "248d6a61d20638b8e5c026930c3e6039a33ce45964ff2167f6ecedd419db06c1",
+        ),
+        (
+            b"abcdefghbcdefghicdefghijdefghijkefghijklfghijklmghijklmnhijklmnoijklmnopjklmnopqklmnopqrlmnopqrsmnopqrstnopqrstu",
+            "cf5b16a778af8380036ce59e7b0492370b249b11e8f07a51afac45037afee9d1",
+        ),
+    ];
+    for (message, digest) in vectors {
+        let test = build_test!(&hash_memory_source(message));
+        test.expect_stack(&to_words(&hex_to_bytes(digest)));
+    }
+
+    // messages of all lengths around block boundaries are padded correctly
+    for len in [1, 54, 55, 56, 57, 63, 64, 65, 119, 120, 128, 200] {
+        let message = (0..len).map(|i| (i * 7 + 3) as u8).collect::<Vec<_>>();
+        let digest = Sha256::digest(&message);
+        let test = build_test!(&hash_memory_source(&message));
+        test.expect_stack(&to_words(&digest));
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns a program which writes the message into memory starting at address 100, and computes
+/// its digest via `hash_memory` procedure.
+///
+/// Unused bytes of the last SHA256 word and the words which follow it in the last memory words
+/// are filled with garbage, since they must be ignored by the procedure.
+fn hash_memory_source(message: &[u8]) -> String {
+    let mut bytes = message.to_vec();
+    bytes.resize((message.len() + 3) / 4 * 4, 0xff);
+    let mut elements = to_words(&bytes);
+    while elements.len() % 16 != 0 {
+        elements.push(u64::MAX >> 8);
+    }
+
+    let stores = elements
+        .chunks(4)
+        .enumerate()
+        .map(|(i, word)| {
+            format!(
+                "push.{}.{}.{}.{} popw.mem.{}",
+                word[0],
+                word[1],
+                word[2],
+                word[3],
+                100 + i
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    format!(
+        "use.std::crypto::hashes::sha256
+        begin
+            {stores}
+            push.{}
+            push.100
+            exec.sha256::hash_memory
+        end",
+        message.len()
+    )
+}
+
+/// Packs each four consecutive bytes into a SHA256 word (big endian byte order).
+fn to_words(bytes: &[u8]) -> Vec<u64> {
+    bytes
+        .chunks(4)
+        .map(|chunk| u32::from_be_bytes(chunk.try_into().unwrap()) as u64)
+        .collect()
+}
+
+fn hex_to_bytes(value: &str) -> Vec<u8> {
+    (0..value.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&value[i..i + 2], 16).unwrap())
+        .collect()
+}
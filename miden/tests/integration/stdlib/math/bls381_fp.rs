@@ -0,0 +1,205 @@
+use super::{build_test, TestError};
+use crate::helpers::execute_with_hints;
+use num_bigint::BigUint;
+use rand_utils::rand_vector;
+use vm_core::StarkField;
+
+/// The base field modulus of the BLS12-381 curve.
+const P: &str = "1a0111ea397fe69a4b1ba7b6434bacd764774b84f38512bf6730d2a0f6b0f6241eabfffeb153ffffb9feffffffffaaab";
+
+/// Addresses of the operands and of the result.
+const A: u64 = 0;
+const B: u64 = 100;
+const C: u64 = 200;
+
+// FIELD OPERATIONS
+// ================================================================================================
+
+#[test]
+fn add() {
+    let p = modulus();
+    let cases = [
+        (rand_element(), rand_element()),
+        (BigUint::from(0u32), rand_element()),
+        (&p - 1u32, BigUint::from(1u32)),
+        (&p - 1u32, &p - 1u32),
+    ];
+    for (a, b) in cases {
+        let expected = (&a + &b) % &p;
+        let source = binary_op_source("add", &a, &b, C);
+        expect_result(&source, &expected);
+    }
+}
+
+#[test]
+fn sub() {
+    let p = modulus();
+    let cases = [
+        (rand_element(), rand_element()),
+        (BigUint::from(0u32), BigUint::from(1u32)),
+        (&p - 1u32, &p - 1u32),
+    ];
+    for (a, b) in cases {
+        let expected = (&a + &p - &b) % &p;
+        let source = binary_op_source("sub", &a, &b, C);
+        expect_result(&source, &expected);
+    }
+}
+
+#[test]
+fn mul() {
+    let p = modulus();
+    let cases = [
+        (rand_element(), rand_element()),
+        (BigUint::from(0u32), rand_element()),
+        (&p - 1u32, &p - 1u32),
+    ];
+    for (a, b) in cases {
+        let expected = (&a * &b) % &p;
+        let source = binary_op_source("mul", &a, &b, C);
+        expect_result(&source, &expected);
+    }
+
+    // the result may be written in place of an operand
+    let a = rand_element();
+    let b = rand_element();
+    let source = binary_op_source("mul", &a, &b, A);
+    expect_result(&source, &((&a * &b) % &p));
+}
+
+#[test]
+fn reduce() {
+    let p = modulus();
+    let max = (BigUint::from(1u32) << 384) - 1u32;
+    for a in [rand_u384(), &p - 1u32, p.clone(), max] {
+        let expected = &a % &p;
+        let source = format!(
+            "use.std::math::fields::bls381_fp
+            begin
+                {}
+                push.{C}
+                push.{A}
+                exec.bls381_fp::reduce
+                {}
+            end",
+            store_limbs(&a, A),
+            load_limbs(C)
+        );
+        expect_result(&source, &expected);
+    }
+}
+
+#[test]
+fn mul_incorrect_hint() {
+    let p = modulus();
+    let a = rand_element();
+    let b = rand_element();
+    let source = binary_op_source("mul", &a, &b, C);
+
+    // without a host which answers the request, the advice tape is empty
+    let test = build_test!(&source);
+    test.expect_error(TestError::ExecutionError("EmptyAdviceTape"));
+
+    // a correct quotient and remainder are accepted regardless of where they come from
+    let product = &a * &b;
+    let (q, r) = (&product / &p, &product % &p);
+    let test = build_test!(&source, &[], &to_tape(&q, &r), vec![]);
+    let expected = to_limbs(&r);
+    test.expect_stack(&expected);
+
+    // a remainder which is not reduced is rejected even though it satisfies t = q * p + r
+    let tape = to_tape(&(&q - 1u32), &(&r + &p));
+    let test = build_test!(&source, &[], &tape, vec![]);
+    test.expect_error(TestError::ExecutionError("FailedAssertion"));
+
+    // a quotient and a remainder which do not satisfy t = q * p + r are rejected
+    let tape = to_tape(&q, &(&r + 1u32));
+    let test = build_test!(&source, &[], &tape, vec![]);
+    test.expect_error(TestError::ExecutionError("FailedAssertion"));
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+fn modulus() -> BigUint {
+    BigUint::parse_bytes(P.as_bytes(), 16).unwrap()
+}
+
+fn rand_u384() -> BigUint {
+    let limbs = rand_vector::<u64>(12)
+        .into_iter()
+        .map(|limb| limb as u32)
+        .collect::<Vec<_>>();
+    BigUint::from_slice(&limbs)
+}
+
+fn rand_element() -> BigUint {
+    rand_u384() % modulus()
+}
+
+/// Returns the 12 little-endian u32 limbs of the specified value.
+fn to_limbs(value: &BigUint) -> Vec<u64> {
+    let mut limbs = value
+        .to_u32_digits()
+        .into_iter()
+        .map(|limb| limb as u64)
+        .collect::<Vec<_>>();
+    limbs.resize(12, 0);
+    limbs
+}
+
+/// Returns the advice tape from which the quotient and the remainder are read.
+fn to_tape(q: &BigUint, r: &BigUint) -> Vec<u64> {
+    let mut tape = to_limbs(q);
+    tape.extend(to_limbs(r));
+    tape
+}
+
+/// Returns the code which stores the limbs of the value into the region at the specified address.
+fn store_limbs(value: &BigUint, addr: u64) -> String {
+    to_limbs(value)
+        .iter()
+        .enumerate()
+        .map(|(i, limb)| format!("push.{limb} pop.mem.{}", addr + i as u64))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Returns the code which pushes the limbs of the region at the specified address onto the stack
+/// so that limb 0 is at the top.
+fn load_limbs(addr: u64) -> String {
+    (0..12)
+        .rev()
+        .map(|i| format!("push.mem.{}", addr + i))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Returns the source of a program which applies the binary operation to a and b, writes the
+/// result into the region at address c, and pushes the result onto the stack.
+fn binary_op_source(op: &str, a: &BigUint, b: &BigUint, c: u64) -> String {
+    format!(
+        "use.std::math::fields::bls381_fp
+        begin
+            {}
+            {}
+            push.{c}
+            push.{B}
+            push.{A}
+            exec.bls381_fp::{op}
+            {}
+        end",
+        store_limbs(a, A),
+        store_limbs(b, B),
+        load_limbs(c)
+    )
+}
+
+/// Executes the program with a host which supplies hints, and checks that the limbs of the
+/// expected value are at the top of the stack.
+fn expect_result(source: &str, expected: &BigUint) {
+    let test = build_test!(source);
+    let trace = execute_with_hints(&test).unwrap();
+    let stack = trace.last_stack_state().map(|value| value.as_int());
+    assert_eq!(to_limbs(expected), stack[..12]);
+}
@@ -0,0 +1,110 @@
+use super::{build_test, Felt, TestError};
+use rand_utils::rand_value;
+use vm_core::{FieldElement, QuadFelt, StarkField};
+
+// ARITHMETIC OPERATIONS
+// ------------------------------------------------------------------------------------------------
+
+#[test]
+fn add_sub_neg() {
+    let a = rand_quad();
+    let b = rand_quad();
+
+    for (proc, c) in [("add", a + b), ("sub", a - b)] {
+        let source = format!("use.std::math::ext2 begin exec.ext2::{proc} end");
+        let test = build_test!(&source, &to_stack_inputs(&[a, b]));
+        test.expect_stack(&to_stack(c));
+    }
+
+    let test = build_test!(
+        "use.std::math::ext2 begin exec.ext2::neg end",
+        &to_stack_inputs(&[a])
+    );
+    test.expect_stack(&to_stack(-a));
+}
+
+#[test]
+fn mul() {
+    let a = rand_quad();
+    let b = rand_quad();
+
+    let test = build_test!(
+        "use.std::math::ext2 begin exec.ext2::mul end",
+        &to_stack_inputs(&[a, b])
+    );
+    test.expect_stack(&to_stack(a * b));
+
+    // x^2 = x - 2
+    let x = QuadFelt::new(Felt::ZERO, Felt::ONE);
+    let test = build_test!(
+        "use.std::math::ext2 begin exec.ext2::mul end",
+        &to_stack_inputs(&[x, x])
+    );
+    test.expect_stack(&to_stack(x - QuadFelt::from(2u8)));
+}
+
+#[test]
+fn inv_div() {
+    let a = rand_quad();
+    let b = rand_quad();
+
+    let test = build_test!(
+        "use.std::math::ext2 begin exec.ext2::inv end",
+        &to_stack_inputs(&[a])
+    );
+    test.expect_stack(&to_stack(a.inv()));
+
+    let test = build_test!(
+        "use.std::math::ext2 begin exec.ext2::div end",
+        &to_stack_inputs(&[a, b])
+    );
+    test.expect_stack(&to_stack(a / b));
+
+    // ZERO does not have an inverse
+    let test = build_test!(
+        "use.std::math::ext2 begin exec.ext2::inv end",
+        &to_stack_inputs(&[QuadFelt::ZERO])
+    );
+    test.expect_error(TestError::ExecutionError("DivideByZero"));
+}
+
+#[test]
+fn exp() {
+    let a = rand_quad();
+
+    for e in [0, 1, 2, 7, rand_value::<u32>(), u32::MAX] {
+        let source = format!("use.std::math::ext2 begin push.{e} exec.ext2::exp end");
+        let test = build_test!(&source, &to_stack_inputs(&[a]));
+        test.expect_stack(&to_stack(a.exp(e as u64)));
+    }
+
+    // the exponent must be a u32 value
+    let source = format!(
+        "use.std::math::ext2 begin push.{} exec.ext2::exp end",
+        1u64 << 32
+    );
+    let test = build_test!(&source, &to_stack_inputs(&[a]));
+    test.expect_error(TestError::ExecutionError("NotU32Value"));
+}
+
+// HELPER FUNCTIONS
+// ------------------------------------------------------------------------------------------------
+
+fn rand_quad() -> QuadFelt {
+    QuadFelt::new(rand_value(), rand_value())
+}
+
+/// Returns the element [a1, a0] as it is positioned on the stack, with a1 at the top.
+fn to_stack(a: QuadFelt) -> Vec<u64> {
+    let elements = QuadFelt::as_base_elements(&[a]).to_vec();
+    vec![elements[1].as_int(), elements[0].as_int()]
+}
+
+/// Returns stack inputs which place the specified elements onto the stack, with the last element
+/// at the top.
+fn to_stack_inputs(values: &[QuadFelt]) -> Vec<u64> {
+    values
+        .iter()
+        .flat_map(|&value| to_stack(value).into_iter().rev())
+        .collect()
+}
@@ -1,6 +1,9 @@
 use super::build_test;
 use crate::helpers::{Felt, TestError};
 
+mod bls381_fp;
+mod ext2;
+mod poly;
 mod secp256k1;
 mod u256_mod;
 mod u64_mod;
@@ -0,0 +1,221 @@
+use super::{build_test, Felt};
+use rand_utils::rand_vector;
+use vm_core::{FieldElement, StarkField};
+
+// EVALUATION
+// ------------------------------------------------------------------------------------------------
+
+#[test]
+fn eval() {
+    // the number of coefficients need not be a power of two
+    let coeffs = rand_vector::<Felt>(5);
+    let x = rand_vector::<Felt>(1)[0];
+
+    let source = format!(
+        "use.std::math::poly
+        begin
+            {}
+            push.{x}
+            push.5
+            push.10
+            exec.poly::eval
+        end",
+        store_values(10, &coeffs)
+    );
+
+    let test = build_test!(&source);
+    test.expect_stack(&[eval_poly(&coeffs, x).as_int()]);
+
+    // a polynomial without coefficients evaluates to zero
+    let source = "
+        use.std::math::poly
+        begin
+            push.3
+            push.0
+            push.10
+            exec.poly::eval
+        end";
+
+    let test = build_test!(source);
+    test.expect_stack(&[0]);
+}
+
+#[test]
+fn mul_pointwise() {
+    let a = rand_vector::<Felt>(6);
+    let b = rand_vector::<Felt>(6);
+    let c = a
+        .iter()
+        .zip(b.iter())
+        .map(|(&a, &b)| a * b)
+        .collect::<Vec<_>>();
+
+    let source = format!(
+        "use.std::math::poly
+        begin
+            {}
+            {}
+            push.6
+            push.20
+            push.10
+            push.0
+            exec.poly::mul_pointwise
+            {}
+        end",
+        store_values(0, &a),
+        store_values(10, &b),
+        load_values(20, 6)
+    );
+
+    let test = build_test!(&source);
+    test.expect_stack(&to_stack(&c));
+}
+
+// NUMBER THEORETIC TRANSFORMS
+// ------------------------------------------------------------------------------------------------
+
+#[test]
+fn ntt() {
+    for n in [4, 6] {
+        let coeffs = rand_vector::<Felt>(n);
+        let w = get_root_of_unity(n);
+
+        let source = format!(
+            "use.std::math::poly
+            begin
+                {}
+                push.{w}
+                push.{n}
+                push.10
+                push.0
+                exec.poly::ntt
+                {}
+            end",
+            store_values(0, &coeffs),
+            load_values(10, n)
+        );
+
+        let test = build_test!(&source);
+        test.expect_stack(&to_stack(&ntt_reference(&coeffs, w)));
+    }
+}
+
+#[test]
+fn intt() {
+    let n = 6;
+    let coeffs = rand_vector::<Felt>(n);
+    let w = get_root_of_unity(n);
+
+    let source = format!(
+        "use.std::math::poly
+        begin
+            {}
+            push.{w}
+            push.{n}
+            push.10
+            push.0
+            exec.poly::intt
+            {}
+        end",
+        store_values(0, &ntt_reference(&coeffs, w)),
+        load_values(10, n)
+    );
+
+    let test = build_test!(&source);
+    test.expect_stack(&to_stack(&coeffs));
+}
+
+#[test]
+fn mul_via_ntt() {
+    // the product of two polynomials of degree 2 and 3 has 6 coefficients, and thus can be
+    // computed via transforms over a subgroup of size 6
+    let n = 6;
+    let mut a = rand_vector::<Felt>(3);
+    let mut b = rand_vector::<Felt>(4);
+    let c = mul_polys(&a, &b);
+    a.resize(n, Felt::ZERO);
+    b.resize(n, Felt::ZERO);
+    let w = get_root_of_unity(n);
+
+    let source = format!(
+        "use.std::math::poly
+        begin
+            {}
+            {}
+            push.{w}.{n}.20.0
+            exec.poly::ntt
+            push.{w}.{n}.30.10
+            exec.poly::ntt
+            push.{n}.20.30.20
+            exec.poly::mul_pointwise
+            push.{w}.{n}.40.20
+            exec.poly::intt
+            {}
+        end",
+        store_values(0, &a),
+        store_values(10, &b),
+        load_values(40, n)
+    );
+
+    let test = build_test!(&source);
+    test.expect_stack(&to_stack(&c));
+}
+
+// REFERENCE IMPLEMENTATIONS
+// ------------------------------------------------------------------------------------------------
+
+/// Evaluates the polynomial with the specified coefficients at point x.
+fn eval_poly(coeffs: &[Felt], x: Felt) -> Felt {
+    coeffs.iter().rev().fold(Felt::ZERO, |acc, &c| acc * x + c)
+}
+
+/// Evaluates the polynomial with the specified coefficients at powers of w.
+fn ntt_reference(coeffs: &[Felt], w: Felt) -> Vec<Felt> {
+    (0..coeffs.len() as u64)
+        .map(|i| eval_poly(coeffs, w.exp(i)))
+        .collect()
+}
+
+/// Multiplies two polynomials via schoolbook multiplication.
+fn mul_polys(a: &[Felt], b: &[Felt]) -> Vec<Felt> {
+    let mut result = vec![Felt::ZERO; a.len() + b.len() - 1];
+    for (i, &a) in a.iter().enumerate() {
+        for (j, &b) in b.iter().enumerate() {
+            result[i + j] += a * b;
+        }
+    }
+    result
+}
+
+// HELPER FUNCTIONS
+// ------------------------------------------------------------------------------------------------
+
+/// Returns a primitive n-th root of unity; n must divide the order of the multiplicative group.
+fn get_root_of_unity(n: usize) -> Felt {
+    Felt::GENERATOR.exp((Felt::MODULUS - 1) / n as u64)
+}
+
+/// Returns a source which stores the specified values in consecutive words starting at address
+/// addr.
+fn store_values(addr: u64, values: &[Felt]) -> String {
+    values
+        .iter()
+        .enumerate()
+        .map(|(i, value)| format!("push.{value} pop.mem.{}", addr + i as u64))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Returns a source which pushes the values stored in n consecutive words starting at address
+/// addr onto the stack, such that the value at address addr ends up deepest in the stack.
+fn load_values(addr: u64, n: usize) -> String {
+    (0..n as u64)
+        .map(|i| format!("push.mem.{}", addr + i))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Converts the specified values into the stack expected after loading them via [load_values()].
+fn to_stack(values: &[Felt]) -> Vec<u64> {
+    values.iter().rev().map(|value| value.as_int()).collect()
+}
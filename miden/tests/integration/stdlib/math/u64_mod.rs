@@ -1,8 +1,10 @@
 use super::{build_test, TestError};
-use crate::helpers::U32_BOUND;
-use proptest::prelude::*;
+use crate::helpers::{execute_with_hints, U32_BOUND};
+use miden::ExecutionError;
+use miden_test::{InputRange, ProcTest};
 use rand_utils::rand_value;
 use std::cmp;
+use vm_core::StarkField;
 
 // ADDITION
 // ------------------------------------------------------------------------------------------------
@@ -370,7 +372,7 @@ fn overflowing_mul() {
 
 #[test]
 fn unchecked_lt() {
-    // test a few manual cases; randomized tests are done using proptest
+    // test a few manual cases; randomized tests are done using miden-test
     let source = "
         use.std::math::u64
         begin
@@ -416,7 +418,7 @@ fn unchecked_lte() {
 
 #[test]
 fn unchecked_gt() {
-    // test a few manual cases; randomized tests are done using proptest
+    // test a few manual cases; randomized tests are done using miden-test
     let source = "
         use.std::math::u64
         begin
@@ -462,7 +464,7 @@ fn unchecked_gte() {
 
 #[test]
 fn unchecked_min() {
-    // test a few manual cases; randomized tests are done using proptest
+    // test a few manual cases; randomized tests are done using miden-test
     let source = "
         use.std::math::u64
         begin
@@ -481,7 +483,7 @@ fn unchecked_min() {
 
 #[test]
 fn unchecked_max() {
-    // test a few manual cases; randomized tests are done using proptest
+    // test a few manual cases; randomized tests are done using miden-test
     let source = "
         use.std::math::u64
         begin
@@ -708,6 +710,47 @@ fn checked_divmod_fail() {
     }
 }
 
+#[test]
+fn divmod_hint() {
+    let a: u64 = rand_value();
+    let b: u64 = rand_value();
+    let q = a / b;
+    let r = a % b;
+
+    let source = "
+        use.std::math::u64
+        begin
+            exec.u64::divmod_hint
+        end";
+
+    let (a1, a0) = split_u64(a);
+    let (b1, b0) = split_u64(b);
+    let (q1, q0) = split_u64(q);
+    let (r1, r0) = split_u64(r);
+
+    // the quotient and the remainder are supplied by the host
+    let test = build_test!(source, &[a0, a1, b0, b1]);
+    let trace = execute_with_hints(&test).unwrap();
+    let stack = trace.last_stack_state().map(|value| value.as_int());
+    assert_eq!([r1, r0, q1, q0], stack[..4]);
+
+    // without a host which answers the request, the advice tape is empty
+    test.expect_error(TestError::ExecutionError("EmptyAdviceTape"));
+
+    // incorrect results supplied by the host are rejected; the tape is read as q_lo, q_hi, r_lo,
+    // r_hi
+    let (w1, w0) = split_u64(q ^ 1);
+    let test = build_test!(source, &[a0, a1, b0, b1], &[w0, w1, r0, r1], vec![]);
+    test.expect_error(TestError::ExecutionError("FailedAssertion"));
+
+    // dividing by zero is rejected by the host
+    let test = build_test!(source, &[a0, a1, 0, 0]);
+    assert!(matches!(
+        execute_with_hints(&test),
+        Err(ExecutionError::DivideByZero(_))
+    ));
+}
+
 // BITWISE OPERATIONS
 // ------------------------------------------------------------------------------------------------
 
@@ -1163,173 +1206,104 @@ fn unchecked_rotr() {
 // RANDOMIZED TESTS
 // ================================================================================================
 
-proptest! {
-    #[test]
-    fn unchecked_lt_proptest(a in any::<u64>(), b in any::<u64>()) {
-
-        let (a1, a0) = split_u64(a);
-        let (b1, b0) = split_u64(b);
-        let c = (a < b) as u64;
-
-        let source = "
-            use.std::math::u64
-            begin
-                exec.u64::unchecked_lt
-            end";
-
-        build_test!(source, &[a0, a1, b0, b1]).prop_expect_stack(&[c])?;
-    }
-
-    #[test]
-    fn unchecked_gt_proptest(a in any::<u64>(), b in any::<u64>()) {
-
-        let (a1, a0) = split_u64(a);
-        let (b1, b0) = split_u64(b);
-        let c = (a > b) as u64;
-
-        let source = "
-            use.std::math::u64
-            begin
-                exec.u64::unchecked_gt
-            end";
-
-        build_test!(source, &[a0, a1, b0, b1]).prop_expect_stack(&[c])?;
-    }
-
-    #[test]
-    fn unchecked_min_proptest(a in any::<u64>(), b in any::<u64>()) {
-
-        let (a1, a0) = split_u64(a);
-        let (b1, b0) = split_u64(b);
-        let c = cmp::min(a, b) as u64;
-        let (c1, c0) = split_u64(c);
-        let source = "
-            use.std::math::u64
-            begin
-                exec.u64::unchecked_min
-            end";
-
-        build_test!(source, &[a0, a1, b0, b1]).prop_expect_stack(&[c1, c0])?;
-    }
-
-    #[test]
-    fn unchecked_max_proptest(a in any::<u64>(), b in any::<u64>()) {
-
-        let (a1, a0) = split_u64(a);
-        let (b1, b0) = split_u64(b);
-        let c = cmp::max(a, b) as u64;
-        let (c1, c0) = split_u64(c);
-        let source = "
-            use.std::math::u64
-            begin
-                exec.u64::unchecked_max
-            end";
-
-        build_test!(source, &[a0, a1, b0, b1]).prop_expect_stack(&[c1, c0])?;
-    }
-
-    #[test]
-    fn unchecked_div_proptest(a in any::<u64>(), b in any::<u64>()) {
-
-        let c = a / b;
-
-        let (a1, a0) = split_u64(a);
-        let (b1, b0) = split_u64(b);
-        let (c1, c0) = split_u64(c);
-
-        let source = "
-            use.std::math::u64
-            begin
-                exec.u64::unchecked_div
-            end";
-
-        build_test!(source, &[a0, a1, b0, b1]).prop_expect_stack(&[c1, c0])?;
-    }
-
-    #[test]
-    fn unchecked_mod_proptest(a in any::<u64>(), b in any::<u64>()) {
-
-        let c = a % b;
-
-        let (a1, a0) = split_u64(a);
-        let (b1, b0) = split_u64(b);
-        let (c1, c0) = split_u64(c);
-
-        let source = "
-            use.std::math::u64
-            begin
-                exec.u64::unchecked_mod
-            end";
-
-        build_test!(source, &[a0, a1, b0, b1]).prop_expect_stack(&[c1, c0])?;
-    }
-
-    fn shl_proptest(a in any::<u64>(), b in 0_u32..64) {
-
-        let c = a.wrapping_shl(b);
-
-        let (a1, a0) = split_u64(a);
-        let (c1, c0) = split_u64(c);
-
-        let source = "
-        use.std::math::u64
-        begin
-            exec.u64::unchecked_shl
-        end";
-
-        build_test!(source, &[5, a0, a1, b as u64]).prop_expect_stack(&[c1, c0, 5])?;
-    }
-
-    #[test]
-    fn shr_proptest(a in any::<u64>(), b in 0_u32..64) {
-
-        let c = a.wrapping_shr(b);
-
-        let (a1, a0) = split_u64(a);
-        let (c1, c0) = split_u64(c);
-
-        let source = "
-        use.std::math::u64
-        begin
-            exec.u64::unchecked_shr
-        end";
-
-        build_test!(source, &[5, a0, a1, b as u64]).prop_expect_stack(&[c1, c0, 5])?;
-    }
-
-    #[test]
-    fn rotl_proptest(a in any::<u64>(), b in 0_u32..64) {
+#[test]
+fn unchecked_lt_proptest() {
+    u64_binary_test("unchecked_lt")
+        .check(|inputs, outputs| {
+            let (a, b) = join_u64_inputs(inputs);
+            outputs[0] == (a < b) as u64
+        })
+        .unwrap();
+}
 
-        let c = a.rotate_left(b);
+#[test]
+fn unchecked_gt_proptest() {
+    u64_binary_test("unchecked_gt")
+        .check(|inputs, outputs| {
+            let (a, b) = join_u64_inputs(inputs);
+            outputs[0] == (a > b) as u64
+        })
+        .unwrap();
+}
 
-        let (a1, a0) = split_u64(a);
-        let (c1, c0) = split_u64(c);
+#[test]
+fn unchecked_min_proptest() {
+    u64_binary_test("unchecked_min")
+        .check(|inputs, outputs| {
+            let (a, b) = join_u64_inputs(inputs);
+            outputs[..2] == split_u64_outputs(cmp::min(a, b))
+        })
+        .unwrap();
+}
 
-        let source = "
-        use.std::math::u64
-        begin
-            exec.u64::unchecked_rotl
-        end";
+#[test]
+fn unchecked_max_proptest() {
+    u64_binary_test("unchecked_max")
+        .check(|inputs, outputs| {
+            let (a, b) = join_u64_inputs(inputs);
+            outputs[..2] == split_u64_outputs(cmp::max(a, b))
+        })
+        .unwrap();
+}
 
-        build_test!(source, &[5, a0, a1, b as u64]).prop_expect_stack(&[c1, c0, 5])?;
-    }
+#[test]
+fn unchecked_div_proptest() {
+    u64_binary_test("unchecked_div")
+        .check_outcome(|inputs, outcome| match join_u64_inputs(inputs) {
+            (_, 0) => true,
+            (a, b) => matches!(outcome, Ok(outputs) if outputs[..2] == split_u64_outputs(a / b)),
+        })
+        .unwrap();
+}
 
-    #[test]
-    fn rotr_proptest(a in any::<u64>(), b in 0_u32..64) {
+#[test]
+fn unchecked_mod_proptest() {
+    u64_binary_test("unchecked_mod")
+        .check_outcome(|inputs, outcome| match join_u64_inputs(inputs) {
+            (_, 0) => true,
+            (a, b) => matches!(outcome, Ok(outputs) if outputs[..2] == split_u64_outputs(a % b)),
+        })
+        .unwrap();
+}
 
-        let c = a.rotate_right(b);
+#[test]
+fn shl_proptest() {
+    u64_shift_test("unchecked_shl")
+        .check(|inputs, outputs| {
+            let c = join_u64(inputs[1], inputs[2]).wrapping_shl(inputs[3] as u32);
+            outputs[..2] == split_u64_outputs(c) && outputs[2] == 5
+        })
+        .unwrap();
+}
 
-        let (a1, a0) = split_u64(a);
-        let (c1, c0) = split_u64(c);
+#[test]
+fn shr_proptest() {
+    u64_shift_test("unchecked_shr")
+        .check(|inputs, outputs| {
+            let c = join_u64(inputs[1], inputs[2]).wrapping_shr(inputs[3] as u32);
+            outputs[..2] == split_u64_outputs(c) && outputs[2] == 5
+        })
+        .unwrap();
+}
 
-        let source = "
-        use.std::math::u64
-        begin
-            exec.u64::unchecked_rotr
-        end";
+#[test]
+fn rotl_proptest() {
+    u64_shift_test("unchecked_rotl")
+        .check(|inputs, outputs| {
+            let c = join_u64(inputs[1], inputs[2]).rotate_left(inputs[3] as u32);
+            outputs[..2] == split_u64_outputs(c) && outputs[2] == 5
+        })
+        .unwrap();
+}
 
-        build_test!(source, &[5, a0, a1, b as u64]).prop_expect_stack(&[c1, c0, 5])?;
-    }
+#[test]
+fn rotr_proptest() {
+    u64_shift_test("unchecked_rotr")
+        .check(|inputs, outputs| {
+            let c = join_u64(inputs[1], inputs[2]).rotate_right(inputs[3] as u32);
+            outputs[..2] == split_u64_outputs(c) && outputs[2] == 5
+        })
+        .unwrap();
 }
 
 // HELPER FUNCTIONS
@@ -1340,6 +1314,44 @@ fn split_u64(value: u64) -> (u64, u64) {
     (value >> 32, value as u32 as u64)
 }
 
+/// Returns the limbs of the provided u64 value in the order in which they are placed on the stack
+/// by the u64 procedures, i.e., the high 32 bits first.
+fn split_u64_outputs(value: u64) -> [u64; 2] {
+    let (hi, lo) = split_u64(value);
+    [hi, lo]
+}
+
+/// Returns the u64 value with the provided low and high 32 bits.
+fn join_u64(lo: u64, hi: u64) -> u64 {
+    (hi << 32) | lo
+}
+
+/// Returns the u64 values a and b from stack inputs [a_lo, a_hi, b_lo, b_hi].
+fn join_u64_inputs(inputs: &[u64]) -> (u64, u64) {
+    (
+        join_u64(inputs[0], inputs[1]),
+        join_u64(inputs[2], inputs[3]),
+    )
+}
+
+/// Returns a property test of the specified u64 procedure which takes two u64 values a and b
+/// as stack inputs [a_lo, a_hi, b_lo, b_hi].
+fn u64_binary_test(proc_name: &str) -> ProcTest {
+    ProcTest::from_stdlib(&format!("std::math::u64::{}", proc_name))
+        .unwrap()
+        .with_inputs([InputRange::u32(); 4])
+}
+
+/// Returns a property test of the specified u64 shift procedure which takes a u64 value a and a
+/// shift b as stack inputs [a_lo, a_hi, b], with a fixed value 5 below them to check that the
+/// rest of the stack is preserved.
+fn u64_shift_test(proc_name: &str) -> ProcTest {
+    ProcTest::from_stdlib(&format!("std::math::u64::{}", proc_name))
+        .unwrap()
+        .with_inputs([InputRange::value(5), InputRange::u32(), InputRange::u32()])
+        .with_input(InputRange::new(0, 63))
+}
+
 fn split_u128(value: u128) -> (u64, u64, u64, u64) {
     (
         (value >> 96) as u64,
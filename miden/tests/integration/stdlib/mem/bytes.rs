@@ -0,0 +1,147 @@
+use super::{build_test, TestError};
+
+#[test]
+fn load_store_byte() {
+    let source = "
+        use.std::mem::bytes
+        begin
+            push.0xab
+            push.5
+            exec.bytes::store_byte
+            push.0xcd
+            push.6
+            exec.bytes::store_byte
+            push.0x12
+            push.17
+            exec.bytes::store_byte
+
+            push.5
+            exec.bytes::load_byte
+            push.6
+            exec.bytes::load_byte
+            push.17
+            exec.bytes::load_byte
+            push.4
+            exec.bytes::load_byte
+        end";
+
+    let test = build_test!(source);
+    test.expect_stack_and_memory(&[0, 0x12, 0xcd, 0xab], 0, &[0, 0xcdab00, 0, 0]);
+
+    // overwriting a byte should leave its neighbors unchanged
+    let source = "
+        use.std::mem::bytes
+        begin
+            push.0xab
+            push.5
+            exec.bytes::store_byte
+            push.0xcd
+            push.6
+            exec.bytes::store_byte
+            push.0x01
+            push.5
+            exec.bytes::store_byte
+
+            push.5
+            exec.bytes::load_byte
+            push.6
+            exec.bytes::load_byte
+        end";
+
+    let test = build_test!(source);
+    test.expect_stack_and_memory(&[0xcd, 0x01], 0, &[0, 0xcd0100, 0, 0]);
+
+    // storing a value which does not fit into a byte should fail
+    let source = "
+        use.std::mem::bytes
+        begin
+            push.256
+            push.5
+            exec.bytes::store_byte
+        end";
+
+    let test = build_test!(source);
+    test.expect_error(TestError::ExecutionError("FailedAssertion"));
+}
+
+#[test]
+fn copy() {
+    let source = "
+        use.std::mem::bytes
+        begin
+            push.1
+            push.14
+            exec.bytes::store_byte
+            push.2
+            push.15
+            exec.bytes::store_byte
+            push.3
+            push.16
+            exec.bytes::store_byte
+
+            push.3
+            push.101
+            push.14
+            exec.bytes::copy
+
+            push.100
+            exec.bytes::load_byte
+            push.101
+            exec.bytes::load_byte
+            push.102
+            exec.bytes::load_byte
+            push.103
+            exec.bytes::load_byte
+            push.104
+            exec.bytes::load_byte
+        end";
+
+    let test = build_test!(source);
+    test.expect_stack(&[0, 3, 2, 1, 0]);
+}
+
+#[test]
+fn load_from_adv() {
+    let source = "
+        use.std::mem::bytes
+        begin
+            push.6
+            push.32
+            exec.bytes::load_from_adv
+
+            push.32
+            exec.bytes::load_byte
+            push.35
+            exec.bytes::load_byte
+            push.37
+            exec.bytes::load_byte
+        end";
+
+    let advice_tape = [0x04030201, 0x08070605, 0, 0];
+    let test = build_test!(source, &[], &advice_tape, vec![]);
+    test.expect_stack_and_memory(&[6, 4, 1], 2, &[0x04030201, 0x08070605, 0, 0]);
+
+    // byte strings must start at word boundaries
+    let source = "
+        use.std::mem::bytes
+        begin
+            push.6
+            push.33
+            exec.bytes::load_from_adv
+        end";
+
+    let test = build_test!(source, &[], &advice_tape, vec![]);
+    test.expect_error(TestError::ExecutionError("FailedAssertion"));
+    // elements read from the advice tape must be valid u32 values
+    let source = "
+        use.std::mem::bytes
+        begin
+            push.6
+            push.32
+            exec.bytes::load_from_adv
+        end";
+
+    let advice_tape = [0x04030201, 1 << 32, 0, 0];
+    let test = build_test!(source, &[], &advice_tape, vec![]);
+    test.expect_error(TestError::ExecutionError("NotU32Value"));
+}
@@ -0,0 +1,127 @@
+use super::build_test;
+use crate::helpers::TestError;
+
+mod bytes;
+
+#[test]
+fn alloc() {
+    let source = "
+        use.std::mem
+        begin
+            push.3
+            exec.mem::alloc
+            push.0
+            exec.mem::alloc
+            push.5
+            exec.mem::alloc
+        end";
+
+    let test = build_test!(source);
+    test.expect_stack(&[2147483651, 2147483651, 2147483648]);
+
+    // allocations which would overflow the heap should fail
+    let source = "
+        use.std::mem
+        begin
+            push.2147483647
+            exec.mem::alloc
+        end";
+
+    let test = build_test!(source);
+    test.expect_error(TestError::ExecutionError("FailedAssertion"));
+}
+
+#[test]
+fn memcopy() {
+    let source = "
+        use.std::mem
+        begin
+            push.1.2.3.4
+            popw.mem.100
+            push.5.6.7.8
+            popw.mem.101
+            push.9.10.11.12
+            popw.mem.102
+
+            push.3
+            push.200
+            push.100
+            exec.mem::memcopy
+
+            pushw.mem.200
+            pushw.mem.201
+            pushw.mem.202
+        end";
+
+    let test = build_test!(source);
+    test.expect_stack_and_memory(
+        &[12, 11, 10, 9, 8, 7, 6, 5, 4, 3, 2, 1],
+        202,
+        &[9, 10, 11, 12],
+    );
+
+    // copying an empty region should leave the memory and the stack untouched
+    let source = "
+        use.std::mem
+        begin
+            push.1.2.3.4
+            popw.mem.100
+
+            push.0
+            push.200
+            push.100
+            exec.mem::memcopy
+
+            pushw.mem.200
+        end";
+
+    let test = build_test!(source);
+    test.expect_stack(&[0, 0, 0, 0]);
+}
+
+#[test]
+fn memset() {
+    let source = "
+        use.std::mem
+        begin
+            push.1.2.3.4
+            push.3
+            push.100
+            exec.mem::memset
+
+            pushw.mem.100
+            pushw.mem.102
+            pushw.mem.103
+        end";
+
+    let test = build_test!(source);
+    test.expect_stack(&[0, 0, 0, 0, 4, 3, 2, 1, 4, 3, 2, 1]);
+}
+
+#[test]
+fn memcmp() {
+    let setup = "
+        push.1.2.3.4
+        popw.mem.100
+        push.5.6.7.8
+        popw.mem.101
+        push.1.2.3.4
+        popw.mem.200
+        push.5.6.7.9
+        popw.mem.201";
+
+    // --- equal regions --------------------------------------------------------------------------
+    let source =
+        format!("use.std::mem begin {setup} push.1 push.200 push.100 exec.mem::memcmp end");
+    build_test!(&source).expect_stack(&[1]);
+
+    // --- regions which differ in the last word --------------------------------------------------
+    let source =
+        format!("use.std::mem begin {setup} push.2 push.200 push.100 exec.mem::memcmp end");
+    build_test!(&source).expect_stack(&[0]);
+
+    // --- empty regions are always equal ---------------------------------------------------------
+    let source =
+        format!("use.std::mem begin {setup} push.0 push.201 push.101 exec.mem::memcmp end");
+    build_test!(&source).expect_stack(&[1]);
+}
@@ -1,5 +1,9 @@
 use crate::build_test;
 
+mod advice;
+mod collections;
 mod crypto;
 mod math;
+mod mem;
+mod returndata;
 mod sys;
@@ -0,0 +1,84 @@
+use super::build_test;
+use crate::helpers::{Felt, TestError};
+use miden::{ReturnData, ReturnDataError};
+use processor::Process;
+
+#[test]
+fn write_and_commit() {
+    let source = "
+        use.std::returndata
+        begin
+            push.1.2.3.4
+            exec.returndata::write_word
+            push.5
+            exec.returndata::write_felt
+            push.4294967295
+            exec.returndata::write_u32
+            push.1.2
+            exec.returndata::write_u64
+            exec.returndata::commit
+        end";
+
+    let test = build_test!(source);
+    let program = test.compile();
+    let mut process = Process::new(test.inputs.clone());
+    process.execute(&program).unwrap();
+
+    // read the returndata region and check it against the commitment on the stack
+    let returndata = ReturnData::read(|addr| process.get_memory_value(addr)).unwrap();
+    assert_eq!(4, returndata.len());
+    let stack = test.get_last_stack_state();
+    returndata.verify(&stack).unwrap();
+
+    // decode the values
+    let mut reader = returndata.reader();
+    let expected_word = [Felt::new(1), Felt::new(2), Felt::new(3), Felt::new(4)];
+    assert_eq!(expected_word, reader.read_word().unwrap());
+    assert_eq!(Felt::new(5), reader.read_felt().unwrap());
+    assert_eq!(u32::MAX, reader.read_u32().unwrap());
+    assert_eq!((2 << 32) | 1, reader.read_u64().unwrap());
+    assert_eq!(0, reader.remaining());
+    assert_eq!(Err(ReturnDataError::UnexpectedEnd(4)), reader.read_felt());
+
+    // a commitment to different data should be rejected
+    let mut tampered_stack = stack;
+    tampered_stack[0] += Felt::new(1);
+    assert_eq!(
+        Err(ReturnDataError::CommitmentMismatch),
+        returndata.verify(&tampered_stack)
+    );
+}
+
+#[test]
+fn commit_empty() {
+    let source = "
+        use.std::returndata
+        begin
+            exec.returndata::commit
+        end";
+
+    let test = build_test!(source);
+    test.expect_stack(&[0, 0, 0, 0, 0]);
+    ReturnData::default()
+        .verify(&test.get_last_stack_state())
+        .unwrap();
+}
+
+#[test]
+fn write_invalid_values() {
+    let source = "
+        use.std::returndata
+        begin
+            push.4294967296
+            exec.returndata::write_u32
+        end";
+    build_test!(source).expect_error(TestError::ExecutionError("NotU32Value"));
+
+    let source = "
+        use.std::returndata
+        begin
+            push.4294967296.1
+            exec.returndata::write_u64
+        end";
+    build_test!(source).expect_error(TestError::ExecutionError("NotU32Value"));
+}
@@ -13,6 +13,35 @@ fn finalize_stack() {
     test.expect_stack(&[0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 2, 3, 4]);
 }
 
+#[test]
+fn spill_and_restore() {
+    let source = "
+        use.std::sys
+        begin
+            push.3 push.100 exec.sys::spill
+            push.mem.100 push.mem.101 push.mem.102
+        end";
+    let test = build_test!(source, &[6, 5, 4, 3, 2, 1]);
+    test.expect_stack(&[3, 2, 1, 4, 5, 6]);
+
+    // restore puts the elements back in the order in which they were spilled
+    let source = "
+        use.std::sys
+        begin
+            push.3 push.100 exec.sys::spill
+            push.0
+            push.3 push.100 exec.sys::restore
+        end";
+    let test = build_test!(source, &[6, 5, 4, 3, 2, 1]);
+    test.expect_stack(&[1, 2, 3, 0, 4, 5, 6]);
+
+    // spilling and restoring zero elements leaves the stack unchanged
+    let source =
+        "use.std::sys begin push.0 push.100 exec.sys::spill push.0 push.100 exec.sys::restore end";
+    let test = build_test!(source, &[2, 1]);
+    test.expect_stack(&[1, 2]);
+}
+
 proptest! {
     #[test]
     fn finalize_stack_proptest(test_values in prop::collection::vec(any::<u64>(), MIN_STACK_DEPTH), n in 1_usize..100) {
@@ -0,0 +1,737 @@
+use super::{
+    AdviceProvider, CodeBlock, Digest, ExecutionError, Felt, MemAdviceProvider, ProgramInputs, Vec,
+    VmState, Word,
+};
+use vm_core::{errors::InputError, AdviceSet, StarkField};
+
+#[cfg(not(feature = "std"))]
+use alloc::boxed::Box;
+
+// CHAINED PROVIDER
+// ================================================================================================
+
+/// An advice provider which combines advice of several providers.
+///
+/// The advice tape of a chained provider is a concatenation of the advice tapes of the underlying
+/// providers: elements are read from the tape of the first provider until it is exhausted, then
+/// from the tape of the second provider etc. Elements are always written to the tape of the first
+/// provider. Advice sets and advice map entries of a chained provider are the union of those of
+/// the underlying providers; requests for an advice set or an entry are served by the first
+/// provider which has it. Debug reports and events are handled by the first provider.
+pub struct ChainedProvider {
+    providers: Vec<Box<dyn AdviceProvider>>,
+}
+
+impl ChainedProvider {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Returns a new provider chaining the specified providers in the specified order.
+    ///
+    /// # Panics
+    /// Panics if the list of providers is empty.
+    pub fn new(providers: Vec<Box<dyn AdviceProvider>>) -> Self {
+        assert!(!providers.is_empty(), "no advice providers to chain");
+        Self { providers }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the underlying providers in the order in which they are chained.
+    pub fn providers(&self) -> &[Box<dyn AdviceProvider>] {
+        &self.providers
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Sends the specified request to the underlying providers one by one, and returns the result
+    /// of the first provider which has the requested advice. If none of the providers has the
+    /// requested advice, the error of the last provider is returned.
+    fn request<T>(
+        &mut self,
+        mut request: impl FnMut(&mut dyn AdviceProvider) -> Result<T, ExecutionError>,
+    ) -> Result<T, ExecutionError> {
+        let (last, providers) = self.providers.split_last_mut().expect("no providers");
+        for provider in providers {
+            match request(provider.as_mut()) {
+                Err(error) if is_missing_advice(&error) => continue,
+                result => return result,
+            }
+        }
+        request(last.as_mut())
+    }
+}
+
+impl AdviceProvider for ChainedProvider {
+    fn read_tape(&mut self) -> Result<Felt, ExecutionError> {
+        self.request(|provider| provider.read_tape())
+    }
+
+    fn write_tape(&mut self, value: Felt) {
+        self.providers[0].write_tape(value)
+    }
+
+    fn has_advice_set(&self, root: Word) -> bool {
+        self.providers
+            .iter()
+            .any(|provider| provider.has_advice_set(root))
+    }
+
+    fn get_tree_node(
+        &mut self,
+        root: Word,
+        depth: Felt,
+        index: Felt,
+    ) -> Result<Word, ExecutionError> {
+        self.request(|provider| provider.get_tree_node(root, depth, index))
+    }
+
+    fn get_merkle_path(
+        &mut self,
+        root: Word,
+        depth: Felt,
+        index: Felt,
+    ) -> Result<Vec<Word>, ExecutionError> {
+        self.request(|provider| provider.get_merkle_path(root, depth, index))
+    }
+
+    fn update_merkle_leaf(
+        &mut self,
+        root: Word,
+        index: Felt,
+        leaf_value: Word,
+        update_in_copy: bool,
+    ) -> Result<Vec<Word>, ExecutionError> {
+        self.request(|provider| {
+            provider.update_merkle_leaf(root, index, leaf_value, update_in_copy)
+        })
+    }
+
+    fn merge_merkle_trees(&mut self, left: Word, right: Word) -> Result<Word, ExecutionError> {
+        self.request(|provider| provider.merge_merkle_trees(left, right))
+    }
+
+    fn get_mapped_values(&mut self, key: Word) -> Result<Vec<Felt>, ExecutionError> {
+        self.request(|provider| provider.get_mapped_values(key))
+    }
+
+    fn get_code_block(&mut self, root: Digest) -> Option<CodeBlock> {
+        self.providers
+            .iter_mut()
+            .find_map(|provider| provider.get_code_block(root))
+    }
+
+    fn on_debug(&mut self, report: &str) {
+        if let Some(provider) = self.providers.first_mut() {
+            provider.on_debug(report);
+        }
+    }
+
+    fn on_event(&mut self, event_id: u32, state: &VmState) -> Result<(), ExecutionError> {
+        match self.providers.first_mut() {
+            Some(provider) => provider.on_event(event_id, state),
+            None => Ok(()),
+        }
+    }
+
+    fn advance_clock(&mut self) {
+        self.providers
+            .iter_mut()
+            .for_each(|provider| provider.advance_clock());
+    }
+}
+
+// FALLBACK PROVIDER
+// ================================================================================================
+
+/// An advice provider which serves advice from a primary provider, and falls back onto a
+/// secondary provider when the primary provider does not have the requested advice.
+///
+/// Only requests for which the primary provider has no advice (i.e., reads from an empty advice
+/// tape, and requests for unknown advice sets, advice map entries, or code blocks) are sent to the fallback provider.
+/// All other errors of the primary provider are returned as is. Elements are always written to
+/// the tape of the primary provider, and debug reports and events are handled by the primary
+/// provider.
+///
+/// The provider counts the requests which were served by the fallback provider; e.g., for a
+/// database-backed provider falling back onto default values, this is the number of misses.
+pub struct FallbackProvider<P, F> {
+    primary: P,
+    fallback: F,
+    num_fallbacks: usize,
+}
+
+impl<P: AdviceProvider, F: AdviceProvider> FallbackProvider<P, F> {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Returns a new provider which falls back onto the `fallback` provider when the `primary`
+    /// provider does not have the requested advice.
+    pub fn new(primary: P, fallback: F) -> Self {
+        Self {
+            primary,
+            fallback,
+            num_fallbacks: 0,
+        }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the primary provider.
+    pub fn primary(&self) -> &P {
+        &self.primary
+    }
+
+    /// Returns the fallback provider.
+    pub fn fallback(&self) -> &F {
+        &self.fallback
+    }
+
+    /// Returns the number of requests which were served by the fallback provider.
+    pub fn num_fallbacks(&self) -> usize {
+        self.num_fallbacks
+    }
+
+    /// Consumes this provider and returns the primary and the fallback providers.
+    pub fn into_parts(self) -> (P, F) {
+        (self.primary, self.fallback)
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Sends the specified request to the primary provider, and to the fallback provider if the
+    /// primary provider does not have the requested advice.
+    fn request<T>(
+        &mut self,
+        mut request: impl FnMut(&mut dyn AdviceProvider) -> Result<T, ExecutionError>,
+    ) -> Result<T, ExecutionError> {
+        match request(&mut self.primary) {
+            Err(error) if is_missing_advice(&error) => {
+                self.num_fallbacks += 1;
+                request(&mut self.fallback)
+            }
+            result => result,
+        }
+    }
+}
+
+impl<P: AdviceProvider, F: AdviceProvider> AdviceProvider for FallbackProvider<P, F> {
+    fn read_tape(&mut self) -> Result<Felt, ExecutionError> {
+        self.request(|provider| provider.read_tape())
+    }
+
+    fn write_tape(&mut self, value: Felt) {
+        self.primary.write_tape(value)
+    }
+
+    fn has_advice_set(&self, root: Word) -> bool {
+        self.primary.has_advice_set(root) || self.fallback.has_advice_set(root)
+    }
+
+    fn get_tree_node(
+        &mut self,
+        root: Word,
+        depth: Felt,
+        index: Felt,
+    ) -> Result<Word, ExecutionError> {
+        self.request(|provider| provider.get_tree_node(root, depth, index))
+    }
+
+    fn get_merkle_path(
+        &mut self,
+        root: Word,
+        depth: Felt,
+        index: Felt,
+    ) -> Result<Vec<Word>, ExecutionError> {
+        self.request(|provider| provider.get_merkle_path(root, depth, index))
+    }
+
+    fn update_merkle_leaf(
+        &mut self,
+        root: Word,
+        index: Felt,
+        leaf_value: Word,
+        update_in_copy: bool,
+    ) -> Result<Vec<Word>, ExecutionError> {
+        self.request(|provider| {
+            provider.update_merkle_leaf(root, index, leaf_value, update_in_copy)
+        })
+    }
+
+    fn merge_merkle_trees(&mut self, left: Word, right: Word) -> Result<Word, ExecutionError> {
+        self.request(|provider| provider.merge_merkle_trees(left, right))
+    }
+
+    fn get_mapped_values(&mut self, key: Word) -> Result<Vec<Felt>, ExecutionError> {
+        self.request(|provider| provider.get_mapped_values(key))
+    }
+
+    fn get_code_block(&mut self, root: Digest) -> Option<CodeBlock> {
+        self.primary.get_code_block(root).or_else(|| {
+            self.num_fallbacks += 1;
+            self.fallback.get_code_block(root)
+        })
+    }
+
+    fn on_debug(&mut self, report: &str) {
+        self.primary.on_debug(report);
+    }
+
+    fn on_event(&mut self, event_id: u32, state: &VmState) -> Result<(), ExecutionError> {
+        self.primary.on_event(event_id, state)
+    }
+
+    fn advance_clock(&mut self) {
+        self.primary.advance_clock();
+        self.fallback.advance_clock();
+    }
+}
+
+// RESOLVING PROVIDER
+// ================================================================================================
+
+/// Advice supplied by the resolvers of a [ResolvingProvider].
+#[derive(Clone, Debug)]
+pub enum ResolvedAdvice {
+    /// An advice set supplied by the advice set resolver.
+    AdviceSet(AdviceSet),
+    /// Values of the advice map entry with the specified key supplied by the advice map resolver.
+    MapEntry { key: Word, values: Vec<Felt> },
+}
+
+/// An advice provider which gives the host a chance to supply advice sets and advice map entries
+/// unknown to the underlying provider before the request fails.
+///
+/// When the underlying provider does not have an advice set with the requested root, the
+/// provider invokes the advice set resolver with the root (e.g., to fetch the set from a database
+/// or to compute it on demand). If the resolver returns an advice set with the requested root,
+/// the set is retained by this provider and the request is served from it; otherwise, the
+/// original error is returned. Similarly, when the underlying provider does not have an advice
+/// map entry with the requested key, the provider invokes the advice map resolver with the key.
+/// Each root and each key is resolved at most once. Merging advice sets unknown to the underlying
+/// provider resolves both sets, and the merged set is retained together with the resolved sets.
+///
+/// Resolved advice is logged together with the clock cycles at which it was resolved. Since the
+/// prover re-executes the program against [ProgramInputs], the logged advice should be added to
+/// the inputs to prove the execution (see [Self::resolved_inputs()]).
+pub struct ResolvingProvider<P, R, M> {
+    provider: P,
+    set_resolver: R,
+    map_resolver: M,
+    resolved_advice: MemAdviceProvider,
+    step: usize,
+    resolved: Vec<(usize, ResolvedAdvice)>,
+}
+
+impl<P, R, M> ResolvingProvider<P, R, M>
+where
+    P: AdviceProvider,
+    R: FnMut(Word) -> Option<AdviceSet>,
+    M: FnMut(Word) -> Option<Vec<Felt>>,
+{
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Returns a new provider which invokes the `set_resolver` for advice sets unknown to the
+    /// specified provider, and the `map_resolver` for advice map keys unknown to it.
+    pub fn new(provider: P, set_resolver: R, map_resolver: M) -> Self {
+        Self {
+            provider,
+            set_resolver,
+            map_resolver,
+            resolved_advice: MemAdviceProvider::new(ProgramInputs::none()),
+            step: 0,
+            resolved: Vec::new(),
+        }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the underlying provider.
+    pub fn inner(&self) -> &P {
+        &self.provider
+    }
+
+    /// Returns the advice supplied by the resolvers together with the clock cycles at which it
+    /// was resolved, in the order in which it was resolved.
+    pub fn resolved(&self) -> &[(usize, ResolvedAdvice)] {
+        &self.resolved
+    }
+
+    /// Returns the specified inputs extended with the advice sets and the advice map entries
+    /// supplied by the resolvers.
+    ///
+    /// Executing the program against the returned inputs does not require the resolvers, and
+    /// thus the returned inputs can be used to prove the execution.
+    ///
+    /// # Errors
+    /// Returns an error if any of the resolved advice sets or advice map entries is already
+    /// present in the inputs.
+    pub fn resolved_inputs(&self, inputs: &ProgramInputs) -> Result<ProgramInputs, InputError> {
+        let mut sets = Vec::new();
+        let mut entries = Vec::new();
+        for (_, advice) in self.resolved.iter() {
+            match advice {
+                ResolvedAdvice::AdviceSet(set) => sets.push(set.clone()),
+                ResolvedAdvice::MapEntry { key, values } => {
+                    entries.push((*key, values.iter().map(|value| value.as_int()).collect()))
+                }
+            }
+        }
+        inputs
+            .clone()
+            .with_advice_sets(sets)?
+            .with_advice_map(entries)
+    }
+
+    /// Consumes this provider and returns the underlying provider and the resolved advice.
+    pub fn into_parts(self) -> (P, Vec<(usize, ResolvedAdvice)>) {
+        (self.provider, self.resolved)
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Sends the specified request for the advice sets with the specified roots to the underlying
+    /// provider, and serves it from the resolved advice sets if the underlying provider does not
+    /// have the sets.
+    ///
+    /// Roots unknown to the underlying provider are resolved before the request is served from the
+    /// resolved advice sets; a request involving both sets known to the underlying provider and
+    /// resolved sets (e.g., merging such sets) thus fails.
+    fn request<T>(
+        &mut self,
+        roots: &[Word],
+        mut request: impl FnMut(&mut dyn AdviceProvider) -> Result<T, ExecutionError>,
+    ) -> Result<T, ExecutionError> {
+        match request(&mut self.provider) {
+            Err(error @ ExecutionError::AdviceSetNotFound(_)) => {
+                for &root in roots {
+                    if self.provider.has_advice_set(root)
+                        || self.resolved_advice.has_advice_set(root)
+                    {
+                        continue;
+                    }
+                    match (self.set_resolver)(root) {
+                        Some(set) if set.root() == root => {
+                            self.resolved
+                                .push((self.step, ResolvedAdvice::AdviceSet(set.clone())));
+                            self.resolved_advice.insert_advice_set(set);
+                        }
+                        _ => return Err(error),
+                    }
+                }
+                request(&mut self.resolved_advice)
+            }
+            result => result,
+        }
+    }
+}
+
+impl<P, R, M> AdviceProvider for ResolvingProvider<P, R, M>
+where
+    P: AdviceProvider,
+    R: FnMut(Word) -> Option<AdviceSet>,
+    M: FnMut(Word) -> Option<Vec<Felt>>,
+{
+    fn read_tape(&mut self) -> Result<Felt, ExecutionError> {
+        self.provider.read_tape()
+    }
+
+    fn write_tape(&mut self, value: Felt) {
+        self.provider.write_tape(value)
+    }
+
+    fn has_advice_set(&self, root: Word) -> bool {
+        self.provider.has_advice_set(root) || self.resolved_advice.has_advice_set(root)
+    }
+
+    fn get_tree_node(
+        &mut self,
+        root: Word,
+        depth: Felt,
+        index: Felt,
+    ) -> Result<Word, ExecutionError> {
+        self.request(&[root], |provider| {
+            provider.get_tree_node(root, depth, index)
+        })
+    }
+
+    fn get_merkle_path(
+        &mut self,
+        root: Word,
+        depth: Felt,
+        index: Felt,
+    ) -> Result<Vec<Word>, ExecutionError> {
+        self.request(&[root], |provider| {
+            provider.get_merkle_path(root, depth, index)
+        })
+    }
+
+    fn update_merkle_leaf(
+        &mut self,
+        root: Word,
+        index: Felt,
+        leaf_value: Word,
+        update_in_copy: bool,
+    ) -> Result<Vec<Word>, ExecutionError> {
+        self.request(&[root], |provider| {
+            provider.update_merkle_leaf(root, index, leaf_value, update_in_copy)
+        })
+    }
+
+    fn merge_merkle_trees(&mut self, left: Word, right: Word) -> Result<Word, ExecutionError> {
+        // a set merged from resolved sets is retained together with them; it is not logged since
+        // it is merged again when the execution is replayed
+        self.request(&[left, right], |provider| {
+            provider.merge_merkle_trees(left, right)
+        })
+    }
+
+    fn get_mapped_values(&mut self, key: Word) -> Result<Vec<Felt>, ExecutionError> {
+        match self.provider.get_mapped_values(key) {
+            Err(error @ ExecutionError::AdviceMapKeyNotFound(_)) => {
+                if let Ok(values) = self.resolved_advice.get_mapped_values(key) {
+                    return Ok(values);
+                }
+                let values = (self.map_resolver)(key).ok_or(error)?;
+                self.resolved.push((
+                    self.step,
+                    ResolvedAdvice::MapEntry {
+                        key,
+                        values: values.clone(),
+                    },
+                ));
+                self.resolved_advice.insert_map_entry(key, values.clone());
+                Ok(values)
+            }
+            result => result,
+        }
+    }
+
+    fn get_code_block(&mut self, root: Digest) -> Option<CodeBlock> {
+        self.provider.get_code_block(root)
+    }
+
+    fn on_debug(&mut self, report: &str) {
+        self.provider.on_debug(report);
+    }
+
+    fn on_event(&mut self, event_id: u32, state: &VmState) -> Result<(), ExecutionError> {
+        self.provider.on_event(event_id, state)
+    }
+
+    fn advance_clock(&mut self) {
+        self.step += 1;
+        self.provider.advance_clock();
+    }
+}
+
+// RECORDING PROVIDER
+// ================================================================================================
+
+/// Advice served to the processor by an advice provider.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AdviceRecord {
+    /// An element read from the advice tape.
+    TapeRead(Felt),
+    /// An element written to the advice tape.
+    TapeWrite(Felt),
+    /// A node at the specified depth and index in the Merkle tree with the specified root.
+    TreeNode {
+        root: Word,
+        depth: Felt,
+        index: Felt,
+        node: Word,
+    },
+    /// A path to the node at the specified depth and index in the Merkle tree with the specified
+    /// root.
+    MerklePath {
+        root: Word,
+        depth: Felt,
+        index: Felt,
+        path: Vec<Word>,
+    },
+    /// A path to the leaf at the specified index in the Merkle tree with the specified root which
+    /// was updated to the specified value.
+    MerkleLeafUpdate {
+        root: Word,
+        index: Felt,
+        leaf_value: Word,
+        path: Vec<Word>,
+    },
+    /// Merkle trees with the specified roots which were merged into a tree with the specified
+    /// root.
+    MerkleMerge { left: Word, right: Word, root: Word },
+    /// Values of the advice map entry with the specified key.
+    MapValues { key: Word, values: Vec<Felt> },
+    /// A code block with the specified hash.
+    CodeBlock(Digest),
+}
+
+/// An advice provider which records all advice served by the underlying provider.
+///
+/// Every successfully served request is recorded together with the clock cycle at which it was
+/// made. Failed requests are not recorded.
+pub struct RecordingProvider<P> {
+    provider: P,
+    step: usize,
+    records: Vec<(usize, AdviceRecord)>,
+}
+
+impl<P: AdviceProvider> RecordingProvider<P> {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Returns a new provider which records advice served by the specified provider.
+    pub fn new(provider: P) -> Self {
+        Self {
+            provider,
+            step: 0,
+            records: Vec::new(),
+        }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the underlying provider.
+    pub fn inner(&self) -> &P {
+        &self.provider
+    }
+
+    /// Returns the advice served so far together with the clock cycles at which it was
+    /// requested, in the order in which it was served.
+    pub fn records(&self) -> &[(usize, AdviceRecord)] {
+        &self.records
+    }
+
+    /// Consumes this provider and returns the underlying provider and the recorded advice.
+    pub fn into_parts(self) -> (P, Vec<(usize, AdviceRecord)>) {
+        (self.provider, self.records)
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Records the specified advice at the current clock cycle.
+    fn record(&mut self, record: AdviceRecord) {
+        self.records.push((self.step, record));
+    }
+}
+
+impl<P: AdviceProvider> AdviceProvider for RecordingProvider<P> {
+    fn read_tape(&mut self) -> Result<Felt, ExecutionError> {
+        let value = self.provider.read_tape()?;
+        self.record(AdviceRecord::TapeRead(value));
+        Ok(value)
+    }
+
+    fn write_tape(&mut self, value: Felt) {
+        self.provider.write_tape(value);
+        self.record(AdviceRecord::TapeWrite(value));
+    }
+
+    fn has_advice_set(&self, root: Word) -> bool {
+        self.provider.has_advice_set(root)
+    }
+
+    fn get_tree_node(
+        &mut self,
+        root: Word,
+        depth: Felt,
+        index: Felt,
+    ) -> Result<Word, ExecutionError> {
+        let node = self.provider.get_tree_node(root, depth, index)?;
+        self.record(AdviceRecord::TreeNode {
+            root,
+            depth,
+            index,
+            node,
+        });
+        Ok(node)
+    }
+
+    fn get_merkle_path(
+        &mut self,
+        root: Word,
+        depth: Felt,
+        index: Felt,
+    ) -> Result<Vec<Word>, ExecutionError> {
+        let path = self.provider.get_merkle_path(root, depth, index)?;
+        self.record(AdviceRecord::MerklePath {
+            root,
+            depth,
+            index,
+            path: path.clone(),
+        });
+        Ok(path)
+    }
+
+    fn update_merkle_leaf(
+        &mut self,
+        root: Word,
+        index: Felt,
+        leaf_value: Word,
+        update_in_copy: bool,
+    ) -> Result<Vec<Word>, ExecutionError> {
+        let path = self
+            .provider
+            .update_merkle_leaf(root, index, leaf_value, update_in_copy)?;
+        self.record(AdviceRecord::MerkleLeafUpdate {
+            root,
+            index,
+            leaf_value,
+            path: path.clone(),
+        });
+        Ok(path)
+    }
+
+    fn merge_merkle_trees(&mut self, left: Word, right: Word) -> Result<Word, ExecutionError> {
+        let root = self.provider.merge_merkle_trees(left, right)?;
+        self.record(AdviceRecord::MerkleMerge { left, right, root });
+        Ok(root)
+    }
+
+    fn get_mapped_values(&mut self, key: Word) -> Result<Vec<Felt>, ExecutionError> {
+        let values = self.provider.get_mapped_values(key)?;
+        self.record(AdviceRecord::MapValues {
+            key,
+            values: values.clone(),
+        });
+        Ok(values)
+    }
+
+    fn get_code_block(&mut self, root: Digest) -> Option<CodeBlock> {
+        let block = self.provider.get_code_block(root)?;
+        self.record(AdviceRecord::CodeBlock(root));
+        Some(block)
+    }
+
+    fn on_debug(&mut self, report: &str) {
+        self.provider.on_debug(report);
+    }
+
+    fn on_event(&mut self, event_id: u32, state: &VmState) -> Result<(), ExecutionError> {
+        self.provider.on_event(event_id, state)
+    }
+
+    fn advance_clock(&mut self) {
+        self.step += 1;
+        self.provider.advance_clock();
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns true if the specified error was caused by a provider not having the requested advice.
+fn is_missing_advice(error: &ExecutionError) -> bool {
+    matches!(
+        error,
+        ExecutionError::EmptyAdviceTape(_)
+            | ExecutionError::AdviceSetNotFound(_)
+            | ExecutionError::AdviceMapKeyNotFound(_)
+    )
+}
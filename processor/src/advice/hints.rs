@@ -0,0 +1,262 @@
+use super::{AdviceProvider, CodeBlock, Digest, ExecutionError, Felt, Vec, VmState, Word};
+use vm_core::{utils::collections::BTreeMap, StarkField, ZERO};
+
+// EVENTS
+// ================================================================================================
+// Event IDs in the range [2^32 - 2^16, 2^32) are reserved for the standard library.
+
+/// Event emitted by `std::math::u64::divmod_hint` procedure to request the quotient and the
+/// remainder of division of two 64-bit integers from the host.
+pub const U64_DIVMOD_EVENT: u32 = 0xFFFF_0000;
+
+/// Event emitted by `std::collections::vec::sort_hint` procedure to request a permutation which
+/// sorts a vector from the host.
+pub const VEC_SORT_EVENT: u32 = 0xFFFF_0001;
+
+/// Event emitted by the procedures of `std::math::fields::bls381_fp` module to request the
+/// quotient and the remainder of division of a 768-bit integer by the BLS12-381 base field
+/// modulus from the host.
+pub const BLS381_FP_REDUCE_EVENT: u32 = 0xFFFF_0002;
+
+/// Limbs of the BLS12-381 base field modulus in little-endian order.
+const BLS381_P: [u32; 12] = [
+    0xffffaaab, 0xb9feffff, 0xb153ffff, 0x1eabfffe, 0xf6b0f624, 0x6730d2a0, 0xf38512bf, 0x64774b84,
+    0x434bacd7, 0x4b1ba7b6, 0x397fe69a, 0x1a0111ea,
+];
+
+// HINT PROVIDER
+// ================================================================================================
+
+/// An advice provider which answers requests for hints made by the procedures of the standard
+/// library.
+///
+/// Hints are results of computations which are expensive to perform in the VM, but cheap to
+/// verify (e.g., a quotient of a division, or a permutation which sorts a list). A procedure
+/// requests a hint by emitting an event, in response to which this provider computes the result
+/// from the state of the VM and writes it into the advice tape of the underlying provider. The
+/// procedure then reads the result from the advice tape and verifies it, and thus the hints do
+/// not need to be trusted. The following events are answered:
+/// - [U64_DIVMOD_EVENT]: the quotient q and the remainder r of division of a by b, where the
+///   stack is [b_hi, b_lo, a_hi, a_lo, ...], are written so that the tape is read as
+///   [q_lo, q_hi, r_lo, r_hi].
+/// - [VEC_SORT_EVENT]: for the vector at address v, where the stack is [v, ...], the indices of
+///   the items in the order in which they appear in the sorted vector are written so that the
+///   tape is read starting with the index of the first item.
+/// - [BLS381_FP_REDUCE_EVENT]: for the 768-bit integer t stored as 24 u32 limbs (one limb per
+///   word) at address a, where the stack is [a, ...], the quotient q and the remainder r of
+///   division of t by the BLS12-381 base field modulus are written so that the tape is read as
+///   [q_0, ..., q_11, r_0, ..., r_11], i.e., as 12 little-endian limbs of each value.
+///
+/// All other events are forwarded to the underlying provider.
+///
+/// Since hints are injected only when a program is executed with this provider, a program which
+/// requests hints should be executed via [execute_with_advice()](crate::execute_with_advice), and
+/// the resulting trace should be proven directly.
+pub struct HintProvider<P> {
+    provider: P,
+}
+
+impl<P: AdviceProvider> HintProvider<P> {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Returns a new provider which answers requests for hints using the advice tape of the
+    /// specified provider.
+    pub fn new(provider: P) -> Self {
+        Self { provider }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the underlying provider.
+    pub fn inner(&self) -> &P {
+        &self.provider
+    }
+
+    /// Consumes this provider and returns the underlying provider.
+    pub fn into_inner(self) -> P {
+        self.provider
+    }
+
+    // HINTS
+    // --------------------------------------------------------------------------------------------
+
+    /// Writes the quotient and the remainder of division of two 64-bit integers at the top of the
+    /// stack into the advice tape.
+    ///
+    /// # Errors
+    /// Returns an error if the divisor is ZERO.
+    fn inject_u64_divmod(&mut self, state: &VmState) -> Result<(), ExecutionError> {
+        let divisor = to_u64(state.stack[0], state.stack[1]);
+        if divisor == 0 {
+            return Err(ExecutionError::DivideByZero(state.clk));
+        }
+        let dividend = to_u64(state.stack[2], state.stack[3]);
+
+        let quotient = dividend / divisor;
+        let remainder = dividend % divisor;
+
+        for value in [remainder, quotient] {
+            self.provider.write_tape(Felt::new(value >> 32));
+            self.provider.write_tape(Felt::new(value as u32 as u64));
+        }
+        Ok(())
+    }
+
+    /// Writes the permutation which sorts the items of the vector at the address at the top of
+    /// the stack by their first elements into the advice tape.
+    fn inject_vec_sort(&mut self, state: &VmState) -> Result<(), ExecutionError> {
+        let memory = state.memory.iter().copied().collect::<BTreeMap<_, _>>();
+        let read_word = |addr: u64| memory.get(&addr).copied().unwrap_or([ZERO; 4]);
+
+        let addr = state.stack[0].as_int();
+        let len = read_word(addr)[0].as_int();
+        let keys = (0..len)
+            .map(|i| read_word(addr + 1 + i)[0].as_int())
+            .collect::<Vec<_>>();
+
+        // the sort is stable, and thus items with equal keys retain their relative order
+        let mut permutation = (0..len).collect::<Vec<_>>();
+        permutation.sort_by_key(|&i| keys[i as usize]);
+
+        // the tape is read in the reverse order of writing
+        for &index in permutation.iter().rev() {
+            self.provider.write_tape(Felt::new(index));
+        }
+        Ok(())
+    }
+
+    /// Writes the quotient and the remainder of division of the 24-limb integer at the address at
+    /// the top of the stack by the BLS12-381 base field modulus into the advice tape.
+    ///
+    /// Only the low 12 limbs of the quotient are written; if the quotient does not fit into 12
+    /// limbs, the verification performed by the VM fails.
+    fn inject_bls381_fp_reduce(&mut self, state: &VmState) -> Result<(), ExecutionError> {
+        let memory = state.memory.iter().copied().collect::<BTreeMap<_, _>>();
+        let addr = state.stack[0].as_int();
+        let mut value = [0u32; 24];
+        for (i, limb) in value.iter_mut().enumerate() {
+            *limb = memory
+                .get(&(addr + i as u64))
+                .map_or(0, |word| word[0].as_int() as u32);
+        }
+
+        let (quotient, remainder) = divrem_bls381_p(&value);
+
+        // the tape is read in the reverse order of writing
+        for &limb in remainder.iter().rev().chain(quotient[..12].iter().rev()) {
+            self.provider.write_tape(Felt::new(limb as u64));
+        }
+        Ok(())
+    }
+}
+
+impl<P: AdviceProvider> AdviceProvider for HintProvider<P> {
+    fn read_tape(&mut self) -> Result<Felt, ExecutionError> {
+        self.provider.read_tape()
+    }
+
+    fn write_tape(&mut self, value: Felt) {
+        self.provider.write_tape(value)
+    }
+
+    fn has_advice_set(&self, root: Word) -> bool {
+        self.provider.has_advice_set(root)
+    }
+
+    fn get_tree_node(
+        &mut self,
+        root: Word,
+        depth: Felt,
+        index: Felt,
+    ) -> Result<Word, ExecutionError> {
+        self.provider.get_tree_node(root, depth, index)
+    }
+
+    fn get_merkle_path(
+        &mut self,
+        root: Word,
+        depth: Felt,
+        index: Felt,
+    ) -> Result<Vec<Word>, ExecutionError> {
+        self.provider.get_merkle_path(root, depth, index)
+    }
+
+    fn update_merkle_leaf(
+        &mut self,
+        root: Word,
+        index: Felt,
+        leaf_value: Word,
+        update_in_copy: bool,
+    ) -> Result<Vec<Word>, ExecutionError> {
+        self.provider
+            .update_merkle_leaf(root, index, leaf_value, update_in_copy)
+    }
+
+    fn merge_merkle_trees(&mut self, left: Word, right: Word) -> Result<Word, ExecutionError> {
+        self.provider.merge_merkle_trees(left, right)
+    }
+
+    fn get_mapped_values(&mut self, key: Word) -> Result<Vec<Felt>, ExecutionError> {
+        self.provider.get_mapped_values(key)
+    }
+
+    fn get_code_block(&mut self, root: Digest) -> Option<CodeBlock> {
+        self.provider.get_code_block(root)
+    }
+
+    fn on_debug(&mut self, report: &str) {
+        self.provider.on_debug(report);
+    }
+
+    fn on_event(&mut self, event_id: u32, state: &VmState) -> Result<(), ExecutionError> {
+        match event_id {
+            U64_DIVMOD_EVENT => self.inject_u64_divmod(state),
+            VEC_SORT_EVENT => self.inject_vec_sort(state),
+            BLS381_FP_REDUCE_EVENT => self.inject_bls381_fp_reduce(state),
+            _ => self.provider.on_event(event_id, state),
+        }
+    }
+
+    fn advance_clock(&mut self) {
+        self.provider.advance_clock();
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Combines the high and the low 32-bit limbs of a 64-bit integer.
+fn to_u64(hi: Felt, lo: Felt) -> u64 {
+    (hi.as_int() << 32).wrapping_add(lo.as_int())
+}
+
+/// Divides the little-endian 24-limb integer by the BLS12-381 base field modulus via binary long
+/// division, and returns the quotient and the remainder.
+fn divrem_bls381_p(value: &[u32; 24]) -> ([u32; 24], [u32; 12]) {
+    let mut quotient = [0u32; 24];
+    // the remainder is smaller than 2p before each subtraction, and thus fits into 12 limbs
+    let mut remainder = [0u32; 12];
+    for bit in (0..24 * 32).rev() {
+        // remainder = 2 * remainder + the next bit of the dividend
+        let mut carry = (value[bit / 32] >> (bit % 32)) & 1;
+        for limb in remainder.iter_mut() {
+            let shifted = (*limb >> 31) & 1;
+            *limb = (*limb << 1) | carry;
+            carry = shifted;
+        }
+
+        // subtract the modulus if the remainder is not smaller than it
+        if remainder.iter().rev().cmp(BLS381_P.iter().rev()).is_ge() {
+            let mut borrow = false;
+            for (limb, &p) in remainder.iter_mut().zip(BLS381_P.iter()) {
+                let (diff, borrow1) = limb.overflowing_sub(p);
+                let (diff, borrow2) = diff.overflowing_sub(borrow as u32);
+                *limb = diff;
+                borrow = borrow1 || borrow2;
+            }
+            quotient[bit / 32] |= 1 << (bit % 32);
+        }
+    }
+    (quotient, remainder)
+}
@@ -0,0 +1,260 @@
+use super::{
+    AdviceProvider, BTreeMap, CodeBlock, Digest, ExecutionError, Felt, ProgramInputs, Vec, Word,
+};
+use vm_core::{
+    utils::{
+        bytes::{read_seq, read_usize, read_vec, write_seq, write_usize, write_vec},
+        ByteReader, ByteWriter, Deserializable, DeserializationError, IntoBytes, Serializable,
+    },
+    AdviceMap, AdviceSet, CodeBlockTable, StarkField,
+};
+
+// MEMORY ADVICE PROVIDER
+// ================================================================================================
+
+/// An advice provider which keeps all non-deterministic inputs in memory.
+///
+/// The provider manages three types of inputs:
+/// 1. An advice tape, from which the program can read elements sequentially. Once read, the
+///    element is removed from the tape.
+/// 2. Advice sets, which can be identified by their roots. Advice sets are views into Merkle
+///    trees and can be used to provide Merkle paths.
+/// 3. An advice map, which maps keys (words) to lists of elements.
+///
+/// The provider can also hold a table of code blocks which are supplied to the processor when it
+/// needs to execute code which is not a part of the program (see [Self::with_code_blocks()]).
+///
+/// A memory advice provider can be instantiated from [ProgramInputs]; this is the provider used
+/// by the processor unless a different provider is specified.
+///
+/// A memory advice provider can be serialized (e.g., as a part of a process checkpoint); the
+/// table of code blocks is not serialized, and thus needs to be supplied again via
+/// [Self::with_code_blocks()] after the provider is deserialized.
+pub struct MemAdviceProvider {
+    step: usize,
+    tape: Vec<Felt>,
+    sets: BTreeMap<[u8; 32], AdviceSet>,
+    map: AdviceMap,
+    code_blocks: CodeBlockTable,
+}
+
+impl MemAdviceProvider {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Returns a new advice provider instantiated from the specified program inputs.
+    pub fn new(inputs: ProgramInputs) -> Self {
+        let (_, mut advice_tape, advice_sets, advice_map) = inputs.into_parts();
+
+        // reverse the advice tape so that we can pop elements off the end
+        advice_tape.reverse();
+
+        Self {
+            step: 0,
+            tape: advice_tape,
+            sets: advice_sets,
+            map: advice_map,
+            code_blocks: CodeBlockTable::default(),
+        }
+    }
+
+    /// Returns this provider with the specified table of code blocks which the provider supplies
+    /// to the processor.
+    pub fn with_code_blocks(mut self, code_blocks: CodeBlockTable) -> Self {
+        self.code_blocks = code_blocks;
+        self
+    }
+
+    // STATE MUTATORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Adds the specified advice set to this provider, replacing an advice set with the same
+    /// root if it is already present.
+    pub(super) fn insert_advice_set(&mut self, advice_set: AdviceSet) {
+        self.sets.insert(advice_set.root().into_bytes(), advice_set);
+    }
+
+    /// Adds the specified advice map entry to this provider, replacing an entry with the same key
+    /// if it is already present.
+    pub(super) fn insert_map_entry(&mut self, key: Word, values: Vec<Felt>) {
+        self.map.insert(key.into_bytes(), values);
+    }
+}
+
+impl AdviceProvider for MemAdviceProvider {
+    // ADVICE TAPE
+    // --------------------------------------------------------------------------------------------
+
+    fn read_tape(&mut self) -> Result<Felt, ExecutionError> {
+        self.tape
+            .pop()
+            .ok_or(ExecutionError::EmptyAdviceTape(self.step))
+    }
+
+    fn write_tape(&mut self, value: Felt) {
+        self.tape.push(value);
+    }
+
+    // ADVISE SETS
+    // --------------------------------------------------------------------------------------------
+
+    fn has_advice_set(&self, root: Word) -> bool {
+        self.sets.contains_key(&root.into_bytes())
+    }
+
+    fn get_tree_node(
+        &mut self,
+        root: Word,
+        depth: Felt,
+        index: Felt,
+    ) -> Result<Word, ExecutionError> {
+        // look up the advice set and return an error if none is found
+        let advice_set = self
+            .sets
+            .get(&root.into_bytes())
+            .ok_or_else(|| ExecutionError::AdviceSetNotFound(root.into_bytes()))?;
+
+        // get the tree node from the advice set based on depth and index
+        let node = advice_set
+            .get_node(depth.as_int() as u32, index.as_int())
+            .map_err(ExecutionError::AdviceSetLookupFailed)?;
+
+        Ok(node)
+    }
+
+    fn get_merkle_path(
+        &mut self,
+        root: Word,
+        depth: Felt,
+        index: Felt,
+    ) -> Result<Vec<Word>, ExecutionError> {
+        // look up the advice set and return an error if none is found
+        let advice_set = self
+            .sets
+            .get(&root.into_bytes())
+            .ok_or_else(|| ExecutionError::AdviceSetNotFound(root.into_bytes()))?;
+
+        // get the Merkle path from the advice set based on depth and index
+        let path = advice_set
+            .get_path(depth.as_int() as u32, index.as_int())
+            .map_err(ExecutionError::AdviceSetLookupFailed)?;
+
+        Ok(path)
+    }
+
+    fn update_merkle_leaf(
+        &mut self,
+        root: Word,
+        index: Felt,
+        leaf_value: Word,
+        update_in_copy: bool,
+    ) -> Result<Vec<Word>, ExecutionError> {
+        // look up the advice set and return error if none is found. if we are updating a copy,
+        // clone the advice set; otherwise remove it from the map because the root will change,
+        // and we'll re-insert the set later under a different root.
+        let mut advice_set = if update_in_copy {
+            // look up the advice set and return an error if none is found
+            self.sets
+                .get(&root.into_bytes())
+                .ok_or_else(|| ExecutionError::AdviceSetNotFound(root.into_bytes()))?
+                .clone()
+        } else {
+            self.sets
+                .remove(&root.into_bytes())
+                .ok_or_else(|| ExecutionError::AdviceSetNotFound(root.into_bytes()))?
+        };
+
+        // get the Merkle path from the advice set for the leaf at the specified index
+        let path = advice_set
+            .get_path(advice_set.depth(), index.as_int())
+            .map_err(ExecutionError::AdviceSetLookupFailed)?;
+
+        // update the advice set and re-insert it into the map
+        advice_set
+            .update_leaf(index.as_int(), leaf_value)
+            .map_err(ExecutionError::AdviceSetLookupFailed)?;
+        self.sets.insert(advice_set.root().into_bytes(), advice_set);
+
+        Ok(path)
+    }
+
+    fn merge_merkle_trees(&mut self, left: Word, right: Word) -> Result<Word, ExecutionError> {
+        // look up the advice sets and return an error if either of them is not found; the sets
+        // are cloned so that they are retained in the provider
+        let [left, right] = [left, right].map(|root| {
+            self.sets
+                .get(&root.into_bytes())
+                .cloned()
+                .ok_or_else(|| ExecutionError::AdviceSetNotFound(root.into_bytes()))
+        });
+
+        // merge the advice sets and insert the merged set into the map
+        let advice_set = AdviceSet::new_merged_tree(left?, right?)
+            .map_err(ExecutionError::AdviceSetLookupFailed)?;
+        let root = advice_set.root();
+        self.sets.insert(root.into_bytes(), advice_set);
+
+        Ok(root)
+    }
+
+    // ADVICE MAP
+    // --------------------------------------------------------------------------------------------
+
+    fn get_mapped_values(&mut self, key: Word) -> Result<Vec<Felt>, ExecutionError> {
+        self.map
+            .get(&key.into_bytes())
+            .cloned()
+            .ok_or_else(|| ExecutionError::AdviceMapKeyNotFound(key.into_bytes()))
+    }
+
+    // CODE BLOCKS
+    // --------------------------------------------------------------------------------------------
+
+    fn get_code_block(&mut self, root: Digest) -> Option<CodeBlock> {
+        self.code_blocks.get(root).cloned()
+    }
+
+    // CONTEXT MANAGEMENT
+    // --------------------------------------------------------------------------------------------
+
+    fn advance_clock(&mut self) {
+        self.step += 1;
+    }
+}
+
+// SERIALIZATION
+// ================================================================================================
+
+impl Serializable for MemAdviceProvider {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        write_usize(target, self.step);
+        write_vec(target, &self.tape);
+        write_seq(target, &self.sets, |target, (root, set)| {
+            target.write_u8_slice(root);
+            set.write_into(target);
+        });
+        write_seq(target, &self.map, |target, (key, values)| {
+            target.write_u8_slice(key);
+            write_vec(target, values);
+        });
+    }
+}
+
+impl Deserializable for MemAdviceProvider {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let step = read_usize(source)?;
+        let tape = read_vec(source)?;
+        let sets = read_seq(source, |source| {
+            Ok((source.read_u8_array::<32>()?, AdviceSet::read_from(source)?))
+        })?;
+        let map = read_seq(source, |source| {
+            Ok((source.read_u8_array::<32>()?, read_vec(source)?))
+        })?;
+        Ok(Self {
+            step,
+            tape,
+            sets: sets.into_iter().collect(),
+            map: map.into_iter().collect(),
+            code_blocks: CodeBlockTable::default(),
+        })
+    }
+}
@@ -1,47 +1,48 @@
-use super::{ExecutionError, Felt, ProgramInputs, Word};
-use vm_core::{
-    utils::{
-        collections::{BTreeMap, Vec},
-        IntoBytes,
-    },
-    AdviceSet, StarkField,
+use super::{CodeBlock, Digest, ExecutionError, Felt, ProgramInputs, VmState, Word};
+use vm_core::utils::{
+    collections::{BTreeMap, Vec},
+    IntoBytes,
 };
 
+#[cfg(not(feature = "std"))]
+use alloc::boxed::Box;
+
+mod mem_provider;
+pub use mem_provider::MemAdviceProvider;
+
+mod combinators;
+pub use combinators::{
+    AdviceRecord, ChainedProvider, FallbackProvider, RecordingProvider, ResolvedAdvice,
+    ResolvingProvider,
+};
+
+mod hints;
+pub use hints::{HintProvider, BLS381_FP_REDUCE_EVENT, U64_DIVMOD_EVENT, VEC_SORT_EVENT};
+
+#[cfg(test)]
+mod tests;
+
 // ADVICE PROVIDER
 // ================================================================================================
 
 /// An advice provider supplies non-deterministic inputs to the processor during program execution.
 ///
-/// The provider manages two types of inputs:
+/// The provider manages three types of inputs:
 /// 1. An advice tape, from which the program can read elements sequentially. Once read, the
 ///    element is removed from the tape.
 /// 2. Advice sets, which can be identified by their roots. Advice sets are views into Merkle
 ///    trees and can be used to provide Merkle paths.
+/// 3. An advice map, which maps keys (words) to lists of elements.
 ///
-/// An advice provider can be instantiated from [ProgramInputs].
-pub struct AdviceProvider {
-    step: usize,
-    tape: Vec<Felt>,
-    sets: BTreeMap<[u8; 32], AdviceSet>,
-}
-
-impl AdviceProvider {
-    // CONSTRUCTOR
-    // --------------------------------------------------------------------------------------------
-    /// Returns a new advice provider instantiated from the specified program inputs.
-    pub fn new(inputs: ProgramInputs) -> Self {
-        let (_, mut advice_tape, advice_sets) = inputs.into_parts();
-
-        // reverse the advice tape so that we can pop elements off the end
-        advice_tape.reverse();
-
-        Self {
-            step: 0,
-            tape: advice_tape,
-            sets: advice_sets,
-        }
-    }
-
+/// Additionally, the provider can supply code blocks which are not a part of the executed program
+/// (e.g., procedures invoked by their MAST roots).
+///
+/// The trait is object safe, and it is implemented for boxed providers and mutable references to
+/// providers. Thus, providers can be selected at runtime as `Box<dyn AdviceProvider>`, and a
+/// provider can be lent to the processor and inspected after the execution. Providers can be
+/// layered on top of each other using [ChainedProvider], [FallbackProvider],
+/// [ResolvingProvider], [RecordingProvider], and [HintProvider].
+pub trait AdviceProvider {
     // ADVICE TAPE
     // --------------------------------------------------------------------------------------------
 
@@ -49,25 +50,16 @@ impl AdviceProvider {
     ///
     /// # Errors
     /// Returns an error if the advice tape is empty.
-    pub fn read_tape(&mut self) -> Result<Felt, ExecutionError> {
-        self.tape
-            .pop()
-            .ok_or(ExecutionError::EmptyAdviceTape(self.step))
-    }
+    fn read_tape(&mut self) -> Result<Felt, ExecutionError>;
 
     /// Writes the provided value at the head of the advice tape.
-    pub fn write_tape(&mut self, value: Felt) {
-        self.tape.push(value);
-    }
+    fn write_tape(&mut self, value: Felt);
 
-    // ADVISE SETS
+    // ADVICE SETS
     // --------------------------------------------------------------------------------------------
 
     /// Returns true if the advice set with the specified root is present in this advice provider.
-    #[cfg(test)]
-    pub fn has_advice_set(&self, root: Word) -> bool {
-        self.sets.contains_key(&root.into_bytes())
-    }
+    fn has_advice_set(&self, root: Word) -> bool;
 
     /// Returns a node at the specified index in a Merkle tree with the specified root.
     ///
@@ -77,26 +69,12 @@ impl AdviceProvider {
     /// - The specified depth is either zero or greater than the depth of the Merkle tree
     ///   identified by the specified root.
     /// - Value of the node at the specified depth and index is not known to this advice provider.
-    #[allow(dead_code)]
-    pub fn get_tree_node(
+    fn get_tree_node(
         &mut self,
         root: Word,
         depth: Felt,
         index: Felt,
-    ) -> Result<Word, ExecutionError> {
-        // look up the advice set and return an error if none is found
-        let advice_set = self
-            .sets
-            .get(&root.into_bytes())
-            .ok_or_else(|| ExecutionError::AdviceSetNotFound(root.into_bytes()))?;
-
-        // get the tree node from the advice set based on depth and index
-        let node = advice_set
-            .get_node(depth.as_int() as u32, index.as_int())
-            .map_err(ExecutionError::AdviceSetLookupFailed)?;
-
-        Ok(node)
-    }
+    ) -> Result<Word, ExecutionError>;
 
     /// Returns a path to a node at the specified index in a Merkle tree with the specified root.
     ///
@@ -106,25 +84,12 @@ impl AdviceProvider {
     /// - The specified depth is either zero or greater than the depth of the Merkle tree
     ///   identified by the specified root.
     /// - Path to the node at the specified depth and index is not known to this advice provider.
-    pub fn get_merkle_path(
+    fn get_merkle_path(
         &mut self,
         root: Word,
         depth: Felt,
         index: Felt,
-    ) -> Result<Vec<Word>, ExecutionError> {
-        // look up the advice set and return an error if none is found
-        let advice_set = self
-            .sets
-            .get(&root.into_bytes())
-            .ok_or_else(|| ExecutionError::AdviceSetNotFound(root.into_bytes()))?;
-
-        // get the Merkle path from the advice set based on depth and index
-        let path = advice_set
-            .get_path(depth.as_int() as u32, index.as_int())
-            .map_err(ExecutionError::AdviceSetLookupFailed)?;
-
-        Ok(path)
-    }
+    ) -> Result<Vec<Word>, ExecutionError>;
 
     /// Updates a leaf at the specified index in the advice set with the specified root with the
     /// provided value and returns a Merkle path to this leaf.
@@ -140,47 +105,210 @@ impl AdviceProvider {
     ///   identified by the specified root.
     /// - Path to the leaf at the specified index in the specified Merkle tree is not known to this
     ///   advice provider.
-    pub fn update_merkle_leaf(
+    fn update_merkle_leaf(
         &mut self,
         root: Word,
         index: Felt,
         leaf_value: Word,
         update_in_copy: bool,
-    ) -> Result<Vec<Word>, ExecutionError> {
-        // look up the advice set and return error if none is found. if we are updating a copy,
-        // clone the advice set; otherwise remove it from the map because the root will change,
-        // and we'll re-insert the set later under a different root.
-        let mut advice_set = if update_in_copy {
-            // look up the advice set and return an error if none is found
-            self.sets
-                .get(&root.into_bytes())
-                .ok_or_else(|| ExecutionError::AdviceSetNotFound(root.into_bytes()))?
-                .clone()
-        } else {
-            self.sets
-                .remove(&root.into_bytes())
-                .ok_or_else(|| ExecutionError::AdviceSetNotFound(root.into_bytes()))?
-        };
-
-        // get the Merkle path from the advice set for the leaf at the specified index
-        let path = advice_set
-            .get_path(advice_set.depth(), index.as_int())
-            .map_err(ExecutionError::AdviceSetLookupFailed)?;
-
-        // update the advice set and re-insert it into the map
-        advice_set
-            .update_leaf(index.as_int(), leaf_value)
-            .map_err(ExecutionError::AdviceSetLookupFailed)?;
-        self.sets.insert(advice_set.root().into_bytes(), advice_set);
-
-        Ok(path)
+    ) -> Result<Vec<Word>, ExecutionError>;
+
+    // ADVICE MAP
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the values of the advice map entry with the specified key.
+    ///
+    /// By default, an advice provider does not have an advice map.
+    ///
+    /// # Errors
+    /// Returns an error if an entry with the specified key is not present in this advice
+    /// provider.
+    fn get_mapped_values(&mut self, key: Word) -> Result<Vec<Felt>, ExecutionError> {
+        Err(ExecutionError::AdviceMapKeyNotFound(key.into_bytes()))
+    }
+
+    /// Creates a new Merkle tree by merging the Merkle trees with the specified roots, and returns
+    /// the root of the new tree. The left and the right subtrees of the root of the new tree are
+    /// the trees with the specified roots, and thus the root of the new tree is the hash of the
+    /// specified roots. The merged trees are retained in this provider.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - A Merkle tree for either of the specified roots cannot be found in this advice provider.
+    /// - The depths of the merged trees are not equal.
+    fn merge_merkle_trees(&mut self, left: Word, right: Word) -> Result<Word, ExecutionError>;
+
+    // CODE BLOCKS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the code block with the specified hash (MAST root), or `None` if the code block is
+    /// not known to this advice provider.
+    ///
+    /// By default, an advice provider does not supply any code blocks.
+    fn get_code_block(&mut self, _root: Digest) -> Option<CodeBlock> {
+        None
+    }
+
+    // DEBUGGING
+    // --------------------------------------------------------------------------------------------
+
+    /// Handles a report of the VM state requested by a debug decorator (e.g., one emitted for a
+    /// `debug.stack` instruction). Debug decorators are executed only when a program is executed
+    /// in debug mode.
+    ///
+    /// By default, the report is written to the log at the debug level.
+    fn on_debug(&mut self, report: &str) {
+        log::debug!("{}", report);
+    }
+
+    /// Handles an event emitted by an `emit` instruction. The state describes the VM at the
+    /// beginning of the cycle in which the next operation is executed. Events are handled
+    /// regardless of whether a program is executed in debug mode, and thus the provider can react
+    /// to an event by writing values into the advice tape before the next operation reads them.
+    ///
+    /// By default, events are ignored.
+    ///
+    /// # Errors
+    /// Returning an error aborts the execution of the program.
+    fn on_event(&mut self, _event_id: u32, _state: &VmState) -> Result<(), ExecutionError> {
+        Ok(())
     }
 
     // CONTEXT MANAGEMENT
     // --------------------------------------------------------------------------------------------
 
     /// Increments the clock cycle.
-    pub fn advance_clock(&mut self) {
-        self.step += 1;
+    fn advance_clock(&mut self);
+}
+
+impl<P: AdviceProvider + ?Sized> AdviceProvider for Box<P> {
+    fn read_tape(&mut self) -> Result<Felt, ExecutionError> {
+        (**self).read_tape()
+    }
+
+    fn write_tape(&mut self, value: Felt) {
+        (**self).write_tape(value)
+    }
+
+    fn has_advice_set(&self, root: Word) -> bool {
+        (**self).has_advice_set(root)
+    }
+
+    fn get_tree_node(
+        &mut self,
+        root: Word,
+        depth: Felt,
+        index: Felt,
+    ) -> Result<Word, ExecutionError> {
+        (**self).get_tree_node(root, depth, index)
+    }
+
+    fn get_merkle_path(
+        &mut self,
+        root: Word,
+        depth: Felt,
+        index: Felt,
+    ) -> Result<Vec<Word>, ExecutionError> {
+        (**self).get_merkle_path(root, depth, index)
+    }
+
+    fn update_merkle_leaf(
+        &mut self,
+        root: Word,
+        index: Felt,
+        leaf_value: Word,
+        update_in_copy: bool,
+    ) -> Result<Vec<Word>, ExecutionError> {
+        (**self).update_merkle_leaf(root, index, leaf_value, update_in_copy)
+    }
+
+    fn merge_merkle_trees(&mut self, left: Word, right: Word) -> Result<Word, ExecutionError> {
+        (**self).merge_merkle_trees(left, right)
+    }
+
+    fn get_mapped_values(&mut self, key: Word) -> Result<Vec<Felt>, ExecutionError> {
+        (**self).get_mapped_values(key)
+    }
+
+    fn get_code_block(&mut self, root: Digest) -> Option<CodeBlock> {
+        (**self).get_code_block(root)
+    }
+
+    fn on_debug(&mut self, report: &str) {
+        (**self).on_debug(report)
+    }
+
+    fn on_event(&mut self, event_id: u32, state: &VmState) -> Result<(), ExecutionError> {
+        (**self).on_event(event_id, state)
+    }
+
+    fn advance_clock(&mut self) {
+        (**self).advance_clock()
+    }
+}
+
+impl<P: AdviceProvider + ?Sized> AdviceProvider for &mut P {
+    fn read_tape(&mut self) -> Result<Felt, ExecutionError> {
+        (**self).read_tape()
+    }
+
+    fn write_tape(&mut self, value: Felt) {
+        (**self).write_tape(value)
+    }
+
+    fn has_advice_set(&self, root: Word) -> bool {
+        (**self).has_advice_set(root)
+    }
+
+    fn get_tree_node(
+        &mut self,
+        root: Word,
+        depth: Felt,
+        index: Felt,
+    ) -> Result<Word, ExecutionError> {
+        (**self).get_tree_node(root, depth, index)
+    }
+
+    fn get_merkle_path(
+        &mut self,
+        root: Word,
+        depth: Felt,
+        index: Felt,
+    ) -> Result<Vec<Word>, ExecutionError> {
+        (**self).get_merkle_path(root, depth, index)
+    }
+
+    fn update_merkle_leaf(
+        &mut self,
+        root: Word,
+        index: Felt,
+        leaf_value: Word,
+        update_in_copy: bool,
+    ) -> Result<Vec<Word>, ExecutionError> {
+        (**self).update_merkle_leaf(root, index, leaf_value, update_in_copy)
+    }
+
+    fn merge_merkle_trees(&mut self, left: Word, right: Word) -> Result<Word, ExecutionError> {
+        (**self).merge_merkle_trees(left, right)
+    }
+
+    fn get_mapped_values(&mut self, key: Word) -> Result<Vec<Felt>, ExecutionError> {
+        (**self).get_mapped_values(key)
+    }
+
+    fn get_code_block(&mut self, root: Digest) -> Option<CodeBlock> {
+        (**self).get_code_block(root)
+    }
+
+    fn on_debug(&mut self, report: &str) {
+        (**self).on_debug(report)
+    }
+
+    fn on_event(&mut self, event_id: u32, state: &VmState) -> Result<(), ExecutionError> {
+        (**self).on_event(event_id, state)
+    }
+
+    fn advance_clock(&mut self) {
+        (**self).advance_clock()
     }
 }
@@ -0,0 +1,435 @@
+use super::{
+    AdviceProvider, AdviceRecord, ChainedProvider, ExecutionError, FallbackProvider, Felt,
+    MemAdviceProvider, ProgramInputs, RecordingProvider, ResolvedAdvice, ResolvingProvider, Vec,
+    Word,
+};
+use crate::{execute, execute_with_advice};
+use core::cell::Cell;
+use vm_core::{
+    code_blocks::CodeBlock, AdviceSet, CodeBlockTable, FieldElement, Operation, Program, StarkField,
+};
+
+// CHAINED PROVIDER TESTS
+// ================================================================================================
+
+#[test]
+fn chained_provider() {
+    let (set_a, set_b) = (build_set(1), build_set(5));
+    let mut provider = ChainedProvider::new(vec![
+        Box::new(build_provider(&[1, 2], vec![set_a.clone()])),
+        Box::new(build_provider(&[], vec![])),
+        Box::new(build_provider(&[3], vec![set_b.clone()])),
+    ]);
+
+    // tapes are concatenated, and written elements are read first
+    provider.write_tape(Felt::new(7));
+    assert_eq!(read_tape(&mut provider, 4), [7, 1, 2, 3]);
+    assert!(matches!(
+        provider.read_tape(),
+        Err(ExecutionError::EmptyAdviceTape(_))
+    ));
+
+    // advice sets are served by the provider which has them
+    assert!(provider.has_advice_set(set_a.root()));
+    assert!(provider.has_advice_set(set_b.root()));
+    let path = provider
+        .get_merkle_path(set_b.root(), Felt::new(2), Felt::new(3))
+        .unwrap();
+    assert_eq!(set_b.get_path(2, 3).unwrap(), path);
+    assert!(matches!(
+        provider.get_tree_node(build_set(9).root(), Felt::new(2), Felt::ZERO),
+        Err(ExecutionError::AdviceSetNotFound(_))
+    ));
+
+    // updated advice sets are kept by the provider which had the original set
+    provider
+        .update_merkle_leaf(set_b.root(), Felt::ZERO, [Felt::ONE; 4], false)
+        .unwrap();
+    assert!(!provider.has_advice_set(set_b.root()));
+    assert!(provider.providers()[2].has_advice_set(updated_root(&set_b, [Felt::ONE; 4])));
+}
+
+// FALLBACK PROVIDER TESTS
+// ================================================================================================
+
+#[test]
+fn fallback_provider() {
+    let (set_a, set_b) = (build_set(1), build_set(5));
+    let primary = build_provider(&[1], vec![set_a.clone()]);
+    let fallback = build_provider(&[2, 3], vec![set_a.clone(), set_b.clone()]);
+    let mut provider = FallbackProvider::new(primary, fallback);
+
+    // the fallback provider is used only when the primary provider has no advice
+    assert_eq!(read_tape(&mut provider, 2), [1, 2]);
+    assert_eq!(1, provider.num_fallbacks());
+    provider
+        .get_tree_node(set_a.root(), Felt::new(2), Felt::ONE)
+        .unwrap();
+    provider
+        .get_tree_node(set_b.root(), Felt::new(2), Felt::ONE)
+        .unwrap();
+    assert_eq!(2, provider.num_fallbacks());
+
+    // failed lookups in the advice sets of the primary provider are not sent to the fallback
+    assert!(matches!(
+        provider.get_merkle_path(set_a.root(), Felt::new(3), Felt::ONE),
+        Err(ExecutionError::AdviceSetLookupFailed(_))
+    ));
+    assert_eq!(2, provider.num_fallbacks());
+
+    let (_, mut fallback) = provider.into_parts();
+    assert_eq!(read_tape(&mut fallback, 1), [3]);
+}
+
+// RESOLVING PROVIDER TESTS
+// ================================================================================================
+
+#[test]
+fn resolving_provider() {
+    let (set_a, set_b) = (build_set(1), build_set(5));
+    let root = set_b.root();
+    let num_calls = Cell::new(0);
+    let resolver = |requested: Word| {
+        num_calls.set(num_calls.get() + 1);
+        if requested == root {
+            Some(set_b.clone())
+        } else {
+            // sets with a root other than the requested one are ignored
+            Some(set_a.clone())
+        }
+    };
+
+    let program = miden_assembly::Assembler::default()
+        .compile("begin mtree_get end")
+        .unwrap();
+    let stack = [
+        root[0],
+        root[1],
+        root[2],
+        root[3],
+        Felt::new(2),
+        Felt::new(2),
+    ];
+    let inputs = ProgramInputs::new(&stack.map(|value| value.as_int()), &[], vec![]).unwrap();
+
+    // the set unknown to the underlying provider is supplied by the resolver
+    let mut provider =
+        ResolvingProvider::new(build_provider(&[], vec![set_a.clone()]), resolver, |_| None);
+    let trace = execute_with_advice(&program, &inputs, &mut provider).unwrap();
+    assert_eq!(Felt::new(7), trace.last_stack_state()[3]);
+    assert!(matches!(
+        provider.resolved(),
+        [(1, ResolvedAdvice::AdviceSet(set))] if set.root() == root
+    ));
+
+    // resolved sets are retained, and each root is resolved at most once
+    assert!(provider.has_advice_set(root));
+    provider
+        .get_merkle_path(root, Felt::new(2), Felt::ONE)
+        .unwrap();
+    assert_eq!(1, num_calls.get());
+
+    // sets known to the underlying provider are not resolved, and failed resolutions yield the
+    // original error
+    provider
+        .get_tree_node(set_a.root(), Felt::new(2), Felt::ONE)
+        .unwrap();
+    assert!(matches!(
+        provider.get_tree_node(build_set(9).root(), Felt::new(2), Felt::ZERO),
+        Err(ExecutionError::AdviceSetNotFound(_))
+    ));
+    assert_eq!(2, num_calls.get());
+    assert_eq!(1, provider.resolved().len());
+
+    // the execution can be replayed without the resolver
+    let resolved_inputs = provider.resolved_inputs(&inputs).unwrap();
+    let replayed = execute(&program, &resolved_inputs).unwrap();
+    assert_eq!(trace.last_stack_state(), replayed.last_stack_state());
+    assert!(provider.resolved_inputs(&resolved_inputs).is_err());
+}
+
+#[test]
+fn resolving_provider_merged_sets() {
+    let (set_a, set_b, set_c) = (build_set(1), build_set(5), build_set(9));
+    let num_calls = Cell::new(0);
+    let resolver = |requested: Word| {
+        num_calls.set(num_calls.get() + 1);
+        [&set_b, &set_c]
+            .into_iter()
+            .find(|set| set.root() == requested)
+            .cloned()
+    };
+
+    // the merged tree is accessed right after the merge
+    let program = miden_assembly::Assembler::default()
+        .compile("begin mtree_merge push.1 push.3 mtree_get end")
+        .unwrap();
+    let stack = [set_b.root(), set_c.root()]
+        .concat()
+        .iter()
+        .map(|value| value.as_int())
+        .collect::<Vec<_>>();
+    let inputs = ProgramInputs::new(&stack, &[], vec![]).unwrap();
+
+    // both sets unknown to the underlying provider are resolved before they are merged
+    let mut provider =
+        ResolvingProvider::new(build_provider(&[], vec![set_a.clone()]), resolver, |_| None);
+    let trace = execute_with_advice(&program, &inputs, &mut provider).unwrap();
+    assert_eq!(Felt::new(6), trace.last_stack_state()[3]);
+    assert!(matches!(
+        provider.resolved(),
+        [(_, ResolvedAdvice::AdviceSet(left)), (_, ResolvedAdvice::AdviceSet(right))]
+            if left.root() == set_b.root() && right.root() == set_c.root()
+    ));
+    assert_eq!(2, num_calls.get());
+
+    // the merged set is retained together with the resolved sets
+    let merged = AdviceSet::new_merged_tree(set_b.clone(), set_c.clone()).unwrap();
+    assert!(provider.has_advice_set(merged.root()));
+    assert!(!provider.inner().has_advice_set(merged.root()));
+    let path = provider
+        .get_merkle_path(merged.root(), Felt::new(3), Felt::new(5))
+        .unwrap();
+    assert_eq!(merged.get_path(3, 5).unwrap(), path);
+    assert_eq!(2, num_calls.get());
+
+    // sets known to the underlying provider are merged by it, and cannot be merged with
+    // resolved sets
+    let root = provider
+        .merge_merkle_trees(set_a.root(), set_a.root())
+        .unwrap();
+    assert!(provider.inner().has_advice_set(root));
+    assert!(matches!(
+        provider.merge_merkle_trees(set_a.root(), set_b.root()),
+        Err(ExecutionError::AdviceSetNotFound(_))
+    ));
+    assert_eq!(2, num_calls.get());
+
+    // the execution can be replayed without the resolver
+    let resolved_inputs = provider.resolved_inputs(&inputs).unwrap();
+    let replayed = execute(&program, &resolved_inputs).unwrap();
+    assert_eq!(trace.last_stack_state(), replayed.last_stack_state());
+}
+
+#[test]
+fn resolving_provider_map_keys() {
+    let key = [Felt::new(1), Felt::new(2), Felt::new(3), Felt::new(4)];
+    let values = vec![Felt::new(7), Felt::new(8), Felt::new(9)];
+    let num_calls = Cell::new(0);
+    let map_resolver = |requested: Word| {
+        num_calls.set(num_calls.get() + 1);
+        if requested == key {
+            Some(values.clone())
+        } else {
+            None
+        }
+    };
+
+    let program = miden_assembly::Assembler::default()
+        .compile("begin adv.mapval push.adv.4 end")
+        .unwrap();
+    let inputs = ProgramInputs::new(&[1, 2, 3, 4], &[], vec![]).unwrap();
+
+    // the entry unknown to the underlying provider is supplied by the map resolver
+    let mut provider = ResolvingProvider::new(build_provider(&[], vec![]), |_| None, map_resolver);
+    let trace = execute_with_advice(&program, &inputs, &mut provider).unwrap();
+    let outputs = trace.last_stack_state()[..4]
+        .iter()
+        .map(|value| value.as_int())
+        .collect::<Vec<_>>();
+    assert_eq!(vec![9, 8, 7, 3], outputs);
+    assert!(matches!(
+        provider.resolved(),
+        [(1, ResolvedAdvice::MapEntry { key: resolved_key, values: resolved_values })]
+            if *resolved_key == key && *resolved_values == values
+    ));
+
+    // resolved entries are retained, and each key is resolved at most once
+    assert_eq!(values, provider.get_mapped_values(key).unwrap());
+    assert_eq!(1, num_calls.get());
+
+    // failed resolutions yield the original error
+    assert!(matches!(
+        provider.get_mapped_values([Felt::ZERO; 4]),
+        Err(ExecutionError::AdviceMapKeyNotFound(_))
+    ));
+    assert_eq!(2, num_calls.get());
+
+    // the execution can be replayed without the resolver
+    let resolved_inputs = provider.resolved_inputs(&inputs).unwrap();
+    assert!(execute(&program, &inputs).is_err());
+    let replayed = execute(&program, &resolved_inputs).unwrap();
+    assert_eq!(trace.last_stack_state(), replayed.last_stack_state());
+    assert!(provider.resolved_inputs(&resolved_inputs).is_err());
+}
+
+// RECORDING PROVIDER TESTS
+// ================================================================================================
+
+#[test]
+fn recording_provider() {
+    let source = "begin push.adv.2 add push.adv.1 mul end";
+    let program = miden_assembly::Assembler::default()
+        .compile(source)
+        .unwrap();
+    let inputs = ProgramInputs::new(&[1], &[2, 3, 4], vec![]).unwrap();
+
+    // a recording provider over a provider with one tape element over the default provider
+    let defaults = MemAdviceProvider::new(inputs.clone());
+    let primary = build_provider(&[5], vec![]);
+    let mut provider = RecordingProvider::new(FallbackProvider::new(primary, defaults));
+    let trace = execute_with_advice(&program, &inputs, &mut provider).unwrap();
+
+    // (5 + 2) * 3
+    assert_eq!(Felt::new(21), trace.last_stack_state()[0]);
+    let records = provider.records();
+    assert_eq!(3, records.len());
+    // the first clock cycle is taken by the SPAN operation
+    assert_eq!((1, AdviceRecord::TapeRead(Felt::new(5))), records[0]);
+    assert_eq!((2, AdviceRecord::TapeRead(Felt::new(2))), records[1]);
+    assert_eq!(AdviceRecord::TapeRead(Felt::new(3)), records[2].1);
+    assert_eq!(2, provider.inner().num_fallbacks());
+
+    // the default provider yields the same result as executing the program against the inputs
+    let expected = execute(
+        &program,
+        &ProgramInputs::new(&[1], &[5, 2, 3], vec![]).unwrap(),
+    );
+    assert_eq!(
+        expected.unwrap().last_stack_state(),
+        trace.last_stack_state()
+    );
+}
+
+#[test]
+fn recording_provider_merged_sets() {
+    let (set_a, set_b) = (build_set(1), build_set(5));
+    let program = miden_assembly::Assembler::default()
+        .compile("begin mtree_merge push.1 push.3 mtree_get end")
+        .unwrap();
+    let stack = [set_a.root(), set_b.root()]
+        .concat()
+        .iter()
+        .map(|value| value.as_int())
+        .collect::<Vec<_>>();
+    let inputs = ProgramInputs::new(&stack, &[], vec![set_a.clone(), set_b.clone()]).unwrap();
+
+    // merges are recorded together with the root of the merged tree, and precede the requests
+    // for the nodes of the merged tree
+    let mut provider = RecordingProvider::new(MemAdviceProvider::new(inputs.clone()));
+    execute_with_advice(&program, &inputs, &mut provider).unwrap();
+    let merged = AdviceSet::new_merged_tree(set_a.clone(), set_b.clone()).unwrap();
+    let records = provider.records();
+    assert_eq!(
+        AdviceRecord::MerkleMerge {
+            left: set_a.root(),
+            right: set_b.root(),
+            root: merged.root(),
+        },
+        records[0].1
+    );
+    assert!(records[1..].iter().any(|(clk, record)| {
+        matches!(record, AdviceRecord::MerklePath { root, .. } if *root == merged.root())
+            && *clk > records[0].0
+    }));
+
+    // failed merges are not recorded
+    let num_records = provider.records().len();
+    assert!(provider
+        .merge_merkle_trees(set_a.root(), build_set(9).root())
+        .is_err());
+    assert_eq!(num_records, provider.records().len());
+}
+
+#[test]
+fn code_blocks() {
+    let block = CodeBlock::new_span(vec![Operation::Add]);
+    let root = block.hash();
+    let code_blocks = CodeBlockTable::from_iter([block]);
+    let fallback = build_provider(&[], vec![]).with_code_blocks(code_blocks);
+    let mut provider =
+        RecordingProvider::new(FallbackProvider::new(build_provider(&[], vec![]), fallback));
+
+    // code blocks unknown to the primary provider are served by the fallback provider
+    assert_eq!(
+        Some(root),
+        provider.get_code_block(root).map(|block| block.hash())
+    );
+    assert_eq!(1, provider.inner().num_fallbacks());
+    assert_eq!(&[(0, AdviceRecord::CodeBlock(root))], provider.records());
+
+    // failed lookups are not recorded
+    let unknown = CodeBlock::new_span(vec![Operation::Mul]).hash();
+    assert!(provider.get_code_block(unknown).is_none());
+    assert_eq!(1, provider.records().len());
+}
+
+#[test]
+fn program_by_hash() {
+    let program = miden_assembly::Assembler::default()
+        .compile("begin push.3 push.4 add end")
+        .unwrap();
+    let hash = program.hash();
+    let inputs = ProgramInputs::none();
+
+    // the code of a program known only by its hash is supplied by the advice provider
+    let code_blocks = CodeBlockTable::from_iter([program.root().clone()]);
+    let provider = build_provider(&[], vec![]).with_code_blocks(code_blocks);
+    let trace = execute_with_advice(&Program::from_hash(hash), &inputs, provider).unwrap();
+    assert_eq!(hash, trace.program_hash());
+    assert_eq!(Felt::new(7), trace.last_stack_state()[0]);
+
+    // the program fails to execute if its code is not available
+    let provider = build_provider(&[], vec![]);
+    let result = execute_with_advice(&Program::from_hash(hash), &inputs, provider);
+    assert!(matches!(result, Err(ExecutionError::CodeBlockNotFound(h)) if h == hash));
+}
+
+#[test]
+fn boxed_provider() {
+    let program = miden_assembly::Assembler::default()
+        .compile("begin push.adv.1 end")
+        .unwrap();
+    let inputs = ProgramInputs::none();
+
+    // providers can be selected at runtime
+    for use_fallback in [false, true] {
+        let provider: Box<dyn AdviceProvider> = if use_fallback {
+            Box::new(FallbackProvider::new(
+                build_provider(&[], vec![]),
+                build_provider(&[3], vec![]),
+            ))
+        } else {
+            Box::new(build_provider(&[3], vec![]))
+        };
+        let trace = execute_with_advice(&program, &inputs, provider).unwrap();
+        assert_eq!(Felt::new(3), trace.last_stack_state()[0]);
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+fn build_provider(tape: &[u64], sets: Vec<AdviceSet>) -> MemAdviceProvider {
+    MemAdviceProvider::new(ProgramInputs::new(&[], tape, sets).unwrap())
+}
+
+/// Builds an advice set from a Merkle tree with 4 leaves starting with the specified value.
+fn build_set(start: u64) -> AdviceSet {
+    let leaves = (start..start + 4)
+        .map(|value| [Felt::new(value), Felt::ZERO, Felt::ZERO, Felt::ZERO])
+        .collect();
+    AdviceSet::new_merkle_tree(leaves).unwrap()
+}
+
+fn updated_root(set: &AdviceSet, leaf_value: Word) -> Word {
+    let mut set = set.clone();
+    set.update_leaf(0, leaf_value).unwrap();
+    set.root()
+}
+
+fn read_tape(provider: &mut dyn AdviceProvider, num_elements: usize) -> Vec<u64> {
+    (0..num_elements)
+        .map(|_| provider.read_tape().unwrap().as_int())
+        .collect()
+}
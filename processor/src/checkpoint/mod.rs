@@ -0,0 +1,446 @@
+use super::{
+    AdviceProvider, BlockProfile, CallTrace, Chiplets, CodeBlock, CodeBlockTable, Coverage,
+    Decoder, Digest, ExecutionError, Felt, GasMeter, Kernel, MemoryPermissions, Process, Program,
+    RangeChecker, Stack, System, Vec, Word,
+};
+use crate::errors::CheckpointError;
+use vm_core::utils::{
+    bytes::{
+        read_bool, read_option, read_seq, read_usize, read_word, write_bool, write_option,
+        write_seq, write_usize, write_word,
+    },
+    ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable, SliceReader,
+};
+
+#[cfg(test)]
+mod tests;
+
+// CONSTANTS
+// ================================================================================================
+
+/// Version of the checkpoint format written by [Process::to_checkpoint()].
+///
+/// The version must be incremented whenever the layout of a checkpoint changes (e.g., when the
+/// state of any VM component changes); checkpoints with a different version are rejected.
+pub const CHECKPOINT_VERSION: u8 = 9;
+
+/// Bytes with which every checkpoint starts.
+const MAGIC: [u8; 4] = *b"MVMC";
+
+// EXECUTION STATUS
+// ================================================================================================
+
+/// Status of a program executed by [Process::execute_until()].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ExecutionStatus {
+    /// The program was executed to completion.
+    Completed,
+    /// Execution of the program was paused at the specified clock cycle; it can be resumed
+    /// later, possibly after the process was saved to and restored from a checkpoint.
+    Paused(usize),
+}
+
+// EXECUTION FRAMES
+// ================================================================================================
+
+/// Progress of a code block which was started but has not ended yet.
+///
+/// Code blocks are executed recursively; thus, a process keeps a stack of frames describing all
+/// blocks which are being executed. When execution is resumed, the frames are used to re-enter
+/// the blocks at the points at which execution was paused.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub(super) enum Frame {
+    /// A JOIN block; the value is set to true once the first child of the block was executed.
+    Join(bool),
+    /// A SPLIT block; the value specifies whether the true branch of the block is executed.
+    Split(bool),
+    /// A LOOP block whose body is being executed.
+    Loop,
+    /// A SPAN block; the value is the index of the next operation batch to be executed.
+    Span(usize),
+    /// A CALL or a SYSCALL block; the value is the state of the caller.
+    Call(CallerState),
+    /// A DYN block; the value is the MAST root of the executed procedure.
+    Dyn(Digest),
+}
+
+/// State of a caller which is restored once a called procedure returns.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub(super) struct CallerState {
+    pub ctx: u32,
+    pub fmp: Felt,
+    pub stack_depth: usize,
+    pub fn_hash: Word,
+}
+
+/// A stack of frames of the code blocks being executed.
+///
+/// While a program is executed normally, a frame is pushed whenever a block is started and
+/// popped whenever the block ends. When execution is resumed, frames of the blocks which were
+/// being executed when execution was paused are already on the stack; these are claimed one by
+/// one (starting with the root block) as the blocks are re-entered.
+#[derive(Debug, Default)]
+pub(super) struct FrameStack {
+    frames: Vec<Frame>,
+    depth: usize,
+}
+
+impl FrameStack {
+    /// Pushes a frame of a block which was just started onto the stack.
+    pub fn push(&mut self, frame: Frame) {
+        debug_assert_eq!(self.depth, self.frames.len(), "unclaimed frames");
+        self.frames.push(frame);
+        self.depth += 1;
+    }
+
+    /// Replaces the frame of the innermost block being executed with the specified frame.
+    pub fn set(&mut self, frame: Frame) {
+        debug_assert_eq!(self.depth, self.frames.len(), "unclaimed frames");
+        *self.frames.last_mut().expect("no frames") = frame;
+    }
+
+    /// Removes the frame of the innermost block being executed from the stack.
+    pub fn pop(&mut self) {
+        debug_assert_eq!(self.depth, self.frames.len(), "unclaimed frames");
+        self.frames.pop().expect("no frames");
+        self.depth -= 1;
+    }
+
+    /// Claims the next frame which was on the stack when execution was paused, if any.
+    fn claim(&mut self) -> Option<Frame> {
+        let frame = self.frames.get(self.depth).copied()?;
+        self.depth += 1;
+        Some(frame)
+    }
+
+    /// Prepares the stack for resuming execution; all frames on the stack become unclaimed.
+    fn rewind(&mut self) {
+        self.depth = 0;
+    }
+
+    /// Returns true if some of the frames on the stack have not been claimed yet, i.e., if the
+    /// blocks being entered are resumed rather than started.
+    fn is_resuming(&self) -> bool {
+        self.depth < self.frames.len()
+    }
+}
+
+// EXECUTION CONTROL
+// ================================================================================================
+
+impl<A: AdviceProvider> Process<A> {
+    /// Executes the provided [Program] in this process until the program completes, or until the
+    /// process reaches the specified clock cycle, whichever comes first. If execution of the
+    /// program was previously paused, it is resumed.
+    ///
+    /// Execution can be paused only before a code block is started or before an operation batch
+    /// of a SPAN block is executed; thus, execution is paused at the first such point at which
+    /// the clock cycle is not smaller than the specified one.
+    ///
+    /// # Errors
+    /// Returns an error if executing the program fails, or if execution of a different program
+    /// was paused in this process.
+    ///
+    /// # Panics
+    /// Panics if a program has already been executed to completion in this process.
+    pub fn execute_until(
+        &mut self,
+        program: &Program,
+        clk: usize,
+    ) -> Result<ExecutionStatus, ExecutionError> {
+        match self.paused {
+            Some(program_hash) if program_hash != program.hash() => {
+                return Err(ExecutionError::InvalidCheckpoint(self.system.clk()))
+            }
+            Some(_) => {
+                self.frames.rewind();
+                if let Some(profile) = self.profile.as_mut() {
+                    profile.rewind();
+                }
+                if let Some(trace) = self.call_trace.as_mut() {
+                    trace.rewind();
+                }
+            }
+            None => assert_eq!(
+                self.system.clk(),
+                0,
+                "a program has already been executed in this process"
+            ),
+        }
+        self.kernel = program.kernel().clone();
+        self.cb_table = program.cb_table().clone();
+        self.paused = None;
+
+        self.pause_at = Some(clk);
+        let result = self.execute_code_block(program.root());
+        self.pause_at = None;
+
+        match result {
+            Ok(()) => Ok(ExecutionStatus::Completed),
+            Err(ExecutionError::ExecutionPaused(clk)) => {
+                self.paused = Some(program.hash());
+                Ok(ExecutionStatus::Paused(clk))
+            }
+            Err(err) => Err(self.attach_call_stack(err)),
+        }
+    }
+
+    /// Resumes execution of the provided [Program] paused in this process, and executes the
+    /// program to completion.
+    ///
+    /// # Errors
+    /// Returns an error if executing the program fails, or if execution of a different program
+    /// was paused in this process.
+    ///
+    /// # Panics
+    /// Panics if execution of a program is not paused in this process.
+    pub fn resume(&mut self, program: &Program) -> Result<(), ExecutionError> {
+        assert!(self.is_paused(), "execution is not paused in this process");
+        self.execute_until(program, usize::MAX).map(|_| ())
+    }
+
+    /// Returns true if execution of a program was paused in this process and has not been resumed
+    /// yet.
+    pub fn is_paused(&self) -> bool {
+        self.paused.is_some()
+    }
+
+    /// Executes the provided [Program] in this process until a code block with the specified hash
+    /// is about to be started, or until the program completes. If execution of the program was
+    /// previously paused, it is resumed.
+    ///
+    /// # Errors
+    /// Returns an error if executing the program fails, or if execution of a different program
+    /// was paused in this process.
+    pub(super) fn execute_until_block(
+        &mut self,
+        program: &Program,
+        hash: Digest,
+    ) -> Result<ExecutionStatus, ExecutionError> {
+        self.pause_before = Some(hash);
+        let result = self.execute_until(program, usize::MAX);
+        self.pause_before = None;
+        result
+    }
+
+    /// Returns the frame of the block about to be executed if execution of the block is being
+    /// resumed, or None if the block is about to be started.
+    pub(super) fn resume_frame(&mut self) -> Option<Frame> {
+        self.frames.claim()
+    }
+
+    /// Returns an error indicating that the frame of a resumed block does not match the block.
+    pub(super) fn invalid_frame(&self) -> ExecutionError {
+        ExecutionError::InvalidCheckpoint(self.system.clk())
+    }
+
+    /// Returns an error if execution should be paused at the current clock cycle.
+    pub(super) fn check_pause(&self) -> Result<(), ExecutionError> {
+        match self.pause_at {
+            Some(clk) if self.system.clk() >= clk => {
+                Err(ExecutionError::ExecutionPaused(self.system.clk()))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Returns an error if execution should be paused before the specified block is started,
+    /// either because of the current clock cycle or because of the hash of the block. Blocks
+    /// which are resumed rather than started are not paused on because of their hash.
+    pub(super) fn check_block_pause(&self, block: &CodeBlock) -> Result<(), ExecutionError> {
+        self.check_pause()?;
+        match self.pause_before {
+            Some(hash) if hash == block.hash() && !self.frames.is_resuming() => {
+                Err(ExecutionError::ExecutionPaused(self.system.clk()))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+// CHECKPOINTS
+// ================================================================================================
+
+impl<A> Process<A>
+where
+    A: AdviceProvider + Serializable + Deserializable,
+{
+    /// Returns a checkpoint of this process, i.e., the state of all components of the process
+    /// (including the advice provider) serialized into a vector of bytes.
+    ///
+    /// A checkpoint does not contain the code of the executed program; thus, execution of a
+    /// program paused in a process restored from a checkpoint is resumed by supplying the same
+    /// program to [Process::execute_until()] or [Process::resume()]. Options of the process are
+    /// preserved, except for the expected number of cycles, which only affects memory allocation.
+    pub fn to_checkpoint(&self) -> Vec<u8> {
+        let mut target = Vec::new();
+        target.write_u8_slice(&MAGIC);
+        target.write_u8(CHECKPOINT_VERSION);
+
+        write_option(&mut target, self.paused, |target, hash| {
+            write_word(target, &hash.into())
+        });
+        write_seq(&mut target, &self.frames.frames, |target, frame| {
+            frame.write_into(target)
+        });
+        write_word(&mut target, &self.fn_hash);
+        target.write_u32(self.next_ctx);
+        write_option(&mut target, self.max_cycles, write_usize);
+        write_option(&mut target, self.meter.as_ref(), |target, meter| {
+            meter.write_into(target)
+        });
+        self.memory_permissions.write_into(&mut target);
+        write_option(&mut target, self.profile.as_ref(), |target, profile| {
+            profile.write_into(target)
+        });
+        write_option(&mut target, self.coverage.as_ref(), |target, coverage| {
+            coverage.write_into(target)
+        });
+        write_option(&mut target, self.random_seed, |target, seed| {
+            target.write_u8_slice(&seed)
+        });
+
+        self.system.write_into(&mut target);
+        self.decoder.write_into(&mut target);
+        self.stack.write_into(&mut target);
+        self.range.write_into(&mut target);
+        self.chiplets.write_into(&mut target);
+        self.advice.write_into(&mut target);
+        target
+    }
+
+    /// Returns a process restored from the specified checkpoint.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - The bytes are not a checkpoint, or the checkpoint was written in an unsupported version
+    ///   of the checkpoint format.
+    /// - The checkpoint is malformed.
+    pub fn from_checkpoint(bytes: &[u8]) -> Result<Self, CheckpointError> {
+        let mut source = SliceReader::new(bytes);
+        let magic = source
+            .read_u8_array::<4>()
+            .map_err(|_| CheckpointError::NotACheckpoint)?;
+        if magic != MAGIC {
+            return Err(CheckpointError::NotACheckpoint);
+        }
+        let version = source
+            .read_u8()
+            .map_err(CheckpointError::DeserializationError)?;
+        if version != CHECKPOINT_VERSION {
+            return Err(CheckpointError::UnsupportedVersion(version));
+        }
+
+        let process =
+            Self::read_state(&mut source).map_err(CheckpointError::DeserializationError)?;
+        if source.has_more_bytes() {
+            return Err(CheckpointError::DeserializationError(
+                DeserializationError::UnconsumedBytes,
+            ));
+        }
+        Ok(process)
+    }
+
+    /// Reads the state of a process from the source; the fields are read in the same order in
+    /// which they are written by [Process::to_checkpoint()].
+    fn read_state<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let paused = read_option(source, |source| read_word(source).map(Digest::from))?;
+        let frames = read_seq(source, Frame::read_from)?;
+        let fn_hash = read_word(source)?;
+        let next_ctx = source.read_u32()?;
+        let max_cycles = read_option(source, read_usize)?;
+        let meter = read_option(source, GasMeter::read_from)?;
+        let memory_permissions = MemoryPermissions::read_from(source)?;
+        let profile = read_option(source, BlockProfile::read_from)?;
+        let coverage = read_option(source, Coverage::read_from)?;
+        let random_seed = read_option(source, |source| source.read_u8_array::<32>())?;
+
+        let mut process = Self {
+            system: System::read_from(source)?,
+            decoder: Decoder::read_from(source)?,
+            stack: Stack::read_from(source)?,
+            range: RangeChecker::read_from(source)?,
+            chiplets: Chiplets::read_from(source)?,
+            advice: A::read_from(source)?,
+            kernel: Kernel::default(),
+            cb_table: CodeBlockTable::default(),
+            fn_hash,
+            next_ctx,
+            max_cycles,
+            meter,
+            memory_permissions,
+            profile,
+            coverage,
+            random_seed,
+            frames: FrameStack {
+                depth: frames.len(),
+                frames,
+            },
+            pause_at: None,
+            pause_before: None,
+            paused,
+            call_trace: None,
+        };
+        process.call_trace = process.decoder.in_debug_mode().then(CallTrace::default);
+        Ok(process)
+    }
+}
+
+// SERIALIZATION
+// ================================================================================================
+
+impl Serializable for Frame {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        match self {
+            Self::Join(first_done) => {
+                target.write_u8(0);
+                write_bool(target, *first_done);
+            }
+            Self::Split(condition) => {
+                target.write_u8(1);
+                write_bool(target, *condition);
+            }
+            Self::Loop => target.write_u8(2),
+            Self::Span(next_batch) => {
+                target.write_u8(3);
+                write_usize(target, *next_batch);
+            }
+            Self::Call(caller) => {
+                target.write_u8(4);
+                target.write_u32(caller.ctx);
+                caller.fmp.write_into(target);
+                write_usize(target, caller.stack_depth);
+                write_word(target, &caller.fn_hash);
+            }
+            Self::Dyn(root) => {
+                target.write_u8(5);
+                write_word(target, &(*root).into());
+            }
+        }
+    }
+}
+
+impl Deserializable for Frame {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let frame = match source.read_u8()? {
+            0 => Self::Join(read_bool(source)?),
+            1 => Self::Split(read_bool(source)?),
+            2 => Self::Loop,
+            3 => Self::Span(read_usize(source)?),
+            4 => Self::Call(CallerState {
+                ctx: source.read_u32()?,
+                fmp: Felt::read_from(source)?,
+                stack_depth: read_usize(source)?,
+                fn_hash: read_word(source)?,
+            }),
+            5 => Self::Dyn(read_word(source)?.into()),
+            tag => {
+                return Err(DeserializationError::InvalidValue(format!(
+                    "{} is not a valid frame type",
+                    tag
+                )))
+            }
+        };
+        Ok(frame)
+    }
+}
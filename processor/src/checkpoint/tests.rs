@@ -0,0 +1,299 @@
+use super::{
+    super::{
+        execute, execute_with_advice, ContextScope, ExecutionOptions, ExecutionTrace, GasSchedule,
+        MemAdviceProvider, MemoryPermission, MemoryPermissions, ProgramInputs, ROOT_CONTEXT,
+    },
+    CheckpointError, ExecutionError, ExecutionStatus, Felt, Process, Program, Vec,
+    CHECKPOINT_VERSION,
+};
+use vm_core::{
+    utils::DeserializationError, AdviceSet, CodeBlockTable, FieldElement, StarkField,
+    AUX_TRACE_RAND_ELEMENTS, TRACE_WIDTH,
+};
+use winterfell::Trace;
+
+// TEST PROGRAMS
+// ================================================================================================
+
+const KERNEL: &str = "
+    export.store
+        dup.0 pop.mem.0 push.mem.0 add
+    end";
+
+/// A program which executes all types of code blocks (except for DYN blocks), multi-batch SPAN
+/// blocks, and operations which use all chiplets.
+const PROGRAM: &str = "
+    proc.double.1
+        pop.local.0 push.local.0 push.local.0 add
+    end
+    begin
+        mtree_get
+        push.3 push.4
+        repeat.40 push.1 add end
+        push.5 push.1
+        while.true
+            sub.1 dup neq.0
+        end
+        drop
+        call.double
+        syscall.store
+        push.7 pop.mem.0 push.mem.0
+        u32checked_and
+        push.adv.1 push.adv.1
+        if.true
+            add
+        else
+            mul
+        end
+        rpperm
+        rphash
+    end";
+
+// RESUMING EXECUTION
+// ================================================================================================
+
+#[test]
+fn resume_from_checkpoints() {
+    let program = compile(PROGRAM);
+    let inputs = inputs();
+    let mut expected = execute(&program, &inputs).unwrap();
+
+    // execution is paused every few cycles, and each time the process is restored from a
+    // checkpoint; the resulting trace is the same as the trace of an uninterrupted execution
+    for step in [1, 7, 64] {
+        let mut process = Process::new(inputs.clone());
+        let num_pauses = run_with_checkpoints(&mut process, &program, step);
+        assert!(num_pauses > 1);
+        assert_traces_eq(&mut expected, process.into_trace());
+    }
+}
+
+#[test]
+fn resume_with_options() {
+    let program = compile(PROGRAM);
+    let inputs = inputs();
+
+    // options of the process are preserved in checkpoints
+    let options = ExecutionOptions::default()
+        .with_gas_schedule(GasSchedule::default())
+        .with_block_profiling()
+        .with_coverage()
+        .with_random_seed([7; 32]);
+    let mut process = Process::new(inputs.clone()).with_options(options.clone());
+    process.execute(&program).unwrap();
+    let mut expected = process.into_trace();
+
+    let mut process = Process::new(inputs.clone()).with_options(options);
+    run_with_checkpoints(&mut process, &program, 10);
+    let trace = process.into_trace();
+    assert_eq!(expected.gas_used(), trace.gas_used());
+    assert_eq!(expected.block_profile(), trace.block_profile());
+    assert_eq!(expected.coverage(), trace.coverage());
+    assert_traces_eq(&mut expected, trace);
+
+    // a cycle limit applies to the execution as a whole
+    let options = ExecutionOptions::default().with_max_cycles(50);
+    let mut process = Process::new(inputs.clone()).with_options(options);
+    let status = process.execute_until(&program, 1).unwrap();
+    assert!(matches!(status, ExecutionStatus::Paused(clk) if clk < 50));
+    let mut process: Process = Process::from_checkpoint(&process.to_checkpoint()).unwrap();
+    assert!(matches!(
+        process.resume(&program),
+        Err(ExecutionError::CycleLimitExceeded(_))
+    ));
+
+    // memory permissions are enforced after resuming execution
+    let permissions = MemoryPermissions::new().with_range(
+        ContextScope::Context(ROOT_CONTEXT),
+        0..1,
+        MemoryPermission::ReadOnly,
+    );
+    let options = ExecutionOptions::default().with_memory_permissions(permissions);
+    let mut process = Process::new(inputs).with_options(options);
+    process.execute_until(&program, 1).unwrap();
+    let mut process: Process = Process::from_checkpoint(&process.to_checkpoint()).unwrap();
+    assert!(matches!(
+        process.resume(&program),
+        Err(ExecutionError::MemoryAccessViolation(_))
+    ));
+}
+
+#[test]
+fn resume_in_debug_mode() {
+    let program = compile_debug(PROGRAM);
+    let inputs = inputs();
+
+    let mut process = Process::new_debug(inputs.clone());
+    process.execute(&program).unwrap();
+    let expected_ops = process.decoder.debug_info().operations().to_vec();
+    let expected_asmops = process.decoder.debug_info().assembly_ops().to_vec();
+
+    let mut process = Process::new_debug(inputs);
+    run_with_checkpoints(&mut process, &program, 25);
+    assert_eq!(expected_ops, process.decoder.debug_info().operations());
+    assert_eq!(expected_asmops, process.decoder.debug_info().assembly_ops());
+}
+
+#[test]
+fn resume_dyn_block() {
+    let callee = compile("begin dropw push.3 push.7 mul end");
+    let root = callee
+        .hash()
+        .as_elements()
+        .iter()
+        .map(|element| element.as_int().to_string())
+        .collect::<Vec<_>>()
+        .join(".");
+    let program = compile(&format!("begin push.2 push.{} dynexec add drop end", root));
+    let inputs = ProgramInputs::none();
+    let code_blocks = CodeBlockTable::from_iter([callee.root().clone()]);
+    let advice = || MemAdviceProvider::new(inputs.clone()).with_code_blocks(code_blocks.clone());
+    let mut expected = execute_with_advice(&program, &inputs, advice()).unwrap();
+
+    // code blocks of the advice provider are not stored in checkpoints, and thus execution is
+    // paused and resumed in the same process
+    for clk in 1..expected.length() {
+        let mut process = Process::with_advice_provider(inputs.clone(), advice());
+        let status = process.execute_until(&program, clk).unwrap();
+        assert_eq!(process.is_paused(), status != ExecutionStatus::Completed);
+        if process.is_paused() {
+            process.resume(&program).unwrap();
+        }
+        assert!(!process.is_paused());
+        assert_traces_eq(&mut expected, process.into_trace());
+    }
+}
+
+#[test]
+fn pause_points() {
+    // SPAN, 160 operations in 4 batches, END
+    let program = compile("begin repeat.80 push.1 drop end end");
+
+    // execution can be paused before the SPAN block starts, and before each RESPAN
+    let mut process = Process::new(ProgramInputs::none());
+    let mut pauses = Vec::new();
+    let mut clk = 0;
+    while let ExecutionStatus::Paused(paused_at) = process.execute_until(&program, clk).unwrap() {
+        pauses.push(paused_at);
+        clk = paused_at + 1;
+    }
+    assert_eq!(vec![0, 73, 146, 219], pauses);
+    assert!(!process.is_paused());
+}
+
+// INVALID CHECKPOINTS
+// ================================================================================================
+
+#[test]
+fn invalid_checkpoints() {
+    let program = compile(PROGRAM);
+    let mut process = Process::new(inputs());
+    process.execute_until(&program, 50).unwrap();
+    let checkpoint = process.to_checkpoint();
+
+    // not a checkpoint
+    let result = Process::<MemAdviceProvider>::from_checkpoint(&[1, 2, 3]);
+    assert!(matches!(result, Err(CheckpointError::NotACheckpoint)));
+    let mut bytes = checkpoint.clone();
+    bytes[0] = b'X';
+    let result = Process::<MemAdviceProvider>::from_checkpoint(&bytes);
+    assert!(matches!(result, Err(CheckpointError::NotACheckpoint)));
+
+    // unsupported version
+    let mut bytes = checkpoint.clone();
+    bytes[4] = CHECKPOINT_VERSION + 1;
+    let result = Process::<MemAdviceProvider>::from_checkpoint(&bytes);
+    assert!(matches!(
+        result,
+        Err(CheckpointError::UnsupportedVersion(v)) if v == CHECKPOINT_VERSION + 1
+    ));
+
+    // truncated or extended checkpoint
+    let result = Process::<MemAdviceProvider>::from_checkpoint(&checkpoint[..checkpoint.len() - 1]);
+    assert!(matches!(
+        result,
+        Err(CheckpointError::DeserializationError(
+            DeserializationError::UnexpectedEOF
+        ))
+    ));
+    let mut bytes = checkpoint.clone();
+    bytes.push(0);
+    let result = Process::<MemAdviceProvider>::from_checkpoint(&bytes);
+    assert!(matches!(
+        result,
+        Err(CheckpointError::DeserializationError(
+            DeserializationError::UnconsumedBytes
+        ))
+    ));
+
+    // execution cannot be resumed with a different program
+    let mut process = Process::<MemAdviceProvider>::from_checkpoint(&checkpoint).unwrap();
+    let other = compile("begin push.1 end");
+    assert!(matches!(
+        process.resume(&other),
+        Err(ExecutionError::InvalidCheckpoint(_))
+    ));
+    assert!(process.resume(&program).is_ok());
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+fn compile(source: &str) -> Program {
+    miden_assembly::Assembler::default()
+        .with_kernel(KERNEL)
+        .unwrap()
+        .compile(source)
+        .unwrap()
+}
+
+fn compile_debug(source: &str) -> Program {
+    miden_assembly::Assembler::new(true)
+        .with_kernel(KERNEL)
+        .unwrap()
+        .compile(source)
+        .unwrap()
+}
+
+fn inputs() -> ProgramInputs {
+    let leaves = [1, 2, 3, 4].map(|value| [Felt::new(value), Felt::ZERO, Felt::ZERO, Felt::ZERO]);
+    let tree = AdviceSet::new_merkle_tree(leaves.to_vec()).unwrap();
+    let root = tree.root().map(|element| element.as_int());
+    let stack_inputs = [root[0], root[1], root[2], root[3], 1, 2];
+    ProgramInputs::new(&stack_inputs, &[6, 1], vec![tree]).unwrap()
+}
+
+/// Executes the program in the specified process, pausing execution every `step` cycles. Every
+/// time execution is paused, the process is replaced with a process restored from a checkpoint.
+/// Returns the number of times execution was paused.
+fn run_with_checkpoints(process: &mut Process, program: &Program, step: usize) -> usize {
+    let mut num_pauses = 0;
+    let mut clk = 0;
+    while let ExecutionStatus::Paused(paused_at) = process.execute_until(program, clk).unwrap() {
+        assert!(paused_at >= clk);
+        *process = Process::from_checkpoint(&process.to_checkpoint()).unwrap();
+        num_pauses += 1;
+        clk = paused_at + step;
+    }
+    num_pauses
+}
+
+fn assert_traces_eq(expected: &mut ExecutionTrace, mut trace: ExecutionTrace) {
+    assert_eq!(expected.length(), trace.length());
+    assert_eq!(expected.program_hash(), trace.program_hash());
+    for i in 0..TRACE_WIDTH {
+        let column = trace.main_segment().get_column(i);
+        assert_eq!(expected.main_segment().get_column(i), column);
+    }
+
+    // the auxiliary segment is built from hints collected during execution
+    let rand_elements = (0..AUX_TRACE_RAND_ELEMENTS as u64)
+        .map(|i| Felt::new(i + 7))
+        .collect::<Vec<_>>();
+    let expected_aux = expected.build_aux_segment(&[], &rand_elements).unwrap();
+    let aux = trace.build_aux_segment(&[], &rand_elements).unwrap();
+    assert_eq!(expected_aux.num_cols(), aux.num_cols());
+    for i in 0..aux.num_cols() {
+        assert_eq!(expected_aux.get_column(i), aux.get_column(i));
+    }
+}
@@ -3,9 +3,15 @@ use super::{
     Vec, BITWISE_AND_LABEL, BITWISE_OR_LABEL, BITWISE_XOR_LABEL,
 };
 use crate::utils::get_trace_len;
-use vm_core::chiplets::bitwise::{
-    A_COL_IDX, BITWISE_AND, BITWISE_OR, BITWISE_XOR, B_COL_IDX, NUM_SELECTORS, OP_CYCLE_LEN,
-    OUTPUT_COL_IDX, PREV_OUTPUT_COL_IDX, TRACE_WIDTH,
+use vm_core::{
+    chiplets::bitwise::{
+        A_COL_IDX, BITWISE_AND, BITWISE_OR, BITWISE_XOR, B_COL_IDX, NUM_SELECTORS, OP_CYCLE_LEN,
+        OUTPUT_COL_IDX, PREV_OUTPUT_COL_IDX, TRACE_WIDTH,
+    },
+    utils::{
+        bytes::{read_columns, write_columns},
+        ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable,
+    },
 };
 
 #[cfg(test)]
@@ -313,3 +319,45 @@ impl LookupTableRow for BitwiseLookup {
             + alphas[4].mul_base(self.z)
     }
 }
+
+// SERIALIZATION
+// ================================================================================================
+
+impl Serializable for Bitwise {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        write_columns(target, &self.trace);
+    }
+}
+
+impl Deserializable for Bitwise {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let trace: [Vec<Felt>; TRACE_WIDTH] = read_columns(source)?;
+        let trace_len = trace[0].len();
+        if trace_len % OP_CYCLE_LEN != 0 || trace.iter().any(|column| column.len() != trace_len) {
+            return Err(DeserializationError::InvalidValue(
+                "inconsistent bitwise trace".into(),
+            ));
+        }
+        Ok(Self { trace })
+    }
+}
+
+impl Serializable for BitwiseLookup {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.op_id.write_into(target);
+        self.a.write_into(target);
+        self.b.write_into(target);
+        self.z.write_into(target);
+    }
+}
+
+impl Deserializable for BitwiseLookup {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        Ok(Self::new(
+            Felt::read_from(source)?,
+            Felt::read_from(source)?,
+            Felt::read_from(source)?,
+            Felt::read_from(source)?,
+        ))
+    }
+}
@@ -2,6 +2,10 @@ use super::{
     hasher::HasherLookup, BTreeMap, BitwiseLookup, Felt, FieldElement, LookupTableRow,
     MemoryLookup, Vec,
 };
+use vm_core::utils::{
+    bytes::{read_seq, read_usize, read_vec, write_seq, write_usize, write_vec},
+    ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable,
+};
 
 mod aux_trace;
 pub use aux_trace::AuxTraceBuilder;
@@ -221,3 +225,124 @@ impl LookupTableRow for ChipletsLookupRow {
         }
     }
 }
+
+// SERIALIZATION
+// ================================================================================================
+
+impl Serializable for ChipletsBus {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        write_seq(target, &self.lookup_hints, |target, (cycle, hint)| {
+            write_usize(target, *cycle);
+            hint.write_into(target);
+        });
+        write_vec(target, &self.request_rows);
+        write_vec(target, &self.response_rows);
+        write_vec(target, &self.queued_requests);
+    }
+}
+
+impl Deserializable for ChipletsBus {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let lookup_hints = read_seq(source, |source| {
+            Ok((read_usize(source)?, ChipletsLookup::read_from(source)?))
+        })?;
+        let request_rows: Vec<ChipletsLookupRow> = read_vec(source)?;
+        let response_rows: Vec<ChipletsLookupRow> = read_vec(source)?;
+
+        // all hints must refer to existing lookup rows
+        let is_valid_hint = |hint: &ChipletsLookup| match *hint {
+            ChipletsLookup::Request(i) => i < request_rows.len(),
+            ChipletsLookup::Response(j) => j < response_rows.len(),
+            ChipletsLookup::RequestAndResponse((i, j)) => {
+                i < request_rows.len() && j < response_rows.len()
+            }
+        };
+        if !lookup_hints.iter().all(|(_, hint)| is_valid_hint(hint)) {
+            return Err(DeserializationError::InvalidValue(
+                "invalid chiplets bus lookup hint".into(),
+            ));
+        }
+
+        Ok(Self {
+            lookup_hints: lookup_hints.into_iter().collect(),
+            request_rows,
+            response_rows,
+            queued_requests: read_vec(source)?,
+        })
+    }
+}
+
+impl Serializable for ChipletsLookup {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        match self {
+            Self::Request(i) => {
+                target.write_u8(0);
+                write_usize(target, *i);
+            }
+            Self::Response(j) => {
+                target.write_u8(1);
+                write_usize(target, *j);
+            }
+            Self::RequestAndResponse((i, j)) => {
+                target.write_u8(2);
+                write_usize(target, *i);
+                write_usize(target, *j);
+            }
+        }
+    }
+}
+
+impl Deserializable for ChipletsLookup {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        match source.read_u8()? {
+            0 => Ok(Self::Request(read_usize(source)?)),
+            1 => Ok(Self::Response(read_usize(source)?)),
+            2 => Ok(Self::RequestAndResponse((
+                read_usize(source)?,
+                read_usize(source)?,
+            ))),
+            tag => Err(DeserializationError::InvalidValue(format!(
+                "{} is not a valid chiplets lookup",
+                tag
+            ))),
+        }
+    }
+}
+
+impl Serializable for ChipletsLookupRow {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        match self {
+            Self::Hasher(lookup) => {
+                target.write_u8(0);
+                lookup.write_into(target);
+            }
+            Self::HasherMulti(lookups) => {
+                target.write_u8(1);
+                write_vec(target, lookups);
+            }
+            Self::Bitwise(lookup) => {
+                target.write_u8(2);
+                lookup.write_into(target);
+            }
+            Self::Memory(lookup) => {
+                target.write_u8(3);
+                lookup.write_into(target);
+            }
+        }
+    }
+}
+
+impl Deserializable for ChipletsLookupRow {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        match source.read_u8()? {
+            0 => Ok(Self::Hasher(HasherLookup::read_from(source)?)),
+            1 => Ok(Self::HasherMulti(read_vec(source)?)),
+            2 => Ok(Self::Bitwise(BitwiseLookup::read_from(source)?)),
+            3 => Ok(Self::Memory(MemoryLookup::read_from(source)?)),
+            tag => Err(DeserializationError::InvalidValue(format!(
+                "{} is not a valid chiplets lookup row",
+                tag
+            ))),
+        }
+    }
+}
@@ -1,5 +1,11 @@
 use super::{Felt, FieldElement, StarkField, Vec, Word};
 use crate::trace::{AuxColumnBuilder, LookupTableRow};
+use vm_core::utils::{
+    bytes::{
+        read_seq, read_usize, read_vec, read_word, write_seq, write_usize, write_vec, write_word,
+    },
+    ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable,
+};
 use winterfell::Matrix;
 
 // AUXILIARY TRACE BUILDER
@@ -144,3 +150,68 @@ impl LookupTableRow for SiblingTableRow {
         }
     }
 }
+
+// SERIALIZATION
+// ================================================================================================
+
+impl Serializable for AuxTraceBuilder {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        write_seq(target, &self.sibling_hints, |target, (step, update)| {
+            write_usize(target, *step);
+            update.write_into(target);
+        });
+        write_vec(target, &self.sibling_rows);
+    }
+}
+
+impl Deserializable for AuxTraceBuilder {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        Ok(Self {
+            sibling_hints: read_seq(source, |source| {
+                Ok((read_usize(source)?, SiblingTableUpdate::read_from(source)?))
+            })?,
+            sibling_rows: read_vec(source)?,
+        })
+    }
+}
+
+impl Serializable for SiblingTableUpdate {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        match self {
+            Self::SiblingAdded(row) => {
+                target.write_u8(0);
+                target.write_u32(*row);
+            }
+            Self::SiblingRemoved(row) => {
+                target.write_u8(1);
+                target.write_u32(*row);
+            }
+        }
+    }
+}
+
+impl Deserializable for SiblingTableUpdate {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        match source.read_u8()? {
+            0 => Ok(Self::SiblingAdded(source.read_u32()?)),
+            1 => Ok(Self::SiblingRemoved(source.read_u32()?)),
+            tag => Err(DeserializationError::InvalidValue(format!(
+                "{} is not a valid sibling table update",
+                tag
+            ))),
+        }
+    }
+}
+
+impl Serializable for SiblingTableRow {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.index.write_into(target);
+        write_word(target, &self.sibling);
+    }
+}
+
+impl Deserializable for SiblingTableRow {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        Ok(Self::new(Felt::read_from(source)?, read_word(source)?))
+    }
+}
@@ -3,6 +3,7 @@ use vm_core::chiplets::hasher::{
     CAPACITY_LEN, DIGEST_RANGE, LINEAR_HASH_LABEL, MP_VERIFY_LABEL, MR_UPDATE_NEW_LABEL,
     MR_UPDATE_OLD_LABEL, RETURN_HASH_LABEL, RETURN_STATE_LABEL, STATE_WIDTH,
 };
+use vm_core::utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable};
 
 // CONSTANTS
 // ================================================================================================
@@ -140,6 +141,52 @@ impl LookupTableRow for HasherLookup {
     }
 }
 
+// SERIALIZATION
+// ================================================================================================
+
+impl Serializable for HasherLookup {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_u8(self.label);
+        Felt::write_batch_into(&self.state, target);
+        target.write_u32(self.addr);
+        self.index.write_into(target);
+        match self.context {
+            HasherLookupContext::Start => target.write_u8(0),
+            HasherLookupContext::Absorb(state) => {
+                target.write_u8(1);
+                Felt::write_batch_into(&state, target);
+            }
+            HasherLookupContext::Return => target.write_u8(2),
+        }
+    }
+}
+
+impl Deserializable for HasherLookup {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let label = source.read_u8()?;
+        let state = read_state(source)?;
+        let addr = source.read_u32()?;
+        if addr == 0 {
+            return Err(DeserializationError::InvalidValue(
+                "hasher addresses start from one".into(),
+            ));
+        }
+        let index = Felt::read_from(source)?;
+        let context = match source.read_u8()? {
+            0 => HasherLookupContext::Start,
+            1 => HasherLookupContext::Absorb(read_state(source)?),
+            2 => HasherLookupContext::Return,
+            tag => {
+                return Err(DeserializationError::InvalidValue(format!(
+                    "{} is not a valid hasher lookup context",
+                    tag
+                )))
+            }
+        };
+        Ok(Self::new(label, state, addr, index, context))
+    }
+}
+
 // HELPER FUNCTIONS
 // ================================================================================================
 
@@ -153,3 +200,9 @@ fn build_value<E: FieldElement<BaseField = Felt>>(alphas: &[E], elements: &[Felt
     }
     value
 }
+
+/// Reads a hasher state from the source.
+fn read_state<R: ByteReader>(source: &mut R) -> Result<HasherState, DeserializationError> {
+    let state = Felt::read_batch_from(source, STATE_WIDTH)?;
+    Ok(state.try_into().expect("failed to convert vector to array"))
+}
@@ -8,6 +8,10 @@ use vm_core::chiplets::hasher::{
     MR_UPDATE_OLD, MR_UPDATE_OLD_LABEL, RETURN_HASH, RETURN_HASH_LABEL, RETURN_STATE,
     RETURN_STATE_LABEL, STATE_WIDTH, TRACE_WIDTH,
 };
+use vm_core::utils::{
+    bytes::{read_vec, write_vec},
+    ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable,
+};
 
 mod lookups;
 pub use lookups::HasherLookup;
@@ -502,6 +506,27 @@ impl Hasher {
     }
 }
 
+// SERIALIZATION
+// ================================================================================================
+
+impl Serializable for Hasher {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.trace.write_into(target);
+        self.aux_trace.write_into(target);
+        write_vec(target, &self.lookups);
+    }
+}
+
+impl Deserializable for Hasher {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        Ok(Self {
+            trace: HasherTrace::read_from(source)?,
+            aux_trace: AuxTraceBuilder::read_from(source)?,
+            lookups: read_vec(source)?,
+        })
+    }
+}
+
 // MERKLE PATH CONTEXT
 // ================================================================================================
 
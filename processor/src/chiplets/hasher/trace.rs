@@ -1,5 +1,11 @@
 use super::{Felt, HasherState, Selectors, TraceFragment, Vec, STATE_WIDTH, TRACE_WIDTH, ZERO};
-use vm_core::chiplets::hasher::{apply_round, NUM_ROUNDS};
+use vm_core::{
+    chiplets::hasher::{apply_round, NUM_ROUNDS},
+    utils::{
+        bytes::{read_columns, read_vec, write_columns, write_vec},
+        ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable,
+    },
+};
 
 // HASHER TRACE
 // ================================================================================================
@@ -126,3 +132,39 @@ impl HasherTrace {
         }
     }
 }
+
+// SERIALIZATION
+// ================================================================================================
+
+impl Serializable for HasherTrace {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        write_columns(target, &self.selectors);
+        write_vec(target, &self.row_addr);
+        write_columns(target, &self.hasher_state);
+        write_vec(target, &self.node_index);
+    }
+}
+
+impl Deserializable for HasherTrace {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let trace = Self {
+            selectors: read_columns(source)?,
+            row_addr: read_vec(source)?,
+            hasher_state: read_columns(source)?,
+            node_index: read_vec(source)?,
+        };
+        let trace_len = trace.row_addr.len();
+        let is_consistent = trace
+            .selectors
+            .iter()
+            .chain(trace.hasher_state.iter())
+            .chain([&trace.node_index])
+            .all(|column| column.len() == trace_len);
+        if !is_consistent {
+            return Err(DeserializationError::InvalidValue(
+                "inconsistent hasher trace".into(),
+            ));
+        }
+        Ok(trace)
+    }
+}
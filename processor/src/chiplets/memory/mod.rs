@@ -8,7 +8,13 @@ use super::{
     BTreeMap, ChipletsBus, Felt, FieldElement, RangeInclusive, StarkField, TraceFragment, Vec,
     Word, ONE, ZERO,
 };
-use vm_core::chiplets::memory::MEMORY_LABEL;
+use vm_core::{
+    chiplets::memory::MEMORY_LABEL,
+    utils::{
+        bytes::{read_bool, read_seq, read_word, write_bool, write_seq, write_word},
+        ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable,
+    },
+};
 
 #[cfg(test)]
 mod tests;
@@ -19,6 +25,10 @@ mod tests;
 /// Initial value of every memory cell.
 const INIT_MEM_VALUE: Word = [ZERO; 4];
 
+/// Accesses to a single memory address, each recorded as (clock cycle, word stored at the address
+/// after the access, access type).
+type AddrTrace = Vec<(Felt, Word, MemoryAccessType)>;
+
 // RANDOM ACCESS MEMORY
 // ================================================================================================
 
@@ -33,6 +43,10 @@ const INIT_MEM_VALUE: Word = [ZERO; 4];
 /// Memory for a a given address is always initialized to zeros. That is, reading from an address
 /// before writing to it will return four ZERO elements.
 ///
+/// Memory is divided into contexts: the same address in different contexts refers to different
+/// memory cells. All reads and writes are made in the current context, which is set via
+/// [Memory::set_ctx()].
+///
 /// ## Execution trace
 /// The layout of the memory access trace is shown below.
 ///
@@ -40,7 +54,8 @@ const INIT_MEM_VALUE: Word = [ZERO; 4];
 /// ├─────┴──────┴─────┴────┴────┴────┴────┴────┴────┴────┴────┴────┴────┴───────┤
 ///
 /// In the above, the meaning of the columns is as follows:
-/// - `ctx` contains context ID. Currently, context ID is always set to ZERO.
+/// - `ctx` contains context ID. Values in this column must increase monotonically but there can
+///   be gaps between two consecutive values of up to 2^32.
 /// - `addr` contains memory address. Values in this column must increase monotonically for a
 ///   given context but there can be gaps between two consecutive values of up to 2^32. Also,
 ///   two consecutive values can be the same.
@@ -67,8 +82,13 @@ pub struct Memory {
     /// Current clock cycle of the VM.
     clk: u64,
 
-    /// Memory access trace sorted first by address and then by clock cycle.
-    trace: BTreeMap<u64, Vec<(Felt, Word)>>,
+    /// Context in which memory is currently accessed.
+    ctx: u32,
+
+    /// Memory access trace sorted first by context, then by address, and then by clock cycle.
+    /// Each access is recorded together with the word stored at the address after the access,
+    /// and with the type of the access.
+    trace: BTreeMap<(u32, u64), AddrTrace>,
 
     /// Total number of entries in the trace; tracked separately so that we don't have to sum up
     /// length of all vectors in the trace map all the time.
@@ -82,6 +102,7 @@ impl Memory {
     pub fn new() -> Self {
         Self {
             clk: 0,
+            ctx: 0,
             trace: BTreeMap::new(),
             num_trace_rows: 0,
         }
@@ -96,24 +117,30 @@ impl Memory {
         self.num_trace_rows
     }
 
-    /// Returns the address and clock cycle of the first trace row, or None if the trace is empty.
-    fn get_first_row_info(&self) -> Option<(Felt, Felt)> {
+    /// Returns the context in which memory is currently accessed.
+    pub fn ctx(&self) -> u32 {
+        self.ctx
+    }
+
+    /// Returns the context, address, and clock cycle of the first trace row, or None if the trace
+    /// is empty.
+    fn get_first_row_info(&self) -> Option<(Felt, Felt, Felt)> {
         match self.trace.iter().next() {
-            Some((&addr, addr_trace)) => {
+            Some((&(ctx, addr), addr_trace)) => {
                 let clk = addr_trace[0].0;
-                Some((Felt::new(addr), clk))
+                Some((Felt::from(ctx), Felt::new(addr), clk))
             }
             None => None,
         }
     }
 
-    /// Returns a word located at the specified address, or None if the address hasn't been
-    /// accessed previously.
+    /// Returns a word located at the specified address in the current context, or None if the
+    /// address hasn't been accessed previously.
     /// Unlike read() that modifies the underlying map, get_value() only attempts to read
     /// or return None when no value exists.
     pub fn get_value(&self, addr: u64) -> Option<Word> {
-        match self.trace.get(&addr) {
-            Some(addr_trace) => addr_trace.last().map(|(_, value)| *value),
+        match self.trace.get(&(self.ctx, addr)) {
+            Some(addr_trace) => addr_trace.last().map(|(_, value, _)| *value),
             None => None,
         }
     }
@@ -125,8 +152,8 @@ impl Memory {
         self.get_value(addr.as_int()).unwrap_or(INIT_MEM_VALUE)
     }
 
-    /// Returns values within a range of addresses, or optionally all values at the beginning of
-    /// the specified cycle.
+    /// Returns values within a range of addresses in the current context, or optionally all values
+    /// at the beginning of the specified cycle.
     /// TODO: refactor to something like `pub fn get_state_at(&self, clk: u64)-> Vec<(u64, Word)>`
     pub fn get_values_at(&self, range: RangeInclusive<u64>, clk: u64) -> Vec<(u64, Word)> {
         let mut data: Vec<(u64, Word)> = Vec::new();
@@ -140,8 +167,9 @@ impl Memory {
         // end of the current cycle.
         let search_step = clk - 1;
 
-        for (&addr, addr_trace) in self.trace.range(range) {
-            match addr_trace.binary_search_by(|(x, _)| x.as_int().cmp(&search_step)) {
+        let range = (self.ctx, *range.start())..=(self.ctx, *range.end());
+        for (&(_, addr), addr_trace) in self.trace.range(range) {
+            match addr_trace.binary_search_by(|(x, ..)| x.as_int().cmp(&search_step)) {
                 Ok(i) => data.push((addr, addr_trace[i].1)),
                 Err(i) => {
                     // Binary search finds the index of the data with the specified clock cycle.
@@ -157,10 +185,36 @@ impl Memory {
         data
     }
 
+    /// Returns an iterator over all memory accesses made so far, sorted first by context, then by
+    /// address, and then by clock cycle.
+    pub fn accesses(&self) -> impl Iterator<Item = MemoryAccess> + '_ {
+        self.trace.iter().flat_map(|(&(ctx, addr), addr_trace)| {
+            let mut old_value = INIT_MEM_VALUE;
+            let mut was_written = false;
+            addr_trace
+                .iter()
+                .map(move |&(clk, new_value, access_type)| {
+                    let access = MemoryAccess {
+                        ctx,
+                        addr,
+                        clk: clk.as_int(),
+                        access_type,
+                        old_value,
+                        new_value,
+                        was_written,
+                    };
+                    old_value = new_value;
+                    was_written |= access_type == MemoryAccessType::Write;
+                    access
+                })
+        })
+    }
+
     // STATE ACCESSORS AND MUTATORS
     // --------------------------------------------------------------------------------------------
 
-    /// Returns a word (4 elements) located in memory at the specified address.
+    /// Returns a word (4 elements) located in memory at the specified address of the current
+    /// context.
     ///
     /// If the specified address hasn't been previously written to, four ZERO elements are
     /// returned. This effectively implies that memory is initialized to ZERO.
@@ -172,18 +226,18 @@ impl Memory {
         // to it; if this is the first time we access this address, create address trace for it
         // with entry (clk, [ZERO, 4]). in both cases, return the last value in the address trace.
         self.trace
-            .entry(addr.as_int())
+            .entry((self.ctx, addr.as_int()))
             .and_modify(|addr_trace| {
                 let last_value = addr_trace.last().expect("empty address trace").1;
-                addr_trace.push((clk, last_value));
+                addr_trace.push((clk, last_value, MemoryAccessType::Read));
             })
-            .or_insert_with(|| vec![(clk, INIT_MEM_VALUE)])
+            .or_insert_with(|| vec![(clk, INIT_MEM_VALUE, MemoryAccessType::Read)])
             .last()
             .expect("empty address trace")
             .1
     }
 
-    /// Writes the provided word (4 elements) at the specified address.
+    /// Writes the provided word (4 elements) at the specified address of the current context.
     pub fn write(&mut self, addr: Felt, value: Word) {
         self.num_trace_rows += 1;
         let clk = Felt::new(self.clk);
@@ -191,9 +245,9 @@ impl Memory {
         // add a tuple (clk, value) to the appropriate address trace; if this is the first time
         // we access this address, initialize address trace.
         self.trace
-            .entry(addr.as_int())
-            .and_modify(|addr_trace| addr_trace.push((clk, value)))
-            .or_insert_with(|| vec![(clk, value)]);
+            .entry((self.ctx, addr.as_int()))
+            .and_modify(|addr_trace| addr_trace.push((clk, value, MemoryAccessType::Write)))
+            .or_insert_with(|| vec![(clk, value, MemoryAccessType::Write)]);
     }
 
     // CONTEXT MANAGEMENT
@@ -204,30 +258,39 @@ impl Memory {
         self.clk += 1;
     }
 
+    /// Sets the context in which memory is accessed by subsequent reads and writes.
+    pub fn set_ctx(&mut self, ctx: u32) {
+        self.ctx = ctx;
+    }
+
     // EXECUTION TRACE GENERATION
     // --------------------------------------------------------------------------------------------
 
     /// Add all of the range checks required by the [Memory] processor to the provided
     /// [RangeChecker] processor instance, along with their row in the finalized execution trace.
     pub fn append_range_checks(&self, memory_start_row: usize, range: &mut RangeChecker) {
-        // set the previous address and clock cycle to the first address and clock cycle of the
-        // trace; we also adjust the clock cycle so that delta value for the first row would end
-        // up being ZERO. if the trace is empty, return without any further processing.
-        let (mut prev_addr, mut prev_clk) = match self.get_first_row_info() {
-            Some((addr, clk)) => (addr.as_int(), clk.as_int() - 1),
+        // set the previous context, address, and clock cycle to the first context, address, and
+        // clock cycle of the trace; we also adjust the clock cycle so that delta value for the
+        // first row would end up being ZERO. if the trace is empty, return without any further
+        // processing.
+        let (mut prev_ctx, mut prev_addr, mut prev_clk) = match self.get_first_row_info() {
+            Some((ctx, addr, clk)) => (ctx.as_int(), addr.as_int(), clk.as_int() - 1),
             None => return,
         };
 
         let mut row = memory_start_row;
         // op range check index
-        for (&addr, addr_trace) in self.trace.iter() {
+        for (&(ctx, addr), addr_trace) in self.trace.iter() {
+            let ctx = ctx as u64;
             // when we start a new address, we set the previous value to all zeros. the effect of
             // this is that memory is always initialized to zero.
-            for (clk, _) in addr_trace {
+            for (clk, ..) in addr_trace {
                 let clk = clk.as_int();
 
-                // compute delta as difference either between addresses or clock cycles
-                let delta = if prev_addr != addr {
+                // compute delta as difference between contexts, addresses, or clock cycles
+                let delta = if prev_ctx != ctx {
+                    ctx - prev_ctx
+                } else if prev_addr != addr {
                     addr - prev_addr
                 } else {
                     clk - prev_clk - 1
@@ -237,6 +300,7 @@ impl Memory {
                 range.add_mem_checks(row, &[delta_lo, delta_hi]);
 
                 // update values for the next iteration of the loop
+                prev_ctx = ctx;
                 prev_addr = addr;
                 prev_clk = clk;
                 row += 1;
@@ -253,24 +317,26 @@ impl Memory {
     ) {
         debug_assert_eq!(self.trace_len(), trace.len(), "inconsistent trace lengths");
 
-        // set the pervious address and clock cycle to the first address and clock cycle of the
-        // trace; we also adjust the clock cycle so that delta value for the first row would end
-        // up being ZERO. if the trace is empty, return without any further processing.
-        let (mut prev_addr, mut prev_clk) = match self.get_first_row_info() {
-            Some((addr, clk)) => (addr, clk - ONE),
+        // set the pervious context, address, and clock cycle to the first context, address, and
+        // clock cycle of the trace; we also adjust the clock cycle so that delta value for the
+        // first row would end up being ZERO. if the trace is empty, return without any further
+        // processing.
+        let (mut prev_ctx, mut prev_addr, mut prev_clk) = match self.get_first_row_info() {
+            Some((ctx, addr, clk)) => (ctx, addr, clk - ONE),
             None => return,
         };
 
-        // iterate through addresses in ascending order, and write trace row for each memory access
-        // into the trace. we expect the trace to be 14 columns wide.
+        // iterate through contexts and addresses in ascending order, and write trace row for each
+        // memory access into the trace. we expect the trace to be 14 columns wide.
         let mut i = 0;
-        for (addr, addr_trace) in self.trace {
+        for ((ctx, addr), addr_trace) in self.trace {
             // when we start a new address, we set the previous value to all zeros. the effect of
             // this is that memory is always initialized to zero.
+            let ctx = Felt::from(ctx);
             let addr = Felt::new(addr);
             let mut prev_value = INIT_MEM_VALUE;
-            for (clk, value) in addr_trace {
-                trace.set(i, 0, ZERO); // ctx
+            for (clk, value, _) in addr_trace {
+                trace.set(i, 0, ctx);
                 trace.set(i, 1, addr);
                 trace.set(i, 2, clk);
                 trace.set(i, 3, prev_value[0]);
@@ -282,8 +348,10 @@ impl Memory {
                 trace.set(i, 9, value[2]);
                 trace.set(i, 10, value[3]);
 
-                // compute delta as difference either between addresses or clock cycles
-                let delta = if prev_addr != addr {
+                // compute delta as difference between contexts, addresses, or clock cycles
+                let delta = if prev_ctx != ctx {
+                    ctx - prev_ctx
+                } else if prev_addr != addr {
                     addr - prev_addr
                 } else {
                     clk - prev_clk - ONE
@@ -296,10 +364,11 @@ impl Memory {
                 trace.set(i, 13, delta.inv());
 
                 // provide the memory access data to the chiplets bus.
-                let memory_lookup = MemoryLookup::new(addr, clk.as_int(), prev_value, value);
+                let memory_lookup = MemoryLookup::new(ctx, addr, clk.as_int(), prev_value, value);
                 chiplets_bus.provide_memory_operation(memory_lookup, memory_start_row + i);
 
                 // update values for the next iteration of the loop
+                prev_ctx = ctx;
                 prev_addr = addr;
                 prev_clk = clk;
                 prev_value = value;
@@ -324,6 +393,72 @@ impl Default for Memory {
     }
 }
 
+// MEMORY ACCESSES
+// ================================================================================================
+
+/// Type of a memory access.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum MemoryAccessType {
+    Read,
+    Write,
+}
+
+/// A single read or write of a memory cell made during program execution.
+///
+/// Memory accesses are recorded regardless of whether a program is executed in debug mode, and
+/// can be used, e.g., to reconstruct the final memory state of each context, to detect reads of
+/// addresses which have never been written to, or to build diffs of memory states.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct MemoryAccess {
+    ctx: u32,
+    addr: u64,
+    clk: u64,
+    access_type: MemoryAccessType,
+    old_value: Word,
+    new_value: Word,
+    was_written: bool,
+}
+
+impl MemoryAccess {
+    /// Returns the context in which the access was made.
+    pub fn ctx(&self) -> u32 {
+        self.ctx
+    }
+
+    /// Returns the accessed memory address.
+    pub fn addr(&self) -> u64 {
+        self.addr
+    }
+
+    /// Returns the clock cycle at which the access was made.
+    pub fn clk(&self) -> u64 {
+        self.clk
+    }
+
+    /// Returns the type of the access.
+    pub fn access_type(&self) -> MemoryAccessType {
+        self.access_type
+    }
+
+    /// Returns true if this is a read of an address which has not been written to previously in
+    /// the same context, i.e., a read of uninitialized memory.
+    pub fn is_uninitialized_read(&self) -> bool {
+        self.access_type == MemoryAccessType::Read && !self.was_written
+    }
+
+    /// Returns the word stored at the address before the access; the memory is initialized to
+    /// ZEROs.
+    pub fn old_value(&self) -> Word {
+        self.old_value
+    }
+
+    /// Returns the word stored at the address after the access. For reads, this is the same as
+    /// the old value.
+    pub fn new_value(&self) -> Word {
+        self.new_value
+    }
+}
+
 // MEMORY LOOKUPS
 // ================================================================================================
 
@@ -338,9 +473,9 @@ pub struct MemoryLookup {
 }
 
 impl MemoryLookup {
-    pub fn new(addr: Felt, clk: u64, old_word: Word, new_word: Word) -> Self {
+    pub fn new(ctx: Felt, addr: Felt, clk: u64, old_word: Word, new_word: Word) -> Self {
         Self {
-            ctx: ZERO,
+            ctx,
             addr,
             clk,
             old_word,
@@ -377,3 +512,73 @@ impl LookupTableRow for MemoryLookup {
             + new_word_value
     }
 }
+
+// SERIALIZATION
+// ================================================================================================
+
+impl Serializable for Memory {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_u64(self.clk);
+        target.write_u32(self.ctx);
+        write_seq(target, &self.trace, |target, ((ctx, addr), accesses)| {
+            target.write_u32(*ctx);
+            target.write_u64(*addr);
+            write_seq(target, accesses, |target, (clk, word, access_type)| {
+                clk.write_into(target);
+                write_word(target, word);
+                write_bool(target, *access_type == MemoryAccessType::Write);
+            });
+        });
+    }
+}
+
+impl Deserializable for Memory {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let clk = source.read_u64()?;
+        let ctx = source.read_u32()?;
+        let trace = read_seq(source, |source| {
+            let key = (source.read_u32()?, source.read_u64()?);
+            let accesses = read_seq(source, |source| {
+                let clk = Felt::read_from(source)?;
+                let word = read_word(source)?;
+                let access_type = match read_bool(source)? {
+                    true => MemoryAccessType::Write,
+                    false => MemoryAccessType::Read,
+                };
+                Ok((clk, word, access_type))
+            })?;
+            Ok((key, accesses))
+        })?;
+
+        // the number of trace rows is not serialized as it can be derived from the trace
+        let num_trace_rows = trace.iter().map(|(_, accesses)| accesses.len()).sum();
+        Ok(Self {
+            clk,
+            ctx,
+            trace: trace.into_iter().collect(),
+            num_trace_rows,
+        })
+    }
+}
+
+impl Serializable for MemoryLookup {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.ctx.write_into(target);
+        self.addr.write_into(target);
+        target.write_u64(self.clk);
+        write_word(target, &self.old_word);
+        write_word(target, &self.new_word);
+    }
+}
+
+impl Deserializable for MemoryLookup {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        Ok(Self::new(
+            Felt::read_from(source)?,
+            Felt::read_from(source)?,
+            source.read_u64()?,
+            read_word(source)?,
+            read_word(source)?,
+        ))
+    }
+}
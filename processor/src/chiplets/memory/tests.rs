@@ -1,6 +1,7 @@
 use super::{
     super::bus::{ChipletsLookup, ChipletsLookupRow},
-    ChipletsBus, Felt, FieldElement, Memory, MemoryLookup, StarkField, TraceFragment, ONE, ZERO,
+    ChipletsBus, Felt, FieldElement, Memory, MemoryAccessType, MemoryLookup, StarkField,
+    TraceFragment, ONE, ZERO,
 };
 use vm_core::MEMORY_TRACE_WIDTH;
 
@@ -52,18 +53,18 @@ fn mem_read() {
 
     // address 0
     let mut prev_row = [ZERO; MEMORY_TRACE_WIDTH];
-    let memory_access = MemoryLookup::new(addr0, 1, [ZERO; 4], [ZERO; 4]);
+    let memory_access = MemoryLookup::new(ZERO, addr0, 1, [ZERO; 4], [ZERO; 4]);
     prev_row = verify_memory_access(&trace, &chiplets_bus, 0, &memory_access, prev_row);
 
-    let memory_access = MemoryLookup::new(addr0, 3, [ZERO; 4], [ZERO; 4]);
+    let memory_access = MemoryLookup::new(ZERO, addr0, 3, [ZERO; 4], [ZERO; 4]);
     prev_row = verify_memory_access(&trace, &chiplets_bus, 1, &memory_access, prev_row);
 
     // address 2
-    let memory_access = MemoryLookup::new(addr2, 4, [ZERO; 4], [ZERO; 4]);
+    let memory_access = MemoryLookup::new(ZERO, addr2, 4, [ZERO; 4], [ZERO; 4]);
     prev_row = verify_memory_access(&trace, &chiplets_bus, 2, &memory_access, prev_row);
 
     // address 3
-    let memory_access = MemoryLookup::new(addr3, 2, [ZERO; 4], [ZERO; 4]);
+    let memory_access = MemoryLookup::new(ZERO, addr3, 2, [ZERO; 4], [ZERO; 4]);
     verify_memory_access(&trace, &chiplets_bus, 3, &memory_access, prev_row);
 }
 
@@ -112,18 +113,18 @@ fn mem_write() {
 
     // address 0
     let mut prev_row = [ZERO; MEMORY_TRACE_WIDTH];
-    let memory_access = MemoryLookup::new(addr0, 1, [ZERO; 4], value1);
+    let memory_access = MemoryLookup::new(ZERO, addr0, 1, [ZERO; 4], value1);
     prev_row = verify_memory_access(&trace, &chiplets_bus, 0, &memory_access, prev_row);
 
-    let memory_access = MemoryLookup::new(addr0, 4, value1, value9);
+    let memory_access = MemoryLookup::new(ZERO, addr0, 4, value1, value9);
     prev_row = verify_memory_access(&trace, &chiplets_bus, 1, &memory_access, prev_row);
 
     // address 1
-    let memory_access = MemoryLookup::new(addr1, 3, [ZERO; 4], value7);
+    let memory_access = MemoryLookup::new(ZERO, addr1, 3, [ZERO; 4], value7);
     prev_row = verify_memory_access(&trace, &chiplets_bus, 2, &memory_access, prev_row);
 
     // address 2
-    let memory_access = MemoryLookup::new(addr2, 2, [ZERO; 4], value5);
+    let memory_access = MemoryLookup::new(ZERO, addr2, 2, [ZERO; 4], value5);
     verify_memory_access(&trace, &chiplets_bus, 3, &memory_access, prev_row);
 }
 
@@ -179,35 +180,81 @@ fn mem_write_read() {
 
     // address 2
     let mut prev_row = [ZERO; MEMORY_TRACE_WIDTH];
-    let memory_access = MemoryLookup::new(addr2, 2, [ZERO; 4], value4);
+    let memory_access = MemoryLookup::new(ZERO, addr2, 2, [ZERO; 4], value4);
     prev_row = verify_memory_access(&trace, &chiplets_bus, 0, &memory_access, prev_row);
 
-    let memory_access = MemoryLookup::new(addr2, 5, value4, value4);
+    let memory_access = MemoryLookup::new(ZERO, addr2, 5, value4, value4);
     prev_row = verify_memory_access(&trace, &chiplets_bus, 1, &memory_access, prev_row);
 
-    let memory_access = MemoryLookup::new(addr2, 6, value4, value7);
+    let memory_access = MemoryLookup::new(ZERO, addr2, 6, value4, value7);
     prev_row = verify_memory_access(&trace, &chiplets_bus, 2, &memory_access, prev_row);
 
-    let memory_access = MemoryLookup::new(addr2, 8, value7, value7);
+    let memory_access = MemoryLookup::new(ZERO, addr2, 8, value7, value7);
     prev_row = verify_memory_access(&trace, &chiplets_bus, 3, &memory_access, prev_row);
 
     // address 5
-    let memory_access = MemoryLookup::new(addr5, 1, [ZERO; 4], value1);
+    let memory_access = MemoryLookup::new(ZERO, addr5, 1, [ZERO; 4], value1);
     prev_row = verify_memory_access(&trace, &chiplets_bus, 4, &memory_access, prev_row);
 
-    let memory_access = MemoryLookup::new(addr5, 3, value1, value1);
+    let memory_access = MemoryLookup::new(ZERO, addr5, 3, value1, value1);
     prev_row = verify_memory_access(&trace, &chiplets_bus, 5, &memory_access, prev_row);
 
-    let memory_access = MemoryLookup::new(addr5, 4, value1, value2);
+    let memory_access = MemoryLookup::new(ZERO, addr5, 4, value1, value2);
     prev_row = verify_memory_access(&trace, &chiplets_bus, 6, &memory_access, prev_row);
 
-    let memory_access = MemoryLookup::new(addr5, 7, value2, value2);
+    let memory_access = MemoryLookup::new(ZERO, addr5, 7, value2, value2);
     prev_row = verify_memory_access(&trace, &chiplets_bus, 7, &memory_access, prev_row);
 
-    let memory_access = MemoryLookup::new(addr5, 9, value2, value2);
+    let memory_access = MemoryLookup::new(ZERO, addr5, 9, value2, value2);
     verify_memory_access(&trace, &chiplets_bus, 8, &memory_access, prev_row);
 }
 
+#[test]
+fn mem_contexts() {
+    let mut mem = Memory::new();
+
+    // write a value into address 3 of the root context; clk = 1
+    mem.advance_clock();
+    let addr3 = Felt::new(3);
+    let value1 = [ONE, ZERO, ZERO, ZERO];
+    mem.write(addr3, value1);
+
+    // the same address of another context is not affected by the write; clk = 2
+    mem.advance_clock();
+    mem.set_ctx(1);
+    assert_eq!([ZERO; 4], mem.read(addr3));
+    let value2 = [Felt::new(2), ZERO, ZERO, ZERO];
+    mem.write(Felt::new(1), value2);
+
+    // switch back to the root context; clk = 3
+    mem.advance_clock();
+    mem.set_ctx(0);
+    assert_eq!(value1, mem.read(addr3));
+    assert_eq!(None, mem.get_value(1));
+    assert_eq!(3, mem.size());
+    assert_eq!(4, mem.trace_len());
+
+    // check generated trace and memory data provided to the ChipletsBus; rows should be sorted by
+    // context, then by address, and then by clock cycle
+    let (trace, chiplets_bus) = build_trace(mem, 4);
+
+    // context 0, address 3
+    let mut prev_row = [ZERO; MEMORY_TRACE_WIDTH];
+    let memory_access = MemoryLookup::new(ZERO, addr3, 1, [ZERO; 4], value1);
+    prev_row = verify_memory_access(&trace, &chiplets_bus, 0, &memory_access, prev_row);
+
+    let memory_access = MemoryLookup::new(ZERO, addr3, 3, value1, value1);
+    prev_row = verify_memory_access(&trace, &chiplets_bus, 1, &memory_access, prev_row);
+
+    // context 1, address 1
+    let memory_access = MemoryLookup::new(ONE, Felt::new(1), 2, [ZERO; 4], value2);
+    prev_row = verify_memory_access(&trace, &chiplets_bus, 2, &memory_access, prev_row);
+
+    // context 1, address 3
+    let memory_access = MemoryLookup::new(ONE, addr3, 2, [ZERO; 4], [ZERO; 4]);
+    verify_memory_access(&trace, &chiplets_bus, 3, &memory_access, prev_row);
+}
+
 #[test]
 fn mem_get_values_at() {
     let mut mem = Memory::new();
@@ -244,6 +291,65 @@ fn mem_get_values_at() {
     assert_eq!(vec![(2_u64, value4)], mem.get_values_at(0..=4, 4));
 }
 
+#[test]
+fn mem_accesses() {
+    let mut mem = Memory::new();
+
+    // read an uninitialized value from address 2 and write into address 1; clk = 1
+    mem.advance_clock();
+    let addr1 = Felt::new(1);
+    let addr2 = Felt::new(2);
+    let value1 = [ONE, ZERO, ZERO, ZERO];
+    mem.read(addr2);
+    mem.write(addr1, value1);
+
+    // overwrite address 1 and read it back; clk = 2, 3
+    mem.advance_clock();
+    let value2 = [Felt::new(2), ZERO, ZERO, ZERO];
+    mem.write(addr1, value2);
+    mem.advance_clock();
+    mem.read(addr1);
+
+    // read address 1 in another context; clk = 4
+    mem.advance_clock();
+    mem.set_ctx(1);
+    mem.read(addr1);
+
+    // accesses are sorted by context, then by address, and then by clock cycle
+    let accesses = mem.accesses().collect::<Vec<_>>();
+    assert_eq!(5, accesses.len());
+
+    let summary = accesses
+        .iter()
+        .map(|a| (a.ctx(), a.addr(), a.clk(), a.access_type()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        vec![
+            (0, 1, 1, MemoryAccessType::Write),
+            (0, 1, 2, MemoryAccessType::Write),
+            (0, 1, 3, MemoryAccessType::Read),
+            (0, 2, 1, MemoryAccessType::Read),
+            (1, 1, 4, MemoryAccessType::Read),
+        ],
+        summary
+    );
+
+    // old and new values track the contents of the address before and after each access
+    assert_eq!([ZERO; 4], accesses[0].old_value());
+    assert_eq!(value1, accesses[0].new_value());
+    assert_eq!(value1, accesses[1].old_value());
+    assert_eq!(value2, accesses[1].new_value());
+    assert_eq!(value2, accesses[2].old_value());
+    assert_eq!(value2, accesses[2].new_value());
+
+    // only reads of addresses which have not been written in the same context are uninitialized
+    let uninitialized = accesses
+        .iter()
+        .map(|a| a.is_uninitialized_read())
+        .collect::<Vec<_>>();
+    assert_eq!(vec![false, false, false, true, true], uninitialized);
+}
+
 // HELPER STRUCT & FUNCTIONS
 // ================================================================================================
 
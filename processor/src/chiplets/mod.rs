@@ -8,6 +8,10 @@ use vm_core::{
     chiplets::bitwise::{BITWISE_AND_LABEL, BITWISE_OR_LABEL, BITWISE_XOR_LABEL},
     chiplets::hasher::{Digest, HasherState},
     code_blocks::OpBatch,
+    utils::{
+        bytes::{read_bool, read_seq, read_usize, write_bool, write_seq, write_usize},
+        ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable,
+    },
 };
 
 mod bitwise;
@@ -19,6 +23,7 @@ pub use hasher::{AuxTraceBuilder as HasherAuxTraceBuilder, SiblingTableRow};
 
 mod memory;
 use memory::{Memory, MemoryLookup};
+pub use memory::{MemoryAccess, MemoryAccessType};
 
 mod bus;
 pub use bus::{AuxTraceBuilder, ChipletsBus};
@@ -69,9 +74,26 @@ pub struct Chiplets {
     bitwise: Bitwise,
     memory: Memory,
     bus: ChipletsBus,
+    /// Lengths of the chiplet traces at the start of each clock cycle. This is populated only when
+    /// `history_enabled` = true.
+    lengths_history: Vec<ChipletsLengths>,
+    history_enabled: bool,
 }
 
 impl Chiplets {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a new [Chiplets] instance. When `enable_history` is set to true, the lengths of the
+    /// chiplet traces are recorded at every clock cycle so that the growth of the traces can be
+    /// attributed to the operations which caused it.
+    pub fn new(enable_history: bool) -> Self {
+        Self {
+            history_enabled: enable_history,
+            ..Default::default()
+        }
+    }
+
     // PUBLIC ACCESSORS
     // --------------------------------------------------------------------------------------------
 
@@ -79,7 +101,23 @@ impl Chiplets {
     /// mandatory padding row required for ensuring sufficient trace length for auxiliary connector
     /// columns that rely on the memory chiplet.
     pub fn trace_len(&self) -> usize {
-        self.hasher.trace_len() + self.bitwise.trace_len() + self.memory.trace_len() + 1
+        self.lengths().trace_len()
+    }
+
+    /// Returns the current lengths of the traces of the individual chiplets.
+    pub fn lengths(&self) -> ChipletsLengths {
+        ChipletsLengths::new(
+            self.hasher.trace_len(),
+            self.bitwise.trace_len(),
+            self.memory.trace_len(),
+        )
+    }
+
+    /// Returns the lengths of the chiplet traces over the course of the execution such that the
+    /// entry at index i contains the lengths after i clock cycles were executed. Returns an empty
+    /// slice unless the history of lengths was enabled when this instance was created.
+    pub fn lengths_history(&self) -> &[ChipletsLengths] {
+        &self.lengths_history
     }
 
     /// Returns the index of the first row of the [Memory] execution trace.
@@ -262,10 +300,11 @@ impl Chiplets {
     /// returned. This effectively implies that memory is initialized to ZERO.
     pub fn read_mem(&mut self, addr: Felt) -> Word {
         // read the word from memory
+        let ctx = Felt::from(self.memory.ctx());
         let value = self.memory.read(addr);
 
         // send the memory read request to the bus
-        let memory_lookup = MemoryLookup::new(addr, self.clk as u64, value, value);
+        let memory_lookup = MemoryLookup::new(ctx, addr, self.clk as u64, value, value);
         self.bus.request_memory_operation(memory_lookup, self.clk);
 
         value
@@ -274,13 +313,14 @@ impl Chiplets {
     /// Writes the provided element to memory at the specified address leaving the remaining 3
     /// elements of the word previously stored at that address unchanged.
     pub fn write_mem_single(&mut self, addr: Felt, value: Felt) -> Word {
+        let ctx = Felt::from(self.memory.ctx());
         let old_word = self.memory.get_old_value(addr);
         let word = [value, old_word[1], old_word[2], old_word[3]];
 
         self.memory.write(addr, word);
 
         // send the memory write request to the bus
-        let memory_lookup = MemoryLookup::new(addr, self.clk as u64, old_word, word);
+        let memory_lookup = MemoryLookup::new(ctx, addr, self.clk as u64, old_word, word);
         self.bus.request_memory_operation(memory_lookup, self.clk);
 
         old_word
@@ -288,11 +328,12 @@ impl Chiplets {
 
     /// Writes the provided word (4 elements) to memory at the specified address.
     pub fn write_mem(&mut self, addr: Felt, word: Word) -> Word {
+        let ctx = Felt::from(self.memory.ctx());
         let old_word = self.memory.get_old_value(addr);
         self.memory.write(addr, word);
 
         // send the memory write request to the bus
-        let memory_lookup = MemoryLookup::new(addr, self.clk as u64, old_word, word);
+        let memory_lookup = MemoryLookup::new(ctx, addr, self.clk as u64, old_word, word);
         self.bus.request_memory_operation(memory_lookup, self.clk);
 
         old_word
@@ -311,6 +352,12 @@ impl Chiplets {
         self.memory.get_values_at(range, step)
     }
 
+    /// Returns all memory accesses made so far, sorted first by context, then by address, and
+    /// then by clock cycle.
+    pub fn get_mem_accesses(&self) -> Vec<MemoryAccess> {
+        self.memory.accesses().collect()
+    }
+
     /// Returns current size of the memory (in words).
     #[cfg(test)]
     pub fn get_mem_size(&self) -> usize {
@@ -322,10 +369,27 @@ impl Chiplets {
 
     /// Increments the clock cycle.
     pub fn advance_clock(&mut self) {
+        if self.history_enabled {
+            // lengths at the start of the first cycle are always zero
+            if self.lengths_history.is_empty() {
+                self.lengths_history.push(ChipletsLengths::default());
+            }
+            self.lengths_history.push(self.lengths());
+        }
         self.memory.advance_clock();
         self.clk += 1;
     }
 
+    /// Returns the context in which memory is currently accessed.
+    pub fn mem_ctx(&self) -> u32 {
+        self.memory.ctx()
+    }
+
+    /// Sets the context in which memory is accessed by subsequent memory operations.
+    pub fn set_mem_ctx(&mut self, ctx: u32) {
+        self.memory.set_ctx(ctx);
+    }
+
     // EXECUTION TRACE
     // --------------------------------------------------------------------------------------------
 
@@ -390,6 +454,7 @@ impl Chiplets {
             bitwise,
             memory,
             mut bus,
+            ..
         } = self;
 
         // allocate fragments to be filled with the respective execution traces of each chiplet
@@ -471,3 +536,86 @@ impl Chiplets {
         (hasher_aux_builder, bus.into_aux_builder())
     }
 }
+
+// CHIPLETS LENGTHS
+// ================================================================================================
+
+/// Lengths of the traces of the hasher, bitwise, and memory chiplets.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct ChipletsLengths {
+    hasher_len: usize,
+    bitwise_len: usize,
+    memory_len: usize,
+}
+
+impl ChipletsLengths {
+    /// Returns [ChipletsLengths] instantiated with the specified trace lengths of the hasher,
+    /// bitwise, and memory chiplets.
+    pub fn new(hasher_len: usize, bitwise_len: usize, memory_len: usize) -> Self {
+        Self {
+            hasher_len,
+            bitwise_len,
+            memory_len,
+        }
+    }
+
+    /// Returns the length of the hasher chiplet trace.
+    pub fn hasher_len(&self) -> usize {
+        self.hasher_len
+    }
+
+    /// Returns the length of the bitwise chiplet trace.
+    pub fn bitwise_len(&self) -> usize {
+        self.bitwise_len
+    }
+
+    /// Returns the length of the memory chiplet trace.
+    pub fn memory_len(&self) -> usize {
+        self.memory_len
+    }
+
+    /// Returns the length of the chiplets trace, i.e., the combined length of the traces of all
+    /// chiplets plus 1 mandatory padding row.
+    pub fn trace_len(&self) -> usize {
+        self.hasher_len + self.bitwise_len + self.memory_len + 1
+    }
+}
+
+// SERIALIZATION
+// ================================================================================================
+
+impl Serializable for Chiplets {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        write_usize(target, self.clk);
+        self.hasher.write_into(target);
+        self.bitwise.write_into(target);
+        self.memory.write_into(target);
+        self.bus.write_into(target);
+        write_bool(target, self.history_enabled);
+        write_seq(target, &self.lengths_history, |target, lengths| {
+            write_usize(target, lengths.hasher_len);
+            write_usize(target, lengths.bitwise_len);
+            write_usize(target, lengths.memory_len);
+        });
+    }
+}
+
+impl Deserializable for Chiplets {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        Ok(Self {
+            clk: read_usize(source)?,
+            hasher: Hasher::read_from(source)?,
+            bitwise: Bitwise::read_from(source)?,
+            memory: Memory::read_from(source)?,
+            bus: ChipletsBus::read_from(source)?,
+            history_enabled: read_bool(source)?,
+            lengths_history: read_seq(source, |source| {
+                Ok(ChipletsLengths::new(
+                    read_usize(source)?,
+                    read_usize(source)?,
+                    read_usize(source)?,
+                ))
+            })?,
+        })
+    }
+}
@@ -0,0 +1,385 @@
+use super::{AdviceProvider, Digest, Process, Span, Vec, Word};
+use core::fmt::Write;
+use vm_core::{
+    utils::{
+        bytes::{read_seq, write_seq},
+        collections::{BTreeMap, BTreeSet},
+        string::{String, ToString},
+        ByteReader, ByteWriter, Deserializable, DeserializationError, IntoBytes, Serializable,
+    },
+    SourceMap,
+};
+
+#[cfg(test)]
+mod tests;
+
+// COVERAGE
+// ================================================================================================
+
+/// Number of times each operation of each span block of a program was executed.
+///
+/// Span blocks are identified by their hashes; thus, the counts of all span blocks with the same
+/// hash (e.g., a procedure inlined at several places of a program) are combined. Span blocks which
+/// were never entered are not included. Operations are counted once they are executed
+/// successfully; NOOPs executed only to satisfy the alignment rules of span blocks are not
+/// counted.
+///
+/// Coverage is carried over when execution is paused and resumed.
+///
+/// Coverage of several executions (e.g., of the programs of a test suite) can be combined via
+/// [Coverage::merge()], and related to the source code of the programs via [CoverageReport].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Coverage {
+    spans: BTreeMap<[u8; 32], Vec<u64>>,
+    current: Option<[u8; 32]>,
+}
+
+impl Coverage {
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the number of times each operation of the span block with the specified hash was
+    /// executed, or None if such a span block was never entered.
+    pub fn get(&self, hash: Digest) -> Option<&[u64]> {
+        self.spans.get(&hash_key(hash)).map(Vec::as_slice)
+    }
+
+    /// Returns the number of distinct span blocks in this coverage.
+    pub fn len(&self) -> usize {
+        self.spans.len()
+    }
+
+    /// Returns true if no span blocks were entered.
+    pub fn is_empty(&self) -> bool {
+        self.spans.is_empty()
+    }
+
+    /// Returns an iterator over the hashes of all span blocks in this coverage (as their 32-byte
+    /// encodings), and the number of times each of their operations was executed.
+    pub fn iter(&self) -> impl Iterator<Item = ([u8; 32], &[u64])> {
+        self.spans
+            .iter()
+            .map(|(&hash, counts)| (hash, counts.as_slice()))
+    }
+
+    // STATE MUTATORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Adds the counts of the specified coverage to this coverage.
+    pub fn merge(&mut self, other: &Coverage) {
+        for (hash, counts) in other.spans.iter() {
+            let entry = self
+                .spans
+                .entry(*hash)
+                .or_insert_with(|| vec![0; counts.len()]);
+            for (total, count) in entry.iter_mut().zip(counts) {
+                *total += count;
+            }
+        }
+    }
+
+    /// Records that the specified span block is entered.
+    fn enter_span(&mut self, span: &Span) {
+        let hash = hash_key(span.hash());
+        let num_ops = span
+            .op_batches()
+            .iter()
+            .map(|batch| batch.ops().len())
+            .sum();
+        self.spans.entry(hash).or_insert_with(|| vec![0; num_ops]);
+        self.current = Some(hash);
+    }
+
+    /// Records that the operation at the specified index of the current span block was executed.
+    fn record_op(&mut self, op_idx: usize) {
+        if let Some(counts) = self.current.and_then(|hash| self.spans.get_mut(&hash)) {
+            counts[op_idx] += 1;
+        }
+    }
+}
+
+// COVERAGE REPORT
+// ================================================================================================
+
+/// Coverage of the source code of one or more programs, aggregated over any number of executions.
+///
+/// Executions are related to the source code via the source maps of the executed programs, and
+/// thus the programs must be compiled in debug mode. Instructions of a program are attributed to
+/// the file of the program, while instructions of library modules are attributed to files with
+/// paths derived from module paths (e.g., `std::math::u64` is attributed to
+/// `std/math/u64.masm`).
+///
+/// For every line of source code, the report holds the number of times the line was executed,
+/// i.e., the number of executions of its most executed instruction (summed over all places into
+/// which the instruction was inlined), and for every procedure, the number of executions of its
+/// most executed line. The report also holds a histogram of executed instructions keyed
+/// by instruction names without numeric parameters (e.g., `push.1` and `push.2` are counted as
+/// `push`).
+///
+/// Span blocks are identified by their hashes; if the same span block appears in several places
+/// of a program, executions of any of them are attributed to all of them.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CoverageReport {
+    files: BTreeMap<String, FileCoverage>,
+    instructions: BTreeMap<String, u64>,
+}
+
+/// Coverage of the lines and procedures of a single source file.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+struct FileCoverage {
+    lines: BTreeMap<u32, u64>,
+    procs: BTreeMap<String, ProcCoverage>,
+}
+
+/// Coverage of a single procedure: the first line of the procedure, and the number of times the
+/// procedure was executed.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+struct ProcCoverage {
+    line: u32,
+    count: u64,
+}
+
+impl CoverageReport {
+    // STATE MUTATORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Adds the coverage of an execution of the program described by the specified source map to
+    /// this report. Instructions of the program itself are attributed to the specified file.
+    ///
+    /// Lines of the program which were not executed are included in the report with zero counts.
+    pub fn add(&mut self, coverage: &Coverage, source_map: &SourceMap, program_file: &str) {
+        let mut execution = Self::default();
+
+        // count executions of the instructions at every source location; an instruction inlined
+        // several times into the same span block is counted once per inlined copy
+        let mut locations = BTreeMap::<_, (u64, Option<&String>)>::new();
+        let mut visited = BTreeSet::new();
+        for span in source_map.spans() {
+            let counts = coverage.spans.get(&span.hash());
+            // counts of span blocks with the same hash are shared, and thus are added only once
+            let is_first_visit = visited.insert(span.hash());
+            for instruction in span.instructions() {
+                if instruction.num_ops() == 0 {
+                    continue;
+                }
+                let count = counts.map_or(0, |counts| counts[instruction.op_idx()]);
+                if is_first_visit {
+                    *execution
+                        .instructions
+                        .entry(instruction_name(instruction.text()))
+                        .or_default() += count;
+                }
+
+                if let Some(location) = instruction.location() {
+                    let key = (location.module(), location.line(), location.column());
+                    let entry = locations
+                        .entry(key)
+                        .or_insert((0, instruction.procs().last()));
+                    entry.0 = if is_first_visit {
+                        entry.0 + count
+                    } else {
+                        entry.0.max(count)
+                    };
+                }
+            }
+        }
+
+        // a line is executed as many times as its most executed instruction, and so is a
+        // procedure
+        for ((module, line, _), (count, label)) in locations {
+            let file = match module {
+                "" => program_file.to_string(),
+                module => module_file(module),
+            };
+            let file = execution.files.entry(file).or_default();
+            let line_count = file.lines.entry(line).or_default();
+            *line_count = (*line_count).max(count);
+            if let Some(label) = label {
+                let proc = file
+                    .procs
+                    .entry(label.clone())
+                    .or_insert(ProcCoverage { line, count });
+                proc.line = proc.line.min(line);
+                proc.count = proc.count.max(count);
+            }
+        }
+        self.merge(&execution);
+    }
+
+    /// Adds the counts of the specified report to this report.
+    pub fn merge(&mut self, other: &CoverageReport) {
+        for (name, file) in other.files.iter() {
+            let total = self.files.entry(name.clone()).or_default();
+            for (&line, &count) in file.lines.iter() {
+                *total.lines.entry(line).or_default() += count;
+            }
+            for (label, proc) in file.procs.iter() {
+                let total = total.procs.entry(label.clone()).or_insert(ProcCoverage {
+                    line: proc.line,
+                    count: 0,
+                });
+                total.line = total.line.min(proc.line);
+                total.count += proc.count;
+            }
+        }
+        for (name, &count) in other.instructions.iter() {
+            *self.instructions.entry(name.clone()).or_default() += count;
+        }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns an iterator over the paths of all source files in this report.
+    pub fn files(&self) -> impl Iterator<Item = &str> {
+        self.files.keys().map(String::as_str)
+    }
+
+    /// Returns an iterator over the lines of the specified file which contain instructions, and
+    /// the number of times each of them was executed.
+    pub fn lines(&self, file: &str) -> impl Iterator<Item = (u32, u64)> + '_ {
+        self.files
+            .get(file)
+            .into_iter()
+            .flat_map(|file| file.lines.iter().map(|(&line, &count)| (line, count)))
+    }
+
+    /// Returns an iterator over the labels of the procedures of the specified file, and the
+    /// number of times each of them was executed.
+    pub fn procedures(&self, file: &str) -> impl Iterator<Item = (&str, u64)> {
+        self.files.get(file).into_iter().flat_map(|file| {
+            file.procs
+                .iter()
+                .map(|(label, proc)| (label.as_str(), proc.count))
+        })
+    }
+
+    /// Returns the number of lines with instructions in all files of this report.
+    pub fn num_lines(&self) -> usize {
+        self.files.values().map(|file| file.lines.len()).sum()
+    }
+
+    /// Returns the number of lines which were executed at least once in all files of this report.
+    pub fn num_lines_hit(&self) -> usize {
+        self.files
+            .values()
+            .map(|file| file.lines.values().filter(|&&count| count > 0).count())
+            .sum()
+    }
+
+    /// Returns the number of times instructions with each name were executed.
+    pub fn instruction_histogram(&self) -> &BTreeMap<String, u64> {
+        &self.instructions
+    }
+
+    /// Returns this report in the LCOV tracefile format, which is understood by common coverage
+    /// tools (e.g., `genhtml` or code coverage integrations of CI services).
+    pub fn to_lcov(&self) -> String {
+        let mut lcov = String::new();
+        for (name, file) in self.files.iter() {
+            writeln!(lcov, "TN:\nSF:{}", name).unwrap();
+            for (label, proc) in file.procs.iter() {
+                writeln!(lcov, "FN:{},{}", proc.line, label).unwrap();
+            }
+            for (label, proc) in file.procs.iter() {
+                writeln!(lcov, "FNDA:{},{}", proc.count, label).unwrap();
+            }
+            let procs_hit = file.procs.values().filter(|proc| proc.count > 0).count();
+            writeln!(lcov, "FNF:{}\nFNH:{}", file.procs.len(), procs_hit).unwrap();
+            for (line, count) in file.lines.iter() {
+                writeln!(lcov, "DA:{},{}", line, count).unwrap();
+            }
+            let lines_hit = file.lines.values().filter(|&&count| count > 0).count();
+            writeln!(lcov, "LF:{}\nLH:{}", file.lines.len(), lines_hit).unwrap();
+            lcov.push_str("end_of_record\n");
+        }
+        lcov
+    }
+}
+
+// SERIALIZATION
+// ================================================================================================
+
+impl Serializable for Coverage {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        write_seq(target, self.spans.iter(), |target, (hash, counts)| {
+            target.write_u8_slice(hash);
+            write_seq(target, counts.iter(), |target, &count| {
+                target.write_u64(count)
+            });
+        });
+    }
+}
+
+impl Deserializable for Coverage {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let spans = read_seq(source, |source| {
+            let hash = source.read_u8_array::<32>()?;
+            let counts = read_seq(source, |source| source.read_u64())?;
+            Ok((hash, counts))
+        })?;
+        Ok(Self {
+            spans: spans.into_iter().collect(),
+            current: None,
+        })
+    }
+}
+
+// COVERAGE PROCESS EXTENSION
+// ================================================================================================
+
+impl<A: AdviceProvider> Process<A> {
+    /// Records that the specified span block is entered, or that the current span block is
+    /// exited if None is provided. This is a noop unless the process was instantiated with
+    /// coverage recording enabled.
+    pub(super) fn coverage_span(&mut self, span: Option<&Span>) {
+        if let Some(coverage) = self.coverage.as_mut() {
+            match span {
+                Some(span) => coverage.enter_span(span),
+                None => coverage.current = None,
+            }
+        }
+    }
+
+    /// Records that the operation at the specified index of the current span block was executed.
+    /// This is a noop unless the process was instantiated with coverage recording enabled.
+    #[inline(always)]
+    pub(super) fn coverage_op(&mut self, op_idx: usize) {
+        if let Some(coverage) = self.coverage.as_mut() {
+            coverage.record_op(op_idx);
+        }
+    }
+
+    /// Returns the coverage of the operations executed by this process so far, or None if
+    /// coverage recording is not enabled for this process.
+    pub fn coverage(&self) -> Option<&Coverage> {
+        self.coverage.as_ref()
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the key under which the counts of the span block with the specified hash are stored.
+fn hash_key(hash: Digest) -> [u8; 32] {
+    Word::from(hash).into_bytes()
+}
+
+/// Returns the path of the file attributed to the library module with the specified path.
+fn module_file(module: &str) -> String {
+    format!("{}.masm", module.replace("::", "/"))
+}
+
+/// Returns the name of an instruction without its numeric parameters; e.g., `push.mem.0` is
+/// named `push.mem`.
+fn instruction_name(text: &str) -> String {
+    let mut parts = text
+        .split('.')
+        .filter(|part| !part.starts_with(|c: char| c.is_ascii_digit()));
+    let mut name = parts.next().unwrap_or_default().to_string();
+    for part in parts {
+        name.push('.');
+        name.push_str(part);
+    }
+    name
+}
@@ -0,0 +1,171 @@
+use super::{
+    super::{execute_with_options, ExecutionOptions, ProgramInputs},
+    Coverage, CoverageReport,
+};
+use vm_core::{
+    utils::{Deserializable, Serializable, SliceReader},
+    Program, SourceMap,
+};
+
+// TEST PROGRAMS
+// ================================================================================================
+
+const PROGRAM: &str = "\
+proc.double
+    dup add
+end
+proc.unused
+    push.1 drop
+end
+begin
+    push.5
+    swap
+    if.true
+        exec.double
+        exec.double
+    else
+        push.3 mul
+    end
+end";
+
+// COVERAGE RECORDING
+// ================================================================================================
+
+#[test]
+fn coverage_counts() {
+    let program = compile(PROGRAM);
+    let coverage = execute(&program, true);
+
+    // the first span and the span of the taken branch are recorded; operations of the inlined
+    // procedure are counted separately for every inlined copy
+    assert_eq!(2, coverage.len());
+    let mut counts = coverage
+        .iter()
+        .map(|(_, counts)| counts.to_vec())
+        .collect::<Vec<_>>();
+    counts.sort_unstable_by_key(Vec::len);
+    assert_eq!(vec![vec![1, 1], vec![1, 1, 1, 1]], counts);
+
+    // without coverage recording enabled, no coverage is recorded
+    let inputs = ProgramInputs::new(&[1], &[], vec![]).unwrap();
+    let trace = execute_with_options(&program, &inputs, ExecutionOptions::default()).unwrap();
+    assert!(trace.coverage().is_none());
+}
+
+#[test]
+fn coverage_merge() {
+    let program = compile(PROGRAM);
+    let mut coverage = execute(&program, true);
+    coverage.merge(&execute(&program, false));
+    coverage.merge(&execute(&program, true));
+
+    // both branches were taken; the first span was executed three times
+    assert_eq!(3, coverage.len());
+    let mut counts = coverage
+        .iter()
+        .map(|(_, counts)| counts[0])
+        .collect::<Vec<_>>();
+    counts.sort_unstable();
+    assert_eq!(vec![1, 2, 3], counts);
+    assert!(coverage
+        .iter()
+        .all(|(_, counts)| counts.iter().all(|&count| count == counts[0])));
+}
+
+#[test]
+fn coverage_serialization() {
+    let program = compile(PROGRAM);
+    let coverage = execute(&program, true);
+    let mut bytes = Vec::new();
+    coverage.write_into(&mut bytes);
+    let mut source = SliceReader::new(&bytes);
+    assert_eq!(coverage, Coverage::read_from(&mut source).unwrap());
+}
+
+// COVERAGE REPORT
+// ================================================================================================
+
+#[test]
+fn coverage_report() {
+    let program = compile(PROGRAM);
+    let source_map = SourceMap::new(&program);
+    let mut report = CoverageReport::default();
+    report.add(&execute(&program, true), &source_map, "test.masm");
+    report.add(&execute(&program, true), &source_map, "test.masm");
+
+    // lines of inlined procedures are counted once per executed copy; the procedure which is
+    // never invoked is not a part of the program
+    assert_eq!(vec!["test.masm"], report.files().collect::<Vec<_>>());
+    assert_eq!(
+        vec![(2, 4), (8, 2), (9, 2), (14, 0)],
+        report.lines("test.masm").collect::<Vec<_>>()
+    );
+    assert_eq!(4, report.num_lines());
+    assert_eq!(3, report.num_lines_hit());
+    assert_eq!(
+        vec![("double", 4)],
+        report.procedures("test.masm").collect::<Vec<_>>()
+    );
+
+    // instructions are counted regardless of their parameters
+    let histogram = report.instruction_histogram();
+    assert_eq!(Some(&2), histogram.get("push"));
+    assert_eq!(Some(&4), histogram.get("dup"));
+    assert_eq!(Some(&0), histogram.get("mul"));
+
+    assert_eq!(
+        "TN:\nSF:test.masm\nFN:2,double\nFNDA:4,double\nFNF:1\nFNH:1\n\
+        DA:2,4\nDA:8,2\nDA:9,2\nDA:14,0\nLF:4\nLH:3\nend_of_record\n",
+        report.to_lcov()
+    );
+}
+
+#[test]
+fn coverage_report_merge() {
+    let program = compile(PROGRAM);
+    let source_map = SourceMap::new(&program);
+    let mut first = CoverageReport::default();
+    first.add(&execute(&program, true), &source_map, "test.masm");
+    let mut second = CoverageReport::default();
+    second.add(&execute(&program, false), &source_map, "test.masm");
+
+    let mut expected = CoverageReport::default();
+    expected.add(&execute(&program, true), &source_map, "test.masm");
+    expected.add(&execute(&program, false), &source_map, "test.masm");
+
+    first.merge(&second);
+    assert_eq!(expected, first);
+    assert_eq!(first.num_lines(), first.num_lines_hit());
+}
+
+#[test]
+fn file_and_instruction_names() {
+    assert_eq!("std/math/u64.masm", super::module_file("std::math::u64"));
+    assert_eq!("push.mem", super::instruction_name("push.mem.0"));
+    assert_eq!("push", super::instruction_name("push.0x01.2"));
+    assert_eq!("u32checked_add", super::instruction_name("u32checked_add"));
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+fn compile(source: &str) -> Program {
+    miden_assembly::Assembler::new(true)
+        .compile(source)
+        .unwrap()
+}
+
+/// Executes the program with the specified condition on top of the stack, and returns the
+/// recorded coverage.
+fn execute(program: &Program, condition: bool) -> Coverage {
+    let inputs = ProgramInputs::new(&[condition as u64], &[], vec![]).unwrap();
+    execute_with_options(
+        program,
+        &inputs,
+        ExecutionOptions::default().with_coverage(),
+    )
+    .unwrap()
+    .coverage()
+    .cloned()
+    .unwrap()
+}
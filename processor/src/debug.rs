@@ -1,6 +1,15 @@
-use crate::{ExecutionError, Felt, Process, StarkField, Vec};
+use crate::{
+    AdviceProvider, ChipletsLengths, Digest, ExecutionError, Felt, Process, StarkField,
+    TraceLenSummary, Vec,
+};
 use core::fmt;
-use vm_core::{utils::string::String, Operation, Word};
+use vm_core::{
+    utils::{hex::digest_to_hex, string::String},
+    Operation, Word,
+};
+
+#[cfg(not(feature = "std"))]
+use alloc::boxed::Box;
 
 /// VmState holds a current process state information at a specific clock cycle.
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -51,6 +60,31 @@ impl VmStateIterator {
         }
     }
 
+    /// Returns a summary of the lengths of the trace components resulting from the execution; if
+    /// the execution failed, the summary describes the trace up to the point of failure.
+    pub fn trace_len_summary(&self) -> TraceLenSummary {
+        let mut range = self.process.range.clone();
+        self.process.chiplets.append_range_checks(&mut range);
+        TraceLenSummary::new(
+            self.process.system.clk(),
+            range.trace_len(),
+            self.process.chiplets.lengths(),
+        )
+    }
+
+    /// Returns the lengths of the traces of the individual chiplets at the specified clock cycle,
+    /// i.e., after the operations of all preceding cycles were executed.
+    ///
+    /// The difference between the lengths at two consecutive clock cycles is the number of chiplet
+    /// rows added by the operation executed at the latter cycle.
+    pub fn chiplets_lengths_at(&self, clk: usize) -> ChipletsLengths {
+        let history = self.process.chiplets.lengths_history();
+        match history.get(clk) {
+            Some(lengths) => *lengths,
+            None => history.last().copied().unwrap_or_default(),
+        }
+    }
+
     /// Returns the asm op info corresponding to this vm state and whether this is the start of
     /// operation sequence corresponding to current assembly instruction.
     fn get_asmop(&self) -> (Option<AsmOpInfo>, bool) {
@@ -213,3 +247,221 @@ impl AsmOpInfo {
         self.cycle_idx
     }
 }
+
+// CALL STACK
+// ================================================================================================
+
+/// The chain of procedures which were being executed when execution of a program failed.
+///
+/// Call stacks are collected only when a program compiled in debug mode is executed in debug
+/// mode. Procedures inlined via `exec` are identified by their labels, while procedures executed
+/// via `call`, `syscall`, or `dynexec`, and procedures executed by their MAST roots, are
+/// identified by their MAST roots (and are followed by the labels of the procedures they inlined).
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CallStack {
+    clk: usize,
+    frames: Vec<CallStackFrame>,
+    span_op: Option<(Digest, usize)>,
+}
+
+impl CallStack {
+    /// Returns the clock cycle at which execution failed.
+    pub fn clk(&self) -> usize {
+        self.clk
+    }
+
+    /// Returns the procedures which were being executed when execution failed, starting with the
+    /// outermost one. The code of the program itself is not listed.
+    pub fn frames(&self) -> &[CallStackFrame] {
+        &self.frames
+    }
+
+    /// Returns the hash of the span block which was being executed when execution failed,
+    /// together with the index of the failed operation within the span block. Returns None if
+    /// execution failed outside of a span block (e.g., when a control block was started).
+    ///
+    /// The failed operation can be mapped to the assembly instruction it was compiled from via
+    /// the [SourceMap](vm_core::SourceMap) of the program.
+    pub fn span_op(&self) -> Option<(Digest, usize)> {
+        self.span_op
+    }
+}
+
+impl fmt::Display for CallStack {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "at clock cycle {}", self.clk)?;
+        for (i, frame) in self.frames.iter().enumerate() {
+            write!(f, "\n  {}: {}", i, frame)?;
+        }
+        Ok(())
+    }
+}
+
+/// A procedure listed in a [CallStack].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum CallStackFrame {
+    /// A procedure identified by its label.
+    Procedure(String),
+    /// A procedure identified by its MAST root.
+    Root(Digest),
+}
+
+impl fmt::Display for CallStackFrame {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Procedure(label) => write!(f, "{}", label),
+            Self::Root(root) => write!(f, "{}", digest_to_hex(*root)),
+        }
+    }
+}
+
+// CALL TRACE
+// ================================================================================================
+
+/// Keeps track of the procedures being executed by a process in debug mode.
+///
+/// The trace consists of a frame for the program itself, followed by a frame for every procedure
+/// being executed via `call`, `syscall`, `dynexec`, or by its MAST root. Each frame holds the
+/// chain of procedures inlined via `exec` which is being executed in it, as reported by the
+/// [Decorator::ProcPath](vm_core::Decorator::ProcPath) decorators of span blocks. Since the chain
+/// is updated only at the start of a span block, failures of control flow operations which follow
+/// an inlined procedure may be attributed to that procedure.
+#[derive(Debug)]
+pub(super) struct CallTrace {
+    frames: Vec<CallFrame>,
+    span_op: Option<(Digest, usize)>,
+}
+
+#[derive(Debug)]
+struct CallFrame {
+    root: Option<Digest>,
+    procs: Vec<String>,
+}
+
+impl Default for CallTrace {
+    fn default() -> Self {
+        Self {
+            frames: vec![CallFrame {
+                root: None,
+                procs: Vec::new(),
+            }],
+            span_op: None,
+        }
+    }
+}
+
+impl CallTrace {
+    /// Adds a frame for the procedure with the specified MAST root.
+    fn enter(&mut self, root: Digest) {
+        self.frames.push(CallFrame {
+            root: Some(root),
+            procs: Vec::new(),
+        });
+    }
+
+    /// Removes the frame of the innermost procedure; the frame of the program is never removed.
+    fn exit(&mut self) {
+        if self.frames.len() > 1 {
+            self.frames.pop();
+        }
+    }
+
+    /// Sets the chain of inlined procedures being executed in the innermost frame.
+    fn set_procs(&mut self, procs: &[String]) {
+        let frame = self.frames.last_mut().expect("no frames");
+        frame.procs.clear();
+        frame.procs.extend_from_slice(procs);
+    }
+
+    /// Sets the span block being executed, or clears it if None is provided.
+    fn set_span(&mut self, hash: Option<Digest>) {
+        self.span_op = hash.map(|hash| (hash, 0));
+    }
+
+    /// Sets the index of the operation being executed within the current span block.
+    fn set_op(&mut self, op_idx: usize) {
+        if let Some((_, idx)) = self.span_op.as_mut() {
+            *idx = op_idx;
+        }
+    }
+
+    /// Prepares the trace for resuming execution; the frames of procedures are added again as
+    /// the procedures are resumed.
+    pub fn rewind(&mut self) {
+        self.frames.truncate(1);
+    }
+
+    /// Returns the call stack described by this call trace at the specified clock cycle.
+    fn to_call_stack(&self, clk: usize) -> CallStack {
+        let mut frames = Vec::new();
+        for frame in self.frames.iter() {
+            frames.extend(frame.root.map(CallStackFrame::Root));
+            frames.extend(frame.procs.iter().cloned().map(CallStackFrame::Procedure));
+        }
+        CallStack {
+            clk,
+            frames,
+            span_op: self.span_op,
+        }
+    }
+}
+
+impl<A: AdviceProvider> Process<A> {
+    /// Records that the procedure with the specified MAST root is entered. This is a noop unless
+    /// the process is executing in debug mode.
+    pub(super) fn trace_enter(&mut self, root: Digest) {
+        if let Some(trace) = self.call_trace.as_mut() {
+            trace.enter(root);
+        }
+    }
+
+    /// Records that the innermost procedure entered via [Process::trace_enter()] returned. This
+    /// is a noop unless the process is executing in debug mode.
+    pub(super) fn trace_exit(&mut self) {
+        if let Some(trace) = self.call_trace.as_mut() {
+            trace.exit();
+        }
+    }
+
+    /// Records that the span block with the specified hash is entered, or that the current span
+    /// block is exited if None is provided. This is a noop unless the process is executing in
+    /// debug mode.
+    pub(super) fn trace_span(&mut self, hash: Option<Digest>) {
+        if let Some(trace) = self.call_trace.as_mut() {
+            trace.set_span(hash);
+        }
+    }
+
+    /// Records the index of the operation being executed within the current span block. This is
+    /// a noop unless the process is executing in debug mode.
+    #[inline(always)]
+    pub(super) fn trace_op(&mut self, op_idx: usize) {
+        if let Some(trace) = self.call_trace.as_mut() {
+            trace.set_op(op_idx);
+        }
+    }
+
+    /// Records the chain of inlined procedures to which the operations being executed belong.
+    /// This is a noop unless the process is executing in debug mode.
+    pub(super) fn trace_procs(&mut self, procs: &[String]) {
+        if let Some(trace) = self.call_trace.as_mut() {
+            trace.set_procs(procs);
+        }
+    }
+
+    /// Attaches the call stack at the current clock cycle to the specified error if the process
+    /// is executing in debug mode; otherwise, the error is returned unchanged.
+    ///
+    /// Pausing execution is not a failure, and thus [ExecutionError::ExecutionPaused] errors are
+    /// always returned unchanged.
+    pub(super) fn attach_call_stack(&self, error: ExecutionError) -> ExecutionError {
+        match (&self.call_trace, error) {
+            (_, error @ ExecutionError::ExecutionPaused(_)) => error,
+            (Some(trace), error) => ExecutionError::WithCallStack(
+                Box::new(error),
+                trace.to_call_stack(self.system.clk()),
+            ),
+            (None, error) => error,
+        }
+    }
+}
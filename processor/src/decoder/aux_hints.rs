@@ -2,6 +2,13 @@ use super::{
     super::trace::LookupTableRow, get_num_groups_in_next_batch, BlockInfo, Felt, FieldElement,
     StarkField, Vec, Word, ONE, ZERO,
 };
+use vm_core::utils::{
+    bytes::{
+        read_bool, read_seq, read_usize, read_vec, read_word, write_bool, write_seq, write_usize,
+        write_vec, write_word,
+    },
+    ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable,
+};
 
 // AUXILIARY TRACE HINTS
 // ================================================================================================
@@ -430,3 +437,151 @@ impl LookupTableRow for OpGroupTableRow {
             + alphas[3].mul_base(self.group_value)
     }
 }
+
+// SERIALIZATION
+// ================================================================================================
+
+impl Serializable for AuxTraceHints {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        write_seq(target, &self.block_exec_hints, |target, (clk, update)| {
+            write_usize(target, *clk);
+            update.write_into(target);
+        });
+        write_vec(target, &self.block_stack_rows);
+        write_vec(target, &self.block_hash_rows);
+        write_seq(target, &self.op_group_hints, |target, (clk, update)| {
+            write_usize(target, *clk);
+            update.write_into(target);
+        });
+        write_vec(target, &self.op_group_rows);
+    }
+}
+
+impl Deserializable for AuxTraceHints {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        Ok(Self {
+            block_exec_hints: read_seq(source, |source| {
+                Ok((read_usize(source)?, BlockTableUpdate::read_from(source)?))
+            })?,
+            block_stack_rows: read_vec(source)?,
+            block_hash_rows: read_vec(source)?,
+            op_group_hints: read_seq(source, |source| {
+                Ok((read_usize(source)?, OpGroupTableUpdate::read_from(source)?))
+            })?,
+            op_group_rows: read_vec(source)?,
+        })
+    }
+}
+
+impl Serializable for BlockTableUpdate {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        match self {
+            Self::BlockStarted(num_children) => {
+                target.write_u8(0);
+                target.write_u32(*num_children);
+            }
+            Self::SpanExtended => target.write_u8(1),
+            Self::LoopRepeated => target.write_u8(2),
+            Self::BlockEnded(is_first_child) => {
+                target.write_u8(3);
+                write_bool(target, *is_first_child);
+            }
+        }
+    }
+}
+
+impl Deserializable for BlockTableUpdate {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        match source.read_u8()? {
+            0 => Ok(Self::BlockStarted(source.read_u32()?)),
+            1 => Ok(Self::SpanExtended),
+            2 => Ok(Self::LoopRepeated),
+            3 => Ok(Self::BlockEnded(read_bool(source)?)),
+            tag => Err(DeserializationError::InvalidValue(format!(
+                "{} is not a valid block table update",
+                tag
+            ))),
+        }
+    }
+}
+
+impl Serializable for OpGroupTableUpdate {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        match self {
+            Self::InsertRows(num_rows) => {
+                target.write_u8(0);
+                target.write_u32(*num_rows);
+            }
+            Self::RemoveRow => target.write_u8(1),
+        }
+    }
+}
+
+impl Deserializable for OpGroupTableUpdate {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        match source.read_u8()? {
+            0 => Ok(Self::InsertRows(source.read_u32()?)),
+            1 => Ok(Self::RemoveRow),
+            tag => Err(DeserializationError::InvalidValue(format!(
+                "{} is not a valid op group table update",
+                tag
+            ))),
+        }
+    }
+}
+
+impl Serializable for BlockStackTableRow {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.block_id.write_into(target);
+        self.parent_id.write_into(target);
+        write_bool(target, self.is_loop);
+    }
+}
+
+impl Deserializable for BlockStackTableRow {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        Ok(Self {
+            block_id: Felt::read_from(source)?,
+            parent_id: Felt::read_from(source)?,
+            is_loop: read_bool(source)?,
+        })
+    }
+}
+
+impl Serializable for BlockHashTableRow {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.parent_id.write_into(target);
+        write_word(target, &self.block_hash);
+        write_bool(target, self.is_first_child);
+        write_bool(target, self.is_loop_body);
+    }
+}
+
+impl Deserializable for BlockHashTableRow {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        Ok(Self {
+            parent_id: Felt::read_from(source)?,
+            block_hash: read_word(source)?,
+            is_first_child: read_bool(source)?,
+            is_loop_body: read_bool(source)?,
+        })
+    }
+}
+
+impl Serializable for OpGroupTableRow {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.batch_id.write_into(target);
+        self.group_pos.write_into(target);
+        self.group_value.write_into(target);
+    }
+}
+
+impl Deserializable for OpGroupTableRow {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        Ok(Self {
+            batch_id: Felt::read_from(source)?,
+            group_pos: Felt::read_from(source)?,
+            group_value: Felt::read_from(source)?,
+        })
+    }
+}
@@ -1,6 +1,7 @@
 use super::{
-    ExecutionError, Felt, FieldElement, Join, Loop, OpBatch, Operation, Process, Span, Split,
-    StarkField, Vec, Word, MIN_TRACE_LEN, ONE, OP_BATCH_SIZE, ZERO,
+    AdviceProvider, Call, Digest, Dyn, ExecutionError, Felt, FieldElement, Join, Loop, OpBatch,
+    Operation, Process, Span, Split, StarkField, Vec, Word, MIN_TRACE_LEN, ONE, OP_BATCH_SIZE,
+    ZERO,
 };
 use vm_core::{
     chiplets::hasher::DIGEST_LEN,
@@ -9,6 +10,13 @@ use vm_core::{
         NUM_HASHER_COLUMNS, NUM_OP_BATCH_FLAGS, NUM_OP_BITS, OP_BATCH_1_GROUPS, OP_BATCH_2_GROUPS,
         OP_BATCH_4_GROUPS, OP_BATCH_8_GROUPS,
     },
+    utils::{
+        bytes::{
+            read_bool, read_option, read_seq, read_usize, read_vec, write_bool, write_option,
+            write_seq, write_usize, write_vec,
+        },
+        ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable,
+    },
     AssemblyOp,
 };
 
@@ -32,12 +40,14 @@ const HASH_CYCLE_LEN: Felt = Felt::new(vm_core::chiplets::hasher::HASH_CYCLE_LEN
 // DECODER PROCESS EXTENSION
 // ================================================================================================
 
-impl Process {
+impl<A: AdviceProvider> Process<A> {
     // JOIN BLOCK
     // --------------------------------------------------------------------------------------------
 
     /// Starts decoding of a JOIN block.
     pub(super) fn start_join_block(&mut self, block: &Join) -> Result<(), ExecutionError> {
+        self.charge_gas(Operation::Join)?;
+
         // use the hasher to compute the hash of the JOIN block; the row address returned by the
         // hasher is used as the ID of the block; the result of the hash is expected to be in
         // row addr + 7.
@@ -55,6 +65,8 @@ impl Process {
 
     ///  Ends decoding of a JOIN block.
     pub(super) fn end_join_block(&mut self, block: &Join) -> Result<(), ExecutionError> {
+        self.charge_gas(Operation::End)?;
+
         // this appends a row with END operation to the decoder trace. when END operation is
         // executed the rest of the VM state does not change
         self.decoder.end_control_block(block.hash().into());
@@ -71,6 +83,8 @@ impl Process {
     /// Starts decoding a SPLIT block. This also pops the value from the top of the stack and
     /// returns it.
     pub(super) fn start_split_block(&mut self, block: &Split) -> Result<Felt, ExecutionError> {
+        self.charge_gas(Operation::Split)?;
+
         let condition = self.stack.peek();
 
         // use the hasher to compute the hash of the SPLIT block; the row address returned by the
@@ -92,6 +106,8 @@ impl Process {
 
     /// Ends decoding of a SPLIT block.
     pub(super) fn end_split_block(&mut self, block: &Split) -> Result<(), ExecutionError> {
+        self.charge_gas(Operation::End)?;
+
         // this appends a row with END operation to the decoder trace. when END operation is
         // executed the rest of the VM state does not change
         self.decoder.end_control_block(block.hash().into());
@@ -108,6 +124,8 @@ impl Process {
     /// Starts decoding a LOOP block. This also pops the value from the top of the stack and
     /// returns it.
     pub(super) fn start_loop_block(&mut self, block: &Loop) -> Result<Felt, ExecutionError> {
+        self.charge_gas(Operation::Loop)?;
+
         let condition = self.stack.peek();
 
         // use the hasher to compute the hash of the LOOP block; for LOOP block there is no
@@ -136,6 +154,8 @@ impl Process {
         block: &Loop,
         pop_stack: bool,
     ) -> Result<(), ExecutionError> {
+        self.charge_gas(Operation::End)?;
+
         // this appends a row with END operation to the decoder trace.
         self.decoder.end_control_block(block.hash().into());
 
@@ -159,11 +179,126 @@ impl Process {
         }
     }
 
+    // DYN BLOCK
+    // --------------------------------------------------------------------------------------------
+
+    /// Starts decoding of a DYN block which executes the procedure with the specified root.
+    pub(super) fn start_dyn_block(
+        &mut self,
+        block: &Dyn,
+        root: Digest,
+    ) -> Result<(), ExecutionError> {
+        self.charge_gas(Operation::Dyn)?;
+
+        // use the hasher to compute the hash of the DYN block; the hash does not depend on the
+        // executed procedure, and thus it is computed from a constant pair of words. the row
+        // address returned by the hasher is used as the ID of the block.
+        let addr = self
+            .chiplets
+            .hash_control_block([ZERO; 4], Dyn::DOMAIN, block.hash());
+
+        // start decoding the DYN block; this appends a row with DYN operation to the decoder
+        // trace. when DYN operation is executed, the rest of the VM state does not change
+        self.decoder.start_dyn(root.into(), addr);
+        self.execute_op(Operation::Noop)
+    }
+
+    /// Ends decoding of a DYN block.
+    pub(super) fn end_dyn_block(&mut self, block: &Dyn) -> Result<(), ExecutionError> {
+        self.charge_gas(Operation::End)?;
+
+        // this appends a row with END operation to the decoder trace. when END operation is
+        // executed the rest of the VM state does not change
+        self.decoder.end_control_block(block.hash().into());
+
+        // send the end of control block to the chiplets bus to handle the final hash request.
+        self.chiplets.read_hash_result();
+
+        self.execute_op(Operation::Noop)
+    }
+
+    // CALL BLOCK
+    // --------------------------------------------------------------------------------------------
+
+    /// Starts decoding of a CALL block.
+    pub(super) fn start_call_block(&mut self, block: &Call) -> Result<(), ExecutionError> {
+        self.charge_gas(Operation::Call)?;
+
+        debug_assert!(!block.is_syscall(), "not a call block");
+
+        // use the hasher to compute the hash of the CALL block; the row address returned by the
+        // hasher is used as the ID of the block; the result of the hash is expected to be in row
+        // addr + 7.
+        let fn_hash = block.fn_hash().into();
+        let addr = self
+            .chiplets
+            .hash_control_block(fn_hash, Call::CALL_DOMAIN, block.hash());
+
+        // start decoding the CALL block; this appends a row with CALL operation to the decoder
+        // trace. when CALL operation is executed, the rest of the VM state does not change
+        self.decoder.start_call(fn_hash, addr);
+        self.execute_op(Operation::Noop)
+    }
+
+    /// Ends decoding of a CALL block.
+    pub(super) fn end_call_block(&mut self, block: &Call) -> Result<(), ExecutionError> {
+        self.charge_gas(Operation::End)?;
+
+        // this appends a row with END operation to the decoder trace. when END operation is
+        // executed the rest of the VM state does not change
+        self.decoder.end_control_block(block.hash().into());
+
+        // send the end of control block to the chiplets bus to handle the final hash request.
+        self.chiplets.read_hash_result();
+
+        self.execute_op(Operation::Noop)
+    }
+
+    // SYSCALL BLOCK
+    // --------------------------------------------------------------------------------------------
+
+    /// Starts decoding of a SYSCALL block.
+    pub(super) fn start_syscall_block(&mut self, block: &Call) -> Result<(), ExecutionError> {
+        self.charge_gas(Operation::SysCall)?;
+
+        debug_assert!(block.is_syscall(), "not a syscall block");
+
+        // use the hasher to compute the hash of the SYSCALL block; the row address returned by
+        // the hasher is used as the ID of the block; the result of the hash is expected to be in
+        // row addr + 7.
+        let fn_hash = block.fn_hash().into();
+        let addr = self
+            .chiplets
+            .hash_control_block(fn_hash, Call::SYSCALL_DOMAIN, block.hash());
+
+        // start decoding the SYSCALL block; this appends a row with SYSCALL operation to the
+        // decoder trace. when SYSCALL operation is executed, the rest of the VM state does not
+        // change
+        self.decoder.start_syscall(fn_hash, addr);
+        self.execute_op(Operation::Noop)
+    }
+
+    /// Ends decoding of a SYSCALL block.
+    pub(super) fn end_syscall_block(&mut self, block: &Call) -> Result<(), ExecutionError> {
+        self.charge_gas(Operation::End)?;
+
+        // this appends a row with END operation to the decoder trace. when END operation is
+        // executed the rest of the VM state does not change
+        self.decoder.end_control_block(block.hash().into());
+
+        // send the end of control block to the chiplets bus to handle the final hash request.
+        self.chiplets.read_hash_result();
+
+        self.execute_op(Operation::Noop)
+    }
+
     // SPAN BLOCK
     // --------------------------------------------------------------------------------------------
 
     /// Starts decoding a SPAN block.
     pub(super) fn start_span_block(&mut self, block: &Span) -> Result<(), ExecutionError> {
+        self.charge_gas(Operation::Span)?;
+
         // use the hasher to compute the hash of the SPAN block; the row address returned by the
         // hasher is used as the ID of the block; hash of a SPAN block is computed by sequentially
         // hashing operation batches. Thus, the result of the hash is expected to be in row
@@ -192,6 +327,8 @@ impl Process {
 
     /// Ends decoding a SPAN block.
     pub(super) fn end_span_block(&mut self, block: &Span) -> Result<(), ExecutionError> {
+        self.charge_gas(Operation::End)?;
+
         // this appends a row with END operation to the decoder trace. when END operation is
         // executed the rest of the VM state does not change
         self.decoder.end_span(block.hash().into());
@@ -388,6 +525,71 @@ impl Decoder {
         self.debug_info.append_operation(Operation::Loop);
     }
 
+    /// Starts decoding of a DYN block.
+    ///
+    /// This pushes a block with ID=addr onto the block stack and appends execution of a DYN
+    /// operation to the trace. The root of the executed procedure is recorded as the only child of
+    /// the block.
+    pub fn start_dyn(&mut self, root: Word, addr: Felt) {
+        // get the current clock cycle here (before the trace table is updated)
+        let clk = self.trace_len();
+
+        // append a DYN row to the execution trace
+        let parent_addr = self.block_stack.push(addr, BlockType::Dyn);
+        self.trace
+            .append_block_start(parent_addr, Operation::Dyn, root, [ZERO; 4]);
+
+        // mark this cycle as the cycle at which a DYN block began execution (this affects block
+        // stack and block hash tables). The executed procedure is the only child of the block.
+        self.aux_hints
+            .block_started(clk, self.block_stack.peek(), Some(root), None);
+
+        self.debug_info.append_operation(Operation::Dyn);
+    }
+
+    /// Starts decoding of a CALL block.
+    ///
+    /// This pushes a block with ID=addr onto the block stack and appends execution of a CALL
+    /// operation to the trace. The body of the called procedure is the only child of the block.
+    pub fn start_call(&mut self, fn_hash: Word, addr: Felt) {
+        // get the current clock cycle here (before the trace table is updated)
+        let clk = self.trace_len();
+
+        // append a CALL row to the execution trace
+        let parent_addr = self.block_stack.push(addr, BlockType::Call);
+        self.trace
+            .append_block_start(parent_addr, Operation::Call, fn_hash, [ZERO; 4]);
+
+        // mark this cycle as the cycle at which a CALL block began execution (this affects block
+        // stack and block hash tables). The called procedure is the only child of the block.
+        self.aux_hints
+            .block_started(clk, self.block_stack.peek(), Some(fn_hash), None);
+
+        self.debug_info.append_operation(Operation::Call);
+    }
+
+    /// Starts decoding of a SYSCALL block.
+    ///
+    /// This pushes a block with ID=addr onto the block stack and appends execution of a SYSCALL
+    /// operation to the trace. The body of the invoked kernel procedure is the only child of the
+    /// block.
+    pub fn start_syscall(&mut self, fn_hash: Word, addr: Felt) {
+        // get the current clock cycle here (before the trace table is updated)
+        let clk = self.trace_len();
+
+        // append a SYSCALL row to the execution trace
+        let parent_addr = self.block_stack.push(addr, BlockType::SysCall);
+        self.trace
+            .append_block_start(parent_addr, Operation::SysCall, fn_hash, [ZERO; 4]);
+
+        // mark this cycle as the cycle at which a SYSCALL block began execution (this affects
+        // block stack and block hash tables). The kernel procedure is the only child of the block.
+        self.aux_hints
+            .block_started(clk, self.block_stack.peek(), Some(fn_hash), None);
+
+        self.debug_info.append_operation(Operation::SysCall);
+    }
+
     /// Starts decoding another iteration of a loop.
     ///
     /// This appends an execution of a REPEAT operation to the trace.
@@ -613,6 +815,11 @@ impl Decoder {
         self.debug_info.append_asmop(clk, asmop);
     }
 
+    /// Allocates memory for the trace to accommodate at least the specified number of rows.
+    pub fn reserve_trace_capacity(&mut self, num_rows: usize) {
+        self.trace.reserve_capacity(num_rows);
+    }
+
     // TEST METHODS
     // --------------------------------------------------------------------------------------------
 
@@ -752,6 +959,9 @@ pub enum BlockType {
     Join(bool), // internal value set to true when the first child is fully executed
     Split,
     Loop(bool), // internal value set to false if the loop is never entered
+    Dyn,
+    Call,
+    SysCall,
     Span,
 }
 
@@ -769,6 +979,9 @@ impl BlockType {
                     0
                 }
             }
+            Self::Dyn => 1,
+            Self::Call => 1,
+            Self::SysCall => 1,
             Self::Span => 0,
         }
     }
@@ -880,3 +1093,121 @@ impl DebugInfo {
         self.assembly_ops.push((clk, asmop));
     }
 }
+
+// SERIALIZATION
+// ================================================================================================
+
+impl Serializable for Decoder {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        write_vec(target, &self.block_stack.blocks);
+        write_option(target, self.span_context.as_ref(), |target, context| {
+            context.group_ops_left.write_into(target);
+            context.num_groups_left.write_into(target);
+        });
+        self.trace.write_into(target);
+        self.aux_hints.write_into(target);
+        self.debug_info.write_into(target);
+    }
+}
+
+impl Deserializable for Decoder {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        Ok(Self {
+            block_stack: BlockStack {
+                blocks: read_vec(source)?,
+            },
+            span_context: read_option(source, |source| {
+                Ok(SpanContext {
+                    group_ops_left: Felt::read_from(source)?,
+                    num_groups_left: Felt::read_from(source)?,
+                })
+            })?,
+            trace: DecoderTrace::read_from(source)?,
+            aux_hints: AuxTraceHints::read_from(source)?,
+            debug_info: DebugInfo::read_from(source)?,
+        })
+    }
+}
+
+impl Serializable for BlockInfo {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.addr.write_into(target);
+        self.block_type.write_into(target);
+        self.parent_addr.write_into(target);
+        write_bool(target, self.is_loop_body);
+        write_bool(target, self.is_first_child);
+    }
+}
+
+impl Deserializable for BlockInfo {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        Ok(Self {
+            addr: Felt::read_from(source)?,
+            block_type: BlockType::read_from(source)?,
+            parent_addr: Felt::read_from(source)?,
+            is_loop_body: read_bool(source)?,
+            is_first_child: read_bool(source)?,
+        })
+    }
+}
+
+impl Serializable for BlockType {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        match self {
+            Self::Join(first_child_executed) => {
+                target.write_u8(0);
+                write_bool(target, *first_child_executed);
+            }
+            Self::Split => target.write_u8(1),
+            Self::Loop(is_entered) => {
+                target.write_u8(2);
+                write_bool(target, *is_entered);
+            }
+            Self::Dyn => target.write_u8(3),
+            Self::Call => target.write_u8(4),
+            Self::SysCall => target.write_u8(5),
+            Self::Span => target.write_u8(6),
+        }
+    }
+}
+
+impl Deserializable for BlockType {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        match source.read_u8()? {
+            0 => Ok(Self::Join(read_bool(source)?)),
+            1 => Ok(Self::Split),
+            2 => Ok(Self::Loop(read_bool(source)?)),
+            3 => Ok(Self::Dyn),
+            4 => Ok(Self::Call),
+            5 => Ok(Self::SysCall),
+            6 => Ok(Self::Span),
+            tag => Err(DeserializationError::InvalidValue(format!(
+                "{} is not a valid block type",
+                tag
+            ))),
+        }
+    }
+}
+
+impl Serializable for DebugInfo {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        write_bool(target, self.in_debug_mode);
+        write_vec(target, &self.operations);
+        write_seq(target, &self.assembly_ops, |target, (clk, asmop)| {
+            write_usize(target, *clk);
+            asmop.write_into(target);
+        });
+    }
+}
+
+impl Deserializable for DebugInfo {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        Ok(Self {
+            in_debug_mode: read_bool(source)?,
+            operations: read_vec(source)?,
+            assembly_ops: read_seq(source, |source| {
+                Ok((read_usize(source)?, AssemblyOp::read_from(source)?))
+            })?,
+        })
+    }
+}
@@ -5,7 +5,10 @@ use super::{
 };
 use crate::utils::get_trace_len;
 use core::ops::Range;
-use vm_core::utils::new_array_vec;
+use vm_core::utils::{
+    bytes::{read_columns, read_vec, write_columns, write_vec},
+    new_array_vec, ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable,
+};
 
 // CONSTANTS
 // ================================================================================================
@@ -346,6 +349,25 @@ impl DecoderTrace {
         self.op_batch_flag_trace[2].push(ZERO);
     }
 
+    /// Allocates memory for the trace to accommodate at least the specified number of rows.
+    pub fn reserve_capacity(&mut self, num_rows: usize) {
+        let additional = num_rows.saturating_sub(self.trace_len());
+        let columns = [&mut self.addr_trace]
+            .into_iter()
+            .chain(self.op_bits_trace.iter_mut())
+            .chain(self.hasher_trace.iter_mut())
+            .chain([
+                &mut self.in_span_trace,
+                &mut self.group_count_trace,
+                &mut self.op_idx_trace,
+            ])
+            .chain(self.op_batch_flag_trace.iter_mut())
+            .chain([&mut self.op_bit_extra]);
+        for column in columns {
+            column.reserve(additional);
+        }
+    }
+
     // TRACE GENERATION
     // --------------------------------------------------------------------------------------------
 
@@ -506,6 +528,58 @@ impl DecoderTrace {
     }
 }
 
+// SERIALIZATION
+// ================================================================================================
+
+impl Serializable for DecoderTrace {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        write_vec(target, &self.addr_trace);
+        write_columns(target, &self.op_bits_trace);
+        write_columns(target, &self.hasher_trace);
+        write_vec(target, &self.in_span_trace);
+        write_vec(target, &self.group_count_trace);
+        write_vec(target, &self.op_idx_trace);
+        write_columns(target, &self.op_batch_flag_trace);
+        write_vec(target, &self.op_bit_extra);
+    }
+}
+
+impl Deserializable for DecoderTrace {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let trace = Self {
+            addr_trace: read_vec(source)?,
+            op_bits_trace: read_columns(source)?,
+            hasher_trace: read_columns(source)?,
+            in_span_trace: read_vec(source)?,
+            group_count_trace: read_vec(source)?,
+            op_idx_trace: read_vec(source)?,
+            op_batch_flag_trace: read_columns(source)?,
+            op_bit_extra: read_vec(source)?,
+        };
+
+        // all columns of the trace must have the same length
+        let trace_len = trace.addr_trace.len();
+        let is_consistent = trace
+            .op_bits_trace
+            .iter()
+            .chain(trace.hasher_trace.iter())
+            .chain(trace.op_batch_flag_trace.iter())
+            .chain([
+                &trace.in_span_trace,
+                &trace.group_count_trace,
+                &trace.op_idx_trace,
+                &trace.op_bit_extra,
+            ])
+            .all(|column| column.len() == trace_len);
+        if !is_consistent {
+            return Err(DeserializationError::InvalidValue(
+                "inconsistent decoder trace length".into(),
+            ));
+        }
+        Ok(trace)
+    }
+}
+
 // HELPER FUNCTIONS
 // ================================================================================================
 
@@ -1,9 +1,17 @@
-use super::{AdviceInjector, Decorator, ExecutionError, Felt, Process, StarkField};
+use super::{
+    AdviceInjector, AdviceProvider, Decorator, ExecutionError, Felt, FieldElement, Process,
+    StackMismatch, StarkField, VmState, Word,
+};
+use core::fmt::Write;
+use vm_core::{
+    utils::{collections::Vec, string::String},
+    DebugOptions, QuadFelt,
+};
 
 // DECORATORS
 // ================================================================================================
 
-impl Process {
+impl<A: AdviceProvider> Process<A> {
     /// Executes the specified decorator
     pub(super) fn execute_decorator(
         &mut self,
@@ -17,6 +25,38 @@ impl Process {
                         .append_asmop(self.system.clk(), assembly_op.clone());
                 }
             }
+            Decorator::Debug(options) => {
+                if self.decoder.in_debug_mode() {
+                    let report = self.build_debug_report(options);
+                    self.advice.on_debug(&report);
+                }
+            }
+            Decorator::ProcPath(procs) => self.trace_procs(procs),
+            Decorator::Event(event_id) => {
+                let state = self.build_event_state();
+                self.advice.on_event(*event_id, &state)?;
+            }
+            Decorator::Panic(message) => {
+                return Err(ExecutionError::Panic(self.system.clk(), message.clone()))
+            }
+            Decorator::ExpectStack(expected) => self.check_stack(expected)?,
+        }
+        Ok(())
+    }
+
+    /// Returns an error if the top of the stack does not consist of the specified values, listed
+    /// starting with the top of the stack.
+    ///
+    /// Unlike the other debugging decorators, the check is performed regardless of whether the
+    /// program is executed in debug mode: the decorator is emitted only when the program is
+    /// compiled in debug mode, and thus it is present only in programs built for testing.
+    fn check_stack(&self, expected: &[Felt]) -> Result<(), ExecutionError> {
+        let actual = (0..expected.len())
+            .map(|pos| self.stack.get(pos))
+            .collect::<Vec<_>>();
+        if actual != expected {
+            let mismatch = StackMismatch::new(self.system.clk(), expected.to_vec(), actual);
+            return Err(ExecutionError::StackMismatch(mismatch));
         }
         Ok(())
     }
@@ -27,7 +67,11 @@ impl Process {
     pub fn dec_advice(&mut self, injector: &AdviceInjector) -> Result<(), ExecutionError> {
         match injector {
             AdviceInjector::MerkleNode => self.inject_merkle_node(),
+            AdviceInjector::MerkleMerge => self.merge_merkle_trees(),
             AdviceInjector::DivResultU64 => self.inject_div_result_u64(),
+            AdviceInjector::GasUsed => self.inject_gas_used(),
+            AdviceInjector::MapValue => self.inject_map_value(),
+            AdviceInjector::Ext2Inv => self.inject_ext2_inv(),
         }
     }
 
@@ -70,6 +114,35 @@ impl Process {
         Ok(())
     }
 
+    /// Creates a new Merkle tree in the advice provider by merging the Merkle trees specified by
+    /// the roots on the stack. The stack is expected to be arranged as follows (from the top):
+    /// - root of the right tree, 4 elements
+    /// - root of the left tree, 4 elements
+    ///
+    /// Nothing is injected into the advice tape: the root of the new tree is computed by the VM.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - The advice provider does not contain Merkle trees with the specified roots.
+    /// - The depths of the specified Merkle trees are not equal.
+    fn merge_merkle_trees(&mut self) -> Result<(), ExecutionError> {
+        let right = [
+            self.stack.get(3),
+            self.stack.get(2),
+            self.stack.get(1),
+            self.stack.get(0),
+        ];
+        let left = [
+            self.stack.get(7),
+            self.stack.get(6),
+            self.stack.get(5),
+            self.stack.get(4),
+        ];
+
+        self.advice.merge_merkle_trees(left, right)?;
+        Ok(())
+    }
+
     /// Injects the result of u64 division (both the quotient and the remainder) at the head of
     /// the advice tape. The stack is expected to be arranged as follows (from the top):
     /// - divisor split into two 32-bit elements
@@ -107,11 +180,163 @@ impl Process {
 
         Ok(())
     }
+
+    /// Injects the amount of gas consumed by the program so far at the head of the advice tape.
+    /// This includes gas charged for all operations executed before the current clock cycle; if
+    /// gas metering is not enabled for this process, ZERO is injected.
+    fn inject_gas_used(&mut self) -> Result<(), ExecutionError> {
+        let gas_used = self.gas_used().unwrap_or_default();
+        self.advice.write_tape(Felt::new(gas_used));
+        Ok(())
+    }
+
+    /// Injects the values of the advice map entry with the key specified by the word at the top
+    /// of the stack at the head of the advice tape.
+    ///
+    /// The values are preceded by their number and padded with ZEROs to the next multiple of 8,
+    /// so that the number of values is read from the advice tape first, and the values can then
+    /// be read in batches of 8 elements (e.g., to be absorbed into the state of a hash function).
+    ///
+    /// # Errors
+    /// Returns an error if the advice map entry with the specified key cannot be found in the
+    /// advice provider.
+    fn inject_map_value(&mut self) -> Result<(), ExecutionError> {
+        let key = [
+            self.stack.get(3),
+            self.stack.get(2),
+            self.stack.get(1),
+            self.stack.get(0),
+        ];
+        let values = self.advice.get_mapped_values(key)?;
+
+        // write the values into the advice tape in reverse order so that the number of values is
+        // read first
+        let num_padding = (8 - values.len() % 8) % 8;
+        for _ in 0..num_padding {
+            self.advice.write_tape(Felt::ZERO);
+        }
+        for &value in values.iter().rev() {
+            self.advice.write_tape(value);
+        }
+        self.advice.write_tape(Felt::new(values.len() as u64));
+
+        Ok(())
+    }
+
+    /// Injects the multiplicative inverse of the element of the quadratic extension field at the
+    /// top of the stack at the head of the advice tape. The stack is expected to be arranged as
+    /// follows (from the top):
+    /// - the coefficient a1 of the element a = a0 + a1 * x, 1 element
+    /// - the coefficient a0 of the element a = a0 + a1 * x, 1 element
+    ///
+    /// The inverse b = b0 + b1 * x is injected so that b0 is read from the advice tape first, and
+    /// thus reading two values from the advice tape places b onto the stack as [b1, b0, ...].
+    ///
+    /// # Errors
+    /// Returns an error if the element is ZERO.
+    fn inject_ext2_inv(&mut self) -> Result<(), ExecutionError> {
+        let a1 = self.stack.get(0);
+        let a0 = self.stack.get(1);
+        let a = QuadFelt::new(a0, a1);
+
+        if a == QuadFelt::ZERO {
+            return Err(ExecutionError::DivideByZero(self.system.clk()));
+        }
+
+        let b = QuadFelt::as_base_elements(&[a.inv()]).to_vec();
+        self.advice.write_tape(b[1]);
+        self.advice.write_tape(b[0]);
+
+        Ok(())
+    }
+}
+
+// DEBUG REPORTS
+// ================================================================================================
+
+impl<A: AdviceProvider> Process<A> {
+    /// Returns a report of the part of the VM state specified by the options. The report
+    /// describes the state at the beginning of the current clock cycle, with one line per stack
+    /// item or memory address.
+    fn build_debug_report(&self, options: &DebugOptions) -> String {
+        let clk = self.system.clk();
+        let mut report = format!("debug.{} at clock cycle {}:", options, clk);
+        match *options {
+            DebugOptions::StackAll | DebugOptions::StackTop(_) => {
+                let stack = self.stack.get_state_at(clk);
+                let num_items = match *options {
+                    DebugOptions::StackTop(n) => stack.len().min(n as usize),
+                    _ => stack.len(),
+                };
+                for (i, item) in stack.iter().take(num_items).enumerate() {
+                    write!(report, "\n  {}: {}", i, item.as_int()).unwrap();
+                }
+            }
+            DebugOptions::MemAll | DebugOptions::MemInterval(..) => {
+                let range = match *options {
+                    DebugOptions::MemInterval(start, end) => start..=end,
+                    _ => 0..=u64::MAX,
+                };
+                let values = self.chiplets.get_mem_values_at(range, clk as u64);
+                if values.is_empty() {
+                    report.push_str("\n  no memory addresses were accessed");
+                }
+                for (addr, value) in values {
+                    write!(report, "\n  {}: {}", addr, format_word(value)).unwrap();
+                }
+            }
+            DebugOptions::LocalInterval(start, end) => {
+                // the local with index i is located at the address fmp - i
+                let fmp = self.system.fmp().as_int();
+                for i in start..=end {
+                    let addr = fmp - i as u64;
+                    let value = self
+                        .chiplets
+                        .get_mem_values_at(addr..=addr, clk as u64)
+                        .first()
+                        .map_or([Felt::ZERO; 4], |&(_, value)| value);
+                    write!(report, "\n  {}: {}", i, format_word(value)).unwrap();
+                }
+            }
+        }
+        report
+    }
+}
+
+// EVENTS
+// ================================================================================================
+
+impl<A: AdviceProvider> Process<A> {
+    /// Returns the state of the VM at the current clock cycle, which is passed to the advice
+    /// provider together with the ID of an emitted event. The memory includes all addresses of
+    /// the current context which have been accessed so far.
+    fn build_event_state(&self) -> VmState {
+        let clk = self.system.clk();
+        VmState {
+            clk,
+            op: None,
+            asmop: None,
+            fmp: self.system.fmp(),
+            stack: self.stack.get_state_at(clk),
+            memory: self.chiplets.get_mem_values_at(0..=u64::MAX, clk as u64),
+        }
+    }
 }
 
 // HELPER FUNCTIONS
 // ================================================================================================
 
+/// Returns the elements of the specified word formatted as integers, e.g. `[1, 2, 3, 4]`.
+fn format_word(word: Word) -> String {
+    format!(
+        "[{}, {}, {}, {}]",
+        word[0].as_int(),
+        word[1].as_int(),
+        word[2].as_int(),
+        word[3].as_int()
+    )
+}
+
 fn u64_to_u32_elements(value: u64) -> (Felt, Felt) {
     let hi = Felt::new(value >> 32);
     let lo = Felt::new((value as u32) as u64);
@@ -127,9 +352,9 @@ mod tests {
         super::{Felt, FieldElement, Operation, StarkField},
         Process,
     };
-    use crate::Word;
+    use crate::{CallStackFrame, ExecutionError, Word};
 
-    use vm_core::{AdviceInjector, AdviceSet, Decorator, ProgramInputs};
+    use vm_core::{AdviceInjector, AdviceSet, Decorator, ProgramInputs, QuadFelt};
 
     #[test]
     fn inject_merkle_node() {
@@ -174,6 +399,147 @@ mod tests {
         assert_eq!(expected_stack, process.stack.trace_state());
     }
 
+    #[test]
+    fn inject_ext2_inv() {
+        let a = QuadFelt::new(Felt::new(3), Felt::new(5));
+        let inputs = ProgramInputs::new(&[3, 5], &[], vec![]).unwrap();
+        let mut process = Process::new(inputs);
+
+        // inject the inverse into the advice tape and read it onto the stack
+        process
+            .execute_decorator(&Decorator::Advice(AdviceInjector::Ext2Inv))
+            .unwrap();
+        process.execute_op(Operation::Read).unwrap();
+        process.execute_op(Operation::Read).unwrap();
+
+        let b = QuadFelt::new(process.stack.get(1), process.stack.get(0));
+        assert_eq!(QuadFelt::ONE, a * b);
+
+        // ZERO does not have an inverse
+        let inputs = ProgramInputs::new(&[0, 0], &[], vec![]).unwrap();
+        let mut process = Process::new(inputs);
+        let result = process.execute_decorator(&Decorator::Advice(AdviceInjector::Ext2Inv));
+        assert!(matches!(result, Err(ExecutionError::DivideByZero(_))));
+    }
+
+    #[test]
+    fn debug_reports() {
+        let source = "
+            proc.foo.2
+                push.7 pop.local.1
+                debug.local
+            end
+            begin
+                push.1 push.2 push.3
+                debug.stack.2
+                push.10 pop.mem.100 debug.mem.99.101
+                exec.foo
+            end";
+        let program = miden_assembly::Assembler::new(true)
+            .compile(source)
+            .unwrap();
+
+        // in debug mode, reports are written to the log by the default advice provider
+        let mut logger = logtest::Logger::start();
+        for state in crate::execute_iter(&program, &ProgramInputs::none()) {
+            state.unwrap();
+        }
+        let reports = logger.by_ref().map(|record| record.args().to_string());
+        assert_eq!(
+            vec![
+                "debug.stack.2 at clock cycle 5:\n  0: 3\n  1: 2",
+                "debug.mem.99.101 at clock cycle 9:\n  100: [10, 0, 0, 0]",
+                "debug.local.0.1 at clock cycle 24:\n  0: [0, 0, 0, 0]\n  1: [7, 0, 0, 0]",
+            ],
+            reports.collect::<Vec<_>>()
+        );
+
+        // in release mode, debug instructions are discarded
+        let program = miden_assembly::Assembler::default()
+            .compile(source)
+            .unwrap();
+        assert_eq!(
+            program.hash(),
+            miden_assembly::Assembler::default()
+                .compile(&source.replace("debug.", "# debug."))
+                .unwrap()
+                .hash()
+        );
+    }
+
+    #[test]
+    fn call_stacks() {
+        let source = "
+            proc.bar
+                push.0 assert
+            end
+            proc.foo
+                push.1 drop exec.bar
+            end
+            proc.baz
+                exec.foo
+            end
+            begin
+                push.1 drop
+                call.baz
+            end";
+        let program = miden_assembly::Assembler::new(true)
+            .compile(source)
+            .unwrap();
+        let baz = program.cb_table().blocks().next().unwrap().hash();
+
+        // in debug mode, the error carries the chain of procedures being executed; the called
+        // procedure is identified by its MAST root
+        let mut process = Process::new_debug(ProgramInputs::none());
+        match process.execute(&program) {
+            Err(ExecutionError::WithCallStack(error, call_stack)) => {
+                assert!(
+                    matches!(*error, ExecutionError::FailedAssertion(clk, 0) if clk == call_stack.clk())
+                );
+                assert_eq!(
+                    &[
+                        CallStackFrame::Root(baz),
+                        CallStackFrame::Procedure("foo".into()),
+                        CallStackFrame::Procedure("bar".into()),
+                    ],
+                    call_stack.frames()
+                );
+
+                // the failed operation is identified by its span block and its index in there;
+                // the body of baz is a single span block in which ASSERT is the fifth operation
+                assert_eq!(Some((baz, 4)), call_stack.span_op());
+            }
+            _ => panic!("expected an error with a call stack"),
+        }
+
+        // procedures which returned are not a part of the call stack
+        let source = "
+            proc.foo
+                push.1 drop
+            end
+            begin
+                exec.foo
+                push.0 assert
+            end";
+        let program = miden_assembly::Assembler::new(true)
+            .compile(source)
+            .unwrap();
+        let mut process = Process::new_debug(ProgramInputs::none());
+        match process.execute(&program) {
+            Err(ExecutionError::WithCallStack(_, call_stack)) => {
+                assert!(call_stack.frames().is_empty())
+            }
+            _ => panic!("expected an error with a call stack"),
+        }
+
+        // in release mode, errors do not carry call stacks
+        let mut process = Process::new(ProgramInputs::none());
+        assert!(matches!(
+            process.execute(&program),
+            Err(ExecutionError::FailedAssertion(..))
+        ));
+    }
+
     // HELPER FUNCTIONS
     // --------------------------------------------------------------------------------------------
     fn init_leaf(value: u64) -> Word {
@@ -1,23 +1,424 @@
-use super::{AdviceSetError, CodeBlock, Felt};
-use winterfell::ProverError;
+use super::{AdviceSetError, CallStack, CodeBlock, Digest, Felt, MemoryViolation, StarkField};
+use core::fmt;
+use vm_core::utils::{
+    collections::Vec,
+    string::{String, ToString},
+};
+use vm_core::{
+    errors::ErrorCode,
+    utils::{hex::digest_to_hex, DeserializationError},
+};
+use winterfell::{ProverError, TraceLayout};
+
+#[cfg(not(feature = "std"))]
+use alloc::boxed::Box;
 
 // EXECUTION ERROR
 // ================================================================================================
 
 #[derive(Debug)]
 pub enum ExecutionError {
+    AdviceMapKeyNotFound([u8; 32]),
     AdviceSetLookupFailed(AdviceSetError),
     AdviceSetNotFound([u8; 32]),
     AdviceSetUpdateFailed(AdviceSetError),
+    CallerOutsideOfKernel(usize),
+    CodeBlockNotFound(Digest),
+    CycleLimitExceeded(usize),
     DivideByZero(usize),
     EmptyAdviceTape(usize),
-    FailedAssertion(usize),
+    ExecutionPaused(usize),
+    FailedAssertion(usize, u32),
+    GasLimitExceeded(usize, u64),
+    InvalidCheckpoint(usize),
+    InvalidCodeBlock(Digest, Digest),
     InvalidFmpValue(Felt, Felt),
     InvalidPowerOfTwo(Felt),
+    InvalidStackDepthOnReturn(usize, usize),
+    InvalidTraceLayout(TraceLayout),
+    InvalidTraceLength(usize),
+    MemoryAccessViolation(MemoryViolation),
     NotBinaryValue(Felt),
     NotU32Value(Felt),
+    Panic(usize, String),
     ProverError(ProverError),
+    ProvingCancelled,
+    StackMismatch(StackMismatch),
+    SyscallFromKernel(Digest),
+    SyscallTargetNotInKernel(Digest),
     TooManyStackOutputs(usize),
-    UnexecutableCodeBlock(CodeBlock),
     UnsupportedCodeBlock(CodeBlock),
+    UnsupportedSecurityLevel(u32),
+    WithCallStack(Box<ExecutionError>, CallStack),
+}
+
+impl ExecutionError {
+    /// Returns the stable code identifying the kind of this error.
+    ///
+    /// Codes and descriptions of all kinds of execution errors are listed in
+    /// [EXECUTION_ERROR_INDEX]. Errors with an attached stack trace have the code of the error
+    /// to which the stack trace is attached.
+    pub fn error_code(&self) -> ErrorCode {
+        match self {
+            Self::AdviceMapKeyNotFound(_) => codes::ADVICE_MAP_KEY_NOT_FOUND,
+            Self::AdviceSetLookupFailed(_) => codes::ADVICE_SET_LOOKUP_FAILED,
+            Self::AdviceSetNotFound(_) => codes::ADVICE_SET_NOT_FOUND,
+            Self::AdviceSetUpdateFailed(_) => codes::ADVICE_SET_UPDATE_FAILED,
+            Self::CallerOutsideOfKernel(_) => codes::CALLER_OUTSIDE_OF_KERNEL,
+            Self::CodeBlockNotFound(_) => codes::CODE_BLOCK_NOT_FOUND,
+            Self::CycleLimitExceeded(_) => codes::CYCLE_LIMIT_EXCEEDED,
+            Self::DivideByZero(_) => codes::DIVIDE_BY_ZERO,
+            Self::EmptyAdviceTape(_) => codes::EMPTY_ADVICE_TAPE,
+            Self::ExecutionPaused(_) => codes::EXECUTION_PAUSED,
+            Self::FailedAssertion(..) => codes::FAILED_ASSERTION,
+            Self::GasLimitExceeded(..) => codes::GAS_LIMIT_EXCEEDED,
+            Self::InvalidCheckpoint(_) => codes::INVALID_CHECKPOINT,
+            Self::InvalidCodeBlock(..) => codes::INVALID_CODE_BLOCK,
+            Self::InvalidFmpValue(..) => codes::INVALID_FMP_VALUE,
+            Self::InvalidPowerOfTwo(_) => codes::INVALID_POWER_OF_TWO,
+            Self::InvalidStackDepthOnReturn(..) => codes::INVALID_STACK_DEPTH_ON_RETURN,
+            Self::InvalidTraceLayout(_) => codes::INVALID_TRACE_LAYOUT,
+            Self::InvalidTraceLength(_) => codes::INVALID_TRACE_LENGTH,
+            Self::MemoryAccessViolation(_) => codes::MEMORY_ACCESS_VIOLATION,
+            Self::NotBinaryValue(_) => codes::NOT_BINARY_VALUE,
+            Self::NotU32Value(_) => codes::NOT_U32_VALUE,
+            Self::Panic(..) => codes::PANIC,
+            Self::ProverError(_) => codes::PROVER_ERROR,
+            Self::ProvingCancelled => codes::PROVING_CANCELLED,
+            Self::StackMismatch(_) => codes::STACK_MISMATCH,
+            Self::SyscallFromKernel(_) => codes::SYSCALL_FROM_KERNEL,
+            Self::SyscallTargetNotInKernel(_) => codes::SYSCALL_TARGET_NOT_IN_KERNEL,
+            Self::TooManyStackOutputs(_) => codes::TOO_MANY_STACK_OUTPUTS,
+            Self::UnsupportedCodeBlock(_) => codes::UNSUPPORTED_CODE_BLOCK,
+            Self::UnsupportedSecurityLevel(_) => codes::UNSUPPORTED_SECURITY_LEVEL,
+            Self::WithCallStack(error, _) => error.error_code(),
+        }
+    }
+}
+
+// EXECUTION ERROR CODES
+// ================================================================================================
+
+/// Error codes assigned to each kind of execution error. Codes of execution errors start at 500
+/// so that they do not overlap with codes of assembly errors.
+mod codes {
+    use super::ErrorCode;
+
+    pub const ADVICE_MAP_KEY_NOT_FOUND: ErrorCode = ErrorCode::new(501);
+    pub const ADVICE_SET_LOOKUP_FAILED: ErrorCode = ErrorCode::new(502);
+    pub const ADVICE_SET_NOT_FOUND: ErrorCode = ErrorCode::new(503);
+    pub const ADVICE_SET_UPDATE_FAILED: ErrorCode = ErrorCode::new(504);
+    pub const CALLER_OUTSIDE_OF_KERNEL: ErrorCode = ErrorCode::new(505);
+    pub const CODE_BLOCK_NOT_FOUND: ErrorCode = ErrorCode::new(506);
+    pub const CYCLE_LIMIT_EXCEEDED: ErrorCode = ErrorCode::new(507);
+    pub const DIVIDE_BY_ZERO: ErrorCode = ErrorCode::new(508);
+    pub const EMPTY_ADVICE_TAPE: ErrorCode = ErrorCode::new(509);
+    pub const EXECUTION_PAUSED: ErrorCode = ErrorCode::new(510);
+    pub const FAILED_ASSERTION: ErrorCode = ErrorCode::new(511);
+    pub const GAS_LIMIT_EXCEEDED: ErrorCode = ErrorCode::new(512);
+    pub const INVALID_CHECKPOINT: ErrorCode = ErrorCode::new(513);
+    pub const INVALID_CODE_BLOCK: ErrorCode = ErrorCode::new(514);
+    pub const INVALID_FMP_VALUE: ErrorCode = ErrorCode::new(515);
+    pub const INVALID_POWER_OF_TWO: ErrorCode = ErrorCode::new(516);
+    pub const INVALID_STACK_DEPTH_ON_RETURN: ErrorCode = ErrorCode::new(517);
+    pub const INVALID_TRACE_LAYOUT: ErrorCode = ErrorCode::new(518);
+    pub const INVALID_TRACE_LENGTH: ErrorCode = ErrorCode::new(519);
+    pub const NOT_BINARY_VALUE: ErrorCode = ErrorCode::new(520);
+    pub const NOT_U32_VALUE: ErrorCode = ErrorCode::new(521);
+    pub const PROVER_ERROR: ErrorCode = ErrorCode::new(522);
+    pub const SYSCALL_FROM_KERNEL: ErrorCode = ErrorCode::new(523);
+    pub const SYSCALL_TARGET_NOT_IN_KERNEL: ErrorCode = ErrorCode::new(524);
+    pub const TOO_MANY_STACK_OUTPUTS: ErrorCode = ErrorCode::new(525);
+    pub const UNSUPPORTED_CODE_BLOCK: ErrorCode = ErrorCode::new(526);
+    pub const PANIC: ErrorCode = ErrorCode::new(527);
+    pub const PROVING_CANCELLED: ErrorCode = ErrorCode::new(528);
+    pub const STACK_MISMATCH: ErrorCode = ErrorCode::new(529);
+    pub const UNSUPPORTED_SECURITY_LEVEL: ErrorCode = ErrorCode::new(530);
+    pub const MEMORY_ACCESS_VIOLATION: ErrorCode = ErrorCode::new(531);
+}
+
+/// Codes and one-line descriptions of all kinds of execution errors, sorted by error code.
+pub const EXECUTION_ERROR_INDEX: [(ErrorCode, &str); 31] = [
+    (
+        codes::ADVICE_MAP_KEY_NOT_FOUND,
+        "advice map does not contain the requested key",
+    ),
+    (
+        codes::ADVICE_SET_LOOKUP_FAILED,
+        "failed to look up a node of an advice set",
+    ),
+    (
+        codes::ADVICE_SET_NOT_FOUND,
+        "advice provider does not contain the requested advice set",
+    ),
+    (
+        codes::ADVICE_SET_UPDATE_FAILED,
+        "failed to update a node of an advice set",
+    ),
+    (
+        codes::CALLER_OUTSIDE_OF_KERNEL,
+        "caller instruction executed outside of a kernel",
+    ),
+    (
+        codes::CODE_BLOCK_NOT_FOUND,
+        "code block to be executed was not found",
+    ),
+    (
+        codes::CYCLE_LIMIT_EXCEEDED,
+        "program exceeded the maximum number of cycles",
+    ),
+    (codes::DIVIDE_BY_ZERO, "division by zero"),
+    (codes::EMPTY_ADVICE_TAPE, "advice tape is empty"),
+    (
+        codes::EXECUTION_PAUSED,
+        "operation is not allowed while execution is paused",
+    ),
+    (codes::FAILED_ASSERTION, "assertion failed"),
+    (codes::GAS_LIMIT_EXCEEDED, "program exceeded the gas limit"),
+    (
+        codes::INVALID_CHECKPOINT,
+        "checkpoint does not match the program being executed",
+    ),
+    (
+        codes::INVALID_CODE_BLOCK,
+        "code block does not have the expected hash",
+    ),
+    (
+        codes::INVALID_FMP_VALUE,
+        "frame pointer was set to an invalid value",
+    ),
+    (
+        codes::INVALID_POWER_OF_TWO,
+        "exponent of a power of two is out of range",
+    ),
+    (
+        codes::INVALID_STACK_DEPTH_ON_RETURN,
+        "invalid stack depth on return from a call",
+    ),
+    (
+        codes::INVALID_TRACE_LAYOUT,
+        "execution trace has an invalid layout",
+    ),
+    (
+        codes::INVALID_TRACE_LENGTH,
+        "execution trace has an invalid length",
+    ),
+    (codes::NOT_BINARY_VALUE, "operand is not a binary value"),
+    (codes::NOT_U32_VALUE, "operand is not a u32 value"),
+    (
+        codes::PROVER_ERROR,
+        "failed to generate a proof of execution",
+    ),
+    (
+        codes::SYSCALL_FROM_KERNEL,
+        "syscall instruction executed inside a kernel",
+    ),
+    (
+        codes::SYSCALL_TARGET_NOT_IN_KERNEL,
+        "syscall target is not a kernel procedure",
+    ),
+    (
+        codes::TOO_MANY_STACK_OUTPUTS,
+        "program left too many values on the stack",
+    ),
+    (
+        codes::UNSUPPORTED_CODE_BLOCK,
+        "code block is not supported by the processor",
+    ),
+    (codes::PANIC, "program panicked"),
+    (codes::PROVING_CANCELLED, "proof generation was cancelled"),
+    (
+        codes::STACK_MISMATCH,
+        "top of the stack does not match the expected values",
+    ),
+    (
+        codes::UNSUPPORTED_SECURITY_LEVEL,
+        "no proof options provide the requested security level",
+    ),
+    (
+        codes::MEMORY_ACCESS_VIOLATION,
+        "memory access violates the memory permissions",
+    ),
+];
+
+// STACK MISMATCH
+// ================================================================================================
+
+/// Describes a failed `expect_stack` check: the values expected at the top of the stack and the
+/// values actually found there, both listed starting with the top of the stack.
+///
+/// The mismatch is displayed as a table which lists expected and actual values side by side,
+/// and marks the positions at which they differ.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StackMismatch {
+    clk: usize,
+    expected: Vec<Felt>,
+    actual: Vec<Felt>,
+}
+
+impl StackMismatch {
+    /// Returns a description of a mismatch between the expected and the actual values at the top
+    /// of the stack found at the specified clock cycle.
+    pub fn new(clk: usize, expected: Vec<Felt>, actual: Vec<Felt>) -> Self {
+        debug_assert_eq!(expected.len(), actual.len());
+        Self {
+            clk,
+            expected,
+            actual,
+        }
+    }
+
+    /// Returns the clock cycle at which the stack was checked.
+    pub fn clk(&self) -> usize {
+        self.clk
+    }
+
+    /// Returns the values which were expected at the top of the stack.
+    pub fn expected(&self) -> &[Felt] {
+        &self.expected
+    }
+
+    /// Returns the values which were found at the top of the stack.
+    pub fn actual(&self) -> &[Felt] {
+        &self.actual
+    }
+}
+
+impl fmt::Display for StackMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let expected = self.expected.iter().map(|v| v.as_int().to_string());
+        let actual = self.actual.iter().map(|v| v.as_int().to_string());
+        let rows = expected.zip(actual).collect::<Vec<_>>();
+        let width = rows
+            .iter()
+            .map(|(expected, _)| expected.len())
+            .chain(["expected".len()])
+            .max()
+            .unwrap_or_default();
+
+        writeln!(
+            f,
+            "top of the stack does not match the expected values at clock cycle {}:",
+            self.clk
+        )?;
+        write!(f, "  pos  {:<width$}  actual", "expected")?;
+        for (pos, (expected, actual)) in rows.iter().enumerate() {
+            let marker = if expected != actual { "  <-" } else { "" };
+            write!(
+                f,
+                "\n  {:>3}  {:<width$}  {}{}",
+                pos, expected, actual, marker
+            )?;
+        }
+        Ok(())
+    }
+}
+
+// CHECKPOINT ERROR
+// ================================================================================================
+
+#[derive(Debug)]
+pub enum CheckpointError {
+    DeserializationError(DeserializationError),
+    NotACheckpoint,
+    UnsupportedVersion(u8),
+}
+
+// HOT SWAP ERROR
+// ================================================================================================
+
+/// An error which can occur when the code of a procedure is replaced in a compiled program (see
+/// [replace_procedure()](crate::replace_procedure)), or when execution of the new program is
+/// resumed from a checkpoint (see [Process::hot_swap()](crate::Process::hot_swap)).
+#[derive(Debug)]
+pub enum HotSwapError {
+    /// A checkpoint could not be restored.
+    CheckpointError(CheckpointError),
+    /// Replaying execution of the program from the earliest checkpoint failed.
+    ExecutionError(ExecutionError),
+    /// The program contains no replaceable code block with the specified MAST root.
+    ProcedureNotFound(Digest),
+    /// The procedure with the specified MAST root is not invoked after the earliest checkpoint.
+    ProcedureNotInvoked(Digest),
+    /// The checkpoint taken at the specified clock cycle was not taken during execution of the
+    /// program.
+    UnrelatedCheckpoint(usize),
+}
+
+impl fmt::Display for HotSwapError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HotSwapError::CheckpointError(err) => {
+                write!(f, "failed to restore checkpoint: {:?}", err)
+            }
+            HotSwapError::ExecutionError(err) => write!(f, "failed to replay execution: {:?}", err),
+            HotSwapError::ProcedureNotFound(root) => {
+                write!(
+                    f,
+                    "procedure {} does not appear in the program",
+                    digest_to_hex(*root)
+                )
+            }
+            HotSwapError::ProcedureNotInvoked(root) => write!(
+                f,
+                "procedure {} is not invoked after the earliest checkpoint",
+                digest_to_hex(*root)
+            ),
+            HotSwapError::UnrelatedCheckpoint(clk) => write!(
+                f,
+                "checkpoint taken at clock cycle {} was not taken during execution of the program",
+                clk
+            ),
+        }
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::{ExecutionError, StackMismatch, EXECUTION_ERROR_INDEX};
+    use vm_core::{Felt, FieldElement};
+
+    #[test]
+    fn execution_error_index() {
+        // codes must be sorted with no duplicates, and must not overlap with assembly error codes
+        for pair in EXECUTION_ERROR_INDEX.windows(2) {
+            assert!(pair[0].0 < pair[1].0);
+        }
+        assert!(EXECUTION_ERROR_INDEX[0].0.as_u16() >= 500);
+        assert!(EXECUTION_ERROR_INDEX
+            .iter()
+            .all(|(_, title)| !title.is_empty()));
+
+        assert_eq!(
+            "E508",
+            ExecutionError::DivideByZero(3).error_code().to_string()
+        );
+        assert_eq!(
+            "E511",
+            ExecutionError::FailedAssertion(7, 0)
+                .error_code()
+                .to_string()
+        );
+        let error = ExecutionError::NotU32Value(Felt::ONE);
+        assert!(EXECUTION_ERROR_INDEX
+            .iter()
+            .any(|&(code, title)| code == error.error_code() && title.contains("u32")));
+    }
+
+    #[test]
+    fn stack_mismatch_display() {
+        let expected = [3, 2, 1].map(Felt::new).to_vec();
+        let actual = [3, 12, 1].map(Felt::new).to_vec();
+        let mismatch = StackMismatch::new(5, expected, actual);
+        let expected = "\
+top of the stack does not match the expected values at clock cycle 5:
+  pos  expected  actual
+    0  3         3
+    1  2         12  <-
+    2  1         1";
+        assert_eq!(expected, mismatch.to_string());
+    }
 }
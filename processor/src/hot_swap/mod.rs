@@ -0,0 +1,253 @@
+use super::{
+    execute_iter, AdviceProvider, BTreeMap, CodeBlock, CodeBlockTable, Digest, ExecutionStatus,
+    HotSwapError, Kernel, Process, Program, ProgramInputs, Vec, VmStateIterator,
+};
+use vm_core::utils::{Deserializable, Serializable};
+
+#[cfg(test)]
+mod tests;
+
+// PROCEDURE REPLACEMENT
+// ================================================================================================
+
+/// Returns a version of the provided program in which the code of the procedure with the
+/// specified MAST root is replaced with the specified code block.
+///
+/// The procedure is replaced wherever it appears as a separate code block: in the body of the
+/// program, in the procedures invoked via `call` instructions, and as the target of proxy blocks.
+/// Call and proxy blocks which invoke code containing the procedure are updated to reference the
+/// new code. The body of a procedure invoked via `exec` is usually merged with the surrounding
+/// code by the assembler, and thus such procedures can be replaced only if the assembler kept them
+/// as separate blocks. Kernel procedures (and the code they invoke) are never replaced, since the
+/// kernel of a program is fixed.
+///
+/// The new code of a procedure can be compiled from edited source code via
+/// `Assembler::recompile_procedure()`.
+///
+/// # Errors
+/// Returns an error if the program contains no code block with the specified hash outside of the
+/// kernel.
+pub fn replace_procedure(
+    program: &Program,
+    root: Digest,
+    body: CodeBlock,
+) -> Result<Program, HotSwapError> {
+    let mut replacer = Replacer::new(program.cb_table(), program.kernel(), root, body);
+    let program_root = replacer.replace(program.root());
+    let cb_table = program
+        .cb_table()
+        .blocks()
+        .map(|block| {
+            if program.kernel().contains_proc(block.hash()) {
+                block.clone()
+            } else {
+                replacer.replace(block)
+            }
+        })
+        .collect::<CodeBlockTable>();
+
+    if replacer.num_replaced == 0 {
+        return Err(HotSwapError::ProcedureNotFound(root));
+    }
+    Ok(Program::with_kernel(
+        program_root,
+        program.kernel().clone(),
+        cb_table,
+    ))
+}
+
+/// Replaces a procedure in trees of code blocks.
+///
+/// Hashes of the blocks invoked via call and proxy blocks are memoized once the procedure is
+/// replaced in them, since the same procedure is commonly invoked from many places.
+struct Replacer<'a> {
+    cb_table: &'a CodeBlockTable,
+    kernel: &'a Kernel,
+    root: Digest,
+    body: CodeBlock,
+    replaced: BTreeMap<[u8; 32], Digest>,
+    num_replaced: usize,
+}
+
+impl<'a> Replacer<'a> {
+    /// Returns a new replacer of the procedure with the specified MAST root; invoked procedures
+    /// are resolved in the specified table.
+    fn new(
+        cb_table: &'a CodeBlockTable,
+        kernel: &'a Kernel,
+        root: Digest,
+        body: CodeBlock,
+    ) -> Self {
+        Self {
+            cb_table,
+            kernel,
+            root,
+            body,
+            replaced: BTreeMap::new(),
+            num_replaced: 0,
+        }
+    }
+
+    /// Returns the specified block with all occurrences of the procedure replaced.
+    fn replace(&mut self, block: &CodeBlock) -> CodeBlock {
+        if block.hash() == self.root {
+            self.num_replaced += 1;
+            return self.body.clone();
+        }
+        match block {
+            CodeBlock::Join(join) => {
+                let first = self.replace(join.first());
+                let second = self.replace(join.second());
+                CodeBlock::new_join([first, second])
+            }
+            CodeBlock::Split(split) => {
+                let on_true = self.replace(split.on_true());
+                let on_false = self.replace(split.on_false());
+                CodeBlock::new_split(on_true, on_false)
+            }
+            CodeBlock::Loop(block) => CodeBlock::new_loop(self.replace(block.body())),
+            CodeBlock::Call(call) if !call.is_syscall() => {
+                CodeBlock::new_call(self.replace_invoked(call.fn_hash()))
+            }
+            CodeBlock::Proxy(proxy) => CodeBlock::new_proxy(self.replace_invoked(proxy.hash())),
+            CodeBlock::Span(_) | CodeBlock::Call(_) | CodeBlock::Dyn(_) => block.clone(),
+        }
+    }
+
+    /// Returns the hash of the block with the specified hash from the code block table once the
+    /// procedure is replaced in it, or the specified hash if the block is not in the table or is
+    /// a kernel procedure.
+    fn replace_invoked(&mut self, hash: Digest) -> Digest {
+        if hash == self.root {
+            self.num_replaced += 1;
+            return self.body.hash();
+        }
+        let key = <[u8; 32]>::from(hash);
+        if let Some(&replaced) = self.replaced.get(&key) {
+            return replaced;
+        }
+        match self.cb_table.get(hash) {
+            Some(block) if !self.kernel.contains_proc(hash) => {
+                let replaced = self.replace(block).hash();
+                self.replaced.insert(key, replaced);
+                replaced
+            }
+            _ => hash,
+        }
+    }
+}
+
+// HOT SWAP
+// ================================================================================================
+
+impl<A> Process<A>
+where
+    A: AdviceProvider + Serializable + Deserializable,
+{
+    /// Replaces the code of the procedure with the specified MAST root in the provided program,
+    /// and returns a process which continues execution of the new program from the latest of the
+    /// specified checkpoints taken before the procedure was first invoked, together with the new
+    /// program.
+    ///
+    /// This shortens the fix-verify loop when debugging a procedure invoked deep into execution:
+    /// instead of re-executing the program from the start, execution of the edited procedure is
+    /// resumed from a checkpoint. The checkpoints must be taken during a single execution of the
+    /// program, and the earliest of them must precede the first invocation of the procedure (e.g.,
+    /// it may be taken at clock cycle 0 via [Process::execute_until()]). The first invocation of
+    /// the procedure is found by replaying execution from the earliest checkpoint.
+    ///
+    /// The procedure is replaced as described in [replace_procedure()]. The resulting process is
+    /// meant for debugging: the part of its trace preceding the checkpoint refers to the blocks of
+    /// the original program, and thus the trace cannot be used to prove execution of the new
+    /// program. To step through execution of the new program in a debugger, the process can be
+    /// passed to [resume_iter()](crate::resume_iter); in this case, the checkpoints should be
+    /// taken from a process created via [Process::new_debug()].
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - The program contains no code block with the specified hash outside of the kernel.
+    /// - Any of the checkpoints is malformed, or was not taken during execution of the program.
+    /// - Replaying execution from the earliest checkpoint fails, or the procedure is not invoked
+    ///   after the earliest checkpoint.
+    pub fn hot_swap(
+        program: &Program,
+        checkpoints: &[Vec<u8>],
+        root: Digest,
+        body: CodeBlock,
+    ) -> Result<(Self, Program), HotSwapError> {
+        let swapped = replace_procedure(program, root, body)?;
+
+        // order the checkpoints by the clock cycle at which they were taken
+        let mut restored = Vec::with_capacity(checkpoints.len());
+        for checkpoint in checkpoints {
+            let process =
+                Self::from_checkpoint(checkpoint).map_err(HotSwapError::CheckpointError)?;
+            let clk = process.system.clk();
+            match process.paused {
+                Some(hash) if hash == program.hash() => (),
+                None if clk == 0 => (),
+                _ => return Err(HotSwapError::UnrelatedCheckpoint(clk)),
+            }
+            restored.push((clk, checkpoint));
+        }
+        restored.sort_by_key(|&(clk, _)| clk);
+        let &(_, earliest) = restored
+            .first()
+            .ok_or(HotSwapError::ProcedureNotInvoked(root))?;
+
+        // replay execution from the earliest checkpoint until the procedure is invoked
+        let mut replay = Self::from_checkpoint(earliest).map_err(HotSwapError::CheckpointError)?;
+        let invoked_at = match replay
+            .execute_until_block(program, root)
+            .map_err(HotSwapError::ExecutionError)?
+        {
+            ExecutionStatus::Paused(clk) => clk,
+            ExecutionStatus::Completed => return Err(HotSwapError::ProcedureNotInvoked(root)),
+        };
+
+        // the frames of the blocks being executed at the checkpoint do not depend on the code of
+        // the procedure, and thus execution can be resumed with the new program
+        let &(_, checkpoint) = restored
+            .iter()
+            .rev()
+            .find(|&&(clk, _)| clk <= invoked_at)
+            .expect("no checkpoint before the replay");
+        let mut process =
+            Self::from_checkpoint(checkpoint).map_err(HotSwapError::CheckpointError)?;
+        if process.paused.is_some() {
+            process.paused = Some(swapped.hash());
+        }
+        Ok((process, swapped))
+    }
+}
+
+// DEBUGGING
+// ================================================================================================
+
+/// Replaces the code of the procedure with the specified MAST root in the provided program, and
+/// returns the new program together with an iterator which steps through its execution against
+/// the provided inputs.
+///
+/// This is the entry point for debuggers built on top of [execute_iter()]: once a procedure is
+/// recompiled from edited source code, the program is re-run with the new code of the procedure
+/// and its execution can be inspected in the same way as before. The procedure is replaced as
+/// described in [replace_procedure()]. Assembly instructions are reported by the iterator only if
+/// both the program and the new code of the procedure were compiled in debug mode.
+///
+/// For procedures invoked deep into execution, re-running the program from the start can be
+/// avoided by resuming execution from a checkpoint via [Process::hot_swap()] and
+/// [resume_iter()](crate::resume_iter).
+///
+/// # Errors
+/// Returns an error if the program contains no code block with the specified hash outside of the
+/// kernel.
+pub fn hot_swap_iter(
+    program: &Program,
+    inputs: &ProgramInputs,
+    root: Digest,
+    body: CodeBlock,
+) -> Result<(Program, VmStateIterator), HotSwapError> {
+    let program = replace_procedure(program, root, body)?;
+    let states = execute_iter(&program, inputs);
+    Ok((program, states))
+}
@@ -0,0 +1,246 @@
+use super::{
+    super::{execute, resume_iter, ExecutionStatus, MemAdviceProvider, ProgramInputs},
+    hot_swap_iter, replace_procedure, CodeBlock, Digest, HotSwapError, Process, Program, Vec,
+};
+use miden_assembly::Assembler;
+use vm_core::{utils::hex::digest_to_hex, StackTopState, StarkField};
+
+// TEST PROGRAMS
+// ================================================================================================
+
+/// A program which invokes a buggy procedure (which doubles instead of tripling a value) only
+/// after a long computation; the procedure is invoked via `call`, and thus it is kept as a
+/// separate block.
+const PROGRAM: &str = "
+    proc.triple
+        mul.2
+    end
+    begin
+        push.1 push.20 push.1
+        while.true
+            swap add.1 swap sub.1 dup.0 neq.0
+        end
+        drop
+        call.triple
+        call.triple
+    end";
+
+const BUGGY_PROC: &str = "proc.triple mul.2 end";
+
+const FIXED_PROC: &str = "proc.triple mul.3 end";
+
+// PROCEDURE REPLACEMENT
+// ================================================================================================
+
+#[test]
+fn replace_called_procedure() {
+    let program = compile(PROGRAM);
+    let root = callee_root(&program);
+    let swapped = replace_procedure(&program, root, recompile(FIXED_PROC, "triple")).unwrap();
+    assert_ne!(program.hash(), swapped.hash());
+    assert_eq!(program.cb_table().len(), swapped.cb_table().len());
+
+    // the new program behaves as if it was compiled from the fixed source
+    let fixed = compile(&PROGRAM.replace("mul.2", "mul.3"));
+    assert_eq!(fixed.hash(), swapped.hash());
+    assert_eq!(189, stack_top(&swapped)[0].as_int());
+}
+
+#[test]
+fn replace_inlined_procedure() {
+    // procedures with control flow invoked via `exec` are kept as separate blocks
+    let procedure = "proc.countdown push.1 while.true sub.1 dup.0 neq.0 end end";
+    let source = format!(
+        "{} begin push.3 exec.countdown push.4 exec.countdown end",
+        procedure
+    );
+    let program = compile(&source);
+    let root = recompile(procedure, "countdown").hash();
+    let body = recompile(&procedure.replace("sub.1", "sub.2"), "countdown");
+    let swapped = replace_procedure(&program, root, body.clone()).unwrap();
+    let fixed = compile(&source.replace("sub.1", "sub.2"));
+    assert_eq!(fixed.hash(), swapped.hash());
+
+    // a procedure which does not appear in the program cannot be replaced
+    let root = compile("begin push.7 end").hash();
+    assert!(matches!(
+        replace_procedure(&program, root, body),
+        Err(HotSwapError::ProcedureNotFound(hash)) if hash == root
+    ));
+}
+
+#[test]
+fn recompile_procedure() {
+    // recompiling a procedure from unchanged source yields its original code
+    let program = compile(PROGRAM);
+    let body = recompile(BUGGY_PROC, "triple");
+    assert_eq!(callee_root(&program), body.hash());
+
+    // the procedure must be declared by the source, and the source must not contain a program body
+    let assembler = Assembler::new(true);
+    assert!(assembler.recompile_procedure(BUGGY_PROC, "double").is_err());
+    assert!(assembler.recompile_procedure(PROGRAM, "triple").is_err());
+}
+
+// HOT SWAP
+// ================================================================================================
+
+#[test]
+fn hot_swap_resumes_from_checkpoint() {
+    let program = compile(PROGRAM);
+    let root = callee_root(&program);
+    let checkpoints = take_checkpoints(&program, 10);
+    assert!(checkpoints.len() > 3);
+
+    let body = recompile(FIXED_PROC, "triple");
+    let (mut process, swapped) =
+        Process::<MemAdviceProvider>::hot_swap(&program, &checkpoints, root, body).unwrap();
+
+    // execution is resumed from a checkpoint taken after the start of the program but before the
+    // procedure was invoked
+    let clk = process.system.clk();
+    assert!(clk > 0);
+    let mut replay: Process = Process::from_checkpoint(&checkpoints[0]).unwrap();
+    let invoked_at = match replay.execute_until_block(&program, root).unwrap() {
+        ExecutionStatus::Paused(invoked_at) => invoked_at,
+        ExecutionStatus::Completed => panic!("the procedure was not invoked"),
+    };
+    assert!(clk <= invoked_at);
+
+    // the latest such checkpoint is used
+    for checkpoint in checkpoints.iter() {
+        let other: Process = Process::from_checkpoint(checkpoint).unwrap();
+        let other_clk = other.system.clk();
+        assert!(other_clk <= clk || other_clk > invoked_at);
+    }
+
+    process.resume(&swapped).unwrap();
+    let trace = process.into_trace();
+    assert_eq!(swapped.hash(), trace.program_hash());
+    assert_eq!(stack_top(&swapped), trace.last_stack_state());
+}
+
+#[test]
+fn hot_swap_errors() {
+    let program = compile(PROGRAM);
+    let root = callee_root(&program);
+    let body = recompile(FIXED_PROC, "triple");
+    let checkpoints = take_checkpoints(&program, 10);
+
+    // checkpoints must be taken during execution of the program
+    let other = compile("begin repeat.20 push.1 drop end end");
+    let mut unrelated = checkpoints.clone();
+    unrelated.extend(take_checkpoints(&other, 10));
+    assert!(matches!(
+        Process::<MemAdviceProvider>::hot_swap(&program, &unrelated, root, body.clone()),
+        Err(HotSwapError::UnrelatedCheckpoint(_))
+    ));
+
+    // the procedure must be invoked after the earliest checkpoint
+    let program = compile("proc.triple mul.2 end begin push.0 if.true call.triple end end");
+    let checkpoints = take_checkpoints(&program, 1);
+    let error = Process::<MemAdviceProvider>::hot_swap(&program, &checkpoints, root, body)
+        .err()
+        .unwrap();
+    assert!(matches!(error, HotSwapError::ProcedureNotInvoked(hash) if hash == root));
+    assert_eq!(
+        format!(
+            "procedure {} is not invoked after the earliest checkpoint",
+            digest_to_hex(root)
+        ),
+        error.to_string()
+    );
+}
+
+// DEBUGGING
+// ================================================================================================
+
+#[test]
+fn hot_swap_iter_reruns_program() {
+    let program = compile(PROGRAM);
+    let root = callee_root(&program);
+    let body = recompile(FIXED_PROC, "triple");
+    let (swapped, states) = hot_swap_iter(&program, &ProgramInputs::none(), root, body).unwrap();
+    assert_eq!(
+        compile(&PROGRAM.replace("mul.2", "mul.3")).hash(),
+        swapped.hash()
+    );
+
+    // the states of the VM reflect execution of the new program
+    let last = states.last().unwrap().unwrap();
+    assert_eq!(189, last.stack[0].as_int());
+}
+
+#[test]
+fn resume_iter_after_hot_swap() {
+    let program = compile(PROGRAM);
+    let root = callee_root(&program);
+    let checkpoints = take_checkpoints(&program, 10);
+    let body = recompile(FIXED_PROC, "triple");
+    let (process, swapped) = Process::hot_swap(&program, &checkpoints, root, body).unwrap();
+
+    // the iterator steps through the entire execution, including the part preceding the
+    // checkpoint, and reflects execution of the new program
+    let states = resume_iter(process, &swapped)
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(0, states[0].clk);
+    let expected = hot_swap_iter(
+        &program,
+        &ProgramInputs::none(),
+        root,
+        recompile(FIXED_PROC, "triple"),
+    )
+    .unwrap()
+    .1
+    .collect::<Result<Vec<_>, _>>()
+    .unwrap();
+    assert_eq!(expected.len(), states.len());
+    assert_eq!(expected.last(), states.last());
+    assert_eq!(189, states.last().unwrap().stack[0].as_int());
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Compiles the program in debug mode, so that its execution can be stepped through.
+fn compile(source: &str) -> Program {
+    Assembler::new(true).compile(source).unwrap()
+}
+
+fn recompile(source: &str, name: &str) -> CodeBlock {
+    Assembler::new(true)
+        .recompile_procedure(source, name)
+        .unwrap()
+}
+
+/// Returns the MAST root of the only procedure invoked by the program via `call`.
+fn callee_root(program: &Program) -> Digest {
+    assert_eq!(1, program.cb_table().len());
+    program
+        .cb_table()
+        .blocks()
+        .map(CodeBlock::hash)
+        .next()
+        .unwrap()
+}
+
+/// Executes the program to completion, and returns the top of the stack.
+fn stack_top(program: &Program) -> StackTopState {
+    execute(program, &ProgramInputs::none())
+        .unwrap()
+        .last_stack_state()
+}
+
+/// Executes the program, pausing execution every `step` cycles starting with cycle 0, and returns
+/// the checkpoints taken at every pause.
+fn take_checkpoints(program: &Program, step: usize) -> Vec<Vec<u8>> {
+    let mut process = Process::new_debug(ProgramInputs::none());
+    let mut checkpoints = Vec::new();
+    let mut clk = 0;
+    while let ExecutionStatus::Paused(paused_at) = process.execute_until(program, clk).unwrap() {
+        checkpoints.push(process.to_checkpoint());
+        clk = paused_at + step;
+    }
+    checkpoints
+}
@@ -7,10 +7,12 @@ extern crate alloc;
 pub use vm_core::{
     chiplets::hasher::Digest,
     errors::{AdviceSetError, InputError},
-    AdviceSet, Program, ProgramInputs,
+    AdviceSet, CodeBlockTable, Kernel, Program, ProgramInputs,
 };
 use vm_core::{
-    code_blocks::{CodeBlock, Join, Loop, OpBatch, Span, Split, OP_BATCH_SIZE, OP_GROUP_SIZE},
+    code_blocks::{
+        Call, CodeBlock, Dyn, Join, Loop, OpBatch, Proxy, Span, Split, OP_BATCH_SIZE, OP_GROUP_SIZE,
+    },
     utils::collections::{BTreeMap, Vec},
     AdviceInjector, Decorator, DecoratorIterator, Felt, FieldElement, Operation, StackTopState,
     StarkField, Word, CHIPLETS_WIDTH, DECODER_TRACE_WIDTH, MIN_STACK_DEPTH, MIN_TRACE_LEN,
@@ -22,7 +24,7 @@ mod operations;
 
 mod system;
 use system::System;
-pub use system::FMP_MIN;
+pub use system::{FMP_MIN, KERNEL_CONTEXT, ROOT_CONTEXT};
 
 mod decoder;
 use decoder::Decoder;
@@ -34,22 +36,56 @@ mod range;
 use range::RangeChecker;
 
 mod advice;
-use advice::AdviceProvider;
+pub use advice::{
+    AdviceProvider, AdviceRecord, ChainedProvider, FallbackProvider, HintProvider,
+    MemAdviceProvider, RecordingProvider, ResolvedAdvice, ResolvingProvider,
+    BLS381_FP_REDUCE_EVENT, U64_DIVMOD_EVENT, VEC_SORT_EVENT,
+};
 
 mod chiplets;
 use chiplets::Chiplets;
+pub use chiplets::{ChipletsLengths, MemoryAccess, MemoryAccessType};
 
 mod trace;
-pub use trace::ExecutionTrace;
 use trace::TraceFragment;
+pub use trace::{ColumnGroup, ColumnSelection, ExecutionTrace, TraceDump, TraceLenSummary};
 
 mod errors;
-pub use errors::ExecutionError;
+pub use errors::{
+    CheckpointError, ExecutionError, HotSwapError, StackMismatch, EXECUTION_ERROR_INDEX,
+};
+
+mod metering;
+use metering::GasMeter;
+pub use metering::{GasSchedule, OpClass};
+
+mod protection;
+pub use protection::{ContextScope, MemoryPermission, MemoryPermissions, MemoryViolation};
+
+mod profiling;
+pub use profiling::{BlockProfile, BlockStats};
+
+mod coverage;
+pub use coverage::{Coverage, CoverageReport};
+
+mod options;
+pub use options::ExecutionOptions;
+
+mod checkpoint;
+use checkpoint::{CallerState, Frame, FrameStack};
+pub use checkpoint::{ExecutionStatus, CHECKPOINT_VERSION};
+
+mod specializer;
+pub use specializer::{specialize, SpecializationRecord};
+
+mod hot_swap;
+pub use hot_swap::{hot_swap_iter, replace_procedure};
 
 mod utils;
 
 mod debug;
-pub use debug::{AsmOpInfo, VmState, VmStateIterator};
+use debug::CallTrace;
+pub use debug::{AsmOpInfo, CallStack, CallStackFrame, VmState, VmStateIterator};
 
 // TYPE ALIASES
 // ================================================================================================
@@ -97,6 +133,61 @@ pub fn execute(
     Ok(trace)
 }
 
+/// Returns an execution trace resulting from executing the provided program against the provided
+/// inputs, with non-deterministic inputs supplied by the provided advice provider.
+///
+/// Only the stack inputs are taken from the provided inputs; the advice tape and advice sets of
+/// the inputs are ignored. To inspect the provider after the execution (e.g., to read advice
+/// recorded by a [RecordingProvider]), a mutable reference to the provider can be passed in.
+pub fn execute_with_advice<A: AdviceProvider>(
+    program: &Program,
+    inputs: &ProgramInputs,
+    advice: A,
+) -> Result<ExecutionTrace, ExecutionError> {
+    let mut process = Process::with_advice_provider(inputs.clone(), advice);
+    process.execute(program)?;
+    let trace = ExecutionTrace::new(process);
+    assert_eq!(
+        program.hash(),
+        trace.program_hash(),
+        "inconsistent program hash"
+    );
+    Ok(trace)
+}
+
+/// Returns an execution trace resulting from executing the provided program against the provided
+/// inputs, with execution controlled by the provided options.
+///
+/// If debug mode is enabled in the options, the program is executed in debug mode; in this case,
+/// execution errors are returned as [ExecutionError::WithCallStack] errors.
+///
+/// # Errors
+/// In addition to errors which can be returned by [execute], returns an error if:
+/// - The program does not complete within the maximum number of cycles set in the options.
+/// - Gas metering is enabled and executing the program would consume more gas than the limit set
+///   in the options.
+/// - The program makes a memory access which violates the memory permissions set in the options.
+pub fn execute_with_options(
+    program: &Program,
+    inputs: &ProgramInputs,
+    options: ExecutionOptions,
+) -> Result<ExecutionTrace, ExecutionError> {
+    let process = if options.is_debug_mode() {
+        Process::new_debug(inputs.clone())
+    } else {
+        Process::new(inputs.clone())
+    };
+    let mut process = process.with_options(options);
+    process.execute(program)?;
+    let trace = ExecutionTrace::new(process);
+    assert_eq!(
+        program.hash(),
+        trace.program_hash(),
+        "inconsistent program hash"
+    );
+    Ok(trace)
+}
+
 /// Returns an iterator that allows callers to step through each execution and inspect
 /// vm state information along side.
 pub fn execute_iter(program: &Program, inputs: &ProgramInputs) -> VmStateIterator {
@@ -112,16 +203,50 @@ pub fn execute_iter(program: &Program, inputs: &ProgramInputs) -> VmStateIterato
     VmStateIterator::new(process, result)
 }
 
+/// Resumes execution of the provided program in the provided process (e.g., a process restored
+/// from a checkpoint), and returns an iterator which steps through the entire execution of the
+/// program, including the part executed before the process was paused.
+///
+/// This allows debuggers to continue stepping through a program without re-executing it from the
+/// start, e.g., after a procedure was hot-swapped via [Process::hot_swap()]. Execution of the
+/// program must either be paused in the process, or not started yet.
+///
+/// # Panics
+/// Panics if a program has already been executed to completion in the process.
+pub fn resume_iter(mut process: Process, program: &Program) -> VmStateIterator {
+    let result = process.execute_until(program, usize::MAX).map(|_| ());
+    VmStateIterator::new(process, result)
+}
+
 // PROCESS
 // ================================================================================================
 
-pub struct Process {
+/// A process executing a program on Miden VM.
+///
+/// Non-deterministic inputs are supplied to the process by an [AdviceProvider]; by default, this
+/// is a [MemAdviceProvider] instantiated from the advice tape and advice sets of program inputs.
+pub struct Process<A = MemAdviceProvider> {
     system: System,
     decoder: Decoder,
     stack: Stack,
     range: RangeChecker,
     chiplets: Chiplets,
-    advice: AdviceProvider,
+    advice: A,
+    kernel: Kernel,
+    cb_table: CodeBlockTable,
+    fn_hash: Word,
+    next_ctx: u32,
+    max_cycles: Option<usize>,
+    meter: Option<GasMeter>,
+    memory_permissions: MemoryPermissions,
+    profile: Option<BlockProfile>,
+    coverage: Option<Coverage>,
+    call_trace: Option<CallTrace>,
+    random_seed: Option<[u8; 32]>,
+    frames: FrameStack,
+    pause_at: Option<usize>,
+    pause_before: Option<Digest>,
+    paused: Option<Digest>,
 }
 
 impl Process {
@@ -129,36 +254,102 @@ impl Process {
     // --------------------------------------------------------------------------------------------
     /// Creates a new process with the provided inputs.
     pub fn new(inputs: ProgramInputs) -> Self {
-        Self::initialize(inputs, false)
+        let stack = Stack::new(&inputs, MIN_TRACE_LEN, false);
+        Self::initialize(stack, MemAdviceProvider::new(inputs), false)
     }
 
     /// Creates a new process with provided inputs and debug options enabled.
     pub fn new_debug(inputs: ProgramInputs) -> Self {
-        Self::initialize(inputs, true)
+        let stack = Stack::new(&inputs, MIN_TRACE_LEN, true);
+        Self::initialize(stack, MemAdviceProvider::new(inputs), true)
     }
+}
 
-    fn initialize(inputs: ProgramInputs, in_debug_mode: bool) -> Self {
+impl<A: AdviceProvider> Process<A> {
+    /// Creates a new process with the provided inputs and non-deterministic inputs supplied by
+    /// the provided advice provider. The advice tape and advice sets of the inputs are ignored.
+    pub fn with_advice_provider(inputs: ProgramInputs, advice: A) -> Self {
+        let stack = Stack::new(&inputs, MIN_TRACE_LEN, false);
+        Self::initialize(stack, advice, false)
+    }
+
+    fn initialize(stack: Stack, advice: A, in_debug_mode: bool) -> Self {
         Self {
             system: System::new(MIN_TRACE_LEN),
             decoder: Decoder::new(in_debug_mode),
-            stack: Stack::new(&inputs, MIN_TRACE_LEN, in_debug_mode),
+            stack,
             range: RangeChecker::new(),
-            chiplets: Chiplets::default(),
-            advice: AdviceProvider::new(inputs),
+            chiplets: Chiplets::new(in_debug_mode),
+            advice,
+            kernel: Kernel::default(),
+            cb_table: CodeBlockTable::default(),
+            fn_hash: [ZERO; 4],
+            next_ctx: KERNEL_CONTEXT + 1,
+            max_cycles: None,
+            meter: None,
+            memory_permissions: MemoryPermissions::default(),
+            profile: None,
+            coverage: None,
+            call_trace: in_debug_mode.then(CallTrace::default),
+            random_seed: None,
+            frames: FrameStack::default(),
+            pause_at: None,
+            pause_before: None,
+            paused: None,
         }
     }
 
+    /// Applies the provided execution options to this process.
+    ///
+    /// Whether a process executes programs in debug mode is determined when the process is
+    /// created (see [Process::new_debug()]), and thus the debug mode of the options is ignored.
+    ///
+    /// # Panics
+    /// Panics if a program has already been executed in this process.
+    pub fn with_options(mut self, options: ExecutionOptions) -> Self {
+        assert_eq!(
+            self.system.clk(),
+            0,
+            "a program has already been executed in this process"
+        );
+        self.max_cycles = options.max_cycles();
+        if let Some(expected_cycles) = options.expected_cycles() {
+            // the trace contains one more row than the number of executed cycles
+            let num_rows = expected_cycles.min(self.max_cycles.unwrap_or(usize::MAX));
+            let num_rows = num_rows.saturating_add(1);
+            self.system.reserve_trace_capacity(num_rows);
+            self.decoder.reserve_trace_capacity(num_rows);
+            self.stack.reserve_trace_capacity(num_rows);
+        }
+        self.meter = options
+            .gas_schedule()
+            .map(|schedule| GasMeter::new(schedule, options.gas_limit()));
+        self.memory_permissions = options.memory_permissions().clone();
+        self.profile = options
+            .is_block_profiling_enabled()
+            .then(BlockProfile::default);
+        self.coverage = options.is_coverage_enabled().then(Coverage::default);
+        self.random_seed = options.random_seed();
+        self
+    }
+
     // PROGRAM EXECUTOR
     // --------------------------------------------------------------------------------------------
 
     /// Executes the provided [Program] in this process.
+    ///
+    /// In debug mode, errors are returned as [ExecutionError::WithCallStack] errors which carry the
+    /// chain of procedures being executed when execution failed.
     pub fn execute(&mut self, program: &Program) -> Result<(), ExecutionError> {
         assert_eq!(
             self.system.clk(),
             0,
             "a program has already been executed in this process"
         );
+        self.kernel = program.kernel().clone();
+        self.cb_table = program.cb_table().clone();
         self.execute_code_block(program.root())
+            .map_err(|err| self.attach_call_stack(err))
     }
 
     // CODE BLOCK EXECUTORS
@@ -166,102 +357,371 @@ impl Process {
 
     /// Executes the specified [CodeBlock].
     ///
+    /// If execution of the block is being resumed from a checkpoint, the block continues from the
+    /// point at which it was paused.
+    ///
     /// # Errors
-    /// Returns an [ExecutionError] if executing the specified block fails for any reason.
+    /// Returns an [ExecutionError] if executing the specified block fails for any reason, or if
+    /// execution is paused before the block is started.
     fn execute_code_block(&mut self, block: &CodeBlock) -> Result<(), ExecutionError> {
+        self.check_block_pause(block)?;
+        self.profile_enter();
         match block {
             CodeBlock::Join(block) => self.execute_join_block(block),
             CodeBlock::Split(block) => self.execute_split_block(block),
             CodeBlock::Loop(block) => self.execute_loop_block(block),
             CodeBlock::Span(block) => self.execute_span_block(block),
-            CodeBlock::Proxy(_) => Err(ExecutionError::UnexecutableCodeBlock(block.clone())),
-            _ => Err(ExecutionError::UnsupportedCodeBlock(block.clone())),
-        }
+            CodeBlock::Call(block) if block.is_syscall() => self.execute_syscall_block(block),
+            CodeBlock::Call(block) => self.execute_call_block(block),
+            CodeBlock::Dyn(block) => self.execute_dyn_block(block),
+            CodeBlock::Proxy(block) => self.execute_proxy_block(block),
+        }?;
+        self.profile_exit(block.hash());
+        Ok(())
     }
 
     /// Executes the specified [Join] block.
     #[inline(always)]
     fn execute_join_block(&mut self, block: &Join) -> Result<(), ExecutionError> {
-        self.start_join_block(block)?;
+        let first_done = match self.resume_frame() {
+            Some(Frame::Join(first_done)) => first_done,
+            Some(_) => return Err(self.invalid_frame()),
+            None => {
+                self.start_join_block(block)?;
+                self.frames.push(Frame::Join(false));
+                false
+            }
+        };
 
         // execute first and then second child of the join block
-        self.execute_code_block(block.first())?;
+        if !first_done {
+            self.execute_code_block(block.first())?;
+            self.frames.set(Frame::Join(true));
+        }
         self.execute_code_block(block.second())?;
 
+        self.frames.pop();
         self.end_join_block(block)
     }
 
     /// Executes the specified [Split] block.
     #[inline(always)]
     fn execute_split_block(&mut self, block: &Split) -> Result<(), ExecutionError> {
-        // start the SPLIT block; this also pops the stack and returns the popped element
-        let condition = self.start_split_block(block)?;
+        let condition = match self.resume_frame() {
+            Some(Frame::Split(condition)) => condition,
+            Some(_) => return Err(self.invalid_frame()),
+            None => {
+                // start the SPLIT block; this also pops the stack and returns the popped element
+                let condition = self.start_split_block(block)?;
+                if condition != ONE && condition != ZERO {
+                    return Err(ExecutionError::NotBinaryValue(condition));
+                }
+                let condition = condition == ONE;
+                self.frames.push(Frame::Split(condition));
+                condition
+            }
+        };
 
         // execute either the true or the false branch of the split block based on the condition
-        if condition == ONE {
+        if condition {
             self.execute_code_block(block.on_true())?;
-        } else if condition == ZERO {
-            self.execute_code_block(block.on_false())?;
         } else {
-            return Err(ExecutionError::NotBinaryValue(condition));
+            self.execute_code_block(block.on_false())?;
         }
 
+        self.frames.pop();
         self.end_split_block(block)
     }
 
     /// Executes the specified [Loop] block.
     #[inline(always)]
     fn execute_loop_block(&mut self, block: &Loop) -> Result<(), ExecutionError> {
-        // start the LOOP block; this also pops the stack and returns the popped element
-        let condition = self.start_loop_block(block)?;
+        match self.resume_frame() {
+            // the loop body was being executed when execution was paused
+            Some(Frame::Loop) => self.execute_code_block(block.body())?,
+            Some(_) => return Err(self.invalid_frame()),
+            None => {
+                // start the LOOP block; this also pops the stack and returns the popped element
+                let condition = self.start_loop_block(block)?;
+
+                // if the top of the stack is ZERO, skip the loop body; in this case, don't drop
+                // the condition from the stack when the LOOP block ends because it was already
+                // dropped when we started the LOOP block
+                if condition == ZERO {
+                    return self.end_loop_block(block, false);
+                } else if condition != ONE {
+                    return Err(ExecutionError::NotBinaryValue(condition));
+                }
+
+                // execute the loop body at least once
+                self.frames.push(Frame::Loop);
+                self.execute_code_block(block.body())?;
+            }
+        }
 
-        // if the top of the stack is ONE, execute the loop body; otherwise skip the loop body
-        if condition == ONE {
-            // execute the loop body at least once
+        // keep executing the loop body until the condition on the top of the stack is no longer
+        // ONE; each iteration of the loop is preceded by executing REPEAT operation which drops
+        // the condition from the stack
+        while self.stack.peek() == ONE {
+            self.charge_gas(Operation::Repeat)?;
+            self.decoder.repeat();
+            self.execute_op(Operation::Drop)?;
             self.execute_code_block(block.body())?;
+        }
 
-            // keep executing the loop body until the condition on the top of the stack is no
-            // longer ONE; each iteration of the loop is preceded by executing REPEAT operation
-            // which drops the condition from the stack
-            while self.stack.peek() == ONE {
-                self.decoder.repeat();
-                self.execute_op(Operation::Drop)?;
-                self.execute_code_block(block.body())?;
+        // end the LOOP block and drop the condition from the stack
+        self.frames.pop();
+        self.end_loop_block(block, true)
+    }
+
+    /// Executes the specified [Dyn] block.
+    ///
+    /// The MAST root of the procedure to be executed is read from the top word of the stack, and
+    /// the code of the procedure is requested from the advice provider. The stack is not modified
+    /// before the procedure is executed.
+    ///
+    /// # Errors
+    /// Returns an error if the advice provider does not have a code block with the specified root,
+    /// or if the hash of the code block supplied by the provider does not match the root.
+    #[inline(always)]
+    fn execute_dyn_block(&mut self, block: &Dyn) -> Result<(), ExecutionError> {
+        let (root, resumed) = match self.resume_frame() {
+            Some(Frame::Dyn(root)) => (root, true),
+            Some(_) => return Err(self.invalid_frame()),
+            None => {
+                let root = [
+                    self.stack.get(3),
+                    self.stack.get(2),
+                    self.stack.get(1),
+                    self.stack.get(0),
+                ];
+                (root.into(), false)
             }
+        };
+        let body = self
+            .advice
+            .get_code_block(root)
+            .ok_or(ExecutionError::CodeBlockNotFound(root))?;
+        if body.hash() != root {
+            return Err(ExecutionError::InvalidCodeBlock(root, body.hash()));
+        }
 
-            // end the LOOP block and drop the condition from the stack
-            self.end_loop_block(block, true)
-        } else if condition == ZERO {
-            // end the LOOP block, but don't drop the condition from the stack because it was
-            // already dropped when we started the LOOP block
-            self.end_loop_block(block, false)
-        } else {
-            Err(ExecutionError::NotBinaryValue(condition))
+        if !resumed {
+            self.start_dyn_block(block, root)?;
+            self.frames.push(Frame::Dyn(root));
+        }
+        self.trace_enter(root);
+        self.execute_code_block(&body)?;
+        self.trace_exit();
+
+        self.frames.pop();
+        self.end_dyn_block(block)
+    }
+
+    /// Executes the specified [Call] block.
+    ///
+    /// The called procedure is executed in a new memory context: memory accessed by the procedure
+    /// is separate from the memory of the caller, and locals of the procedure are allocated
+    /// starting from the bottom of the local memory. The procedure must leave the stack at the
+    /// same depth as it was when the procedure was invoked.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - The code of the called procedure is not known.
+    /// - The stack depth after the procedure returns differs from the depth before the call.
+    #[inline(always)]
+    fn execute_call_block(&mut self, block: &Call) -> Result<(), ExecutionError> {
+        let fn_hash = block.fn_hash();
+        let body = self
+            .cb_table
+            .get(fn_hash)
+            .cloned()
+            .ok_or(ExecutionError::CodeBlockNotFound(fn_hash))?;
+
+        let caller = match self.resume_frame() {
+            Some(Frame::Call(caller)) => caller,
+            Some(_) => return Err(self.invalid_frame()),
+            None => {
+                self.start_call_block(block)?;
+
+                // every call is executed in a fresh memory context; the context remembers the
+                // hash of the procedure which created it
+                let ctx = self.next_ctx;
+                self.next_ctx += 1;
+                let caller = self.enter_context(ctx);
+                self.fn_hash = fn_hash.into();
+                self.frames.push(Frame::Call(caller));
+                caller
+            }
+        };
+
+        self.trace_enter(fn_hash);
+        self.execute_code_block(&body)?;
+        self.trace_exit();
+        self.exit_context(caller)?;
+
+        self.frames.pop();
+        self.end_call_block(block)
+    }
+
+    /// Executes the specified system call block, i.e., a [Call] block which invokes a procedure of
+    /// the kernel against which the program was compiled.
+    ///
+    /// The kernel procedure is executed in the kernel context in the same way as procedures
+    /// invoked via [Call] blocks are executed in new contexts, except that memory of the kernel
+    /// context persists between system calls.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - The system call is made from the kernel context.
+    /// - The invoked procedure is not a part of the kernel, or its code is not known.
+    /// - The stack depth after the procedure returns differs from the depth before the call.
+    #[inline(always)]
+    fn execute_syscall_block(&mut self, block: &Call) -> Result<(), ExecutionError> {
+        let frame = self.resume_frame();
+        let fn_hash = block.fn_hash();
+        if frame.is_none() && self.system.ctx() == KERNEL_CONTEXT {
+            return Err(ExecutionError::SyscallFromKernel(fn_hash));
+        }
+        if !self.kernel.contains_proc(fn_hash) {
+            return Err(ExecutionError::SyscallTargetNotInKernel(fn_hash));
         }
+        let body = self
+            .cb_table
+            .get(fn_hash)
+            .cloned()
+            .ok_or(ExecutionError::CodeBlockNotFound(fn_hash))?;
+
+        let caller = match frame {
+            Some(Frame::Call(caller)) => caller,
+            Some(_) => return Err(self.invalid_frame()),
+            None => {
+                self.start_syscall_block(block)?;
+                let caller = self.enter_context(KERNEL_CONTEXT);
+                self.frames.push(Frame::Call(caller));
+                caller
+            }
+        };
+
+        self.trace_enter(fn_hash);
+        self.execute_code_block(&body)?;
+        self.trace_exit();
+        self.exit_context(caller)?;
+
+        self.frames.pop();
+        self.end_syscall_block(block)
+    }
+
+    /// Switches the process to the specified memory context, in which the body of a called
+    /// procedure is executed. The procedure starts with the free memory pointer set to its
+    /// minimum value.
+    ///
+    /// Returns the state of the caller, which is restored once the procedure returns.
+    fn enter_context(&mut self, ctx: u32) -> CallerState {
+        let caller = CallerState {
+            ctx: self.system.ctx(),
+            fmp: self.system.fmp(),
+            stack_depth: self.stack.depth(),
+            fn_hash: self.fn_hash,
+        };
+        self.set_ctx(ctx);
+        self.system.set_fmp(Felt::new(FMP_MIN));
+        caller
+    }
+
+    /// Switches the process back to the context of the caller once a called procedure returns.
+    ///
+    /// # Errors
+    /// Returns an error if the stack depth after the procedure returns differs from the depth
+    /// before the call.
+    fn exit_context(&mut self, caller: CallerState) -> Result<(), ExecutionError> {
+        // make sure the procedure kept the stack interface
+        if self.stack.depth() != caller.stack_depth {
+            return Err(ExecutionError::InvalidStackDepthOnReturn(
+                caller.stack_depth,
+                self.stack.depth(),
+            ));
+        }
+        self.set_ctx(caller.ctx);
+        self.system.set_fmp(caller.fmp);
+        self.fn_hash = caller.fn_hash;
+
+        Ok(())
+    }
+
+    /// Executes the specified [Proxy] block.
+    ///
+    /// The body of a proxy block is not a part of the program; it is requested from the advice
+    /// provider and is then executed in place of the proxy block. Thus, the resulting execution
+    /// trace is the same as if the body was a part of the program.
+    ///
+    /// # Errors
+    /// Returns an error if the advice provider does not have a code block with the hash of the
+    /// proxy block, or if the hash of the code block supplied by the provider does not match the
+    /// hash of the proxy block.
+    fn execute_proxy_block(&mut self, block: &Proxy) -> Result<(), ExecutionError> {
+        let body = self
+            .advice
+            .get_code_block(block.hash())
+            .ok_or_else(|| ExecutionError::CodeBlockNotFound(block.hash()))?;
+        if body.hash() != block.hash() {
+            return Err(ExecutionError::InvalidCodeBlock(block.hash(), body.hash()));
+        }
+        self.trace_enter(block.hash());
+        self.execute_code_block(&body)?;
+        self.trace_exit();
+        Ok(())
     }
 
     /// Executes the specified [Span] block.
     #[inline(always)]
     fn execute_span_block(&mut self, block: &Span) -> Result<(), ExecutionError> {
-        self.start_span_block(block)?;
-
-        let mut op_offset = 0;
+        let op_batches = block.op_batches();
         let mut decorators = block.decorator_iter();
-
-        // execute the first operation batch
-        self.execute_op_batch(&block.op_batches()[0], &mut decorators, op_offset)?;
-        op_offset += block.op_batches()[0].ops().len();
+        let mut op_offset = 0;
+        self.trace_span(Some(block.hash()));
+        self.coverage_span(Some(block));
+
+        let next_batch = match self.resume_frame() {
+            Some(Frame::Span(next_batch)) if (1..op_batches.len()).contains(&next_batch) => {
+                // skip the batches (and their decorators) which were executed before execution
+                // was paused
+                for op_batch in op_batches[..next_batch].iter() {
+                    for _ in 0..op_batch.ops().len() {
+                        while decorators.next(op_offset).is_some() {}
+                        op_offset += 1;
+                    }
+                }
+                next_batch
+            }
+            Some(_) => return Err(self.invalid_frame()),
+            None => {
+                self.start_span_block(block)?;
+                self.frames.push(Frame::Span(1));
+
+                // execute the first operation batch
+                self.execute_op_batch(&op_batches[0], &mut decorators, op_offset)?;
+                op_offset += op_batches[0].ops().len();
+                1
+            }
+        };
 
         // if the span contains more operation batches, execute them. each additional batch is
         // preceded by a RESPAN operation; executing RESPAN operation does not change the state
-        // of the stack
-        for op_batch in block.op_batches().iter().skip(1) {
+        // of the stack. execution can be paused before any of these batches.
+        for (i, op_batch) in op_batches.iter().enumerate().skip(next_batch) {
+            self.frames.set(Frame::Span(i));
+            self.check_pause()?;
+            self.charge_gas(Operation::Respan)?;
             self.respan(op_batch);
             self.execute_op(Operation::Noop)?;
             self.execute_op_batch(op_batch, &mut decorators, op_offset)?;
             op_offset += op_batch.ops().len();
         }
 
+        self.frames.pop();
+        self.trace_span(None);
+        self.coverage_span(None);
         self.end_span_block(block)
     }
 
@@ -290,13 +750,16 @@ impl Process {
 
         // execute operations in the batch one by one
         for (i, &op) in batch.ops().iter().enumerate() {
+            self.trace_op(i + op_offset);
             while let Some(decorator) = decorators.next(i + op_offset) {
                 self.execute_decorator(decorator)?;
             }
 
             // decode and execute the operation
+            self.charge_gas(op)?;
             self.decoder.execute_user_op(op, op_idx);
             self.execute_op(op)?;
+            self.coverage_op(i + op_offset);
 
             // if the operation carries an immediate value, the value is stored at the next group
             // pointer; so, we advance the pointer to the following group
@@ -315,6 +778,7 @@ impl Process {
                     // is enough room in the group to execute a NOOP (if there isn't, there is a
                     // bug somewhere in the assembler)
                     debug_assert!(op_idx < OP_GROUP_SIZE - 1, "invalid op index");
+                    self.charge_gas(Operation::Noop)?;
                     self.decoder.execute_user_op(Operation::Noop, op_idx + 1);
                     self.execute_op(Operation::Noop)?;
                 }
@@ -338,6 +802,7 @@ impl Process {
         // make sure we execute the required number of operation groups; this would happen when
         // the actual number of operation groups was not a power of two
         for group_idx in group_idx..num_batch_groups {
+            self.charge_gas(Operation::Noop)?;
             self.decoder.execute_user_op(Operation::Noop, 0);
             self.execute_op(Operation::Noop)?;
 
@@ -352,12 +817,41 @@ impl Process {
         Ok(())
     }
 
+    /// Switches the process to the specified memory context starting with the next clock cycle.
+    fn set_ctx(&mut self, ctx: u32) {
+        self.system.set_ctx(ctx);
+        self.chiplets.set_mem_ctx(ctx);
+    }
+
     // PUBLIC ACCESSORS
     // --------------------------------------------------------------------------------------------
     pub fn get_memory_value(&self, addr: u64) -> Option<Word> {
         self.chiplets.get_mem_value(addr)
     }
 
+    /// Returns all memory reads and writes made so far, sorted first by context, then by address,
+    /// and then by clock cycle.
+    pub fn memory_accesses(&self) -> Vec<MemoryAccess> {
+        self.chiplets.get_mem_accesses()
+    }
+
+    /// Returns the advice provider of this process.
+    pub fn advice_provider(&self) -> &A {
+        &self.advice
+    }
+
+    /// Returns the execution trace of the program executed in this process.
+    ///
+    /// # Panics
+    /// Panics if execution of the program was paused and has not been completed.
+    pub fn into_trace(self) -> ExecutionTrace {
+        assert!(
+            !self.is_paused(),
+            "execution of the program has not been completed"
+        );
+        ExecutionTrace::new(self)
+    }
+
     pub fn to_components(self) -> (System, Decoder, Stack, RangeChecker, Chiplets) {
         (
             self.system,
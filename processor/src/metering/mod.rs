@@ -0,0 +1,237 @@
+use super::{AdviceProvider, ExecutionError, Operation, Process, StarkField};
+use vm_core::utils::{
+    bytes::{read_option, write_option},
+    ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable,
+};
+
+#[cfg(test)]
+mod tests;
+
+// OPERATION CLASS
+// ================================================================================================
+
+/// A class of operations for the purposes of gas metering.
+///
+/// Operations of the same class are charged the same amount of gas, except for Merkle tree
+/// operations which are charged per level of the tree.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum OpClass {
+    /// Operations which start, continue, or end code blocks (e.g., JOIN, SPAN, REPEAT, END).
+    ControlFlow,
+    /// System, field, and stack manipulation operations, as well as operations which push values
+    /// onto the stack.
+    Basic,
+    /// u32 operations which require range checks of their results.
+    U32,
+    /// Bitwise u32 operations executed in the bitwise chiplet.
+    Bitwise,
+    /// Operations accessing random access memory.
+    Memory,
+    /// Operations reading from the advice tape.
+    Advice,
+    /// Hash permutations executed in the hash chiplet.
+    Hash,
+    /// Merkle path verifications and Merkle root updates; these are charged per tree level.
+    Merkle,
+}
+
+impl OpClass {
+    /// Number of operation classes.
+    pub const COUNT: usize = 8;
+
+    /// Returns the class of the specified operation.
+    pub fn of(op: Operation) -> Self {
+        match op {
+            Operation::Join
+            | Operation::Split
+            | Operation::Loop
+            | Operation::Dyn
+            | Operation::Call
+            | Operation::SysCall
+            | Operation::Repeat
+            | Operation::Span
+            | Operation::Respan
+            | Operation::End
+            | Operation::Halt => Self::ControlFlow,
+
+            Operation::U32split
+            | Operation::U32add
+            | Operation::U32add3
+            | Operation::U32sub
+            | Operation::U32mul
+            | Operation::U32madd
+            | Operation::U32div
+            | Operation::U32assert2 => Self::U32,
+
+            Operation::U32and | Operation::U32or | Operation::U32xor => Self::Bitwise,
+
+            Operation::MLoadW | Operation::MStoreW | Operation::MLoad | Operation::MStore => {
+                Self::Memory
+            }
+
+            Operation::Read | Operation::ReadW => Self::Advice,
+
+            Operation::RpPerm => Self::Hash,
+            Operation::MpVerify | Operation::MrUpdate(_) => Self::Merkle,
+
+            _ => Self::Basic,
+        }
+    }
+
+    /// Returns the index of this class in a [GasSchedule].
+    fn index(&self) -> usize {
+        *self as usize
+    }
+}
+
+// GAS SCHEDULE
+// ================================================================================================
+
+/// Amounts of gas charged for executing operations of each [OpClass].
+///
+/// By default, most operations are charged one unit of gas. Operations which also require range
+/// checks or memory accesses are charged two units, while operations which take 8 rows of the
+/// hasher or bitwise chiplets are charged 8 units (for Merkle operations, 8 units per tree level).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GasSchedule {
+    costs: [u64; OpClass::COUNT],
+}
+
+impl GasSchedule {
+    /// Returns a schedule which charges the same amount of gas for all operation classes.
+    pub fn uniform(cost: u64) -> Self {
+        Self {
+            costs: [cost; OpClass::COUNT],
+        }
+    }
+
+    /// Sets the amount of gas charged for operations of the specified class.
+    pub fn with_cost(mut self, class: OpClass, cost: u64) -> Self {
+        self.costs[class.index()] = cost;
+        self
+    }
+
+    /// Returns the amount of gas charged for operations of the specified class.
+    pub fn cost(&self, class: OpClass) -> u64 {
+        self.costs[class.index()]
+    }
+}
+
+impl Default for GasSchedule {
+    fn default() -> Self {
+        Self::uniform(1)
+            .with_cost(OpClass::U32, 2)
+            .with_cost(OpClass::Bitwise, 8)
+            .with_cost(OpClass::Memory, 2)
+            .with_cost(OpClass::Hash, 8)
+            .with_cost(OpClass::Merkle, 8)
+    }
+}
+
+// GAS METER
+// ================================================================================================
+
+/// Keeps track of the gas consumed by a process.
+pub(super) struct GasMeter {
+    schedule: GasSchedule,
+    limit: Option<u64>,
+    used: u64,
+}
+
+impl GasMeter {
+    /// Returns a meter which charges gas according to the specified schedule, and which fails
+    /// once the consumed gas would exceed the specified limit.
+    pub fn new(schedule: GasSchedule, limit: Option<u64>) -> Self {
+        Self {
+            schedule,
+            limit,
+            used: 0,
+        }
+    }
+
+    /// Returns the amount of gas consumed so far.
+    pub fn used(&self) -> u64 {
+        self.used
+    }
+}
+
+// SERIALIZATION
+// ================================================================================================
+
+impl Serializable for GasSchedule {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        for cost in self.costs {
+            target.write_u64(cost);
+        }
+    }
+}
+
+impl Deserializable for GasSchedule {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let mut costs = [0; OpClass::COUNT];
+        for cost in costs.iter_mut() {
+            *cost = source.read_u64()?;
+        }
+        Ok(Self { costs })
+    }
+}
+
+impl Serializable for GasMeter {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.schedule.write_into(target);
+        write_option(target, self.limit, |target, limit| target.write_u64(limit));
+        target.write_u64(self.used);
+    }
+}
+
+impl Deserializable for GasMeter {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        Ok(Self {
+            schedule: GasSchedule::read_from(source)?,
+            limit: read_option(source, |source| source.read_u64())?,
+            used: source.read_u64()?,
+        })
+    }
+}
+
+// METERING PROCESS EXTENSION
+// ================================================================================================
+
+impl<A: AdviceProvider> Process<A> {
+    /// Charges gas for executing the specified operation in the current clock cycle. This is a
+    /// noop unless the process was instantiated with gas metering enabled.
+    ///
+    /// Merkle tree operations are charged per level of the tree; the depth of the tree is
+    /// expected to be at the top of the stack.
+    ///
+    /// # Errors
+    /// Returns an error if charging the operation would exceed the gas limit of the process; in
+    /// this case, no gas is charged.
+    pub(super) fn charge_gas(&mut self, op: Operation) -> Result<(), ExecutionError> {
+        let meter = match self.meter.as_mut() {
+            Some(meter) => meter,
+            None => return Ok(()),
+        };
+
+        let class = OpClass::of(op);
+        let mut cost = meter.schedule.cost(class);
+        if class == OpClass::Merkle {
+            cost = cost.saturating_mul(self.stack.get(0).as_int());
+        }
+
+        let used = meter.used.saturating_add(cost);
+        if let Some(limit) = meter.limit {
+            if used > limit {
+                return Err(ExecutionError::GasLimitExceeded(self.system.clk(), limit));
+            }
+        }
+        meter.used = used;
+        Ok(())
+    }
+
+    /// Returns the amount of gas consumed by this process so far, or None if gas metering is not
+    /// enabled for this process.
+    pub fn gas_used(&self) -> Option<u64> {
+        self.meter.as_ref().map(|meter| meter.used())
+    }
+}
@@ -0,0 +1,86 @@
+use super::{
+    super::{execute, execute_with_options, ExecutionOptions, Process, ProgramInputs},
+    ExecutionError, GasSchedule, OpClass, Operation,
+};
+use vm_core::{Program, StarkField};
+
+#[test]
+fn op_classes() {
+    assert_eq!(OpClass::ControlFlow, OpClass::of(Operation::Repeat));
+    assert_eq!(OpClass::Basic, OpClass::of(Operation::Caller));
+    assert_eq!(OpClass::U32, OpClass::of(Operation::U32madd));
+    assert_eq!(OpClass::Bitwise, OpClass::of(Operation::U32xor));
+    assert_eq!(OpClass::Memory, OpClass::of(Operation::MStoreW));
+    assert_eq!(OpClass::Advice, OpClass::of(Operation::ReadW));
+    assert_eq!(OpClass::Hash, OpClass::of(Operation::RpPerm));
+    assert_eq!(OpClass::Merkle, OpClass::of(Operation::MrUpdate(true)));
+}
+
+#[test]
+fn gas_metering() {
+    let program = compile("begin push.7 pop.mem.0 repeat.3 push.mem.0 drop end end");
+
+    // with a uniform schedule, gas is charged once for every cycle
+    let options = ExecutionOptions::default().with_gas_schedule(GasSchedule::uniform(1));
+    let mut process = Process::new(ProgramInputs::none()).with_options(options);
+    process.execute(&program).unwrap();
+    let num_cycles = process.system.clk() as u64;
+    assert_eq!(Some(num_cycles), process.gas_used());
+
+    // only memory accesses are charged
+    let schedule = GasSchedule::uniform(0).with_cost(OpClass::Memory, 5);
+    let options = ExecutionOptions::default().with_gas_schedule(schedule);
+    let trace = execute_with_options(&program, &ProgramInputs::none(), options).unwrap();
+    assert_eq!(Some(20), trace.gas_used());
+
+    // without metering, no gas is reported
+    let trace = execute(&program, &ProgramInputs::none()).unwrap();
+    assert_eq!(None, trace.gas_used());
+}
+
+#[test]
+fn gas_limit() {
+    let program = compile("begin push.1 push.2 add end");
+    let options = ExecutionOptions::default().with_gas_schedule(GasSchedule::uniform(1));
+    let trace = execute_with_options(&program, &ProgramInputs::none(), options).unwrap();
+    let gas_used = trace.gas_used().unwrap();
+
+    // the default schedule is used when only the limit is specified
+    let options = ExecutionOptions::default().with_gas_limit(gas_used);
+    assert_eq!(Some(GasSchedule::default()), options.gas_schedule());
+    let trace = execute_with_options(&program, &ProgramInputs::none(), options).unwrap();
+    assert_eq!(Some(gas_used), trace.gas_used());
+
+    // execution is aborted before the last operation (END of the span) is executed
+    let options = ExecutionOptions::default().with_gas_limit(gas_used - 1);
+    let result = execute_with_options(&program, &ProgramInputs::none(), options);
+    match result {
+        Err(ExecutionError::GasLimitExceeded(clk, limit)) => {
+            assert_eq!(gas_used - 1, clk as u64);
+            assert_eq!(gas_used - 1, limit);
+        }
+        _ => panic!("expected gas limit to be exceeded"),
+    }
+}
+
+#[test]
+fn read_gas_used() {
+    // SPAN is the only operation executed before the gas is read
+    let program = compile("begin adv.gas push.adv.1 end");
+    let options = ExecutionOptions::default().with_gas_schedule(GasSchedule::uniform(3));
+    let trace = execute_with_options(&program, &ProgramInputs::none(), options).unwrap();
+    assert_eq!(3, trace.last_stack_state()[0].as_int());
+
+    // without metering, ZERO is read
+    let trace = execute(&program, &ProgramInputs::none()).unwrap();
+    assert_eq!(0, trace.last_stack_state()[0].as_int());
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+fn compile(source: &str) -> Program {
+    miden_assembly::Assembler::default()
+        .compile(source)
+        .unwrap()
+}
@@ -1,11 +1,11 @@
 use vm_core::{Felt, StarkField};
 
-use super::{ExecutionError, Operation, Process};
+use super::{AdviceProvider, ExecutionError, Operation, Process};
 
 // CRYPTOGRAPHIC OPERATIONS
 // ================================================================================================
 
-impl Process {
+impl<A: AdviceProvider> Process<A> {
     // HASHING OPERATIONS
     // --------------------------------------------------------------------------------------------
     /// Applies Rescue Prime permutation to the top 12 elements of the stack. The stack is assumed
@@ -216,7 +216,7 @@ mod tests {
         super::{init_stack_with, Felt, FieldElement, Operation, StarkField},
         Process,
     };
-    use crate::Word;
+    use crate::{AdviceProvider, Word};
     use rand_utils::rand_vector;
     use vm_core::{
         chiplets::hasher::{apply_permutation, STATE_WIDTH},
@@ -1,9 +1,9 @@
-use super::{utils::assert_binary, ExecutionError, Felt, FieldElement, Process};
+use super::{utils::assert_binary, AdviceProvider, ExecutionError, Felt, FieldElement, Process};
 
 // FIELD OPERATIONS
 // ================================================================================================
 
-impl Process {
+impl<A: AdviceProvider> Process<A> {
     // ARITHMETIC OPERATIONS
     // --------------------------------------------------------------------------------------------
     /// Pops two elements off the stack, adds them together, and pushes the result back onto the
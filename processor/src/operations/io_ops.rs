@@ -1,9 +1,10 @@
-use super::{ExecutionError, Felt, Operation, Process};
+use super::{AdviceProvider, ExecutionError, Felt, Operation, Process};
+use crate::MemoryAccessType;
 
 // INPUT / OUTPUT OPERATIONS
 // ================================================================================================
 
-impl Process {
+impl<A: AdviceProvider> Process<A> {
     // CONSTANT INPUTS
     // --------------------------------------------------------------------------------------------
 
@@ -32,6 +33,7 @@ impl Process {
     pub(super) fn op_mloadw(&mut self) -> Result<(), ExecutionError> {
         // get the address from the stack and read the word from memory
         let addr = self.stack.get(0);
+        self.check_memory_access(addr, MemoryAccessType::Read)?;
         let word = self.chiplets.read_mem(addr);
 
         // update the stack state
@@ -57,6 +59,7 @@ impl Process {
     pub(super) fn op_mload(&mut self) -> Result<(), ExecutionError> {
         // get the address from the stack and read the word from memory
         let addr = self.stack.get(0);
+        self.check_memory_access(addr, MemoryAccessType::Read)?;
         let word = self.chiplets.read_mem(addr);
 
         // update the stack state
@@ -83,6 +86,7 @@ impl Process {
     pub(super) fn op_mstorew(&mut self) -> Result<(), ExecutionError> {
         // get the address from the stack and build the word to be saved from the stack values
         let addr = self.stack.get(0);
+        self.check_memory_access(addr, MemoryAccessType::Write)?;
 
         let word = [
             self.stack.get(4),
@@ -122,6 +126,7 @@ impl Process {
         // get the address and the value from the stack
         let addr = self.stack.get(0);
         let value = self.stack.get(1);
+        self.check_memory_access(addr, MemoryAccessType::Write)?;
 
         // write the value to the memory and get the previous word
         let old_word = self.chiplets.write_mem_single(addr, value);
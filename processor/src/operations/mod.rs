@@ -1,4 +1,4 @@
-use super::{ExecutionError, Felt, FieldElement, Operation, Process, StarkField};
+use super::{AdviceProvider, ExecutionError, Felt, FieldElement, Operation, Process, StarkField};
 
 mod crypto_ops;
 mod field_ops;
@@ -11,9 +11,16 @@ mod utils;
 // OPERATION DISPATCHER
 // ================================================================================================
 
-impl Process {
+impl<A: AdviceProvider> Process<A> {
     /// Executes the specified operation.
     pub(super) fn execute_op(&mut self, op: Operation) -> Result<(), ExecutionError> {
+        // abort the execution if the program ran for the maximum number of cycles
+        if let Some(max_cycles) = self.max_cycles {
+            if self.system.clk() >= max_cycles {
+                return Err(ExecutionError::CycleLimitExceeded(max_cycles));
+            }
+        }
+
         // make sure there is enough memory allocated to hold the execution trace
         self.ensure_trace_capacity();
 
@@ -21,13 +28,18 @@ impl Process {
         match op {
             // ----- system operations ------------------------------------------------------------
             Operation::Noop => self.stack.copy_state(0),
-            Operation::Assert => self.op_assert()?,
+            Operation::Assert(err_code) => self.op_assert(err_code)?,
+            Operation::Caller => self.op_caller()?,
+            Operation::Clk => self.op_clk()?,
 
             // ----- flow control operations ------------------------------------------------------
             // control flow operations are never executed directly
             Operation::Join => unreachable!("control flow operation"),
             Operation::Split => unreachable!("control flow operation"),
             Operation::Loop => unreachable!("control flow operation"),
+            Operation::Dyn => unreachable!("control flow operation"),
+            Operation::Call => unreachable!("control flow operation"),
+            Operation::SysCall => unreachable!("control flow operation"),
             Operation::Repeat => unreachable!("control flow operation"),
             Operation::Span => unreachable!("control flow operation"),
             Operation::Respan => unreachable!("control flow operation"),
@@ -145,18 +157,19 @@ impl Process {
         self.system.ensure_trace_capacity();
         self.stack.ensure_trace_capacity();
     }
+}
 
+#[cfg(test)]
+impl Process {
     // TEST METHODS
     // --------------------------------------------------------------------------------------------
 
     /// Instantiates a new blank process for testing purposes.
-    #[cfg(test)]
     fn new_dummy() -> Self {
         Self::new(super::ProgramInputs::none())
     }
 
     /// Instantiates a new process with an advice tape for testing purposes.
-    #[cfg(test)]
     fn new_dummy_with_advice_tape(advice_tape: &[u64]) -> Self {
         let inputs = super::ProgramInputs::new(&[], advice_tape, vec![]).unwrap();
         Self::new(inputs)
@@ -164,14 +177,12 @@ impl Process {
 
     /// Instantiates a new blank process with one decoder trace row for testing purposes. This
     /// allows for setting helpers in the decoder when executing operations during tests.
-    #[cfg(test)]
     fn new_dummy_with_decoder_helpers() -> Self {
         Self::new_dummy_with_inputs_and_decoder_helpers(super::ProgramInputs::none())
     }
 
     /// Instantiates a new process having Program inputs along with one decoder trace row
     /// for testing purposes.
-    #[cfg(test)]
     fn new_dummy_with_inputs_and_decoder_helpers(input: super::ProgramInputs) -> Self {
         let mut process = Self::new(input);
         process.decoder.add_dummy_trace_row();
@@ -1,6 +1,8 @@
-use super::{super::MIN_STACK_DEPTH, ExecutionError, Felt, FieldElement, Process, StarkField};
+use super::{
+    super::MIN_STACK_DEPTH, AdviceProvider, ExecutionError, Felt, FieldElement, Process, StarkField,
+};
 
-impl Process {
+impl<A: AdviceProvider> Process<A> {
     // STACK MANIPULATION
     // --------------------------------------------------------------------------------------------
     /// Pushes a ZERO onto the stack.
@@ -1,24 +1,52 @@
 use super::{
-    super::system::{FMP_MAX, FMP_MIN},
-    ExecutionError, Felt, FieldElement, Process, StarkField,
+    super::system::{FMP_MAX, FMP_MIN, KERNEL_CONTEXT},
+    AdviceProvider, ExecutionError, Felt, FieldElement, Process, StarkField,
 };
 
 // SYSTEM OPERATIONS
 // ================================================================================================
 
-impl Process {
+impl<A: AdviceProvider> Process<A> {
     /// Pops a value off the stack and asserts that it is equal to ONE.
     ///
     /// # Errors
-    /// Returns an error if the popped value is not ONE.
-    pub(super) fn op_assert(&mut self) -> Result<(), ExecutionError> {
+    /// Returns an error carrying the specified error code if the popped value is not ONE.
+    pub(super) fn op_assert(&mut self, err_code: u32) -> Result<(), ExecutionError> {
         if self.stack.get(0) != Felt::ONE {
-            return Err(ExecutionError::FailedAssertion(self.system.clk()));
+            return Err(ExecutionError::FailedAssertion(self.system.clk(), err_code));
         }
         self.stack.shift_left(1);
         Ok(())
     }
 
+    /// Overwrites the top four stack items with the hash of the caller, i.e., of the procedure
+    /// which created the context from which the current system call was made.
+    ///
+    /// # Errors
+    /// Returns an error if the operation is executed outside of the kernel context.
+    pub(super) fn op_caller(&mut self) -> Result<(), ExecutionError> {
+        if self.system.ctx() != KERNEL_CONTEXT {
+            return Err(ExecutionError::CallerOutsideOfKernel(self.system.clk()));
+        }
+
+        // the root context is not created by a procedure, and thus its hash is all ZEROs
+        self.stack.set(0, self.fn_hash[3]);
+        self.stack.set(1, self.fn_hash[2]);
+        self.stack.set(2, self.fn_hash[1]);
+        self.stack.set(3, self.fn_hash[0]);
+        self.stack.copy_state(4);
+
+        Ok(())
+    }
+
+    /// Pushes the current value of the clock cycle onto the stack.
+    pub(super) fn op_clk(&mut self) -> Result<(), ExecutionError> {
+        let clk = self.system.clk();
+        self.stack.set(0, Felt::new(clk as u64));
+        self.stack.shift_right(0);
+        Ok(())
+    }
+
     // FREE MEMORY POINTER
     // --------------------------------------------------------------------------------------------
 
@@ -61,7 +89,7 @@ impl Process {
 mod tests {
     use super::{
         super::{init_stack_with, Operation},
-        Felt, FieldElement, Process, FMP_MAX, FMP_MIN,
+        ExecutionError, Felt, FieldElement, Process, FMP_MAX, FMP_MIN, KERNEL_CONTEXT,
     };
 
     #[test]
@@ -72,7 +100,53 @@ mod tests {
         process.execute_op(Operation::Swap).unwrap();
         process.execute_op(Operation::Drop).unwrap();
 
-        assert!(process.execute_op(Operation::Assert).is_ok());
+        assert!(process.execute_op(Operation::Assert(0)).is_ok());
+
+        // a failing assertion reports its error code
+        let mut process = Process::new_dummy();
+        match process.execute_op(Operation::Assert(4096)) {
+            Err(ExecutionError::FailedAssertion(_, err_code)) => assert_eq!(4096, err_code),
+            _ => panic!("assertion did not fail"),
+        }
+    }
+
+    #[test]
+    fn op_caller() {
+        // the caller hash is not defined outside of the kernel context
+        let mut process = Process::new_dummy();
+        init_stack_with(&mut process, &[1, 2, 3, 4, 5]);
+        assert!(matches!(
+            process.execute_op(Operation::Caller),
+            Err(ExecutionError::CallerOutsideOfKernel(_))
+        ));
+
+        // the hash of the root context is all ZEROs
+        process.system.set_ctx(KERNEL_CONTEXT);
+        process.execute_op(Operation::Caller).unwrap();
+        let expected = build_expected(&[0, 0, 0, 0, 1]);
+        assert_eq!(expected, process.stack.trace_state());
+
+        // the hash of other contexts is the hash of the procedure which created the context
+        process.fn_hash = [1, 2, 3, 4].map(Felt::new);
+        process.execute_op(Operation::Caller).unwrap();
+        let expected = build_expected(&[4, 3, 2, 1, 1]);
+        assert_eq!(expected, process.stack.trace_state());
+    }
+
+    #[test]
+    fn op_clk() {
+        let mut process = Process::new_dummy();
+        init_stack_with(&mut process, &[1, 2]);
+
+        // two cycles were taken by initializing the stack
+        process.execute_op(Operation::Clk).unwrap();
+        let expected = build_expected(&[2, 2, 1]);
+        assert_eq!(expected, process.stack.trace_state());
+
+        process.execute_op(Operation::Drop).unwrap();
+        process.execute_op(Operation::Clk).unwrap();
+        let expected = build_expected(&[4, 2, 1]);
+        assert_eq!(expected, process.stack.trace_state());
     }
 
     #[test]
@@ -0,0 +1,209 @@
+use super::{GasSchedule, MemoryPermissions};
+
+// EXECUTION OPTIONS
+// ================================================================================================
+
+/// Options which control how a program is executed by a process.
+///
+/// By default, the number of cycles a program can run for is not limited (other than by the
+/// maximum length of an execution trace). Setting the maximum number of cycles aborts runaway
+/// programs early, while setting the expected number of cycles allows the processor to allocate
+/// memory for the execution trace upfront.
+///
+/// By default, gas metering is disabled. Metering is enabled by specifying either a gas schedule
+/// or a gas limit; if only the limit is specified, the default [GasSchedule] is used.
+///
+/// By default, block profiling is disabled. When it is enabled, the processor records the number
+/// of cycles spent executing every code block (see [BlockProfile](crate::BlockProfile)).
+///
+/// By default, coverage recording is disabled. When it is enabled, the processor records the
+/// number of times every operation of every span block was executed (see
+/// [Coverage](crate::Coverage)).
+///
+/// By default, programs are not executed in debug mode. In debug mode, decorators emitted by the
+/// assembler in debug mode (e.g., debug instructions) are executed, and execution errors carry the
+/// [CallStack](crate::CallStack) at which execution failed; debug mode slows execution down.
+///
+/// By default, memory accesses are not restricted. Memory permissions mark ranges of addresses as
+/// read-only or inaccessible in some contexts; accesses which violate them abort execution (see
+/// [MemoryPermissions]).
+///
+/// The processor never draws randomness from the operating system. The values injected into the
+/// last rows of an execution trace are drawn from a hash-based random coin seeded with the random
+/// seed of the options, or with the program hash if no seed is specified.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ExecutionOptions {
+    max_cycles: Option<usize>,
+    expected_cycles: Option<usize>,
+    gas_schedule: Option<GasSchedule>,
+    gas_limit: Option<u64>,
+    block_profiling: bool,
+    coverage: bool,
+    random_seed: Option<[u8; 32]>,
+    debug_mode: bool,
+    memory_permissions: MemoryPermissions,
+}
+
+impl ExecutionOptions {
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Sets the maximum number of cycles a program can run for. Execution of a program is
+    /// aborted if the program does not complete within this number of cycles.
+    pub fn with_max_cycles(mut self, max_cycles: usize) -> Self {
+        self.max_cycles = Some(max_cycles);
+        self
+    }
+
+    /// Sets the number of cycles a program is expected to run for. This is only a hint used to
+    /// allocate memory for the execution trace; the program can run for any number of cycles.
+    pub fn with_expected_cycles(mut self, expected_cycles: usize) -> Self {
+        self.expected_cycles = Some(expected_cycles);
+        self
+    }
+
+    /// Enables gas metering with the specified schedule.
+    pub fn with_gas_schedule(mut self, schedule: GasSchedule) -> Self {
+        self.gas_schedule = Some(schedule);
+        self
+    }
+
+    /// Enables gas metering and sets the maximum amount of gas which can be consumed by a
+    /// program. Execution of a program is aborted before an operation which would push the
+    /// consumed gas past this limit.
+    pub fn with_gas_limit(mut self, limit: u64) -> Self {
+        self.gas_limit = Some(limit);
+        self
+    }
+
+    /// Enables recording of the number of cycles spent executing every code block.
+    pub fn with_block_profiling(mut self) -> Self {
+        self.block_profiling = true;
+        self
+    }
+
+    /// Enables recording of the number of times every operation of every span block is executed.
+    pub fn with_coverage(mut self) -> Self {
+        self.coverage = true;
+        self
+    }
+
+    /// Sets the seed from which the random values injected into the last rows of an execution
+    /// trace are drawn.
+    pub fn with_random_seed(mut self, seed: [u8; 32]) -> Self {
+        self.random_seed = Some(seed);
+        self
+    }
+
+    /// Enables execution of programs in debug mode.
+    pub fn with_debug_mode(mut self) -> Self {
+        self.debug_mode = true;
+        self
+    }
+
+    /// Restricts the memory accesses a program can make to the specified permissions.
+    pub fn with_memory_permissions(mut self, permissions: MemoryPermissions) -> Self {
+        self.memory_permissions = permissions;
+        self
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the maximum number of cycles a program can run for, if any.
+    pub fn max_cycles(&self) -> Option<usize> {
+        self.max_cycles
+    }
+
+    /// Returns the number of cycles a program is expected to run for, if known.
+    pub fn expected_cycles(&self) -> Option<usize> {
+        self.expected_cycles
+    }
+
+    /// Returns true if gas metering is enabled.
+    pub fn is_metered(&self) -> bool {
+        self.gas_schedule.is_some() || self.gas_limit.is_some()
+    }
+
+    /// Returns the gas schedule used for metering, or None if gas metering is disabled.
+    pub fn gas_schedule(&self) -> Option<GasSchedule> {
+        if self.is_metered() {
+            Some(self.gas_schedule.clone().unwrap_or_default())
+        } else {
+            None
+        }
+    }
+
+    /// Returns the maximum amount of gas which can be consumed by a program, if any.
+    pub fn gas_limit(&self) -> Option<u64> {
+        self.gas_limit
+    }
+
+    /// Returns true if block profiling is enabled.
+    pub fn is_block_profiling_enabled(&self) -> bool {
+        self.block_profiling
+    }
+
+    /// Returns true if coverage recording is enabled.
+    pub fn is_coverage_enabled(&self) -> bool {
+        self.coverage
+    }
+
+    /// Returns the seed for the random values injected into execution traces, if any.
+    pub fn random_seed(&self) -> Option<[u8; 32]> {
+        self.random_seed
+    }
+
+    /// Returns true if programs are executed in debug mode.
+    pub fn is_debug_mode(&self) -> bool {
+        self.debug_mode
+    }
+
+    /// Returns the restrictions imposed on the memory accesses of a program.
+    pub fn memory_permissions(&self) -> &MemoryPermissions {
+        &self.memory_permissions
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::ExecutionOptions;
+    use crate::{execute, execute_with_options, ExecutionError, ProgramInputs};
+    use vm_core::{code_blocks::CodeBlock, Operation, Program, TRACE_WIDTH};
+    use winterfell::Trace;
+
+    #[test]
+    fn max_cycles() {
+        // SPAN, 3 operations, END
+        let program = Program::new(CodeBlock::new_span(vec![Operation::Pad; 3]));
+        let inputs = ProgramInputs::none();
+
+        let options = ExecutionOptions::default().with_max_cycles(5);
+        assert!(execute_with_options(&program, &inputs, options).is_ok());
+
+        let options = ExecutionOptions::default().with_max_cycles(4);
+        let result = execute_with_options(&program, &inputs, options);
+        assert!(matches!(result, Err(ExecutionError::CycleLimitExceeded(4))));
+    }
+
+    #[test]
+    fn expected_cycles() {
+        let program = Program::new(CodeBlock::new_span(vec![Operation::Pad; 100]));
+        let inputs = ProgramInputs::none();
+
+        // the hint does not affect the resulting trace, even if it is inaccurate
+        let expected = execute(&program, &inputs).unwrap();
+        for expected_cycles in [10, 1000] {
+            let options = ExecutionOptions::default().with_expected_cycles(expected_cycles);
+            let trace = execute_with_options(&program, &inputs, options).unwrap();
+            assert_eq!(expected.length(), trace.length());
+            for i in 0..TRACE_WIDTH {
+                let column = trace.main_segment().get_column(i);
+                assert_eq!(expected.main_segment().get_column(i), column);
+            }
+        }
+    }
+}
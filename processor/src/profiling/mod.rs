@@ -0,0 +1,179 @@
+use super::{AdviceProvider, Digest, Felt, Process, StarkField, Vec, Word};
+use vm_core::utils::{
+    bytes::{read_seq, read_usize, read_word, write_seq, write_usize, write_word},
+    collections::BTreeMap,
+    ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable,
+};
+
+#[cfg(test)]
+mod tests;
+
+// BLOCK STATISTICS
+// ================================================================================================
+
+/// Execution statistics of a single code block.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct BlockStats {
+    count: u64,
+    cycles: u64,
+}
+
+impl BlockStats {
+    /// Returns the number of times the block was executed.
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// Returns the number of cycles spent executing the block, including the cycles spent
+    /// executing all of its nested blocks, summed over all executions of the block.
+    pub fn cycles(&self) -> u64 {
+        self.cycles
+    }
+}
+
+// BLOCK PROFILE
+// ================================================================================================
+
+/// Cycles spent executing each code block of a program.
+///
+/// Blocks are identified by their hashes; thus, the statistics of all blocks with the same hash
+/// (e.g., a procedure inlined at several places of a program) are combined. Only blocks which
+/// were executed to completion are included in the profile.
+///
+/// The body of a procedure invoked via `exec` is usually merged with the surrounding code by the
+/// assembler, and thus the procedure does not appear in the profile as a separate block. To
+/// attribute cycles to procedures, the program should be compiled with procedure profiling
+/// enabled in the assembler.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct BlockProfile {
+    blocks: BTreeMap<[u64; 4], BlockStats>,
+    starts: Vec<usize>,
+    depth: usize,
+}
+
+impl BlockProfile {
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the statistics of the block with the specified hash, or None if such a block was
+    /// not executed.
+    pub fn get(&self, hash: Digest) -> Option<BlockStats> {
+        self.blocks.get(&hash_key(hash)).copied()
+    }
+
+    /// Returns the number of distinct blocks in this profile.
+    pub fn len(&self) -> usize {
+        self.blocks.len()
+    }
+
+    /// Returns true if no blocks were executed to completion.
+    pub fn is_empty(&self) -> bool {
+        self.blocks.is_empty()
+    }
+
+    /// Returns an iterator over the hashes and statistics of all blocks in this profile.
+    pub fn iter(&self) -> impl Iterator<Item = (Digest, BlockStats)> + '_ {
+        self.blocks.iter().map(|(key, &stats)| {
+            let word: Word = key.map(Felt::new);
+            (Digest::from(word), stats)
+        })
+    }
+
+    // STATE MUTATORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Records that a block was entered at the specified clock cycle. If the block is being
+    /// resumed, the cycle at which the block was originally started is kept.
+    fn enter(&mut self, clk: usize) {
+        if self.depth == self.starts.len() {
+            self.starts.push(clk);
+        }
+        self.depth += 1;
+    }
+
+    /// Records that the innermost block being executed, which has the specified hash, ended at
+    /// the specified clock cycle.
+    fn exit(&mut self, hash: Digest, clk: usize) {
+        let start = self.starts.pop().expect("no block started");
+        self.depth -= 1;
+        let stats = self.blocks.entry(hash_key(hash)).or_default();
+        stats.count += 1;
+        stats.cycles += (clk - start) as u64;
+    }
+
+    /// Prepares the profile for resuming execution; blocks which were being executed when
+    /// execution was paused are re-entered one by one.
+    pub(super) fn rewind(&mut self) {
+        self.depth = 0;
+    }
+}
+
+// SERIALIZATION
+// ================================================================================================
+
+impl Serializable for BlockProfile {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        write_seq(target, self.blocks.iter(), |target, (key, stats)| {
+            write_word(target, &key.map(Felt::new));
+            target.write_u64(stats.count);
+            target.write_u64(stats.cycles);
+        });
+        write_seq(target, self.starts.iter(), |target, &start| {
+            write_usize(target, start)
+        });
+    }
+}
+
+impl Deserializable for BlockProfile {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let blocks = read_seq(source, |source| {
+            let key = read_word(source)?.map(|element| element.as_int());
+            let count = source.read_u64()?;
+            let cycles = source.read_u64()?;
+            Ok((key, BlockStats { count, cycles }))
+        })?;
+        let starts = read_seq(source, read_usize)?;
+        Ok(Self {
+            blocks: blocks.into_iter().collect(),
+            depth: starts.len(),
+            starts,
+        })
+    }
+}
+
+// PROFILING PROCESS EXTENSION
+// ================================================================================================
+
+impl<A: AdviceProvider> Process<A> {
+    /// Records that a code block is entered in the current clock cycle. This is a noop unless
+    /// the process was instantiated with block profiling enabled.
+    pub(super) fn profile_enter(&mut self) {
+        let clk = self.system.clk();
+        if let Some(profile) = self.profile.as_mut() {
+            profile.enter(clk);
+        }
+    }
+
+    /// Records that the code block with the specified hash ended in the current clock cycle. This
+    /// is a noop unless the process was instantiated with block profiling enabled.
+    pub(super) fn profile_exit(&mut self, hash: Digest) {
+        let clk = self.system.clk();
+        if let Some(profile) = self.profile.as_mut() {
+            profile.exit(hash, clk);
+        }
+    }
+
+    /// Returns the profile of the code blocks executed by this process so far, or None if block
+    /// profiling is not enabled for this process.
+    pub fn block_profile(&self) -> Option<&BlockProfile> {
+        self.profile.as_ref()
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the key under which statistics of the block with the specified hash are stored.
+fn hash_key(hash: Digest) -> [u64; 4] {
+    Word::from(hash).map(|element| element.as_int())
+}
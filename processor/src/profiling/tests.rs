@@ -0,0 +1,48 @@
+use super::{
+    super::{execute, execute_with_options, ExecutionOptions, ProgramInputs},
+    BlockStats, Felt,
+};
+use vm_core::{code_blocks::CodeBlock, Operation, Program};
+
+#[test]
+fn block_profiling() {
+    // the first span drops the top of the stack, and thus the loop body is executed twice
+    let span1 = CodeBlock::new_span(vec![Operation::Drop, Operation::Pad, Operation::Drop]);
+    let body = CodeBlock::new_span(vec![Operation::Push(Felt::new(3)), Operation::Drop]);
+    let span2 = CodeBlock::new_span(vec![Operation::Pad; 3]);
+    let loop_block = CodeBlock::new_loop(body.clone());
+    let program = Program::new(CodeBlock::new_join([
+        CodeBlock::new_join([span1.clone(), loop_block.clone()]),
+        span2.clone(),
+    ]));
+    let inputs = ProgramInputs::new(&[0, 1, 1, 5], &[], vec![]).unwrap();
+
+    let options = ExecutionOptions::default().with_block_profiling();
+    let trace = execute_with_options(&program, &inputs, options).unwrap();
+    let profile = trace.block_profile().unwrap();
+    assert_eq!(6, profile.len());
+
+    // SPAN, operations, END
+    assert_eq!(Some(stats(1, 5)), profile.get(span1.hash()));
+    assert_eq!(Some(stats(2, 8)), profile.get(body.hash()));
+    assert_eq!(Some(stats(1, 5)), profile.get(span2.hash()));
+
+    // LOOP, two executions of the body separated by REPEAT, END
+    assert_eq!(Some(stats(1, 11)), profile.get(loop_block.hash()));
+
+    // cycles of nested blocks are included in the cycles of their parents; the root block spans
+    // the entire execution
+    let root = profile.get(program.hash()).unwrap();
+    assert_eq!(stats(1, trace.num_cycles() as u64), root);
+    assert!(profile
+        .iter()
+        .all(|(_, stats)| stats.cycles() <= root.cycles()));
+
+    // without profiling, no profile is recorded
+    let trace = execute(&program, &inputs).unwrap();
+    assert!(trace.block_profile().is_none());
+}
+
+fn stats(count: u64, cycles: u64) -> BlockStats {
+    BlockStats { count, cycles }
+}
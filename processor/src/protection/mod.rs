@@ -0,0 +1,311 @@
+use super::{AdviceProvider, ExecutionError, Felt, MemoryAccessType, Process, StarkField, Vec};
+use core::{fmt, ops::Range};
+use vm_core::utils::{
+    bytes::{read_seq, write_seq},
+    ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable,
+};
+
+#[cfg(test)]
+mod tests;
+
+// MEMORY PERMISSION
+// ================================================================================================
+
+/// Restriction imposed on accesses to a range of memory addresses.
+///
+/// Memory which is not covered by any restriction can be both read and written.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum MemoryPermission {
+    /// The addresses can be read, but not written.
+    ReadOnly,
+    /// The addresses can be neither read nor written.
+    NoAccess,
+}
+
+impl MemoryPermission {
+    /// Returns true if this permission allows the specified type of access.
+    pub fn allows(&self, access_type: MemoryAccessType) -> bool {
+        match self {
+            Self::ReadOnly => access_type == MemoryAccessType::Read,
+            Self::NoAccess => false,
+        }
+    }
+}
+
+impl fmt::Display for MemoryPermission {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ReadOnly => write!(f, "read-only"),
+            Self::NoAccess => write!(f, "no-access"),
+        }
+    }
+}
+
+// CONTEXT SCOPE
+// ================================================================================================
+
+/// Memory contexts in which a restriction applies.
+///
+/// Every context has its own memory; a program starts executing in the
+/// [ROOT_CONTEXT](crate::ROOT_CONTEXT), kernel procedures are executed in the
+/// [KERNEL_CONTEXT](crate::KERNEL_CONTEXT), and every procedure invoked via `call` is executed in
+/// a new context.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ContextScope {
+    /// The restriction applies in all contexts.
+    All,
+    /// The restriction applies only in the specified context.
+    Context(u32),
+    /// The restriction applies in all contexts except the specified one; e.g., memory of the
+    /// kernel context can be protected from user code via `AllExcept(KERNEL_CONTEXT)`.
+    AllExcept(u32),
+}
+
+impl ContextScope {
+    /// Returns true if the specified context belongs to this scope.
+    pub fn contains(&self, ctx: u32) -> bool {
+        match self {
+            Self::All => true,
+            Self::Context(scope_ctx) => ctx == *scope_ctx,
+            Self::AllExcept(scope_ctx) => ctx != *scope_ctx,
+        }
+    }
+}
+
+// MEMORY PERMISSIONS
+// ================================================================================================
+
+/// A set of restrictions on memory accesses made by a program.
+///
+/// Each restriction applies to a range of addresses in a [ContextScope]; memory accesses which
+/// violate any restriction make execution fail with [ExecutionError::MemoryAccessViolation]
+/// before memory is accessed. By default, no restrictions are imposed.
+///
+/// Restrictions apply to the memory operations of a program (including accesses to procedure
+/// locals); they do not apply to memory read by the host (e.g., by advice injectors or debug
+/// decorators).
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MemoryPermissions {
+    rules: Vec<MemoryRule>,
+}
+
+/// A restriction imposed on a range of addresses in a scope of contexts.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct MemoryRule {
+    scope: ContextScope,
+    addrs: Range<u64>,
+    permission: MemoryPermission,
+}
+
+impl MemoryPermissions {
+    /// Returns an empty set of restrictions.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Restricts accesses to the specified range of addresses in the specified contexts.
+    ///
+    /// Ranges of different restrictions can overlap, in which case an access must satisfy all of
+    /// them.
+    pub fn with_range(
+        mut self,
+        scope: ContextScope,
+        addrs: Range<u64>,
+        permission: MemoryPermission,
+    ) -> Self {
+        self.rules.push(MemoryRule {
+            scope,
+            addrs,
+            permission,
+        });
+        self
+    }
+
+    /// Returns true if no restrictions are imposed.
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Checks whether the specified access to the specified address in the specified context is
+    /// allowed, and returns the permission and the address range of the first violated
+    /// restriction otherwise.
+    pub fn check(
+        &self,
+        ctx: u32,
+        addr: u64,
+        access_type: MemoryAccessType,
+    ) -> Result<(), (MemoryPermission, Range<u64>)> {
+        let violated = self.rules.iter().find(|rule| {
+            rule.scope.contains(ctx)
+                && rule.addrs.contains(&addr)
+                && !rule.permission.allows(access_type)
+        });
+        match violated {
+            Some(rule) => Err((rule.permission, rule.addrs.clone())),
+            None => Ok(()),
+        }
+    }
+}
+
+// MEMORY ACCESS VIOLATION
+// ================================================================================================
+
+/// Describes a memory access which violated the [MemoryPermissions] of a process: the access,
+/// and the restriction it violated.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MemoryViolation {
+    clk: u32,
+    ctx: u32,
+    addr: u64,
+    access_type: MemoryAccessType,
+    permission: MemoryPermission,
+    addrs: Range<u64>,
+}
+
+impl MemoryViolation {
+    /// Returns the clock cycle at which the access was attempted.
+    pub fn clk(&self) -> u32 {
+        self.clk
+    }
+
+    /// Returns the context in which the access was attempted.
+    pub fn ctx(&self) -> u32 {
+        self.ctx
+    }
+
+    /// Returns the accessed address.
+    pub fn addr(&self) -> u64 {
+        self.addr
+    }
+
+    /// Returns the type of the access.
+    pub fn access_type(&self) -> MemoryAccessType {
+        self.access_type
+    }
+
+    /// Returns the permission of the violated restriction.
+    pub fn permission(&self) -> MemoryPermission {
+        self.permission
+    }
+
+    /// Returns the range of addresses covered by the violated restriction.
+    pub fn addrs(&self) -> Range<u64> {
+        self.addrs.clone()
+    }
+}
+
+impl fmt::Display for MemoryViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let access = match self.access_type {
+            MemoryAccessType::Read => "read of",
+            MemoryAccessType::Write => "write to",
+        };
+        write!(
+            f,
+            "{} address {} in context {} at clock cycle {} violates {} protection of addresses \
+            {}..{}",
+            access,
+            self.addr,
+            self.ctx,
+            self.clk,
+            self.permission,
+            self.addrs.start,
+            self.addrs.end
+        )
+    }
+}
+
+// SERIALIZATION
+// ================================================================================================
+
+impl Serializable for MemoryPermissions {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        write_seq(target, &self.rules, |target, rule| {
+            match rule.scope {
+                ContextScope::All => target.write_u8(0),
+                ContextScope::Context(ctx) => {
+                    target.write_u8(1);
+                    target.write_u32(ctx);
+                }
+                ContextScope::AllExcept(ctx) => {
+                    target.write_u8(2);
+                    target.write_u32(ctx);
+                }
+            }
+            target.write_u64(rule.addrs.start);
+            target.write_u64(rule.addrs.end);
+            target.write_u8(rule.permission as u8);
+        });
+    }
+}
+
+impl Deserializable for MemoryPermissions {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let rules = read_seq(source, |source| {
+            let scope = match source.read_u8()? {
+                0 => ContextScope::All,
+                1 => ContextScope::Context(source.read_u32()?),
+                2 => ContextScope::AllExcept(source.read_u32()?),
+                value => {
+                    return Err(DeserializationError::InvalidValue(format!(
+                        "invalid context scope: {}",
+                        value
+                    )))
+                }
+            };
+            let addrs = source.read_u64()?..source.read_u64()?;
+            let permission = match source.read_u8()? {
+                0 => MemoryPermission::ReadOnly,
+                1 => MemoryPermission::NoAccess,
+                value => {
+                    return Err(DeserializationError::InvalidValue(format!(
+                        "invalid memory permission: {}",
+                        value
+                    )))
+                }
+            };
+            Ok(MemoryRule {
+                scope,
+                addrs,
+                permission,
+            })
+        })?;
+        Ok(Self { rules })
+    }
+}
+
+// MEMORY PROTECTION PROCESS EXTENSION
+// ================================================================================================
+
+impl<A: AdviceProvider> Process<A> {
+    /// Checks whether the specified access to the specified address in the current context is
+    /// allowed by the memory permissions of this process. This is a noop unless the process was
+    /// instantiated with memory permissions.
+    ///
+    /// # Errors
+    /// Returns an error if the access violates a restriction of the memory permissions.
+    pub(super) fn check_memory_access(
+        &self,
+        addr: Felt,
+        access_type: MemoryAccessType,
+    ) -> Result<(), ExecutionError> {
+        if self.memory_permissions.is_empty() {
+            return Ok(());
+        }
+
+        let ctx = self.system.ctx();
+        let addr = addr.as_int();
+        self.memory_permissions
+            .check(ctx, addr, access_type)
+            .map_err(|(permission, addrs)| {
+                ExecutionError::MemoryAccessViolation(MemoryViolation {
+                    clk: self.system.clk() as u32,
+                    ctx,
+                    addr,
+                    access_type,
+                    permission,
+                    addrs,
+                })
+            })
+    }
+}
@@ -0,0 +1,204 @@
+use super::{
+    super::{execute, execute_with_options, ExecutionOptions, ProgramInputs},
+    ContextScope, ExecutionError, MemoryAccessType, MemoryPermission, MemoryPermissions,
+    MemoryViolation,
+};
+use crate::{KERNEL_CONTEXT, ROOT_CONTEXT};
+use vm_core::{
+    utils::{Deserializable, Serializable, SliceReader},
+    Program,
+};
+
+// TEST PROGRAMS
+// ================================================================================================
+
+const KERNEL: &str = "
+    export.store
+        push.mem.0 add.1 pop.mem.0
+    end";
+
+// PERMISSION CHECKS
+// ================================================================================================
+
+#[test]
+fn permission_checks() {
+    let permissions = MemoryPermissions::new()
+        .with_range(ContextScope::All, 10..20, MemoryPermission::ReadOnly)
+        .with_range(
+            ContextScope::Context(ROOT_CONTEXT),
+            15..30,
+            MemoryPermission::NoAccess,
+        )
+        .with_range(
+            ContextScope::AllExcept(KERNEL_CONTEXT),
+            100..101,
+            MemoryPermission::NoAccess,
+        );
+
+    // unrestricted addresses can be read and written
+    assert!(permissions.check(0, 9, MemoryAccessType::Write).is_ok());
+    assert!(permissions.check(0, 30, MemoryAccessType::Read).is_ok());
+    assert!(permissions.check(5, 20, MemoryAccessType::Write).is_ok());
+
+    // read-only addresses can be read in all contexts
+    assert!(permissions.check(5, 10, MemoryAccessType::Read).is_ok());
+    assert_eq!(
+        Err((MemoryPermission::ReadOnly, 10..20)),
+        permissions.check(5, 19, MemoryAccessType::Write)
+    );
+
+    // overlapping restrictions must all be satisfied
+    assert_eq!(
+        Err((MemoryPermission::NoAccess, 15..30)),
+        permissions.check(ROOT_CONTEXT, 15, MemoryAccessType::Read)
+    );
+
+    // kernel memory is accessible only from the kernel
+    assert!(permissions
+        .check(KERNEL_CONTEXT, 100, MemoryAccessType::Write)
+        .is_ok());
+    assert!(permissions
+        .check(ROOT_CONTEXT, 100, MemoryAccessType::Read)
+        .is_err());
+    assert!(permissions.check(5, 100, MemoryAccessType::Read).is_err());
+
+    // without restrictions, all accesses are allowed
+    assert!(MemoryPermissions::new().is_empty());
+    assert!(MemoryPermissions::new()
+        .check(0, 100, MemoryAccessType::Write)
+        .is_ok());
+}
+
+#[test]
+fn permissions_serialization() {
+    let permissions = MemoryPermissions::new()
+        .with_range(ContextScope::All, 10..20, MemoryPermission::ReadOnly)
+        .with_range(ContextScope::Context(3), 0..1, MemoryPermission::NoAccess)
+        .with_range(
+            ContextScope::AllExcept(1),
+            5..u64::MAX,
+            MemoryPermission::NoAccess,
+        );
+    let mut bytes = Vec::new();
+    permissions.write_into(&mut bytes);
+    let mut source = SliceReader::new(&bytes);
+    assert_eq!(
+        permissions,
+        MemoryPermissions::read_from(&mut source).unwrap()
+    );
+}
+
+// MEMORY PROTECTION
+// ================================================================================================
+
+#[test]
+fn read_only_memory() {
+    let permissions = MemoryPermissions::new().with_range(
+        ContextScope::All,
+        100..200,
+        MemoryPermission::ReadOnly,
+    );
+    let options = ExecutionOptions::default().with_memory_permissions(permissions);
+
+    // read-only memory can be read
+    let program = compile("begin push.mem.150 push.7 pop.mem.99 push.7 pop.mem.200 end");
+    assert!(execute_with_options(&program, &ProgramInputs::none(), options.clone()).is_ok());
+
+    // writing read-only memory aborts execution, regardless of the instruction used
+    for source in [
+        "begin push.7 pop.mem.120 end",
+        "begin push.7 popw.mem.120 end",
+    ] {
+        let program = compile(source);
+        let violation = expect_violation(&program, options.clone());
+        assert_eq!(ROOT_CONTEXT, violation.ctx());
+        assert_eq!(120, violation.addr());
+        assert_eq!(MemoryAccessType::Write, violation.access_type());
+        assert_eq!(MemoryPermission::ReadOnly, violation.permission());
+        assert_eq!(100..200, violation.addrs());
+    }
+
+    // without permissions, the program can write to any address
+    let program = compile("begin push.7 pop.mem.120 end");
+    assert!(execute(&program, &ProgramInputs::none()).is_ok());
+}
+
+#[test]
+fn inaccessible_memory() {
+    let permissions =
+        MemoryPermissions::new().with_range(ContextScope::All, 0..10, MemoryPermission::NoAccess);
+    let options = ExecutionOptions::default().with_memory_permissions(permissions);
+
+    // addresses are checked at runtime, so computed addresses are protected as well
+    let program = compile("begin push.3 push.2 add push.mem push.mem.10 end");
+    let violation = expect_violation(&program, options);
+    assert_eq!(5, violation.addr());
+    assert_eq!(MemoryAccessType::Read, violation.access_type());
+    assert_eq!(
+        format!(
+            "read of address 5 in context 0 at clock cycle {} violates no-access protection of \
+            addresses 0..10",
+            violation.clk()
+        ),
+        violation.to_string()
+    );
+}
+
+#[test]
+fn kernel_memory() {
+    // memory of the kernel can be accessed only by the kernel
+    let permissions = MemoryPermissions::new().with_range(
+        ContextScope::AllExcept(KERNEL_CONTEXT),
+        0..1,
+        MemoryPermission::NoAccess,
+    );
+    let options = ExecutionOptions::default().with_memory_permissions(permissions);
+
+    let program = compile("begin syscall.store syscall.store end");
+    assert!(execute_with_options(&program, &ProgramInputs::none(), options.clone()).is_ok());
+
+    let program = compile("begin syscall.store push.mem.0 end");
+    let violation = expect_violation(&program, options);
+    assert_eq!(ROOT_CONTEXT, violation.ctx());
+    assert_eq!(0, violation.addr());
+}
+
+#[test]
+fn context_permissions() {
+    // the root context is restricted, but procedures invoked via `call` are executed in new
+    // contexts
+    let permissions = MemoryPermissions::new().with_range(
+        ContextScope::Context(ROOT_CONTEXT),
+        0..1,
+        MemoryPermission::ReadOnly,
+    );
+    let options = ExecutionOptions::default().with_memory_permissions(permissions);
+
+    let program = compile(
+        "proc.foo push.7 pop.mem.0 end
+        begin call.foo push.mem.0 drop end",
+    );
+    assert!(execute_with_options(&program, &ProgramInputs::none(), options.clone()).is_ok());
+
+    let program = compile("proc.foo push.7 pop.mem.0 end begin exec.foo end");
+    let violation = expect_violation(&program, options);
+    assert_eq!(ROOT_CONTEXT, violation.ctx());
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+fn compile(source: &str) -> Program {
+    miden_assembly::Assembler::default()
+        .with_kernel(KERNEL)
+        .unwrap()
+        .compile(source)
+        .unwrap()
+}
+
+fn expect_violation(program: &Program, options: ExecutionOptions) -> MemoryViolation {
+    match execute_with_options(program, &ProgramInputs::none(), options) {
+        Err(ExecutionError::MemoryAccessViolation(violation)) => violation,
+        _ => panic!("expected a memory access violation"),
+    }
+}
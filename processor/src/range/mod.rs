@@ -1,6 +1,9 @@
 use super::{BTreeMap, Felt, FieldElement, Vec};
 use crate::RangeCheckTrace;
-use vm_core::utils::uninit_vector;
+use vm_core::utils::{
+    bytes::{read_seq, read_usize, write_seq, write_usize},
+    uninit_vector, ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable,
+};
 
 mod aux_trace;
 pub use aux_trace::AuxTraceBuilder;
@@ -56,6 +59,7 @@ mod tests;
 /// (1, 1, 1, v) and (1, 1, 0, v). The first row specifies that there was 4 lookups and the second
 /// row add the fifth lookup.
 #[allow(dead_code)]
+#[derive(Clone)]
 pub struct RangeChecker {
     /// Tracks lookup count for each checked value.
     lookups: BTreeMap<u16, usize>,
@@ -292,6 +296,37 @@ impl Default for RangeChecker {
     }
 }
 
+// SERIALIZATION
+// ================================================================================================
+
+impl Serializable for RangeChecker {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        write_seq(target, &self.lookups, |target, (value, num_lookups)| {
+            target.write_u16(*value);
+            write_usize(target, *num_lookups);
+        });
+        write_seq(target, &self.cycle_range_checks, |target, (clk, checks)| {
+            write_usize(target, *clk);
+            checks.write_into(target);
+        });
+    }
+}
+
+impl Deserializable for RangeChecker {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let lookups = read_seq(source, |source| {
+            Ok((source.read_u16()?, read_usize(source)?))
+        })?;
+        let cycle_range_checks = read_seq(source, |source| {
+            Ok((read_usize(source)?, CycleRangeChecks::read_from(source)?))
+        })?;
+        Ok(Self {
+            lookups: lookups.into_iter().collect(),
+            cycle_range_checks: cycle_range_checks.into_iter().collect(),
+        })
+    }
+}
+
 // RANGE CHECKER ROWS
 // ================================================================================================
 
@@ -1,5 +1,9 @@
 use super::{Felt, FieldElement};
 use crate::trace::LookupTableRow;
+use vm_core::utils::{
+    bytes::{read_option, write_option},
+    ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable,
+};
 
 // PROCESSOR RANGE CHECKS
 // ================================================================================================
@@ -123,3 +127,41 @@ impl LookupTableRow for RangeCheckRequest {
         value
     }
 }
+
+// SERIALIZATION
+// ================================================================================================
+
+impl Serializable for CycleRangeChecks {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        write_option(target, self.memory.as_ref(), |target, request| {
+            if let RangeCheckRequest::Memory(values) = request {
+                Felt::write_batch_into(values, target);
+            }
+        });
+        write_option(target, self.stack.as_ref(), |target, request| {
+            if let RangeCheckRequest::Stack(values) = request {
+                Felt::write_batch_into(values, target);
+            }
+        });
+    }
+}
+
+impl Deserializable for CycleRangeChecks {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let memory = read_option(source, |source| {
+            Ok(RangeCheckRequest::Memory([
+                Felt::read_from(source)?,
+                Felt::read_from(source)?,
+            ]))
+        })?;
+        let stack = read_option(source, |source| {
+            Ok(RangeCheckRequest::Stack([
+                Felt::read_from(source)?,
+                Felt::read_from(source)?,
+                Felt::read_from(source)?,
+                Felt::read_from(source)?,
+            ]))
+        })?;
+        Ok(Self { memory, stack })
+    }
+}
@@ -0,0 +1,760 @@
+use super::{
+    CodeBlock, Digest, Felt, FieldElement, InputError, Operation, Program, StarkField, Vec,
+    MIN_STACK_DEPTH, ONE, ZERO,
+};
+use vm_core::chiplets::hasher;
+
+#[cfg(test)]
+mod tests;
+
+// PROGRAM SPECIALIZATION
+// ================================================================================================
+
+/// Returns a version of the provided program specialized for executions in which some of the
+/// initial stack values are fixed to known constants, together with a record of the
+/// specialization.
+///
+/// Fixed inputs are specified as `(position, value)` pairs, where position 0 refers to the top of
+/// the initial stack. The specialized program is obtained as follows:
+/// - Values of fixed inputs are propagated through the program until the first operation or block
+///   with a statically unknown effect on the stack (e.g., a loop with a non-constant condition).
+/// - Conditional branches and loops with statically known conditions are folded: only the taken
+///   branch of a split block is kept, and loops which are never entered are removed.
+/// - Within sequences of operations, copies of known values are replaced with constants, and
+///   operations computed entirely over constants are replaced with their results.
+/// - Consecutive spans which do not contain decorators are merged.
+///
+/// The specialized program expects the same inputs as the original program (including the fixed
+/// ones), and for every set of inputs which agrees with the fixed values it produces the same
+/// outputs as the original program.
+///
+/// # Errors
+/// Returns an error if:
+/// - Position of a fixed input is outside of the top 16 stack slots, or is specified more than
+///   once.
+/// - Value of a fixed input is not a valid field element.
+pub fn specialize(
+    program: &Program,
+    fixed_inputs: &[(usize, u64)],
+) -> Result<(Program, SpecializationRecord), InputError> {
+    let mut inputs = Vec::with_capacity(fixed_inputs.len());
+    for &(pos, value) in fixed_inputs {
+        if pos >= MIN_STACK_DEPTH {
+            return Err(InputError::InvalidStackPosition(pos));
+        }
+        if value >= Felt::MODULUS {
+            return Err(InputError::NotFieldElement(value, "fixed stack input"));
+        }
+        if inputs.iter().any(|&(p, _)| p == pos) {
+            return Err(InputError::DuplicateStackPosition(pos));
+        }
+        inputs.push((pos, Felt::new(value)));
+    }
+    inputs.sort_by_key(|&(pos, _)| pos);
+
+    let mut specializer = Specializer::new(&inputs);
+    let mut blocks = Vec::new();
+    specializer.specialize_block(program.root(), &mut blocks);
+    // called procedures are not specialized, and thus their code is carried over as is
+    let specialized = Program::with_kernel(
+        combine_blocks(blocks),
+        program.kernel().clone(),
+        program.cb_table().clone(),
+    );
+
+    let record = SpecializationRecord {
+        program_hash: program.hash(),
+        specialized_hash: specialized.hash(),
+        fixed_inputs: inputs,
+        num_folded_branches: specializer.num_folded_branches,
+    };
+    Ok((specialized, record))
+}
+
+// SPECIALIZATION RECORD
+// ================================================================================================
+
+/// Record binding a specialized program to the program from which it was derived.
+///
+/// A proof of execution of the specialized program attests to the execution of the original
+/// program as long as the public stack inputs of the proof agree with the fixed inputs of the
+/// record (see [SpecializationRecord::check_inputs()]). Thus, a verifier who trusts the
+/// specialization (e.g., because the record was produced by a trusted party, or because it can
+/// re-run the specializer) can accept proofs of the smaller program in place of proofs of the
+/// original one.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SpecializationRecord {
+    program_hash: Digest,
+    specialized_hash: Digest,
+    fixed_inputs: Vec<(usize, Felt)>,
+    num_folded_branches: usize,
+}
+
+impl SpecializationRecord {
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the hash of the original program.
+    pub fn program_hash(&self) -> Digest {
+        self.program_hash
+    }
+
+    /// Returns the hash of the specialized program.
+    pub fn specialized_hash(&self) -> Digest {
+        self.specialized_hash
+    }
+
+    /// Returns the fixed inputs as `(position, value)` pairs sorted by position, where position 0
+    /// refers to the top of the initial stack.
+    pub fn fixed_inputs(&self) -> &[(usize, Felt)] {
+        &self.fixed_inputs
+    }
+
+    /// Returns the number of split and loop blocks which were removed because their conditions
+    /// were known statically.
+    pub fn num_folded_branches(&self) -> usize {
+        self.num_folded_branches
+    }
+
+    /// Returns a commitment to this record computed as a hash of the original program hash, the
+    /// specialized program hash, and the `(position, value)` pairs of the fixed inputs.
+    pub fn commitment(&self) -> Digest {
+        let mut elements = Vec::with_capacity(8 + self.fixed_inputs.len() * 2);
+        elements.extend_from_slice(self.program_hash.as_elements());
+        elements.extend_from_slice(self.specialized_hash.as_elements());
+        for &(pos, value) in self.fixed_inputs.iter() {
+            elements.push(Felt::new(pos as u64));
+            elements.push(value);
+        }
+        hasher::hash_elements(&elements)
+    }
+
+    // VALIDATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns true if the provided stack inputs agree with the fixed inputs of this record.
+    ///
+    /// Stack inputs are expected in the order in which they are pushed onto the stack (i.e., the
+    /// last value is at the top of the stack), which is the order expected by the verifier.
+    /// Stack slots not covered by the inputs are initialized to zeros.
+    pub fn check_inputs(&self, stack_inputs: &[u64]) -> bool {
+        if stack_inputs.len() > MIN_STACK_DEPTH {
+            return false;
+        }
+        self.fixed_inputs.iter().all(|&(pos, value)| {
+            let input = match stack_inputs.len().checked_sub(pos + 1) {
+                Some(idx) => stack_inputs[idx],
+                None => 0,
+            };
+            input == value.as_int()
+        })
+    }
+}
+
+// SPECIALIZER
+// ================================================================================================
+
+/// Partially evaluates code blocks against an abstract stack.
+///
+/// The state of the specializer is None once specialization reaches a point past which the
+/// effect of the program on the stack cannot be determined statically; from then on, blocks are
+/// only simplified locally.
+struct Specializer {
+    stack: Option<AbstractStack>,
+    num_folded_branches: usize,
+}
+
+impl Specializer {
+    /// Returns a new specializer with the initial stack in which the specified positions hold the
+    /// specified values and all other positions are unknown.
+    fn new(fixed_inputs: &[(usize, Felt)]) -> Self {
+        let mut stack = AbstractStack::new();
+        for &(pos, value) in fixed_inputs {
+            stack.set(pos, Some(value));
+        }
+        Self {
+            stack: Some(stack),
+            num_folded_branches: 0,
+        }
+    }
+
+    /// Appends specialized versions of the specified block to the provided list of blocks. The
+    /// blocks appended to the list are expected to be executed in sequence.
+    fn specialize_block(&mut self, block: &CodeBlock, result: &mut Vec<CodeBlock>) {
+        match block {
+            CodeBlock::Span(span) => {
+                let mut ops = Vec::new();
+                for batch in span.op_batches() {
+                    ops.extend_from_slice(batch.ops());
+                }
+
+                // operations are not rewritten in spans with decorators since this would shift
+                // the positions at which decorators are executed
+                if !span.decorators().is_empty() {
+                    for &op in ops.iter() {
+                        self.apply(op);
+                    }
+                    result.push(block.clone());
+                    return;
+                }
+
+                let ops = ops
+                    .into_iter()
+                    .filter(|&op| op != Operation::Noop)
+                    .map(|op| {
+                        let known_copy = dup_position(op).and_then(|pos| self.get(pos));
+                        self.apply(op);
+                        known_copy.map_or(op, Operation::Push)
+                    })
+                    .collect::<Vec<_>>();
+                if !ops.is_empty() {
+                    result.push(CodeBlock::new_span(ops));
+                }
+            }
+            CodeBlock::Join(join) => {
+                self.specialize_block(join.first(), result);
+                self.specialize_block(join.second(), result);
+            }
+            CodeBlock::Split(split) => match self.pop() {
+                Some(condition) if condition == ONE || condition == ZERO => {
+                    // the condition is still on the stack, and thus it has to be dropped
+                    self.num_folded_branches += 1;
+                    result.push(CodeBlock::new_span(vec![Operation::Drop]));
+                    if condition == ONE {
+                        self.specialize_block(split.on_true(), result);
+                    } else {
+                        self.specialize_block(split.on_false(), result);
+                    }
+                }
+                _ => {
+                    let mut false_branch = Specializer {
+                        stack: self.stack.clone(),
+                        num_folded_branches: 0,
+                    };
+                    let mut on_true = Vec::new();
+                    self.specialize_block(split.on_true(), &mut on_true);
+                    let mut on_false = Vec::new();
+                    false_branch.specialize_block(split.on_false(), &mut on_false);
+
+                    self.num_folded_branches += false_branch.num_folded_branches;
+                    self.stack = match (self.stack.take(), false_branch.stack) {
+                        (Some(a), Some(b)) => a.merge(&b),
+                        _ => None,
+                    };
+                    result.push(CodeBlock::new_split(
+                        combine_blocks(on_true),
+                        combine_blocks(on_false),
+                    ));
+                }
+            },
+            CodeBlock::Loop(loop_block) => {
+                if self.pop() == Some(ZERO) {
+                    // the loop is never entered, but its condition still has to be dropped
+                    self.num_folded_branches += 1;
+                    result.push(CodeBlock::new_span(vec![Operation::Drop]));
+                    return;
+                }
+
+                // the number of iterations is not known statically, and thus neither the state
+                // at the start of the loop body nor the state after the loop can be determined
+                self.stack = None;
+                let mut body = Vec::new();
+                self.specialize_block(loop_block.body(), &mut body);
+                self.stack = None;
+                result.push(CodeBlock::new_loop(combine_blocks(body)));
+            }
+            CodeBlock::Call(_) | CodeBlock::Dyn(_) | CodeBlock::Proxy(_) => {
+                self.stack = None;
+                result.push(block.clone());
+            }
+        }
+    }
+
+    // STACK STATE
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the value at the specified position of the stack if it is known.
+    fn get(&self, pos: usize) -> Option<Felt> {
+        self.stack.as_ref().and_then(|stack| stack.get(pos))
+    }
+
+    /// Removes the top value from the stack and returns it if it is known.
+    fn pop(&mut self) -> Option<Felt> {
+        self.stack.as_mut().and_then(|stack| stack.pop())
+    }
+
+    /// Updates the state of the stack according to the specified operation.
+    ///
+    /// Values produced by operations which access memory, the advice provider, or the hasher are
+    /// treated as unknown. If the operation is not expected to be found in a span, the state of
+    /// the stack is discarded.
+    fn apply(&mut self, op: Operation) {
+        let stack = match self.stack.as_mut() {
+            Some(stack) => stack,
+            None => return,
+        };
+
+        match op {
+            Operation::Noop | Operation::MpVerify | Operation::U32assert2 => (),
+            Operation::Assert(_) | Operation::FmpUpdate | Operation::Drop => {
+                stack.pop();
+            }
+            Operation::MStoreW | Operation::MStore => {
+                stack.pop();
+            }
+            Operation::FmpAdd | Operation::MLoad => {
+                stack.pop();
+                stack.push(None);
+            }
+            Operation::Read | Operation::SDepth | Operation::Clk => stack.push(None),
+            Operation::Caller => {
+                for _ in 0..4 {
+                    stack.pop();
+                }
+                for _ in 0..4 {
+                    stack.push(None);
+                }
+            }
+
+            Operation::Add | Operation::Mul | Operation::Eq | Operation::And | Operation::Or => {
+                let b = stack.pop();
+                let a = stack.pop();
+                let result = match (a, b) {
+                    (Some(a), Some(b)) => fold_binary(op, a, b),
+                    _ => None,
+                };
+                stack.push(result);
+            }
+            Operation::Neg | Operation::Inv | Operation::Incr | Operation::Not | Operation::Eqz => {
+                let a = stack.pop();
+                stack.push(a.and_then(|a| fold_unary(op, a)));
+            }
+
+            Operation::U32split => {
+                stack.pop();
+                stack.push_unknown(2);
+            }
+            Operation::U32add | Operation::U32sub | Operation::U32mul | Operation::U32div => {
+                stack.drop_n(2);
+                stack.push_unknown(2);
+            }
+            Operation::U32add3 | Operation::U32madd => {
+                stack.drop_n(3);
+                stack.push_unknown(2);
+            }
+            Operation::U32and | Operation::U32or | Operation::U32xor => {
+                stack.drop_n(2);
+                stack.push(None);
+            }
+
+            Operation::Pad => stack.push(Some(ZERO)),
+            Operation::Push(value) => stack.push(Some(value)),
+            Operation::Dup0
+            | Operation::Dup1
+            | Operation::Dup2
+            | Operation::Dup3
+            | Operation::Dup4
+            | Operation::Dup5
+            | Operation::Dup6
+            | Operation::Dup7
+            | Operation::Dup9
+            | Operation::Dup11
+            | Operation::Dup13
+            | Operation::Dup15 => {
+                let value = dup_position(op).and_then(|pos| stack.get(pos));
+                stack.push(value);
+            }
+            Operation::Swap => stack.swap_range(0, 1, 1),
+            Operation::SwapW => stack.swap_range(0, 4, 4),
+            Operation::SwapW2 => stack.swap_range(0, 8, 4),
+            Operation::SwapW3 => stack.swap_range(0, 12, 4),
+            Operation::SwapDW => stack.swap_range(0, 8, 8),
+            Operation::MovUp2 => stack.move_up(2),
+            Operation::MovUp3 => stack.move_up(3),
+            Operation::MovUp4 => stack.move_up(4),
+            Operation::MovUp5 => stack.move_up(5),
+            Operation::MovUp6 => stack.move_up(6),
+            Operation::MovUp7 => stack.move_up(7),
+            Operation::MovUp8 => stack.move_up(8),
+            Operation::MovDn2 => stack.move_down(2),
+            Operation::MovDn3 => stack.move_down(3),
+            Operation::MovDn4 => stack.move_down(4),
+            Operation::MovDn5 => stack.move_down(5),
+            Operation::MovDn6 => stack.move_down(6),
+            Operation::MovDn7 => stack.move_down(7),
+            Operation::MovDn8 => stack.move_down(8),
+            Operation::CSwap | Operation::CSwapW => {
+                let len = if op == Operation::CSwap { 1 } else { 4 };
+                let condition = stack.pop();
+                if condition == Some(ONE) {
+                    stack.swap_range(0, len, len);
+                } else if condition != Some(ZERO) {
+                    stack.clear_range(0, len * 2);
+                }
+            }
+
+            Operation::ReadW => stack.clear_range(0, 4),
+            Operation::MLoadW => {
+                stack.pop();
+                stack.clear_range(0, 4);
+            }
+            Operation::RpPerm => stack.clear_range(0, 12),
+            Operation::MrUpdate(_) => stack.clear_range(2, 10),
+
+            Operation::Join
+            | Operation::Split
+            | Operation::Loop
+            | Operation::Dyn
+            | Operation::Call
+            | Operation::SysCall
+            | Operation::Span
+            | Operation::End
+            | Operation::Repeat
+            | Operation::Respan
+            | Operation::Halt => self.stack = None,
+        }
+    }
+}
+
+// ABSTRACT STACK
+// ================================================================================================
+
+/// Stack in which every value is either known (Some) or unknown (None).
+///
+/// Values are stored with the top of the stack at the end of the vector. Positions deeper than
+/// the tracked values are treated as unknown since they may hold values from the overflow table.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct AbstractStack(Vec<Option<Felt>>);
+
+impl AbstractStack {
+    /// Returns a stack of [MIN_STACK_DEPTH] unknown values.
+    fn new() -> Self {
+        Self(vec![None; MIN_STACK_DEPTH])
+    }
+
+    fn get(&self, pos: usize) -> Option<Felt> {
+        let len = self.0.len();
+        if pos < len {
+            self.0[len - pos - 1]
+        } else {
+            None
+        }
+    }
+
+    fn set(&mut self, pos: usize, value: Option<Felt>) {
+        self.reserve(pos + 1);
+        let idx = self.0.len() - pos - 1;
+        self.0[idx] = value;
+    }
+
+    fn push(&mut self, value: Option<Felt>) {
+        self.0.push(value);
+    }
+
+    fn push_unknown(&mut self, n: usize) {
+        self.0.extend(core::iter::repeat(None).take(n));
+    }
+
+    fn pop(&mut self) -> Option<Felt> {
+        self.0.pop().flatten()
+    }
+
+    fn drop_n(&mut self, n: usize) {
+        for _ in 0..n {
+            self.pop();
+        }
+    }
+
+    /// Swaps `len` values starting at position `a` with `len` values starting at position `b`.
+    fn swap_range(&mut self, a: usize, b: usize, len: usize) {
+        for i in 0..len {
+            let value_a = self.get(a + i);
+            let value_b = self.get(b + i);
+            self.set(a + i, value_b);
+            self.set(b + i, value_a);
+        }
+    }
+
+    /// Marks `len` values starting at the specified position as unknown.
+    fn clear_range(&mut self, pos: usize, len: usize) {
+        for i in pos..pos + len {
+            self.set(i, None);
+        }
+    }
+
+    /// Moves the value at the specified position to the top of the stack.
+    fn move_up(&mut self, pos: usize) {
+        self.reserve(pos + 1);
+        let idx = self.0.len() - pos - 1;
+        let value = self.0.remove(idx);
+        self.0.push(value);
+    }
+
+    /// Moves the value at the top of the stack to the specified position.
+    fn move_down(&mut self, pos: usize) {
+        self.reserve(pos + 1);
+        let value = self.0.pop().flatten();
+        let idx = self.0.len() - pos;
+        self.0.insert(idx, value);
+    }
+
+    /// Returns a stack in which values known to be the same in both stacks are known, or None if
+    /// the stacks have different depths.
+    fn merge(&self, other: &Self) -> Option<Self> {
+        if self.0.len() != other.0.len() {
+            return None;
+        }
+        let values = self
+            .0
+            .iter()
+            .zip(other.0.iter())
+            .map(|(a, b)| if a == b { *a } else { None })
+            .collect();
+        Some(Self(values))
+    }
+
+    /// Makes sure at least `n` values are tracked by padding the bottom of the stack with unknown
+    /// values.
+    fn reserve(&mut self, n: usize) {
+        if self.0.len() < n {
+            let padding = n - self.0.len();
+            self.0.splice(0..0, core::iter::repeat(None).take(padding));
+        }
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Combines the provided sequence of blocks into a single block.
+///
+/// Consecutive spans without decorators are merged and their operations are folded via
+/// [fold_constants()]. The remaining blocks are combined into a balanced tree of join blocks in
+/// the same way as the assembler combines blocks.
+fn combine_blocks(blocks: Vec<CodeBlock>) -> CodeBlock {
+    let mut merged_blocks = Vec::with_capacity(blocks.len());
+    let mut ops = Vec::new();
+    for block in blocks {
+        match block {
+            CodeBlock::Span(span) if span.decorators().is_empty() => {
+                for batch in span.op_batches() {
+                    ops.extend_from_slice(batch.ops());
+                }
+            }
+            block => {
+                push_span(&mut merged_blocks, &mut ops);
+                merged_blocks.push(block);
+            }
+        }
+    }
+    push_span(&mut merged_blocks, &mut ops);
+
+    if merged_blocks.is_empty() {
+        return CodeBlock::new_span(vec![Operation::Noop]);
+    }
+
+    let mut blocks = merged_blocks;
+    while blocks.len() > 1 {
+        let last_block = if blocks.len() % 2 == 0 {
+            None
+        } else {
+            blocks.pop()
+        };
+
+        let mut grouped_blocks = Vec::with_capacity(blocks.len() / 2 + 1);
+        let mut iter = blocks.into_iter();
+        while let (Some(first), Some(second)) = (iter.next(), iter.next()) {
+            grouped_blocks.push(CodeBlock::new_join([first, second]));
+        }
+        grouped_blocks.extend(last_block);
+        blocks = grouped_blocks;
+    }
+    blocks.remove(0)
+}
+
+/// Folds the accumulated operations and appends them to the list of blocks as a single span,
+/// unless all of them were folded away.
+fn push_span(blocks: &mut Vec<CodeBlock>, ops: &mut Vec<Operation>) {
+    let folded = fold_constants(ops);
+    ops.clear();
+    if !folded.is_empty() {
+        blocks.push(CodeBlock::new_span(folded));
+    }
+}
+
+/// Returns the provided sequence of operations with operations computed entirely over constants
+/// replaced with their results. NOOPs are removed.
+///
+/// Constants pushed onto the stack are tracked as pending values which are materialized only
+/// when an operation which cannot be folded needs them, or at the end of the sequence. Thus,
+/// constants which are dropped, or consumed by foldable operations, never make it into the
+/// result.
+fn fold_constants(ops: &[Operation]) -> Vec<Operation> {
+    let mut result = Vec::with_capacity(ops.len());
+    let mut pending: Vec<Felt> = Vec::new();
+
+    for &op in ops {
+        let n = pending.len();
+        let folded = match op {
+            Operation::Noop => true,
+            Operation::Pad => {
+                pending.push(ZERO);
+                true
+            }
+            Operation::Push(value) => {
+                pending.push(value);
+                true
+            }
+            Operation::Drop if n >= 1 => {
+                pending.pop();
+                true
+            }
+            Operation::Swap if n >= 2 => {
+                pending.swap(n - 1, n - 2);
+                true
+            }
+            Operation::MovUp2
+            | Operation::MovUp3
+            | Operation::MovUp4
+            | Operation::MovUp5
+            | Operation::MovUp6
+            | Operation::MovUp7
+            | Operation::MovUp8 => match move_position(op) {
+                Some(pos) if n > pos => {
+                    let value = pending.remove(n - pos - 1);
+                    pending.push(value);
+                    true
+                }
+                _ => false,
+            },
+            Operation::MovDn2
+            | Operation::MovDn3
+            | Operation::MovDn4
+            | Operation::MovDn5
+            | Operation::MovDn6
+            | Operation::MovDn7
+            | Operation::MovDn8 => match move_position(op) {
+                Some(pos) if n > pos => {
+                    let value = pending.pop().expect("no pending constants");
+                    pending.insert(n - pos - 1, value);
+                    true
+                }
+                _ => false,
+            },
+            _ if dup_position(op).map_or(false, |pos| n > pos) => {
+                let pos = dup_position(op).expect("not a dup operation");
+                pending.push(pending[n - pos - 1]);
+                true
+            }
+            Operation::Add | Operation::Mul | Operation::Eq | Operation::And | Operation::Or
+                if n >= 2 =>
+            {
+                match fold_binary(op, pending[n - 2], pending[n - 1]) {
+                    Some(value) => {
+                        pending.truncate(n - 2);
+                        pending.push(value);
+                        true
+                    }
+                    None => false,
+                }
+            }
+            Operation::Neg | Operation::Inv | Operation::Incr | Operation::Not | Operation::Eqz
+                if n >= 1 =>
+            {
+                match fold_unary(op, pending[n - 1]) {
+                    Some(value) => {
+                        pending[n - 1] = value;
+                        true
+                    }
+                    None => false,
+                }
+            }
+            _ => false,
+        };
+
+        if !folded {
+            materialize(&mut result, &mut pending);
+            result.push(op);
+        }
+    }
+    materialize(&mut result, &mut pending);
+
+    result
+}
+
+/// Appends operations pushing the pending constants onto the stack to the result; the constants
+/// are encoded in the same way as the assembler encodes `push` instructions.
+fn materialize(result: &mut Vec<Operation>, pending: &mut Vec<Felt>) {
+    for value in pending.drain(..) {
+        if value == ZERO {
+            result.push(Operation::Pad);
+        } else if value == ONE {
+            result.push(Operation::Pad);
+            result.push(Operation::Incr);
+        } else {
+            result.push(Operation::Push(value));
+        }
+    }
+}
+
+/// Returns the result of the specified binary operation applied to `a` (the second stack value)
+/// and `b` (the top stack value), or None if the operation would fail for these values.
+fn fold_binary(op: Operation, a: Felt, b: Felt) -> Option<Felt> {
+    let is_binary = |v: Felt| v == ZERO || v == ONE;
+    match op {
+        Operation::Add => Some(a + b),
+        Operation::Mul => Some(a * b),
+        Operation::Eq => Some(if a == b { ONE } else { ZERO }),
+        Operation::And if is_binary(a) && is_binary(b) => Some(a * b),
+        Operation::Or if is_binary(a) && is_binary(b) => Some(a + b - a * b),
+        _ => None,
+    }
+}
+
+/// Returns the result of the specified unary operation applied to `a`, or None if the operation
+/// would fail for this value.
+fn fold_unary(op: Operation, a: Felt) -> Option<Felt> {
+    match op {
+        Operation::Neg => Some(-a),
+        Operation::Inv if a != ZERO => Some(a.inv()),
+        Operation::Incr => Some(a + ONE),
+        Operation::Not if a == ZERO || a == ONE => Some(ONE - a),
+        Operation::Eqz => Some(if a == ZERO { ONE } else { ZERO }),
+        _ => None,
+    }
+}
+
+/// Returns the position of the stack value copied by the specified DUP operation, or None if the
+/// operation is not a DUP operation.
+fn dup_position(op: Operation) -> Option<usize> {
+    match op {
+        Operation::Dup0 => Some(0),
+        Operation::Dup1 => Some(1),
+        Operation::Dup2 => Some(2),
+        Operation::Dup3 => Some(3),
+        Operation::Dup4 => Some(4),
+        Operation::Dup5 => Some(5),
+        Operation::Dup6 => Some(6),
+        Operation::Dup7 => Some(7),
+        Operation::Dup9 => Some(9),
+        Operation::Dup11 => Some(11),
+        Operation::Dup13 => Some(13),
+        Operation::Dup15 => Some(15),
+        _ => None,
+    }
+}
+
+/// Returns the position of the stack value moved by the specified MOVUP or MOVDN operation, or
+/// None if the operation is not a MOVUP or MOVDN operation.
+fn move_position(op: Operation) -> Option<usize> {
+    match op {
+        Operation::MovUp2 | Operation::MovDn2 => Some(2),
+        Operation::MovUp3 | Operation::MovDn3 => Some(3),
+        Operation::MovUp4 | Operation::MovDn4 => Some(4),
+        Operation::MovUp5 | Operation::MovDn5 => Some(5),
+        Operation::MovUp6 | Operation::MovDn6 => Some(6),
+        Operation::MovUp7 | Operation::MovDn7 => Some(7),
+        Operation::MovUp8 | Operation::MovDn8 => Some(8),
+        _ => None,
+    }
+}
@@ -0,0 +1,254 @@
+use super::{fold_constants, specialize, CodeBlock, Felt, InputError, Operation, Program};
+use crate::{execute, ProgramInputs};
+use vm_core::StackTopState;
+
+// SPECIALIZATION TESTS
+// ================================================================================================
+
+#[test]
+fn specialize_split() {
+    let source = "begin dup.0 eq.3 if.true push.5 add else mul end end";
+    let program = compile(source);
+
+    let (specialized, record) = specialize(&program, &[(0, 3)]).unwrap();
+    assert_eq!(1, record.num_folded_branches());
+    assert_eq!(program.hash(), record.program_hash());
+    assert_eq!(specialized.hash(), record.specialized_hash());
+    assert_eq!(0, count_blocks(specialized.root(), &is_split));
+    assert_same_outputs(&program, &specialized, &[7, 3]);
+
+    // a branch which depends on a value that is not fixed is kept
+    let (specialized, record) = specialize(&program, &[(1, 3)]).unwrap();
+    assert_eq!(0, record.num_folded_branches());
+    assert_eq!(1, count_blocks(specialized.root(), &is_split));
+    assert_same_outputs(&program, &specialized, &[2, 3, 4]);
+}
+
+#[test]
+fn specialize_nested_branches() {
+    let source = "
+        begin
+            if.true
+                swap dup.0 if.true add else mul end
+            else
+                drop push.10
+            end
+        end";
+    let program = compile(source);
+
+    let (specialized, record) = specialize(&program, &[(0, 1), (2, 1)]).unwrap();
+    assert_eq!(2, record.num_folded_branches());
+    assert_eq!(0, count_blocks(specialized.root(), &is_split));
+    assert_same_outputs(&program, &specialized, &[4, 1, 6, 1]);
+
+    let (specialized, record) = specialize(&program, &[(0, 0)]).unwrap();
+    assert_eq!(1, record.num_folded_branches());
+    assert_eq!(
+        CodeBlock::new_span(vec![
+            Operation::Drop,
+            Operation::Drop,
+            Operation::Push(Felt::new(10))
+        ])
+        .hash(),
+        specialized.hash()
+    );
+    assert_same_outputs(&program, &specialized, &[8, 9, 0]);
+}
+
+#[test]
+fn specialize_loop() {
+    let source = "begin while.true push.1 add dup.0 neq.5 end end";
+    let program = compile(source);
+
+    // the loop is never entered
+    let (specialized, record) = specialize(&program, &[(0, 0)]).unwrap();
+    assert_eq!(1, record.num_folded_branches());
+    assert_eq!(0, count_blocks(specialized.root(), &is_loop));
+    assert_same_outputs(&program, &specialized, &[2, 0]);
+
+    // the number of iterations is not known statically, and thus the loop is kept
+    let (specialized, record) = specialize(&program, &[(0, 1)]).unwrap();
+    assert_eq!(0, record.num_folded_branches());
+    assert_eq!(1, count_blocks(specialized.root(), &is_loop));
+    assert_same_outputs(&program, &specialized, &[2, 1]);
+}
+
+#[test]
+fn specialize_after_unknown_state() {
+    // the loop is never entered, and thus the state of the stack after it is known
+    let source = "begin push.0 while.true push.0 end push.1 if.true push.2 else push.3 end end";
+    let program = compile(source);
+
+    let (specialized, record) = specialize(&program, &[]).unwrap();
+    assert_eq!(2, record.num_folded_branches());
+    assert_same_outputs(&program, &specialized, &[]);
+
+    // the state of the stack after the loop is unknown, and thus the split is not folded
+    let source = "begin while.true push.0 end push.1 if.true push.2 else push.3 end end";
+    let program = compile(source);
+    let (specialized, record) = specialize(&program, &[]).unwrap();
+    assert_eq!(0, record.num_folded_branches());
+    assert_eq!(1, count_blocks(specialized.root(), &is_split));
+    assert_same_outputs(&program, &specialized, &[0]);
+}
+
+#[test]
+fn specialize_merged_branch_states() {
+    // both branches leave the same value at the top of the stack, and thus the second split can
+    // be folded even though the first one cannot
+    let source = "
+        begin
+            if.true push.1 else push.1 end
+            if.true push.7 else push.8 end
+        end";
+    let program = compile(source);
+
+    let (specialized, record) = specialize(&program, &[]).unwrap();
+    assert_eq!(1, record.num_folded_branches());
+    assert_eq!(1, count_blocks(specialized.root(), &is_split));
+    assert_same_outputs(&program, &specialized, &[0]);
+    assert_same_outputs(&program, &specialized, &[1]);
+}
+
+#[test]
+fn specialize_invalid_inputs() {
+    let program = compile("begin add end");
+    assert!(matches!(
+        specialize(&program, &[(16, 1)]),
+        Err(InputError::InvalidStackPosition(16))
+    ));
+    assert!(matches!(
+        specialize(&program, &[(0, 1), (0, 2)]),
+        Err(InputError::DuplicateStackPosition(0))
+    ));
+    assert!(matches!(
+        specialize(&program, &[(0, u64::MAX)]),
+        Err(InputError::NotFieldElement(_, _))
+    ));
+}
+
+// SPECIALIZATION RECORD TESTS
+// ================================================================================================
+
+#[test]
+fn specialization_record() {
+    let program = compile("begin if.true push.1 else push.2 end end");
+    let (_, record) = specialize(&program, &[(2, 5), (0, 1)]).unwrap();
+    assert_eq!(
+        &[(0, Felt::new(1)), (2, Felt::new(5))],
+        record.fixed_inputs()
+    );
+
+    assert!(record.check_inputs(&[5, 9, 1]));
+    assert!(record.check_inputs(&[4, 5, 9, 1]));
+    assert!(!record.check_inputs(&[5, 9, 0]));
+    assert!(!record.check_inputs(&[6, 9, 1]));
+
+    // positions not covered by the inputs are initialized to zeros
+    assert!(!record.check_inputs(&[1]));
+    let (_, zero_record) = specialize(&program, &[(4, 0)]).unwrap();
+    assert!(zero_record.check_inputs(&[1]));
+
+    // commitments of records for different fixed inputs are different
+    let (_, other) = specialize(&program, &[(2, 6), (0, 1)]).unwrap();
+    assert_ne!(record.commitment(), other.commitment());
+    assert_eq!(
+        record.commitment(),
+        specialize(&program, &[(0, 1), (2, 5)])
+            .unwrap()
+            .1
+            .commitment()
+    );
+}
+
+// CONSTANT FOLDING TESTS
+// ================================================================================================
+
+#[test]
+fn fold_constant_ops() {
+    let push = |value| Operation::Push(Felt::new(value));
+
+    // arithmetic over constants
+    let ops = [push(3), push(4), Operation::Add, push(5), Operation::Mul];
+    assert_eq!(vec![push(35)], fold_constants(&ops));
+
+    // constants are encoded in the same way as in the assembler
+    let ops = [push(2), Operation::Eqz, push(7), push(7), Operation::Eq];
+    assert_eq!(
+        vec![Operation::Pad, Operation::Pad, Operation::Incr],
+        fold_constants(&ops)
+    );
+
+    // stack manipulation over constants
+    let ops = [
+        push(2),
+        push(3),
+        push(4),
+        Operation::MovUp2,
+        Operation::Swap,
+    ];
+    assert_eq!(vec![push(3), push(2), push(4)], fold_constants(&ops));
+    let ops = [
+        push(2),
+        push(3),
+        push(4),
+        Operation::MovDn2,
+        Operation::Dup1,
+    ];
+    assert_eq!(
+        vec![push(4), push(2), push(3), push(2)],
+        fold_constants(&ops)
+    );
+    let ops = [push(2), Operation::Noop, Operation::Drop];
+    assert!(fold_constants(&ops).is_empty());
+
+    // operations which need values below the constants are not folded
+    let ops = [push(2), Operation::Add, push(3), Operation::Drop];
+    assert_eq!(vec![push(2), Operation::Add], fold_constants(&ops));
+
+    // operations which would fail are not folded
+    let ops = [Operation::Pad, Operation::Inv, push(2), Operation::Not];
+    assert_eq!(ops.to_vec(), fold_constants(&ops));
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+fn compile(source: &str) -> Program {
+    let assembler = miden_assembly::Assembler::default();
+    assembler.compile(source).unwrap()
+}
+
+/// Executes both programs against the provided stack inputs and asserts that their outputs are
+/// the same.
+fn assert_same_outputs(program: &Program, specialized: &Program, stack_inputs: &[u64]) {
+    let inputs = ProgramInputs::from_stack_inputs(stack_inputs).unwrap();
+    let expected = run(program, &inputs);
+    assert_eq!(expected, run(specialized, &inputs));
+}
+
+fn run(program: &Program, inputs: &ProgramInputs) -> StackTopState {
+    execute(program, inputs).unwrap().last_stack_state()
+}
+
+fn count_blocks(block: &CodeBlock, predicate: &dyn Fn(&CodeBlock) -> bool) -> usize {
+    let nested = match block {
+        CodeBlock::Join(join) => {
+            count_blocks(join.first(), predicate) + count_blocks(join.second(), predicate)
+        }
+        CodeBlock::Split(split) => {
+            count_blocks(split.on_true(), predicate) + count_blocks(split.on_false(), predicate)
+        }
+        CodeBlock::Loop(loop_block) => count_blocks(loop_block.body(), predicate),
+        _ => 0,
+    };
+    nested + predicate(block) as usize
+}
+
+fn is_split(block: &CodeBlock) -> bool {
+    matches!(block, CodeBlock::Split(_))
+}
+
+fn is_loop(block: &CodeBlock) -> bool {
+    matches!(block, CodeBlock::Loop(_))
+}
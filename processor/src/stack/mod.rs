@@ -3,6 +3,10 @@ use super::{
     NUM_STACK_HELPER_COLS, STACK_TRACE_WIDTH, ZERO,
 };
 use core::cmp;
+use vm_core::utils::{
+    bytes::{read_usize, write_usize},
+    ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable,
+};
 
 mod trace;
 use trace::StackTrace;
@@ -261,6 +265,12 @@ impl Stack {
         self.trace.ensure_trace_capacity(self.clk);
     }
 
+    /// Allocates memory for the trace to accommodate at least the specified number of rows, so
+    /// that the trace does not need to be extended until this number of rows is reached.
+    pub fn reserve_trace_capacity(&mut self, num_rows: usize) {
+        self.trace.reserve_trace_capacity(num_rows);
+    }
+
     // TEST HELPERS
     // --------------------------------------------------------------------------------------------
 
@@ -273,3 +283,32 @@ impl Stack {
         self.trace.get_stack_state_at(self.clk)
     }
 }
+
+// SERIALIZATION
+// ================================================================================================
+
+impl Serializable for Stack {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        write_usize(target, self.clk);
+        self.trace.write_into(target);
+        self.overflow.write_into(target);
+        write_usize(target, self.depth);
+    }
+}
+
+impl Deserializable for Stack {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let stack = Self {
+            clk: read_usize(source)?,
+            trace: StackTrace::read_from(source)?,
+            overflow: OverflowTable::read_from(source)?,
+            depth: read_usize(source)?,
+        };
+        if stack.depth < MIN_STACK_DEPTH {
+            return Err(DeserializationError::InvalidValue(
+                "inconsistent stack state".into(),
+            ));
+        }
+        Ok(stack)
+    }
+}
@@ -1,6 +1,12 @@
 use super::{
     super::trace::LookupTableRow, AuxTraceBuilder, BTreeMap, Felt, FieldElement, Vec, ZERO,
 };
+use vm_core::utils::{
+    bytes::{
+        read_bool, read_seq, read_usize, read_vec, write_bool, write_seq, write_usize, write_vec,
+    },
+    ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable,
+};
 
 // OVERFLOW TABLE
 // ================================================================================================
@@ -181,3 +187,96 @@ pub enum OverflowTableUpdate {
     RowInserted(u32),
     RowRemoved(u32),
 }
+
+// SERIALIZATION
+// ================================================================================================
+
+impl Serializable for OverflowTable {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        write_vec(target, &self.all_rows);
+        write_seq(target, &self.active_rows, |target, &row| {
+            write_usize(target, row)
+        });
+        write_seq(target, &self.update_trace, |target, (clk, update)| {
+            write_usize(target, *clk);
+            update.write_into(target);
+        });
+        write_seq(target, &self.trace, |target, (clk, values)| {
+            write_usize(target, *clk);
+            write_vec(target, values);
+        });
+        write_bool(target, self.trace_enabled);
+    }
+}
+
+impl Deserializable for OverflowTable {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let all_rows: Vec<OverflowTableRow> = read_vec(source)?;
+        let active_rows = read_seq(source, read_usize)?;
+        if active_rows.iter().any(|&row| row >= all_rows.len()) {
+            return Err(DeserializationError::InvalidValue(
+                "invalid overflow table row".into(),
+            ));
+        }
+        let update_trace = read_seq(source, |source| {
+            Ok((read_usize(source)?, OverflowTableUpdate::read_from(source)?))
+        })?;
+        let trace = read_seq(source, |source| {
+            Ok((read_usize(source)?, read_vec(source)?))
+        })?;
+
+        Ok(Self {
+            all_rows,
+            active_rows,
+            update_trace,
+            trace: trace.into_iter().collect(),
+            trace_enabled: read_bool(source)?,
+        })
+    }
+}
+
+impl Serializable for OverflowTableRow {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.val.write_into(target);
+        self.clk.write_into(target);
+        self.prev.write_into(target);
+    }
+}
+
+impl Deserializable for OverflowTableRow {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        Ok(Self {
+            val: Felt::read_from(source)?,
+            clk: Felt::read_from(source)?,
+            prev: Felt::read_from(source)?,
+        })
+    }
+}
+
+impl Serializable for OverflowTableUpdate {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        match self {
+            Self::RowInserted(row) => {
+                target.write_u8(0);
+                target.write_u32(*row);
+            }
+            Self::RowRemoved(row) => {
+                target.write_u8(1);
+                target.write_u32(*row);
+            }
+        }
+    }
+}
+
+impl Deserializable for OverflowTableUpdate {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        match source.read_u8()? {
+            0 => Ok(Self::RowInserted(source.read_u32()?)),
+            1 => Ok(Self::RowRemoved(source.read_u32()?)),
+            tag => Err(DeserializationError::InvalidValue(format!(
+                "{} is not a valid overflow table update",
+                tag
+            ))),
+        }
+    }
+}
@@ -3,7 +3,13 @@ use super::{
     NUM_STACK_HELPER_COLS, STACK_TRACE_WIDTH,
 };
 use crate::utils::get_trace_len;
-use vm_core::StarkField;
+use vm_core::{
+    utils::{
+        bytes::{read_columns, write_columns},
+        ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable,
+    },
+    StarkField,
+};
 
 // STACK TRACE
 // ================================================================================================
@@ -216,4 +222,44 @@ impl StackTrace {
             }
         }
     }
+
+    /// Allocates memory for the trace to accommodate at least the specified number of rows.
+    pub fn reserve_trace_capacity(&mut self, num_rows: usize) {
+        if num_rows > get_trace_len(&self.stack) {
+            for register in self.stack.iter_mut().chain(self.helpers.iter_mut()) {
+                register.resize(num_rows, Felt::ZERO);
+            }
+        }
+    }
+}
+
+// SERIALIZATION
+// ================================================================================================
+
+impl Serializable for StackTrace {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        write_columns(target, &self.stack);
+        write_columns(target, &self.helpers);
+    }
+}
+
+impl Deserializable for StackTrace {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let trace = Self {
+            stack: read_columns(source)?,
+            helpers: read_columns(source)?,
+        };
+        let trace_len = trace.stack[0].len();
+        let is_consistent = trace
+            .stack
+            .iter()
+            .chain(trace.helpers.iter())
+            .all(|column| column.len() == trace_len);
+        if !is_consistent {
+            return Err(DeserializationError::InvalidValue(
+                "inconsistent stack trace".into(),
+            ));
+        }
+        Ok(trace)
+    }
 }
@@ -1,4 +1,8 @@
 use super::{Felt, FieldElement, SysTrace, Vec};
+use vm_core::utils::{
+    bytes::{read_usize, read_vec, write_usize, write_vec},
+    ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable,
+};
 
 // CONSTANTS
 // ================================================================================================
@@ -8,17 +12,25 @@ pub const FMP_MIN: u64 = 2_u64.pow(30);
 // The total number of locals available to all procedures at runtime must be smaller than 2^32.
 pub const FMP_MAX: u64 = FMP_MIN + u32::MAX as u64;
 
+/// ID of the memory context in which a program starts executing.
+pub const ROOT_CONTEXT: u32 = 0;
+/// ID of the memory context in which kernel procedures invoked via system calls are executed.
+pub const KERNEL_CONTEXT: u32 = 1;
+
 // SYSTEM INFO
 // ================================================================================================
 
 /// System info container for the VM.
 ///
-/// Currently, this keeps track of the clock cycle and free memory pointer registers.
+/// Currently, this keeps track of the clock cycle, free memory pointer, and memory context
+/// registers.
 pub struct System {
     clk: usize,
     clk_trace: Vec<Felt>,
     fmp: Felt,
     fmp_trace: Vec<Felt>,
+    ctx: u32,
+    ctx_trace: Vec<Felt>,
 }
 
 impl System {
@@ -37,6 +49,8 @@ impl System {
             clk_trace: Felt::zeroed_vector(init_trace_capacity),
             fmp,
             fmp_trace,
+            ctx: ROOT_CONTEXT,
+            ctx_trace: Felt::zeroed_vector(init_trace_capacity),
         }
     }
 
@@ -55,6 +69,12 @@ impl System {
         self.fmp
     }
 
+    /// Returns the ID of the current memory context of a process.
+    #[inline(always)]
+    pub fn ctx(&self) -> u32 {
+        self.ctx
+    }
+
     /// Returns execution trace length for the systems columns of the process.
     ///
     /// Trace length of the system columns is equal to the number of cycles executed by the VM.
@@ -68,7 +88,8 @@ impl System {
     /// If the trace is smaller than the specified `trace_len`, the columns of the trace are
     /// extended to match the specified length as follows:
     /// - the remainder of the `clk` column is filled in with increasing values of `clk`.
-    /// - the remainder of the `fmp` column is filled in with the last value in the column.
+    /// - the remainder of the `fmp` and `ctx` columns is filled in with the last value in the
+    ///   column.
     ///
     /// `num_rand_rows` indicates the number of rows at the end of the trace which will be
     /// overwritten with random values. This parameter is unused because last rows are just
@@ -95,7 +116,12 @@ impl System {
         self.fmp_trace[clk..].fill(last_value);
         self.fmp_trace.resize(trace_len, last_value);
 
-        [self.clk_trace, self.fmp_trace]
+        // complete the ctx column in the same way
+        let last_value = self.ctx_trace[clk];
+        self.ctx_trace[clk..].fill(last_value);
+        self.ctx_trace.resize(trace_len, last_value);
+
+        [self.clk_trace, self.fmp_trace, self.ctx_trace]
     }
 
     /// Returns free memory pointer at the specified clock cycle.
@@ -113,6 +139,7 @@ impl System {
         self.clk_trace[self.clk] = Felt::new(self.clk as u64);
 
         self.fmp_trace[self.clk] = self.fmp;
+        self.ctx_trace[self.clk] = Felt::from(self.ctx);
     }
 
     /// Sets the value of free memory pointer for the next clock cycle.
@@ -122,6 +149,12 @@ impl System {
         self.fmp = fmp;
     }
 
+    /// Sets the ID of the memory context for the next clock cycle.
+    pub fn set_ctx(&mut self, ctx: u32) {
+        // as with fmp, the trace will be updated with this value when the clock cycle advances
+        self.ctx = ctx;
+    }
+
     // UTILITY METHODS
     // --------------------------------------------------------------------------------------------
 
@@ -134,6 +167,55 @@ impl System {
             let new_length = current_capacity * 2;
             self.clk_trace.resize(new_length, Felt::ZERO);
             self.fmp_trace.resize(new_length, Felt::ZERO);
+            self.ctx_trace.resize(new_length, Felt::ZERO);
+        }
+    }
+
+    /// Allocates memory for the trace to accommodate at least the specified number of rows, so
+    /// that the trace does not need to be extended until this number of rows is reached.
+    pub fn reserve_trace_capacity(&mut self, num_rows: usize) {
+        if num_rows > self.clk_trace.len() {
+            self.clk_trace.resize(num_rows, Felt::ZERO);
+            self.fmp_trace.resize(num_rows, Felt::ZERO);
+            self.ctx_trace.resize(num_rows, Felt::ZERO);
+        }
+    }
+}
+
+// SERIALIZATION
+// ================================================================================================
+
+impl Serializable for System {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        write_usize(target, self.clk);
+        write_vec(target, &self.clk_trace);
+        self.fmp.write_into(target);
+        write_vec(target, &self.fmp_trace);
+        target.write_u32(self.ctx);
+        write_vec(target, &self.ctx_trace);
+    }
+}
+
+impl Deserializable for System {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let system = Self {
+            clk: read_usize(source)?,
+            clk_trace: read_vec(source)?,
+            fmp: Felt::read_from(source)?,
+            fmp_trace: read_vec(source)?,
+            ctx: source.read_u32()?,
+            ctx_trace: read_vec(source)?,
+        };
+
+        let trace_len = system.clk_trace.len();
+        if system.clk >= trace_len
+            || system.fmp_trace.len() != trace_len
+            || system.ctx_trace.len() != trace_len
+        {
+            return Err(DeserializationError::InvalidValue(
+                "inconsistent system trace".into(),
+            ));
         }
+        Ok(system)
     }
 }
@@ -0,0 +1,106 @@
+use super::{Felt, TraceDump, Vec, TRACE_WIDTH};
+use arrow_array::{builder::UInt64Builder, ArrayRef, RecordBatch};
+use arrow_schema::{ArrowError, DataType, Field, Schema, SchemaRef};
+use std::sync::Arc;
+use vm_core::{FieldElement, StarkField};
+use winterfell::Trace;
+
+#[cfg(feature = "parquet")]
+use parquet::{arrow::ArrowWriter, errors::ParquetError};
+
+// CONSTANTS
+// ================================================================================================
+
+/// Maximum number of trace rows in a single record batch written to a Parquet file.
+#[cfg(feature = "parquet")]
+const ROWS_PER_BATCH: usize = 1 << 16;
+
+// ARROW EXPORT
+// ================================================================================================
+
+impl TraceDump<'_> {
+    /// Returns the schema of the record batches into which this dump is exported.
+    ///
+    /// The first column of the schema, `row`, holds the indexes of the dumped trace rows; it is
+    /// followed by a column for every selected trace column, named in the same way as in the
+    /// header of the dump (e.g., `stack[0]`). All columns hold `u64` values; cells which are left
+    /// empty in the dump (see [ColumnGroup](crate::ColumnGroup)) are null.
+    pub fn arrow_schema(&self) -> SchemaRef {
+        let mut fields = Vec::with_capacity(self.column_names().len() + 1);
+        fields.push(Field::new("row", DataType::UInt64, false));
+        for name in self.column_names() {
+            fields.push(Field::new(name, DataType::UInt64, true));
+        }
+        Arc::new(Schema::new(fields))
+    }
+
+    /// Exports the rows of this dump into an Arrow record batch with the schema returned by
+    /// [TraceDump::arrow_schema()].
+    ///
+    /// # Errors
+    /// Returns an error if the record batch could not be built.
+    pub fn to_record_batch(&self) -> Result<RecordBatch, ArrowError> {
+        self.build_record_batch(self.rows())
+    }
+
+    /// Writes the rows of this dump into the specified writer as a Parquet file with the schema
+    /// returned by [TraceDump::arrow_schema()].
+    ///
+    /// Rows are exported in batches, and thus the values of the entire dump are never held in
+    /// memory at once.
+    ///
+    /// # Errors
+    /// Returns an error if the rows could not be encoded or written.
+    #[cfg(feature = "parquet")]
+    pub fn write_parquet<W: std::io::Write + Send>(&self, writer: W) -> Result<(), ParquetError> {
+        let mut writer = ArrowWriter::try_new(writer, self.arrow_schema(), None)?;
+        // every batch spans the same number of steps, and thus starts at an included row
+        let batch_len = ROWS_PER_BATCH.saturating_mul(self.step);
+        let mut start = self.rows.start;
+        while start < self.rows.end {
+            let end = start.saturating_add(batch_len).min(self.rows.end);
+            writer.write(&self.build_record_batch((start..end).step_by(self.step))?)?;
+            start = end;
+        }
+        writer.close()?;
+        Ok(())
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Builds a record batch out of the specified rows of the trace.
+    fn build_record_batch(
+        &self,
+        rows: impl Iterator<Item = usize>,
+    ) -> Result<RecordBatch, ArrowError> {
+        let num_rows = rows.size_hint().0;
+        let mut row_idx = UInt64Builder::with_capacity(num_rows);
+        let mut columns = (0..self.column_names().len())
+            .map(|_| UInt64Builder::with_capacity(num_rows))
+            .collect::<Vec<_>>();
+
+        let mut row = [Felt::ZERO; TRACE_WIDTH];
+        for i in rows {
+            self.trace.main_segment().read_row_into(i, &mut row);
+            row_idx.append_value(i as u64);
+            let mut builders = columns.iter_mut();
+            for selection in self.columns.iter() {
+                let is_active = selection.group().is_active(&row);
+                for (column, builder) in selection.trace_columns().zip(&mut builders) {
+                    if is_active {
+                        builder.append_value(row[column].as_int());
+                    } else {
+                        builder.append_null();
+                    }
+                }
+            }
+        }
+
+        let arrays = core::iter::once(row_idx)
+            .chain(columns)
+            .map(|mut builder| Arc::new(builder.finish()) as ArrayRef)
+            .collect();
+        RecordBatch::try_new(self.arrow_schema(), arrays)
+    }
+}
@@ -0,0 +1,328 @@
+use super::{Digest, ExecutionTrace, Felt, FieldElement, Vec, NUM_RAND_ROWS};
+use core::{fmt, ops::Range, str::FromStr};
+use vm_core::{
+    chiplets::{
+        bitwise, hasher, BITWISE_TRACE_OFFSET, HASHER_TRACE_OFFSET, MEMORY_TRACE_OFFSET,
+        NUM_BITWISE_SELECTORS, NUM_HASHER_SELECTORS, NUM_MEMORY_SELECTORS,
+    },
+    utils::string::String,
+    StarkField, CHIPLETS_OFFSET, CHIPLETS_RANGE, DECODER_TRACE_RANGE, MEMORY_TRACE_WIDTH,
+    RANGE_CHECK_TRACE_RANGE, STACK_TRACE_RANGE, SYS_TRACE_RANGE, TRACE_WIDTH,
+};
+use winterfell::Trace;
+
+// COLUMN GROUP
+// ================================================================================================
+
+/// A group of columns of the main segment of an execution trace.
+///
+/// Columns of the hasher, bitwise, and memory chiplets occupy the same columns of the trace in
+/// different rows. Thus, cells of these groups are meaningful only in the rows of the respective
+/// chiplet, and are left empty in trace dumps for all other rows.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ColumnGroup {
+    System,
+    Decoder,
+    Stack,
+    RangeChecker,
+    Chiplets,
+    Hasher,
+    Bitwise,
+    Memory,
+}
+
+impl ColumnGroup {
+    /// Column groups which together cover the entire main segment of an execution trace.
+    pub const MAIN_SEGMENT: [Self; 5] = [
+        Self::System,
+        Self::Decoder,
+        Self::Stack,
+        Self::RangeChecker,
+        Self::Chiplets,
+    ];
+
+    /// Returns the name of this group.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::System => "system",
+            Self::Decoder => "decoder",
+            Self::Stack => "stack",
+            Self::RangeChecker => "range",
+            Self::Chiplets => "chiplets",
+            Self::Hasher => "hasher",
+            Self::Bitwise => "bitwise",
+            Self::Memory => "memory",
+        }
+    }
+
+    /// Returns the range of columns of the main segment of an execution trace which belong to
+    /// this group.
+    pub fn columns(&self) -> Range<usize> {
+        match self {
+            Self::System => SYS_TRACE_RANGE,
+            Self::Decoder => DECODER_TRACE_RANGE,
+            Self::Stack => STACK_TRACE_RANGE,
+            Self::RangeChecker => RANGE_CHECK_TRACE_RANGE,
+            Self::Chiplets => CHIPLETS_RANGE,
+            Self::Hasher => HASHER_TRACE_OFFSET..HASHER_TRACE_OFFSET + hasher::TRACE_WIDTH,
+            Self::Bitwise => BITWISE_TRACE_OFFSET..BITWISE_TRACE_OFFSET + bitwise::TRACE_WIDTH,
+            Self::Memory => MEMORY_TRACE_OFFSET..MEMORY_TRACE_OFFSET + MEMORY_TRACE_WIDTH,
+        }
+    }
+
+    /// Returns true if the columns of this group are meaningful in the specified trace row.
+    pub(super) fn is_active(&self, row: &[Felt]) -> bool {
+        let selectors = &row[CHIPLETS_OFFSET..];
+        match self {
+            Self::Hasher => selectors[..NUM_HASHER_SELECTORS] == [Felt::ZERO],
+            Self::Bitwise => selectors[..NUM_BITWISE_SELECTORS] == [Felt::ONE, Felt::ZERO],
+            Self::Memory => selectors[..NUM_MEMORY_SELECTORS] == [Felt::ONE, Felt::ONE, Felt::ZERO],
+            _ => true,
+        }
+    }
+}
+
+impl FromStr for ColumnGroup {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        [
+            Self::MAIN_SEGMENT.as_slice(),
+            &[Self::Hasher, Self::Bitwise, Self::Memory],
+        ]
+        .concat()
+        .into_iter()
+        .find(|group| group.name() == s)
+        .ok_or_else(|| format!("unknown trace column group '{}'", s))
+    }
+}
+
+// COLUMN SELECTION
+// ================================================================================================
+
+/// A range of columns of a single column group selected for a trace dump.
+///
+/// A selection can be parsed from a group name optionally followed by a column index or a range
+/// of column indexes within the group, e.g., `stack`, `stack[0]`, or `stack[0..4]`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ColumnSelection {
+    group: ColumnGroup,
+    columns: Range<usize>,
+}
+
+impl ColumnSelection {
+    /// Returns a selection of all columns of the specified group.
+    pub fn new(group: ColumnGroup) -> Self {
+        Self {
+            group,
+            columns: 0..group.columns().len(),
+        }
+    }
+
+    /// Returns a selection of the specified columns of the specified group. Column indexes are
+    /// relative to the first column of the group.
+    ///
+    /// # Errors
+    /// Returns an error if the range is empty or exceeds the number of columns in the group.
+    pub fn with_columns(group: ColumnGroup, columns: Range<usize>) -> Result<Self, String> {
+        let width = group.columns().len();
+        if columns.is_empty() || columns.end > width {
+            return Err(format!(
+                "invalid column range {}..{} for group '{}' with {} columns",
+                columns.start,
+                columns.end,
+                group.name(),
+                width
+            ));
+        }
+        Ok(Self { group, columns })
+    }
+
+    /// Returns the group of the selected columns.
+    pub fn group(&self) -> ColumnGroup {
+        self.group
+    }
+
+    /// Returns the indexes of the selected columns in the main segment of an execution trace.
+    pub fn trace_columns(&self) -> Range<usize> {
+        let offset = self.group.columns().start;
+        offset + self.columns.start..offset + self.columns.end
+    }
+}
+
+impl FromStr for ColumnSelection {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (name, columns) = match s.strip_suffix(']').and_then(|s| s.split_once('[')) {
+            Some((name, columns)) => (name, Some(columns)),
+            None => (s, None),
+        };
+        let group = name.parse::<ColumnGroup>()?;
+
+        let columns = match columns {
+            None => return Ok(Self::new(group)),
+            Some(columns) => columns,
+        };
+        let parse_index = |index: &str| {
+            index
+                .parse::<usize>()
+                .map_err(|_| format!("invalid column index '{}' in '{}'", index, s))
+        };
+        let columns = match columns.split_once("..") {
+            Some((start, end)) => parse_index(start)?..parse_index(end)?,
+            None => {
+                let index = parse_index(columns)?;
+                index..index + 1
+            }
+        };
+        Self::with_columns(group, columns)
+    }
+}
+
+impl fmt::Display for ColumnSelection {
+    /// Writes this selection in the form from which it can be parsed.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = self.group.name();
+        if self.columns == (0..self.group.columns().len()) {
+            write!(f, "{}", name)
+        } else if self.columns.len() == 1 {
+            write!(f, "{}[{}]", name, self.columns.start)
+        } else {
+            write!(f, "{}[{}..{}]", name, self.columns.start, self.columns.end)
+        }
+    }
+}
+
+// TRACE DUMP
+// ================================================================================================
+
+/// A view of a subset of an execution trace which can be written out for debugging.
+///
+/// A dump is written as comma-separated values: the header row contains the names of the
+/// selected columns (e.g., `stack[0]`), and every subsequent row starts with the index of the
+/// trace row followed by the values of the selected columns. By default, all columns of the main
+/// trace segment are dumped for all rows except for the rows holding random values.
+pub struct TraceDump<'a> {
+    pub(super) trace: &'a ExecutionTrace,
+    pub(super) columns: Vec<ColumnSelection>,
+    pub(super) rows: Range<usize>,
+    pub(super) step: usize,
+}
+
+impl<'a> TraceDump<'a> {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Returns a dump of all columns of the main segment of the specified trace.
+    pub fn new(trace: &'a ExecutionTrace) -> Self {
+        Self {
+            trace,
+            columns: ColumnGroup::MAIN_SEGMENT
+                .into_iter()
+                .map(ColumnSelection::new)
+                .collect(),
+            rows: 0..trace.length() - NUM_RAND_ROWS,
+            step: 1,
+        }
+    }
+
+    /// Restricts this dump to the specified columns. Columns are written in the order of
+    /// selection.
+    pub fn with_columns(mut self, columns: Vec<ColumnSelection>) -> Self {
+        self.columns = columns;
+        self
+    }
+
+    /// Restricts this dump to the specified rows of the trace. Rows holding random values are
+    /// never included into the dump.
+    pub fn with_rows(mut self, rows: Range<usize>) -> Self {
+        let num_rows = self.trace.length() - NUM_RAND_ROWS;
+        self.rows = rows.start.min(num_rows)..rows.end.min(num_rows);
+        self
+    }
+
+    /// Downsamples this dump to include only every `step`-th row, starting with the first row of
+    /// the dump.
+    ///
+    /// # Panics
+    /// Panics if `step` is zero.
+    pub fn with_step(mut self, step: usize) -> Self {
+        assert!(step > 0, "step must be greater than zero");
+        self.step = step;
+        self
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the names of the columns included into this dump.
+    pub fn column_names(&self) -> Vec<String> {
+        self.columns
+            .iter()
+            .flat_map(|selection| {
+                let name = selection.group.name();
+                selection
+                    .columns
+                    .clone()
+                    .map(move |i| format!("{}[{}]", name, i))
+            })
+            .collect()
+    }
+
+    /// Returns the indexes of the trace rows included into this dump.
+    pub fn rows(&self) -> impl Iterator<Item = usize> {
+        self.rows.clone().step_by(self.step)
+    }
+
+    /// Returns a digest of the cells included into this dump.
+    ///
+    /// The digest commits to the number of selected columns, and to the index and the selected
+    /// cells of every included row; cells which are left empty in the dump (see [ColumnGroup])
+    /// are hashed as ZERO. Since rows holding random values are never included, executing the
+    /// same program against the same inputs always results in the same digest. This makes the
+    /// digest suitable for detecting changes in the execution trace, e.g., in regression tests.
+    pub fn digest(&self) -> Digest {
+        let num_columns = self.column_names().len() as u64;
+        let mut result = hasher::hash_elements(&[Felt::new(num_columns)]);
+
+        let mut row = [Felt::ZERO; TRACE_WIDTH];
+        let mut cells = Vec::with_capacity(TRACE_WIDTH + 1);
+        for i in self.rows() {
+            self.trace.main_segment().read_row_into(i, &mut row);
+            cells.clear();
+            cells.push(Felt::new(i as u64));
+            for selection in self.columns.iter() {
+                let is_active = selection.group.is_active(&row);
+                for column in selection.trace_columns() {
+                    cells.push(if is_active { row[column] } else { Felt::ZERO });
+                }
+            }
+            result = hasher::merge(&[result, hasher::hash_elements(&cells)]);
+        }
+        result
+    }
+}
+
+impl fmt::Display for TraceDump<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "row,{}", self.column_names().join(","))?;
+
+        let mut row = [Felt::ZERO; TRACE_WIDTH];
+        for i in self.rows() {
+            self.trace.main_segment().read_row_into(i, &mut row);
+            write!(f, "{}", i)?;
+            for selection in self.columns.iter() {
+                let is_active = selection.group.is_active(&row);
+                for column in selection.trace_columns() {
+                    if is_active {
+                        write!(f, ",{}", row[column].as_int())?;
+                    } else {
+                        f.write_str(",")?;
+                    }
+                }
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
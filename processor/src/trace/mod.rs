@@ -3,14 +3,16 @@ use super::{
     decoder::AuxTraceHints as DecoderAuxTraceHints,
     range::AuxTraceBuilder as RangeCheckerAuxTraceBuilder,
     stack::AuxTraceBuilder as StackAuxTraceBuilder,
-    Digest, Felt, FieldElement, Process, StackTopState, Vec,
+    AdviceProvider, BlockProfile, ChipletsLengths, Coverage, Digest, Felt, FieldElement,
+    MemoryAccess, Process, StackTopState, Vec, Word,
 };
 use vm_core::{
     decoder::{NUM_USER_OP_HELPERS, USER_OP_HELPERS_OFFSET},
+    utils::IntoBytes,
     AUX_TRACE_RAND_ELEMENTS, AUX_TRACE_WIDTH, DECODER_TRACE_OFFSET, MIN_STACK_DEPTH, MIN_TRACE_LEN,
     STACK_TRACE_OFFSET, TRACE_WIDTH, ZERO,
 };
-use winterfell::{EvaluationFrame, Matrix, Serializable, Trace, TraceLayout};
+use winterfell::{EvaluationFrame, Matrix, Trace, TraceLayout};
 
 #[cfg(feature = "std")]
 use vm_core::StarkField;
@@ -20,6 +22,12 @@ pub use utils::{build_lookup_table_row_values, AuxColumnBuilder, LookupTableRow,
 
 mod decoder;
 
+mod dump;
+pub use dump::{ColumnGroup, ColumnSelection, TraceDump};
+
+#[cfg(feature = "arrow")]
+mod arrow;
+
 #[cfg(test)]
 mod tests;
 
@@ -58,6 +66,13 @@ pub struct ExecutionTrace {
     main_trace: Matrix<Felt>,
     aux_trace_hints: AuxTraceHints,
     program_hash: Digest,
+    num_cycles: usize,
+    trace_len_summary: TraceLenSummary,
+    gas_used: Option<u64>,
+    block_profile: Option<BlockProfile>,
+    coverage: Option<Coverage>,
+    memory_accesses: Vec<MemoryAccess>,
+    random_seed: [u8; 32],
 }
 
 impl ExecutionTrace {
@@ -70,14 +85,22 @@ impl ExecutionTrace {
     // CONSTRUCTOR
     // --------------------------------------------------------------------------------------------
     /// Builds an execution trace for the provided process.
-    pub(super) fn new(process: Process) -> Self {
-        // use program hash to initialize random element generator; this generator will be used
-        // to inject random values at the end of the trace; using program hash here is OK because
-        // we are using random values only to stabilize constraint degrees, and not to achieve
-        // perfect zero knowledge.
+    pub(super) fn new<A: AdviceProvider>(process: Process<A>) -> Self {
+        // unless a random seed was specified, use program hash to initialize random element
+        // generator; this generator will be used to inject random values at the end of the trace;
+        // using program hash here is OK because we are using random values only to stabilize
+        // constraint degrees, and not to achieve perfect zero knowledge.
         let program_hash: Digest = process.decoder.program_hash().into();
-        let rng = RandomCoin::new(&program_hash.to_bytes());
-        let (main_trace, aux_trace_hints) = finalize_trace(process, rng);
+        let num_cycles = process.system.clk();
+        let gas_used = process.gas_used();
+        let block_profile = process.block_profile().cloned();
+        let coverage = process.coverage().cloned();
+        let memory_accesses = process.memory_accesses();
+        let random_seed = process
+            .random_seed
+            .unwrap_or_else(|| Word::from(program_hash).into_bytes());
+        let rng = RandomCoin::new(&random_seed);
+        let (main_trace, aux_trace_hints, trace_len_summary) = finalize_trace(process, rng);
 
         Self {
             meta: Vec::new(),
@@ -85,6 +108,13 @@ impl ExecutionTrace {
             main_trace: Matrix::new(main_trace),
             aux_trace_hints,
             program_hash,
+            num_cycles,
+            trace_len_summary,
+            gas_used,
+            block_profile,
+            coverage,
+            memory_accesses,
+            random_seed,
         }
     }
 
@@ -96,6 +126,68 @@ impl ExecutionTrace {
         self.program_hash
     }
 
+    /// Returns the number of cycles executed by the VM, i.e., the length of this trace before it
+    /// was padded to the next power of two.
+    pub fn num_cycles(&self) -> usize {
+        self.num_cycles
+    }
+
+    /// Returns a summary of the lengths of the main, range checker, and chiplets traces which
+    /// determined the length of this trace.
+    pub fn trace_len_summary(&self) -> &TraceLenSummary {
+        &self.trace_len_summary
+    }
+
+    /// Returns the amount of gas consumed by the program, or None if the program was executed
+    /// without gas metering.
+    pub fn gas_used(&self) -> Option<u64> {
+        self.gas_used
+    }
+
+    /// Returns the profile of the code blocks executed by the program, or None if the program was
+    /// executed without block profiling.
+    pub fn block_profile(&self) -> Option<&BlockProfile> {
+        self.block_profile.as_ref()
+    }
+
+    /// Returns the number of times every operation of every span block was executed by the
+    /// program, or None if the program was executed without coverage recording.
+    pub fn coverage(&self) -> Option<&Coverage> {
+        self.coverage.as_ref()
+    }
+
+    /// Returns all memory reads and writes made by the program, sorted first by context, then by
+    /// address, and then by clock cycle.
+    pub fn memory_accesses(&self) -> &[MemoryAccess] {
+        &self.memory_accesses
+    }
+
+    /// Returns the words stored in the memory of the root context at the beginning of the
+    /// specified clock cycle, sorted by address. Only addresses which were accessed prior to the
+    /// specified cycle are included.
+    pub fn get_memory_state_at(&self, clk: u64) -> Vec<(u64, Word)> {
+        let mut state: Vec<(u64, Word)> = Vec::new();
+        let root_accesses = self
+            .memory_accesses
+            .iter()
+            .take_while(|access| access.ctx() == 0);
+        for access in root_accesses.filter(|access| access.clk() < clk) {
+            // accesses are sorted by address and then by clock cycle, and thus the last access to
+            // an address before the specified cycle determines the word stored at that address
+            match state.last_mut() {
+                Some((addr, value)) if *addr == access.addr() => *value = access.new_value(),
+                _ => state.push((access.addr(), access.new_value())),
+            }
+        }
+        state
+    }
+
+    /// Returns the words stored in the memory of the root context at the end of the execution,
+    /// sorted by address. Only addresses which were accessed by the program are included.
+    pub fn final_memory(&self) -> Vec<(u64, Word)> {
+        self.get_memory_state_at(u64::MAX)
+    }
+
     /// Returns the initial state of the top 16 stack registers.
     pub fn init_stack_state(&self) -> StackTopState {
         let mut result = [ZERO; MIN_STACK_DEPTH];
@@ -126,6 +218,21 @@ impl ExecutionTrace {
         result
     }
 
+    /// Returns a dump of the main segment of this trace which can be restricted to the columns
+    /// and rows of interest and written out for debugging.
+    pub fn dump(&self) -> TraceDump<'_> {
+        TraceDump::new(self)
+    }
+
+    /// Returns a mutable reference to the main segment of this trace.
+    ///
+    /// This is intended for tools which need to prove traces which do not result from executing a
+    /// program (e.g., to test soundness of the constraints). Auxiliary columns are built from
+    /// hints recorded during program execution, and thus will not reflect the modifications.
+    pub fn main_segment_mut(&mut self) -> &mut Matrix<Felt> {
+        &mut self.main_trace
+    }
+
     // HELPER METHODS
     // --------------------------------------------------------------------------------------------
 
@@ -149,7 +256,8 @@ impl ExecutionTrace {
     #[cfg(test)]
     pub fn test_finalize_trace(process: Process) -> (Vec<Vec<Felt>>, AuxTraceHints) {
         let rng = RandomCoin::new(&[0; 32]);
-        finalize_trace(process, rng)
+        let (trace, aux_trace_hints, _) = finalize_trace(process, rng);
+        (trace, aux_trace_hints)
     }
 }
 
@@ -228,7 +336,7 @@ impl Trace for ExecutionTrace {
             .collect::<Vec<_>>();
 
         // inject random values into the last rows of the trace
-        let mut rng = RandomCoin::new(&self.program_hash.to_bytes());
+        let mut rng = RandomCoin::new(&self.random_seed);
         for i in self.length() - NUM_RAND_ROWS..self.length() {
             for column in aux_columns.iter_mut() {
                 column[i] = rng.draw().expect("failed to draw a random value");
@@ -246,6 +354,75 @@ impl Trace for ExecutionTrace {
     }
 }
 
+// TRACE LENGTH SUMMARY
+// ================================================================================================
+
+/// Lengths of the components of an execution trace.
+///
+/// The length of an execution trace is determined by the longest of the main trace (i.e., the
+/// number of executed cycles), the range checker trace, and the chiplets trace; this length is
+/// then padded to the next power of two, leaving space for the rows holding random values.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct TraceLenSummary {
+    main_trace_len: usize,
+    range_trace_len: usize,
+    chiplets_lengths: ChipletsLengths,
+}
+
+impl TraceLenSummary {
+    /// Returns [TraceLenSummary] instantiated with the specified lengths of the main trace, the
+    /// range checker trace, and the traces of the individual chiplets.
+    pub fn new(
+        main_trace_len: usize,
+        range_trace_len: usize,
+        chiplets_lengths: ChipletsLengths,
+    ) -> Self {
+        Self {
+            main_trace_len,
+            range_trace_len,
+            chiplets_lengths,
+        }
+    }
+
+    /// Returns the length of the main trace, i.e., the number of executed cycles.
+    pub fn main_trace_len(&self) -> usize {
+        self.main_trace_len
+    }
+
+    /// Returns the length of the range checker trace.
+    pub fn range_trace_len(&self) -> usize {
+        self.range_trace_len
+    }
+
+    /// Returns the lengths of the traces of the individual chiplets.
+    pub fn chiplets_lengths(&self) -> ChipletsLengths {
+        self.chiplets_lengths
+    }
+
+    /// Returns the length of the chiplets trace.
+    pub fn chiplets_trace_len(&self) -> usize {
+        self.chiplets_lengths.trace_len()
+    }
+
+    /// Returns the length of the longest trace component.
+    pub fn trace_len(&self) -> usize {
+        self.main_trace_len
+            .max(self.range_trace_len)
+            .max(self.chiplets_trace_len())
+    }
+
+    /// Returns the length of the execution trace, i.e., the length of the longest trace component
+    /// padded to the next power of two such that there is space for the rows of random values.
+    pub fn padded_trace_len(&self) -> usize {
+        (self.trace_len() + NUM_RAND_ROWS).next_power_of_two()
+    }
+
+    /// Returns the number of rows by which the execution trace exceeds its longest component.
+    pub fn padding_len(&self) -> usize {
+        self.padded_trace_len() - self.trace_len()
+    }
+}
+
 // HELPER FUNCTIONS
 // ================================================================================================
 
@@ -257,7 +434,10 @@ impl Trace for ExecutionTrace {
 /// - Inserting random values in the last row of all columns. This helps ensure that there
 ///   are no repeating patterns in each column and each column contains a least two distinct
 ///   values. This, in turn, ensures that polynomial degrees of all columns are stable.
-fn finalize_trace(process: Process, mut rng: RandomCoin) -> (Vec<Vec<Felt>>, AuxTraceHints) {
+fn finalize_trace<A: AdviceProvider>(
+    process: Process<A>,
+    mut rng: RandomCoin,
+) -> (Vec<Vec<Felt>>, AuxTraceHints, TraceLenSummary) {
     let (system, decoder, stack, mut range, chiplets) = process.to_components();
 
     let clk = system.clk();
@@ -274,15 +454,10 @@ fn finalize_trace(process: Process, mut rng: RandomCoin) -> (Vec<Vec<Felt>>, Aux
     // Add the range checks required by the chiplets to the range checker.
     chiplets.append_range_checks(&mut range);
 
-    // Get the trace length required to hold all execution trace steps.
-    let max_len = [clk, range.trace_len(), chiplets.trace_len()]
-        .into_iter()
-        .max()
-        .expect("failed to get max of component trace lengths");
-
-    // pad the trace length to the next power of two and ensure that there is space for the
-    // rows to hold random values
-    let trace_len = (max_len + NUM_RAND_ROWS).next_power_of_two();
+    // Get the trace length required to hold all execution trace steps, padded to the next power
+    // of two such that there is space for the rows to hold random values.
+    let summary = TraceLenSummary::new(clk, range.trace_len(), chiplets.lengths());
+    let trace_len = summary.padded_trace_len();
     assert!(
         trace_len >= MIN_TRACE_LEN,
         "trace length must be at least {}, but was {}",
@@ -320,5 +495,5 @@ fn finalize_trace(process: Process, mut rng: RandomCoin) -> (Vec<Vec<Felt>>, Aux
         chiplets: chiplets_trace.aux_builder,
     };
 
-    (trace, aux_trace_hints)
+    (trace, aux_trace_hints, summary)
 }
@@ -0,0 +1,213 @@
+use super::{
+    super::{ColumnGroup, ColumnSelection},
+    build_trace_from_ops, Felt, Trace, NUM_RAND_ROWS,
+};
+use vm_core::{Operation, STACK_TRACE_OFFSET, TRACE_WIDTH};
+
+#[test]
+fn column_selection() {
+    let selection = "stack[0..4]".parse::<ColumnSelection>().unwrap();
+    assert_eq!(ColumnGroup::Stack, selection.group());
+    assert_eq!(
+        STACK_TRACE_OFFSET..STACK_TRACE_OFFSET + 4,
+        selection.trace_columns()
+    );
+
+    let selection = "stack[2]".parse::<ColumnSelection>().unwrap();
+    assert_eq!(
+        STACK_TRACE_OFFSET + 2..STACK_TRACE_OFFSET + 3,
+        selection.trace_columns()
+    );
+
+    let selection = "memory".parse::<ColumnSelection>().unwrap();
+    assert_eq!(ColumnSelection::new(ColumnGroup::Memory), selection);
+
+    // selections are written in the form from which they are parsed
+    for selection in ["stack", "stack[2]", "stack[0..4]", "memory"] {
+        assert_eq!(
+            selection,
+            selection.parse::<ColumnSelection>().unwrap().to_string()
+        );
+    }
+
+    for invalid in [
+        "storage",
+        "stack[]",
+        "stack[a]",
+        "stack[2..2]",
+        "stack[0..40]",
+        "stack[0",
+    ] {
+        assert!(invalid.parse::<ColumnSelection>().is_err(), "{}", invalid);
+    }
+}
+
+#[test]
+fn trace_dump() {
+    // store 7 at address 3
+    let ops = vec![
+        Operation::Push(Felt::new(7)),
+        Operation::Push(Felt::new(3)),
+        Operation::MStore,
+    ];
+    let trace = build_trace_from_ops(ops, &[]);
+
+    // by default, all columns of the main segment are dumped for all but the random rows
+    let dump = trace.dump().to_string();
+    let lines = dump.lines().collect::<Vec<_>>();
+    assert_eq!(trace.length() - NUM_RAND_ROWS + 1, lines.len());
+    assert_eq!(TRACE_WIDTH + 1, lines[0].split(',').count());
+    assert!(lines[0].starts_with("row,system[0],system[1],system[2],decoder[0]"));
+
+    // cells of the memory chiplet are left empty outside of the memory chiplet rows
+    let columns = ["stack[0..2]", "memory"]
+        .map(|s| s.parse().unwrap())
+        .to_vec();
+    let dump = trace.dump().with_columns(columns.clone()).to_string();
+    let lines = dump.lines().collect::<Vec<_>>();
+    assert!(lines[0].starts_with("row,stack[0],stack[1],memory[0],memory[1],memory[2]"));
+    assert_eq!(2 + 14 + 1, lines[0].split(',').count());
+    // the stack state after PUSH(7) is executed at cycle 1
+    assert!(lines[3].starts_with("2,7,0,,,"));
+
+    let memory_rows = lines[1..]
+        .iter()
+        .filter(|line| !line.ends_with(','))
+        .map(|line| line.split(',').collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    assert_eq!(1, memory_rows.len());
+    // the memory chiplet records address 3 and the new value 7
+    assert_eq!(["3", "7"], [memory_rows[0][4], memory_rows[0][10]]);
+
+    // rows can be restricted and downsampled
+    let dump = trace
+        .dump()
+        .with_columns(columns)
+        .with_rows(1..9)
+        .with_step(3);
+    assert_eq!(vec![1, 4, 7], dump.rows().collect::<Vec<_>>());
+    assert_eq!(4, dump.to_string().lines().count());
+}
+
+#[test]
+fn trace_dump_digest() {
+    let add = vec![
+        Operation::Push(Felt::new(7)),
+        Operation::Push(Felt::new(3)),
+        Operation::Add,
+    ];
+    let mul = vec![
+        Operation::Push(Felt::new(7)),
+        Operation::Push(Felt::new(3)),
+        Operation::Mul,
+    ];
+    let add_trace = build_trace_from_ops(add.clone(), &[]);
+    let mul_trace = build_trace_from_ops(mul, &[]);
+
+    // digests do not depend on the random rows of the trace
+    let digest = add_trace.dump().digest();
+    assert_eq!(digest, build_trace_from_ops(add, &[]).dump().digest());
+    assert_ne!(digest, mul_trace.dump().digest());
+
+    // only the selected columns and rows are committed to
+    let system = vec![ColumnSelection::new(ColumnGroup::System)];
+    assert_eq!(
+        add_trace.dump().with_columns(system.clone()).digest(),
+        mul_trace.dump().with_columns(system.clone()).digest()
+    );
+    let stack = vec!["stack[0]".parse().unwrap()];
+    assert_ne!(
+        add_trace.dump().with_columns(stack.clone()).digest(),
+        mul_trace.dump().with_columns(stack.clone()).digest()
+    );
+    assert_eq!(
+        add_trace
+            .dump()
+            .with_columns(stack.clone())
+            .with_rows(0..3)
+            .digest(),
+        mul_trace
+            .dump()
+            .with_columns(stack)
+            .with_rows(0..3)
+            .digest()
+    );
+    assert_ne!(
+        add_trace.dump().with_columns(system.clone()).digest(),
+        add_trace.dump().with_columns(system).with_step(2).digest()
+    );
+}
+
+#[cfg(feature = "arrow")]
+#[test]
+fn trace_dump_record_batch() {
+    use arrow_array::{cast::AsArray, types::UInt64Type, Array};
+
+    let ops = vec![
+        Operation::Push(Felt::new(7)),
+        Operation::Push(Felt::new(3)),
+        Operation::MStore,
+    ];
+    let trace = build_trace_from_ops(ops, &[]);
+
+    // the batch holds the same values as the text dump, with empty cells being null
+    let columns = ["stack[0..2]", "memory"]
+        .map(|s| s.parse().unwrap())
+        .to_vec();
+    let dump = trace.dump().with_columns(columns);
+    let batch = dump.to_record_batch().unwrap();
+    assert_eq!(trace.length() - NUM_RAND_ROWS, batch.num_rows());
+    assert_eq!(2 + 14 + 1, batch.num_columns());
+
+    let schema = batch.schema();
+    let names = schema
+        .fields()
+        .iter()
+        .map(|field| field.name().as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(["row", "stack[0]", "stack[1]", "memory[0]"], names[..4]);
+
+    let stack = batch.column(1).as_primitive::<UInt64Type>();
+    assert_eq!(0, stack.null_count());
+    assert_eq!(7, stack.value(2));
+
+    // the memory chiplet records address 3 and the new value 7 in a single row
+    let addr = batch.column(4).as_primitive::<UInt64Type>();
+    let value = batch.column(10).as_primitive::<UInt64Type>();
+    assert_eq!(batch.num_rows() - 1, addr.null_count());
+    let row = (0..batch.num_rows()).find(|&i| addr.is_valid(i)).unwrap();
+    assert_eq!([3, 7], [addr.value(row), value.value(row)]);
+
+    // rows can be restricted and downsampled
+    let batch = dump.with_rows(1..9).with_step(3).to_record_batch().unwrap();
+    let rows = batch.column(0).as_primitive::<UInt64Type>();
+    assert_eq!(&[1, 4, 7], rows.values().as_ref());
+}
+
+#[cfg(feature = "parquet")]
+#[test]
+fn trace_dump_parquet() {
+    use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+
+    let ops = vec![Operation::Push(Felt::new(7)), Operation::Drop];
+    let trace = build_trace_from_ops(ops, &[]);
+    let dump = trace
+        .dump()
+        .with_columns(vec!["stack[0..4]".parse().unwrap()])
+        .with_step(2);
+
+    // concurrent runs of the test suite must not write into the same file
+    let file_name = format!("miden_trace_dump_{}.parquet", std::process::id());
+    let path = std::env::temp_dir().join(file_name);
+    dump.write_parquet(std::fs::File::create(&path).unwrap())
+        .unwrap();
+
+    // the file contains the same record batch which is built in memory
+    let reader = ParquetRecordBatchReaderBuilder::try_new(std::fs::File::open(&path).unwrap())
+        .unwrap()
+        .build()
+        .unwrap();
+    let batches = reader.collect::<Result<Vec<_>, _>>().unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(vec![dump.to_record_batch().unwrap()], batches);
+}
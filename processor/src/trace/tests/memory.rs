@@ -0,0 +1,38 @@
+use super::{build_trace_from_ops, Felt, Word, ZERO};
+use vm_core::Operation;
+
+#[test]
+fn memory_state() {
+    #[rustfmt::skip]
+    let operations = vec![
+        Operation::Push(Felt::new(7)), Operation::Push(Felt::new(3)), Operation::MStore,
+        Operation::Drop,
+        Operation::Push(Felt::new(9)), Operation::Push(Felt::new(3)), Operation::MStore,
+        Operation::Drop,
+        Operation::Push(Felt::new(5)), Operation::Push(Felt::new(1)), Operation::MStore,
+        Operation::Drop,
+    ];
+    let trace = build_trace_from_ops(operations, &[]);
+
+    // accesses to address 1 are listed before the accesses to address 3
+    let clks = trace
+        .memory_accesses()
+        .iter()
+        .map(|access| access.clk())
+        .collect::<Vec<_>>();
+    let (clk5, clk7, clk9) = (clks[0], clks[1], clks[2]);
+    assert!(clk7 < clk9 && clk9 < clk5);
+
+    // the state at the beginning of a cycle does not include writes made during that cycle
+    assert!(trace.get_memory_state_at(clk7).is_empty());
+    assert_eq!(vec![(3, word(7))], trace.get_memory_state_at(clk7 + 1));
+    assert_eq!(vec![(3, word(9))], trace.get_memory_state_at(clk5));
+
+    let expected = vec![(1, word(5)), (3, word(9))];
+    assert_eq!(expected, trace.get_memory_state_at(clk5 + 1));
+    assert_eq!(expected, trace.final_memory());
+}
+
+fn word(value: u64) -> Word {
+    [Felt::new(value), ZERO, ZERO, ZERO]
+}
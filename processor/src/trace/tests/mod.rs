@@ -3,7 +3,10 @@ use rand_utils::rand_array;
 use vm_core::{code_blocks::CodeBlock, Operation, ProgramInputs, Word, ONE, ZERO};
 
 mod chiplets;
+mod dump;
 mod hasher;
+mod memory;
+mod random;
 mod range;
 mod stack;
 
@@ -0,0 +1,45 @@
+use super::{ExecutionTrace, Felt, FieldElement, Trace, NUM_RAND_ROWS};
+use crate::{execute, execute_with_options, ExecutionOptions};
+use vm_core::{code_blocks::CodeBlock, Operation, Program, ProgramInputs, TRACE_WIDTH};
+
+#[test]
+fn random_rows() {
+    let program = Program::new(CodeBlock::new_span(vec![Operation::Pad, Operation::Incr]));
+    let inputs = ProgramInputs::none();
+
+    // by default, random values are derived from the program hash
+    let trace = execute(&program, &inputs).unwrap();
+    assert_eq!(
+        last_row(&trace),
+        last_row(&execute(&program, &inputs).unwrap())
+    );
+
+    // the same seed always results in the same random values
+    let options = ExecutionOptions::default().with_random_seed([7; 32]);
+    let seeded_trace = execute_with_options(&program, &inputs, options.clone()).unwrap();
+    assert_ne!(last_row(&trace), last_row(&seeded_trace));
+    let trace = execute_with_options(&program, &inputs, options).unwrap();
+    assert_eq!(last_row(&seeded_trace), last_row(&trace));
+
+    // only the random rows are affected by the seed
+    let row = trace.length() - NUM_RAND_ROWS - 1;
+    let mut expected = [Felt::ZERO; TRACE_WIDTH];
+    let mut actual = [Felt::ZERO; TRACE_WIDTH];
+    trace.main_segment().read_row_into(row, &mut actual);
+    execute(&program, &inputs)
+        .unwrap()
+        .main_segment()
+        .read_row_into(row, &mut expected);
+    assert_eq!(expected, actual);
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+fn last_row(trace: &ExecutionTrace) -> [Felt; TRACE_WIDTH] {
+    let mut row = [Felt::ZERO; TRACE_WIDTH];
+    trace
+        .main_segment()
+        .read_row_into(trace.length() - 1, &mut row);
+    row
+}
@@ -0,0 +1,79 @@
+use super::{ExecutionError, ExecutionOptions, Program, ProgramInputs, ProofOptions};
+use processor::TraceLenSummary;
+
+// PROOF ESTIMATE
+// ================================================================================================
+
+/// Proof options selected for an execution of a program, together with the trace length for
+/// which they were selected.
+///
+/// The options provide the requested security level for traces of the padded length of the
+/// execution trace, and result in the smallest proofs among the explored options (see
+/// [ProofOptions::with_security_level()]).
+#[derive(Clone)]
+pub struct ProofEstimate {
+    trace_len_summary: TraceLenSummary,
+    options: ProofOptions,
+}
+
+impl ProofEstimate {
+    /// Returns the lengths of the components of the execution trace.
+    pub fn trace_len_summary(&self) -> &TraceLenSummary {
+        &self.trace_len_summary
+    }
+
+    /// Returns the padded length of the execution trace.
+    pub fn trace_len(&self) -> usize {
+        self.trace_len_summary.padded_trace_len()
+    }
+
+    /// Returns the selected proof options.
+    pub fn options(&self) -> &ProofOptions {
+        &self.options
+    }
+
+    /// Returns the conjectured security level (in bits) of the proof generated with the selected
+    /// options, which is at least the requested security level.
+    pub fn security_level(&self) -> u32 {
+        self.options.security_level(self.trace_len())
+    }
+
+    /// Returns an estimate of the size (in bytes) of the proof generated with the selected
+    /// options.
+    pub fn proof_size(&self) -> usize {
+        self.options.estimate_proof_size(self.trace_len())
+    }
+}
+
+// PROOF OPTIONS SELECTION
+// ================================================================================================
+
+/// Executes the specified `program` to determine the length of its execution trace, and selects
+/// proof options which provide at least the specified conjectured `security_level` (in bits) for
+/// the trace while resulting in the smallest proof.
+///
+/// The program is executed with the specified inputs and execution options, which should be the
+/// same as the ones with which the program is proved, since the length of the trace depends on
+/// them. No proof is generated.
+///
+/// # Errors
+/// Returns an error if program execution fails, or
+/// [ExecutionError::UnsupportedSecurityLevel] if none of the explored options provides the
+/// security level.
+pub fn select_proof_options(
+    program: &Program,
+    inputs: &ProgramInputs,
+    security_level: u32,
+    exec_options: ExecutionOptions,
+) -> Result<ProofEstimate, ExecutionError> {
+    let trace = processor::execute_with_options(program, inputs, exec_options)?;
+    let trace_len_summary = *trace.trace_len_summary();
+    let options =
+        ProofOptions::with_security_level(trace_len_summary.padded_trace_len(), security_level)
+            .ok_or(ExecutionError::UnsupportedSecurityLevel(security_level))?;
+
+    Ok(ProofEstimate {
+        trace_len_summary,
+        options,
+    })
+}
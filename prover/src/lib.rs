@@ -1,23 +1,36 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
-use air::{ProcessorAir, PublicInputs};
-use processor::ExecutionTrace;
-use prover::Prover;
-use vm_core::{utils::collections::Vec, Felt, StarkField, MIN_STACK_DEPTH};
-
-#[cfg(feature = "std")]
+use air::ProcessorAir;
+use core::marker::PhantomData;
 use log::debug;
+use prover::{Prover, TraceLayout};
+use vm_core::{
+    utils::{collections::Vec, Clock},
+    Felt, StarkField, AUX_TRACE_RAND_ELEMENTS, AUX_TRACE_WIDTH, MIN_STACK_DEPTH, MIN_TRACE_LEN,
+    TRACE_WIDTH,
+};
+
+#[cfg(not(feature = "std"))]
+use vm_core::utils::FixedClock;
 #[cfg(feature = "std")]
-use prover::Trace;
-#[cfg(feature = "std")]
-use std::time::Instant;
+use vm_core::utils::SystemClock;
+
+mod progress;
+use progress::ObservedTrace;
+pub use progress::{ProgressObserver, ProvingStage};
+
+mod estimate;
+pub use estimate::{select_proof_options, ProofEstimate};
 
 // EXPORTS
 // ================================================================================================
 
-pub use air::{FieldExtension, HashFunction, ProofOptions};
-pub use processor::ExecutionError;
-pub use prover::StarkProof;
+pub use air::{
+    ExecutionProof, FieldExtension, HashFunction, ProofCompression, ProofFormatError, ProofHeader,
+    ProofOptions, PublicInputs,
+};
+pub use processor::{ExecutionError, ExecutionOptions, ExecutionTrace, TraceLenSummary};
+pub use prover::{StarkProof, Trace};
 pub use vm_core::{
     chiplets::hasher::Digest,
     errors::{AdviceSetError, InputError},
@@ -36,6 +49,9 @@ pub use vm_core::{
 ///   returned.
 /// * `options` defines parameters for STARK proof generation.
 ///
+/// Timings of execution are measured with the system clock when the `std` feature is enabled,
+/// and are not measured otherwise.
+///
 /// # Errors
 /// Returns an error if program execution or STARK proof generation fails for any reason.
 pub fn prove(
@@ -43,21 +59,86 @@ pub fn prove(
     inputs: &ProgramInputs,
     num_stack_outputs: usize,
     options: &ProofOptions,
-) -> Result<(Vec<u64>, StarkProof), ExecutionError> {
+) -> Result<(Vec<u64>, ExecutionProof), ExecutionError> {
+    #[cfg(feature = "std")]
+    let clock = SystemClock::default();
+    #[cfg(not(feature = "std"))]
+    let clock = FixedClock::default();
+
+    prove_with_options(
+        program,
+        inputs,
+        num_stack_outputs,
+        options,
+        ExecutionOptions::default(),
+        &clock,
+    )
+}
+
+/// Executes and proves the specified `program` in the same way as [prove()], but with execution
+/// controlled by the provided [ExecutionOptions], and with timings measured by the provided
+/// [Clock].
+///
+/// Neither the processor nor the prover draw randomness from the operating system, and the clock
+/// is used only for logging. Thus, the resulting proof depends only on the program, the inputs,
+/// and the options, which makes this function suitable for deterministic environments (e.g.,
+/// tests, WASI, or SGX enclaves).
+///
+/// # Errors
+/// Returns an error if program execution or STARK proof generation fails for any reason.
+pub fn prove_with_options<C: Clock>(
+    program: &Program,
+    inputs: &ProgramInputs,
+    num_stack_outputs: usize,
+    options: &ProofOptions,
+    exec_options: ExecutionOptions,
+    clock: &C,
+) -> Result<(Vec<u64>, ExecutionProof), ExecutionError> {
+    prove_with_progress(
+        program,
+        inputs,
+        num_stack_outputs,
+        options,
+        exec_options,
+        clock,
+        &mut (),
+    )
+}
+
+/// Executes and proves the specified `program` in the same way as [prove_with_options()], while
+/// reporting the stages of proof generation to the provided [ProgressObserver].
+///
+/// The observer can also request proof generation to be cancelled; cancellation is checked at
+/// the points described in [ProgressObserver].
+///
+/// # Errors
+/// Returns an error if program execution or STARK proof generation fails for any reason, or
+/// [ExecutionError::ProvingCancelled] if the observer requested proof generation to be cancelled.
+pub fn prove_with_progress<C: Clock, O: ProgressObserver>(
+    program: &Program,
+    inputs: &ProgramInputs,
+    num_stack_outputs: usize,
+    options: &ProofOptions,
+    exec_options: ExecutionOptions,
+    clock: &C,
+    observer: &mut O,
+) -> Result<(Vec<u64>, ExecutionProof), ExecutionError> {
     if num_stack_outputs > MIN_STACK_DEPTH {
         return Err(ExecutionError::TooManyStackOutputs(num_stack_outputs));
     }
+    if observer.is_cancelled() {
+        return Err(ExecutionError::ProvingCancelled);
+    }
+    observer.on_stage(ProvingStage::ExecutingProgram);
 
     // execute the program to create an execution trace
-    #[cfg(feature = "std")]
-    let now = Instant::now();
-    let trace = processor::execute(program, inputs)?;
-    #[cfg(feature = "std")]
+    let now = clock.now();
+    let trace = processor::execute_with_options(program, inputs, exec_options)?;
     debug!(
         "Generated execution trace of {} columns and {} steps in {} ms",
         trace.layout().main_trace_width(),
         trace.length(),
-        now.elapsed().as_millis()
+        clock.elapsed(now).as_millis()
     );
 
     // copy the stack state at the last step to return as output
@@ -68,45 +149,250 @@ pub fn prove(
 
     // generate STARK proof
     let num_stack_inputs = inputs.stack_init().len();
-    let prover = ExecutionProver::new(options.clone(), num_stack_inputs, num_stack_outputs);
-    let proof = prover.prove(trace).map_err(ExecutionError::ProverError)?;
+    let public_inputs = PublicInputs::new(
+        trace.program_hash(),
+        trace.init_stack_state()[..num_stack_inputs].to_vec(),
+        trace.last_stack_state()[..num_stack_outputs].to_vec(),
+    );
+    if observer.is_cancelled() {
+        return Err(ExecutionError::ProvingCancelled);
+    }
+    let proof = prove_trace(ObservedTrace::new(trace, observer), public_inputs, options)?;
+    if observer.is_cancelled() {
+        return Err(ExecutionError::ProvingCancelled);
+    }
 
     Ok((outputs, proof))
 }
 
+/// Generates a STARK proof attesting that the specified execution `trace` satisfies the
+/// constraints of Miden VM against the specified `public_inputs`.
+///
+/// Unlike [prove()], this function does not execute a program, and thus can be used to prove
+/// traces which were built, or modified, outside of the processor. The trace can be any type
+/// implementing the [Trace] trait; e.g., an [ExecutionTrace] returned by the processor and then
+/// modified via [ExecutionTrace::main_segment_mut()].
+///
+/// The proof is verifiable only if the trace is valid. Note that in debug builds, the underlying
+/// STARK prover validates the trace before generating a proof, and panics if the trace does not
+/// satisfy the constraints.
+///
+/// # Errors
+/// Returns an error if:
+/// - The layout of the trace is different from the layout of the execution traces of Miden VM
+///   (i.e., the trace has an unexpected number of main or auxiliary columns).
+/// - The length of the trace is not a power of two, or is smaller than the minimum trace length.
+/// - STARK proof generation fails for any other reason.
+pub fn prove_trace<T>(
+    trace: T,
+    public_inputs: PublicInputs,
+    options: &ProofOptions,
+) -> Result<ExecutionProof, ExecutionError>
+where
+    T: Trace<BaseField = Felt>,
+{
+    let layout = TraceLayout::new(TRACE_WIDTH, [AUX_TRACE_WIDTH], [AUX_TRACE_RAND_ELEMENTS]);
+    if trace.layout() != &layout {
+        return Err(ExecutionError::InvalidTraceLayout(trace.layout().clone()));
+    }
+    if trace.length() < MIN_TRACE_LEN || !trace.length().is_power_of_two() {
+        return Err(ExecutionError::InvalidTraceLength(trace.length()));
+    }
+
+    let prover = ExecutionProver::new(options.clone(), public_inputs);
+    prover
+        .prove(trace)
+        .map(ExecutionProof::new)
+        .map_err(ExecutionError::ProverError)
+}
+
 // PROVER
 // ================================================================================================
 
-struct ExecutionProver {
+struct ExecutionProver<T> {
     options: ProofOptions,
-    num_stack_inputs: usize,
-    num_stack_outputs: usize,
+    public_inputs: PublicInputs,
+    _trace: PhantomData<T>,
 }
 
-impl ExecutionProver {
-    pub fn new(options: ProofOptions, num_stack_inputs: usize, num_stack_outputs: usize) -> Self {
+impl<T> ExecutionProver<T> {
+    pub fn new(options: ProofOptions, public_inputs: PublicInputs) -> Self {
         Self {
             options,
-            num_stack_inputs,
-            num_stack_outputs,
+            public_inputs,
+            _trace: PhantomData,
         }
     }
 }
 
-impl Prover for ExecutionProver {
+impl<T: Trace<BaseField = Felt>> Prover for ExecutionProver<T> {
     type BaseField = Felt;
     type Air = ProcessorAir;
-    type Trace = ExecutionTrace;
+    type Trace = T;
 
     fn options(&self) -> &prover::ProofOptions {
         &self.options
     }
 
-    fn get_pub_inputs(&self, trace: &ExecutionTrace) -> PublicInputs {
-        PublicInputs::new(
-            trace.program_hash(),
-            trace.init_stack_state()[..self.num_stack_inputs].to_vec(),
-            trace.last_stack_state()[..self.num_stack_outputs].to_vec(),
-        )
+    fn get_pub_inputs(&self, _trace: &T) -> PublicInputs {
+        self.public_inputs.clone()
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        prove, prove_trace, prove_with_progress, select_proof_options, ExecutionError,
+        ExecutionOptions, ExecutionTrace, Felt, ProgramInputs, ProgressObserver, ProofOptions,
+        ProvingStage, PublicInputs, Trace, TraceLayout,
+    };
+    use prover::{EvaluationFrame, Matrix};
+    use vm_core::{code_blocks::CodeBlock, utils::FixedClock, FieldElement, Operation, Program};
+
+    /// An execution trace which reports a different layout than the trace it wraps.
+    struct MislaidTrace {
+        trace: ExecutionTrace,
+        layout: TraceLayout,
+    }
+
+    impl Trace for MislaidTrace {
+        type BaseField = Felt;
+
+        fn layout(&self) -> &TraceLayout {
+            &self.layout
+        }
+
+        fn length(&self) -> usize {
+            self.trace.length()
+        }
+
+        fn meta(&self) -> &[u8] {
+            self.trace.meta()
+        }
+
+        fn main_segment(&self) -> &Matrix<Felt> {
+            self.trace.main_segment()
+        }
+
+        fn build_aux_segment<E: FieldElement<BaseField = Felt>>(
+            &mut self,
+            aux_segments: &[Matrix<E>],
+            rand_elements: &[E],
+        ) -> Option<Matrix<E>> {
+            self.trace.build_aux_segment(aux_segments, rand_elements)
+        }
+
+        fn read_main_frame(&self, row_idx: usize, frame: &mut EvaluationFrame<Felt>) {
+            self.trace.read_main_frame(row_idx, frame)
+        }
+    }
+
+    #[test]
+    fn prove_trace_with_invalid_layout() {
+        let program = Program::new(CodeBlock::new_span(vec![Operation::Add]));
+        let trace = processor::execute(&program, &ProgramInputs::none()).unwrap();
+        let public_inputs = PublicInputs::new(program.hash(), vec![], vec![]);
+
+        let layout = TraceLayout::new(trace.main_trace_width() + 1, [1], [1]);
+        let trace = MislaidTrace { trace, layout };
+        assert!(matches!(
+            prove_trace(trace, public_inputs, &ProofOptions::default()),
+            Err(ExecutionError::InvalidTraceLayout(_))
+        ));
+    }
+
+    /// An observer which records reported stages, and requests cancellation once the specified
+    /// stage has been reported.
+    struct StageRecorder {
+        stages: Vec<ProvingStage>,
+        cancel_at: Option<ProvingStage>,
+    }
+
+    impl ProgressObserver for StageRecorder {
+        fn on_stage(&mut self, stage: ProvingStage) {
+            self.stages.push(stage);
+        }
+
+        fn is_cancelled(&self) -> bool {
+            self.cancel_at
+                .map_or(false, |stage| self.stages.contains(&stage))
+        }
+    }
+
+    #[test]
+    fn prove_with_progress_reports_stages() {
+        let program = Program::new(CodeBlock::new_span(vec![Operation::Pad, Operation::Add]));
+        let mut observer = StageRecorder {
+            stages: Vec::new(),
+            cancel_at: None,
+        };
+        let result = prove_with_progress(
+            &program,
+            &ProgramInputs::none(),
+            1,
+            &ProofOptions::default(),
+            ExecutionOptions::default(),
+            &FixedClock::default(),
+            &mut observer,
+        );
+        assert!(result.is_ok());
+        assert_eq!(
+            vec![
+                ProvingStage::ExecutingProgram,
+                ProvingStage::CommittingMainTrace,
+                ProvingStage::CommittingAuxTrace,
+                ProvingStage::ProvingConstraints,
+            ],
+            observer.stages
+        );
+    }
+
+    #[test]
+    fn prove_with_progress_cancelled() {
+        let program = Program::new(CodeBlock::new_span(vec![Operation::Pad, Operation::Add]));
+        for cancel_at in [
+            ProvingStage::ExecutingProgram,
+            ProvingStage::ProvingConstraints,
+        ] {
+            let mut observer = StageRecorder {
+                stages: Vec::new(),
+                cancel_at: Some(cancel_at),
+            };
+            let result = prove_with_progress(
+                &program,
+                &ProgramInputs::none(),
+                1,
+                &ProofOptions::default(),
+                ExecutionOptions::default(),
+                &FixedClock::default(),
+                &mut observer,
+            );
+            assert!(matches!(result, Err(ExecutionError::ProvingCancelled)));
+            assert_eq!(Some(&cancel_at), observer.stages.last());
+        }
+    }
+
+    #[test]
+    fn select_proof_options_for_security_level() {
+        let program = Program::new(CodeBlock::new_span(vec![Operation::Pad, Operation::Add]));
+        let inputs = ProgramInputs::none();
+        let estimate =
+            select_proof_options(&program, &inputs, 100, ExecutionOptions::default()).unwrap();
+        assert_eq!(1024, estimate.trace_len());
+
+        // the proof provides the requested security level, and its size is close to the estimate
+        let (_, proof) = prove(&program, &inputs, 1, estimate.options()).unwrap();
+        assert!(proof.security_level(true) >= 100);
+        assert_eq!(estimate.security_level(), proof.security_level(true));
+        let proof_size = proof.to_bytes().len();
+        assert!(proof_size.abs_diff(estimate.proof_size()) * 10 < proof_size);
+
+        assert!(matches!(
+            select_proof_options(&program, &inputs, 200, ExecutionOptions::default()),
+            Err(ExecutionError::UnsupportedSecurityLevel(200))
+        ));
     }
 }
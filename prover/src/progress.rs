@@ -0,0 +1,143 @@
+use core::cell::{Cell, RefCell};
+use core::fmt;
+use prover::{EvaluationFrame, Matrix, Trace, TraceLayout};
+use vm_core::{Felt, FieldElement};
+
+// PROVING STAGE
+// ================================================================================================
+
+/// Stages of executing and proving a program, in the order in which they are entered.
+///
+/// The STARK prover does not report its progress after the auxiliary trace segment has been
+/// built, and thus evaluation of constraints, FRI commitments, and query generation are all
+/// reported as a single [ProvingStage::ProvingConstraints] stage.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ProvingStage {
+    /// The program is being executed to build the execution trace.
+    ExecutingProgram,
+    /// The main segment of the execution trace is being extended and committed to.
+    CommittingMainTrace,
+    /// The auxiliary segment of the execution trace is being built and committed to.
+    CommittingAuxTrace,
+    /// Constraints are being evaluated and committed to, and FRI proof is being generated.
+    ProvingConstraints,
+}
+
+impl ProvingStage {
+    /// Number of stages of proof generation.
+    pub const NUM_STAGES: usize = 4;
+
+    /// Returns the index of this stage in the range [0, NUM_STAGES).
+    pub fn index(&self) -> usize {
+        *self as usize
+    }
+}
+
+impl fmt::Display for ProvingStage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ExecutingProgram => write!(f, "executing program"),
+            Self::CommittingMainTrace => write!(f, "committing to main trace"),
+            Self::CommittingAuxTrace => write!(f, "committing to auxiliary trace"),
+            Self::ProvingConstraints => write!(f, "proving constraints"),
+        }
+    }
+}
+
+// PROGRESS OBSERVER
+// ================================================================================================
+
+/// Receives notifications about the progress of proof generation, and can request proof
+/// generation to be cancelled.
+///
+/// Cancellation is checked before the program is executed, before the STARK proof generation
+/// starts, and once the proof has been generated; if a cancellation is requested, the prover
+/// returns [ExecutionError::ProvingCancelled](crate::ExecutionError::ProvingCancelled) at the
+/// next check. The STARK prover itself cannot be interrupted, and thus callers who need to
+/// abandon proving immediately should run it on a separate thread.
+pub trait ProgressObserver {
+    /// Called when proof generation enters the specified stage. Every stage is reported at most
+    /// once, and stages are reported in order.
+    fn on_stage(&mut self, stage: ProvingStage);
+
+    /// Returns true if proof generation should be abandoned.
+    fn is_cancelled(&self) -> bool {
+        false
+    }
+}
+
+/// An observer which ignores all notifications and never cancels proof generation.
+impl ProgressObserver for () {
+    fn on_stage(&mut self, _stage: ProvingStage) {}
+}
+
+// OBSERVED TRACE
+// ================================================================================================
+
+/// An execution trace which reports the stages of proof generation to an observer based on which
+/// parts of the trace are requested by the STARK prover.
+pub(crate) struct ObservedTrace<'a, T, O: ProgressObserver> {
+    trace: T,
+    observer: RefCell<&'a mut O>,
+    stage: Cell<ProvingStage>,
+}
+
+impl<'a, T, O: ProgressObserver> ObservedTrace<'a, T, O> {
+    /// Returns the specified trace observed by the specified observer; the execution of the
+    /// program is assumed to be completed.
+    pub fn new(trace: T, observer: &'a mut O) -> Self {
+        Self {
+            trace,
+            observer: RefCell::new(observer),
+            stage: Cell::new(ProvingStage::ExecutingProgram),
+        }
+    }
+
+    /// Reports the specified stage if it follows the current stage.
+    fn enter(&self, stage: ProvingStage) {
+        if stage > self.stage.get() {
+            self.stage.set(stage);
+            self.observer.borrow_mut().on_stage(stage);
+        }
+    }
+}
+
+impl<'a, T, O> Trace for ObservedTrace<'a, T, O>
+where
+    T: Trace<BaseField = Felt>,
+    O: ProgressObserver,
+{
+    type BaseField = Felt;
+
+    fn layout(&self) -> &TraceLayout {
+        self.trace.layout()
+    }
+
+    fn length(&self) -> usize {
+        self.trace.length()
+    }
+
+    fn meta(&self) -> &[u8] {
+        self.trace.meta()
+    }
+
+    fn main_segment(&self) -> &Matrix<Felt> {
+        self.enter(ProvingStage::CommittingMainTrace);
+        self.trace.main_segment()
+    }
+
+    fn build_aux_segment<E: FieldElement<BaseField = Felt>>(
+        &mut self,
+        aux_segments: &[Matrix<E>],
+        rand_elements: &[E],
+    ) -> Option<Matrix<E>> {
+        self.enter(ProvingStage::CommittingAuxTrace);
+        let segment = self.trace.build_aux_segment(aux_segments, rand_elements);
+        self.enter(ProvingStage::ProvingConstraints);
+        segment
+    }
+
+    fn read_main_frame(&self, row_idx: usize, frame: &mut EvaluationFrame<Felt>) {
+        self.trace.read_main_frame(row_idx, frame)
+    }
+}
@@ -4,24 +4,331 @@
 ///
 /// Entries in the array are tuples containing module namespace and module source code.
 #[rustfmt::skip]
-pub const MODULES: [(&str, &str); 7] = [
+pub const MODULES: [(&str, &str); 17] = [
+// ----- std::advice ------------------------------------------------------------------------------
+("std::advice", "# Advice inputs are supplied by the prover and are not trusted. A common way to use advice inputs
+# securely is to commit to them publicly (e.g., via stack inputs) and to verify the advice against
+# the commitment before it is used. Procedures in this module do this for entries of the advice
+# map: the values of an entry are loaded into memory and hashed in the same pass.
+#
+# The commitment to a list of values is their hash as computed by the hash function of the VM
+# (i.e., Rp64_256::hash_elements() with the values as field elements). The number of values is a
+# part of the hash, and thus padding the values with ZEROs changes the commitment.
+
+#! Loads the values of the advice map entry with key K into memory starting at address addr, and
+#! returns the commitment C to the values together with the number of values n. The values are
+#! stored 4 per word in the order in which they were specified in the advice map, and the last
+#! word is padded with ZEROs; n values thus occupy 2 * ceil(n / 8) words. Fails if the advice map
+#! does not contain an entry with key K.
+#! Input: [K, addr, ...]
+#! Output: [C, n, ...]
+export.load_map_value
+    adv.mapval
+    dropw
+    push.adv.1
+    u32assert
+
+    # arrange the stack as [R, C, addr, n, n, ...], where R and C are the rate and the capacity
+    # portions of the hasher state (the capacity starts with the number of values), addr is the
+    # address of the next word to be written, and the first n is the number of values left to read
+    swap
+    dup.1
+    swap
+    dup.1
+    push.0
+    push.0
+    push.0
+    padw
+    padw
+
+    dup.13
+    neq.0
+    while.true
+        # read the next 8 values and store them in memory at addresses addr and addr + 1
+        movup.12
+        push.adv.8
+        swapw
+        dup.8
+        storew.mem
+        swapw
+        dup.8
+        add.1
+        storew.mem
+
+        # absorb the values into the rate portion of the hasher state, advance the address, and
+        # apply the permutation
+        repeat.8
+            movup.9
+            add
+            movdn.7
+        end
+        movup.8
+        add.2
+        movdn.12
+        rpperm
+
+        # update the number of values left to read
+        movup.13
+        dup
+        push.8
+        u32checked_gt
+        if.true
+            sub.8
+        else
+            drop
+            push.0
+        end
+        dup
+        movdn.14
+        neq.0
+    end
+
+    # the hash of the values is the first word of the rate portion of the hasher state
+    dropw
+    swapw
+    dropw
+    movup.4
+    drop
+    movup.4
+    drop
+end
+
+#! Loads the values of the advice map entry with key K into memory starting at address addr (see
+#! load_map_value), and asserts that the commitment to the values is equal to C. Returns the number
+#! of values n. Fails if the advice map does not contain an entry with key K, or if the values do
+#! not match the commitment.
+#! Input: [K, C, addr, ...]
+#! Output: [n, ...]
+export.load_verified_map_value
+    movup.8
+    movdn.4
+    exec.load_map_value
+    movup.4
+    movdn.8
+    eqw
+    assert
+    dropw
+    dropw
+end
+"),
+// ----- std::collections::vec --------------------------------------------------------------------
+("std::collections::vec", "# A vector is a length-prefixed array of words stored in consecutive memory addresses. A vector
+# located at address v with capacity c occupies c + 1 words of memory:
+# - The word at address v is the header of the vector. The first element of the header holds the
+#   number of items currently in the vector, and the second element holds the capacity of the
+#   vector.
+# - The word at address v + 1 + i holds the i-th item of the vector.
+#
+# Memory for a vector can be obtained via std::mem::alloc, e.g., to create a vector with capacity
+# c: push.c dup add.1 exec.mem::alloc dup movdn.2 exec.vec::init
+
+use.std::mem
+
+#! Initializes an empty vector with the specified capacity at address v. The next c words after v
+#! must be reserved for the items of the vector.
+#! Input: [v, c, ...]
+#! Output: [...]
+export.init
+    push.0
+    movdn.2
+    push.0.0
+    movup.2
+    popw.mem
+end
+
+#! Returns the number of items in the vector at address v.
+#! Input: [v, ...]
+#! Output: [len, ...]
+export.len
+    push.mem
+end
+
+#! Returns the capacity of the vector at address v.
+#! Input: [v, ...]
+#! Output: [c, ...]
+export.capacity
+    pushw.mem
+    drop
+    drop
+    swap
+    drop
+end
+
+#! Appends the word W to the end of the vector at address v. Fails if the vector is full.
+#! Input: [v, W, ...]
+#! Output: [...]
+export.push
+    dup
+    pushw.mem
+    drop
+    drop
+    dup.1
+    dup.1
+    u32checked_lt
+    assert
+    drop
+    dup.1
+    add
+    add.1
+    swap
+    movdn.5
+    popw.mem
+    dup
+    push.mem
+    add.1
+    swap
+    pop.mem
+end
+
+#! Removes the last word from the vector at address v and returns it. Fails if the vector is empty.
+#! Input: [v, ...]
+#! Output: [W, ...]
+export.pop
+    dup
+    push.mem
+    dup
+    neq.0
+    assert
+    sub.1
+    dup
+    dup.2
+    pop.mem
+    add
+    add.1
+    pushw.mem
+end
+
+#! Returns the word at index i of the vector at address v. Fails if i is not smaller than the
+#! number of items in the vector.
+#! Input: [v, i, ...]
+#! Output: [W, ...]
+export.get
+    dup
+    push.mem
+    dup.2
+    swap
+    u32checked_lt
+    assert
+    add
+    add.1
+    pushw.mem
+end
+
+#! Sets the word at index i of the vector at address v to W. Fails if i is not smaller than the
+#! number of items in the vector.
+#! Input: [v, i, W, ...]
+#! Output: [...]
+export.set
+    dup
+    push.mem
+    dup.2
+    swap
+    u32checked_lt
+    assert
+    add
+    add.1
+    popw.mem
+end
+
+#! Sorts the items of the vector at address v in ascending order of their first elements, which
+#! are compared as integers. Items with equal first elements retain their relative order.
+#!
+#! The procedure emits event 4294901761 (0xFFFF0001), in response to which the host is expected to
+#! inject a permutation into the advice tape (e.g., via HintProvider): the i-th value read from
+#! the tape is the index of the item which is moved to position i. The permutation is verified in
+#! the VM, and thus the procedure fails if the host supplies a permutation which does not sort the
+#! vector. Verification uses a scratch region of 2 * len words obtained via std::mem::alloc.
+#! Input: [v, ...]
+#! Output: [...]
+export.sort_hint.3
+    emit.4294901761     # request the sorting permutation from the host
+
+    # allocate the scratch region: its first len words hold a copy of the items, and the next
+    # len words mark the items which have already been moved
+    dup
+    push.mem
+    dup
+    mul.2
+    exec.mem::alloc
+    dup.1
+    dup.1
+    dup.4
+    add.1
+    exec.mem::memcopy
+    pop.local.0
+    pop.local.1
+    pop.local.2
+
+    # the stack is arranged as [i, k, ...], where i is the next position to fill and k is the
+    # first element of the item at the previous position
+    push.0
+    push.0
+    dup
+    push.local.1
+    u32checked_lt
+    while.true
+        # read the index p of the next item and make sure it refers to an item not moved yet
+        push.adv.1
+        dup
+        push.local.1
+        u32checked_lt
+        assert
+        push.local.0
+        push.local.1
+        add
+        dup.1
+        add
+        dup
+        push.mem
+        eq.0
+        assert
+        push.1
+        swap
+        pop.mem
+
+        # load the item and make sure it is not smaller than the item at the previous position
+        push.local.0
+        add
+        pushw.mem
+        dup.3
+        movup.6
+        dup.1
+        lte
+        assert
+        movdn.5
+
+        # store the item at position i and advance to the next position
+        push.local.2
+        add.1
+        dup.5
+        add
+        popw.mem
+        add.1
+        dup
+        push.local.1
+        u32checked_lt
+    end
+    drop
+    drop
+end
+"),
 // ----- std::crypto::hashes::blake3 --------------------------------------------------------------
-("std::crypto::hashes::blake3", "# Initializes four memory addresses, provided for storing initial 4x4 blake3 
-# state matrix ( i.e. 16 elements each of 32 -bit ), for computing blake3 2-to-1 hash
-#
-# Expected stack state:
-#
-# [state_0_3_addr, state_4_7_addr, state_8_11_addr, state_12_15_addr]
-#
-# Note, state_`i`_`j`_addr -> absolute address of {state[i], state[i+1], state[i+2], state[i+3]} in memory | j = i+3
-#
-# Final stack state:
-#
-# [...]
-#
-# Initialized stack state is written back to provided memory addresses.
-#
-# Functionally this routine is equivalent to https://github.com/itzmeanjan/blake3/blob/f07d32e/include/blake3.hpp#L1709-L1713
+("std::crypto::hashes::blake3", "#! Initializes four memory addresses, provided for storing initial 4x4 blake3 
+#! state matrix ( i.e. 16 elements each of 32 -bit ), for computing blake3 2-to-1 hash
+#!
+#! Expected stack state:
+#!
+#! [state_0_3_addr, state_4_7_addr, state_8_11_addr, state_12_15_addr]
+#!
+#! Note, state_`i`_`j`_addr -> absolute address of {state[i], state[i+1], state[i+2], state[i+3]} in memory | j = i+3
+#!
+#! Final stack state:
+#!
+#! [...]
+#!
+#! Initialized stack state is written back to provided memory addresses.
+#!
+#! Functionally this routine is equivalent to https://github.com/itzmeanjan/blake3/blob/f07d32e/include/blake3.hpp#L1709-L1713
 proc.initialize
     push.0xA54FF53A.0x3C6EF372.0xBB67AE85.0x6A09E667
     movup.4
@@ -40,18 +347,18 @@ proc.initialize
     popw.mem
 end
 
-# Permutes ordered message words, kept on stack top ( = sixteen 32 -bit BLAKE3 words )
-#
-# Expected stack top: 
-#
-# [s0, s1, s2, s3, s4, s5, s6, s7, s8, s9, s10, s11, s12, s13, s14, s15]
-#
-# After permutation, stack top:
-#
-# [s2, s6, s3, s10, s7, s0, s4, s13, s1, s11, s12, s5, s9, s14, s15, s8]
-#
-# See https://github.com/itzmeanjan/blake3/blob/f07d32ec10cbc8a10663b7e6539e0b1dab3e453b/include/blake3.hpp#L1623-L1639
-# and https://github.com/maticnetwork/miden/pull/313#discussion_r922627984
+#! Permutes ordered message words, kept on stack top ( = sixteen 32 -bit BLAKE3 words )
+#!
+#! Expected stack top: 
+#!
+#! [s0, s1, s2, s3, s4, s5, s6, s7, s8, s9, s10, s11, s12, s13, s14, s15]
+#!
+#! After permutation, stack top:
+#!
+#! [s2, s6, s3, s10, s7, s0, s4, s13, s1, s11, s12, s5, s9, s14, s15, s8]
+#!
+#! See https://github.com/itzmeanjan/blake3/blob/f07d32ec10cbc8a10663b7e6539e0b1dab3e453b/include/blake3.hpp#L1623-L1639
+#! and https://github.com/maticnetwork/miden/pull/313#discussion_r922627984
 proc.permute_msg_words
     movdn.7
     movup.5
@@ -75,20 +382,20 @@ proc.permute_msg_words
     swapdw
 end
 
-# Given blake3 state matrix on stack top ( in order ) as 16 elements ( each of 32 -bit ),
-# this routine computes output chaining value i.e. 2-to-1 hashing digest.
-#
-# Expected stack state:
-#
-# [state0, state1, state2, state3, state4, state5, state6, state7, state8, state9, state10, state11, state12, state13, state14, state15]
-#
-# After finalizing, stack should look like
-#
-# [dig0, dig1, dig2, dig3, dig4, dig5, dig6, dig7]
-#
-# See https://github.com/BLAKE3-team/BLAKE3/blob/da4c792/reference_impl/reference_impl.rs#L116-L119 ,
-# you'll notice I've skipped executing second statement in loop body of above hyperlinked implementation,
-# that's because it doesn't dictate what output of 2-to-1 hash will be.
+#! Given blake3 state matrix on stack top ( in order ) as 16 elements ( each of 32 -bit ),
+#! this routine computes output chaining value i.e. 2-to-1 hashing digest.
+#!
+#! Expected stack state:
+#!
+#! [state0, state1, state2, state3, state4, state5, state6, state7, state8, state9, state10, state11, state12, state13, state14, state15]
+#!
+#! After finalizing, stack should look like
+#!
+#! [dig0, dig1, dig2, dig3, dig4, dig5, dig6, dig7]
+#!
+#! See https://github.com/BLAKE3-team/BLAKE3/blob/da4c792/reference_impl/reference_impl.rs#L116-L119 ,
+#! you'll notice I've skipped executing second statement in loop body of above hyperlinked implementation,
+#! that's because it doesn't dictate what output of 2-to-1 hash will be.
 proc.finalize
     movup.8
     u32checked_xor
@@ -129,25 +436,25 @@ proc.finalize
     movdn.7
 end
 
-# Given blake3 state matrix ( total 16 elements, each of 32 -bit ) and 
-# 8 message words ( each of 32 -bit ), this routine performs column-wise mixing
-# of message words into blake3 hash state.
-#
-# Functionality wise this routine is equivalent to https://github.com/BLAKE3-team/BLAKE3/blob/da4c792/reference_impl/reference_impl.rs#L55-L59
-#
-# Expected stack state:
-#
-# [state0_3_addr, state4_7_addr, state8_11_addr, state12_15_addr, m0, m1, m2, m3, m4, m5, m6, m7]
-#
-# Note, state_`i`_`j`_addr -> absolute address of {state[i], state[i+1], state[i+2], state[i+3]} in memory | j = i+3
-#
-# Meaning four consecutive blake3 state words can be read from memory easily.
-#
-# Final stack state:
-#
-# [state0, state1, state2, state3, state4, state5, state6, state7, state8, state9, state10, state11, state12, state13, state14, state15]
-#
-# i.e. whole blake3 state is placed on stack ( in order ).
+#! Given blake3 state matrix ( total 16 elements, each of 32 -bit ) and 
+#! 8 message words ( each of 32 -bit ), this routine performs column-wise mixing
+#! of message words into blake3 hash state.
+#!
+#! Functionality wise this routine is equivalent to https://github.com/BLAKE3-team/BLAKE3/blob/da4c792/reference_impl/reference_impl.rs#L55-L59
+#!
+#! Expected stack state:
+#!
+#! [state0_3_addr, state4_7_addr, state8_11_addr, state12_15_addr, m0, m1, m2, m3, m4, m5, m6, m7]
+#!
+#! Note, state_`i`_`j`_addr -> absolute address of {state[i], state[i+1], state[i+2], state[i+3]} in memory | j = i+3
+#!
+#! Meaning four consecutive blake3 state words can be read from memory easily.
+#!
+#! Final stack state:
+#!
+#! [state0, state1, state2, state3, state4, state5, state6, state7, state8, state9, state10, state11, state12, state13, state14, state15]
+#!
+#! i.e. whole blake3 state is placed on stack ( in order ).
 proc.columnar_mixing.1
     swapw.2
     swapw
@@ -361,25 +668,25 @@ proc.columnar_mixing.1
     movupw.3
 end
 
-# Given blake3 state matrix ( total 16 elements, each of 32 -bit ) and 
-# 8 message words ( each of 32 -bit ), this routine performs diagonal-wise mixing
-# of message words into blake3 hash state.
-#
-# Functionality wise this routine is equivalent to https://github.com/BLAKE3-team/BLAKE3/blob/da4c792/reference_impl/reference_impl.rs#L61-L64
-#
-# Expected stack state:
-#
-# [state0_3_addr, state4_7_addr, state8_11_addr, state12_15_addr, m0, m1, m2, m3, m4, m5, m6, m7]
-#
-# Note, state_`i`_`j`_addr -> absolute address of {state[i], state[i+1], state[i+2], state[i+3]} in memory | j = i+3
-#
-# Meaning four consecutive blake3 state words can be read from memory easily.
-#
-# Final stack state:
-#
-# [state0, state1, state2, state3, state4, state5, state6, state7, state8, state9, state10, state11, state12, state13, state14, state15]
-#
-# i.e. whole blake3 state is placed on stack ( in order ).
+#! Given blake3 state matrix ( total 16 elements, each of 32 -bit ) and 
+#! 8 message words ( each of 32 -bit ), this routine performs diagonal-wise mixing
+#! of message words into blake3 hash state.
+#!
+#! Functionality wise this routine is equivalent to https://github.com/BLAKE3-team/BLAKE3/blob/da4c792/reference_impl/reference_impl.rs#L61-L64
+#!
+#! Expected stack state:
+#!
+#! [state0_3_addr, state4_7_addr, state8_11_addr, state12_15_addr, m0, m1, m2, m3, m4, m5, m6, m7]
+#!
+#! Note, state_`i`_`j`_addr -> absolute address of {state[i], state[i+1], state[i+2], state[i+3]} in memory | j = i+3
+#!
+#! Meaning four consecutive blake3 state words can be read from memory easily.
+#!
+#! Final stack state:
+#!
+#! [state0, state1, state2, state3, state4, state5, state6, state7, state8, state9, state10, state11, state12, state13, state14, state15]
+#!
+#! i.e. whole blake3 state is placed on stack ( in order ).
 proc.diagonal_mixing.1
     swapw.2
     swapw
@@ -593,28 +900,28 @@ proc.diagonal_mixing.1
     movupw.3
 end
 
-# Given blake3 state matrix ( total 16 elements, each of 32 -bit ) and 
-# 16 message words ( each of 32 -bit ), this routine applies single round of mixing
-# of message words into hash state i.e. msg_word[0..8] are mixed into hash state using
-# columnar mixing while remaining message words ( msg_word[8..16] ) are mixed into hash state
-# using diagonal mixing.
-#
-# Functionality wise this routine is equivalent to https://github.com/BLAKE3-team/BLAKE3/blob/da4c792/reference_impl/reference_impl.rs#L54-L65
-#
-# Expected stack state:
-#
-# [state0_3_addr, state4_7_addr, state8_11_addr, state12_15_addr, m0, m1, m2, m3, m4, m5, m6, m7, m8, m9, m10, m11, m12, m13, m14, m15]
-#
-# Note, state_`i`_`j`_addr -> absolute address of {state[i], state[i+1], state[i+2], state[i+3]} in memory | j = i+3
-#
-# Meaning four consecutive blake3 state words can be read from memory easily.
-#
-# Final stack state:
-#
-# [...]
-#
-# i.e. mixed state matrix lives in memory addresses {state0_3_addr, state4_7_addr, state8_11_addr, state12_15_addr}, 
-# which were provided, on stack top, while invoking this routine.
+#! Given blake3 state matrix ( total 16 elements, each of 32 -bit ) and 
+#! 16 message words ( each of 32 -bit ), this routine applies single round of mixing
+#! of message words into hash state i.e. msg_word[0..8] are mixed into hash state using
+#! columnar mixing while remaining message words ( msg_word[8..16] ) are mixed into hash state
+#! using diagonal mixing.
+#!
+#! Functionality wise this routine is equivalent to https://github.com/BLAKE3-team/BLAKE3/blob/da4c792/reference_impl/reference_impl.rs#L54-L65
+#!
+#! Expected stack state:
+#!
+#! [state0_3_addr, state4_7_addr, state8_11_addr, state12_15_addr, m0, m1, m2, m3, m4, m5, m6, m7, m8, m9, m10, m11, m12, m13, m14, m15]
+#!
+#! Note, state_`i`_`j`_addr -> absolute address of {state[i], state[i+1], state[i+2], state[i+3]} in memory | j = i+3
+#!
+#! Meaning four consecutive blake3 state words can be read from memory easily.
+#!
+#! Final stack state:
+#!
+#! [...]
+#!
+#! i.e. mixed state matrix lives in memory addresses {state0_3_addr, state4_7_addr, state8_11_addr, state12_15_addr}, 
+#! which were provided, on stack top, while invoking this routine.
 proc.round.5
     storew.local.0
 
@@ -650,27 +957,27 @@ proc.round.5
     end
 end
 
-# Given blake3 state matrix ( total 16 elements, each of 32 -bit ) and a message block
-# i.e. 16 message words ( each of 32 -bit ), this routine applies 7 rounds of mixing
-# of (permuted) message words into hash state.
-#
-# Functionality wise this routine is equivalent to https://github.com/BLAKE3-team/BLAKE3/blob/da4c792/reference_impl/reference_impl.rs#L75-L114
-#
-# Expected stack state:
-#
-# [state0_3_addr, state4_7_addr, state8_11_addr, state12_15_addr, m0, m1, m2, m3, m4, m5, m6, m7, m8, m9, m10, m11, m12, m13, m14, m15]
-#
-# Note, state_`i`_`j`_addr -> absolute address of {state[i], state[i+1], state[i+2], state[i+3]} in memory | j = i+3
-#
-# Meaning four consecutive blake3 state words can be read from memory easily.
-#
-# Final stack state:
-#
-# [...]
-#
-# i.e. 7 -round mixed state matrix lives in memory addresses {state0_3_addr, state4_7_addr, state8_11_addr, state12_15_addr}, 
-# which were provided, on stack top, while invoking this routine. So updated state matrix can be read by caller routine, by reading
-# the content of memory addresses where state was provided as routine input.
+#! Given blake3 state matrix ( total 16 elements, each of 32 -bit ) and a message block
+#! i.e. 16 message words ( each of 32 -bit ), this routine applies 7 rounds of mixing
+#! of (permuted) message words into hash state.
+#!
+#! Functionality wise this routine is equivalent to https://github.com/BLAKE3-team/BLAKE3/blob/da4c792/reference_impl/reference_impl.rs#L75-L114
+#!
+#! Expected stack state:
+#!
+#! [state0_3_addr, state4_7_addr, state8_11_addr, state12_15_addr, m0, m1, m2, m3, m4, m5, m6, m7, m8, m9, m10, m11, m12, m13, m14, m15]
+#!
+#! Note, state_`i`_`j`_addr -> absolute address of {state[i], state[i+1], state[i+2], state[i+3]} in memory | j = i+3
+#!
+#! Meaning four consecutive blake3 state words can be read from memory easily.
+#!
+#! Final stack state:
+#!
+#! [...]
+#!
+#! i.e. 7 -round mixed state matrix lives in memory addresses {state0_3_addr, state4_7_addr, state8_11_addr, state12_15_addr}, 
+#! which were provided, on stack top, while invoking this routine. So updated state matrix can be read by caller routine, by reading
+#! the content of memory addresses where state was provided as routine input.
 proc.compress.1
     popw.local.0
 
@@ -691,19 +998,19 @@ proc.compress.1
     exec.round
 end
 
-# Blake3 2-to-1 hash function, which takes 64 -bytes input and produces 32 -bytes output digest
-#
-# Expected stack state:
-#
-# [msg0, msg1, msg2, msg3, msg4, msg5, msg6, msg7, msg8, msg9, msg10, msg11, msg12, msg13, msg14, msg15]
-#
-# msg`i` -> 32 -bit message word | i ∈ [0, 16)
-#
-# Output stack state:
-#
-# [dig0, dig1, dig2, dig3, dig4, dig5, dig6, dig7]
-#
-# dig`i` -> 32 -bit digest word | i ∈ [0, 8)
+#! Blake3 2-to-1 hash function, which takes 64 -bytes input and produces 32 -bytes output digest
+#!
+#! Expected stack state:
+#!
+#! [msg0, msg1, msg2, msg3, msg4, msg5, msg6, msg7, msg8, msg9, msg10, msg11, msg12, msg13, msg14, msg15]
+#!
+#! msg`i` -> 32 -bit message word | i ∈ [0, 16)
+#!
+#! Output stack state:
+#!
+#! [dig0, dig1, dig2, dig3, dig4, dig5, dig6, dig7]
+#!
+#! dig`i` -> 32 -bit digest word | i ∈ [0, 8)
 export.hash.4
     push.env.locaddr.3
     push.env.locaddr.2
@@ -732,15 +1039,15 @@ export.hash.4
 end
 "),
 // ----- std::crypto::hashes::keccak256 -----------------------------------------------------------
-("std::crypto::hashes::keccak256", "# if stack top has [d, c, b, a], after completion of execution of
-# this procedure stack top should look like [a, b, c, d]
+("std::crypto::hashes::keccak256", "#! if stack top has [d, c, b, a], after completion of execution of
+#! this procedure stack top should look like [a, b, c, d]
 proc.rev_4_elements
     swap
     movup.2
     movup.3
 end
 
-# given four elements of from each of a, b sets, following procedure computes a[i] ^ b[i] ∀ i = [0, 3]
+#! given four elements of from each of a, b sets, following procedure computes a[i] ^ b[i] ∀ i = [0, 3]
 proc.xor_4_elements
     movup.7
     u32checked_xor
@@ -759,9 +1066,9 @@ proc.xor_4_elements
     u32checked_xor
 end
 
-# keccak-p[b, n_r] | b = 1600, n_r = 24, permutation's θ function, which is
-# implemented in terms of 32 -bit word size;
-# see https://github.com/itzmeanjan/merklize-sha/blob/1d35aae9da7fed20127489f362b4bc93242a516c/include/sha3.hpp#L55-L98 for original implementation
+#! keccak-p[b, n_r] | b = 1600, n_r = 24, permutation's θ function, which is
+#! implemented in terms of 32 -bit word size;
+#! see https://github.com/itzmeanjan/merklize-sha/blob/1d35aae9da7fed20127489f362b4bc93242a516c/include/sha3.hpp#L55-L98 for original implementation
 proc.theta.7
     popw.local.0
     popw.local.1
@@ -1808,8 +2115,8 @@ proc.theta.7
     popw.mem
 end
 
-# keccak-p[b, n_r] | b = 1600, n_r = 24, permutation's ρ ( rho ) function, which is
-# implemented in terms of 32 -bit word size; see https://github.com/itzmeanjan/merklize-sha/blob/1d35aae9da7fed20127489f362b4bc93242a516c/include/sha3.hpp#L115-L147
+#! keccak-p[b, n_r] | b = 1600, n_r = 24, permutation's ρ ( rho ) function, which is
+#! implemented in terms of 32 -bit word size; see https://github.com/itzmeanjan/merklize-sha/blob/1d35aae9da7fed20127489f362b4bc93242a516c/include/sha3.hpp#L115-L147
 proc.rho.4
     popw.local.0
     popw.local.1
@@ -2054,8 +2361,8 @@ proc.rho.4
     popw.mem # wrote state[48..50]
 end
 
-# keccak-p[b, n_r] | b = 1600, n_r = 24, permutation's π function, which is
-# implemented in terms of 32 -bit word size; see https://github.com/itzmeanjan/merklize-sha/blob/1d35aae9da7fed20127489f362b4bc93242a516c/include/sha3.hpp#L169-L207
+#! keccak-p[b, n_r] | b = 1600, n_r = 24, permutation's π function, which is
+#! implemented in terms of 32 -bit word size; see https://github.com/itzmeanjan/merklize-sha/blob/1d35aae9da7fed20127489f362b4bc93242a516c/include/sha3.hpp#L169-L207
 proc.pi.17
     popw.local.0
     popw.local.1
@@ -2464,8 +2771,8 @@ proc.pi.17
     dropw
 end
 
-# keccak-p[b, n_r] | b = 1600, n_r = 24, permutation's χ function, which is
-# implemented in terms of 32 -bit word size; see https://github.com/itzmeanjan/merklize-sha/blob/1d35aae9da7fed20127489f362b4bc93242a516c/include/sha3.hpp#L233-L271
+#! keccak-p[b, n_r] | b = 1600, n_r = 24, permutation's χ function, which is
+#! implemented in terms of 32 -bit word size; see https://github.com/itzmeanjan/merklize-sha/blob/1d35aae9da7fed20127489f362b4bc93242a516c/include/sha3.hpp#L233-L271
 proc.chi.7
     popw.local.0
     popw.local.1
@@ -3479,9 +3786,9 @@ proc.chi.7
     popw.mem # write to state[48..50]
 end
 
-# keccak-p[b, n_r] | b = 1600, n_r = 24, permutation's ι ( iota ) function, which is
-# implemented in terms of 32 -bit word size; imagine https://github.com/itzmeanjan/merklize-sha/blob/1d35aae9da7fed20127489f362b4bc93242a516c/include/sha3.hpp#L288-L306
-# invoked with (1u, 0u) as template arguments
+#! keccak-p[b, n_r] | b = 1600, n_r = 24, permutation's ι ( iota ) function, which is
+#! implemented in terms of 32 -bit word size; imagine https://github.com/itzmeanjan/merklize-sha/blob/1d35aae9da7fed20127489f362b4bc93242a516c/include/sha3.hpp#L288-L306
+#! invoked with (1u, 0u) as template arguments
 proc.iota_round_1
     dup
     pushw.mem
@@ -3493,9 +3800,9 @@ proc.iota_round_1
     popw.mem # write to state[0..2]
 end
 
-# keccak-p[b, n_r] | b = 1600, n_r = 24, permutation's ι ( iota ) function, which is
-# implemented in terms of 32 -bit word size; imagine https://github.com/itzmeanjan/merklize-sha/blob/1d35aae9da7fed20127489f362b4bc93242a516c/include/sha3.hpp#L288-L306
-# invoked with (0u, 137u) as template arguments
+#! keccak-p[b, n_r] | b = 1600, n_r = 24, permutation's ι ( iota ) function, which is
+#! implemented in terms of 32 -bit word size; imagine https://github.com/itzmeanjan/merklize-sha/blob/1d35aae9da7fed20127489f362b4bc93242a516c/include/sha3.hpp#L288-L306
+#! invoked with (0u, 137u) as template arguments
 proc.iota_round_2
     dup
     pushw.mem
@@ -3511,9 +3818,9 @@ proc.iota_round_2
     popw.mem # write to state[0..2]
 end
 
-# keccak-p[b, n_r] | b = 1600, n_r = 24, permutation's ι ( iota ) function, which is
-# implemented in terms of 32 -bit word size; imagine https://github.com/itzmeanjan/merklize-sha/blob/1d35aae9da7fed20127489f362b4bc93242a516c/include/sha3.hpp#L288-L306
-# invoked with (0u, 2147483787u) as template arguments
+#! keccak-p[b, n_r] | b = 1600, n_r = 24, permutation's ι ( iota ) function, which is
+#! implemented in terms of 32 -bit word size; imagine https://github.com/itzmeanjan/merklize-sha/blob/1d35aae9da7fed20127489f362b4bc93242a516c/include/sha3.hpp#L288-L306
+#! invoked with (0u, 2147483787u) as template arguments
 proc.iota_round_3
     dup
     pushw.mem
@@ -3529,9 +3836,9 @@ proc.iota_round_3
     popw.mem # write to state[0..2]
 end
 
-# keccak-p[b, n_r] | b = 1600, n_r = 24, permutation's ι ( iota ) function, which is
-# implemented in terms of 32 -bit word size; imagine https://github.com/itzmeanjan/merklize-sha/blob/1d35aae9da7fed20127489f362b4bc93242a516c/include/sha3.hpp#L288-L306
-# invoked with (0u, 2147516544u) as template arguments
+#! keccak-p[b, n_r] | b = 1600, n_r = 24, permutation's ι ( iota ) function, which is
+#! implemented in terms of 32 -bit word size; imagine https://github.com/itzmeanjan/merklize-sha/blob/1d35aae9da7fed20127489f362b4bc93242a516c/include/sha3.hpp#L288-L306
+#! invoked with (0u, 2147516544u) as template arguments
 proc.iota_round_4
     dup
     pushw.mem
@@ -3547,9 +3854,9 @@ proc.iota_round_4
     popw.mem # write to state[0..2]
 end
 
-# keccak-p[b, n_r] | b = 1600, n_r = 24, permutation's ι ( iota ) function, which is
-# implemented in terms of 32 -bit word size; imagine https://github.com/itzmeanjan/merklize-sha/blob/1d35aae9da7fed20127489f362b4bc93242a516c/include/sha3.hpp#L288-L306
-# invoked with (1u, 139u) as template arguments
+#! keccak-p[b, n_r] | b = 1600, n_r = 24, permutation's ι ( iota ) function, which is
+#! implemented in terms of 32 -bit word size; imagine https://github.com/itzmeanjan/merklize-sha/blob/1d35aae9da7fed20127489f362b4bc93242a516c/include/sha3.hpp#L288-L306
+#! invoked with (1u, 139u) as template arguments
 proc.iota_round_5
     dup
     pushw.mem
@@ -3568,9 +3875,9 @@ proc.iota_round_5
     popw.mem # write to state[0..2]
 end
 
-# keccak-p[b, n_r] | b = 1600, n_r = 24, permutation's ι ( iota ) function, which is
-# implemented in terms of 32 -bit word size; imagine https://github.com/itzmeanjan/merklize-sha/blob/1d35aae9da7fed20127489f362b4bc93242a516c/include/sha3.hpp#L288-L306
-# invoked with (1u, 32768u) as template arguments
+#! keccak-p[b, n_r] | b = 1600, n_r = 24, permutation's ι ( iota ) function, which is
+#! implemented in terms of 32 -bit word size; imagine https://github.com/itzmeanjan/merklize-sha/blob/1d35aae9da7fed20127489f362b4bc93242a516c/include/sha3.hpp#L288-L306
+#! invoked with (1u, 32768u) as template arguments
 proc.iota_round_6
     dup
     pushw.mem
@@ -3589,9 +3896,9 @@ proc.iota_round_6
     popw.mem # write to state[0..2]
 end
 
-# keccak-p[b, n_r] | b = 1600, n_r = 24, permutation's ι ( iota ) function, which is
-# implemented in terms of 32 -bit word size; imagine https://github.com/itzmeanjan/merklize-sha/blob/1d35aae9da7fed20127489f362b4bc93242a516c/include/sha3.hpp#L288-L306
-# invoked with (1u, 2147516552u) as template arguments
+#! keccak-p[b, n_r] | b = 1600, n_r = 24, permutation's ι ( iota ) function, which is
+#! implemented in terms of 32 -bit word size; imagine https://github.com/itzmeanjan/merklize-sha/blob/1d35aae9da7fed20127489f362b4bc93242a516c/include/sha3.hpp#L288-L306
+#! invoked with (1u, 2147516552u) as template arguments
 proc.iota_round_7
     dup
     pushw.mem
@@ -3610,9 +3917,9 @@ proc.iota_round_7
     popw.mem # write to state[0..2]
 end
 
-# keccak-p[b, n_r] | b = 1600, n_r = 24, permutation's ι ( iota ) function, which is
-# implemented in terms of 32 -bit word size; imagine https://github.com/itzmeanjan/merklize-sha/blob/1d35aae9da7fed20127489f362b4bc93242a516c/include/sha3.hpp#L288-L306
-# invoked with (1u, 2147483778u) as template arguments
+#! keccak-p[b, n_r] | b = 1600, n_r = 24, permutation's ι ( iota ) function, which is
+#! implemented in terms of 32 -bit word size; imagine https://github.com/itzmeanjan/merklize-sha/blob/1d35aae9da7fed20127489f362b4bc93242a516c/include/sha3.hpp#L288-L306
+#! invoked with (1u, 2147483778u) as template arguments
 proc.iota_round_8
     dup
     pushw.mem
@@ -3631,9 +3938,9 @@ proc.iota_round_8
     popw.mem # write to state[0..2]
 end
 
-# keccak-p[b, n_r] | b = 1600, n_r = 24, permutation's ι ( iota ) function, which is
-# implemented in terms of 32 -bit word size; imagine https://github.com/itzmeanjan/merklize-sha/blob/1d35aae9da7fed20127489f362b4bc93242a516c/include/sha3.hpp#L288-L306
-# invoked with (0u, 11u) as template arguments
+#! keccak-p[b, n_r] | b = 1600, n_r = 24, permutation's ι ( iota ) function, which is
+#! implemented in terms of 32 -bit word size; imagine https://github.com/itzmeanjan/merklize-sha/blob/1d35aae9da7fed20127489f362b4bc93242a516c/include/sha3.hpp#L288-L306
+#! invoked with (0u, 11u) as template arguments
 proc.iota_round_9
     dup
     pushw.mem
@@ -3649,9 +3956,9 @@ proc.iota_round_9
     popw.mem # write to state[0..2]
 end
 
-# keccak-p[b, n_r] | b = 1600, n_r = 24, permutation's ι ( iota ) function, which is
-# implemented in terms of 32 -bit word size; imagine https://github.com/itzmeanjan/merklize-sha/blob/1d35aae9da7fed20127489f362b4bc93242a516c/include/sha3.hpp#L288-L306
-# invoked with (0u, 10u) as template arguments
+#! keccak-p[b, n_r] | b = 1600, n_r = 24, permutation's ι ( iota ) function, which is
+#! implemented in terms of 32 -bit word size; imagine https://github.com/itzmeanjan/merklize-sha/blob/1d35aae9da7fed20127489f362b4bc93242a516c/include/sha3.hpp#L288-L306
+#! invoked with (0u, 10u) as template arguments
 proc.iota_round_10
     dup
     pushw.mem
@@ -3667,9 +3974,9 @@ proc.iota_round_10
     popw.mem # write to state[0..2]
 end
 
-# keccak-p[b, n_r] | b = 1600, n_r = 24, permutation's ι ( iota ) function, which is
-# implemented in terms of 32 -bit word size; imagine https://github.com/itzmeanjan/merklize-sha/blob/1d35aae9da7fed20127489f362b4bc93242a516c/include/sha3.hpp#L288-L306
-# invoked with (1u, 32898u) as template arguments
+#! keccak-p[b, n_r] | b = 1600, n_r = 24, permutation's ι ( iota ) function, which is
+#! implemented in terms of 32 -bit word size; imagine https://github.com/itzmeanjan/merklize-sha/blob/1d35aae9da7fed20127489f362b4bc93242a516c/include/sha3.hpp#L288-L306
+#! invoked with (1u, 32898u) as template arguments
 proc.iota_round_11
     dup
     pushw.mem
@@ -3688,9 +3995,9 @@ proc.iota_round_11
     popw.mem # write to state[0..2]
 end
 
-# keccak-p[b, n_r] | b = 1600, n_r = 24, permutation's ι ( iota ) function, which is
-# implemented in terms of 32 -bit word size; imagine https://github.com/itzmeanjan/merklize-sha/blob/1d35aae9da7fed20127489f362b4bc93242a516c/include/sha3.hpp#L288-L306
-# invoked with (0u, 32771u) as template arguments
+#! keccak-p[b, n_r] | b = 1600, n_r = 24, permutation's ι ( iota ) function, which is
+#! implemented in terms of 32 -bit word size; imagine https://github.com/itzmeanjan/merklize-sha/blob/1d35aae9da7fed20127489f362b4bc93242a516c/include/sha3.hpp#L288-L306
+#! invoked with (0u, 32771u) as template arguments
 proc.iota_round_12
     dup
     pushw.mem
@@ -3706,9 +4013,9 @@ proc.iota_round_12
     popw.mem # write to state[0..2]
 end
 
-# keccak-p[b, n_r] | b = 1600, n_r = 24, permutation's ι ( iota ) function, which is
-# implemented in terms of 32 -bit word size; imagine https://github.com/itzmeanjan/merklize-sha/blob/1d35aae9da7fed20127489f362b4bc93242a516c/include/sha3.hpp#L288-L306
-# invoked with (1u, 32907u) as template arguments
+#! keccak-p[b, n_r] | b = 1600, n_r = 24, permutation's ι ( iota ) function, which is
+#! implemented in terms of 32 -bit word size; imagine https://github.com/itzmeanjan/merklize-sha/blob/1d35aae9da7fed20127489f362b4bc93242a516c/include/sha3.hpp#L288-L306
+#! invoked with (1u, 32907u) as template arguments
 proc.iota_round_13
     dup
     pushw.mem
@@ -3727,9 +4034,9 @@ proc.iota_round_13
     popw.mem # write to state[0..2]
 end
 
-# keccak-p[b, n_r] | b = 1600, n_r = 24, permutation's ι ( iota ) function, which is
-# implemented in terms of 32 -bit word size; imagine https://github.com/itzmeanjan/merklize-sha/blob/1d35aae9da7fed20127489f362b4bc93242a516c/include/sha3.hpp#L288-L306
-# invoked with (1u, 2147483659u) as template arguments
+#! keccak-p[b, n_r] | b = 1600, n_r = 24, permutation's ι ( iota ) function, which is
+#! implemented in terms of 32 -bit word size; imagine https://github.com/itzmeanjan/merklize-sha/blob/1d35aae9da7fed20127489f362b4bc93242a516c/include/sha3.hpp#L288-L306
+#! invoked with (1u, 2147483659u) as template arguments
 proc.iota_round_14
     dup
     pushw.mem
@@ -3748,9 +4055,9 @@ proc.iota_round_14
     popw.mem # write to state[0..2]
 end
 
-# keccak-p[b, n_r] | b = 1600, n_r = 24, permutation's ι ( iota ) function, which is
-# implemented in terms of 32 -bit word size; imagine https://github.com/itzmeanjan/merklize-sha/blob/1d35aae9da7fed20127489f362b4bc93242a516c/include/sha3.hpp#L288-L306
-# invoked with (1u, 2147483786u) as template arguments
+#! keccak-p[b, n_r] | b = 1600, n_r = 24, permutation's ι ( iota ) function, which is
+#! implemented in terms of 32 -bit word size; imagine https://github.com/itzmeanjan/merklize-sha/blob/1d35aae9da7fed20127489f362b4bc93242a516c/include/sha3.hpp#L288-L306
+#! invoked with (1u, 2147483786u) as template arguments
 proc.iota_round_15
     dup
     pushw.mem
@@ -3769,9 +4076,9 @@ proc.iota_round_15
     popw.mem # write to state[0..2]
 end
 
-# keccak-p[b, n_r] | b = 1600, n_r = 24, permutation's ι ( iota ) function, which is
-# implemented in terms of 32 -bit word size; imagine https://github.com/itzmeanjan/merklize-sha/blob/1d35aae9da7fed20127489f362b4bc93242a516c/include/sha3.hpp#L288-L306
-# invoked with (1u, 2147483777u) as template arguments
+#! keccak-p[b, n_r] | b = 1600, n_r = 24, permutation's ι ( iota ) function, which is
+#! implemented in terms of 32 -bit word size; imagine https://github.com/itzmeanjan/merklize-sha/blob/1d35aae9da7fed20127489f362b4bc93242a516c/include/sha3.hpp#L288-L306
+#! invoked with (1u, 2147483777u) as template arguments
 proc.iota_round_16
     dup
     pushw.mem
@@ -3790,9 +4097,9 @@ proc.iota_round_16
     popw.mem # write to state[0..2]
 end
 
-# keccak-p[b, n_r] | b = 1600, n_r = 24, permutation's ι ( iota ) function, which is
-# implemented in terms of 32 -bit word size; imagine https://github.com/itzmeanjan/merklize-sha/blob/1d35aae9da7fed20127489f362b4bc93242a516c/include/sha3.hpp#L288-L306
-# invoked with (0u, 2147483777u) as template arguments
+#! keccak-p[b, n_r] | b = 1600, n_r = 24, permutation's ι ( iota ) function, which is
+#! implemented in terms of 32 -bit word size; imagine https://github.com/itzmeanjan/merklize-sha/blob/1d35aae9da7fed20127489f362b4bc93242a516c/include/sha3.hpp#L288-L306
+#! invoked with (0u, 2147483777u) as template arguments
 proc.iota_round_17
     dup
     pushw.mem
@@ -3808,9 +4115,9 @@ proc.iota_round_17
     popw.mem # write to state[0..2]
 end
 
-# keccak-p[b, n_r] | b = 1600, n_r = 24, permutation's ι ( iota ) function, which is
-# implemented in terms of 32 -bit word size; imagine https://github.com/itzmeanjan/merklize-sha/blob/1d35aae9da7fed20127489f362b4bc93242a516c/include/sha3.hpp#L288-L306
-# invoked with (0u, 2147483656u) as template arguments
+#! keccak-p[b, n_r] | b = 1600, n_r = 24, permutation's ι ( iota ) function, which is
+#! implemented in terms of 32 -bit word size; imagine https://github.com/itzmeanjan/merklize-sha/blob/1d35aae9da7fed20127489f362b4bc93242a516c/include/sha3.hpp#L288-L306
+#! invoked with (0u, 2147483656u) as template arguments
 proc.iota_round_18
     dup
     pushw.mem
@@ -3826,9 +4133,9 @@ proc.iota_round_18
     popw.mem # write to state[0..2]
 end
 
-# keccak-p[b, n_r] | b = 1600, n_r = 24, permutation's ι ( iota ) function, which is
-# implemented in terms of 32 -bit word size; imagine https://github.com/itzmeanjan/merklize-sha/blob/1d35aae9da7fed20127489f362b4bc93242a516c/include/sha3.hpp#L288-L306
-# invoked with (0u, 131u) as template arguments
+#! keccak-p[b, n_r] | b = 1600, n_r = 24, permutation's ι ( iota ) function, which is
+#! implemented in terms of 32 -bit word size; imagine https://github.com/itzmeanjan/merklize-sha/blob/1d35aae9da7fed20127489f362b4bc93242a516c/include/sha3.hpp#L288-L306
+#! invoked with (0u, 131u) as template arguments
 proc.iota_round_19
     dup
     pushw.mem
@@ -3844,9 +4151,9 @@ proc.iota_round_19
     popw.mem # write to state[0..2]
 end
 
-# keccak-p[b, n_r] | b = 1600, n_r = 24, permutation's ι ( iota ) function, which is
-# implemented in terms of 32 -bit word size; imagine https://github.com/itzmeanjan/merklize-sha/blob/1d35aae9da7fed20127489f362b4bc93242a516c/include/sha3.hpp#L288-L306
-# invoked with (0u, 2147516419u) as template arguments
+#! keccak-p[b, n_r] | b = 1600, n_r = 24, permutation's ι ( iota ) function, which is
+#! implemented in terms of 32 -bit word size; imagine https://github.com/itzmeanjan/merklize-sha/blob/1d35aae9da7fed20127489f362b4bc93242a516c/include/sha3.hpp#L288-L306
+#! invoked with (0u, 2147516419u) as template arguments
 proc.iota_round_20
     dup
     pushw.mem
@@ -3862,9 +4169,9 @@ proc.iota_round_20
     popw.mem # write to state[0..2]
 end
 
-# keccak-p[b, n_r] | b = 1600, n_r = 24, permutation's ι ( iota ) function, which is
-# implemented in terms of 32 -bit word size; imagine https://github.com/itzmeanjan/merklize-sha/blob/1d35aae9da7fed20127489f362b4bc93242a516c/include/sha3.hpp#L288-L306
-# invoked with (1u, 2147516552u) as template arguments
+#! keccak-p[b, n_r] | b = 1600, n_r = 24, permutation's ι ( iota ) function, which is
+#! implemented in terms of 32 -bit word size; imagine https://github.com/itzmeanjan/merklize-sha/blob/1d35aae9da7fed20127489f362b4bc93242a516c/include/sha3.hpp#L288-L306
+#! invoked with (1u, 2147516552u) as template arguments
 proc.iota_round_21
     dup
     pushw.mem
@@ -3883,9 +4190,9 @@ proc.iota_round_21
     popw.mem # write to state[0..2]
 end
 
-# keccak-p[b, n_r] | b = 1600, n_r = 24, permutation's ι ( iota ) function, which is
-# implemented in terms of 32 -bit word size; imagine https://github.com/itzmeanjan/merklize-sha/blob/1d35aae9da7fed20127489f362b4bc93242a516c/include/sha3.hpp#L288-L306
-# invoked with (0u, 2147483784u) as template arguments
+#! keccak-p[b, n_r] | b = 1600, n_r = 24, permutation's ι ( iota ) function, which is
+#! implemented in terms of 32 -bit word size; imagine https://github.com/itzmeanjan/merklize-sha/blob/1d35aae9da7fed20127489f362b4bc93242a516c/include/sha3.hpp#L288-L306
+#! invoked with (0u, 2147483784u) as template arguments
 proc.iota_round_22
     dup
     pushw.mem
@@ -3901,9 +4208,9 @@ proc.iota_round_22
     popw.mem # write to state[0..2]
 end
 
-# keccak-p[b, n_r] | b = 1600, n_r = 24, permutation's ι ( iota ) function, which is
-# implemented in terms of 32 -bit word size; imagine https://github.com/itzmeanjan/merklize-sha/blob/1d35aae9da7fed20127489f362b4bc93242a516c/include/sha3.hpp#L288-L306
-# invoked with (1u, 32768u) as template arguments
+#! keccak-p[b, n_r] | b = 1600, n_r = 24, permutation's ι ( iota ) function, which is
+#! implemented in terms of 32 -bit word size; imagine https://github.com/itzmeanjan/merklize-sha/blob/1d35aae9da7fed20127489f362b4bc93242a516c/include/sha3.hpp#L288-L306
+#! invoked with (1u, 32768u) as template arguments
 proc.iota_round_23
     dup
     pushw.mem
@@ -3922,9 +4229,9 @@ proc.iota_round_23
     popw.mem # write to state[0..2]
 end
 
-# keccak-p[b, n_r] | b = 1600, n_r = 24, permutation's ι ( iota ) function, which is
-# implemented in terms of 32 -bit word size; imagine https://github.com/itzmeanjan/merklize-sha/blob/1d35aae9da7fed20127489f362b4bc93242a516c/include/sha3.hpp#L288-L306
-# invoked with (0u, 2147516546u) as template arguments
+#! keccak-p[b, n_r] | b = 1600, n_r = 24, permutation's ι ( iota ) function, which is
+#! implemented in terms of 32 -bit word size; imagine https://github.com/itzmeanjan/merklize-sha/blob/1d35aae9da7fed20127489f362b4bc93242a516c/include/sha3.hpp#L288-L306
+#! invoked with (0u, 2147516546u) as template arguments
 proc.iota_round_24
     dup
     pushw.mem
@@ -3940,14 +4247,14 @@ proc.iota_round_24
     popw.mem # write to state[0..2]
 end
 
-# keccak-p[b, n_r] permutation round, without `iota` function
-# ( all other functions i.e. `theta`, `rho`, `pi`, `chi` are applied in order ) | b = 1600, n_r = 24
-#
-# As `iota` function involves xoring constant factors with first lane of state array ( read state[0, 0] ),
-# specialised implementations are maintained; see above; required to be invoked seperately after completion of
-# this procedure's execution.
-#
-# See https://github.com/itzmeanjan/merklize-sha/blob/1d35aae9da7fed20127489f362b4bc93242a516c/include/sha3.hpp#L325-L340
+#! keccak-p[b, n_r] permutation round, without `iota` function
+#! ( all other functions i.e. `theta`, `rho`, `pi`, `chi` are applied in order ) | b = 1600, n_r = 24
+#!
+#! As `iota` function involves xoring constant factors with first lane of state array ( read state[0, 0] ),
+#! specialised implementations are maintained; see above; required to be invoked seperately after completion of
+#! this procedure's execution.
+#!
+#! See https://github.com/itzmeanjan/merklize-sha/blob/1d35aae9da7fed20127489f362b4bc93242a516c/include/sha3.hpp#L325-L340
 proc.round.4
     storew.local.0
     swapw
@@ -3986,10 +4293,10 @@ proc.round.4
     exec.chi
 end
 
-# keccak-p[1600, 24] permutation, which applies 24 rounds on state array of size  5 x 5 x 64, where each
-# 64 -bit lane is represented in bit interleaved form ( in terms of two 32 -bit words ).
-#
-# See https://github.com/itzmeanjan/merklize-sha/blob/1d35aae9da7fed20127489f362b4bc93242a516c/include/sha3.hpp#L379-L427
+#! keccak-p[1600, 24] permutation, which applies 24 rounds on state array of size  5 x 5 x 64, where each
+#! 64 -bit lane is represented in bit interleaved form ( in terms of two 32 -bit words ).
+#!
+#! See https://github.com/itzmeanjan/merklize-sha/blob/1d35aae9da7fed20127489f362b4bc93242a516c/include/sha3.hpp#L379-L427
 proc.keccak_p.4
     popw.local.0
     popw.local.1
@@ -4405,16 +4712,16 @@ proc.keccak_p.4
     exec.iota_round_24
 end
 
-# given two 32 -bit unsigned integers ( standard form ), representing upper and lower
-# portion of a 64 -bit unsigned integer ( actually a keccak-[1600, 24] lane ),
-# this function converts them into bit interleaved representation, where two 32 -bit
-# unsigned integers ( even portion & then odd portion ) hold bits in even and odd
-# indices of 64 -bit unsigned integer ( remember it's represented in terms of
-# two 32 -bit elements )
-#
-# Read more about bit interleaved representation in section 2.1 of https://keccak.team/files/Keccak-implementation-3.2.pdf
-#
-# See https://github.com/itzmeanjan/merklize-sha/blob/1d35aae9da7fed20127489f362b4bc93242a516c/include/utils.hpp#L123-L149
+#! given two 32 -bit unsigned integers ( standard form ), representing upper and lower
+#! portion of a 64 -bit unsigned integer ( actually a keccak-[1600, 24] lane ),
+#! this function converts them into bit interleaved representation, where two 32 -bit
+#! unsigned integers ( even portion & then odd portion ) hold bits in even and odd
+#! indices of 64 -bit unsigned integer ( remember it's represented in terms of
+#! two 32 -bit elements )
+#!
+#! Read more about bit interleaved representation in section 2.1 of https://keccak.team/files/Keccak-implementation-3.2.pdf
+#!
+#! See https://github.com/itzmeanjan/merklize-sha/blob/1d35aae9da7fed20127489f362b4bc93242a516c/include/utils.hpp#L123-L149
 export.to_bit_interleaved
     dup.1
 
@@ -5110,18 +5417,18 @@ export.to_bit_interleaved
     swap
 end
 
-# given two 32 -bit unsigned integers ( bit interleaved form ), representing even and odd
-# positioned bits of a 64 -bit unsigned integer ( actually a keccak-[1600, 24] lane ),
-# this function converts them into standard representation, where two 32 -bit
-# unsigned integers hold higher ( 32 -bit ) and lower ( 32 -bit ) bits of standard
-# representation of 64 -bit unsigned integer ( remember it's represented in terms of
-# two 32 -bit elements )
-#
-# This function reverts the action done by `to_bit_interleaved` function implemented above.
-#
-# Read more about bit interleaved representation in section 2.1 of https://keccak.team/files/Keccak-implementation-3.2.pdf
-#
-# See https://github.com/itzmeanjan/merklize-sha/blob/1d35aae9da7fed20127489f362b4bc93242a516c/include/utils.hpp#L151-L175
+#! given two 32 -bit unsigned integers ( bit interleaved form ), representing even and odd
+#! positioned bits of a 64 -bit unsigned integer ( actually a keccak-[1600, 24] lane ),
+#! this function converts them into standard representation, where two 32 -bit
+#! unsigned integers hold higher ( 32 -bit ) and lower ( 32 -bit ) bits of standard
+#! representation of 64 -bit unsigned integer ( remember it's represented in terms of
+#! two 32 -bit elements )
+#!
+#! This function reverts the action done by `to_bit_interleaved` function implemented above.
+#!
+#! Read more about bit interleaved representation in section 2.1 of https://keccak.team/files/Keccak-implementation-3.2.pdf
+#!
+#! See https://github.com/itzmeanjan/merklize-sha/blob/1d35aae9da7fed20127489f362b4bc93242a516c/include/utils.hpp#L151-L175
 export.from_bit_interleaved
     dup
 
@@ -5692,13 +5999,13 @@ export.from_bit_interleaved
     u32checked_or
 end
 
-# given 64 -bytes input ( in terms of sixteen u32 elements on stack top ) to 2-to-1
-# keccak256 hash function, this function prepares 5 x 5 x 64 keccak-p[1600, 24] state
-# bit array such that each of twenty five 64 -bit wide lane is represented in bit
-# interleaved form, using two 32 -bit integers. After completion of execution of
-# this function, state array should live in allocated memory ( fifty u32 elements ).
-#
-# See https://github.com/itzmeanjan/merklize-sha/blob/1d35aae9da7fed20127489f362b4bc93242a516c/include/keccak_256.hpp#L73-L153
+#! given 64 -bytes input ( in terms of sixteen u32 elements on stack top ) to 2-to-1
+#! keccak256 hash function, this function prepares 5 x 5 x 64 keccak-p[1600, 24] state
+#! bit array such that each of twenty five 64 -bit wide lane is represented in bit
+#! interleaved form, using two 32 -bit integers. After completion of execution of
+#! this function, state array should live in allocated memory ( fifty u32 elements ).
+#!
+#! See https://github.com/itzmeanjan/merklize-sha/blob/1d35aae9da7fed20127489f362b4bc93242a516c/include/keccak_256.hpp#L73-L153
 proc.to_state_array.4
     popw.local.0
     popw.local.1
@@ -5921,12 +6228,12 @@ proc.to_state_array.4
     popw.mem # write to state[48..50]
 end
 
-# given 32 -bytes digest ( in terms of eight u32 elements on stack top ) in bit interleaved form,
-# this function attempts to convert those into standard representation, where eight u32 elements
-# live on stack top, each pair of them hold higher and lower bits of 64 -bit unsigned
-# integer ( lane of keccak-p[1600, 24] state array )
-#
-# See https://github.com/itzmeanjan/merklize-sha/blob/1d35aae9da7fed20127489f362b4bc93242a516c/include/keccak_256.hpp#L180-L209
+#! given 32 -bytes digest ( in terms of eight u32 elements on stack top ) in bit interleaved form,
+#! this function attempts to convert those into standard representation, where eight u32 elements
+#! live on stack top, each pair of them hold higher and lower bits of 64 -bit unsigned
+#! integer ( lane of keccak-p[1600, 24] state array )
+#!
+#! See https://github.com/itzmeanjan/merklize-sha/blob/1d35aae9da7fed20127489f362b4bc93242a516c/include/keccak_256.hpp#L180-L209
 proc.to_digest
     movup.7
     movup.7
@@ -5969,13 +6276,13 @@ proc.to_digest
     swap
 end
 
-# given 64 -bytes input, in terms of sixteen 32 -bit unsigned integers, where each pair
-# of them holding higher & lower 32 -bits of 64 -bit unsigned integer ( reinterpreted on
-# host CPU from little endian byte array ) respectively, this function computes 32 -bytes
-# keccak256 digest, held on stack top, represented in terms of eight 32 -bit unsigned integers,
-# where each pair of them keeps higher and lower 32 -bits of 64 -bit unsigned integer respectively
-#
-# See https://github.com/itzmeanjan/merklize-sha/blob/1d35aae9da7fed20127489f362b4bc93242a516c/include/keccak_256.hpp#L232-L257
+#! given 64 -bytes input, in terms of sixteen 32 -bit unsigned integers, where each pair
+#! of them holding higher & lower 32 -bits of 64 -bit unsigned integer ( reinterpreted on
+#! host CPU from little endian byte array ) respectively, this function computes 32 -bytes
+#! keccak256 digest, held on stack top, represented in terms of eight 32 -bit unsigned integers,
+#! where each pair of them keeps higher and lower 32 -bits of 64 -bit unsigned integer respectively
+#!
+#! See https://github.com/itzmeanjan/merklize-sha/blob/1d35aae9da7fed20127489f362b4bc93242a516c/include/keccak_256.hpp#L232-L257
 export.hash.13
     push.0.0.0
     push.env.locaddr.12
@@ -6024,10 +6331,10 @@ export.hash.13
 end
 "),
 // ----- std::crypto::hashes::sha256 --------------------------------------------------------------
-("std::crypto::hashes::sha256", "# Given [x, ...] on stack top, this routine computes [y, ...]
-# such that y = σ_0(x), as defined in SHA specification
-#
-# See https://github.com/itzmeanjan/merklize-sha/blob/8a2c006/include/sha2.hpp#L73-L79
+("std::crypto::hashes::sha256", "#! Given [x, ...] on stack top, this routine computes [y, ...]
+#! such that y = σ_0(x), as defined in SHA specification
+#!
+#! See https://github.com/itzmeanjan/merklize-sha/blob/8a2c006/include/sha2.hpp#L73-L79
 proc.small_sigma_0
     dup
     u32unchecked_rotr.7
@@ -6045,10 +6352,10 @@ proc.small_sigma_0
     u32checked_xor
 end
 
-# Given [x, ...] on stack top, this routine computes [y, ...]
-# such that y = σ_1(x), as defined in SHA specification
-#
-# See https://github.com/itzmeanjan/merklize-sha/blob/8a2c006/include/sha2.hpp#L81-L87
+#! Given [x, ...] on stack top, this routine computes [y, ...]
+#! such that y = σ_1(x), as defined in SHA specification
+#!
+#! See https://github.com/itzmeanjan/merklize-sha/blob/8a2c006/include/sha2.hpp#L81-L87
 proc.small_sigma_1
     dup
     u32unchecked_rotr.17
@@ -6066,10 +6373,10 @@ proc.small_sigma_1
     u32checked_xor
 end
 
-# Given [x, ...] on stack top, this routine computes [y, ...]
-# such that y = Σ_0(x), as defined in SHA specification
-#
-# See https://github.com/itzmeanjan/merklize-sha/blob/8a2c006/include/sha2.hpp#L57-L63
+#! Given [x, ...] on stack top, this routine computes [y, ...]
+#! such that y = Σ_0(x), as defined in SHA specification
+#!
+#! See https://github.com/itzmeanjan/merklize-sha/blob/8a2c006/include/sha2.hpp#L57-L63
 proc.cap_sigma_0
     dup
     u32unchecked_rotr.2
@@ -6087,10 +6394,10 @@ proc.cap_sigma_0
     u32checked_xor
 end
 
-# Given [x, ...] on stack top, this routine computes [y, ...]
-# such that y = Σ_1(x), as defined in SHA specification
-#
-# See https://github.com/itzmeanjan/merklize-sha/blob/8a2c006/include/sha2.hpp#L65-L71
+#! Given [x, ...] on stack top, this routine computes [y, ...]
+#! such that y = Σ_1(x), as defined in SHA specification
+#!
+#! See https://github.com/itzmeanjan/merklize-sha/blob/8a2c006/include/sha2.hpp#L65-L71
 proc.cap_sigma_1
     dup
     u32unchecked_rotr.6
@@ -6108,10 +6415,10 @@ proc.cap_sigma_1
     u32checked_xor
 end
 
-# Given [x, y, z, ...] on stack top, this routine computes [o, ...]
-# such that o = ch(x, y, z), as defined in SHA specification
-#
-# See https://github.com/itzmeanjan/merklize-sha/blob/8a2c006/include/sha2.hpp#L37-L45
+#! Given [x, y, z, ...] on stack top, this routine computes [o, ...]
+#! such that o = ch(x, y, z), as defined in SHA specification
+#!
+#! See https://github.com/itzmeanjan/merklize-sha/blob/8a2c006/include/sha2.hpp#L37-L45
 proc.ch
     swap
     dup.1
@@ -6126,10 +6433,10 @@ proc.ch
     u32checked_xor
 end
 
-# Given [x, y, z, ...] on stack top, this routine computes [o, ...]
-# such that o = maj(x, y, z), as defined in SHA specification
-#
-# See https://github.com/itzmeanjan/merklize-sha/blob/8a2c006/include/sha2.hpp#L47-L55
+#! Given [x, y, z, ...] on stack top, this routine computes [o, ...]
+#! such that o = maj(x, y, z), as defined in SHA specification
+#!
+#! See https://github.com/itzmeanjan/merklize-sha/blob/8a2c006/include/sha2.hpp#L47-L55
 proc.maj
     dup.1
     dup.1
@@ -6147,28 +6454,28 @@ proc.maj
     u32checked_xor
 end
 
-# Given [a, b, c, d, ...] on stack top, this routine reverses order of first 
-# four elements on stack top such that final stack state looks like [d, c, b, a, ...]
+#! Given [a, b, c, d, ...] on stack top, this routine reverses order of first 
+#! four elements on stack top such that final stack state looks like [d, c, b, a, ...]
 proc.rev_element_order
     swap
     movup.2
     movup.3
 end
 
-# Given [a, b, c, d, ...] on stack top, this routine computes next message schedule word
-# using following formula
-#
-# t0 = small_sigma_1(a) + b
-# t1 = small_sigma_0(c) + d
-# return t0 + t1
-#
-# If to be computed message schedule word has index i ∈ [16, 64), then 
-# a, b, c, d will have following indices in message schedule
-#
-# a = msg[i - 2]
-# b = msg[i - 7]
-# c = msg[i - 15]
-# d = msg[i - 16]
+#! Given [a, b, c, d, ...] on stack top, this routine computes next message schedule word
+#! using following formula
+#!
+#! t0 = small_sigma_1(a) + b
+#! t1 = small_sigma_0(c) + d
+#! return t0 + t1
+#!
+#! If to be computed message schedule word has index i ∈ [16, 64), then 
+#! a, b, c, d will have following indices in message schedule
+#!
+#! a = msg[i - 2]
+#! b = msg[i - 7]
+#! c = msg[i - 15]
+#! d = msg[i - 16]
 proc.compute_message_schedule_word
     exec.small_sigma_1
     movup.2
@@ -6179,19 +6486,19 @@ proc.compute_message_schedule_word
     u32wrapping_add
 end
 
-# Given eight working variables of SHA256 ( i.e. hash state ), a 32 -bit round constant & 
-# 32 -bit message word on stack top, this routine consumes constant & message word into 
-# hash state.
-#
-# Expected stack state looks like
-#
-# [a, b, c, d, e, f, g, h, CONST_i, WORD_i] | i ∈ [0, 64)
-#
-# After finishing execution, stack looks like
-#
-# [a', b', c', d', e', f', g', h']
-#
-# See https://github.com/itzmeanjan/merklize-sha/blob/8a2c006/include/sha2_256.hpp#L165-L175
+#! Given eight working variables of SHA256 ( i.e. hash state ), a 32 -bit round constant & 
+#! 32 -bit message word on stack top, this routine consumes constant & message word into 
+#! hash state.
+#!
+#! Expected stack state looks like
+#!
+#! [a, b, c, d, e, f, g, h, CONST_i, WORD_i] | i ∈ [0, 64)
+#!
+#! After finishing execution, stack looks like
+#!
+#! [a', b', c', d', e', f', g', h']
+#!
+#! See https://github.com/itzmeanjan/merklize-sha/blob/8a2c006/include/sha2_256.hpp#L165-L175
 proc.consume_message_word
     dup.6
     dup.6
@@ -6229,22 +6536,25 @@ proc.consume_message_word
     u32wrapping_add
 end
 
-# Given 32 -bytes hash state ( in terms of 8 SHA256 words ) and 64 -bytes input 
-# message ( in terms of 16 SHA256 words ) on stack top, this routine computes
-# whole message schedule of 64 message words and consumes them into hash state.
-#
-# Expected stack state:
-#
-# [state0, state1, state2, state3, state4, state5, state6, state7, msg0, msg1, msg2, msg3, msg4, msg5, msg6, msg7, msg8, msg9, msg10, msg11, msg12, msg13, msg14, msg15]
-#
-# Final stack state after completion of execution
-#
-# [state0', state1', state2', state3', state4', state5', state6', state7']
-#
-# Note, each SHA256 word is 32 -bit wide
-#
-# See https://github.com/itzmeanjan/merklize-sha/blob/8a2c006/include/sha2.hpp#L89-L113
-# & https://github.com/itzmeanjan/merklize-sha/blob/8a2c006/include/sha2_256.hpp#L148-L187 ( loop body execution when i = 0 )
+#! Given 32 -bytes hash state ( in terms of 8 SHA256 words ) and 64 -bytes input 
+#! message ( in terms of 16 SHA256 words ) on stack top, this routine computes
+#! whole message schedule of 64 message words and consumes them into hash state.
+#!
+#! Expected stack state:
+#!
+#! [state0, state1, state2, state3, state4, state5, state6, state7, msg0, msg1, msg2, msg3, msg4, msg5, msg6, msg7, msg8, msg9, msg10, msg11, msg12, msg13, msg14, msg15]
+#!
+#! Final stack state after completion of execution
+#!
+#! [a, b, c, d, e, f, g, h]
+#!
+#! i.e. the working variables after 64 rounds; the input hash state is not added to them
+#! ( see `compress` routine ).
+#!
+#! Note, each SHA256 word is 32 -bit wide
+#!
+#! See https://github.com/itzmeanjan/merklize-sha/blob/8a2c006/include/sha2.hpp#L89-L113
+#! & https://github.com/itzmeanjan/merklize-sha/blob/8a2c006/include/sha2_256.hpp#L148-L187 ( loop body execution when i = 0 )
 proc.prepare_message_schedule_and_consume.2
     popw.local.0
     popw.local.1
@@ -7013,67 +7323,29 @@ proc.prepare_message_schedule_and_consume.2
     push.0xc67178f2
     movdn.8
     exec.consume_message_word # consume msg[63]
-
-    push.0x6a09e667
-    u32wrapping_add
-
-    swap
-    push.0xbb67ae85
-    u32wrapping_add
-    swap
-
-    movup.2
-    push.0x3c6ef372
-    u32wrapping_add
-    movdn.2
-
-    movup.3
-    push.0xa54ff53a
-    u32wrapping_add
-    movdn.3
-
-    movup.4
-    push.0x510e527f
-    u32wrapping_add
-    movdn.4
-
-    movup.5
-    push.0x9b05688c
-    u32wrapping_add
-    movdn.5
-
-    movup.6
-    push.0x1f83d9ab
-    u32wrapping_add
-    movdn.6
-
-    movup.7
-    push.0x5be0cd19
-    u32wrapping_add
-    movdn.7
 end
 
-# Given 32 -bytes hash state ( in terms of 8 SHA256 words ) and precomputed message 
-# schedule of padding bytes ( in terms of 64 message words ), this routine consumes
-# that into hash state, leaving final hash state, which is 32 -bytes SHA256 digest.
-#
-# Note, in SHA256 2-to-1 hashing, 64 -bytes are padded, which is processed as second message
-# block ( each SHA256 message block is 64 -bytes wide ). That message block is used for generating 
-# message schedule of 64 SHA256 words. That's exactly what can be precomputed & is consumed here 
-# ( in this routine ) into provided hash state.
-#
-# Expected stack state:
-#
-# [state0, state1, state2, state3, state4, state5, state6, state7, ...]
-#
-# Final stack state after completion of execution
-#
-# [state0', state1', state2', state3', state4', state5', state6', state7']
-#
-# Note, each SHA256 word is 32 -bit wide
-#
-# See https://github.com/itzmeanjan/merklize-sha/blob/8a2c006/include/sha2_256.hpp#L148-L187 ( loop 
-# body execution when i = 1 i.e. consuming padding bytes )
+#! Given 32 -bytes hash state ( in terms of 8 SHA256 words ) and precomputed message 
+#! schedule of padding bytes ( in terms of 64 message words ), this routine consumes
+#! that into hash state, leaving final hash state, which is 32 -bytes SHA256 digest.
+#!
+#! Note, in SHA256 2-to-1 hashing, 64 -bytes are padded, which is processed as second message
+#! block ( each SHA256 message block is 64 -bytes wide ). That message block is used for generating 
+#! message schedule of 64 SHA256 words. That's exactly what can be precomputed & is consumed here 
+#! ( in this routine ) into provided hash state.
+#!
+#! Expected stack state:
+#!
+#! [state0, state1, state2, state3, state4, state5, state6, state7, ...]
+#!
+#! Final stack state after completion of execution
+#!
+#! [state0', state1', state2', state3', state4', state5', state6', state7']
+#!
+#! Note, each SHA256 word is 32 -bit wide
+#!
+#! See https://github.com/itzmeanjan/merklize-sha/blob/8a2c006/include/sha2_256.hpp#L148-L187 ( loop 
+#! body execution when i = 1 i.e. consuming padding bytes )
 proc.consume_padding_message_schedule
     dupw.1
     dupw.1
@@ -7501,39 +7773,1148 @@ proc.consume_padding_message_schedule
     movdn.7
 end
 
-# Given 64 -bytes input, this routine computes 32 -bytes SAH256 digest
-#
-# Expected stack state:
-#
-# [m0, m1, m2, m3, m4, m5, m6, m7, m8, m9, m10, m11, m12, m13, m14, m15] | m[0,16) = 32 -bit word
-#
-# Note, each SHA256 word is 32 -bit wide, so that's how input is expected.
-# If you've 64 -bytes, consider packing 4 consecutive bytes into single word, 
-# maintaining big endian byte order.
-#
-# Final stack state:
-#
-# [dig0, dig1, dig2, dig3, dig4, dig5, dig6, dig7]
-#
-# SHA256 digest is represented in terms of eight 32 -bit words ( big endian byte order ).
-export.hash
-    push.0x5be0cd19.0x1f83d9ab.0x9b05688c.0x510e527f
-    push.0xa54ff53a.0x3c6ef372.0xbb67ae85.0x6a09e667
-
+#! Given 32 -bytes hash state ( in terms of 8 SHA256 words ) and 64 -bytes input
+#! message block ( in terms of 16 SHA256 words ) on stack top, this routine applies
+#! SHA256 compression function, i.e. consumes the message block into hash state.
+#!
+#! Expected stack state:
+#!
+#! [state0, state1, state2, state3, state4, state5, state6, state7, msg0, msg1, msg2, msg3, msg4, msg5, msg6, msg7, msg8, msg9, msg10, msg11, msg12, msg13, msg14, msg15]
+#!
+#! Final stack state after completion of execution
+#!
+#! [state0', state1', state2', state3', state4', state5', state6', state7']
+#!
+#! Note, each SHA256 word is 32 -bit wide
+#!
+#! See https://github.com/itzmeanjan/merklize-sha/blob/8a2c006/include/sha2_256.hpp#L148-L187
+export.compress.2
+    storew.local.0
+    swapw
+    storew.local.1
+    swapw
+
     exec.prepare_message_schedule_and_consume
+
+    pushw.local.0
+    u32wrapping_add_w
+    swapw
+    pushw.local.1
+    u32wrapping_add_w
+    swapw
+end
+
+#! Given 64 -bytes input, this routine computes 32 -bytes SAH256 digest
+#!
+#! Expected stack state:
+#!
+#! [m0, m1, m2, m3, m4, m5, m6, m7, m8, m9, m10, m11, m12, m13, m14, m15] | m[0,16) = 32 -bit word
+#!
+#! Note, each SHA256 word is 32 -bit wide, so that's how input is expected.
+#! If you've 64 -bytes, consider packing 4 consecutive bytes into single word, 
+#! maintaining big endian byte order.
+#!
+#! Final stack state:
+#!
+#! [dig0, dig1, dig2, dig3, dig4, dig5, dig6, dig7]
+#!
+#! SHA256 digest is represented in terms of eight 32 -bit words ( big endian byte order ).
+export.hash
+    push.0x5be0cd19.0x1f83d9ab.0x9b05688c.0x510e527f
+    push.0xa54ff53a.0x3c6ef372.0xbb67ae85.0x6a09e667
+
+    exec.compress
     exec.consume_padding_message_schedule
 end
+
+#! Given [e, i, q, r, ...] on stack top, where e is the SHA256 word at index i of the last
+#! message block, q is the index of the SHA256 word holding the first padding byte & r is the
+#! number of message bytes in that word, this routine computes [e', i + 1, q, r, ...] such that
+#! e' is the word at index i of the padded message block, i.e.
+#!
+#! e' = e, if i < q
+#! e' = first r bytes of e followed by byte 0x80 & zero bytes, if i = q
+#! e' = 0, if i > q
+#!
+#! Note, the message length is not a part of the computed words.
+proc.pad_element
+    dup.1
+    dup.3
+    dup.1
+    dup.1
+    lt
+    movdn.2
+    eq
+
+    if.true
+        drop
+        dup.3
+        dup
+        neq.0
+        if.true
+            # keep the first r bytes of e, i.e. e & (2^32 - 2^(32 - 8r))
+            mul.8
+            push.32
+            swap
+            sub
+            unchecked_pow2
+            dup
+            push.4294967296
+            swap
+            sub
+            movup.2
+            u32checked_and
+
+            # padding byte 0x80 is placed right after the first r bytes, i.e. 2^(31 - 8r)
+            swap
+            div.2
+            add
+        else
+            drop
+            drop
+            push.2147483648
+        end
+    else
+        mul
+    end
+
+    swap
+    add.1
+    swap
+end
+
+#! Given [i, q, r, a, ...] on stack top, where a is the memory address of the last message
+#! block & i is the index of the first SHA256 word of a memory word in that block ( i.e. i is
+#! a multiple of 4 ), this routine loads the memory word at address a + i / 4 and pads its
+#! SHA256 words ( see `pad_element` routine ), such that final stack state looks like
+#!
+#! [m_i, m_(i + 1), m_(i + 2), m_(i + 3), i + 4, q, r, a, ...]
+proc.load_padded_word
+    dup.3
+    dup.1
+    div.4
+    add
+    pushw.mem
+    exec.rev_element_order
+
+    swapw
+    repeat.4
+        movup.4
+        exec.pad_element
+        movdn.7
+    end
+    swapw
+end
+
+#! Given [a, n, ...] on stack top, this routine computes 32 -bytes SHA256 digest of n -bytes
+#! message stored in memory starting at address a.
+#!
+#! Each element of memory holds 4 consecutive bytes of the message packed into a SHA256 word
+#! ( big endian byte order ), and each memory word holds 4 such elements, i.e. byte j of the
+#! message is located in element (j mod 16) / 4 of memory word at address a + j / 16. Unused
+#! bytes of the last SHA256 word of the message are ignored, and so are the words which follow
+#! it in the same 64 -bytes block.
+#!
+#! Final stack state:
+#!
+#! [dig0, dig1, dig2, dig3, dig4, dig5, dig6, dig7, ...]
+#!
+#! SHA256 digest is represented in terms of eight 32 -bit words ( big endian byte order ).
+#!
+#! Fails if n is not a u32 value.
+export.hash_memory.3
+    # local.0 = address of the next message block, local.1 = n
+    pop.local.0
+    dup
+    pop.local.1
+
+    # consume all complete message blocks
+    u32checked_div.64
+    dup
+    pop.local.2
+
+    push.0x5be0cd19.0x1f83d9ab.0x9b05688c.0x510e527f
+    push.0xa54ff53a.0x3c6ef372.0xbb67ae85.0x6a09e667
+
+    movup.8
+    neq.0
+    while.true
+        push.local.0
+        add.3
+        pushw.mem
+        exec.rev_element_order
+        movdnw.2
+
+        push.local.0
+        add.2
+        pushw.mem
+        exec.rev_element_order
+        movdnw.2
+
+        push.local.0
+        add.1
+        pushw.mem
+        exec.rev_element_order
+        movdnw.2
+
+        push.local.0
+        pushw.mem
+        exec.rev_element_order
+        movdnw.2
+
+        exec.compress
+
+        push.local.0
+        add.4
+        pop.local.0
+
+        push.local.2
+        sub.1
+        dup
+        pop.local.2
+        neq.0
+    end
+
+    # pad the remaining (n mod 64) bytes of the message
+    push.local.0
+    push.local.1
+    u32unchecked_mod.64
+    dup
+    u32unchecked_mod.4
+    swap
+    u32unchecked_div.4
+    push.12             # [12, q, r, a, state0, ...]
+
+    exec.load_padded_word
+
+    # if the padded message fits into a single block, message length in bits is placed into
+    # the last two SHA256 words of the block
+    dup.5
+    push.14
+    lt
+    if.true
+        push.local.1
+        mul.8
+        u32split
+        movup.4
+        add
+        movdn.3
+        movup.4
+        add
+        movdn.3
+    end
+    movdnw.3
+
+    sub.8
+    exec.load_padded_word
+    movdnw.3
+
+    sub.8
+    exec.load_padded_word
+    movdnw.3
+
+    sub.8
+    exec.load_padded_word
+    movdnw.3
+
+    dup.1
+    push.14
+    lt
+    pop.local.2
+    dropw
+
+    exec.compress
+
+    # otherwise, message length in bits is placed into one more block
+    push.local.2
+    eq.0
+    if.true
+        push.local.1
+        mul.8
+        u32split
+        push.0
+        push.0
+        movdnw.2
+
+        padw
+        movdnw.2
+        padw
+        movdnw.2
+        padw
+        movdnw.2
+
+        exec.compress
+    end
+end
+"),
+// ----- std::crypto::merkle ----------------------------------------------------------------------
+("std::crypto::merkle", "# Merkle trees in this module are binary trees whose nodes are hashed with the native hash function
+# of the VM (Rescue Prime), i.e., a parent node is computed as rphash of its left and right
+# children. The trees are the same as the ones which can be put into the advice provider (e.g.,
+# via AdviceSet::new_merkle_tree()), and thus the roots computed by the procedures of this module
+# can also be used with mtree_get, mtree_set and mtree_cwm instructions.
+#
+# Leaves of a tree are read from a region of consecutive words in memory: the word at address
+# a + i is the leaf at index i. A Merkle path of a node at depth d is stored in memory as a region
+# of d consecutive words which starts with the sibling of the node and ends with the sibling of
+# the child of the root, i.e., in the same order as returned by AdviceSet::get_path().
+
+#! Computes the root of the Merkle tree whose n leaves are the words at addresses a, a + 1, ...,
+#! a + n - 1. Memory is not modified. Fails if n is not a power of two.
+#!
+#! The tree is built from left to right: the roots of complete subtrees are kept on the stack,
+#! and after the leaf at index i is pushed, the subtrees are merged as many times as there are
+#! trailing zeros in the binary representation of i + 1. Thus, the stack never holds more than
+#! log2(n) + 1 words.
+#! Input: [a, n, ...]
+#! Output: [R, ...]
+export.build_root.3
+    pop.local.0
+    u32assert
+    dup
+    neq.0
+    assert
+    dup
+    dup
+    sub.1
+    u32checked_and
+    assertz
+    pop.local.1
+    push.0
+    pop.local.2
+    push.1
+    while.true
+        push.local.2
+        push.local.0
+        add
+        pushw.mem       # [L, ...], where L is the leaf at index i
+        push.local.2
+        add.1
+        dup
+        pop.local.2
+        dup
+        push.1
+        u32checked_and
+        eq.0
+        while.true
+            movdn.8
+            rphash
+            movup.4
+            u32unchecked_div.2
+            dup
+            push.1
+            u32checked_and
+            eq.0
+        end
+        drop
+        push.local.2
+        push.local.1
+        neq
+    end
+end
+
+#! Computes the root of a Merkle tree of depth d from the node V at index i and the Merkle path
+#! of the node stored at address p. Fails if i is not a u32 value or is not smaller than 2^d.
+#! Input: [p, d, i, V, ...]
+#! Output: [R, ...]
+export.compute_root.2
+    pop.local.0
+    swap
+    u32assert
+    pop.local.1
+    dup
+    neq.0
+    while.true
+        movdn.4
+        push.local.0
+        pushw.mem       # [S, V, d, ...], where S is the sibling of the current node
+        push.local.1
+        push.2
+        u32unchecked_divmod
+        swap
+        pop.local.1
+        # if the current node is the right child, its parent is hash(S, V)
+        if.true
+            swapw
+        end
+        rphash
+        push.local.0
+        add.1
+        pop.local.0
+        movup.4
+        sub.1
+        dup
+        neq.0
+    end
+    drop
+    push.local.1
+    assertz
+end
+
+#! Verifies that the node V is at index i of a Merkle tree of depth d with root R, using the
+#! Merkle path of the node stored at address p. Fails if the path does not open to R, or if i is
+#! not a u32 value or is not smaller than 2^d.
+#! Input: [p, d, i, V, R, ...]
+#! Output: [...]
+export.verify_membership
+    exec.compute_root
+    eqw
+    assert
+    dropw
+    dropw
+end
+"),
+// ----- std::crypto::random_coin -----------------------------------------------------------------
+("std::crypto::random_coin", "# A random coin draws deterministic pseudo-random values from a seed using the Rescue Prime hash
+# function, and can be used as a Fiat-Shamir coin inside the VM. A coin located at address c
+# occupies 2 words of memory:
+# - The word at address c holds the seed S of the coin.
+# - The first element of the word at address c + 1 holds the number of values drawn since the coin
+#   was last seeded.
+#
+# The i-th word drawn after seeding is R_i = hash(S, [i, 0, 0, 0]), where i starts at 1, and
+# reseeding the coin with a word D sets its seed to hash(S, D). Thus, values drawn from a coin are
+# determined by the data the coin was seeded with, and a host can reproduce them via the
+# RandomCoin struct of the standard library.
+
+#! Initializes the coin at address c with the seed S and resets its counter. The seed is
+#! expected to be a commitment to data the drawn values should depend on.
+#! Input: [c, S, ...]
+#! Output: [...]
+export.init
+    dup
+    movdn.5
+    popw.mem
+    push.0
+    swap
+    add.1
+    pop.mem
+end
+
+#! Updates the seed of the coin at address c to hash(S, D), where S is the current seed of the
+#! coin, and resets its counter.
+#! Input: [c, D, ...]
+#! Output: [...]
+export.reseed
+    dup
+    movdn.5
+    pushw.mem
+    swapw
+    rphash
+    dup.4
+    popw.mem
+    push.0
+    swap
+    add.1
+    pop.mem
+end
+
+#! Draws the next word R from the coin at address c.
+#! Input: [c, ...]
+#! Output: [R, ...]
+export.draw_word
+    dup
+    add.1
+    dup
+    push.mem
+    add.1
+    dup
+    movup.2
+    pop.mem
+    swap
+    pushw.mem
+    movup.4
+    push.0.0.0
+    rphash
+end
+
+#! Draws the next word from the coin at address c and returns its top element r.
+#! Input: [c, ...]
+#! Output: [r, ...]
+export.draw
+    exec.draw_word
+    movdn.3
+    drop
+    drop
+    drop
+end
+
+#! Draws the next element from the coin at address c and maps it to an index i in the range
+#! [0, n) by reducing its lower 32 bits modulo n. Fails if n is zero or is not a valid u32 value.
+#! Input: [c, n, ...]
+#! Output: [i, ...]
+export.draw_index
+    exec.draw
+    u32split
+    drop
+    swap
+    u32checked_mod
+end
+"),
+// ----- std::math::ext2 --------------------------------------------------------------------------
+("std::math::ext2", "# An element a = a0 + a1 * x of the quadratic extension field, which is defined by the irreducible
+# polynomial x^2 - x + 2 over the base field, is represented by two base field elements. When
+# placed on the stack, the coefficient a0 is assumed to be deeper in the stack, i.e., the element
+# is positioned on the stack as [a1, a0, ...]. This is the same extension field as the one used
+# by the STARK protocol of the VM.
+
+#! Computes c = a + b.
+#! Input: [b1, b0, a1, a0, ...]
+#! Output: [c1, c0, ...]
+export.add
+    movup.2
+    add
+    movdn.2
+    add
+    swap
+end
+
+#! Computes c = a - b.
+#! Input: [b1, b0, a1, a0, ...]
+#! Output: [c1, c0, ...]
+export.sub
+    swap
+    movup.3
+    swap
+    sub
+    movdn.2
+    sub
+end
+
+#! Computes c = -a.
+#! Input: [a1, a0, ...]
+#! Output: [c1, c0, ...]
+export.neg
+    neg
+    swap
+    neg
+    swap
+end
+
+#! Computes c = a * b as c0 = a0 * b0 - 2 * a1 * b1 and c1 = (a0 + a1) * (b0 + b1) - a0 * b0.
+#! Input: [b1, b0, a1, a0, ...]
+#! Output: [c1, c0, ...]
+export.mul
+    dup.3
+    dup.2
+    mul         # [a0 * b0, b1, b0, a1, a0, ...]
+    dup.4
+    dup.4
+    add
+    dup.3
+    dup.3
+    add
+    mul         # [(a0 + a1) * (b0 + b1), a0 * b0, b1, b0, a1, a0, ...]
+    dup.1
+    sub
+    movdn.5     # [a0 * b0, b1, b0, a1, a0, c1, ...]
+    movup.3
+    movup.2
+    mul
+    mul.2
+    sub         # [c0, b0, a0, c1, ...]
+    movdn.2
+    drop
+    drop
+    swap
+end
+
+#! Computes c = a^(-1). The inverse is read from the advice tape, and the procedure verifies that
+#! a * c = 1.
+#! Input: [a1, a0, ...]
+#! Output: [c1, c0, ...]
+#!
+#! Fails if a = 0.
+export.inv
+    adv.ext2inv
+    push.adv.2
+    dup.1
+    dup.1
+    movup.5
+    movup.5
+    exec.mul
+    assertz
+    assert
+end
+
+#! Computes c = a / b.
+#! Input: [b1, b0, a1, a0, ...]
+#! Output: [c1, c0, ...]
+#!
+#! Fails if b = 0.
+export.div
+    exec.inv
+    exec.mul
+end
+
+#! Computes c = a^e via square-and-multiply, where the exponent e is a u32 value.
+#! Input: [e, a1, a0, ...]
+#! Output: [c1, c0, ...]
+#!
+#! Fails if e is not a u32 value.
+export.exp
+    u32assert
+    push.1
+    push.0
+    movup.2     # [e, r1, r0, a1, a0, ...], where r = 1
+    dup
+    neq.0
+    while.true
+        push.2
+        u32unchecked_divmod
+        if.true
+            movdn.4
+            dup.3
+            dup.3
+            exec.mul
+            movup.4
+        end
+        movup.4
+        movup.4
+        dup.1
+        dup.1
+        exec.mul
+        movdn.4
+        movdn.4
+        dup
+        neq.0
+    end
+    drop
+    movup.2
+    drop
+    movup.2
+    drop
+end
+"),
+// ----- std::math::fields::bls381_fp -------------------------------------------------------------
+("std::math::fields::bls381_fp", "# Arithmetic in the base field of the BLS12-381 curve, i.e., modulo the 381-bit prime
+# p = 0x1a0111ea397fe69a4b1ba7b6434bacd764774b84f38512bf6730d2a0f6b0f6241eabfffeb153ffffb9feffffffffaaab.
+#
+# An element is represented by 12 u32 limbs in little-endian order and is stored in memory as a
+# region of 12 consecutive words, one limb per word: the first element of the word at address
+# a + i holds limb i, and the remaining elements of the word are ignored. Procedures take the
+# addresses of their operands and of the result; the result region may coincide with any of the
+# operand regions. Limbs of the operands are assumed to be u32 values, and the operands of add,
+# sub and mul are assumed to be canonical (i.e., smaller than p), but this is not checked.
+#
+# Reductions of wide values are computed by the host: reduce and mul emit event 4294901762
+# (0xFFFF0002), in response to which the host is expected to inject the quotient and the
+# remainder of division of the 24-limb value at the address at the top of the stack by p into
+# the advice tape (e.g., via HintProvider). The tape is read as [q_0, ..., q_11, r_0, ..., r_11].
+# Both values are verified in the VM, and thus the procedures fail if the host supplies incorrect
+# results.
+
+use.std::mem
+
+# ===== HELPER PROCEDURES =========================================================================
+
+#! Writes the limbs of p into the region at address a.
+#! Input: [a, ...]
+#! Output: [...]
+proc.store_p
+    push.4294945451
+    dup.1
+    pop.mem
+    push.3120496639
+    dup.1
+    add.1
+    pop.mem
+    push.2975072255
+    dup.1
+    add.2
+    pop.mem
+    push.514588670
+    dup.1
+    add.3
+    pop.mem
+    push.4138792484
+    dup.1
+    add.4
+    pop.mem
+    push.1731252896
+    dup.1
+    add.5
+    pop.mem
+    push.4085584575
+    dup.1
+    add.6
+    pop.mem
+    push.1685539716
+    dup.1
+    add.7
+    pop.mem
+    push.1129032919
+    dup.1
+    add.8
+    pop.mem
+    push.1260103606
+    dup.1
+    add.9
+    pop.mem
+    push.964683418
+    dup.1
+    add.10
+    pop.mem
+    push.436277738
+    dup.1
+    add.11
+    pop.mem
+    drop
+end
+
+#! Reads 12 limbs from the advice tape into the region at address a, starting with limb 0. Fails
+#! if any of the limbs is not a u32 value.
+#! Input: [a, ...]
+#! Output: [...]
+proc.read_limbs
+    repeat.12
+        push.adv.1
+        u32assert
+        dup.1
+        pop.mem
+        add.1
+    end
+    drop
+end
+
+#! Computes c = a + b for the 12-limb values at addresses a and b, and writes the low 12 limbs of
+#! the sum into the region at address c.
+#! Input: [a, b, c, ...]
+#! Output: [carry, ...]
+proc.add_limbs
+    push.0
+    push.0          # [i, carry, a, b, c, ...]
+    push.1
+    while.true
+        dup
+        dup.3
+        add
+        push.mem
+        dup.1
+        dup.5
+        add
+        push.mem    # [b_i, a_i, i, carry, a, b, c, ...]
+        movup.3
+        u32overflowing_add3
+        swap        # [c_i, carry, i, a, b, c, ...]
+        dup.2
+        dup.6
+        add
+        pop.mem
+        swap
+        add.1
+        dup
+        neq.12
+    end
+    drop
+    movdn.3
+    drop
+    drop
+    drop
+end
+
+#! Computes c = a - b for the 12-limb values at addresses a and b modulo 2^384, and writes the
+#! result into the region at address c. The returned borrow is 1 if a < b, and 0 otherwise.
+#! Input: [a, b, c, ...]
+#! Output: [borrow, ...]
+proc.sub_limbs
+    push.0
+    push.0          # [i, borrow, a, b, c, ...]
+    push.1
+    while.true
+        dup
+        dup.3
+        add
+        push.mem
+        dup.1
+        dup.5
+        add
+        push.mem    # [b_i, a_i, i, borrow, a, b, c, ...]
+        u32overflowing_sub
+        swap
+        movup.3
+        u32overflowing_sub
+        movup.2
+        add
+        swap        # [c_i, borrow, i, a, b, c, ...]
+        dup.2
+        dup.6
+        add
+        pop.mem
+        swap
+        add.1
+        dup
+        neq.12
+    end
+    drop
+    movdn.3
+    drop
+    drop
+    drop
+end
+
+#! Computes c = c + a * b, where a and b are the 12-limb values at addresses a and b, and c is the
+#! 24-limb value at address c whose low 12 limbs are added to the product; the high 12 limbs of c
+#! are overwritten, and thus need not be initialized.
+#! Input: [a, b, c, ...]
+#! Output: [...]
+proc.mul_wide.3
+    pop.local.0
+    pop.local.1
+    pop.local.2
+    push.0          # [i, ...]
+    push.1
+    while.true
+        push.0
+        push.0      # [j, carry, i, ...]
+        push.1
+        while.true
+            dup.2
+            push.local.0
+            add
+            push.mem
+            dup.1
+            push.local.1
+            add
+            push.mem
+            dup.2
+            dup.5
+            add
+            push.local.2
+            add     # [addr, b_j, a_i, j, carry, i, ...], where addr is the address of c_(i+j)
+            dup
+            push.mem
+            movdn.3
+            movdn.3
+            u32overflowing_madd
+            swap
+            movup.4
+            u32overflowing_add
+            movup.2
+            add     # [carry, c_(i+j), addr, j, i, ...]
+            swap
+            movup.2
+            pop.mem
+            swap
+            add.1
+            dup
+            neq.12
+        end
+        drop
+        dup.1
+        push.local.2
+        add
+        add.12
+        pop.mem
+        add.1
+        dup
+        neq.12
+    end
+    drop
+end
+
+#! Asserts that the n-limb values at addresses a and b are equal.
+#! Input: [a, b, n, ...]
+#! Output: [...]
+proc.assert_eq_limbs
+    dup.2
+    neq.0
+    while.true
+        dup
+        push.mem
+        dup.2
+        push.mem
+        assert_eq
+        add.1
+        swap
+        add.1
+        swap
+        movup.2
+        sub.1
+        dup
+        movdn.3
+        neq.0
+    end
+    drop
+    drop
+    drop
+end
+
+#! Reduces the 24-limb value t at address t modulo p, and writes the result into the region at
+#! address c. The quotient and the remainder are supplied by the host, and the procedure verifies
+#! that t = q * p + r and r < p. The value t must be smaller than 2^384 * p.
+#! Input: [t, c, ...]
+#! Output: [...]
+proc.reduce_wide.50
+    emit.4294901762     # request the quotient and the remainder from the host
+    pop.local.48
+    pop.local.49
+    push.env.locaddr.11
+    exec.read_limbs
+    push.local.49
+    exec.read_limbs
+    push.env.locaddr.23
+    exec.store_p
+    # r < p iff r - p borrows
+    push.env.locaddr.47
+    push.env.locaddr.23
+    push.local.49
+    exec.sub_limbs
+    assert
+    # check that r + q * p = t
+    push.12
+    push.env.locaddr.47
+    push.local.49
+    exec.mem::memcopy
+    push.env.locaddr.47
+    push.env.locaddr.23
+    push.env.locaddr.11
+    exec.mul_wide
+    push.24
+    push.local.48
+    push.env.locaddr.47
+    exec.assert_eq_limbs
+end
+
+# ===== FIELD OPERATIONS ==========================================================================
+
+#! Computes c = a + b mod p.
+#! Input: [a, b, c, ...]
+#! Output: [...]
+export.add.37
+    movup.2
+    pop.local.36
+    push.env.locaddr.11
+    movdn.2
+    exec.add_limbs
+    push.env.locaddr.23
+    exec.store_p
+    push.env.locaddr.35
+    push.env.locaddr.23
+    push.env.locaddr.11
+    exec.sub_limbs
+    # the sum is reduced if it overflows 384 bits or is not smaller than p
+    not
+    or
+    if.true
+        push.env.locaddr.35
+    else
+        push.env.locaddr.11
+    end
+    push.12
+    push.local.36
+    movup.2
+    exec.mem::memcopy
+end
+
+#! Computes c = a - b mod p.
+#! Input: [a, b, c, ...]
+#! Output: [...]
+export.sub.12
+    dup.2
+    movdn.3
+    exec.sub_limbs
+    if.true
+        push.env.locaddr.11
+        exec.store_p
+        dup
+        push.env.locaddr.11
+        swap
+        exec.add_limbs
+        drop
+    else
+        drop
+    end
+end
+
+#! Computes c = a * b mod p.
+#! Input: [a, b, c, ...]
+#! Output: [...]
+export.mul.24
+    padw
+    push.12
+    push.env.locaddr.23
+    exec.mem::memset
+    push.env.locaddr.23
+    movdn.2
+    exec.mul_wide
+    push.env.locaddr.23
+    exec.reduce_wide
+end
+
+#! Computes c = a mod p for an arbitrary 12-limb value a.
+#! Input: [a, c, ...]
+#! Output: [...]
+export.reduce.24
+    push.12
+    push.env.locaddr.23
+    movup.2
+    exec.mem::memcopy
+    padw
+    push.12
+    push.env.locaddr.11
+    exec.mem::memset
+    push.env.locaddr.23
+    exec.reduce_wide
+end
+"),
+// ----- std::math::poly --------------------------------------------------------------------------
+("std::math::poly", "# A polynomial of degree smaller than n is stored in memory as a region of n consecutive words,
+# one coefficient per word: the first element of the word at address p + i holds the coefficient
+# of x^i, and the remaining elements of the word are ignored. Thus, the number of coefficients n
+# need not be a power of two.
+#
+# Number theoretic transforms in this module evaluate polynomials over the multiplicative subgroup
+# generated by an n-th root of unity w, which exists for every n dividing 2^64 - 2^32 (e.g., any
+# power of two up to 2^32, as well as 3, 5, 6, 12 etc.). The transforms are computed directly
+# rather than via FFT, and thus require O(n^2) field multiplications.
+
+#! Evaluates the polynomial with n coefficients at address p at point x.
+#! Input: [p, n, x, ...]
+#! Output: [y, ...]
+export.eval
+    push.0
+    movdn.3
+    dup.1
+    add
+    swap
+    dup
+    neq.0
+    while.true
+        swap
+        sub.1
+        dup
+        push.mem
+        movup.4
+        dup.4
+        mul
+        add
+        movdn.3
+        swap
+        sub.1
+        dup
+        neq.0
+    end
+    drop
+    drop
+    drop
+end
+
+#! Multiplies the n elements at address a by the n elements at address b pointwise, and writes
+#! the products into the n words at address c. The region at c may coincide with the region at a
+#! or b.
+#! Input: [a, b, c, n, ...]
+#! Output: [...]
+export.mul_pointwise
+    dup.3
+    neq.0
+    while.true
+        dup
+        push.mem
+        dup.2
+        push.mem
+        mul
+        dup.3
+        pop.mem
+        add.1
+        swap
+        add.1
+        swap
+        movup.2
+        add.1
+        movdn.2
+        movup.3
+        sub.1
+        dup
+        movdn.4
+        neq.0
+    end
+    dropw
+end
+
+#! Computes the forward NTT of the polynomial with n coefficients at address p, i.e., evaluates
+#! the polynomial at points w^0, w^1, ..., w^(n - 1), where w is an n-th root of unity, and writes
+#! the evaluations into the n words at address q. The regions at p and q must not overlap.
+#! Input: [p, q, n, w, ...]
+#! Output: [...]
+export.ntt
+    push.1
+    movdn.4
+    dup.2
+    dup
+    neq.0
+    while.true
+        dup.5
+        dup.4
+        dup.3
+        exec.eval
+        dup.3
+        pop.mem
+        movup.2
+        add.1
+        movdn.2
+        movup.5
+        dup.5
+        mul
+        movdn.5
+        sub.1
+        dup
+        neq.0
+    end
+    dropw
+    drop
+    drop
+end
+
+#! Computes the inverse NTT of the n evaluations at address p, i.e., interpolates the polynomial
+#! which evaluates to them at points w^0, w^1, ..., w^(n - 1), where w is an n-th root of unity,
+#! and writes its n coefficients into the n words at address q. The regions at p and q must not
+#! overlap, and n must not be zero.
+#! Input: [p, q, n, w, ...]
+#! Output: [...]
+export.intt
+    movup.3
+    inv
+    movdn.3
+    dup.2
+    dup.2
+    movdn.5
+    movdn.5
+    exec.ntt
+    dup.1
+    inv
+    movdn.2
+    dup.1
+    neq.0
+    while.true
+        dup
+        push.mem
+        dup.3
+        mul
+        dup.1
+        pop.mem
+        add.1
+        swap
+        sub.1
+        dup
+        movdn.2
+        neq.0
+    end
+    drop
+    drop
+    drop
+end
 "),
 // ----- std::math::secp256k1 ---------------------------------------------------------------------
-("std::math::secp256k1", "# Given [b, c, a, carry] on stack top, following function computes
-#
-#  tmp = a + (b * c) + carry
-#  hi = tmp >> 32
-#  lo = tmp & 0xffff_ffff
-#  return (hi, lo)
-#
-# At end of execution of this function, stack top should look like [hi, lo]
-# See https://github.com/itzmeanjan/secp256k1/blob/ec3652afe8ed72b29b0e39273a876a898316fb9a/utils.py#L75-L80
+("std::math::secp256k1", "#! Given [b, c, a, carry] on stack top, following function computes
+#!
+#!  tmp = a + (b * c) + carry
+#!  hi = tmp >> 32
+#!  lo = tmp & 0xffff_ffff
+#!  return (hi, lo)
+#!
+#! At end of execution of this function, stack top should look like [hi, lo]
+#! See https://github.com/itzmeanjan/secp256k1/blob/ec3652afe8ed72b29b0e39273a876a898316fb9a/utils.py#L75-L80
 proc.mac
   u32overflowing_madd
 
@@ -7544,31 +8925,31 @@ proc.mac
   add
 end
 
-# Given [a, b, borrow] on stack top, following function computes
-#
-#  tmp = a - (b + borrow)
-#  hi = tmp >> 32
-#  lo = tmp & 0xffff_ffff
-#  return (hi, lo)
-#
-# At end of execution of this function, stack top should look like [hi, lo]
-# See https://github.com/itzmeanjan/secp256k1/blob/ec3652afe8ed72b29b0e39273a876a898316fb9a/utils.py#L83-L89
+#! Given [a, b, borrow] on stack top, following function computes
+#!
+#!  tmp = a - (b + borrow)
+#!  hi = tmp >> 32
+#!  lo = tmp & 0xffff_ffff
+#!  return (hi, lo)
+#!
+#! At end of execution of this function, stack top should look like [hi, lo]
+#! See https://github.com/itzmeanjan/secp256k1/blob/ec3652afe8ed72b29b0e39273a876a898316fb9a/utils.py#L83-L89
 proc.sbb
   movdn.2
   add
   u32overflowing_sub
 end
 
-# Given a secp256k1 field element in radix-2^32 representation and 32 -bit unsigned integer,
-# this routine computes a 288 -bit number.
-#
-# Input via stack is expected in this form
-#
-# [a0, a1, a2, a3, a4, a5, a6, a7, b] | a[0..8] -> 256 -bit number, b = 32 -bit number
-#
-# Computed output looks like below, on stack
-#
-# [carry, b7, b6, b5, b4, b3, b2, b1, b0]
+#! Given a secp256k1 field element in radix-2^32 representation and 32 -bit unsigned integer,
+#! this routine computes a 288 -bit number.
+#!
+#! Input via stack is expected in this form
+#!
+#! [a0, a1, a2, a3, a4, a5, a6, a7, b] | a[0..8] -> 256 -bit number, b = 32 -bit number
+#!
+#! Computed output looks like below, on stack
+#!
+#! [carry, b7, b6, b5, b4, b3, b2, b1, b0]
 proc.u256xu32
   movup.8
   
@@ -7606,16 +8987,16 @@ proc.u256xu32
   u32overflowing_madd
 end
 
-# Given a 288 -bit number and 256 -bit number on stack ( in order ), this routine
-# computes a 288 -bit number
-#
-# Expected stack state during routine invocation
-#
-# [carry, b7, b6, b5, b4, b3, b2, b1, b0, c0, c1, c2, c3, c4, c5, c6, c7]
-#
-# While after execution of this routine, stack should look like
-#
-# [d0, d1, d2, d3, d4, d5, d6, d7, carry]
+#! Given a 288 -bit number and 256 -bit number on stack ( in order ), this routine
+#! computes a 288 -bit number
+#!
+#! Expected stack state during routine invocation
+#!
+#! [carry, b7, b6, b5, b4, b3, b2, b1, b0, c0, c1, c2, c3, c4, c5, c6, c7]
+#!
+#! While after execution of this routine, stack should look like
+#!
+#! [d0, d1, d2, d3, d4, d5, d6, d7, carry]
 proc.u288_add_u256
   swapw
   movupw.2
@@ -7673,17 +9054,17 @@ proc.u288_add_u256
   movup.8
 end
 
-# Given [c0, c1, c2, c3, c4, c5, c6, c7, c8, pc] on stack top,
-# this function attempts to reduce 288 -bit number to 256 -bit number
-# along with carry, using montgomery reduction method
-#
-# In stack top content c[0..9] i.e. first 9 elements, holding 288 -bit
-# number. Stack element `pc` ( at stack[9] ) is previous reduction round's
-# carry ( for first reduction round, it'll be set to 0 ).
-#
-# After finishing execution of this function, stack top should look like
-#
-# [c0, c1, c2, c3, c4, c5, c6, c7, pc] | pc = next round's carry
+#! Given [c0, c1, c2, c3, c4, c5, c6, c7, c8, pc] on stack top,
+#! this function attempts to reduce 288 -bit number to 256 -bit number
+#! along with carry, using montgomery reduction method
+#!
+#! In stack top content c[0..9] i.e. first 9 elements, holding 288 -bit
+#! number. Stack element `pc` ( at stack[9] ) is previous reduction round's
+#! carry ( for first reduction round, it'll be set to 0 ).
+#!
+#! After finishing execution of this function, stack top should look like
+#!
+#! [c0, c1, c2, c3, c4, c5, c6, c7, pc] | pc = next round's carry
 proc.u288_reduce
   dup
   push.3525653809
@@ -7749,22 +9130,22 @@ proc.u288_reduce
   movup.8
 end
 
-# Given two 256 -bit numbers on stack, where each number is represented in
-# radix-2^32 form ( i.e. each number having eight 32 -bit limbs ), following function
-# computes modular multiplication of those two operands, computing 256 -bit result.
-#
-# Stack expected as below, holding input
-#
-# [a0, a1, a2, a3, a4, a5, a6, a7, b0, b1, b2, b3, b4, b5, b6, b7] | a[0..8], b[0..8] are 256 -bit numbers
-#
-# After finishing execution of this function, stack should look like
-#
-# [c0, c1, c2, c3, c4, c5, c6, c7] | c[0..8] is a 256 -bit number
-#
-# Note, for computing modular multiplication of a[0..8] & b[0..8],
-# school book multiplication equipped with montgomery reduction technique
-# is used, which is why a[0..8], b[0..8] are expected to be in montgomery form,
-# while computed c[0..8] will also be in montgomery form.
+#! Given two 256 -bit numbers on stack, where each number is represented in
+#! radix-2^32 form ( i.e. each number having eight 32 -bit limbs ), following function
+#! computes modular multiplication of those two operands, computing 256 -bit result.
+#!
+#! Stack expected as below, holding input
+#!
+#! [a0, a1, a2, a3, a4, a5, a6, a7, b0, b1, b2, b3, b4, b5, b6, b7] | a[0..8], b[0..8] are 256 -bit numbers
+#!
+#! After finishing execution of this function, stack should look like
+#!
+#! [c0, c1, c2, c3, c4, c5, c6, c7] | c[0..8] is a 256 -bit number
+#!
+#! Note, for computing modular multiplication of a[0..8] & b[0..8],
+#! school book multiplication equipped with montgomery reduction technique
+#! is used, which is why a[0..8], b[0..8] are expected to be in montgomery form,
+#! while computed c[0..8] will also be in montgomery form.
 export.u256_mod_mul.2
   storew.local.0
   swapw
@@ -7856,19 +9237,19 @@ export.u256_mod_mul.2
   drop
 end
 
-# Given two 256 -bit numbers on stack, where each number is represented in
-# radix-2^32 form ( i.e. each number having eight 32 -bit limbs ), following function
-# computes modular addition of those two operands, in secp256k1 prime field.
-#
-# Stack expected as below, holding input
-#
-# [a0, a1, a2, a3, a4, a5, a6, a7, b0, b1, b2, b3, b4, b5, b6, b7] | a[0..8], b[0..8] are 256 -bit numbers
-#
-# After finishing execution of this function, stack should look like
-#
-# [c0, c1, c2, c3, c4, c5, c6, c7] | c[0..8] is a 256 -bit number
-#
-# This implementation takes inspiration from https://gist.github.com/itzmeanjan/d4853347dfdfa853993f5ea059824de6#file-test_montgomery_arithmetic-py-L236-L256
+#! Given two 256 -bit numbers on stack, where each number is represented in
+#! radix-2^32 form ( i.e. each number having eight 32 -bit limbs ), following function
+#! computes modular addition of those two operands, in secp256k1 prime field.
+#!
+#! Stack expected as below, holding input
+#!
+#! [a0, a1, a2, a3, a4, a5, a6, a7, b0, b1, b2, b3, b4, b5, b6, b7] | a[0..8], b[0..8] are 256 -bit numbers
+#!
+#! After finishing execution of this function, stack should look like
+#!
+#! [c0, c1, c2, c3, c4, c5, c6, c7] | c[0..8] is a 256 -bit number
+#!
+#! This implementation takes inspiration from https://gist.github.com/itzmeanjan/d4853347dfdfa853993f5ea059824de6#file-test_montgomery_arithmetic-py-L236-L256
 export.u256_mod_add
   movupw.2
 
@@ -7920,23 +9301,23 @@ export.u256_mod_add
   movup.5
   movup.6
   movup.7
-  movup.6
-  movup.7
-end
-
-# Given a secp256k1 field element ( say `a` ) on stack, represented in Montgomery form 
-# ( i.e. number having eight 32 -bit limbs ), following function negates it to
-# field element `a'` | a' + a = 0
-#
-# Stack expected as below, holding input
-#
-# [a0, a1, a2, a3, a4, a5, a6, a7] | a[0..8] is a secp256k1 field element
-#
-# After finishing execution of this function, stack should look like
-#
-# [c0, c1, c2, c3, c4, c5, c6, c7] | c[0..8] is a secp256k1 field element
-#
-# See https://github.com/itzmeanjan/secp256k1/blob/ec3652afe8ed72b29b0e39273a876a898316fb9a/field.py#L77-L95
+  movup.6
+  movup.7
+end
+
+#! Given a secp256k1 field element ( say `a` ) on stack, represented in Montgomery form 
+#! ( i.e. number having eight 32 -bit limbs ), following function negates it to
+#! field element `a'` | a' + a = 0
+#!
+#! Stack expected as below, holding input
+#!
+#! [a0, a1, a2, a3, a4, a5, a6, a7] | a[0..8] is a secp256k1 field element
+#!
+#! After finishing execution of this function, stack should look like
+#!
+#! [c0, c1, c2, c3, c4, c5, c6, c7] | c[0..8] is a secp256k1 field element
+#!
+#! See https://github.com/itzmeanjan/secp256k1/blob/ec3652afe8ed72b29b0e39273a876a898316fb9a/field.py#L77-L95
 export.u256_mod_neg
   push.0
   swap
@@ -7982,19 +9363,19 @@ export.u256_mod_neg
   movup.7
 end
 
-# Given two secp256k1 field elements, say a, b, ( represented in Montgomery form, each number having 
-# eight 32 -bit limbs ) on stack, following function computes modular subtraction of those 
-# two operands c = a + (-b) = a - b
-#
-# Stack expected as below, holding input
-#
-# [a0, a1, a2, a3, a4, a5, a6, a7, b0, b1, b2, b3, b4, b5, b6, b7] | a[0..8], b[0..8] are secp256k1 field elements
-#
-# After finishing execution of this function, stack should look like
-#
-# [c0, c1, c2, c3, c4, c5, c6, c7] | c[0..8] is a secp256k1 field element
-#
-# See https://github.com/itzmeanjan/secp256k1/blob/ec3652afe8ed72b29b0e39273a876a898316fb9a/field.py#L97-L101
+#! Given two secp256k1 field elements, say a, b, ( represented in Montgomery form, each number having 
+#! eight 32 -bit limbs ) on stack, following function computes modular subtraction of those 
+#! two operands c = a + (-b) = a - b
+#!
+#! Stack expected as below, holding input
+#!
+#! [a0, a1, a2, a3, a4, a5, a6, a7, b0, b1, b2, b3, b4, b5, b6, b7] | a[0..8], b[0..8] are secp256k1 field elements
+#!
+#! After finishing execution of this function, stack should look like
+#!
+#! [c0, c1, c2, c3, c4, c5, c6, c7] | c[0..8] is a secp256k1 field element
+#!
+#! See https://github.com/itzmeanjan/secp256k1/blob/ec3652afe8ed72b29b0e39273a876a898316fb9a/field.py#L97-L101
 export.u256_mod_sub
   movupw.3
   movupw.3
@@ -8003,19 +9384,19 @@ export.u256_mod_sub
   exec.u256_mod_add
 end
 
-# Given a 256 -bit number on stack, represented in radix-2^32 
-# form i.e. eight 32 -bit limbs, this routine computes Montgomery
-# representation of provided radix-2^32 number.
-#
-# - u256 radix-2^32 form input expected on stack as
-#
-#  [a0, a1, a2, a3, a4, a5, a6, a7]
-#
-# - u256 montgomery form output on stack
-#
-# [a0`, a1`, a2`, a3`, a4`, a5`, a6`, a7`]
-#
-# See section 2.2 of https://eprint.iacr.org/2017/1057.pdf
+#! Given a 256 -bit number on stack, represented in radix-2^32 
+#! form i.e. eight 32 -bit limbs, this routine computes Montgomery
+#! representation of provided radix-2^32 number.
+#!
+#! - u256 radix-2^32 form input expected on stack as
+#!
+#!  [a0, a1, a2, a3, a4, a5, a6, a7]
+#!
+#! - u256 montgomery form output on stack
+#!
+#! [a0`, a1`, a2`, a3`, a4`, a5`, a6`, a7`]
+#!
+#! See section 2.2 of https://eprint.iacr.org/2017/1057.pdf
 export.to_mont
   push.0.0.0.0
   push.0.1.1954.954529 # pushed R2's radix-2^32 form;
@@ -8024,19 +9405,19 @@ export.to_mont
   exec.u256_mod_mul
 end
 
-# Given a 256 -bit number on stack, represented in Montgomery 
-# form i.e. eight 32 -bit limbs, this routine computes radix-2^32
-# representation of provided u256 number.
-#
-# - u256 montgomery form input on stack expected
-#
-#  [a0, a1, a2, a3, a4, a5, a6, a7]
-#
-# - u256 radix-2^32 form output on stack as
-#
-# [a0`, a1`, a2`, a3`, a4`, a5`, a6`, a7`]
-#
-# See section 2.2 of https://eprint.iacr.org/2017/1057.pdf
+#! Given a 256 -bit number on stack, represented in Montgomery 
+#! form i.e. eight 32 -bit limbs, this routine computes radix-2^32
+#! representation of provided u256 number.
+#!
+#! - u256 montgomery form input on stack expected
+#!
+#!  [a0, a1, a2, a3, a4, a5, a6, a7]
+#!
+#! - u256 radix-2^32 form output on stack as
+#!
+#! [a0`, a1`, a2`, a3`, a4`, a5`, a6`, a7`]
+#!
+#! See section 2.2 of https://eprint.iacr.org/2017/1057.pdf
 export.from_mont
   push.0.0.0.0
   push.0.0.0.1 # pushed 1's radix-2^32 form;
@@ -8045,41 +9426,41 @@ export.from_mont
   exec.u256_mod_mul
 end
 
-# Given a secp256k1 point in projective coordinate system ( i.e. with x, y, z -coordinates
-# as secp256k1 prime field elements, represented in Montgomery form ), this routine adds 
-# that point with self i.e. does point doubling on elliptic curve, using exception-free 
-# doubling formula from algorithm 9 of https://eprint.iacr.org/2015/1060.pdf, while 
-# following prototype implementation https://github.com/itzmeanjan/secp256k1/blob/ec3652a/point.py#L131-L165
-# 
-# Input:
-#
-# 12 memory addresses on stack such that first 6 memory addresses are for input point &
-# last 6 are for storing resulting point.
-#
-# First 6 addresses hold input elliptic curve point's x, y, z -coordinates, where each coordinate
-# is represented in Montgomery form, as eight 32 -bit limbs.
-#
-# Similarly, last 6 addresses hold resulting (doubled) point's x, y, z -coordinates, where each
-# coordinate is represented in Montgomery form, as eight 32 -bit limbs. Note, this is where
-# output will be written, so called is expected to read doubled point from last 6 memory addresses.
-#
-# Expected stack during invocation of this routine:
-#
-#   [x_addr[0..4], x_addr[4..8], y_addr[0..4], y_addr[4..8], z_addr[0..4], z_addr[4..8], 
-#     x3_addr[0..4], x3_addr[4..8], y3_addr[0..4], y3_addr[4..8], z3_addr[0..4], z3_addr[4..8]]
-#
-# Note, (X, Y, Z)    => input point
-#       (X3, Y3, Z3) => output point
-#
-# Output:
-#
-# Last 6 memory addresses of 12 memory addresses which were provided during invocation, where resulting doubled
-# point is kept in similar form. For seeing X3, Y3, Z3 -coordinates of doubled point, one needs to read from
-# those 6 memory addresses.
-#
-# Stack at end of execution of routine looks like
-#
-#   [x3_addr[0..4], x3_addr[4..8], y3_addr[0..4], y3_addr[4..8], z3_addr[0..4], z3_addr[4..8]]
+#! Given a secp256k1 point in projective coordinate system ( i.e. with x, y, z -coordinates
+#! as secp256k1 prime field elements, represented in Montgomery form ), this routine adds 
+#! that point with self i.e. does point doubling on elliptic curve, using exception-free 
+#! doubling formula from algorithm 9 of https://eprint.iacr.org/2015/1060.pdf, while 
+#! following prototype implementation https://github.com/itzmeanjan/secp256k1/blob/ec3652a/point.py#L131-L165
+#! 
+#! Input:
+#!
+#! 12 memory addresses on stack such that first 6 memory addresses are for input point &
+#! last 6 are for storing resulting point.
+#!
+#! First 6 addresses hold input elliptic curve point's x, y, z -coordinates, where each coordinate
+#! is represented in Montgomery form, as eight 32 -bit limbs.
+#!
+#! Similarly, last 6 addresses hold resulting (doubled) point's x, y, z -coordinates, where each
+#! coordinate is represented in Montgomery form, as eight 32 -bit limbs. Note, this is where
+#! output will be written, so called is expected to read doubled point from last 6 memory addresses.
+#!
+#! Expected stack during invocation of this routine:
+#!
+#!   [x_addr[0..4], x_addr[4..8], y_addr[0..4], y_addr[4..8], z_addr[0..4], z_addr[4..8], 
+#!     x3_addr[0..4], x3_addr[4..8], y3_addr[0..4], y3_addr[4..8], z3_addr[0..4], z3_addr[4..8]]
+#!
+#! Note, (X, Y, Z)    => input point
+#!       (X3, Y3, Z3) => output point
+#!
+#! Output:
+#!
+#! Last 6 memory addresses of 12 memory addresses which were provided during invocation, where resulting doubled
+#! point is kept in similar form. For seeing X3, Y3, Z3 -coordinates of doubled point, one needs to read from
+#! those 6 memory addresses.
+#!
+#! Stack at end of execution of routine looks like
+#!
+#!   [x3_addr[0..4], x3_addr[4..8], y3_addr[0..4], y3_addr[4..8], z3_addr[0..4], z3_addr[4..8]]
 export.point_doubling.12
   dup.3
   pushw.mem
@@ -8275,37 +9656,37 @@ export.point_doubling.12
   popw.mem          # write z3[4..8] to memory
 end
 
-# Given two secp256k1 points in projective coordinate system ( i.e. with x, y, z -coordinates
-# as secp256k1 prime field elements, represented in Montgomery form, each coordinate using eight 32 -bit limbs ),
-# this routine adds those two points on elliptic curve, using exception-free addition formula from
-# algorithm 7 of https://eprint.iacr.org/2015/1060.pdf, while following prototype
-# implementation https://github.com/itzmeanjan/secp256k1/blob/ec3652a/point.py#L60-L115
-# 
-# Input:
-#
-# 18 memory addresses on stack such that first 6 memory addresses are for first input point, next 6
-# memory addresses holding x, y, z -coordinates of second input point & last 6 addresses are for storing 
-# resulting point ( addition of two input points ).
-#
-# Expected stack during invocation of this routine:
-#
-#   [x1_addr[0..4], x1_addr[4..8], y1_addr[0..4], y1_addr[4..8], z1_addr[0..4], z1_addr[4..8], 
-#     x2_addr[0..4], x2_addr[4..8], y2_addr[0..4], y2_addr[4..8], z2_addr[0..4], z2_addr[4..8],
-#       x3_addr[0..4], x3_addr[4..8], y3_addr[0..4], y3_addr[4..8], z3_addr[0..4], z3_addr[4..8]]
-#
-# Note, (X1, Y1, Z1)    => input point 1
-#       (X2, Y2, Z2)    => input point 2
-#       (X3, Y3, Z3)    => output point
-#
-# Output:
-#
-# Last 6 memory addresses of 18 input memory addresses which were provided during invocation, where resulting elliptic curve
-# point is kept in similar form. For seeing X3, Y3, Z3 -coordinates of doubled point, one needs to read from
-# those 6 memory addresses.
-#
-# Stack at end of execution of routine looks like
-#
-#   [x3_addr[0..4], x3_addr[4..8], y3_addr[0..4], y3_addr[4..8], z3_addr[0..4], z3_addr[4..8]]
+#! Given two secp256k1 points in projective coordinate system ( i.e. with x, y, z -coordinates
+#! as secp256k1 prime field elements, represented in Montgomery form, each coordinate using eight 32 -bit limbs ),
+#! this routine adds those two points on elliptic curve, using exception-free addition formula from
+#! algorithm 7 of https://eprint.iacr.org/2015/1060.pdf, while following prototype
+#! implementation https://github.com/itzmeanjan/secp256k1/blob/ec3652a/point.py#L60-L115
+#! 
+#! Input:
+#!
+#! 18 memory addresses on stack such that first 6 memory addresses are for first input point, next 6
+#! memory addresses holding x, y, z -coordinates of second input point & last 6 addresses are for storing 
+#! resulting point ( addition of two input points ).
+#!
+#! Expected stack during invocation of this routine:
+#!
+#!   [x1_addr[0..4], x1_addr[4..8], y1_addr[0..4], y1_addr[4..8], z1_addr[0..4], z1_addr[4..8], 
+#!     x2_addr[0..4], x2_addr[4..8], y2_addr[0..4], y2_addr[4..8], z2_addr[0..4], z2_addr[4..8],
+#!       x3_addr[0..4], x3_addr[4..8], y3_addr[0..4], y3_addr[4..8], z3_addr[0..4], z3_addr[4..8]]
+#!
+#! Note, (X1, Y1, Z1)    => input point 1
+#!       (X2, Y2, Z2)    => input point 2
+#!       (X3, Y3, Z3)    => output point
+#!
+#! Output:
+#!
+#! Last 6 memory addresses of 18 input memory addresses which were provided during invocation, where resulting elliptic curve
+#! point is kept in similar form. For seeing X3, Y3, Z3 -coordinates of doubled point, one needs to read from
+#! those 6 memory addresses.
+#!
+#! Stack at end of execution of routine looks like
+#!
+#!   [x3_addr[0..4], x3_addr[4..8], y3_addr[0..4], y3_addr[4..8], z3_addr[0..4], z3_addr[4..8]]
 export.point_addition.16
   dup.6
   dup.8
@@ -8720,38 +10101,38 @@ export.point_addition.16
   popw.mem          # write z3[4..8] to memory
 end
 
-# Given a 256 -bit scalar, in radix-2^32 representation ( such that it
-# takes 8 stack elements to represent whole scalar, where each limb is 
-# of 32 -bit width ), this routine multiplies group identity point 
-# ( 0, 1, 0 in projective coordinate system ) with given scalar, producing
-# another point on secp256k1 curve, which will also be presented in projective coordinate
-# system.
-#
-# Input:
-#
-# During invocation, this routine expects stack in following form
-#
-# [Sc0, Sc1, Sc2, Sc3, Sc4, Sc5, Sc6, Sc7, X_addr_0, X_addr_1, Y_addr_0, Y_addr_1, Z_addr_0, Z_addr_1]
-#
-# Sc{0..8}           -> 256 -bit scalar in radix-2^32 form | Sc0 is least significant limb & Sc7 is most significant limb
-# X_addr_0, X_addr_1 -> Resulting secp256k1 point's X -coordinate to be placed, in Montgomery form, in given addresses
-# Y_addr_0, Y_addr_1 -> Resulting secp256k1 point's Y -coordinate to be placed, in Montgomery form, in given addresses
-# Z_addr_1, Z_addr_1 -> Resulting secp256k1 point's Z -coordinate to be placed, in Montgomery form, in given addresses
-#
-# Output:
-#
-# At end of execution of this routine, stack should look like below
-#
-# [X_addr_0, X_addr_1, Y_addr_0, Y_addr_1, Z_addr_0, Z_addr_1]
-#
-# X_addr_0, X_addr_1 -> Resulting secp256k1 point's X -coordinate written, in Montgomery form, in given addresses
-# Y_addr_0, Y_addr_1 -> Resulting secp256k1 point's Y -coordinate written, in Montgomery form, in given addresses
-# Z_addr_0, Z_addr_1 -> Resulting secp256k1 point's Z -coordinate written, in Montgomery form, in given addresses
-#
-# One interested in resulting point, should read from provided address on stack.
-# 
-# This routine implements double-and-add algorithm, while following 
-# https://github.com/itzmeanjan/secp256k1/blob/d23ea7d/point.py#L174-L186 
+#! Given a 256 -bit scalar, in radix-2^32 representation ( such that it
+#! takes 8 stack elements to represent whole scalar, where each limb is 
+#! of 32 -bit width ), this routine multiplies group identity point 
+#! ( 0, 1, 0 in projective coordinate system ) with given scalar, producing
+#! another point on secp256k1 curve, which will also be presented in projective coordinate
+#! system.
+#!
+#! Input:
+#!
+#! During invocation, this routine expects stack in following form
+#!
+#! [Sc0, Sc1, Sc2, Sc3, Sc4, Sc5, Sc6, Sc7, X_addr_0, X_addr_1, Y_addr_0, Y_addr_1, Z_addr_0, Z_addr_1]
+#!
+#! Sc{0..8}           -> 256 -bit scalar in radix-2^32 form | Sc0 is least significant limb & Sc7 is most significant limb
+#! X_addr_0, X_addr_1 -> Resulting secp256k1 point's X -coordinate to be placed, in Montgomery form, in given addresses
+#! Y_addr_0, Y_addr_1 -> Resulting secp256k1 point's Y -coordinate to be placed, in Montgomery form, in given addresses
+#! Z_addr_1, Z_addr_1 -> Resulting secp256k1 point's Z -coordinate to be placed, in Montgomery form, in given addresses
+#!
+#! Output:
+#!
+#! At end of execution of this routine, stack should look like below
+#!
+#! [X_addr_0, X_addr_1, Y_addr_0, Y_addr_1, Z_addr_0, Z_addr_1]
+#!
+#! X_addr_0, X_addr_1 -> Resulting secp256k1 point's X -coordinate written, in Montgomery form, in given addresses
+#! Y_addr_0, Y_addr_1 -> Resulting secp256k1 point's Y -coordinate written, in Montgomery form, in given addresses
+#! Z_addr_0, Z_addr_1 -> Resulting secp256k1 point's Z -coordinate written, in Montgomery form, in given addresses
+#!
+#! One interested in resulting point, should read from provided address on stack.
+#! 
+#! This routine implements double-and-add algorithm, while following 
+#! https://github.com/itzmeanjan/secp256k1/blob/d23ea7d/point.py#L174-L186 
 export.point_mul.20
   # identity point of group (0, 1, 0) in projective coordinate
   # see https://github.com/itzmeanjan/secp256k1/blob/d23ea7d/point.py#L40-L45
@@ -11173,11 +12554,11 @@ proc.mulstep4
     movdn.6
 end
 
-# Performs addition of two unsigned 256 bit integers discarding the overflow.
-# The input values are assumed to be represented using 32 bit limbs, but this is not checked.
-# Stack transition looks as follows:
-# [b7, b6, b5, b4, b3, b2, b1, b0, a7, a6, a5, a4, a3, a2, a1, a0, ...] -> [c7, c6, c5, c4, c3, c2, c1, c0, ...]
-# where c = (a * b) % 2^256, and a0, b0, and c0 are least significant 32-bit limbs of a, b, and c respectively.
+#! Performs addition of two unsigned 256 bit integers discarding the overflow.
+#! The input values are assumed to be represented using 32 bit limbs, but this is not checked.
+#! Stack transition looks as follows:
+#! [b7, b6, b5, b4, b3, b2, b1, b0, a7, a6, a5, a4, a3, a2, a1, a0, ...] -> [c7, c6, c5, c4, c3, c2, c1, c0, ...]
+#! where c = (a * b) % 2^256, and a0, b0, and c0 are least significant 32-bit limbs of a, b, and c respectively.
 export.mul_unsafe.6
     # Memory storing setup
     popw.local.0
@@ -11473,8 +12854,8 @@ end"),
 // ----- std::math::u64 ---------------------------------------------------------------------------
 ("std::math::u64", "# ===== HELPER FUNCTIONS ==========================================================================
 
-# Asserts that both values at the top of the stack are u64 values.
-# The input values are assumed to be represented using 32 bit limbs, fails if they are not.
+#! Asserts that both values at the top of the stack are u64 values.
+#! The input values are assumed to be represented using 32 bit limbs, fails if they are not.
 proc.u32assert4
     u32assert.2
     movup.3
@@ -11486,10 +12867,10 @@ end
 
 # ===== ADDITION ==================================================================================
 
-# Performs addition of two unsigned 64 bit integers preserving the overflow.
-# The input values are assumed to be represented using 32 bit limbs, but this is not checked.
-# Stack transition looks as follows:
-# [b_hi, b_lo, a_hi, a_lo, ...] -> [overflowing_flag, c_hi, c_lo, ...], where c = (a + b) % 2^64
+#! Performs addition of two unsigned 64 bit integers preserving the overflow.
+#! The input values are assumed to be represented using 32 bit limbs, but this is not checked.
+#! Stack transition looks as follows:
+#! [b_hi, b_lo, a_hi, a_lo, ...] -> [overflowing_flag, c_hi, c_lo, ...], where c = (a + b) % 2^64
 export.overflowing_add
     swap
     movup.3
@@ -11499,19 +12880,19 @@ export.overflowing_add
     u32overflowing_add3
 end
 
-# Performs addition of two unsigned 64 bit integers discarding the overflow.
-# The input values are assumed to be represented using 32 bit limbs, but this is not checked.
-# Stack transition looks as follows:
-# [b_hi, b_lo, a_hi, a_lo, ...] -> [c_hi, c_lo, ...], where c = (a + b) % 2^64
+#! Performs addition of two unsigned 64 bit integers discarding the overflow.
+#! The input values are assumed to be represented using 32 bit limbs, but this is not checked.
+#! Stack transition looks as follows:
+#! [b_hi, b_lo, a_hi, a_lo, ...] -> [c_hi, c_lo, ...], where c = (a + b) % 2^64
 export.wrapping_add
     exec.overflowing_add
     drop
 end
 
-# Performs addition of two unsigned 64 bit integers, fails when overflowing.
-# The input values are assumed to be represented using 32 bit limbs, fails if they are not.
-# Stack transition looks as follows:
-# [b_hi, b_lo, a_hi, a_lo, ...] -> [c_hi, c_lo, ...], where c = (a + b) % 2^64
+#! Performs addition of two unsigned 64 bit integers, fails when overflowing.
+#! The input values are assumed to be represented using 32 bit limbs, fails if they are not.
+#! Stack transition looks as follows:
+#! [b_hi, b_lo, a_hi, a_lo, ...] -> [c_hi, c_lo, ...], where c = (a + b) % 2^64
 export.checked_add
     swap
     movup.3
@@ -11527,10 +12908,10 @@ end
 
 # ===== SUBTRACTION ===============================================================================
 
-# Performs subtraction of two unsigned 64 bit integers discarding the overflow.
-# The input values are assumed to be represented using 32 bit limbs, but this is not checked.
-# Stack transition looks as follows:
-# [b_hi, b_lo, a_hi, a_lo, ...] -> [c_hi, c_lo, ...], where c = (a - b) % 2^64
+#! Performs subtraction of two unsigned 64 bit integers discarding the overflow.
+#! The input values are assumed to be represented using 32 bit limbs, but this is not checked.
+#! Stack transition looks as follows:
+#! [b_hi, b_lo, a_hi, a_lo, ...] -> [c_hi, c_lo, ...], where c = (a - b) % 2^64
 export.wrapping_sub
     movup.3
     movup.2
@@ -11544,10 +12925,10 @@ export.wrapping_sub
     drop
 end
 
-# Performs subtraction of two unsigned 64 bit integers, fails when underflowing.
-# The input values are assumed to be represented using 32 bit limbs, fails if they are not.
-# Stack transition looks as follows:
-# [b_hi, b_lo, a_hi, a_lo, ...] -> [c_hi, c_lo, ...], where c = (a - b) % 2^64
+#! Performs subtraction of two unsigned 64 bit integers, fails when underflowing.
+#! The input values are assumed to be represented using 32 bit limbs, fails if they are not.
+#! Stack transition looks as follows:
+#! [b_hi, b_lo, a_hi, a_lo, ...] -> [c_hi, c_lo, ...], where c = (a - b) % 2^64
 export.checked_sub
     movup.3
     movup.2
@@ -11565,10 +12946,10 @@ export.checked_sub
     assert
 end
 
-# Performs subtraction of two unsigned 64 bit integers preserving the overflow.
-# The input values are assumed to be represented using 32 bit limbs, but this is not checked.
-# Stack transition looks as follows:
-# [b_hi, b_lo, a_hi, a_lo, ...] -> [underflowing_flag, c_hi, c_lo, ...], where c = (a - b) % 2^64
+#! Performs subtraction of two unsigned 64 bit integers preserving the overflow.
+#! The input values are assumed to be represented using 32 bit limbs, but this is not checked.
+#! Stack transition looks as follows:
+#! [b_hi, b_lo, a_hi, a_lo, ...] -> [underflowing_flag, c_hi, c_lo, ...], where c = (a - b) % 2^64
 export.overflowing_sub
     movup.3
     movup.2
@@ -11585,10 +12966,10 @@ end
 
 # ===== MULTIPLICATION ============================================================================
 
-# Performs multiplication of two unsigned 64 bit integers discarding the overflow.
-# The input values are assumed to be represented using 32 bit limbs, but this is not checked.
-# Stack transition looks as follows:
-# [b_hi, b_lo, a_hi, a_lo, ...] -> [c_hi, c_lo, ...], where c = (a * b) % 2^64
+#! Performs multiplication of two unsigned 64 bit integers discarding the overflow.
+#! The input values are assumed to be represented using 32 bit limbs, but this is not checked.
+#! Stack transition looks as follows:
+#! [b_hi, b_lo, a_hi, a_lo, ...] -> [c_hi, c_lo, ...], where c = (a * b) % 2^64
 export.wrapping_mul
     dup.3
     dup.2
@@ -11603,11 +12984,11 @@ export.wrapping_mul
     drop
 end
 
-# Performs multiplication of two unsigned 64 bit integers preserving the overflow.
-# The input values are assumed to be represented using 32 bit limbs, but this is not checked.
-# Stack transition looks as follows:
-# [b_hi, b_lo, a_hi, a_lo, ...] -> [c_hi, c_mid_hi, c_mid_lo, c_lo, ...], where c = (a * b) % 2^64
-# This takes 18 cycles.
+#! Performs multiplication of two unsigned 64 bit integers preserving the overflow.
+#! The input values are assumed to be represented using 32 bit limbs, but this is not checked.
+#! Stack transition looks as follows:
+#! [b_hi, b_lo, a_hi, a_lo, ...] -> [c_hi, c_mid_hi, c_mid_lo, c_lo, ...], where c = (a * b) % 2^64
+#! This takes 18 cycles.
 export.overflowing_mul
     dup.3
     dup.2
@@ -11629,10 +13010,10 @@ export.overflowing_mul
     add
 end
 
-# Performs multiplication of two unsigned 64 bit integers, fails when overflowing.
-# The input values are assumed to be represented using 32 bit limbs, fails if they are not.
-# Stack transition looks as follows:
-# [b_hi, b_lo, a_hi, a_lo, ...] -> [c_hi, c_lo, ...], where c = (a * b) % 2^64
+#! Performs multiplication of two unsigned 64 bit integers, fails when overflowing.
+#! The input values are assumed to be represented using 32 bit limbs, fails if they are not.
+#! Stack transition looks as follows:
+#! [b_hi, b_lo, a_hi, a_lo, ...] -> [c_hi, c_lo, ...], where c = (a * b) % 2^64
 export.checked_mul
     dup.3
     dup.2
@@ -11660,10 +13041,10 @@ end
 
 # ===== COMPARISONS ===============================================================================
 
-# Performs less-than comparison of two unsigned 64 bit integers.
-# The input values are assumed to be represented using 32 bit limbs, but this is not checked.
-# Stack transition looks as follows:
-# [b_hi, b_lo, a_hi, a_lo, ...] -> [c, ...], where c = 1 when a < b, and 0 otherwise.
+#! Performs less-than comparison of two unsigned 64 bit integers.
+#! The input values are assumed to be represented using 32 bit limbs, but this is not checked.
+#! Stack transition looks as follows:
+#! [b_hi, b_lo, a_hi, a_lo, ...] -> [c, ...], where c = 1 when a < b, and 0 otherwise.
 export.unchecked_lt
     movup.3
     movup.2
@@ -11678,10 +13059,10 @@ export.unchecked_lt
     or
 end
 
-# Performs less-than comparison of two unsigned 64 bit integers.
-# The input values are assumed to be represented using 32 bit limbs, fails if they are not.
-# Stack transition looks as follows:
-# [b_hi, b_lo, a_hi, a_lo, ...] -> [c, ...], where c = 1 when a < b, and 0 otherwise.
+#! Performs less-than comparison of two unsigned 64 bit integers.
+#! The input values are assumed to be represented using 32 bit limbs, fails if they are not.
+#! Stack transition looks as follows:
+#! [b_hi, b_lo, a_hi, a_lo, ...] -> [c, ...], where c = 1 when a < b, and 0 otherwise.
 export.checked_lt
     movup.3
     movup.2
@@ -11698,11 +13079,11 @@ export.checked_lt
     or
 end
 
-# Performs greater-than comparison of two unsigned 64 bit integers.
-# The input values are assumed to be represented using 32 bit limbs, but this is not checked.
-# Stack transition looks as follows:
-# [b_hi, b_lo, a_hi, a_lo, ...] -> [c, ...], where c = 1 when a > b, and 0 otherwise.
-# This takes 11 cycles.
+#! Performs greater-than comparison of two unsigned 64 bit integers.
+#! The input values are assumed to be represented using 32 bit limbs, but this is not checked.
+#! Stack transition looks as follows:
+#! [b_hi, b_lo, a_hi, a_lo, ...] -> [c, ...], where c = 1 when a > b, and 0 otherwise.
+#! This takes 11 cycles.
 export.unchecked_gt
     movup.2
     u32overflowing_sub
@@ -11717,10 +13098,10 @@ export.unchecked_gt
     or
 end
 
-# Performs greater-than comparison of two unsigned 64 bit integers.
-# The input values are assumed to be represented using 32 bit limbs, fails if they are not.
-# Stack transition looks as follows:
-# [b_hi, b_lo, a_hi, a_lo, ...] -> [c, ...], where c = 1 when a > b, and 0 otherwise.
+#! Performs greater-than comparison of two unsigned 64 bit integers.
+#! The input values are assumed to be represented using 32 bit limbs, fails if they are not.
+#! Stack transition looks as follows:
+#! [b_hi, b_lo, a_hi, a_lo, ...] -> [c, ...], where c = 1 when a > b, and 0 otherwise.
 export.checked_gt
     movup.2
     u32assert.2
@@ -11737,46 +13118,46 @@ export.checked_gt
     or
 end
 
-# Performs less-than-or-equal comparison of two unsigned 64 bit integers.
-# The input values are assumed to be represented using 32 bit limbs, but this is not checked.
-# Stack transition looks as follows:
-# [b_hi, b_lo, a_hi, a_lo, ...] -> [c, ...], where c = 1 when a <= b, and 0 otherwise.
+#! Performs less-than-or-equal comparison of two unsigned 64 bit integers.
+#! The input values are assumed to be represented using 32 bit limbs, but this is not checked.
+#! Stack transition looks as follows:
+#! [b_hi, b_lo, a_hi, a_lo, ...] -> [c, ...], where c = 1 when a <= b, and 0 otherwise.
 export.unchecked_lte
     exec.unchecked_gt
     not
 end
 
-# Performs less-than-or-equal comparison of two unsigned 64 bit integers.
-# The input values are assumed to be represented using 32 bit limbs, fails if they are not.
-# Stack transition looks as follows:
-# [b_hi, b_lo, a_hi, a_lo, ...] -> [c, ...], where c = 1 when a <= b, and 0 otherwise.
+#! Performs less-than-or-equal comparison of two unsigned 64 bit integers.
+#! The input values are assumed to be represented using 32 bit limbs, fails if they are not.
+#! Stack transition looks as follows:
+#! [b_hi, b_lo, a_hi, a_lo, ...] -> [c, ...], where c = 1 when a <= b, and 0 otherwise.
 export.checked_lte
     exec.checked_gt
     not
 end
 
-# Performs greater-than-or-equal comparison of two unsigned 64 bit integers.
-# The input values are assumed to be represented using 32 bit limbs, but this is not checked.
-# Stack transition looks as follows:
-# [b_hi, b_lo, a_hi, a_lo, ...] -> [c, ...], where c = 1 when a >= b, and 0 otherwise.
+#! Performs greater-than-or-equal comparison of two unsigned 64 bit integers.
+#! The input values are assumed to be represented using 32 bit limbs, but this is not checked.
+#! Stack transition looks as follows:
+#! [b_hi, b_lo, a_hi, a_lo, ...] -> [c, ...], where c = 1 when a >= b, and 0 otherwise.
 export.unchecked_gte
     exec.unchecked_lt
     not
 end
 
-# Performs greater-than-or-equal comparison of two unsigned 64 bit integers.
-# The input values are assumed to be represented using 32 bit limbs, fails if they are not.
-# Stack transition looks as follows:
-# [b_hi, b_lo, a_hi, a_lo, ...] -> [c, ...], where c = 1 when a >= b, and 0 otherwise.
+#! Performs greater-than-or-equal comparison of two unsigned 64 bit integers.
+#! The input values are assumed to be represented using 32 bit limbs, fails if they are not.
+#! Stack transition looks as follows:
+#! [b_hi, b_lo, a_hi, a_lo, ...] -> [c, ...], where c = 1 when a >= b, and 0 otherwise.
 export.checked_gte
     exec.checked_lt
     not
 end
 
-# Performs equality comparison of two unsigned 64 bit integers.
-# The input values are assumed to be represented using 32 bit limbs, but this is not checked.
-# Stack transition looks as follows:
-# [b_hi, b_lo, a_hi, a_lo, ...] -> [c, ...], where c = 1 when a == b, and 0 otherwise.
+#! Performs equality comparison of two unsigned 64 bit integers.
+#! The input values are assumed to be represented using 32 bit limbs, but this is not checked.
+#! Stack transition looks as follows:
+#! [b_hi, b_lo, a_hi, a_lo, ...] -> [c, ...], where c = 1 when a == b, and 0 otherwise.
 export.unchecked_eq
     movup.2
     u32checked_eq
@@ -11786,10 +13167,10 @@ export.unchecked_eq
     and
 end
 
-# Performs equality comparison of two unsigned 64 bit integers.
-# The input values are assumed to be represented using 32 bit limbs, fails if they are not.
-# Stack transition looks as follows:
-# [b_hi, b_lo, a_hi, a_lo, ...] -> [c, ...], where c = 1 when a == b, and 0 otherwise.
+#! Performs equality comparison of two unsigned 64 bit integers.
+#! The input values are assumed to be represented using 32 bit limbs, fails if they are not.
+#! Stack transition looks as follows:
+#! [b_hi, b_lo, a_hi, a_lo, ...] -> [c, ...], where c = 1 when a == b, and 0 otherwise.
 export.checked_eq
     movup.2
     u32checked_eq
@@ -11799,10 +13180,10 @@ export.checked_eq
     and
 end
 
-# Performs inequality comparison of two unsigned 64 bit integers.
-# The input values are assumed to be represented using 32 bit limbs, but this is not checked.
-# Stack transition looks as follows:
-# [b_hi, b_lo, a_hi, a_lo, ...] -> [c, ...], where c = 1 when a != b, and 0 otherwise.
+#! Performs inequality comparison of two unsigned 64 bit integers.
+#! The input values are assumed to be represented using 32 bit limbs, but this is not checked.
+#! Stack transition looks as follows:
+#! [b_hi, b_lo, a_hi, a_lo, ...] -> [c, ...], where c = 1 when a != b, and 0 otherwise.
 export.unchecked_neq
     movup.2
     u32checked_neq
@@ -11812,19 +13193,19 @@ export.unchecked_neq
     or
 end
 
-# Performs inequality comparison of two unsigned 64 bit integers.
-# The input values are assumed to be represented using 32 bit limbs, fails if they are not.
-# Stack transition looks as follows:
-# [b_hi, b_lo, a_hi, a_lo, ...] -> [c, ...], where c = 1 when a == b, and 0 otherwise.
+#! Performs inequality comparison of two unsigned 64 bit integers.
+#! The input values are assumed to be represented using 32 bit limbs, fails if they are not.
+#! Stack transition looks as follows:
+#! [b_hi, b_lo, a_hi, a_lo, ...] -> [c, ...], where c = 1 when a == b, and 0 otherwise.
 export.checked_neq
     exec.checked_eq
     not
 end
 
-# Performs comparison to zero of an unsigned 64 bit integer.
-# The input value is assumed to be represented using 32 bit limbs, but this is not checked.
-# Stack transition looks as follows:
-# [a_hi, a_lo, ...] -> [c, ...], where c = 1 when a == 0, and 0 otherwise.
+#! Performs comparison to zero of an unsigned 64 bit integer.
+#! The input value is assumed to be represented using 32 bit limbs, but this is not checked.
+#! Stack transition looks as follows:
+#! [a_hi, a_lo, ...] -> [c, ...], where c = 1 when a == 0, and 0 otherwise.
 export.unchecked_eqz
     eq.0
     swap
@@ -11832,10 +13213,10 @@ export.unchecked_eqz
     and
 end
 
-# Performs comparison to zero of an unsigned 64 bit integer.
-# The input value is assumed to be represented using 32 bit limbs, fails if it is not.
-# Stack transition looks as follows:
-# [a_hi, a_lo, ...] -> [c, ...], where c = 1 when a == 0, and 0 otherwise.
+#! Performs comparison to zero of an unsigned 64 bit integer.
+#! The input value is assumed to be represented using 32 bit limbs, fails if it is not.
+#! Stack transition looks as follows:
+#! [a_hi, a_lo, ...] -> [c, ...], where c = 1 when a == 0, and 0 otherwise.
 export.checked_eqz
     u32assert.2
     eq.0
@@ -11844,10 +13225,10 @@ export.checked_eqz
     and
 end
 
-# Compares two unsigned 64 bit integers and drop the larger one from the stack.
-# The input values are assumed to be represented using 32 bit limbs, but this is not checked.
-# Stack transition looks as follows:
-# [b_hi, b_lo, a_hi, a_lo, ...] -> [c_hi, c_lo, ...], where c = a when a < b, and b otherwise.
+#! Compares two unsigned 64 bit integers and drop the larger one from the stack.
+#! The input values are assumed to be represented using 32 bit limbs, but this is not checked.
+#! Stack transition looks as follows:
+#! [b_hi, b_lo, a_hi, a_lo, ...] -> [c_hi, c_lo, ...], where c = a when a < b, and b otherwise.
 export.unchecked_min
     dupw
     exec.unchecked_gt
@@ -11859,19 +13240,19 @@ export.unchecked_min
     cdrop
 end
 
-# Compares two unsigned 64 bit integers and drop the larger one from the stack.
-# The input values are assumed to be represented using 32 bit limbs, fails if they are not.
-# Stack transition looks as follows:
-# [b_hi, b_lo, a_hi, a_lo, ...] -> [c_hi, c_lo, ...], where c = a when a < b, and b otherwise.
+#! Compares two unsigned 64 bit integers and drop the larger one from the stack.
+#! The input values are assumed to be represented using 32 bit limbs, fails if they are not.
+#! Stack transition looks as follows:
+#! [b_hi, b_lo, a_hi, a_lo, ...] -> [c_hi, c_lo, ...], where c = a when a < b, and b otherwise.
 export.checked_min
     exec.u32assert4
     exec.unchecked_min
 end
 
-# Compares two unsigned 64 bit integers and drop the smaller one from the stack.
-# The input values are assumed to be represented using 32 bit limbs, but this is not checked.
-# Stack transition looks as follows:
-# [b_hi, b_lo, a_hi, a_lo, ...] -> [c_hi, c_lo, ...], where c = a when a > b, and b otherwise.
+#! Compares two unsigned 64 bit integers and drop the smaller one from the stack.
+#! The input values are assumed to be represented using 32 bit limbs, but this is not checked.
+#! Stack transition looks as follows:
+#! [b_hi, b_lo, a_hi, a_lo, ...] -> [c_hi, c_lo, ...], where c = a when a > b, and b otherwise.
 export.unchecked_max
     dupw
     exec.unchecked_lt
@@ -11883,10 +13264,10 @@ export.unchecked_max
     cdrop
 end
 
-# Compares two unsigned 64 bit integers and drop the smaller one from the stack.
-# The input values are assumed to be represented using 32 bit limbs, fails if they are not.
-# Stack transition looks as follows:
-# [b_hi, b_lo, a_hi, a_lo, ...] -> [c_hi, c_lo, ...], where c = a when a > b, and b otherwise.
+#! Compares two unsigned 64 bit integers and drop the smaller one from the stack.
+#! The input values are assumed to be represented using 32 bit limbs, fails if they are not.
+#! Stack transition looks as follows:
+#! [b_hi, b_lo, a_hi, a_lo, ...] -> [c_hi, c_lo, ...], where c = a when a > b, and b otherwise.
 export.checked_max
     exec.u32assert4
     exec.unchecked_max
@@ -11895,10 +13276,10 @@ end
 
 # ===== DIVISION ==================================================================================
 
-# Performs division of two unsigned 64 bit integers discarding the remainder.
-# The input values are assumed to be represented using 32 bit limbs, but this is not checked.
-# Stack transition looks as follows:
-# [b_hi, b_lo, a_hi, a_lo, ...] -> [c_hi, c_lo, ...], where c = a // b
+#! Performs division of two unsigned 64 bit integers discarding the remainder.
+#! The input values are assumed to be represented using 32 bit limbs, but this is not checked.
+#! Stack transition looks as follows:
+#! [b_hi, b_lo, a_hi, a_lo, ...] -> [c_hi, c_lo, ...], where c = a // b
 export.unchecked_div
     adv.u64div          # inject the quotient and the remainder into the advice tape
 
@@ -11949,10 +13330,10 @@ export.unchecked_div
     assert_eq           # quotient remains on the stack
 end
 
-# Performs division of two unsigned 64 bit integers discarding the remainder.
-# The input values are assumed to be represented using 32 bit limbs, fails if they are not.
-# Stack transition looks as follows:
-# [b_hi, b_lo, a_hi, a_lo, ...] -> [c_hi, c_lo, ...], where c = a // b
+#! Performs division of two unsigned 64 bit integers discarding the remainder.
+#! The input values are assumed to be represented using 32 bit limbs, fails if they are not.
+#! Stack transition looks as follows:
+#! [b_hi, b_lo, a_hi, a_lo, ...] -> [c_hi, c_lo, ...], where c = a // b
 export.checked_div
     exec.u32assert4
     exec.unchecked_div
@@ -11960,10 +13341,10 @@ end
 
 # ===== MODULO OPERATION ==========================================================================
 
-# Performs modulo operation of two unsigned 64 bit integers.
-# The input values are assumed to be represented using 32 bit limbs, but this is not checked.
-# Stack transition looks as follows:
-# [b_hi, b_lo, a_hi, a_lo, ...] -> [c_hi, c_lo, ...], where c = a % b
+#! Performs modulo operation of two unsigned 64 bit integers.
+#! The input values are assumed to be represented using 32 bit limbs, but this is not checked.
+#! Stack transition looks as follows:
+#! [b_hi, b_lo, a_hi, a_lo, ...] -> [c_hi, c_lo, ...], where c = a % b
 export.unchecked_mod
     adv.u64div          # inject the quotient and the remainder into the advice tape
 
@@ -12014,10 +13395,10 @@ export.unchecked_mod
     assert_eq           # remainder remains on the stack
 end
 
-# Performs modulo operation of two unsigned 64 bit integers.
-# The input values are assumed to be represented using 32 bit limbs, fails if they are not.
-# Stack transition looks as follows:
-# [b_hi, b_lo, a_hi, a_lo, ...] -> [c_hi, c_lo, ...], where c = a % b
+#! Performs modulo operation of two unsigned 64 bit integers.
+#! The input values are assumed to be represented using 32 bit limbs, fails if they are not.
+#! Stack transition looks as follows:
+#! [b_hi, b_lo, a_hi, a_lo, ...] -> [c_hi, c_lo, ...], where c = a % b
 export.checked_mod
     exec.u32assert4
     exec.unchecked_mod
@@ -12025,13 +13406,12 @@ end
 
 # ===== DIVMOD OPERATION ==========================================================================
 
-# Performs divmod operation of two unsigned 64 bit integers.
-# The input values are assumed to be represented using 32 bit limbs, but this is not checked.
-# Stack transition looks as follows:
-# [b_hi, b_lo, a_hi, a_lo, ...] -> [r_hi, r_lo, q_hi, q_lo ...], where r = a % b, q = a / b
-export.unchecked_divmod
-    adv.u64div          # inject the quotient and the remainder into the advice tape
-
+#! Verifies the quotient and the remainder of division of two unsigned 64 bit integers supplied
+#! via the advice tape, with the quotient read first, and leaves them on the stack. The input
+#! values are assumed to be represented using 32 bit limbs, but this is not checked.
+#! Stack transition looks as follows:
+#! [b_hi, b_lo, a_hi, a_lo, ...] -> [r_hi, r_lo, q_hi, q_lo ...], where r = a % b, q = a / b
+proc.verify_divmod
     push.adv.2          # read the quotient from the advice tape and make sure it consists of
     u32assert.2         # 32-bit limbs
 
@@ -12079,21 +13459,44 @@ export.unchecked_divmod
     assert_eq           # remainder remains on the stack
 end
 
-# Performs divmod operation of two unsigned 64 bit integers.
-# The input values are assumed to be represented using 32 bit limbs, fails if they are not.
-# Stack transition looks as follows:
-# [b_hi, b_lo, a_hi, a_lo, ...] -> [r_hi, r_lo, q_hi, q_lo ...], where r = a % b, q = a / b
+#! Performs divmod operation of two unsigned 64 bit integers.
+#! The input values are assumed to be represented using 32 bit limbs, but this is not checked.
+#! Stack transition looks as follows:
+#! [b_hi, b_lo, a_hi, a_lo, ...] -> [r_hi, r_lo, q_hi, q_lo ...], where r = a % b, q = a / b
+export.unchecked_divmod
+    adv.u64div          # inject the quotient and the remainder into the advice tape
+    exec.verify_divmod
+end
+
+#! Performs divmod operation of two unsigned 64 bit integers.
+#! The input values are assumed to be represented using 32 bit limbs, fails if they are not.
+#! Stack transition looks as follows:
+#! [b_hi, b_lo, a_hi, a_lo, ...] -> [r_hi, r_lo, q_hi, q_lo ...], where r = a % b, q = a / b
 export.checked_divmod
     exec.u32assert4
     exec.unchecked_divmod
 end
 
+#! Performs divmod operation of two unsigned 64 bit integers using the quotient and the remainder
+#! computed by the host. The procedure emits event 4294901760 (0xFFFF0000), in response to which
+#! the host is expected to inject the quotient and the remainder into the advice tape in the same
+#! way as adv.u64div instruction does (e.g., via HintProvider); the results are then verified in
+#! the VM, and thus the procedure fails if the host supplies incorrect results.
+#! The input values are assumed to be represented using 32 bit limbs, fails if they are not.
+#! Stack transition looks as follows:
+#! [b_hi, b_lo, a_hi, a_lo, ...] -> [r_hi, r_lo, q_hi, q_lo ...], where r = a % b, q = a / b
+export.divmod_hint
+    exec.u32assert4
+    emit.4294901760     # request the quotient and the remainder from the host
+    exec.verify_divmod
+end
+
 # ===== BITWISE OPERATIONS ========================================================================
 
-# Performs bitwise AND of two unsigned 64-bit integers.
-# The input values are assumed to be represented using 32 bit limbs, but this is not checked.
-# Stack transition looks as follows:
-# [b_hi, b_lo, a_hi, a_lo, ...] -> [c_hi, c_lo, ...], where c = a AND b.
+#! Performs bitwise AND of two unsigned 64-bit integers.
+#! The input values are assumed to be represented using 32 bit limbs, but this is not checked.
+#! Stack transition looks as follows:
+#! [b_hi, b_lo, a_hi, a_lo, ...] -> [c_hi, c_lo, ...], where c = a AND b.
 export.checked_and
     swap
     movup.3
@@ -12103,10 +13506,10 @@ export.checked_and
     u32checked_and
 end
 
-# Performs bitwise OR of two unsigned 64 bit integers.
-# The input values are assumed to be represented using 32 bit limbs, fails if they are not.
-# Stack transition looks as follows:
-# [b_hi, b_lo, a_hi, a_lo, ...] -> [c_hi, c_lo, ...], where c = a OR b.
+#! Performs bitwise OR of two unsigned 64 bit integers.
+#! The input values are assumed to be represented using 32 bit limbs, fails if they are not.
+#! Stack transition looks as follows:
+#! [b_hi, b_lo, a_hi, a_lo, ...] -> [c_hi, c_lo, ...], where c = a OR b.
 export.checked_or
     swap
     movup.3
@@ -12116,10 +13519,10 @@ export.checked_or
     u32checked_or
 end
 
-# Performs bitwise XOR of two unsigned 64 bit integers.
-# The input values are assumed to be represented using 32 bit limbs, fails if they are not.
-# Stack transition looks as follows:
-# [b_hi, b_lo, a_hi, a_lo, ...] -> [c_hi, c_lo, ...], where c = a XOR b.
+#! Performs bitwise XOR of two unsigned 64 bit integers.
+#! The input values are assumed to be represented using 32 bit limbs, fails if they are not.
+#! Stack transition looks as follows:
+#! [b_hi, b_lo, a_hi, a_lo, ...] -> [c_hi, c_lo, ...], where c = a XOR b.
 export.checked_xor
     swap
     movup.3
@@ -12129,12 +13532,12 @@ export.checked_xor
     u32checked_xor
 end
 
-# Performs left shift of one unsigned 64-bit integer using the pow2 operation.
-# The input value to be shifted is assumed to be represented using 32 bit limbs.
-# The shift value is assumed to be in the range [0, 64).
-# Stack transition looks as follows:
-# [b, a_hi, a_lo, ...] -> [c_hi, c_lo, ...], where c = a << b mod 2^64.
-# This takes 50 cycles.
+#! Performs left shift of one unsigned 64-bit integer using the pow2 operation.
+#! The input value to be shifted is assumed to be represented using 32 bit limbs.
+#! The shift value is assumed to be in the range [0, 64).
+#! Stack transition looks as follows:
+#! [b, a_hi, a_lo, ...] -> [c_hi, c_lo, ...], where c = a << b mod 2^64.
+#! This takes 50 cycles.
 export.unchecked_shl
     unchecked_pow2
     u32split
@@ -12142,12 +13545,12 @@ export.unchecked_shl
 end
 
 
-# Performs right shift of one unsigned 64-bit integer using the pow2 operation.
-# The input value to be shifted is assumed to be represented using 32 bit limbs.
-# The shift value is assumed to be in the range [0, 64).
-# Stack transition looks as follows:
-# [b, a_hi, a_lo, ...] -> [c_hi, c_lo, ...], where c = a >> b.
-# This takes 66 cycles.
+#! Performs right shift of one unsigned 64-bit integer using the pow2 operation.
+#! The input value to be shifted is assumed to be represented using 32 bit limbs.
+#! The shift value is assumed to be in the range [0, 64).
+#! Stack transition looks as follows:
+#! [b, a_hi, a_lo, ...] -> [c_hi, c_lo, ...], where c = a >> b.
+#! This takes 66 cycles.
 export.unchecked_shr
     unchecked_pow2
     u32split
@@ -12180,27 +13583,27 @@ export.unchecked_shr
     cswap
 end
 
-# Performs left shift of one unsigned 64-bit integer preserving the overflow and
-# using the pow2 operation.
-# The input value to be shifted is assumed to be represented using 32 bit limbs.
-# The shift value is assumed to be in the range [0, 64).
-# Stack transition looks as follows:
-# [b, a_hi, a_lo, ...] -> [d_hi, d_lo, c_hi, c_lo, ...], where (d,c) = a << b,
-# which d contains the bits shifted out.
-# This takes 57 cycles.
+#! Performs left shift of one unsigned 64-bit integer preserving the overflow and
+#! using the pow2 operation.
+#! The input value to be shifted is assumed to be represented using 32 bit limbs.
+#! The shift value is assumed to be in the range [0, 64).
+#! Stack transition looks as follows:
+#! [b, a_hi, a_lo, ...] -> [d_hi, d_lo, c_hi, c_lo, ...], where (d,c) = a << b,
+#! which d contains the bits shifted out.
+#! This takes 57 cycles.
 export.overflowing_shl
     unchecked_pow2
     u32split
     exec.overflowing_mul
 end
 
-# Performs right shift of one unsigned 64-bit integer preserving the overflow and
-# using the pow2 operation.
-# The input value to be shifted is assumed to be represented using 32 bit limbs.
-# The shift value is assumed to be in the range [0, 64).
-# Stack transition looks as follows:
-# [b, a_hi, a_lo, ...] -> [d_hi, d_lo, c_hi, c_lo, ...], where c = a >> b, d = a << (64 - b).
-# This takes 138 cycles.
+#! Performs right shift of one unsigned 64-bit integer preserving the overflow and
+#! using the pow2 operation.
+#! The input value to be shifted is assumed to be represented using 32 bit limbs.
+#! The shift value is assumed to be in the range [0, 64).
+#! Stack transition looks as follows:
+#! [b, a_hi, a_lo, ...] -> [d_hi, d_lo, c_hi, c_lo, ...], where c = a >> b, d = a << (64 - b).
+#! This takes 138 cycles.
 export.overflowing_shr
     push.64             # (64 - b)
     dup.1
@@ -12226,12 +13629,12 @@ export.overflowing_shr
     exec.unchecked_shl  # d = a << (64 - b)
 end
 
-# Performs left rotation of one unsigned 64-bit integer using the pow2 operation.
-# The input value to be shifted is assumed to be represented using 32 bit limbs.
-# The shift value is assumed to be in the range [0, 64).
-# Stack transition looks as follows:
-# [b, a_hi, a_lo, ...] -> [c_hi, c_lo, ...], where c = a << b mod 2^64.
-# This takes 57 cycles.
+#! Performs left rotation of one unsigned 64-bit integer using the pow2 operation.
+#! The input value to be shifted is assumed to be represented using 32 bit limbs.
+#! The shift value is assumed to be in the range [0, 64).
+#! Stack transition looks as follows:
+#! [b, a_hi, a_lo, ...] -> [c_hi, c_lo, ...], where c = a << b mod 2^64.
+#! This takes 57 cycles.
 export.unchecked_rotl
     push.31
     dup.1
@@ -12263,12 +13666,12 @@ export.unchecked_rotl
     cswap
 end
 
-# Performs right rotation of one unsigned 64-bit integer using the pow2 operation.
-# The input value to be shifted is assumed to be represented using 32 bit limbs.
-# The shift value is assumed to be in the range [0, 64).
-# Stack transition looks as follows:
-# [b, a_hi, a_lo, ...] -> [c_hi, c_lo, ...], where c = a << b mod 2^64.
-# This takes 62 cycles.
+#! Performs right rotation of one unsigned 64-bit integer using the pow2 operation.
+#! The input value to be shifted is assumed to be represented using 32 bit limbs.
+#! The shift value is assumed to be in the range [0, 64).
+#! Stack transition looks as follows:
+#! [b, a_hi, a_lo, ...] -> [c_hi, c_lo, ...], where c = a << b mod 2^64.
+#! This takes 62 cycles.
 export.unchecked_rotr
     push.31
     dup.1
@@ -12305,11 +13708,398 @@ export.unchecked_rotr
     cswap
 end
 "),
+// ----- std::mem ---------------------------------------------------------------------------------
+("std::mem", "# Memory addresses in Miden VM are word-addressed, i.e. each address holds a single word (4 field
+# elements). All procedures in this module operate on regions of consecutive words: a region of
+# n words starting at address a spans addresses a, a + 1, ..., a + n - 1.
+#
+# The bump allocator keeps its state (the number of words allocated so far) in the first element
+# of the word at address 2^32 - 1. Allocated regions start at address 2^31, so programs using the
+# allocator should not write to addresses in the range [2^31, 2^32) directly.
+
+#! Allocates a region of n words and returns the address of its first word. Regions are handed out
+#! sequentially starting at address 2^31 and are never freed. The total number of words allocated
+#! over the lifetime of a program must be smaller than 2^31 - 1; otherwise the procedure fails.
+#! Input: [n, ...]
+#! Output: [addr, ...]
+export.alloc
+    u32assert
+    push.mem.4294967295
+    dup
+    movup.2
+    u32checked_add
+    dup
+    push.2147483647
+    u32checked_lt
+    assert
+    pop.mem.4294967295
+    push.2147483648
+    add
+end
+
+#! Copies a region of n words starting at address src into a region starting at address dst. The
+#! copy is performed word by word from the lowest address up, and thus if the regions overlap, dst
+#! must not be greater than src.
+#! Input: [src, dst, n, ...]
+#! Output: [...]
+export.memcopy
+    dup.2
+    neq.0
+    while.true
+        dup
+        pushw.mem
+        dup.5
+        storew.mem
+        dropw
+        add.1
+        swap
+        add.1
+        swap
+        movup.2
+        sub.1
+        dup
+        movdn.3
+        neq.0
+    end
+    drop
+    drop
+    drop
+end
+
+#! Sets every word of the region of n words starting at address addr to the word V.
+#! Input: [addr, n, V, ...]
+#! Output: [...]
+export.memset
+    movdn.5
+    movdn.5
+    dup.5
+    neq.0
+    while.true
+        dup.4
+        storew.mem
+        movup.4
+        add.1
+        movdn.4
+        movup.5
+        sub.1
+        dup
+        movdn.6
+        neq.0
+    end
+    dropw
+    drop
+    drop
+end
+
+#! Compares the region of n words starting at address a with the region of n words starting at
+#! address b. Returns 1 if the regions are equal and 0 otherwise. The comparison stops at the
+#! first pair of words which differ.
+#! Input: [a, b, n, ...]
+#! Output: [is_equal, ...]
+export.memcmp
+    push.1
+    movdn.3
+    dup.2
+    neq.0
+    while.true
+        dup
+        pushw.mem
+        dup.5
+        pushw.mem
+        eqw
+        movdn.8
+        dropw
+        dropw
+        movup.4
+        and
+        movdn.3
+        add.1
+        swap
+        add.1
+        swap
+        movup.2
+        sub.1
+        dup
+        movdn.3
+        neq.0
+        dup.4
+        and
+    end
+    drop
+    drop
+    drop
+end
+"),
+// ----- std::mem::bytes --------------------------------------------------------------------------
+("std::mem::bytes", "# Procedures in this module provide byte-level access to word-addressed memory. Bytes are packed
+# 4 per element in little-endian order, and thus a single word of memory holds 16 bytes. Byte
+# address b maps onto memory as follows:
+# - the word containing the byte is located at address b / 16,
+# - the element containing the byte is at index (b mod 16) / 4 of that word,
+# - the byte occupies bits [8 * (b mod 4), 8 * (b mod 4) + 8) of that element.
+#
+# All byte addresses must be valid u32 values, and elements holding bytes must be valid u32
+# values (i.e., memory written by means other than these procedures must respect the packing).
+
+#! Returns the element at index j of the word W, where j is in the range [0, 4).
+#! Input: [j, W, ...]
+#! Output: [W[j], ...]
+proc.select
+    dup
+    eq.3
+    movup.2
+    mul
+    swap
+    dup
+    eq.2
+    movup.3
+    mul
+    movup.2
+    add
+    swap
+    dup
+    eq.1
+    movup.3
+    mul
+    movup.2
+    add
+    swap
+    eq.0
+    movup.2
+    mul
+    add
+end
+
+#! Adds d to the element at index j of the word W, where j is in the range [0, 4).
+#! Input: [j, d, W, ...]
+#! Output: [W', ...]
+proc.add_at
+    dup
+    eq.3
+    dup.2
+    mul
+    movup.3
+    add
+    movdn.5
+    dup
+    eq.2
+    dup.2
+    mul
+    movup.3
+    add
+    movdn.5
+    dup
+    eq.1
+    dup.2
+    mul
+    movup.3
+    add
+    movdn.5
+    eq.0
+    mul
+    add
+    movdn.3
+end
+
+#! Returns the byte located at byte address b.
+#! Input: [b, ...]
+#! Output: [x, ...]
+export.load_byte
+    u32checked_divmod.16
+    u32checked_divmod.4
+    mul.8
+    checked_pow2
+    movup.2
+    pushw.mem
+    movup.5
+    exec.select
+    swap
+    u32checked_div
+    u32checked_mod.256
+end
+
+#! Writes byte x to byte address b, leaving all other bytes in memory unchanged. Fails if x is not
+#! a valid byte.
+#! Input: [b, x, ...]
+#! Output: [...]
+export.store_byte
+    dup.1
+    push.256
+    u32checked_lt
+    assert
+    u32checked_divmod.16
+    u32checked_divmod.4
+    mul.8
+    checked_pow2
+    dup.2
+    pushw.mem
+    dupw
+    dup.9
+    exec.select
+    dup.5
+    u32checked_div
+    u32checked_mod.256
+    movup.8
+    swap
+    sub
+    movup.5
+    mul
+    movup.5
+    exec.add_at
+    movup.4
+    popw.mem
+end
+
+#! Copies n bytes starting at byte address src to byte address dst. The copy is performed byte by
+#! byte from the lowest address up, and thus if the ranges overlap, dst must not be greater than
+#! src.
+#! Input: [src, dst, n, ...]
+#! Output: [...]
+export.copy
+    dup.2
+    neq.0
+    while.true
+        dup
+        exec.load_byte
+        dup.2
+        exec.store_byte
+        add.1
+        swap
+        add.1
+        swap
+        movup.2
+        sub.1
+        dup
+        movdn.3
+        neq.0
+    end
+    drop
+    drop
+    drop
+end
+
+#! Reads a byte string of length n from the advice tape and writes it to memory starting at byte
+#! address b, which must be a multiple of 16. The byte string is expected to be on the advice tape
+#! as ceil(n / 16) words with bytes packed as described above; bytes past the end of the string in
+#! the last word are written to memory as well. Execution fails if any element read from the
+#! advice tape is not a valid u32 value.
+#! Input: [b, n, ...]
+#! Output: [...]
+export.load_from_adv
+    u32checked_divmod.16
+    eq.0
+    assert
+    swap
+    add.15
+    u32checked_div.16
+    dup
+    neq.0
+    while.true
+        padw
+        loadw.adv
+        u32assertw
+        dup.5
+        popw.mem
+        sub.1
+        swap
+        add.1
+        swap
+        dup
+        neq.0
+    end
+    drop
+    drop
+end
+"),
+// ----- std::returndata --------------------------------------------------------------------------
+("std::returndata", "# Procedures in this module implement a convention for returning data which does not fit into the
+# 16 elements of the output stack. Values are appended to the returndata region of memory, one
+# value per word:
+# - the first element of the word at address 2^30 holds the number of words written so far,
+# - the i-th value is written to the word at address 2^30 + 1 + i.
+#
+# Before the program exits, it should call commit, which leaves a commitment to the returndata
+# region on the stack. The host can then read the region from the final memory state and check it
+# against the commitment.
+
+#! Appends the word W to the returndata region.
+#! Input: [W, ...]
+#! Output: [...]
+export.write_word
+    push.mem.1073741824
+    dup
+    push.1073741825
+    add
+    movdn.5
+    add.1
+    pop.mem.1073741824
+    movup.4
+    popw.mem
+end
+
+#! Appends the field element x to the returndata region. The element is written as the word
+#! [x, 0, 0, 0].
+#! Input: [x, ...]
+#! Output: [...]
+export.write_felt
+    push.0.0.0
+    exec.write_word
+end
+
+#! Appends the u32 value x to the returndata region. Fails if x is not a valid u32 value.
+#! Input: [x, ...]
+#! Output: [...]
+export.write_u32
+    u32assert
+    exec.write_felt
+end
+
+#! Appends the u64 value x = x_hi * 2^32 + x_lo to the returndata region. The value is written as
+#! the word [x_lo, x_hi, 0, 0]. Fails if either of the limbs is not a valid u32 value.
+#! Input: [x_hi, x_lo, ...]
+#! Output: [...]
+export.write_u64
+    u32assert.2
+    push.0.0
+    exec.write_word
+end
+
+#! Computes a commitment to the returndata region and returns it together with the number of
+#! words in the region. The commitment C is computed by sequentially hashing all words in the
+#! region, i.e. C = hash(...hash(hash(0, W_0), W_1)..., W_{n-1}), where 0 is the word of zeros.
+#! Input: [...]
+#! Output: [C, n, ...]
+export.commit
+    push.mem.1073741824
+    dup
+    push.1073741825
+    padw
+    dup.5
+    neq.0
+    while.true
+        dup.4
+        pushw.mem
+        rphash
+        movup.4
+        add.1
+        movdn.4
+        movup.5
+        sub.1
+        dup
+        movdn.6
+        neq.0
+    end
+    movup.4
+    drop
+    movup.4
+    drop
+end
+"),
 // ----- std::sys ---------------------------------------------------------------------------------
-("std::sys", "# Removes elements deep in the stack until the depth of the stack is exactly 16. The elements
-# are removed in such a way that the top 16 elements of the stack remain unchanged.
-# Input: Stack with 16 or more elements.
-# Output: Stack with only the original top 16 elements.
+("std::sys", "#! Removes elements deep in the stack until the depth of the stack is exactly 16. The elements
+#! are removed in such a way that the top 16 elements of the stack remain unchanged.
+#! Input: Stack with 16 or more elements.
+#! Output: Stack with only the original top 16 elements.
 export.finalize_stack.4
     popw.local.0
     popw.local.1
@@ -12330,5 +14120,53 @@ export.finalize_stack.4
     swapw.1
     loadw.local.0
 end
+
+#! Moves n elements from the top of the stack into memory, one element per word, starting at the
+#! specified address; element e_i is stored as [e_i, 0, 0, 0] at address addr + i. The elements
+#! can be put back onto the stack via restore.
+#! Input: [addr, n, e_0, ..., e_{n-1}, ...]
+#! Output: [...]
+export.spill
+    dup.1
+    neq.0
+    while.true
+        movup.2
+        dup.1
+        pop.mem
+        add.1
+        swap
+        sub.1
+        swap
+        dup.1
+        neq.0
+    end
+    drop
+    drop
+end
+
+#! Pushes n elements stored in memory, one element per word, starting at the specified address
+#! onto the stack, such that the element stored at addr ends up at the top of the stack. This
+#! reverses the effect of spill.
+#! Input: [addr, n, ...]
+#! Output: [e_0, ..., e_{n-1}, ...], where e_i is the first element of the word at addr + i
+export.restore
+    dup.1
+    neq.0
+    while.true
+        dup.1
+        dup.1
+        add
+        sub.1
+        push.mem
+        movdn.2
+        swap
+        sub.1
+        swap
+        dup.1
+        neq.0
+    end
+    drop
+    drop
+end
 "),
 ];
@@ -9,6 +9,12 @@ use vm_core::{
 mod asm;
 use asm::MODULES;
 
+mod random_coin;
+pub use random_coin::RandomCoin;
+
+mod returndata;
+pub use returndata::{ReturnData, ReturnDataReader, MAX_RETURNDATA_LEN, RETURNDATA_ADDR};
+
 // CONSTANTS
 // ================================================================================================
 
@@ -27,6 +33,17 @@ pub struct StdLibrary {
     modules: ModuleMap,
 }
 
+impl StdLibrary {
+    /// Returns an iterator over the paths of all modules of the standard library (e.g.,
+    /// `std::math::u64`), in lexicographic order.
+    ///
+    /// Exported procedures of a module can be described via [Library::exported_procedures()],
+    /// using the assembler as the module parser.
+    pub fn module_paths(&self) -> impl Iterator<Item = &str> {
+        self.modules.keys().copied()
+    }
+}
+
 impl Library for StdLibrary {
     /// Returns root namespace of the standard library, which is always "std".
     fn root_ns(&self) -> &str {
@@ -69,6 +86,17 @@ impl Default for StdLibrary {
 #[cfg(test)]
 mod tests {
     use super::Library;
+    use vm_core::utils::collections::Vec;
+
+    #[test]
+    fn module_paths() {
+        let stdlib = super::StdLibrary::default();
+        let paths = stdlib.module_paths().collect::<Vec<_>>();
+        assert!(paths.contains(&"std::math::u64"));
+        assert!(paths
+            .iter()
+            .all(|path| stdlib.get_module_source(path).is_ok()));
+    }
 
     #[test]
     fn lib_version() {
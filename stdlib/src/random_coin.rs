@@ -0,0 +1,80 @@
+use vm_core::{chiplets::hasher::hash_elements, Felt, FieldElement, StarkField, Word};
+
+// RANDOM COIN
+// ================================================================================================
+
+/// Random coin which draws the same values as the procedures of `std::crypto::random_coin`
+/// module.
+///
+/// The coin draws words by hashing its seed together with the number of words drawn since the
+/// coin was last seeded. A host which knows the data a coin inside the VM was seeded with can use
+/// this struct to reproduce the values drawn by a program, e.g., to prepare advice inputs which
+/// depend on them.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RandomCoin {
+    seed: Word,
+    counter: u64,
+}
+
+impl RandomCoin {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a new coin with the specified seed, as initialized by
+    /// `std::crypto::random_coin::init` procedure.
+    pub fn new(seed: Word) -> Self {
+        Self { seed, counter: 0 }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the current seed of this coin.
+    pub fn seed(&self) -> Word {
+        self.seed
+    }
+
+    // DRAWING
+    // --------------------------------------------------------------------------------------------
+
+    /// Updates the seed of this coin to hash(seed, data) and resets its counter.
+    pub fn reseed(&mut self, data: Word) {
+        self.seed = merge(self.seed, data);
+        self.counter = 0;
+    }
+
+    /// Draws the next word from this coin.
+    pub fn draw_word(&mut self) -> Word {
+        self.counter += 1;
+        merge(
+            self.seed,
+            [Felt::new(self.counter), Felt::ZERO, Felt::ZERO, Felt::ZERO],
+        )
+    }
+
+    /// Draws the next field element from this coin. The element is the top element of the drawn
+    /// word when the word is placed onto the stack, i.e., the last element of the word.
+    pub fn draw(&mut self) -> Felt {
+        self.draw_word()[3]
+    }
+
+    /// Draws the next field element from this coin and maps it to an index in the range [0, n) by
+    /// reducing its lower 32 bits modulo n.
+    ///
+    /// # Panics
+    /// Panics if n is zero.
+    pub fn draw_index(&mut self, n: u32) -> u32 {
+        (self.draw().as_int() as u32) % n
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Hashes two words in the same way as `rphash` instruction does for the stack [b, a, ...].
+fn merge(a: Word, b: Word) -> Word {
+    let mut elements = [Felt::ZERO; 8];
+    elements[..4].copy_from_slice(&a);
+    elements[4..].copy_from_slice(&b);
+    hash_elements(&elements).into()
+}
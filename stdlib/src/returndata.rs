@@ -0,0 +1,183 @@
+use vm_core::{
+    chiplets::hasher::hash_elements, errors::ReturnDataError, utils::collections::Vec, Felt,
+    FieldElement, StarkField, Word,
+};
+
+// CONSTANTS
+// ================================================================================================
+
+/// Memory address of the returndata header. The first element of the word at this address holds
+/// the number of words in the returndata region.
+pub const RETURNDATA_ADDR: u64 = 1 << 30;
+
+/// Maximum number of words which can be stored in the returndata region.
+pub const MAX_RETURNDATA_LEN: u64 = (1 << 30) - 1;
+
+// RETURN DATA
+// ================================================================================================
+
+/// Contents of the returndata region written by the procedures of `std::returndata` module.
+///
+/// Programs which need to return more data than fits into the output stack can append values to
+/// the returndata region of memory and then commit to it by calling `std::returndata::commit`.
+/// The host reads the region from the final memory state via [ReturnData::read()], checks it
+/// against the commitment left on the output stack via [ReturnData::verify()], and decodes values
+/// using a [ReturnDataReader].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ReturnData {
+    words: Vec<Word>,
+}
+
+impl ReturnData {
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Reads the returndata region using the provided memory accessor.
+    ///
+    /// The accessor is expected to return the word at the specified address, or None if the
+    /// address has never been written to (e.g., `Process::get_memory_value()`). Addresses which
+    /// have never been written to are treated as holding words of zeros.
+    ///
+    /// # Errors
+    /// Returns an error if the length of the region stored in the header is invalid.
+    pub fn read<F>(read_mem: F) -> Result<Self, ReturnDataError>
+    where
+        F: Fn(u64) -> Option<Word>,
+    {
+        let len = read_mem(RETURNDATA_ADDR).unwrap_or([Felt::ZERO; 4])[0].as_int();
+        if len > MAX_RETURNDATA_LEN {
+            return Err(ReturnDataError::InvalidLength(len));
+        }
+
+        let words = (0..len)
+            .map(|i| read_mem(RETURNDATA_ADDR + 1 + i).unwrap_or([Felt::ZERO; 4]))
+            .collect();
+        Ok(Self { words })
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the number of words in the returndata region.
+    pub fn len(&self) -> usize {
+        self.words.len()
+    }
+
+    /// Returns true if no words were written to the returndata region.
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    /// Returns the words of the returndata region.
+    pub fn words(&self) -> &[Word] {
+        &self.words
+    }
+
+    /// Returns a commitment to the returndata region computed in the same way as
+    /// `std::returndata::commit` procedure computes it.
+    pub fn commitment(&self) -> Word {
+        self.words.iter().fold([Felt::ZERO; 4], |acc, word| {
+            let mut elements = [Felt::ZERO; 8];
+            elements[..4].copy_from_slice(&acc);
+            elements[4..].copy_from_slice(word);
+            hash_elements(&elements).into()
+        })
+    }
+
+    /// Checks the returndata region against the commitment and the length left at the top of the
+    /// output stack by `std::returndata::commit` procedure. `stack` is expected to be ordered
+    /// from the top of the stack.
+    ///
+    /// # Errors
+    /// Returns an error if the stack does not contain a commitment to this returndata region.
+    pub fn verify(&self, stack: &[Felt]) -> Result<(), ReturnDataError> {
+        if stack.len() < 5 {
+            return Err(ReturnDataError::CommitmentMismatch);
+        }
+
+        let len = stack[4].as_int();
+        if len != self.len() as u64 {
+            return Err(ReturnDataError::LengthMismatch(len, self.len() as u64));
+        }
+
+        // words are placed onto the stack in reverse order
+        let mut commitment = self.commitment();
+        commitment.reverse();
+        if commitment != stack[..4] {
+            return Err(ReturnDataError::CommitmentMismatch);
+        }
+
+        Ok(())
+    }
+
+    /// Returns a reader which decodes values from this returndata region in the order in which
+    /// they were written.
+    pub fn reader(&self) -> ReturnDataReader<'_> {
+        ReturnDataReader {
+            words: &self.words,
+            pos: 0,
+        }
+    }
+}
+
+// RETURN DATA READER
+// ================================================================================================
+
+/// Decodes typed values from a [ReturnData] region.
+///
+/// Each value occupies a single word of the region; values must be read using the methods which
+/// correspond to the `std::returndata` procedures used to write them.
+pub struct ReturnDataReader<'a> {
+    words: &'a [Word],
+    pos: usize,
+}
+
+impl<'a> ReturnDataReader<'a> {
+    /// Returns the number of words which have not been read yet.
+    pub fn remaining(&self) -> usize {
+        self.words.len() - self.pos
+    }
+
+    /// Reads a word written by `std::returndata::write_word`.
+    pub fn read_word(&mut self) -> Result<Word, ReturnDataError> {
+        let word = *self
+            .words
+            .get(self.pos)
+            .ok_or(ReturnDataError::UnexpectedEnd(self.pos))?;
+        self.pos += 1;
+        Ok(word)
+    }
+
+    /// Reads a field element written by `std::returndata::write_felt`.
+    pub fn read_felt(&mut self) -> Result<Felt, ReturnDataError> {
+        let pos = self.pos;
+        let word = self.read_word()?;
+        if word[1..].iter().any(|&v| v != Felt::ZERO) {
+            return Err(ReturnDataError::InvalidValue(pos, "field element"));
+        }
+        Ok(word[0])
+    }
+
+    /// Reads a u32 value written by `std::returndata::write_u32`.
+    pub fn read_u32(&mut self) -> Result<u32, ReturnDataError> {
+        let pos = self.pos;
+        let value = self.read_felt()?.as_int();
+        value
+            .try_into()
+            .map_err(|_| ReturnDataError::InvalidValue(pos, "u32"))
+    }
+
+    /// Reads a u64 value written by `std::returndata::write_u64`.
+    pub fn read_u64(&mut self) -> Result<u64, ReturnDataError> {
+        let pos = self.pos;
+        let word = self.read_word()?;
+        let (lo, hi) = (word[0].as_int(), word[1].as_int());
+        if lo > u32::MAX as u64
+            || hi > u32::MAX as u64
+            || word[2..].iter().any(|&v| v != Felt::ZERO)
+        {
+            return Err(ReturnDataError::InvalidValue(pos, "u64"));
+        }
+        Ok((hi << 32) | lo)
+    }
+}
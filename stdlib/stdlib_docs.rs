@@ -8,9 +8,9 @@ use std::path::Path;
 // CONSTANTS
 // ================================================================================================
 
-pub const COMMENT_PREFIX: &str = "#";
+pub const DOC_COMMENT_PREFIX: &str = "#!";
 pub const FUNC_PREFIX: &str = "export.";
-pub const MODULE_COMMENT_PREFIX: &str = "#!";
+pub const PROC_PREFIX: &str = "proc.";
 
 /// Holds a stdlib function name and comments for docs purposes.
 pub struct Function {
@@ -104,9 +104,10 @@ impl Renderer for MarkdownRenderer {
 #[derive(PartialEq)]
 enum AsmSourceState {
     Empty,
-    Comment,
+    DocComment,
     Func,
-    ModuleComment,
+    Proc,
+    Other,
 }
 
 // Writes Miden standard library modules documentation markdown files based on the available modules and comments.
@@ -132,9 +133,13 @@ fn render_docs(stdlib: &Stdlib, output_dir: &str) {
 }
 
 // Parses the namespace and source file into stdlib Module.
+//
+// A block of doc comments immediately preceding an exported procedure documents the procedure. A
+// block of doc comments followed by an empty line before any procedure is declared documents the
+// module.
 fn parse_module(ns: String, source: String, stdlib: &mut Stdlib) {
-    let current_state = AsmSourceState::Empty;
     let mut comments = Vec::<String>::new();
+    let mut seen_procs = false;
 
     let module = stdlib
         .modules
@@ -142,29 +147,27 @@ fn parse_module(ns: String, source: String, stdlib: &mut Stdlib) {
         .or_insert_with(|| Module::new(ns.clone()));
 
     for line in source.lines() {
-        let new_state = parse_new_state(line);
-        if new_state != current_state {
-            match new_state {
-                AsmSourceState::Func => {
-                    let func_name = remove_prefix(FUNC_PREFIX, line);
-                    module.functions.push(Function {
-                        name: func_name,
-                        comments: comments.clone(),
-                    });
-                    comments.clear();
-                }
-                AsmSourceState::Comment => comments.push(remove_prefix(COMMENT_PREFIX, line)),
-                AsmSourceState::Empty => comments.clear(),
-                AsmSourceState::ModuleComment => module
-                    .comments
-                    .push(remove_prefix(MODULE_COMMENT_PREFIX, line)),
+        match parse_new_state(line) {
+            AsmSourceState::DocComment => {
+                comments.push(remove_prefix(DOC_COMMENT_PREFIX, line));
             }
-        } else {
-            match new_state {
-                AsmSourceState::Comment => comments.push(remove_prefix(COMMENT_PREFIX, line)),
-                AsmSourceState::Empty => comments.clear(),
-                _ => (),
+            AsmSourceState::Func => {
+                let func_name = remove_prefix(FUNC_PREFIX, line);
+                module.functions.push(Function {
+                    name: func_name,
+                    comments: comments.clone(),
+                });
+                comments.clear();
+                seen_procs = true;
             }
+            AsmSourceState::Proc => {
+                comments.clear();
+                seen_procs = true;
+            }
+            AsmSourceState::Empty if !seen_procs => {
+                module.comments.append(&mut comments);
+            }
+            AsmSourceState::Empty | AsmSourceState::Other => comments.clear(),
         }
     }
 }
@@ -173,14 +176,16 @@ fn parse_module(ns: String, source: String, stdlib: &mut Stdlib) {
 // ================================================================================================
 
 fn parse_new_state(line: &str) -> AsmSourceState {
-    if line.starts_with(MODULE_COMMENT_PREFIX) {
-        AsmSourceState::ModuleComment
-    } else if line.starts_with(COMMENT_PREFIX) {
-        AsmSourceState::Comment
+    if line.starts_with(DOC_COMMENT_PREFIX) {
+        AsmSourceState::DocComment
     } else if line.starts_with(FUNC_PREFIX) {
         AsmSourceState::Func
-    } else {
+    } else if line.starts_with(PROC_PREFIX) {
+        AsmSourceState::Proc
+    } else if line.trim().is_empty() {
         AsmSourceState::Empty
+    } else {
+        AsmSourceState::Other
     }
 }
 
@@ -0,0 +1,655 @@
+use super::build_runner;
+use assembly::{Assembler, AssemblyError, SourceOutline};
+use core::fmt;
+use proptest::{
+    prelude::*,
+    sample::{select, subsequence, Index},
+    strategy::{Union, ValueTree},
+    test_runner::{TestCaseError, TestError as RunnerError},
+};
+use std::panic::{self, AssertUnwindSafe};
+use vm_core::{
+    code_blocks::{walk_block, Visitor},
+    utils::{Deserializable, Serializable, SliceReader},
+    Decorator, Operation, Program,
+};
+
+// CONSTANTS
+// ================================================================================================
+
+/// Number of randomly generated sources checked by default.
+pub const DEFAULT_NUM_SOURCES: u32 = 256;
+
+/// Instructions used to generate sources, other than instructions with randomly drawn immediate
+/// values.
+const INSTRUCTIONS: &[&str] = &[
+    "add",
+    "sub",
+    "mul",
+    "div",
+    "neg",
+    "inv",
+    "checked_pow2",
+    "exp",
+    "not",
+    "and",
+    "or",
+    "xor",
+    "eq",
+    "neq",
+    "lt",
+    "lte",
+    "gt",
+    "eqw",
+    "drop",
+    "dropw",
+    "padw",
+    "dup",
+    "dupw",
+    "swap",
+    "swapw",
+    "swapdw",
+    "movup.2",
+    "movdn.3",
+    "movupw.2",
+    "cswap",
+    "cdrop",
+    "assert",
+    "assertz",
+    "assert_eq",
+    "u32assert",
+    "u32split",
+    "u32checked_add",
+    "u32wrapping_mul",
+    "u32overflowing_add3",
+    "u32checked_div",
+    "u32checked_and",
+    "u32unchecked_shr",
+    "u32checked_min",
+    "push.mem",
+    "pop.mem.3",
+    "pushw.mem.1",
+    "storew.mem",
+    "loadw.mem.2",
+    "push.env.sdepth",
+    "push.adv.1",
+    "adv.u64div",
+    "rphash",
+    "rpperm",
+    "mtree_get",
+    "push.1.2.3",
+    "push.0x10",
+];
+
+/// Standard library modules which can be imported by generated sources, together with some of
+/// their procedures.
+const MODULES: &[(&str, &[&str])] = &[
+    (
+        "std::math::u64",
+        &[
+            "wrapping_add",
+            "checked_eq",
+            "overflowing_sub",
+            "unchecked_lt",
+        ],
+    ),
+    ("std::crypto::hashes::blake3", &["hash"]),
+];
+
+/// Tokens which are substituted for tokens of generated sources to produce malformed sources.
+const MUTATIONS: &[&str] = &[
+    "begin",
+    "end",
+    "if.true",
+    "else",
+    "while.true",
+    "repeat.0",
+    "proc.p0",
+    "export.p1",
+    "exec.p2",
+    "call.p0",
+    "use.std::math::u64",
+    "const.A=1",
+    "push.",
+    "push.A",
+    "dup.16",
+    "movup.1",
+    "push.18446744069414584321",
+    "u32checked_shl.32",
+    "#",
+    "loc_store.4294967296",
+    "",
+];
+
+// DIFFERENTIAL CHECK
+// ================================================================================================
+
+/// Checks that all front ends of the assembler agree on the provided source, and that none of
+/// them panics.
+///
+/// The source is processed by the assembler (both in release and in debug mode), by the
+/// canonicalizer, and by the [SourceOutline] scanner used by editor tooling. If the source is a
+/// valid program or module, the following must hold:
+/// - the outline recognizes the source as a program or as a module, respectively, and all of its
+///   procedures are terminated;
+/// - the program compiles to the same MAST regardless of debug mode;
+/// - the source can be canonicalized, the canonical form compiles to the same program or module
+///   as the source, has the same outline (up to labels of private procedures), and is its own
+///   canonical form;
+/// - all operations and decorators of the compiled program survive a serialization round trip.
+///
+/// Malformed sources must be rejected with an error by the assembler; the canonicalizer and the
+/// outline scanner may accept them, but must not panic.
+///
+/// # Errors
+/// Returns the first divergence found.
+pub fn check_source(source: &str) -> Result<(), Divergence> {
+    let outline = catch_panic(Stage::Outline, || SourceOutline::parse(source))?;
+    let program = catch_panic(Stage::Compile, || Assembler::default().compile(source))?;
+
+    // the canonicalizer is run on malformed sources too, but only to check that it does not panic
+    let canonical = catch_panic(Stage::Canonicalize, || {
+        Assembler::default().canonicalize(source)
+    })?;
+
+    let is_program = match program {
+        Ok(program) => {
+            let debug_program =
+                catch_panic(Stage::DebugCompile, || Assembler::new(true).compile(source))?
+                    .map_err(Divergence::DebugBuildRejected)?;
+            if program.hash() != debug_program.hash() {
+                return Err(Divergence::DebugBuildMismatch);
+            }
+            check_round_trip(&debug_program)?;
+            true
+        }
+        // a source which is not a program can still be a valid module
+        Err(_) => {
+            let module = catch_panic(Stage::Compile, || Assembler::default().check_module(source))?;
+            if module.is_err() {
+                return Ok(());
+            }
+            false
+        }
+    };
+
+    if outline.is_program() != is_program {
+        return Err(Divergence::OutlineMismatch(format!(
+            "the outline recognizes the source as a {}",
+            if outline.is_program() {
+                "program"
+            } else {
+                "module"
+            }
+        )));
+    }
+    if let Some(proc) = outline.procedures().iter().find(|p| p.end_pos().is_none()) {
+        return Err(Divergence::OutlineMismatch(format!(
+            "procedure {} is not terminated",
+            proc.label()
+        )));
+    }
+
+    let canonical = canonical.map_err(Divergence::CanonicalFormRejected)?;
+    let canonical_outline = SourceOutline::parse(&canonical);
+    if signatures(&outline) != signatures(&canonical_outline)
+        || outline.imports() != canonical_outline.imports()
+    {
+        return Err(Divergence::OutlineMismatch(
+            "the canonical form declares different imports or procedures".to_string(),
+        ));
+    }
+    let recanonical = catch_panic(Stage::Canonicalize, || {
+        Assembler::default().canonicalize(&canonical)
+    })?
+    .map_err(Divergence::CanonicalFormRejected)?;
+    if recanonical != canonical {
+        return Err(Divergence::NotIdempotent {
+            canonical,
+            recanonical,
+        });
+    }
+    Ok(())
+}
+
+// DIFFERENTIAL TEST
+// ================================================================================================
+
+/// A differential test of the assembler front ends against randomly generated sources.
+///
+/// Sources are generated from a grammar of Miden assembly programs (see [source_strategy()]) and
+/// checked via [check_source()]. When a divergence is found, the source is shrunk, and the
+/// smallest source found which still causes a divergence is reported.
+pub struct DifferentialTest {
+    num_sources: u32,
+    seed: Option<u64>,
+}
+
+impl DifferentialTest {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a new differential test checking [DEFAULT_NUM_SOURCES] sources.
+    pub fn new() -> Self {
+        Self {
+            num_sources: DEFAULT_NUM_SOURCES,
+            seed: None,
+        }
+    }
+
+    /// Sets the number of randomly generated sources.
+    pub fn with_sources(mut self, num_sources: u32) -> Self {
+        self.num_sources = num_sources;
+        self
+    }
+
+    /// Sets the seed used to generate the sources; tests with the same seed check the same
+    /// sources. When not set, different sources are checked every time a test is run.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    // TEST RUNNER
+    // --------------------------------------------------------------------------------------------
+
+    /// Checks all generated sources.
+    ///
+    /// # Errors
+    /// Returns the smallest source found for which the front ends diverge.
+    pub fn check(&self) -> Result<(), DivergentSource> {
+        let runner = &mut build_runner(self.num_sources, self.seed);
+        let result = runner.run(&source_strategy(), |source| {
+            check_source(&source).map_err(|divergence| TestCaseError::fail(divergence.to_string()))
+        });
+
+        match result {
+            Ok(()) => Ok(()),
+            Err(RunnerError::Fail(_, source)) => {
+                let divergence = check_source(&source).expect_err("divergence not reproduced");
+                Err(DivergentSource { source, divergence })
+            }
+            Err(RunnerError::Abort(reason)) => panic!("differential test aborted: {}", reason),
+        }
+    }
+}
+
+impl Default for DifferentialTest {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// SOURCE GENERATION
+// ================================================================================================
+
+/// Returns a strategy which generates Miden assembly sources.
+///
+/// Most generated sources are programs consisting of imports, constants, procedures, and a
+/// program body with nested control flow. Instructions of these programs refer only to declared
+/// constants, procedures, and locals, although some of them are drawn with immediate values which
+/// are out of range. The remaining sources are produced from such programs by replacing,
+/// duplicating, or removing a single token, so that error paths of the front ends are exercised
+/// as well.
+pub fn source_strategy() -> impl Strategy<Value = String> {
+    let program = (
+        subsequence(MODULES, 0..=MODULES.len()),
+        prop::collection::vec(0u64..100, 0..3),
+        prop::collection::vec(0u32..3, 0..3),
+    )
+        .prop_flat_map(|(imports, constants, proc_locals)| {
+            let scope = Scope {
+                imports: imports.clone(),
+                num_constants: constants.len(),
+                num_procs: 0,
+                num_locals: 0,
+            };
+            // a procedure can invoke only the procedures declared before it
+            let procedures = proc_locals
+                .iter()
+                .enumerate()
+                .map(|(i, &num_locals)| {
+                    body_strategy(&Scope {
+                        num_procs: i,
+                        num_locals,
+                        ..scope.clone()
+                    })
+                })
+                .collect::<Vec<_>>();
+            let body = body_strategy(&Scope {
+                num_procs: proc_locals.len(),
+                ..scope
+            });
+            (
+                Just(imports),
+                Just(constants),
+                Just(proc_locals),
+                procedures,
+                body,
+            )
+        })
+        .prop_map(|(imports, constants, proc_locals, procedures, body)| {
+            let mut source = String::new();
+            for (path, _) in imports {
+                source.push_str(&format!("use.{}\n", path));
+            }
+            for (i, value) in constants.iter().enumerate() {
+                source.push_str(&format!("const.C{}={}\n", i, value));
+            }
+            for (i, (num_locals, body)) in proc_locals.iter().zip(procedures).enumerate() {
+                source.push_str(&format!("# procedure {}\nproc.p{}.{}\n", i, i, num_locals));
+                write_body(&mut source, &body, 1);
+                source.push_str("end\n");
+            }
+            source.push_str("begin\n");
+            write_body(&mut source, &body, 1);
+            source.push_str("end\n");
+            source
+        })
+        .boxed();
+
+    prop_oneof![
+        3 => program.clone(),
+        1 => (program, any::<Index>(), select(MUTATIONS), 0u8..3).prop_map(mutate),
+    ]
+}
+
+/// Returns a source generated via [source_strategy()] from the specified seed; the same seed always
+/// yields the same source.
+pub fn generate_source(seed: u64) -> String {
+    let mut runner = build_runner(1, Some(seed));
+    source_strategy()
+        .new_tree(&mut runner)
+        .expect("failed to generate source")
+        .current()
+}
+
+/// Declarations which can be referenced by instructions of a generated procedure or program body.
+#[derive(Clone, Debug)]
+struct Scope {
+    imports: Vec<(&'static str, &'static [&'static str])>,
+    num_constants: usize,
+    num_procs: usize,
+    num_locals: u32,
+}
+
+/// A statement of a generated program body: either a single instruction or a control flow block
+/// with nested statements.
+#[derive(Clone, Debug)]
+enum Statement {
+    Instruction(String),
+    Block(String, Vec<Statement>, Option<Vec<Statement>>),
+}
+
+fn body_strategy(scope: &Scope) -> impl Strategy<Value = Vec<Statement>> {
+    let mut instructions = vec![
+        (12, select(INSTRUCTIONS).prop_map(str::to_string).boxed()),
+        (1, any::<u64>().prop_map(|v| format!("push.{}", v)).boxed()),
+        (1, (0u32..17).prop_map(|i| format!("dup.{}", i)).boxed()),
+        (1, (2u32..17).prop_map(|i| format!("movup.{}", i)).boxed()),
+        (
+            1,
+            (0u32..33)
+                .prop_map(|i| format!("u32checked_shl.{}", i))
+                .boxed(),
+        ),
+    ];
+    if scope.num_constants > 0 {
+        let constants = 0..scope.num_constants;
+        instructions.push((1, constants.prop_map(|i| format!("push.C{}", i)).boxed()));
+    }
+    if scope.num_procs > 0 {
+        let procs = 0..scope.num_procs;
+        instructions.push((2, procs.prop_map(|i| format!("exec.p{}", i)).boxed()));
+    }
+    if scope.num_locals > 0 {
+        let locals = 0..scope.num_locals;
+        let local_ops = (
+            select(&["push.local", "pop.local", "storew.local"][..]),
+            locals,
+        );
+        instructions.push((
+            2,
+            local_ops
+                .prop_map(|(op, i)| format!("{}.{}", op, i))
+                .boxed(),
+        ));
+    }
+    for &(path, procs) in scope.imports.iter() {
+        let module = path.rsplit("::").next().expect("empty module path");
+        let procs = select(procs).prop_map(move |proc| format!("exec.{}::{}", module, proc));
+        instructions.push((1, procs.boxed()));
+    }
+    let instruction = Union::new_weighted(instructions).prop_map(Statement::Instruction);
+
+    let statement = instruction.prop_recursive(3, 24, 4, |inner| {
+        let body = prop::collection::vec(inner, 1..4);
+        prop_oneof![
+            (body.clone(), body.clone()).prop_map(|(on_true, on_false)| {
+                Statement::Block("if.true".to_string(), on_true, Some(on_false))
+            }),
+            body.clone()
+                .prop_map(|body| Statement::Block("if.true".to_string(), body, None)),
+            body.clone()
+                .prop_map(|body| Statement::Block("while.true".to_string(), body, None)),
+            (1u32..4, body).prop_map(|(n, body)| Statement::Block(
+                format!("repeat.{}", n),
+                body,
+                None
+            )),
+        ]
+    });
+    prop::collection::vec(statement, 1..6)
+}
+
+/// Appends the provided statements to the source, indented to the specified depth.
+fn write_body(source: &mut String, body: &[Statement], depth: usize) {
+    let indent = "    ".repeat(depth);
+    for statement in body {
+        match statement {
+            Statement::Instruction(instruction) => {
+                source.push_str(&format!("{}{}\n", indent, instruction));
+            }
+            Statement::Block(header, body, on_false) => {
+                source.push_str(&format!("{}{}\n", indent, header));
+                write_body(source, body, depth + 1);
+                if let Some(on_false) = on_false {
+                    source.push_str(&format!("{}else\n", indent));
+                    write_body(source, on_false, depth + 1);
+                }
+                source.push_str(&format!("{}end\n", indent));
+            }
+        }
+    }
+}
+
+/// Replaces (kind 0), duplicates (kind 1), or removes (kind 2) a single token of the source.
+///
+/// Line breaks are preserved so that comments do not extend over the rest of the source.
+fn mutate((source, index, token, kind): (String, Index, &str, u8)) -> String {
+    let mut lines = source
+        .lines()
+        .map(|line| line.split_whitespace().collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    let positions = lines
+        .iter()
+        .enumerate()
+        .flat_map(|(i, line)| (0..line.len()).map(move |j| (i, j)))
+        .collect::<Vec<_>>();
+    let (i, j) = positions[index.index(positions.len())];
+    match kind {
+        0 => lines[i][j] = token,
+        1 => {
+            let duplicate = lines[i][j];
+            lines[i].insert(j, duplicate);
+        }
+        _ => {
+            lines[i].remove(j);
+        }
+    }
+    lines
+        .iter()
+        .map(|line| line.join(" "))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+// DIVERGENCE
+// ================================================================================================
+
+/// A front end of the assembler processed by [check_source()].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Stage {
+    Outline,
+    Canonicalize,
+    Compile,
+    DebugCompile,
+}
+
+impl fmt::Display for Stage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Outline => write!(f, "outline"),
+            Self::Canonicalize => write!(f, "canonicalize"),
+            Self::Compile => write!(f, "compile"),
+            Self::DebugCompile => write!(f, "compile (debug mode)"),
+        }
+    }
+}
+
+/// A disagreement between the front ends of the assembler found by [check_source()].
+#[derive(Debug)]
+pub enum Divergence {
+    /// A front end panicked while processing the source.
+    Panic(Stage, String),
+    /// The source compiles in release mode but not in debug mode.
+    DebugBuildRejected(AssemblyError),
+    /// The source compiles to different programs in release and in debug mode.
+    DebugBuildMismatch,
+    /// The outline of the source does not match the compiled program or module.
+    OutlineMismatch(String),
+    /// The source compiles, but its canonical form could not be produced or compiled.
+    CanonicalFormRejected(AssemblyError),
+    /// The canonical form of the source differs from the canonical form of the canonical form.
+    NotIdempotent {
+        canonical: String,
+        recanonical: String,
+    },
+    /// An operation or a decorator of the compiled program changed in a serialization round trip.
+    RoundTrip(String),
+}
+
+impl fmt::Display for Divergence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Panic(stage, message) => write!(f, "{} panicked: {}", stage, message),
+            Self::DebugBuildRejected(err) => {
+                write!(f, "debug build rejected a valid source: {}", err)
+            }
+            Self::DebugBuildMismatch => {
+                write!(f, "debug and release builds compile to different programs")
+            }
+            Self::OutlineMismatch(reason) => write!(f, "outline mismatch: {}", reason),
+            Self::CanonicalFormRejected(err) => {
+                write!(f, "failed to canonicalize a valid source: {}", err)
+            }
+            Self::NotIdempotent {
+                canonical,
+                recanonical,
+            } => write!(
+                f,
+                "canonical form is not idempotent:\n{}\nwas canonicalized to:\n{}",
+                canonical, recanonical
+            ),
+            Self::RoundTrip(item) => write!(f, "serialization round trip changed {}", item),
+        }
+    }
+}
+
+/// A source for which the front ends of the assembler diverge, together with the divergence.
+#[derive(Debug)]
+pub struct DivergentSource {
+    source: String,
+    divergence: Divergence,
+}
+
+impl DivergentSource {
+    /// Returns the divergent source.
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// Returns the divergence found for the source.
+    pub fn divergence(&self) -> &Divergence {
+        &self.divergence
+    }
+}
+
+impl fmt::Display for DivergentSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} for source:\n{}", self.divergence, self.source)
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Invokes the provided closure, and converts a panic into a divergence of the specified stage.
+fn catch_panic<T, F: FnOnce() -> T>(stage: Stage, f: F) -> Result<T, Divergence> {
+    panic::catch_unwind(AssertUnwindSafe(f)).map_err(|payload| {
+        let message = match payload.downcast::<String>() {
+            Ok(message) => *message,
+            Err(payload) => match payload.downcast::<&str>() {
+                Ok(message) => message.to_string(),
+                Err(_) => "unknown panic".to_string(),
+            },
+        };
+        Divergence::Panic(stage, message)
+    })
+}
+
+/// Returns the signatures of procedures declared in the outline, with the labels of private
+/// procedures omitted (they are renamed by the canonicalizer).
+fn signatures(outline: &SourceOutline) -> Vec<(Option<&str>, u32)> {
+    outline
+        .procedures()
+        .iter()
+        .map(|proc| (proc.is_export().then(|| proc.label()), proc.num_locals()))
+        .collect()
+}
+
+/// Checks that all operations and decorators of the program survive a serialization round trip.
+fn check_round_trip(program: &Program) -> Result<(), Divergence> {
+    struct RoundTrip(Option<String>);
+
+    impl RoundTrip {
+        fn check<T>(&mut self, item: &T)
+        where
+            T: Serializable + Deserializable + PartialEq + fmt::Debug,
+        {
+            let bytes = item.to_bytes();
+            let result = T::read_from(&mut SliceReader::new(&bytes));
+            if self.0.is_none() && result.ok().as_ref() != Some(item) {
+                self.0 = Some(format!("{:?}", item));
+            }
+        }
+    }
+
+    impl Visitor for RoundTrip {
+        fn visit_op(&mut self, op: &Operation) {
+            self.check(op);
+        }
+
+        fn visit_decorator(&mut self, decorator: &Decorator) {
+            if let Decorator::AsmOp(asm_op) = decorator {
+                self.check(asm_op);
+            }
+        }
+    }
+
+    let mut round_trip = RoundTrip(None);
+    walk_block(&mut round_trip, program.root());
+    round_trip
+        .0
+        .map_or(Ok(()), |item| Err(Divergence::RoundTrip(item)))
+}
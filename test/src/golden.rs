@@ -0,0 +1,281 @@
+use core::fmt;
+use processor::{ColumnGroup, ColumnSelection, ExecutionTrace};
+use std::{env, fs, io, path::PathBuf};
+use vm_core::{StarkField, Word};
+
+// CONSTANTS
+// ================================================================================================
+
+/// Environment variable which enables blessing: when it is set to a value other than an empty
+/// string or `0`, golden trace checks write the digests of the checked traces into their golden
+/// files instead of comparing against them.
+pub const BLESS_ENV_VAR: &str = "MIDEN_BLESS";
+
+/// Header written at the start of every golden file.
+const GOLDEN_HEADER: &str =
+    "# Golden trace digests; to update, re-run the test with MIDEN_BLESS=1 set in the environment";
+
+/// Name of the golden file entry holding the padded length of the trace.
+const TRACE_LEN_ENTRY: &str = "trace_len";
+
+// GOLDEN TRACE
+// ================================================================================================
+
+/// A golden-trace regression check.
+///
+/// A golden file records the length of an execution trace and a digest of every selected column
+/// range (see [processor::TraceDump::digest()]). A trace matches the golden file if all of these
+/// are the same; thus, any change to the VM which alters the semantics of the executed program or
+/// the layout of the trace is detected, and the mismatching entries point to the affected part of
+/// the trace. By default, each column group of the main trace segment is recorded separately.
+///
+/// Golden files are created and updated by running the check with the [BLESS_ENV_VAR]
+/// environment variable set (e.g., `MIDEN_BLESS=1 cargo test`). The changes to the golden files
+/// can then be reviewed and committed together with the change to the VM which caused them.
+pub struct GoldenTrace {
+    path: PathBuf,
+    columns: Vec<ColumnSelection>,
+}
+
+impl GoldenTrace {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a check of traces against the golden file at the specified path.
+    pub fn new<P: Into<PathBuf>>(path: P) -> Self {
+        Self {
+            path: path.into(),
+            columns: ColumnGroup::MAIN_SEGMENT
+                .into_iter()
+                .map(ColumnSelection::new)
+                .collect(),
+        }
+    }
+
+    /// Restricts this check to the specified columns of the trace; a digest is recorded for each
+    /// of the selections. This can be used to exclude parts of the trace which are expected to
+    /// change (e.g., the chiplets when testing the stack).
+    pub fn with_columns(mut self, columns: Vec<ColumnSelection>) -> Self {
+        self.columns = columns;
+        self
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the path to the golden file.
+    pub fn path(&self) -> &PathBuf {
+        &self.path
+    }
+
+    // CHECKS
+    // --------------------------------------------------------------------------------------------
+
+    /// Checks that the specified trace matches the golden file, or writes the golden file for the
+    /// trace if blessing is enabled via the [BLESS_ENV_VAR] environment variable.
+    ///
+    /// # Errors
+    /// Returns an error if the golden file does not exist, cannot be read or parsed, or if the
+    /// trace does not match it.
+    pub fn check(&self, trace: &ExecutionTrace) -> Result<(), GoldenError> {
+        if is_blessing_enabled() {
+            return self.bless(trace);
+        }
+
+        let golden = match fs::read_to_string(&self.path) {
+            Ok(golden) => golden,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {
+                return Err(GoldenError::MissingGolden(self.path.clone()))
+            }
+            Err(err) => return Err(GoldenError::IoError(self.path.clone(), err)),
+        };
+        let expected = parse_golden(&golden)
+            .map_err(|line| GoldenError::MalformedGolden(self.path.clone(), line))?;
+        let actual = self.build_entries(trace);
+
+        // entries are compared in the order of the actual entries, followed by the entries which
+        // are present only in the golden file
+        let mut mismatches = Vec::new();
+        for (name, value) in actual.iter() {
+            match expected
+                .iter()
+                .find(|(expected_name, _)| expected_name == name)
+            {
+                Some((_, expected_value)) if expected_value == value => (),
+                expected_entry => mismatches.push(EntryMismatch {
+                    name: name.clone(),
+                    expected: expected_entry.map(|(_, value)| value.clone()),
+                    actual: Some(value.clone()),
+                }),
+            }
+        }
+        for (name, value) in expected.iter() {
+            if !actual.iter().any(|(actual_name, _)| actual_name == name) {
+                mismatches.push(EntryMismatch {
+                    name: name.clone(),
+                    expected: Some(value.clone()),
+                    actual: None,
+                });
+            }
+        }
+
+        if mismatches.is_empty() {
+            Ok(())
+        } else {
+            Err(GoldenError::TraceMismatch(self.path.clone(), mismatches))
+        }
+    }
+
+    /// Writes the golden file for the specified trace, overwriting the existing file if there is
+    /// one. Missing parent directories are created.
+    ///
+    /// # Errors
+    /// Returns an error if the golden file cannot be written.
+    pub fn bless(&self, trace: &ExecutionTrace) -> Result<(), GoldenError> {
+        let mut golden = format!("{}\n", GOLDEN_HEADER);
+        for (name, value) in self.build_entries(trace) {
+            golden.push_str(&format!("{} = {}\n", name, value));
+        }
+
+        let io_error = |err| GoldenError::IoError(self.path.clone(), err);
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir).map_err(io_error)?;
+        }
+        fs::write(&self.path, golden).map_err(io_error)
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the entries of the golden file for the specified trace.
+    fn build_entries(&self, trace: &ExecutionTrace) -> Vec<(String, String)> {
+        let trace_len = trace.trace_len_summary().padded_trace_len();
+        let mut entries = vec![(TRACE_LEN_ENTRY.to_string(), trace_len.to_string())];
+        for selection in self.columns.iter() {
+            let digest: Word = trace
+                .dump()
+                .with_columns(vec![selection.clone()])
+                .digest()
+                .into();
+            let hex = digest
+                .iter()
+                .map(|element| format!("{:016x}", element.as_int()))
+                .collect::<String>();
+            entries.push((selection.to_string(), hex));
+        }
+        entries
+    }
+}
+
+// GOLDEN ERROR
+// ================================================================================================
+
+/// An error returned when an execution trace cannot be checked against a golden file, or does not
+/// match it.
+#[derive(Debug)]
+pub enum GoldenError {
+    IoError(PathBuf, io::Error),
+    MalformedGolden(PathBuf, usize),
+    MissingGolden(PathBuf),
+    TraceMismatch(PathBuf, Vec<EntryMismatch>),
+}
+
+impl fmt::Display for GoldenError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::IoError(path, err) => {
+                write!(
+                    f,
+                    "failed to access golden file {}: {}",
+                    path.display(),
+                    err
+                )
+            }
+            Self::MalformedGolden(path, line) => {
+                write!(
+                    f,
+                    "malformed golden file {} at line {}",
+                    path.display(),
+                    line
+                )
+            }
+            Self::MissingGolden(path) => write!(
+                f,
+                "golden file {} does not exist; run with {}=1 to create it",
+                path.display(),
+                BLESS_ENV_VAR
+            ),
+            Self::TraceMismatch(path, mismatches) => {
+                writeln!(f, "trace does not match golden file {}:", path.display())?;
+                for mismatch in mismatches {
+                    writeln!(f, "  {}", mismatch)?;
+                }
+                write!(
+                    f,
+                    "if the change is intended, run with {}=1 to update the golden file",
+                    BLESS_ENV_VAR
+                )
+            }
+        }
+    }
+}
+
+/// An entry of a golden file which does not match the checked trace.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EntryMismatch {
+    name: String,
+    expected: Option<String>,
+    actual: Option<String>,
+}
+
+impl EntryMismatch {
+    /// Returns the name of the entry, i.e., `trace_len` or a column selection such as `stack`.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the value recorded in the golden file, or None if the golden file does not contain
+    /// the entry.
+    pub fn expected(&self) -> Option<&str> {
+        self.expected.as_deref()
+    }
+
+    /// Returns the value computed for the checked trace, or None if the entry is not checked.
+    pub fn actual(&self) -> Option<&str> {
+        self.actual.as_deref()
+    }
+}
+
+impl fmt::Display for EntryMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: expected {}, got {}",
+            self.name,
+            self.expected.as_deref().unwrap_or("<missing>"),
+            self.actual.as_deref().unwrap_or("<missing>")
+        )
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns true if the [BLESS_ENV_VAR] environment variable enables blessing.
+pub fn is_blessing_enabled() -> bool {
+    env::var_os(BLESS_ENV_VAR).map_or(false, |value| !value.is_empty() && value != "0")
+}
+
+/// Parses `name = value` entries of a golden file, skipping empty lines and comments. On failure,
+/// returns the (1-based) number of the malformed line.
+fn parse_golden(golden: &str) -> Result<Vec<(String, String)>, usize> {
+    golden
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty() && !line.trim_start().starts_with('#'))
+        .map(|(i, line)| match line.split_once('=') {
+            Some((name, value)) => Ok((name.trim().to_string(), value.trim().to_string())),
+            None => Err(i + 1),
+        })
+        .collect()
+}
@@ -0,0 +1,330 @@
+use super::GoldenTrace;
+use air::ProofOptions;
+use assembly::Assembler;
+use processor::{ExecutionError, ExecutionTrace, Process, Program, ProgramInputs, VmStateIterator};
+use proptest::prop_assert_eq;
+use std::{env, path::Path};
+use vm_core::{Felt, FieldElement, MIN_STACK_DEPTH};
+
+// TEST HANDLER
+// ================================================================================================
+
+/// This is used to specify the expected error type when using Test to test errors.
+/// `Test::expect_error` will try to either compile or execute the test data, according to the
+/// provided TestError variant. Then it will validate that the resulting error contains the
+/// TestError variant's string slice.
+pub enum TestError<'a> {
+    AssemblyError(&'a str),
+    ExecutionError(&'a str),
+}
+
+/// This is a container for the data required to run tests, which allows for running several
+/// different types of tests.
+///
+/// Tests are usually built via the [build_test!](crate::build_test),
+/// [build_op_test!](crate::build_op_test), and [build_debug_test!](crate::build_debug_test)
+/// macros. Programs are compiled with the standard library available, so procedures imported from
+/// it via `use` can be invoked directly.
+///
+/// Types of valid result tests:
+/// - Execution test: check that running a program compiled from the given source has the
+///   specified results (final stack state and memory) for the given (optional) inputs.
+/// - Proptest: run an execution test inside a proptest.
+/// - Golden trace test: check that the execution trace matches the one recorded in a golden file.
+///
+/// Types of failure tests:
+/// - Assembly error test: check that attempting to compile the given source causes an
+/// AssemblyError which contains the specified substring.
+/// - Execution error test: check that running a program compiled from the given source causes
+///   an ExecutionError which contains the specified substring.
+pub struct Test {
+    pub source: String,
+    pub inputs: ProgramInputs,
+    pub in_debug_mode: bool,
+}
+
+impl Test {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+
+    /// Creates the simplest possible new test, with only a source string and no inputs.
+    pub fn new(source: &str, in_debug_mode: bool) -> Self {
+        Test {
+            source: String::from(source),
+            inputs: ProgramInputs::none(),
+            in_debug_mode,
+        }
+    }
+
+    // TEST METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Asserts that running the test for the expected TestError variant will result in an error
+    /// that contains the TestError's error substring in its error message.
+    pub fn expect_error(&self, error: TestError) {
+        match error {
+            TestError::AssemblyError(substr) => {
+                assert_eq!(
+                    std::panic::catch_unwind(|| self.compile())
+                        .err()
+                        .and_then(|a| { a.downcast_ref::<String>().map(|s| s.contains(substr)) }),
+                    Some(true)
+                );
+            }
+            TestError::ExecutionError(substr) => {
+                assert_eq!(
+                    std::panic::catch_unwind(|| self.execute().unwrap())
+                        .err()
+                        .and_then(|a| { a.downcast_ref::<String>().map(|s| s.contains(substr)) }),
+                    Some(true)
+                );
+            }
+        }
+    }
+
+    /// Builds a final stack from the provided stack-ordered array and asserts that executing the
+    /// test will result in the expected final stack state.
+    pub fn expect_stack(&self, final_stack: &[u64]) {
+        let expected = convert_to_stack(final_stack);
+        let result = self.get_last_stack_state();
+
+        assert_eq!(expected, result);
+    }
+
+    /// Executes the test and validates that the process memory has the elements of `expected_mem`
+    /// at address `mem_addr`.
+    pub fn expect_memory(&self, mem_addr: u64, expected_mem: &[u64]) {
+        let mut process = Process::new(self.inputs.clone());
+
+        // execute the test
+        let program = self.compile();
+        process.execute(&program).unwrap();
+
+        // validate the memory state
+        let mem_state = process.get_memory_value(mem_addr).unwrap();
+        let expected_mem: Vec<Felt> = expected_mem.iter().map(|&v| Felt::new(v)).collect();
+        assert_eq!(expected_mem, mem_state);
+    }
+
+    /// Executes the test and validates that the process memory has the elements of `expected_mem`
+    /// at address `mem_addr` and that the end of the stack execution trace matches the
+    /// `final_stack`.
+    pub fn expect_stack_and_memory(
+        &self,
+        final_stack: &[u64],
+        mem_addr: u64,
+        expected_mem: &[u64],
+    ) {
+        // validate the memory state
+        self.expect_memory(mem_addr, expected_mem);
+
+        // validate the stack state
+        self.expect_stack(final_stack);
+    }
+
+    /// Asserts that executing the test inside a proptest results in the expected final stack state.
+    /// The proptest will return a test failure instead of panicking if the assertion condition
+    /// fails.
+    pub fn prop_expect_stack(
+        &self,
+        final_stack: &[u64],
+    ) -> Result<(), proptest::test_runner::TestCaseError> {
+        let expected = convert_to_stack(final_stack);
+        let result = self.get_last_stack_state();
+
+        prop_assert_eq!(expected, result);
+
+        Ok(())
+    }
+
+    /// Asserts that executing the test results in the execution trace recorded in the golden file
+    /// at the specified path. Relative paths are resolved against the directory of the package
+    /// being tested (i.e., `CARGO_MANIFEST_DIR`). To create or update the golden file, run the
+    /// test with `MIDEN_BLESS=1` set in the environment (see [GoldenTrace]).
+    pub fn expect_golden_trace<P: AsRef<Path>>(&self, path: P) {
+        let path = match env::var_os("CARGO_MANIFEST_DIR") {
+            Some(dir) => Path::new(&dir).join(path),
+            None => path.as_ref().to_path_buf(),
+        };
+        let trace = self.execute().unwrap();
+
+        if let Err(err) = GoldenTrace::new(path).check(&trace) {
+            panic!("{}", err);
+        }
+    }
+
+    // UTILITY METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Compiles a test's source and returns the resulting Program.
+    pub fn compile(&self) -> Program {
+        let assembler = Assembler::new(self.in_debug_mode);
+        assembler
+            .compile(&self.source)
+            .expect("Failed to compile test source.")
+    }
+
+    /// Compiles the test's source to a Program and executes it with the tests inputs. Returns a
+    /// resulting execution trace or error.
+    pub fn execute(&self) -> Result<ExecutionTrace, ExecutionError> {
+        let program = self.compile();
+        processor::execute(&program, &self.inputs)
+    }
+
+    /// Compiles the test's code into a program, then generates and verifies a proof of execution
+    /// using the given public inputs and the specified number of stack outputs. When `test_fail`
+    /// is true, this function will force a failure by modifying the first output.
+    pub fn prove_and_verify(
+        &self,
+        pub_inputs: Vec<u64>,
+        num_stack_outputs: usize,
+        test_fail: bool,
+    ) {
+        let program = self.compile();
+        let (mut outputs, proof) = prover::prove(
+            &program,
+            &self.inputs,
+            num_stack_outputs,
+            &ProofOptions::default(),
+        )
+        .unwrap();
+
+        if test_fail {
+            outputs[0] += 1;
+            assert!(verifier::verify(program.hash(), &pub_inputs, &outputs, proof).is_err());
+        } else {
+            assert!(verifier::verify(program.hash(), &pub_inputs, &outputs, proof).is_ok());
+        }
+    }
+
+    /// Compiles the test's source to a Program and executes it with the tests inputs. Returns a
+    /// VmStateIterator that allows us to iterate through each clock cycle and inpsect the process
+    /// state.
+    pub fn execute_iter(&self) -> VmStateIterator {
+        let program = self.compile();
+        processor::execute_iter(&program, &self.inputs)
+    }
+
+    /// Returns the last state of the stack after executing a test.
+    pub fn get_last_stack_state(&self) -> [Felt; MIN_STACK_DEPTH] {
+        let trace = self.execute().unwrap();
+
+        trace.last_stack_state()
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Takes an array of u64 values and builds a stack, perserving their order and converting them to
+/// field elements.
+pub fn convert_to_stack(values: &[u64]) -> [Felt; MIN_STACK_DEPTH] {
+    let mut result = [Felt::ZERO; MIN_STACK_DEPTH];
+    for (&value, result) in values.iter().zip(result.iter_mut()) {
+        *result = Felt::new(value);
+    }
+    result
+}
+
+// MACROS TO BUILD TESTS
+// ================================================================================================
+
+/// Returns a Test struct in non debug mode from a string of one or more operations and any
+/// specified stack and advice inputs.
+///
+/// Parameters are expected in the following order:
+/// `source`, `stack_inputs` (optional), `advice_tape` (optional), `advice_sets` (optional)
+///
+/// * `source`: a string of one or more operations, e.g. "push.1 push.2".
+/// * `stack_inputs` (optional): the initial inputs which must be at the top of the stack before
+/// executing the `source`. Stack inputs can be provided independently without any advice inputs.
+/// * `advice_tape` (optional): the initial advice tape values. When provided, `stack_inputs` and
+/// `advice_sets` are also expected.
+/// * `advice_sets` (optional): the initial advice set values. When provided, `stack_inputs` and
+/// `advice_tape` are also expected.
+#[macro_export]
+macro_rules! build_op_test {
+    ($op_str:expr) => {{
+        let source = format!("begin {} end", $op_str);
+        $crate::build_test!(&source)
+    }};
+    ($op_str:expr, $($tail:tt)+) => {{
+        let source = format!("begin {} end", $op_str);
+        $crate::build_test!(&source, $($tail)+)
+    }};
+}
+
+/// Returns a Test struct in non debug mode from the provided source string, and any specified
+/// stack and advice inputs.
+///
+/// Parameters are expected in the following order:
+/// `source`, `stack_inputs` (optional), `advice_tape` (optional), `advice_sets` (optional)
+///
+/// * `source`: a well-formed source string.
+/// * `stack_inputs` (optional): the initial inputs which must be at the top of the stack before
+/// executing the `source`. Stack inputs can be provided independently without any advice inputs.
+/// * `advice_tape` (optional): the initial advice tape values. When provided, `stack_inputs` and
+/// `advice_sets` are also expected.
+/// * `advice_sets` (optional): the initial advice set values. When provided, `stack_inputs` and
+/// `advice_tape` are also expected.
+#[macro_export]
+macro_rules! build_test {
+    ($($params:tt)+) => {{
+        $crate::build_test_by_mode!(false, $($params)+)
+    }}
+}
+
+/// Returns a Test struct in debug mode from the provided source string and any specified stack
+/// and advice inputs.
+///
+/// Parameters are expected in the following order:
+/// `source`, `stack_inputs` (optional), `advice_tape` (optional), `advice_sets` (optional)
+///
+/// * `source`: a well-formed source string.
+/// * `stack_inputs` (optional): the initial inputs which must be at the top of the stack before
+/// executing the `source`. Stack inputs can be provided independently without any advice inputs.
+/// * `advice_tape` (optional): the initial advice tape values. When provided, `stack_inputs` and
+/// `advice_sets` are also expected.
+/// * `advice_sets` (optional): the initial advice set values. When provided, `stack_inputs` and
+/// `advice_tape` are also expected.
+#[macro_export]
+macro_rules! build_debug_test {
+    ($($params:tt)+) => {{
+        $crate::build_test_by_mode!(true, $($params)+)
+    }}
+}
+
+/// Returns a Test struct in the specified debug or non-debug mode using the provided source string
+/// and any specified stack and advice inputs.
+///
+/// Parameters start with a boolean flag, `in_debug_mode`, specifying whether the test is built in
+/// debug or non-debug mode. After that, they match the parameters of `build_test` and
+///`build_debug_test` macros.
+///
+/// This macro is an internal test builder, and is not intended to be called directly from tests.
+/// Instead, the build_test and build_debug_test wrappers should be used.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! build_test_by_mode {
+    ($in_debug_mode:expr, $source:expr) => {{
+        $crate::Test::new($source, $in_debug_mode)
+    }};
+    ($in_debug_mode:expr, $source:expr, $stack_inputs:expr) => {{
+        let inputs = $crate::ProgramInputs::new($stack_inputs, &[], vec![]).unwrap();
+
+        $crate::Test {
+            source: String::from($source),
+            inputs,
+            in_debug_mode: $in_debug_mode,
+        }
+    }};
+    ($in_debug_mode:expr, $source:expr, $stack_inputs:expr, $advice_tape:expr, $advice_sets:expr) => {{
+        let inputs = $crate::ProgramInputs::new($stack_inputs, $advice_tape, $advice_sets).unwrap();
+
+        $crate::Test {
+            source: String::from($source),
+            inputs,
+            in_debug_mode: $in_debug_mode,
+        }
+    }};
+}
@@ -0,0 +1,138 @@
+use core::{fmt, ops::RangeInclusive, str::FromStr};
+use vm_core::{Felt, StarkField};
+
+// INPUT RANGE
+// ================================================================================================
+
+/// An inclusive range of values from which a single stack input of a procedure is drawn.
+///
+/// All values in the range must be valid field elements. When a failing case is shrunk, the value
+/// is moved towards the lower bound of the range.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct InputRange {
+    min: u64,
+    max: u64,
+}
+
+impl InputRange {
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a range containing all values between `min` and `max` (both inclusive).
+    ///
+    /// # Panics
+    /// Panics if `min` is greater than `max`, or if `max` is not a valid field element.
+    pub fn new(min: u64, max: u64) -> Self {
+        assert!(min <= max, "invalid input range {}..={}", min, max);
+        assert!(
+            max < Felt::MODULUS,
+            "input range upper bound {} is not a valid field element",
+            max
+        );
+        Self { min, max }
+    }
+
+    /// Returns a range containing all values which fit into 32 bits.
+    pub fn u32() -> Self {
+        Self::new(0, u32::MAX as u64)
+    }
+
+    /// Returns a range containing all field elements.
+    pub fn felt() -> Self {
+        Self::new(0, Felt::MODULUS - 1)
+    }
+
+    /// Returns a range containing ZERO and ONE.
+    pub fn bool() -> Self {
+        Self::new(0, 1)
+    }
+
+    /// Returns a range containing only the specified value.
+    pub fn value(value: u64) -> Self {
+        Self::new(value, value)
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the smallest value in this range.
+    pub fn min(&self) -> u64 {
+        self.min
+    }
+
+    /// Returns the largest value in this range.
+    pub fn max(&self) -> u64 {
+        self.max
+    }
+
+    /// Returns true if the specified value is in this range.
+    pub fn contains(&self, value: u64) -> bool {
+        self.min <= value && value <= self.max
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns this range as a proptest strategy.
+    pub(crate) fn strategy(&self) -> RangeInclusive<u64> {
+        self.min..=self.max
+    }
+}
+
+impl fmt::Display for InputRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.min == self.max {
+            write!(f, "{}", self.min)
+        } else {
+            write!(f, "{}..={}", self.min, self.max)
+        }
+    }
+}
+
+impl FromStr for InputRange {
+    type Err = String;
+
+    /// Parses a range from one of the following forms: `u32`, `felt`, `bool`, a single value `N`,
+    /// an inclusive range `A..=B`, or an exclusive range `A..B`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parse_value = |value: &str| {
+            value
+                .trim()
+                .parse::<u64>()
+                .map_err(|_| format!("invalid input value '{}'", value.trim()))
+        };
+
+        let (min, max) = match s.trim() {
+            "u32" => return Ok(Self::u32()),
+            "felt" => return Ok(Self::felt()),
+            "bool" => return Ok(Self::bool()),
+            range => match range.split_once("..") {
+                Some((min, max)) => match max.strip_prefix('=') {
+                    Some(max) => (parse_value(min)?, parse_value(max)?),
+                    None => {
+                        let (min, max) = (parse_value(min)?, parse_value(max)?);
+                        if max == 0 {
+                            return Err(format!("input range '{}' is empty", range));
+                        }
+                        (min, max - 1)
+                    }
+                },
+                None => {
+                    let value = parse_value(range)?;
+                    (value, value)
+                }
+            },
+        };
+
+        if min > max {
+            return Err(format!("input range '{}' is empty", s.trim()));
+        }
+        if max >= Felt::MODULUS {
+            return Err(format!(
+                "input range '{}' contains values which are not valid field elements",
+                s.trim()
+            ));
+        }
+        Ok(Self { min, max })
+    }
+}
@@ -0,0 +1,324 @@
+use assembly::{Assembler, AssemblyError};
+use core::fmt;
+use processor::{ExecutionError, ExecutionOptions, Program};
+use proptest::test_runner::{
+    Config, RngAlgorithm, TestCaseError, TestError as RunnerError, TestRng, TestRunner,
+};
+use vm_core::{StarkField, MIN_STACK_DEPTH};
+
+pub use processor::ProgramInputs;
+
+mod differential;
+pub use differential::{
+    check_source, generate_source, source_strategy, DifferentialTest, Divergence, DivergentSource,
+    Stage, DEFAULT_NUM_SOURCES,
+};
+
+mod golden;
+pub use golden::{is_blessing_enabled, EntryMismatch, GoldenError, GoldenTrace, BLESS_ENV_VAR};
+
+mod harness;
+pub use harness::{convert_to_stack, Test, TestError};
+
+mod inputs;
+pub use inputs::InputRange;
+
+#[cfg(test)]
+mod tests;
+
+// CONSTANTS
+// ================================================================================================
+
+/// Number of randomly generated cases a procedure is executed against by default.
+pub const DEFAULT_NUM_CASES: u32 = 256;
+
+// TYPE ALIASES
+// ================================================================================================
+
+/// Result of executing a procedure against a single set of inputs: either the top 16 elements of
+/// the stack after the execution (with the top of the stack first), or the error which caused the
+/// execution to fail.
+pub type Outcome = Result<Vec<u64>, ExecutionError>;
+
+// PROCEDURE TEST
+// ================================================================================================
+
+/// A property-based test of a single Miden assembly procedure.
+///
+/// The procedure is executed against stack inputs drawn randomly from the declared input ranges,
+/// and a user-provided predicate is checked for every execution. When the predicate does not hold
+/// for some inputs, the inputs are shrunk towards the lower bounds of their ranges, and the
+/// smallest inputs found for which the predicate still fails are reported as a [Counterexample].
+///
+/// Inputs are specified in the same order as stack inputs of [ProgramInputs], i.e., the last
+/// declared input is at the top of the stack when the procedure is invoked. For example, a
+/// procedure which expects a u64 value `a` split into 32-bit limbs as `[a_hi, a_lo, ...]` would
+/// declare two u32 inputs, the first one for `a_lo` and the second one for `a_hi`.
+pub struct ProcTest {
+    program: Program,
+    inputs: Vec<InputRange>,
+    num_cases: u32,
+    seed: Option<u64>,
+    options: ExecutionOptions,
+}
+
+impl ProcTest {
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a test of the procedure with the specified name declared in the provided module
+    /// source code. Both exported and local procedures of the module can be tested.
+    ///
+    /// # Errors
+    /// Returns an error if the module cannot be compiled, or if it does not declare the specified
+    /// procedure.
+    pub fn new(source: &str, proc_name: &str) -> Result<Self, AssemblyError> {
+        // exported procedures are declared as local procedures so that the module can be compiled
+        // as a program invoking the procedure under test
+        let module = source
+            .lines()
+            .map(|line| match line.trim_start().strip_prefix("export.") {
+                Some(declaration) => format!("proc.{}", declaration),
+                None => line.to_string(),
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        Self::compile(&format!("{}\nbegin\n    exec.{}\nend", module, proc_name))
+    }
+
+    /// Returns a test of a procedure from the standard library specified by its fully-qualified
+    /// path (e.g., `std::math::u64::wrapping_add`).
+    ///
+    /// # Errors
+    /// Returns an error if the path is malformed or if the standard library does not contain the
+    /// specified procedure.
+    pub fn from_stdlib(proc_path: &str) -> Result<Self, AssemblyError> {
+        match proc_path.rsplit_once("::") {
+            Some((module_path, proc_name)) => {
+                let module_name = module_path.rsplit("::").next().expect("empty module path");
+                Self::compile(&format!(
+                    "use.{}\nbegin\n    exec.{}::{}\nend",
+                    module_path, module_name, proc_name
+                ))
+            }
+            // a path without a module is reported as an invocation of an undefined procedure
+            None => Self::compile(&format!("begin\n    exec.{}\nend", proc_path)),
+        }
+    }
+
+    /// Sets the range from which the next stack input is drawn.
+    ///
+    /// # Panics
+    /// Panics if more than 16 inputs are declared.
+    pub fn with_input(mut self, input: InputRange) -> Self {
+        assert!(
+            self.inputs.len() < MIN_STACK_DEPTH,
+            "a procedure cannot be tested with more than {} inputs",
+            MIN_STACK_DEPTH
+        );
+        self.inputs.push(input);
+        self
+    }
+
+    /// Sets the ranges from which the next stack inputs are drawn.
+    ///
+    /// # Panics
+    /// Panics if more than 16 inputs are declared.
+    pub fn with_inputs<I: IntoIterator<Item = InputRange>>(self, inputs: I) -> Self {
+        inputs.into_iter().fold(self, Self::with_input)
+    }
+
+    /// Sets the number of randomly generated cases the procedure is executed against.
+    pub fn with_cases(mut self, num_cases: u32) -> Self {
+        self.num_cases = num_cases;
+        self
+    }
+
+    /// Sets the seed used to generate the cases; tests with the same seed are executed against
+    /// the same inputs. When not set, the inputs are different every time a test is run.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Sets the options (e.g., the maximum number of cycles) with which the procedure is executed.
+    pub fn with_options(mut self, options: ExecutionOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the program which invokes the procedure under test.
+    pub fn program(&self) -> &Program {
+        &self.program
+    }
+
+    /// Returns the ranges from which the stack inputs are drawn.
+    pub fn inputs(&self) -> &[InputRange] {
+        &self.inputs
+    }
+
+    // TEST RUNNERS
+    // --------------------------------------------------------------------------------------------
+
+    /// Executes the procedure against the specified stack inputs and returns the outcome of the
+    /// execution. The inputs do not need to be within the declared ranges.
+    ///
+    /// # Panics
+    /// Panics if the inputs contain more than 16 values or values which are not valid field
+    /// elements.
+    pub fn run(&self, inputs: &[u64]) -> Outcome {
+        let inputs = ProgramInputs::new(inputs, &[], vec![]).expect("invalid stack inputs");
+        processor::execute_with_options(&self.program, &inputs, self.options.clone()).map(|trace| {
+            trace
+                .last_stack_state()
+                .iter()
+                .map(|value| value.as_int())
+                .collect()
+        })
+    }
+
+    /// Checks that the procedure executes successfully for all generated inputs, and that the
+    /// provided predicate holds for the inputs and the resulting stack outputs (top of the stack
+    /// first).
+    ///
+    /// # Errors
+    /// Returns the smallest counterexample found if an execution fails or the predicate does not
+    /// hold.
+    pub fn check<P>(&self, predicate: P) -> Result<(), Counterexample>
+    where
+        P: Fn(&[u64], &[u64]) -> bool,
+    {
+        self.check_outcome(|inputs, outcome| match outcome {
+            Ok(outputs) => predicate(inputs, outputs),
+            Err(_) => false,
+        })
+    }
+
+    /// Checks that the provided predicate holds for all generated inputs and the outcomes of
+    /// executing the procedure against them. Unlike [ProcTest::check()], this can be used to test
+    /// that the procedure fails for some inputs.
+    ///
+    /// # Errors
+    /// Returns the smallest counterexample found if the predicate does not hold.
+    pub fn check_outcome<P>(&self, predicate: P) -> Result<(), Counterexample>
+    where
+        P: Fn(&[u64], &Outcome) -> bool,
+    {
+        let strategy = self
+            .inputs
+            .iter()
+            .map(InputRange::strategy)
+            .collect::<Vec<_>>();
+
+        let result = self.build_runner().run(&strategy, |inputs| {
+            if predicate(&inputs, &self.run(&inputs)) {
+                Ok(())
+            } else {
+                Err(TestCaseError::fail("predicate does not hold"))
+            }
+        });
+
+        match result {
+            Ok(()) => Ok(()),
+            Err(RunnerError::Fail(_, inputs)) => {
+                let outcome = self.run(&inputs);
+                Err(Counterexample { inputs, outcome })
+            }
+            Err(RunnerError::Abort(reason)) => panic!("property test aborted: {}", reason),
+        }
+    }
+
+    /// Checks that the procedure is equivalent to the reference procedure for all generated
+    /// inputs: either both procedures produce the same stack outputs, or both executions fail.
+    ///
+    /// Inputs are generated from the ranges declared for this test; the ranges declared for the
+    /// reference test are ignored.
+    ///
+    /// # Errors
+    /// Returns the smallest counterexample found (with the outcome of executing this procedure)
+    /// if the procedures are not equivalent.
+    pub fn check_equiv(&self, reference: &ProcTest) -> Result<(), Counterexample> {
+        self.check_outcome(|inputs, outcome| match (outcome, reference.run(inputs)) {
+            (Ok(outputs), Ok(expected)) => *outputs == expected,
+            (Err(_), Err(_)) => true,
+            _ => false,
+        })
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    fn compile(source: &str) -> Result<Self, AssemblyError> {
+        let program = Assembler::default().compile(source)?;
+        Ok(Self {
+            program,
+            inputs: Vec::new(),
+            num_cases: DEFAULT_NUM_CASES,
+            seed: None,
+            options: ExecutionOptions::default(),
+        })
+    }
+
+    fn build_runner(&self) -> TestRunner {
+        build_runner(self.num_cases, self.seed)
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns a runner which generates the specified number of cases from the specified seed, or
+/// from a random seed if none is specified.
+fn build_runner(num_cases: u32, seed: Option<u64>) -> TestRunner {
+    let config = Config {
+        cases: num_cases,
+        failure_persistence: None,
+        ..Config::default()
+    };
+    match seed {
+        Some(seed) => {
+            let mut seed_bytes = [0; 32];
+            seed_bytes[..8].copy_from_slice(&seed.to_le_bytes());
+            let rng = TestRng::from_seed(RngAlgorithm::ChaCha, &seed_bytes);
+            TestRunner::new_with_rng(config, rng)
+        }
+        None => TestRunner::new(config),
+    }
+}
+
+// COUNTEREXAMPLE
+// ================================================================================================
+
+/// Inputs for which a property of a procedure does not hold, together with the outcome of
+/// executing the procedure against them.
+#[derive(Debug)]
+pub struct Counterexample {
+    inputs: Vec<u64>,
+    outcome: Outcome,
+}
+
+impl Counterexample {
+    /// Returns the stack inputs of the failing case, in the order in which the inputs were
+    /// declared.
+    pub fn inputs(&self) -> &[u64] {
+        &self.inputs
+    }
+
+    /// Returns the outcome of executing the procedure against the failing inputs.
+    pub fn outcome(&self) -> &Outcome {
+        &self.outcome
+    }
+}
+
+impl fmt::Display for Counterexample {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "property does not hold for inputs {:?}: ", self.inputs)?;
+        match &self.outcome {
+            Ok(outputs) => write!(f, "procedure returned {:?}", outputs),
+            Err(err) => write!(f, "procedure failed with {:?}", err),
+        }
+    }
+}
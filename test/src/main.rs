@@ -0,0 +1,99 @@
+use miden_test::{InputRange, ProcTest};
+use processor::ExecutionOptions;
+use std::{fs, path::PathBuf, process};
+use structopt::StructOpt;
+
+#[derive(StructOpt, Debug)]
+#[structopt(
+    name = "miden-test",
+    about = "Run property-based tests of Miden assembly procedures"
+)]
+pub struct TestCmd {
+    /// Path to .masm module declaring the procedure; if omitted, the procedure is taken from the
+    /// standard library
+    #[structopt(short = "a", long = "assembly", parse(from_os_str))]
+    assembly_file: Option<PathBuf>,
+    /// Name of the procedure to test, or its full path (e.g. std::math::u64::wrapping_add) for
+    /// procedures from the standard library
+    #[structopt(short = "p", long = "proc")]
+    proc_name: String,
+    /// Range of a stack input (u32, felt, bool, N, A..B, or A..=B); the last input is at the top
+    /// of the stack
+    #[structopt(short = "i", long = "input")]
+    inputs: Vec<InputRange>,
+    /// Number of randomly generated cases
+    #[structopt(short = "n", long = "cases", default_value = "256")]
+    num_cases: u32,
+    /// Seed used to generate the cases
+    #[structopt(short = "s", long = "seed")]
+    seed: Option<u64>,
+    /// Maximum number of cycles a single execution can run for
+    #[structopt(long = "max-cycles")]
+    max_cycles: Option<usize>,
+    /// Check that the procedure is equivalent to another procedure (declared in the same module,
+    /// or in the standard library)
+    #[structopt(long = "equiv")]
+    reference: Option<String>,
+    /// Check that the procedure fails for all generated inputs
+    #[structopt(long = "fails")]
+    expect_failure: bool,
+}
+
+impl TestCmd {
+    pub fn execute(&self) -> Result<(), String> {
+        let test = self.build_test(&self.proc_name)?;
+
+        let result = if let Some(reference) = &self.reference {
+            test.check_equiv(&self.build_test(reference)?)
+        } else if self.expect_failure {
+            test.check_outcome(|_, outcome| outcome.is_err())
+        } else {
+            test.check(|_, _| true)
+        };
+
+        match result {
+            Ok(()) => {
+                println!(
+                    "Procedure {} passed {} cases",
+                    self.proc_name, self.num_cases
+                );
+                Ok(())
+            }
+            Err(counterexample) => Err(counterexample.to_string()),
+        }
+    }
+
+    fn build_test(&self, proc_name: &str) -> Result<ProcTest, String> {
+        let test = match &self.assembly_file {
+            Some(path) => {
+                let source = fs::read_to_string(path).map_err(|err| {
+                    format!("Failed to open module `{}` - {}", path.display(), err)
+                })?;
+                ProcTest::new(&source, proc_name)
+            }
+            None => ProcTest::from_stdlib(proc_name),
+        }
+        .map_err(|err| format!("Failed to compile procedure {} - {}", proc_name, err))?;
+
+        let mut options = ExecutionOptions::default();
+        if let Some(max_cycles) = self.max_cycles {
+            options = options.with_max_cycles(max_cycles);
+        }
+
+        let mut test = test
+            .with_inputs(self.inputs.iter().copied())
+            .with_cases(self.num_cases)
+            .with_options(options);
+        if let Some(seed) = self.seed {
+            test = test.with_seed(seed);
+        }
+        Ok(test)
+    }
+}
+
+fn main() {
+    if let Err(message) = TestCmd::from_args().execute() {
+        eprintln!("{}", message);
+        process::exit(1);
+    }
+}
@@ -0,0 +1,237 @@
+use super::{
+    check_source, generate_source, DifferentialTest, GoldenError, GoldenTrace, InputRange,
+    ProcTest, TestError,
+};
+use crate::{build_op_test, build_test};
+use assembly::Assembler;
+use processor::{ExecutionTrace, ProgramInputs};
+use std::{env, fs};
+
+// MODULE TESTS
+// ================================================================================================
+
+const MODULE: &str = "
+    export.add_u32
+        u32wrapping_add
+    end
+
+    proc.add_buggy
+        dup.1
+        push.1000
+        u32checked_gt
+        if.true
+            add
+        else
+            add
+            add.1
+        end
+    end";
+
+#[test]
+fn check_passing_property() {
+    let test = ProcTest::new(MODULE, "add_u32")
+        .unwrap()
+        .with_inputs([InputRange::u32(), InputRange::u32()])
+        .with_cases(32)
+        .with_seed(1);
+
+    test.check(|inputs, outputs| {
+        outputs[0] == (inputs[0] as u32).wrapping_add(inputs[1] as u32) as u64
+    })
+    .unwrap();
+    assert_eq!(5, test.run(&[2, 3]).unwrap()[0]);
+}
+
+#[test]
+fn check_shrinks_counterexample() {
+    // add_buggy is off by one whenever the second element from the top of the stack is not greater
+    // than 1000; the smallest counterexample is thus [0, 0]
+    let test = ProcTest::new(MODULE, "add_buggy")
+        .unwrap()
+        .with_inputs([InputRange::new(0, 5000), InputRange::new(0, 5000)])
+        .with_cases(64)
+        .with_seed(7);
+
+    let err = test
+        .check(|inputs, outputs| outputs[0] == inputs[0] + inputs[1])
+        .unwrap_err();
+    assert_eq!(&[0, 0], err.inputs());
+    assert_eq!(1, err.outcome().as_ref().unwrap()[0]);
+}
+
+#[test]
+fn check_execution_failures() {
+    let test = ProcTest::from_stdlib("std::math::u64::checked_add")
+        .unwrap()
+        .with_inputs([InputRange::felt(); 4])
+        .with_cases(32)
+        .with_seed(3);
+
+    // execution errors are counterexamples for check(), but can be expected by check_outcome()
+    let err = test.check(|_, _| true).unwrap_err();
+    assert!(err.outcome().is_err());
+    test.check_outcome(|inputs, outcome| inputs.iter().all(|&v| v < 1 << 32) || outcome.is_err())
+        .unwrap();
+}
+
+#[test]
+fn check_equivalence() {
+    let test = ProcTest::new(MODULE, "add_u32")
+        .unwrap()
+        .with_inputs([InputRange::new(1001, 2000), InputRange::new(0, 100)])
+        .with_cases(32);
+
+    let reference = ProcTest::new(MODULE, "add_buggy").unwrap();
+    test.check_equiv(&reference).unwrap();
+
+    let test = test.with_inputs([InputRange::u32()]);
+    let reference = ProcTest::new("proc.foo add add end", "foo").unwrap();
+    assert!(test.check_equiv(&reference).is_err());
+}
+
+#[test]
+fn unknown_procedure() {
+    assert!(ProcTest::new(MODULE, "foo").is_err());
+    assert!(ProcTest::from_stdlib("std::math::u64::foo").is_err());
+    assert!(ProcTest::from_stdlib("wrapping_add").is_err());
+}
+
+// INPUT RANGE TESTS
+// ================================================================================================
+
+#[test]
+fn parse_input_range() {
+    assert_eq!(Ok(InputRange::u32()), "u32".parse());
+    assert_eq!(Ok(InputRange::felt()), "felt".parse());
+    assert_eq!(Ok(InputRange::bool()), "bool".parse());
+    assert_eq!(Ok(InputRange::value(7)), "7".parse());
+    assert_eq!(Ok(InputRange::new(1, 10)), "1..=10".parse());
+    assert_eq!(Ok(InputRange::new(1, 9)), "1..10".parse());
+
+    assert!("5..5".parse::<InputRange>().is_err());
+    assert!("5..=4".parse::<InputRange>().is_err());
+    assert!("0..=18446744069414584321".parse::<InputRange>().is_err());
+    assert!("u64".parse::<InputRange>().is_err());
+    assert!("1..x".parse::<InputRange>().is_err());
+}
+
+// GOLDEN TRACE TESTS
+// ================================================================================================
+
+#[test]
+fn golden_trace() {
+    let path = env::temp_dir().join(format!("miden-test-{}", std::process::id()));
+    let golden = GoldenTrace::new(path.join("add.golden"));
+    let add = execute("begin push.1 push.2 add end");
+    let mul = execute("begin push.1 push.2 mul end");
+
+    // goldens must be blessed before they can be checked
+    assert!(matches!(
+        golden.check(&add),
+        Err(GoldenError::MissingGolden(_))
+    ));
+    golden.bless(&add).unwrap();
+    golden.check(&add).unwrap();
+
+    // mismatching column groups are reported
+    match golden.check(&mul) {
+        Err(GoldenError::TraceMismatch(_, mismatches)) => {
+            let names = mismatches.iter().map(|m| m.name()).collect::<Vec<_>>();
+            assert_eq!(vec!["decoder", "stack", "chiplets"], names);
+        }
+        result => panic!("unexpected result {:?}", result),
+    }
+
+    // checks can be restricted to the columns of interest
+    let golden = golden.with_columns(vec!["system".parse().unwrap()]);
+    match golden.check(&mul) {
+        Err(GoldenError::TraceMismatch(_, mismatches)) => {
+            let names = mismatches.iter().map(|m| m.name()).collect::<Vec<_>>();
+            assert_eq!(vec!["decoder", "stack", "range", "chiplets"], names);
+            assert!(mismatches.iter().all(|m| m.actual().is_none()));
+        }
+        result => panic!("unexpected result {:?}", result),
+    }
+    golden.bless(&add).unwrap();
+    golden.check(&mul).unwrap();
+
+    fs::write(golden.path(), "trace_len\n").unwrap();
+    assert!(matches!(
+        golden.check(&add),
+        Err(GoldenError::MalformedGolden(_, 1))
+    ));
+    fs::remove_dir_all(path).unwrap();
+}
+
+// TEST HARNESS TESTS
+// ================================================================================================
+
+#[test]
+fn test_harness() {
+    build_op_test!("add", &[2, 3]).expect_stack(&[5]);
+
+    // programs are compiled with the standard library
+    let source = "
+        use.std::math::u64
+        begin
+            exec.u64::wrapping_add
+        end";
+    build_test!(source, &[1, 2, 3, 4]).expect_stack(&[6, 4]);
+
+    // advice inputs can be provided, and memory state can be checked
+    build_op_test!("push.adv.2 pop.mem.7", &[], &[8, 9], vec![]).expect_stack_and_memory(
+        &[8],
+        7,
+        &[9, 0, 0, 0],
+    );
+
+    build_op_test!("foo").expect_error(TestError::AssemblyError("foo"));
+    build_op_test!("u32checked_add", &[1 << 32, 1])
+        .expect_error(TestError::ExecutionError("NotU32Value"));
+}
+
+// DIFFERENTIAL TESTS
+// ================================================================================================
+
+#[test]
+fn differential_check() {
+    // valid programs and modules
+    let source = "
+        use.std::math::u64
+        proc.foo.1
+            pop.local.0
+        end
+        begin
+            push.1 exec.foo exec.u64::wrapping_add
+        end";
+    check_source(source).unwrap();
+    check_source("export.foo add end").unwrap();
+
+    // malformed sources are rejected by the assembler without panicking
+    check_source("begin if.true add end").unwrap();
+    check_source("").unwrap();
+
+    // spans ending with an advice injector used to panic in both release and debug mode
+    check_source("begin push.1 if.true add adv.u64div end end").unwrap();
+    check_source("begin push.1 if.true adv.u64div end end").unwrap();
+}
+
+#[test]
+fn differential_test() {
+    DifferentialTest::new()
+        .with_sources(32)
+        .with_seed(0)
+        .check()
+        .unwrap();
+
+    // generated sources are determined by the seed
+    assert_eq!(generate_source(7), generate_source(7));
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+fn execute(source: &str) -> ExecutionTrace {
+    let program = Assembler::default().compile(source).unwrap();
+    processor::execute(&program, &ProgramInputs::none()).unwrap()
+}
@@ -0,0 +1,28 @@
+#![no_std]
+
+//! A `no_std` crate which verifies proofs of Miden VM programs without relying on the Rust
+//! standard library. The crate is not meant to be used directly; it ensures that the verifier is
+//! usable from `no_std` code, and its tests verify proofs with all memory served by a fixed-size
+//! [ArenaAllocator].
+
+pub use verifier::{ArenaAllocator, Digest, VerificationError};
+
+// CONSTANTS
+// ================================================================================================
+
+/// The maximum size of a serialized proof accepted by [verify()].
+pub const MAX_PROOF_SIZE: usize = 256 * 1024;
+
+// VERIFIER
+// ================================================================================================
+
+/// Verifies the serialized proof of a program execution; proofs larger than [MAX_PROOF_SIZE]
+/// bytes are rejected.
+pub fn verify(
+    program_hash: Digest,
+    stack_inputs: &[u64],
+    stack_outputs: &[u64],
+    proof_bytes: &[u8],
+) -> Result<(), VerificationError> {
+    verifier::verify_bytes::<MAX_PROOF_SIZE>(program_hash, stack_inputs, stack_outputs, proof_bytes)
+}
@@ -0,0 +1,90 @@
+use miden_verifier_no_std_test::{verify, ArenaAllocator, VerificationError, MAX_PROOF_SIZE};
+use prover::{ProgramInputs, ProofOptions};
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    sync::atomic::{AtomicBool, Ordering},
+};
+use verifier::assembly::Assembler;
+
+// ALLOCATOR
+// ================================================================================================
+
+/// The size of the arena from which all memory used by the verifier is allocated.
+const ARENA_SIZE: usize = 4 * 1024 * 1024;
+
+/// An allocator which serves allocations from the arena while the arena is active, and from the
+/// system allocator otherwise. This way, proofs are generated using the system allocator, while
+/// the verifier can use only the memory of the arena.
+struct TestAllocator {
+    arena: ArenaAllocator<ARENA_SIZE>,
+    arena_active: AtomicBool,
+}
+
+impl TestAllocator {
+    fn owns(&self, ptr: *mut u8) -> bool {
+        let start = &self.arena as *const _ as usize;
+        let addr = ptr as usize;
+        addr >= start && addr < start + core::mem::size_of::<ArenaAllocator<ARENA_SIZE>>()
+    }
+}
+
+unsafe impl GlobalAlloc for TestAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        if self.arena_active.load(Ordering::SeqCst) {
+            self.arena.alloc(layout)
+        } else {
+            System.alloc(layout)
+        }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        if self.owns(ptr) {
+            self.arena.dealloc(ptr, layout)
+        } else {
+            System.dealloc(ptr, layout)
+        }
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: TestAllocator = TestAllocator {
+    arena: ArenaAllocator::new(),
+    arena_active: AtomicBool::new(false),
+};
+
+// TESTS
+// ================================================================================================
+
+#[test]
+fn verify_with_arena() {
+    let program = Assembler::default()
+        .compile("begin push.3 push.5 add mul end")
+        .unwrap();
+    let inputs = ProgramInputs::new(&[2], &[], vec![]).unwrap();
+    let (outputs, proof) = prover::prove(&program, &inputs, 1, &ProofOptions::default()).unwrap();
+    assert_eq!(vec![16], outputs);
+    let proof_bytes = proof.to_bytes();
+    assert!(proof_bytes.len() <= MAX_PROOF_SIZE);
+
+    // a valid proof and a proof of a different output are verified using only the arena; the
+    // arena is reset after each verification
+    for (output, expected) in [(16, true), (15, false)] {
+        ALLOCATOR.arena_active.store(true, Ordering::SeqCst);
+        let is_valid = verify(program.hash(), &[2], &[output], &proof_bytes).is_ok();
+        ALLOCATOR.arena_active.store(false, Ordering::SeqCst);
+        assert!(ALLOCATOR.arena.used() > 0);
+        unsafe { ALLOCATOR.arena.reset() };
+        assert_eq!(expected, is_valid);
+    }
+    assert!(ALLOCATOR.arena.peak() <= ARENA_SIZE);
+
+    // proofs larger than the bound are rejected
+    let large_proof = vec![0; MAX_PROOF_SIZE + 1];
+    assert_eq!(
+        Err(VerificationError::ProofTooLarge(
+            MAX_PROOF_SIZE,
+            MAX_PROOF_SIZE + 1
+        )),
+        verify(program.hash(), &[2], &[16], &large_proof)
+    );
+}
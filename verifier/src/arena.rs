@@ -0,0 +1,206 @@
+use core::{
+    alloc::{GlobalAlloc, Layout},
+    cell::UnsafeCell,
+    ptr,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+// ARENA ALLOCATOR
+// ================================================================================================
+
+/// A memory allocator which serves all allocations from a fixed-size buffer of `SIZE` bytes.
+///
+/// The allocator is intended for environments without a heap (e.g., embedded devices, or the
+/// guest environment of another VM), where it can be registered as the global allocator:
+/// ```ignore
+/// #[global_allocator]
+/// static ALLOCATOR: ArenaAllocator<{ 8 * 1024 * 1024 }> = ArenaAllocator::new();
+/// ```
+/// Since the buffer is a part of the allocator, a static allocator is placed into the static
+/// memory of a program, and no memory is requested from the environment at runtime.
+///
+/// Memory is allocated by bumping an offset into the buffer. Freed memory is reused only when it
+/// was the most recent allocation; otherwise, it is reclaimed all at once by [reset()]
+/// (ArenaAllocator::reset), which is expected to be called after each verification. When the
+/// buffer is exhausted, allocations fail (i.e., a null pointer is returned).
+pub struct ArenaAllocator<const SIZE: usize> {
+    memory: UnsafeCell<[u8; SIZE]>,
+    offset: AtomicUsize,
+    peak: AtomicUsize,
+}
+
+// memory regions handed out by the allocator never overlap, as the offset is updated atomically
+unsafe impl<const SIZE: usize> Sync for ArenaAllocator<SIZE> {}
+
+impl<const SIZE: usize> ArenaAllocator<SIZE> {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Returns a new allocator with an empty buffer of `SIZE` bytes.
+    pub const fn new() -> Self {
+        Self {
+            memory: UnsafeCell::new([0; SIZE]),
+            offset: AtomicUsize::new(0),
+            peak: AtomicUsize::new(0),
+        }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the size of the buffer of this allocator in bytes.
+    pub const fn capacity(&self) -> usize {
+        SIZE
+    }
+
+    /// Returns the number of bytes which are currently allocated (including alignment padding
+    /// and memory which was freed but not yet reclaimed).
+    pub fn used(&self) -> usize {
+        self.offset.load(Ordering::SeqCst)
+    }
+
+    /// Returns the largest number of bytes which were allocated at the same time since this
+    /// allocator was created.
+    pub fn peak(&self) -> usize {
+        self.peak.load(Ordering::SeqCst)
+    }
+
+    // STATE MUTATORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Reclaims all memory of the buffer.
+    ///
+    /// # Safety
+    /// All memory allocated by this allocator must have been freed, i.e., no value allocated by
+    /// this allocator can be used after this call.
+    pub unsafe fn reset(&self) {
+        self.offset.store(0, Ordering::SeqCst);
+    }
+
+    // HELPER FUNCTIONS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the address of the start of the buffer.
+    fn base(&self) -> usize {
+        self.memory.get() as usize
+    }
+}
+
+impl<const SIZE: usize> Default for ArenaAllocator<SIZE> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+unsafe impl<const SIZE: usize> GlobalAlloc for ArenaAllocator<SIZE> {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let base = self.base();
+        let mut offset = self.offset.load(Ordering::SeqCst);
+        loop {
+            // align the absolute address, as the buffer itself is only byte-aligned
+            let start = match (base + offset).checked_add(layout.align() - 1) {
+                Some(addr) => (addr & !(layout.align() - 1)) - base,
+                None => return ptr::null_mut(),
+            };
+            let end = match start.checked_add(layout.size()) {
+                Some(end) if end <= SIZE => end,
+                _ => return ptr::null_mut(),
+            };
+
+            match self
+                .offset
+                .compare_exchange(offset, end, Ordering::SeqCst, Ordering::SeqCst)
+            {
+                Ok(_) => {
+                    self.peak.fetch_max(end, Ordering::SeqCst);
+                    return (base + start) as *mut u8;
+                }
+                Err(current) => offset = current,
+            }
+        }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        // memory of the most recent allocation is reclaimed right away; if another allocation
+        // happened in the meantime, the exchange fails and the memory is reclaimed on reset
+        let start = ptr as usize - self.base();
+        let end = start + layout.size();
+        let _ = self
+            .offset
+            .compare_exchange(end, start, Ordering::SeqCst, Ordering::SeqCst);
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        // the most recent allocation is resized in place; this makes growing vectors cheap
+        let start = ptr as usize - self.base();
+        let end = start + layout.size();
+        if let Some(new_end) = start
+            .checked_add(new_size)
+            .filter(|&new_end| new_end <= SIZE)
+        {
+            if self
+                .offset
+                .compare_exchange(end, new_end, Ordering::SeqCst, Ordering::SeqCst)
+                .is_ok()
+            {
+                self.peak.fetch_max(new_end, Ordering::SeqCst);
+                return ptr;
+            }
+        }
+
+        let new_layout = Layout::from_size_align_unchecked(new_size, layout.align());
+        let new_ptr = self.alloc(new_layout);
+        if !new_ptr.is_null() {
+            ptr::copy_nonoverlapping(ptr, new_ptr, layout.size().min(new_size));
+            self.dealloc(ptr, layout);
+        }
+        new_ptr
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::{ArenaAllocator, GlobalAlloc, Layout};
+
+    #[test]
+    fn allocate_and_reclaim() {
+        let arena = ArenaAllocator::<64>::new();
+        let layout = Layout::from_size_align(10, 8).unwrap();
+
+        unsafe {
+            let a = arena.alloc(layout);
+            let b = arena.alloc(layout);
+            assert_eq!(0, a as usize % 8);
+            assert_eq!(0, b as usize % 8);
+            assert!(b as usize >= a as usize + 10);
+
+            // the most recent allocation is reclaimed right away, others only on reset
+            let used = arena.used();
+            arena.dealloc(b, layout);
+            assert!(arena.used() < used);
+            let used = arena.used();
+            let c = arena.alloc(layout);
+            arena.dealloc(a, layout);
+            assert!(arena.used() > used);
+
+            // the most recent allocation grows in place
+            let c = arena.realloc(c, layout, 20);
+            assert!(!c.is_null());
+
+            // allocations fail once the buffer is exhausted
+            assert!(arena
+                .alloc(Layout::from_size_align(64, 1).unwrap())
+                .is_null());
+            let peak = arena.peak();
+
+            arena.reset();
+            assert_eq!(0, arena.used());
+            assert_eq!(peak, arena.peak());
+            assert!(!arena
+                .alloc(Layout::from_size_align(64, 1).unwrap())
+                .is_null());
+        }
+    }
+}
@@ -2,15 +2,22 @@
 
 use air::{ProcessorAir, PublicInputs};
 use core::fmt;
-use vm_core::{utils::collections::Vec, MIN_STACK_DEPTH};
+use vm_core::{
+    chiplets::hasher,
+    utils::{collections::Vec, hex::digest_from_hex},
+    Felt, MIN_STACK_DEPTH,
+};
 use winterfell::VerifierError;
 
+mod arena;
+pub use arena::ArenaAllocator;
+
 // EXPORTS
 // ================================================================================================
 
+pub use air::{ExecutionProof, ProofCompression, ProofFormatError, ProofHeader};
 pub use assembly;
-pub use vm_core::chiplets::hasher::Digest;
-pub use winterfell::StarkProof;
+pub use vm_core::{chiplets::hasher::Digest, errors::HexParseError};
 
 // VERIFIER
 // ================================================================================================
@@ -36,25 +43,147 @@ pub fn verify(
     program_hash: Digest,
     stack_inputs: &[u64],
     stack_outputs: &[u64],
-    proof: StarkProof,
+    proof: ExecutionProof,
 ) -> Result<(), VerificationError> {
-    if stack_inputs.len() > MIN_STACK_DEPTH {
-        return Err(VerificationError::TooManyInputValues(
-            MIN_STACK_DEPTH,
-            stack_inputs.len(),
+    // build public inputs and try to verify the proof
+    let pub_inputs = build_pub_inputs(program_hash, stack_inputs, stack_outputs)?;
+    verify_proof(pub_inputs, proof)
+}
+
+/// Returns Ok(()) if the specified program was executed correctly against the specified inputs
+/// and outputs, and the serialized proof is at most `MAX_PROOF_SIZE` bytes long.
+///
+/// This function is the same as [verify()], except that the proof is provided in its serialized
+/// form, and proofs larger than `MAX_PROOF_SIZE` are rejected before they are deserialized. Memory
+/// used to verify a proof grows with the size of the proof; thus, in environments with a fixed
+/// amount of memory (e.g., when the verifier uses an [ArenaAllocator]), the bound ensures that
+/// verification of a malicious proof cannot exhaust the memory.
+///
+/// # Errors
+/// Returns an error if the proof is larger than `MAX_PROOF_SIZE` bytes, if it cannot be
+/// deserialized, or if it does not prove a correct execution of the program.
+pub fn verify_bytes<const MAX_PROOF_SIZE: usize>(
+    program_hash: Digest,
+    stack_inputs: &[u64],
+    stack_outputs: &[u64],
+    proof_bytes: &[u8],
+) -> Result<(), VerificationError> {
+    if proof_bytes.len() > MAX_PROOF_SIZE {
+        return Err(VerificationError::ProofTooLarge(
+            MAX_PROOF_SIZE,
+            proof_bytes.len(),
         ));
     }
 
-    // convert stack inputs to field elements
-    let mut stack_input_felts = Vec::with_capacity(stack_inputs.len());
-    for &input in stack_inputs.iter().rev() {
-        stack_input_felts.push(
-            input
-                .try_into()
-                .map_err(|_| VerificationError::InputNotFieldElement(input))?,
-        );
+    let pub_inputs = build_pub_inputs(program_hash, stack_inputs, stack_outputs)?;
+    let proof = ExecutionProof::from_bytes(proof_bytes)
+        .map_err(VerificationError::ProofDeserializationError)?;
+    verify_proof(pub_inputs, proof)
+}
+
+/// Returns Ok(()) if all programs in the specified batch were executed correctly against the
+/// specified inputs and outputs.
+///
+/// Each entry of the batch consists of a program hash, stack inputs, stack outputs, and a proof,
+/// which are interpreted in the same way as the parameters of [verify()]. Proofs in the batch are
+/// independent of each other; thus, the result is the same as calling [verify()] for every entry,
+/// but stack inputs and outputs of all entries are validated before any of the proofs is
+/// verified, and when the `concurrent` feature is enabled, proofs are verified in multiple
+/// threads. The proofs are consumed by the verification, and thus they are never copied.
+///
+/// # Errors
+/// Returns an error if any of the proofs does not prove a correct execution of its program; the
+/// error lists the indexes of all such entries in the batch together with the reasons of failures.
+pub fn verify_batch(
+    proofs: Vec<(Digest, &[u64], &[u64], ExecutionProof)>,
+) -> Result<(), BatchVerificationError> {
+    let mut failures = Vec::new();
+
+    // validate the inputs and outputs of all entries upfront, so that malformed entries are
+    // reported without spending time on verifying the proofs
+    let mut entries = Vec::with_capacity(proofs.len());
+    for (i, (program_hash, stack_inputs, stack_outputs, proof)) in proofs.into_iter().enumerate() {
+        match build_pub_inputs(program_hash, stack_inputs, stack_outputs) {
+            Ok(pub_inputs) => entries.push((i, pub_inputs, proof)),
+            Err(err) => failures.push((i, err)),
+        }
+    }
+
+    #[cfg(not(feature = "concurrent"))]
+    let results = entries
+        .into_iter()
+        .map(|(i, pub_inputs, proof)| (i, verify_proof(pub_inputs, proof)))
+        .collect::<Vec<_>>();
+
+    #[cfg(feature = "concurrent")]
+    let results = {
+        use winter_utils::rayon::prelude::*;
+        entries
+            .into_par_iter()
+            .map(|(i, pub_inputs, proof)| (i, verify_proof(pub_inputs, proof)))
+            .collect::<Vec<_>>()
+    };
+
+    for (i, result) in results {
+        if let Err(err) = result {
+            failures.push((i, err));
+        }
     }
 
+    if failures.is_empty() {
+        Ok(())
+    } else {
+        failures.sort_by_key(|(i, _)| *i);
+        Err(BatchVerificationError { failures })
+    }
+}
+
+// PROGRAM HASHES
+// ================================================================================================
+
+/// Parses a program hash from its hex encoding.
+///
+/// The hash is expected in the canonical hex encoding of digests: 64 lowercase hex digits with the
+/// `0x` prefix (e.g., as printed by the `miden compile` command). Thus, a verifier needs only the
+/// hash of a program to verify proofs of its execution, and never needs the program itself.
+///
+/// # Errors
+/// Returns an error if the string is not a canonical encoding of a hash (e.g., if the `0x` prefix
+/// is missing), or if any of the elements of the hash is not a valid field element.
+pub fn parse_program_hash(hex: &str) -> Result<Digest, HexParseError> {
+    digest_from_hex(hex)
+}
+
+// INPUT COMMITMENTS
+// ================================================================================================
+
+/// Returns the commitment to the specified stack inputs, which are expected in the same order as
+/// they are passed to [verify()].
+///
+/// The commitment is the same as the one returned by
+/// [ProgramInputs::stack_commitment()](vm_core::ProgramInputs::stack_commitment) for inputs with
+/// these stack values, and thus parties which receive proofs can check the stack inputs against
+/// a commitment published by the party which requested the proof without executing the program.
+///
+/// # Errors
+/// Returns an error if there are more than 16 stack inputs, or if any of the inputs is not a
+/// valid field element.
+pub fn stack_inputs_commitment(stack_inputs: &[u64]) -> Result<Digest, VerificationError> {
+    stack_inputs_to_felts(stack_inputs).map(|elements| hasher::hash_elements(&elements))
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Builds public inputs of a proof from the specified program hash, stack inputs, and stack
+/// outputs.
+fn build_pub_inputs(
+    program_hash: Digest,
+    stack_inputs: &[u64],
+    stack_outputs: &[u64],
+) -> Result<PublicInputs, VerificationError> {
+    let stack_input_felts = stack_inputs_to_felts(stack_inputs)?;
+
     if stack_outputs.len() > MIN_STACK_DEPTH {
         return Err(VerificationError::TooManyOutputValues(
             MIN_STACK_DEPTH,
@@ -72,9 +201,38 @@ pub fn verify(
         );
     }
 
-    // build public inputs and try to verify the proof
-    let pub_inputs = PublicInputs::new(program_hash, stack_input_felts, stack_output_felts);
-    winterfell::verify::<ProcessorAir>(proof, pub_inputs).map_err(VerificationError::VerifierError)
+    Ok(PublicInputs::new(
+        program_hash,
+        stack_input_felts,
+        stack_output_felts,
+    ))
+}
+
+/// Converts the specified stack inputs into field elements ordered from the top of the stack
+/// down, i.e., in the order in which they appear in the public inputs of a proof.
+fn stack_inputs_to_felts(stack_inputs: &[u64]) -> Result<Vec<Felt>, VerificationError> {
+    if stack_inputs.len() > MIN_STACK_DEPTH {
+        return Err(VerificationError::TooManyInputValues(
+            MIN_STACK_DEPTH,
+            stack_inputs.len(),
+        ));
+    }
+
+    let mut stack_input_felts = Vec::with_capacity(stack_inputs.len());
+    for &input in stack_inputs.iter().rev() {
+        stack_input_felts.push(
+            input
+                .try_into()
+                .map_err(|_| VerificationError::InputNotFieldElement(input))?,
+        );
+    }
+    Ok(stack_input_felts)
+}
+
+/// Verifies the specified proof against the specified public inputs.
+fn verify_proof(pub_inputs: PublicInputs, proof: ExecutionProof) -> Result<(), VerificationError> {
+    winterfell::verify::<ProcessorAir>(proof.into_stark_proof(), pub_inputs)
+        .map_err(VerificationError::VerifierError)
 }
 
 // ERRORS
@@ -88,6 +246,8 @@ pub enum VerificationError {
     TooManyInputValues(usize, usize),
     OutputNotFieldElement(u64),
     TooManyOutputValues(usize, usize),
+    ProofTooLarge(usize, usize),
+    ProofDeserializationError(ProofFormatError),
 }
 
 impl fmt::Display for VerificationError {
@@ -96,3 +256,36 @@ impl fmt::Display for VerificationError {
         write!(f, "{:?}", self)
     }
 }
+
+/// An error returned by [verify_batch()] when some of the proofs in a batch fail verification.
+#[derive(Debug, PartialEq, Eq)]
+pub struct BatchVerificationError {
+    failures: Vec<(usize, VerificationError)>,
+}
+
+impl BatchVerificationError {
+    /// Returns the indexes of the batch entries which failed verification together with the
+    /// reasons of failures. The entries are sorted by index in ascending order.
+    pub fn failures(&self) -> &[(usize, VerificationError)] {
+        &self.failures
+    }
+
+    /// Returns the indexes of the batch entries which failed verification, in ascending order.
+    pub fn failed_indexes(&self) -> Vec<usize> {
+        self.failures.iter().map(|(i, _)| *i).collect()
+    }
+}
+
+impl fmt::Display for BatchVerificationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} of the proofs failed verification:",
+            self.failures.len()
+        )?;
+        for (i, err) in self.failures.iter() {
+            write!(f, " [{}] {}", i, err)?;
+        }
+        Ok(())
+    }
+}